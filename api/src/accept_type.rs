@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use aptos_api_types::mime_types::BCS;
+use aptos_api_types::mime_types::{BCS, PROTOBUF};
 use poem::{web::Accept, FromRequest, Request, RequestBody, Result};
 
 /// Accept types from input headers
@@ -13,6 +13,9 @@ pub enum AcceptType {
     Json,
     /// Take types with as little conversion as possible from the database
     Bcs,
+    /// Convert types to the indexer protobuf schema (only supported by the
+    /// transactions and blocks APIs)
+    Protobuf,
 }
 
 /// This impl allows us to get the data straight from the arguments to the
@@ -29,8 +32,10 @@ impl<'a> FromRequest<'a> for AcceptType {
 /// overriding explicit accept type, default to JSON.
 fn parse_accept(accept: &Accept) -> Result<AcceptType> {
     for mime in &accept.0 {
-        if matches!(mime.as_ref(), BCS) {
-            return Ok(AcceptType::Bcs);
+        match mime.as_ref() {
+            BCS => return Ok(AcceptType::Bcs),
+            PROTOBUF => return Ok(AcceptType::Protobuf),
+            _ => {},
         }
     }
 
@@ -4,12 +4,13 @@
 
 use crate::{
     accept_type::AcceptType,
-    context::{api_spawn_blocking, Context},
+    context::{api_spawn_blocking, Context, ResourceFilter},
     failpoint::fail_point_poem,
     page::determine_limit,
     response::{
-        account_not_found, resource_not_found, struct_field_not_found, BadRequestError,
-        BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404, InternalError,
+        account_not_found, protobuf_api_unsupported, resource_not_found, struct_field_not_found,
+        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
+        InternalError,
     },
     ApiTags,
 };
@@ -26,7 +27,9 @@ use aptos_types::{
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use move_core_types::{
-    identifier::Identifier, language_storage::StructTag, move_resource::MoveStructType,
+    identifier::Identifier,
+    language_storage::{StructTag, TypeTag},
+    move_resource::MoveStructType,
     resolver::MoveResolver,
 };
 use poem_openapi::{
@@ -108,11 +111,37 @@ impl AccountsApi {
         ///
         /// If not provided, defaults to default page size.
         limit: Query<Option<u16>>,
+        /// Only return resources defined in modules published at this address
+        ///
+        /// For example, `0x1` only returns resources defined by the framework.
+        module_address: Query<Option<Address>>,
+        /// Only return resources defined in modules with a name that starts with this prefix
+        ///
+        /// For example, `coin` returns resources defined in the `coin` module.
+        module_name_prefix: Query<Option<String>>,
+        /// Only return resources that have this type as one of their generic type arguments
+        ///
+        /// For example, `0x1::aptos_coin::AptosCoin` returns `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>`.
+        generic_type: Query<Option<MoveStructTag>>,
     ) -> BasicResultWith404<Vec<MoveResource>> {
         fail_point_poem("endpoint_get_account_resources")?;
         self.context
             .check_api_output_enabled("Get account resources", &accept_type)?;
 
+        let generic_type: Option<StructTag> = generic_type
+            .0
+            .map(|generic_type| generic_type.try_into())
+            .transpose()
+            .context("Failed to parse given generic type")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let resource_filter = ResourceFilter {
+            module_address: module_address.0.map(Into::into),
+            module_name_prefix: module_name_prefix.0,
+            generic_type: generic_type.map(|struct_tag| TypeTag::Struct(Box::new(struct_tag))),
+        };
+
         let context = self.context.clone();
         api_spawn_blocking(move || {
             let account = Account::new(
@@ -121,7 +150,8 @@ impl AccountsApi {
                 ledger_version.0,
                 start.0.map(StateKey::from),
                 limit.0,
-            )?;
+            )?
+            .with_resource_filter(resource_filter);
             account.resources(&accept_type)
         })
         .await
@@ -186,11 +216,13 @@ pub struct Account {
     /// Address of account
     address: Address,
     /// Lookup ledger version
-    ledger_version: u64,
+    pub ledger_version: u64,
     /// Where to start for pagination
     start: Option<StateKey>,
     /// Max number of items to retrieve
     limit: Option<u16>,
+    /// Filter for the resources to retrieve
+    resource_filter: ResourceFilter,
     /// Current ledger info
     pub latest_ledger_info: LedgerInfo,
 }
@@ -217,10 +249,17 @@ impl Account {
             ledger_version: requested_ledger_version,
             start,
             limit,
+            resource_filter: ResourceFilter::default(),
             latest_ledger_info,
         })
     }
 
+    /// Only retrieve the resources that match the given filter
+    pub fn with_resource_filter(mut self, resource_filter: ResourceFilter) -> Self {
+        self.resource_filter = resource_filter;
+        self
+    }
+
     // These functions map directly to endpoint functions.
 
     /// Retrieves the [`AccountData`] for the associated account
@@ -254,6 +293,7 @@ impl Account {
                 &self.latest_ledger_info,
                 BasicResponseStatus::Ok,
             )),
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Get account")),
         }
     }
 
@@ -333,6 +373,7 @@ impl Account {
                     max_account_resources_page_size,
                     &self.latest_ledger_info,
                 )? as u64,
+                &self.resource_filter,
             )
             .context("Failed to get resources from storage")
             .map_err(|err| {
@@ -381,6 +422,7 @@ impl Account {
                 ))
                 .map(|v| v.with_cursor(next_state_key))
             },
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Get account resources")),
         }
     }
 
@@ -456,6 +498,7 @@ impl Account {
                 ))
                 .map(|v| v.with_cursor(next_state_key))
             },
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Get account modules")),
         }
     }
 
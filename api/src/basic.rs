@@ -22,7 +22,12 @@ const OPEN_API_HTML: &str = include_str!("../doc/spec.html");
 
 // Generate error and response types
 generate_success_response!(HealthCheckResponse, (200, Ok));
-generate_error_response!(HealthCheckError, (503, ServiceUnavailable), (500, Internal));
+generate_error_response!(
+    HealthCheckError,
+    (400, BadRequest),
+    (503, ServiceUnavailable),
+    (500, Internal)
+);
 pub type HealthCheckResult<T> = poem::Result<HealthCheckResponse<T>, HealthCheckError>;
 
 /// Basic API does healthchecking and shows the OpenAPI spec
@@ -5,10 +5,16 @@ use crate::{
     accept_type::AcceptType,
     context::{api_spawn_blocking, Context},
     failpoint::fail_point_poem,
-    response::{BasicResponse, BasicResponseStatus, BasicResultWith404},
+    proto_convert::convert_timestamp_usecs,
+    response::{
+        build_not_found, protobuf_api_unsupported, BasicErrorWith404, BasicResponse,
+        BasicResponseStatus, BasicResultWith404,
+    },
     ApiTags,
 };
-use aptos_api_types::{BcsBlock, Block, LedgerInfo};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{AptosErrorCode, BcsBlock, Block, EpochLedgerStats, LedgerInfo, U64};
+use aptos_protos::transaction::v1 as transaction_pb;
 use poem_openapi::{
     param::{Path, Query},
     OpenApi,
@@ -51,7 +57,7 @@ impl BlocksApi {
     ) -> BasicResultWith404<Block> {
         fail_point_poem("endpoint_get_block_by_height")?;
         self.context
-            .check_api_output_enabled("Get block by height", &accept_type)?;
+            .check_api_output_enabled_with_protobuf("Get block by height", &accept_type)?;
         let api = self.clone();
         api_spawn_blocking(move || {
             api.get_by_height(
@@ -91,7 +97,7 @@ impl BlocksApi {
     ) -> BasicResultWith404<Block> {
         fail_point_poem("endpoint_get_block_by_version")?;
         self.context
-            .check_api_output_enabled("Get block by version", &accept_type)?;
+            .check_api_output_enabled_with_protobuf("Get block by version", &accept_type)?;
         let api = self.clone();
         api_spawn_blocking(move || {
             api.get_by_version(
@@ -102,23 +108,90 @@ impl BlocksApi {
         })
         .await
     }
+
+    /// Get epoch ledger stats
+    ///
+    /// Retrieves the aggregate statistics (e.g., the transaction counts by type,
+    /// the total gas used and the number of unique senders) of the transactions
+    /// committed in the given epoch. The statistics are computed by the node at
+    /// commit time, so they only cover the versions committed by the node.
+    #[oai(
+        path = "/experimental/epochs/:epoch/ledger_stats",
+        method = "get",
+        operation_id = "get_epoch_ledger_stats",
+        tag = "ApiTags::Experimental"
+    )]
+    async fn get_epoch_ledger_stats(
+        &self,
+        accept_type: AcceptType,
+        /// Epoch to lookup the ledger stats for
+        epoch: Path<U64>,
+    ) -> BasicResultWith404<EpochLedgerStats> {
+        fail_point_poem("endpoint_get_epoch_ledger_stats")?;
+        self.context
+            .check_api_output_enabled("Get epoch ledger stats", &accept_type)?;
+        self.context
+            .check_ledger_version_not_pinned("Get epoch ledger stats")?;
+        let api = self.clone();
+        api_spawn_blocking(move || api.epoch_ledger_stats(accept_type, epoch.0 .0)).await
+    }
 }
 
 impl BlocksApi {
+    fn epoch_ledger_stats(
+        &self,
+        accept_type: AcceptType,
+        epoch: u64,
+    ) -> BasicResultWith404<EpochLedgerStats> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let epoch_ledger_stats = self
+            .context
+            .db
+            .get_epoch_ledger_stats(epoch)
+            .context("Failed to read the epoch ledger stats from the DB")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &latest_ledger_info,
+                )
+            })?
+            .ok_or_else(|| {
+                build_not_found(
+                    "Epoch ledger stats",
+                    format!("Epoch({})", epoch),
+                    AptosErrorCode::VersionNotFound,
+                    &latest_ledger_info,
+                )
+            })?;
+
+        match accept_type {
+            AcceptType::Json => BasicResponse::try_from_json((
+                EpochLedgerStats::from(epoch_ledger_stats),
+                &latest_ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+            AcceptType::Bcs => BasicResponse::try_from_bcs((
+                epoch_ledger_stats,
+                &latest_ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Get epoch ledger stats")),
+        }
+    }
+
     fn get_by_height(
         &self,
         accept_type: AcceptType,
         block_height: u64,
         with_transactions: bool,
     ) -> BasicResultWith404<Block> {
-        let latest_ledger_info = self.context.get_latest_ledger_info()?;
-        let bcs_block = self.context.get_block_by_height(
-            block_height,
-            &latest_ledger_info,
-            with_transactions,
-        )?;
+        let ledger_info = self.context.get_pinned_ledger_info()?;
+        let bcs_block =
+            self.context
+                .get_block_by_height(block_height, &ledger_info, with_transactions)?;
 
-        self.render_bcs_block(&accept_type, latest_ledger_info, bcs_block)
+        self.render_bcs_block(&accept_type, ledger_info, bcs_block)
     }
 
     fn get_by_version(
@@ -127,15 +200,16 @@ impl BlocksApi {
         version: u64,
         with_transactions: bool,
     ) -> BasicResultWith404<Block> {
-        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let ledger_info = self.context.get_pinned_ledger_info()?;
         let bcs_block =
             self.context
-                .get_block_by_version(version, &latest_ledger_info, with_transactions)?;
+                .get_block_by_version(version, &ledger_info, with_transactions)?;
 
-        self.render_bcs_block(&accept_type, latest_ledger_info, bcs_block)
+        self.render_bcs_block(&accept_type, ledger_info, bcs_block)
     }
 
-    /// Renders a [`BcsBlock`] into a [`Block`] if it's a JSON accept type
+    /// Renders a [`BcsBlock`] into a [`Block`] if it's a JSON accept type, or
+    /// into the indexer protobuf block if it's a protobuf accept type
     fn render_bcs_block(
         &self,
         accept_type: &AcceptType,
@@ -168,6 +242,25 @@ impl BlocksApi {
                 &latest_ledger_info,
                 BasicResponseStatus::Ok,
             )),
+            AcceptType::Protobuf => {
+                let transactions = match bcs_block.transactions {
+                    Some(inner) => self
+                        .context
+                        .render_transactions_protobuf(&latest_ledger_info, inner)?,
+                    None => vec![],
+                };
+                let block = transaction_pb::Block {
+                    timestamp: Some(convert_timestamp_usecs(bcs_block.block_timestamp)),
+                    height: bcs_block.block_height,
+                    transactions,
+                    chain_id: latest_ledger_info.chain_id as u32,
+                };
+                BasicResponse::try_from_protobuf((
+                    block,
+                    &latest_ledger_info,
+                    BasicResponseStatus::Ok,
+                ))
+            },
         }
     }
 }
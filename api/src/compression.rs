@@ -0,0 +1,332 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::RESPONSE_COMPRESSION;
+use aptos_config::config::ApiCompressionConfig;
+use flate2::{write::GzEncoder, Compression};
+use poem::{
+    http::{header, HeaderMap, HeaderValue},
+    Body, Endpoint, IntoResponse, Middleware, Request, Response, Result,
+};
+use std::{io::Write, sync::Arc};
+use tokio::sync::Semaphore;
+
+// Useful metric labels for the compression results
+const ABOVE_MAX_SIZE: &str = "above_max_size";
+const BELOW_MIN_SIZE: &str = "below_min_size";
+const BUDGET_EXHAUSTED: &str = "budget_exhausted";
+const COMPRESSED: &str = "compressed";
+const COMPRESSION_FAILED: &str = "compression_failed";
+const NOT_SMALLER: &str = "not_smaller";
+
+/// The content encodings supported for API responses
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum ContentEncoding {
+    Gzip,
+    Zstd,
+}
+
+impl ContentEncoding {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Zstd => "zstd",
+        }
+    }
+}
+
+/// This middleware compresses response bodies using the content encoding
+/// preferred by the client (zstd over gzip). Responses are only compressed
+/// if their size is within the configured thresholds (which can be set per
+/// route) and the concurrent compression budget is not exhausted. Otherwise,
+/// the response is sent as-is.
+pub struct ResponseCompression {
+    config: Arc<ApiCompressionConfig>,
+    compression_budget: Arc<Semaphore>,
+}
+
+impl ResponseCompression {
+    pub fn new(config: ApiCompressionConfig) -> Self {
+        let compression_budget = Semaphore::new(config.max_concurrent_compressions as usize);
+        Self {
+            config: Arc::new(config),
+            compression_budget: Arc::new(compression_budget),
+        }
+    }
+}
+
+impl<E: Endpoint> Middleware<E> for ResponseCompression {
+    type Output = ResponseCompressionEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        ResponseCompressionEndpoint {
+            inner: ep,
+            config: self.config.clone(),
+            compression_budget: self.compression_budget.clone(),
+        }
+    }
+}
+
+/// Endpoint for ResponseCompression middleware.
+pub struct ResponseCompressionEndpoint<E> {
+    inner: E,
+    config: Arc<ApiCompressionConfig>,
+    compression_budget: Arc<Semaphore>,
+}
+
+#[async_trait::async_trait]
+impl<E: Endpoint> Endpoint for ResponseCompressionEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        if !self.config.enabled {
+            return self.inner.call(req).await.map(IntoResponse::into_response);
+        }
+
+        // Identify the content encoding preferred by the client (if any)
+        let content_encoding = match select_content_encoding(req.headers()) {
+            Some(content_encoding) => content_encoding,
+            None => return self.inner.call(req).await.map(IntoResponse::into_response),
+        };
+        let min_response_size_bytes = self.config.get_min_response_size_bytes(req.uri().path());
+
+        // Fetch the response and skip bodies that are already encoded
+        let response = self.inner.call(req).await?.into_response();
+        if response.headers().contains_key(header::CONTENT_ENCODING) {
+            return Ok(response);
+        }
+        let (mut parts, body) = response.into_parts();
+        let body = body.into_bytes().await?;
+
+        // Verify the response size is within the compression thresholds
+        let num_bytes = body.len() as u64;
+        if num_bytes < min_response_size_bytes {
+            update_compression_metrics(content_encoding, BELOW_MIN_SIZE);
+            return Ok(Response::from_parts(parts, Body::from_bytes(body)));
+        }
+        if num_bytes > self.config.max_response_size_bytes {
+            update_compression_metrics(content_encoding, ABOVE_MAX_SIZE);
+            return Ok(Response::from_parts(parts, Body::from_bytes(body)));
+        }
+
+        // Acquire a permit from the compression budget (without waiting)
+        let _permit = match self.compression_budget.clone().try_acquire_owned() {
+            Ok(permit) => permit,
+            Err(_) => {
+                update_compression_metrics(content_encoding, BUDGET_EXHAUSTED);
+                return Ok(Response::from_parts(parts, Body::from_bytes(body)));
+            },
+        };
+
+        // Compress the response body (off the async worker threads)
+        let config = self.config.clone();
+        let uncompressed_body = body.clone();
+        let compressed_body = tokio::task::spawn_blocking(move || {
+            compress_bytes(content_encoding, &config, &uncompressed_body)
+        })
+        .await;
+        match compressed_body {
+            Ok(Ok(compressed_body)) if compressed_body.len() < body.len() => {
+                update_compression_metrics(content_encoding, COMPRESSED);
+                parts.headers.insert(
+                    header::CONTENT_ENCODING,
+                    HeaderValue::from_static(content_encoding.as_str()),
+                );
+                parts.headers.remove(header::CONTENT_LENGTH);
+                parts
+                    .headers
+                    .append(header::VARY, HeaderValue::from_static("accept-encoding"));
+                Ok(Response::from_parts(parts, Body::from_vec(compressed_body)))
+            },
+            Ok(Ok(_)) => {
+                update_compression_metrics(content_encoding, NOT_SMALLER);
+                Ok(Response::from_parts(parts, Body::from_bytes(body)))
+            },
+            _ => {
+                update_compression_metrics(content_encoding, COMPRESSION_FAILED);
+                Ok(Response::from_parts(parts, Body::from_bytes(body)))
+            },
+        }
+    }
+}
+
+/// Returns the content encoding to use for the response (based on the
+/// `Accept-Encoding` request headers). Zstd is preferred over gzip.
+fn select_content_encoding(headers: &HeaderMap) -> Option<ContentEncoding> {
+    let mut accepts_gzip = false;
+    let mut accepts_zstd = false;
+    for header_value in headers.get_all(header::ACCEPT_ENCODING) {
+        let header_value = match header_value.to_str() {
+            Ok(header_value) => header_value,
+            Err(_) => continue, // Ignore invalid header values
+        };
+
+        for encoding in header_value.split(',') {
+            // Ignore the encodings explicitly rejected by the client (i.e., q=0)
+            let mut encoding_params = encoding.split(';');
+            let encoding_name = encoding_params.next().unwrap_or_default().trim();
+            let rejected = encoding_params.any(|param| {
+                param
+                    .trim()
+                    .strip_prefix("q=")
+                    .and_then(|quality| quality.trim().parse::<f32>().ok())
+                    .map(|quality| quality <= 0.0)
+                    .unwrap_or(false)
+            });
+            if rejected {
+                continue;
+            }
+
+            if encoding_name.eq_ignore_ascii_case("zstd") {
+                accepts_zstd = true;
+            } else if encoding_name.eq_ignore_ascii_case("gzip") || encoding_name == "*" {
+                accepts_gzip = true;
+            }
+        }
+    }
+
+    if accepts_zstd {
+        Some(ContentEncoding::Zstd)
+    } else if accepts_gzip {
+        Some(ContentEncoding::Gzip)
+    } else {
+        None
+    }
+}
+
+/// Compresses the given bytes using the specified content encoding
+fn compress_bytes(
+    content_encoding: ContentEncoding,
+    config: &ApiCompressionConfig,
+    bytes: &[u8],
+) -> std::io::Result<Vec<u8>> {
+    match content_encoding {
+        ContentEncoding::Gzip => {
+            let mut encoder = GzEncoder::new(vec![], Compression::new(config.gzip_level));
+            encoder.write_all(bytes)?;
+            encoder.finish()
+        },
+        ContentEncoding::Zstd => zstd::encode_all(bytes, config.zstd_level),
+    }
+}
+
+/// Updates the response compression metrics
+fn update_compression_metrics(content_encoding: ContentEncoding, result: &str) {
+    RESPONSE_COMPRESSION
+        .with_label_values(&[content_encoding.as_str(), result])
+        .inc();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use poem::{endpoint::make_sync, http::Uri, EndpointExt};
+    use std::io::Read;
+
+    #[tokio::test]
+    async fn test_compress_responses() {
+        let body = "aptos".repeat(1000);
+        for (accept_encoding, expected_encoding) in [
+            ("gzip", "gzip"),
+            ("zstd", "zstd"),
+            ("gzip, deflate, br, zstd", "zstd"),
+            ("zstd;q=0, gzip;q=0.5", "gzip"),
+            ("*", "gzip"),
+        ] {
+            // Send a request that accepts the encoding
+            let response = call_endpoint(
+                ApiCompressionConfig {
+                    enabled: true,
+                    ..Default::default()
+                },
+                body.clone(),
+                "/v1/accounts",
+                Some(accept_encoding),
+            )
+            .await;
+
+            // Verify the response is compressed with the expected encoding
+            let content_encoding = response.headers().get(header::CONTENT_ENCODING).unwrap();
+            assert_eq!(content_encoding, expected_encoding);
+            let compressed_body = response.into_body().into_vec().await.unwrap();
+            assert!(compressed_body.len() < body.len());
+
+            // Verify the response body can be decompressed
+            let decompressed_body = if expected_encoding == "gzip" {
+                let mut decompressed_body = String::new();
+                flate2::read::GzDecoder::new(compressed_body.as_slice())
+                    .read_to_string(&mut decompressed_body)
+                    .unwrap();
+                decompressed_body
+            } else {
+                String::from_utf8(zstd::decode_all(compressed_body.as_slice()).unwrap()).unwrap()
+            };
+            assert_eq!(decompressed_body, body);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_skip_compression() {
+        let body = "aptos".repeat(1000);
+        let enabled_config = ApiCompressionConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        for (config, path, accept_encoding) in [
+            // Compression is disabled
+            (ApiCompressionConfig::default(), "/v1", Some("gzip")),
+            // The client doesn't accept a supported encoding
+            (enabled_config.clone(), "/v1", None),
+            (enabled_config.clone(), "/v1", Some("br, deflate")),
+            (enabled_config.clone(), "/v1", Some("gzip;q=0")),
+            // The response is below the route threshold
+            (
+                ApiCompressionConfig {
+                    route_min_response_size_bytes: [("/v1/blocks".into(), 10_000)].into(),
+                    ..enabled_config.clone()
+                },
+                "/v1/blocks/by_height/1",
+                Some("gzip"),
+            ),
+            // The response is above the max size
+            (
+                ApiCompressionConfig {
+                    max_response_size_bytes: 100,
+                    ..enabled_config.clone()
+                },
+                "/v1",
+                Some("gzip"),
+            ),
+            // The compression budget is exhausted
+            (
+                ApiCompressionConfig {
+                    max_concurrent_compressions: 0,
+                    ..enabled_config.clone()
+                },
+                "/v1",
+                Some("gzip"),
+            ),
+        ] {
+            // Send the request and verify the response is not compressed
+            let response = call_endpoint(config, body.clone(), path, accept_encoding).await;
+            assert!(response.headers().get(header::CONTENT_ENCODING).is_none());
+            assert_eq!(response.into_body().into_string().await.unwrap(), body);
+        }
+    }
+
+    /// Calls a compressing endpoint (that returns the given body) at the
+    /// specified path and returns the response.
+    async fn call_endpoint(
+        config: ApiCompressionConfig,
+        body: String,
+        path: &'static str,
+        accept_encoding: Option<&'static str>,
+    ) -> Response {
+        let endpoint = make_sync(move |_| body.clone()).with(ResponseCompression::new(config));
+        let mut request = Request::builder().uri(Uri::from_static(path));
+        if let Some(accept_encoding) = accept_encoding {
+            request = request.header(header::ACCEPT_ENCODING, accept_encoding);
+        }
+        endpoint.call(request.finish()).await.unwrap()
+    }
+}
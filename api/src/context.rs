@@ -4,11 +4,15 @@
 
 use crate::{
     accept_type::AcceptType,
+    ledger_version_pin::{get_pinned_ledger_version, PinnedLedgerVersion},
     metrics,
+    proto_convert::{convert_transaction, convert_transaction_size_info},
     response::{
         bcs_api_disabled, block_not_found_by_height, block_not_found_by_version,
-        block_pruned_by_height, json_api_disabled, version_not_found, version_pruned,
-        ForbiddenError, InternalError, NotFoundError, ServiceUnavailableError, StdApiError,
+        block_pruned_by_height, json_api_disabled, ledger_version_pin_unsupported,
+        protobuf_api_disabled, protobuf_api_unsupported, version_not_found, version_pruned,
+        BadRequestError, ForbiddenError, InternalError, NotFoundError, ServiceUnavailableError,
+        StdApiError,
     },
 };
 use anyhow::{anyhow, bail, ensure, format_err, Context as AnyhowContext, Result};
@@ -22,7 +26,10 @@ use aptos_db_indexer::table_info_reader::TableInfoReader;
 use aptos_gas_schedule::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_logger::{error, info, Schema};
 use aptos_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
+use aptos_protos::transaction::v1 as transaction_pb;
 use aptos_storage_interface::{
+    disk_space_guardrails::DISK_SPACE_GUARDRAILS,
+    read_context::{ReadContext, ReadSubsystem},
     state_view::{DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView},
     DbReader, Order, MAX_REQUEST_LIMIT,
 };
@@ -50,7 +57,7 @@ use futures::{channel::oneshot, SinkExt};
 use mini_moka::sync::Cache;
 use move_core_types::{
     identifier::Identifier,
-    language_storage::{ModuleId, StructTag},
+    language_storage::{ModuleId, StructTag, TypeTag},
     move_resource::MoveResource,
 };
 use serde::Serialize;
@@ -153,14 +160,21 @@ impl Context {
         Ok(self.db.latest_state_checkpoint_view()?)
     }
 
+    /// Returns the latest state view, or the state view at the ledger version
+    /// the request is pinned to (if any).
     pub fn latest_state_view_poem<E: InternalError>(
         &self,
         ledger_info: &LedgerInfo,
     ) -> Result<DbStateView, E> {
-        self.db
-            .latest_state_checkpoint_view()
-            .context("Failed to read latest state checkpoint from DB")
-            .map_err(|e| E::internal_with_code(e, AptosErrorCode::InternalError, ledger_info))
+        let state_view = match get_pinned_ledger_version() {
+            Some(pinned_version) => self
+                .state_view_at_version(pinned_version)
+                .context("Failed to read the state at the pinned ledger version from DB"),
+            None => self
+                .latest_state_view()
+                .context("Failed to read latest state checkpoint from DB"),
+        };
+        state_view.map_err(|e| E::internal_with_code(e, AptosErrorCode::InternalError, ledger_info))
     }
 
     pub fn state_view<E: StdApiError>(
@@ -257,8 +271,11 @@ impl Context {
     ) -> Result<(LedgerInfo, Version), E> {
         let latest_ledger_info = self.get_latest_ledger_info()?;
 
-        let requested_ledger_version =
-            requested_ledger_version.unwrap_or_else(|| latest_ledger_info.version());
+        // An explicitly requested version takes precedence over the version
+        // the request is pinned to (if any).
+        let requested_ledger_version = requested_ledger_version
+            .or_else(get_pinned_ledger_version)
+            .unwrap_or_else(|| latest_ledger_info.version());
 
         // This is too far in the future, a retriable case
         if requested_ledger_version > latest_ledger_info.version() {
@@ -276,6 +293,31 @@ impl Context {
         Ok((latest_ledger_info, requested_ledger_version))
     }
 
+    /// Returns the ledger info at the version the request is pinned to, or the
+    /// latest ledger info if the request isn't pinned.
+    pub fn get_pinned_ledger_info<E: StdApiError>(&self) -> Result<LedgerInfo, E> {
+        let (latest_ledger_info, pinned_version) =
+            self.get_latest_ledger_info_and_verify_lookup_version(None)?;
+        if pinned_version == latest_ledger_info.version() {
+            return Ok(latest_ledger_info);
+        }
+
+        let (_, _, block_event) = self
+            .db
+            .get_block_info_by_version(pinned_version)
+            .context("Failed to retrieve the block information at the pinned ledger version")
+            .map_err(|err| {
+                E::internal_with_code(err, AptosErrorCode::InternalError, &latest_ledger_info)
+            })?;
+        Ok(LedgerInfo {
+            epoch: block_event.epoch().into(),
+            ledger_version: pinned_version.into(),
+            block_height: block_event.height().into(),
+            ledger_timestamp: block_event.proposed_time().into(),
+            ..latest_ledger_info
+        })
+    }
+
     pub fn get_latest_ledger_info_with_signatures(&self) -> Result<LedgerInfoWithSignatures> {
         Ok(self.db.get_latest_ledger_info()?)
     }
@@ -376,6 +418,7 @@ impl Context {
         prev_state_key: Option<&StateKey>,
         version: u64,
         limit: u64,
+        filter: &ResourceFilter,
     ) -> Result<(Vec<(StructTag, Vec<u8>)>, Option<StateKey>)> {
         let account_iter = self.db.get_prefixed_state_value_iterator(
             &StateKeyPrefix::from(address),
@@ -392,10 +435,14 @@ impl Context {
                 Ok((k, v)) => match k.inner() {
                     StateKeyInner::AccessPath(AccessPath { address: _, path }) => {
                         match Path::try_from(path.as_slice()) {
-                            Ok(Path::Resource(struct_tag)) => {
-                                Some(Ok((struct_tag, v.bytes().to_vec())))
-                            }
+                            // Filter resources while iterating, so non-matching resources
+                            // don't count towards the page limit
+                            Ok(Path::Resource(struct_tag)) => filter
+                                .matches(&struct_tag)
+                                .then(|| Ok((struct_tag, v.bytes().to_vec()))),
                             // TODO: Consider expanding to Path::Resource
+                            // Resource groups are always kept, as their members are
+                            // only filtered once the group has been expanded (below).
                             Ok(Path::ResourceGroup(struct_tag)) => {
                                 Some(Ok((struct_tag, v.bytes().to_vec())))
                             }
@@ -444,7 +491,7 @@ impl Context {
                     bcs::from_bytes::<ResourceGroup>(&value)
                         .map(|map| {
                             map.into_iter()
-                                .map(|(key, value)| (key, value))
+                                .filter(|(key, _)| filter.matches(key))
                                 .collect::<Vec<_>>()
                         })
                         .map_err(|e| e.into())
@@ -693,6 +740,53 @@ impl Context {
         Ok(txns)
     }
 
+    /// Renders the given transactions using the indexer protobuf schema.
+    /// Unlike the JSON rendering, the block height and epoch of each
+    /// transaction are also populated.
+    pub fn render_transactions_protobuf<E: InternalError>(
+        &self,
+        ledger_info: &LedgerInfo,
+        data: Vec<TransactionOnChainData>,
+    ) -> Result<Vec<transaction_pb::Transaction>, E> {
+        if data.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let state_view = self.latest_state_view_poem(ledger_info)?;
+        let resolver = state_view.as_move_resolver();
+        let converter = resolver.as_converter(self.db.clone(), self.table_info_reader.clone());
+
+        // Transactions are often in the same block, so we cache the last block info
+        let mut block_info: Option<(Version, Version, NewBlockEvent)> = None;
+        let txns = data
+            .into_iter()
+            .map(|t| {
+                let is_cached = matches!(
+                    &block_info,
+                    Some((first_version, last_version, _))
+                        if (*first_version..=*last_version).contains(&t.version)
+                );
+                if !is_cached {
+                    block_info = Some(self.db.get_block_info_by_version(t.version)?);
+                }
+                let (_, _, new_block_event) = block_info.as_ref().expect("Block info must exist!");
+                let block_height = new_block_event.height();
+                let epoch = new_block_event.epoch();
+                let timestamp = new_block_event.proposed_time();
+
+                let size_info = convert_transaction_size_info(&t);
+                let txn = converter.try_into_onchain_transaction(timestamp, t)?;
+                convert_transaction(&txn, block_height, epoch, size_info)
+            })
+            .collect::<Result<_, anyhow::Error>>()
+            .context("Failed to convert transaction data from storage")
+            .map_err(|err| {
+                E::internal_with_code(err, AptosErrorCode::InternalError, ledger_info)
+            })?;
+
+        Ok(txns)
+    }
+
     pub fn get_transactions(
         &self,
         start_version: u64,
@@ -1275,7 +1369,7 @@ impl Context {
         }
     }
 
-    pub fn check_api_output_enabled<E: ForbiddenError>(
+    pub fn check_api_output_enabled<E: BadRequestError + ForbiddenError>(
         &self,
         api_name: &'static str,
         accept_type: &AcceptType,
@@ -1291,6 +1385,55 @@ impl Context {
                     return Err(bcs_api_disabled(api_name));
                 }
             },
+            AcceptType::Protobuf => return Err(protobuf_api_unsupported(api_name)),
+        }
+        Ok(())
+    }
+
+    /// Returns an error if the request is pinned to a ledger version, for APIs
+    /// that can only be served at the latest ledger version (e.g., transaction
+    /// submission and simulation).
+    pub fn check_ledger_version_not_pinned<E: BadRequestError>(
+        &self,
+        api_name: &'static str,
+    ) -> Result<(), E> {
+        if get_pinned_ledger_version().is_some() {
+            return Err(ledger_version_pin_unsupported(api_name));
+        }
+        Ok(())
+    }
+
+    /// Identical to `check_api_output_enabled`, but for APIs that also
+    /// support protobuf output (i.e., the transactions and blocks APIs).
+    pub fn check_api_output_enabled_with_protobuf<E: BadRequestError + ForbiddenError>(
+        &self,
+        api_name: &'static str,
+        accept_type: &AcceptType,
+    ) -> Result<(), E> {
+        if accept_type == &AcceptType::Protobuf {
+            if !self.node_config.api.protobuf_output_enabled {
+                return Err(protobuf_api_disabled(api_name));
+            }
+            return Ok(());
+        }
+        self.check_api_output_enabled(api_name, accept_type)
+    }
+
+    /// Returns an error if the given (heavy) API is temporarily refused
+    /// because the disk space guardrails are active (i.e., the node is low
+    /// on disk space).
+    pub fn check_disk_space_guardrails<E: ServiceUnavailableError>(
+        &self,
+        api_name: &'static str,
+    ) -> Result<(), E> {
+        if DISK_SPACE_GUARDRAILS.are_guardrails_active() {
+            return Err(E::service_unavailable_with_code_no_info(
+                format!(
+                    "{} is temporarily disabled because the node is low on disk space",
+                    api_name
+                ),
+                AptosErrorCode::ApiDisabled,
+            ));
         }
         Ok(())
     }
@@ -1316,6 +1459,34 @@ impl Context {
     }
 }
 
+/// A filter over the resources returned by [`Context::get_resources_by_pagination`].
+/// A resource is only returned if it matches all of the specified criteria.
+#[derive(Clone, Debug, Default)]
+pub struct ResourceFilter {
+    /// The address of the module that defines the resource
+    pub module_address: Option<AccountAddress>,
+    /// A prefix of the name of the module that defines the resource
+    pub module_name_prefix: Option<String>,
+    /// A type that must be one of the generic type arguments of the resource
+    pub generic_type: Option<TypeTag>,
+}
+
+impl ResourceFilter {
+    /// Returns true iff the given resource struct tag matches the filter
+    pub fn matches(&self, struct_tag: &StructTag) -> bool {
+        let address_matches = self
+            .module_address
+            .map_or(true, |module_address| struct_tag.address == module_address);
+        let module_name_matches = self.module_name_prefix.as_ref().map_or(true, |prefix| {
+            struct_tag.module.as_str().starts_with(prefix)
+        });
+        let generic_type_matches = self.generic_type.as_ref().map_or(true, |generic_type| {
+            struct_tag.type_args.contains(generic_type)
+        });
+        address_matches && module_name_matches && generic_type_matches
+    }
+}
+
 pub struct GasScheduleCache {
     last_updated_epoch: Option<u64>,
     gas_schedule_params: Option<AptosGasParameters>,
@@ -1335,16 +1506,23 @@ pub struct GasLimitCache {
 }
 
 /// This function just calls tokio::task::spawn_blocking with the given closure and in
-/// the case of an error when joining the task converts it into a 500.
+/// the case of an error when joining the task converts it into a 500. All storage reads
+/// performed by the closure are attributed to the API, and served at the ledger version
+/// the request is pinned to (if any).
 pub async fn api_spawn_blocking<F, T, E>(func: F) -> Result<T, E>
 where
     F: FnOnce() -> Result<T, E> + Send + 'static,
     T: Send + 'static,
     E: InternalError + Send + 'static,
 {
-    tokio::task::spawn_blocking(func)
-        .await
-        .map_err(|err| E::internal_with_code_no_info(err, AptosErrorCode::InternalError))?
+    let pinned_ledger_version = get_pinned_ledger_version();
+    tokio::task::spawn_blocking(move || {
+        let _read_context = ReadContext::enter(ReadSubsystem::Api);
+        let _pinned_ledger_version = PinnedLedgerVersion::enter(pinned_ledger_version);
+        func()
+    })
+    .await
+    .map_err(|err| E::internal_with_code_no_info(err, AptosErrorCode::InternalError))?
 }
 
 #[derive(Schema)]
@@ -9,8 +9,8 @@ use crate::{
     failpoint::fail_point_poem,
     page::Page,
     response::{
-        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
-        InternalError,
+        protobuf_api_unsupported, BadRequestError, BasicErrorWith404, BasicResponse,
+        BasicResponseStatus, BasicResultWith404, InternalError,
     },
     ApiTags,
 };
@@ -82,6 +82,7 @@ impl EventsApi {
             account.verify_account_or_object_resource()?;
             api.list(
                 account.latest_ledger_info,
+                account.ledger_version,
                 accept_type,
                 page,
                 EventKey::new(creation_number.0 .0, address.0.into()),
@@ -147,7 +148,13 @@ impl EventsApi {
         api_spawn_blocking(move || {
             let account = Account::new(api.context.clone(), address.0, None, None, None)?;
             let key = account.find_event_key(event_handle.0, field_name.0.into())?;
-            api.list(account.latest_ledger_info, accept_type, page, key)
+            api.list(
+                account.latest_ledger_info,
+                account.ledger_version,
+                accept_type,
+                page,
+                key,
+            )
         })
         .await
     }
@@ -158,11 +165,11 @@ impl EventsApi {
     fn list(
         &self,
         latest_ledger_info: LedgerInfo,
+        ledger_version: u64,
         accept_type: AcceptType,
         page: Page,
         event_key: EventKey,
     ) -> BasicResultWith404<Vec<VersionedEvent>> {
-        let ledger_version = latest_ledger_info.version();
         let events = self
             .context
             .get_events(
@@ -205,6 +212,7 @@ impl EventsApi {
             AcceptType::Bcs => {
                 BasicResponse::try_from_bcs((events, &latest_ledger_info, BasicResponseStatus::Ok))
             },
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Get events")),
         }
     }
 }
@@ -5,7 +5,7 @@
 use crate::{
     accept_type::AcceptType,
     context::{api_spawn_blocking, Context},
-    response::{BasicResponse, BasicResponseStatus, BasicResult},
+    response::{protobuf_api_unsupported, BasicResponse, BasicResponseStatus, BasicResult},
     ApiTags,
 };
 use aptos_api_types::{IndexResponse, IndexResponseBcs};
@@ -53,6 +53,7 @@ impl IndexApi {
                 let index_response = IndexResponseBcs::new(ledger_info.clone(), node_role);
                 BasicResponse::try_from_bcs((index_response, &ledger_info, BasicResponseStatus::Ok))
             },
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Get ledger info")),
         })
         .await
     }
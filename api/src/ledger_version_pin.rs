@@ -0,0 +1,122 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Supports pinning API requests to a specific ledger version. Clients set the
+//! [`X_APTOS_PIN_LEDGER_VERSION`] header, and every lookup performed by the
+//! request that does not explicitly specify a ledger version is served at the
+//! pinned version instead of the latest one. This allows clients to issue
+//! multiple consistent queries without racing new commits. APIs that can only
+//! be served at the latest ledger version (e.g., transaction submission and
+//! simulation) reject pinned requests.
+//!
+//! The pin is tracked per request: it is scoped to the request future by the
+//! [`LedgerVersionPin`] middleware, and carried over to the blocking threads
+//! that serve the request by [`PinnedLedgerVersion::enter`].
+
+use aptos_api_types::{AptosError, AptosErrorCode, X_APTOS_PIN_LEDGER_VERSION};
+use aptos_types::transaction::Version;
+use poem::{http::StatusCode, Endpoint, IntoResponse, Middleware, Request, Response, Result};
+use poem_openapi::payload::Json;
+use std::{cell::Cell, marker::PhantomData};
+
+tokio::task_local! {
+    static REQUEST_PINNED_LEDGER_VERSION: Option<Version>;
+}
+
+thread_local! {
+    static THREAD_PINNED_LEDGER_VERSION: Cell<Option<Version>> = Cell::new(None);
+}
+
+/// Returns the ledger version the current request is pinned to (if any)
+pub fn get_pinned_ledger_version() -> Option<Version> {
+    THREAD_PINNED_LEDGER_VERSION
+        .with(|pinned_version| pinned_version.get())
+        .or_else(|| {
+            REQUEST_PINNED_LEDGER_VERSION
+                .try_with(|pinned_version| *pinned_version)
+                .ok()
+                .flatten()
+        })
+}
+
+/// A guard that pins all lookups on the current thread to a ledger version.
+/// The previous pin is restored when the guard is dropped.
+#[must_use = "The ledger version is unpinned when the guard is dropped"]
+pub struct PinnedLedgerVersion {
+    previous_version: Option<Version>,
+    // The pin is thread-local, so the guard must not leave the thread
+    _not_send: PhantomData<*const ()>,
+}
+
+impl PinnedLedgerVersion {
+    /// Pins the current thread to the given ledger version (if any)
+    pub fn enter(version: Option<Version>) -> Self {
+        let previous_version =
+            THREAD_PINNED_LEDGER_VERSION.with(|pinned_version| pinned_version.replace(version));
+        Self {
+            previous_version,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Drop for PinnedLedgerVersion {
+    fn drop(&mut self) {
+        THREAD_PINNED_LEDGER_VERSION
+            .with(|pinned_version| pinned_version.set(self.previous_version));
+    }
+}
+
+/// This middleware parses the ledger version pin header (if set) and pins
+/// the request to the specified ledger version. Requests with an invalid
+/// pin are rejected.
+pub struct LedgerVersionPin;
+
+impl<E: Endpoint> Middleware<E> for LedgerVersionPin {
+    type Output = LedgerVersionPinEndpoint<E>;
+
+    fn transform(&self, ep: E) -> Self::Output {
+        LedgerVersionPinEndpoint { inner: ep }
+    }
+}
+
+/// Endpoint for LedgerVersionPin middleware.
+pub struct LedgerVersionPinEndpoint<E> {
+    inner: E,
+}
+
+#[async_trait::async_trait]
+impl<E: Endpoint> Endpoint for LedgerVersionPinEndpoint<E> {
+    type Output = Response;
+
+    async fn call(&self, req: Request) -> Result<Self::Output> {
+        let pinned_version = match req.headers().get(X_APTOS_PIN_LEDGER_VERSION) {
+            Some(header_value) => match header_value
+                .to_str()
+                .ok()
+                .and_then(|value| value.trim().parse::<Version>().ok())
+            {
+                Some(version) => Some(version),
+                None => {
+                    let error = AptosError::new_with_error_code(
+                        format!(
+                            "Invalid {} header, expected a ledger version: {:?}",
+                            X_APTOS_PIN_LEDGER_VERSION, header_value
+                        ),
+                        AptosErrorCode::InvalidInput,
+                    );
+                    return Ok(Json(error)
+                        .with_status(StatusCode::BAD_REQUEST)
+                        .into_response());
+                },
+            },
+            None => None,
+        };
+
+        REQUEST_PINNED_LEDGER_VERSION
+            .scope(pinned_version, async move {
+                self.inner.call(req).await.map(IntoResponse::into_response)
+            })
+            .await
+    }
+}
@@ -10,14 +10,19 @@ mod basic;
 mod bcs_payload;
 mod blocks;
 mod check_size;
+mod compression;
 pub mod context;
 mod error_converter;
 mod events;
 mod failpoint;
 mod index;
+mod ledger_version_pin;
+mod light_client;
 mod log;
 pub mod metrics;
 mod page;
+pub mod proto_convert;
+mod protobuf_payload;
 mod response;
 mod runtime;
 mod set_failpoints;
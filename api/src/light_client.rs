@@ -0,0 +1,436 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    accept_type::AcceptType,
+    context::api_spawn_blocking,
+    failpoint::fail_point_poem,
+    response::{
+        api_forbidden, build_not_found, protobuf_api_unsupported, transaction_not_found_by_version,
+        version_pruned, BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus,
+        BasicResultWith404, InternalError,
+    },
+    ApiTags, Context,
+};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{
+    AptosErrorCode, LedgerInfo, LightClientStateValueProof, LightClientTransactionProof, MoveValue,
+    RawStateValueRequest, U64,
+};
+use aptos_types::{
+    epoch_change::EpochChangeProof, ledger_info::LedgerInfoWithSignatures,
+    state_store::state_key::StateKey, waypoint::Waypoint,
+};
+use poem_openapi::{
+    param::{Path, Query},
+    payload::Json,
+    OpenApi,
+};
+use serde::Serialize;
+use std::{str::FromStr, sync::Arc};
+
+/// API for serving the minimal proof artifacts required by light clients.
+/// All artifacts are only served as BCS.
+#[derive(Clone)]
+pub struct LightClientApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl LightClientApi {
+    /// Get epoch change proof
+    ///
+    /// Retrieves the epoch change proof (i.e., the epoch ending ledger infos) from the
+    /// epoch following the given trusted waypoint to the latest epoch. If there are too
+    /// many epochs to return in a single response, the proof is marked as incomplete.
+    #[oai(
+        path = "/experimental/light_client/epoch_change_proof",
+        method = "get",
+        operation_id = "get_light_client_epoch_change_proof",
+        tag = "ApiTags::Experimental"
+    )]
+    async fn get_epoch_change_proof(
+        &self,
+        accept_type: AcceptType,
+        /// The trusted waypoint of the client, e.g., `0:<hash>` for the genesis waypoint
+        waypoint: Query<String>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_light_client_epoch_change_proof")?;
+        self.check_light_client_output("Get epoch change proof", &accept_type)?;
+
+        let api = self.clone();
+        api_spawn_blocking(move || api.epoch_change_proof(&accept_type, waypoint.0)).await
+    }
+
+    /// Get ledger info with signatures
+    ///
+    /// Retrieves the latest ledger info with signatures. If a version is provided,
+    /// the epoch ending ledger info at that version is returned instead (signatures
+    /// are only persisted for epoch ending ledger infos).
+    #[oai(
+        path = "/experimental/light_client/ledger_info",
+        method = "get",
+        operation_id = "get_light_client_ledger_info",
+        tag = "ApiTags::Experimental"
+    )]
+    async fn get_ledger_info_with_signatures(
+        &self,
+        accept_type: AcceptType,
+        /// The version of an epoch ending ledger info
+        ///
+        /// If not provided, the latest ledger info is returned
+        version: Query<Option<U64>>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_light_client_ledger_info")?;
+        self.check_light_client_output("Get ledger info with signatures", &accept_type)?;
+
+        let api = self.clone();
+        api_spawn_blocking(move || {
+            api.ledger_info_with_signatures(&accept_type, version.0.map(|inner| inner.0))
+        })
+        .await
+    }
+
+    /// Get transaction inclusion proof
+    ///
+    /// Retrieves a proof that the transaction at the given version was committed,
+    /// together with the latest ledger info the proof was created against.
+    ///
+    /// If the transaction has been pruned, the server responds with a 410.
+    #[oai(
+        path = "/experimental/light_client/transactions/:version/proof",
+        method = "get",
+        operation_id = "get_light_client_transaction_proof",
+        tag = "ApiTags::Experimental"
+    )]
+    async fn get_transaction_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Version of the transaction to prove
+        version: Path<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_light_client_transaction_proof")?;
+        self.check_light_client_output("Get transaction proof", &accept_type)?;
+
+        let api = self.clone();
+        api_spawn_blocking(move || api.transaction_proof(&accept_type, version.0 .0)).await
+    }
+
+    /// Get state value proof
+    ///
+    /// Retrieves the value (or absence) of the state key provided in the request body,
+    /// together with a proof against the latest persisted state checkpoint and the
+    /// latest ledger info.
+    #[oai(
+        path = "/experimental/light_client/state_values/proof",
+        method = "post",
+        operation_id = "get_light_client_state_value_proof",
+        tag = "ApiTags::Experimental"
+    )]
+    async fn get_state_value_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Request that carries the BCS serialized state key
+        request: Json<RawStateValueRequest>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_light_client_state_value_proof")?;
+        self.check_light_client_output("Get state value proof", &accept_type)?;
+
+        let api = self.clone();
+        api_spawn_blocking(move || api.state_value_proof(&accept_type, request.0)).await
+    }
+}
+
+impl LightClientApi {
+    /// Verifies that the given accept type can be served (i.e., only BCS)
+    fn check_light_client_output(
+        &self,
+        operation: &'static str,
+        accept_type: &AcceptType,
+    ) -> Result<(), BasicErrorWith404> {
+        if AcceptType::Json == *accept_type {
+            return Err(api_forbidden(
+                operation,
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled(operation, accept_type)
+    }
+
+    /// Returns the epoch change proof from the given waypoint to the latest epoch
+    fn epoch_change_proof(
+        &self,
+        accept_type: &AcceptType,
+        waypoint: String,
+    ) -> BasicResultWith404<MoveValue> {
+        let (ledger_info, latest_ledger_info) = self.get_latest_ledger_infos()?;
+
+        // Parse the waypoint and fetch the corresponding epoch ending ledger info
+        let waypoint = Waypoint::from_str(&waypoint)
+            .context(format!("Failed to parse the waypoint: {}", waypoint))
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+        let waypoint_ledger_info =
+            self.get_epoch_ending_ledger_info(waypoint.version(), &ledger_info)?;
+        waypoint
+            .verify(waypoint_ledger_info.ledger_info())
+            .context("The waypoint does not match the epoch ending ledger info")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+
+        // Fetch the epoch ending ledger infos from the waypoint to the latest epoch
+        let start_epoch = waypoint_ledger_info.ledger_info().next_block_epoch();
+        let end_epoch = latest_ledger_info.ledger_info().epoch();
+        let epoch_change_proof = if start_epoch < end_epoch {
+            self.context
+                .db
+                .get_epoch_ending_ledger_infos(start_epoch, end_epoch)
+                .context(format!(
+                    "Failed to get the epoch ending ledger infos from epoch {} to {}",
+                    start_epoch, end_epoch
+                ))
+                .map_err(|err| {
+                    BasicErrorWith404::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        &ledger_info,
+                    )
+                })?
+        } else {
+            EpochChangeProof::new(vec![], false)
+        };
+
+        self.create_bcs_response(
+            "Get epoch change proof",
+            accept_type,
+            epoch_change_proof,
+            &ledger_info,
+        )
+    }
+
+    /// Returns the ledger info with signatures (at the given epoch ending version, if specified)
+    fn ledger_info_with_signatures(
+        &self,
+        accept_type: &AcceptType,
+        version: Option<u64>,
+    ) -> BasicResultWith404<MoveValue> {
+        let (ledger_info, latest_ledger_info) = self.get_latest_ledger_infos()?;
+        let ledger_info_with_signatures = match version {
+            Some(version) => self.get_epoch_ending_ledger_info(version, &ledger_info)?,
+            None => latest_ledger_info,
+        };
+
+        self.create_bcs_response(
+            "Get ledger info with signatures",
+            accept_type,
+            ledger_info_with_signatures,
+            &ledger_info,
+        )
+    }
+
+    /// Returns the inclusion proof of the transaction at the given version
+    fn transaction_proof(
+        &self,
+        accept_type: &AcceptType,
+        version: u64,
+    ) -> BasicResultWith404<MoveValue> {
+        let (ledger_info, latest_ledger_info) = self.get_latest_ledger_infos()?;
+
+        // Verify the transaction exists and hasn't been pruned
+        let proof_version = latest_ledger_info.ledger_info().version();
+        if version > proof_version {
+            return Err(transaction_not_found_by_version(version, &ledger_info));
+        }
+        if version < ledger_info.oldest_ledger_version.0 {
+            return Err(version_pruned(version, &ledger_info));
+        }
+
+        // Fetch the transaction with proof
+        let transaction_with_proof = self
+            .context
+            .db
+            .get_transaction_by_version(version, proof_version, false)
+            .context(format!(
+                "Failed to get the transaction proof at version {}",
+                version
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let transaction_proof = LightClientTransactionProof {
+            ledger_info_with_signatures: latest_ledger_info,
+            transaction_with_proof,
+        };
+
+        self.create_bcs_response(
+            "Get transaction proof",
+            accept_type,
+            transaction_proof,
+            &ledger_info,
+        )
+    }
+
+    /// Returns the state value (and proof) of the given state key
+    fn state_value_proof(
+        &self,
+        accept_type: &AcceptType,
+        request: RawStateValueRequest,
+    ) -> BasicResultWith404<MoveValue> {
+        let (ledger_info, latest_ledger_info) = self.get_latest_ledger_infos()?;
+        let state_key: StateKey = bcs::from_bytes(&request.key.0)
+            .context(format!("Failed deserializing state key: {}", request.key))
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+
+        // Identify the latest persisted state checkpoint (proofs can only be
+        // created against state checkpoints that have been persisted).
+        let proof_version = latest_ledger_info.ledger_info().version();
+        let state_checkpoint_version = self
+            .context
+            .db
+            .get_state_snapshot_before(proof_version + 1)
+            .context("Failed to get the latest persisted state checkpoint")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?
+            .map(|(version, _)| version)
+            .ok_or_else(|| {
+                build_not_found(
+                    "State checkpoint",
+                    format!("Ledger version({})", proof_version),
+                    AptosErrorCode::StateValueNotFound,
+                    &ledger_info,
+                )
+            })?;
+
+        // Fetch the state value with proof and the checkpoint transaction info
+        let (state_value, state_value_proof) = self
+            .context
+            .db
+            .get_state_value_with_proof_by_version(&state_key, state_checkpoint_version)
+            .context(format!(
+                "Failed to get the state value proof. key: {}",
+                request.key
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let state_checkpoint_info_with_proof = self
+            .context
+            .db
+            .get_transaction_by_version(state_checkpoint_version, proof_version, false)
+            .context(format!(
+                "Failed to get the state checkpoint proof at version {}",
+                state_checkpoint_version
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?
+            .proof;
+        let state_value_proof = LightClientStateValueProof {
+            ledger_info_with_signatures: latest_ledger_info,
+            state_checkpoint_version,
+            state_checkpoint_info_with_proof,
+            state_value,
+            state_value_proof,
+        };
+
+        self.create_bcs_response(
+            "Get state value proof",
+            accept_type,
+            state_value_proof,
+            &ledger_info,
+        )
+    }
+
+    /// Returns the latest ledger info (for the response headers) and the
+    /// latest ledger info with signatures (to create proofs against).
+    fn get_latest_ledger_infos(
+        &self,
+    ) -> Result<(LedgerInfo, LedgerInfoWithSignatures), BasicErrorWith404> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let latest_ledger_info = self
+            .context
+            .get_latest_ledger_info_with_signatures()
+            .context("Failed to get the latest ledger info with signatures")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        Ok((ledger_info, latest_ledger_info))
+    }
+
+    /// Returns the epoch ending ledger info at the given version
+    fn get_epoch_ending_ledger_info(
+        &self,
+        version: u64,
+        ledger_info: &LedgerInfo,
+    ) -> Result<LedgerInfoWithSignatures, BasicErrorWith404> {
+        self.context
+            .db
+            .get_epoch_ending_ledger_info(version)
+            .map_err(|_| {
+                build_not_found(
+                    "Epoch ending ledger info",
+                    format!("Ledger version({})", version),
+                    AptosErrorCode::VersionNotFound,
+                    ledger_info,
+                )
+            })
+    }
+
+    /// Creates a BCS response for the given light client artifact
+    fn create_bcs_response<T: Serialize>(
+        &self,
+        operation: &'static str,
+        accept_type: &AcceptType,
+        artifact: T,
+        ledger_info: &LedgerInfo,
+    ) -> BasicResultWith404<MoveValue> {
+        match accept_type {
+            AcceptType::Json => Err(api_forbidden(
+                operation,
+                "Only BCS is supported as an AcceptType.",
+            )),
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((artifact, ledger_info, BasicResponseStatus::Ok))
+            },
+            AcceptType::Protobuf => Err(protobuf_api_unsupported(operation)),
+        }
+    }
+}
@@ -68,6 +68,15 @@ pub static REQUEST_SOURCE_CLIENT: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static RESPONSE_COMPRESSION: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_response_compression",
+        "API responses eligible for compression grouped by encoding and result",
+        &["encoding", "result"]
+    )
+    .unwrap()
+});
+
 pub static GAS_ESTIMATE: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
         "aptos_api_gas_estimate",
@@ -87,3 +96,21 @@ pub static GAS_USED: Lazy<HistogramVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static SUBMIT_AND_WAIT_RESULTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_submit_and_wait_results",
+        "Number of submit and wait transaction requests grouped by outcome",
+        &["outcome"]
+    )
+    .unwrap()
+});
+
+pub static TRANSACTION_VALIDATIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_api_transaction_validations",
+        "Number of transactions validated (without submission) grouped by outcome",
+        &["outcome"]
+    )
+    .unwrap()
+});
@@ -0,0 +1,74 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines a Poem payload type for protobuf output. Unlike BCS,
+//! protobuf is only supported as an output format (i.e., for responses).
+
+use aptos_api_types::mime_types::PROTOBUF;
+use poem::{http::header, IntoResponse, Response};
+use poem_openapi::{
+    payload::Payload,
+    registry::{MetaMediaType, MetaResponse, MetaResponses, MetaSchemaRef, Registry},
+    types::Type,
+    ApiResponse,
+};
+use std::ops::{Deref, DerefMut};
+
+/// A wrapper struct for a payload containing protobuf encoded bytes
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Protobuf(pub Vec<u8>);
+
+impl Deref for Protobuf {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for Protobuf {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Payload for Protobuf {
+    const CONTENT_TYPE: &'static str = PROTOBUF;
+
+    fn schema_ref() -> MetaSchemaRef {
+        Vec::<u8>::schema_ref()
+    }
+
+    #[allow(unused_variables)]
+    fn register(registry: &mut Registry) {
+        Vec::<u8>::register(registry);
+    }
+}
+
+impl IntoResponse for Protobuf {
+    fn into_response(self) -> Response {
+        Response::builder()
+            .header(header::CONTENT_TYPE, Self::CONTENT_TYPE)
+            .body(self.0)
+    }
+}
+
+impl ApiResponse for Protobuf {
+    fn meta() -> MetaResponses {
+        MetaResponses {
+            responses: vec![MetaResponse {
+                description: "Protobuf: The indexer protobuf schema",
+                status: Some(200),
+                content: vec![MetaMediaType {
+                    content_type: Self::CONTENT_TYPE,
+                    schema: Self::schema_ref(),
+                }],
+                headers: vec![],
+            }],
+        }
+    }
+
+    fn register(registry: &mut Registry) {
+        Vec::<u8>::register(registry);
+    }
+}
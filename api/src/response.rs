@@ -29,8 +29,10 @@
 
 // TODO: https://github.com/aptos-labs/aptos-core/issues/2279
 
-use super::{accept_type::AcceptType, bcs_payload::Bcs};
-use aptos_api_types::{Address, AptosError, AptosErrorCode, HashValue, LedgerInfo};
+use super::{accept_type::AcceptType, bcs_payload::Bcs, protobuf_payload::Protobuf};
+use aptos_api_types::{
+    Address, AptosError, AptosErrorCode, HashValue, LedgerInfo, X_APTOS_PIN_LEDGER_VERSION,
+};
 use move_core_types::{
     identifier::{IdentStr, Identifier},
     language_storage::StructTag,
@@ -50,6 +52,10 @@ pub enum AptosResponseContent<T: ToJSON + Send + Sync> {
     /// from either an internal Rust type being serialized into bytes, or just
     /// the bytes directly from storage.
     Bcs(Bcs),
+
+    /// Return the data as protobuf (using the indexer transaction schema).
+    /// This is only supported by the transactions and blocks APIs.
+    Protobuf(Protobuf),
 }
 
 /// This trait defines common functions that all error responses should impl.
@@ -376,12 +382,31 @@ macro_rules! generate_success_response {
             }
         }
 
+        // Generate a From impl that builds a response from a Protobuf and friends.
+        impl<T: poem_openapi::types::ToJSON + Send + Sync> From<($crate::protobuf_payload::Protobuf, &aptos_api_types::LedgerInfo, [<$enum_name Status>])>
+            for $enum_name<T>
+        {
+            fn from(
+                (value, ledger_info, status): (
+                    $crate::protobuf_payload::Protobuf,
+                    &aptos_api_types::LedgerInfo,
+                    [<$enum_name Status>]
+                ),
+            ) -> Self {
+                let content = $crate::response::AptosResponseContent::Protobuf(value);
+                Self::from((content, ledger_info, status))
+            }
+        }
+
         // Generate a TryFrom impl that builds a response from a T, an AcceptType,
         // and all the other usual suspects. It expects to be called with a generic
-        // parameter E: InternalError, with which we can build an internal error
-        // response in case the BCS serialization fails.
+        // parameter E: InternalError + BadRequestError, with which we can build an
+        // internal error response in case the BCS serialization fails (and a bad
+        // request response in case the accept type is not supported).
         impl<T: poem_openapi::types::ToJSON + Send + Sync + serde::Serialize> $enum_name<T> {
-            pub fn try_from_rust_value<E: $crate::response::InternalError>(
+            pub fn try_from_rust_value<
+                E: $crate::response::InternalError + $crate::response::BadRequestError
+            >(
                 (value, ledger_info, status, accept_type): (
                     T,
                     &aptos_api_types::LedgerInfo,
@@ -407,6 +432,11 @@ macro_rules! generate_success_response {
                         ledger_info,
                         status
                     ))),
+                    AcceptType::Protobuf => Err(E::bad_request_with_code(
+                        "Protobuf output is not supported for this response",
+                        aptos_api_types::AptosErrorCode::ProtobufNotSupported,
+                        ledger_info
+                    )),
                 }
             }
 
@@ -445,6 +475,22 @@ macro_rules! generate_success_response {
                )))
             }
 
+            pub fn try_from_protobuf<M: prost::Message, E: $crate::response::InternalError>(
+                (value, ledger_info, status): (
+                    M,
+                    &aptos_api_types::LedgerInfo,
+                    [<$enum_name Status>],
+                ),
+            ) -> Result<Self, E> {
+               Ok(Self::from((
+                    $crate::protobuf_payload::Protobuf(
+                        prost::Message::encode_to_vec(&value)
+                    ),
+                    ledger_info,
+                    status
+               )))
+            }
+
             pub fn try_from_encoded<E: $crate::response::InternalError>(
                 (value, ledger_info, status): (
                     Vec<u8>,
@@ -557,6 +603,36 @@ pub fn bcs_api_disabled<S: Display, E: ForbiddenError>(identifier: S) -> E {
     )
 }
 
+pub fn protobuf_api_disabled<S: Display, E: ForbiddenError>(identifier: S) -> E {
+    E::forbidden_with_code_no_info(
+        format!(
+            "{} with protobuf output is disabled on this endpoint",
+            identifier
+        ),
+        AptosErrorCode::ApiDisabled,
+    )
+}
+
+pub fn protobuf_api_unsupported<S: Display, E: BadRequestError>(identifier: S) -> E {
+    E::bad_request_with_code_no_info(
+        format!(
+            "{} does not support protobuf output. Protobuf output is only supported by the transactions and blocks APIs",
+            identifier
+        ),
+        AptosErrorCode::ProtobufNotSupported,
+    )
+}
+
+pub fn ledger_version_pin_unsupported<S: Display, E: BadRequestError>(identifier: S) -> E {
+    E::bad_request_with_code_no_info(
+        format!(
+            "{} can only be served at the latest ledger version, the {} header is not supported",
+            identifier, X_APTOS_PIN_LEDGER_VERSION
+        ),
+        AptosErrorCode::InvalidInput,
+    )
+}
+
 pub fn api_disabled<S: Display, E: ForbiddenError>(identifier: S) -> E {
     E::forbidden_with_code_no_info(
         format!("{} is disabled on this endpoint", identifier),
@@ -4,9 +4,10 @@
 
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
-    context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
-    view_function::ViewFunctionApi,
+    compression::ResponseCompression, context::Context, error_converter::convert_error,
+    events::EventsApi, index::IndexApi, ledger_version_pin::LedgerVersionPin,
+    light_client::LightClientApi, log::middleware_log, set_failpoints, state::StateApi,
+    transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_config::config::{ApiConfig, NodeConfig};
@@ -105,6 +106,7 @@ pub fn get_api_service(
         BlocksApi,
         EventsApi,
         IndexApi,
+        LightClientApi,
         StateApi,
         TransactionsApi,
         ViewFunctionApi,
@@ -128,6 +130,9 @@ pub fn get_api_service(
         IndexApi {
             context: context.clone(),
         },
+        LightClientApi {
+            context: context.clone(),
+        },
         StateApi {
             context: context.clone(),
         },
@@ -162,6 +167,7 @@ pub fn attach_poem_to_runtime(
     let context = Arc::new(context);
 
     let size_limit = context.content_length_limit();
+    let compression_config = config.api.compression.clone();
 
     let api_service = get_api_service(context.clone());
 
@@ -232,7 +238,9 @@ pub fn attach_poem_to_runtime(
                     ),
             )
             .with(cors)
+            .with(LedgerVersionPin)
             .with(PostSizeLimit::new(size_limit))
+            .with(ResponseCompression::new(compression_config))
             // NOTE: Make sure to keep this after all the `with` middleware.
             .catch_all_error(convert_error)
             .around(middleware_log);
@@ -6,9 +6,9 @@ use crate::{
     context::api_spawn_blocking,
     failpoint::fail_point_poem,
     response::{
-        api_forbidden, build_not_found, module_not_found, resource_not_found, table_item_not_found,
-        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
-        InternalError,
+        api_forbidden, build_not_found, module_not_found, protobuf_api_unsupported,
+        resource_not_found, table_item_not_found, BadRequestError, BasicErrorWith404,
+        BasicResponse, BasicResponseStatus, BasicResultWith404, InternalError,
     },
     ApiTags, Context,
 };
@@ -336,6 +336,7 @@ impl StateApi {
                 &ledger_info,
                 BasicResponseStatus::Ok,
             )),
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Get account resource")),
         }
     }
 
@@ -390,6 +391,7 @@ impl StateApi {
                 &ledger_info,
                 BasicResponseStatus::Ok,
             )),
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Get account module")),
         }
     }
 
@@ -486,6 +488,7 @@ impl StateApi {
                 &ledger_info,
                 BasicResponseStatus::Ok,
             )),
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Get table item")),
         }
     }
 
@@ -541,6 +544,7 @@ impl StateApi {
                 &ledger_info,
                 BasicResponseStatus::Ok,
             )),
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Get raw table item")),
         }
     }
 
@@ -610,6 +614,7 @@ impl StateApi {
             AcceptType::Bcs => {
                 BasicResponse::try_from_encoded((bytes, &ledger_info, BasicResponseStatus::Ok))
             },
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Get raw state value")),
         }
     }
 }
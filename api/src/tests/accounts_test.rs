@@ -3,8 +3,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::new_test_context;
-use aptos_api_test_context::{current_function_name, find_value};
-use aptos_api_types::{MoveModuleBytecode, MoveResource, MoveStructTag, StateKeyWrapper};
+use aptos_api_test_context::{current_function_name, find_value, TestContext};
+use aptos_api_types::{
+    MoveModuleBytecode, MoveResource, MoveStructTag, MoveType, StateKeyWrapper,
+    X_APTOS_PIN_LEDGER_VERSION,
+};
 use aptos_cached_packages::aptos_stdlib;
 use serde_json::json;
 use std::str::FromStr;
@@ -125,6 +128,58 @@ async fn test_get_account_resources_by_ledger_version() {
     assert_eq!(root_account["data"]["sequence_number"], "0");
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resources_with_pinned_ledger_version() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn.clone()]).await;
+    let root_address = context.root_account().await.address().to_hex_literal();
+
+    // Verify the pinned ledger version is used if no version is requested
+    let pinned_resources = context
+        .execute(
+            warp::test::request()
+                .method("GET")
+                .path(&context.prepend_path(&account_resources(&root_address)))
+                .header(X_APTOS_PIN_LEDGER_VERSION, "0"),
+        )
+        .await;
+    let root_account = find_value(&pinned_resources, |f| f["type"] == "0x1::account::Account");
+    assert_eq!(root_account["data"]["sequence_number"], "0");
+
+    // Verify an explicitly requested version takes precedence over the pin
+    let latest_version = context.get_latest_ledger_info().version();
+    let requested_resources = context
+        .execute(
+            warp::test::request()
+                .method("GET")
+                .path(
+                    &context.prepend_path(&account_resources_with_ledger_version(
+                        &root_address,
+                        latest_version as i128,
+                    )),
+                )
+                .header(X_APTOS_PIN_LEDGER_VERSION, "0"),
+        )
+        .await;
+    let root_account = find_value(&requested_resources, |f| {
+        f["type"] == "0x1::account::Account"
+    });
+    assert_eq!(root_account["data"]["sequence_number"], "1");
+
+    // Verify an invalid pin is rejected
+    context
+        .expect_status_code(400)
+        .execute(
+            warp::test::request()
+                .method("GET")
+                .path(&context.prepend_path(&account_resources(&root_address)))
+                .header(X_APTOS_PIN_LEDGER_VERSION, "latest"),
+        )
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_resources_by_too_large_ledger_version() {
     let mut context = new_test_context(current_function_name!());
@@ -271,6 +326,86 @@ async fn test_get_account_resources_with_pagination() {
     assert_eq!(resources, all_resources[9..].to_vec());
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resources_with_filters() {
+    let context = new_test_context(current_function_name!());
+    let address = "0x1";
+
+    // Fetch all resources (unfiltered)
+    let req = warp::test::request()
+        .method("GET")
+        .path(&format!("/v1{}", account_resources(address)));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    let all_resources: Vec<MoveResource> = serde_json::from_slice(resp.body()).unwrap();
+
+    // Filter the resources by module name prefix and verify the results
+    let resources = get_filtered_resources(&context, address, "module_name_prefix=coin").await;
+    let expected_resources: Vec<MoveResource> = all_resources
+        .iter()
+        .filter(|resource| resource.typ.module.as_str().starts_with("coin"))
+        .cloned()
+        .collect();
+    assert!(!resources.is_empty());
+    assert_eq!(resources, expected_resources);
+
+    // Filter the resources by generic type and verify the results
+    let resources =
+        get_filtered_resources(&context, address, "generic_type=0x1::aptos_coin::AptosCoin").await;
+    let aptos_coin =
+        MoveType::Struct(MoveStructTag::from_str("0x1::aptos_coin::AptosCoin").unwrap());
+    let expected_resources: Vec<MoveResource> = all_resources
+        .iter()
+        .filter(|resource| resource.typ.generic_type_params.contains(&aptos_coin))
+        .cloned()
+        .collect();
+    assert!(!resources.is_empty());
+    assert_eq!(resources, expected_resources);
+
+    // Combine the filters with a non-matching module address and verify nothing is returned
+    let resources = get_filtered_resources(
+        &context,
+        address,
+        "module_name_prefix=coin&module_address=0x2",
+    )
+    .await;
+    assert!(resources.is_empty());
+
+    // Verify pagination only counts the filtered resources
+    let req = warp::test::request().method("GET").path(&format!(
+        "/v1{}?module_address=0x1&limit=1000",
+        account_resources(address)
+    ));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    assert!(!resp.headers().contains_key("X-Aptos-Cursor"));
+    let resources: Vec<MoveResource> = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(resources, all_resources);
+
+    // Verify an invalid generic type is rejected
+    let req = warp::test::request().method("GET").path(&format!(
+        "/v1{}?generic_type=invalid",
+        account_resources(address)
+    ));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+async fn get_filtered_resources(
+    context: &TestContext,
+    address: &str,
+    filter_query: &str,
+) -> Vec<MoveResource> {
+    let req = warp::test::request().method("GET").path(&format!(
+        "/v1{}?{}",
+        account_resources(address),
+        filter_query
+    ));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    serde_json::from_slice(resp.body()).unwrap()
+}
+
 // Same as the above test but for modules.
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_modules_with_pagination() {
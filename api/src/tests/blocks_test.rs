@@ -1,8 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_protobuf};
 use aptos_api_test_context::current_function_name;
+use aptos_api_types::mime_types::PROTOBUF;
+use aptos_protos::transaction::v1::Block;
+use prost::Message;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_genesis_block_by_height() {
@@ -23,6 +26,30 @@ async fn test_get_unknown_block_by_height() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_block_by_height_protobuf() {
+    let mut context = new_test_context_with_protobuf(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", PROTOBUF)
+        .path(&context.prepend_path(&format!("{}?with_transactions=true", blocks_by_height(1))));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+
+    // Verify the block and its transactions (block metadata, user txn and checkpoint)
+    let block = Block::decode(resp.body().as_ref()).unwrap();
+    assert_eq!(block.height, 1);
+    assert_eq!(block.chain_id, 4);
+    assert_eq!(block.transactions.len(), 3);
+    for txn in block.transactions {
+        assert_eq!(txn.block_height, 1);
+    }
+}
+
 fn blocks_by_height(height: u64) -> String {
     format!("/blocks/by_height/{}", height)
 }
@@ -4,6 +4,7 @@
 
 use super::new_test_context;
 use aptos_api_test_context::current_function_name;
+use aptos_api_types::mime_types::PROTOBUF;
 use serde_json::json;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -43,6 +44,19 @@ async fn test_health_check() {
     assert_eq!(resp.status(), 200)
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_health_check_protobuf_not_supported() {
+    let context = new_test_context(current_function_name!());
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", PROTOBUF)
+        .path("/v1/-/healthy");
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 400);
+    let err: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(err["error_code"], "protobuf_not_supported");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_openapi_spec() {
     let context = new_test_context(current_function_name!());
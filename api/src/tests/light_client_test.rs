@@ -0,0 +1,139 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context;
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::{HexEncodedBytes, LightClientStateValueProof, LightClientTransactionProof};
+use aptos_storage_interface::DbReader;
+use aptos_types::{
+    access_path::AccessPath, account_address::AccountAddress, account_config::AccountResource,
+    epoch_change::EpochChangeProof, ledger_info::LedgerInfoWithSignatures,
+    state_store::state_key::StateKey, waypoint::Waypoint,
+};
+use move_core_types::move_resource::MoveStructType;
+use serde_json::json;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_light_client_epoch_change_proof() {
+    let context = new_test_context(current_function_name!());
+
+    // Fetch the epoch change proof from the genesis waypoint
+    let genesis_ledger_info = context.db.get_epoch_ending_ledger_info(0).unwrap();
+    let waypoint = Waypoint::new_epoch_boundary(genesis_ledger_info.ledger_info()).unwrap();
+    let response = context
+        .get_bcs(&format!(
+            "/experimental/light_client/epoch_change_proof?waypoint={}",
+            waypoint
+        ))
+        .await;
+
+    // Verify the proof is empty (the test chain never changes epoch)
+    let epoch_change_proof: EpochChangeProof = bcs::from_bytes(&response).unwrap();
+    assert!(epoch_change_proof.ledger_info_with_sigs.is_empty());
+    assert!(!epoch_change_proof.more);
+
+    // Verify that invalid waypoints are rejected
+    context
+        .expect_status_code(400)
+        .get_bcs("/experimental/light_client/epoch_change_proof?waypoint=invalid")
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_light_client_ledger_info() {
+    let mut context = new_test_context(current_function_name!());
+    context.create_account().await;
+
+    // Fetch the latest ledger info and verify the version
+    let response = context
+        .get_bcs("/experimental/light_client/ledger_info")
+        .await;
+    let ledger_info: LedgerInfoWithSignatures = bcs::from_bytes(&response).unwrap();
+    assert_eq!(
+        ledger_info.ledger_info().version(),
+        context.get_latest_ledger_info().version()
+    );
+
+    // Fetch the genesis ledger info and verify the version
+    let response = context
+        .get_bcs("/experimental/light_client/ledger_info?version=0")
+        .await;
+    let ledger_info: LedgerInfoWithSignatures = bcs::from_bytes(&response).unwrap();
+    assert_eq!(ledger_info.ledger_info().version(), 0);
+    assert!(ledger_info.ledger_info().ends_epoch());
+
+    // Verify that JSON responses are forbidden
+    context
+        .expect_status_code(403)
+        .get("/experimental/light_client/ledger_info")
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_light_client_transaction_proof() {
+    let mut context = new_test_context(current_function_name!());
+    context.create_account().await;
+
+    // Fetch and verify the transaction proofs
+    let latest_version = context.get_latest_ledger_info().version();
+    for version in [0, 1, latest_version] {
+        let response = context
+            .get_bcs(&format!(
+                "/experimental/light_client/transactions/{}/proof",
+                version
+            ))
+            .await;
+        let transaction_proof: LightClientTransactionProof = bcs::from_bytes(&response).unwrap();
+        transaction_proof.verify(version).unwrap();
+        assert!(transaction_proof.verify(version + 1).is_err());
+    }
+
+    // Verify that proofs for missing transactions are not found
+    context
+        .expect_status_code(404)
+        .get_bcs(&format!(
+            "/experimental/light_client/transactions/{}/proof",
+            latest_version + 100
+        ))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_light_client_state_value_proof() {
+    let context = new_test_context(current_function_name!());
+
+    // Fetch and verify the state value proof of an existing resource
+    let state_key =
+        create_account_resource_key(AccountAddress::from_hex_literal("0xA550C18").unwrap());
+    let state_value_proof = get_state_value_proof(&context, &state_key).await;
+    state_value_proof.verify(&state_key).unwrap();
+    assert!(state_value_proof.state_value.is_some());
+
+    // Fetch and verify the state value proof of a missing resource
+    let state_key =
+        create_account_resource_key(AccountAddress::from_hex_literal("0xA550C19").unwrap());
+    let state_value_proof = get_state_value_proof(&context, &state_key).await;
+    state_value_proof.verify(&state_key).unwrap();
+    assert!(state_value_proof.state_value.is_none());
+}
+
+/// Creates the state key of the account resource at the given address
+fn create_account_resource_key(address: AccountAddress) -> StateKey {
+    StateKey::access_path(
+        AccessPath::resource_access_path(address, AccountResource::struct_tag()).unwrap(),
+    )
+}
+
+/// Fetches the state value proof of the given state key
+async fn get_state_value_proof(
+    context: &TestContext,
+    state_key: &StateKey,
+) -> LightClientStateValueProof {
+    let response = context
+        .post_bcs(
+            "/experimental/light_client/state_values/proof",
+            json!({ "key": HexEncodedBytes::from(bcs::to_bytes(state_key).unwrap()) }),
+        )
+        .await;
+    bcs::from_bytes(&response).unwrap()
+}
@@ -8,6 +8,7 @@ mod converter_test;
 mod events_test;
 mod index_test;
 mod invalid_post_request_test;
+mod light_client_test;
 mod modules;
 mod multisig_transactions_test;
 mod objects;
@@ -31,3 +32,10 @@ fn new_test_context(test_name: String) -> TestContext {
 fn new_test_context_with_config(test_name: String, node_config: NodeConfig) -> TestContext {
     super_new_test_context(test_name, node_config, false)
 }
+
+/// Protobuf output requires decoded table items, so the DB is opened with the indexer
+fn new_test_context_with_protobuf(test_name: String) -> TestContext {
+    let mut node_config = NodeConfig::default();
+    node_config.api.protobuf_output_enabled = true;
+    super_new_test_context(test_name, node_config, true)
+}
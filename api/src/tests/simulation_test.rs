@@ -85,6 +85,11 @@ async fn test_simulate_transaction_with_insufficient_balance() {
     let mut context = new_test_context(current_function_name!());
     let resp = simulate_aptos_transfer(&mut context, false, LARGE_TRANSFER_AMOUNT, 200).await;
     assert!(!resp[0]["success"].as_bool().is_some_and(|v| v));
+
+    // Verify the abort code is resolved using the error map of the module
+    let abort_details = &resp[0]["abort_details"];
+    assert_eq!(abort_details["reason_name"], "EINSUFFICIENT_BALANCE");
+    assert!(!abort_details["description"].as_str().unwrap().is_empty());
 }
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -3,14 +3,18 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::new_test_context;
-use crate::tests::new_test_context_with_config;
+use crate::tests::{new_test_context_with_config, new_test_context_with_protobuf};
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
+use aptos_api_types::{mime_types::PROTOBUF, X_APTOS_PIN_LEDGER_VERSION};
 use aptos_config::config::{GasEstimationStaticOverride, NodeConfig};
 use aptos_crypto::{
     ed25519::Ed25519PrivateKey,
     multi_ed25519::{MultiEd25519PrivateKey, MultiEd25519PublicKey},
     PrivateKey, SigningKey, Uniform,
 };
+use aptos_protos::{
+    indexer::v1::TransactionsResponse, transaction::v1::Transaction as TransactionPb,
+};
 use aptos_sdk::types::LocalAccount;
 use aptos_types::{
     account_address::AccountAddress,
@@ -26,6 +30,7 @@ use move_core_types::{
     language_storage::{ModuleId, TypeTag},
 };
 use poem_openapi::types::ParseFromJSON;
+use prost::Message;
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use serde_json::json;
 use std::{path::PathBuf, time::Duration};
@@ -94,6 +99,57 @@ async fn test_get_transactions_with_invalid_limit_param() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transactions_with_pinned_ledger_version() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+    let pinned_version = context.get_latest_ledger_info().version();
+
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+    assert!(context.get_latest_ledger_info().version() > pinned_version);
+
+    // Verify the transactions are listed up to the pinned version
+    let txns = context
+        .execute(
+            warp::test::request()
+                .method("GET")
+                .path(&context.prepend_path("/transactions"))
+                .header(X_APTOS_PIN_LEDGER_VERSION, pinned_version.to_string()),
+        )
+        .await;
+    let last_txn = txns.as_array().unwrap().last().unwrap();
+    assert_eq!(last_txn["version"], pinned_version.to_string());
+
+    // Verify the transactions after the pinned version are not found
+    context
+        .expect_status_code(404)
+        .execute(
+            warp::test::request()
+                .method("GET")
+                .path(
+                    &context
+                        .prepend_path(&format!("/transactions/by_version/{}", pinned_version + 1)),
+                )
+                .header(X_APTOS_PIN_LEDGER_VERSION, pinned_version.to_string()),
+        )
+        .await;
+
+    // Verify the APIs that can only be served at the latest version reject the pin
+    context
+        .expect_status_code(400)
+        .execute(
+            warp::test::request()
+                .method("GET")
+                .path(&context.prepend_path("/estimate_gas_price"))
+                .header(X_APTOS_PIN_LEDGER_VERSION, pinned_version.to_string()),
+        )
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_transactions_with_zero_limit() {
     let mut context = new_test_context(current_function_name!());
@@ -167,6 +223,118 @@ async fn test_post_invalid_signature_transaction() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_validate_transaction() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let body = bcs::to_bytes(&txn).unwrap();
+    let resp = context
+        .expect_status_code(200)
+        .post_bcs_txn("/transactions/validate", body)
+        .await;
+
+    // Verify the transaction is valid and that it was not submitted to mempool
+    assert_eq!(resp["valid"], true);
+    assert_eq!(resp["signature_valid"], true);
+    assert_eq!(resp["gas_bounds_valid"], true);
+    assert_eq!(resp["prologue_valid"], true);
+    assert!(resp["vm_status"].is_null());
+    context
+        .expect_status_code(404)
+        .get(&format!(
+            "/transactions/by_hash/{}",
+            resp["hash"].as_str().unwrap()
+        ))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_validate_invalid_signature_transaction() {
+    let mut context = new_test_context(current_function_name!());
+    let txn = context.create_invalid_signature_transaction().await;
+    let body = bcs::to_bytes(&txn).unwrap();
+    let resp = context
+        .expect_status_code(200)
+        .post_bcs_txn("/transactions/validate", &body)
+        .await;
+
+    // Verify the signature failure is reported (and the later checks are skipped)
+    assert_eq!(resp["valid"], false);
+    assert_eq!(resp["signature_valid"], false);
+    assert!(resp["gas_bounds_valid"].is_null());
+    assert!(resp["prologue_valid"].is_null());
+    assert_eq!(resp["vm_status"], "INVALID_SIGNATURE");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_validate_committed_transaction() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn.clone()]).await;
+
+    // Validate the transaction again (its sequence number is now too old)
+    let body = bcs::to_bytes(&txn).unwrap();
+    let resp = context
+        .expect_status_code(200)
+        .post_bcs_txn("/transactions/validate", &body)
+        .await;
+
+    // Verify the prologue failure is reported (and the earlier checks passed)
+    assert_eq!(resp["valid"], false);
+    assert_eq!(resp["signature_valid"], true);
+    assert_eq!(resp["gas_bounds_valid"], true);
+    assert_eq!(resp["prologue_valid"], false);
+    assert_eq!(resp["vm_status"], "SEQUENCE_NUMBER_TOO_OLD");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_submit_and_wait_transaction_pending() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let body = bcs::to_bytes(&txn).unwrap();
+
+    // The transaction is never committed, so the wait should time out
+    let resp = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions/submit_and_wait?wait_timeout_ms=100", body)
+        .await;
+    assert_eq!(resp["status"], "pending");
+    assert_eq!(resp["simulated_transaction"]["success"], true);
+    assert!(resp["committed_transaction"].is_null());
+
+    // Verify the transaction was submitted to mempool
+    let resp = context
+        .get(&format!(
+            "/transactions/by_hash/{}",
+            resp["hash"].as_str().unwrap()
+        ))
+        .await;
+    assert_eq!(resp["type"], "pending_transaction");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_submit_and_wait_transaction_simulation_failed() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn.clone()]).await;
+
+    // The sequence number has already been used, so the simulation should fail
+    let resp = context
+        .expect_status_code(200)
+        .post_bcs_txn(
+            "/transactions/submit_and_wait",
+            bcs::to_bytes(&txn).unwrap(),
+        )
+        .await;
+    assert_eq!(resp["status"], "simulation_failed");
+    assert_eq!(resp["simulated_transaction"]["success"], false);
+    assert!(resp["committed_transaction"].is_null());
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_post_transaction_rejected_by_mempool() {
     let mut context = new_test_context(current_function_name!());
@@ -187,6 +355,98 @@ async fn test_post_transaction_rejected_by_mempool() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_submit_ordered_transactions_batch() {
+    let mut context = new_test_context(current_function_name!());
+    let mut root_account = context.root_account().await;
+    let receiver = context.gen_account();
+
+    // Submit an ordered batch of transfers and verify that all are accepted
+    let txns: Vec<_> = (0..3)
+        .map(|_| context.account_transfer_to(&mut root_account, receiver.address(), 1))
+        .collect();
+    let resp = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions/batch/ordered", bcs::to_bytes(&txns).unwrap())
+        .await;
+    assert!(resp["transaction_failures"].as_array().unwrap().is_empty());
+
+    // Create an ordered batch where the second transaction has an invalid signature
+    let invalid_private_key = Ed25519PrivateKey::generate_for_testing();
+    let mut invalid_root_account = LocalAccount::new(
+        root_account.address(),
+        invalid_private_key,
+        root_account.sequence_number() + 1,
+    );
+    let txns = vec![
+        context.account_transfer_to(&mut root_account, receiver.address(), 1),
+        context.account_transfer_to(&mut invalid_root_account, receiver.address(), 1),
+        context.account_transfer_to(&mut invalid_root_account, receiver.address(), 1),
+    ];
+
+    // Verify that the first transaction is accepted, and the suffix is rejected
+    let resp = context
+        .expect_status_code(206)
+        .post_bcs_txn("/transactions/batch/ordered", bcs::to_bytes(&txns).unwrap())
+        .await;
+    let failures = resp["transaction_failures"].as_array().unwrap();
+    let failed_indices: Vec<_> = failures
+        .iter()
+        .map(|failure| failure["transaction_index"].as_u64().unwrap())
+        .collect();
+    assert_eq!(failed_indices, vec![1, 2]);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_submit_invalid_ordered_transactions_batch() {
+    let mut context = new_test_context(current_function_name!());
+    let mut root_account = context.root_account().await;
+    let mut other_account = context.gen_account();
+    let receiver = context.gen_account();
+
+    // Verify that batches with non-consecutive sequence numbers are rejected
+    let txn_1 = context.account_transfer_to(&mut root_account, receiver.address(), 1);
+    root_account.increment_sequence_number();
+    let txn_2 = context.account_transfer_to(&mut root_account, receiver.address(), 1);
+    context
+        .expect_status_code(400)
+        .post_bcs_txn(
+            "/transactions/batch/ordered",
+            bcs::to_bytes(&vec![txn_1, txn_2]).unwrap(),
+        )
+        .await;
+
+    // Verify that batches with multiple senders are rejected
+    let txn_1 = context.account_transfer_to(&mut root_account, receiver.address(), 1);
+    let txn_2 = context.account_transfer_to(&mut other_account, receiver.address(), 1);
+    context
+        .expect_status_code(400)
+        .post_bcs_txn(
+            "/transactions/batch/ordered",
+            bcs::to_bytes(&vec![txn_1, txn_2]).unwrap(),
+        )
+        .await;
+
+    // Verify that batches with overflowing sequence numbers are rejected
+    root_account.set_sequence_number(u64::MAX);
+    let txn_1 = context.account_transfer_to(&mut root_account, receiver.address(), 1);
+    let txn_2 = context.account_transfer_to(&mut root_account, receiver.address(), 1);
+    context
+        .expect_status_code(400)
+        .post_bcs_txn(
+            "/transactions/batch/ordered",
+            bcs::to_bytes(&vec![txn_1, txn_2]).unwrap(),
+        )
+        .await;
+
+    // Verify that empty batches are rejected
+    let txns: Vec<SignedTransaction> = vec![];
+    context
+        .expect_status_code(400)
+        .post_bcs_txn("/transactions/batch/ordered", bcs::to_bytes(&txns).unwrap())
+        .await;
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_multi_agent_signed_transaction() {
     let mut context = new_test_context(current_function_name!());
@@ -536,6 +796,95 @@ async fn test_get_transaction_by_version() {
     assert_json(resp, txns[0].clone())
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transactions_protobuf() {
+    let mut context = new_test_context_with_protobuf(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn.clone()]).await;
+    let json_txns = context.get("/transactions?start=0&limit=3").await;
+
+    // Fetch the same transactions as protobuf
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", PROTOBUF)
+        .path(&context.prepend_path("/transactions?start=0&limit=3"));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    assert_eq!(resp.headers()["Content-Type"], PROTOBUF);
+    let response = TransactionsResponse::decode(resp.body().as_ref()).unwrap();
+
+    // Verify the protobuf transactions match the JSON transactions
+    assert_eq!(response.chain_id, Some(4));
+    assert_eq!(response.transactions.len(), 3);
+    for (pb_txn, json_txn) in response
+        .transactions
+        .iter()
+        .zip(json_txns.as_array().unwrap())
+    {
+        assert_eq!(
+            pb_txn.version.to_string(),
+            json_txn["version"].as_str().unwrap()
+        );
+        assert_eq!(
+            hex::encode(&pb_txn.info.as_ref().unwrap().hash),
+            json_txn["hash"].as_str().unwrap().trim_start_matches("0x")
+        );
+    }
+
+    // Fetch a single transaction by version
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", PROTOBUF)
+        .path(&context.prepend_path("/transactions/by_version/2"));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    let pb_txn = TransactionPb::decode(resp.body().as_ref()).unwrap();
+    assert_eq!(pb_txn, response.transactions[2]);
+    assert_eq!(pb_txn.block_height, 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transactions_protobuf_disabled() {
+    let context = new_test_context(current_function_name!());
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", PROTOBUF)
+        .path(&context.prepend_path("/transactions?start=0&limit=1"));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 403);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transactions_protobuf_without_table_info() {
+    // Without the indexer, table items can't be decoded (e.g., the
+    // aggregator writes of genesis) and the conversion must fail gracefully.
+    let mut node_config = NodeConfig::default();
+    node_config.api.protobuf_output_enabled = true;
+    let context = new_test_context_with_config(current_function_name!(), node_config);
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", PROTOBUF)
+        .path(&context.prepend_path("/transactions?start=0&limit=1"));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 500);
+    let err: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(err["error_code"], "internal_error");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_protobuf_unsupported_endpoint() {
+    let context = new_test_context(current_function_name!());
+    let req = warp::test::request()
+        .method("GET")
+        .header("Accept", PROTOBUF)
+        .path(&context.prepend_path("/accounts/0x1"));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 400);
+    let err: serde_json::Value = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(err["error_code"], "protobuf_not_supported");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_pending_transaction_by_hash() {
     let mut context = new_test_context(current_function_name!());
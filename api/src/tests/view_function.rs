@@ -3,6 +3,7 @@
 
 use super::{new_test_context, new_test_context_with_config};
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::X_APTOS_PIN_LEDGER_VERSION;
 use aptos_cached_packages::aptos_stdlib;
 use aptos_config::config::{NodeConfig, ViewFilter, ViewFunctionId};
 use aptos_types::account_address::AccountAddress;
@@ -43,6 +44,41 @@ async fn test_simple_view() {
     context.check_golden_output_no_prune(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_with_pinned_ledger_version() {
+    let mut context = new_test_context(current_function_name!());
+    let creator = &mut context.gen_account();
+    let owner = &mut context.gen_account();
+    let txn = context.mint_user_account(creator).await;
+    context.commit_block(&vec![txn]).await;
+    let pinned_version = context.get_latest_ledger_info().version();
+
+    let txn = context.account_transfer(creator, owner, 100_000);
+    context.commit_block(&vec![txn]).await;
+
+    // Verify the view function is executed at the pinned version
+    let pinned_balance = context
+        .execute(
+            warp::test::request()
+                .method("POST")
+                .path(&context.prepend_path("/view"))
+                .json(&build_coin_balance_request(&creator.address()))
+                .header(X_APTOS_PIN_LEDGER_VERSION, pinned_version.to_string()),
+        )
+        .await;
+    let requested_balance = context
+        .post(
+            &format!("/view?ledger_version={}", pinned_version),
+            build_coin_balance_request(&creator.address()),
+        )
+        .await;
+    let latest_balance = context
+        .post("/view", build_coin_balance_request(&creator.address()))
+        .await;
+    assert_eq!(pinned_balance, requested_balance);
+    assert_ne!(pinned_balance, latest_balance);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_view_allowlist() {
     let mut node_config = NodeConfig::default();
@@ -11,7 +11,7 @@ use crate::{
     generate_error_response, generate_success_response, metrics,
     page::Page,
     response::{
-        api_disabled, api_forbidden, transaction_not_found_by_hash,
+        api_disabled, api_forbidden, protobuf_api_unsupported, transaction_not_found_by_hash,
         transaction_not_found_by_version, version_pruned, BadRequestError, BasicError,
         BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404,
         ForbiddenError, InsufficientStorageError, InternalError,
@@ -22,12 +22,14 @@ use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
     verify_function_identifier, verify_module_identifier, Address, AptosError, AptosErrorCode,
     AsConverter, EncodeSubmissionRequest, GasEstimation, GasEstimationBcs, HashValue,
-    HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitTransactionRequest,
-    Transaction, TransactionData, TransactionOnChainData, TransactionsBatchSingleSubmissionFailure,
+    HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitAndWaitTransactionResult,
+    SubmitAndWaitTransactionStatus, SubmitTransactionRequest, Transaction, TransactionData,
+    TransactionOnChainData, TransactionValidationResult, TransactionsBatchSingleSubmissionFailure,
     TransactionsBatchSubmissionResult, UserTransaction, VerifyInput, VerifyInputWithRecursion,
     MAX_RECURSIVE_TYPES_ALLOWED, U64,
 };
 use aptos_crypto::{hash::CryptoHash, signing_message};
+use aptos_protos::indexer::v1::TransactionsResponse;
 use aptos_types::{
     account_config::CoinStoreResource,
     mempool_status::MempoolStatusCode,
@@ -37,14 +39,16 @@ use aptos_types::{
     },
     vm_status::StatusCode,
 };
-use aptos_vm::{data_cache::AsMoveResolver, AptosSimulationVM};
+use aptos_vm::{
+    data_cache::AsMoveResolver, AptosSimulationVM, AptosVM, TransactionValidationCheck,
+};
 use move_core_types::vm_status::VMStatus;
 use poem_openapi::{
     param::{Path, Query},
     payload::Json,
     ApiRequest, OpenApi,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 generate_success_response!(SubmitTransactionResponse, (202, Accepted));
 
@@ -73,6 +77,11 @@ type SubmitTransactionsBatchResult<T> =
 
 type SimulateTransactionResult<T> = poem::Result<BasicResponse<T>, SubmitTransactionError>;
 
+generate_success_response!(SubmitAndWaitTransactionResponse, (200, Ok), (202, Accepted));
+
+type SubmitAndWaitTransactionResult<T> =
+    poem::Result<SubmitAndWaitTransactionResponse<T>, SubmitTransactionError>;
+
 // TODO: Consider making both content types accept either
 // SubmitTransactionRequest or SignedTransaction, the way
 // it is now is quite confusing.
@@ -164,7 +173,7 @@ impl TransactionsApi {
     ) -> BasicResultWith404<Vec<Transaction>> {
         fail_point_poem("endpoint_get_transactions")?;
         self.context
-            .check_api_output_enabled("Get transactions", &accept_type)?;
+            .check_api_output_enabled_with_protobuf("Get transactions", &accept_type)?;
         let page = Page::new(
             start.0.map(|v| v.0),
             limit.0,
@@ -204,7 +213,7 @@ impl TransactionsApi {
     ) -> BasicResultWith404<Transaction> {
         fail_point_poem("endpoint_transaction_by_hash")?;
         self.context
-            .check_api_output_enabled("Get transactions by hash", &accept_type)?;
+            .check_api_output_enabled_with_protobuf("Get transactions by hash", &accept_type)?;
         self.get_transaction_by_hash_inner(&accept_type, txn_hash.0)
             .await
     }
@@ -227,7 +236,7 @@ impl TransactionsApi {
     ) -> BasicResultWith404<Transaction> {
         fail_point_poem("endpoint_transaction_by_version")?;
         self.context
-            .check_api_output_enabled("Get transactions by version", &accept_type)?;
+            .check_api_output_enabled_with_protobuf("Get transactions by version", &accept_type)?;
         let api = self.clone();
         api_spawn_blocking(move || {
             api.get_transaction_by_version_inner(&accept_type, txn_version.0)
@@ -265,7 +274,7 @@ impl TransactionsApi {
     ) -> BasicResultWith404<Vec<Transaction>> {
         fail_point_poem("endpoint_get_accounts_transactions")?;
         self.context
-            .check_api_output_enabled("Get account transactions", &accept_type)?;
+            .check_api_output_enabled_with_protobuf("Get account transactions", &accept_type)?;
         let page = Page::new(
             start.0.map(|v| v.0),
             limit.0,
@@ -319,6 +328,8 @@ impl TransactionsApi {
         }
         self.context
             .check_api_output_enabled("Submit transaction", &accept_type)?;
+        self.context
+            .check_ledger_version_not_pinned("Submit transaction")?;
         let ledger_info = self.context.get_latest_ledger_info()?;
         let signed_transaction = self.get_signed_transaction(&ledger_info, data)?;
         self.create(&accept_type, &ledger_info, signed_transaction)
@@ -372,6 +383,8 @@ impl TransactionsApi {
         }
         self.context
             .check_api_output_enabled("Submit batch transactions", &accept_type)?;
+        self.context
+            .check_ledger_version_not_pinned("Submit batch transactions")?;
         let ledger_info = self.context.get_latest_ledger_info()?;
         let signed_transactions_batch = self.get_signed_transactions_batch(&ledger_info, data)?;
         if self.context.max_submit_transaction_batch_size() < signed_transactions_batch.len() {
@@ -389,6 +402,64 @@ impl TransactionsApi {
             .await
     }
 
+    /// Submit an ordered batch of transactions
+    ///
+    /// This allows you to submit an ordered batch of transactions from a single
+    /// sender. The transactions must have consecutive sequence numbers, and are
+    /// submitted to mempool in order. If a transaction fails to be submitted, all
+    /// subsequent transactions in the batch are rejected (i.e., they are not
+    /// submitted), so that mempool never holds a gap in the ordered batch.
+    ///
+    /// The response has the same outcomes as the batch submission endpoint:
+    ///
+    ///   1. All transactions succeed, and it will return a 202
+    ///   2. Some (or no) transactions succeed, and it will return the failed
+    ///      (and rejected) transactions and a 206
+    #[oai(
+        path = "/transactions/batch/ordered",
+        method = "post",
+        operation_id = "submit_ordered_batch_transactions",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn submit_ordered_transactions_batch(
+        &self,
+        accept_type: AcceptType,
+        data: SubmitTransactionsBatchPost,
+    ) -> SubmitTransactionsBatchResult<TransactionsBatchSubmissionResult> {
+        data.verify()
+            .context("Submitted transactions invalid")
+            .map_err(|err| {
+                SubmitTransactionError::bad_request_with_code_no_info(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                )
+            })?;
+        fail_point_poem("endpoint_submit_ordered_batch_transactions")?;
+        if !self.context.node_config.api.transaction_submission_enabled {
+            return Err(api_disabled("Submit ordered batch transaction"));
+        }
+        self.context
+            .check_api_output_enabled("Submit ordered batch transactions", &accept_type)?;
+        self.context
+            .check_ledger_version_not_pinned("Submit ordered batch transactions")?;
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let signed_transactions_batch = self.get_signed_transactions_batch(&ledger_info, data)?;
+        if self.context.max_submit_transaction_batch_size() < signed_transactions_batch.len() {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                format!(
+                    "Submitted too many transactions: {}, while limit is {}",
+                    signed_transactions_batch.len(),
+                    self.context.max_submit_transaction_batch_size(),
+                ),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+        verify_ordered_transactions_batch(&ledger_info, &signed_transactions_batch)?;
+        self.create_ordered_batch(&accept_type, &ledger_info, signed_transactions_batch)
+            .await
+    }
+
     /// Simulate transaction
     ///
     /// The output of the transaction will have the exact transaction outputs and events that running
@@ -436,6 +507,10 @@ impl TransactionsApi {
         }
         self.context
             .check_api_output_enabled("Simulate transaction", &accept_type)?;
+        self.context
+            .check_ledger_version_not_pinned("Simulate transaction")?;
+        self.context
+            .check_disk_space_guardrails("Simulate transaction")?;
 
         let api = self.clone();
         let context = self.context.clone();
@@ -530,6 +605,242 @@ impl TransactionsApi {
         .await
     }
 
+    /// Submit and wait for transaction
+    ///
+    /// Simulates, submits and waits for the commitment of a transaction in a single
+    /// call. This reduces the three round trips of a simple integration (i.e., simulate,
+    /// submit and poll by hash) to one. The transaction must be signed, and accepts the
+    /// same formats as the submit transaction endpoint.
+    ///
+    /// The response has three outcomes:
+    ///
+    ///   1. The transaction committed before the wait timed out, and it will return a 200
+    ///      (with the committed transaction)
+    ///   2. The simulation failed and the submission was aborted, and it will return a 200
+    ///      (with the simulated transaction)
+    ///   3. The transaction was submitted but did not commit before the wait timed out,
+    ///      and it will return a 202. The transaction can then be polled by hash.
+    ///
+    /// If the simulation or submission times out, a 503 will be returned. Only JSON
+    /// output is supported.
+    #[oai(
+        path = "/transactions/submit_and_wait",
+        method = "post",
+        operation_id = "submit_and_wait_transaction",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn submit_and_wait_transaction(
+        &self,
+        accept_type: AcceptType,
+        /// If set to false, the transaction is submitted without being simulated first.
+        ///
+        /// If not provided, defaults to true
+        simulate: Query<Option<bool>>,
+        /// If set to false, the transaction is submitted even if the simulation fails.
+        ///
+        /// If not provided, defaults to true
+        abort_on_simulation_failure: Query<Option<bool>>,
+        /// Timeout (ms) for simulating the transaction
+        ///
+        /// If not provided, defaults to the node's configured timeout
+        simulation_timeout_ms: Query<Option<U64>>,
+        /// Timeout (ms) for submitting the transaction to mempool
+        ///
+        /// If not provided, defaults to the node's configured timeout
+        submission_timeout_ms: Query<Option<U64>>,
+        /// Timeout (ms) for waiting for the transaction to commit
+        ///
+        /// If not provided, defaults to the node's configured timeout
+        wait_timeout_ms: Query<Option<U64>>,
+        data: SubmitTransactionPost,
+    ) -> SubmitAndWaitTransactionResult<SubmitAndWaitTransactionResult> {
+        data.verify()
+            .context("Submitted transaction invalid'")
+            .map_err(|err| {
+                SubmitTransactionError::bad_request_with_code_no_info(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                )
+            })?;
+        fail_point_poem("endpoint_submit_and_wait_transaction")?;
+        let api_config = &self.context.node_config.api;
+        if !api_config.transaction_submission_enabled || !api_config.submit_and_wait.enabled {
+            return Err(api_disabled("Submit and wait transaction"));
+        }
+        let simulate = simulate.0.unwrap_or(true);
+        if simulate && !api_config.transaction_simulation_enabled {
+            return Err(api_disabled("Simulate transaction"));
+        }
+        if accept_type != AcceptType::Json {
+            return Err(api_forbidden(
+                "Submit and wait transaction",
+                "Only JSON is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Submit and wait transaction", &accept_type)?;
+        self.context
+            .check_ledger_version_not_pinned("Submit and wait transaction")?;
+
+        // Calculate the timeouts for each step
+        let submit_and_wait_config = &api_config.submit_and_wait;
+        let get_step_timeout = |timeout_ms: Option<U64>, default_timeout_ms: u64| {
+            let timeout_ms = timeout_ms.map(|timeout_ms| timeout_ms.0);
+            Duration::from_millis(
+                timeout_ms
+                    .unwrap_or(default_timeout_ms)
+                    .min(submit_and_wait_config.max_step_timeout_ms),
+            )
+        };
+        let simulation_timeout = get_step_timeout(
+            simulation_timeout_ms.0,
+            submit_and_wait_config.default_simulation_timeout_ms,
+        );
+        let submission_timeout = get_step_timeout(
+            submission_timeout_ms.0,
+            submit_and_wait_config.default_submission_timeout_ms,
+        );
+        let wait_timeout = get_step_timeout(
+            wait_timeout_ms.0,
+            submit_and_wait_config.default_wait_timeout_ms,
+        );
+
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let signed_transaction = self.get_signed_transaction(&ledger_info, data)?;
+        let hash = signed_transaction.clone().committed_hash();
+
+        // Simulate the transaction (if requested)
+        let simulated_transaction = if simulate {
+            let api = self.clone();
+            let simulation_ledger_info = ledger_info.clone();
+            let simulation_transaction = signed_transaction.clone();
+            let simulation = api_spawn_blocking(move || {
+                let (vm_status, simulated_txn) = api
+                    .simulate_signed_transaction(&simulation_ledger_info, simulation_transaction)?;
+                api.render_simulated_transaction(&simulation_ledger_info, &vm_status, simulated_txn)
+            });
+            let simulated_transaction = tokio::time::timeout(simulation_timeout, simulation)
+                .await
+                .map_err(|_| step_timeout_error("simulating", simulation_timeout))??;
+
+            // Abort the submission if the simulation failed
+            if !simulated_transaction.info.success && abort_on_simulation_failure.0.unwrap_or(true)
+            {
+                metrics::SUBMIT_AND_WAIT_RESULTS
+                    .with_label_values(&["simulation_failed"])
+                    .inc();
+                return SubmitAndWaitTransactionResponse::try_from_json((
+                    SubmitAndWaitTransactionResult {
+                        hash: hash.into(),
+                        status: SubmitAndWaitTransactionStatus::SimulationFailed,
+                        simulated_transaction: Some(simulated_transaction),
+                        committed_transaction: None,
+                    },
+                    &ledger_info,
+                    SubmitAndWaitTransactionResponseStatus::Ok,
+                ));
+            }
+            Some(simulated_transaction)
+        } else {
+            None
+        };
+
+        // Submit the transaction to mempool
+        tokio::time::timeout(submission_timeout, self.create_internal(signed_transaction))
+            .await
+            .map_err(|_| step_timeout_error("submitting", submission_timeout))?
+            .map_err(|error| submission_error(error, &ledger_info))?;
+
+        // Wait for the transaction to commit
+        let committed_transaction =
+            match tokio::time::timeout(wait_timeout, self.wait_for_committed_transaction(hash))
+                .await
+            {
+                Ok(committed_transaction) => Some(committed_transaction?),
+                Err(_) => None, // The transaction did not commit before the timeout
+            };
+
+        // Return the result
+        let (status, response_status) = if committed_transaction.is_some() {
+            (
+                SubmitAndWaitTransactionStatus::Committed,
+                SubmitAndWaitTransactionResponseStatus::Ok,
+            )
+        } else {
+            (
+                SubmitAndWaitTransactionStatus::Pending,
+                SubmitAndWaitTransactionResponseStatus::Accepted,
+            )
+        };
+        metrics::SUBMIT_AND_WAIT_RESULTS
+            .with_label_values(&[status.as_str()])
+            .inc();
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        SubmitAndWaitTransactionResponse::try_from_json((
+            SubmitAndWaitTransactionResult {
+                hash: hash.into(),
+                status,
+                simulated_transaction,
+                committed_transaction,
+            },
+            &ledger_info,
+            response_status,
+        ))
+    }
+
+    /// Validate transaction
+    ///
+    /// Runs the same validation that mempool performs when admitting a transaction
+    /// (i.e., signature verification, gas bounds checks and the account prologue)
+    /// against the latest committed state, without submitting the transaction to
+    /// mempool. This can be used to determine if a signed transaction will be
+    /// rejected before it is broadcast. The checks are run in order, and the
+    /// result of each check is reported (or null, if the check was skipped
+    /// because an earlier check failed).
+    ///
+    /// Note: a successful validation does not guarantee that the transaction will
+    /// execute successfully, only that it is likely to be accepted by mempool.
+    ///
+    /// To use this endpoint with BCS, you must submit a SignedTransaction
+    /// encoded as BCS. See SignedTransaction in types/src/transaction/mod.rs.
+    #[oai(
+        path = "/transactions/validate",
+        method = "post",
+        operation_id = "validate_transaction",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn validate_transaction(
+        &self,
+        accept_type: AcceptType,
+        data: SubmitTransactionPost,
+    ) -> SimulateTransactionResult<TransactionValidationResult> {
+        data.verify()
+            .context("Validated transaction invalid")
+            .map_err(|err| {
+                SubmitTransactionError::bad_request_with_code_no_info(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                )
+            })?;
+        fail_point_poem("endpoint_validate_transaction")?;
+        if !self.context.node_config.api.transaction_submission_enabled {
+            return Err(api_disabled("Validate transaction"));
+        }
+        self.context
+            .check_api_output_enabled("Validate transaction", &accept_type)?;
+        self.context
+            .check_ledger_version_not_pinned("Validate transaction")?;
+
+        let api = self.clone();
+        let context = self.context.clone();
+        api_spawn_blocking(move || {
+            let ledger_info = context.get_latest_ledger_info()?;
+            let signed_transaction = api.get_signed_transaction(&ledger_info, data)?;
+            api.validate(&accept_type, ledger_info, signed_transaction)
+        })
+        .await
+    }
+
     /// Encode submission
     ///
     /// This endpoint accepts an EncodeSubmissionRequest, which internally is a
@@ -576,6 +887,8 @@ impl TransactionsApi {
         }
         self.context
             .check_api_output_enabled("Encode submission", &accept_type)?;
+        self.context
+            .check_ledger_version_not_pinned("Encode submission")?;
         let api = self.clone();
         api_spawn_blocking(move || api.get_signing_message(&accept_type, data.0)).await
     }
@@ -619,6 +932,8 @@ impl TransactionsApi {
         fail_point_poem("endpoint_encode_submission")?;
         self.context
             .check_api_output_enabled("Estimate gas price", &accept_type)?;
+        self.context
+            .check_ledger_version_not_pinned("Estimate gas price")?;
 
         let context = self.context.clone();
         api_spawn_blocking(move || {
@@ -642,6 +957,7 @@ impl TransactionsApi {
                         BasicResponseStatus::Ok,
                     ))
                 },
+                AcceptType::Protobuf => Err(protobuf_api_unsupported("Estimate gas price")),
             }
         })
         .await
@@ -651,7 +967,7 @@ impl TransactionsApi {
 impl TransactionsApi {
     /// List all transactions paging by ledger version
     fn list(&self, accept_type: &AcceptType, page: Page) -> BasicResultWith404<Vec<Transaction>> {
-        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let latest_ledger_info = self.context.get_pinned_ledger_info()?;
         let ledger_version = latest_ledger_info.version();
 
         let limit = page.limit(&latest_ledger_info)?;
@@ -686,6 +1002,7 @@ impl TransactionsApi {
             AcceptType::Bcs => {
                 BasicResponse::try_from_bcs((data, &latest_ledger_info, BasicResponseStatus::Ok))
             },
+            AcceptType::Protobuf => self.render_protobuf_transactions(&latest_ledger_info, data),
         }
     }
 
@@ -697,7 +1014,7 @@ impl TransactionsApi {
         let context = self.context.clone();
         let accept_type = accept_type.clone();
 
-        let ledger_info = api_spawn_blocking(move || context.get_latest_ledger_info()).await?;
+        let ledger_info = api_spawn_blocking(move || context.get_pinned_ledger_info()).await?;
 
         let txn_data = self
             .get_by_hash(hash.into(), &ledger_info)
@@ -723,7 +1040,7 @@ impl TransactionsApi {
         accept_type: &AcceptType,
         version: U64,
     ) -> BasicResultWith404<Transaction> {
-        let ledger_info = self.context.get_latest_ledger_info()?;
+        let ledger_info = self.context.get_pinned_ledger_info()?;
         let txn_data = self
             .get_by_version(version.0, &ledger_info)
             .context(format!("Failed to get transaction by version {}", version))
@@ -799,9 +1116,55 @@ impl TransactionsApi {
                 ledger_info,
                 BasicResponseStatus::Ok,
             )),
+            AcceptType::Protobuf => match transaction_data {
+                TransactionData::OnChain(txn) => {
+                    let transaction = self
+                        .context
+                        .render_transactions_protobuf(ledger_info, vec![txn])?
+                        .pop()
+                        .context("Failed to render the transaction as protobuf")
+                        .map_err(|err| {
+                            BasicErrorWith404::internal_with_code(
+                                err,
+                                AptosErrorCode::InternalError,
+                                ledger_info,
+                            )
+                        })?;
+                    BasicResponse::try_from_protobuf((
+                        transaction,
+                        ledger_info,
+                        BasicResponseStatus::Ok,
+                    ))
+                },
+                TransactionData::Pending(_) => Err(BasicErrorWith404::bad_request_with_code(
+                    "Pending transactions do not support protobuf output",
+                    AptosErrorCode::InvalidInput,
+                    ledger_info,
+                )),
+            },
         }
     }
 
+    /// Renders the given transactions as a protobuf transactions response
+    fn render_protobuf_transactions(
+        &self,
+        ledger_info: &LedgerInfo,
+        data: Vec<TransactionOnChainData>,
+    ) -> BasicResultWith404<Vec<Transaction>> {
+        let transactions = self
+            .context
+            .render_transactions_protobuf(ledger_info, data)?;
+        let transactions_response = TransactionsResponse {
+            transactions,
+            chain_id: Some(ledger_info.chain_id as u64),
+        };
+        BasicResponse::try_from_protobuf((
+            transactions_response,
+            ledger_info,
+            BasicResponseStatus::Ok,
+        ))
+    }
+
     /// Retrieves a transaction by ledger version
     fn get_by_version(
         &self,
@@ -864,7 +1227,7 @@ impl TransactionsApi {
             address.into(),
             page.start_option(),
             page.limit(&latest_ledger_info)?,
-            latest_ledger_info.version(),
+            account.ledger_version,
             &latest_ledger_info,
         )?;
         match accept_type {
@@ -877,6 +1240,7 @@ impl TransactionsApi {
             AcceptType::Bcs => {
                 BasicResponse::try_from_bcs((data, &latest_ledger_info, BasicResponseStatus::Ok))
             },
+            AcceptType::Protobuf => self.render_protobuf_transactions(&latest_ledger_info, data),
         }
     }
 
@@ -1155,30 +1519,80 @@ impl TransactionsApi {
                     ledger_info,
                     SubmitTransactionResponseStatus::Accepted,
                 )),
+                AcceptType::Protobuf => Err(protobuf_api_unsupported("Submit transaction")),
             },
-            Err(error) => match error.error_code {
-                AptosErrorCode::InternalError => Err(
-                    SubmitTransactionError::internal_from_aptos_error(error, ledger_info),
-                ),
-                AptosErrorCode::VmError
-                | AptosErrorCode::SequenceNumberTooOld
-                | AptosErrorCode::InvalidTransactionUpdate => Err(
-                    SubmitTransactionError::bad_request_from_aptos_error(error, ledger_info),
-                ),
-                AptosErrorCode::MempoolIsFull => Err(
-                    SubmitTransactionError::insufficient_storage_from_aptos_error(
-                        error,
-                        ledger_info,
-                    ),
-                ),
-                _ => Err(SubmitTransactionError::internal_from_aptos_error(
-                    error,
-                    ledger_info,
-                )),
-            },
+            Err(error) => Err(submission_error(error, ledger_info)),
+        }
+    }
+
+    /// Waits (indefinitely) for the transaction with the given hash to be
+    /// committed, and returns the committed transaction. Callers should
+    /// bound the wait with a timeout.
+    async fn wait_for_committed_transaction(
+        &self,
+        hash: aptos_crypto::HashValue,
+    ) -> Result<Transaction, SubmitTransactionError> {
+        let poll_interval = Duration::from_millis(
+            self.context
+                .node_config
+                .api
+                .submit_and_wait
+                .wait_poll_interval_ms,
+        );
+        loop {
+            let api = self.clone();
+            if let Some(transaction) =
+                api_spawn_blocking(move || api.get_committed_transaction(hash)).await?
+            {
+                return Ok(transaction);
+            }
+            tokio::time::sleep(poll_interval).await;
         }
     }
 
+    /// Returns the committed (JSON) transaction with the given hash (if any)
+    fn get_committed_transaction(
+        &self,
+        hash: aptos_crypto::HashValue,
+    ) -> Result<Option<Transaction>, SubmitTransactionError> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let txn = match self
+            .context
+            .get_transaction_by_hash(hash, ledger_info.version())
+            .context(format!("Failed to get transaction by hash {}", hash))
+            .map_err(|err| {
+                SubmitTransactionError::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })? {
+            Some(txn) => txn,
+            None => return Ok(None), // The transaction has not committed yet
+        };
+
+        let timestamp = self
+            .context
+            .get_block_timestamp(&ledger_info, txn.version)?;
+        let state_view = self.context.latest_state_view_poem(&ledger_info)?;
+        let transaction = state_view
+            .as_move_resolver()
+            .as_converter(
+                self.context.db.clone(),
+                self.context.table_info_reader.clone(),
+            )
+            .try_into_onchain_transaction(timestamp, txn)
+            .context("Failed to convert on chain transaction to Transaction")
+            .map_err(|err| {
+                SubmitTransactionError::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        Ok(Some(transaction))
+    }
+
     /// Submits a batch of transactions
     async fn create_batch(
         &self,
@@ -1197,6 +1611,51 @@ impl TransactionsApi {
             }
         }
 
+        Self::batch_submission_response(accept_type, ledger_info, txn_failures)
+    }
+
+    /// Submits an ordered batch of transactions. Once a transaction fails,
+    /// all subsequent transactions are rejected (and not submitted).
+    async fn create_ordered_batch(
+        &self,
+        accept_type: &AcceptType,
+        ledger_info: &LedgerInfo,
+        txns: Vec<SignedTransaction>,
+    ) -> SubmitTransactionsBatchResult<TransactionsBatchSubmissionResult> {
+        // Iterate through transactions (in order) until the first failure
+        let mut txn_failures = Vec::new();
+        let mut failed_index = None;
+        for (idx, txn) in txns.into_iter().enumerate() {
+            if let Some(failed_index) = failed_index {
+                // Reject the remaining transactions in the batch
+                txn_failures.push(TransactionsBatchSingleSubmissionFailure {
+                    error: AptosError::new_with_error_code(
+                        format!(
+                            "Transaction rejected because an earlier transaction in the ordered batch failed (at position {})",
+                            failed_index
+                        ),
+                        AptosErrorCode::InvalidInput,
+                    ),
+                    transaction_index: idx,
+                });
+            } else if let Err(error) = self.create_internal(txn).await {
+                failed_index = Some(idx);
+                txn_failures.push(TransactionsBatchSingleSubmissionFailure {
+                    error,
+                    transaction_index: idx,
+                });
+            }
+        }
+
+        Self::batch_submission_response(accept_type, ledger_info, txn_failures)
+    }
+
+    /// Creates the response for a batch submission with the given failures
+    fn batch_submission_response(
+        accept_type: &AcceptType,
+        ledger_info: &LedgerInfo,
+        txn_failures: Vec<TransactionsBatchSingleSubmissionFailure>,
+    ) -> SubmitTransactionsBatchResult<TransactionsBatchSubmissionResult> {
         // Return the possible failures, and have a different success code for partial success
         let response_status = if txn_failures.is_empty() {
             SubmitTransactionsBatchResponseStatus::Accepted
@@ -1239,7 +1698,32 @@ impl TransactionsApi {
         }
 
         // Simulate transaction
-        let state_view = self.context.latest_state_view_poem(&ledger_info)?;
+        let (vm_status, simulated_txn) = self.simulate_signed_transaction(&ledger_info, txn)?;
+        match accept_type {
+            AcceptType::Json => {
+                let user_transaction =
+                    self.render_simulated_transaction(&ledger_info, &vm_status, simulated_txn)?;
+                BasicResponse::try_from_json((
+                    vec![user_transaction],
+                    &ledger_info,
+                    BasicResponseStatus::Ok,
+                ))
+            },
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((simulated_txn, &ledger_info, BasicResponseStatus::Ok))
+            },
+            AcceptType::Protobuf => Err(protobuf_api_unsupported("Simulate transaction")),
+        }
+    }
+
+    /// Simulates the given signed transaction in the VM (without verifying the
+    /// signature), and returns the VM status and the simulated transaction.
+    fn simulate_signed_transaction(
+        &self,
+        ledger_info: &LedgerInfo,
+        txn: SignedTransaction,
+    ) -> Result<(VMStatus, TransactionOnChainData), SubmitTransactionError> {
+        let state_view = self.context.latest_state_view_poem(ledger_info)?;
         let (vm_status, output) =
             AptosSimulationVM::create_vm_and_simulate_signed_transaction(&txn, &state_view);
         let version = ledger_info.version();
@@ -1299,55 +1783,102 @@ impl TransactionsApi {
             accumulator_root_hash: zero_hash,
             changes: output.write_set().clone(),
         };
+        Ok((vm_status, simulated_txn))
+    }
 
-        match accept_type {
-            AcceptType::Json => {
-                let transactions = self
-                    .context
-                    .render_transactions_non_sequential(&ledger_info, vec![simulated_txn])?;
-
-                // Users can only make requests to simulate UserTransactions, so unpack
-                // the Vec<Transaction> into Vec<UserTransaction>.
-                let mut user_transactions = Vec::new();
-                for transaction in transactions.into_iter() {
-                    match transaction {
-                        Transaction::UserTransaction(user_txn) => {
-                            let mut txn = *user_txn;
-                            match &vm_status {
-                                VMStatus::Error {
-                                    message: Some(msg), ..
-                                }
-                                | VMStatus::ExecutionFailure {
-                                    message: Some(msg), ..
-                                } => {
-                                    txn.info.vm_status +=
-                                        format!("\nExecution failed with status: {}", msg).as_str();
-                                },
-                                _ => (),
-                            }
-                            user_transactions.push(txn);
-                        },
-                        _ => {
-                            return Err(SubmitTransactionError::internal_with_code(
-                                "Simulation transaction resulted in a non-UserTransaction",
-                                AptosErrorCode::InternalError,
-                                &ledger_info,
-                            ))
-                        },
+    /// Renders the given simulated transaction as a (JSON) user transaction
+    fn render_simulated_transaction(
+        &self,
+        ledger_info: &LedgerInfo,
+        vm_status: &VMStatus,
+        simulated_txn: TransactionOnChainData,
+    ) -> Result<UserTransaction, SubmitTransactionError> {
+        let transaction = self
+            .context
+            .render_transactions_non_sequential(ledger_info, vec![simulated_txn])?
+            .pop();
+
+        // Users can only make requests to simulate UserTransactions, so unpack
+        // the Transaction into a UserTransaction.
+        match transaction {
+            Some(Transaction::UserTransaction(user_txn)) => {
+                let mut txn = *user_txn;
+                match vm_status {
+                    VMStatus::Error {
+                        message: Some(msg), ..
                     }
+                    | VMStatus::ExecutionFailure {
+                        message: Some(msg), ..
+                    } => {
+                        txn.info.vm_status +=
+                            format!("\nExecution failed with status: {}", msg).as_str();
+                    },
+                    _ => (),
                 }
-                BasicResponse::try_from_json((
-                    user_transactions,
-                    &ledger_info,
-                    BasicResponseStatus::Ok,
-                ))
-            },
-            AcceptType::Bcs => {
-                BasicResponse::try_from_bcs((simulated_txn, &ledger_info, BasicResponseStatus::Ok))
+                Ok(txn)
             },
+            _ => Err(SubmitTransactionError::internal_with_code(
+                "Simulation transaction resulted in a non-UserTransaction",
+                AptosErrorCode::InternalError,
+                ledger_info,
+            )),
         }
     }
 
+    /// Validates a transaction in the VM (as mempool would) without submitting it
+    pub fn validate(
+        &self,
+        accept_type: &AcceptType,
+        ledger_info: LedgerInfo,
+        txn: SignedTransaction,
+    ) -> SimulateTransactionResult<TransactionValidationResult> {
+        let hash = txn.clone().committed_hash().into();
+
+        // Run the VM validation (as mempool would) against the latest state. The
+        // checks run in order, so the checks after a failed check are skipped.
+        let state_view = self.context.latest_state_view_poem(&ledger_info)?;
+        let vm = AptosVM::new(&state_view.as_move_resolver(), None);
+        let validation_result = match vm.validate_transaction_with_checks(txn, &state_view) {
+            Ok(()) => TransactionValidationResult {
+                hash,
+                valid: true,
+                signature_valid: true,
+                gas_bounds_valid: Some(true),
+                prologue_valid: Some(true),
+                vm_status: None,
+            },
+            Err((failed_check, status_code)) => {
+                let (signature_valid, gas_bounds_valid, prologue_valid) = match failed_check {
+                    TransactionValidationCheck::Signature => (false, None, None),
+                    TransactionValidationCheck::GasBounds => (true, Some(false), None),
+                    TransactionValidationCheck::Prologue => (true, Some(true), Some(false)),
+                };
+                TransactionValidationResult {
+                    hash,
+                    valid: false,
+                    signature_valid,
+                    gas_bounds_valid,
+                    prologue_valid,
+                    vm_status: Some(format!("{:?}", status_code)),
+                }
+            },
+        };
+        metrics::TRANSACTION_VALIDATIONS
+            .with_label_values(&[if validation_result.valid {
+                "valid"
+            } else {
+                "invalid"
+            }])
+            .inc();
+
+        BasicResponse::try_from_rust_value((
+            validation_result,
+            &ledger_info,
+            BasicResponseStatus::Ok,
+            accept_type,
+        ))
+    }
+
     /// Encode message as BCS
     pub fn get_signing_message(
         &self,
@@ -1431,6 +1962,92 @@ fn override_gas_parameters(
     SignedTransaction::new_with_authenticator(raw_txn, signed_txn.authenticator())
 }
 
+/// Verifies that the ordered batch is non-empty, has a single sender, and
+/// that the transactions have consecutive sequence numbers.
+fn verify_ordered_transactions_batch(
+    ledger_info: &LedgerInfo,
+    txns: &[SignedTransaction],
+) -> Result<(), SubmitTransactionError> {
+    let first_txn = txns.first().ok_or_else(|| {
+        SubmitTransactionError::bad_request_with_code(
+            "The ordered batch must contain at least one transaction",
+            AptosErrorCode::InvalidInput,
+            ledger_info,
+        )
+    })?;
+
+    for (idx, txn) in txns.iter().enumerate() {
+        if txn.sender() != first_txn.sender() {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                format!(
+                    "All transactions in the ordered batch must have the same sender! Expected: {}, found: {} (at position {})",
+                    first_txn.sender(),
+                    txn.sender(),
+                    idx
+                ),
+                AptosErrorCode::InvalidInput,
+                ledger_info,
+            ));
+        }
+
+        // The batch is not authenticated yet, so the sequence numbers may overflow
+        let expected_sequence_number = match first_txn.sequence_number().checked_add(idx as u64) {
+            Some(expected_sequence_number) => expected_sequence_number,
+            None => {
+                return Err(SubmitTransactionError::bad_request_with_code(
+                    format!(
+                        "The sequence numbers of the ordered batch overflow! First sequence number: {}, batch size: {}",
+                        first_txn.sequence_number(),
+                        txns.len()
+                    ),
+                    AptosErrorCode::InvalidInput,
+                    ledger_info,
+                ));
+            },
+        };
+        if txn.sequence_number() != expected_sequence_number {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                format!(
+                    "The transactions in the ordered batch must have consecutive sequence numbers! Expected: {}, found: {} (at position {})",
+                    expected_sequence_number,
+                    txn.sequence_number(),
+                    idx
+                ),
+                AptosErrorCode::InvalidInput,
+                ledger_info,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Converts the given mempool submission error into an API error
+fn submission_error(error: AptosError, ledger_info: &LedgerInfo) -> SubmitTransactionError {
+    match error.error_code {
+        AptosErrorCode::InternalError => {
+            SubmitTransactionError::internal_from_aptos_error(error, ledger_info)
+        },
+        AptosErrorCode::VmError
+        | AptosErrorCode::SequenceNumberTooOld
+        | AptosErrorCode::InvalidTransactionUpdate => {
+            SubmitTransactionError::bad_request_from_aptos_error(error, ledger_info)
+        },
+        AptosErrorCode::MempoolIsFull => {
+            SubmitTransactionError::insufficient_storage_from_aptos_error(error, ledger_info)
+        },
+        _ => SubmitTransactionError::internal_from_aptos_error(error, ledger_info),
+    }
+}
+
+/// Returns an error indicating that the given submit and wait step timed out
+fn step_timeout_error(step: &str, timeout: Duration) -> SubmitTransactionError {
+    SubmitTransactionError::service_unavailable_with_code_no_info(
+        format!("Timed out {} the transaction after {:?}", step, timeout),
+        AptosErrorCode::InternalError,
+    )
+}
+
 enum GetByVersionResponse {
     VersionTooNew,
     VersionTooOld,
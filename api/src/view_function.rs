@@ -7,8 +7,8 @@ use crate::{
     context::{api_spawn_blocking, FunctionStats},
     failpoint::fail_point_poem,
     response::{
-        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
-        ForbiddenError, InternalError,
+        protobuf_api_unsupported, BadRequestError, BasicErrorWith404, BasicResponse,
+        BasicResponseStatus, BasicResultWith404, ForbiddenError, InternalError,
     },
     ApiTags, Context,
 };
@@ -66,6 +66,7 @@ impl ViewFunctionApi {
         fail_point_poem("endpoint_view_function")?;
         self.context
             .check_api_output_enabled("View function", &accept_type)?;
+        self.context.check_disk_space_guardrails("View function")?;
 
         let context = self.context.clone();
         api_spawn_blocking(move || view_request(context, accept_type, request, ledger_version))
@@ -205,6 +206,7 @@ fn view_request(
 
             BasicResponse::try_from_json((move_vals, &ledger_info, BasicResponseStatus::Ok))
         },
+        AcceptType::Protobuf => Err(protobuf_api_unsupported("View function")),
     };
     context.view_function_stats().increment(
         FunctionStats::function_to_key(&view_function.module, &view_function.function),
@@ -52,7 +52,10 @@ use hyper::{HeaderMap, Response};
 use rand::SeedableRng;
 use serde_json::{json, Value};
 use std::{boxed::Box, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
-use warp::{http::header::CONTENT_TYPE, Filter, Rejection, Reply};
+use warp::{
+    http::header::{ACCEPT, CONTENT_TYPE},
+    Filter, Rejection, Reply,
+};
 use warp_reverse_proxy::reverse_proxy_filter;
 
 const TRANSFER_AMOUNT: u64 = 200_000_000;
@@ -908,6 +911,38 @@ impl TestContext {
         .await
     }
 
+    /// Sends a GET request that expects a BCS response and returns the response body
+    pub async fn get_bcs(&self, path: &str) -> Bytes {
+        self.execute_bcs(
+            warp::test::request()
+                .method("GET")
+                .path(&self.prepend_path(path)),
+        )
+        .await
+    }
+
+    /// Sends a POST request that expects a BCS response and returns the response body
+    pub async fn post_bcs(&self, path: &str, body: Value) -> Bytes {
+        self.execute_bcs(
+            warp::test::request()
+                .method("POST")
+                .path(&self.prepend_path(path))
+                .json(&body),
+        )
+        .await
+    }
+
+    pub async fn execute_bcs(&self, req: warp::test::RequestBuilder) -> Bytes {
+        let resp = self.reply(req.header(ACCEPT, mime_types::BCS)).await;
+        assert_eq!(
+            self.expect_status_code,
+            resp.status(),
+            "\nresponse: {:?}",
+            resp.body()
+        );
+        resp.into_body()
+    }
+
     pub async fn reply(&self, req: warp::test::RequestBuilder) -> Response<Bytes> {
         match self.api_specific_config {
             ApiSpecificConfig::V1(address) => req.reply(&self.get_routes_with_poem(address)).await,
@@ -5,7 +5,7 @@
 use crate::{
     transaction::{
         DecodedTableData, DeleteModule, DeleteResource, DeleteTableItem, DeletedTableData,
-        MultisigPayload, MultisigTransactionPayload, StateCheckpointTransaction,
+        MoveAbortDetails, MultisigPayload, MultisigTransactionPayload, StateCheckpointTransaction,
         UserTransactionRequestInner, WriteModule, WriteResource, WriteTableItem,
     },
     view::{ViewFunction, ViewRequest},
@@ -18,7 +18,8 @@ use crate::{
 use anyhow::{bail, ensure, format_err, Context as AnyhowContext, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_db_indexer::table_info_reader::TableInfoReader;
-use aptos_storage_interface::DbReader;
+use aptos_framework::get_metadata_from_compiled_module;
+use aptos_storage_interface::{state_view::DbStateViewAtVersion, DbReader};
 use aptos_types::{
     access_path::{AccessPath, Path},
     chain_id::ChainId,
@@ -26,14 +27,16 @@ use aptos_types::{
     state_store::{
         state_key::{StateKey, StateKeyInner},
         table::{TableHandle, TableInfo},
+        TStateView,
     },
     transaction::{
-        EntryFunction, ExecutionStatus, Multisig, RawTransaction, Script, SignedTransaction,
+        AbortInfo, EntryFunction, ExecutionStatus, Multisig, RawTransaction, Script,
+        SignedTransaction,
     },
     vm_status::AbortLocation,
     write_set::WriteOp,
 };
-use move_binary_format::file_format::FunctionHandleIndex;
+use move_binary_format::{file_format::FunctionHandleIndex, CompiledModule};
 use move_core_types::{
     account_address::AccountAddress,
     ident_str,
@@ -184,11 +187,59 @@ impl<'a, R: ModuleResolver + ?Sized> MoveConverter<'a, R> {
                 .filter_map(|(sk, wo)| self.try_into_write_set_changes(sk, wo).ok())
                 .flatten()
                 .collect(),
+            abort_details: self.try_into_move_abort_details(version, info.status()),
             block_height: None,
             epoch: None,
         }
     }
 
+    /// Returns the details of the abort if the given status is a Move abort in
+    /// a module. If the VM didn't attach the abort info to the status, it is
+    /// resolved using the error map of the module at the given version.
+    pub fn try_into_move_abort_details(
+        &self,
+        version: u64,
+        status: &ExecutionStatus,
+    ) -> Option<MoveAbortDetails> {
+        let ExecutionStatus::MoveAbort {
+            location: AbortLocation::Module(module_id),
+            code,
+            info,
+        } = status
+        else {
+            return None;
+        };
+
+        let abort_info = match info {
+            Some(abort_info) => abort_info.clone(),
+            None => self.resolve_abort_info(version, module_id, *code)?,
+        };
+        Some(MoveAbortDetails {
+            module: module_id.clone().into(),
+            code: (*code).into(),
+            reason_name: abort_info.reason_name,
+            description: abort_info.description,
+        })
+    }
+
+    /// Resolves the abort info of the given code using the error map in the
+    /// metadata of the module at the given version (if it's still available).
+    fn resolve_abort_info(
+        &self,
+        version: u64,
+        module_id: &ModuleId,
+        code: u64,
+    ) -> Option<AbortInfo> {
+        let state_view = self.db.state_view_at_version(Some(version)).ok()?;
+        let state_key = StateKey::access_path(AccessPath::code_access_path(module_id.clone()));
+        let module_bytes = state_view
+            .get_state_value_bytes(&state_key)
+            .ok()
+            .flatten()?;
+        let module = CompiledModule::deserialize(&module_bytes).ok()?;
+        get_metadata_from_compiled_module(&module)?.extract_abort_info(code)
+    }
+
     pub fn try_into_transaction_payload(
         &self,
         payload: aptos_types::transaction::TransactionPayload,
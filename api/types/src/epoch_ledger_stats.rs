@@ -0,0 +1,48 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::U64;
+use poem_openapi::Object;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Aggregate statistics for the transactions committed in an epoch
+///
+/// The statistics are computed by the node when the transactions are committed,
+/// so they only cover the versions from `first_version` to `last_version` (e.g.,
+/// if the node was bootstrapped from a snapshot, earlier versions are not covered).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize, Object)]
+pub struct EpochLedgerStats {
+    pub epoch: U64,
+    /// The first ledger version covered by the statistics
+    pub first_version: U64,
+    /// The last ledger version covered by the statistics
+    pub last_version: U64,
+    pub num_transactions: U64,
+    /// The number of transactions by type (e.g., `user_transaction`)
+    pub num_transactions_by_type: BTreeMap<String, U64>,
+    /// The number of transactions that did not execute successfully
+    pub num_failed_transactions: U64,
+    pub total_gas_used: U64,
+    /// The number of unique senders of user transactions
+    pub num_unique_senders: U64,
+}
+
+impl From<aptos_storage_interface::epoch_ledger_stats::EpochLedgerStats> for EpochLedgerStats {
+    fn from(stats: aptos_storage_interface::epoch_ledger_stats::EpochLedgerStats) -> Self {
+        Self {
+            epoch: stats.epoch.into(),
+            first_version: stats.first_version.into(),
+            last_version: stats.last_version.into(),
+            num_transactions: stats.num_transactions.into(),
+            num_transactions_by_type: stats
+                .num_transactions_by_type
+                .into_iter()
+                .map(|(transaction_type, count)| (transaction_type, count.into()))
+                .collect(),
+            num_failed_transactions: stats.num_failed_transactions.into(),
+            total_gas_used: stats.total_gas_used.into(),
+            num_unique_senders: stats.num_unique_senders.into(),
+        }
+    }
+}
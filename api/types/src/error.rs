@@ -110,6 +110,8 @@ pub enum AptosErrorCode {
     BcsNotSupported = 602,
     /// API Disabled
     ApiDisabled = 603,
+    /// Protobuf format is not supported on this API.
+    ProtobufNotSupported = 604,
 }
 
 impl AptosErrorCode {
@@ -19,3 +19,5 @@ pub const X_APTOS_LEDGER_TIMESTAMP: &str = "X-Aptos-Ledger-TimestampUsec";
 pub const X_APTOS_CURSOR: &str = "X-Aptos-Cursor";
 /// Provided by the client to identify what client it is.
 pub const X_APTOS_CLIENT: &str = "x-aptos-client";
+/// Provided by the client to pin the request to a specific ledger version.
+pub const X_APTOS_PIN_LEDGER_VERSION: &str = "x-aptos-pin-ledger-version";
@@ -10,11 +10,13 @@ mod block;
 mod bytecode;
 mod convert;
 mod derives;
+mod epoch_ledger_stats;
 mod error;
 mod hash;
 mod headers;
 mod index;
 mod ledger_info;
+mod light_client;
 pub mod mime_types;
 mod move_types;
 mod state;
@@ -28,11 +30,13 @@ pub use address::Address;
 pub use block::{BcsBlock, Block};
 pub use bytecode::Bytecode;
 pub use convert::{new_vm_utf8_string, AsConverter, ExplainVMStatus, MoveConverter};
+pub use epoch_ledger_stats::EpochLedgerStats;
 pub use error::{AptosError, AptosErrorCode};
 pub use hash::HashValue;
 pub use headers::*;
 pub use index::{IndexResponse, IndexResponseBcs};
 pub use ledger_info::LedgerInfo;
+pub use light_client::{LightClientStateValueProof, LightClientTransactionProof};
 pub use move_types::{
     verify_field_identifier, verify_function_identifier, verify_module_identifier, EntryFunctionId,
     HexEncodedBytes, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
@@ -48,14 +52,15 @@ pub use transaction::{
     AccountSignature, BlockMetadataTransaction, DeleteModule, DeleteResource, DeleteTableItem,
     DirectWriteSet, Ed25519Signature, EncodeSubmissionRequest, EntryFunctionPayload, Event,
     FeePayerSignature, GasEstimation, GasEstimationBcs, GenesisPayload, GenesisTransaction,
-    MultiAgentSignature, MultiEd25519Signature, MultiKeySignature, MultisigPayload,
-    MultisigTransactionPayload, PendingTransaction, PublicKey, ScriptPayload, ScriptWriteSet,
-    Signature, SingleKeySignature, SubmitTransactionRequest, Transaction, TransactionData,
+    MoveAbortDetails, MultiAgentSignature, MultiEd25519Signature, MultiKeySignature,
+    MultisigPayload, MultisigTransactionPayload, PendingTransaction, PublicKey, ScriptPayload,
+    ScriptWriteSet, Signature, SingleKeySignature, SubmitAndWaitTransactionResult,
+    SubmitAndWaitTransactionStatus, SubmitTransactionRequest, Transaction, TransactionData,
     TransactionId, TransactionInfo, TransactionOnChainData, TransactionPayload,
-    TransactionSignature, TransactionSigningMessage, TransactionsBatchSingleSubmissionFailure,
-    TransactionsBatchSubmissionResult, UserCreateSigningMessageRequest, UserTransaction,
-    UserTransactionRequest, VersionedEvent, WriteModule, WriteResource, WriteSet, WriteSetChange,
-    WriteSetPayload, WriteTableItem,
+    TransactionSignature, TransactionSigningMessage, TransactionValidationResult,
+    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult,
+    UserCreateSigningMessageRequest, UserTransaction, UserTransactionRequest, VersionedEvent,
+    WriteModule, WriteResource, WriteSet, WriteSetChange, WriteSetPayload, WriteTableItem,
 };
 pub use view::{ViewFunction, ViewRequest};
 pub use wrappers::{EventGuid, IdentifierWrapper, StateKeyWrapper};
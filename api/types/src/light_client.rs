@@ -0,0 +1,79 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Compact proof artifacts served to light clients (e.g., embedded or wasm
+//! clients). These are only served as BCS, and each artifact carries the
+//! ledger info it was proven against. Note: the signatures of the ledger
+//! info must be verified separately by the client (e.g., using the
+//! validator set obtained from an epoch change proof).
+
+use anyhow::{ensure, format_err};
+use aptos_crypto::hash::CryptoHash;
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{SparseMerkleProof, TransactionInfoWithProof},
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{TransactionWithProof, Version},
+};
+use serde::{Deserialize, Serialize};
+
+/// A proof that a transaction was committed at a given version
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct LightClientTransactionProof {
+    pub ledger_info_with_signatures: LedgerInfoWithSignatures,
+    pub transaction_with_proof: TransactionWithProof,
+}
+
+impl LightClientTransactionProof {
+    /// Verifies that the transaction was committed at the given version
+    /// in the ledger represented by the ledger info.
+    pub fn verify(&self, version: Version) -> anyhow::Result<()> {
+        ensure!(
+            self.transaction_with_proof.version == version,
+            "The transaction version ({}) does not match the expected version ({})!",
+            self.transaction_with_proof.version,
+            version
+        );
+        self.transaction_with_proof
+            .verify(self.ledger_info_with_signatures.ledger_info())
+    }
+}
+
+/// A proof of the value (or absence) of a state key at a state checkpoint
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+pub struct LightClientStateValueProof {
+    pub ledger_info_with_signatures: LedgerInfoWithSignatures,
+    pub state_checkpoint_version: Version,
+    pub state_checkpoint_info_with_proof: TransactionInfoWithProof,
+    pub state_value: Option<StateValue>,
+    pub state_value_proof: SparseMerkleProof,
+}
+
+impl LightClientStateValueProof {
+    /// Verifies the state value (or absence) of the given state key
+    /// in the ledger represented by the ledger info.
+    pub fn verify(&self, state_key: &StateKey) -> anyhow::Result<()> {
+        // Verify the transaction info of the state checkpoint
+        self.state_checkpoint_info_with_proof.verify(
+            self.ledger_info_with_signatures.ledger_info(),
+            self.state_checkpoint_version,
+        )?;
+
+        // Verify the state value against the state checkpoint root hash
+        let state_root_hash = self
+            .state_checkpoint_info_with_proof
+            .transaction_info()
+            .state_checkpoint_hash()
+            .ok_or_else(|| {
+                format_err!(
+                    "No state checkpoint hash found at version: {}",
+                    self.state_checkpoint_version
+                )
+            })?;
+        self.state_value_proof.verify(
+            state_root_hash,
+            CryptoHash::hash(state_key),
+            self.state_value.as_ref(),
+        )
+    }
+}
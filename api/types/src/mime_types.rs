@@ -12,3 +12,6 @@ pub const JSON: &str = "application/json";
 
 /// MIME type to get BCS output
 pub const BCS: &str = "application/x-bcs";
+
+/// MIME type to get protobuf output (i.e., the indexer transaction protos)
+pub const PROTOBUF: &str = "application/x-protobuf";
@@ -31,7 +31,7 @@ use aptos_types::{
     },
 };
 use once_cell::sync::Lazy;
-use poem_openapi::{Object, Union};
+use poem_openapi::{Enum, Object, Union};
 use serde::{Deserialize, Serialize};
 use std::{
     boxed::Box,
@@ -373,6 +373,9 @@ pub struct TransactionInfo {
     pub accumulator_root_hash: HashValue,
     /// Final state of resources changed by the transaction
     pub changes: Vec<WriteSetChange>,
+    /// The reason the transaction aborted, if it aborted in a module that has an error map
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abort_details: Option<MoveAbortDetails>,
     /// Block height that the transaction belongs in, this field will not be present through the API
     #[oai(skip)]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -383,6 +386,19 @@ pub struct TransactionInfo {
     pub epoch: Option<U64>,
 }
 
+/// The reason a transaction aborted, resolved using the error map of the aborting module
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MoveAbortDetails {
+    /// The module that aborted
+    pub module: MoveModuleId,
+    /// The raw abort code
+    pub code: U64,
+    /// The name of the error constant (e.g., `EINSUFFICIENT_BALANCE`)
+    pub reason_name: String,
+    /// The description of the error (i.e., the doc comment of the error constant)
+    pub description: String,
+}
+
 /// A transaction waiting in mempool
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct PendingTransaction {
@@ -459,6 +475,71 @@ pub struct TransactionsBatchSingleSubmissionFailure {
     pub transaction_index: usize,
 }
 
+/// The outcome of a submit and wait transaction request
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum SubmitAndWaitTransactionStatus {
+    /// The transaction was submitted and committed
+    Committed,
+    /// The transaction was submitted, but did not commit before the wait timed out
+    Pending,
+    /// The simulation of the transaction failed, so it was not submitted
+    SimulationFailed,
+}
+
+impl SubmitAndWaitTransactionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmitAndWaitTransactionStatus::Committed => "committed",
+            SubmitAndWaitTransactionStatus::Pending => "pending",
+            SubmitAndWaitTransactionStatus::SimulationFailed => "simulation_failed",
+        }
+    }
+}
+
+/// The result of a submit and wait transaction request
+///
+/// The transaction is (optionally) simulated, submitted and then
+/// waited for until it commits (or the wait times out).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct SubmitAndWaitTransactionResult {
+    /// Hash of the transaction
+    pub hash: HashValue,
+    /// The outcome of the request
+    pub status: SubmitAndWaitTransactionStatus,
+    /// The simulated transaction (if the transaction was simulated)
+    pub simulated_transaction: Option<UserTransaction>,
+    /// The committed transaction (if the transaction committed before the wait timed out)
+    pub committed_transaction: Option<Transaction>,
+}
+
+/// The outcome of validating a signed transaction without submitting it
+///
+/// This runs the same checks that mempool runs on admission (signature
+/// verification, gas bounds and the account prologue) against the latest
+/// committed state. A valid result means the transaction will likely be
+/// accepted by mempool, but execution may still fail.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TransactionValidationResult {
+    /// Hash of the validated transaction
+    pub hash: HashValue,
+    /// True iff the transaction passed all validation checks
+    pub valid: bool,
+    /// True iff the transaction signature(s) and authenticator(s) verified successfully
+    pub signature_valid: bool,
+    /// True iff the max gas amount, gas unit price and transaction size
+    /// are within the bounds of the on-chain gas schedule. This is None if
+    /// the check was skipped (i.e., because the signature check failed).
+    pub gas_bounds_valid: Option<bool>,
+    /// True iff the account prologue (e.g., sequence number, balance
+    /// and expiration checks) passed. This is None if the check was
+    /// skipped (i.e., because an earlier check failed).
+    pub prologue_valid: Option<bool>,
+    /// The VM status code that rejected the transaction (if any)
+    pub vm_status: Option<String>,
+}
+
 // TODO: Rename this to remove the Inner when we cut over.
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
 pub struct UserTransactionRequestInner {
@@ -2,7 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{execute_past_transactions, execute_pending_block};
-use anyhow::Result;
+use anyhow::{format_err, Result};
+use aptos_types::{chain_id::ChainId, on_chain_config::FeatureFlag};
+use aptos_vm::feature_overrides::{set_execution_feature_overrides, ExecutionFeatureOverrides};
 use clap::Parser;
 use std::path::PathBuf;
 
@@ -29,6 +31,52 @@ pub struct Opts {
 
     #[clap(long, default_value_t = 1)]
     pub(crate) concurrency_level: usize,
+
+    #[clap(flatten)]
+    pub(crate) feature_overrides: FeatureOverrideOpts,
+}
+
+/// Local feature flag overrides used when executing transactions (e.g., to
+/// test upcoming feature behavior against existing state). Not supported on mainnet.
+#[derive(Parser)]
+pub struct FeatureOverrideOpts {
+    /// The chain ID of the target (required to apply any feature overrides).
+    #[clap(long)]
+    pub(crate) override_chain_id: Option<ChainId>,
+
+    /// Feature flags (by ID) to enable, regardless of the on-chain config.
+    #[clap(long, num_args = 0..)]
+    pub(crate) enable_features: Vec<usize>,
+
+    /// Feature flags (by ID) to disable, regardless of the on-chain config.
+    #[clap(long, num_args = 0..)]
+    pub(crate) disable_features: Vec<usize>,
+}
+
+impl FeatureOverrideOpts {
+    /// Registers the feature overrides with the VM (if any are specified)
+    pub(crate) fn register_overrides(&self) -> Result<()> {
+        if self.enable_features.is_empty() && self.disable_features.is_empty() {
+            return Ok(());
+        }
+        let chain_id = self.override_chain_id.ok_or_else(|| {
+            format_err!("The chain ID must be specified to override feature flags!")
+        })?;
+
+        let mut overrides = ExecutionFeatureOverrides::new();
+        for feature in &self.enable_features {
+            overrides = overrides.enable_feature(parse_feature_flag(*feature)?);
+        }
+        for feature in &self.disable_features {
+            overrides = overrides.disable_feature(parse_feature_flag(*feature)?);
+        }
+        set_execution_feature_overrides(chain_id, overrides)
+    }
+}
+
+/// Parses the feature flag with the given ID
+fn parse_feature_flag(feature: usize) -> Result<FeatureFlag> {
+    FeatureFlag::from_repr(feature).ok_or_else(|| format_err!("Unknown feature flag: {}", feature))
 }
 
 #[derive(Parser)]
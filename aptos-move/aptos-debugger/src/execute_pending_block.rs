@@ -40,6 +40,7 @@ pub struct Command {
 impl Command {
     pub async fn run(self) -> Result<()> {
         AptosVM::set_concurrency_level_once(self.opts.concurrency_level);
+        self.opts.feature_overrides.register_overrides()?;
 
         let debugger = if let Some(rest_endpoint) = self.opts.target.rest_endpoint {
             AptosDebugger::rest_client(Client::new(Url::parse(&rest_endpoint)?))?
@@ -504,6 +504,20 @@ where
             .map_err(|e| e.finish(Location::Undefined))
     }
 
+    fn charge_io_gas_for_events(&mut self, events_size: NumBytes) -> VMResult<()> {
+        if self.feature_version() < 15 {
+            return Ok(());
+        }
+
+        let excess = events_size
+            .checked_sub(self.vm_gas_params().txn.storage_io_free_event_bytes_quota)
+            .unwrap_or_else(|| 0.into());
+
+        self.algebra
+            .charge_io(STORAGE_IO_PER_EVENT_BYTE_WRITE * excess)
+            .map_err(|e| e.finish(Location::Undefined))
+    }
+
     fn charge_intrinsic_gas_for_transaction(&mut self, txn_size: NumBytes) -> VMResult<()> {
         let excess = txn_size
             .checked_sub(self.vm_gas_params().txn.large_transaction_cutoff)
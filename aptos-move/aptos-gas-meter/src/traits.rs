@@ -114,6 +114,12 @@ pub trait AptosGasMeter: MoveGasMeter {
     /// storage costs.
     fn charge_io_gas_for_write(&mut self, key: &StateKey, op: &WriteOpSize) -> VMResult<()>;
 
+    /// Charges IO gas for the events emitted by the transaction, given their total size.
+    ///
+    /// Similar to writes, this covers the cost of writing the events down to storage. Only the
+    /// bytes above a free quota are charged, and only since gas feature version 15.
+    fn charge_io_gas_for_events(&mut self, events_size: NumBytes) -> VMResult<()>;
+
     /// Charges the storage fees for writes, events & txn storage in a lump sum, minimizing the
     /// loss of precision. Refundable portion of the charge is recorded on the WriteOp itself,
     /// due to which mutable references are required on the parameter list wherever proper.
@@ -118,6 +118,14 @@ impl ExecutionAndIOCosts {
             insert_or_add(&mut storage_writes, key, write.cost);
         }
 
+        if !self.events_io_cost.is_zero() {
+            insert_or_add(
+                &mut storage_writes,
+                "events".to_string(),
+                self.events_io_cost,
+            );
+        }
+
         AggregatedExecutionGasEvents {
             gas_scaling_factor: self.gas_scaling_factor,
             total: self.total,
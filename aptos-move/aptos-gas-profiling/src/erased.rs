@@ -208,6 +208,8 @@ impl ExecutionAndIOCosts {
         );
         nodes.push(writes);
 
+        nodes.push(Node::new("events", self.events_io_cost));
+
         TypeErasedExecutionAndIoCosts {
             gas_scaling_factor: self.gas_scaling_factor,
             total: self.total,
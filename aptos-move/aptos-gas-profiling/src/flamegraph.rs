@@ -163,6 +163,8 @@ impl ExecutionAndIOCosts {
             )
         }
 
+        lines.push("events", self.events_io_cost);
+
         lines.into_inner()
     }
 
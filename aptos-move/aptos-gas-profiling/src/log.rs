@@ -102,6 +102,7 @@ pub struct ExecutionAndIOCosts {
     pub intrinsic_cost: InternalGas,
     pub call_graph: CallFrame,
     pub write_set_transient: Vec<WriteTransient>,
+    pub events_io_cost: InternalGas,
 }
 
 #[derive(Debug)]
@@ -233,6 +234,8 @@ impl ExecutionAndIOCosts {
             total += write.cost;
         }
 
+        total += self.events_io_cost;
+
         if total != self.total {
             panic!(
                 "Execution & IO costs do not add up. Check if the gas meter & the gas profiler have been implemented correctly. From gas meter: {}. Calculated: {}.",
@@ -34,6 +34,7 @@ pub struct GasProfiler<G> {
     intrinsic_cost: Option<InternalGas>,
     frames: Vec<CallFrame>,
     write_set_transient: Vec<WriteTransient>,
+    events_io_cost: Option<InternalGas>,
     storage_fees: Option<StorageFees>,
 }
 
@@ -87,6 +88,7 @@ impl<G> GasProfiler<G> {
             intrinsic_cost: None,
             frames: vec![CallFrame::new_script()],
             write_set_transient: vec![],
+            events_io_cost: None,
             storage_fees: None,
         }
     }
@@ -103,6 +105,7 @@ impl<G> GasProfiler<G> {
             intrinsic_cost: None,
             frames: vec![CallFrame::new_function(module_id, func_name, ty_args)],
             write_set_transient: vec![],
+            events_io_cost: None,
             storage_fees: None,
         }
     }
@@ -511,6 +514,14 @@ where
         res
     }
 
+    fn charge_io_gas_for_events(&mut self, events_size: NumBytes) -> VMResult<()> {
+        let (cost, res) = self.delegate_charge(|base| base.charge_io_gas_for_events(events_size));
+
+        self.events_io_cost = Some(cost);
+
+        res
+    }
+
     fn process_storage_fee_for_all(
         &mut self,
         change_set: &mut VMChangeSet,
@@ -617,6 +628,7 @@ where
             intrinsic_cost: self.intrinsic_cost.unwrap_or_else(|| 0.into()),
             call_graph: self.frames.pop().expect("frame must exist"),
             write_set_transient: self.write_set_transient,
+            events_io_cost: self.events_io_cost.unwrap_or_else(|| 0.into()),
         };
         exec_io.assert_consistency();
 
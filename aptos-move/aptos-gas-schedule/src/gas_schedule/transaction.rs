@@ -117,6 +117,25 @@ crate::gas_schedule::macros::define_gas_parameters!(
             { 0..=9 => "write_data.per_byte_in_val" },
             10_000
         ],
+        // Gas parameters for writing events to storage.
+        //
+        // Event bytes go down the same (RocksDB) write path as state value bytes, so they are
+        // priced the same as `storage_io_per_state_byte_write`. There is no per event charge:
+        // events don't touch the JMT, and the fixed cost of writing the transaction output
+        // (including the event accumulator) is already covered by the intrinsic gas.
+        //
+        // Only the event bytes above the free quota are charged, so that regular transactions
+        // (and their gas costs) are not affected; this targets transactions with large outputs.
+        [
+            storage_io_per_event_byte_write: InternalGasPerByte,
+            { 15.. => "storage_io_per_event_byte_write" },
+            89,
+        ],
+        [
+            storage_io_free_event_bytes_quota: NumBytes,
+            { 15.. => "storage_io_free_event_bytes_quota" },
+            1024, // 1KB free event bytes per transaction
+        ],
         [memory_quota: AbstractValueSize, { 1.. => "memory_quota" }, 10_000_000],
         [
             legacy_free_write_bytes_quota: NumBytes,
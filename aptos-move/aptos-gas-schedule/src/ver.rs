@@ -8,6 +8,8 @@
 ///   - Changing how gas is calculated in any way
 ///
 /// Change log:
+/// - V15
+///   - IO gas for events: the event bytes of a transaction above a free quota are charged per byte written
 /// - V14
 ///   - Storage Fee: Make state bytes refundable and remove the per slot free quota, gated by flag REFUNDABLE_BYTES
 /// - V13
@@ -49,4 +51,4 @@
 ///       global operations.
 /// - V1
 ///   - TBA
-pub const LATEST_GAS_FEATURE_VERSION: u64 = 14;
+pub const LATEST_GAS_FEATURE_VERSION: u64 = 15;
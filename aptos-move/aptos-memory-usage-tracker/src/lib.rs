@@ -472,6 +472,8 @@ where
 
         fn charge_io_gas_for_write(&mut self, key: &StateKey, op: &WriteOpSize) -> VMResult<()>;
 
+        fn charge_io_gas_for_events(&mut self, events_size: NumBytes) -> VMResult<()>;
+
         fn charge_storage_fee(
             &mut self,
             amount: Fee,
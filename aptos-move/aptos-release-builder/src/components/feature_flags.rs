@@ -3,7 +3,9 @@
 
 use crate::utils::*;
 use anyhow::Result;
-use aptos_types::on_chain_config::{FeatureFlag as AptosFeatureFlag, Features as AptosFeatures};
+use aptos_types::on_chain_config::{
+    FeatureFlag as AptosFeatureFlag, FeatureFlagPreset, Features as AptosFeatures,
+};
 use move_model::{code_writer::CodeWriter, emit, emitln, model::Loc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashSet;
@@ -382,3 +384,11 @@ impl From<&AptosFeatures> for Features {
         Features { enabled, disabled }
     }
 }
+
+impl From<&FeatureFlagPreset> for Features {
+    /// Converts the preset into an explicit set of features. All flags not
+    /// included in the preset are disabled, so the preset is applied atomically.
+    fn from(preset: &FeatureFlagPreset) -> Features {
+        Features::from(&preset.to_features())
+    }
+}
@@ -10,8 +10,9 @@ use aptos_temppath::TempPath;
 use aptos_types::{
     account_config::CORE_CODE_ADDRESS,
     on_chain_config::{
-        ExecutionConfigV1, FeatureFlag as AptosFeatureFlag, GasScheduleV2, OnChainConfig,
-        OnChainConsensusConfig, OnChainExecutionConfig, TransactionShufflerType, Version,
+        ExecutionConfigV1, FeatureFlag as AptosFeatureFlag, FeatureFlagPresetConfig, GasScheduleV2,
+        OnChainConfig, OnChainConsensusConfig, OnChainExecutionConfig, TransactionShufflerType,
+        Version,
     },
 };
 use futures::executor::block_on;
@@ -49,7 +50,7 @@ pub struct Proposal {
 }
 
 impl Proposal {
-    fn consolidated_side_effects(&self) -> Vec<ReleaseEntry> {
+    fn consolidated_side_effects(&self) -> Result<Vec<ReleaseEntry>> {
         let mut ret = vec![];
         let mut features_diff = Features::empty();
         for entry in &self.update_sequence {
@@ -57,6 +58,9 @@ impl Proposal {
                 ReleaseEntry::FeatureFlag(feature_flags) => {
                     features_diff.squash(feature_flags.clone())
                 },
+                ReleaseEntry::FeatureFlagPreset(preset_config) => {
+                    features_diff.squash(Features::from(&preset_config.load()?))
+                },
                 ReleaseEntry::Framework(_)
                 | ReleaseEntry::CustomGas(_)
                 | ReleaseEntry::DefaultGas
@@ -72,7 +76,7 @@ impl Proposal {
             ret.push(ReleaseEntry::FeatureFlag(features_diff));
         }
 
-        ret
+        Ok(ret)
     }
 }
 
@@ -110,6 +114,9 @@ pub enum ReleaseEntry {
     DefaultGasWithOverride(Vec<GasOverride>),
     Version(Version),
     FeatureFlag(Features),
+    /// A named set of feature flags (built-in, or loaded from a file). All flags outside of
+    /// the preset are disabled.
+    FeatureFlagPreset(FeatureFlagPresetConfig),
     Consensus(OnChainConsensusConfig),
     Execution(OnChainExecutionConfig),
     RawScript(PathBuf),
@@ -223,6 +230,10 @@ impl ReleaseEntry {
                     )?);
                 }
             },
+            ReleaseEntry::FeatureFlagPreset(preset_config) => {
+                ReleaseEntry::FeatureFlag(Features::from(&preset_config.load()?))
+                    .generate_release_script(client, result, execution_mode)?;
+            },
             ReleaseEntry::Consensus(consensus_config) => {
                 if !fetch_and_equals(client, consensus_config)? {
                     result.append(&mut consensus_config::generate_consensus_upgrade_proposal(
@@ -354,6 +365,10 @@ impl ReleaseEntry {
                     }
                 }
             },
+            ReleaseEntry::FeatureFlagPreset(preset_config) => {
+                ReleaseEntry::FeatureFlag(Features::from(&preset_config.load()?))
+                    .validate_upgrade(client)?;
+            },
             ReleaseEntry::Consensus(consensus_config) => {
                 if !fetch_and_equals(client_opt, consensus_config)? {
                     bail!("Consensus config mismatch: Expected {:?}", consensus_config);
@@ -561,7 +576,7 @@ impl ReleaseConfig {
     // Fetch all configs from a remote rest endpoint and assert all the configs are the same as the ones specified locally.
     pub fn validate_upgrade(&self, endpoint: &Url, proposal: &Proposal) -> Result<()> {
         let client = Client::new(endpoint.clone());
-        for entry in proposal.consolidated_side_effects() {
+        for entry in proposal.consolidated_side_effects()? {
             entry.validate_upgrade(&client)?;
         }
         Ok(())
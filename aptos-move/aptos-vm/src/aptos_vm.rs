@@ -7,6 +7,7 @@ use crate::{
     counters::*,
     data_cache::{AsMoveResolver, StorageAdapter},
     errors::{discarded_output, expect_only_successful_execution},
+    feature_overrides::get_execution_feature_overrides,
     gas::{check_gas, get_gas_parameters},
     keyless_validation,
     move_vm_ext::{
@@ -25,10 +26,10 @@ use aptos_framework::{
     natives::{code::PublishRequest, transaction_context::NativeTransactionContext},
     RuntimeModuleMetadataV1,
 };
-use aptos_gas_algebra::{Gas, GasQuantity, Octa};
+use aptos_gas_algebra::{Gas, GasQuantity, NumBytes, Octa};
 use aptos_gas_meter::{AptosGasMeter, GasAlgebra, StandardGasAlgebra, StandardGasMeter};
 use aptos_gas_schedule::{AptosGasParameters, VMGasParameters};
-use aptos_logger::{enabled, prelude::*, Level};
+use aptos_logger::{debug_toggles, enabled, prelude::*, Level};
 use aptos_memory_usage_tracker::MemoryTrackedGasMeter;
 use aptos_metrics_core::TimerHelper;
 #[cfg(any(test, feature = "testing"))]
@@ -61,7 +62,9 @@ use aptos_types::{
     vm_status::{AbortLocation, StatusCode, VMStatus},
 };
 use aptos_utils::{aptos_try, return_on_failure};
-use aptos_vm_logging::{log_schema::AdapterLogSchema, speculative_error, speculative_log};
+use aptos_vm_logging::{
+    log_schema::AdapterLogSchema, speculative_error, speculative_info, speculative_log,
+};
 use aptos_vm_types::{
     abstract_write_op::AbstractResourceWriteOp,
     change_set::VMChangeSet,
@@ -97,6 +100,7 @@ use std::{
     collections::{BTreeMap, BTreeSet},
     marker::Sync,
     sync::Arc,
+    time::Instant,
 };
 
 static EXECUTION_CONCURRENCY_LEVEL: OnceCell<usize> = OnceCell::new();
@@ -181,6 +185,18 @@ pub(crate) fn get_or_vm_startup_failure<'a, T>(
     })
 }
 
+/// The checks run (in order) when validating a transaction, e.g., on
+/// mempool admission. If a check fails, the later checks are not run.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TransactionValidationCheck {
+    /// The signature and authenticator checks (including feature gating)
+    Signature,
+    /// The gas (and size) bounds of the on-chain gas schedule
+    GasBounds,
+    /// The account (and multisig) prologue
+    Prologue,
+}
+
 pub struct AptosVM {
     is_simulation: bool,
     move_vm: MoveVmExt,
@@ -197,7 +213,14 @@ impl AptosVM {
     ) -> Self {
         let _timer = TIMER.timer_with(&["AptosVM::new"]);
 
-        let features = Features::fetch_config(resolver).unwrap_or_default();
+        // If no chain ID is in storage, we assume we are in a testing environment and use ChainId::TESTING
+        let chain_id = ChainId::fetch_config(resolver).unwrap_or_else(ChainId::test);
+        let execution_feature_overrides = get_execution_feature_overrides(chain_id);
+
+        let mut features = Features::fetch_config(resolver).unwrap_or_default();
+        if let Some(overrides) = &execution_feature_overrides {
+            overrides.apply_to_features(&mut features);
+        }
         let (
             gas_params,
             storage_gas_params,
@@ -206,9 +229,6 @@ impl AptosVM {
             gas_feature_version,
         ) = get_gas_parameters(&features, resolver);
 
-        // If no chain ID is in storage, we assume we are in a testing environment and use ChainId::TESTING
-        let chain_id = ChainId::fetch_config(resolver).unwrap_or_else(ChainId::test);
-
         let timestamp = ConfigurationResource::fetch_config(resolver)
             .map(|config| config.last_reconfiguration_time())
             .unwrap_or(0);
@@ -217,6 +237,9 @@ impl AptosVM {
         if let Some(profile) = Self::get_timed_feature_override() {
             timed_features_builder = timed_features_builder.with_override_profile(profile)
         }
+        if let Some(overrides) = &execution_feature_overrides {
+            timed_features_builder = overrides.apply_to_timed_features(timed_features_builder);
+        }
         let timed_features = timed_features_builder.build();
 
         // If aggregator execution is enabled, we need to tag aggregator_v2 types,
@@ -805,6 +828,12 @@ impl AptosVM {
         for (key, op_size) in change_set.write_set_size_iter() {
             gas_meter.charge_io_gas_for_write(key, &op_size)?;
         }
+        let events_size = change_set
+            .events()
+            .iter()
+            .map(|(event, _)| event.size() as u64)
+            .sum::<u64>();
+        gas_meter.charge_io_gas_for_events(NumBytes::new(events_size))?;
 
         let mut storage_refund = gas_meter.process_storage_fee_for_all(
             change_set,
@@ -1325,31 +1354,69 @@ impl AptosVM {
         transaction_data: &TransactionMetadata,
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
+        self.validate_signed_transaction_with_checks(
+            session,
+            resolver,
+            transaction,
+            transaction_data,
+            log_context,
+        )
+        .map_err(|(_, err)| err)
+    }
+
+    /// Validates the signed transaction, and returns the check that failed (if any)
+    fn validate_signed_transaction_with_checks(
+        &self,
+        session: &mut SessionExt,
+        resolver: &impl AptosMoveResolver,
+        transaction: &SignedTransaction,
+        transaction_data: &TransactionMetadata,
+        log_context: &AdapterLogSchema,
+    ) -> Result<(), (TransactionValidationCheck, VMStatus)> {
         // Check transaction format.
         if transaction.contains_duplicate_signers() {
-            return Err(VMStatus::error(
-                StatusCode::SIGNERS_CONTAIN_DUPLICATES,
-                None,
+            return Err((
+                TransactionValidationCheck::Signature,
+                VMStatus::error(StatusCode::SIGNERS_CONTAIN_DUPLICATES, None),
             ));
         }
 
-        let authenticators = aptos_types::keyless::get_authenticators(transaction)
-            .map_err(|_| VMStatus::error(StatusCode::INVALID_SIGNATURE, None))?;
+        let authenticators =
+            aptos_types::keyless::get_authenticators(transaction).map_err(|_| {
+                (
+                    TransactionValidationCheck::Signature,
+                    VMStatus::error(StatusCode::INVALID_SIGNATURE, None),
+                )
+            })?;
 
         // If there are keyless TXN authenticators, validate them all.
         if !authenticators.is_empty() {
             // Feature-gating keyless TXNs: if they are *not* enabled, return `FEATURE_UNDER_GATING`,
             // which will discard the TXN from being put on-chain.
             if !self.features().is_keyless_enabled() {
-                return Err(VMStatus::error(StatusCode::FEATURE_UNDER_GATING, None));
+                return Err((
+                    TransactionValidationCheck::Signature,
+                    VMStatus::error(StatusCode::FEATURE_UNDER_GATING, None),
+                ));
             }
-            keyless_validation::validate_authenticators(
-                &authenticators,
-                self.features(),
-                resolver,
-            )?;
+            keyless_validation::validate_authenticators(&authenticators, self.features(), resolver)
+                .map_err(|err| (TransactionValidationCheck::Signature, err))?;
         }
 
+        // Check the gas bounds before running the prologue.
+        get_or_vm_startup_failure(&self.gas_params, log_context)
+            .and_then(|gas_params| {
+                check_gas(
+                    gas_params,
+                    self.gas_feature_version,
+                    resolver,
+                    transaction_data,
+                    self.features(),
+                    log_context,
+                )
+            })
+            .map_err(|err| (TransactionValidationCheck::GasBounds, err))?;
+
         // The prologue MUST be run AFTER any validation. Otherwise you may run prologue and hit
         // SEQUENCE_NUMBER_TOO_NEW if there is more than one transaction from the same sender and
         // end up skipping validation.
@@ -1360,6 +1427,7 @@ impl AptosVM {
             transaction_data,
             log_context,
         )
+        .map_err(|err| (TransactionValidationCheck::Prologue, err))
     }
 
     // Called when the execution of the user transaction fails, in order to discard the
@@ -1528,7 +1596,33 @@ impl AptosVM {
         // TODO: would we end up having a diverging behavior by creating the gas meter at an earlier time?
         let mut gas_meter = unwrap_or_discard!(self.make_standard_gas_meter(balance, log_context));
 
-        self.execute_user_transaction_impl(resolver, txn, log_context, &mut gas_meter)
+        if !debug_toggles::EXECUTION_TRACING.should_trace() {
+            return self.execute_user_transaction_impl(resolver, txn, log_context, &mut gas_meter);
+        }
+
+        // Trace the execution of the transaction (this is toggled via the admin service)
+        let start_time = Instant::now();
+        let (vm_status, vm_output) =
+            self.execute_user_transaction_impl(resolver, txn, log_context, &mut gas_meter);
+        speculative_info!(
+            log_context,
+            format!(
+                "[Execution tracing] Executed transaction from {} (sequence number: {}) in {:?}. \
+                 VM status: {:?}, gas used: {}, execution gas: {}, io gas: {}, storage fee: {}, \
+                 write ops: {}, events: {}",
+                txn.sender(),
+                txn.sequence_number(),
+                start_time.elapsed(),
+                vm_status,
+                vm_output.gas_used(),
+                gas_meter.execution_gas_used(),
+                gas_meter.io_gas_used(),
+                gas_meter.storage_fee_used(),
+                vm_output.change_set().num_write_ops(),
+                vm_output.change_set().events().len(),
+            )
+        );
+        (vm_status, vm_output)
     }
 
     pub fn execute_user_transaction_with_custom_gas_meter<G, F>(
@@ -1910,15 +2004,6 @@ impl AptosVM {
         txn_data: &TransactionMetadata,
         log_context: &AdapterLogSchema,
     ) -> Result<(), VMStatus> {
-        check_gas(
-            get_or_vm_startup_failure(&self.gas_params, log_context)?,
-            self.gas_feature_version,
-            resolver,
-            txn_data,
-            self.features(),
-            log_context,
-        )?;
-
         match payload {
             TransactionPayload::Script(_) | TransactionPayload::EntryFunction(_) => {
                 transaction_validation::run_script_prologue(session, txn_data, log_context)
@@ -2181,6 +2266,25 @@ impl VMValidator for AptosVM {
         transaction: SignedTransaction,
         state_view: &impl StateView,
     ) -> VMValidatorResult {
+        let gas_unit_price = transaction.gas_unit_price();
+        match self.validate_transaction_with_checks(transaction, state_view) {
+            Err((_, status_code)) if status_code != StatusCode::SEQUENCE_NUMBER_TOO_NEW => {
+                VMValidatorResult::error(status_code)
+            },
+            _ => VMValidatorResult::new(None, gas_unit_price),
+        }
+    }
+}
+
+impl AptosVM {
+    /// Validates the transaction (see `VMValidator::validate_transaction`), and returns the
+    /// check that rejected the transaction (if any). Unlike mempool, this reports a sequence
+    /// number that is too new as a prologue failure (so callers can decide how to handle it).
+    pub fn validate_transaction_with_checks(
+        &self,
+        transaction: SignedTransaction,
+        state_view: &impl StateView,
+    ) -> Result<(), (TransactionValidationCheck, StatusCode)> {
         let _timer = TXN_VALIDATION_SECONDS.start_timer();
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
 
@@ -2189,7 +2293,10 @@ impl VMValidator for AptosVM {
             .is_enabled(FeatureFlag::SINGLE_SENDER_AUTHENTICATOR)
         {
             if let aptos_types::transaction::authenticator::TransactionAuthenticator::SingleSender{ .. } = transaction.authenticator_ref() {
-                return VMValidatorResult::error(StatusCode::FEATURE_UNDER_GATING);
+                return Err((
+                    TransactionValidationCheck::Signature,
+                    StatusCode::FEATURE_UNDER_GATING,
+                ));
             }
         }
 
@@ -2200,18 +2307,27 @@ impl VMValidator for AptosVM {
             {
                 for authenticator in sk_authenticators {
                     if let AnySignature::WebAuthn { .. } = authenticator.signature() {
-                        return VMValidatorResult::error(StatusCode::FEATURE_UNDER_GATING);
+                        return Err((
+                            TransactionValidationCheck::Signature,
+                            StatusCode::FEATURE_UNDER_GATING,
+                        ));
                     }
                 }
             } else {
-                return VMValidatorResult::error(StatusCode::INVALID_SIGNATURE);
+                return Err((
+                    TransactionValidationCheck::Signature,
+                    StatusCode::INVALID_SIGNATURE,
+                ));
             }
         }
 
         let txn = match transaction.check_signature() {
             Ok(t) => t,
             _ => {
-                return VMValidatorResult::error(StatusCode::INVALID_SIGNATURE);
+                return Err((
+                    TransactionValidationCheck::Signature,
+                    StatusCode::INVALID_SIGNATURE,
+                ));
             },
         };
         let txn_data = TransactionMetadata::new(&txn);
@@ -2220,23 +2336,21 @@ impl VMValidator for AptosVM {
         let mut session = self.new_session(&resolver, SessionId::prologue_meta(&txn_data));
 
         // Increment the counter for transactions verified.
-        let (counter_label, result) = match self.validate_signed_transaction(
-            &mut session,
-            &resolver,
-            &txn,
-            &txn_data,
-            &log_context,
-        ) {
-            Err(err) if err.status_code() != StatusCode::SEQUENCE_NUMBER_TOO_NEW => (
-                "failure",
-                VMValidatorResult::new(Some(err.status_code()), 0),
-            ),
-            _ => (
-                "success",
-                VMValidatorResult::new(None, txn.gas_unit_price()),
-            ),
+        let result = self
+            .validate_signed_transaction_with_checks(
+                &mut session,
+                &resolver,
+                &txn,
+                &txn_data,
+                &log_context,
+            )
+            .map_err(|(check, err)| (check, err.status_code()));
+        let counter_label = match result {
+            Err((_, status_code)) if status_code != StatusCode::SEQUENCE_NUMBER_TOO_NEW => {
+                "failure"
+            },
+            _ => "success",
         };
-
         TRANSACTIONS_VALIDATED
             .with_label_values(&[counter_label])
             .inc();
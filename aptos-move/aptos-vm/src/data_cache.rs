@@ -4,6 +4,7 @@
 //! Scratchpad for on chain values during the execution.
 
 use crate::{
+    feature_overrides::{get_execution_feature_overrides, has_execution_feature_overrides},
     gas::get_gas_config_from_storage,
     move_vm_ext::{
         get_max_binary_format_version, get_max_identifier_size, resource_state_key,
@@ -18,6 +19,7 @@ use aptos_aggregator::{
 use aptos_table_natives::{TableHandle, TableResolver};
 use aptos_types::{
     access_path::AccessPath,
+    chain_id::ChainId,
     delayed_fields::PanicError,
     on_chain_config::{ConfigStorage, Features, OnChainConfig},
     state_store::{
@@ -319,7 +321,13 @@ pub trait AsMoveResolver<S> {
 impl<S: StateView> AsMoveResolver<S> for S {
     fn as_move_resolver(&self) -> StorageAdapter<S> {
         let (_, gas_feature_version) = get_gas_config_from_storage(self);
-        let features = Features::fetch_config(self).unwrap_or_default();
+        let mut features = Features::fetch_config(self).unwrap_or_default();
+        if has_execution_feature_overrides() {
+            let chain_id = ChainId::fetch_config(self).unwrap_or_else(ChainId::test);
+            if let Some(overrides) = get_execution_feature_overrides(chain_id) {
+                overrides.apply_to_features(&mut features);
+            }
+        }
         let max_binary_version =
             get_max_binary_format_version(&features, Some(gas_feature_version));
         let resource_group_adapter = ResourceGroupAdapter::new(
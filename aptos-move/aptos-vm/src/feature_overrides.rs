@@ -0,0 +1,160 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Local overrides for the on-chain feature flags and timed features.
+//!
+//! These allow tooling (e.g., the debugger and transaction simulation) to test
+//! upcoming feature behavior against existing chain state, without modifying
+//! the on-chain configs. Overrides are registered per chain ID and are never
+//! applied to mainnet.
+
+use anyhow::{ensure, Result};
+use aptos_infallible::RwLock;
+use aptos_types::{
+    chain_id::ChainId,
+    on_chain_config::{FeatureFlag, Features, TimedFeatureFlag, TimedFeaturesBuilder},
+};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+
+static EXECUTION_FEATURE_OVERRIDES: Lazy<RwLock<HashMap<ChainId, ExecutionFeatureOverrides>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// A set of feature flag and timed feature overrides for a single chain
+#[derive(Clone, Debug, Default)]
+pub struct ExecutionFeatureOverrides {
+    enabled_features: Vec<FeatureFlag>,
+    disabled_features: Vec<FeatureFlag>,
+    timed_feature_overrides: Vec<(TimedFeatureFlag, bool)>,
+}
+
+impl ExecutionFeatureOverrides {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables the given feature flag (regardless of the on-chain config)
+    pub fn enable_feature(mut self, flag: FeatureFlag) -> Self {
+        self.disabled_features.retain(|disabled| *disabled != flag);
+        self.enabled_features.push(flag);
+        self
+    }
+
+    /// Disables the given feature flag (regardless of the on-chain config)
+    pub fn disable_feature(mut self, flag: FeatureFlag) -> Self {
+        self.enabled_features.retain(|enabled| *enabled != flag);
+        self.disabled_features.push(flag);
+        self
+    }
+
+    /// Enables or disables the given timed feature (regardless of the block timestamp)
+    pub fn override_timed_feature(mut self, flag: TimedFeatureFlag, enabled: bool) -> Self {
+        self.timed_feature_overrides.push((flag, enabled));
+        self
+    }
+
+    /// Applies the feature flag overrides to the given features
+    pub fn apply_to_features(&self, features: &mut Features) {
+        for flag in &self.enabled_features {
+            features.enable(*flag);
+        }
+        for flag in &self.disabled_features {
+            features.disable(*flag);
+        }
+    }
+
+    /// Applies the timed feature overrides to the given builder
+    pub fn apply_to_timed_features(
+        &self,
+        mut timed_features_builder: TimedFeaturesBuilder,
+    ) -> TimedFeaturesBuilder {
+        for (flag, enabled) in &self.timed_feature_overrides {
+            timed_features_builder = timed_features_builder.with_flag_override(*flag, *enabled);
+        }
+        timed_features_builder
+    }
+}
+
+/// Registers the execution feature overrides for the given chain ID. This
+/// should only be used by local tooling (e.g., simulation and the debugger).
+/// Overrides cannot be registered for mainnet.
+pub fn set_execution_feature_overrides(
+    chain_id: ChainId,
+    overrides: ExecutionFeatureOverrides,
+) -> Result<()> {
+    ensure!(
+        !chain_id.is_mainnet(),
+        "Execution feature overrides are not supported on mainnet!"
+    );
+    EXECUTION_FEATURE_OVERRIDES
+        .write()
+        .insert(chain_id, overrides);
+    Ok(())
+}
+
+/// Removes any execution feature overrides registered for the given chain ID
+pub fn clear_execution_feature_overrides(chain_id: ChainId) {
+    EXECUTION_FEATURE_OVERRIDES.write().remove(&chain_id);
+}
+
+/// Returns the execution feature overrides registered for the given chain ID
+/// (if any). Overrides are never returned for mainnet.
+pub fn get_execution_feature_overrides(chain_id: ChainId) -> Option<ExecutionFeatureOverrides> {
+    if chain_id.is_mainnet() {
+        return None;
+    }
+    EXECUTION_FEATURE_OVERRIDES.read().get(&chain_id).cloned()
+}
+
+/// Returns true iff execution feature overrides are registered for any chain
+pub(crate) fn has_execution_feature_overrides() -> bool {
+    !EXECUTION_FEATURE_OVERRIDES.read().is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::chain_id::NamedChain;
+
+    #[test]
+    fn test_feature_overrides() {
+        // Create the overrides
+        let overrides = ExecutionFeatureOverrides::new()
+            .enable_feature(FeatureFlag::VM_BINARY_FORMAT_V7)
+            .disable_feature(FeatureFlag::EMIT_FEE_STATEMENT)
+            .override_timed_feature(TimedFeatureFlag::LimitTypeTagSize, false);
+
+        // Verify the feature flag overrides
+        let mut features = Features::default();
+        assert!(!features.is_enabled(FeatureFlag::VM_BINARY_FORMAT_V7));
+        assert!(features.is_emit_fee_statement_enabled());
+        overrides.apply_to_features(&mut features);
+        assert!(features.is_enabled(FeatureFlag::VM_BINARY_FORMAT_V7));
+        assert!(!features.is_emit_fee_statement_enabled());
+
+        // Verify the timed feature overrides
+        let timed_features = overrides
+            .apply_to_timed_features(TimedFeaturesBuilder::enable_all())
+            .build();
+        assert!(!timed_features.is_enabled(TimedFeatureFlag::LimitTypeTagSize));
+        assert!(
+            timed_features.is_enabled(TimedFeatureFlag::DisableInvariantViolationCheckInSwapLoc)
+        );
+    }
+
+    #[test]
+    fn test_mainnet_overrides_rejected() {
+        let mainnet = ChainId::new(NamedChain::MAINNET.id());
+        let overrides =
+            ExecutionFeatureOverrides::new().enable_feature(FeatureFlag::VM_BINARY_FORMAT_V7);
+        assert!(set_execution_feature_overrides(mainnet, overrides.clone()).is_err());
+        assert!(get_execution_feature_overrides(mainnet).is_none());
+
+        // Verify that overrides can be registered for other chains
+        let testnet = ChainId::new(NamedChain::TESTNET.id());
+        set_execution_feature_overrides(testnet, overrides).unwrap();
+        assert!(get_execution_feature_overrides(testnet).is_some());
+        clear_execution_feature_overrides(testnet);
+        assert!(get_execution_feature_overrides(testnet).is_none());
+    }
+}
@@ -108,6 +108,7 @@ pub mod data_cache;
 pub mod aptos_vm;
 pub mod block_executor;
 mod errors;
+pub mod feature_overrides;
 mod gas;
 mod keyless_validation;
 pub mod move_vm_ext;
@@ -120,7 +121,7 @@ mod transaction_validation;
 pub mod validator_txns;
 pub mod verifier;
 
-pub use crate::aptos_vm::{AptosSimulationVM, AptosVM};
+pub use crate::aptos_vm::{AptosSimulationVM, AptosVM, TransactionValidationCheck};
 use crate::sharded_block_executor::{executor_client::ExecutorClient, ShardedBlockExecutor};
 use aptos_types::{
     block_executor::{
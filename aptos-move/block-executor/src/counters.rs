@@ -1,9 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::gas_calibration::GasCalibrationReport;
 use aptos_metrics_core::{
-    exponential_buckets, register_histogram, register_histogram_vec, register_int_counter,
-    register_int_counter_vec, Histogram, HistogramVec, IntCounter, IntCounterVec,
+    exponential_buckets, register_gauge_vec, register_histogram, register_histogram_vec,
+    register_int_counter, register_int_counter_vec, GaugeVec, Histogram, HistogramVec, IntCounter,
+    IntCounterVec,
 };
 use aptos_types::fee_statement::FeeStatement;
 use once_cell::sync::Lazy;
@@ -211,6 +213,25 @@ pub static BLOCK_COMMITTED_TXNS: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+pub static BLOCK_EFFECTIVE_GAS_PER_SECOND: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_execution_block_effective_gas_per_second",
+        "Histogram for the effective block gas executed per second - used for calibrating the block gas limit",
+        &["mode"],
+        exponential_buckets(/*start=*/ 1.0, /*factor=*/ 2.0, /*count=*/ 40).unwrap(),
+    )
+    .unwrap()
+});
+
+pub static GAS_CALIBRATION_REPORT: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "aptos_execution_gas_calibration_report",
+        "The values of the most recently generated gas calibration report",
+        &["value"]
+    )
+    .unwrap()
+});
+
 fn observe_gas(counter: &Lazy<HistogramVec>, mode_str: &str, fee_statement: &FeeStatement) {
     counter
         .with_label_values(&[mode_str, GasType::TOTAL_GAS])
@@ -275,3 +296,50 @@ pub(crate) fn update_txn_gas_counters(txn_fee_statements: &Vec<FeeStatement>, is
         observe_gas(&TXN_GAS, mode_str, fee_statement);
     }
 }
+
+pub(crate) fn observe_block_effective_gas_per_sec(effective_gas_per_sec: f64, is_parallel: bool) {
+    let mode_str = if is_parallel {
+        Mode::PARALLEL
+    } else {
+        Mode::SEQUENTIAL
+    };
+
+    BLOCK_EFFECTIVE_GAS_PER_SECOND
+        .with_label_values(&[mode_str])
+        .observe(effective_gas_per_sec);
+}
+
+pub(crate) fn update_gas_calibration_report_gauges(report: &GasCalibrationReport) {
+    let values = [
+        ("num_samples", Some(report.num_samples as f64)),
+        (
+            "median_effective_gas_per_sec",
+            report.median_effective_gas_per_sec,
+        ),
+        (
+            "conservative_effective_gas_per_sec",
+            report.conservative_effective_gas_per_sec,
+        ),
+        (
+            "suggested_effective_block_gas_limit",
+            report
+                .suggested_effective_block_gas_limit
+                .map(|limit| limit as f64),
+        ),
+        (
+            "suggested_block_gas_limit_adjustment",
+            report.suggested_block_gas_limit_adjustment,
+        ),
+        (
+            "suggested_io_to_execution_gas_ratio",
+            report.suggested_io_to_execution_gas_ratio,
+        ),
+    ];
+    for (label, value) in values {
+        if let Some(value) = value {
+            GAS_CALIBRATION_REPORT
+                .with_label_values(&[label])
+                .set(value);
+        }
+    }
+}
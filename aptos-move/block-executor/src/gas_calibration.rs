@@ -0,0 +1,380 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Block gas limit calibration. The block executor records the actual execution
+//! time and the gas consumed by each block, and calibration reports are derived
+//! from the recent samples. The reports suggest adjustments to the block gas limit
+//! (and the relative cost of execution and io gas), and can be consumed by
+//! governance tooling (e.g., via the admin service).
+
+use crate::counters;
+use aptos_infallible::Mutex;
+use aptos_types::{fee_statement::FeeStatement, on_chain_config::BlockGasLimitType};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{collections::VecDeque, time::Duration};
+
+/// The maximum number of block samples kept by the global recorder
+pub const DEFAULT_MAX_NUM_CALIBRATION_SAMPLES: usize = 10_000;
+
+/// The minimum number of samples required before adjustments are suggested
+pub const MIN_NUM_SAMPLES_FOR_SUGGESTIONS: usize = 10;
+
+/// The percentile of the observed gas throughput used to suggest the block gas
+/// limit. A low percentile is used so that the suggested limit is conservative
+/// (i.e., the slower blocks still execute within the target block time).
+const SUGGESTION_GAS_PER_SEC_PERCENTILE: f64 = 0.1;
+
+/// The global gas calibration recorder
+pub static GAS_CALIBRATION_RECORDER: Lazy<GasCalibrationRecorder> =
+    Lazy::new(|| GasCalibrationRecorder::new(DEFAULT_MAX_NUM_CALIBRATION_SAMPLES));
+
+/// The execution time and gas consumed by a single block
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct BlockCalibrationSample {
+    pub is_parallel: bool,
+    pub num_committed_txns: u64,
+    pub execution_gas: u64,
+    pub io_gas: u64,
+    pub effective_block_gas: u64,
+    pub effective_block_gas_limit: Option<u64>, // The block gas limit at execution time
+    pub execution_time_secs: f64,
+}
+
+impl BlockCalibrationSample {
+    pub fn new(
+        is_parallel: bool,
+        num_committed_txns: u64,
+        fee_statement: &FeeStatement,
+        effective_block_gas: u64,
+        block_gas_limit_type: &BlockGasLimitType,
+        execution_time: Duration,
+    ) -> Self {
+        Self {
+            is_parallel,
+            num_committed_txns,
+            execution_gas: fee_statement.execution_gas_used(),
+            io_gas: fee_statement.io_gas_used(),
+            effective_block_gas,
+            effective_block_gas_limit: block_gas_limit_type.block_gas_limit(),
+            execution_time_secs: execution_time.as_secs_f64(),
+        }
+    }
+
+    /// Returns the effective gas executed per second (if it can be computed)
+    pub fn effective_gas_per_sec(&self) -> Option<f64> {
+        if self.execution_time_secs > 0.0 {
+            Some(self.effective_block_gas as f64 / self.execution_time_secs)
+        } else {
+            None
+        }
+    }
+
+    /// Returns true iff the sample is useful for calibration
+    fn is_usable(&self) -> bool {
+        self.num_committed_txns > 0
+            && self.effective_block_gas > 0
+            && self.execution_time_secs > 0.0
+    }
+}
+
+/// A calibration report derived from the recorded block samples
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct GasCalibrationReport {
+    /// The number of samples the report is derived from
+    pub num_samples: usize,
+    /// The target block execution time (used to suggest the block gas limit)
+    pub target_block_time_ms: u64,
+    /// The effective block gas limit of the most recent sample (if any)
+    pub current_effective_block_gas_limit: Option<u64>,
+    /// The mean block execution time (in milliseconds)
+    pub mean_block_time_ms: Option<f64>,
+    /// The median effective gas executed per second
+    pub median_effective_gas_per_sec: Option<f64>,
+    /// The conservative (low percentile) effective gas executed per second
+    pub conservative_effective_gas_per_sec: Option<f64>,
+    /// The estimated execution time per unit of execution gas (in microseconds)
+    pub execution_gas_cost_us: Option<f64>,
+    /// The estimated execution time per unit of io gas (in microseconds)
+    pub io_gas_cost_us: Option<f64>,
+    /// The suggested effective block gas limit for the target block time
+    pub suggested_effective_block_gas_limit: Option<u64>,
+    /// The ratio of the suggested block gas limit to the current limit
+    pub suggested_block_gas_limit_adjustment: Option<f64>,
+    /// The suggested ratio of the io gas multiplier to the execution gas
+    /// multiplier (i.e., how expensive io gas is relative to execution gas).
+    pub suggested_io_to_execution_gas_ratio: Option<f64>,
+}
+
+/// Records block calibration samples (up to a maximum number of samples)
+pub struct GasCalibrationRecorder {
+    max_num_samples: usize,
+    samples: Mutex<VecDeque<BlockCalibrationSample>>,
+}
+
+impl GasCalibrationRecorder {
+    pub fn new(max_num_samples: usize) -> Self {
+        Self {
+            max_num_samples,
+            samples: Mutex::new(VecDeque::with_capacity(max_num_samples)),
+        }
+    }
+
+    /// Records the given block sample (evicting the oldest sample, if required)
+    pub fn record_sample(&self, sample: BlockCalibrationSample) {
+        if !sample.is_usable() || self.max_num_samples == 0 {
+            return;
+        }
+
+        // Update the calibration metrics
+        if let Some(effective_gas_per_sec) = sample.effective_gas_per_sec() {
+            counters::observe_block_effective_gas_per_sec(
+                effective_gas_per_sec,
+                sample.is_parallel,
+            );
+        }
+
+        // Store the sample
+        let mut samples = self.samples.lock();
+        if samples.len() >= self.max_num_samples {
+            samples.pop_front();
+        }
+        samples.push_back(sample);
+    }
+
+    /// Returns a copy of the recorded samples (oldest first)
+    pub fn get_samples(&self) -> Vec<BlockCalibrationSample> {
+        self.samples.lock().iter().cloned().collect()
+    }
+
+    /// Removes all recorded samples
+    pub fn clear(&self) {
+        self.samples.lock().clear();
+    }
+
+    /// Generates a calibration report for the given target block time
+    pub fn generate_report(&self, target_block_time: Duration) -> GasCalibrationReport {
+        let report = generate_calibration_report(&self.get_samples(), target_block_time);
+        counters::update_gas_calibration_report_gauges(&report);
+        report
+    }
+}
+
+/// Generates a calibration report from the given samples and target block time
+pub fn generate_calibration_report(
+    samples: &[BlockCalibrationSample],
+    target_block_time: Duration,
+) -> GasCalibrationReport {
+    let samples: Vec<_> = samples.iter().filter(|sample| sample.is_usable()).collect();
+    let num_samples = samples.len();
+    let current_effective_block_gas_limit = samples
+        .last()
+        .and_then(|sample| sample.effective_block_gas_limit);
+
+    // Calculate the observed block times and gas throughput
+    let mean_block_time_ms = if num_samples > 0 {
+        let total_time_secs: f64 = samples
+            .iter()
+            .map(|sample| sample.execution_time_secs)
+            .sum();
+        Some(total_time_secs * 1000.0 / num_samples as f64)
+    } else {
+        None
+    };
+    let mut gas_per_sec: Vec<f64> = samples
+        .iter()
+        .filter_map(|sample| sample.effective_gas_per_sec())
+        .collect();
+    gas_per_sec.sort_by(|a, b| a.total_cmp(b));
+    let median_effective_gas_per_sec = percentile(&gas_per_sec, 0.5);
+    let conservative_effective_gas_per_sec =
+        percentile(&gas_per_sec, SUGGESTION_GAS_PER_SEC_PERCENTILE);
+
+    // Estimate the cost of each gas type
+    let (execution_gas_cost_secs, io_gas_cost_secs) = match estimate_gas_costs(&samples) {
+        Some((execution_gas_cost, io_gas_cost)) => (Some(execution_gas_cost), Some(io_gas_cost)),
+        None => (None, None),
+    };
+
+    // Only suggest adjustments if there are enough samples
+    let mut report = GasCalibrationReport {
+        num_samples,
+        target_block_time_ms: target_block_time.as_millis() as u64,
+        current_effective_block_gas_limit,
+        mean_block_time_ms,
+        median_effective_gas_per_sec,
+        conservative_effective_gas_per_sec,
+        execution_gas_cost_us: execution_gas_cost_secs.map(|cost| cost * 1_000_000.0),
+        io_gas_cost_us: io_gas_cost_secs.map(|cost| cost * 1_000_000.0),
+        suggested_effective_block_gas_limit: None,
+        suggested_block_gas_limit_adjustment: None,
+        suggested_io_to_execution_gas_ratio: None,
+    };
+    if num_samples < MIN_NUM_SAMPLES_FOR_SUGGESTIONS {
+        return report;
+    }
+
+    // Suggest the block gas limit
+    if let Some(gas_per_sec) = conservative_effective_gas_per_sec {
+        let suggested_limit = (gas_per_sec * target_block_time.as_secs_f64()) as u64;
+        report.suggested_effective_block_gas_limit = Some(suggested_limit);
+        report.suggested_block_gas_limit_adjustment = current_effective_block_gas_limit
+            .filter(|current_limit| *current_limit > 0)
+            .map(|current_limit| suggested_limit as f64 / current_limit as f64);
+    }
+
+    // Suggest the relative cost of io gas
+    if let (Some(execution_gas_cost), Some(io_gas_cost)) =
+        (execution_gas_cost_secs, io_gas_cost_secs)
+    {
+        if execution_gas_cost > 0.0 && io_gas_cost > 0.0 {
+            report.suggested_io_to_execution_gas_ratio = Some(io_gas_cost / execution_gas_cost);
+        }
+    }
+
+    report
+}
+
+/// Returns the value at the given percentile (of the sorted values)
+fn percentile(sorted_values: &[f64], percentile: f64) -> Option<f64> {
+    if sorted_values.is_empty() {
+        return None;
+    }
+    let index = ((sorted_values.len() - 1) as f64 * percentile).round() as usize;
+    sorted_values.get(index).copied()
+}
+
+/// Estimates the execution time per unit of execution gas and io gas (in seconds),
+/// using a least squares fit of: time = (execution_gas_cost * execution_gas) +
+/// (io_gas_cost * io_gas). Returns None if the samples don't determine the fit
+/// (e.g., the gas types are always consumed in the same proportion).
+fn estimate_gas_costs(samples: &[&BlockCalibrationSample]) -> Option<(f64, f64)> {
+    let (mut xx, mut xy, mut yy, mut xt, mut yt) = (0.0, 0.0, 0.0, 0.0, 0.0);
+    for sample in samples {
+        let execution_gas = sample.execution_gas as f64;
+        let io_gas = sample.io_gas as f64;
+        let time = sample.execution_time_secs;
+        xx += execution_gas * execution_gas;
+        xy += execution_gas * io_gas;
+        yy += io_gas * io_gas;
+        xt += execution_gas * time;
+        yt += io_gas * time;
+    }
+
+    // Solve the normal equations
+    let determinant = xx * yy - xy * xy;
+    if determinant.abs() <= f64::EPSILON * xx * yy {
+        return None;
+    }
+    let execution_gas_cost = (xt * yy - yt * xy) / determinant;
+    let io_gas_cost = (yt * xx - xt * xy) / determinant;
+    Some((execution_gas_cost, io_gas_cost))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const TARGET_BLOCK_TIME: Duration = Duration::from_millis(100);
+
+    fn create_sample(
+        execution_gas: u64,
+        io_gas: u64,
+        execution_time_secs: f64,
+    ) -> BlockCalibrationSample {
+        BlockCalibrationSample {
+            is_parallel: true,
+            num_committed_txns: 10,
+            execution_gas,
+            io_gas,
+            effective_block_gas: execution_gas + io_gas,
+            effective_block_gas_limit: Some(20_000),
+            execution_time_secs,
+        }
+    }
+
+    #[test]
+    fn test_recorder_evicts_oldest_samples() {
+        let recorder = GasCalibrationRecorder::new(3);
+        for execution_gas in 1..=5 {
+            recorder.record_sample(create_sample(execution_gas, 0, 1.0));
+        }
+
+        let execution_gas: Vec<_> = recorder
+            .get_samples()
+            .iter()
+            .map(|sample| sample.execution_gas)
+            .collect();
+        assert_eq!(execution_gas, vec![3, 4, 5]);
+
+        recorder.clear();
+        assert!(recorder.get_samples().is_empty());
+    }
+
+    #[test]
+    fn test_recorder_ignores_unusable_samples() {
+        let recorder = GasCalibrationRecorder::new(10);
+        recorder.record_sample(create_sample(0, 0, 1.0));
+        recorder.record_sample(create_sample(100, 0, 0.0));
+        assert!(recorder.get_samples().is_empty());
+    }
+
+    #[test]
+    fn test_report_without_enough_samples() {
+        let samples = vec![create_sample(1000, 1000, 0.01); MIN_NUM_SAMPLES_FOR_SUGGESTIONS - 1];
+        let report = generate_calibration_report(&samples, TARGET_BLOCK_TIME);
+
+        assert_eq!(report.num_samples, MIN_NUM_SAMPLES_FOR_SUGGESTIONS - 1);
+        assert!((report.median_effective_gas_per_sec.unwrap() - 200_000.0).abs() < 1e-3);
+        assert_eq!(report.suggested_effective_block_gas_limit, None);
+        assert_eq!(report.suggested_io_to_execution_gas_ratio, None);
+    }
+
+    #[test]
+    fn test_report_suggestions() {
+        // Execution gas costs 1us per unit, and io gas costs 3us per unit
+        let samples: Vec<_> = (1..=20)
+            .map(|i| {
+                let execution_gas = 1000 * i;
+                let io_gas = 500 * (i % 4 + 1);
+                let execution_time_secs = (execution_gas + 3 * io_gas) as f64 / 1_000_000.0;
+                create_sample(execution_gas, io_gas, execution_time_secs)
+            })
+            .collect();
+        let report = generate_calibration_report(&samples, TARGET_BLOCK_TIME);
+
+        assert_eq!(report.num_samples, 20);
+        assert_eq!(report.current_effective_block_gas_limit, Some(20_000));
+        assert!((report.execution_gas_cost_us.unwrap() - 1.0).abs() < 1e-6);
+        assert!((report.io_gas_cost_us.unwrap() - 3.0).abs() < 1e-6);
+        assert!((report.suggested_io_to_execution_gas_ratio.unwrap() - 3.0).abs() < 1e-6);
+
+        // The suggested limit is derived from the conservative gas throughput
+        let conservative_gas_per_sec = report.conservative_effective_gas_per_sec.unwrap();
+        assert!(conservative_gas_per_sec <= report.median_effective_gas_per_sec.unwrap());
+        let suggested_limit = report.suggested_effective_block_gas_limit.unwrap();
+        assert_eq!(
+            suggested_limit,
+            (conservative_gas_per_sec * TARGET_BLOCK_TIME.as_secs_f64()) as u64
+        );
+        assert_eq!(
+            report.suggested_block_gas_limit_adjustment,
+            Some(suggested_limit as f64 / 20_000.0)
+        );
+    }
+
+    #[test]
+    fn test_report_with_proportional_gas() {
+        // If the gas types are always consumed in the same proportion,
+        // the relative cost of io gas can't be determined.
+        let samples: Vec<_> = (1..=20)
+            .map(|i| create_sample(1000 * i, 1000 * i, (2000 * i) as f64 / 1_000_000.0))
+            .collect();
+        let report = generate_calibration_report(&samples, TARGET_BLOCK_TIME);
+
+        assert_eq!(report.execution_gas_cost_us, None);
+        assert_eq!(report.suggested_io_to_execution_gas_ratio, None);
+        let suggested_limit = report.suggested_effective_block_gas_limit.unwrap();
+        assert!((99_999..=100_000).contains(&suggested_limit));
+    }
+}
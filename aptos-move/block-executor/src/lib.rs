@@ -145,6 +145,7 @@ pub mod errors;
 pub mod executor;
 mod executor_utilities;
 pub mod explicit_sync_wrapper;
+pub mod gas_calibration;
 mod limit_processor;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod proptest_types;
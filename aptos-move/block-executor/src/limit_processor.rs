@@ -1,13 +1,18 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{counters, types::ReadWriteSummary};
+use crate::{
+    counters,
+    gas_calibration::{BlockCalibrationSample, GAS_CALIBRATION_RECORDER},
+    types::ReadWriteSummary,
+};
 use aptos_logger::info;
 use aptos_types::{
     fee_statement::FeeStatement, on_chain_config::BlockGasLimitType,
     transaction::BlockExecutableTransaction as Transaction,
 };
 use claims::{assert_le, assert_none};
+use std::time::Instant;
 
 pub struct BlockGasLimitProcessor<T: Transaction> {
     block_gas_limit_type: BlockGasLimitType,
@@ -18,6 +23,7 @@ pub struct BlockGasLimitProcessor<T: Transaction> {
     txn_read_write_summaries: Vec<ReadWriteSummary<T>>,
     block_limit_reached: bool,
     module_rw_conflict: bool,
+    start_time: Instant, // Used to record the block execution time (for gas calibration)
 }
 
 impl<T: Transaction> BlockGasLimitProcessor<T> {
@@ -31,6 +37,7 @@ impl<T: Transaction> BlockGasLimitProcessor<T> {
             txn_read_write_summaries: Vec::with_capacity(init_size),
             block_limit_reached: false,
             module_rw_conflict: false,
+            start_time: Instant::now(),
         }
     }
 
@@ -207,6 +214,14 @@ impl<T: Transaction> BlockGasLimitProcessor<T> {
             is_parallel,
         );
         counters::update_txn_gas_counters(&self.txn_fee_statements, is_parallel);
+        GAS_CALIBRATION_RECORDER.record_sample(BlockCalibrationSample::new(
+            is_parallel,
+            num_committed as u64,
+            &self.accumulated_fee_statement,
+            accumulated_effective_block_gas,
+            &self.block_gas_limit_type,
+            self.start_time.elapsed(),
+        ));
 
         info!(
             effective_block_gas = accumulated_effective_block_gas,
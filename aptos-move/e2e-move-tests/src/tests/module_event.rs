@@ -116,3 +116,43 @@ fn verify_module_event_upgrades() {
     let result = h.publish_package(&account, path.path());
     assert_vm_status!(result, StatusCode::EVENT_METADATA_VALIDATION_ERROR);
 }
+
+#[test]
+fn test_io_gas_for_large_events() {
+    let mut h = MoveHarness::new_with_features(vec![FeatureFlag::MODULE_EVENT], vec![]);
+
+    let addr = AccountAddress::from_hex_literal("0xcafe").unwrap();
+    let account = h.new_account_at(addr);
+
+    let mut build_options = aptos_framework::BuildOptions::default();
+    build_options
+        .named_addresses
+        .insert("event".to_string(), addr);
+    assert_success!(h.publish_package_with_options(
+        &account,
+        &common::test_dir_path("../../../move-examples/event"),
+        build_options,
+    ));
+
+    // Make the event bytes above the free quota super expensive, so that they exceed the IO limit
+    h.modify_gas_schedule(|gas_params| {
+        gas_params.vm.txn.storage_io_per_event_byte_write = 10_000_000.into();
+    });
+
+    // A few (small) events fit into the free quota and are not charged
+    assert_success!(h.run_entry_function(
+        &account,
+        str::parse("0xcafe::event::emit").unwrap(),
+        vec![],
+        vec![bcs::to_bytes(&1u64).unwrap()],
+    ));
+
+    // Many events exceed the free quota and are charged per byte
+    let status = h.run_entry_function(
+        &account,
+        str::parse("0xcafe::event::emit").unwrap(),
+        vec![],
+        vec![bcs::to_bytes(&100u64).unwrap()],
+    );
+    assert_vm_status!(status, StatusCode::IO_LIMIT_REACHED);
+}
@@ -0,0 +1,222 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Support for publishing packages that exceed the maximum transaction size.
+//!
+//! The package metadata and code are split into chunks, each of which is staged on chain
+//! (under the publisher's account) by a separate call to `large_packages::stage_code`. The
+//! final chunk publishes the staged package and removes the staging area. As chunking is
+//! deterministic, an interrupted publish can be resumed by comparing the staging area on
+//! chain with the expected staging area after each chunk.
+
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{EntryFunction, TransactionPayload},
+};
+use move_core_types::{ident_str, language_storage::ModuleId};
+use serde::{Deserialize, Serialize};
+
+/// The maximum number of package bytes to stage in a single transaction. This leaves
+/// enough room for the BCS and transaction overheads.
+pub const CHUNK_SIZE_IN_BYTES: usize = 55_000;
+
+/// The address of the `large_packages` module deployed on devnet, testnet and mainnet
+pub const LARGE_PACKAGES_MODULE_ADDRESS: &str =
+    "0xfa3911d7715238b2e3bd5b26b6a35e11ffa16cff318bc11471e84eccee8bd291";
+
+/// The Rust representation of `large_packages::StagingArea`
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StagingArea {
+    pub metadata_serialized: Vec<u8>,
+    pub code: Vec<Vec<u8>>,
+}
+
+impl StagingArea {
+    /// Returns the resource type of the staging area for the given module address
+    pub fn struct_tag(large_packages_module_address: AccountAddress) -> String {
+        format!(
+            "{}::large_packages::StagingArea",
+            large_packages_module_address.to_hex_literal()
+        )
+    }
+
+    /// Returns the total number of package bytes in the staging area
+    pub fn num_staged_bytes(&self) -> usize {
+        self.metadata_serialized.len() + self.code.iter().map(Vec::len).sum::<usize>()
+    }
+
+    /// Stages the given chunk (mirroring the behaviour of `large_packages::stage_code`)
+    fn stage_chunk(&mut self, chunk: &PublishChunk) {
+        self.metadata_serialized
+            .extend_from_slice(&chunk.metadata_chunk);
+        for (code_index, code_chunk) in chunk.code_indices.iter().zip(&chunk.code_chunks) {
+            let code_index = *code_index as usize;
+            if self.code.len() <= code_index {
+                self.code.resize(code_index + 1, vec![]);
+            }
+            self.code[code_index].extend_from_slice(code_chunk);
+        }
+    }
+}
+
+/// A chunk of the package to stage in a single transaction
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PublishChunk {
+    pub metadata_chunk: Vec<u8>,
+    pub code_indices: Vec<u16>,
+    pub code_chunks: Vec<Vec<u8>>,
+}
+
+impl PublishChunk {
+    /// Returns the number of package bytes in the chunk
+    pub fn size(&self) -> usize {
+        self.metadata_chunk.len() + self.code_chunks.iter().map(Vec::len).sum::<usize>()
+    }
+
+    /// Creates the `large_packages::stage_code` payload for the chunk. If `publish`
+    /// is set, the staged package is published by the same transaction.
+    pub fn into_payload(
+        self,
+        large_packages_module_address: AccountAddress,
+        publish: bool,
+    ) -> TransactionPayload {
+        TransactionPayload::EntryFunction(EntryFunction::new(
+            ModuleId::new(
+                large_packages_module_address,
+                ident_str!("large_packages").to_owned(),
+            ),
+            ident_str!("stage_code").to_owned(),
+            vec![],
+            vec![
+                bcs::to_bytes(&self.metadata_chunk).unwrap(),
+                bcs::to_bytes(&self.code_indices).unwrap(),
+                bcs::to_bytes(&self.code_chunks).unwrap(),
+                bcs::to_bytes(&publish).unwrap(),
+            ],
+        ))
+    }
+}
+
+/// Splits the package metadata and code into chunks of at most `chunk_size` bytes.
+/// At least one chunk is always returned (the last chunk publishes the package).
+pub fn chunk_package(
+    metadata_serialized: &[u8],
+    code: &[Vec<u8>],
+    chunk_size: usize,
+) -> Vec<PublishChunk> {
+    let mut chunks = vec![];
+    let mut current_chunk = PublishChunk::default();
+
+    // Chunk the metadata first (it is appended to the staging area in order)
+    for metadata_piece in metadata_serialized.chunks(chunk_size) {
+        if current_chunk.size() + metadata_piece.len() > chunk_size {
+            chunks.push(std::mem::take(&mut current_chunk));
+        }
+        current_chunk
+            .metadata_chunk
+            .extend_from_slice(metadata_piece);
+    }
+
+    // Chunk each module, filling each chunk as much as possible
+    for (code_index, module) in code.iter().enumerate() {
+        for code_piece in module.chunks(chunk_size) {
+            if current_chunk.size() + code_piece.len() > chunk_size {
+                chunks.push(std::mem::take(&mut current_chunk));
+            }
+            current_chunk.code_indices.push(code_index as u16);
+            current_chunk.code_chunks.push(code_piece.to_vec());
+        }
+    }
+    chunks.push(current_chunk);
+
+    chunks
+}
+
+/// Returns the number of chunks that have already been staged, given the staging
+/// area currently on chain (if any). Returns `None` if the staging area does not
+/// correspond to any prefix of the chunks (e.g., it was created for another
+/// package), in which case it must be cleaned up before publishing.
+pub fn get_num_staged_chunks(
+    chunks: &[PublishChunk],
+    staging_area: Option<&StagingArea>,
+) -> Option<usize> {
+    let staging_area = match staging_area {
+        Some(staging_area) => staging_area,
+        None => return Some(0),
+    };
+
+    // The last chunk publishes the package (and removes the staging area),
+    // so it is never reflected in the staging area.
+    let mut expected_staging_area = StagingArea::default();
+    for (num_staged_chunks, chunk) in chunks.iter().enumerate() {
+        if expected_staging_area.num_staged_bytes() == staging_area.num_staged_bytes() {
+            return (&expected_staging_area == staging_area).then_some(num_staged_chunks);
+        }
+        expected_staging_area.stage_chunk(chunk);
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunk_package() {
+        let metadata_serialized = vec![0; 25];
+        let code = vec![vec![1; 10], vec![2; 45], vec![3; 5]];
+        let chunks = chunk_package(&metadata_serialized, &code, 20);
+
+        // Verify no chunk exceeds the chunk size
+        for chunk in &chunks {
+            assert!(chunk.size() <= 20);
+        }
+
+        // Verify staging all chunks reconstructs the package
+        let mut staging_area = StagingArea::default();
+        for chunk in &chunks {
+            staging_area.stage_chunk(chunk);
+        }
+        assert_eq!(staging_area, StagingArea {
+            metadata_serialized,
+            code,
+        });
+    }
+
+    #[test]
+    fn test_small_package_is_a_single_chunk() {
+        let chunks = chunk_package(&[0; 10], &[vec![1; 10]], CHUNK_SIZE_IN_BYTES);
+        assert_eq!(chunks, vec![PublishChunk {
+            metadata_chunk: vec![0; 10],
+            code_indices: vec![0],
+            code_chunks: vec![vec![1; 10]],
+        }]);
+    }
+
+    #[test]
+    fn test_get_num_staged_chunks() {
+        let chunks = chunk_package(&[0; 25], &[vec![1; 10], vec![2; 45]], 20);
+        assert_eq!(get_num_staged_chunks(&chunks, None), Some(0));
+
+        // Verify the staged chunks are identified for every (partial) staging area
+        let mut staging_area = StagingArea::default();
+        for (num_staged_chunks, chunk) in chunks.iter().enumerate().take(chunks.len() - 1) {
+            staging_area.stage_chunk(chunk);
+            assert_eq!(
+                get_num_staged_chunks(&chunks, Some(&staging_area)),
+                Some(num_staged_chunks + 1)
+            );
+        }
+
+        // Verify a staging area for a different package is not resumed
+        let other_staging_area = StagingArea {
+            metadata_serialized: vec![5; 20],
+            code: vec![],
+        };
+        assert_eq!(
+            get_num_staged_chunks(&chunks, Some(&other_staging_area)),
+            None
+        );
+    }
+}
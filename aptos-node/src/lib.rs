@@ -25,10 +25,12 @@ use aptos_config::config::{
 };
 use aptos_dkg_runtime::start_dkg_runtime;
 use aptos_framework::ReleaseBundle;
+use aptos_inspection_service::startup_progress::{StartupPhase, StartupProgressReporter};
 use aptos_jwk_consensus::start_jwk_consensus_runtime;
 use aptos_logger::{prelude::*, telemetry_log_writer::TelemetryLog, Level, LoggerFilterUpdater};
 use aptos_safety_rules::safety_rules_manager::load_consensus_key_from_secure_storage;
 use aptos_state_sync_driver::driver_factory::StateSyncRuntimes;
+use aptos_time_service::TimeService;
 use aptos_types::chain_id::ChainId;
 use aptos_validator_transaction_pool::VTxnPoolState;
 use clap::Parser;
@@ -567,15 +569,27 @@ pub fn setup_environment_and_start_node(
     // Starts the admin service
     let admin_service = services::start_admin_service(&node_config);
 
+    // Create the time service shared by consensus, mempool and state sync
+    let time_service = utils::create_time_service(&node_config)?;
+    admin_service.set_time_service(time_service.clone());
+
+    // Create the startup progress reporter (this is exposed by the inspection service)
+    let startup_progress_reporter = StartupProgressReporter::new(TimeService::real());
+
     // Set up the storage database and any RocksDB checkpoints
+    startup_progress_reporter.start_phase(StartupPhase::OpenStorage);
     let (db_rw, backup_service, genesis_waypoint) =
         storage::initialize_database_and_checkpoints(&mut node_config)?;
+    startup_progress_reporter.complete_phase(StartupPhase::OpenStorage);
 
     admin_service.set_aptos_db(db_rw.clone().into());
 
     // Set the Aptos VM configurations
     utils::set_aptos_vm_configurations(&node_config);
 
+    // Set the memory budget of the execution pipeline
+    utils::set_memory_budget_configurations(&node_config);
+
     // Obtain the chain_id from the DB
     let chain_id = utils::fetch_chain_id(&db_rw)?;
 
@@ -624,30 +638,49 @@ pub fn setup_environment_and_start_node(
     );
 
     // Start state sync and get the notification endpoints for mempool and consensus
-    let (aptos_data_client, state_sync_runtimes, mempool_listener, consensus_notifier) =
-        state_sync::start_state_sync_and_get_notification_handles(
-            &node_config,
-            storage_service_network_interfaces,
-            genesis_waypoint,
-            event_subscription_service,
-            db_rw.clone(),
-        )?;
+    startup_progress_reporter.start_phase(StartupPhase::StateSyncBootstrapping);
+    let (
+        aptos_data_client,
+        stream_audit_logs,
+        stream_diagnostics,
+        sync_progress_reporter,
+        state_sync_runtimes,
+        mempool_listener,
+        consensus_notifier,
+    ) = state_sync::start_state_sync_and_get_notification_handles(
+        &node_config,
+        storage_service_network_interfaces,
+        genesis_waypoint,
+        event_subscription_service,
+        db_rw.clone(),
+        time_service.clone(),
+    )?;
+
+    admin_service.set_state_sync_bandwidth_budget(aptos_data_client.get_bandwidth_budget());
 
     // Start the node inspection service
     services::start_node_inspection_service(
         &node_config,
         aptos_data_client,
         peers_and_metadata.clone(),
+        stream_audit_logs,
+        stream_diagnostics,
+        sync_progress_reporter,
+        startup_progress_reporter.clone(),
     );
 
     // Bootstrap the API and indexer
+    startup_progress_reporter.start_phase(StartupPhase::StartApi);
     let (
+        mempool_client_sender,
         mempool_client_receiver,
         api_runtime,
         indexer_table_info_runtime,
         indexer_runtime,
         indexer_grpc_runtime,
     ) = services::bootstrap_api_and_indexer(&node_config, db_rw.clone(), chain_id)?;
+    startup_progress_reporter.complete_phase(StartupPhase::StartApi);
+    admin_service.set_mempool_client_sender(mempool_client_sender);
 
     // Create mempool and get the consensus to mempool sender
     let (mempool_runtime, consensus_to_mempool_sender) =
@@ -659,6 +692,7 @@ pub fn setup_environment_and_start_node(
             mempool_listener,
             mempool_client_receiver,
             peers_and_metadata,
+            time_service.clone(),
         );
 
     // Ensure consensus key in secure DB.
@@ -681,6 +715,7 @@ pub fn setup_environment_and_start_node(
             let ApplicationNetworkInterfaces {
                 network_client,
                 network_service_events,
+                ..
             } = interfaces;
             let (reconfig_events, dkg_start_events) = dkg_subscriptions
                 .expect("DKG needs to listen to NewEpochEvents events and DKGStartEvents");
@@ -711,6 +746,7 @@ pub fn setup_environment_and_start_node(
             let ApplicationNetworkInterfaces {
                 network_client,
                 network_service_events,
+                ..
             } = interfaces;
             let (reconfig_events, onchain_jwk_updated_events) = jwk_consensus_subscriptions.expect(
                 "JWK consensus needs to listen to NewEpochEvents and OnChainJWKMapUpdated events.",
@@ -736,8 +772,10 @@ pub fn setup_environment_and_start_node(
         debug!("Waiting until state sync is initialized!");
         state_sync_runtimes.block_until_initialized();
         debug!("State sync initialization complete.");
+        startup_progress_reporter.complete_phase(StartupPhase::StateSyncBootstrapping);
 
         // Initialize and start consensus
+        startup_progress_reporter.start_phase(StartupPhase::StartConsensus);
         let (runtime, consensus_db, quorum_store_db) = services::start_consensus_runtime(
             &mut node_config,
             db_rw,
@@ -746,11 +784,22 @@ pub fn setup_environment_and_start_node(
             consensus_notifier,
             consensus_to_mempool_sender,
             vtxn_pool,
+            time_service,
         );
         admin_service.set_consensus_dbs(consensus_db, quorum_store_db);
+        startup_progress_reporter.complete_phase(StartupPhase::StartConsensus);
         runtime
     });
 
+    // If consensus isn't running, monitor state sync bootstrapping in the background
+    if consensus_runtime.is_none() {
+        startup_progress_reporter.skip_phase(StartupPhase::StartConsensus);
+        state_sync::complete_bootstrapping_phase_once_bootstrapped(
+            &state_sync_runtimes,
+            startup_progress_reporter,
+        );
+    }
+
     Ok(AptosHandle {
         _admin_service: admin_service,
         _api_runtime: api_runtime,
@@ -22,7 +22,7 @@ use aptos_network::{
         NetworkApplicationConfig, NetworkClientConfig, NetworkEvents, NetworkSender,
         NetworkServiceConfig,
     },
-    ProtocolId,
+    ConnectivityRequest, ProtocolId,
 };
 use aptos_network_benchmark::NetbenchMessage;
 use aptos_network_builder::builder::NetworkBuilder;
@@ -39,6 +39,8 @@ use tokio::runtime::Runtime;
 pub struct ApplicationNetworkInterfaces<T> {
     pub network_client: NetworkClient<T>,
     pub network_service_events: NetworkServiceEvents<T>,
+    // The connectivity manager of the validator network (only set for consensus)
+    pub conn_mgr_reqs_tx: Option<aptos_channels::Sender<ConnectivityRequest>>,
 }
 
 /// A simple struct that holds an individual application
@@ -237,6 +239,7 @@ pub fn setup_networks_and_get_interfaces(
     // Create each network and register the application handles
     let mut network_runtimes = vec![];
     let mut consensus_network_handle = None;
+    let mut validator_conn_mgr_reqs_tx = None;
     let mut dkg_network_handle = None;
     let mut jwk_consensus_network_handle = None;
     let mut mempool_network_handles = vec![];
@@ -267,6 +270,7 @@ pub fn setup_networks_and_get_interfaces(
             if consensus_network_handle.is_some() {
                 panic!("There can be at most one validator network!");
             } else {
+                validator_conn_mgr_reqs_tx = network_builder.conn_mgr_reqs_tx();
                 consensus_network_handle = Some(register_client_and_service_with_network(
                     &mut network_builder,
                     network_id,
@@ -348,7 +352,7 @@ pub fn setup_networks_and_get_interfaces(
 
     // Transform all network handles into application interfaces
     let (
-        consensus_interfaces,
+        mut consensus_interfaces,
         dkg_interfaces,
         jwk_consensus_interfaces,
         mempool_interfaces,
@@ -365,6 +369,11 @@ pub fn setup_networks_and_get_interfaces(
         peers_and_metadata.clone(),
     );
 
+    // Consensus uses the connectivity manager to dial validators early (e.g., before an epoch change)
+    if let Some(consensus_interfaces) = consensus_interfaces.as_mut() {
+        consensus_interfaces.conn_mgr_reqs_tx = validator_conn_mgr_reqs_tx;
+    }
+
     if !netbench_handles.is_empty() {
         let netbench_interfaces = create_network_interfaces(
             netbench_handles,
@@ -527,5 +536,6 @@ fn create_network_interfaces<
     ApplicationNetworkInterfaces {
         network_client,
         network_service_events,
+        conn_mgr_reqs_tx: None,
     }
 }
@@ -11,12 +11,18 @@ use aptos_consensus::{
 };
 use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::client::AptosDataClient;
+use aptos_data_streaming_service::{
+    stream_audit::StreamAuditLogs, stream_diagnostics::StreamDiagnostics,
+};
 use aptos_db_indexer::table_info_reader::TableInfoReader;
 use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
 use aptos_indexer_grpc_fullnode::runtime::bootstrap as bootstrap_indexer_grpc;
 use aptos_indexer_grpc_table_info::runtime::bootstrap as bootstrap_indexer_table_info;
+use aptos_inspection_service::startup_progress::StartupProgressReporter;
 use aptos_logger::{debug, telemetry_log_writer::TelemetryLog, LoggerFilterUpdater};
-use aptos_mempool::{network::MempoolSyncMsg, MempoolClientRequest, QuorumStoreRequest};
+use aptos_mempool::{
+    network::MempoolSyncMsg, MempoolClientRequest, MempoolClientSender, QuorumStoreRequest,
+};
 use aptos_mempool_notifications::MempoolNotificationListener;
 use aptos_network::application::{interface::NetworkClientInterface, storage::PeersAndMetadata};
 use aptos_network_benchmark::{run_netbench_service, NetbenchMessage};
@@ -25,6 +31,7 @@ use aptos_peer_monitoring_service_server::{
     PeerMonitoringServiceServer,
 };
 use aptos_peer_monitoring_service_types::PeerMonitoringServiceMessage;
+use aptos_state_sync_driver::sync_progress::SyncProgressReporter;
 use aptos_storage_interface::{DbReader, DbReaderWriter};
 use aptos_time_service::TimeService;
 use aptos_types::chain_id::ChainId;
@@ -37,12 +44,13 @@ const AC_SMP_CHANNEL_BUFFER_SIZE: usize = 1_024;
 const INTRA_NODE_CHANNEL_BUFFER_SIZE: usize = 1;
 
 /// Bootstraps the API and the indexer. Returns the Mempool client
-/// receiver, and both the api and indexer runtimes.
+/// sender and receiver, and both the api and indexer runtimes.
 pub fn bootstrap_api_and_indexer(
     node_config: &NodeConfig,
     db_rw: DbReaderWriter,
     chain_id: ChainId,
 ) -> anyhow::Result<(
+    MempoolClientSender,
     Receiver<MempoolClientRequest>,
     Option<Runtime>,
     Option<Runtime>,
@@ -94,10 +102,11 @@ pub fn bootstrap_api_and_indexer(
         node_config,
         chain_id,
         db_rw.reader.clone(),
-        mempool_client_sender,
+        mempool_client_sender.clone(),
     )?;
 
     Ok((
+        mempool_client_sender,
         mempool_client_receiver,
         api_runtime,
         indexer_table_info_runtime,
@@ -115,18 +124,21 @@ pub fn start_consensus_runtime(
     consensus_notifier: ConsensusNotifier,
     consensus_to_mempool_sender: Sender<QuorumStoreRequest>,
     vtxn_pool: VTxnPoolState,
+    time_service: TimeService,
 ) -> (Runtime, Arc<StorageWriteProxy>, Arc<QuorumStoreDB>) {
     let instant = Instant::now();
     let consensus = aptos_consensus::consensus_provider::start_consensus(
         node_config,
         consensus_network_interfaces.network_client,
         consensus_network_interfaces.network_service_events,
+        consensus_network_interfaces.conn_mgr_reqs_tx,
         Arc::new(consensus_notifier),
         consensus_to_mempool_sender,
         db_rw,
         consensus_reconfig_subscription
             .expect("Consensus requires a reconfiguration subscription!"),
         vtxn_pool,
+        time_service,
     );
     debug!("Consensus started in {} ms", instant.elapsed().as_millis());
     consensus
@@ -141,6 +153,7 @@ pub fn start_mempool_runtime_and_get_consensus_sender(
     mempool_listener: MempoolNotificationListener,
     mempool_client_receiver: Receiver<MempoolClientRequest>,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    time_service: TimeService,
 ) -> (Runtime, Sender<QuorumStoreRequest>) {
     // Create a communication channel between consensus and mempool
     let (consensus_to_mempool_sender, consensus_to_mempool_receiver) =
@@ -158,6 +171,7 @@ pub fn start_mempool_runtime_and_get_consensus_sender(
         mempool_listener,
         mempool_reconfig_subscription,
         peers_and_metadata,
+        time_service,
     );
     debug!("Mempool started in {} ms", instant.elapsed().as_millis());
 
@@ -174,11 +188,19 @@ pub fn start_node_inspection_service(
     node_config: &NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    stream_audit_logs: StreamAuditLogs,
+    stream_diagnostics: StreamDiagnostics,
+    sync_progress_reporter: SyncProgressReporter,
+    startup_progress_reporter: StartupProgressReporter,
 ) {
     aptos_inspection_service::start_inspection_service(
         node_config.clone(),
         aptos_data_client,
         peers_and_metadata,
+        stream_audit_logs,
+        stream_diagnostics,
+        sync_progress_reporter,
+        startup_progress_reporter,
     )
 }
 
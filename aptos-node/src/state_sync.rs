@@ -6,6 +6,8 @@ use aptos_config::config::{NodeConfig, StateSyncConfig};
 use aptos_consensus_notifications::ConsensusNotifier;
 use aptos_data_client::{client::AptosDataClient, poller};
 use aptos_data_streaming_service::{
+    stream_audit::StreamAuditLogs,
+    stream_diagnostics::StreamDiagnostics,
     streaming_client::{new_streaming_service_client_listener_pair, StreamingServiceClient},
     streaming_service::DataStreamingService,
 };
@@ -15,6 +17,8 @@ use aptos_event_notifications::{
 };
 use aptos_executor::chunk_executor::ChunkExecutor;
 use aptos_infallible::RwLock;
+use aptos_inspection_service::startup_progress::{StartupPhase, StartupProgressReporter};
+use aptos_logger::warn;
 use aptos_mempool_notifications::MempoolNotificationListener;
 use aptos_network::application::{
     interface::{NetworkClient, NetworkClientInterface, NetworkServiceEvents},
@@ -23,18 +27,21 @@ use aptos_network::application::{
 use aptos_state_sync_driver::{
     driver_factory::{DriverFactory, StateSyncRuntimes},
     metadata_storage::PersistentMetadataStorage,
+    sync_progress::SyncProgressReporter,
 };
 use aptos_storage_interface::{DbReader, DbReaderWriter};
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_storage_service_notifications::StorageServiceNotificationListener;
 use aptos_storage_service_server::{
-    network::StorageServiceNetworkEvents, storage::StorageReader, StorageServiceServer,
+    network::StorageServiceNetworkEvents, snapshot_artifact::SNAPSHOT_ARTIFACT_DIRECTORY_NAME,
+    storage::StorageReader, StorageServiceServer,
 };
-use aptos_storage_service_types::StorageServiceMessage;
+use aptos_storage_service_types::{bandwidth::BandwidthBudget, StorageServiceMessage};
 use aptos_time_service::TimeService;
 use aptos_types::waypoint::Waypoint;
 use aptos_vm::AptosVM;
-use std::sync::Arc;
+use futures::executor::block_on;
+use std::{path::Path, sync::Arc, thread};
 use tokio::runtime::Runtime;
 
 /// Creates the event subscription service and two reconfiguration
@@ -108,15 +115,21 @@ pub fn create_event_subscription_service(
     )
 }
 
-/// Sets up all state sync runtimes and return the notification endpoints
+/// Sets up all state sync runtimes and return the notification endpoints.
+/// The given time service drives the timeouts of the state sync driver,
+/// the data streaming service and the data client.
 pub fn start_state_sync_and_get_notification_handles(
     node_config: &NodeConfig,
     storage_network_interfaces: ApplicationNetworkInterfaces<StorageServiceMessage>,
     waypoint: Waypoint,
     event_subscription_service: EventSubscriptionService,
     db_rw: DbReaderWriter,
+    time_service: TimeService,
 ) -> anyhow::Result<(
     AptosDataClient,
+    StreamAuditLogs,
+    StreamDiagnostics,
+    SyncProgressReporter,
     StateSyncRuntimes,
     MempoolNotificationListener,
     ConsensusNotifier,
@@ -125,18 +138,43 @@ pub fn start_state_sync_and_get_notification_handles(
     let network_client = storage_network_interfaces.network_client;
     let network_service_events = storage_network_interfaces.network_service_events;
 
+    // Create the bandwidth budget shared by the data client and storage service
+    let bandwidth_budget = Arc::new(BandwidthBudget::new(
+        node_config
+            .state_sync
+            .aptos_data_client
+            .data_bandwidth_config,
+        TimeService::real(),
+    ));
+
     // Start the data client
     let peers_and_metadata = network_client.get_peers_and_metadata();
-    let (aptos_data_client, aptos_data_client_runtime) =
-        setup_aptos_data_client(node_config, network_client, db_rw.reader.clone())?;
+    let (aptos_data_client, aptos_data_client_runtime) = setup_aptos_data_client(
+        node_config,
+        network_client,
+        db_rw.reader.clone(),
+        bandwidth_budget.clone(),
+        time_service.clone(),
+    )?;
 
     // Start the data streaming service
-    let state_sync_config = node_config.state_sync;
-    let (streaming_service_client, streaming_service_runtime) =
-        setup_data_streaming_service(state_sync_config, aptos_data_client.clone())?;
+    let state_sync_config = node_config.state_sync.clone();
+    let (
+        streaming_service_client,
+        stream_audit_logs,
+        stream_diagnostics,
+        streaming_service_runtime,
+    ) = setup_data_streaming_service(
+        state_sync_config.clone(),
+        aptos_data_client.clone(),
+        time_service.clone(),
+    )?;
 
     // Create the chunk executor and persistent storage
-    let chunk_executor = Arc::new(ChunkExecutor::<AptosVM>::new(db_rw.clone()));
+    let chunk_executor = Arc::new(ChunkExecutor::<AptosVM>::new_with_write_set_aggregation(
+        db_rw.clone(),
+        node_config.execution.aggregate_chunk_write_sets,
+    ));
     let metadata_storage = PersistentMetadataStorage::new(&node_config.storage.dir());
 
     // Create notification senders and listeners for mempool, consensus and the storage service
@@ -158,10 +196,12 @@ pub fn start_state_sync_and_get_notification_handles(
     // Start the state sync storage service
     let storage_service_runtime = setup_state_sync_storage_service(
         state_sync_config,
+        &node_config.storage.dir(),
         peers_and_metadata,
         network_service_events,
         &db_rw,
         storage_service_listener,
+        bandwidth_budget,
     )?;
 
     // Create the state sync driver factory
@@ -178,9 +218,11 @@ pub fn start_state_sync_and_get_notification_handles(
         event_subscription_service,
         aptos_data_client.clone(),
         streaming_service_client,
-        TimeService::real(),
+        time_service,
     );
 
+    let sync_progress_reporter = state_sync.get_sync_progress_reporter();
+
     // Create a new state sync runtime handle
     let state_sync_runtimes = StateSyncRuntimes::new(
         aptos_data_client_runtime,
@@ -191,6 +233,9 @@ pub fn start_state_sync_and_get_notification_handles(
 
     Ok((
         aptos_data_client,
+        stream_audit_logs,
+        stream_diagnostics,
+        sync_progress_reporter,
         state_sync_runtimes,
         mempool_listener,
         consensus_notifier,
@@ -201,7 +246,13 @@ pub fn start_state_sync_and_get_notification_handles(
 fn setup_data_streaming_service(
     state_sync_config: StateSyncConfig,
     aptos_data_client: AptosDataClient,
-) -> anyhow::Result<(StreamingServiceClient, Runtime)> {
+    time_service: TimeService,
+) -> anyhow::Result<(
+    StreamingServiceClient,
+    StreamAuditLogs,
+    StreamDiagnostics,
+    Runtime,
+)> {
     // Create the data streaming service
     let (streaming_service_client, streaming_service_listener) =
         new_streaming_service_client_listener_pair();
@@ -210,14 +261,21 @@ fn setup_data_streaming_service(
         state_sync_config.data_streaming_service,
         aptos_data_client,
         streaming_service_listener,
-        TimeService::real(),
+        time_service,
     );
+    let stream_audit_logs = data_streaming_service.get_stream_audit_logs();
+    let stream_diagnostics = data_streaming_service.get_stream_diagnostics();
 
     // Start the data streaming service
     let streaming_service_runtime = aptos_runtimes::spawn_named_runtime("stream-serv".into(), None);
     streaming_service_runtime.spawn(data_streaming_service.start_service());
 
-    Ok((streaming_service_client, streaming_service_runtime))
+    Ok((
+        streaming_service_client,
+        stream_audit_logs,
+        stream_diagnostics,
+        streaming_service_runtime,
+    ))
 }
 
 /// Sets up the aptos data client runtime
@@ -225,6 +283,8 @@ fn setup_aptos_data_client(
     node_config: &NodeConfig,
     network_client: NetworkClient<StorageServiceMessage>,
     storage: Arc<dyn DbReader>,
+    bandwidth_budget: Arc<BandwidthBudget>,
+    time_service: TimeService,
 ) -> anyhow::Result<(AptosDataClient, Runtime)> {
     // Create the storage service client
     let storage_service_client = StorageServiceClient::new(network_client);
@@ -236,10 +296,12 @@ fn setup_aptos_data_client(
     let (aptos_data_client, data_summary_poller) = AptosDataClient::new(
         node_config.state_sync.aptos_data_client,
         node_config.base.clone(),
-        TimeService::real(),
+        time_service,
         storage,
         storage_service_client,
         Some(aptos_data_client_runtime.handle().clone()),
+        bandwidth_budget,
+        node_config.state_sync.trusted_peer_only.clone(),
     );
     aptos_data_client_runtime.spawn(poller::start_poller(data_summary_poller));
 
@@ -249,16 +311,26 @@ fn setup_aptos_data_client(
 /// Sets up the state sync storage service runtime
 fn setup_state_sync_storage_service(
     config: StateSyncConfig,
+    storage_dir: &Path,
     peers_and_metadata: Arc<PeersAndMetadata>,
     network_service_events: NetworkServiceEvents<StorageServiceMessage>,
     db_rw: &DbReaderWriter,
     storage_service_listener: StorageServiceNotificationListener,
+    bandwidth_budget: Arc<BandwidthBudget>,
 ) -> anyhow::Result<Runtime> {
     // Create a new state sync storage service runtime
     let storage_service_runtime = aptos_runtimes::spawn_named_runtime("stor-server".into(), None);
 
     // Spawn the state sync storage service servers on the runtime
-    let storage_reader = StorageReader::new(config.storage_service, Arc::clone(&db_rw.reader));
+    let mut storage_reader = StorageReader::new(config.storage_service, Arc::clone(&db_rw.reader));
+    if config
+        .storage_service
+        .snapshot_artifact_config
+        .enable_snapshot_artifacts
+    {
+        storage_reader = storage_reader
+            .with_snapshot_artifact_store(storage_dir.join(SNAPSHOT_ARTIFACT_DIRECTORY_NAME));
+    }
     let service = StorageServiceServer::new(
         config,
         storage_service_runtime.handle().clone(),
@@ -267,8 +339,30 @@ fn setup_state_sync_storage_service(
         peers_and_metadata,
         StorageServiceNetworkEvents::new(network_service_events),
         storage_service_listener,
+        bandwidth_budget,
     );
     storage_service_runtime.spawn(service.start());
 
     Ok(storage_service_runtime)
 }
+
+/// Spawns a thread that marks the state sync bootstrapping phase as complete
+/// once state sync has bootstrapped. This is only required for nodes that do
+/// not block on state sync initialization during startup (e.g., fullnodes).
+pub fn complete_bootstrapping_phase_once_bootstrapped(
+    state_sync_runtimes: &StateSyncRuntimes,
+    startup_progress_reporter: StartupProgressReporter,
+) {
+    let state_sync_client = state_sync_runtimes.create_driver_client();
+    thread::spawn(
+        move || match block_on(state_sync_client.notify_once_bootstrapped()) {
+            Ok(()) => {
+                startup_progress_reporter.complete_phase(StartupPhase::StateSyncBootstrapping)
+            },
+            Err(error) => warn!(
+                "Failed to wait for state sync bootstrapping to complete! Error: {:?}",
+                error
+            ),
+        },
+    );
+}
@@ -4,7 +4,10 @@
 use anyhow::{anyhow, Result};
 use aptos_backup_service::start_backup_service;
 use aptos_config::{config::NodeConfig, utils::get_genesis_txn};
-use aptos_db::{fast_sync_storage_wrapper::FastSyncStorageWrapper, AptosDB};
+use aptos_db::{
+    disk_space_monitor::start_disk_space_monitor,
+    fast_sync_storage_wrapper::FastSyncStorageWrapper, AptosDB,
+};
 use aptos_executor::db_bootstrapper::maybe_bootstrap;
 use aptos_logger::{debug, info};
 use aptos_storage_interface::{DbReader, DbReaderWriter};
@@ -93,7 +96,7 @@ pub(crate) fn bootstrap_db(
         &node_config.storage.dir(),
         false, /* readonly */
         node_config.storage.storage_pruner_config,
-        node_config.storage.rocksdb_configs,
+        node_config.storage.rocksdb_configs.clone(),
         node_config.storage.enable_indexer,
         node_config.storage.buffered_state_target_items,
         node_config.storage.max_num_nodes_per_lru_cache_shard,
@@ -163,6 +166,12 @@ pub fn initialize_database_and_checkpoints(
         instant.elapsed().as_millis()
     );
 
+    // Start monitoring the available disk space
+    start_disk_space_monitor(
+        node_config.storage.disk_space_guardrails_config,
+        node_config.storage.get_dir_paths().all_root_paths(),
+    );
+
     Ok((
         db_rw,
         backup_service,
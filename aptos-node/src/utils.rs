@@ -3,7 +3,10 @@
 
 use anyhow::anyhow;
 use aptos_config::config::{NodeConfig, DEFAULT_CONCURRENCY_LEVEL};
+use aptos_executor::components::speculative_state::set_speculative_state_config_once;
+use aptos_executor_types::memory_budget::MemoryBudget;
 use aptos_storage_interface::{state_view::LatestDbStateCheckpointView, DbReaderWriter};
+use aptos_time_service::TimeService;
 use aptos_types::{
     account_config::CORE_CODE_ADDRESS, account_view::AccountView, chain_id::ChainId,
     state_store::account_with_state_view::AsAccountWithStateView,
@@ -33,6 +36,29 @@ pub fn create_global_rayon_pool(create_global_rayon_pool: bool) {
     }
 }
 
+/// Creates the time service shared by consensus, mempool and state sync. If the
+/// node is configured to use a mock time service, time only advances when
+/// requested (via the admin service).
+pub fn create_time_service(node_config: &NodeConfig) -> anyhow::Result<TimeService> {
+    if !node_config.node_startup.use_mock_time_service {
+        return Ok(TimeService::real());
+    }
+
+    #[cfg(feature = "smoke-test")]
+    {
+        // Start the simulated time at the current time (so on-chain timestamps remain sensible)
+        let start_time = aptos_infallible::duration_since_epoch();
+        Ok(TimeService::from_mock(
+            aptos_time_service::MockTimeService::new_with_start_time(start_time),
+        ))
+    }
+
+    #[cfg(not(feature = "smoke-test"))]
+    Err(anyhow!(
+        "[aptos-node] the mock time service is only supported by smoke-test builds!"
+    ))
+}
+
 /// Fetches the chain ID from on-chain resources
 pub fn fetch_chain_id(db: &DbReaderWriter) -> anyhow::Result<ChainId> {
     let db_state_view = db
@@ -68,3 +94,16 @@ pub fn set_aptos_vm_configurations(node_config: &NodeConfig) {
         AptosVM::set_processed_transactions_detailed_counters();
     }
 }
+
+/// Sets the memory budget shared by the execution pipeline (i.e., the
+/// executor, quorum store and state sync), and the speculative state
+/// eviction policy of the executor, based on the node configurations
+pub fn set_memory_budget_configurations(node_config: &NodeConfig) {
+    let memory_budget_config = node_config.execution.memory_budget;
+    MemoryBudget::set_global_once(MemoryBudget::new(
+        memory_budget_config.enable_memory_budget,
+        memory_budget_config.target_rss_bytes,
+        memory_budget_config.backpressure_threshold_percent,
+    ));
+    set_speculative_state_config_once(node_config.execution.speculative_state);
+}
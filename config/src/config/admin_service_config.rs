@@ -20,6 +20,9 @@ pub struct AdminServiceConfig {
     pub port: u16,
     // If empty, will allow all requests without authentication. (Not allowed on mainnet.)
     pub authentication_configs: Vec<AuthenticationConfig>,
+    // The maximum duration (in seconds) for which a debug toggle can be enabled.
+    // Debug toggles automatically revert once this duration has passed.
+    pub max_debug_toggle_duration_secs: u64,
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -44,6 +47,7 @@ impl Default for AdminServiceConfig {
             address: "0.0.0.0".to_string(),
             port: 9102,
             authentication_configs: vec![],
+            max_debug_toggle_duration_secs: 3600, // 1 hour
         }
     }
 }
@@ -12,7 +12,7 @@ use crate::{
 };
 use aptos_types::{account_address::AccountAddress, chain_id::ChainId};
 use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
+use std::{collections::BTreeMap, net::SocketAddr};
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
@@ -40,6 +40,10 @@ pub struct ApiConfig {
     /// Enables BCS output of APIs that support it
     #[serde(default = "default_enabled")]
     pub bcs_output_enabled: bool,
+    /// Enables protobuf output of APIs that support it (i.e., transactions and blocks).
+    /// Note: table items can only be rendered if the table info indexer is enabled.
+    #[serde(default = "default_disabled")]
+    pub protobuf_output_enabled: bool,
     /// Enables encode submission API
     #[serde(default = "default_enabled")]
     pub encode_submission_enabled: bool,
@@ -81,6 +85,10 @@ pub struct ApiConfig {
     pub view_filter: ViewFilter,
     /// Periodically log stats for view function and simulate transaction usage
     pub periodic_function_stats_sec: Option<u64>,
+    /// Configuration for the compression of API responses
+    pub compression: ApiCompressionConfig,
+    /// Configuration for the submit and wait transaction API
+    pub submit_and_wait: SubmitAndWaitConfig,
 }
 
 const DEFAULT_ADDRESS: &str = "127.0.0.1";
@@ -112,6 +120,7 @@ impl Default for ApiConfig {
             content_length_limit: None,
             failpoints_enabled: default_disabled(),
             bcs_output_enabled: default_enabled(),
+            protobuf_output_enabled: default_disabled(),
             json_output_enabled: default_enabled(),
             encode_submission_enabled: default_enabled(),
             transaction_submission_enabled: default_enabled(),
@@ -129,6 +138,8 @@ impl Default for ApiConfig {
             simulation_filter: Filter::default(),
             view_filter: ViewFilter::default(),
             periodic_function_stats_sec: Some(60),
+            compression: ApiCompressionConfig::default(),
+            submit_and_wait: SubmitAndWaitConfig::default(),
         }
     }
 }
@@ -188,6 +199,29 @@ impl ConfigSanitizer for ApiConfig {
             }
         }
 
+        // Verify that the compression levels are valid
+        let compression_config = &api_config.compression;
+        if compression_config.gzip_level > MAX_GZIP_COMPRESSION_LEVEL {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The gzip compression level must be <= {}!",
+                    MAX_GZIP_COMPRESSION_LEVEL
+                ),
+            ));
+        }
+        if !(MIN_ZSTD_COMPRESSION_LEVEL..=MAX_ZSTD_COMPRESSION_LEVEL)
+            .contains(&compression_config.zstd_level)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The zstd compression level must be between {} and {}!",
+                    MIN_ZSTD_COMPRESSION_LEVEL, MAX_ZSTD_COMPRESSION_LEVEL
+                ),
+            ));
+        }
+
         // Sanitize the gas estimation config
         GasEstimationConfig::sanitize(node_config, node_type, chain_id)?;
 
@@ -195,6 +229,96 @@ impl ConfigSanitizer for ApiConfig {
     }
 }
 
+const MAX_GZIP_COMPRESSION_LEVEL: u32 = 9;
+const MIN_ZSTD_COMPRESSION_LEVEL: i32 = 1;
+const MAX_ZSTD_COMPRESSION_LEVEL: i32 = 22;
+
+/// The config for compressing API responses (using gzip or zstd, as
+/// negotiated with the client via the `Accept-Encoding` header).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ApiCompressionConfig {
+    /// Enables the compression of API responses
+    pub enabled: bool,
+    /// The gzip compression level (0 to 9)
+    pub gzip_level: u32,
+    /// The maximum number of responses that can be compressed concurrently.
+    /// If this budget is exhausted, responses are sent uncompressed (to bound
+    /// the CPU spent on compression).
+    pub max_concurrent_compressions: u64,
+    /// The maximum size (bytes) of responses to compress. Larger responses
+    /// are sent uncompressed (to bound the CPU spent on a single response).
+    pub max_response_size_bytes: u64,
+    /// The minimum size (bytes) of responses to compress. Smaller responses
+    /// are sent uncompressed (as the savings are negligible).
+    pub min_response_size_bytes: u64,
+    /// Per-route overrides for the minimum response size (bytes) to compress,
+    /// keyed by route path prefix (e.g., "/v1/accounts"). If multiple prefixes
+    /// match a route, the longest prefix is used.
+    pub route_min_response_size_bytes: BTreeMap<String, u64>,
+    /// The zstd compression level (1 to 22)
+    pub zstd_level: i32,
+}
+
+impl Default for ApiCompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            gzip_level: 6,
+            max_concurrent_compressions: 16,
+            max_response_size_bytes: 64 * 1024 * 1024, // 64 MiB
+            min_response_size_bytes: 2 * 1024,         // 2 KiB
+            route_min_response_size_bytes: BTreeMap::new(),
+            zstd_level: 3,
+        }
+    }
+}
+
+impl ApiCompressionConfig {
+    /// Returns the minimum response size (bytes) to compress for the given
+    /// route path (using the longest matching route override, if any).
+    pub fn get_min_response_size_bytes(&self, path: &str) -> u64 {
+        self.route_min_response_size_bytes
+            .iter()
+            .filter(|(route_prefix, _)| path.starts_with(route_prefix.as_str()))
+            .max_by_key(|(route_prefix, _)| route_prefix.len())
+            .map(|(_, min_response_size_bytes)| *min_response_size_bytes)
+            .unwrap_or(self.min_response_size_bytes)
+    }
+}
+
+/// The config for the submit and wait transaction API (which simulates,
+/// submits and waits for the commitment of a transaction in a single call).
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SubmitAndWaitConfig {
+    /// Enables the submit and wait API
+    pub enabled: bool,
+    /// The default timeout (ms) for simulating the transaction
+    pub default_simulation_timeout_ms: u64,
+    /// The default timeout (ms) for submitting the transaction to mempool
+    pub default_submission_timeout_ms: u64,
+    /// The default timeout (ms) for waiting for the transaction to commit
+    pub default_wait_timeout_ms: u64,
+    /// The maximum timeout (ms) that can be requested for any step
+    pub max_step_timeout_ms: u64,
+    /// The interval (ms) at which to check if the transaction has committed
+    pub wait_poll_interval_ms: u64,
+}
+
+impl Default for SubmitAndWaitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_simulation_timeout_ms: 5_000, // 5 seconds
+            default_submission_timeout_ms: 5_000, // 5 seconds
+            default_wait_timeout_ms: 20_000,      // 20 seconds
+            max_step_timeout_ms: 60_000,          // 60 seconds
+            wait_poll_interval_ms: 100,
+        }
+    }
+}
+
 // This is necessary because we can't import the EntryFunctionId type from the API types.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -303,4 +427,58 @@ mod tests {
                 .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_invalid_compression_levels() {
+        for (gzip_level, zstd_level) in [(10, 3), (6, 0), (6, 23)] {
+            // Create a node config with invalid compression levels
+            let node_config = NodeConfig {
+                api: ApiConfig {
+                    enabled: true,
+                    compression: ApiCompressionConfig {
+                        enabled: true,
+                        gzip_level,
+                        zstd_level,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            };
+
+            // Sanitize the config and verify that it fails
+            let error =
+                ApiConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                    .unwrap_err();
+            assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+        }
+    }
+
+    #[test]
+    fn test_compression_route_min_response_sizes() {
+        // Create a compression config with route overrides
+        let compression_config = ApiCompressionConfig {
+            min_response_size_bytes: 100,
+            route_min_response_size_bytes: BTreeMap::from([
+                ("/v1/accounts".into(), 10),
+                ("/v1/accounts/0x1/resources".into(), 1),
+            ]),
+            ..Default::default()
+        };
+
+        // Verify the minimum response sizes for different routes
+        assert_eq!(compression_config.get_min_response_size_bytes("/v1"), 100);
+        assert_eq!(
+            compression_config.get_min_response_size_bytes("/v1/transactions"),
+            100
+        );
+        assert_eq!(
+            compression_config.get_min_response_size_bytes("/v1/accounts/0x1/modules"),
+            10
+        );
+        assert_eq!(
+            compression_config.get_min_response_size_bytes("/v1/accounts/0x1/resources"),
+            1
+        );
+    }
 }
@@ -194,6 +194,14 @@ fn sanitize_validator_network_config(
                 "Mutual authentication must be enabled for the validator network!".into(),
             ));
         }
+
+        // Ensure that port mapping is disabled (validators should not be behind a NAT)
+        if validator_network_config.port_mapping.enable {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "Port mapping cannot be enabled for the validator network!".into(),
+            ));
+        }
     }
 
     Ok(())
@@ -203,7 +211,7 @@ fn sanitize_validator_network_config(
 mod tests {
     use super::*;
     use crate::{
-        config::{node_startup_config::NodeStartupConfig, NetworkConfig},
+        config::{node_startup_config::NodeStartupConfig, NetworkConfig, PortMappingConfig},
         network_id::NetworkId,
     };
 
@@ -383,6 +391,32 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_validator_port_mapping() {
+        // Create a validator config with port mapping enabled
+        let node_config = NodeConfig {
+            validator_network: Some(NetworkConfig {
+                network_id: NetworkId::Validator,
+                mutual_authentication: true,
+                port_mapping: PortMappingConfig {
+                    enable: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = sanitize_validator_network_config(
+            &node_config,
+            NodeType::Validator,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     #[test]
     fn test_sanitize_validator_incorrect_network_id() {
         // Create a validator config with the wrong network ID
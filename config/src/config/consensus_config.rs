@@ -67,6 +67,14 @@ pub struct ConsensusConfig {
     pub max_blocks_per_sending_request_quorum_store_override: u64,
     pub max_blocks_per_receiving_request: u64,
     pub max_blocks_per_receiving_request_quorum_store_override: u64,
+    // Whether to start executing proposed blocks as soon as they are received (i.e., before
+    // they are ordered). The results are reused if the blocks are ordered, and discarded
+    // otherwise. This is only supported when randomness is disabled.
+    pub enable_optimistic_execution: bool,
+    // Whether to prepare the next epoch (i.e., keep the next epoch state, dial the incoming
+    // validators and warm up the executor caches) as soon as the block that ends the current
+    // epoch has been executed, instead of waiting for the reconfiguration to commit.
+    pub enable_epoch_warmup: bool,
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
@@ -279,6 +287,8 @@ impl Default for ConsensusConfig {
             max_blocks_per_sending_request_quorum_store_override: 10,
             max_blocks_per_receiving_request: 10,
             max_blocks_per_receiving_request_quorum_store_override: 100,
+            enable_optimistic_execution: false,
+            enable_epoch_warmup: false,
         }
     }
 }
@@ -43,6 +43,17 @@ pub struct ExecutionConfig {
     pub transaction_filter: Filter,
     /// Used during DB bootstrapping
     pub genesis_waypoint: Option<WaypointConfig>,
+    /// Enables aggregating the state updates of all transactions in a synced chunk
+    /// (keeping only the last write per key) before they are committed to storage.
+    /// This reduces the state KV write volume, but state values are only guaranteed
+    /// to be available at chunk boundaries (and not at every version in the chunk).
+    pub aggregate_chunk_write_sets: bool,
+    /// The memory budget shared by the execution pipeline (i.e., the executor,
+    /// quorum store and state sync), used to apply coordinated backpressure.
+    pub memory_budget: MemoryBudgetConfig,
+    /// The eviction policy for the speculative state retained across uncommitted
+    /// blocks (e.g., when the commit stage lags during long consensus stalls).
+    pub speculative_state: SpeculativeStateConfig,
 }
 
 impl std::fmt::Debug for ExecutionConfig {
@@ -75,6 +86,52 @@ impl Default for ExecutionConfig {
             processed_transactions_detailed_counters: false,
             transaction_filter: Filter::empty(),
             genesis_waypoint: None,
+            aggregate_chunk_write_sets: false,
+            memory_budget: MemoryBudgetConfig::default(),
+            speculative_state: SpeculativeStateConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct MemoryBudgetConfig {
+    /// Whether or not the memory budget is enabled. If disabled, large
+    /// allocations are still tracked, but backpressure is never applied.
+    pub enable_memory_budget: bool,
+    /// The RSS (resident set size) the node should stay below
+    pub target_rss_bytes: u64,
+    /// The percentage of the target RSS at which backpressure is applied
+    pub backpressure_threshold_percent: u64,
+}
+
+impl Default for MemoryBudgetConfig {
+    fn default() -> Self {
+        Self {
+            enable_memory_budget: false,
+            target_rss_bytes: 32 * 1024 * 1024 * 1024, // 32 GiB
+            backpressure_threshold_percent: 90,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpeculativeStateConfig {
+    /// Whether or not to evict speculative state once the limit is exceeded. If
+    /// disabled, speculative state is still accounted for (and reported). Note:
+    /// only cached state is evicted, uncommitted blocks are never dropped.
+    pub enable_eviction: bool,
+    /// The maximum (estimated) bytes of speculative state retained across all
+    /// uncommitted blocks, before cached state is flushed.
+    pub max_speculative_state_bytes: u64,
+}
+
+impl Default for SpeculativeStateConfig {
+    fn default() -> Self {
+        Self {
+            enable_eviction: false,
+            max_speculative_state_bytes: 8 * 1024 * 1024 * 1024, // 8 GiB
         }
     }
 }
@@ -165,6 +222,22 @@ impl ConfigSanitizer for ExecutionConfig {
             }
         }
 
+        // Verify the memory budget config
+        let memory_budget_config = &execution_config.memory_budget;
+        if memory_budget_config.enable_memory_budget
+            && (memory_budget_config.target_rss_bytes == 0
+                || memory_budget_config.backpressure_threshold_percent == 0
+                || memory_budget_config.backpressure_threshold_percent > 100)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The memory budget target RSS must be non-zero, and the backpressure threshold must be in (0, 100]! Config: {:?}",
+                    memory_budget_config
+                ),
+            ));
+        }
+
         Ok(())
     }
 }
@@ -233,6 +306,26 @@ mod test {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_invalid_memory_budget() {
+        // Create a node config with an invalid memory budget threshold
+        let node_config = NodeConfig {
+            execution: ExecutionConfig {
+                memory_budget: MemoryBudgetConfig {
+                    enable_memory_budget: true,
+                    backpressure_threshold_percent: 101,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Sanitize the config and verify that it fails
+        let error = ExecutionConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     #[test]
     fn test_no_genesis() {
         let (mut config, path) = generate_config();
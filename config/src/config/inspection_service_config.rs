@@ -19,7 +19,14 @@ pub struct InspectionServiceConfig {
     pub port: u16,
     pub expose_configuration: bool,
     pub expose_peer_information: bool,
+    pub expose_startup_progress: bool,
+    pub expose_stream_audit_logs: bool,
+    pub expose_stream_diagnostics: bool,
+    pub expose_sync_progress: bool,
     pub expose_system_information: bool,
+    pub max_metrics_per_family: Option<u64>, // The max metrics (label combinations) per family
+    pub metrics_allowlist: Vec<String>,      // Metric family prefixes to expose (all if empty)
+    pub metrics_denylist: Vec<String>,       // Metric family prefixes to hide (takes priority)
 }
 
 impl Default for InspectionServiceConfig {
@@ -29,7 +36,14 @@ impl Default for InspectionServiceConfig {
             port: 9101,
             expose_configuration: false,
             expose_peer_information: true,
+            expose_startup_progress: true,
+            expose_stream_audit_logs: true,
+            expose_stream_diagnostics: true,
+            expose_sync_progress: true,
             expose_system_information: true,
+            max_metrics_per_family: None,
+            metrics_allowlist: vec![],
+            metrics_denylist: vec![],
         }
     }
 }
@@ -38,6 +52,20 @@ impl InspectionServiceConfig {
     pub fn randomize_ports(&mut self) {
         self.port = utils::get_available_port();
     }
+
+    /// Returns true iff the given metric family should be exposed
+    pub fn is_metric_family_exposed(&self, metric_family_name: &str) -> bool {
+        let is_allowed = self.metrics_allowlist.is_empty()
+            || self
+                .metrics_allowlist
+                .iter()
+                .any(|prefix| metric_family_name.starts_with(prefix.as_str()));
+        let is_denied = self
+            .metrics_denylist
+            .iter()
+            .any(|prefix| metric_family_name.starts_with(prefix.as_str()));
+        is_allowed && !is_denied
+    }
 }
 
 impl ConfigSanitizer for InspectionServiceConfig {
@@ -201,6 +229,24 @@ mod tests {
         assert!(node_config.inspection_service.expose_system_information);
     }
 
+    #[test]
+    fn test_metric_family_exposure() {
+        // Verify that all metric families are exposed by default
+        let mut inspection_service_config = InspectionServiceConfig::default();
+        assert!(inspection_service_config.is_metric_family_exposed("aptos_consensus_round"));
+        assert!(inspection_service_config.is_metric_family_exposed("aptos_storage_latency"));
+
+        // Add an allowlist and verify only the allowed families are exposed
+        inspection_service_config.metrics_allowlist = vec!["aptos_consensus".into()];
+        assert!(inspection_service_config.is_metric_family_exposed("aptos_consensus_round"));
+        assert!(!inspection_service_config.is_metric_family_exposed("aptos_storage_latency"));
+
+        // Add a denylist and verify that it overrides the allowlist
+        inspection_service_config.metrics_denylist = vec!["aptos_consensus_round".into()];
+        assert!(!inspection_service_config.is_metric_family_exposed("aptos_consensus_round"));
+        assert!(inspection_service_config.is_metric_family_exposed("aptos_consensus_timeout"));
+    }
+
     #[test]
     fn test_sanitize_valid_service_config() {
         // Create an inspection service config with the configuration endpoint enabled
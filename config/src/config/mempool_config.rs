@@ -53,6 +53,26 @@ pub struct MempoolConfig {
     pub broadcast_buckets: Vec<u64>,
     pub eager_expire_threshold_ms: Option<u64>,
     pub eager_expire_time_ms: u64,
+    /// Maximum number of provenances (i.e., upstream peers and API clients) to track stats for
+    pub max_tracked_provenances: usize,
+    /// Minimum number of completed transactions before a provenance is assigned a reputation
+    pub provenance_reputation_min_samples: u64,
+    /// If set, evictions prefer transactions from provenances whose reputation (i.e., the
+    /// percentage of their transactions that committed) is below this threshold.
+    pub provenance_eviction_reputation_threshold_pct: Option<u64>,
+    /// Number of eviction candidates to sample when weighting evictions by provenance
+    pub provenance_eviction_num_candidates: usize,
+    /// Whether to deduplicate the transactions received on multiple networks (e.g., the
+    /// validator and public networks). If enabled, transactions already in mempool (from
+    /// another network) are not revalidated, nor broadcast back to the peers that sent them.
+    pub enable_cross_network_dedup: bool,
+    /// Whether to validate (and accept) incoming transactions. If disabled, all incoming
+    /// transactions are rejected without validation (e.g., for nodes with filtered state,
+    /// where validation would read missing state).
+    pub enable_transaction_validation: bool,
+    /// Whether to remove the transactions invalidated by on-chain key rotations. Rotations are
+    /// detected (off the commit path) by comparing the authentication keys of committed senders.
+    pub enable_key_rotation_eviction: bool,
 }
 
 impl Default for MempoolConfig {
@@ -77,6 +97,13 @@ impl Default for MempoolConfig {
             broadcast_buckets: DEFAULT_BUCKETS.to_vec(),
             eager_expire_threshold_ms: Some(10_000),
             eager_expire_time_ms: 3_000,
+            max_tracked_provenances: 1_000,
+            provenance_reputation_min_samples: 100,
+            provenance_eviction_reputation_threshold_pct: None,
+            provenance_eviction_num_candidates: 4,
+            enable_cross_network_dedup: true,
+            enable_transaction_validation: true,
+            enable_key_rotation_eviction: true,
         }
     }
 }
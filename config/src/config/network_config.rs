@@ -26,6 +26,7 @@ use std::{
     collections::{HashMap, HashSet},
     convert::TryFrom,
     fmt,
+    net::Ipv4Addr,
     path::PathBuf,
     string::ToString,
 };
@@ -127,6 +128,8 @@ pub struct NetworkConfig {
     pub max_parallel_deserialization_tasks: Option<usize>,
     /// Whether or not to enable latency aware peer dialing
     pub enable_latency_aware_dialing: bool,
+    /// Port mapping (NAT traversal) configuration for the listen address
+    pub port_mapping: PortMappingConfig,
 }
 
 impl Default for NetworkConfig {
@@ -169,6 +172,7 @@ impl NetworkConfig {
             outbound_tx_buffer_size_bytes: None,
             max_parallel_deserialization_tasks: None,
             enable_latency_aware_dialing: true,
+            port_mapping: PortMappingConfig::default(),
         };
 
         // Configure the number of parallel deserialization tasks
@@ -392,6 +396,47 @@ impl Default for RateLimitConfig {
     }
 }
 
+/// The protocols that can be used to map the listen port on the local NAT gateway
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PortMappingProtocol {
+    NatPmp, // NAT Port Mapping Protocol (RFC 6886)
+    Upnp,   // UPnP Internet Gateway Device (IGD) protocol
+}
+
+/// Configuration for mapping the listen port on the local NAT gateway (e.g., a home
+/// router), so that nodes behind a NAT can be dialed by other peers without manual
+/// router configuration. The discovered external address is advertised by the node.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PortMappingConfig {
+    /// Whether or not to attempt to map the listen port on the NAT gateway
+    pub enable: bool,
+    /// The protocols to attempt (in order), until a mapping is created
+    pub protocols: Vec<PortMappingProtocol>,
+    /// The address of the NAT gateway for NAT-PMP. If not set, the default gateway is used.
+    pub gateway_address: Option<Ipv4Addr>,
+    /// The requested lifetime of each port mapping (the mapping is refreshed at half-life)
+    pub mapping_lifetime_secs: u32,
+    /// The interval at which to retry port mapping (after a failed attempt)
+    pub retry_interval_secs: u64,
+    /// The timeout of each request sent to the NAT gateway
+    pub request_timeout_ms: u64,
+}
+
+impl Default for PortMappingConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            protocols: vec![PortMappingProtocol::NatPmp, PortMappingProtocol::Upnp],
+            gateway_address: None,
+            mapping_lifetime_secs: 7200, // 2 hours
+            retry_interval_secs: 300,    // 5 minutes
+            request_timeout_ms: 2000,
+        }
+    }
+}
+
 pub type PeerSet = HashMap<PeerId, Peer>;
 
 // TODO: Combine with RoleType?
@@ -8,6 +8,10 @@ use serde::{Deserialize, Serialize};
 pub struct NodeStartupConfig {
     pub skip_config_optimizer: bool, // Whether or not to skip the config optimizer at startup
     pub skip_config_sanitizer: bool, // Whether or not to skip the config sanitizer at startup
+    // Whether or not to use a mock time service for consensus, mempool and state sync. This
+    // is only supported by test builds (i.e., with the `smoke-test` feature), and time only
+    // advances when requested via the admin service (e.g., for deterministic timeout tests).
+    pub use_mock_time_service: bool,
 }
 
 #[allow(clippy::derivable_impls)] // Derive default manually (this is safer than guessing defaults)
@@ -16,6 +20,7 @@ impl Default for NodeStartupConfig {
         Self {
             skip_config_optimizer: false,
             skip_config_sanitizer: false,
+            use_mock_time_service: false,
         }
     }
 }
@@ -29,8 +34,9 @@ mod test {
         // Create the default config
         let config = NodeStartupConfig::default();
 
-        // Verify both fields are set to false
+        // Verify all fields are set to false
         assert!(!config.skip_config_optimizer);
         assert!(!config.skip_config_sanitizer);
+        assert!(!config.use_mock_time_service);
     }
 }
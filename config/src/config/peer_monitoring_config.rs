@@ -23,6 +23,7 @@ pub struct PeerMonitoringServiceConfig {
     pub node_monitoring: NodeMonitoringConfig,
     pub peer_monitor_interval_usec: u64, // The interval (usec) between peer monitor executions
     pub performance_monitoring: PerformanceMonitoringConfig,
+    pub upstream_selection: UpstreamSelectionConfig,
 }
 
 impl Default for PeerMonitoringServiceConfig {
@@ -39,6 +40,7 @@ impl Default for PeerMonitoringServiceConfig {
             node_monitoring: NodeMonitoringConfig::default(),
             peer_monitor_interval_usec: 1_000_000, // 1 second
             performance_monitoring: PerformanceMonitoringConfig::default(),
+            upstream_selection: UpstreamSelectionConfig::default(),
         }
     }
 }
@@ -125,6 +127,33 @@ impl Default for NodeMonitoringConfig {
     }
 }
 
+/// The config for the automatic selection of preferred upstream peers. This
+/// is only used by PFNs (the preferred upstream peers are prioritized for
+/// mempool forwarding and state sync).
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UpstreamSelectionConfig {
+    pub enable_upstream_selection: bool, // Whether or not to select upstream peers by measured quality
+    pub max_ledger_lag_secs: u64, // Max ledger lag (secs) behind the freshest upstream peer before a peer is unselected
+    pub min_latency_improvement_percent: u64, // Min latency improvement (%) required to replace a selected peer
+    pub min_selection_duration_ms: u64, // Min duration (ms) a peer stays selected before it can be replaced
+    pub num_preferred_upstream_peers: u64, // The number of preferred upstream peers to select
+    pub rebalance_interval_ms: u64,     // The interval (ms) between upstream peer rebalances
+}
+
+impl Default for UpstreamSelectionConfig {
+    fn default() -> Self {
+        Self {
+            enable_upstream_selection: true,
+            max_ledger_lag_secs: 10,
+            min_latency_improvement_percent: 25,
+            min_selection_duration_ms: 300_000, // 5 minutes
+            num_preferred_upstream_peers: 3,
+            rebalance_interval_ms: 30_000, // 30 seconds
+        }
+    }
+}
+
 impl ConfigSanitizer for PeerMonitoringServiceConfig {
     fn sanitize(
         node_config: &NodeConfig,
@@ -24,6 +24,7 @@ use std::{
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct SafetyRulesConfig {
+    pub audit_log: SafetyRulesAuditLogConfig,
     pub backend: SecureBackend,
     pub logger: LoggerConfig,
     pub service: SafetyRulesService,
@@ -37,6 +38,7 @@ pub struct SafetyRulesConfig {
 impl Default for SafetyRulesConfig {
     fn default() -> Self {
         Self {
+            audit_log: SafetyRulesAuditLogConfig::default(),
             backend: SecureBackend::InMemoryStorage,
             logger: LoggerConfig::default(),
             service: SafetyRulesService::Local,
@@ -51,6 +53,7 @@ impl Default for SafetyRulesConfig {
 
 impl SafetyRulesConfig {
     pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        self.audit_log.set_data_dir(data_dir.clone());
         if let SecureBackend::OnDiskStorage(backend) = &mut self.backend {
             backend.set_data_dir(data_dir);
         }
@@ -83,6 +86,40 @@ impl ConfigSanitizer for SafetyRulesConfig {
             return Ok(());
         }
 
+        // Verify that remote safety rules processes are only reachable locally
+        if let SafetyRulesService::Process(remote_service) = &safety_rules_config.service {
+            if !remote_service.is_loopback() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The safety rules process must listen on a loopback address! Given address: {:?}",
+                        remote_service.server_address
+                    ),
+                ));
+            }
+            if remote_service.max_message_size_bytes == 0
+                || remote_service.request_latency_budget_ms == 0
+            {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The safety rules process message size limit and latency budget must be non-zero!"
+                        .to_string(),
+                ));
+            }
+        }
+
+        // Verify that the audit log rotation limits are valid
+        let audit_log_config = &safety_rules_config.audit_log;
+        if audit_log_config.enabled
+            && (audit_log_config.max_file_size_bytes == 0 || audit_log_config.max_num_files == 0)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The safety rules audit log file size and file count limits must be non-zero!"
+                    .to_string(),
+            ));
+        }
+
         if let Some(chain_id) = chain_id {
             // Verify that the secure backend is appropriate for mainnet validators
             if chain_id.is_mainnet()
@@ -97,10 +134,14 @@ impl ConfigSanitizer for SafetyRulesConfig {
             }
 
             // Verify that the safety rules service is set to local for optimal performance
-            if chain_id.is_mainnet() && !safety_rules_config.service.is_local() {
+            // (unless the operator requires signing isolation via an authenticated process).
+            if chain_id.is_mainnet()
+                && !safety_rules_config.service.is_local()
+                && !safety_rules_config.service.is_authenticated_process()
+            {
                 return Err(Error::ConfigSanitizerFailed(
                     sanitizer_name,
-                    format!("The safety rules service should be set to local in mainnet for optimal performance! Given config: {:?}", &safety_rules_config.service)
+                    format!("The safety rules service should be set to local (or an authenticated process) in mainnet! Given config: {:?}", &safety_rules_config.service)
                 ));
             }
 
@@ -117,6 +158,49 @@ impl ConfigSanitizer for SafetyRulesConfig {
     }
 }
 
+/// The config for the append-only (hash-chained) audit log that records
+/// every signing decision made by safety rules.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SafetyRulesAuditLogConfig {
+    /// Whether or not to record signing decisions in the audit log
+    pub enabled: bool,
+    /// The directory of the audit log files (relative paths use the data dir)
+    pub path: PathBuf,
+    /// Maximum size (bytes) of an audit log file before it is rotated
+    pub max_file_size_bytes: u64,
+    /// Maximum number of audit log files to keep (including the active file)
+    pub max_num_files: u64,
+    #[serde(skip)]
+    data_dir: PathBuf,
+}
+
+impl Default for SafetyRulesAuditLogConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: PathBuf::from("safety_rules_audit_log"),
+            max_file_size_bytes: 100 * 1024 * 1024, // 100 MiB
+            max_num_files: 10,
+            data_dir: PathBuf::new(),
+        }
+    }
+}
+
+impl SafetyRulesAuditLogConfig {
+    pub fn path(&self) -> PathBuf {
+        if self.path.is_relative() {
+            self.data_dir.join(&self.path)
+        } else {
+            self.path.clone()
+        }
+    }
+
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        self.data_dir = data_dir;
+    }
+}
+
 // TODO: Find a cleaner way so WaypointConfig isn't duplicated
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -182,15 +266,53 @@ impl SafetyRulesService {
     fn is_local(&self) -> bool {
         matches!(self, SafetyRulesService::Local)
     }
+
+    /// Returns true iff the service runs in an authenticated, separate process
+    fn is_authenticated_process(&self) -> bool {
+        matches!(self, SafetyRulesService::Process(service) if service.is_authenticated())
+    }
 }
 
+// Default values for the remote safety rules service
+const DEFAULT_MAX_MESSAGE_SIZE_BYTES: u64 = 4 * 1024 * 1024; // 4 MiB
+const DEFAULT_REQUEST_LATENCY_BUDGET_MS: u64 = 1_000; // 1 second
+
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RemoteService {
     pub server_address: NetworkAddress,
+    /// The path to a file containing the shared secret used to authenticate
+    /// all messages exchanged with the remote safety rules process. If not
+    /// set, messages are not authenticated.
+    #[serde(default)]
+    pub authentication_key_path: Option<PathBuf>,
+    /// The maximum size (in bytes) of any message exchanged with the remote process
+    #[serde(default = "RemoteService::default_max_message_size_bytes")]
+    pub max_message_size_bytes: u64,
+    /// The latency budget (in ms) for a single request to the remote process
+    /// (including all retries). Requests that exceed the budget will fail.
+    #[serde(default = "RemoteService::default_request_latency_budget_ms")]
+    pub request_latency_budget_ms: u64,
 }
 
 impl RemoteService {
+    pub fn new(server_address: NetworkAddress) -> Self {
+        Self {
+            server_address,
+            authentication_key_path: None,
+            max_message_size_bytes: DEFAULT_MAX_MESSAGE_SIZE_BYTES,
+            request_latency_budget_ms: DEFAULT_REQUEST_LATENCY_BUDGET_MS,
+        }
+    }
+
+    fn default_max_message_size_bytes() -> u64 {
+        DEFAULT_MAX_MESSAGE_SIZE_BYTES
+    }
+
+    fn default_request_latency_budget_ms() -> u64 {
+        DEFAULT_REQUEST_LATENCY_BUDGET_MS
+    }
+
     pub fn server_address(&self) -> SocketAddr {
         self.server_address
             .to_socket_addrs()
@@ -198,6 +320,19 @@ impl RemoteService {
             .next()
             .expect("server_address invalid")
     }
+
+    /// Returns true iff the server address resolves to a loopback address
+    fn is_loopback(&self) -> bool {
+        self.server_address
+            .to_socket_addrs()
+            .map(|mut addresses| addresses.all(|address| address.ip().is_loopback()))
+            .unwrap_or(false)
+    }
+
+    /// Returns true iff messages sent to the remote service are authenticated
+    pub fn is_authenticated(&self) -> bool {
+        self.authentication_key_path.is_some()
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
@@ -302,6 +437,64 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_process_service_for_mainnet() {
+        // Create a node config with an unauthenticated process service
+        let server_address: NetworkAddress = "/ip4/127.0.0.1/tcp/6191".parse().unwrap();
+        let mut node_config = NodeConfig {
+            consensus: ConsensusConfig {
+                safety_rules: SafetyRulesConfig {
+                    backend: SecureBackend::OnDiskStorage(Default::default()),
+                    service: SafetyRulesService::Process(RemoteService::new(
+                        server_address.clone(),
+                    )),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config sanitizer fails
+        let error = SafetyRulesConfig::sanitize(
+            &node_config,
+            NodeType::Validator,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Enable authentication for the process service
+        let mut remote_service = RemoteService::new(server_address);
+        remote_service.authentication_key_path = Some(PathBuf::from("safety_rules.key"));
+        node_config.consensus.safety_rules.service = SafetyRulesService::Process(remote_service);
+
+        // Verify that the config sanitizer now passes
+        SafetyRulesConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_non_loopback_process_service() {
+        // Create a node config with a process service on a public address
+        let server_address: NetworkAddress = "/ip4/8.8.8.8/tcp/6191".parse().unwrap();
+        let node_config = NodeConfig {
+            consensus: ConsensusConfig {
+                safety_rules: SafetyRulesConfig {
+                    service: SafetyRulesService::Process(RemoteService::new(server_address)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config sanitizer fails
+        let error =
+            SafetyRulesConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
     #[test]
     fn test_sanitize_test_config_on_mainnet() {
         // Create a node config with a test config
@@ -325,4 +518,28 @@ mod tests {
         .unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_sanitize_invalid_audit_log_limits() {
+        // Create a node config with an audit log that can never be written
+        let node_config = NodeConfig {
+            consensus: ConsensusConfig {
+                safety_rules: SafetyRulesConfig {
+                    audit_log: SafetyRulesAuditLogConfig {
+                        enabled: true,
+                        max_num_files: 0,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config sanitizer fails
+        let error =
+            SafetyRulesConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
 }
@@ -6,9 +6,11 @@ use crate::config::{
     config_optimizer::ConfigOptimizer, config_sanitizer::ConfigSanitizer,
     node_config_loader::NodeType, Error, NodeConfig,
 };
-use aptos_types::chain_id::ChainId;
+use aptos_compression::codec::{CompressionCodec, DEFAULT_ZSTD_COMPRESSION_LEVEL};
+use aptos_types::{account_address::AccountAddress, chain_id::ChainId, PeerId};
 use serde::{Deserialize, Serialize};
 use serde_yaml::Value;
+use std::{collections::HashSet, path::PathBuf};
 
 // The maximum message size per state sync message
 const MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024; /* 4 MiB */
@@ -23,13 +25,89 @@ const MAX_TRANSACTION_OUTPUT_CHUNK_SIZE: u64 = 1000;
 const MAX_CONCURRENT_REQUESTS: u64 = 6;
 const MAX_CONCURRENT_STATE_REQUESTS: u64 = 6;
 
-#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StateSyncConfig {
     pub data_streaming_service: DataStreamingServiceConfig,
     pub aptos_data_client: AptosDataClientConfig,
+    pub chunk_journal: ChunkJournalConfig,
+    /// A local backup directory (i.e., as produced by the db-backup tool) from
+    /// which to bootstrap transaction outputs. This is only used when applying
+    /// transaction outputs from genesis. Any data that cannot be served by the
+    /// backup is fetched from the network.
+    pub local_backup_dir: Option<PathBuf>,
+    pub state_snapshot_filter: StateSnapshotFilterConfig,
     pub state_sync_driver: StateSyncDriverConfig,
     pub storage_service: StorageServiceConfig,
+    pub trusted_peer_only: TrustedPeerOnlyConfig,
+}
+
+/// The config for the chunk journal, a debug feature that records every data
+/// chunk sent to the storage synchronizer executor (to a rotating set of files
+/// on disk). The journal can then be replayed offline to reproduce execution bugs.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChunkJournalConfig {
+    /// The directory to write the journal files to. If not set, the journal is disabled.
+    pub journal_dir: Option<PathBuf>,
+    /// The max size (bytes) of a journal file before a new file is started
+    pub max_file_size_bytes: u64,
+    /// The max number of journal files to keep (the oldest files are deleted first)
+    pub max_num_files: u64,
+}
+
+impl Default for ChunkJournalConfig {
+    fn default() -> Self {
+        Self {
+            journal_dir: None,
+            max_file_size_bytes: 256 * 1024 * 1024, // 256 MiB
+            max_num_files: 16,
+        }
+    }
+}
+
+impl ChunkJournalConfig {
+    /// Returns true iff the journal is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.journal_dir.is_some()
+    }
+}
+
+/// The config for filtering the state values persisted by a state snapshot sync
+/// (e.g., for analytics nodes that only care about specific accounts). The state
+/// tree is still fully restored (so that the snapshot can be verified), but only
+/// the values of state keys that match the allowlists are persisted. If both
+/// allowlists are empty, the filter is disabled (and all state values are kept).
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StateSnapshotFilterConfig {
+    /// The accounts whose resources and modules should be persisted
+    pub account_allowlist: Vec<AccountAddress>,
+    /// The table handles whose items should be persisted
+    pub table_handle_allowlist: Vec<AccountAddress>,
+}
+
+impl StateSnapshotFilterConfig {
+    /// Returns true iff the filter is enabled
+    pub fn is_enabled(&self) -> bool {
+        !self.account_allowlist.is_empty() || !self.table_handle_allowlist.is_empty()
+    }
+}
+
+/// The config for the trusted-peer-only mode of the data client (e.g., for
+/// operators whose compliance rules forbid syncing from arbitrary public peers).
+/// If enabled, the data client only sends requests (including data summary polls)
+/// to the trusted peers. Note: the data received from the trusted peers is still
+/// fully verified (exactly as for any other peer).
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TrustedPeerOnlyConfig {
+    /// Whether or not to only send data client requests to the trusted peers
+    pub enable: bool,
+    /// The trusted peers, ordered by preference. Requests are sent to the first
+    /// peers in the list that can service them, and fail over to the next peers
+    /// (e.g., if the preferred peers are disconnected, lagging or unresponsive).
+    pub trusted_peers: Vec<PeerId>,
 }
 
 /// The bootstrapping mode determines how the node will bootstrap to the latest
@@ -44,6 +122,9 @@ pub enum BootstrappingMode {
     ExecuteTransactionsFromGenesis,
     /// Executes transactions or applies outputs from genesis (whichever is faster)
     ExecuteOrApplyFromGenesis,
+    /// Downloads the state keys and values (at the latest version), and then
+    /// applies transaction outputs to reach the highest known version
+    DownloadLatestStatesThenApplyOutputs,
 }
 
 impl BootstrappingMode {
@@ -57,12 +138,19 @@ impl BootstrappingMode {
                 "execute_transactions_from_genesis"
             },
             BootstrappingMode::ExecuteOrApplyFromGenesis => "execute_or_apply_from_genesis",
+            BootstrappingMode::DownloadLatestStatesThenApplyOutputs => {
+                "download_latest_states_then_apply_outputs"
+            },
         }
     }
 
     /// Returns true iff the bootstrapping mode is fast sync
     pub fn is_fast_sync(&self) -> bool {
-        *self == BootstrappingMode::DownloadLatestStates
+        matches!(
+            self,
+            BootstrappingMode::DownloadLatestStates
+                | BootstrappingMode::DownloadLatestStatesThenApplyOutputs
+        )
     }
 }
 
@@ -77,6 +165,9 @@ pub enum ContinuousSyncingMode {
     ExecuteTransactions,
     /// Executes transactions or applies outputs to stay up-to-date (whichever is faster)
     ExecuteTransactionsOrApplyOutputs,
+    /// Applies transaction outputs to stay up-to-date, but re-executes a sample
+    /// of the chunks to verify the outputs (the sample rate is a percentage).
+    ApplyWithSpotVerification { sample_rate: u64 },
 }
 
 impl ContinuousSyncingMode {
@@ -87,6 +178,9 @@ impl ContinuousSyncingMode {
             ContinuousSyncingMode::ExecuteTransactionsOrApplyOutputs => {
                 "execute_transactions_or_apply_outputs"
             },
+            ContinuousSyncingMode::ApplyWithSpotVerification { .. } => {
+                "apply_with_spot_verification"
+            },
         }
     }
 }
@@ -98,14 +192,31 @@ pub struct StateSyncDriverConfig {
     pub bootstrapping_mode: BootstrappingMode,
     /// The maximum time taken to process a commit notification
     pub commit_notification_timeout_ms: u64,
+    /// The config for the post-processing of committed data chunks
+    pub commit_post_processor_config: CommitPostProcessorConfig,
+    /// The initial backoff (ms) before retrying a failed chunk commit
+    /// (the backoff doubles on each retry). This overrides the backoff of
+    /// the storage IO policy in `error_retry_config` for chunk commits.
+    pub commit_retry_backoff_ms: u64,
     /// The mode by which to sync after bootstrapping
     pub continuous_syncing_mode: ContinuousSyncingMode,
     /// Enable auto-bootstrapping if no peers are found after `max_connection_deadline_secs`
     pub enable_auto_bootstrapping: bool,
+    /// The retry policies for failed storage synchronizer operations (by error
+    /// class). Failed chunk commits use the storage IO policy, with the retries
+    /// and backoff given by `max_commit_retries` and `commit_retry_backoff_ms`.
+    pub error_retry_config: ErrorRetryConfig,
     /// The interval (ms) to refresh the storage summary
     pub fallback_to_output_syncing_secs: u64,
     /// The interval (ms) at which to check state sync progress
     pub progress_check_interval_ms: u64,
+    /// The maximum time (ms) to spend committing a single chunk (including
+    /// retries). Once the deadline elapses, failed commits are not retried.
+    pub max_chunk_commit_time_ms: u64,
+    /// The maximum number of times to retry a failed chunk commit. This
+    /// overrides the retries of the storage IO policy in `error_retry_config`
+    /// for chunk commits.
+    pub max_commit_retries: u64,
     /// The maximum time (secs) to wait for connections from peers before auto-bootstrapping
     pub max_connection_deadline_secs: u64,
     /// The maximum number of notifications to process per driver loop
@@ -116,10 +227,51 @@ pub struct StateSyncDriverConfig {
     pub max_pending_data_chunks: u64,
     /// The maximum number of pending mempool commit notifications
     pub max_pending_mempool_notifications: u64,
+    /// The maximum time (secs) the storage synchronizer pipeline may hold pending
+    /// data chunks without any stage making progress, before a stall is reported
+    /// by the pipeline watchdog. If this is 0, the watchdog is disabled.
+    pub max_pipeline_stall_secs: u64,
+    /// The maximum number of workers that prepare state value chunks for commit
+    /// (when fast syncing). If this is 1, chunks are prepared on the commit task.
+    pub max_state_commit_workers: u64,
     /// The maximum time (ms) to wait for a data stream notification
     pub max_stream_wait_time_ms: u64,
+    /// The maximum number of committed chunks to coalesce into a single mempool
+    /// notification (when the node is far behind). If this is 1, mempool is
+    /// notified of every committed chunk.
+    pub mempool_notification_batch_size: u64,
+    /// The number of versions the node must be behind the highest known version
+    /// before mempool notifications are batched.
+    pub mempool_notification_batching_lag: u64,
+    /// The maximum time (ms) to delay a batched mempool notification
+    pub mempool_notification_max_delay_ms: u64,
+    /// The number of threads in the dedicated thread pool used to execute (or
+    /// apply) data chunks. This prevents large chunks from starving other
+    /// blocking work (e.g., storage service request handling). If this is 0,
+    /// chunks are executed on the shared tokio blocking pool.
+    pub num_chunk_executor_threads: u64,
     /// The version lag we'll tolerate before snapshot syncing
     pub num_versions_to_skip_snapshot_sync: u64,
+    /// The number of pending data chunks at which the driver stops processing
+    /// stream notifications (to let the storage synchronizer catch up)
+    pub pending_data_chunks_high_watermark: u64,
+    /// The number of pending data chunks at which the driver resumes processing
+    /// stream notifications (after hitting the high watermark)
+    pub pending_data_chunks_low_watermark: u64,
+    /// Whether or not the pipeline watchdog should reset the storage synchronizer
+    /// pipeline when a stall is detected (i.e., drop any lost chunks from the
+    /// pending data chunk count and reset the active data stream).
+    pub reset_stalled_pipeline: bool,
+    /// The number of verifier tasks that verify state value chunks (when fast
+    /// syncing) before they are committed. This allows the verification of a
+    /// chunk to overlap with the commit of the previous chunk. If this is 0,
+    /// chunks are only verified when they are committed.
+    pub state_value_verifier_threads: u64,
+    /// Whether or not the storage synchronizer should re-verify the epoch
+    /// change proofs (against the trusted waypoint) before finalizing a state
+    /// snapshot. The proofs are already verified by the driver, so this is
+    /// only an additional safety net for paranoid operators.
+    pub verify_epoch_proofs_on_commit: bool,
 }
 
 /// The default state sync driver config will be the one that gets (and keeps)
@@ -129,17 +281,128 @@ impl Default for StateSyncDriverConfig {
         Self {
             bootstrapping_mode: BootstrappingMode::ExecuteOrApplyFromGenesis,
             commit_notification_timeout_ms: 5000,
+            commit_post_processor_config: CommitPostProcessorConfig::default(),
+            commit_retry_backoff_ms: 100,
             continuous_syncing_mode: ContinuousSyncingMode::ExecuteTransactionsOrApplyOutputs,
             enable_auto_bootstrapping: false,
+            error_retry_config: ErrorRetryConfig::default(),
             fallback_to_output_syncing_secs: 180, // 3 minutes
             progress_check_interval_ms: 100,
+            max_chunk_commit_time_ms: 5000,
+            max_commit_retries: 3,
             max_connection_deadline_secs: 10,
             max_consecutive_stream_notifications: 10,
             max_num_stream_timeouts: 12,
             max_pending_data_chunks: 50,
             max_pending_mempool_notifications: 100,
+            max_pipeline_stall_secs: 120, // 2 minutes
+            max_state_commit_workers: 1,
             max_stream_wait_time_ms: 5000,
+            mempool_notification_batch_size: 20,
+            mempool_notification_batching_lag: 10_000,
+            mempool_notification_max_delay_ms: 500,
+            num_chunk_executor_threads: 0,
             num_versions_to_skip_snapshot_sync: 100_000_000, // At 5k TPS, this allows a node to fail for about 6 hours.
+            pending_data_chunks_high_watermark: 40,
+            pending_data_chunks_low_watermark: 20,
+            reset_stalled_pipeline: false,
+            state_value_verifier_threads: 4,
+            verify_epoch_proofs_on_commit: false,
+        }
+    }
+}
+
+/// The config for the commit post-processor (i.e., the component that notifies
+/// other components of data chunks committed by the storage synchronizer). Nodes
+/// that don't require all notifications (e.g., archival nodes without a mempool)
+/// can partially (or fully) bypass the post-processor.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommitPostProcessorConfig {
+    /// Whether to notify the event subscription service of committed events
+    pub notify_event_subscribers: bool,
+    /// Whether to notify mempool of committed transactions
+    pub notify_mempool: bool,
+    /// Whether to notify the storage service of newly committed data
+    pub notify_storage_service: bool,
+}
+
+impl Default for CommitPostProcessorConfig {
+    fn default() -> Self {
+        Self {
+            notify_event_subscribers: true,
+            notify_mempool: true,
+            notify_storage_service: true,
+        }
+    }
+}
+
+impl CommitPostProcessorConfig {
+    /// Returns true iff the post-processor is required (i.e., at
+    /// least one notification has been enabled).
+    pub fn is_enabled(&self) -> bool {
+        self.notify_event_subscribers || self.notify_mempool || self.notify_storage_service
+    }
+}
+
+/// The retry policy for failed storage synchronizer operations of a single
+/// error class. Only operations that are safe to retry (e.g., idempotent
+/// storage writes) are retried.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ErrorRetryPolicy {
+    /// The maximum number of times to retry a failed operation
+    pub max_retries: u64,
+    /// The initial backoff (ms) before retrying a failed operation
+    /// (the backoff doubles on each retry).
+    pub retry_backoff_ms: u64,
+}
+
+impl ErrorRetryPolicy {
+    /// Returns a policy that never retries failed operations
+    pub const fn no_retries() -> Self {
+        Self {
+            max_retries: 0,
+            retry_backoff_ms: 0,
+        }
+    }
+}
+
+impl Default for ErrorRetryPolicy {
+    fn default() -> Self {
+        Self::no_retries()
+    }
+}
+
+/// The retry policies for failed storage synchronizer operations (by error
+/// class). Errors that aren't retried (or exhaust their retries) are sent to
+/// the driver, which resets the active data stream.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ErrorRetryConfig {
+    /// The retry policy for closed channels between components
+    pub channel_closed: ErrorRetryPolicy,
+    /// The retry policy for a busy (or stalled) executor
+    pub executor_busy: ErrorRetryPolicy,
+    /// The retry policy for storage reads and writes (e.g., transient IO failures)
+    pub storage_io: ErrorRetryPolicy,
+    /// The retry policy for data that failed verification
+    pub verification_failure: ErrorRetryPolicy,
+}
+
+impl Default for ErrorRetryConfig {
+    fn default() -> Self {
+        Self {
+            channel_closed: ErrorRetryPolicy::no_retries(), // Closed channels never recover
+            executor_busy: ErrorRetryPolicy {
+                max_retries: 3,
+                retry_backoff_ms: 500,
+            },
+            storage_io: ErrorRetryPolicy {
+                max_retries: 3,
+                retry_backoff_ms: 100,
+            },
+            verification_failure: ErrorRetryPolicy::no_retries(), // The data must be refetched
         }
     }
 }
@@ -147,6 +410,10 @@ impl Default for StateSyncDriverConfig {
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct StorageServiceConfig {
+    /// The compression config for responses sent by the storage service
+    pub compression_config: StorageServiceCompressionConfig,
+    /// The per-peer concurrency limits config for the storage service
+    pub concurrency_limits_config: StorageServiceConcurrencyLimitsConfig,
     /// Maximum number of concurrent storage server tasks
     pub max_concurrent_requests: u64,
     /// Maximum number of epoch ending ledger infos per chunk
@@ -173,15 +440,22 @@ pub struct StorageServiceConfig {
     pub max_transaction_output_chunk_size: u64,
     /// Minimum time (secs) to ignore peers after too many invalid requests
     pub min_time_to_ignore_peers_secs: u64,
+    /// The peer-class-aware request scheduling config for the storage service
+    pub priority_scheduling_config: StorageServicePrioritySchedulingConfig,
     /// The interval (ms) to refresh the request moderator state
     pub request_moderator_refresh_interval_ms: u64,
-    /// The interval (ms) to refresh the storage summary
+    /// The state snapshot artifact config for the storage service
+    pub snapshot_artifact_config: StorageServiceSnapshotArtifactConfig,
+    /// The interval (ms) to refresh the storage summary. Note: the summary is
+    /// also refreshed on every commit, so this interval is only a fallback.
     pub storage_summary_refresh_interval_ms: u64,
 }
 
 impl Default for StorageServiceConfig {
     fn default() -> Self {
         Self {
+            compression_config: StorageServiceCompressionConfig::default(),
+            concurrency_limits_config: StorageServiceConcurrencyLimitsConfig::default(),
             max_concurrent_requests: 4000,
             max_epoch_chunk_size: MAX_EPOCH_CHUNK_SIZE,
             max_invalid_requests_per_peer: 500,
@@ -195,12 +469,104 @@ impl Default for StorageServiceConfig {
             max_transaction_chunk_size: MAX_TRANSACTION_CHUNK_SIZE,
             max_transaction_output_chunk_size: MAX_TRANSACTION_OUTPUT_CHUNK_SIZE,
             min_time_to_ignore_peers_secs: 300, // 5 minutes
+            priority_scheduling_config: StorageServicePrioritySchedulingConfig::default(),
             request_moderator_refresh_interval_ms: 1000, // 1 second
+            snapshot_artifact_config: StorageServiceSnapshotArtifactConfig::default(),
             storage_summary_refresh_interval_ms: 100, // Optimal for <= 10 blocks per second
         }
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageServiceConcurrencyLimitsConfig {
+    /// Whether or not to limit the in-flight requests of each peer. If enabled,
+    /// each peer is also limited to a fair share of the concurrent requests
+    /// (i.e., `max_concurrent_requests` divided by the number of active peers).
+    pub enable_per_peer_limits: bool,
+    /// Maximum number of estimated bytes in flight (per peer). Each request is
+    /// estimated using the maximum size of its response.
+    pub max_bytes_in_flight_per_peer: u64,
+    /// Maximum number of concurrent requests (per peer)
+    pub max_concurrent_requests_per_peer: u64,
+}
+
+impl Default for StorageServiceConcurrencyLimitsConfig {
+    fn default() -> Self {
+        Self {
+            enable_per_peer_limits: true,
+            max_bytes_in_flight_per_peer: 100 * 1024 * 1024, // 100 MiB
+            max_concurrent_requests_per_peer: 200,           // 5% of the max concurrent requests
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageServicePrioritySchedulingConfig {
+    /// Whether or not to schedule requests by peer class. If enabled, requests
+    /// from validators and VFNs (e.g., consensus fallbacks) are served ahead of
+    /// requests from public peers (e.g., bulk historical syncing).
+    pub enable_priority_scheduling: bool,
+    /// Maximum number of pending requests (per peer class) waiting to be served.
+    /// Requests that arrive when the queue is full are rejected.
+    pub max_pending_requests_per_class: u64,
+}
+
+impl Default for StorageServicePrioritySchedulingConfig {
+    fn default() -> Self {
+        Self {
+            enable_priority_scheduling: true,
+            max_pending_requests_per_class: 4000, // Matches the max network channel size
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageServiceSnapshotArtifactConfig {
+    /// Whether or not to serve state value chunks from precomputed state
+    /// snapshot artifacts (instead of iterating the state tree for every
+    /// request). Artifacts are stored in the node's storage directory.
+    pub enable_snapshot_artifacts: bool,
+    /// The interval (secs) to check for a new epoch ending snapshot and
+    /// regenerate the artifact (if required).
+    pub snapshot_artifact_refresh_interval_secs: u64,
+}
+
+impl Default for StorageServiceSnapshotArtifactConfig {
+    fn default() -> Self {
+        Self {
+            enable_snapshot_artifacts: false,
+            snapshot_artifact_refresh_interval_secs: 600, // 10 minutes
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StorageServiceCompressionConfig {
+    /// Whether or not clients can negotiate the compression codec (per
+    /// connection). If disabled, all compressed responses use LZ4.
+    pub enable_compression_negotiation: bool,
+    /// The codec to use for peers on a local network (i.e., private addresses)
+    pub lan_compression_codec: CompressionCodec,
+    /// The codec to use for peers on a wide area network (i.e., public addresses)
+    pub wan_compression_codec: CompressionCodec,
+}
+
+impl Default for StorageServiceCompressionConfig {
+    fn default() -> Self {
+        Self {
+            enable_compression_negotiation: true,
+            lan_compression_codec: CompressionCodec::Lz4, // Bandwidth is cheap, so prefer speed
+            wan_compression_codec: CompressionCodec::Zstd {
+                level: DEFAULT_ZSTD_COMPRESSION_LEVEL,
+            }, // Bandwidth is expensive, so prefer a higher compression ratio
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct DataStreamingServiceConfig {
@@ -213,6 +579,10 @@ pub struct DataStreamingServiceConfig {
     /// The interval (milliseconds) at which to refresh the global data summary.
     pub global_summary_refresh_interval_ms: u64,
 
+    /// Maximum number of recent data streams for which to hold audit logs
+    /// (e.g., for post-hoc investigations). If 0, stream auditing is disabled.
+    pub max_audited_streams: u64,
+
     /// Maximum number of concurrent data client requests (per stream).
     pub max_concurrent_requests: u64,
 
@@ -239,11 +609,21 @@ pub struct DataStreamingServiceConfig {
     /// stream will terminate.
     pub max_request_retry: u64,
 
+    /// Maximum number of entries held in the audit log of each stream. Once
+    /// the number grows beyond this value, the oldest entries are dropped.
+    pub max_stream_audit_log_entries: u64,
+
     /// Maximum lag (in seconds) we'll tolerate when sending subscription requests
     pub max_subscription_stream_lag_secs: u64,
 
     /// The interval (milliseconds) at which to check the progress of each stream.
     pub progress_check_interval_ms: u64,
+
+    /// The request scheduler config for the data streaming service
+    pub request_scheduler: RequestSchedulerConfig,
+
+    /// The mid-stream failover config for the data streaming service
+    pub stream_failover: StreamFailoverConfig,
 }
 
 impl Default for DataStreamingServiceConfig {
@@ -252,6 +632,7 @@ impl Default for DataStreamingServiceConfig {
             dynamic_prefetching: DynamicPrefetchingConfig::default(),
             enable_subscription_streaming: false,
             global_summary_refresh_interval_ms: 50,
+            max_audited_streams: 10,
             max_concurrent_requests: MAX_CONCURRENT_REQUESTS,
             max_concurrent_state_requests: MAX_CONCURRENT_STATE_REQUESTS,
             max_data_stream_channel_sizes: 50,
@@ -259,8 +640,88 @@ impl Default for DataStreamingServiceConfig {
             max_num_consecutive_subscriptions: 40, // At ~4 blocks per second, this should last 10 seconds
             max_pending_requests: 50,
             max_request_retry: 5,
+            max_stream_audit_log_entries: 1000,
             max_subscription_stream_lag_secs: 15, // 15 seconds
             progress_check_interval_ms: 50,
+            request_scheduler: RequestSchedulerConfig::default(),
+            stream_failover: StreamFailoverConfig::default(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequestSchedulerConfig {
+    /// Whether or not to schedule data client requests (across all streams)
+    /// using a weighted fair queue with a lane per request type. Optimistic
+    /// fetch and subscription requests are never queued.
+    pub enable_request_scheduler: bool,
+
+    /// The weight of the epoch ending ledger info lane
+    pub epoch_ending_ledger_infos_weight: u64,
+
+    /// Maximum number of in-flight data client requests (across all streams).
+    /// Once reached, new requests are queued in their lanes.
+    pub max_in_flight_requests: u64,
+
+    /// The weight of the number of states lane
+    pub number_of_states_weight: u64,
+
+    /// The weight of the state values lane
+    pub state_values_weight: u64,
+
+    /// The weight of the transaction outputs lane
+    pub transaction_outputs_weight: u64,
+
+    /// The weight of the transactions lane
+    pub transactions_weight: u64,
+
+    /// The weight of the transactions or outputs lane
+    pub transactions_or_outputs_weight: u64,
+}
+
+impl Default for RequestSchedulerConfig {
+    fn default() -> Self {
+        Self {
+            enable_request_scheduler: true,
+            epoch_ending_ledger_infos_weight: 10,
+            max_in_flight_requests: 30,
+            number_of_states_weight: 10,
+            state_values_weight: 2,
+            transaction_outputs_weight: 1,
+            transactions_weight: 2,
+            transactions_or_outputs_weight: 1,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StreamFailoverConfig {
+    /// Whether or not to fail over to alternate peers when the peer
+    /// serving a data stream degrades (instead of terminating the stream).
+    pub enable_stream_failover: bool,
+
+    /// The number of consecutive failures of a single request (after the
+    /// primary peer last served the stream) that triggers a failover.
+    pub failover_failure_threshold: u64,
+
+    /// Maximum number of failovers per stream. Once exceeded, failures are
+    /// handled as usual (i.e., the stream will eventually terminate).
+    pub max_failovers_per_stream: u64,
+
+    /// The duration (milliseconds) for which a degraded peer is excluded
+    /// from servicing requests after a failover.
+    pub peer_exclusion_duration_ms: u64,
+}
+
+impl Default for StreamFailoverConfig {
+    fn default() -> Self {
+        Self {
+            enable_stream_failover: true,
+            failover_failure_threshold: 2,
+            max_failovers_per_stream: 3,
+            peer_exclusion_duration_ms: 60_000, // 1 minute
         }
     }
 }
@@ -309,6 +770,12 @@ impl Default for DynamicPrefetchingConfig {
 pub struct AptosDataPollerConfig {
     /// The additional number of polls to send per peer bucket (per second)
     pub additional_polls_per_peer_bucket: u64,
+    /// The safety margin (as a percentage of the advertised data windows) to
+    /// exclude from the lowest versions advertised by pruned peers. This data
+    /// is likely to be pruned before requests for it are served.
+    pub data_window_safety_margin_percent: u64,
+    /// Whether or not to poll pruned peers for their data windows
+    pub enable_data_window_polling: bool,
     /// The minimum number of polls that should be sent per second
     pub min_polls_per_second: u64,
     /// The maximum number of in-flight polls for priority peers
@@ -317,6 +784,9 @@ pub struct AptosDataPollerConfig {
     pub max_num_in_flight_regular_polls: u64,
     /// The maximum number of polls that should be sent per second
     pub max_polls_per_second: u64,
+    /// The maximum age (in seconds) of a peer's storage summary before the
+    /// summary is considered stale (and ignored for peer selection).
+    pub max_storage_summary_age_secs: u64,
     /// The number of peers per bucket
     pub peer_bucket_size: u64,
     /// Interval (in ms) between summary poll loop executions
@@ -327,10 +797,13 @@ impl Default for AptosDataPollerConfig {
     fn default() -> Self {
         Self {
             additional_polls_per_peer_bucket: 1,
+            data_window_safety_margin_percent: 5,
+            enable_data_window_polling: true,
             min_polls_per_second: 5,
             max_num_in_flight_priority_polls: 30,
             max_num_in_flight_regular_polls: 30,
             max_polls_per_second: 20,
+            max_storage_summary_age_secs: 120, // 2 minutes
             peer_bucket_size: 10,
             poll_loop_interval_ms: 100,
         }
@@ -388,13 +861,112 @@ impl Default for AptosLatencyFilteringConfig {
     }
 }
 
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AptosThroughputScoringConfig {
+    /// Whether or not to select peers for transaction output requests
+    /// (i.e., large chunks) using their measured response throughput.
+    pub enable_throughput_scoring: bool,
+    /// The weight (percent) given to each new throughput sample when
+    /// updating the peer's moving average (EWMA) throughput.
+    pub ewma_new_sample_weight_percent: u64,
+    /// The minimum response size (bytes) for a response to be used when
+    /// measuring throughput (small responses are dominated by latency).
+    pub min_response_bytes_for_scoring: u64,
+    /// Peers with a throughput below this percent of the best peer's
+    /// throughput are not selected (if enough other peers are available).
+    pub min_relative_throughput_percent: u64,
+}
+
+impl Default for AptosThroughputScoringConfig {
+    fn default() -> Self {
+        Self {
+            enable_throughput_scoring: true,
+            ewma_new_sample_weight_percent: 20,
+            min_response_bytes_for_scoring: 64 * 1024, // 64 KiB
+            min_relative_throughput_percent: 25, // Ignore peers slower than 25% of the best peer
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AptosDataBandwidthConfig {
+    /// Whether or not to cap the bandwidth consumed by the data client
+    /// when the local storage service is serving other peers.
+    pub enable_bandwidth_budgeting: bool,
+    /// The maximum number of bytes per second that the data client may consume
+    /// (e.g., for operators on metered links). This is a hard cap that applies
+    /// even if budgeting is disabled. If this is 0, syncing is not capped.
+    pub max_syncing_bytes_per_second: u64,
+    /// The maximum number of bytes per second that should be shared
+    /// between syncing (data client) and serving (storage service).
+    pub max_total_bytes_per_second: u64,
+    /// The minimum number of bytes per second that the data client is
+    /// always allowed to consume (regardless of the serving load).
+    pub min_syncing_bytes_per_second: u64,
+    /// The maximum delay (in ms) to wait before sending a throttled request
+    pub max_throttle_delay_ms: u64,
+    /// The hour of the day (UTC, 0 to 23) at which the off-peak window ends
+    /// (exclusive). The window may wrap around midnight.
+    pub off_peak_end_hour_utc: u64,
+    /// The syncing cap (bytes per second) during the off-peak window (if one
+    /// is configured). If this is 0, syncing is not capped during the window.
+    pub off_peak_max_syncing_bytes_per_second: u64,
+    /// The hour of the day (UTC, 0 to 23) at which the off-peak window starts.
+    /// If this is equal to the end hour, there is no off-peak window.
+    pub off_peak_start_hour_utc: u64,
+    /// The relative priority weight given to serving other peers
+    pub serving_priority_weight: u64,
+    /// The relative priority weight given to syncing from other peers
+    pub syncing_priority_weight: u64,
+    /// The window duration (in ms) over which bandwidth usage is measured
+    pub usage_window_ms: u64,
+}
+
+impl Default for AptosDataBandwidthConfig {
+    fn default() -> Self {
+        Self {
+            enable_bandwidth_budgeting: false,
+            max_syncing_bytes_per_second: 0,
+            max_total_bytes_per_second: 100 * 1024 * 1024, // 100 MiB/s
+            min_syncing_bytes_per_second: 10 * 1024 * 1024, // 10 MiB/s
+            max_throttle_delay_ms: 1000,                   // 1 second
+            off_peak_end_hour_utc: 0,
+            off_peak_max_syncing_bytes_per_second: 0,
+            off_peak_start_hour_utc: 0,
+            serving_priority_weight: 1,
+            syncing_priority_weight: 1,
+            usage_window_ms: 1000, // 1 second
+        }
+    }
+}
+
+impl AptosDataBandwidthConfig {
+    /// Returns true iff the given hour of the day (UTC) is in the off-peak window
+    pub fn is_off_peak_hour(&self, hour_utc: u64) -> bool {
+        let start_hour = self.off_peak_start_hour_utc;
+        let end_hour = self.off_peak_end_hour_utc;
+        if start_hour <= end_hour {
+            start_hour <= hour_utc && hour_utc < end_hour
+        } else {
+            hour_utc >= start_hour || hour_utc < end_hour // The window wraps around midnight
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct AptosDataClientConfig {
+    /// The aptos data bandwidth config for the data client
+    pub data_bandwidth_config: AptosDataBandwidthConfig,
     /// The aptos data poller config for the data client
     pub data_poller_config: AptosDataPollerConfig,
     /// The aptos data multi-fetch config for the data client
     pub data_multi_fetch_config: AptosDataMultiFetchConfig,
+    /// Whether or not to negotiate the compression codec with each peer
+    /// (per connection). Otherwise, compressed responses always use LZ4.
+    pub enable_compression_negotiation: bool,
     /// The aptos latency filtering config for the data client
     pub latency_filtering_config: AptosLatencyFilteringConfig,
     /// The interval (milliseconds) at which to refresh the latency monitor
@@ -421,6 +993,8 @@ pub struct AptosDataClientConfig {
     pub response_timeout_ms: u64,
     /// Timeout (in ms) when waiting for a subscription response
     pub subscription_response_timeout_ms: u64,
+    /// The aptos throughput scoring config for the data client
+    pub throughput_scoring_config: AptosThroughputScoringConfig,
     /// Whether or not to request compression for incoming data
     pub use_compression: bool,
 }
@@ -428,8 +1002,10 @@ pub struct AptosDataClientConfig {
 impl Default for AptosDataClientConfig {
     fn default() -> Self {
         Self {
+            data_bandwidth_config: AptosDataBandwidthConfig::default(),
             data_poller_config: AptosDataPollerConfig::default(),
             data_multi_fetch_config: AptosDataMultiFetchConfig::default(),
+            enable_compression_negotiation: false,
             latency_filtering_config: AptosLatencyFilteringConfig::default(),
             latency_monitor_loop_interval_ms: 100,
             max_epoch_chunk_size: MAX_EPOCH_CHUNK_SIZE,
@@ -443,6 +1019,7 @@ impl Default for AptosDataClientConfig {
             optimistic_fetch_timeout_ms: 5000,        // 5 seconds
             response_timeout_ms: 10_000,              // 10 seconds
             subscription_response_timeout_ms: 20_000, // 20 seconds (must be longer than a regular timeout because of pre-fetching)
+            throughput_scoring_config: AptosThroughputScoringConfig::default(),
             use_compression: true,
         }
     }
@@ -455,15 +1032,97 @@ impl ConfigSanitizer for StateSyncConfig {
         chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
         // Sanitize the state sync driver config
-        StateSyncDriverConfig::sanitize(node_config, node_type, chain_id)
+        StateSyncDriverConfig::sanitize(node_config, node_type, chain_id)?;
+
+        // Sanitize the data client bandwidth config
+        AptosDataBandwidthConfig::sanitize(node_config, node_type, chain_id)?;
+
+        // Sanitize the trusted peer only config
+        TrustedPeerOnlyConfig::sanitize(node_config, node_type, chain_id)
     }
 }
 
-impl ConfigSanitizer for StateSyncDriverConfig {
+impl ConfigSanitizer for AptosDataBandwidthConfig {
     fn sanitize(
         node_config: &NodeConfig,
         _node_type: NodeType,
         _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let bandwidth_config = &node_config
+            .state_sync
+            .aptos_data_client
+            .data_bandwidth_config;
+
+        // Verify that the off-peak window hours are valid
+        let start_hour = bandwidth_config.off_peak_start_hour_utc;
+        let end_hour = bandwidth_config.off_peak_end_hour_utc;
+        if start_hour > 23 || end_hour > 23 {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The off-peak bandwidth window hours must be valid hours of the day (0-23)! Start hour: {}, end hour: {}",
+                    start_hour, end_hour
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl ConfigSanitizer for TrustedPeerOnlyConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<(), Error> {
+        let sanitizer_name = Self::get_sanitizer_name();
+        let trusted_peer_only_config = &node_config.state_sync.trusted_peer_only;
+        if !trusted_peer_only_config.enable {
+            return Ok(());
+        }
+
+        // Verify that the mode is not enabled for validators (validators
+        // must be able to sync from all other validators in the set).
+        if node_type.is_validator() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The trusted peer only mode should not be enabled for validators!".to_string(),
+            ));
+        }
+
+        // Verify that the trusted peers are not empty
+        let trusted_peers = &trusted_peer_only_config.trusted_peers;
+        if trusted_peers.is_empty() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The trusted peer only mode is enabled, but no trusted peers are specified!"
+                    .to_string(),
+            ));
+        }
+
+        // Verify that the trusted peers are unique (otherwise the order is ambiguous)
+        let unique_trusted_peers: HashSet<_> = trusted_peers.iter().collect();
+        if unique_trusted_peers.len() != trusted_peers.len() {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The trusted peers must be unique! Found: {:?}",
+                    trusted_peers
+                ),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl ConfigSanitizer for StateSyncDriverConfig {
+    fn sanitize(
+        node_config: &NodeConfig,
+        node_type: NodeType,
+        _chain_id: Option<ChainId>,
     ) -> Result<(), Error> {
         let sanitizer_name = Self::get_sanitizer_name();
         let state_sync_driver_config = &node_config.state_sync.state_sync_driver;
@@ -479,6 +1138,81 @@ impl ConfigSanitizer for StateSyncDriverConfig {
             ));
         }
 
+        // Verify that the pending data chunk watermarks are valid
+        let high_watermark = state_sync_driver_config.pending_data_chunks_high_watermark;
+        let low_watermark = state_sync_driver_config.pending_data_chunks_low_watermark;
+        let max_pending_data_chunks = state_sync_driver_config.max_pending_data_chunks;
+        if low_watermark > high_watermark || high_watermark > max_pending_data_chunks {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The pending data chunk watermarks are invalid! Low watermark: {}, high watermark: {}, max pending data chunks: {}",
+                    low_watermark, high_watermark, max_pending_data_chunks
+                ),
+            ));
+        }
+
+        // Verify that the spot verification sample rate is a valid percentage
+        if let ContinuousSyncingMode::ApplyWithSpotVerification { sample_rate } =
+            state_sync_driver_config.continuous_syncing_mode
+        {
+            if sample_rate > 100 {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The spot verification sample rate must be a percentage (0-100)! Found: {}",
+                        sample_rate
+                    ),
+                ));
+            }
+        }
+
+        // Verify that validators always notify mempool of committed transactions
+        let commit_post_processor_config = state_sync_driver_config.commit_post_processor_config;
+        if node_type.is_validator() && !commit_post_processor_config.notify_mempool {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "Mempool commit notifications should not be disabled for validators!".to_string(),
+            ));
+        }
+
+        // Verify that filtered snapshot syncing is only used by fast syncing
+        // fullnodes that apply outputs (the filtered state values are missing,
+        // so transactions cannot be executed).
+        if node_config.state_sync.state_snapshot_filter.is_enabled() {
+            if node_type.is_validator() {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The state snapshot filter should not be enabled for validators!".to_string(),
+                ));
+            }
+            // The filtered state values are missing, so the API and mempool
+            // would silently serve (or validate against) incomplete state.
+            if node_config.api.enabled || node_config.mempool.enable_transaction_validation {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The state snapshot filter requires the API and mempool transaction validation to be disabled! API enabled: {:?}, mempool validation enabled: {:?}",
+                        node_config.api.enabled,
+                        node_config.mempool.enable_transaction_validation
+                    ),
+                ));
+            }
+            if !fast_sync_enabled
+                || state_sync_driver_config.continuous_syncing_mode
+                    != ContinuousSyncingMode::ApplyTransactionOutputs
+            {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The state snapshot filter requires fast syncing and applying transaction outputs! Bootstrapping mode: {:?}, continuous syncing mode: {:?}",
+                        state_sync_driver_config.bootstrapping_mode,
+                        state_sync_driver_config.continuous_syncing_mode
+                    ),
+                ));
+            }
+        }
+
         Ok(())
     }
 }
@@ -500,7 +1234,15 @@ impl ConfigOptimizer for StateSyncConfig {
             chain_id,
         )?;
 
-        Ok(modified_driver_config || modified_data_streaming_config)
+        // Optimize the data client bandwidth config
+        let modified_bandwidth_config = AptosDataBandwidthConfig::optimize(
+            node_config,
+            local_config_yaml,
+            node_type,
+            chain_id,
+        )?;
+
+        Ok(modified_driver_config || modified_data_streaming_config || modified_bandwidth_config)
     }
 }
 
@@ -563,6 +1305,45 @@ impl ConfigOptimizer for DataStreamingServiceConfig {
     }
 }
 
+impl ConfigOptimizer for AptosDataBandwidthConfig {
+    fn optimize(
+        node_config: &mut NodeConfig,
+        local_config_yaml: &Value,
+        node_type: NodeType,
+        _chain_id: Option<ChainId>,
+    ) -> Result<bool, Error> {
+        let bandwidth_config = &mut node_config
+            .state_sync
+            .aptos_data_client
+            .data_bandwidth_config;
+        let local_bandwidth_config_yaml =
+            &local_config_yaml["state_sync"]["aptos_data_client"]["data_bandwidth_config"];
+
+        // Validators must keep up with consensus, so they prioritize syncing.
+        // VFNs are the main data source for PFNs, so they prioritize serving.
+        let (serving_priority_weight, syncing_priority_weight) = if node_type.is_validator() {
+            (1, 3)
+        } else if node_type.is_validator_fullnode() {
+            (3, 1)
+        } else {
+            return Ok(false); // PFNs use the default (equal) priorities
+        };
+
+        // Update the priority weights (if they haven't been manually set)
+        let mut modified_config = false;
+        if local_bandwidth_config_yaml["serving_priority_weight"].is_null() {
+            bandwidth_config.serving_priority_weight = serving_priority_weight;
+            modified_config = true;
+        }
+        if local_bandwidth_config_yaml["syncing_priority_weight"].is_null() {
+            bandwidth_config.syncing_priority_weight = syncing_priority_weight;
+            modified_config = true;
+        }
+
+        Ok(modified_config)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -761,6 +1542,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_optimize_bandwidth_priorities_validator() {
+        // Create a default node config
+        let mut node_config = NodeConfig::default();
+
+        // Optimize the config and verify modifications are made
+        let modified_config = StateSyncConfig::optimize(
+            &mut node_config,
+            &serde_yaml::from_str("{}").unwrap(), // An empty local config,
+            NodeType::Validator,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap();
+        assert!(modified_config);
+
+        // Verify that syncing is prioritized over serving
+        let bandwidth_config = &node_config
+            .state_sync
+            .aptos_data_client
+            .data_bandwidth_config;
+        assert_eq!(bandwidth_config.serving_priority_weight, 1);
+        assert_eq!(bandwidth_config.syncing_priority_weight, 3);
+    }
+
+    #[test]
+    fn test_optimize_bandwidth_priorities_vfn_no_override() {
+        // Create a node config where the serving weight is set to 10
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                aptos_data_client: AptosDataClientConfig {
+                    data_bandwidth_config: AptosDataBandwidthConfig {
+                        serving_priority_weight: 10,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Create a local config YAML where the serving weight is set to 10
+        let local_config_yaml = serde_yaml::from_str(
+            r#"
+            state_sync:
+                aptos_data_client:
+                    data_bandwidth_config:
+                        serving_priority_weight: 10
+            "#,
+        )
+        .unwrap();
+
+        // Optimize the config and verify modifications are made
+        let modified_config = StateSyncConfig::optimize(
+            &mut node_config,
+            &local_config_yaml,
+            NodeType::ValidatorFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+        assert!(modified_config);
+
+        // Verify that only the syncing weight was changed
+        let bandwidth_config = &node_config
+            .state_sync
+            .aptos_data_client
+            .data_bandwidth_config;
+        assert_eq!(bandwidth_config.serving_priority_weight, 10);
+        assert_eq!(bandwidth_config.syncing_priority_weight, 1);
+    }
+
     #[test]
     fn test_sanitize_auto_bootstrapping_fast_sync() {
         // Create a node config with fast sync and
@@ -784,6 +1636,290 @@ mod tests {
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
 
+    #[test]
+    fn test_sanitize_pending_data_chunks_watermarks() {
+        // Create a node config with the low watermark above the high watermark
+        let node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    pending_data_chunks_high_watermark: 10,
+                    pending_data_chunks_low_watermark: 20,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a node config with the high watermark above the max pending data chunks
+        let node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    max_pending_data_chunks: 50,
+                    pending_data_chunks_high_watermark: 60,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_spot_verification_sample_rate() {
+        // Create a node config with a valid spot verification sample rate
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    continuous_syncing_mode: ContinuousSyncingMode::ApplyWithSpotVerification {
+                        sample_rate: 100,
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization passes
+        StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+            .unwrap();
+
+        // Update the sample rate to be larger than 100%
+        node_config
+            .state_sync
+            .state_sync_driver
+            .continuous_syncing_mode =
+            ContinuousSyncingMode::ApplyWithSpotVerification { sample_rate: 101 };
+
+        // Verify that sanitization fails
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_commit_post_processor_mempool() {
+        // Create a node config with mempool commit notifications disabled
+        let node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                state_sync_driver: StateSyncDriverConfig {
+                    commit_post_processor_config: CommitPostProcessorConfig {
+                        notify_mempool: false,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails for validators
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that sanitization passes for fullnodes
+        StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_bandwidth_off_peak_hours() {
+        // Create a node config with a valid off-peak window
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                aptos_data_client: AptosDataClientConfig {
+                    data_bandwidth_config: AptosDataBandwidthConfig {
+                        off_peak_start_hour_utc: 22,
+                        off_peak_end_hour_utc: 6,
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization passes
+        StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+            .unwrap();
+
+        // Update the start hour to be an invalid hour
+        node_config
+            .state_sync
+            .aptos_data_client
+            .data_bandwidth_config
+            .off_peak_start_hour_utc = 24;
+
+        // Verify that sanitization fails
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_state_snapshot_filter() {
+        // Create a node config with a state snapshot filter (but no fast sync)
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                state_snapshot_filter: StateSnapshotFilterConfig {
+                    account_allowlist: vec![AccountAddress::ONE],
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error = StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Enable fast sync and output syncing
+        let state_sync_driver_config = &mut node_config.state_sync.state_sync_driver;
+        state_sync_driver_config.bootstrapping_mode = BootstrappingMode::DownloadLatestStates;
+        state_sync_driver_config.continuous_syncing_mode =
+            ContinuousSyncingMode::ApplyTransactionOutputs;
+
+        // Verify that sanitization fails (the API and mempool validation are enabled)
+        let error = StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Disable the API and verify that sanitization still fails
+        node_config.api.enabled = false;
+        let error = StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Disable mempool transaction validation
+        node_config.mempool.enable_transaction_validation = false;
+
+        // Verify that sanitization passes for fullnodes (but fails for validators)
+        StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::testnet()),
+        )
+        .unwrap();
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::testnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_sanitize_trusted_peer_only() {
+        // Create a node config with the trusted peer only mode (but no trusted peers)
+        let mut node_config = NodeConfig {
+            state_sync: StateSyncConfig {
+                trusted_peer_only: TrustedPeerOnlyConfig {
+                    enable: true,
+                    trusted_peers: vec![],
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that sanitization fails
+        let error = StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Add duplicate trusted peers and verify that sanitization fails
+        let trusted_peer = PeerId::random();
+        node_config.state_sync.trusted_peer_only.trusted_peers = vec![trusted_peer, trusted_peer];
+        let error = StateSyncConfig::sanitize(
+            &node_config,
+            NodeType::PublicFullnode,
+            Some(ChainId::mainnet()),
+        )
+        .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Use unique trusted peers and verify that sanitization passes for fullnodes
+        node_config.state_sync.trusted_peer_only.trusted_peers =
+            vec![trusted_peer, PeerId::random()];
+        for node_type in [NodeType::PublicFullnode, NodeType::ValidatorFullnode] {
+            StateSyncConfig::sanitize(&node_config, node_type, Some(ChainId::mainnet())).unwrap();
+        }
+
+        // Verify that sanitization fails for validators
+        let error =
+            StateSyncConfig::sanitize(&node_config, NodeType::Validator, Some(ChainId::mainnet()))
+                .unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_bandwidth_off_peak_hours() {
+        // Verify a window that doesn't wrap around midnight
+        let bandwidth_config = AptosDataBandwidthConfig {
+            off_peak_start_hour_utc: 1,
+            off_peak_end_hour_utc: 5,
+            ..Default::default()
+        };
+        assert!(!bandwidth_config.is_off_peak_hour(0));
+        assert!(bandwidth_config.is_off_peak_hour(1));
+        assert!(bandwidth_config.is_off_peak_hour(4));
+        assert!(!bandwidth_config.is_off_peak_hour(5));
+
+        // Verify a window that wraps around midnight
+        let bandwidth_config = AptosDataBandwidthConfig {
+            off_peak_start_hour_utc: 22,
+            off_peak_end_hour_utc: 2,
+            ..Default::default()
+        };
+        assert!(!bandwidth_config.is_off_peak_hour(21));
+        assert!(bandwidth_config.is_off_peak_hour(22));
+        assert!(bandwidth_config.is_off_peak_hour(1));
+        assert!(!bandwidth_config.is_off_peak_hour(2));
+
+        // Verify that an empty window contains no hours
+        let bandwidth_config = AptosDataBandwidthConfig::default();
+        assert!((0..24).all(|hour| !bandwidth_config.is_off_peak_hour(hour)));
+    }
+
     /// Creates and returns a node config with the syncing modes set to execution
     fn create_execution_mode_config() -> NodeConfig {
         NodeConfig {
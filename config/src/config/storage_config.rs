@@ -13,7 +13,7 @@ use arr_macro::arr;
 use number_range::NumberRangeOptions;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     net::{IpAddr, Ipv4Addr, SocketAddr},
     path::{Path, PathBuf},
 };
@@ -76,10 +76,68 @@ impl ShardedDbPathConfig {
     }
 }
 
+/// The compression types supported for RocksDB column families
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RocksdbCompressionType {
+    None,
+    Snappy,
+    Zlib,
+    Bz2,
+    Lz4,
+    Lz4hc,
+    Zstd,
+}
+
+/// The compaction styles supported for RocksDB column families
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RocksdbCompactionStyle {
+    Level,
+    Universal,
+    Fifo,
+}
+
+/// Port selected RocksDB options for tuning individual column families. Options that
+/// are not set fall back to the options of the db (and then to the built-in defaults).
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RocksdbColumnFamilyConfig {
+    /// Block cache size. If set, the column families get a dedicated block cache
+    /// (instead of sharing the block cache of the db). The cache is shared by all
+    /// column families (and shards) the option applies to, e.g., a column family
+    /// override gets a single cache for the column family across all shards.
+    pub block_cache_size: Option<u64>,
+    /// Compression type of each level (starting with level 0). If not set, all
+    /// levels are compressed with LZ4. Note: the compression types must be
+    /// supported by the RocksDB build of the node (otherwise the db fails to open).
+    pub compression_per_level: Option<Vec<RocksdbCompressionType>>,
+    /// Bits per key of the bloom filter. If not set, no bloom filter is used.
+    pub bloom_filter_bits_per_key: Option<u32>,
+    /// Compaction style. If not set, level compaction is used.
+    pub compaction_style: Option<RocksdbCompactionStyle>,
+}
+
+impl RocksdbColumnFamilyConfig {
+    /// Returns the config with the unset options taken from the given fallback config
+    pub fn or(self, fallback: &RocksdbColumnFamilyConfig) -> Self {
+        Self {
+            block_cache_size: self.block_cache_size.or(fallback.block_cache_size),
+            compression_per_level: self
+                .compression_per_level
+                .or_else(|| fallback.compression_per_level.clone()),
+            bloom_filter_bits_per_key: self
+                .bloom_filter_bits_per_key
+                .or(fallback.bloom_filter_bits_per_key),
+            compaction_style: self.compaction_style.or(fallback.compaction_style),
+        }
+    }
+}
+
 /// Port selected RocksDB options for tuning underlying rocksdb instance of AptosDB.
 /// see <https://github.com/facebook/rocksdb/blob/master/include/rocksdb/options.h>
 /// for detailed explanations.
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct RocksdbConfig {
     /// Maximum number of files open by RocksDB at one time
@@ -94,6 +152,21 @@ pub struct RocksdbConfig {
     pub block_size: u64,
     /// Whether cache index and filter blocks into block cache.
     pub cache_index_and_filter_blocks: bool,
+    /// Column family options applied to all column families of the db
+    pub column_family_config: RocksdbColumnFamilyConfig,
+    /// Column family options for individual column families (by column family
+    /// name). These take precedence over `column_family_config`.
+    pub column_family_overrides: BTreeMap<String, RocksdbColumnFamilyConfig>,
+}
+
+impl RocksdbConfig {
+    /// Returns the options of the given column family
+    pub fn get_column_family_config(&self, cf_name: &str) -> RocksdbColumnFamilyConfig {
+        match self.column_family_overrides.get(cf_name) {
+            Some(cf_config) => cf_config.clone().or(&self.column_family_config),
+            None => self.column_family_config.clone(),
+        }
+    }
 }
 
 impl Default for RocksdbConfig {
@@ -113,11 +186,13 @@ impl Default for RocksdbConfig {
             block_size: 4 * (1u64 << 10),
             // Whether cache index and filter blocks into block cache.
             cache_index_and_filter_blocks: false,
+            column_family_config: RocksdbColumnFamilyConfig::default(),
+            column_family_overrides: BTreeMap::new(),
         }
     }
 }
 
-#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct RocksdbConfigs {
     // TODO(grao): Add RocksdbConfig for individual ledger DBs when necessary.
@@ -171,10 +246,163 @@ pub struct StorageConfig {
     /// mode, the indexer db needs to be copied in from another node.
     /// TODO(jill): deprecate Indexer once Indexer Async V2 is ready
     pub enable_indexer: bool,
+    /// Whether to index the write sets by the state keys they write to. This allows the
+    /// history of a state key (or prefix) to be read without replaying the transaction
+    /// outputs, at the cost of additional disk space. The index only covers the versions
+    /// committed since it was (last) enabled.
+    pub enable_write_set_index: bool,
     /// Fine grained control for db paths of individal databases/shards.
     /// If not specificed, will use `dir` as default.
     /// Only allowed when sharding is enabled.
     pub db_path_overrides: Option<DbPathConfig>,
+    /// Disk space guardrails configuration
+    pub disk_space_guardrails_config: DiskSpaceGuardrailsConfig,
+    /// Tiered storage (cold data offloading) configuration
+    pub tiered_storage_config: TieredStorageConfig,
+    /// Background consistency scrubber configuration
+    pub consistency_scrubber_config: ConsistencyScrubberConfig,
+}
+
+/// The disk space guardrails config. When the available disk space drops below
+/// the configured thresholds, the node raises alerts and proactively pauses
+/// non-critical operations (e.g., serving backup reads and heavy API queries)
+/// and accelerates pruning. This avoids running out of disk space, which can
+/// corrupt RocksDB. The guardrails can be overridden via the admin service.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DiskSpaceGuardrailsConfig {
+    /// Whether the available disk space should be monitored
+    pub enable: bool,
+    /// The interval (ms) at which to check the available disk space
+    pub check_interval_ms: u64,
+    /// The available disk space (as a percentage of the total disk space)
+    /// below which alerts are raised.
+    pub warning_available_space_percent: u64,
+    /// The available disk space (as a percentage of the total disk space)
+    /// below which non-critical operations are paused.
+    pub low_available_space_percent: u64,
+}
+
+impl Default for DiskSpaceGuardrailsConfig {
+    fn default() -> Self {
+        Self {
+            enable: true,
+            check_interval_ms: 10_000, // 10 seconds
+            warning_available_space_percent: 15,
+            low_available_space_percent: 5,
+        }
+    }
+}
+
+/// The tiered storage config. When enabled, transactions, events and write sets
+/// older than the cold version horizon are migrated (in compressed segments) to
+/// cold storage (e.g., an object store), and removed from the (hot) ledger db.
+/// Reads of migrated versions are transparently served from cold storage.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TieredStorageConfig {
+    /// Whether cold data should be migrated to cold storage
+    pub enable: bool,
+    /// The number of (most recent) versions that are always kept in the
+    /// ledger db. Older versions are migrated to cold storage.
+    pub cold_version_horizon: u64,
+    /// The number of versions stored in each cold storage segment
+    pub segment_num_versions: u64,
+    /// The interval (ms) at which to check for data to migrate
+    pub migration_interval_ms: u64,
+    /// The max number of (decompressed) segments cached for reads
+    pub max_num_cached_segments: usize,
+    /// The cold storage backend
+    pub backend: ColdStorageBackendConfig,
+}
+
+impl Default for TieredStorageConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            cold_version_horizon: 100_000_000,
+            segment_num_versions: 10_000,
+            migration_interval_ms: 60_000, // 1 minute
+            max_num_cached_segments: 16,
+            backend: ColdStorageBackendConfig::default(),
+        }
+    }
+}
+
+/// The backend holding the cold storage segments
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum ColdStorageBackendConfig {
+    /// Segments are stored in a local (or mounted) directory. If the
+    /// path is relative, it is relative to the storage directory.
+    LocalDirectory { path: PathBuf },
+    /// Segments are stored and fetched using shell commands (e.g., via the
+    /// S3 or GCS CLIs). The segment name is passed to the commands in the
+    /// `$SEGMENT_NAME` environment variable. The put command reads the segment
+    /// from stdin, and the get command writes the segment to stdout, e.g.:
+    ///   put: `aws s3 cp - "s3://<bucket>/$SEGMENT_NAME"`
+    ///   get: `aws s3 cp "s3://<bucket>/$SEGMENT_NAME" -`
+    Command {
+        put_segment_command: String,
+        get_segment_command: String,
+    },
+    /// Segments are stored in an Amazon S3 (or S3-compatible) bucket. Requests
+    /// are signed using the credentials in the `AWS_ACCESS_KEY_ID`,
+    /// `AWS_SECRET_ACCESS_KEY` (and optional `AWS_SESSION_TOKEN`) environment
+    /// variables. If no endpoint is specified, the AWS endpoint of the region is used.
+    S3 {
+        bucket: String,
+        region: String,
+        #[serde(default)]
+        prefix: String,
+        #[serde(default)]
+        endpoint: Option<String>,
+    },
+    /// Segments are stored in a Google Cloud Storage bucket (via the XML API).
+    /// Requests are signed using the HMAC key in the `GCS_HMAC_ACCESS_KEY_ID`
+    /// and `GCS_HMAC_SECRET` environment variables.
+    Gcs {
+        bucket: String,
+        #[serde(default)]
+        prefix: String,
+    },
+}
+
+impl Default for ColdStorageBackendConfig {
+    fn default() -> Self {
+        Self::LocalDirectory {
+            path: PathBuf::from("cold_storage"),
+        }
+    }
+}
+
+/// The consistency scrubber config. When enabled, a background task incrementally
+/// re-verifies the stored transaction accumulator (against the root hash of the latest
+/// ledger info) and the state merkle nodes of the latest state snapshot (against the
+/// state checkpoint hash of the snapshot version). This detects silent disk corruption
+/// before it propagates into served proofs. Corruption is reported via metrics and logs.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ConsistencyScrubberConfig {
+    /// Whether the consistency scrubber should run
+    pub enable: bool,
+    /// The interval (ms) between scrubbing batches (this throttles the scrubber)
+    pub scrub_interval_ms: u64,
+    /// The number of transaction infos verified (against the accumulator) per batch
+    pub transaction_batch_size: u64,
+    /// The number of state merkle nodes verified per batch
+    pub state_merkle_batch_size: u64,
+}
+
+impl Default for ConsistencyScrubberConfig {
+    fn default() -> Self {
+        Self {
+            enable: false,
+            scrub_interval_ms: 1000, // 1 second
+            transaction_batch_size: 1000,
+            state_merkle_batch_size: 1000,
+        }
+    }
 }
 
 pub const NO_OP_STORAGE_PRUNER_CONFIG: PrunerConfig = PrunerConfig {
@@ -321,7 +549,11 @@ impl Default for StorageConfig {
             data_dir: PathBuf::from("/opt/aptos/data"),
             rocksdb_configs: RocksdbConfigs::default(),
             enable_indexer: false,
+            enable_write_set_index: false,
             db_path_overrides: None,
+            disk_space_guardrails_config: DiskSpaceGuardrailsConfig::default(),
+            tiered_storage_config: TieredStorageConfig::default(),
+            consistency_scrubber_config: ConsistencyScrubberConfig::default(),
             buffered_state_target_items: BUFFERED_STATE_TARGET_ITEMS,
             max_num_nodes_per_lru_cache_shard: DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
         }
@@ -417,6 +649,23 @@ impl StorageDirPaths {
             .unwrap_or(&self.default_path)
     }
 
+    /// Returns all (unique) root paths used by the databases and shards
+    pub fn all_root_paths(&self) -> Vec<PathBuf> {
+        let mut root_paths = vec![
+            self.default_root_path().clone(),
+            self.ledger_db_root_path().clone(),
+            self.state_kv_db_metadata_root_path().clone(),
+            self.state_merkle_db_metadata_root_path().clone(),
+        ];
+        for shard_id in 0..16 {
+            root_paths.push(self.state_kv_db_shard_root_path(shard_id).clone());
+            root_paths.push(self.state_merkle_db_shard_root_path(shard_id).clone());
+        }
+        root_paths.sort();
+        root_paths.dedup();
+        root_paths
+    }
+
     pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
         Self {
             default_path: path.as_ref().to_path_buf(),
@@ -517,6 +766,104 @@ impl ConfigSanitizer for StorageConfig {
             ));
         }
 
+        let disk_space_guardrails_config = &config.disk_space_guardrails_config;
+        if disk_space_guardrails_config.warning_available_space_percent > 100
+            || disk_space_guardrails_config.low_available_space_percent
+                > disk_space_guardrails_config.warning_available_space_percent
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                format!(
+                    "The disk space thresholds are invalid! The low threshold ({}%) must not exceed the warning threshold ({}%), which must not exceed 100%.",
+                    disk_space_guardrails_config.low_available_space_percent,
+                    disk_space_guardrails_config.warning_available_space_percent
+                ),
+            ));
+        }
+
+        let tiered_storage_config = &config.tiered_storage_config;
+        if tiered_storage_config.enable {
+            if tiered_storage_config.segment_num_versions == 0 {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    "The tiered storage segment_num_versions must be greater than 0.".to_string(),
+                ));
+            }
+            if let ColdStorageBackendConfig::S3 { bucket, .. }
+            | ColdStorageBackendConfig::Gcs { bucket, .. } = &tiered_storage_config.backend
+            {
+                if bucket.is_empty() {
+                    return Err(Error::ConfigSanitizerFailed(
+                        sanitizer_name,
+                        "The tiered storage object store bucket must not be empty.".to_string(),
+                    ));
+                }
+            }
+            let ledger_pruner_config = &config.storage_pruner_config.ledger_pruner_config;
+            if ledger_pruner_config.enable
+                && ledger_prune_window <= tiered_storage_config.cold_version_horizon
+            {
+                return Err(Error::ConfigSanitizerFailed(
+                    sanitizer_name,
+                    format!(
+                        "The ledger prune_window ({}) must exceed the tiered storage cold_version_horizon ({}), otherwise data is pruned before it is migrated to cold storage.",
+                        ledger_prune_window, tiered_storage_config.cold_version_horizon
+                    ),
+                ));
+            }
+        }
+
+        let consistency_scrubber_config = &config.consistency_scrubber_config;
+        if consistency_scrubber_config.enable
+            && (consistency_scrubber_config.transaction_batch_size == 0
+                || consistency_scrubber_config.state_merkle_batch_size == 0)
+        {
+            return Err(Error::ConfigSanitizerFailed(
+                sanitizer_name,
+                "The consistency scrubber batch sizes must be greater than 0.".to_string(),
+            ));
+        }
+
+        let rocksdb_configs = &config.rocksdb_configs;
+        for (db_name, rocksdb_config) in [
+            ("ledger_db_config", &rocksdb_configs.ledger_db_config),
+            (
+                "state_merkle_db_config",
+                &rocksdb_configs.state_merkle_db_config,
+            ),
+            ("state_kv_db_config", &rocksdb_configs.state_kv_db_config),
+            ("index_db_config", &rocksdb_configs.index_db_config),
+        ] {
+            let cf_configs = std::iter::once(&rocksdb_config.column_family_config)
+                .chain(rocksdb_config.column_family_overrides.values());
+            for cf_config in cf_configs {
+                if cf_config
+                    .compression_per_level
+                    .as_ref()
+                    .map_or(false, |compression_per_level| {
+                        compression_per_level.is_empty()
+                    })
+                {
+                    return Err(Error::ConfigSanitizerFailed(
+                        sanitizer_name,
+                        format!(
+                            "The compression_per_level of the {} column families cannot be empty.",
+                            db_name
+                        ),
+                    ));
+                }
+                if cf_config.bloom_filter_bits_per_key == Some(0) {
+                    return Err(Error::ConfigSanitizerFailed(
+                        sanitizer_name,
+                        format!(
+                            "The bloom_filter_bits_per_key of the {} column families must be greater than 0.",
+                            db_name
+                        ),
+                    ));
+                }
+            }
+        }
+
         if let Some(db_path_overrides) = config.db_path_overrides.as_ref() {
             if !config.rocksdb_configs.enable_storage_sharding {
                 return Err(Error::ConfigSanitizerFailed(
@@ -574,7 +921,224 @@ impl ConfigSanitizer for StorageConfig {
 
 #[cfg(test)]
 mod test {
-    use crate::config::{PrunerConfig, ShardPathConfig, ShardedDbPathConfig};
+    use crate::config::{
+        config_sanitizer::ConfigSanitizer, node_config_loader::NodeType, ColdStorageBackendConfig,
+        ConsistencyScrubberConfig, DiskSpaceGuardrailsConfig, Error, NodeConfig, PrunerConfig,
+        RocksdbColumnFamilyConfig, RocksdbCompactionStyle, RocksdbCompressionType, RocksdbConfig,
+        RocksdbConfigs, ShardPathConfig, ShardedDbPathConfig, StorageConfig, TieredStorageConfig,
+    };
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_sanitize_disk_space_guardrails() {
+        // Create a node config with a low threshold above the warning threshold
+        let node_config = NodeConfig {
+            storage: StorageConfig {
+                disk_space_guardrails_config: DiskSpaceGuardrailsConfig {
+                    warning_available_space_percent: 10,
+                    low_available_space_percent: 20,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error = StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that the default config passes sanitization
+        let node_config = NodeConfig::default();
+        StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_consistency_scrubber() {
+        // Create a node config with the consistency scrubber enabled (and an empty batch size)
+        let mut node_config = NodeConfig {
+            storage: StorageConfig {
+                consistency_scrubber_config: ConsistencyScrubberConfig {
+                    enable: true,
+                    state_merkle_batch_size: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error = StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Disable the scrubber and verify that the config passes sanitization
+        node_config.storage.consistency_scrubber_config.enable = false;
+        StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+
+        // Enable the scrubber (with the default batch sizes) and verify the config passes
+        node_config.storage.consistency_scrubber_config = ConsistencyScrubberConfig {
+            enable: true,
+            ..Default::default()
+        };
+        StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+    }
+
+    #[test]
+    fn test_sanitize_rocksdb_column_family_configs() {
+        // Create a node config with an empty compression_per_level
+        let node_config = NodeConfig {
+            storage: StorageConfig {
+                rocksdb_configs: RocksdbConfigs {
+                    state_kv_db_config: RocksdbConfig {
+                        column_family_overrides: BTreeMap::from([(
+                            "state_value".to_string(),
+                            RocksdbColumnFamilyConfig {
+                                compression_per_level: Some(vec![]),
+                                ..Default::default()
+                            },
+                        )]),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error = StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Create a node config with a zero bloom_filter_bits_per_key
+        let node_config = NodeConfig {
+            storage: StorageConfig {
+                rocksdb_configs: RocksdbConfigs {
+                    ledger_db_config: RocksdbConfig {
+                        column_family_config: RocksdbColumnFamilyConfig {
+                            bloom_filter_bits_per_key: Some(0),
+                            ..Default::default()
+                        },
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        // Verify that the config fails sanitization
+        let error = StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+    }
+
+    #[test]
+    fn test_get_column_family_config() {
+        let rocksdb_config = RocksdbConfig {
+            column_family_config: RocksdbColumnFamilyConfig {
+                bloom_filter_bits_per_key: Some(10),
+                compaction_style: Some(RocksdbCompactionStyle::Level),
+                ..Default::default()
+            },
+            column_family_overrides: BTreeMap::from([(
+                "state_value".to_string(),
+                RocksdbColumnFamilyConfig {
+                    block_cache_size: Some(1 << 30),
+                    compression_per_level: Some(vec![
+                        RocksdbCompressionType::None,
+                        RocksdbCompressionType::Lz4,
+                        RocksdbCompressionType::Zstd,
+                    ]),
+                    compaction_style: Some(RocksdbCompactionStyle::Universal),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        // Verify that the overrides take precedence over the db-wide options
+        assert_eq!(
+            rocksdb_config.get_column_family_config("state_value"),
+            RocksdbColumnFamilyConfig {
+                block_cache_size: Some(1 << 30),
+                compression_per_level: Some(vec![
+                    RocksdbCompressionType::None,
+                    RocksdbCompressionType::Lz4,
+                    RocksdbCompressionType::Zstd,
+                ]),
+                bloom_filter_bits_per_key: Some(10),
+                compaction_style: Some(RocksdbCompactionStyle::Universal),
+            }
+        );
+
+        // Verify that the db-wide options apply to the other column families
+        assert_eq!(
+            rocksdb_config.get_column_family_config("db_metadata"),
+            rocksdb_config.column_family_config
+        );
+    }
+
+    #[test]
+    fn test_sanitize_tiered_storage() {
+        // Create a node config with a ledger prune window below the cold version horizon
+        let mut node_config = NodeConfig {
+            storage: StorageConfig {
+                tiered_storage_config: TieredStorageConfig {
+                    enable: true,
+                    cold_version_horizon: 100_000_000,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        node_config
+            .storage
+            .storage_pruner_config
+            .ledger_pruner_config
+            .prune_window = 50_000_000;
+
+        // Verify that the config fails sanitization
+        let error = StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Disable the ledger pruner and verify that the config passes sanitization
+        node_config
+            .storage
+            .storage_pruner_config
+            .ledger_pruner_config
+            .enable = false;
+        StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+
+        // Verify that empty segments are rejected
+        node_config
+            .storage
+            .tiered_storage_config
+            .segment_num_versions = 0;
+        let error = StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+
+        // Verify that object store backends require a bucket
+        node_config
+            .storage
+            .tiered_storage_config
+            .segment_num_versions = 10_000;
+        node_config.storage.tiered_storage_config.backend = ColdStorageBackendConfig::Gcs {
+            bucket: "".into(),
+            prefix: "ledger/".into(),
+        };
+        let error = StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap_err();
+        assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
+        node_config.storage.tiered_storage_config.backend = ColdStorageBackendConfig::S3 {
+            bucket: "cold-ledger".into(),
+            region: "us-west-2".into(),
+            prefix: "".into(),
+            endpoint: None,
+        };
+        StorageConfig::sanitize(&node_config, NodeType::Validator, None).unwrap();
+    }
 
     #[test]
     pub fn test_default_prune_window() {
@@ -0,0 +1,542 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{counters, Error};
+use aptos_config::config::SafetyRulesAuditLogConfig;
+use aptos_consensus_types::common::Round;
+use aptos_crypto::HashValue;
+use aptos_logger::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::{Path, PathBuf},
+};
+
+/// The name of the active audit log file. Rotated files are
+/// suffixed with their index (e.g., `signing_audit.log.1`).
+const AUDIT_LOG_FILE_NAME: &str = "signing_audit.log";
+
+/// The signing operations recorded in the audit log
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningOperation {
+    CommitVote,
+    Proposal,
+    Timeout,
+    Vote,
+}
+
+/// The decision made by safety rules for a signing request
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SigningDecision {
+    Signed,
+    Refused(String), // The reason for refusing to sign
+}
+
+/// The contents of an audit log record (i.e., everything covered by the hash)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AuditLogRecordData {
+    pub sequence_number: u64, // The sequence number of the record (across all files)
+    pub timestamp_usecs: u64, // The time (in microseconds) the decision was made
+    pub operation: SigningOperation,
+    pub epoch: u64,
+    pub round: Round,
+    pub id: HashValue, // The id of the signed object (e.g., the block id)
+    pub decision: SigningDecision,
+    pub previous_hash: HashValue, // The hash of the previous record in the chain
+}
+
+impl AuditLogRecordData {
+    /// Returns the hash of the record data
+    fn hash(&self) -> Result<HashValue, Error> {
+        let bytes = bcs::to_bytes(self).map_err(|error| {
+            Error::SerializationError(format!("Failed to serialize audit record: {}", error))
+        })?;
+        Ok(HashValue::sha3_256_of(&bytes))
+    }
+}
+
+/// A single (hash-chained) record in the audit log
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct AuditLogRecord {
+    #[serde(flatten)]
+    pub data: AuditLogRecordData,
+    pub hash: HashValue,
+}
+
+/// An append-only, hash-chained audit log of all signing decisions made by
+/// safety rules. Each record contains the hash of the previous record, so the
+/// log can be used to prove the signing behavior of the validator (and any
+/// modifications can be detected). Records are written as JSON lines, and the
+/// log is rotated once the active file reaches the configured size limit.
+pub struct SigningAuditLog {
+    audit_log_config: SafetyRulesAuditLogConfig,
+    active_file: File,
+    active_file_size_bytes: u64,
+    last_hash: HashValue,
+    next_sequence_number: u64,
+}
+
+impl SigningAuditLog {
+    /// Opens the audit log and resumes the hash chain from the last record
+    pub fn open(audit_log_config: SafetyRulesAuditLogConfig) -> Result<Self, Error> {
+        // Create the audit log directory (if it doesn't exist)
+        let directory = audit_log_config.path();
+        fs::create_dir_all(&directory).map_err(|error| {
+            Error::InternalError(format!(
+                "Failed to create the audit log directory {:?}: {}",
+                directory, error
+            ))
+        })?;
+
+        // Remove any partially written record at the end of the active file
+        // (e.g., if the process crashed while appending a record).
+        let active_file_path = get_audit_log_file_path(&directory, 0);
+        if active_file_path.exists() {
+            truncate_partial_record(&active_file_path)?;
+        }
+
+        // Identify the last record in the log (to resume the chain)
+        let mut last_record = None;
+        for path in get_audit_log_paths(&audit_log_config).iter().rev() {
+            if let Some(record) = read_audit_log_file(path)?.pop() {
+                last_record = Some(record);
+                break;
+            }
+        }
+        let (last_hash, next_sequence_number) = match last_record {
+            Some(record) => (record.hash, record.data.sequence_number + 1),
+            None => (HashValue::zero(), 0),
+        };
+
+        // Open the active file
+        let active_file = open_audit_log_file(&active_file_path)?;
+        let active_file_size_bytes = get_file_size_bytes(&active_file)?;
+
+        Ok(Self {
+            audit_log_config,
+            active_file,
+            active_file_size_bytes,
+            last_hash,
+            next_sequence_number,
+        })
+    }
+
+    /// Appends a record for the given signing decision to the audit log
+    pub fn record(
+        &mut self,
+        operation: SigningOperation,
+        epoch: u64,
+        round: Round,
+        id: HashValue,
+        decision: SigningDecision,
+    ) -> Result<(), Error> {
+        // Create the record (chained to the previous record)
+        let data = AuditLogRecordData {
+            sequence_number: self.next_sequence_number,
+            timestamp_usecs: aptos_infallible::duration_since_epoch().as_micros() as u64,
+            operation,
+            epoch,
+            round,
+            id,
+            decision,
+            previous_hash: self.last_hash,
+        };
+        let hash = data.hash()?;
+        let record = AuditLogRecord { data, hash };
+        let mut line = serde_json::to_vec(&record)?;
+        line.push(b'\n');
+
+        // Rotate the log if the active file is full
+        let line_size_bytes = line.len() as u64;
+        if self.active_file_size_bytes > 0
+            && self.active_file_size_bytes + line_size_bytes
+                > self.audit_log_config.max_file_size_bytes
+        {
+            self.rotate()?;
+        }
+
+        // Append the record and sync it to disk
+        {
+            let _timer = counters::start_timer("internal", "audit_log_sync");
+            self.active_file
+                .write_all(&line)
+                .and_then(|_| self.active_file.sync_data())
+                .map_err(|error| {
+                    Error::InternalError(format!("Failed to write the audit record: {}", error))
+                })?;
+        }
+
+        // Update the chain state
+        self.active_file_size_bytes += line_size_bytes;
+        self.last_hash = hash;
+        self.next_sequence_number += 1;
+
+        Ok(())
+    }
+
+    /// Rotates the audit log files (the oldest file is deleted if
+    /// the max number of files would be exceeded).
+    fn rotate(&mut self) -> Result<(), Error> {
+        let directory = self.audit_log_config.path();
+        let max_num_files = self.audit_log_config.max_num_files;
+
+        // Shift the rotated files (removing the oldest file)
+        for index in (1..max_num_files).rev() {
+            let path = get_audit_log_file_path(&directory, index);
+            if !path.exists() {
+                continue;
+            }
+            let result = if index + 1 >= max_num_files {
+                fs::remove_file(&path)
+            } else {
+                fs::rename(&path, get_audit_log_file_path(&directory, index + 1))
+            };
+            result.map_err(|error| {
+                Error::InternalError(format!(
+                    "Failed to rotate the audit log file {:?}: {}",
+                    path, error
+                ))
+            })?;
+        }
+
+        // Rotate the active file (or remove it if only a single file is kept)
+        let active_file_path = get_audit_log_file_path(&directory, 0);
+        let result = if max_num_files > 1 {
+            fs::rename(&active_file_path, get_audit_log_file_path(&directory, 1))
+        } else {
+            fs::remove_file(&active_file_path)
+        };
+        result.map_err(|error| {
+            Error::InternalError(format!("Failed to rotate the active audit log: {}", error))
+        })?;
+
+        // Open a new active file
+        self.active_file = open_audit_log_file(&active_file_path)?;
+        self.active_file_size_bytes = 0;
+
+        Ok(())
+    }
+
+    /// Reopens the active file as read-only, so that all writes fail
+    #[cfg(test)]
+    pub(crate) fn make_read_only_for_test(&mut self) {
+        let active_file_path = get_audit_log_file_path(&self.audit_log_config.path(), 0);
+        self.active_file = File::open(active_file_path).unwrap();
+    }
+
+    /// Exports all records in the audit log (from oldest to newest) and
+    /// verifies that the hash chain is intact. Note: the chain is verified
+    /// from the oldest retained record (older records may have been rotated).
+    pub fn export(
+        audit_log_config: &SafetyRulesAuditLogConfig,
+    ) -> Result<Vec<AuditLogRecord>, Error> {
+        let mut records = vec![];
+        for path in get_audit_log_paths(audit_log_config) {
+            records.extend(read_audit_log_file(&path)?);
+        }
+        verify_audit_log_records(&records)?;
+        Ok(records)
+    }
+}
+
+/// Verifies that the given records form a valid hash chain
+pub fn verify_audit_log_records(records: &[AuditLogRecord]) -> Result<(), Error> {
+    let mut previous_record: Option<&AuditLogRecord> = None;
+    for record in records {
+        // Verify the record hash
+        let expected_hash = record.data.hash()?;
+        if record.hash != expected_hash {
+            return Err(Error::InternalError(format!(
+                "Audit record {} has an invalid hash! Expected: {}, found: {}",
+                record.data.sequence_number, expected_hash, record.hash
+            )));
+        }
+
+        // Verify the record is chained to the previous record
+        if let Some(previous_record) = previous_record {
+            if record.data.previous_hash != previous_record.hash
+                || record.data.sequence_number != previous_record.data.sequence_number + 1
+            {
+                return Err(Error::InternalError(format!(
+                    "Audit record {} is not chained to the previous record {}!",
+                    record.data.sequence_number, previous_record.data.sequence_number
+                )));
+            }
+        }
+        previous_record = Some(record);
+    }
+
+    Ok(())
+}
+
+/// Returns the paths of the existing audit log files (from oldest to newest)
+fn get_audit_log_paths(audit_log_config: &SafetyRulesAuditLogConfig) -> Vec<PathBuf> {
+    let directory = audit_log_config.path();
+    (0..audit_log_config.max_num_files)
+        .rev()
+        .map(|index| get_audit_log_file_path(&directory, index))
+        .filter(|path| path.exists())
+        .collect()
+}
+
+/// Returns the path of the audit log file with the given index (0 is the active file)
+fn get_audit_log_file_path(directory: &Path, index: u64) -> PathBuf {
+    if index == 0 {
+        directory.join(AUDIT_LOG_FILE_NAME)
+    } else {
+        directory.join(format!("{}.{}", AUDIT_LOG_FILE_NAME, index))
+    }
+}
+
+/// Opens the audit log file at the given path (in append mode)
+fn open_audit_log_file(path: &Path) -> Result<File, Error> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|error| {
+            Error::InternalError(format!(
+                "Failed to open the audit log file {:?}: {}",
+                path, error
+            ))
+        })
+}
+
+/// Returns the size of the given file
+fn get_file_size_bytes(file: &File) -> Result<u64, Error> {
+    file.metadata()
+        .map(|metadata| metadata.len())
+        .map_err(|error| {
+            Error::InternalError(format!("Failed to read the audit log metadata: {}", error))
+        })
+}
+
+/// Truncates the audit log file at the given path to the end of its last complete
+/// record (i.e., the last newline). Records are always written with a trailing
+/// newline, so any bytes after the last newline belong to a partial (torn) record.
+fn truncate_partial_record(path: &Path) -> Result<(), Error> {
+    let to_error = |error: std::io::Error| {
+        Error::InternalError(format!(
+            "Failed to truncate the audit log file {:?}: {}",
+            path, error
+        ))
+    };
+
+    let bytes = fs::read(path).map_err(to_error)?;
+    let complete_length = bytes
+        .iter()
+        .rposition(|byte| *byte == b'\n')
+        .map_or(0, |index| index + 1);
+    if complete_length == bytes.len() {
+        return Ok(()); // There is no partial record
+    }
+
+    warn!(
+        "Truncating a partial record ({} bytes) at the end of the audit log file {:?}",
+        bytes.len() - complete_length,
+        path
+    );
+    let file = OpenOptions::new()
+        .write(true)
+        .open(path)
+        .map_err(to_error)?;
+    file.set_len(complete_length as u64)
+        .and_then(|_| file.sync_all())
+        .map_err(to_error)
+}
+
+/// Reads all records in the audit log file at the given path
+fn read_audit_log_file(path: &Path) -> Result<Vec<AuditLogRecord>, Error> {
+    let file = File::open(path).map_err(|error| {
+        Error::InternalError(format!(
+            "Failed to open the audit log file {:?}: {}",
+            path, error
+        ))
+    })?;
+
+    let mut records = vec![];
+    for line in BufReader::new(file).lines() {
+        let line = line.map_err(|error| {
+            Error::InternalError(format!(
+                "Failed to read the audit log file {:?}: {}",
+                path, error
+            ))
+        })?;
+        if !line.is_empty() {
+            records.push(serde_json::from_str(&line)?);
+        }
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_temppath::TempPath;
+
+    #[test]
+    fn test_record_and_export() {
+        // Create an audit log
+        let temp_path = TempPath::new();
+        let audit_log_config = create_audit_log_config(&temp_path, u64::MAX, 1);
+        let mut audit_log = SigningAuditLog::open(audit_log_config.clone()).unwrap();
+
+        // Record several signing decisions
+        record_decisions(&mut audit_log, 10);
+
+        // Export the records and verify the chain
+        let records = SigningAuditLog::export(&audit_log_config).unwrap();
+        assert_eq!(records.len(), 10);
+        assert_eq!(records[0].data.previous_hash, HashValue::zero());
+        for (index, record) in records.iter().enumerate() {
+            assert_eq!(record.data.sequence_number, index as u64);
+            assert_eq!(record.data.round, index as u64);
+        }
+        assert_eq!(
+            records[1].data.decision,
+            SigningDecision::Refused("Not safe to vote!".into())
+        );
+
+        // Reopen the audit log and verify the chain is resumed
+        let mut audit_log = SigningAuditLog::open(audit_log_config.clone()).unwrap();
+        record_decisions(&mut audit_log, 1);
+        let records = SigningAuditLog::export(&audit_log_config).unwrap();
+        assert_eq!(records.len(), 11);
+        assert_eq!(records[10].data.sequence_number, 10);
+    }
+
+    #[test]
+    fn test_tampered_records() {
+        // Create an audit log and record several signing decisions
+        let temp_path = TempPath::new();
+        let audit_log_config = create_audit_log_config(&temp_path, u64::MAX, 1);
+        let mut audit_log = SigningAuditLog::open(audit_log_config.clone()).unwrap();
+        record_decisions(&mut audit_log, 5);
+
+        // Tamper with a record and verify the hash check fails
+        let mut records = SigningAuditLog::export(&audit_log_config).unwrap();
+        records[2].data.decision = SigningDecision::Signed;
+        assert!(verify_audit_log_records(&records).is_err());
+
+        // Remove a record and verify the chain check fails
+        let mut records = SigningAuditLog::export(&audit_log_config).unwrap();
+        records.remove(2);
+        assert!(verify_audit_log_records(&records).is_err());
+    }
+
+    #[test]
+    fn test_partial_record() {
+        // Create an audit log and record several signing decisions
+        let temp_path = TempPath::new();
+        let audit_log_config = create_audit_log_config(&temp_path, u64::MAX, 1);
+        let mut audit_log = SigningAuditLog::open(audit_log_config.clone()).unwrap();
+        record_decisions(&mut audit_log, 5);
+        drop(audit_log);
+
+        // Append a partial record (e.g., the process crashed mid-write)
+        let active_file_path = get_audit_log_file_path(&audit_log_config.path(), 0);
+        let complete_size_bytes = fs::metadata(&active_file_path).unwrap().len();
+        let mut file = open_audit_log_file(&active_file_path).unwrap();
+        file.write_all(b"{\"sequence_number\":5,\"times").unwrap();
+
+        // Reopen the audit log and verify the partial record was removed
+        let mut audit_log = SigningAuditLog::open(audit_log_config.clone()).unwrap();
+        assert_eq!(
+            fs::metadata(&active_file_path).unwrap().len(),
+            complete_size_bytes
+        );
+
+        // Verify the chain is resumed from the last complete record
+        record_decisions(&mut audit_log, 1);
+        let records = SigningAuditLog::export(&audit_log_config).unwrap();
+        assert_eq!(records.len(), 6);
+        assert_eq!(records[5].data.sequence_number, 5);
+    }
+
+    #[test]
+    fn test_rotation() {
+        // Create an audit log that rotates after every few records
+        let temp_path = TempPath::new();
+        let max_num_files = 3;
+        let audit_log_config = create_audit_log_config(&temp_path, 1000, max_num_files);
+        let mut audit_log = SigningAuditLog::open(audit_log_config.clone()).unwrap();
+
+        // Record many signing decisions
+        let num_decisions = 100;
+        record_decisions(&mut audit_log, num_decisions);
+
+        // Verify the number of files is bounded
+        let paths = get_audit_log_paths(&audit_log_config);
+        assert_eq!(paths.len() as u64, max_num_files);
+        for path in paths {
+            assert!(fs::metadata(path).unwrap().len() <= 1000);
+        }
+
+        // Verify the retained records are the most recent and form a valid chain
+        let records = SigningAuditLog::export(&audit_log_config).unwrap();
+        assert!(records.len() < num_decisions as usize);
+        assert_eq!(
+            records.last().unwrap().data.sequence_number,
+            num_decisions - 1
+        );
+    }
+
+    #[test]
+    fn test_failed_record() {
+        let temp_path = TempPath::new();
+        let audit_log_config = create_audit_log_config(&temp_path, u64::MAX, 1);
+        let mut audit_log = SigningAuditLog::open(audit_log_config).unwrap();
+
+        // Verify that recording fails if the record can't be written
+        audit_log.make_read_only_for_test();
+        assert!(audit_log
+            .record(
+                SigningOperation::Vote,
+                1,
+                0,
+                HashValue::random(),
+                SigningDecision::Signed
+            )
+            .is_err());
+        assert_eq!(audit_log.next_sequence_number, 0);
+    }
+
+    /// Creates an audit log config at the given path with the specified limits
+    fn create_audit_log_config(
+        temp_path: &TempPath,
+        max_file_size_bytes: u64,
+        max_num_files: u64,
+    ) -> SafetyRulesAuditLogConfig {
+        SafetyRulesAuditLogConfig {
+            enabled: true,
+            path: temp_path.path().to_path_buf(),
+            max_file_size_bytes,
+            max_num_files,
+            ..Default::default()
+        }
+    }
+
+    /// Records the given number of signing decisions (every other decision is a refusal)
+    fn record_decisions(audit_log: &mut SigningAuditLog, num_decisions: u64) {
+        for _ in 0..num_decisions {
+            let round = audit_log.next_sequence_number;
+            let decision = if round % 2 == 0 {
+                SigningDecision::Signed
+            } else {
+                SigningDecision::Refused("Not safe to vote!".into())
+            };
+            audit_log
+                .record(
+                    SigningOperation::Vote,
+                    1,
+                    round,
+                    HashValue::random(),
+                    decision,
+                )
+                .unwrap();
+        }
+    }
+}
@@ -56,6 +56,12 @@ pub enum Error {
     WaypointOutOfDate(u64, u64, u64, u64),
     #[error("Invalid Timeout: {0}")]
     InvalidTimeout(String),
+    #[error("Message authentication failed: {0}")]
+    AuthenticationError(String),
+    #[error("Message is too large! Size: {0} bytes, limit: {1} bytes")]
+    MessageTooLarge(u64, u64),
+    #[error("Request exceeded the latency budget of {0} ms")]
+    LatencyBudgetExceeded(u64),
 }
 
 impl From<serde_json::Error> for Error {
@@ -4,11 +4,13 @@
 
 #![forbid(unsafe_code)]
 
+mod audit_log;
 mod consensus_state;
 mod counters;
 mod error;
 mod local_client;
 mod logging;
+mod message_authenticator;
 mod persistent_safety_storage;
 mod process;
 mod remote_service;
@@ -20,9 +22,18 @@ mod t_safety_rules;
 mod thread;
 
 pub use crate::{
-    consensus_state::ConsensusState, error::Error,
-    persistent_safety_storage::PersistentSafetyStorage, process::Process,
-    safety_rules::SafetyRules, safety_rules_manager::SafetyRulesManager,
+    audit_log::{
+        verify_audit_log_records, AuditLogRecord, AuditLogRecordData, SigningAuditLog,
+        SigningDecision, SigningOperation,
+    },
+    consensus_state::ConsensusState,
+    error::Error,
+    message_authenticator::MessageAuthenticator,
+    persistent_safety_storage::PersistentSafetyStorage,
+    process::Process,
+    remote_service::RemoteChannelConfig,
+    safety_rules::SafetyRules,
+    safety_rules_manager::SafetyRulesManager,
     t_safety_rules::TSafetyRules,
 };
 
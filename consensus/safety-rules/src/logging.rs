@@ -41,6 +41,7 @@ impl<'a> SafetyLogSchema<'a> {
 #[derive(Clone, Copy, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LogEntry {
+    AuditLog,
     ConsensusState,
     ConstructAndSignVoteTwoChain,
     Epoch,
@@ -59,6 +60,7 @@ pub enum LogEntry {
 impl LogEntry {
     pub fn as_str(&self) -> &'static str {
         match self {
+            LogEntry::AuditLog => "audit_log",
             LogEntry::ConsensusState => "consensus_state",
             LogEntry::ConstructAndSignVoteTwoChain => "construct_and_sign_vote_2chain",
             LogEntry::Epoch => "epoch",
@@ -0,0 +1,41 @@
+// Copyright © Aptos Foundation
+// Parts of the project are originally copyright © Meta Platforms, Inc.
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+//! Runs safety rules in a separate process. Consensus communicates with
+//! the process over a local socket (see `SafetyRulesService::Process`).
+
+use aptos_config::config::NodeConfig;
+use aptos_logger::prelude::*;
+use aptos_safety_rules::Process;
+use std::{env, process};
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() != 2 {
+        eprintln!("Incorrect number of parameters, expected a path to a config file");
+        process::exit(1);
+    }
+
+    let config = NodeConfig::load_from_path(&args[1]).unwrap_or_else(|error| {
+        eprintln!("Failed to load node config file: {:?}", error);
+        process::exit(1);
+    });
+
+    let logger_config = &config.consensus.safety_rules.logger;
+    aptos_logger::Logger::new()
+        .channel_size(logger_config.chan_size)
+        .is_async(logger_config.is_async)
+        .level(logger_config.level)
+        .init();
+
+    info!(
+        "Starting the safety rules process with service: {:?}",
+        config.consensus.safety_rules.service
+    );
+
+    let mut service = Process::new(config.consensus.safety_rules);
+    service.start();
+}
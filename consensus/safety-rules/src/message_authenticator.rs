@@ -0,0 +1,114 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Error;
+use aptos_crypto::HashValue;
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use std::{fs, path::Path};
+
+/// The minimum length (in bytes) of the shared authentication key
+const MIN_AUTHENTICATION_KEY_LENGTH: usize = 32;
+
+/// The length (in bytes) of the nonces exchanged during the session handshake
+pub const SESSION_NONCE_LENGTH: usize = 32;
+
+/// A message envelope exchanged between consensus and a remote safety rules
+/// process. The envelope schema is strict: unknown fields are rejected.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+struct AuthenticatedMessage {
+    session_id: HashValue,
+    sequence_number: u64,
+    payload: Vec<u8>,
+    mac: HashValue,
+}
+
+/// Authenticates messages using a secret shared by consensus and the remote
+/// safety rules process. The MAC is an HMAC-SHA256 over the session id,
+/// sequence number and payload of the message.
+#[derive(Clone)]
+pub struct MessageAuthenticator {
+    key: hmac::Key,
+}
+
+impl MessageAuthenticator {
+    pub fn new(key: Vec<u8>) -> Result<Self, Error> {
+        if key.len() < MIN_AUTHENTICATION_KEY_LENGTH {
+            return Err(Error::AuthenticationError(format!(
+                "The authentication key is too short! Expected at least {} bytes, found {}",
+                MIN_AUTHENTICATION_KEY_LENGTH,
+                key.len()
+            )));
+        }
+
+        Ok(Self {
+            key: hmac::Key::new(hmac::HMAC_SHA256, &key),
+        })
+    }
+
+    /// Loads the shared authentication key from the given file
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let key = fs::read(path).map_err(|error| {
+            Error::AuthenticationError(format!(
+                "Failed to read the authentication key at {:?}: {}",
+                path, error
+            ))
+        })?;
+        Self::new(key)
+    }
+
+    /// Wraps the payload in an authenticated envelope for the given session and sequence number
+    pub fn seal(
+        &self,
+        session_id: HashValue,
+        sequence_number: u64,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let tag = hmac::sign(&self.key, &mac_input(session_id, sequence_number, payload));
+        let mac = HashValue::from_slice(tag.as_ref())
+            .map_err(|error| Error::AuthenticationError(error.to_string()))?;
+        let message = AuthenticatedMessage {
+            session_id,
+            sequence_number,
+            payload: payload.to_vec(),
+            mac,
+        };
+        Ok(serde_json::to_vec(&message)?)
+    }
+
+    /// Verifies the authenticated envelope and returns the session id, sequence number and payload
+    pub fn open(&self, message: &[u8]) -> Result<(HashValue, u64, Vec<u8>), Error> {
+        let message: AuthenticatedMessage = serde_json::from_slice(message)?;
+        let mac_input = mac_input(
+            message.session_id,
+            message.sequence_number,
+            &message.payload,
+        );
+        if hmac::verify(&self.key, &mac_input, message.mac.as_ref()).is_err() {
+            return Err(Error::AuthenticationError(format!(
+                "Invalid message authentication code for sequence number: {}",
+                message.sequence_number
+            )));
+        }
+
+        Ok((message.session_id, message.sequence_number, message.payload))
+    }
+}
+
+/// Returns the input of the MAC (i.e., the session id, sequence number and payload)
+fn mac_input(session_id: HashValue, sequence_number: u64, payload: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(HashValue::LENGTH + 8 + payload.len());
+    message.extend_from_slice(session_id.as_ref());
+    message.extend_from_slice(&sequence_number.to_le_bytes());
+    message.extend_from_slice(payload);
+    message
+}
+
+/// Derives the id of a session from the nonces chosen by the client and the server
+pub fn derive_session_id(client_nonce: &[u8], server_nonce: &[u8]) -> HashValue {
+    let mut nonces = Vec::with_capacity(client_nonce.len() + server_nonce.len());
+    nonces.extend_from_slice(client_nonce);
+    nonces.extend_from_slice(server_nonce);
+    HashValue::sha3_256_of(&nonces)
+}
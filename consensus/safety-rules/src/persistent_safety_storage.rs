@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    audit_log::SigningAuditLog,
     counters,
     logging::{self, LogEntry, LogEvent},
     Error,
@@ -26,6 +27,7 @@ pub struct PersistentSafetyStorage {
     enable_cached_safety_data: bool,
     cached_safety_data: Option<SafetyData>,
     internal_store: Storage,
+    signing_audit_log: Option<SigningAuditLog>,
 }
 
 impl PersistentSafetyStorage {
@@ -48,6 +50,7 @@ impl PersistentSafetyStorage {
             enable_cached_safety_data,
             cached_safety_data: Some(safety_data.clone()),
             internal_store,
+            signing_audit_log: None,
         };
 
         // Initialize the safety data and waypoint
@@ -88,9 +91,21 @@ impl PersistentSafetyStorage {
             enable_cached_safety_data,
             cached_safety_data: None,
             internal_store,
+            signing_audit_log: None,
         }
     }
 
+    /// Records all signing decisions in the given audit log
+    pub fn with_signing_audit_log(mut self, signing_audit_log: SigningAuditLog) -> Self {
+        self.signing_audit_log = Some(signing_audit_log);
+        self
+    }
+
+    /// Returns the signing audit log (if one is being recorded)
+    pub fn signing_audit_log(&mut self) -> Option<&mut SigningAuditLog> {
+        self.signing_audit_log.as_mut()
+    }
+
     pub fn author(&self) -> Result<Author, Error> {
         let _timer = counters::start_timer("get", OWNER_ACCOUNT);
         Ok(self.internal_store.get(OWNER_ACCOUNT).map(|v| v.value)?)
@@ -4,7 +4,7 @@
 
 use crate::{
     persistent_safety_storage::PersistentSafetyStorage,
-    remote_service::{self, RemoteService},
+    remote_service::{self, RemoteChannelConfig, RemoteService},
     safety_rules_manager,
 };
 use aptos_config::config::{SafetyRulesConfig, SafetyRulesService};
@@ -23,19 +23,27 @@ impl Process {
             _ => panic!("Unexpected SafetyRules service: {:?}", config.service),
         };
         let server_addr = service.server_address();
+        let channel_config = RemoteChannelConfig::from_config(service)
+            .unwrap_or_else(|error| panic!("Invalid SafetyRules channel config: {}", error));
 
         Self {
             data: Some(ProcessData {
                 server_addr,
                 storage,
                 network_timeout: config.network_timeout_ms,
+                channel_config,
             }),
         }
     }
 
     pub fn start(&mut self) {
         let data = self.data.take().expect("Unable to retrieve ProcessData");
-        remote_service::execute(data.storage, data.server_addr, data.network_timeout);
+        remote_service::execute(
+            data.storage,
+            data.server_addr,
+            data.network_timeout,
+            data.channel_config,
+        );
     }
 }
 
@@ -44,18 +52,26 @@ struct ProcessData {
     storage: PersistentSafetyStorage,
     // Timeout in Seconds for network operations
     network_timeout: u64,
+    // The security and latency parameters of the channel
+    channel_config: RemoteChannelConfig,
 }
 
 pub struct ProcessService {
     server_addr: SocketAddr,
     network_timeout_ms: u64,
+    channel_config: RemoteChannelConfig,
 }
 
 impl ProcessService {
-    pub fn new(server_addr: SocketAddr, network_timeout: u64) -> Self {
+    pub fn new(
+        server_addr: SocketAddr,
+        network_timeout: u64,
+        channel_config: RemoteChannelConfig,
+    ) -> Self {
         Self {
             server_addr,
             network_timeout_ms: network_timeout,
+            channel_config,
         }
     }
 }
@@ -68,4 +84,8 @@ impl RemoteService for ProcessService {
     fn network_timeout_ms(&self) -> u64 {
         self.network_timeout_ms
     }
+
+    fn channel_config(&self) -> RemoteChannelConfig {
+        self.channel_config.clone()
+    }
 }
@@ -3,13 +3,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    counters,
+    message_authenticator::{derive_session_id, MessageAuthenticator, SESSION_NONCE_LENGTH},
     persistent_safety_storage::PersistentSafetyStorage,
     serializer::{SafetyRulesInput, SerializerClient, SerializerService, TSerializerClient},
     Error, SafetyRules, TSafetyRules,
 };
-use aptos_logger::warn;
+use aptos_config::config;
+use aptos_crypto::HashValue;
+use aptos_logger::{info, warn};
 use aptos_secure_net::{NetworkClient, NetworkServer};
-use std::net::SocketAddr;
+use std::{collections::HashSet, net::SocketAddr, time::Instant};
 
 pub trait RemoteService {
     fn client(&self) -> SerializerClient {
@@ -18,7 +22,7 @@ pub trait RemoteService {
             self.server_address(),
             self.network_timeout_ms(),
         );
-        let service = Box::new(RemoteClient::new(network_client));
+        let service = Box::new(RemoteClient::new(network_client, self.channel_config()));
         SerializerClient::new_client(service)
     }
 
@@ -26,9 +30,69 @@ pub trait RemoteService {
 
     /// Network Timeout in milliseconds.
     fn network_timeout_ms(&self) -> u64;
+
+    /// The security and latency parameters of the channel to the service
+    fn channel_config(&self) -> RemoteChannelConfig {
+        RemoteChannelConfig::default()
+    }
+}
+
+/// The security and latency parameters of the channel between
+/// consensus and a remote safety rules service.
+#[derive(Clone)]
+pub struct RemoteChannelConfig {
+    /// The authenticator for all messages (if messages are authenticated)
+    pub authenticator: Option<MessageAuthenticator>,
+    /// The maximum size (in bytes) of any message sent over the channel
+    pub max_message_size_bytes: u64,
+    /// The latency budget (in ms) for a single request (including retries)
+    pub request_latency_budget_ms: Option<u64>,
+}
+
+impl RemoteChannelConfig {
+    /// Creates a channel config for the given remote service config
+    pub fn from_config(remote_service: &config::RemoteService) -> Result<Self, Error> {
+        let authenticator = match &remote_service.authentication_key_path {
+            Some(key_path) => Some(MessageAuthenticator::from_file(key_path)?),
+            None => None,
+        };
+
+        Ok(Self {
+            authenticator,
+            max_message_size_bytes: remote_service.max_message_size_bytes,
+            request_latency_budget_ms: Some(remote_service.request_latency_budget_ms),
+        })
+    }
+
+    /// Verifies that the message size is within the configured limit
+    fn ensure_message_size(&self, message: &[u8]) -> Result<(), Error> {
+        let message_size = message.len() as u64;
+        if message_size > self.max_message_size_bytes {
+            return Err(Error::MessageTooLarge(
+                message_size,
+                self.max_message_size_bytes,
+            ));
+        }
+        Ok(())
+    }
 }
 
-pub fn execute(storage: PersistentSafetyStorage, listen_addr: SocketAddr, network_timeout_ms: u64) {
+impl Default for RemoteChannelConfig {
+    fn default() -> Self {
+        Self {
+            authenticator: None,
+            max_message_size_bytes: u64::MAX,
+            request_latency_budget_ms: None,
+        }
+    }
+}
+
+pub fn execute(
+    storage: PersistentSafetyStorage,
+    listen_addr: SocketAddr,
+    network_timeout_ms: u64,
+    channel_config: RemoteChannelConfig,
+) {
     let mut safety_rules = SafetyRules::new(storage);
     if let Err(e) = safety_rules.consensus_state() {
         warn!("Unable to print consensus state: {}", e);
@@ -38,46 +102,226 @@ pub fn execute(storage: PersistentSafetyStorage, listen_addr: SocketAddr, networ
     let mut network_server =
         NetworkServer::new("safety-rules".to_string(), listen_addr, network_timeout_ms);
 
+    // The currently active session with the client (if messages are authenticated)
+    let mut session = None;
+    // The client nonces of all previous handshakes (so handshakes can't be replayed).
+    // Handshakes must be authenticated, so only the client can grow this set.
+    let mut seen_client_nonces = HashSet::new();
     loop {
-        if let Err(e) = process_one_message(&mut network_server, &mut serializer_service) {
+        if let Err(e) = process_one_message(
+            &mut network_server,
+            &mut serializer_service,
+            &channel_config,
+            &mut session,
+            &mut seen_client_nonces,
+        ) {
             warn!("Failed to process message: {}", e);
         }
     }
 }
 
+/// An authenticated session between the client and the remote service. Sessions are
+/// established by a handshake: the client sends a fresh nonce (using the reserved zero
+/// session id), and the service replies with its own fresh nonce. The session id is
+/// derived from both nonces, so messages from previous sessions (or connections) can't
+/// be replayed, and sequence numbers restart at 1 for every session. Handshakes that
+/// reuse a client nonce are refused, so a replayed handshake can't replace the session.
+struct Session {
+    session_id: HashValue,
+    last_sequence_number: u64, // The highest sequence number used in the session
+}
+
+impl Session {
+    fn new(session_id: HashValue) -> Self {
+        Self {
+            session_id,
+            last_sequence_number: 0,
+        }
+    }
+}
+
+/// The session id reserved for handshake messages
+fn handshake_session_id() -> HashValue {
+    HashValue::zero()
+}
+
 fn process_one_message(
     network_server: &mut NetworkServer,
     serializer_service: &mut SerializerService,
+    channel_config: &RemoteChannelConfig,
+    session: &mut Option<Session>,
+    seen_client_nonces: &mut HashSet<Vec<u8>>,
 ) -> Result<(), Error> {
     let request = network_server.read()?;
-    let response = serializer_service.handle_message(request)?;
+    channel_config.ensure_message_size(&request)?;
+
+    let response = match &channel_config.authenticator {
+        Some(authenticator) => {
+            let (session_id, sequence_number, payload) = authenticator.open(&request)?;
+            if session_id == handshake_session_id() {
+                // Establish a new session (replacing any existing session)
+                let client_nonce = payload;
+                if client_nonce.len() != SESSION_NONCE_LENGTH {
+                    return Err(Error::AuthenticationError(format!(
+                        "Invalid handshake nonce length: {}, expected: {}",
+                        client_nonce.len(),
+                        SESSION_NONCE_LENGTH
+                    )));
+                }
+                if !seen_client_nonces.insert(client_nonce.clone()) {
+                    counters::increment_query("remote_service", "replayed_handshake");
+                    return Err(Error::AuthenticationError(
+                        "Received a handshake with a previously used client nonce!".into(),
+                    ));
+                }
+                let server_nonce: [u8; SESSION_NONCE_LENGTH] = rand::random();
+                let new_session_id = derive_session_id(&client_nonce, &server_nonce);
+                *session = Some(Session::new(new_session_id));
+                counters::increment_query("remote_service", "new_session");
+                info!("Established a new session with the safety rules client.");
+
+                authenticator.seal(new_session_id, 0, &server_nonce)?
+            } else {
+                // Verify the request belongs to the active session and isn't a replay
+                let session = session.as_mut().ok_or_else(|| {
+                    Error::AuthenticationError("No session has been established!".into())
+                })?;
+                if session_id != session.session_id {
+                    counters::increment_query("remote_service", "stale_session");
+                    return Err(Error::AuthenticationError(format!(
+                        "Received a message for an unknown session: {}",
+                        session_id
+                    )));
+                }
+                if sequence_number <= session.last_sequence_number {
+                    counters::increment_query("remote_service", "replayed_message");
+                    return Err(Error::AuthenticationError(format!(
+                        "Received a stale sequence number: {}, last sequence number: {}",
+                        sequence_number, session.last_sequence_number
+                    )));
+                }
+                session.last_sequence_number = sequence_number;
+
+                // Bind the response to the session and sequence number of the request
+                let response = serializer_service.handle_message(payload)?;
+                authenticator.seal(session_id, sequence_number, &response)?
+            }
+        },
+        None => serializer_service.handle_message(request)?,
+    };
+
+    channel_config.ensure_message_size(&response)?;
     network_server.write(&response)?;
     Ok(())
 }
 
 struct RemoteClient {
     network_client: NetworkClient,
+    channel_config: RemoteChannelConfig,
+    session: Option<Session>, // The active session (if messages are authenticated)
 }
 
 impl RemoteClient {
-    pub fn new(network_client: NetworkClient) -> Self {
-        Self { network_client }
+    pub fn new(network_client: NetworkClient, channel_config: RemoteChannelConfig) -> Self {
+        Self {
+            network_client,
+            channel_config,
+            session: None,
+        }
     }
 
     fn process_one_message(&mut self, input: &[u8]) -> Result<Vec<u8>, Error> {
-        self.network_client.write(input)?;
-        self.network_client.read().map_err(|e| e.into())
+        let authenticator = match self.channel_config.authenticator.clone() {
+            Some(authenticator) => authenticator,
+            None => return self.send_and_receive(input),
+        };
+
+        // Any failure may mean the service dropped our session (e.g., it restarted or
+        // accepted a new handshake), so reset the session and re-handshake on the next try.
+        let result = self.process_one_authenticated_message(&authenticator, input);
+        if result.is_err() {
+            self.session = None;
+        }
+        result
+    }
+
+    fn process_one_authenticated_message(
+        &mut self,
+        authenticator: &MessageAuthenticator,
+        input: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        // Establish a session (if there isn't one already)
+        if self.session.is_none() {
+            let session_id = self.establish_session(authenticator)?;
+            self.session = Some(Session::new(session_id));
+        }
+        let session = self.session.as_mut().expect("The session must exist!");
+        session.last_sequence_number += 1;
+        let (session_id, sequence_number) = (session.session_id, session.last_sequence_number);
+
+        // Authenticate the request, and verify the response is bound to it
+        let request = authenticator.seal(session_id, sequence_number, input)?;
+        let response = self.send_and_receive(&request)?;
+        let (response_session_id, response_sequence_number, payload) =
+            authenticator.open(&response)?;
+        if response_session_id != session_id || response_sequence_number != sequence_number {
+            return Err(Error::AuthenticationError(format!(
+                "Unexpected response session: {}, sequence number: {}. Expected session: {}, sequence number: {}",
+                response_session_id, response_sequence_number, session_id, sequence_number
+            )));
+        }
+        Ok(payload)
+    }
+
+    /// Performs the session handshake with the service and returns the new session id
+    fn establish_session(
+        &mut self,
+        authenticator: &MessageAuthenticator,
+    ) -> Result<HashValue, Error> {
+        let client_nonce: [u8; SESSION_NONCE_LENGTH] = rand::random();
+        let request = authenticator.seal(handshake_session_id(), 0, &client_nonce)?;
+        let response = self.send_and_receive(&request)?;
+
+        // Verify the session id is derived from our (fresh) nonce
+        let (session_id, _, server_nonce) = authenticator.open(&response)?;
+        if session_id != derive_session_id(&client_nonce, &server_nonce) {
+            return Err(Error::AuthenticationError(format!(
+                "The handshake response doesn't match the request! Session: {}",
+                session_id
+            )));
+        }
+        Ok(session_id)
+    }
+
+    /// Sends the given message to the service and returns the response
+    fn send_and_receive(&mut self, request: &[u8]) -> Result<Vec<u8>, Error> {
+        self.channel_config.ensure_message_size(request)?;
+        self.network_client.write(request)?;
+        let response = self.network_client.read()?;
+        self.channel_config.ensure_message_size(&response)?;
+        Ok(response)
     }
 }
 
 impl TSerializerClient for RemoteClient {
     fn request(&mut self, input: SafetyRulesInput) -> Result<Vec<u8>, Error> {
         let input_message = serde_json::to_vec(&input)?;
+        let start_time = Instant::now();
         loop {
             match self.process_one_message(&input_message) {
-                Err(err) => warn!("Failed to communicate with SafetyRules service: {}", err),
+                Err(err) => {
+                    warn!("Failed to communicate with SafetyRules service: {}", err);
+                },
                 Ok(value) => return Ok(value),
             }
+
+            // Stop retrying if the latency budget has been exceeded
+            if let Some(latency_budget_ms) = self.channel_config.request_latency_budget_ms {
+                if start_time.elapsed().as_millis() > latency_budget_ms as u128 {
+                    counters::increment_query("remote_service", "latency_budget_exceeded");
+                    return Err(Error::LatencyBudgetExceeded(latency_budget_ms));
+                }
+            }
         }
     }
 }
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    audit_log::{SigningDecision, SigningOperation},
     consensus_state::ConsensusState,
     counters,
     error::Error,
@@ -20,7 +21,7 @@ use aptos_consensus_types::{
     vote_data::VoteData,
     vote_proposal::VoteProposal,
 };
-use aptos_crypto::{bls12381, hash::CryptoHash};
+use aptos_crypto::{bls12381, hash::CryptoHash, HashValue};
 use aptos_logger::prelude::*;
 use aptos_types::{
     epoch_change::EpochChangeProof,
@@ -360,6 +361,39 @@ impl SafetyRules {
 
         Ok(signature)
     }
+
+    /// Records the signing decision in the audit log (if enabled) and returns the
+    /// result. The record is synced to disk before a signature is returned, and if
+    /// the record can't be written, safety rules refuse to sign (so that every
+    /// signature is accounted for in the audit log).
+    fn record_signing_decision<T>(
+        &mut self,
+        operation: SigningOperation,
+        epoch: u64,
+        round: Round,
+        id: HashValue,
+        result: Result<T, Error>,
+    ) -> Result<T, Error> {
+        if let Some(signing_audit_log) = self.persistent_storage.signing_audit_log() {
+            let decision = match &result {
+                Ok(_) => SigningDecision::Signed,
+                Err(error) => SigningDecision::Refused(error.to_string()),
+            };
+            if let Err(error) = signing_audit_log.record(operation, epoch, round, id, decision) {
+                error!(SafetyLogSchema::new(LogEntry::AuditLog, LogEvent::Error)
+                    .round(round)
+                    .error(&error));
+                counters::increment_query(LogEntry::AuditLog.as_str(), "error");
+                if result.is_ok() {
+                    return Err(Error::InternalError(format!(
+                        "Refusing to sign! Failed to record the signing decision: {}",
+                        error
+                    )));
+                }
+            }
+        }
+        result
+    }
 }
 
 impl TSafetyRules for SafetyRules {
@@ -376,7 +410,14 @@ impl TSafetyRules for SafetyRules {
     fn sign_proposal(&mut self, block_data: &BlockData) -> Result<bls12381::Signature, Error> {
         let round = block_data.round();
         let cb = || self.guarded_sign_proposal(block_data);
-        run_and_log(cb, |log| log.round(round), LogEntry::SignProposal)
+        let result = run_and_log(cb, |log| log.round(round), LogEntry::SignProposal);
+        self.record_signing_decision(
+            SigningOperation::Proposal,
+            block_data.epoch(),
+            round,
+            block_data.hash(),
+            result,
+        )
     }
 
     fn sign_timeout_with_qc(
@@ -385,10 +426,17 @@ impl TSafetyRules for SafetyRules {
         timeout_cert: Option<&TwoChainTimeoutCertificate>,
     ) -> Result<bls12381::Signature, Error> {
         let cb = || self.guarded_sign_timeout_with_qc(timeout, timeout_cert);
-        run_and_log(
+        let result = run_and_log(
             cb,
             |log| log.round(timeout.round()),
             LogEntry::SignTimeoutWithQC,
+        );
+        self.record_signing_decision(
+            SigningOperation::Timeout,
+            timeout.epoch(),
+            timeout.round(),
+            timeout.quorum_cert().certified_block().id(),
+            result,
         )
     }
 
@@ -399,10 +447,17 @@ impl TSafetyRules for SafetyRules {
     ) -> Result<Vote, Error> {
         let round = vote_proposal.block().round();
         let cb = || self.guarded_construct_and_sign_vote_two_chain(vote_proposal, timeout_cert);
-        run_and_log(
+        let result = run_and_log(
             cb,
             |log| log.round(round),
             LogEntry::ConstructAndSignVoteTwoChain,
+        );
+        self.record_signing_decision(
+            SigningOperation::Vote,
+            vote_proposal.block().epoch(),
+            round,
+            vote_proposal.block().id(),
+            result,
         )
     }
 
@@ -411,8 +466,14 @@ impl TSafetyRules for SafetyRules {
         ledger_info: LedgerInfoWithSignatures,
         new_ledger_info: LedgerInfo,
     ) -> Result<bls12381::Signature, Error> {
+        let (epoch, round, id) = (
+            new_ledger_info.epoch(),
+            new_ledger_info.round(),
+            new_ledger_info.commit_info().id(),
+        );
         let cb = || self.guarded_sign_commit_vote(ledger_info, new_ledger_info);
-        run_and_log(cb, |log| log, LogEntry::SignCommitVote)
+        let result = run_and_log(cb, |log| log, LogEntry::SignCommitVote);
+        self.record_signing_decision(SigningOperation::CommitVote, epoch, round, id, result)
     }
 }
 
@@ -3,10 +3,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    audit_log::SigningAuditLog,
     local_client::LocalClient,
     persistent_safety_storage::PersistentSafetyStorage,
     process::ProcessService,
-    remote_service::RemoteService,
+    remote_service::{RemoteChannelConfig, RemoteService},
     serializer::{SerializerClient, SerializerService},
     thread::ThreadService,
     SafetyRules, TSafetyRules,
@@ -26,7 +27,7 @@ pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
         panic!("Storage is not available: {:?}", error);
     }
 
-    if let Some(test_config) = &config.test {
+    let storage = if let Some(test_config) = &config.test {
         let author = test_config.author;
         let consensus_private_key = test_config
             .consensus_key
@@ -73,6 +74,15 @@ pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
                 "Safety rules storage is not initialized, provide an initial safety rules config"
             )
         }
+    };
+
+    // Record all signing decisions in the audit log (if enabled)
+    if config.audit_log.enabled {
+        let signing_audit_log = SigningAuditLog::open(config.audit_log.clone())
+            .unwrap_or_else(|error| panic!("Failed to open the signing audit log: {}", error));
+        storage.with_signing_audit_log(signing_audit_log)
+    } else {
+        storage
     }
 }
 
@@ -101,7 +111,13 @@ pub struct SafetyRulesManager {
 impl SafetyRulesManager {
     pub fn new(config: &SafetyRulesConfig) -> Self {
         if let SafetyRulesService::Process(conf) = &config.service {
-            return Self::new_process(conf.server_address(), config.network_timeout_ms);
+            let channel_config = RemoteChannelConfig::from_config(conf)
+                .unwrap_or_else(|error| panic!("Invalid SafetyRules channel config: {}", error));
+            return Self::new_process(
+                conf.server_address(),
+                config.network_timeout_ms,
+                channel_config,
+            );
         }
 
         let storage = storage(config);
@@ -120,8 +136,12 @@ impl SafetyRulesManager {
         }
     }
 
-    pub fn new_process(server_addr: SocketAddr, timeout_ms: u64) -> Self {
-        let process_service = ProcessService::new(server_addr, timeout_ms);
+    pub fn new_process(
+        server_addr: SocketAddr,
+        timeout_ms: u64,
+        channel_config: RemoteChannelConfig,
+    ) -> Self {
+        let process_service = ProcessService::new(server_addr, timeout_ms, channel_config);
         Self {
             internal_safety_rules: SafetyRulesWrapper::Process(process_service),
         }
@@ -2,8 +2,22 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{test_utils, SafetyRulesManager};
+use crate::{
+    message_authenticator::derive_session_id, remote_service, serializer::SafetyRulesInput,
+    test_utils, Error, MessageAuthenticator, RemoteChannelConfig, SafetyRulesManager,
+};
+use aptos_config::utils;
+use aptos_crypto::HashValue;
+use aptos_secure_net::NetworkClient;
 use aptos_types::validator_signer::ValidatorSigner;
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    thread,
+};
+
+// Test values for the network timeout and latency budget, in milliseconds
+const LATENCY_BUDGET_MS: u64 = 2_000;
+const NETWORK_TIMEOUT_MS: u64 = 500;
 
 #[test]
 fn test_reconnect() {
@@ -18,3 +32,160 @@ fn test_reconnect() {
     let state1 = safety_rules_manager.client().consensus_state().unwrap();
     assert_eq!(state0, state1);
 }
+
+#[test]
+fn test_authenticated_process() {
+    // Start a remote safety rules service that authenticates all messages
+    let authenticator = MessageAuthenticator::new(vec![7; 32]).unwrap();
+    let server_addr = spawn_remote_service(Some(authenticator.clone()));
+
+    // Verify that a client with the same key can communicate with the service
+    let channel_config = create_channel_config(Some(authenticator));
+    let safety_rules_manager =
+        SafetyRulesManager::new_process(server_addr, NETWORK_TIMEOUT_MS, channel_config);
+    let state0 = safety_rules_manager.client().consensus_state().unwrap();
+    let state1 = safety_rules_manager.client().consensus_state().unwrap();
+    assert_eq!(state0, state1);
+}
+
+#[test]
+fn test_unauthenticated_client() {
+    // Start a remote safety rules service that authenticates all messages
+    let authenticator = MessageAuthenticator::new(vec![7; 32]).unwrap();
+    let server_addr = spawn_remote_service(Some(authenticator));
+
+    // Verify that a client with a different key fails within the latency budget
+    let invalid_authenticator = MessageAuthenticator::new(vec![8; 32]).unwrap();
+    let channel_config = create_channel_config(Some(invalid_authenticator));
+    let safety_rules_manager =
+        SafetyRulesManager::new_process(server_addr, NETWORK_TIMEOUT_MS, channel_config);
+    let error = safety_rules_manager.client().consensus_state().unwrap_err();
+    assert_eq!(error, Error::LatencyBudgetExceeded(LATENCY_BUDGET_MS));
+}
+
+#[test]
+fn test_message_authenticator() {
+    // Verify that keys that are too short are rejected
+    assert!(MessageAuthenticator::new(vec![1; 16]).is_err());
+
+    // Seal a message and verify it can be opened
+    let authenticator = MessageAuthenticator::new(vec![1; 32]).unwrap();
+    let session_id = HashValue::random();
+    let message = authenticator.seal(session_id, 10, b"payload").unwrap();
+    let (message_session_id, sequence_number, payload) = authenticator.open(&message).unwrap();
+    assert_eq!(message_session_id, session_id);
+    assert_eq!(sequence_number, 10);
+    assert_eq!(payload, b"payload".to_vec());
+
+    // Verify that a different key cannot open the message
+    let other_authenticator = MessageAuthenticator::new(vec![2; 32]).unwrap();
+    let error = other_authenticator.open(&message).unwrap_err();
+    assert!(matches!(error, Error::AuthenticationError(_)));
+
+    // Verify that a tampered message is rejected
+    let tampered_message = String::from_utf8(message)
+        .unwrap()
+        .replace("\"sequence_number\":10", "\"sequence_number\":11");
+    let error = authenticator.open(tampered_message.as_bytes()).unwrap_err();
+    assert!(matches!(error, Error::AuthenticationError(_)));
+}
+
+#[test]
+fn test_replayed_messages() {
+    // Start a remote safety rules service that authenticates all messages
+    let authenticator = MessageAuthenticator::new(vec![7; 32]).unwrap();
+    let server_addr = spawn_remote_service(Some(authenticator.clone()));
+    let mut network_client =
+        NetworkClient::new("test".to_string(), server_addr, NETWORK_TIMEOUT_MS);
+
+    // Establish a session and send a request
+    let session_id = establish_session(&mut network_client, &authenticator);
+    let payload = serde_json::to_vec(&SafetyRulesInput::ConsensusState).unwrap();
+    let request = authenticator.seal(session_id, 1, &payload).unwrap();
+    network_client.write(&request).unwrap();
+    let (response_session_id, response_sequence_number, _) =
+        authenticator.open(&network_client.read().unwrap()).unwrap();
+    assert_eq!(response_session_id, session_id);
+    assert_eq!(response_sequence_number, 1);
+
+    // Verify that the request can't be replayed in the same session
+    network_client.write(&request).unwrap();
+    assert!(network_client.read().is_err());
+
+    // Establish a new session and verify that messages for the old session are rejected
+    let new_session_id = establish_session(&mut network_client, &authenticator);
+    assert_ne!(new_session_id, session_id);
+    let old_session_request = authenticator.seal(session_id, 2, &payload).unwrap();
+    network_client.write(&old_session_request).unwrap();
+    assert!(network_client.read().is_err());
+}
+
+#[test]
+fn test_replayed_handshake() {
+    // Start a remote safety rules service that authenticates all messages
+    let authenticator = MessageAuthenticator::new(vec![7; 32]).unwrap();
+    let server_addr = spawn_remote_service(Some(authenticator.clone()));
+    let mut network_client =
+        NetworkClient::new("test".to_string(), server_addr, NETWORK_TIMEOUT_MS);
+
+    // Establish a session
+    let client_nonce: [u8; 32] = rand::random();
+    let handshake = authenticator
+        .seal(HashValue::zero(), 0, &client_nonce)
+        .unwrap();
+    network_client.write(&handshake).unwrap();
+    let (session_id, _, _) = authenticator.open(&network_client.read().unwrap()).unwrap();
+
+    // Verify that the replayed handshake is refused
+    network_client.write(&handshake).unwrap();
+    assert!(network_client.read().is_err());
+
+    // Verify that the session is still active
+    let payload = serde_json::to_vec(&SafetyRulesInput::ConsensusState).unwrap();
+    let request = authenticator.seal(session_id, 1, &payload).unwrap();
+    network_client.write(&request).unwrap();
+    let (response_session_id, response_sequence_number, _) =
+        authenticator.open(&network_client.read().unwrap()).unwrap();
+    assert_eq!(response_session_id, session_id);
+    assert_eq!(response_sequence_number, 1);
+}
+
+/// Performs the session handshake with the service and returns the session id
+fn establish_session(
+    network_client: &mut NetworkClient,
+    authenticator: &MessageAuthenticator,
+) -> HashValue {
+    let client_nonce: [u8; 32] = rand::random();
+    let request = authenticator
+        .seal(HashValue::zero(), 0, &client_nonce)
+        .unwrap();
+    network_client.write(&request).unwrap();
+    let (session_id, _, server_nonce) =
+        authenticator.open(&network_client.read().unwrap()).unwrap();
+    assert_eq!(session_id, derive_session_id(&client_nonce, &server_nonce));
+    session_id
+}
+
+/// Creates a channel config with the given authenticator and test limits
+fn create_channel_config(authenticator: Option<MessageAuthenticator>) -> RemoteChannelConfig {
+    RemoteChannelConfig {
+        authenticator,
+        max_message_size_bytes: 1024 * 1024,
+        request_latency_budget_ms: Some(LATENCY_BUDGET_MS),
+    }
+}
+
+/// Spawns a remote safety rules service on a new thread and returns its address
+fn spawn_remote_service(authenticator: Option<MessageAuthenticator>) -> SocketAddr {
+    let signer = ValidatorSigner::from_int(0);
+    let storage = test_utils::test_storage(&signer);
+    let listen_port = utils::get_available_port();
+    let server_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listen_port);
+
+    let channel_config = create_channel_config(authenticator);
+    thread::spawn(move || {
+        remote_service::execute(storage, server_addr, NETWORK_TIMEOUT_MS, channel_config)
+    });
+
+    server_addr
+}
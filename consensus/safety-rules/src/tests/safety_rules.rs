@@ -2,7 +2,11 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{test_utils, tests::suite, SafetyRules};
+use crate::{
+    test_utils, tests::suite, SafetyRules, SigningAuditLog, SigningDecision, TSafetyRules,
+};
+use aptos_config::config::SafetyRulesAuditLogConfig;
+use aptos_temppath::TempPath;
 use aptos_types::validator_signer::ValidatorSigner;
 
 #[test]
@@ -10,6 +14,57 @@ fn test() {
     suite::run_test_suite(&safety_rules());
 }
 
+#[test]
+fn test_with_audit_log() {
+    // Create an audit log config
+    let temp_path = TempPath::new();
+    let audit_log_config = SafetyRulesAuditLogConfig {
+        enabled: true,
+        path: temp_path.path().to_path_buf(),
+        ..Default::default()
+    };
+
+    // Run the test suite with the audit log enabled
+    suite::run_test_suite(&safety_rules_with_audit_log(audit_log_config.clone()));
+
+    // Verify the signing decisions were recorded and the chain is valid
+    let records = SigningAuditLog::export(&audit_log_config).unwrap();
+    assert!(records
+        .iter()
+        .any(|record| record.data.decision == SigningDecision::Signed));
+    assert!(records
+        .iter()
+        .any(|record| matches!(record.data.decision, SigningDecision::Refused(_))));
+}
+
+#[test]
+fn test_refuse_to_sign_without_audit_record() {
+    // Create safety rules with an audit log that can't be written
+    let temp_path = TempPath::new();
+    let audit_log_config = SafetyRulesAuditLogConfig {
+        enabled: true,
+        path: temp_path.path().to_path_buf(),
+        ..Default::default()
+    };
+    let signer = ValidatorSigner::from_int(0);
+    let mut signing_audit_log = SigningAuditLog::open(audit_log_config.clone()).unwrap();
+    signing_audit_log.make_read_only_for_test();
+    let storage = test_utils::test_storage(&signer).with_signing_audit_log(signing_audit_log);
+    let mut safety_rules = SafetyRules::new(storage);
+
+    // Verify that safety rules refuse to sign (the decision can't be recorded)
+    let (proof, genesis_qc) = test_utils::make_genesis(&signer);
+    let round = genesis_qc.certified_block().round();
+    safety_rules.initialize(&proof).unwrap();
+    let proposal = test_utils::make_proposal_with_qc(round + 1, genesis_qc, &signer);
+    assert!(safety_rules
+        .sign_proposal(proposal.block().block_data())
+        .is_err());
+    assert!(SigningAuditLog::export(&audit_log_config)
+        .unwrap()
+        .is_empty());
+}
+
 fn safety_rules() -> suite::Callback {
     Box::new(move || {
         let signer = ValidatorSigner::from_int(0);
@@ -18,3 +73,13 @@ fn safety_rules() -> suite::Callback {
         (safety_rules, signer)
     })
 }
+
+fn safety_rules_with_audit_log(audit_log_config: SafetyRulesAuditLogConfig) -> suite::Callback {
+    Box::new(move || {
+        let signer = ValidatorSigner::from_int(0);
+        let signing_audit_log = SigningAuditLog::open(audit_log_config.clone()).unwrap();
+        let storage = test_utils::test_storage(&signer).with_signing_audit_log(signing_audit_log);
+        let safety_rules = Box::new(SafetyRules::new(storage));
+        (safety_rules, signer)
+    })
+}
@@ -10,7 +10,7 @@
 
 use crate::{
     persistent_safety_storage::PersistentSafetyStorage,
-    remote_service::{self, RemoteService},
+    remote_service::{self, RemoteChannelConfig, RemoteService},
 };
 use aptos_config::utils;
 use std::{
@@ -32,7 +32,14 @@ impl ThreadService {
         let listen_addr = SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), listen_port);
         let server_addr = listen_addr;
 
-        let child = thread::spawn(move || remote_service::execute(storage, listen_addr, timeout));
+        let child = thread::spawn(move || {
+            remote_service::execute(
+                storage,
+                listen_addr,
+                timeout,
+                RemoteChannelConfig::default(),
+            )
+        });
 
         Self {
             _child: child,
@@ -342,6 +342,12 @@ impl BlockStore {
         self.inner.write().insert_block(pipelined_block)
     }
 
+    /// Starts executing the given (proposed) block before it is ordered. This is a
+    /// no-op unless optimistic execution is enabled.
+    pub async fn optimistic_execute(&self, block: &Block) {
+        self.execution_client.optimistic_execute(block).await;
+    }
+
     /// Validates quorum certificates and inserts it into block tree assuming dependencies exist.
     pub fn insert_single_quorum_cert(&self, qc: QuorumCert) -> anyhow::Result<()> {
         // If the parent block is not the root block (i.e not None), ensure the executed state
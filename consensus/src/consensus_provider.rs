@@ -5,6 +5,7 @@
 use crate::{
     counters,
     epoch_manager::EpochManager,
+    epoch_warmup::EpochWarmup,
     network::NetworkTask,
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
     persistent_liveness_storage::StorageWriteProxy,
@@ -23,7 +24,10 @@ use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListe
 use aptos_executor::block_executor::BlockExecutor;
 use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
-use aptos_network::application::interface::{NetworkClient, NetworkServiceEvents};
+use aptos_network::{
+    application::interface::{NetworkClient, NetworkServiceEvents},
+    ConnectivityRequest,
+};
 use aptos_storage_interface::DbReaderWriter;
 use aptos_validator_transaction_pool::VTxnPoolState;
 use aptos_vm::AptosVM;
@@ -36,11 +40,13 @@ pub fn start_consensus(
     node_config: &NodeConfig,
     network_client: NetworkClient<ConsensusMsg>,
     network_service_events: NetworkServiceEvents<ConsensusMsg>,
+    conn_mgr_reqs_tx: Option<aptos_channels::Sender<ConnectivityRequest>>,
     state_sync_notifier: Arc<dyn ConsensusNotificationSender>,
     consensus_to_mempool_sender: mpsc::Sender<QuorumStoreRequest>,
     aptos_db: DbReaderWriter,
     reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
     vtxn_pool: VTxnPoolState,
+    aptos_time_service: aptos_time_service::TimeService,
 ) -> (Runtime, Arc<StorageWriteProxy>, Arc<QuorumStoreDB>) {
     let runtime = aptos_runtimes::spawn_named_runtime("consensus".into(), None);
     let storage = Arc::new(StorageWriteProxy::new(node_config, aptos_db.reader.clone()));
@@ -51,15 +57,19 @@ pub fn start_consensus(
         node_config.consensus.mempool_executed_txn_timeout_ms,
     ));
 
+    let executor = Arc::new(BlockExecutor::<AptosVM>::new(aptos_db.clone()));
     let execution_proxy = ExecutionProxy::new(
-        Arc::new(BlockExecutor::<AptosVM>::new(aptos_db)),
+        executor.clone(),
         txn_notifier,
         state_sync_notifier,
         runtime.handle(),
         TransactionFilter::new(node_config.execution.transaction_filter.clone()),
     );
 
-    let time_service = Arc::new(ClockTimeService::new(runtime.handle().clone()));
+    let time_service = Arc::new(ClockTimeService::new_with_time_service(
+        runtime.handle().clone(),
+        aptos_time_service.clone(),
+    ));
 
     let (timeout_sender, timeout_receiver) =
         aptos_channels::new(1_024, &counters::PENDING_ROUND_TIMEOUTS);
@@ -69,6 +79,11 @@ pub fn start_consensus(
     let bounded_executor = BoundedExecutor::new(8, runtime.handle().clone());
     let rand_storage = Arc::new(RandDb::new(node_config.storage.dir()));
 
+    let epoch_warmup = EpochWarmup::new(node_config.consensus.enable_epoch_warmup).with_resources(
+        executor,
+        aptos_db.reader.clone(),
+        conn_mgr_reqs_tx,
+    );
     let execution_client = Arc::new(ExecutionProxyClient::new(
         node_config.consensus.clone(),
         Arc::new(execution_proxy),
@@ -77,6 +92,7 @@ pub fn start_consensus(
         consensus_network_client.clone(),
         bounded_executor.clone(),
         rand_storage.clone(),
+        epoch_warmup.clone(),
     ));
 
     let epoch_mgr = EpochManager::new(
@@ -91,9 +107,10 @@ pub fn start_consensus(
         quorum_store_db.clone(),
         reconfig_events,
         bounded_executor,
-        aptos_time_service::TimeService::real(),
+        aptos_time_service,
         vtxn_pool,
         rand_storage,
+        epoch_warmup,
     );
 
     let (network_task, network_receiver) = NetworkTask::new(network_service_events, self_receiver);
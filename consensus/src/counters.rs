@@ -101,6 +101,16 @@ pub static PROPOSALS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!("aptos_consensus_proposals_count", "Count of the block proposals sent by this validator since last restart (both primary and secondary)").unwrap()
 });
 
+/// Count of the fallback block proposals sent by this validator since last restart
+/// (i.e., proposals sent because the round's proposer failed to propose in time)
+pub static FALLBACK_PROPOSALS_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_fallback_proposals_count",
+        "Count of the fallback block proposals sent by this validator since last restart"
+    )
+    .unwrap()
+});
+
 /// Count the number of times a validator voted for a nil block since last restart.
 pub static VOTE_NIL_COUNT: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -136,6 +146,36 @@ pub static TXN_SHUFFLE_SECONDS: Lazy<Histogram> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Count of the epochs prepared before their reconfiguration committed
+pub static EPOCH_WARMUP_PREPARED_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_epoch_warmup_prepared_count",
+        "Count of the epochs prepared before their reconfiguration committed"
+    )
+    .unwrap()
+});
+
+/// Count of the prepared epochs that did not match the committed validator set
+pub static EPOCH_WARMUP_MISMATCH_COUNT: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_consensus_epoch_warmup_mismatch_count",
+        "Count of the prepared epochs that did not match the committed validator set"
+    )
+    .unwrap()
+});
+
+/// Time between preparing the next epoch and starting it
+pub static EPOCH_WARMUP_LEAD_TIME_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        // metric name
+        "aptos_consensus_epoch_warmup_lead_time_seconds",
+        // metric description
+        "The time in seconds between preparing the next epoch and starting it",
+        exponential_buckets(/*start=*/ 1e-3, /*factor=*/ 2.0, /*count=*/ 20).unwrap(),
+    )
+    .unwrap()
+});
+
 /// Transaction dedup call latency
 pub static TXN_DEDUP_SECONDS: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!(
@@ -700,6 +740,17 @@ pub static PENDING_QUORUM_STORE_COMMIT_NOTIFICATION: Lazy<IntGauge> = Lazy::new(
     .unwrap()
 });
 
+/// Counters(started, used, wasted, failed) related to optimistic block executions,
+/// i.e., executions of proposed blocks that start before the blocks are ordered.
+pub static OPTIMISTIC_EXECUTION_BLOCKS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_consensus_optimistic_execution_blocks_count",
+        "Counters(started,used,wasted,failed,skipped) related to optimistic block executions",
+        &["state"]
+    )
+    .unwrap()
+});
+
 /// Counters related to pending commit votes
 pub static BUFFER_MANAGER_MSGS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -116,6 +116,7 @@ impl LeaderReputationAdapter {
                 0,
                 true,
                 window_for_chain_health,
+                0,
             ),
             data_source: backend,
         }
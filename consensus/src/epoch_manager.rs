@@ -9,6 +9,7 @@ use crate::{
     },
     counters,
     dag::{DagBootstrapper, DagCommitSigner, StorageAdapter},
+    epoch_warmup::EpochWarmup,
     error::{error_kind, DbError},
     liveness::{
         cached_proposer_election::CachedProposerElection,
@@ -166,9 +167,11 @@ pub struct EpochManager<P: OnChainConfigProvider> {
     dag_config: DagConsensusConfig,
     payload_manager: Arc<PayloadManager>,
     rand_storage: Arc<dyn RandStorage<AugmentedData>>,
+    epoch_warmup: EpochWarmup,
 }
 
 impl<P: OnChainConfigProvider> EpochManager<P> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn new(
         node_config: &NodeConfig,
         time_service: Arc<dyn TimeService>,
@@ -184,6 +187,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         aptos_time_service: aptos_time_service::TimeService,
         vtxn_pool: VTxnPoolState,
         rand_storage: Arc<dyn RandStorage<AugmentedData>>,
+        epoch_warmup: EpochWarmup,
     ) -> Self {
         let author = node_config.validator_network.as_ref().unwrap().peer_id();
         let config = node_config.consensus.clone();
@@ -225,6 +229,7 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             dag_config,
             payload_manager: Arc::new(PayloadManager::DirectMempool),
             rand_storage,
+            epoch_warmup,
         }
     }
 
@@ -355,6 +360,9 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                     onchain_config.leader_reputation_exclude_round(),
                     leader_reputation_type.use_root_hash_for_seed(),
                     self.config.window_for_chain_health,
+                    onchain_config
+                        .proposer_fallback_config()
+                        .num_fallback_proposers as usize,
                 ));
                 // LeaderReputation is not cheap, so we can cache the amount of rounds round_manager needs.
                 Arc::new(CachedProposerElection::new(
@@ -972,10 +980,18 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         let validator_set: ValidatorSet = payload
             .get()
             .expect("failed to get ValidatorSet from payload");
-        let epoch_state = Arc::new(EpochState {
-            epoch: payload.epoch(),
-            verifier: (&validator_set).into(),
-        });
+
+        // Reuse the epoch state prepared before the reconfiguration committed (if any,
+        // and if it matches the committed validator set). Otherwise, use the payload.
+        let epoch_state = self
+            .epoch_warmup
+            .take_prepared_epoch_state(payload.epoch(), &validator_set)
+            .unwrap_or_else(|| {
+                Arc::new(EpochState {
+                    epoch: payload.epoch(),
+                    verifier: (&validator_set).into(),
+                })
+            });
 
         self.epoch_state = Some(epoch_state.clone());
 
@@ -988,6 +1004,10 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
             error!("Failed to read on-chain consensus config {}", error);
         }
 
+        if let Some(Err(error)) = onchain_consensus_config.as_ref().ok().map(|c| c.validate()) {
+            error!("Invalid on-chain consensus config {}", error);
+        }
+
         if let Err(error) = &onchain_execution_config {
             error!("Failed to read on-chain execution config {}", error);
         }
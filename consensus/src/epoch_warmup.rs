@@ -0,0 +1,330 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::counters;
+use aptos_config::config::{Peer, PeerRole, PeerSet};
+use aptos_crypto::HashValue;
+use aptos_executor_types::BlockExecutorTrait;
+use aptos_infallible::Mutex;
+use aptos_logger::{error, info, warn};
+use aptos_network::{connectivity_manager::DiscoverySource, ConnectivityRequest};
+use aptos_storage_interface::{state_view::LatestDbStateCheckpointView, DbReader};
+use aptos_types::{
+    epoch_state::EpochState,
+    on_chain_config::{OnChainConfig, ValidatorSet},
+    validator_verifier::ValidatorVerifier,
+};
+use futures::SinkExt;
+use std::{sync::Arc, time::Instant};
+
+/// The state of the next epoch, prepared before the reconfiguration commits
+struct PreparedEpoch {
+    epoch_state: Arc<EpochState>,
+    prepare_time: Instant,
+}
+
+/// Prepares the next epoch as soon as the block that ends the current epoch has
+/// been executed (i.e., before the reconfiguration commits). Preparing the epoch:
+/// (i) keeps the next epoch state built by execution (it is validated against the
+/// committed validator set once the reconfiguration notification arrives); (ii)
+/// dials the validators joining in the next epoch; and (iii) warms up the executor
+/// caches for the next epoch. The warm-up is shared between the execution pipeline
+/// (which prepares the next epoch) and the epoch manager (which starts it).
+#[derive(Clone)]
+pub struct EpochWarmup {
+    enabled: bool,
+    prepared_epoch: Arc<Mutex<Option<PreparedEpoch>>>,
+    executor: Option<Arc<dyn BlockExecutorTrait>>,
+    aptos_db: Option<Arc<dyn DbReader>>,
+    conn_mgr_reqs_tx: Option<aptos_channels::Sender<ConnectivityRequest>>,
+}
+
+impl EpochWarmup {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            prepared_epoch: Arc::new(Mutex::new(None)),
+            executor: None,
+            aptos_db: None,
+            conn_mgr_reqs_tx: None,
+        }
+    }
+
+    /// Enables warming up the executor caches, and dialing the incoming
+    /// validators (if a connectivity manager request sender is given).
+    pub fn with_resources(
+        mut self,
+        executor: Arc<dyn BlockExecutorTrait>,
+        aptos_db: Arc<dyn DbReader>,
+        conn_mgr_reqs_tx: Option<aptos_channels::Sender<ConnectivityRequest>>,
+    ) -> Self {
+        self.executor = Some(executor);
+        self.aptos_db = Some(aptos_db);
+        self.conn_mgr_reqs_tx = conn_mgr_reqs_tx;
+        self
+    }
+
+    /// Prepares the given next epoch (if the warm-up is enabled and the epoch
+    /// has not already been prepared). The given block is the executed block
+    /// that ends the current epoch.
+    pub fn prepare_next_epoch(&self, reconfig_block_id: HashValue, next_epoch_state: &EpochState) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut prepared_epoch = self.prepared_epoch.lock();
+        if let Some(prepared_epoch) = prepared_epoch.as_ref() {
+            if prepared_epoch.epoch_state.epoch == next_epoch_state.epoch {
+                return; // The epoch has already been prepared
+            }
+        }
+
+        info!(
+            epoch = next_epoch_state.epoch,
+            "Prepared the next epoch before the reconfiguration commits"
+        );
+        counters::EPOCH_WARMUP_PREPARED_COUNT.inc();
+        *prepared_epoch = Some(PreparedEpoch {
+            epoch_state: Arc::new(next_epoch_state.clone()),
+            prepare_time: Instant::now(),
+        });
+
+        self.warm_up_executor(reconfig_block_id);
+        self.dial_incoming_validators(next_epoch_state.verifier.clone());
+    }
+
+    /// Returns the prepared state of the given epoch (if it was prepared and it
+    /// matches the committed validator set). Any prepared state is cleared, as
+    /// it is only used once.
+    pub fn take_prepared_epoch_state(
+        &self,
+        epoch: u64,
+        validator_set: &ValidatorSet,
+    ) -> Option<Arc<EpochState>> {
+        let prepared_epoch = self.prepared_epoch.lock().take()?;
+
+        // The incoming validators are now discovered via the on-chain validator set
+        self.update_discovered_peers(PeerSet::new());
+
+        if prepared_epoch.epoch_state.epoch != epoch {
+            return None; // The prepared epoch is stale (e.g., we state synced past it)
+        }
+        if prepared_epoch.epoch_state.verifier != ValidatorVerifier::from(validator_set) {
+            error!(
+                epoch = epoch,
+                "The prepared epoch state does not match the committed validator set! Ignoring it."
+            );
+            counters::EPOCH_WARMUP_MISMATCH_COUNT.inc();
+            return None;
+        }
+
+        counters::EPOCH_WARMUP_LEAD_TIME_SECONDS
+            .observe(prepared_epoch.prepare_time.elapsed().as_secs_f64());
+        Some(prepared_epoch.epoch_state)
+    }
+
+    /// Warms up the executor caches for executing the blocks of the next epoch
+    fn warm_up_executor(&self, reconfig_block_id: HashValue) {
+        let Some(executor) = self.executor.clone() else {
+            return;
+        };
+        tokio::task::spawn_blocking(move || {
+            if let Err(error) = executor.warm_up(reconfig_block_id) {
+                warn!(
+                    block_id = reconfig_block_id,
+                    "Failed to warm up the executor for the next epoch: {:?}", error
+                );
+            }
+        });
+    }
+
+    /// Dials the validators joining in the next epoch. Their network addresses are
+    /// read from the committed validator set (where they are still pending active).
+    fn dial_incoming_validators(&self, next_verifier: ValidatorVerifier) {
+        let (Some(aptos_db), Some(_)) = (self.aptos_db.clone(), self.conn_mgr_reqs_tx.as_ref())
+        else {
+            return;
+        };
+        let epoch_warmup = self.clone();
+        tokio::task::spawn_blocking(move || {
+            let validator_set = aptos_db
+                .latest_state_checkpoint_view()
+                .ok()
+                .and_then(|state_view| ValidatorSet::fetch_config(&state_view));
+            match validator_set {
+                Some(validator_set) => {
+                    let incoming_validators =
+                        get_incoming_validators(&validator_set, &next_verifier);
+                    info!(
+                        "Dialing {} incoming validators before the reconfiguration commits",
+                        incoming_validators.len()
+                    );
+                    epoch_warmup.update_discovered_peers(incoming_validators);
+                },
+                None => warn!("Failed to read the validator set to dial the incoming validators!"),
+            }
+        });
+    }
+
+    /// Sends the given discovered peers to the connectivity manager (if any)
+    fn update_discovered_peers(&self, peers: PeerSet) {
+        let Some(mut conn_mgr_reqs_tx) = self.conn_mgr_reqs_tx.clone() else {
+            return;
+        };
+        let request =
+            ConnectivityRequest::UpdateDiscoveredPeers(DiscoverySource::NextValidatorSet, peers);
+        tokio::spawn(async move {
+            if let Err(error) = conn_mgr_reqs_tx.send(request).await {
+                warn!(
+                    "Failed to send the incoming validators to the connectivity manager: {:?}",
+                    error
+                );
+            }
+        });
+    }
+}
+
+/// Returns the validators (and their network addresses) that are pending active in the
+/// given validator set, and that are part of the next epoch (i.e., the next verifier).
+fn get_incoming_validators(
+    validator_set: &ValidatorSet,
+    next_verifier: &ValidatorVerifier,
+) -> PeerSet {
+    validator_set
+        .pending_active
+        .iter()
+        .filter(|info| {
+            next_verifier
+                .get_voting_power(info.account_address())
+                .is_some()
+        })
+        .filter_map(|info| {
+            let addresses = info.config().validator_network_addresses().ok()?;
+            Some((
+                *info.account_address(),
+                Peer::from_addrs(PeerRole::Validator, addresses),
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_incoming_validators, EpochWarmup};
+    use aptos_crypto::HashValue;
+    use aptos_types::{
+        epoch_state::EpochState,
+        on_chain_config::ValidatorSet,
+        validator_info::ValidatorInfo,
+        validator_verifier::{random_validator_verifier, ValidatorVerifier},
+    };
+
+    #[test]
+    fn test_take_prepared_epoch_state() {
+        // Prepare the next epoch
+        let epoch_warmup = EpochWarmup::new(true);
+        let (_, verifier) = random_validator_verifier(4, None, false);
+        let validator_set = create_validator_set(&verifier);
+        let epoch_state = EpochState {
+            epoch: 10,
+            verifier,
+        };
+        epoch_warmup.prepare_next_epoch(HashValue::random(), &epoch_state);
+
+        // Verify the prepared state is only returned for the prepared epoch
+        assert!(epoch_warmup
+            .take_prepared_epoch_state(11, &validator_set)
+            .is_none());
+        epoch_warmup.prepare_next_epoch(HashValue::random(), &epoch_state);
+        let prepared_epoch_state = epoch_warmup
+            .take_prepared_epoch_state(10, &validator_set)
+            .unwrap();
+        assert_eq!(*prepared_epoch_state, epoch_state);
+
+        // Verify the prepared state is cleared once taken
+        assert!(epoch_warmup
+            .take_prepared_epoch_state(10, &validator_set)
+            .is_none());
+    }
+
+    #[test]
+    fn test_mismatched_epoch_state() {
+        // Prepare the next epoch
+        let epoch_warmup = EpochWarmup::new(true);
+        let (_, verifier) = random_validator_verifier(4, None, false);
+        epoch_warmup.prepare_next_epoch(HashValue::random(), &EpochState {
+            epoch: 10,
+            verifier,
+        });
+
+        // Verify the prepared state is ignored if it doesn't match the committed validator set
+        let (_, other_verifier) = random_validator_verifier(5, None, false);
+        let validator_set = create_validator_set(&other_verifier);
+        assert!(epoch_warmup
+            .take_prepared_epoch_state(10, &validator_set)
+            .is_none());
+    }
+
+    #[test]
+    fn test_disabled_epoch_warmup() {
+        // Prepare the next epoch with the warm-up disabled
+        let epoch_warmup = EpochWarmup::new(false);
+        let (_, verifier) = random_validator_verifier(4, None, false);
+        let validator_set = create_validator_set(&verifier);
+        epoch_warmup.prepare_next_epoch(HashValue::random(), &EpochState {
+            epoch: 10,
+            verifier,
+        });
+
+        // Verify nothing was prepared
+        assert!(epoch_warmup
+            .take_prepared_epoch_state(10, &validator_set)
+            .is_none());
+    }
+
+    #[test]
+    fn test_get_incoming_validators() {
+        // Create a validator set where the last validator is pending active
+        let (_, verifier) = random_validator_verifier(4, None, false);
+        let mut validator_set = create_validator_set(&verifier);
+        let incoming_validator = validator_set.active_validators.pop().unwrap();
+        validator_set
+            .pending_active
+            .push(incoming_validator.clone());
+
+        // Verify only the pending active validator is returned
+        let incoming_validators = get_incoming_validators(&validator_set, &verifier);
+        assert_eq!(incoming_validators.len(), 1);
+        let peer = incoming_validators
+            .get(incoming_validator.account_address())
+            .unwrap();
+        assert_eq!(
+            peer.addresses,
+            incoming_validator
+                .config()
+                .validator_network_addresses()
+                .unwrap()
+        );
+
+        // Verify pending active validators that are not in the next epoch are ignored
+        let (_, other_verifier) = random_validator_verifier(3, None, false);
+        assert!(get_incoming_validators(&validator_set, &other_verifier).is_empty());
+    }
+
+    /// Returns a validator set (with test network addresses) that matches the verifier
+    fn create_validator_set(verifier: &ValidatorVerifier) -> ValidatorSet {
+        let validator_infos = verifier
+            .get_ordered_account_addresses_iter()
+            .enumerate()
+            .map(|(index, address)| {
+                ValidatorInfo::new_with_test_network_keys(
+                    address,
+                    verifier.get_public_key(&address).unwrap(),
+                    verifier.get_voting_power(&address).unwrap(),
+                    index as u64,
+                )
+            })
+            .collect();
+        ValidatorSet::new(validator_infos)
+    }
+}
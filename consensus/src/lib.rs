@@ -21,6 +21,7 @@ mod block_storage;
 mod consensusdb;
 mod dag;
 mod epoch_manager;
+mod epoch_warmup;
 mod error;
 mod liveness;
 mod logging;
@@ -20,6 +20,9 @@ pub struct CachedProposerElection {
     // LRU cache wouldn't work as well, as access order of the elements
     // would define eviction, and could lead to evicting still needed elements.
     recent_elections: Mutex<BTreeMap<Round, (Author, f64)>>,
+    // Fallback proposers are cached separately, as they are only computed when
+    // fallback proposers are enabled.
+    recent_fallback_elections: Mutex<BTreeMap<Round, Vec<Author>>>,
     window: usize,
 }
 
@@ -33,6 +36,7 @@ impl CachedProposerElection {
             epoch,
             proposer_election,
             recent_elections: Mutex::new(BTreeMap::new()),
+            recent_fallback_elections: Mutex::new(BTreeMap::new()),
             window,
         }
     }
@@ -56,6 +60,28 @@ impl CachedProposerElection {
             result
         })
     }
+
+    pub fn get_or_compute_fallback_entry(&self, round: Round) -> Vec<Author> {
+        let mut recent_fallback_elections = self.recent_fallback_elections.lock();
+
+        if round > self.window as u64 {
+            *recent_fallback_elections =
+                recent_fallback_elections.split_off(&(round - self.window as u64));
+        }
+
+        recent_fallback_elections
+            .entry(round)
+            .or_insert_with(|| {
+                let _timer = PROPOSER_ELECTION_DURATION.start_timer();
+                let result = self.proposer_election.get_fallback_proposers(round);
+                info!(
+                    "Fallback ProposerElection for epoch {} and round {}: {:?}",
+                    self.epoch, round, result
+                );
+                result
+            })
+            .clone()
+    }
 }
 
 impl ProposerElection for CachedProposerElection {
@@ -63,6 +89,10 @@ impl ProposerElection for CachedProposerElection {
         self.get_or_compute_entry(round).0
     }
 
+    fn get_fallback_proposers(&self, round: Round) -> Vec<Author> {
+        self.get_or_compute_fallback_entry(round)
+    }
+
     fn get_voting_power_participation_ratio(&self, round: Round) -> f64 {
         self.get_or_compute_entry(round).1
     }
@@ -25,6 +25,13 @@ impl ProposerElection for MockProposerElection {
         asked.replace(asked.get() + 1);
         self.proposers[round_uszie % self.proposers.len()]
     }
+
+    fn get_fallback_proposers(&self, round: Round) -> Vec<Author> {
+        let round_uszie = round as usize;
+        let asked = self.asked.lock();
+        asked.replace(asked.get() + 1);
+        vec![self.proposers[(round_uszie + 1) % self.proposers.len()]]
+    }
 }
 
 #[test]
@@ -67,3 +74,30 @@ fn test_get_valid_proposer_caching() {
     assert_eq!(cpe.get_valid_proposer(1), authors[1]);
     assert_eq!(asked.lock().get(), 4);
 }
+
+#[test]
+fn test_get_fallback_proposers_caching() {
+    let asked = Arc::new(Mutex::new(Cell::new(0)));
+    let authors: Vec<Author> = (0..4).map(|_| Author::random()).collect();
+    let cpe = CachedProposerElection::new(
+        1,
+        Box::new(MockProposerElection::new(authors.clone(), asked.clone())),
+        10,
+    );
+
+    assert_eq!(cpe.get_fallback_proposers(0), vec![authors[1]]);
+    assert_eq!(asked.lock().get(), 1);
+    assert_eq!(cpe.get_fallback_proposer_index(authors[1], 0), Some(0));
+    assert_eq!(cpe.get_fallback_proposer_index(authors[0], 0), None);
+    assert_eq!(asked.lock().get(), 1);
+
+    // fallback proposers are cached independently of the valid proposers
+    assert_eq!(cpe.get_valid_proposer(0), authors[0]);
+    assert_eq!(asked.lock().get(), 2);
+
+    // round=0 is outside the caching window
+    assert_eq!(cpe.get_fallback_proposers(11), vec![authors[0]]);
+    assert_eq!(asked.lock().get(), 3);
+    assert_eq!(cpe.get_fallback_proposers(0), vec![authors[1]]);
+    assert_eq!(asked.lock().get(), 4);
+}
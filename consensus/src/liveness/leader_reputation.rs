@@ -548,6 +548,7 @@ pub struct LeaderReputation {
     exclude_round: u64,
     use_root_hash: bool,
     window_for_chain_health: usize,
+    num_fallback_proposers: usize,
 }
 
 impl LeaderReputation {
@@ -560,6 +561,7 @@ impl LeaderReputation {
         exclude_round: u64,
         use_root_hash: bool,
         window_for_chain_health: usize,
+        num_fallback_proposers: usize,
     ) -> Self {
         assert!(epoch_to_proposers.contains_key(&epoch));
         assert_eq!(epoch_to_proposers[&epoch].len(), voting_powers.len());
@@ -573,9 +575,65 @@ impl LeaderReputation {
             exclude_round,
             use_root_hash,
             window_for_chain_health,
+            num_fallback_proposers,
         }
     }
 
+    // Elect an ordered list of (at most num_proposers) distinct proposers for the round.
+    // The first proposer is the valid proposer for the round, and each following
+    // proposer is chosen from the remaining candidates using the same weights.
+    fn elect_proposers(
+        &self,
+        round: Round,
+        sliding_window: &[NewBlockEvent],
+        root_hash: HashValue,
+        num_proposers: usize,
+    ) -> Vec<Author> {
+        let mut weights =
+            self.heuristic
+                .get_weights(self.epoch, &self.epoch_to_proposers, sliding_window);
+        let proposers = &self.epoch_to_proposers[&self.epoch];
+        assert_eq!(weights.len(), proposers.len());
+
+        // Multiply weights by voting power:
+        let mut stake_weights: Vec<u128> = weights
+            .iter_mut()
+            .enumerate()
+            .map(|(i, w)| *w as u128 * self.voting_powers[i] as u128)
+            .collect();
+
+        let state = if self.use_root_hash {
+            [
+                root_hash.to_vec(),
+                self.epoch.to_le_bytes().to_vec(),
+                round.to_le_bytes().to_vec(),
+            ]
+            .concat()
+        } else {
+            [
+                self.epoch.to_le_bytes().to_vec(),
+                round.to_le_bytes().to_vec(),
+            ]
+            .concat()
+        };
+
+        let mut elected = Vec::with_capacity(num_proposers);
+        for i in 0..std::cmp::min(num_proposers, proposers.len()) {
+            // The valid proposer uses the original state, fallbacks extend it with their position
+            let state = if i == 0 {
+                state.clone()
+            } else if stake_weights.iter().all(|w| *w == 0) {
+                break;
+            } else {
+                [state.clone(), (i as u64).to_le_bytes().to_vec()].concat()
+            };
+            let chosen_index = choose_index(stake_weights.clone(), state);
+            elected.push(proposers[chosen_index]);
+            stake_weights[chosen_index] = 0;
+        }
+        elected
+    }
+
     // Compute chain health metrics, and
     // - return participating voting power percentage for the window_for_chain_health
     // - update metric counters for different windows
@@ -683,36 +741,23 @@ impl ProposerElection for LeaderReputation {
         let (sliding_window, root_hash) = self.backend.get_block_metadata(self.epoch, target_round);
         let voting_power_participation_ratio =
             self.compute_chain_health_and_add_metrics(&sliding_window, round);
-        let mut weights =
-            self.heuristic
-                .get_weights(self.epoch, &self.epoch_to_proposers, &sliding_window);
-        let proposers = &self.epoch_to_proposers[&self.epoch];
-        assert_eq!(weights.len(), proposers.len());
-
-        // Multiply weights by voting power:
-        let stake_weights: Vec<u128> = weights
-            .iter_mut()
-            .enumerate()
-            .map(|(i, w)| *w as u128 * self.voting_powers[i] as u128)
-            .collect();
-
-        let state = if self.use_root_hash {
-            [
-                root_hash.to_vec(),
-                self.epoch.to_le_bytes().to_vec(),
-                round.to_le_bytes().to_vec(),
-            ]
-            .concat()
-        } else {
-            [
-                self.epoch.to_le_bytes().to_vec(),
-                round.to_le_bytes().to_vec(),
-            ]
-            .concat()
-        };
+        let proposers = self.elect_proposers(round, &sliding_window, root_hash, 1);
+        (proposers[0], voting_power_participation_ratio)
+    }
 
-        let chosen_index = choose_index(stake_weights, state);
-        (proposers[chosen_index], voting_power_participation_ratio)
+    fn get_fallback_proposers(&self, round: Round) -> Vec<Author> {
+        if self.num_fallback_proposers == 0 {
+            return vec![];
+        }
+        let target_round = round.saturating_sub(self.exclude_round);
+        let (sliding_window, root_hash) = self.backend.get_block_metadata(self.epoch, target_round);
+        self.elect_proposers(
+            round,
+            &sliding_window,
+            root_hash,
+            self.num_fallback_proposers + 1,
+        )
+        .split_off(1)
     }
 
     fn get_valid_proposer(&self, round: Round) -> Author {
@@ -340,6 +340,7 @@ fn test_api(use_root_hash: bool) {
             4,
             use_root_hash,
             30,
+            2,
         );
         let round = 42u64;
 
@@ -361,6 +362,20 @@ fn test_api(use_root_hash: bool) {
         assert_eq!(output, proposers[expected_index]);
         assert!(leader_reputation.is_valid_proposer(proposers[expected_index], round));
         assert!(!leader_reputation.is_valid_proposer(proposers[unexpected_index], round));
+
+        // fallback proposers are distinct, deterministic and exclude the valid proposer
+        let fallback_proposers = leader_reputation.get_fallback_proposers(round);
+        assert_eq!(fallback_proposers.len(), 2);
+        assert!(!fallback_proposers.contains(&output));
+        assert_ne!(fallback_proposers[0], fallback_proposers[1]);
+        assert_eq!(
+            fallback_proposers,
+            leader_reputation.get_fallback_proposers(round)
+        );
+        assert_eq!(
+            leader_reputation.get_fallback_proposer_index(fallback_proposers[1], round),
+            Some(1)
+        );
     }
 
     for i in 0..5 {
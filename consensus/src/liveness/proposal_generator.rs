@@ -355,10 +355,12 @@ impl ProposalGenerator {
         };
 
         let quorum_cert = hqc.as_ref().clone();
+        // A fallback proposer also reports the valid proposer of this round as failed
+        let is_fallback_proposal = !proposer_election.is_valid_proposer(self.author, round);
         let failed_authors = self.compute_failed_authors(
             round,
             quorum_cert.certified_block().round(),
-            false,
+            is_fallback_proposal,
             proposer_election,
         );
 
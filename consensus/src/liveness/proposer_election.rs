@@ -20,6 +20,21 @@ pub trait ProposerElection {
     /// used by e.g., voters for choosing the destinations for sending their votes to).
     fn get_valid_proposer(&self, round: Round) -> Author;
 
+    /// Return the ordered list of fallback proposers for a given round (excluding
+    /// the valid proposer). Fallback proposers may propose (in order) if no proposal
+    /// has been seen after their fallback timeout. By default, there are none.
+    fn get_fallback_proposers(&self, _round: Round) -> Vec<Author> {
+        vec![]
+    }
+
+    /// Return the position of the given author in the fallback proposers for a
+    /// given round (if the author is a fallback proposer).
+    fn get_fallback_proposer_index(&self, author: Author, round: Round) -> Option<usize> {
+        self.get_fallback_proposers(round)
+            .iter()
+            .position(|fallback_proposer| *fallback_proposer == author)
+    }
+
     /// Return the chain health: a ratio of voting power participating in the consensus.
     fn get_voting_power_participation_ratio(&self, _round: Round) -> f64 {
         1.0
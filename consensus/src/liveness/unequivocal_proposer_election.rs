@@ -9,15 +9,15 @@ use aptos_consensus_types::{
 use aptos_crypto::HashValue;
 use aptos_infallible::Mutex;
 use aptos_logger::{error, warn, SecurityEvent};
-use std::{cmp::Ordering, sync::Arc};
+use std::{cmp::Ordering, collections::HashMap, sync::Arc};
 
 // Wrapper around ProposerElection.
 //
 // Provides is_valid_proposal that remembers, and rejects if
-// the same leader proposes multiple blocks.
+// the same leader (or fallback proposer) proposes multiple blocks.
 pub struct UnequivocalProposerElection {
     proposer_election: Arc<dyn ProposerElection + Send + Sync>,
-    already_proposed: Mutex<(Round, HashValue)>,
+    already_proposed: Mutex<(Round, HashMap<Author, HashValue>)>,
 }
 
 impl ProposerElection for UnequivocalProposerElection {
@@ -25,6 +25,10 @@ impl ProposerElection for UnequivocalProposerElection {
         self.proposer_election.get_valid_proposer(round)
     }
 
+    fn get_fallback_proposers(&self, round: Round) -> Vec<Author> {
+        self.proposer_election.get_fallback_proposers(round)
+    }
+
     fn get_voting_power_participation_ratio(&self, round: Round) -> f64 {
         self.proposer_election
             .get_voting_power_participation_ratio(round)
@@ -35,23 +39,27 @@ impl UnequivocalProposerElection {
     pub fn new(proposer_election: Arc<dyn ProposerElection + Send + Sync>) -> Self {
         Self {
             proposer_election,
-            already_proposed: Mutex::new((0, HashValue::zero())),
+            already_proposed: Mutex::new((0, HashMap::new())),
         }
     }
 
     // Return if a given proposed block is valid:
-    // - if a given author is a valid candidate for being a proposer
+    // - if a given author is a valid candidate (or fallback candidate) for being a proposer
     // - if this is the first block proposer has submitted in this round
     // - if it is not old proposal
     pub fn is_valid_proposal(&self, block: &Block) -> bool {
         block.author().map_or(false, |author| {
-            let valid_author = self.is_valid_proposer(author, block.round());
+            let valid_author = self.is_valid_proposer(author, block.round())
+                || self
+                    .get_fallback_proposer_index(author, block.round())
+                    .is_some();
             if !valid_author {
                 warn!(
                     SecurityEvent::InvalidConsensusProposal,
-                    "Proposal is not from valid author {}, expected {} for round {} and id {}",
+                    "Proposal is not from valid author {}, expected {} (or fallbacks {:?}) for round {} and id {}",
                     author,
                     self.get_valid_proposer(block.round()),
+                    self.get_fallback_proposers(block.round()),
                     block.round(),
                     block.id()
                 );
@@ -59,27 +67,30 @@ impl UnequivocalProposerElection {
                 return false;
             }
             let mut already_proposed = self.already_proposed.lock();
-            // detect if the leader proposes more than once in this round
+            // detect if the proposer proposes more than once in this round
             match block.round().cmp(&already_proposed.0) {
                 Ordering::Greater => {
                     already_proposed.0 = block.round();
-                    already_proposed.1 = block.id();
+                    already_proposed.1 = HashMap::from([(author, block.id())]);
                     true
                 },
-                Ordering::Equal => {
-                    if already_proposed.1 != block.id() {
+                Ordering::Equal => match already_proposed.1.get(&author) {
+                    Some(already_proposed_id) if *already_proposed_id != block.id() => {
                         error!(
                             SecurityEvent::InvalidConsensusProposal,
                             "Multiple proposals from {} for round {}: {} and {}",
                             author,
                             block.round(),
-                            already_proposed.1,
+                            already_proposed_id,
                             block.id()
                         );
                         false
-                    } else {
+                    },
+                    Some(_) => true,
+                    None => {
+                        already_proposed.1.insert(author, block.id());
                         true
-                    }
+                    },
                 },
                 Ordering::Less => false,
             }
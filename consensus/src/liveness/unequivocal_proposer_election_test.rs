@@ -12,11 +12,18 @@ use std::{collections::HashMap, sync::Arc};
 
 struct MockProposerElection {
     proposers: HashMap<Round, Author>,
+    fallback_proposers: HashMap<Round, Vec<Author>>,
 }
 
 impl MockProposerElection {
-    pub fn new(proposers: HashMap<Round, Author>) -> Self {
-        Self { proposers }
+    pub fn new(
+        proposers: HashMap<Round, Author>,
+        fallback_proposers: HashMap<Round, Vec<Author>>,
+    ) -> Self {
+        Self {
+            proposers,
+            fallback_proposers,
+        }
     }
 }
 
@@ -24,6 +31,13 @@ impl ProposerElection for MockProposerElection {
     fn get_valid_proposer(&self, round: Round) -> Author {
         *self.proposers.get(&round).unwrap()
     }
+
+    fn get_fallback_proposers(&self, round: Round) -> Vec<Author> {
+        self.fallback_proposers
+            .get(&round)
+            .cloned()
+            .unwrap_or_default()
+    }
 }
 
 #[test]
@@ -82,11 +96,10 @@ fn test_is_valid_proposal() {
     )
     .unwrap();
 
-    let pe =
-        UnequivocalProposerElection::new(Arc::new(MockProposerElection::new(HashMap::from([
-            (1, chosen_author),
-            (2, chosen_author),
-        ]))));
+    let pe = UnequivocalProposerElection::new(Arc::new(MockProposerElection::new(
+        HashMap::from([(1, chosen_author), (2, chosen_author)]),
+        HashMap::new(),
+    )));
 
     assert!(pe.is_valid_proposer(chosen_author, 1));
     assert!(pe.is_valid_proposal(&good_proposal));
@@ -104,3 +117,50 @@ fn test_is_valid_proposal() {
     // Proposal from previous round is not valid any more:
     assert!(!pe.is_valid_proposal(&good_proposal));
 }
+
+#[test]
+fn test_is_valid_fallback_proposal() {
+    let chosen_validator_signer = ValidatorSigner::random([0u8; 32]);
+    let chosen_author = chosen_validator_signer.author();
+    let fallback_validator_signer = ValidatorSigner::random([1u8; 32]);
+    let fallback_author = fallback_validator_signer.author();
+    let another_validator_signer = ValidatorSigner::random([2u8; 32]);
+
+    let quorum_cert = certificate_for_genesis();
+    let create_proposal = |round, timestamp, signer| {
+        Block::new_proposal(
+            Payload::empty(false),
+            round,
+            timestamp,
+            quorum_cert.clone(),
+            signer,
+            Vec::new(),
+        )
+        .unwrap()
+    };
+    let good_proposal = create_proposal(1, 1, &chosen_validator_signer);
+    let fallback_proposal = create_proposal(1, 2, &fallback_validator_signer);
+    let fallback_duplicate_proposal = create_proposal(1, 3, &fallback_validator_signer);
+    let bad_author_proposal = create_proposal(1, 4, &another_validator_signer);
+    let next_fallback_proposal = create_proposal(2, 5, &fallback_validator_signer);
+
+    let pe = UnequivocalProposerElection::new(Arc::new(MockProposerElection::new(
+        HashMap::from([(1, chosen_author), (2, chosen_author)]),
+        HashMap::from([(1, vec![fallback_author])]),
+    )));
+
+    assert_eq!(pe.get_fallback_proposer_index(fallback_author, 1), Some(0));
+    assert_eq!(pe.get_fallback_proposer_index(chosen_author, 1), None);
+
+    // proposals from both the valid proposer and the fallback proposer pass
+    assert!(pe.is_valid_proposal(&fallback_proposal));
+    assert!(pe.is_valid_proposal(&good_proposal));
+    assert!(!pe.is_valid_proposal(&bad_author_proposal));
+
+    // another proposal from the fallback proposer should fail
+    assert!(!pe.is_valid_proposal(&fallback_duplicate_proposal));
+    assert!(pe.is_valid_proposal(&fallback_proposal));
+
+    // the fallback proposer is not a fallback in the next round
+    assert!(!pe.is_valid_proposal(&next_fallback_proposal));
+}
@@ -4,7 +4,9 @@
 
 use crate::{
     block_storage::tracing::{observe_block, BlockStage},
-    counters, monitor,
+    counters,
+    epoch_warmup::EpochWarmup,
+    monitor,
     network::{IncomingCommitRequest, NetworkSender},
     network_interface::ConsensusMsg,
     pipeline::{
@@ -129,6 +131,7 @@ pub struct BufferManager {
     previous_commit_time: Instant,
     reset_flag: Arc<AtomicBool>,
     bounded_executor: BoundedExecutor,
+    epoch_warmup: EpochWarmup,
 }
 
 impl BufferManager {
@@ -153,6 +156,7 @@ impl BufferManager {
         ongoing_tasks: Arc<AtomicU64>,
         reset_flag: Arc<AtomicBool>,
         executor: BoundedExecutor,
+        epoch_warmup: EpochWarmup,
     ) -> Self {
         let buffer = Buffer::<BufferItem>::new();
 
@@ -198,6 +202,7 @@ impl BufferManager {
             previous_commit_time: Instant::now(),
             reset_flag,
             bounded_executor: executor,
+            epoch_warmup,
         }
     }
 
@@ -459,6 +464,17 @@ impl BufferManager {
             return;
         }
 
+        // Prepare the next epoch if the blocks end the current epoch. The
+        // blocks are ordered, so the reconfiguration is guaranteed to commit.
+        if let Some((reconfig_block_id, next_epoch_state)) = executed_blocks.iter().find_map(|b| {
+            b.block_info()
+                .next_epoch_state()
+                .map(|next_epoch_state| (b.id(), next_epoch_state.clone()))
+        }) {
+            self.epoch_warmup
+                .prepare_next_epoch(reconfig_block_id, &next_epoch_state);
+        }
+
         // Handle reconfiguration timestamp reconciliation.
         // end epoch timestamp is set to the first block that causes the reconfiguration.
         // once it's set, any subsequent block commit info will be set to this timestamp.
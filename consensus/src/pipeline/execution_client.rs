@@ -4,6 +4,7 @@
 
 use crate::{
     counters,
+    epoch_warmup::EpochWarmup,
     error::StateSyncError,
     network::{IncomingCommitRequest, IncomingRandGenRequest, NetworkSender},
     network_interface::{ConsensusMsg, ConsensusNetworkClient},
@@ -28,7 +29,7 @@ use anyhow::Result;
 use aptos_bounded_executor::BoundedExecutor;
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::config::ConsensusConfig;
-use aptos_consensus_types::{common::Author, pipelined_block::PipelinedBlock};
+use aptos_consensus_types::{block::Block, common::Author, pipelined_block::PipelinedBlock};
 use aptos_executor_types::ExecutorResult;
 use aptos_infallible::RwLock;
 use aptos_logger::prelude::*;
@@ -67,6 +68,9 @@ pub trait TExecutionClient: Send + Sync {
     /// This is needed for some DAG tests. Clean this up as a TODO.
     fn get_execution_channel(&self) -> Option<UnboundedSender<OrderedBlocks>>;
 
+    /// Start executing the given proposed block before it is ordered (if enabled).
+    async fn optimistic_execute(&self, block: &Block);
+
     /// Send ordered blocks to the real execution phase through the channel.
     async fn finalize_order(
         &self,
@@ -142,6 +146,7 @@ pub struct ExecutionProxyClient {
     // channels to buffer manager
     handle: Arc<RwLock<BufferManagerHandle>>,
     rand_storage: Arc<dyn RandStorage<AugmentedData>>,
+    epoch_warmup: EpochWarmup,
 }
 
 impl ExecutionProxyClient {
@@ -153,6 +158,7 @@ impl ExecutionProxyClient {
         network_sender: ConsensusNetworkClient<NetworkClient<ConsensusMsg>>,
         bounded_executor: BoundedExecutor,
         rand_storage: Arc<dyn RandStorage<AugmentedData>>,
+        epoch_warmup: EpochWarmup,
     ) -> Self {
         Self {
             consensus_config,
@@ -163,6 +169,7 @@ impl ExecutionProxyClient {
             bounded_executor,
             handle: Arc::new(RwLock::new(BufferManagerHandle::new())),
             rand_storage,
+            epoch_warmup,
         }
     }
 
@@ -252,6 +259,7 @@ impl ExecutionProxyClient {
             reset_buffer_manager_rx,
             epoch_state,
             self.bounded_executor.clone(),
+            self.epoch_warmup.clone(),
         );
 
         tokio::spawn(execution_schedule_phase.start());
@@ -306,6 +314,14 @@ impl TExecutionClient for ExecutionProxyClient {
         self.handle.read().execute_tx.clone()
     }
 
+    async fn optimistic_execute(&self, block: &Block) {
+        if self.consensus_config.enable_optimistic_execution && !block.is_nil_block() {
+            self.execution_proxy
+                .schedule_optimistic_compute(block, block.parent_id())
+                .await;
+        }
+    }
+
     async fn finalize_order(
         &self,
         blocks: &[Arc<PipelinedBlock>],
@@ -458,6 +474,8 @@ impl TExecutionClient for DummyExecutionClient {
         None
     }
 
+    async fn optimistic_execute(&self, _: &Block) {}
+
     async fn finalize_order(
         &self,
         _: &[Arc<PipelinedBlock>],
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    epoch_warmup::EpochWarmup,
     metrics_safety_rules::MetricsSafetyRules,
     network::{IncomingCommitRequest, NetworkSender},
     network_interface::{ConsensusMsg, ConsensusNetworkClient, DIRECT_SEND, RPC},
@@ -156,6 +157,7 @@ pub fn prepare_buffer_manager(
             verifier: validators.clone(),
         }),
         bounded_executor,
+        EpochWarmup::new(false),
     );
 
     (
@@ -16,6 +16,7 @@ use aptos_consensus_types::{
     common::{TransactionInProgress, TransactionSummary},
     proof_of_store::{BatchId, BatchInfo},
 };
+use aptos_executor_types::memory_budget::MemoryBudget;
 use aptos_logger::prelude::*;
 use aptos_mempool::QuorumStoreRequest;
 use aptos_types::{transaction::SignedTransaction, PeerId};
@@ -353,6 +354,7 @@ impl BatchGenerator {
         let mut dynamic_pull_txn_per_s = (self.config.back_pressure.dynamic_min_txn_per_s
             + self.config.back_pressure.dynamic_max_txn_per_s)
             / 2;
+        let memory_budget = MemoryBudget::global();
 
         loop {
             let _timer = counters::BATCH_GENERATOR_MAIN_LOOP.start_timer();
@@ -395,11 +397,19 @@ impl BatchGenerator {
                     } else {
                         counters::QS_BACKPRESSURE_PROOF_COUNT.observe(0.0);
                     }
+                    // Avoid creating new batches while the node is approaching its memory budget
+                    let memory_back_pressure = memory_budget.should_apply_backpressure();
+                    if memory_back_pressure {
+                        counters::QS_BACKPRESSURE_MEMORY_BUDGET.observe(1.0);
+                    } else {
+                        counters::QS_BACKPRESSURE_MEMORY_BUDGET.observe(0.0);
+                    }
                     let since_last_non_empty_pull_ms = std::cmp::min(
                         tick_start.duration_since(last_non_empty_pull).as_millis(),
                         self.config.batch_generation_max_interval_ms as u128
                     ) as usize;
                     if (!self.back_pressure.proof_count
+                        && !memory_back_pressure
                         && since_last_non_empty_pull_ms >= self.config.batch_generation_min_non_empty_interval_ms)
                         || since_last_non_empty_pull_ms == self.config.batch_generation_max_interval_ms {
 
@@ -14,7 +14,10 @@ use crate::{
 use anyhow::bail;
 use aptos_consensus_types::proof_of_store::{ProofOfStore, SignedBatchInfo};
 use aptos_crypto::HashValue;
-use aptos_executor_types::{ExecutorError, ExecutorResult};
+use aptos_executor_types::{
+    memory_budget::{MemoryBudget, MemoryConsumer},
+    ExecutorError, ExecutorResult,
+};
 use aptos_logger::prelude::*;
 use aptos_types::{transaction::SignedTransaction, validator_signer::ValidatorSigner, PeerId};
 use dashmap::{
@@ -187,6 +190,12 @@ impl BatchStore {
             .get_mut(&value.author())
             .expect("No QuotaManager for batch author");
         quota_manager.free_quota(value.num_bytes() as usize, value.payload_storage_mode());
+
+        // Release the payload memory from the memory budget
+        if value.payload_storage_mode() == StorageMode::MemoryAndPersisted {
+            MemoryBudget::global()
+                .record_release(MemoryConsumer::QuorumStoreBatches, value.num_bytes());
+        }
     }
 
     // Inserts a PersistedValue into the in-memory db_cache. If an entry with a higher
@@ -216,7 +225,7 @@ impl BatchStore {
                 }
             };
 
-            if self
+            let storage_mode = self
                 .peer_quota
                 .entry(author)
                 .or_insert(QuotaManager::new(
@@ -224,10 +233,13 @@ impl BatchStore {
                     self.memory_quota,
                     self.batch_quota,
                 ))
-                .update_quota(value.num_bytes() as usize)?
-                == StorageMode::PersistedOnly
-            {
+                .update_quota(value.num_bytes() as usize)?;
+            if storage_mode == StorageMode::PersistedOnly {
                 value.remove_payload();
+            } else {
+                // Track the payload memory in the memory budget
+                MemoryBudget::global()
+                    .record_allocation(MemoryConsumer::QuorumStoreBatches, value.num_bytes());
             }
 
             match cache_entry {
@@ -574,6 +574,13 @@ pub static QS_BACKPRESSURE_PROOF_COUNT: Lazy<Histogram> = Lazy::new(|| {
     )
 });
 
+pub static QS_BACKPRESSURE_MEMORY_BUDGET: Lazy<Histogram> = Lazy::new(|| {
+    register_avg_counter(
+        "quorum_store_backpressure_memory_budget",
+        "Indicator of whether Quorum Store is backpressured due to the node memory budget.",
+    )
+});
+
 pub static QS_BACKPRESSURE_DYNAMIC_MAX: Lazy<Histogram> = Lazy::new(|| {
     register_avg_counter(
         "quorum_store_backpressure_dynamic_max",
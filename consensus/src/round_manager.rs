@@ -43,7 +43,7 @@ use aptos_consensus_types::{
     vote_msg::VoteMsg,
 };
 use aptos_infallible::{checked, Mutex};
-use aptos_logger::prelude::*;
+use aptos_logger::{debug_toggles, prelude::*};
 #[cfg(test)]
 use aptos_safety_rules::ConsensusState;
 use aptos_safety_rules::TSafetyRules;
@@ -54,8 +54,12 @@ use aptos_types::{
     PeerId,
 };
 use fail::fail_point;
-use futures::{channel::oneshot, FutureExt, StreamExt};
-use futures_channel::mpsc::UnboundedReceiver;
+use futures::{
+    channel::oneshot,
+    future::{AbortHandle, Abortable},
+    FutureExt, StreamExt,
+};
+use futures_channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
 use serde::Serialize;
 use std::{mem::Discriminant, sync::Arc, time::Duration};
 use tokio::{
@@ -213,6 +217,14 @@ pub struct RoundManager {
     local_config: ConsensusConfig,
     features: Features,
     broadcast_vote: bool,
+    // Self sender (and receiver) for fallback proposal timeouts (i.e., the rounds
+    // in which this node may propose as a fallback proposer).
+    fallback_proposal_tx: UnboundedSender<Round>,
+    fallback_proposal_rx: Option<UnboundedReceiver<Round>>,
+    // The abort handle of the scheduled fallback proposal task (if any)
+    fallback_proposal_abort_handle: Option<AbortHandle>,
+    // The highest round for which a valid proposal has been received
+    highest_received_proposal_round: Round,
 }
 
 impl RoundManager {
@@ -241,6 +253,7 @@ impl RoundManager {
             .set(onchain_config.decoupled_execution() as i64);
         let vtxn_config = onchain_config.effective_validator_txn_config();
         debug!("vtxn_config={:?}", vtxn_config);
+        let (fallback_proposal_tx, fallback_proposal_rx) = unbounded();
         Self {
             epoch_state,
             block_store,
@@ -256,6 +269,10 @@ impl RoundManager {
             local_config,
             features,
             broadcast_vote,
+            fallback_proposal_tx,
+            fallback_proposal_rx: Some(fallback_proposal_rx),
+            fallback_proposal_abort_handle: None,
+            highest_received_proposal_round: 0,
         }
     }
 
@@ -282,6 +299,12 @@ impl RoundManager {
     /// once per round, we ensure that only at most one proposal can get generated per round to
     /// avoid accidental equivocation of proposals.
     ///
+    /// Fallback proposer:
+    ///
+    /// If fallback proposers are enabled and this replica is a fallback proposer for this round,
+    /// schedule a fallback proposal. It is only generated if no proposal has been voted for
+    /// by the time the fallback timeout expires.
+    ///
     /// Replica:
     ///
     /// Do nothing
@@ -304,13 +327,18 @@ impl RoundManager {
             reason = new_round_event.reason
         );
 
+        // The fallback proposal of the previous round (if any) is no longer needed
+        if let Some(abort_handle) = self.fallback_proposal_abort_handle.take() {
+            abort_handle.abort();
+        }
+
         if self
             .proposer_election
             .is_valid_proposer(self.proposal_generator.author(), new_round_event.round)
         {
             self.log_collected_vote_stats(&new_round_event);
             self.round_state.setup_leader_timeout();
-            let proposal_msg = self.generate_proposal(new_round_event).await?;
+            let proposal_msg = self.generate_proposal(new_round_event.round).await?;
             #[cfg(feature = "failpoints")]
             {
                 if self.check_whether_to_inject_reconfiguration_error() {
@@ -320,7 +348,69 @@ impl RoundManager {
             }
             self.network.broadcast_proposal(proposal_msg).await;
             counters::PROPOSALS_COUNT.inc();
+        } else {
+            self.schedule_fallback_proposal(new_round_event.round);
+        }
+        Ok(())
+    }
+
+    /// Schedules a fallback proposal for the given round if this replica is a fallback
+    /// proposer. The n-th fallback proposer waits n times the fallback timeout. The
+    /// scheduled task is aborted once the round advances.
+    fn schedule_fallback_proposal(&mut self, round: Round) {
+        let proposer_fallback_config = self.onchain_config.proposer_fallback_config();
+        if !proposer_fallback_config.enabled() {
+            return;
+        }
+
+        if let Some(fallback_index) = self
+            .proposer_election
+            .get_fallback_proposer_index(self.proposal_generator.author(), round)
+        {
+            let fallback_timeout = Duration::from_millis(
+                proposer_fallback_config.fallback_timeout_ms * (fallback_index as u64 + 1),
+            );
+            info!(
+                self.new_log(LogEvent::NewRound),
+                "Scheduling fallback proposal (fallback index: {}) after {:?}",
+                fallback_index,
+                fallback_timeout
+            );
+            let fallback_proposal_tx = self.fallback_proposal_tx.clone();
+            let (abort_handle, abort_registration) = AbortHandle::new_pair();
+            tokio::spawn(Abortable::new(
+                async move {
+                    sleep(fallback_timeout).await;
+                    let _ = fallback_proposal_tx.unbounded_send(round);
+                },
+                abort_registration,
+            ));
+            self.fallback_proposal_abort_handle = Some(abort_handle);
+        }
+    }
+
+    /// Fallback proposer:
+    ///
+    /// Triggered once the fallback timeout for the given round expires. If the round is still
+    /// the current round, and no proposal has been received or voted for (i.e., the previous
+    /// proposers have failed to propose in time), generate and broadcast a fallback proposal.
+    pub async fn process_fallback_proposal_timeout(&mut self, round: Round) -> anyhow::Result<()> {
+        if round != self.round_state.current_round()
+            || self.round_state.vote_sent().is_some()
+            || self.highest_received_proposal_round >= round
+        {
+            return Ok(());
         }
+
+        warn!(
+            self.new_log(LogEvent::Propose),
+            "No proposal has been voted for by the fallback timeout, proposing as a fallback proposer (expected proposer: {})",
+            self.proposer_election.get_valid_proposer(round)
+        );
+        let proposal_msg = self.generate_proposal(round).await?;
+        self.network.broadcast_proposal(proposal_msg).await;
+        counters::PROPOSALS_COUNT.inc();
+        counters::FALLBACK_PROPOSALS_COUNT.inc();
         Ok(())
     }
 
@@ -393,10 +483,7 @@ impl RoundManager {
         );
     }
 
-    async fn generate_proposal(
-        &mut self,
-        new_round_event: NewRoundEvent,
-    ) -> anyhow::Result<ProposalMsg> {
+    async fn generate_proposal(&mut self, round: Round) -> anyhow::Result<ProposalMsg> {
         // Proposal generator will ensure that at most one proposal is generated per round
         let sync_info = self.block_store.sync_info();
         let sender = self.network.clone();
@@ -407,7 +494,7 @@ impl RoundManager {
 
         let proposal = self
             .proposal_generator
-            .generate_proposal(new_round_event.round, &mut self.proposer_election, callback)
+            .generate_proposal(round, &mut self.proposer_election, callback)
             .await?;
         let signature = self.safety_rules.lock().sign_proposal(&proposal)?;
         let signed_proposal =
@@ -439,6 +526,18 @@ impl RoundManager {
             block_hash = proposal_msg.proposal().id(),
             block_parent_hash = proposal_msg.proposal().quorum_cert().certified_block().id(),
         );
+        if debug_toggles::VERBOSE_CONSENSUS_LOGGING
+            .should_log_round(proposal_msg.proposal().round())
+        {
+            info!(
+                self.new_log(LogEvent::ReceiveProposal)
+                    .remote_peer(proposal_msg.proposer()),
+                "[Verbose] Received proposal: {}, sync info: {}, payload size: {}",
+                proposal_msg.proposal(),
+                proposal_msg.sync_info(),
+                proposal_msg.proposal().payload_size(),
+            );
+        }
 
         if self
             .ensure_round_and_sync_up(
@@ -745,12 +844,18 @@ impl RoundManager {
             author,
             proposal,
         );
+        self.highest_received_proposal_round =
+            std::cmp::max(self.highest_received_proposal_round, proposal.round());
 
         // Validate that failed_authors list is correctly specified in the block.
+        // Fallback proposals also include the valid proposer of the current round.
+        let is_fallback_proposal = !self
+            .proposer_election
+            .is_valid_proposer(author, proposal.round());
         let expected_failed_authors = self.proposal_generator.compute_failed_authors(
             proposal.round(),
             proposal.quorum_cert().certified_block().round(),
-            false,
+            is_fallback_proposal,
             &mut self.proposer_election,
         );
         ensure!(
@@ -845,7 +950,15 @@ impl RoundManager {
                 self.new_log(LogEvent::Vote).remote_peer(recipient),
                 "{}", vote
             );
-            self.network.send_vote(vote_msg, vec![recipient]).await;
+            // Fallback proposers of the next round also need the votes to form a QC
+            let mut recipients = vec![recipient];
+            if self.onchain_config.proposer_fallback_config().enabled() {
+                recipients.extend(
+                    self.proposer_election
+                        .get_fallback_proposers(proposal_round + 1),
+                );
+            }
+            self.network.send_vote(vote_msg, recipients).await;
         }
         Ok(())
     }
@@ -862,6 +975,11 @@ impl RoundManager {
             .await
             .context("[RoundManager] Failed to execute_and_insert the block")?;
 
+        // Start executing the block while waiting for it to be ordered (if enabled)
+        self.block_store
+            .optimistic_execute(executed_block.block())
+            .await;
+
         // Short circuit if already voted.
         ensure!(
             self.round_state.vote_sent().is_none(),
@@ -904,6 +1022,17 @@ impl RoundManager {
         fail_point!("consensus::process_vote_msg", |_| {
             Err(anyhow::anyhow!("Injected error in process_vote_msg"))
         });
+        if debug_toggles::VERBOSE_CONSENSUS_LOGGING
+            .should_log_round(vote_msg.vote().vote_data().proposed().round())
+        {
+            info!(
+                self.new_log(LogEvent::ReceiveVote)
+                    .remote_peer(vote_msg.vote().author()),
+                "[Verbose] Received vote: {}, sync info: {}",
+                vote_msg.vote(),
+                vote_msg.sync_info(),
+            );
+        }
         // Check whether this validator is a valid recipient of the vote.
         if self
             .ensure_round_and_sync_up(
@@ -940,11 +1069,17 @@ impl RoundManager {
         );
 
         if !self.broadcast_vote && !vote.is_timeout() {
-            // Unlike timeout votes regular votes are sent to the leaders of the next round only.
+            // Unlike timeout votes regular votes are sent to the leaders (and fallback
+            // proposers) of the next round only.
             let next_round = round + 1;
+            let author = self.proposal_generator.author();
             ensure!(
-                self.proposer_election
-                    .is_valid_proposer(self.proposal_generator.author(), next_round),
+                self.proposer_election.is_valid_proposer(author, next_round)
+                    || (self.onchain_config.proposer_fallback_config().enabled()
+                        && self
+                            .proposer_election
+                            .get_fallback_proposer_index(author, next_round)
+                            .is_some()),
                 "[RoundManager] Received {}, but I am not a valid proposer for round {}, ignore.",
                 vote,
                 next_round
@@ -1075,6 +1210,10 @@ impl RoundManager {
     ) {
         info!(epoch = self.epoch_state().epoch, "RoundManager started");
         let mut close_rx = close_rx.into_stream();
+        let mut fallback_proposal_rx = self
+            .fallback_proposal_rx
+            .take()
+            .expect("The fallback proposal receiver is missing!");
         loop {
             tokio::select! {
                 biased;
@@ -1097,6 +1236,19 @@ impl RoundManager {
                         }
                     }
                 },
+                round = fallback_proposal_rx.select_next_some() => {
+                    let result = monitor!(
+                        "process_fallback_proposal_timeout",
+                        self.process_fallback_proposal_timeout(round).await
+                    );
+                    match result {
+                        Ok(_) => trace!(RoundStateLogSchema::new(self.round_state())),
+                        Err(e) => {
+                            counters::ERROR_COUNT.inc();
+                            warn!(error = ?e, kind = error_kind(&e), RoundStateLogSchema::new(self.round_state()));
+                        }
+                    }
+                },
                 proposal = buffered_proposal_rx.select_next_some() => {
                     let mut proposals = vec![proposal];
                     while let Some(Some(proposal)) = buffered_proposal_rx.next().now_or_never() {
@@ -21,7 +21,7 @@ use aptos_consensus_notifications::ConsensusNotificationSender;
 use aptos_consensus_types::{block::Block, common::Round, pipelined_block::PipelinedBlock};
 use aptos_crypto::HashValue;
 use aptos_executor_types::{BlockExecutorTrait, ExecutorResult, StateComputeResult};
-use aptos_infallible::RwLock;
+use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::prelude::*;
 use aptos_types::{
     account_address::AccountAddress,
@@ -34,7 +34,7 @@ use aptos_types::{
 };
 use fail::fail_point;
 use futures::{future::BoxFuture, SinkExt, StreamExt};
-use std::{boxed::Box, sync::Arc};
+use std::{boxed::Box, collections::HashMap, sync::Arc};
 use tokio::sync::Mutex as AsyncMutex;
 
 pub type StateComputeResultFut = BoxFuture<'static, ExecutorResult<PipelineExecutionResult>>;
@@ -90,6 +90,8 @@ pub struct ExecutionProxy {
     transaction_filter: Arc<TransactionFilter>,
     execution_pipeline: ExecutionPipeline,
     state: RwLock<Option<MutableState>>,
+    // The rounds of the blocks that were optimistically executed but not yet ordered
+    optimistic_executions: Mutex<HashMap<HashValue, Round>>,
 }
 
 impl ExecutionProxy {
@@ -125,6 +127,106 @@ impl ExecutionProxy {
             transaction_filter: Arc::new(txn_filter),
             execution_pipeline,
             state: RwLock::new(None),
+            optimistic_executions: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Starts executing the given (proposed) block before it is ordered. The output is
+    /// cached by the executor and reused once the block is ordered and executed via
+    /// `schedule_compute()`. Note: this is only supported when randomness is disabled,
+    /// as the block metadata depends on the randomness generated after ordering. The
+    /// block is also skipped if its parent has not been executed yet (the execution
+    /// would otherwise fail, as the parent output is not available).
+    pub async fn schedule_optimistic_compute(&self, block: &Block, parent_block_id: HashValue) {
+        let Some(MutableState {
+            validators,
+            payload_manager,
+            transaction_shuffler,
+            block_executor_onchain_config,
+            transaction_deduper,
+            is_randomness_enabled,
+        }) = self.state.read().as_ref().cloned()
+        else {
+            return; // The epoch has ended
+        };
+        if is_randomness_enabled {
+            return;
+        }
+        if !self.executor.is_block_executed(parent_block_id) {
+            counters::OPTIMISTIC_EXECUTION_BLOCKS
+                .with_label_values(&["skipped"])
+                .inc();
+            return;
+        }
+
+        // Ensure the block is only executed optimistically once
+        let block_id = block.id();
+        if self
+            .optimistic_executions
+            .lock()
+            .insert(block_id, block.round())
+            .is_some()
+        {
+            return;
+        }
+
+        debug!(
+            block = %block,
+            parent_id = parent_block_id,
+            "Executing block optimistically",
+        );
+        let transaction_generator = BlockPreparer::new(
+            payload_manager,
+            self.transaction_filter.clone(),
+            transaction_deduper,
+            transaction_shuffler,
+        );
+        let fut = self
+            .execution_pipeline
+            .queue(
+                block.clone(),
+                block.new_block_metadata(&validators).into(),
+                parent_block_id,
+                transaction_generator,
+                block_executor_onchain_config,
+            )
+            .await;
+        counters::OPTIMISTIC_EXECUTION_BLOCKS
+            .with_label_values(&["started"])
+            .inc();
+
+        // Wait for the result (it will be fetched from the executor once the block is ordered)
+        tokio::spawn(async move {
+            if let Err(error) = fut.await {
+                counters::OPTIMISTIC_EXECUTION_BLOCKS
+                    .with_label_values(&["failed"])
+                    .inc();
+                debug!(
+                    block_id = block_id,
+                    "Failed to execute block {} optimistically: {:?}", block_id, error
+                );
+            }
+        });
+    }
+
+    /// Returns the number of optimistically executed blocks that are not yet ordered
+    #[cfg(test)]
+    pub fn num_optimistic_executions(&self) -> usize {
+        self.optimistic_executions.lock().len()
+    }
+
+    /// Discards the optimistic executions of all blocks at or below the given
+    /// round (or all blocks, if no round is given). These were never ordered,
+    /// so their executions are counted as wasted work.
+    fn discard_optimistic_executions(&self, max_round: Option<Round>) {
+        let mut optimistic_executions = self.optimistic_executions.lock();
+        let num_executions = optimistic_executions.len();
+        optimistic_executions.retain(|_, round| max_round.is_some_and(|max| *round > max));
+        let num_discarded = num_executions - optimistic_executions.len();
+        if num_discarded > 0 {
+            counters::OPTIMISTIC_EXECUTION_BLOCKS
+                .with_label_values(&["wasted"])
+                .inc_by(num_discarded as u64);
         }
     }
 
@@ -174,6 +276,16 @@ impl StateComputer for ExecutionProxy {
             parent_id = parent_block_id,
             "Executing block",
         );
+        if self
+            .optimistic_executions
+            .lock()
+            .remove(&block_id)
+            .is_some()
+        {
+            counters::OPTIMISTIC_EXECUTION_BLOCKS
+                .with_label_values(&["used"])
+                .inc();
+        }
         let MutableState {
             validators,
             payload_manager,
@@ -302,6 +414,7 @@ impl StateComputer for ExecutionProxy {
 
         *latest_logical_time = logical_time;
         payload_manager.notify_commit(block_timestamp, payloads);
+        self.discard_optimistic_executions(Some(logical_time.round));
         Ok(())
     }
 
@@ -350,6 +463,7 @@ impl StateComputer for ExecutionProxy {
 
         // Similarly, after the state synchronization, we have to reset the cache
         // of BlockExecutor to guarantee the latest committed state is up to date.
+        self.discard_optimistic_executions(None);
         self.executor.reset()?;
 
         res.map_err(|error| {
@@ -385,6 +499,7 @@ impl StateComputer for ExecutionProxy {
     // on the next epoch.
     fn end_epoch(&self) {
         self.state.write().take();
+        self.discard_optimistic_executions(None);
     }
 }
 
@@ -420,6 +535,10 @@ async fn test_commit_sync_race() {
             Ok(())
         }
 
+        fn is_block_executed(&self, _block_id: HashValue) -> bool {
+            false
+        }
+
         fn execute_block(
             &self,
             _block: ExecutableBlock,
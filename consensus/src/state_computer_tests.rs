@@ -18,6 +18,7 @@ use aptos_infallible::Mutex;
 use aptos_types::{
     aggregate_signature::AggregateSignature,
     block_executor::{config::BlockExecutorConfigFromOnchain, partitioner::ExecutableBlock},
+    block_info::BlockInfo,
     contract_event::ContractEvent,
     epoch_state::EpochState,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
@@ -92,6 +93,15 @@ impl BlockExecutorTrait for DummyBlockExecutor {
         Ok(())
     }
 
+    fn is_block_executed(&self, block_id: HashValue) -> bool {
+        block_id == self.committed_block_id()
+            || self
+                .blocks_received
+                .lock()
+                .iter()
+                .any(|block| block.block_id == block_id)
+    }
+
     fn execute_block(
         &self,
         _block: ExecutableBlock,
@@ -261,3 +271,150 @@ async fn commit_should_discover_validator_txns() {
     assert_eq!(&validator_txn_0, supposed_validator_txn_0);
     assert_eq!(&validator_txn_1, supposed_validator_txn_1);
 }
+
+fn create_execution_proxy(
+    executor: Arc<DummyBlockExecutor>,
+    state_sync_notifier: Arc<DummyStateSyncNotifier>,
+) -> ExecutionProxy {
+    let execution_proxy = ExecutionProxy::new(
+        executor,
+        Arc::new(DummyTxnNotifier {}),
+        state_sync_notifier,
+        &Handle::current(),
+        TransactionFilter::new(Filter::empty()),
+    );
+    execution_proxy.new_epoch(
+        &EpochState::empty(),
+        Arc::new(PayloadManager::DirectMempool),
+        Arc::new(NoOpShuffler {}),
+        BlockExecutorConfigFromOnchain::new_no_block_limit(),
+        Arc::new(NoOpDeduper {}),
+        false,
+    );
+    execution_proxy
+}
+
+fn create_block_for_round_one() -> Block {
+    Block::new_for_testing(
+        HashValue::random(),
+        BlockData::dummy_with_validator_txns(vec![]),
+        None,
+    )
+}
+
+#[tokio::test]
+async fn optimistic_compute_should_be_reused_once_ordered() {
+    let execution_proxy = create_execution_proxy(
+        Arc::new(DummyBlockExecutor::new()),
+        Arc::new(DummyStateSyncNotifier::new()),
+    );
+    let block = create_block_for_round_one();
+
+    // Execute the block optimistically (on top of the committed block)
+    execution_proxy
+        .schedule_optimistic_compute(&block, HashValue::zero())
+        .await;
+    assert_eq!(execution_proxy.num_optimistic_executions(), 1);
+
+    // Ensure the block is only executed optimistically once
+    execution_proxy
+        .schedule_optimistic_compute(&block, HashValue::zero())
+        .await;
+    assert_eq!(execution_proxy.num_optimistic_executions(), 1);
+
+    // Order the block and verify the optimistic execution is used
+    let _ = execution_proxy
+        .schedule_compute(&block, HashValue::zero(), None)
+        .await
+        .await;
+    assert_eq!(execution_proxy.num_optimistic_executions(), 0);
+}
+
+#[tokio::test]
+async fn optimistic_compute_should_require_executed_parent() {
+    let executor = Arc::new(DummyBlockExecutor::new());
+    let execution_proxy =
+        create_execution_proxy(executor.clone(), Arc::new(DummyStateSyncNotifier::new()));
+    let block = create_block_for_round_one();
+
+    // Verify the block is not executed if its parent has not been executed
+    execution_proxy
+        .schedule_optimistic_compute(&block, HashValue::random())
+        .await;
+    assert_eq!(execution_proxy.num_optimistic_executions(), 0);
+    assert!(executor.blocks_received.lock().is_empty());
+}
+
+#[tokio::test]
+async fn optimistic_compute_should_be_discarded_if_not_ordered() {
+    let execution_proxy = create_execution_proxy(
+        Arc::new(DummyBlockExecutor::new()),
+        Arc::new(DummyStateSyncNotifier::new()),
+    );
+
+    // Execute the block optimistically
+    let block = create_block_for_round_one();
+    execution_proxy
+        .schedule_optimistic_compute(&block, HashValue::zero())
+        .await;
+    assert_eq!(execution_proxy.num_optimistic_executions(), 1);
+
+    // Commit a different block for the same round
+    let committed_block = create_block_for_round_one();
+    let ledger_info = LedgerInfo::new(
+        BlockInfo::new(
+            0,
+            committed_block.round(),
+            committed_block.id(),
+            HashValue::zero(),
+            0,
+            0,
+            None,
+        ),
+        HashValue::zero(),
+    );
+    let state_compute_result =
+        StateComputeResult::new_dummy_with_compute_status(vec![TransactionStatus::Keep(
+            ExecutionStatus::Success,
+        )]);
+    let blocks = vec![Arc::new(PipelinedBlock::new(
+        committed_block,
+        vec![],
+        state_compute_result,
+    ))];
+    let _ = execution_proxy
+        .commit(
+            blocks.as_slice(),
+            LedgerInfoWithSignatures::new(ledger_info, AggregateSignature::empty()),
+            Box::new(|_: &[Arc<PipelinedBlock>], _: LedgerInfoWithSignatures| {}),
+        )
+        .await;
+
+    // Verify the speculative result was discarded
+    assert_eq!(execution_proxy.num_optimistic_executions(), 0);
+}
+
+#[tokio::test]
+async fn optimistic_compute_should_be_discarded_on_epoch_end() {
+    let execution_proxy = create_execution_proxy(
+        Arc::new(DummyBlockExecutor::new()),
+        Arc::new(DummyStateSyncNotifier::new()),
+    );
+
+    // Execute the block optimistically
+    let block = create_block_for_round_one();
+    execution_proxy
+        .schedule_optimistic_compute(&block, HashValue::zero())
+        .await;
+    assert_eq!(execution_proxy.num_optimistic_executions(), 1);
+
+    // End the epoch and verify the speculative result was discarded
+    execution_proxy.end_epoch();
+    assert_eq!(execution_proxy.num_optimistic_executions(), 0);
+
+    // Verify no blocks are executed optimistically until the next epoch starts
+    execution_proxy
+        .schedule_optimistic_compute(&create_block_for_round_one(), HashValue::zero())
+        .await;
+    assert_eq!(execution_proxy.num_optimistic_executions(), 0);
+}
@@ -16,7 +16,7 @@ use crate::{
 };
 use anyhow::{format_err, Result};
 use aptos_channels::aptos_channel;
-use aptos_consensus_types::{common::Payload, pipelined_block::PipelinedBlock};
+use aptos_consensus_types::{block::Block, common::Payload, pipelined_block::PipelinedBlock};
 use aptos_crypto::HashValue;
 use aptos_executor_types::ExecutorResult;
 use aptos_infallible::Mutex;
@@ -106,6 +106,8 @@ impl TExecutionClient for MockExecutionClient {
         Some(self.executor_channel.clone())
     }
 
+    async fn optimistic_execute(&self, _block: &Block) {}
+
     async fn finalize_order(
         &self,
         blocks: &[Arc<PipelinedBlock>],
@@ -5,6 +5,7 @@
 use crate::{
     counters,
     epoch_manager::EpochManager,
+    epoch_warmup::EpochWarmup,
     network::NetworkTask,
     network_interface::{ConsensusNetworkClient, DIRECT_SEND, RPC},
     network_tests::{NetworkPlayground, TwinId},
@@ -164,6 +165,7 @@ impl SMRNode {
             aptos_time_service::TimeService::real(),
             vtxn_pool,
             Arc::new(InMemRandDb::new()),
+            EpochWarmup::new(config.consensus.enable_epoch_warmup),
         );
         let (network_task, network_receiver) =
             NetworkTask::new(network_service_events, self_receiver);
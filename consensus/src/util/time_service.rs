@@ -4,13 +4,14 @@
 
 use crate::counters;
 use aptos_logger::prelude::*;
+use aptos_time_service::TimeServiceTrait;
 use async_trait::async_trait;
 use futures::{
     future::{AbortHandle, Abortable},
     Future, FutureExt, SinkExt,
 };
 use std::{pin::Pin, time::Duration};
-use tokio::{runtime::Handle, time::sleep};
+use tokio::runtime::Handle;
 
 /// Time service is an abstraction for operations that depend on time
 /// It supports implementations that can simulated time or depend on actual time
@@ -91,16 +92,29 @@ where
     }
 }
 
-/// TimeService implementation that uses actual clock to schedule tasks
+/// TimeService implementation that uses a clock (usually the actual clock) to schedule tasks
 pub struct ClockTimeService {
     executor: Handle,
+    time_service: aptos_time_service::TimeService,
 }
 
 impl ClockTimeService {
     /// Creates new TimeService that runs tasks based on actual clock
     /// It needs executor to schedule internal tasks that facilitates it's work
     pub fn new(executor: Handle) -> ClockTimeService {
-        ClockTimeService { executor }
+        Self::new_with_time_service(executor, aptos_time_service::TimeService::real())
+    }
+
+    /// Creates new TimeService that runs tasks based on the clock of the given time
+    /// service (e.g., a mock clock that only advances when requested by tests)
+    pub fn new_with_time_service(
+        executor: Handle,
+        time_service: aptos_time_service::TimeService,
+    ) -> ClockTimeService {
+        ClockTimeService {
+            executor,
+            time_service,
+        }
     }
 }
 
@@ -108,9 +122,10 @@ impl ClockTimeService {
 impl TimeService for ClockTimeService {
     fn run_after(&self, timeout: Duration, mut t: Box<dyn ScheduledTask>) -> AbortHandle {
         let (abort_handle, abort_registration) = AbortHandle::new_pair();
+        let time_service = self.time_service.clone();
         let task = Abortable::new(
             async move {
-                sleep(timeout).await;
+                time_service.sleep(timeout).await;
                 t.run().await;
             },
             abort_registration,
@@ -120,11 +135,11 @@ impl TimeService for ClockTimeService {
     }
 
     fn get_current_timestamp(&self) -> Duration {
-        aptos_infallible::duration_since_epoch()
+        self.time_service.now_unix_time()
     }
 
     async fn sleep(&self, t: Duration) {
-        sleep(t).await
+        self.time_service.sleep(t).await
     }
 }
 
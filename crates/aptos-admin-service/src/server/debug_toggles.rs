@@ -0,0 +1,160 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{reply_with, reply_with_status};
+use aptos_logger::{
+    debug_toggles::{
+        self, ExecutionTracing, NetworkMessageDump, VerboseConsensusLogging, EXECUTION_TRACING,
+        NETWORK_MESSAGE_DUMP, VERBOSE_CONSENSUS_LOGGING,
+    },
+    info,
+};
+use aptos_types::PeerId;
+use http::header::{HeaderValue, CONTENT_TYPE};
+use hyper::{Body, Request, Response, StatusCode};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+    time::Duration,
+};
+
+/// Returns the statuses of all enabled debug toggles (as JSON)
+pub async fn handle_debug_toggles_status_request(
+    _req: Request<Body>,
+) -> hyper::Result<Response<Body>> {
+    let statuses = debug_toggles::get_enabled_toggle_statuses();
+    match serde_json::to_string_pretty(&statuses) {
+        Ok(body) => Ok(reply_with(
+            vec![(
+                CONTENT_TYPE,
+                HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
+            )],
+            body,
+        )),
+        Err(error) => Ok(reply_with_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            error.to_string(),
+        )),
+    }
+}
+
+/// Enables the debug toggle specified in the request. The toggle automatically
+/// reverts after `duration_secs`, which cannot exceed the given maximum duration.
+/// For example:
+///   - `/debug/toggles/enable?toggle=execution_tracing&sample_frequency=100&duration_secs=60`
+///   - `/debug/toggles/enable?toggle=verbose_consensus_logging&num_rounds=20&duration_secs=300`
+///   - `/debug/toggles/enable?toggle=network_message_dump&peers=0x1,0x2&duration_secs=60`
+pub async fn handle_enable_debug_toggle_request(
+    req: Request<Body>,
+    max_duration: Duration,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    // Parse the duration of the toggle
+    let duration_secs = match parse_query_param(&query_pairs, "duration_secs") {
+        Ok(duration_secs) => duration_secs,
+        Err(error) => return Ok(reply_with_status(StatusCode::BAD_REQUEST, error)),
+    };
+    let duration = Duration::from_secs(duration_secs);
+    if duration > max_duration {
+        return Ok(reply_with_status(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "The duration ({:?}) exceeds the maximum duration ({:?})!",
+                duration, max_duration
+            ),
+        ));
+    }
+
+    // Enable the toggle
+    let toggle_name = query_pairs.get("toggle").map(|toggle| toggle.to_string());
+    let result = match toggle_name.as_deref() {
+        Some("execution_tracing") => {
+            parse_query_param(&query_pairs, "sample_frequency").map(|sample_frequency| {
+                EXECUTION_TRACING.enable(ExecutionTracing::new(sample_frequency), duration)
+            })
+        },
+        Some("verbose_consensus_logging") => {
+            parse_query_param(&query_pairs, "num_rounds").map(|num_rounds| {
+                VERBOSE_CONSENSUS_LOGGING.enable(VerboseConsensusLogging::new(num_rounds), duration)
+            })
+        },
+        Some("network_message_dump") => parse_peers(&query_pairs)
+            .map(|peers| NETWORK_MESSAGE_DUMP.enable(NetworkMessageDump::new(peers), duration)),
+        _ => Err(format!("Unknown debug toggle: {:?}", toggle_name)),
+    };
+
+    match result {
+        Ok(()) => {
+            info!(
+                "Enabled debug toggle {:?} for {:?}. Query: {}",
+                toggle_name, duration, query
+            );
+            Ok(reply_with_status(
+                StatusCode::OK,
+                format!("Enabled debug toggle {:?} for {:?}.", toggle_name, duration),
+            ))
+        },
+        Err(error) => Ok(reply_with_status(StatusCode::BAD_REQUEST, error)),
+    }
+}
+
+/// Disables the debug toggle specified in the request.
+/// For example: `/debug/toggles/disable?toggle=execution_tracing`
+pub async fn handle_disable_debug_toggle_request(
+    req: Request<Body>,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    let toggle_name = query_pairs.get("toggle").map(|toggle| toggle.to_string());
+    match toggle_name.as_deref() {
+        Some("execution_tracing") => EXECUTION_TRACING.disable(),
+        Some("verbose_consensus_logging") => VERBOSE_CONSENSUS_LOGGING.disable(),
+        Some("network_message_dump") => NETWORK_MESSAGE_DUMP.disable(),
+        _ => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                format!("Unknown debug toggle: {:?}", toggle_name),
+            ))
+        },
+    }
+
+    info!("Disabled debug toggle {:?}.", toggle_name);
+    Ok(reply_with_status(
+        StatusCode::OK,
+        format!("Disabled debug toggle {:?}.", toggle_name),
+    ))
+}
+
+/// Parses the given (required) query parameter
+fn parse_query_param<T: FromStr>(
+    query_pairs: &HashMap<Cow<str>, Cow<str>>,
+    name: &str,
+) -> Result<T, String>
+where
+    T::Err: Display,
+{
+    let value = query_pairs
+        .get(name)
+        .ok_or_else(|| format!("Missing query parameter: {}", name))?;
+    value
+        .parse()
+        .map_err(|error| format!("Invalid {}: {}", name, error))
+}
+
+/// Parses the comma separated list of peers in the query
+fn parse_peers(query_pairs: &HashMap<Cow<str>, Cow<str>>) -> Result<HashSet<String>, String> {
+    let peers: String = parse_query_param(query_pairs, "peers")?;
+    peers
+        .split(',')
+        .map(|peer| {
+            PeerId::from_str(peer.trim())
+                .map(|peer_id| peer_id.to_string())
+                .map_err(|error| format!("Invalid peer {}: {}", peer, error))
+        })
+        .collect()
+}
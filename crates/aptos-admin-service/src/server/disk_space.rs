@@ -0,0 +1,72 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{reply_with, reply_with_status};
+use aptos_logger::info;
+use aptos_storage_interface::disk_space_guardrails::DISK_SPACE_GUARDRAILS;
+use http::header::{HeaderValue, CONTENT_TYPE};
+use hyper::{Body, Request, Response, StatusCode};
+use std::collections::HashMap;
+
+/// Returns a summary of the disk space guardrails (as JSON)
+pub async fn handle_disk_space_guardrails_status_request(
+    _req: Request<Body>,
+) -> hyper::Result<Response<Body>> {
+    let summary = DISK_SPACE_GUARDRAILS.get_summary();
+    match serde_json::to_string_pretty(&summary) {
+        Ok(body) => Ok(reply_with(
+            vec![(
+                CONTENT_TYPE,
+                HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
+            )],
+            body,
+        )),
+        Err(error) => Ok(reply_with_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            error.to_string(),
+        )),
+    }
+}
+
+/// Sets (or clears) the disk space guardrails admin override. While the
+/// override is set, non-critical operations are never paused. For example:
+///   - `/debug/storage/disk_space/override?enabled=true`
+///   - `/debug/storage/disk_space/override?enabled=false`
+pub async fn handle_disk_space_guardrails_override_request(
+    req: Request<Body>,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    // Parse the override value
+    let enabled: bool = match query_pairs.get("enabled") {
+        Some(enabled) => match enabled.parse() {
+            Ok(enabled) => enabled,
+            Err(error) => {
+                return Ok(reply_with_status(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid enabled: {}", error),
+                ))
+            },
+        },
+        None => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                "Missing query parameter: enabled",
+            ))
+        },
+    };
+
+    DISK_SPACE_GUARDRAILS.set_admin_override(enabled);
+    info!(
+        "Set the disk space guardrails admin override to {}.",
+        enabled
+    );
+    Ok(reply_with_status(
+        StatusCode::OK,
+        format!(
+            "Set the disk space guardrails admin override to {}.",
+            enabled
+        ),
+    ))
+}
@@ -0,0 +1,53 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{reply_with, reply_with_status};
+use aptos_block_executor::gas_calibration::GAS_CALIBRATION_RECORDER;
+use http::header::{HeaderValue, CONTENT_TYPE};
+use hyper::{Body, Request, Response, StatusCode};
+use std::{collections::HashMap, time::Duration};
+
+// The default target block execution time used by the calibration report
+const DEFAULT_TARGET_BLOCK_TIME_MS: u64 = 200;
+
+/// Returns a gas calibration report for the recently executed blocks (as JSON).
+/// The report suggests the block gas limit for the given target block time.
+/// For example:
+///   - `/debug/execution/gas_calibration`
+///   - `/debug/execution/gas_calibration?target_block_time_ms=300`
+pub async fn handle_gas_calibration_report_request(
+    req: Request<Body>,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    // Parse the target block time (if any)
+    let target_block_time_ms: u64 = match query_pairs.get("target_block_time_ms") {
+        Some(target_block_time_ms) => match target_block_time_ms.parse() {
+            Ok(target_block_time_ms) => target_block_time_ms,
+            Err(error) => {
+                return Ok(reply_with_status(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid target_block_time_ms: {}", error),
+                ))
+            },
+        },
+        None => DEFAULT_TARGET_BLOCK_TIME_MS,
+    };
+
+    let report =
+        GAS_CALIBRATION_RECORDER.generate_report(Duration::from_millis(target_block_time_ms));
+    match serde_json::to_string_pretty(&report) {
+        Ok(body) => Ok(reply_with(
+            vec![(
+                CONTENT_TYPE,
+                HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
+            )],
+            body,
+        )),
+        Err(error) => Ok(reply_with_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            error.to_string(),
+        )),
+    }
+}
@@ -0,0 +1,53 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{reply_with, reply_with_status};
+use aptos_logger::info;
+use aptos_mempool::{MempoolClientRequest, MempoolClientSender};
+use futures::{channel::oneshot, SinkExt};
+use http::header::{HeaderValue, CONTENT_TYPE};
+use hyper::{Body, Request, Response, StatusCode};
+
+/// Returns the transaction stats of each provenance (i.e., the upstream peer or
+/// API client that first delivered the transactions) tracked by mempool (as JSON).
+pub async fn handle_provenance_stats_request(
+    _req: Request<Body>,
+    mut mempool_client_sender: MempoolClientSender,
+) -> hyper::Result<Response<Body>> {
+    info!("Fetching mempool provenance stats.");
+
+    // Request the stats from mempool
+    let (callback, callback_receiver) = oneshot::channel();
+    if let Err(error) = mempool_client_sender
+        .send(MempoolClientRequest::GetProvenanceStats(callback))
+        .await
+    {
+        return Ok(reply_with_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to send the request to mempool: {}", error),
+        ));
+    }
+    let provenance_stats = match callback_receiver.await {
+        Ok(provenance_stats) => provenance_stats,
+        Err(error) => {
+            return Ok(reply_with_status(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to receive the stats from mempool: {}", error),
+            ))
+        },
+    };
+
+    match serde_json::to_string_pretty(&provenance_stats) {
+        Ok(body) => Ok(reply_with(
+            vec![(
+                CONTENT_TYPE,
+                HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
+            )],
+            body,
+        )),
+        Err(error) => Ok(reply_with_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            error.to_string(),
+        )),
+    }
+}
@@ -8,7 +8,10 @@ use aptos_consensus::{
 };
 use aptos_infallible::RwLock;
 use aptos_logger::info;
+use aptos_mempool::MempoolClientSender;
 use aptos_storage_interface::DbReaderWriter;
+use aptos_storage_service_types::bandwidth::BandwidthBudget;
+use aptos_time_service::TimeService;
 use hyper::{
     service::{make_service_fn, service_fn},
     Body, Request, Response, Server, StatusCode,
@@ -18,23 +21,34 @@ use std::{
     convert::Infallible,
     net::{SocketAddr, ToSocketAddrs},
     sync::Arc,
+    time::Duration,
 };
 use tokio::runtime::Runtime;
 
 mod consensus;
+mod debug_toggles;
+mod disk_space;
+mod execution;
+mod mempool;
 #[cfg(target_os = "linux")]
 pub mod profiling;
+mod state_sync;
 #[cfg(target_os = "linux")]
 mod thread_dump;
+mod time;
 mod utils;
 
 #[derive(Default)]
 pub struct Context {
     authentication_configs: Vec<AuthenticationConfig>,
+    max_debug_toggle_duration: Duration,
 
     aptos_db: RwLock<Option<Arc<DbReaderWriter>>>,
     consensus_db: RwLock<Option<Arc<StorageWriteProxy>>>,
     quorum_store_db: RwLock<Option<Arc<QuorumStoreDB>>>,
+    mempool_client_sender: RwLock<Option<MempoolClientSender>>,
+    state_sync_bandwidth_budget: RwLock<Option<Arc<BandwidthBudget>>>,
+    time_service: RwLock<Option<TimeService>>,
 }
 
 impl Context {
@@ -50,6 +64,18 @@ impl Context {
         *self.consensus_db.write() = Some(consensus_db);
         *self.quorum_store_db.write() = Some(quorum_store_db);
     }
+
+    fn set_mempool_client_sender(&self, mempool_client_sender: MempoolClientSender) {
+        *self.mempool_client_sender.write() = Some(mempool_client_sender);
+    }
+
+    fn set_state_sync_bandwidth_budget(&self, bandwidth_budget: Arc<BandwidthBudget>) {
+        *self.state_sync_bandwidth_budget.write() = Some(bandwidth_budget);
+    }
+
+    fn set_time_service(&self, time_service: TimeService) {
+        *self.time_service.write() = Some(time_service);
+    }
 }
 
 pub struct AdminService {
@@ -84,6 +110,9 @@ impl AdminService {
             runtime,
             context: Arc::new(Context {
                 authentication_configs: node_config.admin_service.authentication_configs.clone(),
+                max_debug_toggle_duration: Duration::from_secs(
+                    node_config.admin_service.max_debug_toggle_duration_secs,
+                ),
                 ..Default::default()
             }),
         };
@@ -108,6 +137,20 @@ impl AdminService {
             .set_consensus_dbs(consensus_db, quorum_store_db)
     }
 
+    pub fn set_mempool_client_sender(&self, mempool_client_sender: MempoolClientSender) {
+        self.context
+            .set_mempool_client_sender(mempool_client_sender)
+    }
+
+    pub fn set_state_sync_bandwidth_budget(&self, bandwidth_budget: Arc<BandwidthBudget>) {
+        self.context
+            .set_state_sync_bandwidth_budget(bandwidth_budget)
+    }
+
+    pub fn set_time_service(&self, time_service: TimeService) {
+        self.context.set_time_service(time_service)
+    }
+
     fn start(&self, address: SocketAddr, enabled: bool) {
         let context = self.context.clone();
         self.runtime.spawn(async move {
@@ -211,6 +254,83 @@ impl AdminService {
                     ))
                 }
             },
+            (hyper::Method::GET, "/debug/execution/gas_calibration") => {
+                execution::handle_gas_calibration_report_request(req).await
+            },
+            (hyper::Method::GET, "/debug/mempool/provenance") => {
+                let mempool_client_sender = context.mempool_client_sender.read().clone();
+                if let Some(mempool_client_sender) = mempool_client_sender {
+                    mempool::handle_provenance_stats_request(req, mempool_client_sender).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Mempool is not available.",
+                    ))
+                }
+            },
+            (hyper::Method::GET, "/debug/state_sync/bandwidth_limit") => {
+                let bandwidth_budget = context.state_sync_bandwidth_budget.read().clone();
+                if let Some(bandwidth_budget) = bandwidth_budget {
+                    state_sync::handle_bandwidth_limit_status_request(req, bandwidth_budget).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "State sync is not available.",
+                    ))
+                }
+            },
+            (hyper::Method::POST, "/debug/state_sync/bandwidth_limit") => {
+                let bandwidth_budget = context.state_sync_bandwidth_budget.read().clone();
+                if let Some(bandwidth_budget) = bandwidth_budget {
+                    state_sync::handle_set_bandwidth_limit_request(req, bandwidth_budget).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "State sync is not available.",
+                    ))
+                }
+            },
+            (hyper::Method::GET, "/debug/time") => {
+                let time_service = context.time_service.read().clone();
+                if let Some(time_service) = time_service {
+                    time::handle_time_status_request(req, time_service).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Time service is not available.",
+                    ))
+                }
+            },
+            (hyper::Method::POST, "/debug/time/advance") => {
+                let time_service = context.time_service.read().clone();
+                if let Some(time_service) = time_service {
+                    time::handle_advance_time_request(req, time_service).await
+                } else {
+                    Ok(reply_with_status(
+                        StatusCode::NOT_FOUND,
+                        "Time service is not available.",
+                    ))
+                }
+            },
+            (hyper::Method::GET, "/debug/toggles") => {
+                debug_toggles::handle_debug_toggles_status_request(req).await
+            },
+            (hyper::Method::POST, "/debug/toggles/enable") => {
+                debug_toggles::handle_enable_debug_toggle_request(
+                    req,
+                    context.max_debug_toggle_duration,
+                )
+                .await
+            },
+            (hyper::Method::POST, "/debug/toggles/disable") => {
+                debug_toggles::handle_disable_debug_toggle_request(req).await
+            },
+            (hyper::Method::GET, "/debug/storage/disk_space") => {
+                disk_space::handle_disk_space_guardrails_status_request(req).await
+            },
+            (hyper::Method::POST, "/debug/storage/disk_space/override") => {
+                disk_space::handle_disk_space_guardrails_override_request(req).await
+            },
             _ => Ok(reply_with_status(StatusCode::NOT_FOUND, "Not found.")),
         }
     }
@@ -0,0 +1,70 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{reply_with, reply_with_status};
+use aptos_logger::info;
+use aptos_storage_service_types::bandwidth::BandwidthBudget;
+use http::header::{HeaderValue, CONTENT_TYPE};
+use hyper::{Body, Request, Response, StatusCode};
+use std::{collections::HashMap, sync::Arc};
+
+/// Returns a summary of the state sync bandwidth budget (as JSON)
+pub async fn handle_bandwidth_limit_status_request(
+    _req: Request<Body>,
+    bandwidth_budget: Arc<BandwidthBudget>,
+) -> hyper::Result<Response<Body>> {
+    let summary = bandwidth_budget.get_summary();
+    match serde_json::to_string_pretty(&summary) {
+        Ok(body) => Ok(reply_with(
+            vec![(
+                CONTENT_TYPE,
+                HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
+            )],
+            body,
+        )),
+        Err(error) => Ok(reply_with_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            error.to_string(),
+        )),
+    }
+}
+
+/// Sets (or clears) the runtime state sync bandwidth limit. While set, the
+/// runtime limit overrides the configured syncing caps (a limit of 0 is unlimited).
+/// For example:
+///   - `/debug/state_sync/bandwidth_limit?bytes_per_second=1048576`
+///   - `/debug/state_sync/bandwidth_limit` (clears the runtime limit)
+pub async fn handle_set_bandwidth_limit_request(
+    req: Request<Body>,
+    bandwidth_budget: Arc<BandwidthBudget>,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    // Parse the runtime limit (if any)
+    let runtime_limit: Option<u64> = match query_pairs.get("bytes_per_second") {
+        Some(bytes_per_second) => match bytes_per_second.parse() {
+            Ok(bytes_per_second) => Some(bytes_per_second),
+            Err(error) => {
+                return Ok(reply_with_status(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid bytes_per_second: {}", error),
+                ))
+            },
+        },
+        None => None,
+    };
+
+    bandwidth_budget.set_runtime_syncing_limit(runtime_limit);
+    info!(
+        "Set the state sync runtime bandwidth limit (bytes/sec) to {:?}.",
+        runtime_limit
+    );
+    Ok(reply_with_status(
+        StatusCode::OK,
+        format!(
+            "Set the state sync runtime bandwidth limit (bytes/sec) to {:?}.",
+            runtime_limit
+        ),
+    ))
+}
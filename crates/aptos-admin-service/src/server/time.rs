@@ -0,0 +1,82 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{reply_with, reply_with_status};
+use aptos_logger::info;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use http::header::{HeaderValue, CONTENT_TYPE};
+use hyper::{Body, Request, Response, StatusCode};
+use std::{collections::HashMap, time::Duration};
+
+/// Returns the current time of the node's time service (as JSON), and
+/// whether or not the time service is mocked.
+pub async fn handle_time_status_request(
+    _req: Request<Body>,
+    time_service: TimeService,
+) -> hyper::Result<Response<Body>> {
+    let status = serde_json::json!({
+        "mock_time_service": time_service.is_mock(),
+        "now_unix_time_ms": time_service.now_unix_time().as_millis() as u64,
+    });
+    match serde_json::to_string_pretty(&status) {
+        Ok(body) => Ok(reply_with(
+            vec![(
+                CONTENT_TYPE,
+                HeaderValue::from_static(mime::APPLICATION_JSON.as_ref()),
+            )],
+            body,
+        )),
+        Err(error) => Ok(reply_with_status(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            error.to_string(),
+        )),
+    }
+}
+
+/// Advances the node's mock time service by the given duration, waking any
+/// timers that expire. This is only supported if the node uses a mock time
+/// service. For example:
+///   - `/debug/time/advance?duration_ms=5000`
+pub async fn handle_advance_time_request(
+    req: Request<Body>,
+    time_service: TimeService,
+) -> hyper::Result<Response<Body>> {
+    let query = req.uri().query().unwrap_or("");
+    let query_pairs: HashMap<_, _> = url::form_urlencoded::parse(query.as_bytes()).collect();
+
+    // Parse the duration to advance by
+    let duration = match query_pairs.get("duration_ms") {
+        Some(duration_ms) => match duration_ms.parse() {
+            Ok(duration_ms) => Duration::from_millis(duration_ms),
+            Err(error) => {
+                return Ok(reply_with_status(
+                    StatusCode::BAD_REQUEST,
+                    format!("Invalid duration_ms: {}", error),
+                ))
+            },
+        },
+        None => {
+            return Ok(reply_with_status(
+                StatusCode::BAD_REQUEST,
+                "Missing duration_ms!",
+            ))
+        },
+    };
+
+    match time_service.advance_mock_time(duration) {
+        Some(num_woken) => {
+            let message = format!(
+                "Advanced the mock time by {:?} (woke {} timers). Current unix time: {:?}.",
+                duration,
+                num_woken,
+                time_service.now_unix_time()
+            );
+            info!("{}", message);
+            Ok(reply_with_status(StatusCode::OK, message))
+        },
+        None => Ok(reply_with_status(
+            StatusCode::BAD_REQUEST,
+            "The node does not use a mock time service!",
+        )),
+    }
+}
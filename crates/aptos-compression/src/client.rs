@@ -6,10 +6,12 @@
 /// each client.
 #[derive(Clone, Copy, Debug)]
 pub enum CompressionClient {
+    ColdStorage,
     Consensus,
     DKG,
     JWKConsensus,
     Mempool,
+    StateSnapshot,
     StateSync,
 }
 
@@ -17,10 +19,12 @@ impl CompressionClient {
     /// Returns a summary label for the request
     pub fn get_label(&self) -> &'static str {
         match self {
+            Self::ColdStorage => "cold_storage",
             Self::Consensus => "consensus",
             Self::DKG => "dkg",
             Self::JWKConsensus => "jwk_consensus",
             Self::Mempool => "mempool",
+            Self::StateSnapshot => "state_snapshot",
             Self::StateSync => "state_sync",
         }
     }
@@ -0,0 +1,43 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+
+/// The default compression level to use for zstd. This offers a
+/// reasonable trade-off between compression ratio and speed.
+pub const DEFAULT_ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+/// The codec used to compress and decompress data. This allows callers
+/// to pick cheaper compression (e.g., for peers on a local network) or
+/// stronger compression (e.g., for peers on a wide area network).
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CompressionCodec {
+    None,                // The data is not compressed
+    Lz4,                 // The data is compressed using LZ4 (in fast mode)
+    Zstd { level: i32 }, // The data is compressed using zstd (at the given level)
+}
+
+impl CompressionCodec {
+    /// Returns all supported codecs (using the default compression levels)
+    pub fn all() -> Vec<CompressionCodec> {
+        vec![Self::None, Self::Lz4, Self::Zstd {
+            level: DEFAULT_ZSTD_COMPRESSION_LEVEL,
+        }]
+    }
+
+    /// Returns a summary label for the codec
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Lz4 => "lz4",
+            Self::Zstd { .. } => "zstd",
+        }
+    }
+
+    /// Returns true iff the given codec uses the same algorithm
+    /// as this codec (regardless of the compression level).
+    pub fn is_same_algorithm(&self, other: &CompressionCodec) -> bool {
+        self.get_label() == other.get_label()
+    }
+}
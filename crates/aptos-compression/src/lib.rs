@@ -3,6 +3,7 @@
 
 use crate::{
     client::CompressionClient,
+    codec::CompressionCodec,
     Error::{CompressionError, DecompressionError},
 };
 use aptos_logger::prelude::*;
@@ -15,11 +16,14 @@ use thiserror::Error;
 /// sent across the network (e.g., by state sync and consensus).
 /// Internally, it uses LZ4 in fast mode to compress the data.
 /// See <https://github.com/10xGenomics/lz4-rs> for more information.
+/// Callers may also select a specific codec (e.g., zstd at a given
+/// level) using `compress_with_codec` and `decompress_with_codec`.
 ///
 /// Note: the crate also exposes some basic compression metrics
 /// that can be used to track the cumulative compression ratio
 /// and compression/decompression durations during the runtime.
 pub mod client;
+pub mod codec;
 mod metrics;
 #[cfg(test)]
 mod tests;
@@ -120,6 +124,152 @@ pub fn decompress(
     Ok(raw_data)
 }
 
+/// Compresses the raw data stream using the given codec
+pub fn compress_with_codec(
+    raw_data: Vec<u8>,
+    codec: CompressionCodec,
+    client: CompressionClient,
+    max_bytes: usize,
+) -> Result<CompressedData, Error> {
+    // Compress the data using the codec
+    let raw_data_len = raw_data.len();
+    let compressed_data = match codec {
+        CompressionCodec::None => {
+            if raw_data.len() > max_bytes {
+                let error_string = format!(
+                    "Raw data size greater than max bytes limit: {}, max: {}",
+                    raw_data.len(),
+                    max_bytes
+                );
+                return create_compression_error(&client, error_string);
+            }
+            raw_data
+        },
+        CompressionCodec::Lz4 => compress(raw_data, client, max_bytes)?,
+        CompressionCodec::Zstd { level } => compress_zstd(raw_data, level, &client, max_bytes)?,
+    };
+
+    // Update the codec metrics
+    metrics::update_codec_metrics(
+        metrics::COMPRESS,
+        &client,
+        &codec,
+        raw_data_len,
+        compressed_data.len(),
+    );
+
+    Ok(compressed_data)
+}
+
+/// Decompresses the compressed data stream using the given codec
+pub fn decompress_with_codec(
+    compressed_data: &CompressedData,
+    codec: CompressionCodec,
+    client: CompressionClient,
+    max_size: usize,
+) -> Result<Vec<u8>, Error> {
+    // Decompress the data using the codec
+    let raw_data = match codec {
+        CompressionCodec::None => {
+            if compressed_data.len() > max_size {
+                let error_string = format!(
+                    "Raw data size greater than max size limit: {}, max: {}",
+                    compressed_data.len(),
+                    max_size
+                );
+                return create_decompression_error(&client, error_string);
+            }
+            compressed_data.clone()
+        },
+        CompressionCodec::Lz4 => decompress(compressed_data, client, max_size)?,
+        CompressionCodec::Zstd { .. } => decompress_zstd(compressed_data, &client, max_size)?,
+    };
+
+    // Update the codec metrics
+    metrics::update_codec_metrics(
+        metrics::DECOMPRESS,
+        &client,
+        &codec,
+        raw_data.len(),
+        compressed_data.len(),
+    );
+
+    Ok(raw_data)
+}
+
+/// Compresses the raw data using zstd (at the given level)
+fn compress_zstd(
+    raw_data: Vec<u8>,
+    level: i32,
+    client: &CompressionClient,
+    max_bytes: usize,
+) -> Result<CompressedData, Error> {
+    // Start the compression timer
+    let start_time = Instant::now();
+
+    // Ensure that the raw data size is not greater than the max bytes limit
+    if raw_data.len() > max_bytes {
+        let error_string = format!(
+            "Raw data size greater than max bytes limit: {}, max: {}",
+            raw_data.len(),
+            max_bytes
+        );
+        return create_compression_error(client, error_string);
+    }
+
+    // Compress the data
+    let compressed_data = match zstd::bulk::compress(&raw_data, level) {
+        Ok(compressed_data) => compressed_data,
+        Err(error) => {
+            let error_string = format!("Failed to compress the data using zstd: {}", error);
+            return create_compression_error(client, error_string);
+        },
+    };
+
+    // Ensure that the compressed data size is not greater than the max byte limit
+    if compressed_data.len() > max_bytes {
+        let error_string = format!(
+            "Compressed size greater than max bytes limit: {}, max: {}",
+            compressed_data.len(),
+            max_bytes
+        );
+        return create_compression_error(client, error_string);
+    }
+
+    // Stop the timer and update the metrics
+    metrics::observe_compression_operation_time(client, start_time);
+    metrics::update_compression_metrics(client, &raw_data, &compressed_data);
+
+    Ok(compressed_data)
+}
+
+/// Decompresses the compressed data using zstd. Note: the max size
+/// limits the capacity of the decompression buffer, so decompression
+/// fails if the raw data would exceed the limit.
+fn decompress_zstd(
+    compressed_data: &CompressedData,
+    client: &CompressionClient,
+    max_size: usize,
+) -> Result<Vec<u8>, Error> {
+    // Start the decompression timer
+    let start_time = Instant::now();
+
+    // Decompress the data
+    let raw_data = match zstd::bulk::decompress(compressed_data, max_size) {
+        Ok(raw_data) => raw_data,
+        Err(error) => {
+            let error_string = format!("Failed to decompress the data using zstd: {}", error);
+            return create_decompression_error(client, error_string);
+        },
+    };
+
+    // Stop the timer and update the metrics
+    metrics::observe_decompression_operation_time(client, start_time);
+    metrics::update_decompression_metrics(client, compressed_data, &raw_data);
+
+    Ok(raw_data)
+}
+
 /// A simple utility function that wraps the given error string in a compression error
 fn create_compression_error(
     client: &CompressionClient,
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::client::CompressionClient;
+use crate::{client::CompressionClient, codec::CompressionCodec};
 use aptos_metrics_core::{
     exponential_buckets, register_histogram_vec, register_int_counter_vec, HistogramVec,
     IntCounterVec,
@@ -25,6 +25,16 @@ pub static BYTE_COUNTS: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counters for tracking the data compression ratio of each codec (i.e., total byte counts)
+pub static CODEC_BYTE_COUNTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_compression_codec_byte_count",
+        "Counters for tracking the data compression ratio of each codec",
+        &["operation", "data_type", "client", "codec"]
+    )
+    .unwrap()
+});
+
 /// Counters for tracking compression/decompression errors
 pub static ERROR_COUNTS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -58,6 +68,21 @@ fn increment_compression_byte_count(
         .inc_by(byte_count)
 }
 
+/// Updates the codec byte counts for the given operation (e.g., raw and compressed data sizes)
+pub fn update_codec_metrics(
+    operation: &str,
+    client: &CompressionClient,
+    codec: &CompressionCodec,
+    raw_bytes: usize,
+    compressed_bytes: usize,
+) {
+    for (data_type, byte_count) in [(RAW_BYTES, raw_bytes), (COMPRESSED_BYTES, compressed_bytes)] {
+        CODEC_BYTE_COUNTS
+            .with_label_values(&[operation, data_type, client.get_label(), codec.get_label()])
+            .inc_by(byte_count as u64)
+    }
+}
+
 /// Increments the compression error count based on the given operation
 pub fn increment_compression_error(client: &CompressionClient) {
     increment_error_count(COMPRESS, client)
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::CompressionClient;
+use crate::{codec::CompressionCodec, CompressionClient};
 use aptos_crypto::{ed25519::Ed25519PrivateKey, hash::HashValue, PrivateKey, SigningKey, Uniform};
 use aptos_types::{
     account_address::AccountAddress,
@@ -74,6 +74,41 @@ fn test_compression_limits() {
     assert!(maybe_decompressed_bytes.is_err());
 }
 
+#[test]
+fn test_codec_compression() {
+    // Create test data
+    let transactions_with_proof = create_transaction_list_with_proof(1000, 1999, 1999, true);
+    let bcs_encoded_bytes = bcs::to_bytes(&transactions_with_proof).unwrap();
+
+    // Verify that every codec can compress and decompress the data
+    for codec in CompressionCodec::all() {
+        let compressed_bytes = crate::compress_with_codec(
+            bcs_encoded_bytes.clone(),
+            codec,
+            CompressionClient::StateSync,
+            MAX_COMPRESSION_SIZE,
+        )
+        .unwrap();
+        let decompressed_bytes = crate::decompress_with_codec(
+            &compressed_bytes,
+            codec,
+            CompressionClient::StateSync,
+            MAX_COMPRESSION_SIZE,
+        )
+        .unwrap();
+        assert_eq!(decompressed_bytes, bcs_encoded_bytes);
+
+        // Verify that decompression fails if the raw data exceeds the limit
+        let maybe_decompressed_bytes = crate::decompress_with_codec(
+            &compressed_bytes,
+            codec,
+            CompressionClient::StateSync,
+            bcs_encoded_bytes.len() - 1,
+        );
+        assert!(maybe_decompressed_bytes.is_err());
+    }
+}
+
 /// Ensures that the given object can be compressed and decompressed successfully
 /// when BCS encoded.
 fn test_compress_and_decompress<T: Debug + DeserializeOwned + PartialEq + Serialize>(object: T) {
@@ -27,7 +27,9 @@ use aptos_keygen::KeyGen;
 use aptos_logger::prelude::*;
 use aptos_types::{
     chain_id::ChainId,
-    on_chain_config::{Features, GasScheduleV2, OnChainConsensusConfig, OnChainExecutionConfig},
+    on_chain_config::{
+        FeatureFlagPreset, Features, GasScheduleV2, OnChainConsensusConfig, OnChainExecutionConfig,
+    },
     transaction::Transaction,
     waypoint::Waypoint,
 };
@@ -448,6 +450,7 @@ pub struct Builder {
     init_config: Option<InitConfigFn>,
     init_genesis_stake: Option<InitGenesisStakeFn>,
     init_genesis_config: Option<InitGenesisConfigFn>,
+    feature_flag_preset: Option<FeatureFlagPreset>,
 }
 
 impl Builder {
@@ -463,6 +466,7 @@ impl Builder {
             init_config: None,
             init_genesis_stake: None,
             init_genesis_config: None,
+            feature_flag_preset: None,
         })
     }
 
@@ -497,6 +501,16 @@ impl Builder {
         self
     }
 
+    /// Applies the given feature flag preset at genesis. Note: the genesis
+    /// config init function (if any) may still override the features.
+    pub fn with_feature_flag_preset(
+        mut self,
+        feature_flag_preset: Option<FeatureFlagPreset>,
+    ) -> Self {
+        self.feature_flag_preset = feature_flag_preset;
+        self
+    }
+
     /// Build all of the validators and save their configs
     pub fn build<R>(
         mut self,
@@ -649,7 +663,10 @@ impl Builder {
             consensus_config: OnChainConsensusConfig::default_for_genesis(),
             execution_config: OnChainExecutionConfig::default_for_genesis(),
             gas_schedule: default_gas_schedule(),
-            initial_features_override: None,
+            initial_features_override: self
+                .feature_flag_preset
+                .as_ref()
+                .map(FeatureFlagPreset::to_features),
         };
         if let Some(init_genesis_config) = &self.init_genesis_config {
             (init_genesis_config)(&mut genesis_config);
@@ -7,7 +7,7 @@ use aptos_types::{
     account_address::{AccountAddress, AccountAddressWithChecks},
     chain_id::ChainId,
     network_address::{DnsName, NetworkAddress, Protocol},
-    on_chain_config::{OnChainConsensusConfig, OnChainExecutionConfig},
+    on_chain_config::{FeatureFlagPresetConfig, OnChainConsensusConfig, OnChainExecutionConfig},
     transaction::authenticator::AuthenticationKey,
 };
 use aptos_vm_genesis::{AccountBalance, EmployeePool, Validator, ValidatorWithCommissionRate};
@@ -73,6 +73,10 @@ pub struct Layout {
     /// Onchain Execution Config
     #[serde(default = "OnChainExecutionConfig::default_for_genesis")]
     pub on_chain_execution_config: OnChainExecutionConfig,
+    /// Named set of feature flags to enable at genesis (instead of the defaults). Preset
+    /// files are read from the genesis repository.
+    #[serde(default)]
+    pub feature_flag_preset: Option<FeatureFlagPresetConfig>,
 }
 
 impl Layout {
@@ -112,6 +116,7 @@ impl Default for Layout {
             employee_vesting_period_duration: Some(5 * 60), // 5 minutes
             on_chain_consensus_config: OnChainConsensusConfig::default(),
             on_chain_execution_config: OnChainExecutionConfig::default_for_genesis(),
+            feature_flag_preset: None,
         }
     }
 }
@@ -3,5 +3,6 @@
 
 pub mod inspection_client;
 pub mod server;
+pub mod startup_progress;
 
 pub use server::*;
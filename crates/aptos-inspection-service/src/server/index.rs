@@ -3,7 +3,8 @@
 
 use crate::{
     server::utils::CONTENT_TYPE_TEXT, CONFIGURATION_PATH, FORGE_METRICS_PATH, JSON_METRICS_PATH,
-    METRICS_PATH, PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    METRICS_PATH, PEER_INFORMATION_PATH, STARTUP_PROGRESS_PATH, STREAM_AUDIT_LOGS_PATH,
+    STREAM_DIAGNOSTICS_PATH, SYNC_PROGRESS_PATH, SYSTEM_INFORMATION_PATH,
 };
 use hyper::{Body, StatusCode};
 
@@ -29,6 +30,10 @@ fn get_index_response() -> String {
     index_response.push(format!("\t- {}", JSON_METRICS_PATH));
     index_response.push(format!("\t- {}", METRICS_PATH));
     index_response.push(format!("\t- {}", PEER_INFORMATION_PATH));
+    index_response.push(format!("\t- {}", STARTUP_PROGRESS_PATH));
+    index_response.push(format!("\t- {}", STREAM_AUDIT_LOGS_PATH));
+    index_response.push(format!("\t- {}", STREAM_DIAGNOSTICS_PATH));
+    index_response.push(format!("\t- {}", SYNC_PROGRESS_PATH));
     index_response.push(format!("\t- {}", SYSTEM_INFORMATION_PATH));
 
     index_response.join("\n") // Separate each entry with a newline
@@ -6,13 +6,14 @@ use crate::server::{
     utils,
     utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT},
 };
+use aptos_config::config::NodeConfig;
 use hyper::{Body, StatusCode};
 use prometheus::TextEncoder;
 
 /// Handles a new forge metrics request
-pub fn handle_forge_metrics() -> (StatusCode, Body, String) {
+pub fn handle_forge_metrics(node_config: &NodeConfig) -> (StatusCode, Body, String) {
     // Get and encode the metrics
-    let metrics = utils::get_all_metrics();
+    let metrics = utils::get_all_metrics(&node_config.inspection_service);
     let encoded_metrics = match serde_json::to_string(&metrics) {
         Ok(encoded_metrics) => encoded_metrics,
         Err(error) => format!("Failed to get forge metrics! Error: {}", error),
@@ -26,13 +27,13 @@ pub fn handle_forge_metrics() -> (StatusCode, Body, String) {
 }
 
 /// Handles a new metrics request (with JSON encoding)
-pub fn handle_json_metrics_request() -> (StatusCode, Body, String) {
-    let buffer = utils::get_encoded_metrics(JsonEncoder);
+pub fn handle_json_metrics_request(node_config: &NodeConfig) -> (StatusCode, Body, String) {
+    let buffer = utils::get_encoded_metrics(&node_config.inspection_service, JsonEncoder);
     (StatusCode::OK, Body::from(buffer), CONTENT_TYPE_JSON.into())
 }
 
 /// Handles a new metrics request (with text encoding)
-pub fn handle_metrics_request() -> (StatusCode, Body, String) {
-    let buffer = utils::get_encoded_metrics(TextEncoder::new());
+pub fn handle_metrics_request(node_config: &NodeConfig) -> (StatusCode, Body, String) {
+    let buffer = utils::get_encoded_metrics(&node_config.inspection_service, TextEncoder::new());
     (StatusCode::OK, Body::from(buffer), CONTENT_TYPE_TEXT.into())
 }
@@ -1,11 +1,15 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::server::utils::CONTENT_TYPE_TEXT;
+use crate::{server::utils::CONTENT_TYPE_TEXT, startup_progress::StartupProgressReporter};
 use aptos_config::config::NodeConfig;
 use aptos_data_client::client::AptosDataClient;
+use aptos_data_streaming_service::{
+    stream_audit::StreamAuditLogs, stream_diagnostics::StreamDiagnostics,
+};
 use aptos_logger::debug;
 use aptos_network::application::storage::PeersAndMetadata;
+use aptos_state_sync_driver::sync_progress::SyncProgressReporter;
 use hyper::{
     service::{make_service_fn, service_fn},
     Body, Method, Request, Response, Server, StatusCode,
@@ -22,6 +26,10 @@ mod index;
 mod json_encoder;
 mod metrics;
 mod peer_information;
+mod startup_progress;
+mod stream_audit_logs;
+mod stream_diagnostics;
+mod sync_progress;
 mod system_information;
 pub mod utils;
 
@@ -35,6 +43,10 @@ pub const INDEX_PATH: &str = "/";
 pub const JSON_METRICS_PATH: &str = "/json_metrics";
 pub const METRICS_PATH: &str = "/metrics";
 pub const PEER_INFORMATION_PATH: &str = "/peer_information";
+pub const STARTUP_PROGRESS_PATH: &str = "/startup_progress";
+pub const STREAM_AUDIT_LOGS_PATH: &str = "/stream_audit_logs";
+pub const STREAM_DIAGNOSTICS_PATH: &str = "/stream_diagnostics";
+pub const SYNC_PROGRESS_PATH: &str = "/state_sync/progress";
 pub const SYSTEM_INFORMATION_PATH: &str = "/system_information";
 
 // Useful string constants
@@ -48,6 +60,10 @@ pub fn start_inspection_service(
     node_config: NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    stream_audit_logs: StreamAuditLogs,
+    stream_diagnostics: StreamDiagnostics,
+    sync_progress_reporter: SyncProgressReporter,
+    startup_progress_reporter: StartupProgressReporter,
 ) {
     // Fetch the service port and address
     let service_port = node_config.inspection_service.port;
@@ -75,6 +91,10 @@ pub fn start_inspection_service(
             let node_config = node_config.clone();
             let aptos_data_client = aptos_data_client.clone();
             let peers_and_metadata = peers_and_metadata.clone();
+            let stream_audit_logs = stream_audit_logs.clone();
+            let stream_diagnostics = stream_diagnostics.clone();
+            let sync_progress_reporter = sync_progress_reporter.clone();
+            let startup_progress_reporter = startup_progress_reporter.clone();
             async move {
                 Ok::<_, Infallible>(service_fn(move |request| {
                     serve_requests(
@@ -82,6 +102,10 @@ pub fn start_inspection_service(
                         node_config.clone(),
                         aptos_data_client.clone(),
                         peers_and_metadata.clone(),
+                        stream_audit_logs.clone(),
+                        stream_diagnostics.clone(),
+                        sync_progress_reporter.clone(),
+                        startup_progress_reporter.clone(),
                     )
                 }))
             }
@@ -103,6 +127,10 @@ async fn serve_requests(
     node_config: NodeConfig,
     aptos_data_client: AptosDataClient,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    stream_audit_logs: StreamAuditLogs,
+    stream_diagnostics: StreamDiagnostics,
+    sync_progress_reporter: SyncProgressReporter,
+    startup_progress_reporter: StartupProgressReporter,
 ) -> Result<Response<Body>, hyper::Error> {
     // Process the request and get the response components
     let (status_code, body, content_type) = match req.uri().path() {
@@ -114,7 +142,7 @@ async fn serve_requests(
         FORGE_METRICS_PATH => {
             // /forge_metrics
             // Exposes forge encoded metrics
-            metrics::handle_forge_metrics()
+            metrics::handle_forge_metrics(&node_config)
         },
         INDEX_PATH => {
             // /
@@ -124,12 +152,12 @@ async fn serve_requests(
         JSON_METRICS_PATH => {
             // /json_metrics
             // Exposes JSON encoded metrics
-            metrics::handle_json_metrics_request()
+            metrics::handle_json_metrics_request(&node_config)
         },
         METRICS_PATH => {
             // /metrics
             // Exposes text encoded metrics
-            metrics::handle_metrics_request()
+            metrics::handle_metrics_request(&node_config)
         },
         PEER_INFORMATION_PATH => {
             // /peer_information
@@ -140,6 +168,29 @@ async fn serve_requests(
                 peers_and_metadata,
             )
         },
+        STARTUP_PROGRESS_PATH => {
+            // /startup_progress
+            // Exposes the current node startup progress
+            startup_progress::handle_startup_progress_request(
+                &node_config,
+                startup_progress_reporter,
+            )
+        },
+        STREAM_AUDIT_LOGS_PATH => {
+            // /stream_audit_logs
+            // Exposes the audit logs of the most recent data streams
+            stream_audit_logs::handle_stream_audit_logs_request(&node_config, stream_audit_logs)
+        },
+        STREAM_DIAGNOSTICS_PATH => {
+            // /stream_diagnostics
+            // Exposes the live state of all active data streams
+            stream_diagnostics::handle_stream_diagnostics_request(&node_config, stream_diagnostics)
+        },
+        SYNC_PROGRESS_PATH => {
+            // /state_sync/progress
+            // Exposes the current state sync progress
+            sync_progress::handle_sync_progress_request(&node_config, sync_progress_reporter)
+        },
         SYSTEM_INFORMATION_PATH => {
             // /system_information
             // Exposes the system and build information
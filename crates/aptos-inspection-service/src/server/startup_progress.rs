@@ -0,0 +1,42 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT},
+    startup_progress::StartupProgressReporter,
+};
+use aptos_config::config::NodeConfig;
+use hyper::{Body, StatusCode};
+
+// The message to display when the startup progress endpoint is disabled
+pub const STARTUP_PROGRESS_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_startup_progress: true";
+
+/// Handles a new startup progress request
+pub fn handle_startup_progress_request(
+    node_config: &NodeConfig,
+    startup_progress_reporter: StartupProgressReporter,
+) -> (StatusCode, Body, String) {
+    // Only return the startup progress if the endpoint is enabled
+    if node_config.inspection_service.expose_startup_progress {
+        (
+            StatusCode::OK,
+            Body::from(get_startup_progress_json(startup_progress_reporter)),
+            CONTENT_TYPE_JSON.into(),
+        )
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            Body::from(STARTUP_PROGRESS_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        )
+    }
+}
+
+/// Returns a JSON formatted string with the current node startup progress
+fn get_startup_progress_json(startup_progress_reporter: StartupProgressReporter) -> String {
+    match serde_json::to_string_pretty(&startup_progress_reporter.get_startup_progress()) {
+        Ok(startup_progress) => startup_progress,
+        Err(error) => format!("Failed to get the startup progress! Error: {}", error),
+    }
+}
@@ -0,0 +1,40 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_config::config::NodeConfig;
+use aptos_data_streaming_service::stream_audit::StreamAuditLogs;
+use hyper::{Body, StatusCode};
+
+// The message to display when the stream audit logs endpoint is disabled
+pub const STREAM_AUDIT_LOGS_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_stream_audit_logs: true";
+
+/// Handles a new stream audit logs request
+pub fn handle_stream_audit_logs_request(
+    node_config: &NodeConfig,
+    stream_audit_logs: StreamAuditLogs,
+) -> (StatusCode, Body, String) {
+    // Only return the stream audit logs if the endpoint is enabled
+    if node_config.inspection_service.expose_stream_audit_logs {
+        (
+            StatusCode::OK,
+            Body::from(get_stream_audit_logs_json(stream_audit_logs)),
+            CONTENT_TYPE_JSON.into(),
+        )
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            Body::from(STREAM_AUDIT_LOGS_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        )
+    }
+}
+
+/// Returns a JSON formatted string with the audit logs of the most recent data streams
+fn get_stream_audit_logs_json(stream_audit_logs: StreamAuditLogs) -> String {
+    match serde_json::to_string_pretty(&stream_audit_logs.get_audit_logs()) {
+        Ok(stream_audit_logs) => stream_audit_logs,
+        Err(error) => format!("Failed to get the stream audit logs! Error: {}", error),
+    }
+}
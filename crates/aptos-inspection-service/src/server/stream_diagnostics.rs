@@ -0,0 +1,40 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_config::config::NodeConfig;
+use aptos_data_streaming_service::stream_diagnostics::StreamDiagnostics;
+use hyper::{Body, StatusCode};
+
+// The message to display when the stream diagnostics endpoint is disabled
+pub const STREAM_DIAGNOSTICS_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_stream_diagnostics: true";
+
+/// Handles a new stream diagnostics request
+pub fn handle_stream_diagnostics_request(
+    node_config: &NodeConfig,
+    stream_diagnostics: StreamDiagnostics,
+) -> (StatusCode, Body, String) {
+    // Only return the stream diagnostics if the endpoint is enabled
+    if node_config.inspection_service.expose_stream_diagnostics {
+        (
+            StatusCode::OK,
+            Body::from(get_stream_diagnostics_json(stream_diagnostics)),
+            CONTENT_TYPE_JSON.into(),
+        )
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            Body::from(STREAM_DIAGNOSTICS_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        )
+    }
+}
+
+/// Returns a JSON formatted string with the diagnostics of all active data streams
+fn get_stream_diagnostics_json(stream_diagnostics: StreamDiagnostics) -> String {
+    match serde_json::to_string_pretty(&stream_diagnostics.get_stream_diagnostics()) {
+        Ok(stream_diagnostics) => stream_diagnostics,
+        Err(error) => format!("Failed to get the stream diagnostics! Error: {}", error),
+    }
+}
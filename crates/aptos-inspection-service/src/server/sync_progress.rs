@@ -0,0 +1,40 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::server::utils::{CONTENT_TYPE_JSON, CONTENT_TYPE_TEXT};
+use aptos_config::config::NodeConfig;
+use aptos_state_sync_driver::sync_progress::SyncProgressReporter;
+use hyper::{Body, StatusCode};
+
+// The message to display when the sync progress endpoint is disabled
+pub const SYNC_PROGRESS_DISABLED_MESSAGE: &str =
+    "This endpoint is disabled! Enable it in the node config at inspection_service.expose_sync_progress: true";
+
+/// Handles a new sync progress request
+pub fn handle_sync_progress_request(
+    node_config: &NodeConfig,
+    sync_progress_reporter: SyncProgressReporter,
+) -> (StatusCode, Body, String) {
+    // Only return the sync progress if the endpoint is enabled
+    if node_config.inspection_service.expose_sync_progress {
+        (
+            StatusCode::OK,
+            Body::from(get_sync_progress_json(sync_progress_reporter)),
+            CONTENT_TYPE_JSON.into(),
+        )
+    } else {
+        (
+            StatusCode::FORBIDDEN,
+            Body::from(SYNC_PROGRESS_DISABLED_MESSAGE),
+            CONTENT_TYPE_TEXT.into(),
+        )
+    }
+}
+
+/// Returns a JSON formatted string with the current state sync progress
+fn get_sync_progress_json(sync_progress_reporter: SyncProgressReporter) -> String {
+    match serde_json::to_string_pretty(&sync_progress_reporter.get_sync_progress()) {
+        Ok(sync_progress) => sync_progress,
+        Err(error) => format!("Failed to get the sync progress! Error: {}", error),
+    }
+}
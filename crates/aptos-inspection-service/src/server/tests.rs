@@ -4,23 +4,43 @@
 use crate::{
     server::{
         configuration::CONFIGURATION_DISABLED_MESSAGE,
-        peer_information::PEER_INFO_DISABLED_MESSAGE, serve_requests,
-        system_information::SYS_INFO_DISABLED_MESSAGE, utils::get_all_metrics,
+        peer_information::PEER_INFO_DISABLED_MESSAGE,
+        serve_requests,
+        startup_progress::STARTUP_PROGRESS_DISABLED_MESSAGE,
+        stream_audit_logs::STREAM_AUDIT_LOGS_DISABLED_MESSAGE,
+        stream_diagnostics::STREAM_DIAGNOSTICS_DISABLED_MESSAGE,
+        sync_progress::SYNC_PROGRESS_DISABLED_MESSAGE,
+        system_information::SYS_INFO_DISABLED_MESSAGE,
+        utils::{filter_metric_families, get_all_metrics},
     },
+    startup_progress::{StartupPhase, StartupPhaseStatus, StartupProgressReporter},
     CONFIGURATION_PATH, FORGE_METRICS_PATH, INDEX_PATH, JSON_METRICS_PATH, METRICS_PATH,
-    PEER_INFORMATION_PATH, SYSTEM_INFORMATION_PATH,
+    PEER_INFORMATION_PATH, STARTUP_PROGRESS_PATH, STREAM_AUDIT_LOGS_PATH, STREAM_DIAGNOSTICS_PATH,
+    SYNC_PROGRESS_PATH, SYSTEM_INFORMATION_PATH,
+};
+use aptos_config::config::{
+    AptosDataClientConfig, BaseConfig, DataStreamingServiceConfig, InspectionServiceConfig,
+    NodeConfig, TrustedPeerOnlyConfig,
 };
-use aptos_config::config::{AptosDataClientConfig, BaseConfig, NodeConfig};
 use aptos_data_client::client::AptosDataClient;
+use aptos_data_streaming_service::{
+    stream_audit::StreamAuditLogs,
+    stream_diagnostics::{DataStreamDiagnostics, StreamDiagnostics, StreamProgress},
+    streaming_client::{GetAllStatesRequest, StreamRequest},
+};
 use aptos_network::application::{interface::NetworkClient, storage::PeersAndMetadata};
+use aptos_state_sync_driver::sync_progress::SyncProgressReporter;
 use aptos_storage_interface::DbReader;
 use aptos_storage_service_client::StorageServiceClient;
+use aptos_storage_service_types::bandwidth::BandwidthBudget;
 use aptos_time_service::TimeService;
 use assert_approx_eq::assert_approx_eq;
 use futures::executor::block_on;
 use hyper::{body, Body, Method, Request, Response, StatusCode};
 use once_cell::sync::Lazy;
-use prometheus::{proto::MetricFamily, register_int_counter, Counter, IntCounter, Opts, Registry};
+use prometheus::{
+    proto::MetricFamily, register_int_counter, Counter, IntCounter, IntCounterVec, Opts, Registry,
+};
 use rusty_fork::rusty_fork_test;
 use std::{collections::HashMap, io::read_to_string, string::String, sync::Arc};
 
@@ -90,6 +110,7 @@ async fn test_inspect_index() {
     assert!(response_body_string.contains(JSON_METRICS_PATH));
     assert!(response_body_string.contains(METRICS_PATH));
     assert!(response_body_string.contains(PEER_INFORMATION_PATH));
+    assert!(response_body_string.contains(STARTUP_PROGRESS_PATH));
     assert!(response_body_string.contains(SYSTEM_INFORMATION_PATH));
 }
 
@@ -180,6 +201,168 @@ async fn test_inspect_peer_information() {
     assert!(response_body_string.contains("State sync metadata"));
 }
 
+#[tokio::test]
+async fn test_inspect_startup_progress() {
+    // Create a validator node config
+    let mut config = NodeConfig::get_default_validator_config();
+
+    // Disable the startup progress endpoint and ping it
+    config.inspection_service.expose_startup_progress = false;
+    let mut response = send_get_request_to_path(&config, STARTUP_PROGRESS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, STARTUP_PROGRESS_DISABLED_MESSAGE);
+
+    // Enable the startup progress endpoint and ping it
+    config.inspection_service.expose_startup_progress = true;
+    let mut response = send_get_request_to_path(&config, STARTUP_PROGRESS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the response contains the expected information
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response_body_string.contains("\"current_phase\": \"state_sync_bootstrapping\""));
+    assert!(response_body_string.contains("\"startup_complete\": false"));
+    assert!(response_body_string.contains("\"status\": \"complete\""));
+}
+
+#[test]
+fn test_startup_progress_reporter() {
+    // Create a startup progress reporter
+    let startup_progress_reporter = StartupProgressReporter::new(TimeService::mock());
+
+    // Verify that no phases have started
+    let startup_progress = startup_progress_reporter.get_startup_progress();
+    assert_eq!(startup_progress.current_phase, None);
+    assert!(!startup_progress.startup_complete);
+    for phase_progress in startup_progress.phases {
+        assert_eq!(phase_progress.status, StartupPhaseStatus::Pending);
+    }
+
+    // Move through several phases and verify the current phase
+    startup_progress_reporter.start_phase(StartupPhase::OpenStorage);
+    assert_eq!(
+        startup_progress_reporter
+            .get_startup_progress()
+            .current_phase,
+        Some(StartupPhase::OpenStorage)
+    );
+    startup_progress_reporter.complete_phase(StartupPhase::OpenStorage);
+    startup_progress_reporter.start_phase(StartupPhase::StateSyncBootstrapping);
+    startup_progress_reporter.start_phase(StartupPhase::StartApi);
+    assert_eq!(
+        startup_progress_reporter
+            .get_startup_progress()
+            .current_phase,
+        Some(StartupPhase::StateSyncBootstrapping)
+    );
+    startup_progress_reporter.complete_phase(StartupPhase::StateSyncBootstrapping);
+    assert_eq!(
+        startup_progress_reporter
+            .get_startup_progress()
+            .current_phase,
+        Some(StartupPhase::StartApi)
+    );
+
+    // Complete the remaining phases and verify that startup is complete
+    startup_progress_reporter.complete_phase(StartupPhase::StartApi);
+    startup_progress_reporter.skip_phase(StartupPhase::StartConsensus);
+    let startup_progress = startup_progress_reporter.get_startup_progress();
+    assert_eq!(startup_progress.current_phase, None);
+    assert!(startup_progress.startup_complete);
+    for phase_progress in startup_progress.phases {
+        if phase_progress.phase == StartupPhase::StartConsensus {
+            assert_eq!(phase_progress.status, StartupPhaseStatus::Skipped);
+        } else {
+            assert_eq!(phase_progress.status, StartupPhaseStatus::Complete);
+            assert!(phase_progress.started_at_usecs.is_some());
+            assert!(phase_progress.completed_at_usecs.is_some());
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_inspect_stream_audit_logs() {
+    // Create a validator node config
+    let mut config = NodeConfig::get_default_validator_config();
+
+    // Disable the stream audit logs endpoint and ping it
+    config.inspection_service.expose_stream_audit_logs = false;
+    let mut response = send_get_request_to_path(&config, STREAM_AUDIT_LOGS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, STREAM_AUDIT_LOGS_DISABLED_MESSAGE);
+
+    // Enable the stream audit logs endpoint and ping it
+    config.inspection_service.expose_stream_audit_logs = true;
+    let mut response = send_get_request_to_path(&config, STREAM_AUDIT_LOGS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the response contains the expected information
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response_body_string.contains("data_stream_id"));
+    assert!(response_body_string.contains("GetAllStates"));
+    assert!(response_body_string.contains("end_of_stream"));
+}
+
+#[tokio::test]
+async fn test_inspect_stream_diagnostics() {
+    // Create a validator node config
+    let mut config = NodeConfig::get_default_validator_config();
+
+    // Disable the stream diagnostics endpoint and ping it
+    config.inspection_service.expose_stream_diagnostics = false;
+    let mut response = send_get_request_to_path(&config, STREAM_DIAGNOSTICS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, STREAM_DIAGNOSTICS_DISABLED_MESSAGE);
+
+    // Enable the stream diagnostics endpoint and ping it
+    config.inspection_service.expose_stream_diagnostics = true;
+    let mut response = send_get_request_to_path(&config, STREAM_DIAGNOSTICS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the response contains the expected information
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response_body_string.contains("\"data_stream_id\": 0"));
+    assert!(response_body_string.contains("\"target_version\": 100"));
+    assert!(response_body_string.contains("\"request_failure_count\": 2"));
+}
+
+#[tokio::test]
+async fn test_inspect_sync_progress() {
+    // Create a validator node config
+    let mut config = NodeConfig::get_default_validator_config();
+
+    // Disable the sync progress endpoint and ping it
+    config.inspection_service.expose_sync_progress = false;
+    let mut response = send_get_request_to_path(&config, SYNC_PROGRESS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+
+    // Verify that the response contains an error
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert_eq!(response_body, SYNC_PROGRESS_DISABLED_MESSAGE);
+
+    // Enable the sync progress endpoint and ping it
+    config.inspection_service.expose_sync_progress = true;
+    let mut response = send_get_request_to_path(&config, SYNC_PROGRESS_PATH).await;
+    let response_body = body::to_bytes(response.body_mut()).await.unwrap();
+    let response_body_string = read_to_string(response_body.as_ref()).unwrap();
+
+    // Verify that the response contains the expected information
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response_body_string.contains("\"synced_version\": 100"));
+    assert!(response_body_string.contains("\"highest_known_version\": 200"));
+}
+
 rusty_fork_test! {
 #[test]
 fn test_gather_metrics() {
@@ -190,11 +373,11 @@ fn test_gather_metrics() {
     }
 
     // Fetch the metrics and verify that a new entry was added
-    let all_metrics = get_all_metrics();
+    let all_metrics = get_all_metrics(&InspectionServiceConfig::default());
     assert_eq!(all_metrics.len(), 1);
 
     // Verify that the counter has the expected value
-    for (metric, value) in get_all_metrics() {
+    for (metric, value) in get_all_metrics(&InspectionServiceConfig::default()) {
         if metric.starts_with(INT_COUNTER_NAME) {
             assert_eq!(value, iterations.to_string());
             return;
@@ -211,7 +394,7 @@ fn test_get_all_metrics() {
     INT_COUNTER.inc();
 
     // Verify that the metrics map only has one entry
-    let metrics = get_all_metrics();
+    let metrics = get_all_metrics(&InspectionServiceConfig::default());
     assert_eq!(metrics.len(), 1);
 
     // Verify that the counter has the expected value
@@ -220,6 +403,42 @@ fn test_get_all_metrics() {
 }
 }
 
+#[test]
+fn test_filter_metric_families() {
+    // Create and register several counter metrics (each with multiple labels)
+    let registry = Registry::new();
+    for counter_name in ["allowed_counter", "allowed_denied_counter", "other_counter"] {
+        let counter_opts = Opts::new(counter_name, "test counter help");
+        let counter = IntCounterVec::new(counter_opts, &["label"]).unwrap();
+        registry.register(Box::new(counter.clone())).unwrap();
+        for label in 0..10 {
+            counter.with_label_values(&[&label.to_string()]).inc();
+        }
+    }
+
+    // Filter the metric families and verify that nothing is removed by default
+    let metric_families = registry.gather();
+    let filtered_metric_families =
+        filter_metric_families(&InspectionServiceConfig::default(), metric_families.clone());
+    assert_eq!(filtered_metric_families, metric_families);
+
+    // Create an inspection service config with an allowlist, denylist and cardinality limit
+    let inspection_service_config = InspectionServiceConfig {
+        max_metrics_per_family: Some(4),
+        metrics_allowlist: vec!["allowed".into()],
+        metrics_denylist: vec!["allowed_denied".into()],
+        ..Default::default()
+    };
+
+    // Filter the metric families and verify only the allowed family remains (with a capped size)
+    let filtered_metric_families =
+        filter_metric_families(&inspection_service_config, metric_families);
+    assert_eq!(filtered_metric_families.len(), 1);
+    let metric_family = filtered_metric_families.first().unwrap();
+    assert_eq!(metric_family.get_name(), "allowed_counter");
+    assert_eq!(metric_family.get_metric().len(), 4);
+}
+
 #[test]
 fn test_publish_metrics() {
     // Create a counter metric
@@ -257,15 +476,67 @@ async fn send_get_request_to_path(config: &NodeConfig, endpoint: &str) -> Respon
     // Create the data client
     let network_client =
         NetworkClient::new(vec![], vec![], HashMap::new(), peers_and_metadata.clone());
+    let data_client_config = AptosDataClientConfig::default();
+    let bandwidth_budget = Arc::new(BandwidthBudget::new(
+        data_client_config.data_bandwidth_config,
+        TimeService::mock(),
+    ));
     let (aptos_data_client, _) = AptosDataClient::new(
-        AptosDataClientConfig::default(),
+        data_client_config,
         BaseConfig::default(),
         TimeService::mock(),
         Arc::new(MockDatabaseReader {}),
         StorageServiceClient::new(network_client),
         None,
+        bandwidth_budget,
+        TrustedPeerOnlyConfig::default(),
     );
 
+    // Create the stream audit logs (with a single terminated stream)
+    let stream_audit_logs =
+        StreamAuditLogs::new(DataStreamingServiceConfig::default(), TimeService::mock());
+    let stream_request = StreamRequest::GetAllStates(GetAllStatesRequest {
+        version: 100,
+        start_index: 0,
+    });
+    stream_audit_logs
+        .create_stream_auditor(0, &stream_request)
+        .record_stream_terminated("end_of_stream");
+
+    // Create the stream diagnostics (with a single active stream)
+    let stream_diagnostics = StreamDiagnostics::new();
+    stream_diagnostics.update_stream_diagnostics(vec![DataStreamDiagnostics {
+        data_stream_id: 0,
+        stream_request: format!("{:?}", stream_request),
+        stream_progress: StreamProgress {
+            target_version: Some(100),
+            next_request_index: 0,
+            next_stream_index: 0,
+            end_index: None,
+            stream_is_complete: false,
+        },
+        num_in_flight_requests: 0,
+        requests_per_peer: Default::default(),
+        sent_requests: vec![],
+        request_failure_count: 2,
+        max_request_retry: 5,
+        max_concurrent_requests: 1,
+        num_stream_failovers: 0,
+        primary_peer: None,
+        last_error: None,
+    }]);
+
+    // Create the sync progress reporter
+    let sync_progress_reporter = SyncProgressReporter::new(TimeService::mock());
+    sync_progress_reporter.update_synced_version(100);
+    sync_progress_reporter.update_highest_known_version(200);
+
+    // Create the startup progress reporter (with storage opened)
+    let startup_progress_reporter = StartupProgressReporter::new(TimeService::mock());
+    startup_progress_reporter.start_phase(StartupPhase::OpenStorage);
+    startup_progress_reporter.complete_phase(StartupPhase::OpenStorage);
+    startup_progress_reporter.start_phase(StartupPhase::StateSyncBootstrapping);
+
     // Serve the request
     serve_requests(
         Request::builder()
@@ -276,6 +547,10 @@ async fn send_get_request_to_path(config: &NodeConfig, endpoint: &str) -> Respon
         config.clone(),
         aptos_data_client,
         peers_and_metadata,
+        stream_audit_logs,
+        stream_diagnostics,
+        sync_progress_reporter,
+        startup_progress_reporter,
     )
     .await
     .unwrap()
@@ -1,6 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use aptos_config::config::InspectionServiceConfig;
 use aptos_logger::{error, warn};
 use aptos_metrics_core::{register_int_counter_vec, IntCounterVec};
 use once_cell::sync::Lazy;
@@ -22,16 +23,26 @@ pub static NUM_METRICS: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
-/// A simple utility function that returns all metrics as a HashMap
-pub fn get_all_metrics() -> HashMap<String, String> {
-    let metric_families = get_metric_families();
+/// Counter for the number of metrics dropped by the cardinality guard (by family)
+pub static NUM_METRICS_OVER_CARDINALITY_LIMIT: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_metrics_over_cardinality_limit",
+        "Number of metrics dropped because the family exceeded the cardinality limit",
+        &["metric_family"]
+    )
+    .unwrap()
+});
+
+/// A simple utility function that returns all exposed metrics as a HashMap
+pub fn get_all_metrics(config: &InspectionServiceConfig) -> HashMap<String, String> {
+    let metric_families = filter_metric_families(config, get_metric_families());
     get_metrics_map(metric_families)
 }
 
-/// A simple utility function that encodes the metrics using the given encoder
-pub fn get_encoded_metrics(encoder: impl Encoder) -> Vec<u8> {
-    // Gather and encode the metrics
-    let metric_families = get_metric_families();
+/// A simple utility function that encodes the exposed metrics using the given encoder
+pub fn get_encoded_metrics(config: &InspectionServiceConfig, encoder: impl Encoder) -> Vec<u8> {
+    // Gather, filter and encode the metrics
+    let metric_families = filter_metric_families(config, get_metric_families());
     let mut encoded_buffer = vec![];
     if let Err(error) = encoder.encode(&metric_families, &mut encoded_buffer) {
         error!("Failed to encode metrics! Error: {}", error);
@@ -78,6 +89,47 @@ fn get_metric_families() -> Vec<MetricFamily> {
     metric_families
 }
 
+/// Filters the given metric families using the configured allow and deny
+/// lists, and caps the number of metrics (i.e., label combinations) in each
+/// family. Any metrics over the cap are dropped and counted by family.
+pub fn filter_metric_families(
+    config: &InspectionServiceConfig,
+    metric_families: Vec<MetricFamily>,
+) -> Vec<MetricFamily> {
+    let mut filtered_metric_families = vec![];
+    let mut num_filtered_families: u64 = 0;
+
+    for mut metric_family in metric_families {
+        // Skip the metric family if it isn't exposed
+        if !config.is_metric_family_exposed(metric_family.get_name()) {
+            num_filtered_families = num_filtered_families.saturating_add(1);
+            continue;
+        }
+
+        // Enforce the cardinality limit for the metric family
+        if let Some(max_metrics_per_family) = config.max_metrics_per_family {
+            let num_metrics = metric_family.get_metric().len() as u64;
+            if num_metrics > max_metrics_per_family {
+                NUM_METRICS_OVER_CARDINALITY_LIMIT
+                    .with_label_values(&[metric_family.get_name()])
+                    .inc_by(num_metrics - max_metrics_per_family);
+                metric_family
+                    .mut_metric()
+                    .truncate(max_metrics_per_family as usize);
+            }
+        }
+
+        filtered_metric_families.push(metric_family);
+    }
+
+    // This metric will be reported on the next pull, rather than create a new family
+    NUM_METRICS
+        .with_label_values(&["filtered_families"])
+        .inc_by(num_filtered_families);
+
+    filtered_metric_families
+}
+
 /// A simple utility function that parses and collects all metrics
 /// associated with the given families.
 fn get_metrics_map(metric_families: Vec<MetricFamily>) -> HashMap<String, String> {
@@ -0,0 +1,165 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_infallible::RwLock;
+use aptos_logger::info;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use serde::Serialize;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// The named phases of node startup (in the order they are reported)
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupPhase {
+    OpenStorage,            // Opening the storage database (and any checkpoints)
+    StateSyncBootstrapping, // Catching up to the network via state sync bootstrapping
+    StartConsensus,         // Starting consensus (validators only)
+    StartApi,               // Starting the API (and indexers)
+}
+
+impl StartupPhase {
+    /// Returns all startup phases (in the order they are reported)
+    pub fn all_phases() -> [StartupPhase; 4] {
+        [
+            StartupPhase::OpenStorage,
+            StartupPhase::StateSyncBootstrapping,
+            StartupPhase::StartConsensus,
+            StartupPhase::StartApi,
+        ]
+    }
+}
+
+/// The status of a single startup phase
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StartupPhaseStatus {
+    #[default]
+    Pending, // The phase has not yet started
+    InProgress, // The phase has started but not yet completed
+    Complete,   // The phase has completed
+    Skipped,    // The phase is not required by the node (e.g., consensus for fullnodes)
+}
+
+/// The progress of a single startup phase (timestamps are unix times in microseconds)
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct StartupPhaseProgress {
+    pub phase: StartupPhase,
+    pub status: StartupPhaseStatus,
+    pub started_at_usecs: Option<u64>,
+    pub completed_at_usecs: Option<u64>,
+}
+
+impl StartupPhaseProgress {
+    fn new(phase: StartupPhase) -> Self {
+        Self {
+            phase,
+            status: StartupPhaseStatus::Pending,
+            started_at_usecs: None,
+            completed_at_usecs: None,
+        }
+    }
+}
+
+/// A snapshot of the current node startup progress (e.g., for orchestration systems)
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct StartupProgress {
+    pub current_phase: Option<StartupPhase>,
+    pub startup_complete: bool,
+    pub startup_began_at_usecs: u64,
+    pub current_time_usecs: u64,
+    pub phases: Vec<StartupPhaseProgress>,
+}
+
+/// A simple reporter that tracks the progress of node startup. The reporter
+/// is updated as the node moves through each startup phase and can be queried
+/// (read-only) by the node inspection service. This allows operators to
+/// distinguish a slow startup from a hung node.
+#[derive(Clone)]
+pub struct StartupProgressReporter {
+    phases: Arc<RwLock<BTreeMap<StartupPhase, StartupPhaseProgress>>>,
+    startup_began_at_usecs: u64,
+    time_service: TimeService,
+}
+
+impl StartupProgressReporter {
+    pub fn new(time_service: TimeService) -> Self {
+        let phases = StartupPhase::all_phases()
+            .into_iter()
+            .map(|phase| (phase, StartupPhaseProgress::new(phase)))
+            .collect();
+        let startup_began_at_usecs = time_service.now_unix_time().as_micros() as u64;
+
+        Self {
+            phases: Arc::new(RwLock::new(phases)),
+            startup_began_at_usecs,
+            time_service,
+        }
+    }
+
+    /// Marks the given phase as started
+    pub fn start_phase(&self, phase: StartupPhase) {
+        let now_usecs = self.get_current_time_usecs();
+        if let Some(phase_progress) = self.phases.write().get_mut(&phase) {
+            phase_progress.status = StartupPhaseStatus::InProgress;
+            phase_progress.started_at_usecs = Some(now_usecs);
+        }
+        info!("Startup phase {:?} has started!", phase);
+    }
+
+    /// Marks the given phase as complete
+    pub fn complete_phase(&self, phase: StartupPhase) {
+        let now_usecs = self.get_current_time_usecs();
+        if let Some(phase_progress) = self.phases.write().get_mut(&phase) {
+            phase_progress.status = StartupPhaseStatus::Complete;
+            phase_progress.completed_at_usecs = Some(now_usecs);
+
+            // Log the time taken to complete the phase
+            let started_at_usecs = phase_progress.started_at_usecs.unwrap_or(now_usecs);
+            info!(
+                "Startup phase {:?} has completed! Time taken (ms): {}",
+                phase,
+                now_usecs.saturating_sub(started_at_usecs) / 1000
+            );
+        }
+    }
+
+    /// Marks the given phase as skipped (i.e., it is not required by the node)
+    pub fn skip_phase(&self, phase: StartupPhase) {
+        if let Some(phase_progress) = self.phases.write().get_mut(&phase) {
+            phase_progress.status = StartupPhaseStatus::Skipped;
+        }
+        info!("Startup phase {:?} has been skipped!", phase);
+    }
+
+    /// Returns a snapshot of the current startup progress
+    pub fn get_startup_progress(&self) -> StartupProgress {
+        let phases: Vec<_> = self.phases.read().values().cloned().collect();
+
+        // The current phase is the first phase that is still in progress
+        let current_phase = phases
+            .iter()
+            .find(|phase_progress| phase_progress.status == StartupPhaseStatus::InProgress)
+            .map(|phase_progress| phase_progress.phase);
+
+        // Startup is complete once all phases are complete (or skipped)
+        let startup_complete = phases.iter().all(|phase_progress| {
+            matches!(
+                phase_progress.status,
+                StartupPhaseStatus::Complete | StartupPhaseStatus::Skipped
+            )
+        });
+
+        StartupProgress {
+            current_phase,
+            startup_complete,
+            startup_began_at_usecs: self.startup_began_at_usecs,
+            current_time_usecs: self.get_current_time_usecs(),
+            phases,
+        }
+    }
+
+    /// Returns the current unix time (in microseconds)
+    fn get_current_time_usecs(&self) -> u64 {
+        self.time_service.now_unix_time().as_micros() as u64
+    }
+}
@@ -0,0 +1,280 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Expensive debug features that can be toggled at runtime (e.g., through the
+//! admin service). Each toggle is enabled with a deadline, after which it
+//! automatically reverts, so that expensive features are never left on by
+//! accident. Checking a disabled toggle only requires a single atomic load.
+
+use aptos_infallible::Mutex;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::{
+    collections::HashSet,
+    fmt::{Debug, Display},
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+/// Traces the execution of a sampled subset of transactions
+pub static EXECUTION_TRACING: Lazy<DebugToggle<ExecutionTracing>> =
+    Lazy::new(|| DebugToggle::new("execution_tracing"));
+
+/// Emits verbose consensus logs for a number of rounds
+pub static VERBOSE_CONSENSUS_LOGGING: Lazy<DebugToggle<VerboseConsensusLogging>> =
+    Lazy::new(|| DebugToggle::new("verbose_consensus_logging"));
+
+/// Dumps all network messages exchanged with a set of peers
+pub static NETWORK_MESSAGE_DUMP: Lazy<DebugToggle<NetworkMessageDump>> =
+    Lazy::new(|| DebugToggle::new("network_message_dump"));
+
+/// The state of the execution tracing toggle
+#[derive(Clone, Debug)]
+pub struct ExecutionTracing {
+    sample_frequency: u64, // One out of every `sample_frequency` transactions is traced
+    num_transactions_seen: u64,
+}
+
+impl ExecutionTracing {
+    pub fn new(sample_frequency: u64) -> Self {
+        Self {
+            sample_frequency: sample_frequency.max(1),
+            num_transactions_seen: 0,
+        }
+    }
+}
+
+/// The state of the verbose consensus logging toggle
+#[derive(Clone, Debug)]
+pub struct VerboseConsensusLogging {
+    num_rounds: u64,
+    end_round: Option<u64>, // Set when the first round is observed
+}
+
+impl VerboseConsensusLogging {
+    pub fn new(num_rounds: u64) -> Self {
+        Self {
+            num_rounds,
+            end_round: None,
+        }
+    }
+}
+
+/// The state of the network message dump toggle
+#[derive(Clone, Debug)]
+pub struct NetworkMessageDump {
+    peers: HashSet<String>,
+}
+
+impl NetworkMessageDump {
+    pub fn new(peers: HashSet<String>) -> Self {
+        Self { peers }
+    }
+}
+
+/// The status of an enabled debug toggle (e.g., to display to operators)
+#[derive(Clone, Debug, Serialize)]
+pub struct DebugToggleStatus {
+    pub name: &'static str,
+    pub state: String,
+    pub remaining_secs: u64,
+}
+
+/// A debug feature that (once enabled) remains enabled until a deadline
+pub struct DebugToggle<T> {
+    name: &'static str,
+    enabled: AtomicBool, // Allows callers to skip the lock when the toggle is disabled
+    state: Mutex<Option<EnabledToggle<T>>>,
+}
+
+struct EnabledToggle<T> {
+    state: T,
+    deadline: Instant,
+}
+
+impl<T: Debug> DebugToggle<T> {
+    pub fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            enabled: AtomicBool::new(false),
+            state: Mutex::new(None),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Enables the toggle with the given state for the given duration.
+    /// If the toggle is already enabled, the state and deadline are replaced.
+    pub fn enable(&self, state: T, duration: Duration) {
+        *self.state.lock() = Some(EnabledToggle {
+            state,
+            deadline: Instant::now() + duration,
+        });
+        self.enabled.store(true, Ordering::Release);
+    }
+
+    /// Disables the toggle (if it is enabled)
+    pub fn disable(&self) {
+        *self.state.lock() = None;
+        self.enabled.store(false, Ordering::Release);
+    }
+
+    /// Returns true iff the toggle is enabled (and the deadline hasn't passed)
+    pub fn is_enabled(&self) -> bool {
+        self.with_state(|_| ()).is_some()
+    }
+
+    /// Applies the given function to the state of the toggle and returns the
+    /// result. If the toggle is disabled, None is returned. If the deadline has
+    /// passed, the toggle is reverted (i.e., disabled) and None is returned.
+    pub fn with_state<R>(&self, function: impl FnOnce(&mut T) -> R) -> Option<R> {
+        if !self.enabled.load(Ordering::Acquire) {
+            return None;
+        }
+
+        let mut state = self.state.lock();
+        match state.as_mut() {
+            Some(enabled_toggle) if Instant::now() < enabled_toggle.deadline => {
+                Some(function(&mut enabled_toggle.state))
+            },
+            _ => {
+                *state = None;
+                self.enabled.store(false, Ordering::Release);
+                None
+            },
+        }
+    }
+
+    /// Returns the status of the toggle (if it is enabled)
+    pub fn status(&self) -> Option<DebugToggleStatus> {
+        let state = self.state.lock();
+        let enabled_toggle = state.as_ref()?;
+        let remaining = enabled_toggle
+            .deadline
+            .checked_duration_since(Instant::now())?;
+
+        Some(DebugToggleStatus {
+            name: self.name,
+            state: format!("{:?}", enabled_toggle.state),
+            remaining_secs: remaining.as_secs(),
+        })
+    }
+}
+
+impl DebugToggle<ExecutionTracing> {
+    /// Returns true iff the next transaction should be traced
+    pub fn should_trace(&self) -> bool {
+        self.with_state(|tracing| {
+            let should_trace = tracing.num_transactions_seen % tracing.sample_frequency == 0;
+            tracing.num_transactions_seen += 1;
+            should_trace
+        })
+        .unwrap_or(false)
+    }
+}
+
+impl DebugToggle<VerboseConsensusLogging> {
+    /// Returns true iff verbose logs should be emitted for the given round. The
+    /// rounds are counted from the first round observed after the toggle was
+    /// enabled. Once all rounds have passed, the toggle is reverted.
+    pub fn should_log_round(&self, round: u64) -> bool {
+        let should_log = self.with_state(|logging| {
+            let end_round = *logging
+                .end_round
+                .get_or_insert_with(|| round.saturating_add(logging.num_rounds));
+            round < end_round
+        });
+
+        match should_log {
+            Some(true) => true,
+            Some(false) => {
+                self.disable();
+                false
+            },
+            None => false,
+        }
+    }
+}
+
+impl DebugToggle<NetworkMessageDump> {
+    /// Returns true iff messages exchanged with the given peer should be dumped
+    pub fn should_dump(&self, peer: impl Display) -> bool {
+        self.with_state(|dump| dump.peers.contains(&peer.to_string()))
+            .unwrap_or(false)
+    }
+}
+
+/// Returns the statuses of all enabled debug toggles
+pub fn get_enabled_toggle_statuses() -> Vec<DebugToggleStatus> {
+    [
+        EXECUTION_TRACING.status(),
+        VERBOSE_CONSENSUS_LOGGING.status(),
+        NETWORK_MESSAGE_DUMP.status(),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toggle_deadline() {
+        let toggle = DebugToggle::new("test");
+        assert!(!toggle.is_enabled());
+
+        // Enable the toggle and verify it is enabled
+        toggle.enable(ExecutionTracing::new(1), Duration::from_secs(60));
+        assert!(toggle.is_enabled());
+        assert!(toggle.status().is_some());
+
+        // Enable the toggle with a deadline that has already passed
+        toggle.enable(ExecutionTracing::new(1), Duration::ZERO);
+        assert!(!toggle.is_enabled());
+        assert!(toggle.status().is_none());
+        assert!(!toggle.should_trace());
+    }
+
+    #[test]
+    fn test_execution_tracing_sampling() {
+        let toggle = DebugToggle::new("test");
+        toggle.enable(ExecutionTracing::new(3), Duration::from_secs(60));
+
+        // Verify that one out of every three transactions is traced
+        let traced: Vec<_> = (0..6).map(|_| toggle.should_trace()).collect();
+        assert_eq!(traced, vec![true, false, false, true, false, false]);
+
+        // Disable the toggle and verify nothing is traced
+        toggle.disable();
+        assert!(!toggle.should_trace());
+    }
+
+    #[test]
+    fn test_verbose_consensus_logging_rounds() {
+        let toggle = DebugToggle::new("test");
+        toggle.enable(VerboseConsensusLogging::new(2), Duration::from_secs(60));
+
+        // Verify that only the first two rounds are logged
+        assert!(toggle.should_log_round(10));
+        assert!(toggle.should_log_round(11));
+        assert!(!toggle.should_log_round(12));
+
+        // Verify the toggle was reverted
+        assert!(!toggle.is_enabled());
+        assert!(!toggle.should_log_round(11));
+    }
+
+    #[test]
+    fn test_network_message_dump_peers() {
+        let toggle = DebugToggle::new("test");
+        let peers = HashSet::from(["0xa".to_string()]);
+        toggle.enable(NetworkMessageDump::new(peers), Duration::from_secs(60));
+
+        assert!(toggle.should_dump("0xa"));
+        assert!(!toggle.should_dump("0xb"));
+    }
+}
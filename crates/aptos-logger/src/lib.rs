@@ -145,6 +145,7 @@ pub mod prelude {
 }
 
 pub mod aptos_logger;
+pub mod debug_toggles;
 mod event;
 mod filter;
 mod kv;
@@ -36,8 +36,11 @@ use aptos_types::{
     account_address::AccountAddress,
     account_config::{AccountResource, CoinStoreResource, NewBlockEvent, CORE_CODE_ADDRESS},
     contract_event::EventWithVersion,
+    epoch_change::EpochChangeProof,
+    ledger_info::LedgerInfoWithSignatures,
     state_store::state_key::StateKey,
     transaction::SignedTransaction,
+    waypoint::Waypoint,
 };
 use move_core_types::language_storage::StructTag;
 use reqwest::{
@@ -285,6 +288,33 @@ impl Client {
         Ok(response)
     }
 
+    /// Returns the epoch change proof from the epoch following the given (trusted)
+    /// waypoint to the latest epoch. Note: the proof may be incomplete if there are
+    /// too many epochs to return in a single response (see `EpochChangeProof::more`).
+    pub async fn get_epoch_change_proof(
+        &self,
+        waypoint: Waypoint,
+    ) -> AptosResult<Response<EpochChangeProof>> {
+        let mut url = self.build_path("experimental/light_client/epoch_change_proof")?;
+        url.set_query(Some(format!("waypoint={}", waypoint).as_str()));
+        let response = self.get_bcs(url).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
+    /// Returns the latest ledger info with signatures. If a version is
+    /// provided, the epoch ending ledger info at that version is returned.
+    pub async fn get_ledger_info_with_signatures(
+        &self,
+        version: Option<u64>,
+    ) -> AptosResult<Response<LedgerInfoWithSignatures>> {
+        let mut url = self.build_path("experimental/light_client/ledger_info")?;
+        if let Some(version) = version {
+            url.set_query(Some(format!("version={}", version).as_str()));
+        }
+        let response = self.get_bcs(url).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn view(
         &self,
         request: &ViewRequest,
@@ -0,0 +1,324 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::{self, set_telemetry_buffer_bytes};
+use anyhow::{anyhow, Result};
+use aptos_infallible::Mutex;
+use aptos_logger::warn;
+use aptos_telemetry_service::types::telemetry::TelemetryDump;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs,
+    path::{Path, PathBuf},
+};
+
+// The file extension of buffered telemetry entries
+const BUFFER_ENTRY_EXTENSION: &str = "json";
+
+// The file extension of partially written entries
+const TEMP_ENTRY_EXTENSION: &str = "tmp";
+
+/// Telemetry that could not be sent to the telemetry service and
+/// should be replayed once the service is reachable again.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub(crate) enum BufferedTelemetry {
+    CustomEvent {
+        event_name: String,
+        telemetry_dump: TelemetryDump,
+    },
+    Logs(Vec<String>),
+}
+
+impl BufferedTelemetry {
+    /// Returns a simple label for the telemetry type (used for metrics)
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            BufferedTelemetry::CustomEvent { .. } => "custom_event",
+            BufferedTelemetry::Logs(_) => "logs",
+        }
+    }
+}
+
+/// A bounded, disk-backed FIFO buffer of telemetry. Each entry is stored
+/// in its own file (named by a monotonically increasing sequence number),
+/// so the buffer survives node restarts. If the buffer is full, the oldest
+/// entries are evicted to make room for new ones.
+///
+/// Note: the buffer performs blocking file IO (while holding its lock), so
+/// async callers should access it via `tokio::task::spawn_blocking`.
+pub(crate) struct TelemetryBuffer {
+    buffer_dir: PathBuf,
+    max_buffer_bytes: u64,
+    state: Mutex<BufferState>,
+}
+
+struct BufferState {
+    entries: VecDeque<BufferEntry>, // Ordered from oldest to newest
+    next_sequence_number: u64,
+    total_bytes: u64,
+}
+
+struct BufferEntry {
+    sequence_number: u64,
+    num_bytes: u64,
+}
+
+impl TelemetryBuffer {
+    /// Opens (or creates) the buffer in the given directory. Any entries
+    /// left over from a previous run are loaded for replay.
+    pub fn new(buffer_dir: PathBuf, max_buffer_bytes: u64) -> Result<Self> {
+        fs::create_dir_all(&buffer_dir)?;
+
+        // Load all existing entries
+        let mut entries = vec![];
+        for dir_entry in fs::read_dir(&buffer_dir)? {
+            let path = dir_entry?.path();
+            match path.extension().and_then(|extension| extension.to_str()) {
+                Some(BUFFER_ENTRY_EXTENSION) => {
+                    if let Some(sequence_number) = parse_sequence_number(&path) {
+                        let num_bytes = fs::metadata(&path)?.len();
+                        entries.push(BufferEntry {
+                            sequence_number,
+                            num_bytes,
+                        });
+                    }
+                },
+                Some(TEMP_ENTRY_EXTENSION) => {
+                    // The entry was never fully written, so it can be removed
+                    let _ = fs::remove_file(&path);
+                },
+                _ => {}, // Ignore any unknown files
+            }
+        }
+        entries.sort_by_key(|entry| entry.sequence_number);
+
+        // Initialize the buffer state
+        let next_sequence_number = entries
+            .last()
+            .map(|entry| entry.sequence_number + 1)
+            .unwrap_or(0);
+        let total_bytes = entries.iter().map(|entry| entry.num_bytes).sum();
+        let state = BufferState {
+            entries: entries.into(),
+            next_sequence_number,
+            total_bytes,
+        };
+        set_telemetry_buffer_bytes(total_bytes);
+
+        let buffer = Self {
+            buffer_dir,
+            max_buffer_bytes,
+            state: Mutex::new(state),
+        };
+        buffer.evict_entries_to_fit(&mut buffer.state.lock(), 0);
+        Ok(buffer)
+    }
+
+    /// Appends the given telemetry to the buffer, evicting the
+    /// oldest entries if there's not enough space.
+    pub fn push(&self, telemetry: &BufferedTelemetry) -> Result<()> {
+        let bytes = serde_json::to_vec(telemetry)?;
+        let num_bytes = bytes.len() as u64;
+        if num_bytes > self.max_buffer_bytes {
+            metrics::increment_telemetry_buffer_operations(telemetry.get_label(), "dropped");
+            return Err(anyhow!(
+                "The telemetry entry is too large to buffer! Size: {}, max buffer size: {}",
+                num_bytes,
+                self.max_buffer_bytes
+            ));
+        }
+
+        // Make room for the new entry
+        let mut state = self.state.lock();
+        self.evict_entries_to_fit(&mut state, num_bytes);
+
+        let sequence_number = state.next_sequence_number;
+
+        // Write the entry atomically (i.e., write a temp file and rename it)
+        let entry_path = self.get_entry_path(sequence_number);
+        let temp_path = entry_path.with_extension(TEMP_ENTRY_EXTENSION);
+        fs::write(&temp_path, &bytes)?;
+        fs::rename(&temp_path, &entry_path)?;
+
+        // Update the buffer state
+        state.entries.push_back(BufferEntry {
+            sequence_number,
+            num_bytes,
+        });
+        state.next_sequence_number += 1;
+        state.total_bytes += num_bytes;
+        set_telemetry_buffer_bytes(state.total_bytes);
+        metrics::increment_telemetry_buffer_operations(telemetry.get_label(), "buffered");
+
+        Ok(())
+    }
+
+    /// Returns the oldest entry in the buffer (and its sequence number).
+    /// Entries that can no longer be read are removed from the buffer.
+    pub fn peek(&self) -> Option<(u64, BufferedTelemetry)> {
+        loop {
+            let sequence_number = self.state.lock().entries.front()?.sequence_number;
+            match self.read_entry(sequence_number) {
+                Ok(telemetry) => return Some((sequence_number, telemetry)),
+                Err(error) => {
+                    warn!(
+                        "Failed to read buffered telemetry entry {}: {}. Removing the entry.",
+                        sequence_number, error
+                    );
+                    self.remove(sequence_number);
+                },
+            }
+        }
+    }
+
+    /// Removes the entry with the given sequence number from the buffer
+    pub fn remove(&self, sequence_number: u64) {
+        let mut state = self.state.lock();
+        if let Some(index) = state
+            .entries
+            .iter()
+            .position(|entry| entry.sequence_number == sequence_number)
+        {
+            let entry = state.entries.remove(index).expect("Entry must exist!");
+            state.total_bytes = state.total_bytes.saturating_sub(entry.num_bytes);
+            set_telemetry_buffer_bytes(state.total_bytes);
+            self.remove_entry_file(sequence_number);
+        }
+    }
+
+    /// Returns the number of entries in the buffer
+    pub fn num_entries(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    /// Returns the total number of bytes in the buffer
+    pub fn total_bytes(&self) -> u64 {
+        self.state.lock().total_bytes
+    }
+
+    /// Evicts the oldest entries until the given number of bytes fit in the buffer
+    fn evict_entries_to_fit(&self, state: &mut BufferState, num_bytes: u64) {
+        while state.total_bytes + num_bytes > self.max_buffer_bytes {
+            let entry = match state.entries.pop_front() {
+                Some(entry) => entry,
+                None => break,
+            };
+            state.total_bytes = state.total_bytes.saturating_sub(entry.num_bytes);
+            self.remove_entry_file(entry.sequence_number);
+            metrics::increment_telemetry_buffer_operations("any", "evicted");
+        }
+        set_telemetry_buffer_bytes(state.total_bytes);
+    }
+
+    fn get_entry_path(&self, sequence_number: u64) -> PathBuf {
+        self.buffer_dir.join(format!(
+            "{:020}.{}",
+            sequence_number, BUFFER_ENTRY_EXTENSION
+        ))
+    }
+
+    fn read_entry(&self, sequence_number: u64) -> Result<BufferedTelemetry> {
+        let bytes = fs::read(self.get_entry_path(sequence_number))?;
+        Ok(serde_json::from_slice(&bytes)?)
+    }
+
+    fn remove_entry_file(&self, sequence_number: u64) {
+        let entry_path = self.get_entry_path(sequence_number);
+        if let Err(error) = fs::remove_file(&entry_path) {
+            warn!(
+                "Failed to remove buffered telemetry file {:?}: {}",
+                entry_path, error
+            );
+        }
+    }
+}
+
+/// Parses the sequence number from the given entry path
+fn parse_sequence_number(path: &Path) -> Option<u64> {
+    path.file_stem()?.to_str()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_temppath::TempPath;
+
+    #[test]
+    fn test_push_peek_remove() {
+        let buffer_dir = TempPath::new();
+        let buffer = TelemetryBuffer::new(buffer_dir.path().to_path_buf(), 1024 * 1024).unwrap();
+        assert!(buffer.peek().is_none());
+
+        // Push several log batches
+        for i in 0..3 {
+            buffer
+                .push(&BufferedTelemetry::Logs(vec![format!("log_{}", i)]))
+                .unwrap();
+        }
+        assert_eq!(buffer.num_entries(), 3);
+
+        // Verify the entries are returned in order
+        for i in 0..3 {
+            let (sequence_number, telemetry) = buffer.peek().unwrap();
+            assert_eq!(sequence_number, i);
+            match telemetry {
+                BufferedTelemetry::Logs(logs) => assert_eq!(logs, vec![format!("log_{}", i)]),
+                telemetry => panic!("Unexpected telemetry: {:?}", telemetry),
+            }
+            buffer.remove(sequence_number);
+        }
+        assert!(buffer.peek().is_none());
+        assert_eq!(buffer.total_bytes(), 0);
+    }
+
+    #[test]
+    fn test_eviction() {
+        let entry = BufferedTelemetry::Logs(vec!["log".into()]);
+        let entry_size = serde_json::to_vec(&entry).unwrap().len() as u64;
+
+        // Create a buffer that can hold two entries
+        let buffer_dir = TempPath::new();
+        let buffer = TelemetryBuffer::new(buffer_dir.path().to_path_buf(), entry_size * 2).unwrap();
+
+        // Push three entries and verify the oldest was evicted
+        for _ in 0..3 {
+            buffer.push(&entry).unwrap();
+        }
+        assert_eq!(buffer.num_entries(), 2);
+        assert_eq!(buffer.peek().unwrap().0, 1);
+
+        // Verify that entries larger than the buffer are rejected
+        let large_entry = BufferedTelemetry::Logs(vec!["log".repeat(entry_size as usize)]);
+        assert!(buffer.push(&large_entry).is_err());
+        assert_eq!(buffer.num_entries(), 2);
+    }
+
+    #[test]
+    fn test_reload_from_disk() {
+        let buffer_dir = TempPath::new();
+        {
+            let buffer =
+                TelemetryBuffer::new(buffer_dir.path().to_path_buf(), 1024 * 1024).unwrap();
+            for i in 0..5 {
+                buffer
+                    .push(&BufferedTelemetry::Logs(vec![format!("log_{}", i)]))
+                    .unwrap();
+            }
+            buffer.remove(0);
+        }
+
+        // Reopen the buffer and verify the remaining entries are loaded
+        let buffer = TelemetryBuffer::new(buffer_dir.path().to_path_buf(), 1024 * 1024).unwrap();
+        assert_eq!(buffer.num_entries(), 4);
+        assert_eq!(buffer.peek().unwrap().0, 1);
+
+        // Verify new entries continue the sequence
+        buffer
+            .push(&BufferedTelemetry::Logs(vec!["log_5".into()]))
+            .unwrap();
+        assert_eq!(buffer.num_entries(), 5);
+        assert_eq!(parse_sequence_number(&buffer.get_entry_path(5)), Some(5));
+        assert!(buffer.get_entry_path(5).exists());
+    }
+}
@@ -14,6 +14,8 @@ pub(crate) const ENV_APTOS_DISABLE_TELEMETRY_PUSH_EVENTS: &str =
 pub(crate) const ENV_APTOS_DISABLE_PROMETHEUS_NODE_METRICS: &str =
     "APTOS_DISABLE_PROMETHEUS_NODE_METRICS";
 pub(crate) const ENV_APTOS_DISABLE_LOG_ENV_POLLING: &str = "APTOS_DISABLE_LOG_ENV_POLLING";
+pub(crate) const ENV_APTOS_DISABLE_TELEMETRY_BUFFERING: &str = "APTOS_DISABLE_TELEMETRY_BUFFERING";
+pub(crate) const ENV_APTOS_TELEMETRY_BUFFER_MAX_BYTES: &str = "APTOS_TELEMETRY_BUFFER_MAX_BYTES";
 
 pub(crate) const ENV_GA_MEASUREMENT_ID: &str = "GA_MEASUREMENT_ID";
 pub(crate) const ENV_GA_API_SECRET: &str = "GA_API_SECRET";
@@ -43,3 +45,9 @@ pub(crate) const NODE_CONFIG_FREQ_SECS: u64 = 60 * 60; // 60 minutes
 pub(crate) const PROMETHEUS_PUSH_METRICS_FREQ_SECS: u64 = 15; // 15 seconds
 pub(crate) const CHAIN_ACCESS_CHECK_FREQ_SECS: u64 = 30 * 60; // 30 minutes
 pub(crate) const LOG_ENV_POLL_FREQ_SECS: u64 = 5 * 60; // 5 minutes
+pub(crate) const TELEMETRY_BUFFER_REPLAY_FREQ_SECS: u64 = 60; // 1 minute
+
+// The disk-backed buffer for telemetry that fails to send (stored in the node's data directory)
+pub(crate) const TELEMETRY_BUFFER_DIR_NAME: &str = "telemetry_buffer";
+pub(crate) const TELEMETRY_BUFFER_DEFAULT_MAX_BYTES: u64 = 100 * 1024 * 1024; // 100 MiB (overridden by the env)
+pub(crate) const TELEMETRY_BUFFER_MAX_REPLAYS_PER_TICK: usize = 100;
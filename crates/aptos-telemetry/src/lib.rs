@@ -3,6 +3,7 @@
 
 #![forbid(unsafe_code)]
 
+mod buffer;
 mod constants;
 mod core_metrics;
 mod metrics;
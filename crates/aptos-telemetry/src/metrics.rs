@@ -2,7 +2,8 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_metrics_core::{
-    register_int_counter, register_int_counter_vec, IntCounter, IntCounterVec,
+    register_int_counter, register_int_counter_vec, register_int_gauge, IntCounter, IntCounterVec,
+    IntGauge,
 };
 use once_cell::sync::Lazy;
 
@@ -117,3 +118,34 @@ pub(crate) fn increment_log_ingest_too_large_by(v: u64) {
 pub(crate) fn increment_log_ingest_failures_by(v: u64) {
     APTOS_LOG_INGEST_FAILURE.inc_by(v);
 }
+
+/// Counter for operations on the telemetry buffer (e.g., buffered, replayed, evicted)
+pub(crate) static APTOS_TELEMETRY_BUFFER_OPERATIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_telemetry_buffer_operations",
+        "Number of operations performed on the telemetry buffer",
+        &["telemetry_type", "operation"]
+    )
+    .unwrap()
+});
+
+/// Gauge for the number of bytes currently held in the telemetry buffer
+pub(crate) static APTOS_TELEMETRY_BUFFER_BYTES: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_telemetry_buffer_bytes",
+        "Number of bytes currently held in the telemetry buffer"
+    )
+    .unwrap()
+});
+
+/// Increments the number of operations performed on the telemetry buffer
+pub(crate) fn increment_telemetry_buffer_operations(telemetry_type: &str, operation: &str) {
+    APTOS_TELEMETRY_BUFFER_OPERATIONS
+        .with_label_values(&[telemetry_type, operation])
+        .inc();
+}
+
+/// Sets the number of bytes currently held in the telemetry buffer
+pub(crate) fn set_telemetry_buffer_bytes(num_bytes: u64) {
+    APTOS_TELEMETRY_BUFFER_BYTES.set(num_bytes as i64);
+}
@@ -1,7 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::metrics::{self, increment_log_ingest_failures_by, increment_log_ingest_successes_by};
+use crate::{
+    buffer::{BufferedTelemetry, TelemetryBuffer},
+    constants::TELEMETRY_BUFFER_MAX_REPLAYS_PER_TICK,
+    metrics::{self, increment_log_ingest_failures_by, increment_log_ingest_successes_by},
+};
 use anyhow::{anyhow, Error, Result};
 use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::{
@@ -9,7 +13,7 @@ use aptos_crypto::{
     x25519,
 };
 use aptos_infallible::{Mutex, RwLock};
-use aptos_logger::debug;
+use aptos_logger::{debug, warn};
 use aptos_telemetry_service::types::{
     auth::{AuthRequest, AuthResponse},
     response::IndexResponse,
@@ -22,6 +26,7 @@ use reqwest::{header::CONTENT_ENCODING, Response, StatusCode, Url};
 use reqwest_middleware::{ClientBuilder, ClientWithMiddleware, RequestBuilder};
 use reqwest_retry::{policies::ExponentialBackoff, RetryTransientMiddleware};
 use std::{io::Write, sync::Arc, time::Duration};
+use thiserror::Error as ThisError;
 use uuid::Uuid;
 
 pub const DEFAULT_VERSION_PATH_BASE: &str = "api/v1/";
@@ -55,6 +60,7 @@ pub(crate) struct TelemetrySender {
     client: ClientWithMiddleware,
     auth_context: Arc<AuthContext>,
     uuid: Uuid,
+    buffer: Option<Arc<TelemetryBuffer>>,
 }
 
 impl TelemetrySender {
@@ -88,9 +94,19 @@ impl TelemetrySender {
             client,
             auth_context: Arc::new(AuthContext::new(node_config)),
             uuid: uuid::Uuid::new_v4(),
+            buffer: None,
         }
     }
 
+    /// Sets the buffer used to hold logs and custom events that fail to
+    /// send (so that they can be replayed once the service is reachable).
+    /// Note: prometheus metrics are not buffered, as they are only a
+    /// snapshot of the current state (and will be pushed again anyway).
+    pub(crate) fn with_buffer(mut self, buffer: Option<Arc<TelemetryBuffer>>) -> Self {
+        self.buffer = buffer;
+        self
+    }
+
     pub fn build_path(&self, path: &str) -> Result<Url> {
         Ok(self.base_url.join(&self.version_path_base)?.join(path)?)
     }
@@ -185,6 +201,7 @@ impl TelemetrySender {
                 Err(error) => {
                     increment_log_ingest_failures_by(batch.len() as u64);
                     debug!("Failed send log of length: {} with error: {}", len, error);
+                    self.buffer_telemetry(BufferedTelemetry::Logs(batch)).await;
                 },
             }
         } else {
@@ -214,7 +231,7 @@ impl TelemetrySender {
     }
 
     pub async fn try_send_custom_metrics(&self, event_name: String, telemetry_dump: TelemetryDump) {
-        match self.post_custom_metrics(&telemetry_dump.clone()).await {
+        match self.post_custom_metrics(&telemetry_dump).await {
             Ok(_) => {
                 metrics::increment_telemetry_service_successes(&event_name);
                 debug!("Custom metrics with name {} sent successfully.", event_name);
@@ -222,10 +239,89 @@ impl TelemetrySender {
             Err(e) => {
                 metrics::increment_telemetry_service_failures(&event_name);
                 debug!("Failed to send custom metrics: {}", e);
+                self.buffer_telemetry(BufferedTelemetry::CustomEvent {
+                    event_name,
+                    telemetry_dump,
+                })
+                .await;
             },
         }
     }
 
+    /// Buffers the given telemetry for replay (if buffering is enabled).
+    /// The buffer is disk-backed, so the write happens on a blocking thread.
+    async fn buffer_telemetry(&self, telemetry: BufferedTelemetry) {
+        if let Some(buffer) = self.buffer.clone() {
+            let result = tokio::task::spawn_blocking(move || buffer.push(&telemetry)).await;
+            match result {
+                Ok(Ok(())) => {},
+                Ok(Err(error)) => debug!("Failed to buffer telemetry: {}", error),
+                Err(error) => debug!("Failed to spawn the telemetry buffer write: {}", error),
+            }
+        }
+    }
+
+    /// Replays the telemetry that was buffered while the telemetry service
+    /// was unreachable (oldest first). Entries rejected by the service (i.e.,
+    /// with a client error) will never succeed, so they are dropped and the
+    /// replay continues. Otherwise, replay stops at the first failure, and
+    /// the remaining entries are retried on the next call.
+    pub(crate) async fn replay_buffered_telemetry(&self) {
+        let buffer = match &self.buffer {
+            Some(buffer) => buffer.clone(),
+            None => return,
+        };
+
+        for _ in 0..TELEMETRY_BUFFER_MAX_REPLAYS_PER_TICK {
+            let peek_buffer = buffer.clone();
+            let (sequence_number, telemetry) =
+                match tokio::task::spawn_blocking(move || peek_buffer.peek()).await {
+                    Ok(Some(entry)) => entry,
+                    Ok(None) => return, // The buffer is empty
+                    Err(error) => {
+                        debug!("Failed to spawn the telemetry buffer read: {}", error);
+                        return;
+                    },
+                };
+
+            // Resend the telemetry
+            let result = match &telemetry {
+                BufferedTelemetry::CustomEvent { telemetry_dump, .. } => {
+                    self.post_custom_metrics(telemetry_dump).await
+                },
+                BufferedTelemetry::Logs(batch) => match serde_json::to_vec(batch) {
+                    Ok(json) => self.post_logs(&json).await,
+                    Err(error) => Err(error.into()),
+                },
+            };
+
+            // Remove the entry from the buffer if it was sent successfully (or
+            // if it was rejected by the service and should not be retried).
+            let operation = match result {
+                Ok(_) => "replayed",
+                Err(error) if is_permanent_failure(&error) => {
+                    warn!(
+                        "Dropping buffered telemetry entry {} rejected by the service: {}",
+                        sequence_number, error
+                    );
+                    "rejected"
+                },
+                Err(error) => {
+                    debug!("Failed to replay buffered telemetry: {}", error);
+                    return;
+                },
+            };
+            let remove_buffer = buffer.clone();
+            if let Err(error) =
+                tokio::task::spawn_blocking(move || remove_buffer.remove(sequence_number)).await
+            {
+                debug!("Failed to spawn the telemetry buffer removal: {}", error);
+                return;
+            }
+            metrics::increment_telemetry_buffer_operations(telemetry.get_label(), operation);
+        }
+    }
+
     async fn post_custom_metrics(
         &self,
         telemetry_dump: &TelemetryDump,
@@ -404,19 +500,46 @@ impl TelemetrySender {
     }
 }
 
+/// An error status returned by the telemetry service
+#[derive(Debug, ThisError)]
+#[error("HTTP status error ({status}) for url ({url}): {body}")]
+struct HttpStatusError {
+    status: StatusCode,
+    url: Url,
+    body: String,
+}
+
 async fn error_for_status_with_body(response: Response) -> Result<Response, anyhow::Error> {
     if response.status().is_client_error() || response.status().is_server_error() {
-        Err(anyhow!(
-            "HTTP status error ({}) for url ({}): {}",
-            response.status(),
-            response.url().clone(),
-            response.text().await?,
-        ))
+        let status = response.status();
+        let url = response.url().clone();
+        Err(HttpStatusError {
+            status,
+            url,
+            body: response.text().await?,
+        }
+        .into())
     } else {
         Ok(response)
     }
 }
 
+/// Returns true iff the error is a client error that will never succeed on
+/// retry (e.g., a malformed payload). Auth failures and rate limiting are
+/// transient, so they are not considered permanent.
+fn is_permanent_failure(error: &anyhow::Error) -> bool {
+    match error.downcast_ref::<HttpStatusError>() {
+        Some(error) => {
+            error.status.is_client_error()
+                && error.status != StatusCode::UNAUTHORIZED
+                && error.status != StatusCode::FORBIDDEN
+                && error.status != StatusCode::REQUEST_TIMEOUT
+                && error.status != StatusCode::TOO_MANY_REQUESTS
+        },
+        None => false,
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -424,6 +547,7 @@ mod tests {
     use crate::metrics::{APTOS_TELEMETRY_SERVICE_FAILURE, APTOS_TELEMETRY_SERVICE_SUCCESS};
     use aptos_crypto::Uniform;
     use aptos_telemetry_service::types::telemetry::TelemetryEvent;
+    use aptos_temppath::TempPath;
     use httpmock::MockServer;
     use prometheus::{register_int_counter_vec_with_registry, Registry};
     use std::{
@@ -655,6 +779,95 @@ mod tests {
         mock.assert();
     }
 
+    #[tokio::test]
+    async fn test_buffer_and_replay_logs() {
+        let batch = vec!["log1".to_string(), "log2".to_string()];
+
+        // Create a server that fails all logs (with a transient error)
+        let server = MockServer::start();
+        let mut failure_mock = server.mock(|when, then| {
+            when.method("POST").path("/api/v1/ingest/logs");
+            then.status(403);
+        });
+
+        // Create a client with a telemetry buffer
+        let buffer_dir = TempPath::new();
+        let buffer =
+            Arc::new(TelemetryBuffer::new(buffer_dir.path().to_path_buf(), 1024 * 1024).unwrap());
+        let client = TelemetrySender::new(
+            Url::parse(&server.base_url()).expect("unable to parse base url"),
+            ChainId::default(),
+            &NodeConfig::default(),
+        )
+        .with_buffer(Some(buffer.clone()));
+        {
+            *client.auth_context.token.write() = Some("SECRET_JWT_TOKEN".into());
+        }
+
+        // Send the logs and verify they were buffered
+        client.try_send_logs(batch.clone()).await;
+        failure_mock.assert();
+        assert_eq!(buffer.num_entries(), 1);
+
+        // Verify the logs remain buffered if the replay fails
+        client.replay_buffered_telemetry().await;
+        failure_mock.assert_hits(2);
+        assert_eq!(buffer.num_entries(), 1);
+
+        // Update the server to accept the logs
+        failure_mock.delete();
+        let json = serde_json::to_string(&batch).unwrap();
+        let mut gzip_encoder = GzEncoder::new(Vec::new(), Compression::default());
+        gzip_encoder.write_all(json.as_bytes()).unwrap();
+        let expected_compressed_bytes = gzip_encoder.finish().unwrap();
+        let success_mock = server.mock(|when, then| {
+            when.method("POST")
+                .header("Authorization", "Bearer SECRET_JWT_TOKEN")
+                .path("/api/v1/ingest/logs")
+                .body(String::from_utf8_lossy(&expected_compressed_bytes));
+            then.status(200);
+        });
+
+        // Replay the logs and verify the buffer is now empty
+        client.replay_buffered_telemetry().await;
+        success_mock.assert();
+        assert_eq!(buffer.num_entries(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_replay_drops_rejected_telemetry() {
+        // Create a server that rejects all logs (e.g., the payload is malformed)
+        let server = MockServer::start();
+        let rejection_mock = server.mock(|when, then| {
+            when.method("POST").path("/api/v1/ingest/logs");
+            then.status(400);
+        });
+
+        // Create a client with a telemetry buffer holding several log batches
+        let buffer_dir = TempPath::new();
+        let buffer =
+            Arc::new(TelemetryBuffer::new(buffer_dir.path().to_path_buf(), 1024 * 1024).unwrap());
+        for i in 0..3 {
+            buffer
+                .push(&BufferedTelemetry::Logs(vec![format!("log_{}", i)]))
+                .unwrap();
+        }
+        let client = TelemetrySender::new(
+            Url::parse(&server.base_url()).expect("unable to parse base url"),
+            ChainId::default(),
+            &NodeConfig::default(),
+        )
+        .with_buffer(Some(buffer.clone()));
+        {
+            *client.auth_context.token.write() = Some("SECRET_JWT_TOKEN".into());
+        }
+
+        // Replay the logs and verify the rejected entries don't block the replay
+        client.replay_buffered_telemetry().await;
+        rejection_mock.assert_hits(3);
+        assert_eq!(buffer.num_entries(), 0);
+    }
+
     #[tokio::test]
     async fn test_check_chain_access() {
         let server = MockServer::start();
@@ -4,8 +4,8 @@
 #![forbid(unsafe_code)]
 
 use crate::{
-    constants::*, core_metrics::create_core_metric_telemetry_event, metrics,
-    network_metrics::create_network_metric_telemetry_event, sender::TelemetrySender,
+    buffer::TelemetryBuffer, constants::*, core_metrics::create_core_metric_telemetry_event,
+    metrics, network_metrics::create_network_metric_telemetry_event, sender::TelemetrySender,
     system_information::create_system_info_telemetry_event,
     telemetry_log_sender::TelemetryLogSender, utils::create_build_info_telemetry_event,
 };
@@ -26,6 +26,7 @@ use std::{
     collections::BTreeMap,
     env,
     future::Future,
+    sync::Arc,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use tokio::{runtime::Runtime, task::JoinHandle, time};
@@ -89,6 +90,28 @@ fn enable_push_custom_events() -> bool {
         || !(telemetry_is_disabled() || env::var(ENV_APTOS_DISABLE_TELEMETRY_PUSH_EVENTS).is_ok())
 }
 
+/// Flag to control enabling/disabling buffering (and replay) of
+/// telemetry that fails to send to the telemetry service.
+#[inline]
+fn enable_telemetry_buffering() -> bool {
+    env::var(ENV_APTOS_DISABLE_TELEMETRY_BUFFERING).is_err()
+}
+
+/// Returns the max number of bytes held by the telemetry buffer. This can
+/// be overridden via the environment (e.g., for nodes with small disks).
+fn get_telemetry_buffer_max_bytes() -> u64 {
+    match env::var(ENV_APTOS_TELEMETRY_BUFFER_MAX_BYTES) {
+        Ok(value) => value.parse().unwrap_or_else(|error| {
+            warn!(
+                "Invalid value for {}: {}. Using the default of {} bytes.",
+                ENV_APTOS_TELEMETRY_BUFFER_MAX_BYTES, error, TELEMETRY_BUFFER_DEFAULT_MAX_BYTES
+            );
+            TELEMETRY_BUFFER_DEFAULT_MAX_BYTES
+        }),
+        Err(_) => TELEMETRY_BUFFER_DEFAULT_MAX_BYTES,
+    }
+}
+
 #[inline]
 fn enable_log_env_polling() -> bool {
     force_enable_telemetry()
@@ -152,7 +175,8 @@ async fn spawn_telemetry_service(
                 .expect("unable to parse telemetry service default URL")
     });
 
-    let telemetry_sender = TelemetrySender::new(base_url, chain_id, &node_config);
+    let telemetry_sender = TelemetrySender::new(base_url, chain_id, &node_config)
+        .with_buffer(try_create_telemetry_buffer(&node_config).await);
 
     if !force_enable_telemetry() && !telemetry_sender.check_chain_access(chain_id).await {
         warn!(
@@ -186,6 +210,7 @@ async fn spawn_telemetry_service(
     try_spawn_log_sender(telemetry_sender.clone(), remote_log_rx);
     try_spawn_metrics_sender(telemetry_sender.clone());
     try_spawn_custom_event_sender(node_config, telemetry_sender.clone(), chain_id, build_info);
+    try_spawn_buffer_replay_task(telemetry_sender.clone());
     try_spawn_log_env_poll_task(telemetry_sender);
 
     // Run the logger filter update job within the telemetry runtime.
@@ -221,6 +246,56 @@ fn try_spawn_log_env_poll_task(sender: TelemetrySender) {
     }
 }
 
+/// Creates the disk-backed telemetry buffer (if buffering is enabled)
+async fn try_create_telemetry_buffer(node_config: &NodeConfig) -> Option<Arc<TelemetryBuffer>> {
+    if !enable_telemetry_buffering() {
+        return None;
+    }
+
+    // Open the buffer on a blocking thread (existing entries are loaded from disk)
+    let buffer_dir = node_config.get_data_dir().join(TELEMETRY_BUFFER_DIR_NAME);
+    let max_buffer_bytes = get_telemetry_buffer_max_bytes();
+    let result = {
+        let buffer_dir = buffer_dir.clone();
+        tokio::task::spawn_blocking(move || TelemetryBuffer::new(buffer_dir, max_buffer_bytes))
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result)
+    };
+    match result {
+        Ok(buffer) => {
+            info!(
+                "Telemetry buffer opened at {:?} with {} entries ({} bytes)",
+                buffer_dir,
+                buffer.num_entries(),
+                buffer.total_bytes()
+            );
+            Some(Arc::new(buffer))
+        },
+        Err(error) => {
+            warn!(
+                "Failed to open the telemetry buffer at {:?}: {}. Telemetry will not be buffered!",
+                buffer_dir, error
+            );
+            None
+        },
+    }
+}
+
+fn try_spawn_buffer_replay_task(telemetry_sender: TelemetrySender) {
+    if enable_telemetry_buffering() {
+        tokio::spawn(async move {
+            // Periodically replay any buffered telemetry (e.g., after a network partition)
+            let mut interval =
+                time::interval(Duration::from_secs(TELEMETRY_BUFFER_REPLAY_FREQ_SECS));
+            loop {
+                interval.tick().await;
+                telemetry_sender.replay_buffered_telemetry().await;
+            }
+        });
+    }
+}
+
 fn try_spawn_custom_event_sender(
     node_config: NodeConfig,
     telemetry_sender: TelemetrySender,
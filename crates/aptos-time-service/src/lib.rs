@@ -104,6 +104,27 @@ impl TimeService {
             ts => panic!("Unexpected TimeService, expected MockTimeService: {:?}", ts),
         }
     }
+
+    /// Returns true iff this is a mock time service. Unlike [`into_mock`](#method.into_mock),
+    /// this is available in all builds (e.g., for services that expose the time to tests).
+    pub fn is_mock(&self) -> bool {
+        match self {
+            TimeService::RealTimeService(_) => false,
+            #[cfg(any(test, feature = "testing"))]
+            TimeService::MockTimeService(_) => true,
+        }
+    }
+
+    /// Advances a mock time service by `duration` and returns the number of
+    /// waiters that were woken up. Returns `None` for real time services
+    /// (which cannot be advanced). This is available in all builds.
+    pub fn advance_mock_time(&self, duration: Duration) -> Option<usize> {
+        match self {
+            TimeService::RealTimeService(_) => None,
+            #[cfg(any(test, feature = "testing"))]
+            TimeService::MockTimeService(inner) => Some(inner.advance(duration)),
+        }
+    }
 }
 
 impl Default for TimeService {
@@ -99,6 +99,15 @@ impl MockTimeService {
         }
     }
 
+    /// Create a new `MockTimeService` with no auto advance, whose simulated unix
+    /// time starts at `start_time` (instead of zero). This is useful when the
+    /// simulated time must be comparable to real timestamps (e.g., on-chain time).
+    pub fn new_with_start_time(start_time: Duration) -> Self {
+        let time_service = Self::new();
+        time_service.lock().now = start_time;
+        time_service
+    }
+
     /// Create a new `MockTimeService` that will auto advance forever.
     pub fn new_auto_advance() -> Self {
         Self::new_auto_advance_for(duration_max())
@@ -469,6 +478,19 @@ mod test {
         assert_ready!(sleep.poll());
     }
 
+    #[tokio::test]
+    async fn test_start_time() {
+        let time = MockTimeService::new_with_start_time(secs(1_000));
+        assert_eq!(time.now_unix_time(), secs(1_000));
+
+        // Sleeps are relative to the start time
+        let mut sleep = task::spawn(time.sleep(ms(10)));
+        assert_pending!(sleep.poll());
+        assert_eq!(time.advance_async(ms(10)).await, 1);
+        assert_ready!(sleep.poll());
+        assert_eq!(time.now_unix_time(), secs(1_000) + ms(10));
+    }
+
     #[tokio::test]
     async fn test_sleep_until() {
         let time = MockTimeService::new();
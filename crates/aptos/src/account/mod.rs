@@ -12,11 +12,13 @@ pub mod key_rotation;
 pub mod list;
 pub mod multisig_account;
 pub mod transfer;
+pub mod watch;
 
 /// Tool for interacting with accounts
 ///
 /// This tool is used to create accounts, get information about the
-/// account's resources, and transfer resources between accounts.
+/// account's resources, watch the account's resources for changes,
+/// and transfer resources between accounts.
 #[derive(Debug, Subcommand)]
 pub enum AccountTool {
     Create(create::CreateAccount),
@@ -27,6 +29,7 @@ pub enum AccountTool {
     LookupAddress(key_rotation::LookupAddress),
     RotateKey(key_rotation::RotateKey),
     Transfer(transfer::TransferCoins),
+    Watch(watch::WatchAccount),
 }
 
 impl AccountTool {
@@ -40,6 +43,7 @@ impl AccountTool {
             AccountTool::LookupAddress(tool) => tool.execute_serialized().await,
             AccountTool::RotateKey(tool) => tool.execute_serialized().await,
             AccountTool::Transfer(tool) => tool.execute_serialized().await,
+            AccountTool::Watch(tool) => tool.execute_serialized().await,
         }
     }
 }
@@ -0,0 +1,115 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    types::{CliCommand, CliTypedResult, ProfileOptions, RestOptions},
+    watch::{diff_values, ValueChange, WatchOptions, WatchSummary},
+};
+use aptos_types::account_address::AccountAddress;
+use async_trait::async_trait;
+use clap::Parser;
+use serde::Serialize;
+use std::{
+    collections::BTreeMap,
+    fmt::{Display, Formatter},
+};
+
+/// Watch the resources of an account and print changes as they happen
+///
+/// The account's resources are polled from the fullnode, and each resource that is
+/// added, modified or removed is printed (along with the ledger version it was seen at).
+/// The current resources are printed as additions when the command starts.
+#[derive(Debug, Parser)]
+pub struct WatchAccount {
+    /// Address of the account you want to watch
+    ///
+    /// Defaults to the account of the profile
+    #[clap(long, value_parser = crate::common::types::load_account_arg)]
+    pub(crate) account: Option<AccountAddress>,
+
+    /// Only watch resources with a type starting with one of the given prefixes
+    ///
+    /// e.g. `0x1::coin::CoinStore` to watch the coin stores of all coin types
+    #[clap(long, num_args = 0..)]
+    pub(crate) resource_types: Vec<String>,
+
+    #[clap(flatten)]
+    pub(crate) watch_options: WatchOptions,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// A change to a resource, seen at the given ledger version
+#[derive(Debug, Serialize)]
+pub struct ResourceChange {
+    pub ledger_version: u64,
+    #[serde(flatten)]
+    pub change: ValueChange,
+}
+
+impl Display for ResourceChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "[version {}] {}", self.ledger_version, self.change)
+    }
+}
+
+#[async_trait]
+impl CliCommand<WatchSummary> for WatchAccount {
+    fn command_name(&self) -> &'static str {
+        "WatchAccount"
+    }
+
+    async fn execute(self) -> CliTypedResult<WatchSummary> {
+        let account = match self.account {
+            Some(account) => account,
+            None => self.profile_options.account_address()?,
+        };
+        let client = self.rest_options.client(&self.profile_options)?;
+
+        let mut summary = WatchSummary::default();
+        let mut resources = BTreeMap::new();
+        loop {
+            // Fetch the latest resources of the account
+            let response = client.get_account_resources(account).await?;
+            let ledger_version = response.state().version;
+            let latest_resources: BTreeMap<_, _> = response
+                .into_inner()
+                .into_iter()
+                .map(|resource| (resource.resource_type.to_string(), resource.data))
+                .filter(|(resource_type, _)| self.is_watched(resource_type))
+                .collect();
+            summary.num_polls += 1;
+            summary.last_ledger_version = Some(ledger_version);
+
+            // Print the changes since the last poll
+            for change in diff_values(&resources, &latest_resources) {
+                self.watch_options.print_update(&ResourceChange {
+                    ledger_version,
+                    change,
+                });
+                summary.num_updates += 1;
+                if self.watch_options.is_done(summary.num_updates) {
+                    return Ok(summary);
+                }
+            }
+            resources = latest_resources;
+
+            if !self.watch_options.wait_for_next_poll().await {
+                return Ok(summary);
+            }
+        }
+    }
+}
+
+impl WatchAccount {
+    /// Returns true iff the given resource type should be watched
+    fn is_watched(&self, resource_type: &str) -> bool {
+        self.resource_types.is_empty()
+            || self
+                .resource_types
+                .iter()
+                .any(|prefix| resource_type.starts_with(prefix.as_str()))
+    }
+}
@@ -4,3 +4,4 @@
 pub mod init;
 pub mod types;
 pub mod utils;
+pub mod watch;
@@ -29,7 +29,7 @@ use aptos_keygen::KeyGen;
 use aptos_logger::Level;
 use aptos_move_debugger::aptos_debugger::AptosDebugger;
 use aptos_rest_client::{
-    aptos_api_types::{EntryFunctionId, HashValue, MoveType, ViewRequest},
+    aptos_api_types::{EntryFunctionId, HashValue, MoveType, UserTransaction, ViewRequest},
     error::RestError,
     AptosBaseUrl, Client, Transaction,
 };
@@ -1836,6 +1836,53 @@ impl TransactionOptions {
                 ))
             })
     }
+
+    /// Simulates the transaction (without submitting it) and returns the simulated
+    /// transaction. If no max gas is specified, the max gas amount is estimated.
+    pub async fn simulate_transaction(
+        &self,
+        payload: TransactionPayload,
+    ) -> CliTypedResult<UserTransaction> {
+        let client = self.rest_client()?;
+        let (sender_public_key, sender_address) = self.get_public_key_and_address()?;
+
+        // Use the gas unit price if specified, otherwise estimate it
+        let gas_unit_price = if let Some(gas_unit_price) = self.gas_options.gas_unit_price {
+            gas_unit_price
+        } else {
+            client.estimate_gas_price().await?.into_inner().gas_estimate
+        };
+
+        // Build the unsigned transaction using the current chain state
+        let (account, state) = get_account_with_state(&client, sender_address).await?;
+        let mut transaction_factory = TransactionFactory::new(ChainId::new(state.chain_id))
+            .with_gas_unit_price(gas_unit_price);
+        if let Some(max_gas) = self.gas_options.max_gas {
+            transaction_factory = transaction_factory.with_max_gas_amount(max_gas);
+        }
+        let expiration_time_secs =
+            state.timestamp_usecs / US_IN_SECS + self.gas_options.expiration_secs;
+        let unsigned_transaction = transaction_factory
+            .payload(payload)
+            .sender(sender_address)
+            .sequence_number(account.sequence_number)
+            .expiration_timestamp_secs(expiration_time_secs)
+            .build();
+
+        // Simulate the transaction with an empty signature
+        let signed_transaction = SignedTransaction::new(
+            unsigned_transaction,
+            sender_public_key,
+            Ed25519Signature::try_from([0u8; 64].as_ref()).unwrap(),
+        );
+        let estimate_max_gas_amount = self.gas_options.max_gas.is_none();
+        let mut txns = client
+            .simulate_with_gas_estimation(&signed_transaction, estimate_max_gas_amount, false)
+            .await?
+            .into_inner();
+        txns.pop()
+            .ok_or_else(|| CliError::UnexpectedError("No simulated transaction returned".into()))
+    }
 }
 
 #[derive(Parser)]
@@ -0,0 +1,245 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use clap::Parser;
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::{Display, Formatter},
+    time::Duration,
+};
+
+/// Options for commands that watch on-chain state for changes
+#[derive(Clone, Copy, Debug, Parser)]
+pub struct WatchOptions {
+    /// Interval in milliseconds between polls of the fullnode
+    #[clap(long, default_value_t = 1000)]
+    pub poll_interval_ms: u64,
+
+    /// Stop watching after this many updates have been printed
+    ///
+    /// If not provided, the command watches until it is interrupted (e.g., with Ctrl-C)
+    #[clap(long)]
+    pub max_updates: Option<u64>,
+
+    /// Print each update as a single line of JSON (instead of a human readable diff)
+    #[clap(long)]
+    pub json: bool,
+}
+
+impl WatchOptions {
+    /// Waits for the next poll. Returns false if the command was interrupted.
+    pub async fn wait_for_next_poll(&self) -> bool {
+        tokio::select! {
+            _ = tokio::time::sleep(Duration::from_millis(self.poll_interval_ms)) => true,
+            _ = tokio::signal::ctrl_c() => false,
+        }
+    }
+
+    /// Prints the given update to stdout
+    pub fn print_update<T: Display + Serialize>(&self, update: &T) {
+        if self.json {
+            match serde_json::to_string(update) {
+                Ok(update) => println!("{}", update),
+                Err(error) => eprintln!("Failed to serialize the update! Error: {}", error),
+            }
+        } else {
+            println!("{}", update);
+        }
+    }
+
+    /// Returns true iff the maximum number of updates has been reached
+    pub fn is_done(&self, num_updates: u64) -> bool {
+        self.max_updates
+            .map(|max_updates| num_updates >= max_updates)
+            .unwrap_or(false)
+    }
+}
+
+/// The summary of a watch command, returned once it stops watching
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct WatchSummary {
+    pub num_polls: u64,
+    pub num_updates: u64,
+    pub last_ledger_version: Option<u64>,
+}
+
+/// A change to a single keyed value (e.g., a resource of an account)
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(tag = "change", rename_all = "snake_case")]
+pub enum ValueChange {
+    Added {
+        key: String,
+        value: serde_json::Value,
+    },
+    Modified {
+        key: String,
+        old_value: serde_json::Value,
+        new_value: serde_json::Value,
+    },
+    Removed {
+        key: String,
+        value: serde_json::Value,
+    },
+}
+
+impl Display for ValueChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValueChange::Added { key, value } => write!(f, "+ {}: {}", key, value),
+            ValueChange::Modified {
+                key,
+                old_value,
+                new_value,
+            } => {
+                write!(f, "~ {}:", key)?;
+                for (path, old_value, new_value) in diff_json_paths(old_value, new_value) {
+                    write!(f, "\n    {}: {} -> {}", path, old_value, new_value)?;
+                }
+                Ok(())
+            },
+            ValueChange::Removed { key, value } => write!(f, "- {}: {}", key, value),
+        }
+    }
+}
+
+/// Returns the changes between the old and new keyed values (ordered by key)
+pub fn diff_values(
+    old_values: &BTreeMap<String, serde_json::Value>,
+    new_values: &BTreeMap<String, serde_json::Value>,
+) -> Vec<ValueChange> {
+    let mut changes = vec![];
+    for (key, new_value) in new_values {
+        match old_values.get(key) {
+            None => changes.push(ValueChange::Added {
+                key: key.clone(),
+                value: new_value.clone(),
+            }),
+            Some(old_value) if old_value != new_value => changes.push(ValueChange::Modified {
+                key: key.clone(),
+                old_value: old_value.clone(),
+                new_value: new_value.clone(),
+            }),
+            Some(_) => {},
+        }
+    }
+    for (key, old_value) in old_values {
+        if !new_values.contains_key(key) {
+            changes.push(ValueChange::Removed {
+                key: key.clone(),
+                value: old_value.clone(),
+            });
+        }
+    }
+    changes.sort_by(|change_1, change_2| change_key(change_1).cmp(change_key(change_2)));
+    changes
+}
+
+fn change_key(change: &ValueChange) -> &str {
+    match change {
+        ValueChange::Added { key, .. }
+        | ValueChange::Modified { key, .. }
+        | ValueChange::Removed { key, .. } => key,
+    }
+}
+
+/// Returns the (JSON pointer) paths of the leaves that differ between the old
+/// and new values, along with the old and new leaf values (null if missing).
+fn diff_json_paths(
+    old_value: &serde_json::Value,
+    new_value: &serde_json::Value,
+) -> Vec<(String, serde_json::Value, serde_json::Value)> {
+    let mut differences = vec![];
+    collect_json_differences("", old_value, new_value, &mut differences);
+    differences
+}
+
+fn collect_json_differences(
+    path: &str,
+    old_value: &serde_json::Value,
+    new_value: &serde_json::Value,
+    differences: &mut Vec<(String, serde_json::Value, serde_json::Value)>,
+) {
+    use serde_json::Value;
+
+    if old_value == new_value {
+        return;
+    }
+    match (old_value, new_value) {
+        (Value::Object(old_map), Value::Object(new_map)) => {
+            let keys: BTreeSet<_> = old_map.keys().chain(new_map.keys()).collect();
+            for key in keys {
+                collect_json_differences(
+                    &format!("{}/{}", path, key),
+                    old_map.get(key).unwrap_or(&Value::Null),
+                    new_map.get(key).unwrap_or(&Value::Null),
+                    differences,
+                );
+            }
+        },
+        (Value::Array(old_values), Value::Array(new_values))
+            if old_values.len() == new_values.len() =>
+        {
+            for (index, (old_value, new_value)) in old_values.iter().zip(new_values).enumerate() {
+                collect_json_differences(
+                    &format!("{}/{}", path, index),
+                    old_value,
+                    new_value,
+                    differences,
+                );
+            }
+        },
+        _ => {
+            let path = if path.is_empty() { "/" } else { path };
+            differences.push((path.to_string(), old_value.clone(), new_value.clone()));
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_diff_values() {
+        let old_values = BTreeMap::from([
+            ("0x1::a::A".to_string(), json!({"value": 1})),
+            ("0x1::b::B".to_string(), json!({"value": 2})),
+            ("0x1::c::C".to_string(), json!({"value": 3})),
+        ]);
+        let new_values = BTreeMap::from([
+            ("0x1::a::A".to_string(), json!({"value": 1})),
+            ("0x1::b::B".to_string(), json!({"value": 5})),
+            ("0x1::d::D".to_string(), json!({"value": 4})),
+        ]);
+
+        // Verify the changes are ordered by key (and unchanged values are skipped)
+        assert_eq!(diff_values(&old_values, &new_values), vec![
+            ValueChange::Modified {
+                key: "0x1::b::B".into(),
+                old_value: json!({"value": 2}),
+                new_value: json!({"value": 5}),
+            },
+            ValueChange::Removed {
+                key: "0x1::c::C".into(),
+                value: json!({"value": 3}),
+            },
+            ValueChange::Added {
+                key: "0x1::d::D".into(),
+                value: json!({"value": 4}),
+            },
+        ]);
+        assert!(diff_values(&new_values, &new_values).is_empty());
+    }
+
+    #[test]
+    fn test_diff_json_paths() {
+        let old_value = json!({"coin": {"value": "10"}, "frozen": false, "list": [1, 2]});
+        let new_value = json!({"coin": {"value": "15"}, "frozen": false, "list": [1, 2, 3]});
+        assert_eq!(diff_json_paths(&old_value, &new_value), vec![
+            ("/coin/value".into(), json!("10"), json!("15")),
+            ("/list".into(), json!([1, 2]), json!([1, 2, 3])),
+        ]);
+    }
+}
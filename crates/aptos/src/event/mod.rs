@@ -0,0 +1,151 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    types::{CliCommand, CliResult, CliTypedResult, ProfileOptions, RestOptions},
+    watch::{WatchOptions, WatchSummary},
+};
+use aptos_rest_client::aptos_api_types::VersionedEvent;
+use aptos_types::account_address::AccountAddress;
+use async_trait::async_trait;
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+// The maximum number of events to fetch per poll
+const MAX_EVENTS_PER_POLL: u16 = 100;
+
+/// Tool for interacting with events
+///
+/// This tool is used to watch the events emitted to an event handle
+#[derive(Debug, Subcommand)]
+pub enum EventTool {
+    Watch(WatchEvents),
+}
+
+impl EventTool {
+    pub async fn execute(self) -> CliResult {
+        match self {
+            EventTool::Watch(tool) => tool.execute_serialized().await,
+        }
+    }
+}
+
+/// Watch an event handle and print new events as they are emitted
+///
+/// The event handle is polled from the fullnode, and each new event is printed (along
+/// with the ledger version it was emitted at). By default, only events emitted after
+/// the command starts are printed.
+#[derive(Debug, Parser)]
+pub struct WatchEvents {
+    /// Address of the account holding the event handle
+    ///
+    /// Defaults to the account of the profile
+    #[clap(long, value_parser = crate::common::types::load_account_arg)]
+    pub(crate) account: Option<AccountAddress>,
+
+    /// The struct holding the event handle
+    ///
+    /// e.g. `0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>`
+    #[clap(long)]
+    pub(crate) event_handle: String,
+
+    /// The name of the event handle field in the struct
+    ///
+    /// e.g. `deposit_events`
+    #[clap(long)]
+    pub(crate) field_name: String,
+
+    /// The sequence number of the first event to print
+    ///
+    /// If not provided, only new events are printed
+    #[clap(long)]
+    pub(crate) start: Option<u64>,
+
+    #[clap(flatten)]
+    pub(crate) watch_options: WatchOptions,
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+/// A newly emitted event
+#[derive(Debug, Serialize)]
+pub struct EventUpdate {
+    #[serde(flatten)]
+    pub event: VersionedEvent,
+}
+
+impl Display for EventUpdate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[version {}] #{} {}: {}",
+            self.event.version.0, self.event.sequence_number.0, self.event.typ, self.event.data
+        )
+    }
+}
+
+#[async_trait]
+impl CliCommand<WatchSummary> for WatchEvents {
+    fn command_name(&self) -> &'static str {
+        "WatchEvents"
+    }
+
+    async fn execute(self) -> CliTypedResult<WatchSummary> {
+        let account = match self.account {
+            Some(account) => account,
+            None => self.profile_options.account_address()?,
+        };
+        let client = self.rest_options.client(&self.profile_options)?;
+
+        // Determine the sequence number of the first event to print
+        let mut next_sequence_number = match self.start {
+            Some(start) => start,
+            None => client
+                .get_account_events(account, &self.event_handle, &self.field_name, None, Some(1))
+                .await?
+                .into_inner()
+                .last()
+                .map(|event| event.sequence_number.0 + 1)
+                .unwrap_or(0),
+        };
+
+        let mut summary = WatchSummary::default();
+        loop {
+            // Fetch the events emitted since the last poll
+            let response = client
+                .get_account_events(
+                    account,
+                    &self.event_handle,
+                    &self.field_name,
+                    Some(next_sequence_number),
+                    Some(MAX_EVENTS_PER_POLL),
+                )
+                .await?;
+            summary.num_polls += 1;
+            summary.last_ledger_version = Some(response.state().version);
+
+            // Print the new events
+            let events = response.into_inner();
+            let num_events = events.len();
+            for event in events {
+                next_sequence_number = event.sequence_number.0 + 1;
+                self.watch_options.print_update(&EventUpdate { event });
+                summary.num_updates += 1;
+                if self.watch_options.is_done(summary.num_updates) {
+                    return Ok(summary);
+                }
+            }
+
+            // If a full page was returned, there may be more events to fetch
+            if num_events == MAX_EVENTS_PER_POLL as usize {
+                continue;
+            }
+            if !self.watch_options.wait_for_next_poll().await {
+                return Ok(summary);
+            }
+        }
+    }
+}
@@ -34,7 +34,10 @@ use aptos_genesis::{
 use aptos_logger::info;
 use aptos_types::{
     account_address::{AccountAddress, AccountAddressWithChecks},
-    on_chain_config::{OnChainConsensusConfig, OnChainExecutionConfig},
+    on_chain_config::{
+        FeatureFlagPreset, FeatureFlagPresetConfig, Features, OnChainConsensusConfig,
+        OnChainExecutionConfig,
+    },
 };
 use aptos_vm_genesis::{default_gas_schedule, AccountBalance, EmployeePool};
 use async_trait::async_trait;
@@ -234,6 +237,7 @@ pub fn fetch_mainnet_genesis_info(git_options: GitOptions) -> CliTypedResult<Mai
     )?;
 
     let framework = client.get_framework()?;
+    let initial_features_override = load_feature_flag_preset(&client, &layout)?;
     Ok(MainnetGenesisInfo::new(
         layout.chain_id,
         accounts,
@@ -257,7 +261,7 @@ pub fn fetch_mainnet_genesis_info(git_options: GitOptions) -> CliTypedResult<Mai
             consensus_config: OnChainConsensusConfig::default_for_genesis(),
             execution_config: OnChainExecutionConfig::default_for_genesis(),
             gas_schedule: default_gas_schedule(),
-            initial_features_override: None,
+            initial_features_override,
         },
     )?)
 }
@@ -276,6 +280,7 @@ pub fn fetch_genesis_info(git_options: GitOptions) -> CliTypedResult<GenesisInfo
 
     let validators = get_validator_configs(&client, &layout, false).map_err(parse_error)?;
     let framework = client.get_framework()?;
+    let initial_features_override = load_feature_flag_preset(&client, &layout)?;
     Ok(GenesisInfo::new(
         layout.chain_id,
         layout.root_key.unwrap(),
@@ -298,11 +303,28 @@ pub fn fetch_genesis_info(git_options: GitOptions) -> CliTypedResult<GenesisInfo
             consensus_config: layout.on_chain_consensus_config,
             execution_config: layout.on_chain_execution_config,
             gas_schedule: default_gas_schedule(),
-            initial_features_override: None,
+            initial_features_override,
         },
     )?)
 }
 
+/// Loads the feature flag preset of the layout (if any). Preset files are read from the
+/// genesis repository.
+fn load_feature_flag_preset(client: &Client, layout: &Layout) -> CliTypedResult<Option<Features>> {
+    let preset = match &layout.feature_flag_preset {
+        Some(FeatureFlagPresetConfig::File(path)) => {
+            FeatureFlagPreset::from_definition(client.get(path)?)
+        },
+        Some(preset_config) => preset_config.load(),
+        None => return Ok(None),
+    };
+    preset
+        .map(|preset| Some(preset.to_features()))
+        .map_err(|error| {
+            CliError::UnexpectedError(format!("Failed to load the feature flag preset: {}", error))
+        })
+}
+
 fn parse_error(errors: Vec<String>) -> CliError {
     eprintln!(
         "Failed to parse genesis inputs:\n{}",
@@ -6,6 +6,7 @@
 pub mod account;
 pub mod common;
 pub mod config;
+pub mod event;
 pub mod ffi;
 pub mod genesis;
 pub mod governance;
@@ -34,6 +35,8 @@ pub enum Tool {
     #[clap(subcommand)]
     Config(config::ConfigTool),
     #[clap(subcommand)]
+    Event(event::EventTool),
+    #[clap(subcommand)]
     Genesis(genesis::GenesisTool),
     #[clap(subcommand)]
     Governance(governance::GovernanceTool),
@@ -58,6 +61,7 @@ impl Tool {
         match self {
             Account(tool) => tool.execute().await,
             Config(tool) => tool.execute().await,
+            Event(tool) => tool.execute().await,
             Genesis(tool) => tool.execute().await,
             Governance(tool) => tool.execute().await,
             Info(tool) => tool.execute_serialized().await,
@@ -0,0 +1,192 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    common::{
+        types::{CliCommand, CliError, CliTypedResult, TransactionOptions, TransactionSummary},
+        utils::profile_or_submit,
+    },
+    move_tool::PackagePublicationData,
+};
+use aptos_framework::chunked_publish::{
+    chunk_package, get_num_staged_chunks, StagingArea, CHUNK_SIZE_IN_BYTES,
+    LARGE_PACKAGES_MODULE_ADDRESS,
+};
+use aptos_rest_client::{
+    aptos_api_types::{AptosError, AptosErrorCode},
+    error::{AptosErrorResponse, RestError},
+    Client,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{EntryFunction, TransactionPayload},
+};
+use async_trait::async_trait;
+use clap::Parser;
+use move_core_types::{ident_str, language_storage::ModuleId};
+
+#[derive(Default, Parser)]
+pub struct ChunkedPublishOption {
+    /// Whether to publish the package in chunks
+    ///
+    /// This allows publishing packages that exceed the maximum transaction size. The package
+    /// is staged under the sender's account over multiple transactions using the
+    /// `large_packages` module, and published by the last transaction. If a previous chunked
+    /// publish of the same package was interrupted, it is resumed from the last staged chunk.
+    #[clap(long)]
+    pub(crate) chunked_publish: bool,
+
+    #[clap(flatten)]
+    pub(crate) large_packages_module_option: LargePackagesModuleOption,
+}
+
+#[derive(Parser)]
+pub struct LargePackagesModuleOption {
+    /// Address of the `large_packages` module used to stage chunked packages
+    #[clap(
+        long,
+        default_value = LARGE_PACKAGES_MODULE_ADDRESS,
+        value_parser = crate::common::types::load_account_arg
+    )]
+    pub(crate) large_packages_module_address: AccountAddress,
+}
+
+impl Default for LargePackagesModuleOption {
+    fn default() -> Self {
+        Self {
+            large_packages_module_address: AccountAddress::from_hex_literal(
+                LARGE_PACKAGES_MODULE_ADDRESS,
+            )
+            .expect("The large packages module address should be valid"),
+        }
+    }
+}
+
+/// Publishes the package in chunks, resuming from the staging area on chain (if any).
+/// Returns the summary of the transaction that published the package.
+pub(crate) async fn submit_chunked_publish(
+    package_publication_data: PackagePublicationData,
+    large_packages_module_address: AccountAddress,
+    txn_options: &TransactionOptions,
+) -> CliTypedResult<TransactionSummary> {
+    let chunks = chunk_package(
+        &package_publication_data.metadata_serialized,
+        &package_publication_data.compiled_units,
+        CHUNK_SIZE_IN_BYTES,
+    );
+    let num_chunks = chunks.len();
+
+    // Identify the chunks that have already been staged
+    let client = txn_options
+        .rest_options
+        .client(&txn_options.profile_options)?;
+    let staging_area = get_staging_area(
+        &client,
+        txn_options.sender_address()?,
+        large_packages_module_address,
+    )
+    .await?;
+    let num_staged_chunks =
+        get_num_staged_chunks(&chunks, staging_area.as_ref()).ok_or_else(|| {
+            CliError::UnexpectedError(
+                "The staging area of the account does not match the package! To publish the \
+                package, clear the staging area via `aptos move clear-staging-area`"
+                    .to_string(),
+            )
+        })?;
+    if num_staged_chunks > 0 {
+        eprintln!(
+            "Resuming the chunked publish ({} of {} chunks already staged)",
+            num_staged_chunks, num_chunks
+        );
+    }
+
+    // Stage the remaining chunks (the last chunk publishes the package)
+    let mut summary = None;
+    for (chunk_index, chunk) in chunks.into_iter().enumerate().skip(num_staged_chunks) {
+        let publish = chunk_index == num_chunks - 1;
+        eprintln!(
+            "Submitting chunk {} of {} ({} bytes)",
+            chunk_index + 1,
+            num_chunks,
+            chunk.size()
+        );
+        let chunk_summary = TransactionSummary::from(
+            txn_options
+                .submit_transaction(chunk.into_payload(large_packages_module_address, publish))
+                .await?,
+        );
+        if chunk_summary.success == Some(false) {
+            return Err(CliError::ApiError(format!(
+                "Failed to stage chunk {} of {}: {}. The chunked publish can be resumed \
+                by re-running the command",
+                chunk_index + 1,
+                num_chunks,
+                chunk_summary.vm_status.unwrap_or_default()
+            )));
+        }
+        summary = Some(chunk_summary);
+    }
+
+    // At least the last chunk is always submitted
+    summary.ok_or_else(|| CliError::UnexpectedError("No chunks were submitted".to_string()))
+}
+
+/// Returns the staging area of the given account (if any)
+pub(crate) async fn get_staging_area(
+    client: &Client,
+    owner: AccountAddress,
+    large_packages_module_address: AccountAddress,
+) -> CliTypedResult<Option<StagingArea>> {
+    match client
+        .get_account_resource_bcs::<StagingArea>(
+            owner,
+            &StagingArea::struct_tag(large_packages_module_address),
+        )
+        .await
+    {
+        Ok(response) => Ok(Some(response.into_inner())),
+        Err(RestError::Api(AptosErrorResponse {
+            error:
+                AptosError {
+                    error_code: AptosErrorCode::ResourceNotFound,
+                    ..
+                },
+            ..
+        })) => Ok(None),
+        Err(error) => Err(error.into()),
+    }
+}
+
+/// Clears the staging area of an interrupted chunked publish
+///
+/// This removes any package chunks staged under the sender's account, e.g., to restart
+/// the chunked publish of a package that has changed since it was partially staged.
+#[derive(Parser)]
+pub struct ClearStagingArea {
+    #[clap(flatten)]
+    pub(crate) large_packages_module_option: LargePackagesModuleOption,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+#[async_trait]
+impl CliCommand<TransactionSummary> for ClearStagingArea {
+    fn command_name(&self) -> &'static str {
+        "ClearStagingArea"
+    }
+
+    async fn execute(self) -> CliTypedResult<TransactionSummary> {
+        let payload = TransactionPayload::EntryFunction(EntryFunction::new(
+            ModuleId::new(
+                self.large_packages_module_option
+                    .large_packages_module_address,
+                ident_str!("large_packages").to_owned(),
+            ),
+            ident_str!("cleanup").to_owned(),
+            vec![],
+            vec![],
+        ));
+        profile_or_submit(payload, &self.txn_options).await
+    }
+}
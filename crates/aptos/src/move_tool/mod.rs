@@ -2,12 +2,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod aptos_debug_natives;
+pub mod chunked_publish;
 pub mod coverage;
 mod disassembler;
 mod manifest;
 pub mod package_hooks;
 mod show;
 pub mod stored_package;
+mod upgrade_plan;
 
 use crate::{
     account::derive_resource_account::ResourceAccountSeed,
@@ -26,6 +28,7 @@ use crate::{
     },
     governance::CompileScriptFunction,
     move_tool::{
+        chunked_publish::{submit_chunked_publish, ChunkedPublishOption, ClearStagingArea},
         coverage::SummaryCoverage,
         disassembler::Disassemble,
         manifest::{Dependency, ManifestNamedAddress, MovePackageManifest, PackageInfo},
@@ -78,6 +81,7 @@ use tokio::task;
 pub enum MoveTool {
     BuildPublishPayload(BuildPublishPayload),
     Clean(CleanPackage),
+    ClearStagingArea(ClearStagingArea),
     Compile(CompilePackage),
     CompileScript(CompileScript),
     #[clap(subcommand)]
@@ -97,6 +101,7 @@ pub enum MoveTool {
     #[clap(subcommand, hide = true)]
     Show(show::ShowTool),
     Test(TestPackage),
+    UpgradePlan(upgrade_plan::UpgradePlan),
     VerifyPackage(VerifyPackage),
     View(ViewFunction),
 }
@@ -106,6 +111,7 @@ impl MoveTool {
         match self {
             MoveTool::BuildPublishPayload(tool) => tool.execute_serialized().await,
             MoveTool::Clean(tool) => tool.execute_serialized().await,
+            MoveTool::ClearStagingArea(tool) => tool.execute_serialized().await,
             MoveTool::Compile(tool) => tool.execute_serialized().await,
             MoveTool::CompileScript(tool) => tool.execute_serialized().await,
             MoveTool::Coverage(tool) => tool.execute().await,
@@ -127,6 +133,7 @@ impl MoveTool {
             MoveTool::RunScript(tool) => tool.execute_serialized().await,
             MoveTool::Show(tool) => tool.execute_serialized().await,
             MoveTool::Test(tool) => tool.execute_serialized().await,
+            MoveTool::UpgradePlan(tool) => tool.execute_serialized().await,
             MoveTool::VerifyPackage(tool) => tool.execute_serialized().await,
             MoveTool::View(tool) => tool.execute_serialized().await,
         }
@@ -628,6 +635,8 @@ pub struct IncludedArtifactsArgs {
 pub struct PublishPackage {
     #[clap(flatten)]
     pub(crate) override_size_check_option: OverrideSizeCheckOption,
+    #[clap(flatten)]
+    pub(crate) chunked_publish_option: ChunkedPublishOption,
 
     #[clap(flatten)]
     pub(crate) included_artifacts_args: IncludedArtifactsArgs,
@@ -637,7 +646,7 @@ pub struct PublishPackage {
     pub(crate) txn_options: TransactionOptions,
 }
 
-struct PackagePublicationData {
+pub(crate) struct PackagePublicationData {
     metadata_serialized: Vec<u8>,
     compiled_units: Vec<Vec<u8>>,
     payload: TransactionPayload,
@@ -681,11 +690,15 @@ impl TryInto<PackagePublicationData> for &PublishPackage {
         );
         let size = bcs::serialized_size(&payload)?;
         println!("package size {} bytes", size);
-        if !self.override_size_check_option.value && size > MAX_PUBLISH_PACKAGE_SIZE {
+        if !self.override_size_check_option.value
+            && !self.chunked_publish_option.chunked_publish
+            && size > MAX_PUBLISH_PACKAGE_SIZE
+        {
             return Err(CliError::UnexpectedError(format!(
                 "The package is larger than {} bytes ({} bytes)! To lower the size \
                 you may want to include fewer artifacts via `--included-artifacts`. \
-                You can also override this check with `--override-size-check",
+                You can also publish the package in chunks via `--chunked-publish`, \
+                or override this check with `--override-size-check",
                 MAX_PUBLISH_PACKAGE_SIZE, size
             )));
         }
@@ -802,7 +815,18 @@ impl CliCommand<TransactionSummary> for PublishPackage {
 
     async fn execute(self) -> CliTypedResult<TransactionSummary> {
         let package_publication_data: PackagePublicationData = (&self).try_into()?;
-        profile_or_submit(package_publication_data.payload, &self.txn_options).await
+        if self.chunked_publish_option.chunked_publish {
+            submit_chunked_publish(
+                package_publication_data,
+                self.chunked_publish_option
+                    .large_packages_module_option
+                    .large_packages_module_address,
+                &self.txn_options,
+            )
+            .await
+        } else {
+            profile_or_submit(package_publication_data.payload, &self.txn_options).await
+        }
     }
 }
 
@@ -813,6 +837,12 @@ impl CliCommand<String> for BuildPublishPayload {
     }
 
     async fn execute(self) -> CliTypedResult<String> {
+        if self.publish_package.chunked_publish_option.chunked_publish {
+            return Err(CliError::CommandArgumentError(
+                "`--chunked-publish` is not supported when building a publication payload"
+                    .to_string(),
+            ));
+        }
         let package_publication_data: PackagePublicationData =
             (&self.publish_package).try_into()?;
         // Extract entry function data from publication payload.
@@ -3,7 +3,7 @@
 
 use anyhow::bail;
 use aptos_framework::{
-    natives::code::{ModuleMetadata, PackageMetadata, PackageRegistry, UpgradePolicy},
+    natives::code::{ModuleMetadata, PackageDep, PackageMetadata, PackageRegistry, UpgradePolicy},
     unzip_metadata_str,
 };
 use aptos_rest_client::Client;
@@ -110,6 +110,10 @@ impl<'a> CachedPackageMetadata<'a> {
         unzip_metadata_str(&self.metadata.manifest)
     }
 
+    pub fn deps(&self) -> &[PackageDep] {
+        &self.metadata.deps
+    }
+
     pub fn module_names(&self) -> Vec<&str> {
         self.metadata
             .modules
@@ -0,0 +1,412 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{CachedPackageRegistry, IncludedArtifactsArgs};
+use crate::common::types::{
+    CliCommand, CliError, CliTypedResult, MovePackageDir, TransactionOptions,
+};
+use aptos_framework::{
+    natives::code::{PackageDep, UpgradePolicy},
+    BuildOptions, BuiltPackage,
+};
+use aptos_rest_client::Client;
+use aptos_types::account_address::AccountAddress;
+use async_trait::async_trait;
+use clap::Parser;
+use move_binary_format::{
+    access::ModuleAccess, compatibility::Compatibility, normalized, CompiledModule,
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Analyzes the impact of upgrading a package against the version published on-chain
+///
+/// This builds the local package and compares it to the package currently published
+/// by the sender account. It reports a compatibility verdict for each module, estimates
+/// the gas required to publish the upgrade (via simulation), and detects packages
+/// on-chain that depend on this package and might break. Nothing is submitted.
+#[derive(Parser)]
+pub struct UpgradePlan {
+    /// Additional accounts to scan for packages that depend on this package
+    ///
+    /// The sender account is always scanned.
+    #[clap(long, value_parser = crate::common::types::load_account_arg, num_args = 0..)]
+    pub(crate) dependent_accounts: Vec<AccountAddress>,
+
+    #[clap(flatten)]
+    pub(crate) included_artifacts_args: IncludedArtifactsArgs,
+    #[clap(flatten)]
+    pub(crate) move_options: MovePackageDir,
+    #[clap(flatten)]
+    pub(crate) txn_options: TransactionOptions,
+}
+
+/// The impact analysis of upgrading a package
+#[derive(Debug, Serialize)]
+pub struct UpgradePlanSummary {
+    pub package_name: String,
+    pub publisher: AccountAddress,
+    /// The upgrade number of the published package (if it is published)
+    pub onchain_upgrade_number: Option<u64>,
+    /// The upgrade policy of the published package (if it is published)
+    pub onchain_upgrade_policy: Option<String>,
+    pub local_upgrade_policy: String,
+    /// True iff the upgrade is expected to be accepted on-chain
+    pub upgrade_allowed: bool,
+    /// The reasons the upgrade would be rejected (if any)
+    pub upgrade_errors: Vec<String>,
+    pub modules: Vec<ModuleUpgradeVerdict>,
+    pub gas_estimate: Option<PublishGasEstimate>,
+    pub dependent_packages: Vec<DependentPackage>,
+}
+
+/// The compatibility verdict for a single module
+#[derive(Debug, Serialize)]
+pub struct ModuleUpgradeVerdict {
+    pub module_name: String,
+    pub verdict: ModuleVerdict,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ModuleVerdict {
+    /// The module is not yet published on-chain
+    New,
+    /// The module bytecode is identical to the published module
+    Unchanged,
+    /// The module is compatible with the published module
+    Compatible,
+    /// The module is incompatible with the published module
+    Incompatible,
+    /// The published module is missing from the local package
+    Removed,
+}
+
+impl ModuleVerdict {
+    /// Returns true iff the verdict would break the upgrade (and its dependents)
+    pub fn is_breaking(&self) -> bool {
+        matches!(self, ModuleVerdict::Incompatible | ModuleVerdict::Removed)
+    }
+}
+
+/// The estimated cost of publishing the package
+#[derive(Debug, Serialize)]
+pub struct PublishGasEstimate {
+    pub success: bool,
+    pub vm_status: String,
+    pub gas_used: u64,
+    pub gas_unit_price: u64,
+    pub estimated_cost_octas: u64,
+}
+
+/// A package on-chain that depends on the package being upgraded
+#[derive(Debug, Serialize)]
+pub struct DependentPackage {
+    pub account: AccountAddress,
+    pub package_name: String,
+    /// True iff any module of the upgraded package is incompatible or removed
+    pub at_risk: bool,
+}
+
+#[async_trait]
+impl CliCommand<UpgradePlanSummary> for UpgradePlan {
+    fn command_name(&self) -> &'static str {
+        "UpgradePlan"
+    }
+
+    async fn execute(self) -> CliTypedResult<UpgradePlanSummary> {
+        // Build the package locally
+        let build_options = BuildOptions {
+            install_dir: self.move_options.output_dir.clone(),
+            ..self
+                .included_artifacts_args
+                .included_artifacts
+                .build_options(
+                    self.move_options.dev,
+                    self.move_options.skip_fetch_latest_git_deps,
+                    self.move_options.named_addresses(),
+                    self.move_options.bytecode_version,
+                    self.move_options.compiler_version,
+                    self.move_options.skip_attribute_checks,
+                    self.move_options.check_test_code,
+                )
+        };
+        let package = BuiltPackage::build(self.move_options.get_package_path()?, build_options)
+            .map_err(|e| CliError::MoveCompilationError(format!("{:#}", e)))?;
+        let metadata = package.extract_metadata()?;
+        let package_name = package.name().to_string();
+
+        // Fetch the published package (if any) from the sender account
+        let publisher = self.txn_options.sender_address()?;
+        let url = self
+            .txn_options
+            .rest_options
+            .url(&self.txn_options.profile_options)?;
+        let client = Client::new(url.clone());
+        let publisher_registry = CachedPackageRegistry::create(url.clone(), publisher)
+            .await
+            .ok();
+        let onchain_package = match &publisher_registry {
+            Some(registry) => registry.get_package(&package_name).await.ok(),
+            None => None,
+        };
+
+        // Check the upgrade policy and the compatibility of each module
+        let mut upgrade_errors = vec![];
+        let mut modules = vec![];
+        if let Some(onchain_package) = &onchain_package {
+            upgrade_errors.extend(check_upgrade_policy(
+                onchain_package.upgrade_policy(),
+                metadata.upgrade_policy,
+            ));
+
+            let onchain_modules =
+                fetch_onchain_modules(&client, publisher, &onchain_package.module_names()).await?;
+            modules = check_module_compatibility(
+                &onchain_modules,
+                package.modules(),
+                onchain_package.upgrade_policy(),
+            );
+        } else {
+            for module in package.modules() {
+                modules.push(ModuleUpgradeVerdict {
+                    module_name: module.self_id().name().to_string(),
+                    verdict: ModuleVerdict::New,
+                    details: None,
+                });
+            }
+        }
+        for module in &modules {
+            if module.verdict.is_breaking() {
+                upgrade_errors.push(format!(
+                    "Module `{}` is {:?}",
+                    module.module_name, module.verdict
+                ));
+            }
+        }
+
+        // Estimate the gas required to publish the package
+        let payload = aptos_cached_packages::aptos_stdlib::code_publish_package_txn(
+            bcs::to_bytes(&metadata).expect("PackageMetadata has BCS"),
+            package.extract_code(),
+        );
+        let gas_estimate = match self.txn_options.simulate_transaction(payload).await {
+            Ok(simulated_txn) => {
+                let gas_used = simulated_txn.info.gas_used.0;
+                let gas_unit_price = simulated_txn.request.gas_unit_price.0;
+                Some(PublishGasEstimate {
+                    success: simulated_txn.info.success,
+                    vm_status: simulated_txn.info.vm_status,
+                    gas_used,
+                    gas_unit_price,
+                    estimated_cost_octas: gas_used * gas_unit_price,
+                })
+            },
+            Err(error) => {
+                eprintln!("Failed to estimate the gas for publishing: {}", error);
+                None
+            },
+        };
+
+        // Find the packages on-chain that depend on this package
+        let package_dep = PackageDep {
+            account: publisher,
+            package_name: package_name.clone(),
+        };
+        let at_risk = modules.iter().any(|module| module.verdict.is_breaking());
+        let mut dependent_packages = vec![];
+        if let Some(registry) = &publisher_registry {
+            collect_dependent_packages(
+                registry,
+                publisher,
+                &package_dep,
+                at_risk,
+                &mut dependent_packages,
+            )
+            .await;
+        }
+        for account in self.dependent_accounts {
+            if account == publisher {
+                continue; // The sender account has already been scanned
+            }
+            match CachedPackageRegistry::create(url.clone(), account).await {
+                Ok(registry) => {
+                    collect_dependent_packages(
+                        &registry,
+                        account,
+                        &package_dep,
+                        at_risk,
+                        &mut dependent_packages,
+                    )
+                    .await;
+                },
+                Err(error) => {
+                    // Accounts without a package registry have no dependent packages
+                    eprintln!(
+                        "Skipping account {} without a package registry: {}",
+                        account, error
+                    );
+                },
+            }
+        }
+
+        Ok(UpgradePlanSummary {
+            package_name,
+            publisher,
+            onchain_upgrade_number: onchain_package.as_ref().map(|p| p.upgrade_number()),
+            onchain_upgrade_policy: onchain_package
+                .as_ref()
+                .map(|p| p.upgrade_policy().to_string()),
+            local_upgrade_policy: metadata.upgrade_policy.to_string(),
+            upgrade_allowed: upgrade_errors.is_empty(),
+            upgrade_errors,
+            modules,
+            gas_estimate,
+            dependent_packages,
+        })
+    }
+}
+
+/// Returns the reasons (if any) the upgrade policies prevent the upgrade. This
+/// mirrors the checks performed by `0x1::code` when publishing a package.
+fn check_upgrade_policy(onchain_policy: UpgradePolicy, local_policy: UpgradePolicy) -> Vec<String> {
+    let mut errors = vec![];
+    if onchain_policy == UpgradePolicy::immutable() {
+        errors.push("The published package is immutable and cannot be upgraded".to_string());
+    }
+    if local_policy.policy < onchain_policy.policy {
+        errors.push(format!(
+            "The upgrade policy cannot be weakened (from `{}` to `{}`)",
+            onchain_policy, local_policy
+        ));
+    }
+    errors
+}
+
+/// Fetches and deserializes the given modules published at the account
+async fn fetch_onchain_modules(
+    client: &Client,
+    account: AccountAddress,
+    module_names: &[&str],
+) -> CliTypedResult<BTreeMap<String, CompiledModule>> {
+    let mut modules = BTreeMap::new();
+    for module_name in module_names {
+        let bytes = client
+            .get_account_module_bcs(account, module_name)
+            .await?
+            .into_inner();
+        let module = CompiledModule::deserialize(&bytes).map_err(|error| {
+            CliError::UnexpectedError(format!(
+                "Failed to deserialize on-chain module {}: {:?}",
+                module_name, error
+            ))
+        })?;
+        modules.insert(module_name.to_string(), module);
+    }
+    Ok(modules)
+}
+
+/// Compares the local modules with the published modules and returns a verdict per module
+fn check_module_compatibility<'a>(
+    onchain_modules: &BTreeMap<String, CompiledModule>,
+    local_modules: impl Iterator<Item = &'a CompiledModule>,
+    onchain_policy: UpgradePolicy,
+) -> Vec<ModuleUpgradeVerdict> {
+    // Friend functions are treated as private (as is the case on-chain)
+    let compatibility = Compatibility::new(true, true, false);
+
+    let mut verdicts = vec![];
+    let mut local_module_names = vec![];
+    for local_module in local_modules {
+        let module_name = local_module.self_id().name().to_string();
+        local_module_names.push(module_name.clone());
+
+        let (verdict, details) = match onchain_modules.get(&module_name) {
+            None => (ModuleVerdict::New, None),
+            Some(onchain_module) if onchain_module == local_module => {
+                (ModuleVerdict::Unchanged, None)
+            },
+            Some(_) if onchain_policy == UpgradePolicy::arbitrary() => (
+                ModuleVerdict::Compatible,
+                Some("Compatibility is not enforced for arbitrary packages".to_string()),
+            ),
+            Some(onchain_module) => {
+                let old_module = normalized::Module::new(onchain_module);
+                let new_module = normalized::Module::new(local_module);
+                match compatibility.check(&old_module, &new_module) {
+                    Ok(()) => (ModuleVerdict::Compatible, None),
+                    Err(error) => (
+                        ModuleVerdict::Incompatible,
+                        Some(format!("{:?}", error.major_status())),
+                    ),
+                }
+            },
+        };
+        verdicts.push(ModuleUpgradeVerdict {
+            module_name,
+            verdict,
+            details,
+        });
+    }
+
+    // Published modules cannot be removed by an upgrade
+    for module_name in onchain_modules.keys() {
+        if !local_module_names.contains(module_name) {
+            verdicts.push(ModuleUpgradeVerdict {
+                module_name: module_name.clone(),
+                verdict: ModuleVerdict::Removed,
+                details: None,
+            });
+        }
+    }
+    verdicts
+}
+
+/// Adds the packages in the registry that depend on the given package
+async fn collect_dependent_packages(
+    registry: &CachedPackageRegistry,
+    account: AccountAddress,
+    package_dep: &PackageDep,
+    at_risk: bool,
+    dependent_packages: &mut Vec<DependentPackage>,
+) {
+    for package_name in registry.package_names() {
+        if let Ok(package) = registry.get_package(package_name).await {
+            if package.deps().contains(package_dep) {
+                dependent_packages.push(DependentPackage {
+                    account,
+                    package_name: package_name.to_string(),
+                    at_risk,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_upgrade_policy() {
+        // Compatible upgrades are allowed
+        assert!(check_upgrade_policy(UpgradePolicy::compat(), UpgradePolicy::compat()).is_empty());
+        assert!(
+            check_upgrade_policy(UpgradePolicy::arbitrary(), UpgradePolicy::compat()).is_empty()
+        );
+
+        // Weakening the policy is not allowed
+        assert_eq!(
+            check_upgrade_policy(UpgradePolicy::compat(), UpgradePolicy::arbitrary()).len(),
+            1
+        );
+
+        // Immutable packages cannot be upgraded
+        assert_eq!(
+            check_upgrade_policy(UpgradePolicy::immutable(), UpgradePolicy::immutable()).len(),
+            1
+        );
+    }
+}
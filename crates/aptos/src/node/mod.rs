@@ -3,8 +3,12 @@
 
 pub mod analyze;
 pub mod local_testnet;
+pub mod waypoint;
 
-use self::local_testnet::RunLocalTestnet;
+use self::{
+    local_testnet::RunLocalTestnet,
+    waypoint::{CreateWaypoint, VerifyWaypoint},
+};
 use crate::{
     common::{
         types::{
@@ -67,6 +71,7 @@ pub enum NodeTool {
     AnalyzeValidatorPerformance(AnalyzeValidatorPerformance),
     BootstrapDb(BootstrapDb),
     CheckNetworkConnectivity(CheckNetworkConnectivity),
+    CreateWaypoint(CreateWaypoint),
     GetPerformance(GetPerformance),
     GetStakePool(GetStakePool),
     InitializeValidator(InitializeValidator),
@@ -79,6 +84,7 @@ pub enum NodeTool {
     RunLocalTestnet(RunLocalTestnet),
     UpdateConsensusKey(UpdateConsensusKey),
     UpdateValidatorNetworkAddresses(UpdateValidatorNetworkAddresses),
+    VerifyWaypoint(VerifyWaypoint),
 }
 
 impl NodeTool {
@@ -91,6 +97,7 @@ impl NodeTool {
                     .await
             },
             CheckNetworkConnectivity(tool) => tool.execute_serialized().await,
+            CreateWaypoint(tool) => tool.execute_serialized().await,
             GetPerformance(tool) => tool.execute_serialized().await,
             GetStakePool(tool) => tool.execute_serialized().await,
             InitializeValidator(tool) => tool.execute_serialized().await,
@@ -106,6 +113,7 @@ impl NodeTool {
                 .map(|_| "".to_string()),
             UpdateConsensusKey(tool) => tool.execute_serialized().await,
             UpdateValidatorNetworkAddresses(tool) => tool.execute_serialized().await,
+            VerifyWaypoint(tool) => tool.execute_serialized().await,
         }
     }
 }
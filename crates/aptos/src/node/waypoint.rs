@@ -0,0 +1,489 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::{
+    types::{CliCommand, CliError, CliTypedResult, ProfileOptions, PromptOptions, RestOptions},
+    utils::{check_if_file_exists, write_to_file},
+};
+use anyhow::{anyhow, ensure};
+use aptos_backup_cli::{
+    backup_types::epoch_ending::restore::EpochHistoryRestoreController,
+    metadata::{self, cache::MetadataCacheOpt},
+    storage::{
+        command_adapter::CommandAdapterOpt, local_fs::LocalFsOpt, BackupStorage, StorageOpt,
+    },
+    utils::{ConcurrentDownloadsOpt, GlobalRestoreOptions, RestoreRunMode, TrustedWaypointOpt},
+};
+use aptos_config::config::{NodeConfig, WaypointConfig};
+use aptos_rest_client::Client;
+use aptos_types::{ledger_info::LedgerInfo, transaction::Version, waypoint::Waypoint};
+use async_trait::async_trait;
+use clap::{Parser, ValueEnum};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::PathBuf, sync::Arc};
+
+/// The formats in which waypoints can be emitted
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum WaypointFormat {
+    /// The plain waypoint (e.g., for a `waypoint.txt` file referenced by `from_file`)
+    #[default]
+    Text,
+    /// A node config snippet that sets the waypoint (i.e., `base.waypoint.from_config`)
+    NodeConfig,
+}
+
+/// Formats the waypoint so that it can be consumed by the node config
+pub fn format_waypoint(waypoint: Waypoint, format: WaypointFormat) -> anyhow::Result<String> {
+    #[derive(Serialize)]
+    struct BaseConfigSnippet {
+        waypoint: WaypointConfig,
+    }
+
+    #[derive(Serialize)]
+    struct NodeConfigSnippet {
+        base: BaseConfigSnippet,
+    }
+
+    match format {
+        WaypointFormat::Text => Ok(waypoint.to_string()),
+        WaypointFormat::NodeConfig => Ok(serde_yaml::to_string(&NodeConfigSnippet {
+            base: BaseConfigSnippet {
+                waypoint: WaypointConfig::FromConfig(waypoint),
+            },
+        })?),
+    }
+}
+
+/// Returns the epoch boundary waypoint for the epoch ending ledger info of the
+/// given epoch. If no epoch is specified, the latest epoch ending is used.
+pub fn select_waypoint(
+    epoch_endings: &[LedgerInfo],
+    epoch: Option<u64>,
+) -> anyhow::Result<Waypoint> {
+    let ledger_info = match epoch {
+        Some(epoch) => epoch_endings
+            .iter()
+            .find(|ledger_info| ledger_info.epoch() == epoch)
+            .ok_or_else(|| anyhow!("No epoch ending ledger info was found for epoch {}", epoch))?,
+        None => epoch_endings
+            .last()
+            .ok_or_else(|| anyhow!("No epoch ending ledger infos were found"))?,
+    };
+    Waypoint::new_epoch_boundary(ledger_info)
+}
+
+/// Verifies the waypoint against the epoch ending ledger info at the waypoint
+/// version. Returns the epoch of the ledger info if the waypoint is valid.
+pub fn verify_waypoint(epoch_endings: &[LedgerInfo], waypoint: &Waypoint) -> anyhow::Result<u64> {
+    let latest_version = epoch_endings
+        .last()
+        .map(|ledger_info| ledger_info.version())
+        .ok_or_else(|| anyhow!("No epoch ending ledger infos were found"))?;
+    let ledger_info = epoch_endings
+        .iter()
+        .find(|ledger_info| ledger_info.version() == waypoint.version())
+        .ok_or_else(|| {
+            if waypoint.version() > latest_version {
+                anyhow!(
+                    "The waypoint version ({}) is ahead of the latest epoch ending version ({})",
+                    waypoint.version(),
+                    latest_version
+                )
+            } else {
+                anyhow!(
+                    "The waypoint version ({}) is not an epoch ending version",
+                    waypoint.version()
+                )
+            }
+        })?;
+    waypoint.verify(ledger_info)?;
+    Ok(ledger_info.epoch())
+}
+
+/// Fetches the epoch ending ledger infos from the connected node, and verifies them
+/// from the latest trusted waypoint to the latest epoch. If no waypoints are trusted,
+/// the genesis ledger info of the node is trusted.
+pub async fn fetch_epoch_ending_ledger_infos(
+    client: &Client,
+    trusted_waypoints: &HashMap<Version, Waypoint>,
+) -> anyhow::Result<Vec<LedgerInfo>> {
+    // Fetch and verify the ledger info to start from
+    let start_waypoint = trusted_waypoints
+        .values()
+        .max_by_key(|waypoint| waypoint.version())
+        .copied();
+    let start_version = start_waypoint.map_or(0, |waypoint| waypoint.version());
+    let start_ledger_info = client
+        .get_ledger_info_with_signatures(Some(start_version))
+        .await?
+        .into_inner();
+    if let Some(start_waypoint) = start_waypoint {
+        start_waypoint.verify(start_ledger_info.ledger_info())?;
+    }
+    ensure!(
+        start_ledger_info.ledger_info().ends_epoch(),
+        "The ledger info at version {} does not end an epoch",
+        start_version
+    );
+
+    // Fetch and verify the epoch change proofs until the latest epoch
+    let mut epoch_endings = vec![start_ledger_info.ledger_info().clone()];
+    loop {
+        let latest_epoch_ending = epoch_endings.last().expect("Epoch endings can't be empty");
+        let epoch_change_proof = client
+            .get_epoch_change_proof(Waypoint::new_epoch_boundary(latest_epoch_ending)?)
+            .await?
+            .into_inner();
+        if epoch_change_proof.ledger_info_with_sigs.is_empty() {
+            break;
+        }
+
+        let epoch_state = latest_epoch_ending
+            .next_epoch_state()
+            .ok_or_else(|| anyhow!("The epoch ending ledger info has no next epoch state"))?;
+        epoch_change_proof.verify(epoch_state)?;
+        for ledger_info_with_sigs in &epoch_change_proof.ledger_info_with_sigs {
+            let ledger_info = ledger_info_with_sigs.ledger_info();
+            let expected_epoch = epoch_endings.last().unwrap().next_block_epoch();
+            ensure!(
+                ledger_info.epoch() == expected_epoch,
+                "Unexpected epoch in the epoch change proof! Expected: {}, found: {}",
+                expected_epoch,
+                ledger_info.epoch()
+            );
+            if let Some(trusted_waypoint) = trusted_waypoints.get(&ledger_info.version()) {
+                trusted_waypoint.verify(ledger_info)?;
+            }
+            epoch_endings.push(ledger_info.clone());
+        }
+
+        if !epoch_change_proof.more {
+            break;
+        }
+    }
+
+    Ok(epoch_endings)
+}
+
+/// Loads the epoch ending ledger infos from the backup storage, and verifies
+/// them (from genesis) using the trusted waypoints and the signatures.
+pub async fn load_epoch_ending_ledger_infos(
+    storage: Arc<dyn BackupStorage>,
+    metadata_cache_opt: &MetadataCacheOpt,
+    trusted_waypoints: HashMap<Version, Waypoint>,
+    concurrent_downloads: usize,
+) -> anyhow::Result<Vec<LedgerInfo>> {
+    let metadata_view =
+        metadata::cache::sync_and_load(metadata_cache_opt, storage.clone(), concurrent_downloads)
+            .await?;
+    let epoch_ending_backups = metadata_view.select_epoch_ending_backups(Version::MAX)?;
+
+    let global_opt = GlobalRestoreOptions {
+        target_version: Version::MAX,
+        trusted_waypoints: Arc::new(trusted_waypoints),
+        run_mode: Arc::new(RestoreRunMode::Verify),
+        concurrent_downloads,
+        replay_concurrency_level: 0, // won't replay, doesn't matter
+    };
+    let epoch_history = EpochHistoryRestoreController::new(
+        epoch_ending_backups
+            .into_iter()
+            .map(|backup| backup.manifest)
+            .collect(),
+        global_opt,
+        storage,
+    )
+    .run()
+    .await?;
+
+    Ok(epoch_history.epoch_endings)
+}
+
+/// Options for selecting where the epoch ending ledger infos are read from
+///
+/// By default, the ledger infos are fetched from a connected node. If a backup
+/// storage is specified, the ledger infos are read from the backup instead.
+#[derive(Parser)]
+pub struct WaypointSourceOptions {
+    /// Directory of a local backup storage to read the epoch endings from
+    #[clap(long, group = "backup_storage")]
+    backup_local_fs_dir: Option<LocalFsOpt>,
+
+    /// Config file of a command adapter backup storage to read the epoch endings from
+    #[clap(long, group = "backup_storage")]
+    backup_command_adapter_config: Option<CommandAdapterOpt>,
+
+    #[clap(flatten)]
+    metadata_cache_opt: MetadataCacheOpt,
+    #[clap(flatten)]
+    concurrent_downloads: ConcurrentDownloadsOpt,
+    #[clap(flatten)]
+    trusted_waypoints: TrustedWaypointOpt,
+    #[clap(flatten)]
+    rest_options: RestOptions,
+    #[clap(flatten)]
+    profile_options: ProfileOptions,
+}
+
+impl WaypointSourceOptions {
+    /// Returns the verified epoch ending ledger infos from the selected source
+    async fn get_epoch_ending_ledger_infos(self) -> CliTypedResult<Vec<LedgerInfo>> {
+        let trusted_waypoints = self
+            .trusted_waypoints
+            .verify()
+            .map_err(|error| CliError::CommandArgumentError(error.to_string()))?;
+        if trusted_waypoints.is_empty() {
+            eprintln!(
+                "No trusted waypoints were provided! The genesis ledger info of the source is trusted."
+            );
+        }
+
+        let storage = match (self.backup_local_fs_dir, self.backup_command_adapter_config) {
+            (Some(local_fs_opt), _) => Some(StorageOpt::LocalFs(local_fs_opt)),
+            (None, Some(command_adapter_opt)) => {
+                Some(StorageOpt::CommandAdapter(command_adapter_opt))
+            },
+            (None, None) => None,
+        };
+        let epoch_endings = match storage {
+            Some(storage) => {
+                let metadata_cache_opt = self.metadata_cache_opt;
+                let concurrent_downloads = self.concurrent_downloads.get();
+                // hack: get around the higher-ranked lifetime error of `async_trait`
+                // (see `BootstrapDb`).
+                tokio::task::spawn_blocking(move || {
+                    let runtime = tokio::runtime::Runtime::new().unwrap();
+                    runtime.block_on(async move {
+                        load_epoch_ending_ledger_infos(
+                            storage.init_storage().await?,
+                            &metadata_cache_opt,
+                            trusted_waypoints,
+                            concurrent_downloads,
+                        )
+                        .await
+                    })
+                })
+                .await
+                .map_err(|error| CliError::UnexpectedError(error.to_string()))??
+            },
+            None => {
+                let client = self.rest_options.client(&self.profile_options)?;
+                fetch_epoch_ending_ledger_infos(&client, &trusted_waypoints).await?
+            },
+        };
+        Ok(epoch_endings)
+    }
+}
+
+/// Create a waypoint
+///
+/// Derives the waypoint of an epoch ending ledger info from a connected node
+/// or a trusted backup. The epoch endings are verified from the trusted waypoints
+/// (or genesis) before the waypoint is created.
+#[derive(Parser)]
+pub struct CreateWaypoint {
+    /// The epoch of the epoch ending ledger info (e.g., `0` for the genesis waypoint)
+    ///
+    /// Defaults to the latest epoch ending
+    #[clap(long)]
+    epoch: Option<u64>,
+
+    /// The format of the waypoint
+    #[clap(long, value_enum, default_value_t = WaypointFormat::Text)]
+    format: WaypointFormat,
+
+    /// Output file for the waypoint
+    ///
+    /// If not provided, the waypoint is only displayed
+    #[clap(long, value_parser)]
+    output_file: Option<PathBuf>,
+
+    #[clap(flatten)]
+    source_options: WaypointSourceOptions,
+    #[clap(flatten)]
+    prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<String> for CreateWaypoint {
+    fn command_name(&self) -> &'static str {
+        "CreateWaypoint"
+    }
+
+    async fn execute(self) -> CliTypedResult<String> {
+        if let Some(output_file) = &self.output_file {
+            check_if_file_exists(output_file, self.prompt_options)?;
+        }
+
+        let epoch_endings = self.source_options.get_epoch_ending_ledger_infos().await?;
+        let waypoint = select_waypoint(&epoch_endings, self.epoch)?;
+        let formatted_waypoint = format_waypoint(waypoint, self.format)?;
+        if let Some(output_file) = &self.output_file {
+            write_to_file(output_file, "Waypoint", formatted_waypoint.as_bytes())?;
+        }
+        Ok(formatted_waypoint)
+    }
+}
+
+/// Verify a waypoint
+///
+/// Verifies the given waypoint (or the waypoints configured in a node config) against
+/// the epoch ending ledger infos of a connected node or a trusted backup.
+#[derive(Parser)]
+pub struct VerifyWaypoint {
+    /// The waypoint to verify, e.g., `0:<hash>`
+    #[clap(
+        long,
+        group = "waypoint_source",
+        required_unless_present = "node_config_path"
+    )]
+    waypoint: Option<Waypoint>,
+
+    /// Path to a node config. The configured waypoint (and genesis waypoint, if any) is verified
+    #[clap(long, value_parser, group = "waypoint_source")]
+    node_config_path: Option<PathBuf>,
+
+    #[clap(flatten)]
+    source_options: WaypointSourceOptions,
+}
+
+/// A waypoint that was successfully verified
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct VerifiedWaypoint {
+    /// Where the waypoint was found (e.g., the node config field)
+    pub source: String,
+    pub waypoint: Waypoint,
+    /// The epoch of the epoch ending ledger info at the waypoint
+    pub epoch: u64,
+}
+
+#[async_trait]
+impl CliCommand<Vec<VerifiedWaypoint>> for VerifyWaypoint {
+    fn command_name(&self) -> &'static str {
+        "VerifyWaypoint"
+    }
+
+    async fn execute(self) -> CliTypedResult<Vec<VerifiedWaypoint>> {
+        // Identify the waypoints to verify
+        let waypoints = match (self.waypoint, &self.node_config_path) {
+            (Some(waypoint), _) => vec![("waypoint".to_string(), waypoint)],
+            (None, Some(node_config_path)) => {
+                let node_config = NodeConfig::load_from_path(node_config_path)?;
+                let mut waypoints = vec![];
+                if node_config.base.waypoint != WaypointConfig::None {
+                    waypoints.push((
+                        "base.waypoint".to_string(),
+                        node_config.base.waypoint.waypoint(),
+                    ));
+                }
+                if let Some(genesis_waypoint) = &node_config.execution.genesis_waypoint {
+                    waypoints.push((
+                        "execution.genesis_waypoint".to_string(),
+                        genesis_waypoint.genesis_waypoint(),
+                    ));
+                }
+                if waypoints.is_empty() {
+                    return Err(CliError::CommandArgumentError(format!(
+                        "No waypoints are configured in the node config: {:?}",
+                        node_config_path
+                    )));
+                }
+                waypoints
+            },
+            (None, None) => {
+                return Err(CliError::CommandArgumentError(
+                    "Either --waypoint or --node-config-path must be provided".into(),
+                ))
+            },
+        };
+
+        // Verify the waypoints against the epoch endings
+        let epoch_endings = self.source_options.get_epoch_ending_ledger_infos().await?;
+        waypoints
+            .into_iter()
+            .map(|(source, waypoint)| {
+                let epoch = verify_waypoint(&epoch_endings, &waypoint).map_err(|error| {
+                    CliError::UnexpectedError(format!(
+                        "Failed to verify the waypoint ({}) {}: {}",
+                        source, waypoint, error
+                    ))
+                })?;
+                Ok(VerifiedWaypoint {
+                    source,
+                    waypoint,
+                    epoch,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::HashValue;
+    use aptos_types::{block_info::BlockInfo, epoch_state::EpochState};
+
+    #[test]
+    fn test_select_and_verify_waypoint() {
+        let epoch_endings = create_epoch_endings(5);
+
+        // Verify the latest epoch ending is selected by default
+        let waypoint = select_waypoint(&epoch_endings, None).unwrap();
+        assert_eq!(waypoint.version(), epoch_endings[4].version());
+        assert_eq!(verify_waypoint(&epoch_endings, &waypoint).unwrap(), 4);
+
+        // Verify a specific epoch can be selected
+        let genesis_waypoint = select_waypoint(&epoch_endings, Some(0)).unwrap();
+        assert_eq!(genesis_waypoint.version(), 0);
+        assert_eq!(
+            verify_waypoint(&epoch_endings, &genesis_waypoint).unwrap(),
+            0
+        );
+        assert!(select_waypoint(&epoch_endings, Some(5)).is_err());
+
+        // Verify that invalid waypoints are rejected
+        let invalid_waypoint = Waypoint::new_any(&LedgerInfo::new(
+            BlockInfo::new(2, 0, HashValue::random(), HashValue::random(), 20, 0, None),
+            HashValue::zero(),
+        ));
+        assert!(verify_waypoint(&epoch_endings, &invalid_waypoint).is_err());
+        let future_waypoint = Waypoint::new_any(&LedgerInfo::new(
+            BlockInfo::new(9, 0, HashValue::zero(), HashValue::zero(), 90, 0, None),
+            HashValue::zero(),
+        ));
+        assert!(verify_waypoint(&epoch_endings, &future_waypoint).is_err());
+    }
+
+    #[test]
+    fn test_format_waypoint() {
+        let waypoint = select_waypoint(&create_epoch_endings(1), None).unwrap();
+        assert_eq!(
+            format_waypoint(waypoint, WaypointFormat::Text).unwrap(),
+            waypoint.to_string()
+        );
+
+        // Verify the node config snippet can be loaded by the node config
+        let node_config_snippet = format_waypoint(waypoint, WaypointFormat::NodeConfig).unwrap();
+        let node_config: NodeConfig = serde_yaml::from_str(&node_config_snippet).unwrap();
+        assert_eq!(node_config.base.waypoint.waypoint(), waypoint);
+    }
+
+    /// Creates the epoch ending ledger infos for the given number of epochs
+    fn create_epoch_endings(num_epochs: u64) -> Vec<LedgerInfo> {
+        (0..num_epochs)
+            .map(|epoch| {
+                let block_info = BlockInfo::new(
+                    epoch,
+                    0,
+                    HashValue::zero(),
+                    HashValue::zero(),
+                    epoch * 10,
+                    0,
+                    Some(EpochState::empty()),
+                );
+                LedgerInfo::new(block_info, HashValue::zero())
+            })
+            .collect()
+    }
+}
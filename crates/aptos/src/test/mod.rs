@@ -26,9 +26,9 @@ use crate::{
         SubmitVote, SubmitVoteArgs, VerifyProposal, VerifyProposalResponse,
     },
     move_tool::{
-        ArgWithType, CompilePackage, DownloadPackage, FrameworkPackageArgs, IncludedArtifacts,
-        IncludedArtifactsArgs, InitPackage, MemberId, PublishPackage, RunFunction, RunScript,
-        TestPackage,
+        chunked_publish::ChunkedPublishOption, ArgWithType, CompilePackage, DownloadPackage,
+        FrameworkPackageArgs, IncludedArtifacts, IncludedArtifactsArgs, InitPackage, MemberId,
+        PublishPackage, RunFunction, RunScript, TestPackage,
     },
     node::{
         AnalyzeMode, AnalyzeValidatorPerformance, GetStakePool, InitializeValidator,
@@ -877,6 +877,7 @@ impl CliTestFramework {
             move_options: self.move_options(account_strs),
             txn_options: self.transaction_options(index, gas_options),
             override_size_check_option: OverrideSizeCheckOption { value: false },
+            chunked_publish_option: ChunkedPublishOption::default(),
             included_artifacts_args: IncludedArtifactsArgs {
                 included_artifacts: included_artifacts.unwrap_or(IncludedArtifacts::Sparse),
             },
@@ -203,6 +203,14 @@ pub struct EmitArgs {
     #[clap(long)]
     pub coordination_delay_between_instances: Option<u64>,
 
+    // The unix timestamp (in seconds) at which load generation should start.
+    // When running from multiple machines, this ensures all emitters start
+    // simultaneously (instead of each waiting a fixed delay after minting).
+    // Note: the coordinator (see `coordinator.rs`) hands out the start time
+    // once all workers are set up instead.
+    #[clap(long)]
+    pub coordinated_start_time_unix_secs: Option<u64>,
+
     #[clap(long)]
     pub account_minter_seed: Option<String>,
 
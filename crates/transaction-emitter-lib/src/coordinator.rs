@@ -0,0 +1,704 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Coordinated load generation across many machines. A single coordinator
+//! drives the workers through the following phases (each phase is a barrier,
+//! i.e., no worker moves on until all workers are done with the phase):
+//! 1. Registration: the coordinator waits for a fixed number of workers to
+//!    register, and splits the load (target TPS or mempool backlog, and
+//!    accounts) between them.
+//! 2. Setup: each worker mints its accounts and initializes its transaction
+//!    generators, and notifies the coordinator once it is ready.
+//! 3. Load: the coordinator hands out a common start time to all ready
+//!    workers, and finally aggregates the stats reported by each worker.
+//!
+//! Each phase has a timeout: workers that don't make it through a phase in
+//! time are dropped (and reported as failed), so a worker that never connects
+//! or dies mid-run can't stall the coordinator.
+//!
+//! Messages are exchanged over TCP as length prefixed (u32, big endian) JSON.
+
+use crate::{
+    args::{ClusterArgs, EmitArgs},
+    emitter::{parse_seed, stats::TxnStats, StartBarrier},
+    wrappers::emit_transactions_with_start_barrier,
+};
+use anyhow::{bail, ensure, format_err, Context, Result};
+use aptos_logger::{error, info, warn};
+use futures::future::join_all;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::{watch, Notify},
+    time::{timeout, timeout_at},
+};
+
+// The default time given to workers to mint accounts and initialize
+// (if no coordination delay is specified).
+const DEFAULT_WORKER_SETUP_TIMEOUT_SECS: u64 = 180;
+
+// The delay between the end of the setup phase and the start of the load
+// (so that the start message reaches all workers before the start time).
+const START_DELAY_SECS: u64 = 5;
+
+// The time given to a connection to send its registration (so that a
+// silent connection doesn't block the registration of the other workers).
+const REGISTRATION_MESSAGE_TIMEOUT_SECS: u64 = 10;
+
+// The maximum size of a single coordination message
+const MAX_MESSAGE_SIZE_BYTES: usize = 16 * 1024 * 1024;
+
+/// Messages sent from a worker to the coordinator
+#[derive(Debug, Deserialize, Serialize)]
+enum WorkerMessage {
+    Register { worker_name: String },
+    Ready,
+    Report(TxnStats),
+    Failed(String),
+}
+
+/// Messages sent from the coordinator to a worker
+#[derive(Debug, Deserialize, Serialize)]
+enum CoordinatorMessage {
+    Assignment(WorkerAssignment),
+    Start { start_time_unix_secs: u64 },
+    Abort(String),
+}
+
+/// The share of the load test assigned to a single worker
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WorkerAssignment {
+    pub worker_index: usize,
+    pub num_workers: usize,
+    pub emit_args: EmitArgs,
+}
+
+/// The timeouts of the coordination phases. The setup timeout is given by
+/// the coordination delay of the emit args.
+#[derive(Clone, Copy, Debug)]
+pub struct CoordinatorTimeouts {
+    /// The time to wait for all workers to register
+    pub registration: Duration,
+    /// The time to wait for all workers to report (after the load duration)
+    pub report_grace: Duration,
+}
+
+/// The aggregated results of a coordinated load test
+#[derive(Debug, Default)]
+pub struct CoordinatedStats {
+    /// The stats reported by each (successful) worker
+    pub worker_stats: Vec<(String, TxnStats)>,
+    /// The errors reported by each failed worker
+    pub failed_workers: Vec<(String, String)>,
+    /// The combined stats of all successful workers
+    pub total: TxnStats,
+}
+
+impl CoordinatedStats {
+    fn add_failed_worker(&mut self, worker_name: String, error: String) {
+        error!("Worker {} failed: {}", worker_name, error);
+        self.failed_workers.push((worker_name, error));
+    }
+}
+
+/// Runs the coordinator: waits for `num_workers` workers to register on the
+/// given address, distributes the load and returns the aggregated results.
+pub async fn run_coordinator(
+    listen_address: SocketAddr,
+    num_workers: usize,
+    emit_args: &EmitArgs,
+    timeouts: CoordinatorTimeouts,
+) -> Result<CoordinatedStats> {
+    let listener = TcpListener::bind(listen_address)
+        .await
+        .with_context(|| format!("Failed to bind the coordinator to {}", listen_address))?;
+    info!(
+        "Coordinator listening on {}, waiting for {} workers",
+        listen_address, num_workers
+    );
+    coordinate_workers(listener, num_workers, emit_args, timeouts).await
+}
+
+async fn coordinate_workers(
+    listener: TcpListener,
+    num_workers: usize,
+    emit_args: &EmitArgs,
+    timeouts: CoordinatorTimeouts,
+) -> Result<CoordinatedStats> {
+    ensure!(num_workers > 0, "At least one worker is required!");
+    let assignments = create_worker_assignments(emit_args, num_workers)?;
+    let mut coordinated_stats = CoordinatedStats::default();
+
+    // Registration phase: wait for all workers to register
+    let mut workers = timeout(
+        timeouts.registration,
+        register_workers(&listener, num_workers),
+    )
+    .await
+    .map_err(|_| {
+        format_err!(
+            "Timed out after {:?} waiting for {} workers to register",
+            timeouts.registration,
+            num_workers
+        )
+    })?;
+
+    // Hand out the assignments
+    for ((worker_name, stream), assignment) in workers.iter_mut().zip(assignments) {
+        info!(
+            "Assigning target_tps: {:?}, mempool_backlog: {:?}, num_accounts: {:?} to worker {}",
+            assignment.emit_args.target_tps,
+            assignment.emit_args.mempool_backlog,
+            assignment.emit_args.num_accounts,
+            worker_name
+        );
+        write_message(stream, &CoordinatorMessage::Assignment(assignment))
+            .await
+            .with_context(|| format!("Failed to send the assignment to worker {}", worker_name))?;
+    }
+
+    // Setup phase: wait for all workers to be ready (dropping the workers that aren't)
+    let setup_timeout = Duration::from_secs(
+        emit_args
+            .coordination_delay_between_instances
+            .unwrap_or(DEFAULT_WORKER_SETUP_TIMEOUT_SECS),
+    );
+    info!(
+        "Waiting up to {:?} for the workers to set up",
+        setup_timeout
+    );
+    let setup_results = join_all(
+        workers
+            .into_iter()
+            .map(|(worker_name, mut stream)| async move {
+                let result = match timeout(setup_timeout, read_message(&mut stream)).await {
+                    Ok(Ok(WorkerMessage::Ready)) => Ok(()),
+                    Ok(Ok(WorkerMessage::Failed(error))) => Err(error),
+                    Ok(Ok(message)) => {
+                        Err(format!("Unexpected message during setup: {:?}", message))
+                    },
+                    Ok(Err(error)) => Err(format!("Failed to read the setup status: {:?}", error)),
+                    Err(_) => Err(format!("Timed out after {:?} during setup", setup_timeout)),
+                };
+                (worker_name, stream, result)
+            }),
+    )
+    .await;
+    let mut ready_workers = vec![];
+    for (worker_name, mut stream, result) in setup_results {
+        match result {
+            Ok(()) => ready_workers.push((worker_name, stream)),
+            Err(error) => {
+                abort_worker(&worker_name, &mut stream, &error).await;
+                coordinated_stats.add_failed_worker(worker_name, error);
+            },
+        }
+    }
+    if ready_workers.is_empty() {
+        bail!(
+            "All workers failed during setup! Errors: {:?}",
+            coordinated_stats.failed_workers
+        );
+    }
+
+    // Load phase: start all ready workers simultaneously
+    let start_time_unix_secs =
+        SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + START_DELAY_SECS;
+    info!(
+        "{} workers are ready, starting the load at {}",
+        ready_workers.len(),
+        start_time_unix_secs
+    );
+    let start_message = CoordinatorMessage::Start {
+        start_time_unix_secs,
+    };
+    let mut started_workers = vec![];
+    for (worker_name, mut stream) in ready_workers {
+        match write_message(&mut stream, &start_message).await {
+            Ok(()) => started_workers.push((worker_name, stream)),
+            Err(error) => coordinated_stats
+                .add_failed_worker(worker_name, format!("Failed to start: {:?}", error)),
+        }
+    }
+
+    // Wait for all started workers to report their results
+    let report_deadline = Instant::now()
+        + Duration::from_secs(START_DELAY_SECS + emit_args.duration)
+        + timeouts.report_grace;
+    let reports = join_all(started_workers.into_iter().map(
+        |(worker_name, mut stream)| async move {
+            let report = timeout_at(
+                report_deadline.into(),
+                read_message::<_, WorkerMessage>(&mut stream),
+            )
+            .await;
+            (worker_name, report)
+        },
+    ))
+    .await;
+
+    // Aggregate the results
+    for (worker_name, report) in reports {
+        match report {
+            Ok(Ok(WorkerMessage::Report(stats))) => {
+                info!("Worker {} finished: {}", worker_name, stats.rate());
+                coordinated_stats.total = coordinated_stats.total.combine_concurrent(&stats);
+                coordinated_stats.worker_stats.push((worker_name, stats));
+            },
+            Ok(Ok(WorkerMessage::Failed(error))) => {
+                coordinated_stats.add_failed_worker(worker_name, error);
+            },
+            Ok(Ok(message)) => {
+                let error = format!("Unexpected message: {:?}", message);
+                coordinated_stats.add_failed_worker(worker_name, error);
+            },
+            Ok(Err(error)) => {
+                let error = format!("Failed to read the report: {:?}", error);
+                coordinated_stats.add_failed_worker(worker_name, error);
+            },
+            Err(_) => {
+                let error = "Timed out waiting for the report".to_string();
+                coordinated_stats.add_failed_worker(worker_name, error);
+            },
+        }
+    }
+
+    if coordinated_stats.worker_stats.is_empty() {
+        bail!(
+            "All workers failed! Errors: {:?}",
+            coordinated_stats.failed_workers
+        );
+    }
+    Ok(coordinated_stats)
+}
+
+/// Accepts connections until the given number of workers have registered
+async fn register_workers(
+    listener: &TcpListener,
+    num_workers: usize,
+) -> Result<Vec<(String, TcpStream)>> {
+    let mut workers = vec![];
+    while workers.len() < num_workers {
+        let (mut stream, peer_address) = listener.accept().await?;
+        let registration_timeout = Duration::from_secs(REGISTRATION_MESSAGE_TIMEOUT_SECS);
+        match timeout(
+            registration_timeout,
+            read_message::<_, WorkerMessage>(&mut stream),
+        )
+        .await
+        {
+            Ok(Ok(WorkerMessage::Register { worker_name })) => {
+                info!(
+                    "Worker {} registered from {} ({}/{})",
+                    worker_name,
+                    peer_address,
+                    workers.len() + 1,
+                    num_workers
+                );
+                workers.push((worker_name, stream));
+            },
+            Ok(Ok(message)) => warn!(
+                "Unexpected message from {} during registration: {:?}",
+                peer_address, message
+            ),
+            Ok(Err(error)) => warn!(
+                "Failed to read the registration from {}: {:?}",
+                peer_address, error
+            ),
+            Err(_) => warn!(
+                "Timed out after {:?} waiting for the registration from {}",
+                registration_timeout, peer_address
+            ),
+        }
+    }
+    Ok(workers)
+}
+
+/// Notifies the worker that it has been dropped from the load test (best effort)
+async fn abort_worker(worker_name: &str, stream: &mut TcpStream, reason: &str) {
+    let message = CoordinatorMessage::Abort(reason.into());
+    if let Err(error) = write_message(stream, &message).await {
+        warn!("Failed to abort worker {}: {:?}", worker_name, error);
+    }
+}
+
+/// Runs a worker: registers with the coordinator, sets up and runs the
+/// assigned share of the load test (in lockstep with the other workers)
+/// and reports the results back.
+pub async fn run_worker(
+    coordinator_address: &str,
+    worker_name: String,
+    cluster_args: &ClusterArgs,
+) -> Result<()> {
+    let mut stream = TcpStream::connect(coordinator_address)
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to connect to the coordinator {}",
+                coordinator_address
+            )
+        })?;
+    write_message(&mut stream, &WorkerMessage::Register { worker_name }).await?;
+
+    let assignment = match read_message(&mut stream).await? {
+        CoordinatorMessage::Assignment(assignment) => assignment,
+        message => bail!("Expected an assignment, but got: {:?}", message),
+    };
+    info!(
+        "Received assignment {}/{} from the coordinator",
+        assignment.worker_index + 1,
+        assignment.num_workers
+    );
+
+    // Start the emitter, which blocks on the start barrier once it is set up
+    let (start_time_sender, start_time_receiver) = watch::channel(None);
+    let start_barrier = StartBarrier {
+        initialized: Arc::new(Notify::new()),
+        start_time: start_time_receiver,
+    };
+    let emit = emit_transactions_with_start_barrier(
+        cluster_args,
+        &assignment.emit_args,
+        Some(start_barrier.clone()),
+    );
+    tokio::pin!(emit);
+
+    // Setup phase: notify the coordinator once the emitter is set up (or
+    // stop if the emitter fails, or the coordinator drops this worker)
+    let setup_result = tokio::select! {
+        result = &mut emit => match result {
+            // The emitter can only finish once the start time is set
+            Ok(_) => Err(format_err!("The emitter finished before the load started")),
+            Err(error) => Err(error),
+        },
+        message = read_message::<_, CoordinatorMessage>(&mut stream) => match message {
+            Ok(CoordinatorMessage::Abort(reason)) => {
+                Err(format_err!("Aborted by the coordinator: {}", reason))
+            },
+            Ok(message) => Err(format_err!("Unexpected message during setup: {:?}", message)),
+            Err(error) => Err(error),
+        },
+        _ = start_barrier.initialized.notified() => Ok(()),
+    };
+    if let Err(error) = setup_result {
+        error!("Failed to set up the assignment: {:?}", error);
+        // Best effort (the coordinator might have dropped this worker already)
+        let message = WorkerMessage::Failed(format!("{:?}", error));
+        let _ = write_message(&mut stream, &message).await;
+        return Err(error);
+    }
+    info!("Finished the setup, waiting for the coordinator to start the load");
+    write_message(&mut stream, &WorkerMessage::Ready).await?;
+
+    // Load phase: wait for the common start time and run the load
+    match read_message(&mut stream).await? {
+        CoordinatorMessage::Start {
+            start_time_unix_secs,
+        } => {
+            start_time_sender
+                .send(Some(UNIX_EPOCH + Duration::from_secs(start_time_unix_secs)))
+                .map_err(|_| format_err!("The emitter stopped before the load started"))?;
+        },
+        CoordinatorMessage::Abort(reason) => bail!("Aborted by the coordinator: {}", reason),
+        message => bail!("Expected the start of the load, but got: {:?}", message),
+    }
+    let message = match emit.await {
+        Ok(stats) => {
+            info!("Finished the assignment: {}", stats.rate());
+            WorkerMessage::Report(stats)
+        },
+        Err(error) => {
+            error!("Failed to run the assignment: {:?}", error);
+            WorkerMessage::Failed(format!("{:?}", error))
+        },
+    };
+    write_message(&mut stream, &message).await
+}
+
+/// Splits the load test described by the given args between the workers
+pub fn create_worker_assignments(
+    emit_args: &EmitArgs,
+    num_workers: usize,
+) -> Result<Vec<WorkerAssignment>> {
+    ensure!(num_workers > 0, "At least one worker is required!");
+    for (name, total) in [
+        ("target_tps", emit_args.target_tps),
+        ("mempool_backlog", emit_args.mempool_backlog),
+        ("num_accounts", emit_args.num_accounts),
+    ] {
+        if let Some(total) = total {
+            ensure!(
+                total >= num_workers,
+                "{} ({}) must be at least the number of workers ({})",
+                name,
+                total,
+                num_workers
+            );
+        }
+    }
+
+    // The common start time is handed out once all workers are set up
+    let setup_timeout_secs = emit_args
+        .coordination_delay_between_instances
+        .unwrap_or(DEFAULT_WORKER_SETUP_TIMEOUT_SECS);
+
+    let assignments = (0..num_workers)
+        .map(|worker_index| {
+            let mut worker_args = emit_args.clone();
+            let split = |total: Option<usize>| {
+                total.map(|total| split_evenly(total, num_workers, worker_index))
+            };
+            worker_args.target_tps = split(emit_args.target_tps);
+            worker_args.mempool_backlog = split(emit_args.mempool_backlog);
+            worker_args.num_accounts = split(emit_args.num_accounts);
+
+            // Each worker must use a disjoint range of accounts
+            if let Some(seed) = &emit_args.account_minter_seed {
+                worker_args.account_minter_seed = Some(derive_worker_seed(seed, worker_index));
+            }
+
+            worker_args.coordination_delay_between_instances = Some(setup_timeout_secs);
+            worker_args.coordinated_start_time_unix_secs = None;
+
+            WorkerAssignment {
+                worker_index,
+                num_workers,
+                emit_args: worker_args,
+            }
+        })
+        .collect();
+    Ok(assignments)
+}
+
+/// Returns the share of the total for the given worker (the
+/// remainder is distributed between the first workers).
+fn split_evenly(total: usize, num_workers: usize, worker_index: usize) -> usize {
+    total / num_workers + usize::from(worker_index < total % num_workers)
+}
+
+/// Derives a distinct account minter seed for the given worker
+fn derive_worker_seed(seed: &str, worker_index: usize) -> String {
+    let mut seed = parse_seed(seed);
+    for (seed_byte, index_byte) in seed[24..]
+        .iter_mut()
+        .zip((worker_index as u64).to_le_bytes())
+    {
+        *seed_byte ^= index_byte;
+    }
+    format!("{:?}", seed)
+}
+
+async fn write_message<W: AsyncWrite + Unpin, T: Serialize>(
+    stream: &mut W,
+    message: &T,
+) -> Result<()> {
+    let bytes = serde_json::to_vec(message)?;
+    ensure!(
+        bytes.len() <= MAX_MESSAGE_SIZE_BYTES,
+        "The message is too large: {} bytes",
+        bytes.len()
+    );
+    stream.write_u32(bytes.len() as u32).await?;
+    stream.write_all(&bytes).await?;
+    stream.flush().await?;
+    Ok(())
+}
+
+async fn read_message<R: AsyncRead + Unpin, T: DeserializeOwned>(stream: &mut R) -> Result<T> {
+    let num_bytes = stream.read_u32().await? as usize;
+    ensure!(
+        num_bytes <= MAX_MESSAGE_SIZE_BYTES,
+        "The message is too large: {} bytes",
+        num_bytes
+    );
+    let mut bytes = vec![0; num_bytes];
+    stream.read_exact(&mut bytes).await?;
+    Ok(serde_json::from_slice(&bytes)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn test_timeouts() -> CoordinatorTimeouts {
+        CoordinatorTimeouts {
+            registration: Duration::from_secs(10),
+            report_grace: Duration::from_secs(10),
+        }
+    }
+
+    #[test]
+    fn test_create_worker_assignments() {
+        let emit_args = EmitArgs {
+            target_tps: Some(1003),
+            num_accounts: Some(10),
+            account_minter_seed: Some(format!("{:?}", [7u8; 32])),
+            coordination_delay_between_instances: Some(60),
+            ..Default::default()
+        };
+        let assignments = create_worker_assignments(&emit_args, 4).unwrap();
+
+        // Verify the load is split between the workers
+        let target_tps: Vec<_> = assignments
+            .iter()
+            .map(|assignment| assignment.emit_args.target_tps.unwrap())
+            .collect();
+        assert_eq!(target_tps, vec![251, 251, 251, 250]);
+        let num_accounts: Vec<_> = assignments
+            .iter()
+            .map(|assignment| assignment.emit_args.num_accounts.unwrap())
+            .collect();
+        assert_eq!(num_accounts, vec![3, 3, 2, 2]);
+
+        // Verify all workers share the same setup timeout (the start time is
+        // only handed out after the setup), but use different seeds
+        for (worker_index, assignment) in assignments.iter().enumerate() {
+            assert_eq!(assignment.worker_index, worker_index);
+            assert_eq!(
+                assignment.emit_args.coordination_delay_between_instances,
+                Some(60)
+            );
+            assert_eq!(assignment.emit_args.coordinated_start_time_unix_secs, None);
+            assert_eq!(assignment.emit_args.mempool_backlog, None);
+        }
+        let seeds: Vec<_> = assignments
+            .iter()
+            .map(|assignment| assignment.emit_args.account_minter_seed.clone().unwrap())
+            .collect();
+        assert_eq!(seeds[0], emit_args.account_minter_seed.unwrap());
+        let seeds: HashSet<_> = seeds.into_iter().collect();
+        assert_eq!(seeds.len(), 4);
+
+        // Verify the load must be large enough to split
+        assert!(create_worker_assignments(&emit_args, 11).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_coordinate_workers() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let coordinator_address = listener.local_addr().unwrap();
+        let emit_args = EmitArgs {
+            mempool_backlog: Some(100),
+            duration: 0,
+            coordination_delay_between_instances: Some(1),
+            ..Default::default()
+        };
+        let coordinator = tokio::spawn(async move {
+            coordinate_workers(listener, 4, &emit_args, test_timeouts())
+                .await
+                .unwrap()
+        });
+
+        // Register several fake workers (in order)
+        let mut streams = vec![];
+        for worker_index in 0..4 {
+            let mut stream = TcpStream::connect(coordinator_address).await.unwrap();
+            let worker_name = format!("worker_{}", worker_index);
+            write_message(&mut stream, &WorkerMessage::Register { worker_name })
+                .await
+                .unwrap();
+            streams.push(stream);
+        }
+
+        // Have the workers report their assignment as stats (the third one fails
+        // during the load, and the last one never finishes its setup)
+        let workers = streams.into_iter().map(|mut stream| async move {
+            let assignment = match read_message(&mut stream).await.unwrap() {
+                CoordinatorMessage::Assignment(assignment) => assignment,
+                message => panic!("Unexpected message: {:?}", message),
+            };
+            let worker_index = assignment.worker_index as u64;
+            if worker_index == 3 {
+                // Verify the worker is dropped once the setup times out
+                match read_message(&mut stream).await.unwrap() {
+                    CoordinatorMessage::Abort(_) => return,
+                    message => panic!("Unexpected message: {:?}", message),
+                }
+            }
+
+            // Verify the load only starts once all workers are set up
+            write_message(&mut stream, &WorkerMessage::Ready)
+                .await
+                .unwrap();
+            match read_message(&mut stream).await.unwrap() {
+                CoordinatorMessage::Start { .. } => {},
+                message => panic!("Unexpected message: {:?}", message),
+            }
+
+            let message = if worker_index == 2 {
+                WorkerMessage::Failed("Out of coins".into())
+            } else {
+                WorkerMessage::Report(TxnStats {
+                    submitted: assignment.emit_args.mempool_backlog.unwrap() as u64,
+                    lasted: Duration::from_secs(10 + worker_index),
+                    ..Default::default()
+                })
+            };
+            write_message(&mut stream, &message).await.unwrap();
+        });
+        join_all(workers).await;
+
+        // Verify the results were aggregated
+        let coordinated_stats = coordinator.await.unwrap();
+        assert_eq!(coordinated_stats.worker_stats.len(), 2);
+        assert_eq!(coordinated_stats.failed_workers.len(), 2);
+        assert_eq!(coordinated_stats.total.submitted, 50);
+        assert_eq!(coordinated_stats.total.lasted, Duration::from_secs(11));
+    }
+
+    #[tokio::test]
+    async fn test_coordinate_workers_timeouts() {
+        // Verify the coordinator gives up if not all workers register in time
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let coordinator_address = listener.local_addr().unwrap();
+        let emit_args = EmitArgs {
+            mempool_backlog: Some(100),
+            duration: 0,
+            ..Default::default()
+        };
+        let timeouts = CoordinatorTimeouts {
+            registration: Duration::from_millis(500),
+            report_grace: Duration::from_millis(500),
+        };
+        let mut stream = TcpStream::connect(coordinator_address).await.unwrap();
+        write_message(&mut stream, &WorkerMessage::Register {
+            worker_name: "worker_0".into(),
+        })
+        .await
+        .unwrap();
+        assert!(coordinate_workers(listener, 2, &emit_args, timeouts)
+            .await
+            .is_err());
+
+        // Verify the coordinator gives up on workers that die mid-run
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let coordinator_address = listener.local_addr().unwrap();
+        let coordinator =
+            tokio::spawn(
+                async move { coordinate_workers(listener, 1, &emit_args, timeouts).await },
+            );
+        let mut stream = TcpStream::connect(coordinator_address).await.unwrap();
+        write_message(&mut stream, &WorkerMessage::Register {
+            worker_name: "worker_0".into(),
+        })
+        .await
+        .unwrap();
+        read_message::<_, CoordinatorMessage>(&mut stream)
+            .await
+            .unwrap();
+        write_message(&mut stream, &WorkerMessage::Ready)
+            .await
+            .unwrap();
+        read_message::<_, CoordinatorMessage>(&mut stream)
+            .await
+            .unwrap();
+        assert!(coordinator.await.unwrap().is_err());
+    }
+}
@@ -37,9 +37,14 @@ use std::{
         atomic::{AtomicBool, Ordering},
         Arc,
     },
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
+};
+use tokio::{
+    runtime::Handle,
+    sync::{watch, Notify},
+    task::JoinHandle,
+    time,
 };
-use tokio::{runtime::Handle, task::JoinHandle, time};
 
 // Max is 100k TPS for 3 hours
 const MAX_TXNS: u64 = 1_000_000_000;
@@ -133,6 +138,31 @@ impl NumAccountsMode {
     }
 }
 
+/// A barrier between the initialization of an emitter (i.e., account minting and txn
+/// generator initialization) and the start of the load, used to coordinate many emitters.
+/// Once initialized, the emitter notifies `initialized` and waits for the start time to be
+/// set (and reached).
+#[derive(Clone, Debug)]
+pub struct StartBarrier {
+    pub initialized: Arc<Notify>,
+    pub start_time: watch::Receiver<Option<SystemTime>>,
+}
+
+impl StartBarrier {
+    /// Notifies that the emitter is initialized and waits for the start time to be set
+    async fn wait_for_start_time(&self) -> Result<SystemTime> {
+        self.initialized.notify_one();
+        let mut start_time = self.start_time.clone();
+        let start_time = start_time
+            .wait_for(|start_time| start_time.is_some())
+            .await
+            .map_err(|_| {
+                format_err!("The start barrier was dropped before the start time was set")
+            })?;
+        Ok(start_time.expect("The start time must be set!"))
+    }
+}
+
 /// total coins consumed are less than 2 * max_txns * expected_gas_per_txn * gas_price,
 /// which is by default 100000000000 * 100000, but can be overriden.
 #[derive(Clone, Debug)]
@@ -160,6 +190,8 @@ pub struct EmitJobRequest {
     prompt_before_spending: bool,
 
     coordination_delay_between_instances: Duration,
+    coordinated_start_time: Option<SystemTime>,
+    start_barrier: Option<StartBarrier>,
 
     latency_polling_interval: Duration,
 
@@ -189,6 +221,8 @@ impl Default for EmitJobRequest {
             expected_gas_per_txn: aptos_global_constants::MAX_GAS_AMOUNT,
             prompt_before_spending: false,
             coordination_delay_between_instances: Duration::from_secs(0),
+            coordinated_start_time: None,
+            start_barrier: None,
             latency_polling_interval: Duration::from_millis(300),
             account_minter_seed: None,
             coins_per_account_override: None,
@@ -286,6 +320,20 @@ impl EmitJobRequest {
         self
     }
 
+    /// Sets the (wall clock) time at which load generation should start.
+    /// This is used to start emitters on different machines simultaneously.
+    pub fn coordinated_start_time(mut self, coordinated_start_time: SystemTime) -> Self {
+        self.coordinated_start_time = Some(coordinated_start_time);
+        self
+    }
+
+    /// Sets the barrier to wait on (once initialized) for the start time of the load. This
+    /// overrides the coordinated start time (if any).
+    pub fn start_barrier(mut self, start_barrier: StartBarrier) -> Self {
+        self.start_barrier = Some(start_barrier);
+        self
+    }
+
     pub fn latency_polling_interval(mut self, latency_polling_interval: Duration) -> Self {
         self.latency_polling_interval = latency_polling_interval;
         self
@@ -676,7 +724,25 @@ impl TxnEmitter {
         )
         .await;
 
-        if !req.coordination_delay_between_instances.is_zero() {
+        let coordinated_start_time = match &req.start_barrier {
+            Some(start_barrier) => Some(start_barrier.wait_for_start_time().await?),
+            None => req.coordinated_start_time,
+        };
+        if let Some(coordinated_start_time) = coordinated_start_time {
+            match coordinated_start_time.duration_since(SystemTime::now()) {
+                Ok(delay) => {
+                    info!(
+                        "Sleeping after minting/txn generator initialization until the coordinated start time ({}s)",
+                        delay.as_secs()
+                    );
+                    tokio::time::sleep(delay).await;
+                },
+                Err(error) => warn!(
+                    "Initialization finished {}s after the coordinated start time, starting immediately",
+                    error.duration().as_secs()
+                ),
+            }
+        } else if !req.coordination_delay_between_instances.is_zero() {
             info!(
                 "Sleeping after minting/txn generator initialization for {}s",
                 req.coordination_delay_between_instances.as_secs()
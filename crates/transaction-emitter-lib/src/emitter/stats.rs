@@ -1,7 +1,9 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use serde::{Deserialize, Serialize};
 use std::{
+    cmp::max,
     fmt,
     ops::{Add, Sub},
     sync::{
@@ -11,7 +13,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 pub struct TxnStats {
     pub submitted: u64,
     pub committed: u64,
@@ -51,6 +53,14 @@ impl fmt::Display for TxnStatsRate {
 }
 
 impl TxnStats {
+    /// Combines the stats of emitters that ran concurrently (e.g., on
+    /// different machines). Unlike addition, the durations are not summed.
+    pub fn combine_concurrent(&self, other: &TxnStats) -> TxnStats {
+        let mut combined = self + other;
+        combined.lasted = max(self.lasted, other.lasted);
+        combined
+    }
+
     pub fn rate(&self) -> TxnStatsRate {
         let mut window_secs = self.lasted.as_secs();
         if window_secs < 1 {
@@ -201,7 +211,7 @@ impl AtomicHistogramAccumulator {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AtomicHistogramSnapshot {
     capacity: usize,
     step_width: u64,
@@ -387,6 +397,33 @@ mod test {
         }
     }
 
+    #[test]
+    pub fn test_combine_concurrent() {
+        let histogram = AtomicHistogramAccumulator::default();
+        histogram.record_data_point(100, 1);
+        let first = TxnStats {
+            submitted: 10,
+            committed: 8,
+            latency_buckets: histogram.snapshot(),
+            lasted: Duration::from_secs(10),
+            ..Default::default()
+        };
+        let second = TxnStats {
+            submitted: 20,
+            committed: 16,
+            latency_buckets: histogram.snapshot(),
+            lasted: Duration::from_secs(12),
+            ..Default::default()
+        };
+
+        // Verify the counts are summed, but the durations are not
+        let combined = first.combine_concurrent(&second);
+        assert_eq!(combined.submitted, 30);
+        assert_eq!(combined.committed, 24);
+        assert_eq!(combined.lasted, Duration::from_secs(12));
+        assert_eq!(combined.latency_buckets.buckets.iter().sum::<u64>(), 2);
+    }
+
     #[test]
     pub fn test_percentile_latency() {
         let histogram = AtomicHistogramAccumulator::default();
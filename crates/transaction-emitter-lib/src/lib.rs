@@ -5,6 +5,7 @@
 
 mod args;
 mod cluster;
+mod coordinator;
 pub mod emitter;
 mod instance;
 mod wrappers;
@@ -13,9 +14,13 @@ mod wrappers;
 pub use args::{ClusterArgs, CoinSourceArgs, CreateAccountsArgs, EmitArgs};
 // We export these if you want finer grained control.
 pub use cluster::Cluster;
+pub use coordinator::{
+    create_worker_assignments, run_coordinator, run_worker, CoordinatedStats, CoordinatorTimeouts,
+    WorkerAssignment,
+};
 pub use emitter::{
     query_sequence_number, query_sequence_numbers,
     stats::{TxnStats, TxnStatsRate},
-    EmitJob, EmitJobMode, EmitJobRequest, EmitModeParams, TxnEmitter,
+    EmitJob, EmitJobMode, EmitJobRequest, EmitModeParams, StartBarrier, TxnEmitter,
 };
 pub use wrappers::{create_accounts_command, emit_transactions, emit_transactions_with_cluster};
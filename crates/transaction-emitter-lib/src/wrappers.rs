@@ -6,7 +6,7 @@ use crate::{
     cluster::Cluster,
     emitter::{
         create_accounts, parse_seed, stats::TxnStats, EmitJobMode, EmitJobRequest, NumAccountsMode,
-        TxnEmitter,
+        StartBarrier, TxnEmitter,
     },
     instance::Instance,
     CreateAccountsArgs,
@@ -17,17 +17,26 @@ use aptos_logger::{error, info};
 use aptos_sdk::transaction_builder::TransactionFactory;
 use aptos_transaction_generator_lib::args::TransactionTypeArg;
 use rand::{rngs::StdRng, Rng, SeedableRng};
-use std::time::{Duration, Instant};
+use std::time::{Duration, Instant, UNIX_EPOCH};
 
 pub async fn emit_transactions(
     cluster_args: &ClusterArgs,
     emit_args: &EmitArgs,
+) -> Result<TxnStats> {
+    emit_transactions_with_start_barrier(cluster_args, emit_args, None).await
+}
+
+/// Emits transactions, waiting on the given barrier (if any) after initialization
+pub(crate) async fn emit_transactions_with_start_barrier(
+    cluster_args: &ClusterArgs,
+    emit_args: &EmitArgs,
+    start_barrier: Option<StartBarrier>,
 ) -> Result<TxnStats> {
     if emit_args.coordination_delay_between_instances.is_none() {
         let cluster = Cluster::try_from_cluster_args(cluster_args)
             .await
             .context("Failed to build cluster")?;
-        emit_transactions_with_cluster(&cluster, emit_args).await
+        run_emitter(&cluster, emit_args, start_barrier).await
     } else {
         let initial_delay_after_minting = emit_args.coordination_delay_between_instances.unwrap();
         let start_time = Instant::now();
@@ -54,7 +63,7 @@ pub async fn emit_transactions(
                 .await
                 .context("Failed to build cluster")?;
 
-            let result = emit_transactions_with_cluster(&cluster, &cur_emit_args).await;
+            let result = run_emitter(&cluster, &cur_emit_args, start_barrier.clone()).await;
             match result {
                 Ok(value) => return Ok(value),
                 Err(e) => {
@@ -69,6 +78,14 @@ pub async fn emit_transactions(
 pub async fn emit_transactions_with_cluster(
     cluster: &Cluster,
     args: &EmitArgs,
+) -> Result<TxnStats> {
+    run_emitter(cluster, args, None).await
+}
+
+async fn run_emitter(
+    cluster: &Cluster,
+    args: &EmitArgs,
+    start_barrier: Option<StartBarrier>,
 ) -> Result<TxnStats> {
     let emitter_mode = EmitJobMode::create(args.mempool_backlog, args.target_tps);
 
@@ -125,9 +142,18 @@ pub async fn emit_transactions_with_cluster(
     if let Some(expected_gas_per_txn) = args.expected_gas_per_txn {
         emit_job_request = emit_job_request.expected_gas_per_txn(expected_gas_per_txn);
     }
+    if let Some(start_time_unix_secs) = args.coordinated_start_time_unix_secs {
+        emit_job_request = emit_job_request
+            .coordinated_start_time(UNIX_EPOCH + Duration::from_secs(start_time_unix_secs));
+    }
+    let is_coordinated = args.coordinated_start_time_unix_secs.is_some() || start_barrier.is_some();
+    if let Some(start_barrier) = start_barrier {
+        emit_job_request = emit_job_request.start_barrier(start_barrier);
+    }
     if cluster.coin_source_is_root {
         emit_job_request = emit_job_request.set_mint_to_root();
-    } else {
+    } else if !is_coordinated {
+        // Coordinated emitters run unattended, so they can't prompt
         emit_job_request = emit_job_request.prompt_before_spending();
     }
 
@@ -7,10 +7,12 @@ mod diag;
 use anyhow::{Context, Result};
 use aptos_logger::{Level, Logger};
 use aptos_transaction_emitter_lib::{
-    create_accounts_command, emit_transactions, Cluster, ClusterArgs, CreateAccountsArgs, EmitArgs,
+    create_accounts_command, emit_transactions, run_coordinator, run_worker, Cluster, ClusterArgs,
+    CoordinatorTimeouts, CreateAccountsArgs, EmitArgs,
 };
 use clap::{Parser, Subcommand};
 use diag::diag;
+use std::{net::SocketAddr, time::Duration};
 
 #[derive(Parser, Debug)]
 struct Args {
@@ -35,6 +37,14 @@ enum TxnEmitterCommand {
     /// Just pings a set of end points and determines if they are reachable and have
     /// up to date ledger information
     PingEndPoints(PingEndPoints),
+
+    /// Coordinates a load test across many worker machines. The coordinator
+    /// splits the load between the workers, starts them simultaneously and
+    /// aggregates their results.
+    Coordinate(Coordinate),
+
+    /// Runs the share of a coordinated load test assigned by the coordinator
+    Worker(Worker),
 }
 
 #[derive(Parser, Debug)]
@@ -61,6 +71,42 @@ struct PingEndPoints {
     cluster_args: ClusterArgs,
 }
 
+#[derive(Parser, Debug)]
+struct Coordinate {
+    /// The address on which to wait for workers to register
+    #[clap(long, default_value = "0.0.0.0:6190")]
+    listen_address: SocketAddr,
+
+    /// The number of workers to wait for before starting the load test
+    #[clap(long)]
+    num_workers: usize,
+
+    /// The time (in seconds) to wait for all workers to register
+    #[clap(long, default_value_t = 600)]
+    registration_timeout_secs: u64,
+
+    /// The time (in seconds) to wait for the worker reports, after the load duration
+    #[clap(long, default_value_t = 300)]
+    report_timeout_secs: u64,
+
+    #[clap(flatten)]
+    emit_args: EmitArgs,
+}
+
+#[derive(Parser, Debug)]
+struct Worker {
+    /// The address of the coordinator (e.g., "10.0.0.1:6190")
+    #[clap(long)]
+    coordinator_address: String,
+
+    /// The name used to identify this worker (defaults to the hostname)
+    #[clap(long)]
+    worker_name: Option<String>,
+
+    #[clap(flatten)]
+    cluster_args: ClusterArgs,
+}
+
 #[derive(Parser, Debug)]
 struct Diag {
     #[clap(flatten)]
@@ -104,6 +150,39 @@ pub async fn main() -> Result<()> {
                 .context("Failed to build cluster")?;
             Ok(())
         },
+        TxnEmitterCommand::Coordinate(args) => {
+            let timeouts = CoordinatorTimeouts {
+                registration: Duration::from_secs(args.registration_timeout_secs),
+                report_grace: Duration::from_secs(args.report_timeout_secs),
+            };
+            let stats = run_coordinator(
+                args.listen_address,
+                args.num_workers,
+                &args.emit_args,
+                timeouts,
+            )
+            .await
+            .context("Coordinated load test failed")?;
+            for (worker_name, worker_stats) in &stats.worker_stats {
+                println!("Worker {} rate: {}", worker_name, worker_stats.rate());
+            }
+            for (worker_name, error) in &stats.failed_workers {
+                println!("Worker {} failed: {}", worker_name, error);
+            }
+            println!("Total stats: {}", stats.total);
+            println!("Average rate: {}", stats.total.rate());
+            Ok(())
+        },
+        TxnEmitterCommand::Worker(args) => {
+            let worker_name = args.worker_name.unwrap_or_else(|| {
+                hostname::get()
+                    .map(|hostname| hostname.to_string_lossy().into_owned())
+                    .unwrap_or_else(|_| "unknown".into())
+            });
+            run_worker(&args.coordinator_address, worker_name, &args.cluster_args)
+                .await
+                .context("Worker failed")
+        },
     }
 }
 
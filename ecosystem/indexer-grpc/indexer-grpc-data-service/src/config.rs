@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::service::RawDataServerWrapper;
+use crate::{
+    service::RawDataServerWrapper,
+    transaction_transform::{TransactionTransformConfig, TransactionTransforms},
+};
 use anyhow::{bail, Result};
 use aptos_indexer_grpc_server_framework::RunnableConfig;
 use aptos_indexer_grpc_utils::{
@@ -13,7 +16,7 @@ use aptos_protos::{
     util::timestamp::FILE_DESCRIPTOR_SET as UTIL_TIMESTAMP_FILE_DESCRIPTOR_SET,
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::HashSet, net::SocketAddr};
+use std::{collections::HashSet, net::SocketAddr, sync::Arc};
 use tonic::{
     codec::CompressionEncoding,
     codegen::InterceptedService,
@@ -72,6 +75,10 @@ pub struct IndexerGrpcDataServiceConfig {
     /// Support compressed cache data.
     #[serde(default = "IndexerGrpcDataServiceConfig::default_enable_cache_compression")]
     pub enable_cache_compression: bool,
+    /// The transforms (e.g., redactions) applied to all transactions before
+    /// they are streamed to clients. Transforms are applied in order.
+    #[serde(default)]
+    pub transaction_transforms: Vec<TransactionTransformConfig>,
 }
 
 impl IndexerGrpcDataServiceConfig {
@@ -84,6 +91,7 @@ impl IndexerGrpcDataServiceConfig {
         file_store_config: IndexerGrpcFileStoreConfig,
         redis_read_replica_address: RedisUrl,
         enable_cache_compression: bool,
+        transaction_transforms: Vec<TransactionTransformConfig>,
     ) -> Self {
         Self {
             data_service_grpc_tls_config,
@@ -95,6 +103,7 @@ impl IndexerGrpcDataServiceConfig {
             file_store_config,
             redis_read_replica_address,
             enable_cache_compression,
+            transaction_transforms,
         }
     }
 
@@ -162,12 +171,18 @@ impl RunnableConfig for IndexerGrpcDataServiceConfig {
         } else {
             StorageFormat::Base64UncompressedProto
         };
+        let transaction_transforms = TransactionTransforms::new(&self.transaction_transforms);
+        tracing::info!(
+            transaction_transforms = ?transaction_transforms.get_transform_names(),
+            "[Data Service] Configured the transaction transforms."
+        );
         // Add authentication interceptor.
         let server = RawDataServerWrapper::new(
             self.redis_read_replica_address.clone(),
             self.file_store_config.clone(),
             self.data_service_response_channel_size,
             cache_storage_format,
+            Arc::new(transaction_transforms),
         )?;
         let svc = aptos_protos::indexer::v1::raw_data_server::RawDataServer::new(server)
             .send_compressed(CompressionEncoding::Gzip)
@@ -6,5 +6,7 @@ mod grpc_response_stream;
 mod metrics;
 mod response_dispatcher;
 mod service;
+mod transaction_transform;
 
 pub use config::{IndexerGrpcDataServiceConfig, NonTlsConfig, SERVER_NAME};
+pub use transaction_transform::TransactionTransformConfig;
@@ -1,11 +1,14 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::metrics::{
-    BYTES_READY_TO_TRANSFER_FROM_SERVER, CONNECTION_COUNT, ERROR_COUNT,
-    LATEST_PROCESSED_VERSION as LATEST_PROCESSED_VERSION_OLD, PROCESSED_BATCH_SIZE,
-    PROCESSED_LATENCY_IN_SECS, PROCESSED_LATENCY_IN_SECS_ALL, PROCESSED_VERSIONS_COUNT,
-    SHORT_CONNECTION_COUNT,
+use crate::{
+    metrics::{
+        BYTES_READY_TO_TRANSFER_FROM_SERVER, CONNECTION_COUNT, ERROR_COUNT,
+        LATEST_PROCESSED_VERSION as LATEST_PROCESSED_VERSION_OLD, PROCESSED_BATCH_SIZE,
+        PROCESSED_LATENCY_IN_SECS, PROCESSED_LATENCY_IN_SECS_ALL, PROCESSED_VERSIONS_COUNT,
+        SHORT_CONNECTION_COUNT,
+    },
+    transaction_transform::TransactionTransforms,
 };
 use anyhow::{Context, Result};
 use aptos_indexer_grpc_utils::{
@@ -80,6 +83,7 @@ pub struct RawDataServerWrapper {
     pub file_store_config: IndexerGrpcFileStoreConfig,
     pub data_service_response_channel_size: usize,
     pub cache_storage_format: StorageFormat,
+    pub transaction_transforms: Arc<TransactionTransforms>,
 }
 
 impl RawDataServerWrapper {
@@ -88,6 +92,7 @@ impl RawDataServerWrapper {
         file_store_config: IndexerGrpcFileStoreConfig,
         data_service_response_channel_size: usize,
         cache_storage_format: StorageFormat,
+        transaction_transforms: Arc<TransactionTransforms>,
     ) -> anyhow::Result<Self> {
         Ok(Self {
             redis_client: Arc::new(
@@ -98,6 +103,7 @@ impl RawDataServerWrapper {
             file_store_config,
             data_service_response_channel_size,
             cache_storage_format,
+            transaction_transforms,
         })
     }
 }
@@ -171,6 +177,7 @@ impl RawData for RawDataServerWrapper {
 
         let redis_client = self.redis_client.clone();
         let cache_storage_format = self.cache_storage_format;
+        let transaction_transforms = self.transaction_transforms.clone();
         let request_metadata = Arc::new(request_metadata);
         tokio::spawn({
             let request_metadata = request_metadata.clone();
@@ -179,6 +186,7 @@ impl RawData for RawDataServerWrapper {
                     redis_client,
                     file_store_operator,
                     cache_storage_format,
+                    transaction_transforms,
                     request_metadata,
                     transactions_count,
                     tx,
@@ -347,6 +355,7 @@ async fn data_fetcher_task(
     redis_client: Arc<Client>,
     file_store_operator: Arc<Box<dyn FileStoreOperator>>,
     cache_storage_format: StorageFormat,
+    transaction_transforms: Arc<TransactionTransforms>,
     request_metadata: Arc<IndexerGrpcRequestMetadata>,
     transactions_count: Option<u64>,
     tx: tokio::sync::mpsc::Sender<Result<TransactionsResponse, Status>>,
@@ -471,6 +480,8 @@ async fn data_fetcher_task(
                 transactions_count = Some(count - transaction_data.len() as u64);
             }
         };
+        // Apply the configured transforms (e.g., redactions) before streaming the data.
+        transaction_transforms.apply(&mut transaction_data);
         // Note: this is the protobuf encoded transaction size.
         let bytes_ready_to_transfer = transaction_data
             .iter()
@@ -0,0 +1,224 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_protos::transaction::v1::{transaction::TxnData, Event, Transaction};
+use serde::{Deserialize, Serialize};
+
+/// A transform that is applied (server-side) to every transaction before it is
+/// streamed to clients, e.g., to redact or annotate the transaction. Transforms
+/// modify transactions in place and must never drop them, as clients expect
+/// the streamed versions to be contiguous.
+pub trait TransactionTransform: Send + Sync {
+    /// Returns the name of the transform (used for logging)
+    fn name(&self) -> &'static str;
+
+    /// Transforms the given transaction in place
+    fn transform(&self, transaction: &mut Transaction);
+}
+
+/// The config of a transaction transform. Transforms are compiled into the
+/// data service and selected (and ordered) by the operator using the config.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum TransactionTransformConfig {
+    /// Removes all events emitted by the given modules (e.g., `0x1::coin`)
+    StripModuleEvents { modules: Vec<String> },
+    /// Removes the signatures of all user transactions
+    RedactSignatures,
+    /// Removes the write set changes of all transactions
+    StripWriteSetChanges,
+}
+
+impl TransactionTransformConfig {
+    /// Creates the transform specified by the config
+    fn create_transform(&self) -> Box<dyn TransactionTransform> {
+        match self {
+            Self::StripModuleEvents { modules } => Box::new(StripModuleEvents::new(modules)),
+            Self::RedactSignatures => Box::new(RedactSignatures),
+            Self::StripWriteSetChanges => Box::new(StripWriteSetChanges),
+        }
+    }
+}
+
+/// The ordered list of transforms applied to all streamed transactions
+pub struct TransactionTransforms {
+    transforms: Vec<Box<dyn TransactionTransform>>,
+}
+
+impl TransactionTransforms {
+    pub fn new(transform_configs: &[TransactionTransformConfig]) -> Self {
+        let transforms = transform_configs
+            .iter()
+            .map(|transform_config| transform_config.create_transform())
+            .collect();
+        Self { transforms }
+    }
+
+    /// Returns the names of the transforms (in the order they are applied)
+    pub fn get_transform_names(&self) -> Vec<&'static str> {
+        self.transforms
+            .iter()
+            .map(|transform| transform.name())
+            .collect()
+    }
+
+    /// Applies all transforms (in order) to the given transactions
+    pub fn apply(&self, transactions: &mut [Transaction]) {
+        if self.transforms.is_empty() {
+            return;
+        }
+
+        for transaction in transactions.iter_mut() {
+            for transform in &self.transforms {
+                transform.transform(transaction);
+            }
+        }
+    }
+}
+
+/// Removes all events emitted by the specified modules
+struct StripModuleEvents {
+    module_prefixes: Vec<String>, // The event type prefixes (e.g., `0x1::coin::`)
+}
+
+impl StripModuleEvents {
+    fn new(modules: &[String]) -> Self {
+        let module_prefixes = modules
+            .iter()
+            .map(|module| format!("{}::", module))
+            .collect();
+        Self { module_prefixes }
+    }
+
+    /// Returns true iff the event was emitted by one of the specified modules
+    fn is_stripped_event(&self, event: &Event) -> bool {
+        self.module_prefixes
+            .iter()
+            .any(|module_prefix| event.type_str.starts_with(module_prefix))
+    }
+}
+
+impl TransactionTransform for StripModuleEvents {
+    fn name(&self) -> &'static str {
+        "strip_module_events"
+    }
+
+    fn transform(&self, transaction: &mut Transaction) {
+        let events = match transaction.txn_data.as_mut() {
+            Some(TxnData::BlockMetadata(transaction)) => &mut transaction.events,
+            Some(TxnData::Genesis(transaction)) => &mut transaction.events,
+            Some(TxnData::User(transaction)) => &mut transaction.events,
+            _ => return, // The transaction has no events
+        };
+        events.retain(|event| !self.is_stripped_event(event));
+    }
+}
+
+/// Removes the signatures of user transactions
+struct RedactSignatures;
+
+impl TransactionTransform for RedactSignatures {
+    fn name(&self) -> &'static str {
+        "redact_signatures"
+    }
+
+    fn transform(&self, transaction: &mut Transaction) {
+        if let Some(TxnData::User(transaction)) = transaction.txn_data.as_mut() {
+            if let Some(request) = transaction.request.as_mut() {
+                request.signature = None;
+            }
+        }
+    }
+}
+
+/// Removes the write set changes of transactions
+struct StripWriteSetChanges;
+
+impl TransactionTransform for StripWriteSetChanges {
+    fn name(&self) -> &'static str {
+        "strip_write_set_changes"
+    }
+
+    fn transform(&self, transaction: &mut Transaction) {
+        if let Some(info) = transaction.info.as_mut() {
+            info.changes.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_protos::transaction::v1::{
+        Signature, TransactionInfo, UserTransaction, UserTransactionRequest, WriteSetChange,
+    };
+
+    #[test]
+    fn test_transaction_transforms() {
+        // Create the transforms from the config
+        let transform_configs: Vec<TransactionTransformConfig> = serde_json::from_str(
+            r#"[
+                {"type": "strip_module_events", "modules": ["0x1::coin"]},
+                {"type": "redact_signatures"},
+                {"type": "strip_write_set_changes"}
+            ]"#,
+        )
+        .unwrap();
+        let transaction_transforms = TransactionTransforms::new(&transform_configs);
+        assert_eq!(transaction_transforms.get_transform_names(), vec![
+            "strip_module_events",
+            "redact_signatures",
+            "strip_write_set_changes"
+        ]);
+
+        // Create a user transaction with events, a signature and write set changes
+        let mut transactions = vec![Transaction {
+            version: 10,
+            info: Some(TransactionInfo {
+                changes: vec![WriteSetChange::default()],
+                ..TransactionInfo::default()
+            }),
+            txn_data: Some(TxnData::User(UserTransaction {
+                request: Some(UserTransactionRequest {
+                    signature: Some(Signature::default()),
+                    ..UserTransactionRequest::default()
+                }),
+                events: vec![
+                    create_event("0x1::coin::WithdrawEvent"),
+                    create_event("0x1::coin_store::DepositEvent"),
+                    create_event("0x1::account::CoinRegisterEvent"),
+                ],
+            })),
+            ..Transaction::default()
+        }];
+
+        // Apply the transforms and verify the transaction
+        transaction_transforms.apply(&mut transactions);
+        let transaction = transactions.pop().unwrap();
+        assert_eq!(transaction.version, 10);
+        assert!(transaction.info.unwrap().changes.is_empty());
+        match transaction.txn_data.unwrap() {
+            TxnData::User(user_transaction) => {
+                assert!(user_transaction.request.unwrap().signature.is_none());
+                let event_types: Vec<_> = user_transaction
+                    .events
+                    .iter()
+                    .map(|event| event.type_str.as_str())
+                    .collect();
+                assert_eq!(event_types, vec![
+                    "0x1::coin_store::DepositEvent",
+                    "0x1::account::CoinRegisterEvent"
+                ]);
+            },
+            txn_data => panic!("Expected a user transaction, but got: {:?}", txn_data),
+        }
+    }
+
+    /// Creates an event with the given type
+    fn create_event(type_str: &str) -> Event {
+        Event {
+            type_str: type_str.into(),
+            ..Event::default()
+        }
+    }
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    convert::convert_transaction,
+    convert::{convert_transaction, convert_transaction_size_info},
     counters::{FETCHED_TRANSACTION, UNABLE_TO_FETCH_TRANSACTION},
     runtime::{DEFAULT_NUM_RETRIES, RETRY_TIME_MILLIS},
 };
@@ -18,14 +18,11 @@ use aptos_protos::{
     internal::fullnode::v1::{
         transactions_from_node_response, TransactionsFromNodeResponse, TransactionsOutput,
     },
-    transaction::v1::{
-        EventSizeInfo, Transaction as TransactionPB, TransactionSizeInfo, WriteOpSizeInfo,
-    },
+    transaction::v1::{Transaction as TransactionPB, TransactionSizeInfo},
     util::timestamp::Timestamp,
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use itertools::Itertools;
-use serde::Serialize;
 use std::{sync::Arc, time::Duration};
 use tokio::sync::mpsc;
 use tonic::Status;
@@ -380,7 +377,7 @@ impl IndexerStreamCoordinator {
                     block_height_bcs = aptos_api_types::U64::from(block_height);
                 }
             }
-            let size_info = Self::get_size_info(&raw_txn);
+            let size_info = convert_transaction_size_info(&raw_txn);
             match converter
                 .try_into_onchain_transaction(timestamp, raw_txn)
                 .map(|mut txn| {
@@ -453,32 +450,6 @@ impl IndexerStreamCoordinator {
         transactions
     }
 
-    fn ser_size_u32<T: Serialize>(t: &T) -> u32 {
-        bcs::serialized_size(t).expect("serialized_size() failed") as u32
-    }
-
-    fn get_size_info(raw_txn: &TransactionOnChainData) -> TransactionSizeInfo {
-        TransactionSizeInfo {
-            transaction_bytes: Self::ser_size_u32(&raw_txn.transaction),
-            event_size_info: raw_txn
-                .events
-                .iter()
-                .map(|event| EventSizeInfo {
-                    type_tag_bytes: Self::ser_size_u32(event.type_tag()),
-                    total_bytes: event.size() as u32,
-                })
-                .collect(),
-            write_op_size_info: raw_txn
-                .changes
-                .iter()
-                .map(|(state_key, write_op)| WriteOpSizeInfo {
-                    key_bytes: Self::ser_size_u32(state_key),
-                    value_bytes: write_op.size() as u32,
-                })
-                .collect(),
-        }
-    }
-
     fn convert_to_pb_txns(
         api_txns: Vec<(APITransaction, TransactionSizeInfo)>,
     ) -> Vec<TransactionPB> {
@@ -492,6 +463,12 @@ impl IndexerStreamCoordinator {
                     info.epoch.unwrap().0,
                     size_info,
                 )
+                .unwrap_or_else(|error| {
+                    panic!(
+                        "[Indexer Fullnode] Could not convert transaction to protobuf: {:?}",
+                        error
+                    )
+                })
             })
             .collect()
     }
@@ -10,7 +10,9 @@ mod hardware;
 mod latency;
 mod minimum_peers;
 mod node_identity;
+mod plugin;
 mod state_sync_version;
+mod storage_lag;
 mod tps;
 mod traits;
 mod transaction_correctness;
@@ -26,10 +28,13 @@ use self::{
     latency::{LatencyChecker, LatencyCheckerConfig},
     minimum_peers::{MinimumPeersChecker, MinimumPeersCheckerConfig},
     node_identity::{NodeIdentityChecker, NodeIdentityCheckerConfig},
+    plugin::{build_plugin_checker, PluginCheckerConfig},
     state_sync_version::{StateSyncVersionChecker, StateSyncVersionCheckerConfig},
+    storage_lag::{StorageLagChecker, StorageLagCheckerConfig},
     tps::{TpsChecker, TpsCheckerConfig},
     transaction_correctness::{TransactionCorrectnessChecker, TransactionCorrectnessCheckerConfig},
 };
+pub use plugin::{register_checker_plugin, CheckerPlugin};
 use serde::{Deserialize, Serialize};
 pub use traits::{Checker, CheckerError};
 pub use types::{CheckResult, CheckSummary};
@@ -49,7 +54,9 @@ pub enum CheckerConfig {
     Latency(LatencyCheckerConfig),
     MinimumPeers(MinimumPeersCheckerConfig),
     NodeIdentity(NodeIdentityCheckerConfig),
+    Plugin(PluginCheckerConfig),
     StateSyncVersion(StateSyncVersionCheckerConfig),
+    StorageLag(StorageLagCheckerConfig),
     Tps(TpsCheckerConfig),
     TransactionCorrectness(TransactionCorrectnessCheckerConfig),
 }
@@ -68,7 +75,9 @@ impl CheckerConfig {
             Self::Latency(config) => Ok(Box::new(LatencyChecker::new(config))),
             Self::MinimumPeers(config) => Ok(Box::new(MinimumPeersChecker::new(config))),
             Self::NodeIdentity(config) => Ok(Box::new(NodeIdentityChecker::new(config))),
+            Self::Plugin(config) => build_plugin_checker(config),
             Self::StateSyncVersion(config) => Ok(Box::new(StateSyncVersionChecker::new(config))),
+            Self::StorageLag(config) => Ok(Box::new(StorageLagChecker::new(config))),
             Self::Tps(config) => Ok(Box::new(TpsChecker::new(config)?)),
             Self::TransactionCorrectness(config) => {
                 Ok(Box::new(TransactionCorrectnessChecker::new(config)))
@@ -0,0 +1,91 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Plugins allow new Checkers to be added to NHC without modifying the set of
+//! built-in Checkers (i.e. CheckerConfig). A plugin is registered by name when
+//! NHC starts up, and can then be composed into any baseline configuration
+//! (alongside the built-in Checkers) using the `Plugin` checker type, e.g.
+//!
+//! ```yaml
+//! checkers:
+//!   - type: "Plugin"
+//!     plugin_name: "TlsExpiry"
+//!     plugin_config:
+//!       min_days_remaining: 14
+//! ```
+
+use super::{Checker, CommonCheckerConfig};
+use anyhow::{anyhow, bail, Result};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+/// All plugins registered with NHC (indexed by plugin name).
+static CHECKER_PLUGINS: Lazy<RwLock<HashMap<String, Arc<dyn CheckerPlugin>>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// A CheckerPlugin is responsible for building a Checker from the plugin
+/// specific configuration found in a baseline configuration.
+pub trait CheckerPlugin: Send + Sync {
+    /// The name used to reference this plugin in baseline configurations.
+    fn name(&self) -> &'static str;
+
+    /// Builds a new Checker using the given plugin specific config. This
+    /// should return an error if the config is invalid.
+    fn build_checker(
+        &self,
+        common: CommonCheckerConfig,
+        plugin_config: serde_yaml::Value,
+    ) -> Result<Box<dyn Checker>>;
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PluginCheckerConfig {
+    #[serde(flatten)]
+    pub common: CommonCheckerConfig,
+
+    /// The name of the registered plugin to build the Checker with.
+    pub plugin_name: String,
+
+    /// The plugin specific config (this is passed to the plugin as-is).
+    #[serde(default)]
+    pub plugin_config: serde_yaml::Value,
+}
+
+/// Registers the given plugin so that it can be used in baseline
+/// configurations. This must be called before any configurations are
+/// read. Registering two plugins with the same name is an error.
+pub fn register_checker_plugin(plugin: Arc<dyn CheckerPlugin>) -> Result<()> {
+    let mut checker_plugins = CHECKER_PLUGINS
+        .write()
+        .map_err(|error| anyhow!("Failed to lock the checker plugins: {}", error))?;
+    let plugin_name = plugin.name().to_string();
+    if checker_plugins.contains_key(&plugin_name) {
+        bail!(
+            "A checker plugin named {} is already registered",
+            plugin_name
+        );
+    }
+    checker_plugins.insert(plugin_name, plugin);
+    Ok(())
+}
+
+/// Builds the Checker for the given plugin config using the registered plugin.
+pub fn build_plugin_checker(config: PluginCheckerConfig) -> Result<Box<dyn Checker>> {
+    let plugin = CHECKER_PLUGINS
+        .read()
+        .map_err(|error| anyhow!("Failed to lock the checker plugins: {}", error))?
+        .get(&config.plugin_name)
+        .cloned()
+        .ok_or_else(|| {
+            anyhow!(
+                "No checker plugin named {} is registered",
+                config.plugin_name
+            )
+        })?;
+    plugin.build_checker(config.common, config.plugin_config)
+}
@@ -0,0 +1,138 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This Checker is primarily useful for validators. It verifies that storage
+//! is keeping up with consensus, i.e. the latest version committed to storage
+//! is not lagging too far behind the latest version committed by consensus.
+
+use super::{CheckResult, Checker, CheckerError, CommonCheckerConfig};
+use crate::{
+    get_provider,
+    provider::{
+        metrics::{get_metric, GetMetricResult, MetricsProvider},
+        Provider, ProviderCollection,
+    },
+};
+use anyhow::Result;
+use prometheus_parse::Scrape;
+use serde::{Deserialize, Serialize};
+
+// TODO: When we have it, switch to using a crate that unifies metric names.
+// As it is now, these metric names could change and we'd never catch it here
+// at compile time.
+const CONSENSUS_METRIC: &str = "aptos_consensus_last_committed_version";
+const STORAGE_METRIC: &str = "aptos_storage_latest_transaction_version";
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StorageLagCheckerConfig {
+    #[serde(flatten)]
+    pub common: CommonCheckerConfig,
+
+    /// The maximum number of versions that storage is allowed to lag
+    /// behind consensus.
+    #[serde(default = "StorageLagCheckerConfig::default_max_storage_lag")]
+    pub max_storage_lag: u64,
+}
+
+impl StorageLagCheckerConfig {
+    fn default_max_storage_lag() -> u64 {
+        1000
+    }
+}
+
+#[derive(Debug)]
+pub struct StorageLagChecker {
+    config: StorageLagCheckerConfig,
+}
+
+impl StorageLagChecker {
+    pub fn new(config: StorageLagCheckerConfig) -> Self {
+        Self { config }
+    }
+
+    fn get_version(&self, metrics: &Scrape, metric: &str) -> GetMetricResult {
+        let result_on_missing_fn = || {
+            Self::build_result(
+                "Storage lag metric missing".to_string(),
+                0,
+                format!(
+                    "The metrics from the target node are missing the metric: {}",
+                    metric
+                ),
+            )
+        };
+        get_metric(metrics, metric, None, result_on_missing_fn)
+    }
+
+    fn build_check_result(&self, consensus_version: u64, storage_version: u64) -> CheckResult {
+        let storage_lag = consensus_version.saturating_sub(storage_version);
+        if storage_lag > self.config.max_storage_lag {
+            Self::build_result(
+                "Storage is lagging behind consensus".to_string(),
+                50,
+                format!(
+                    "The latest version committed to storage ({}) is {} versions behind \
+                    the latest version committed by consensus ({}), more than the allowed \
+                    lag of {}. Your node's storage may not be keeping up.",
+                    storage_version, storage_lag, consensus_version, self.config.max_storage_lag
+                ),
+            )
+        } else {
+            Self::build_result(
+                "Storage is keeping up with consensus".to_string(),
+                100,
+                format!(
+                    "The latest version committed to storage ({}) is within {} versions \
+                    of the latest version committed by consensus ({}).",
+                    storage_version, self.config.max_storage_lag, consensus_version
+                ),
+            )
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Checker for StorageLagChecker {
+    /// Assert that storage is not lagging too far behind consensus on the target node.
+    async fn check(
+        &self,
+        providers: &ProviderCollection,
+    ) -> Result<Vec<CheckResult>, CheckerError> {
+        let target_metrics_provider = get_provider!(
+            providers.target_metrics_provider,
+            self.config.common.required,
+            MetricsProvider
+        );
+
+        let scrape = match target_metrics_provider.provide().await {
+            Ok(scrape) => scrape,
+            Err(e) => {
+                return Ok(vec![Self::build_result(
+                    "Failed to check storage lag".to_string(),
+                    0,
+                    format!("Failed to scrape metrics from your node: {:#}", e),
+                )])
+            },
+        };
+
+        let mut check_results = vec![];
+
+        let consensus_version = self
+            .get_version(&scrape, CONSENSUS_METRIC)
+            .unwrap(&mut check_results);
+
+        let storage_version = self
+            .get_version(&scrape, STORAGE_METRIC)
+            .unwrap(&mut check_results);
+
+        if !check_results.is_empty() {
+            return Ok(check_results);
+        }
+
+        Ok(vec![self.build_check_result(
+            consensus_version.unwrap(),
+            storage_version.unwrap(),
+        )])
+    }
+}
@@ -8,4 +8,8 @@ mod provider;
 mod runner;
 pub mod server;
 
-pub use checker::CheckSummary;
+pub use checker::{
+    register_checker_plugin, CheckResult, CheckSummary, Checker, CheckerError, CheckerPlugin,
+    CommonCheckerConfig,
+};
+pub use provider::ProviderCollection;
@@ -209,4 +209,25 @@ impl LedgerUpdateOutput {
     pub fn first_version(&self) -> Version {
         self.transaction_accumulator.num_leaves() - self.to_commit.len() as Version
     }
+
+    /// Returns the (estimated) number of bytes held by the output, i.e., the
+    /// sizes of all write sets and events of the transactions to commit.
+    pub fn estimated_size_bytes(&self) -> u64 {
+        self.to_commit
+            .iter()
+            .map(|txn_to_commit| {
+                let write_set_size: usize = txn_to_commit
+                    .write_set()
+                    .iter()
+                    .map(|(key, op)| key.size() + op.size())
+                    .sum();
+                let events_size: usize = txn_to_commit
+                    .events()
+                    .iter()
+                    .map(|event| event.size())
+                    .sum();
+                (write_set_size + events_size) as u64
+            })
+            .sum()
+    }
 }
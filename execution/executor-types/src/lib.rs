@@ -46,6 +46,7 @@ mod error;
 mod executed_chunk;
 pub mod execution_output;
 mod ledger_update_output;
+pub mod memory_budget;
 pub mod parsed_transaction_output;
 pub mod state_checkpoint_output;
 
@@ -136,6 +137,10 @@ pub trait BlockExecutorTrait: Send + Sync {
     /// Reset the internal state including cache with newly fetched latest committed block from storage.
     fn reset(&self) -> Result<()>;
 
+    /// Returns true iff the given block has been executed (including the ledger update), i.e.,
+    /// its output is cached and blocks can be executed on top of it.
+    fn is_block_executed(&self, block_id: HashValue) -> bool;
+
     /// Executes a block - TBD, this API will be removed in favor of `execute_and_state_checkpoint`, followed
     /// by `ledger_update` once we have ledger update as a separate pipeline phase.
     fn execute_block(
@@ -165,6 +170,13 @@ pub trait BlockExecutorTrait: Send + Sync {
         state_checkpoint_output: StateCheckpointOutput,
     ) -> ExecutorResult<StateComputeResult>;
 
+    /// Warms up the executor caches (e.g., the VM) for executing blocks on top of the
+    /// given executed block. This is a best-effort optimization (e.g., used to prepare
+    /// the execution of the next epoch before the reconfiguration commits).
+    fn warm_up(&self, _block_id: HashValue) -> ExecutorResult<()> {
+        Ok(())
+    }
+
     /// Saves eligible blocks to persistent storage.
     /// If we have multiple blocks and not all of them have signatures, we may send them to storage
     /// in a few batches. For example, if we have
@@ -0,0 +1,357 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_logger::{info, warn};
+use aptos_metrics_core::{register_int_gauge, register_int_gauge_vec, IntGauge, IntGaugeVec};
+use once_cell::sync::{Lazy, OnceCell};
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+/// The minimum interval between two samples of the process RSS
+const RSS_SAMPLE_INTERVAL_MS: u64 = 1_000;
+
+/// The memory budget shared by all subsystems (set once on node startup)
+static MEMORY_BUDGET: OnceCell<Arc<MemoryBudget>> = OnceCell::new();
+
+/// The bytes allocated by each memory consumer
+static MEMORY_BUDGET_ALLOCATED_BYTES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_memory_budget_allocated_bytes",
+        "The number of bytes allocated by each consumer of the memory budget",
+        &["consumer"]
+    )
+    .unwrap()
+});
+
+/// The estimated RSS of the node (as seen by the memory budget)
+static MEMORY_BUDGET_ESTIMATED_RSS_BYTES: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_memory_budget_estimated_rss_bytes",
+        "The estimated RSS (in bytes) of the node, as seen by the memory budget"
+    )
+    .unwrap()
+});
+
+/// Whether or not the memory budget is applying backpressure
+static MEMORY_BUDGET_BACKPRESSURE: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_memory_budget_backpressure",
+        "Whether or not the memory budget is applying backpressure (1 if so, 0 otherwise)"
+    )
+    .unwrap()
+});
+
+/// The subsystems that hold large allocations tracked by the memory budget
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MemoryConsumer {
+    BlockOutputs,       // Executed (but uncommitted) block outputs
+    QuorumStoreBatches, // Batch payloads held in memory by quorum store
+    StateSyncChunks,    // Executed (but uncommitted) state sync chunks
+}
+
+impl MemoryConsumer {
+    const NUM_CONSUMERS: usize = 3;
+
+    /// Returns a summary label for the consumer
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            Self::BlockOutputs => "block_outputs",
+            Self::QuorumStoreBatches => "quorum_store_batches",
+            Self::StateSyncChunks => "state_sync_chunks",
+        }
+    }
+
+    fn get_index(&self) -> usize {
+        match self {
+            Self::BlockOutputs => 0,
+            Self::QuorumStoreBatches => 1,
+            Self::StateSyncChunks => 2,
+        }
+    }
+}
+
+/// A memory budget that tracks the large allocations of several subsystems
+/// (e.g., the executor, quorum store and state sync) and determines when the
+/// node is approaching its target RSS. This allows all subsystems to apply
+/// coordinated backpressure, instead of each subsystem enforcing independent
+/// limits that collectively overcommit memory.
+///
+/// The RSS is estimated using the last sampled RSS of the process, plus any
+/// tracked allocations made since the sample was taken. If the RSS cannot be
+/// sampled (e.g., on non-Linux platforms), only tracked allocations are used.
+pub struct MemoryBudget {
+    enabled: bool,
+    backpressure_threshold_bytes: u64,
+
+    allocated_bytes: [AtomicU64; MemoryConsumer::NUM_CONSUMERS],
+    allocated_bytes_at_sample: AtomicU64, // The total allocated bytes when the RSS was last sampled
+    sampled_rss_bytes: AtomicU64,         // The last sampled RSS (0 if unavailable)
+    last_sample_time_ms: AtomicU64,       // The time of the last RSS sample (relative to start)
+    start_time: Instant,
+
+    applying_backpressure: AtomicBool,
+}
+
+impl MemoryBudget {
+    pub fn new(enabled: bool, target_rss_bytes: u64, backpressure_threshold_percent: u64) -> Self {
+        let backpressure_threshold_bytes =
+            (target_rss_bytes as u128 * backpressure_threshold_percent as u128 / 100) as u64;
+        Self {
+            enabled,
+            backpressure_threshold_bytes,
+            allocated_bytes: Default::default(),
+            allocated_bytes_at_sample: AtomicU64::new(0),
+            sampled_rss_bytes: AtomicU64::new(0),
+            last_sample_time_ms: AtomicU64::new(0),
+            start_time: Instant::now(),
+            applying_backpressure: AtomicBool::new(false),
+        }
+    }
+
+    /// Creates a memory budget that tracks allocations, but never applies backpressure
+    pub fn disabled() -> Self {
+        Self::new(false, 0, 0)
+    }
+
+    /// Sets the memory budget shared by all subsystems. This should only be
+    /// called once (on node startup), before any subsystem is started.
+    pub fn set_global_once(memory_budget: MemoryBudget) {
+        if MEMORY_BUDGET.set(Arc::new(memory_budget)).is_err() {
+            warn!("The global memory budget has already been set!");
+        }
+    }
+
+    /// Returns the memory budget shared by all subsystems. If no budget
+    /// has been set, a disabled budget is used.
+    pub fn global() -> Arc<MemoryBudget> {
+        MEMORY_BUDGET
+            .get_or_init(|| Arc::new(MemoryBudget::disabled()))
+            .clone()
+    }
+
+    /// Records an allocation of the given number of bytes for the consumer.
+    /// The allocation must later be released using `record_release()`.
+    pub fn record_allocation(&self, consumer: MemoryConsumer, num_bytes: u64) {
+        let allocated_bytes = self.allocated_bytes[consumer.get_index()]
+            .fetch_add(num_bytes, Ordering::Relaxed)
+            .saturating_add(num_bytes);
+        update_allocated_bytes_metric(consumer, allocated_bytes);
+    }
+
+    /// Records the release of the given number of bytes for the consumer
+    pub fn record_release(&self, consumer: MemoryConsumer, num_bytes: u64) {
+        let previous_bytes = self.allocated_bytes[consumer.get_index()]
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |allocated_bytes| {
+                Some(allocated_bytes.saturating_sub(num_bytes))
+            })
+            .unwrap_or_default(); // The closure always returns Some
+        update_allocated_bytes_metric(consumer, previous_bytes.saturating_sub(num_bytes));
+    }
+
+    /// Records an allocation and returns a reservation that releases
+    /// the allocation when it is dropped.
+    pub fn reserve(
+        self: &Arc<Self>,
+        consumer: MemoryConsumer,
+        num_bytes: u64,
+    ) -> MemoryReservation {
+        self.record_allocation(consumer, num_bytes);
+        MemoryReservation {
+            memory_budget: self.clone(),
+            consumer,
+            num_bytes,
+        }
+    }
+
+    /// Returns the number of bytes currently allocated by the consumer
+    pub fn get_allocated_bytes(&self, consumer: MemoryConsumer) -> u64 {
+        self.allocated_bytes[consumer.get_index()].load(Ordering::Relaxed)
+    }
+
+    /// Returns the total number of bytes currently allocated by all consumers
+    pub fn get_total_allocated_bytes(&self) -> u64 {
+        self.allocated_bytes
+            .iter()
+            .map(|allocated_bytes| allocated_bytes.load(Ordering::Relaxed))
+            .fold(0, u64::saturating_add)
+    }
+
+    /// Returns the estimated RSS of the node (in bytes)
+    pub fn get_estimated_rss_bytes(&self) -> u64 {
+        // Sample the RSS (if enough time has passed since the last sample)
+        self.maybe_sample_rss();
+
+        // If the RSS is unavailable, only the tracked allocations are used
+        let total_allocated_bytes = self.get_total_allocated_bytes();
+        let sampled_rss_bytes = self.sampled_rss_bytes.load(Ordering::Relaxed);
+        if sampled_rss_bytes == 0 {
+            return total_allocated_bytes;
+        }
+
+        // Otherwise, add any allocations made since the last sample
+        let allocated_bytes_at_sample = self.allocated_bytes_at_sample.load(Ordering::Relaxed);
+        sampled_rss_bytes
+            .saturating_add(total_allocated_bytes.saturating_sub(allocated_bytes_at_sample))
+    }
+
+    /// Returns true iff subsystems should apply backpressure (i.e., the
+    /// budget is enabled and the node is approaching its target RSS).
+    pub fn should_apply_backpressure(&self) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        // Determine if backpressure should be applied
+        let estimated_rss_bytes = self.get_estimated_rss_bytes();
+        let apply_backpressure = estimated_rss_bytes >= self.backpressure_threshold_bytes;
+        MEMORY_BUDGET_ESTIMATED_RSS_BYTES.set(estimated_rss_bytes as i64);
+
+        // Log any changes to the backpressure state
+        let applying_backpressure = self
+            .applying_backpressure
+            .swap(apply_backpressure, Ordering::Relaxed);
+        if apply_backpressure != applying_backpressure {
+            info!(
+                "Memory budget backpressure updated! Applying backpressure: {}, estimated RSS: {}, threshold: {}",
+                apply_backpressure, estimated_rss_bytes, self.backpressure_threshold_bytes
+            );
+            MEMORY_BUDGET_BACKPRESSURE.set(apply_backpressure as i64);
+        }
+
+        apply_backpressure
+    }
+
+    /// Samples the RSS of the process (if the sample interval has elapsed)
+    fn maybe_sample_rss(&self) {
+        // Check if the sample interval has elapsed (and claim the sample)
+        let current_time_ms = self.start_time.elapsed().as_millis() as u64;
+        let last_sample_time_ms = self.last_sample_time_ms.load(Ordering::Relaxed);
+        if last_sample_time_ms != 0
+            && current_time_ms.saturating_sub(last_sample_time_ms) < RSS_SAMPLE_INTERVAL_MS
+        {
+            return;
+        }
+        if self
+            .last_sample_time_ms
+            .compare_exchange(
+                last_sample_time_ms,
+                current_time_ms.max(1),
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            )
+            .is_err()
+        {
+            return; // Another thread is sampling the RSS
+        }
+
+        // Sample the RSS
+        if let Some(rss_bytes) = read_process_rss_bytes() {
+            self.allocated_bytes_at_sample
+                .store(self.get_total_allocated_bytes(), Ordering::Relaxed);
+            self.sampled_rss_bytes.store(rss_bytes, Ordering::Relaxed);
+        }
+    }
+}
+
+/// A reservation of memory in the memory budget. The reserved bytes
+/// are released when the reservation is dropped.
+pub struct MemoryReservation {
+    memory_budget: Arc<MemoryBudget>,
+    consumer: MemoryConsumer,
+    num_bytes: u64,
+}
+
+impl MemoryReservation {
+    /// Returns the number of reserved bytes
+    pub fn num_bytes(&self) -> u64 {
+        self.num_bytes
+    }
+}
+
+impl Drop for MemoryReservation {
+    fn drop(&mut self) {
+        self.memory_budget
+            .record_release(self.consumer, self.num_bytes);
+    }
+}
+
+/// Updates the allocated bytes metric for the given consumer
+fn update_allocated_bytes_metric(consumer: MemoryConsumer, allocated_bytes: u64) {
+    MEMORY_BUDGET_ALLOCATED_BYTES
+        .with_label_values(&[consumer.get_label()])
+        .set(allocated_bytes as i64);
+}
+
+/// Reads the RSS of the current process (in bytes). Returns None if
+/// the RSS is unavailable (e.g., the platform is not Linux).
+fn read_process_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_rss_bytes(&status)
+}
+
+/// Parses the RSS (in bytes) from the contents of `/proc/<pid>/status`
+fn parse_rss_bytes(status: &str) -> Option<u64> {
+    let rss_line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    let rss_kilobytes = rss_line
+        .trim_start_matches("VmRSS:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    rss_kilobytes.checked_mul(1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_memory_reservations() {
+        // Reserve memory for several consumers
+        let memory_budget = Arc::new(MemoryBudget::disabled());
+        let block_reservation = memory_budget.reserve(MemoryConsumer::BlockOutputs, 100);
+        let chunk_reservation = memory_budget.reserve(MemoryConsumer::StateSyncChunks, 50);
+        memory_budget.record_allocation(MemoryConsumer::QuorumStoreBatches, 25);
+
+        // Verify the allocations are tracked
+        assert_eq!(
+            memory_budget.get_allocated_bytes(MemoryConsumer::BlockOutputs),
+            100
+        );
+        assert_eq!(memory_budget.get_total_allocated_bytes(), 175);
+
+        // Release the memory and verify the allocations are updated
+        drop(block_reservation);
+        memory_budget.record_release(MemoryConsumer::QuorumStoreBatches, 25);
+        assert_eq!(memory_budget.get_total_allocated_bytes(), 50);
+        assert_eq!(chunk_reservation.num_bytes(), 50);
+        drop(chunk_reservation);
+        assert_eq!(memory_budget.get_total_allocated_bytes(), 0);
+
+        // Verify the budget never applies backpressure when disabled
+        assert!(!memory_budget.should_apply_backpressure());
+    }
+
+    #[test]
+    fn test_backpressure() {
+        // Create a budget with a target RSS that is always exceeded
+        let memory_budget = Arc::new(MemoryBudget::new(true, 1, 100));
+        let _reservation = memory_budget.reserve(MemoryConsumer::BlockOutputs, 10);
+
+        // Verify backpressure is applied
+        assert!(memory_budget.should_apply_backpressure());
+    }
+
+    #[test]
+    fn test_parse_rss_bytes() {
+        let status = "Name:\taptos-node\nVmPeak:\t 2048 kB\nVmRSS:\t    1024 kB\nThreads:\t8\n";
+        assert_eq!(parse_rss_bytes(status), Some(1024 * 1024));
+        assert_eq!(parse_rss_bytes("Name:\taptos-node\n"), None);
+    }
+}
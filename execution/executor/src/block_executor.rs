@@ -7,6 +7,7 @@
 use crate::{
     components::{
         apply_chunk_output::ApplyChunkOutput, block_tree::BlockTree, chunk_output::ChunkOutput,
+        speculative_state::get_speculative_state_config,
     },
     logging::{LogEntry, LogSchema},
     metrics::{
@@ -27,7 +28,10 @@ use aptos_infallible::RwLock;
 use aptos_logger::prelude::*;
 use aptos_scratchpad::SparseMerkleTree;
 use aptos_storage_interface::{
-    async_proof_fetcher::AsyncProofFetcher, cached_state_view::CachedStateView, DbReaderWriter,
+    async_proof_fetcher::AsyncProofFetcher,
+    cached_state_view::CachedStateView,
+    read_context::{ReadContext, ReadSubsystem},
+    DbReaderWriter,
 };
 use aptos_types::{
     block_executor::{
@@ -37,7 +41,7 @@ use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
     state_store::{state_value::StateValue, StateViewId},
 };
-use aptos_vm::AptosVM;
+use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
 use fail::fail_point;
 use std::{marker::PhantomData, sync::Arc};
 
@@ -47,6 +51,9 @@ pub trait TransactionBlockExecutor: Send + Sync {
         state_view: CachedStateView,
         onchain_config: BlockExecutorConfigFromOnchain,
     ) -> Result<ChunkOutput>;
+
+    /// Warms up the executor (e.g., the VM caches) for executing transactions on the given state
+    fn warm_up(_state_view: &CachedStateView) {}
 }
 
 impl TransactionBlockExecutor for AptosVM {
@@ -57,6 +64,11 @@ impl TransactionBlockExecutor for AptosVM {
     ) -> Result<ChunkOutput> {
         ChunkOutput::by_transaction_execution::<AptosVM>(transactions, state_view, onchain_config)
     }
+
+    fn warm_up(state_view: &CachedStateView) {
+        // Creating the VM loads the on-chain configs and populates the warm VM cache
+        let _ = AptosVM::new(&state_view.as_move_resolver(), None);
+    }
 }
 
 pub struct BlockExecutor<V> {
@@ -109,12 +121,30 @@ where
         Ok(())
     }
 
+    fn is_block_executed(&self, block_id: HashValue) -> bool {
+        self.inner
+            .read()
+            .as_ref()
+            .map_or(false, |inner| inner.is_block_executed(block_id))
+    }
+
+    fn warm_up(&self, block_id: HashValue) -> ExecutorResult<()> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Execution);
+        self.maybe_initialize()?;
+        self.inner
+            .read()
+            .as_ref()
+            .expect("BlockExecutor is not reset")
+            .warm_up(block_id)
+    }
+
     fn execute_and_state_checkpoint(
         &self,
         block: ExecutableBlock,
         parent_block_id: HashValue,
         onchain_config: BlockExecutorConfigFromOnchain,
     ) -> ExecutorResult<StateCheckpointOutput> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Execution);
         self.maybe_initialize()?;
         self.inner
             .read()
@@ -129,6 +159,7 @@ where
         parent_block_id: HashValue,
         state_checkpoint_output: StateCheckpointOutput,
     ) -> ExecutorResult<StateComputeResult> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Execution);
         self.maybe_initialize()?;
         self.inner
             .read()
@@ -187,6 +218,29 @@ where
         self.block_tree.root_block().id
     }
 
+    fn is_block_executed(&self, block_id: HashValue) -> bool {
+        matches!(
+            self.block_tree.get_blocks_opt(&[block_id]).as_deref(),
+            Ok([Some(block)]) if block.output.has_ledger_update()
+        )
+    }
+
+    fn warm_up(&self, block_id: HashValue) -> ExecutorResult<()> {
+        let _timer = APTOS_EXECUTOR_OTHER_TIMERS_SECONDS
+            .with_label_values(&["warm_up"])
+            .start_timer();
+        let block = self.block_tree.get_block(block_id)?;
+        let state_view = CachedStateView::new(
+            StateViewId::Miscellaneous,
+            Arc::clone(&self.db.reader),
+            block.output.next_version(),
+            block.output.state().current.clone(),
+            Arc::new(AsyncProofFetcher::new(self.db.reader.clone())),
+        )?;
+        V::warm_up(&state_view);
+        Ok(())
+    }
+
     fn execute_and_state_checkpoint(
         &self,
         block: ExecutableBlock,
@@ -205,6 +259,18 @@ where
             .pop()
             .expect("Must exist.")
             .ok_or(ExecutorError::BlockNotFound(parent_block_id))?;
+        if let Some(existing_block) = block_vec.pop().expect("Must exist.") {
+            // The block has already been executed (e.g., optimistically, before it was
+            // ordered), so the existing output will be reused by the ledger update.
+            if existing_block.output.has_ledger_update() {
+                parent_block.ensure_has_child(block_id)?;
+                info!(
+                    LogSchema::new(LogEntry::BlockExecutor).block_id(block_id),
+                    "skip_execution_of_executed_block"
+                );
+                return Ok(StateCheckpointOutput::default());
+            }
+        }
         let parent_output = &parent_block.output;
         info!(
             LogSchema::new(LogEntry::BlockExecutor).block_id(block_id),
@@ -326,6 +392,14 @@ where
             current_output.output.epoch_state().clone(),
         );
         current_output.output.set_ledger_update(output);
+
+        // Account for the speculative state of the block, and apply the eviction
+        // policy (in case the commit stage is lagging behind).
+        current_output.account_speculative_memory();
+        let _ = self
+            .block_tree
+            .evict_speculative_state(&get_speculative_state_config());
+
         Ok(state_compute_result)
     }
 
@@ -396,6 +470,15 @@ where
             APTOS_EXECUTOR_TRANSACTIONS_SAVED.observe(to_commit as f64);
 
             let result_in_memory_state = block.output.state().clone();
+
+            // Prevent the state cache from being flushed during the commit. If the
+            // cache has already been flushed, storage must read the state directly.
+            let state_cache_flushed = block.lock_state_cache();
+            let sharded_state_cache = if *state_cache_flushed {
+                None
+            } else {
+                Some(&block.output.get_ledger_update().sharded_state_cache)
+            };
             self.db.writer.save_transactions(
                 txns_to_commit,
                 first_version,
@@ -413,8 +496,9 @@ where
                     .get_ledger_update()
                     .state_updates_until_last_checkpoint
                     .clone(),
-                Some(&block.output.get_ledger_update().sharded_state_cache),
+                sharded_state_cache,
             )?;
+            drop(state_cache_flushed);
             first_version += txns_to_commit.len() as u64;
             committed_block = block.clone();
         }
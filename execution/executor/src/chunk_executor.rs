@@ -12,9 +12,9 @@ use crate::{
     },
     logging::{LogEntry, LogSchema},
     metrics::{
-        APTOS_CHUNK_EXECUTOR_OTHER_SECONDS, APTOS_EXECUTOR_APPLY_CHUNK_SECONDS,
-        APTOS_EXECUTOR_COMMIT_CHUNK_SECONDS, APTOS_EXECUTOR_EXECUTE_CHUNK_SECONDS,
-        APTOS_EXECUTOR_VM_EXECUTE_CHUNK_SECONDS,
+        APTOS_CHUNK_EXECUTOR_OTHER_SECONDS, APTOS_EXECUTOR_AGGREGATED_STATE_UPDATES,
+        APTOS_EXECUTOR_APPLY_CHUNK_SECONDS, APTOS_EXECUTOR_COMMIT_CHUNK_SECONDS,
+        APTOS_EXECUTOR_EXECUTE_CHUNK_SECONDS, APTOS_EXECUTOR_VM_EXECUTE_CHUNK_SECONDS,
     },
 };
 use anyhow::{anyhow, ensure, Result};
@@ -28,14 +28,17 @@ use aptos_infallible::{Mutex, RwLock};
 use aptos_logger::prelude::*;
 use aptos_metrics_core::TimerHelper;
 use aptos_storage_interface::{
-    async_proof_fetcher::AsyncProofFetcher, cached_state_view::CachedStateView,
-    state_delta::StateDelta, DbReaderWriter, ExecutedTrees,
+    async_proof_fetcher::AsyncProofFetcher,
+    cached_state_view::CachedStateView,
+    read_context::{ReadContext, ReadSubsystem},
+    state_delta::StateDelta,
+    DbReaderWriter, ExecutedTrees,
 };
 use aptos_types::{
     block_executor::config::BlockExecutorConfigFromOnchain,
     contract_event::ContractEvent,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::StateViewId,
+    state_store::{ShardedStateUpdates, StateViewId},
     transaction::{
         signature_verified_transaction::SignatureVerifiedTransaction, Transaction,
         TransactionAuxiliaryData, TransactionInfo, TransactionListWithProof, TransactionOutput,
@@ -48,7 +51,7 @@ use fail::fail_point;
 use itertools::multizip;
 use once_cell::sync::Lazy;
 use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
-use std::{iter::once, marker::PhantomData, sync::Arc};
+use std::{collections::HashSet, iter::once, marker::PhantomData, sync::Arc};
 
 pub static SIG_VERIFY_POOL: Lazy<Arc<rayon::ThreadPool>> = Lazy::new(|| {
     Arc::new(
@@ -63,13 +66,23 @@ pub static SIG_VERIFY_POOL: Lazy<Arc<rayon::ThreadPool>> = Lazy::new(|| {
 pub struct ChunkExecutor<V> {
     db: DbReaderWriter,
     inner: RwLock<Option<ChunkExecutorInner<V>>>,
+    aggregate_write_sets: bool,
 }
 
 impl<V: VMExecutor> ChunkExecutor<V> {
     pub fn new(db: DbReaderWriter) -> Self {
+        Self::new_with_write_set_aggregation(db, false)
+    }
+
+    /// Creates a new chunk executor. If `aggregate_write_sets` is true, the state
+    /// updates of each chunk are deduplicated before commit (i.e., only the last
+    /// write per key is persisted). This is only safe when the per-version
+    /// granularity of state values isn't required (e.g., syncing historical data).
+    pub fn new_with_write_set_aggregation(db: DbReaderWriter, aggregate_write_sets: bool) -> Self {
         Self {
             db,
             inner: RwLock::new(None),
+            aggregate_write_sets,
         }
     }
 
@@ -88,6 +101,7 @@ impl<V: VMExecutor> ChunkExecutorTrait for ChunkExecutor<V> {
         verified_target_li: &LedgerInfoWithSignatures,
         epoch_change_li: Option<&LedgerInfoWithSignatures>,
     ) -> Result<()> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Execution);
         self.maybe_initialize()?;
         self.inner
             .read()
@@ -102,6 +116,7 @@ impl<V: VMExecutor> ChunkExecutorTrait for ChunkExecutor<V> {
         verified_target_li: &LedgerInfoWithSignatures,
         epoch_change_li: Option<&LedgerInfoWithSignatures>,
     ) -> Result<()> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Execution);
         self.inner
             .read()
             .as_ref()
@@ -114,6 +129,7 @@ impl<V: VMExecutor> ChunkExecutorTrait for ChunkExecutor<V> {
     }
 
     fn update_ledger(&self) -> Result<()> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Execution);
         self.inner
             .read()
             .as_ref()
@@ -130,7 +146,10 @@ impl<V: VMExecutor> ChunkExecutorTrait for ChunkExecutor<V> {
     }
 
     fn reset(&self) -> Result<()> {
-        *self.inner.write() = Some(ChunkExecutorInner::new(self.db.clone())?);
+        *self.inner.write() = Some(ChunkExecutorInner::new(
+            self.db.clone(),
+            self.aggregate_write_sets,
+        )?);
         Ok(())
     }
 
@@ -142,15 +161,17 @@ impl<V: VMExecutor> ChunkExecutorTrait for ChunkExecutor<V> {
 struct ChunkExecutorInner<V> {
     db: DbReaderWriter,
     commit_queue: Mutex<ChunkCommitQueue>,
+    aggregate_write_sets: bool,
     _phantom: PhantomData<V>,
 }
 
 impl<V: VMExecutor> ChunkExecutorInner<V> {
-    pub fn new(db: DbReaderWriter) -> Result<Self> {
+    pub fn new(db: DbReaderWriter, aggregate_write_sets: bool) -> Result<Self> {
         let commit_queue = Mutex::new(ChunkCommitQueue::new_from_db(&db.reader)?);
         Ok(Self {
             db,
             commit_queue,
+            aggregate_write_sets,
             _phantom: PhantomData,
         })
     }
@@ -168,32 +189,33 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
 
     fn commit_chunk_impl(&self) -> Result<ExecutedChunk> {
         let _timer = APTOS_CHUNK_EXECUTOR_OTHER_SECONDS.timer_with(&["commit_chunk_impl__total"]);
-        let (persisted_state, chunk) = {
+        let (persisted_state, mut chunk) = {
             let _timer = APTOS_CHUNK_EXECUTOR_OTHER_SECONDS
                 .timer_with(&["commit_chunk_impl__next_chunk_to_commit"]);
             self.commit_queue.lock().next_chunk_to_commit()?
         };
 
+        if self.aggregate_write_sets {
+            let _timer = APTOS_CHUNK_EXECUTOR_OTHER_SECONDS
+                .timer_with(&["commit_chunk_impl__aggregate_write_sets"]);
+            let num_dropped_updates = aggregate_state_updates(
+                chunk
+                    .ledger_update_output
+                    .to_commit
+                    .iter_mut()
+                    .map(|txn_to_commit| &mut txn_to_commit.state_updates),
+            );
+            APTOS_EXECUTOR_AGGREGATED_STATE_UPDATES.inc_by(num_dropped_updates as u64);
+        }
+
         if chunk.ledger_info.is_some() || !chunk.transactions_to_commit().is_empty() {
             let _timer =
                 APTOS_CHUNK_EXECUTOR_OTHER_SECONDS.timer_with(&["commit_chunk_impl__save_txns"]);
-            fail_point!("executor::commit_chunk", |_| {
-                Err(anyhow::anyhow!("Injected error in commit_chunk"))
-            });
-            self.db.writer.save_transactions(
-                chunk.transactions_to_commit(),
-                persisted_state.next_version(),
-                persisted_state.base_version,
-                chunk.ledger_info.as_ref(),
-                false, // sync_commit
-                chunk.result_state.clone(),
-                // TODO(aldenhu): avoid cloning
-                chunk
-                    .ledger_update_output
-                    .state_updates_until_last_checkpoint
-                    .clone(),
-                Some(&chunk.ledger_update_output.sharded_state_cache),
-            )?;
+            if let Err(error) = self.save_chunk(&persisted_state, &chunk) {
+                // Put the chunk back, so that the commit can be retried
+                self.commit_queue.lock().return_chunk_to_commit(chunk)?;
+                return Err(error);
+            }
         }
 
         DEFAULT_DROPPER.schedule_drop(persisted_state);
@@ -207,6 +229,27 @@ impl<V: VMExecutor> ChunkExecutorInner<V> {
         Ok(chunk)
     }
 
+    fn save_chunk(&self, persisted_state: &StateDelta, chunk: &ExecutedChunk) -> Result<()> {
+        fail_point!("executor::commit_chunk", |_| {
+            Err(anyhow::anyhow!("Injected error in commit_chunk"))
+        });
+        self.db.writer.save_transactions(
+            chunk.transactions_to_commit(),
+            persisted_state.next_version(),
+            persisted_state.base_version,
+            chunk.ledger_info.as_ref(),
+            false, // sync_commit
+            chunk.result_state.clone(),
+            // TODO(aldenhu): avoid cloning
+            chunk
+                .ledger_update_output
+                .state_updates_until_last_checkpoint
+                .clone(),
+            Some(&chunk.ledger_update_output.sharded_state_cache),
+        )?;
+        Ok(())
+    }
+
     // ************************* Chunk Executor Implementation *************************
     fn enqueue_chunk_by_execution(
         &self,
@@ -502,6 +545,26 @@ fn verify_chunk(
     Ok(())
 }
 
+/// Deduplicates the given per-transaction state updates (ordered by version), such
+/// that each state key is only updated by the last transaction that wrote it. The
+/// write sets and events of the transactions are untouched. Returns the number of
+/// state updates that were dropped.
+pub(crate) fn aggregate_state_updates<'a>(
+    state_updates: impl DoubleEndedIterator<Item = &'a mut ShardedStateUpdates>,
+) -> usize {
+    let mut seen_keys: [HashSet<_>; 16] = Default::default();
+    let mut num_dropped_updates = 0;
+    for sharded_updates in state_updates.rev() {
+        for (shard_updates, shard_seen_keys) in sharded_updates.iter_mut().zip(seen_keys.iter_mut())
+        {
+            let num_updates = shard_updates.len();
+            shard_updates.retain(|state_key, _| shard_seen_keys.insert(state_key.clone()));
+            num_dropped_updates += num_updates - shard_updates.len();
+        }
+    }
+    num_dropped_updates
+}
+
 impl<V: VMExecutor> TransactionReplayer for ChunkExecutor<V> {
     fn replay(
         &self,
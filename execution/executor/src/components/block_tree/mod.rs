@@ -7,31 +7,56 @@
 #[cfg(test)]
 mod test;
 
-use crate::logging::{LogEntry, LogSchema};
+use crate::{
+    components::speculative_state::{SpeculativeMemoryTracker, SpeculativeMemoryUsage},
+    logging::{LogEntry, LogSchema},
+    metrics::APTOS_EXECUTOR_SPECULATIVE_STATE_EVICTIONS,
+};
 use anyhow::{anyhow, ensure, Result};
+use aptos_config::config::SpeculativeStateConfig;
 use aptos_consensus_types::block::Block as ConsensusBlock;
 use aptos_crypto::HashValue;
 use aptos_drop_helper::DEFAULT_DROPPER;
-use aptos_executor_types::{execution_output::ExecutionOutput, ExecutorError, LedgerUpdateOutput};
+use aptos_executor_types::{
+    execution_output::ExecutionOutput,
+    memory_budget::{MemoryBudget, MemoryConsumer, MemoryReservation},
+    ExecutorError, LedgerUpdateOutput,
+};
 use aptos_infallible::Mutex;
-use aptos_logger::{debug, info};
+use aptos_logger::{debug, info, warn};
 use aptos_storage_interface::DbReader;
 use aptos_types::{ledger_info::LedgerInfo, proof::definition::LeafCount};
+use rayon::prelude::*;
 use std::{
     collections::{hash_map::Entry, HashMap},
-    sync::{mpsc::Receiver, Arc, Weak},
+    sync::{mpsc::Receiver, Arc, MutexGuard, Weak},
+    time::Instant,
 };
 
+// Useful labels for the speculative state eviction metrics
+const FLUSHED_STATE_CACHE_LABEL: &str = "flushed_state_cache";
+
 pub struct Block {
     pub id: HashValue,
     pub output: ExecutionOutput,
     children: Mutex<Vec<Arc<Block>>>,
     block_lookup: Arc<BlockLookup>,
+    // The time the block was added to the tree
+    creation_time: Instant,
+    // The speculative memory retained by the block (released when the block is dropped)
+    memory_usage: Mutex<SpeculativeMemoryUsage>,
+    memory_reservation: Mutex<Option<MemoryReservation>>,
+    // Whether or not the state cache of the block has been flushed. Note: the lock
+    // is held while the block is committed (to prevent concurrent flushes).
+    state_cache_flushed: std::sync::Mutex<bool>,
 }
 
 impl Drop for Block {
     fn drop(&mut self) {
         self.block_lookup.remove(self.id);
+        self.block_lookup
+            .memory_tracker
+            .release(*self.memory_usage.lock());
         debug!(
             LogSchema::new(LogEntry::SpeculationCache).block_id(self.id),
             "Block dropped."
@@ -44,6 +69,60 @@ impl Block {
         self.children.lock().push(child)
     }
 
+    /// Accounts for the speculative memory retained by the block (i.e., the
+    /// outputs, state updates and state cache of the ledger update). This
+    /// replaces any existing accounting for the block.
+    pub fn account_speculative_memory(&self) {
+        let memory_usage = SpeculativeMemoryUsage::new(self.output.get_ledger_update());
+        self.update_memory_usage(memory_usage);
+    }
+
+    /// Flushes the state cache of the block (the cache is only used to speed
+    /// up commits). Returns the number of bytes released, or None if the cache
+    /// was already flushed (or is currently in use by a commit).
+    fn flush_state_cache(&self) -> Option<u64> {
+        if !self.output.has_ledger_update() {
+            return None;
+        }
+        let mut state_cache_flushed = self.state_cache_flushed.try_lock().ok()?;
+        if *state_cache_flushed {
+            return None;
+        }
+
+        // Clear the cache and update the memory accounting
+        let sharded_state_cache = &self.output.get_ledger_update().sharded_state_cache;
+        sharded_state_cache
+            .par_iter()
+            .for_each(|state_cache_shard| state_cache_shard.clear());
+        *state_cache_flushed = true;
+        let mut memory_usage = *self.memory_usage.lock();
+        let released_bytes = memory_usage.state_cache_bytes;
+        memory_usage.state_cache_bytes = 0;
+        self.update_memory_usage(memory_usage);
+
+        Some(released_bytes)
+    }
+
+    /// Locks the state cache of the block (preventing it from being flushed).
+    /// The returned guard holds true iff the cache has already been flushed.
+    pub fn lock_state_cache(&self) -> MutexGuard<'_, bool> {
+        self.state_cache_flushed
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+    }
+
+    /// Updates the speculative memory tracked for the block
+    fn update_memory_usage(&self, memory_usage: SpeculativeMemoryUsage) {
+        let memory_tracker = &self.block_lookup.memory_tracker;
+        let previous_memory_usage = std::mem::replace(&mut *self.memory_usage.lock(), memory_usage);
+        memory_tracker.release(previous_memory_usage);
+        memory_tracker.record(memory_usage);
+
+        let memory_reservation = MemoryBudget::global()
+            .reserve(MemoryConsumer::BlockOutputs, memory_usage.total_bytes());
+        *self.memory_reservation.lock() = Some(memory_reservation);
+    }
+
     pub fn num_persisted_transactions(&self) -> LeafCount {
         self.output
             .get_ledger_update()
@@ -122,6 +201,10 @@ impl BlockLookupInner {
                     output,
                     children: Mutex::new(Vec::new()),
                     block_lookup: block_lookup.clone(),
+                    creation_time: Instant::now(),
+                    memory_usage: Mutex::new(SpeculativeMemoryUsage::default()),
+                    memory_reservation: Mutex::new(None),
+                    state_cache_flushed: std::sync::Mutex::new(false),
                 });
                 entry.insert(Arc::downgrade(&block));
                 Ok((block, false, parent_block))
@@ -132,12 +215,14 @@ impl BlockLookupInner {
 
 struct BlockLookup {
     inner: Mutex<BlockLookupInner>,
+    memory_tracker: SpeculativeMemoryTracker,
 }
 
 impl BlockLookup {
     fn new() -> Self {
         Self {
             inner: Mutex::new(BlockLookupInner(HashMap::new())),
+            memory_tracker: SpeculativeMemoryTracker::default(),
         }
     }
 
@@ -298,4 +383,68 @@ impl BlockTree {
     pub fn root_block(&self) -> Arc<Block> {
         self.root.lock().clone()
     }
+
+    /// Returns the speculative memory retained across all uncommitted blocks
+    pub fn get_speculative_memory_usage(&self) -> SpeculativeMemoryUsage {
+        self.block_lookup.memory_tracker.get_usage()
+    }
+
+    /// Applies the eviction policy to the speculative state retained across uncommitted
+    /// blocks. If the (estimated) speculative state exceeds the limit, the state caches
+    /// of the oldest blocks are flushed (until the limit is no longer exceeded). If the
+    /// policy was applied, a receiver is returned that is notified once it completes.
+    ///
+    /// Note: blocks are never evicted here. Any uncommitted fork might still be the one
+    /// that is certified and committed, so forks are only dropped once consensus rules
+    /// them out (i.e., once they are no longer descendants of the committed root, see
+    /// `prune`).
+    pub fn evict_speculative_state(&self, config: &SpeculativeStateConfig) -> Option<Receiver<()>> {
+        let max_speculative_state_bytes = config.max_speculative_state_bytes;
+        let exceeds_limit =
+            || self.get_speculative_memory_usage().total_bytes() > max_speculative_state_bytes;
+        if !config.enable_eviction || !exceeds_limit() {
+            return None;
+        }
+
+        // Flush the state caches of the oldest blocks first
+        let root = self.root_block();
+        let mut blocks = Self::get_descendants(&root);
+        blocks.sort_by_key(|block| block.creation_time);
+        for block in &blocks {
+            if !exceeds_limit() {
+                break;
+            }
+            if block.flush_state_cache().is_some() {
+                APTOS_EXECUTOR_SPECULATIVE_STATE_EVICTIONS
+                    .with_label_values(&[FLUSHED_STATE_CACHE_LABEL])
+                    .inc();
+            }
+        }
+
+        if exceeds_limit() {
+            warn!(
+                LogSchema::new(LogEntry::SpeculationCache),
+                "Speculative state exceeds the limit after flushing all state caches! \
+                Speculative state bytes: {}, limit: {}",
+                self.get_speculative_memory_usage().total_bytes(),
+                max_speculative_state_bytes,
+            );
+        }
+
+        // Drop the block references asynchronously
+        Some(DEFAULT_DROPPER.schedule_drop_with_waiter(blocks))
+    }
+
+    /// Returns all descendants of the given block
+    fn get_descendants(block: &Arc<Block>) -> Vec<Arc<Block>> {
+        let mut descendants = vec![];
+        let mut blocks_to_visit = vec![block.clone()];
+        while let Some(block) = blocks_to_visit.pop() {
+            for child in block.children.lock().iter() {
+                descendants.push(child.clone());
+                blocks_to_visit.push(child.clone());
+            }
+        }
+        descendants
+    }
 }
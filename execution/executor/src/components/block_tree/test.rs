@@ -2,7 +2,11 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::components::block_tree::{epoch_genesis_block_id, BlockLookup, BlockTree};
+use crate::components::{
+    block_tree::{epoch_genesis_block_id, BlockLookup, BlockTree},
+    speculative_state::SpeculativeMemoryUsage,
+};
+use aptos_config::config::SpeculativeStateConfig;
 use aptos_crypto::{hash::PRE_GENESIS_BLOCK_ID, HashValue};
 use aptos_executor_types::{execution_output::ExecutionOutput, LedgerUpdateOutput};
 use aptos_infallible::Mutex;
@@ -144,3 +148,132 @@ fn test_add_block_missing_parent() {
         .add_block(id(99), id(100), empty_block())
         .is_err());
 }
+
+#[test]
+fn test_speculative_memory_accounting() {
+    let block_tree = create_tree();
+    assert_eq!(block_tree.get_speculative_memory_usage().total_bytes(), 0);
+
+    // Account for the memory of each block
+    for index in 1..=11 {
+        set_memory_usage(&block_tree, index, 100, 10);
+    }
+    let memory_usage = block_tree.get_speculative_memory_usage();
+    assert_eq!(memory_usage.outputs_bytes, 1100);
+    assert_eq!(memory_usage.state_cache_bytes, 110);
+
+    // Updating the accounting of a block should replace the existing accounting
+    set_memory_usage(&block_tree, 1, 50, 0);
+    assert_eq!(
+        block_tree.get_speculative_memory_usage().total_bytes(),
+        1150
+    );
+
+    // Pruning the tree should release the memory of the dropped blocks
+    block_tree
+        .prune(&gen_ledger_info(id(9), false))
+        .unwrap()
+        .recv()
+        .unwrap();
+    let memory_usage = block_tree.get_speculative_memory_usage();
+    assert_eq!(memory_usage.outputs_bytes, 300);
+    assert_eq!(memory_usage.state_cache_bytes, 30);
+}
+
+#[test]
+fn test_evict_speculative_state() {
+    let block_tree = create_tree();
+    for index in 1..=11 {
+        set_memory_usage(&block_tree, index, 100, 10);
+    }
+
+    // Verify nothing is evicted if eviction is disabled, or the limit isn't exceeded
+    let mut config = SpeculativeStateConfig {
+        enable_eviction: false,
+        max_speculative_state_bytes: 500,
+    };
+    assert!(block_tree.evict_speculative_state(&config).is_none());
+    config.enable_eviction = true;
+    config.max_speculative_state_bytes = 2000;
+    assert!(block_tree.evict_speculative_state(&config).is_none());
+    assert_eq!(block_tree.size(), 12);
+
+    // Verify that the state caches are flushed first
+    config.max_speculative_state_bytes = 1100;
+    block_tree
+        .evict_speculative_state(&config)
+        .unwrap()
+        .recv()
+        .unwrap();
+    let memory_usage = block_tree.get_speculative_memory_usage();
+    assert_eq!(memory_usage.state_cache_bytes, 0);
+    assert_eq!(memory_usage.outputs_bytes, 1100);
+    assert_eq!(block_tree.size(), 12);
+
+    // Verify that no blocks are evicted (even if the limit is still exceeded)
+    config.max_speculative_state_bytes = 500;
+    block_tree
+        .evict_speculative_state(&config)
+        .unwrap()
+        .recv()
+        .unwrap();
+    assert_eq!(
+        block_tree.get_speculative_memory_usage().total_bytes(),
+        1100
+    );
+    assert_eq!(block_tree.size(), 12);
+}
+
+#[test]
+fn test_commit_older_sibling_after_eviction() {
+    let block_tree = create_tree();
+    for index in 1..=11 {
+        set_memory_usage(&block_tree, index, 100, 10);
+    }
+
+    // Apply the eviction policy (with a limit that can't be satisfied)
+    let config = SpeculativeStateConfig {
+        enable_eviction: true,
+        max_speculative_state_bytes: 0,
+    };
+    block_tree
+        .evict_speculative_state(&config)
+        .unwrap()
+        .recv()
+        .unwrap();
+
+    // Commit the older fork (i.e., block 3, which has a newer sibling subtree
+    // under block 6) and verify the tree is pruned to the committed fork.
+    block_tree
+        .prune(&gen_ledger_info(id(1), false))
+        .unwrap()
+        .recv()
+        .unwrap();
+    block_tree
+        .prune(&gen_ledger_info(id(3), false))
+        .unwrap()
+        .recv()
+        .unwrap();
+    assert_eq!(block_tree.root_block().id, id(3));
+    assert_eq!(block_tree.size(), 3);
+
+    // Verify that children of the committed block can still be executed
+    block_tree.get_block(id(4)).unwrap();
+    block_tree.add_block(id(4), id(12), empty_block()).unwrap();
+    assert_eq!(block_tree.size(), 4);
+}
+
+/// Sets the speculative memory usage of the specified block
+fn set_memory_usage(
+    block_tree: &BlockTree,
+    index: u64,
+    outputs_bytes: u64,
+    state_cache_bytes: u64,
+) {
+    let block = block_tree.get_block(id(index)).unwrap();
+    block.update_memory_usage(SpeculativeMemoryUsage {
+        outputs_bytes,
+        state_updates_bytes: 0,
+        state_cache_bytes,
+    });
+}
@@ -5,7 +5,11 @@
 #![forbid(unsafe_code)]
 
 use anyhow::{anyhow, ensure, Result};
-use aptos_executor_types::{state_checkpoint_output::StateCheckpointOutput, ExecutedChunk};
+use aptos_executor_types::{
+    memory_budget::{MemoryBudget, MemoryConsumer, MemoryReservation},
+    state_checkpoint_output::StateCheckpointOutput,
+    ExecutedChunk,
+};
 use aptos_storage_interface::{state_delta::StateDelta, DbReader, ExecutedTrees};
 use aptos_types::{
     epoch_state::EpochState,
@@ -42,6 +46,8 @@ pub struct ChunkCommitQueue {
     latest_state: StateDelta,
     latest_txn_accumulator: Arc<InMemoryTransactionAccumulator>,
     to_commit: VecDeque<Option<ExecutedChunk>>,
+    /// The memory reserved for each chunk in to_commit (released once the chunk is committed)
+    to_commit_memory_reservations: VecDeque<MemoryReservation>,
     to_update_ledger: VecDeque<Option<ChunkToUpdateLedger>>,
 }
 
@@ -56,6 +62,7 @@ impl ChunkCommitQueue {
             latest_state: state,
             latest_txn_accumulator: transaction_accumulator,
             to_commit: VecDeque::new(),
+            to_commit_memory_reservations: VecDeque::new(),
             to_update_ledger: VecDeque::new(),
         })
     }
@@ -109,7 +116,7 @@ impl ChunkCommitQueue {
         );
         self.latest_txn_accumulator = chunk.ledger_update_output.transaction_accumulator.clone();
         self.to_update_ledger.pop_front();
-        self.to_commit.push_back(Some(chunk));
+        self.push_chunk_to_commit(chunk);
 
         Ok(())
     }
@@ -125,6 +132,21 @@ impl ChunkCommitQueue {
         Ok((self.persisted_state.clone(), chunk))
     }
 
+    /// Puts a chunk taken by `next_chunk_to_commit` back at the head of the queue (e.g., after
+    /// the chunk failed to be saved), so that the commit can be retried.
+    pub(crate) fn return_chunk_to_commit(&mut self, chunk: ExecutedChunk) -> Result<()> {
+        let chunk_opt = self
+            .to_commit
+            .front_mut()
+            .ok_or_else(|| anyhow!("No chunk to return."))?;
+        ensure!(
+            chunk_opt.is_none(),
+            "Head of to_commit has not been processed."
+        );
+        *chunk_opt = Some(chunk);
+        Ok(())
+    }
+
     pub(crate) fn enqueue_chunk_to_commit_directly(&mut self, chunk: ExecutedChunk) -> Result<()> {
         ensure!(
             self.to_update_ledger.is_empty(),
@@ -132,7 +154,7 @@ impl ChunkCommitQueue {
         );
         self.latest_state = chunk.result_state.clone();
         self.latest_txn_accumulator = chunk.ledger_update_output.transaction_accumulator.clone();
-        self.to_commit.push_back(Some(chunk));
+        self.push_chunk_to_commit(chunk);
         Ok(())
     }
 
@@ -143,10 +165,22 @@ impl ChunkCommitQueue {
             "Head of to_commit has not been processed."
         );
         self.to_commit.pop_front();
+        self.to_commit_memory_reservations.pop_front();
         self.persisted_state = latest_state;
         self.persisted_state
             .current
             .log_generation("commit_queue_base");
         Ok(())
     }
+
+    /// Pushes the chunk to the back of to_commit (and reserves the memory it holds)
+    fn push_chunk_to_commit(&mut self, chunk: ExecutedChunk) {
+        let memory_reservation = MemoryBudget::global().reserve(
+            MemoryConsumer::StateSyncChunks,
+            chunk.ledger_update_output.estimated_size_bytes(),
+        );
+        self.to_commit_memory_reservations
+            .push_back(memory_reservation);
+        self.to_commit.push_back(Some(chunk));
+    }
 }
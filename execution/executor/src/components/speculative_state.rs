@@ -0,0 +1,141 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+
+use crate::metrics::APTOS_EXECUTOR_SPECULATIVE_STATE_BYTES;
+use aptos_config::config::SpeculativeStateConfig;
+use aptos_executor_types::LedgerUpdateOutput;
+use aptos_logger::warn;
+use aptos_storage_interface::cached_state_view::ShardedStateCache;
+use once_cell::sync::OnceCell;
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The speculative state eviction policy (set once on node startup)
+static SPECULATIVE_STATE_CONFIG: OnceCell<SpeculativeStateConfig> = OnceCell::new();
+
+/// Sets the speculative state eviction policy. This should only be
+/// called once (on node startup), before the executor is started.
+pub fn set_speculative_state_config_once(config: SpeculativeStateConfig) {
+    if SPECULATIVE_STATE_CONFIG.set(config).is_err() {
+        warn!("The speculative state config has already been set!");
+    }
+}
+
+/// Returns the speculative state eviction policy. If no
+/// policy has been set, the default policy is used.
+pub fn get_speculative_state_config() -> SpeculativeStateConfig {
+    SPECULATIVE_STATE_CONFIG.get().copied().unwrap_or_default()
+}
+
+/// The (estimated) memory retained by the speculative state of an uncommitted block
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SpeculativeMemoryUsage {
+    pub outputs_bytes: u64, // The write sets and events of the transactions to commit
+    pub state_updates_bytes: u64, // The (sparse merkle tree) state updates of the block
+    pub state_cache_bytes: u64, // The state values cached during execution
+}
+
+impl SpeculativeMemoryUsage {
+    pub fn new(ledger_update_output: &LedgerUpdateOutput) -> Self {
+        let state_updates_bytes: usize = ledger_update_output
+            .state_updates_until_last_checkpoint
+            .iter()
+            .flat_map(|sharded_state_updates| sharded_state_updates.iter())
+            .flat_map(|state_updates| state_updates.iter())
+            .map(|(key, value)| key.size() + value.as_ref().map_or(0, |value| value.size()))
+            .sum();
+
+        Self {
+            outputs_bytes: ledger_update_output.estimated_size_bytes(),
+            state_updates_bytes: state_updates_bytes as u64,
+            state_cache_bytes: estimate_state_cache_bytes(
+                &ledger_update_output.sharded_state_cache,
+            ),
+        }
+    }
+
+    /// Returns the total (estimated) number of bytes
+    pub fn total_bytes(&self) -> u64 {
+        self.outputs_bytes
+            .saturating_add(self.state_updates_bytes)
+            .saturating_add(self.state_cache_bytes)
+    }
+}
+
+/// Tracks the speculative memory retained across all uncommitted blocks
+#[derive(Debug, Default)]
+pub struct SpeculativeMemoryTracker {
+    outputs_bytes: AtomicU64,
+    state_updates_bytes: AtomicU64,
+    state_cache_bytes: AtomicU64,
+}
+
+impl SpeculativeMemoryTracker {
+    /// Records the memory retained by an uncommitted block
+    pub fn record(&self, usage: SpeculativeMemoryUsage) {
+        self.outputs_bytes
+            .fetch_add(usage.outputs_bytes, Ordering::Relaxed);
+        self.state_updates_bytes
+            .fetch_add(usage.state_updates_bytes, Ordering::Relaxed);
+        self.state_cache_bytes
+            .fetch_add(usage.state_cache_bytes, Ordering::Relaxed);
+        self.update_metrics();
+    }
+
+    /// Records the release of memory retained by an uncommitted block
+    pub fn release(&self, usage: SpeculativeMemoryUsage) {
+        saturating_sub(&self.outputs_bytes, usage.outputs_bytes);
+        saturating_sub(&self.state_updates_bytes, usage.state_updates_bytes);
+        saturating_sub(&self.state_cache_bytes, usage.state_cache_bytes);
+        self.update_metrics();
+    }
+
+    /// Returns the memory currently retained across all uncommitted blocks
+    pub fn get_usage(&self) -> SpeculativeMemoryUsage {
+        SpeculativeMemoryUsage {
+            outputs_bytes: self.outputs_bytes.load(Ordering::Relaxed),
+            state_updates_bytes: self.state_updates_bytes.load(Ordering::Relaxed),
+            state_cache_bytes: self.state_cache_bytes.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Updates the speculative state metrics
+    fn update_metrics(&self) {
+        let usage = self.get_usage();
+        for (component, num_bytes) in [
+            ("outputs", usage.outputs_bytes),
+            ("state_updates", usage.state_updates_bytes),
+            ("state_cache", usage.state_cache_bytes),
+        ] {
+            APTOS_EXECUTOR_SPECULATIVE_STATE_BYTES
+                .with_label_values(&[component])
+                .set(num_bytes as i64);
+        }
+    }
+}
+
+/// Returns the (estimated) number of bytes held by the given state cache
+pub fn estimate_state_cache_bytes(sharded_state_cache: &ShardedStateCache) -> u64 {
+    let num_bytes: usize = sharded_state_cache
+        .par_iter()
+        .map(|shard| {
+            shard
+                .iter()
+                .map(|entry| {
+                    let (_, value) = entry.value();
+                    entry.key().size() + value.as_ref().map_or(0, |value| value.size())
+                })
+                .sum::<usize>()
+        })
+        .sum();
+    num_bytes as u64
+}
+
+/// Subtracts the given value from the counter (saturating at zero)
+fn saturating_sub(counter: &AtomicU64, value: u64) {
+    let _ = counter.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| {
+        Some(current.saturating_sub(value))
+    });
+}
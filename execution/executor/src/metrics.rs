@@ -4,7 +4,8 @@
 
 use aptos_metrics_core::{
     exponential_buckets, register_histogram, register_histogram_vec, register_int_counter,
-    register_int_counter_vec, Histogram, HistogramVec, IntCounter, IntCounterVec,
+    register_int_counter_vec, register_int_gauge_vec, Histogram, HistogramVec, IntCounter,
+    IntCounterVec, IntGaugeVec,
 };
 use once_cell::sync::Lazy;
 
@@ -68,6 +69,14 @@ pub static APTOS_EXECUTOR_ERRORS: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!("aptos_executor_error_total", "Cumulative number of errors").unwrap()
 });
 
+pub static APTOS_EXECUTOR_AGGREGATED_STATE_UPDATES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_executor_aggregated_state_updates_total",
+        "Cumulative number of state updates dropped by chunk write set aggregation"
+    )
+    .unwrap()
+});
+
 pub static APTOS_EXECUTOR_EXECUTE_BLOCK_SECONDS: Lazy<Histogram> = Lazy::new(|| {
     register_histogram!(
         // metric name
@@ -219,3 +228,23 @@ pub static APTOS_PROCESSED_TXNS_OUTPUT_SIZE: Lazy<Histogram> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+/// The (estimated) bytes of speculative state retained across uncommitted blocks
+pub static APTOS_EXECUTOR_SPECULATIVE_STATE_BYTES: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_executor_speculative_state_bytes",
+        "The estimated bytes of speculative state retained across uncommitted blocks",
+        &["component"]
+    )
+    .unwrap()
+});
+
+/// Counter of speculative state evictions (by eviction type)
+pub static APTOS_EXECUTOR_SPECULATIVE_STATE_EVICTIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_executor_speculative_state_evictions",
+        "Counter of speculative state evictions (e.g., flushed state caches)",
+        &["eviction_type"]
+    )
+    .unwrap()
+});
@@ -6,7 +6,7 @@
 
 use crate::{
     block_executor::BlockExecutor,
-    chunk_executor::ChunkExecutor,
+    chunk_executor::{aggregate_state_updates, ChunkExecutor},
     db_bootstrapper::{generate_waypoint, maybe_bootstrap},
     mock_vm::{encode_mint_transaction, MockVM},
     tests,
@@ -14,13 +14,24 @@ use crate::{
 use aptos_crypto::HashValue;
 use aptos_db::AptosDB;
 use aptos_executor_types::{BlockExecutorTrait, ChunkExecutorTrait};
-use aptos_storage_interface::DbReaderWriter;
+use aptos_storage_interface::{
+    cached_state_view::ShardedStateCache, state_delta::StateDelta, AptosDbError, DbReaderWriter,
+    DbWriter,
+};
 use aptos_types::{
     ledger_info::LedgerInfoWithSignatures,
+    state_store::{
+        create_empty_sharded_state_updates, state_key::StateKey, state_value::StateValue,
+        ShardedStateUpdates,
+    },
     test_helpers::transaction_test_helpers::{block, TEST_BLOCK_EXECUTOR_ONCHAIN_CONFIG},
-    transaction::TransactionListWithProof,
+    transaction::{TransactionListWithProof, TransactionToCommit, Version},
 };
 use rand::Rng;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
 
 pub struct TestExecutor {
     _path: aptos_temppath::TempPath,
@@ -202,6 +213,130 @@ fn test_executor_execute_and_commit_chunk_restart() {
     }
 }
 
+/// A db writer that fails the first `num_failures` saves, and then delegates to the inner writer
+struct FailingDbWriter {
+    inner: Arc<dyn DbWriter>,
+    num_failures: AtomicUsize,
+}
+
+impl DbWriter for FailingDbWriter {
+    fn save_transactions(
+        &self,
+        txns_to_commit: &[TransactionToCommit],
+        first_version: Version,
+        base_state_version: Option<Version>,
+        ledger_info_with_sigs: Option<&LedgerInfoWithSignatures>,
+        sync_commit: bool,
+        latest_in_memory_state: StateDelta,
+        state_updates_until_last_checkpoint: Option<ShardedStateUpdates>,
+        sharded_state_cache: Option<&ShardedStateCache>,
+    ) -> aptos_storage_interface::Result<()> {
+        if self
+            .num_failures
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |num_failures| {
+                num_failures.checked_sub(1)
+            })
+            .is_ok()
+        {
+            return Err(AptosDbError::Other("Injected db write failure".into()));
+        }
+        self.inner.save_transactions(
+            txns_to_commit,
+            first_version,
+            base_state_version,
+            ledger_info_with_sigs,
+            sync_commit,
+            latest_in_memory_state,
+            state_updates_until_last_checkpoint,
+            sharded_state_cache,
+        )
+    }
+}
+
+#[test]
+fn test_executor_commit_chunk_retry_after_db_failure() {
+    let (chunks, ledger_info) = tests::create_transaction_chunks(vec![1..31]);
+
+    // Create a chunk executor on top of a db writer that fails the first two saves
+    let TestExecutor { _path, db, .. } = TestExecutor::new();
+    let failing_db = DbReaderWriter {
+        reader: db.reader.clone(),
+        writer: Arc::new(FailingDbWriter {
+            inner: db.writer.clone(),
+            num_failures: AtomicUsize::new(2),
+        }),
+    };
+    let executor = ChunkExecutor::<MockVM>::new(failing_db);
+    executor
+        .execute_chunk(chunks[0].clone(), &ledger_info, None)
+        .unwrap();
+
+    // Verify that the failed commits leave the chunk in the queue (to be retried)
+    for _ in 0..2 {
+        assert!(executor.commit_chunk().is_err());
+        assert_eq!(
+            db.reader
+                .get_latest_ledger_info()
+                .unwrap()
+                .ledger_info()
+                .version(),
+            0
+        );
+    }
+
+    // Verify that the retried commit succeeds
+    executor.commit_chunk().unwrap();
+    assert_eq!(db.reader.get_latest_ledger_info().unwrap(), ledger_info);
+}
+
+#[test]
+#[cfg_attr(feature = "consensus-only-perf-test", ignore)]
+fn test_executor_execute_and_commit_chunk_with_write_set_aggregation() {
+    let (chunks, ledger_info) = tests::create_transaction_chunks(vec![1..31, 31..71, 71..91]);
+
+    // Execute and commit the chunks with write set aggregation enabled
+    let TestExecutor { _path, db, .. } = TestExecutor::new();
+    let executor = ChunkExecutor::<MockVM>::new_with_write_set_aggregation(db.clone(), true);
+    execute_and_commit_chunks(chunks, ledger_info.clone(), &db, &executor);
+
+    // Verify the transaction outputs are still available for every version
+    let outputs = db
+        .reader
+        .get_transaction_outputs(1, 90, ledger_info.ledger_info().version())
+        .unwrap();
+    assert_eq!(outputs.transactions_and_outputs.len(), 90);
+}
+
+#[test]
+fn test_aggregate_state_updates() {
+    let key_1 = StateKey::raw(vec![1]);
+    let key_2 = StateKey::raw(vec![2]);
+    let value = |byte: u8| Some(StateValue::from(vec![byte]));
+
+    // Create the state updates for three transactions
+    let mut state_updates = vec![create_empty_sharded_state_updates(); 3];
+    for (index, key, value) in [
+        (0, &key_1, value(0)),
+        (0, &key_2, value(0)),
+        (1, &key_1, None),
+        (2, &key_1, value(2)),
+    ] {
+        state_updates[index][key.get_shard_id() as usize].insert(key.clone(), value);
+    }
+
+    // Aggregate the updates and verify only the last write per key remains
+    assert_eq!(aggregate_state_updates(state_updates.iter_mut()), 2);
+    let get_update = |index: usize, key: &StateKey| {
+        state_updates[index][key.get_shard_id() as usize]
+            .get(key)
+            .cloned()
+    };
+    assert_eq!(get_update(0, &key_1), None);
+    assert_eq!(get_update(0, &key_2), Some(value(0)));
+    assert_eq!(get_update(1, &key_1), None);
+    assert_eq!(get_update(2, &key_1), Some(value(2)));
+}
+
 #[test]
 #[cfg_attr(feature = "consensus-only-perf-test", ignore)]
 fn test_executor_execute_and_commit_chunk_local_result_mismatch() {
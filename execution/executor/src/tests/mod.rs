@@ -326,6 +326,50 @@ fn test_executor_execute_same_block_multiple_times() {
     assert_eq!(responses.len(), 1);
 }
 
+#[test]
+fn test_executor_reuse_executed_block() {
+    let executor = TestExecutor::new();
+    let parent_block_id = executor.committed_block_id();
+    let block_id = gen_block_id(1);
+
+    let txns: Vec<_> = (0..10)
+        .map(|i| encode_mint_transaction(gen_address(i), 100))
+        .collect();
+
+    // Only the committed block has been executed
+    assert!(executor.is_block_executed(parent_block_id));
+    assert!(!executor.is_block_executed(block_id));
+
+    // Execute the block (e.g., optimistically, before it is ordered)
+    let output = executor
+        .execute_block(
+            (block_id, block(txns.clone())).into(),
+            parent_block_id,
+            TEST_BLOCK_EXECUTOR_ONCHAIN_CONFIG,
+        )
+        .unwrap();
+    assert!(executor.is_block_executed(block_id));
+
+    // Executing the block again skips the execution and reuses the existing output
+    let state_checkpoint_output = executor
+        .execute_and_state_checkpoint(
+            (block_id, block(txns)).into(),
+            parent_block_id,
+            TEST_BLOCK_EXECUTOR_ONCHAIN_CONFIG,
+        )
+        .unwrap();
+    assert_eq!(state_checkpoint_output.input_txns_len(), 0);
+    let reused_output = executor
+        .ledger_update(block_id, parent_block_id, state_checkpoint_output)
+        .unwrap();
+    assert_eq!(output, reused_output);
+
+    // Resetting the executor (e.g., after state sync or an epoch change) discards the output
+    executor.reset().unwrap();
+    assert!(!executor.is_block_executed(block_id));
+    assert!(executor.is_block_executed(executor.committed_block_id()));
+}
+
 /// Generates a list of `TransactionListWithProof`s according to the given ranges.
 fn create_transaction_chunks(
     chunk_ranges: Vec<std::ops::Range<Version>>,
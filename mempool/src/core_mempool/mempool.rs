@@ -7,6 +7,7 @@
 use crate::{
     core_mempool::{
         index::TxnPointer,
+        provenance::{ProvenanceStats, TransactionProvenance},
         transaction::{InsertionInfo, MempoolTransaction, TimelineState},
         transaction_store::TransactionStore,
     },
@@ -14,12 +15,14 @@ use crate::{
     logging::{LogEntry, LogSchema, TxnsLog},
     shared_mempool::types::MultiBucketTimelineIndexIds,
 };
-use aptos_config::config::NodeConfig;
+use aptos_config::{config::NodeConfig, network_id::PeerNetworkId};
 use aptos_consensus_types::common::{TransactionInProgress, TransactionSummary};
 use aptos_crypto::HashValue;
 use aptos_logger::prelude::*;
+use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{
     account_address::AccountAddress,
+    account_config::KeyRotation,
     mempool_status::{MempoolStatus, MempoolStatusCode},
     transaction::SignedTransaction,
     vm_status::DiscardedVMStatus,
@@ -35,15 +38,25 @@ pub struct Mempool {
     transactions: TransactionStore,
 
     pub system_transaction_timeout: Duration,
+
+    // The time service used to expire transactions by SystemTTL
+    time_service: TimeService,
 }
 
 impl Mempool {
     pub fn new(config: &NodeConfig) -> Self {
+        Self::new_with_time_service(config, TimeService::real())
+    }
+
+    /// Creates a new mempool that expires transactions by SystemTTL using
+    /// the given time service (e.g., a mock time service in tests).
+    pub fn new_with_time_service(config: &NodeConfig, time_service: TimeService) -> Self {
         Mempool {
             transactions: TransactionStore::new(&config.mempool),
             system_transaction_timeout: Duration::from_secs(
                 config.mempool.system_transaction_timeout_secs,
             ),
+            time_service,
         }
     }
 
@@ -119,6 +132,54 @@ impl Mempool {
             .reject_transaction(sender, sequence_number, hash);
     }
 
+    /// Returns true iff the transaction with the given hash is already in mempool, and was
+    /// first received from a different network than the given peer (i.e., it's a cross-network
+    /// duplicate). If so, the peer is recorded so that the transaction isn't broadcast back.
+    pub(crate) fn record_cross_network_duplicate(
+        &mut self,
+        hash: &HashValue,
+        peer: PeerNetworkId,
+    ) -> bool {
+        self.transactions.record_cross_network_duplicate(hash, peer)
+    }
+
+    /// Returns true iff the given transaction (in mempool) was sent to us by the given peer
+    pub(crate) fn was_received_from(&self, txn: &SignedTransaction, peer: &PeerNetworkId) -> bool {
+        self.transactions.was_received_from(txn, peer)
+    }
+
+    /// Returns true iff the given account has transactions in mempool
+    pub(crate) fn has_transactions(&self, address: &AccountAddress) -> bool {
+        self.transactions.has_transactions(address)
+    }
+
+    /// Removes the transactions invalidated by the given (on-chain) key rotations. The
+    /// rotations are grouped by account, so that the transactions of each account are
+    /// checked (in a single batch) against the latest authentication key of the account.
+    /// Returns the number of removed transactions.
+    pub(crate) fn reject_transactions_with_rotated_keys(
+        &mut self,
+        key_rotations: &[KeyRotation],
+    ) -> usize {
+        let mut latest_authentication_keys = HashMap::new();
+        for key_rotation in key_rotations {
+            latest_authentication_keys.insert(
+                *key_rotation.account(),
+                key_rotation.new_authentication_key(),
+            );
+        }
+
+        let num_rejected_txns = latest_authentication_keys
+            .into_iter()
+            .map(|(account, authentication_key)| {
+                self.transactions
+                    .reject_transactions_with_stale_authentication_key(&account, authentication_key)
+            })
+            .sum();
+        counters::CORE_MEMPOOL_KEY_ROTATION_REJECTED_TXNS.inc_by(num_rejected_txns as u64);
+        num_rejected_txns
+    }
+
     pub(crate) fn log_txn_latency(
         insertion_info: &InsertionInfo,
         bucket: &str,
@@ -198,7 +259,7 @@ impl Mempool {
         ranking_score: u64,
         db_sequence_number: u64,
         timeline_state: TimelineState,
-        client_submitted: bool,
+        provenance: TransactionProvenance,
     ) -> MempoolStatus {
         trace!(
             LogSchema::new(LogEntry::AddTxn)
@@ -216,8 +277,7 @@ impl Mempool {
         }
 
         let now = SystemTime::now();
-        let expiration_time =
-            aptos_infallible::duration_since_epoch_at(&now) + self.system_transaction_timeout;
+        let expiration_time = self.time_service.now_unix_time() + self.system_transaction_timeout;
 
         let txn_info = MempoolTransaction::new(
             txn,
@@ -226,7 +286,7 @@ impl Mempool {
             timeline_state,
             db_sequence_number,
             now,
-            client_submitted,
+            provenance,
         );
 
         let status = self.transactions.insert(txn_info);
@@ -421,7 +481,7 @@ impl Mempool {
     /// Removes all expired transactions and clears expired entries in metrics
     /// cache and sequence number cache.
     pub(crate) fn gc(&mut self) {
-        let now = aptos_infallible::duration_since_epoch();
+        let now = self.time_service.now_unix_time();
         self.transactions.gc_by_system_ttl(now);
     }
 
@@ -451,6 +511,11 @@ impl Mempool {
         self.transactions.gen_snapshot()
     }
 
+    /// Returns the transaction stats of all tracked provenances
+    pub fn get_provenance_stats(&self) -> Vec<ProvenanceStats> {
+        self.transactions.get_provenance_stats()
+    }
+
     #[cfg(test)]
     pub fn get_parking_lot_size(&self) -> usize {
         self.transactions.get_parking_lot_size()
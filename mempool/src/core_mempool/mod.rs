@@ -4,12 +4,16 @@
 
 mod index;
 mod mempool;
+mod provenance;
 mod transaction;
 mod transaction_store;
 
 #[cfg(test)]
 pub use self::transaction::{MempoolTransaction, SubmittedBy};
 pub use self::{
-    index::TxnPointer, mempool::Mempool as CoreMempool, transaction::TimelineState,
+    index::TxnPointer,
+    mempool::Mempool as CoreMempool,
+    provenance::{ProvenanceStats, TransactionProvenance},
+    transaction::TimelineState,
     transaction_store::TXN_INDEX_ESTIMATED_BYTES,
 };
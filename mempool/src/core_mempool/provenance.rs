@@ -0,0 +1,187 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tracks the provenance of transactions in mempool, i.e., the peer or API client
+//! that first delivered each transaction, together with the outcomes of those
+//! transactions (e.g., committed, expired or rejected). This allows operators to
+//! identify spammy upstreams and misbehaving gateways.
+
+use crate::counters;
+use aptos_config::{config::MempoolConfig, network_id::PeerNetworkId};
+use serde::Serialize;
+use std::{collections::HashMap, fmt};
+
+/// The source that first delivered a transaction to mempool
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum TransactionProvenance {
+    /// The transaction was submitted by a client via the API
+    Client,
+    /// The transaction was broadcast to us by the given peer
+    Peer(PeerNetworkId),
+    /// The source of the transaction is unknown
+    Unknown,
+}
+
+impl TransactionProvenance {
+    /// Updates the provenance metrics for the given outcome
+    fn update_metrics(&self, outcome: ProvenanceOutcome) {
+        // The metrics are only labelled by network (the peer labels would be
+        // unbounded). The per-peer outcomes are tracked by the provenance stats.
+        let network_label = match self {
+            TransactionProvenance::Client => counters::CLIENT_LABEL,
+            TransactionProvenance::Peer(peer) => peer.network_id().as_str(),
+            TransactionProvenance::Unknown => counters::UNKNOWN_PEER,
+        };
+        counters::mempool_provenance_txns_inc(network_label, outcome.get_label());
+    }
+}
+
+impl fmt::Display for TransactionProvenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TransactionProvenance::Client => write!(f, "client"),
+            TransactionProvenance::Peer(peer) => write!(f, "peer:{}", peer),
+            TransactionProvenance::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+/// The outcome of a transaction delivered by a provenance
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ProvenanceOutcome {
+    Inserted,
+    Committed,
+    Expired,
+    Rejected,
+    Evicted,
+    Replaced, // The transaction was replaced by a gas upgraded transaction
+}
+
+impl ProvenanceOutcome {
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ProvenanceOutcome::Inserted => "inserted",
+            ProvenanceOutcome::Committed => "committed",
+            ProvenanceOutcome::Expired => "expired",
+            ProvenanceOutcome::Rejected => "rejected",
+            ProvenanceOutcome::Evicted => "evicted",
+            ProvenanceOutcome::Replaced => "replaced",
+        }
+    }
+}
+
+/// The transaction statistics for a single provenance
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct ProvenanceStats {
+    pub provenance: String,
+    pub num_inserted: u64,
+    pub num_committed: u64,
+    pub num_expired: u64,
+    pub num_rejected: u64,
+    pub num_evicted: u64,
+    pub num_in_mempool: u64,
+}
+
+impl ProvenanceStats {
+    fn new(provenance: &TransactionProvenance) -> Self {
+        Self {
+            provenance: provenance.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns the reputation of the provenance, i.e., the fraction of its
+    /// transactions that were committed (out of those that committed, expired
+    /// or were rejected). If there are not enough samples, None is returned.
+    fn reputation(&self, min_samples: u64) -> Option<f64> {
+        let num_samples = self.num_committed + self.num_expired + self.num_rejected;
+        if num_samples == 0 || num_samples < min_samples {
+            return None;
+        }
+        Some(self.num_committed as f64 / num_samples as f64)
+    }
+}
+
+/// Tracks the transaction statistics of each provenance
+pub(crate) struct ProvenanceTracker {
+    stats: HashMap<TransactionProvenance, ProvenanceStats>,
+    max_tracked_provenances: usize,
+    reputation_min_samples: u64,
+}
+
+impl ProvenanceTracker {
+    pub(crate) fn new(config: &MempoolConfig) -> Self {
+        Self {
+            stats: HashMap::new(),
+            max_tracked_provenances: config.max_tracked_provenances,
+            reputation_min_samples: config.provenance_reputation_min_samples,
+        }
+    }
+
+    /// Records the outcome of a transaction for the given provenance
+    pub(crate) fn record(
+        &mut self,
+        provenance: &TransactionProvenance,
+        outcome: ProvenanceOutcome,
+    ) {
+        // Make room for the provenance (if required)
+        if !self.stats.contains_key(provenance) {
+            if outcome != ProvenanceOutcome::Inserted {
+                return; // The provenance was already pruned
+            }
+            self.prune_idle_provenances();
+            if self.stats.len() >= self.max_tracked_provenances {
+                return; // There are too many active provenances to track
+            }
+        }
+
+        // Update the stats
+        let stats = self
+            .stats
+            .entry(*provenance)
+            .or_insert_with(|| ProvenanceStats::new(provenance));
+        match outcome {
+            ProvenanceOutcome::Inserted => {
+                stats.num_inserted += 1;
+                stats.num_in_mempool += 1;
+            },
+            ProvenanceOutcome::Committed => stats.num_committed += 1,
+            ProvenanceOutcome::Expired => stats.num_expired += 1,
+            ProvenanceOutcome::Rejected => stats.num_rejected += 1,
+            ProvenanceOutcome::Evicted => stats.num_evicted += 1,
+            ProvenanceOutcome::Replaced => {},
+        }
+        if outcome != ProvenanceOutcome::Inserted {
+            stats.num_in_mempool = stats.num_in_mempool.saturating_sub(1);
+        }
+
+        // Update the metrics
+        provenance.update_metrics(outcome);
+    }
+
+    /// Removes the stats of all provenances without transactions in mempool
+    /// (if the number of tracked provenances has hit the maximum).
+    fn prune_idle_provenances(&mut self) {
+        if self.stats.len() >= self.max_tracked_provenances {
+            self.stats.retain(|_, stats| stats.num_in_mempool > 0);
+        }
+    }
+
+    /// Returns the reputation of the given provenance (if known)
+    pub(crate) fn reputation(&self, provenance: &TransactionProvenance) -> Option<f64> {
+        self.stats
+            .get(provenance)
+            .and_then(|stats| stats.reputation(self.reputation_min_samples))
+    }
+
+    /// Returns the stats of all tracked provenances (sorted by insertions)
+    pub(crate) fn get_stats(&self) -> Vec<ProvenanceStats> {
+        let mut stats: Vec<_> = self.stats.values().cloned().collect();
+        stats.sort_by(|a, b| {
+            b.num_inserted
+                .cmp(&a.num_inserted)
+                .then_with(|| a.provenance.cmp(&b.provenance))
+        });
+        stats
+    }
+}
@@ -2,7 +2,10 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{core_mempool::TXN_INDEX_ESTIMATED_BYTES, counters};
+use crate::{
+    core_mempool::{TransactionProvenance, TXN_INDEX_ESTIMATED_BYTES},
+    counters,
+};
 use aptos_crypto::HashValue;
 use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
 use serde::{Deserialize, Serialize};
@@ -35,7 +38,7 @@ impl MempoolTransaction {
         timeline_state: TimelineState,
         seqno: u64,
         insertion_time: SystemTime,
-        client_submitted: bool,
+        provenance: TransactionProvenance,
     ) -> Self {
         Self {
             sequence_info: SequenceInfo {
@@ -46,7 +49,7 @@ impl MempoolTransaction {
             expiration_time,
             ranking_score,
             timeline_state,
-            insertion_info: InsertionInfo::new(insertion_time, client_submitted, timeline_state),
+            insertion_info: InsertionInfo::new(insertion_time, provenance, timeline_state),
             was_parked: false,
         }
     }
@@ -60,7 +63,7 @@ impl MempoolTransaction {
     }
 
     pub(crate) fn get_committed_hash(&self) -> HashValue {
-        self.txn.clone().committed_hash()
+        self.txn.committed_hash()
     }
 
     pub(crate) fn get_estimated_bytes(&self) -> usize {
@@ -112,16 +115,17 @@ pub enum SubmittedBy {
 pub struct InsertionInfo {
     pub insertion_time: SystemTime,
     pub submitted_by: SubmittedBy,
+    pub provenance: TransactionProvenance,
     pub consensus_pulled_counter: Arc<AtomicU8>,
 }
 
 impl InsertionInfo {
     pub fn new(
         insertion_time: SystemTime,
-        client_submitted: bool,
+        provenance: TransactionProvenance,
         timeline_state: TimelineState,
     ) -> Self {
-        let submitted_by = if client_submitted {
+        let submitted_by = if provenance == TransactionProvenance::Client {
             SubmittedBy::Client
         } else if timeline_state == TimelineState::NonQualified {
             SubmittedBy::PeerValidator
@@ -131,6 +135,7 @@ impl InsertionInfo {
         Self {
             insertion_time,
             submitted_by,
+            provenance,
             consensus_pulled_counter: Arc::new(AtomicU8::new(0)),
         }
     }
@@ -146,7 +151,7 @@ impl InsertionInfo {
 
 #[cfg(test)]
 mod test {
-    use crate::core_mempool::{MempoolTransaction, TimelineState};
+    use crate::core_mempool::{MempoolTransaction, TimelineState, TransactionProvenance};
     use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey, Uniform};
     use aptos_types::{
         account_address::AccountAddress,
@@ -173,7 +178,7 @@ mod test {
             TimelineState::NotReady,
             0,
             SystemTime::now(),
-            false,
+            TransactionProvenance::Unknown,
         )
     }
 
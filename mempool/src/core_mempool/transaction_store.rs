@@ -9,6 +9,9 @@ use crate::{
             PriorityQueueIter, TTLIndex,
         },
         mempool::Mempool,
+        provenance::{
+            ProvenanceOutcome, ProvenanceStats, ProvenanceTracker, TransactionProvenance,
+        },
         transaction::{InsertionInfo, MempoolTransaction, TimelineState},
         TxnPointer,
     },
@@ -17,7 +20,7 @@ use crate::{
     logging::{LogEntry, LogEvent, LogSchema, TxnsLog},
     shared_mempool::types::MultiBucketTimelineIndexIds,
 };
-use aptos_config::config::MempoolConfig;
+use aptos_config::{config::MempoolConfig, network_id::PeerNetworkId};
 use aptos_crypto::HashValue;
 use aptos_logger::{prelude::*, Level};
 use aptos_types::{
@@ -28,6 +31,7 @@ use aptos_types::{
 use std::{
     cmp::max,
     collections::HashMap,
+    iter,
     mem::size_of,
     ops::Bound,
     time::{Duration, SystemTime},
@@ -69,6 +73,10 @@ pub struct TransactionStore {
     size_bytes: usize,
     // keeps track of txns that were resubmitted with higher gas
     gas_upgraded_index: HashMap<TxnPointer, u64>,
+    // keeps track of the txn outcomes of each provenance (i.e., txn source)
+    provenance_tracker: ProvenanceTracker,
+    // keeps track of the peers (on other networks) that also sent each txn
+    cross_network_senders: HashMap<TxnPointer, Vec<PeerNetworkId>>,
 
     // configuration
     capacity: usize,
@@ -79,6 +87,10 @@ pub struct TransactionStore {
     // eager expiration
     eager_expire_threshold: Option<Duration>,
     eager_expire_time: Duration,
+
+    // provenance weighted eviction
+    provenance_eviction_reputation_threshold: Option<f64>,
+    provenance_eviction_num_candidates: usize,
 }
 
 impl TransactionStore {
@@ -101,6 +113,8 @@ impl TransactionStore {
             // estimated size in bytes
             size_bytes: 0,
             gas_upgraded_index: HashMap::new(),
+            provenance_tracker: ProvenanceTracker::new(config),
+            cross_network_senders: HashMap::new(),
 
             // configuration
             capacity: config.capacity,
@@ -111,6 +125,12 @@ impl TransactionStore {
             // eager expiration
             eager_expire_threshold: config.eager_expire_threshold_ms.map(Duration::from_millis),
             eager_expire_time: Duration::from_millis(config.eager_expire_time_ms),
+
+            // provenance weighted eviction
+            provenance_eviction_reputation_threshold: config
+                .provenance_eviction_reputation_threshold_pct
+                .map(|threshold_pct| threshold_pct as f64 / 100.0),
+            provenance_eviction_num_candidates: config.provenance_eviction_num_candidates,
         }
     }
 
@@ -125,6 +145,59 @@ impl TransactionStore {
             .and_then(|txns| txns.get(&sequence_number))
     }
 
+    /// Returns true iff the transaction with the given hash is in the store, and was first
+    /// received from a peer on a different network than the given peer. If so, the given
+    /// peer is also recorded as a sender of the transaction.
+    pub(crate) fn record_cross_network_duplicate(
+        &mut self,
+        hash: &HashValue,
+        peer: PeerNetworkId,
+    ) -> bool {
+        let Some(&(address, sequence_number)) = self.hash_index.get(hash) else {
+            return false;
+        };
+        let first_sender = match self
+            .get_mempool_txn(&address, sequence_number)
+            .map(|txn| txn.insertion_info.provenance)
+        {
+            Some(TransactionProvenance::Peer(first_sender)) => first_sender,
+            _ => return false,
+        };
+        if first_sender.network_id() == peer.network_id() {
+            return false;
+        }
+
+        let senders = self
+            .cross_network_senders
+            .entry(TxnPointer::new(address, sequence_number))
+            .or_default();
+        if !senders.contains(&peer) {
+            senders.push(peer);
+        }
+        true
+    }
+
+    /// Returns true iff the given transaction was sent to us by the given peer
+    pub(crate) fn was_received_from(&self, txn: &SignedTransaction, peer: &PeerNetworkId) -> bool {
+        let sent_by_peer = self
+            .get_mempool_txn(&txn.sender(), txn.sequence_number())
+            .map_or(false, |mempool_txn| {
+                mempool_txn.insertion_info.provenance == TransactionProvenance::Peer(*peer)
+            });
+        sent_by_peer
+            || self
+                .cross_network_senders
+                .get(&TxnPointer::new(txn.sender(), txn.sequence_number()))
+                .map_or(false, |senders| senders.contains(peer))
+    }
+
+    /// Returns true iff the given account has transactions in the store
+    pub(crate) fn has_transactions(&self, address: &AccountAddress) -> bool {
+        self.transactions
+            .get(address)
+            .map_or(false, |txns| !txns.is_empty())
+    }
+
     /// Fetch transaction by account address + sequence_number.
     pub(crate) fn get(
         &self,
@@ -224,7 +297,7 @@ impl TransactionStore {
                 } else if current_version.get_gas_price() < txn.get_gas_price() {
                     // Update txn if gas unit price is a larger value than before
                     if let Some(txn) = txns.remove(&txn_seq_num) {
-                        self.index_remove(&txn);
+                        self.index_remove(&txn, ProvenanceOutcome::Replaced);
                     };
                     gas_upgraded = true;
                 } else if current_version.get_gas_price() > txn.get_gas_price() {
@@ -275,6 +348,8 @@ impl TransactionStore {
                 self.gas_upgraded_index
                     .insert(TxnPointer::from(&txn), txn.get_gas_price());
             }
+            self.provenance_tracker
+                .record(&txn.insertion_info.provenance, ProvenanceOutcome::Inserted);
             txns.insert(txn_seq_num, txn);
             self.track_indices();
         }
@@ -325,7 +400,7 @@ impl TransactionStore {
     ) -> bool {
         if self.is_full() && self.check_txn_ready(txn, curr_sequence_number) {
             // try to free some space in Mempool from ParkingLot by evicting a non-ready txn
-            if let Some(txn_pointer) = self.parking_lot_index.get_poppable() {
+            if let Some(txn_pointer) = self.get_eviction_candidate() {
                 if let Some(txn) = self
                     .transactions
                     .get_mut(&txn_pointer.sender)
@@ -337,13 +412,47 @@ impl TransactionStore {
                             txn.sequence_info.transaction_sequence_number
                         ))
                     );
-                    self.index_remove(&txn);
+                    self.index_remove(&txn, ProvenanceOutcome::Evicted);
                 }
             }
         }
         self.is_full()
     }
 
+    /// Returns a non-ready txn from the ParkingLot to evict. If provenance weighted
+    /// eviction is enabled, several random candidates are sampled, and the candidate
+    /// whose provenance has the lowest reputation (below the threshold) is preferred.
+    fn get_eviction_candidate(&self) -> Option<TxnPointer> {
+        let first_candidate = self.parking_lot_index.get_poppable()?;
+        let reputation_threshold = match self.provenance_eviction_reputation_threshold {
+            Some(reputation_threshold) => reputation_threshold,
+            None => return Some(first_candidate),
+        };
+
+        let mut lowest_reputation_candidate: Option<(TxnPointer, f64)> = None;
+        let other_candidates = (1..self.provenance_eviction_num_candidates)
+            .filter_map(|_| self.parking_lot_index.get_poppable());
+        for candidate in iter::once(first_candidate).chain(other_candidates) {
+            let reputation = self
+                .get_mempool_txn(&candidate.sender, candidate.sequence_number)
+                .and_then(|txn| {
+                    self.provenance_tracker
+                        .reputation(&txn.insertion_info.provenance)
+                });
+            if let Some(reputation) = reputation {
+                let is_lowest = lowest_reputation_candidate
+                    .map_or(true, |(_, lowest_reputation)| {
+                        reputation < lowest_reputation
+                    });
+                if reputation < reputation_threshold && is_lowest {
+                    lowest_reputation_candidate = Some((candidate, reputation));
+                }
+            }
+        }
+
+        Some(lowest_reputation_candidate.map_or(first_candidate, |(candidate, _)| candidate))
+    }
+
     fn is_full(&self) -> bool {
         self.system_ttl_index.size() >= self.capacity || self.size_bytes >= self.capacity_bytes
     }
@@ -496,7 +605,7 @@ impl TransactionStore {
                     transaction.get_sender(),
                     transaction.sequence_info.transaction_sequence_number,
                 );
-                self.index_remove(transaction);
+                self.index_remove(transaction, ProvenanceOutcome::Committed);
             }
             trace!(
                 LogSchema::new(LogEntry::CleanCommittedTxn).txns(rm_txns),
@@ -534,7 +643,7 @@ impl TransactionStore {
             if let Some(txns) = self.transactions.get_mut(account) {
                 txns.remove(&sequence_number);
             }
-            self.index_remove(&txn_to_remove);
+            self.index_remove(&txn_to_remove, ProvenanceOutcome::Rejected);
 
             if aptos_logger::enabled!(Level::Trace) {
                 let mut txns_log = TxnsLog::new();
@@ -547,9 +656,82 @@ impl TransactionStore {
         }
     }
 
+    /// Removes all transactions of the given account that were not signed by the given
+    /// authentication key (i.e., transactions invalidated by an on-chain key rotation).
+    /// Any remaining transactions following a removed transaction are parked, as they
+    /// can't be executed until the sequence number gap is filled. Returns the number of
+    /// removed transactions.
+    pub(crate) fn reject_transactions_with_stale_authentication_key(
+        &mut self,
+        account: &AccountAddress,
+        authentication_key: &[u8],
+    ) -> usize {
+        let mut txns_to_remove = vec![];
+        if let Some(txns) = self.transactions.get_mut(account) {
+            let stale_sequence_numbers: Vec<u64> = txns
+                .iter()
+                .filter(|(_, txn)| {
+                    txn.txn
+                        .authenticator_ref()
+                        .sender()
+                        .authentication_key()
+                        .to_vec()
+                        != authentication_key
+                })
+                .map(|(sequence_number, _)| *sequence_number)
+                .collect();
+            let Some(first_stale_sequence_number) = stale_sequence_numbers.first() else {
+                return 0; // No transactions were invalidated
+            };
+
+            // Mark all following txns as non-ready, i.e. park them
+            let park_range = (
+                Bound::Excluded(*first_stale_sequence_number),
+                Bound::Unbounded,
+            );
+            for (_, t) in txns.range_mut(park_range) {
+                self.parking_lot_index.insert(t);
+                t.was_parked = true;
+                self.priority_index.remove(t);
+                self.timeline_index.remove(t);
+                if let TimelineState::Ready(_) = t.timeline_state {
+                    t.timeline_state = TimelineState::NotReady;
+                }
+            }
+            for sequence_number in &stale_sequence_numbers {
+                if let Some(txn) = txns.remove(sequence_number) {
+                    txns_to_remove.push(txn);
+                }
+            }
+        } else {
+            return 0; // No transactions exist for the account
+        }
+
+        let mut rm_txns = match aptos_logger::enabled!(Level::Trace) {
+            true => TxnsLog::new(),
+            false => TxnsLog::new_with_max(10),
+        };
+        for txn in &txns_to_remove {
+            rm_txns.add(
+                txn.get_sender(),
+                txn.sequence_info.transaction_sequence_number,
+            );
+            self.index_remove(txn, ProvenanceOutcome::Rejected);
+        }
+        debug!(
+            LogSchema::new(LogEntry::CleanRotatedKeyTxns).txns(rm_txns),
+            "txns cleaned after key rotation of account {}", account
+        );
+
+        txns_to_remove.len()
+    }
+
     /// Removes transaction from all indexes. Only call after removing from main transactions DS.
-    fn index_remove(&mut self, txn: &MempoolTransaction) {
+    /// The outcome is recorded against the provenance of the transaction.
+    fn index_remove(&mut self, txn: &MempoolTransaction, outcome: ProvenanceOutcome) {
         counters::CORE_MEMPOOL_REMOVED_TXNS.inc();
+        self.provenance_tracker
+            .record(&txn.insertion_info.provenance, outcome);
         self.system_ttl_index.remove(txn);
         self.expiration_time_index.remove(txn);
         self.priority_index.remove(txn);
@@ -558,6 +740,7 @@ impl TransactionStore {
         self.hash_index.remove(&txn.get_committed_hash());
         self.size_bytes -= txn.get_estimated_bytes();
         self.gas_upgraded_index.remove(&TxnPointer::from(txn));
+        self.cross_network_senders.remove(&TxnPointer::from(txn));
 
         // Remove account datastructures if there are no more transactions for the account.
         let address = &txn.get_sender();
@@ -744,7 +927,7 @@ impl TransactionStore {
                     }
 
                     // remove txn
-                    self.index_remove(&txn);
+                    self.index_remove(&txn, ProvenanceOutcome::Expired);
                 }
             }
         }
@@ -757,6 +940,10 @@ impl TransactionStore {
         self.track_indices();
     }
 
+    pub(crate) fn get_provenance_stats(&self) -> Vec<ProvenanceStats> {
+        self.provenance_tracker.get_stats()
+    }
+
     pub(crate) fn iter_queue(&self) -> PriorityQueueIter {
         self.priority_index.iter()
     }
@@ -162,6 +162,16 @@ pub static CORE_MEMPOOL_REMOVED_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter tracking number of txns removed from core mempool because
+/// the authentication key of the sender was rotated on-chain
+pub static CORE_MEMPOOL_KEY_ROTATION_REJECTED_TXNS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_core_mempool_key_rotation_rejected_txns_count",
+        "Number of txns removed from core mempool due to sender key rotations"
+    )
+    .unwrap()
+});
+
 /// Counter tracking number of txns received that are idempotent duplicates
 pub static CORE_MEMPOOL_IDEMPOTENT_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -171,6 +181,26 @@ pub static CORE_MEMPOOL_IDEMPOTENT_TXNS: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter tracking number of txns received on one network that were already received on another
+pub static SHARED_MEMPOOL_CROSS_NETWORK_DUPLICATE_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_shared_mempool_cross_network_duplicate_txns_count",
+        "Number of txns received on a network that were already received on another network",
+        &["network"]
+    )
+    .unwrap()
+});
+
+/// Counter tracking number of txns not broadcast to a peer because the peer sent them to us
+pub static SHARED_MEMPOOL_SKIPPED_BROADCAST_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_shared_mempool_skipped_broadcast_txns_count",
+        "Number of txns not broadcast to a peer because they were received from the peer",
+        &["network"]
+    )
+    .unwrap()
+});
+
 pub fn core_mempool_txn_commit_latency(
     stage: &'static str,
     submitted_by: &'static str,
@@ -429,6 +459,22 @@ pub fn shared_mempool_pending_broadcasts(peer: &PeerNetworkId) -> IntGauge {
     ])
 }
 
+/// Counter tracking the outcomes of transactions by provenance (i.e., the first source of the transaction)
+static MEMPOOL_PROVENANCE_TXNS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_mempool_provenance_txns",
+        "Number of transactions by provenance and outcome",
+        &["network", "outcome"]
+    )
+    .unwrap()
+});
+
+pub fn mempool_provenance_txns_inc(network: &str, outcome: &str) {
+    MEMPOOL_PROVENANCE_TXNS
+        .with_label_values(&[network, outcome])
+        .inc();
+}
+
 static SHARED_MEMPOOL_TRANSACTIONS_PROCESSED: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
         "aptos_shared_mempool_transactions_processed",
@@ -169,6 +169,7 @@ pub enum LogEntry {
     GCRemoveTxns,
     CleanCommittedTxn,
     CleanRejectedTxn,
+    CleanRotatedKeyTxns,
     ProcessReadyTxns,
     DBError,
     UnexpectedNetworkMsg,
@@ -30,14 +30,24 @@ use aptos_network::{
     },
     protocols::network::Event,
 };
-use aptos_types::on_chain_config::{OnChainConfigPayload, OnChainConfigProvider};
+use aptos_storage_interface::DbReader;
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::{AccountResource, KeyRotation},
+    on_chain_config::{OnChainConfigPayload, OnChainConfigProvider},
+    state_store::state_key::StateKey,
+    transaction::Version,
+};
 use aptos_vm_validator::vm_validator::TransactionValidation;
 use futures::{
     channel::mpsc,
     stream::{select_all, FuturesUnordered},
     FutureExt, StreamExt,
 };
+use move_core_types::move_resource::MoveStructType;
 use std::{
+    collections::HashSet,
     sync::Arc,
     time::{Duration, Instant, SystemTime},
 };
@@ -136,13 +146,174 @@ fn spawn_commit_notification_handler<NetworkClient, TransactionValidator>(
     let mempool = smp.mempool.clone();
     let mempool_validator = smp.validator.clone();
 
+    // Key rotations are identified off the commit path (as they require storage reads)
+    let key_rotation_sender = if smp.config.enable_key_rotation_eviction {
+        let (key_rotation_sender, key_rotation_receiver) = mpsc::unbounded();
+        spawn_key_rotation_handler(smp, key_rotation_receiver);
+        Some(key_rotation_sender)
+    } else {
+        None
+    };
+
     tokio::spawn(async move {
         while let Some(commit_notification) = mempool_listener.next().await {
+            if let Some(key_rotation_sender) = &key_rotation_sender {
+                let senders = commit_notification
+                    .transactions
+                    .iter()
+                    .map(|txn| txn.sender)
+                    .collect();
+                if let Err(error) = key_rotation_sender.unbounded_send(senders) {
+                    warn!(
+                        error = ?error,
+                        "Failed to send the committed senders to the key rotation handler!"
+                    );
+                }
+            }
             handle_commit_notification(&mempool, &mempool_validator, commit_notification);
         }
     });
 }
 
+/// Spawn a task to remove the transactions invalidated by key rotations. The task receives
+/// the senders of the committed transactions, and checks them (on a blocking thread) for key
+/// rotations. Senders received while a check is running are batched into the next check.
+fn spawn_key_rotation_handler<NetworkClient, TransactionValidator>(
+    smp: &SharedMempool<NetworkClient, TransactionValidator>,
+    mut committed_senders: mpsc::UnboundedReceiver<Vec<AccountAddress>>,
+) where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg> + 'static,
+    TransactionValidator: TransactionValidation + 'static,
+{
+    let mempool = smp.mempool.clone();
+    let db = smp.db.clone();
+
+    tokio::spawn(async move {
+        // The first version that hasn't been checked for key rotations
+        let mut next_version = None;
+        while let Some(senders) = committed_senders.next().await {
+            let mut senders: HashSet<AccountAddress> = senders.into_iter().collect();
+            while let Ok(Some(more_senders)) = committed_senders.try_next() {
+                senders.extend(more_senders);
+            }
+
+            let mempool = mempool.clone();
+            let db = db.clone();
+            let mut version = next_version;
+            let result = tokio::task::spawn_blocking(move || {
+                handle_key_rotations(&mempool, &db, &senders, &mut version);
+                version
+            })
+            .await;
+            match result {
+                Ok(version) => next_version = version,
+                Err(error) => {
+                    warn!(error = ?error, "Failed to join the key rotation handler!");
+                },
+            }
+        }
+    });
+}
+
+/// Handle removing the transactions invalidated by key rotations from local mempool. Otherwise,
+/// these transactions would occupy space in mempool until they expire (and fail at execution).
+/// Rotations are detected from the changes to the authentication keys in the `Account` resources
+/// of the given (committed) senders, so that all ways of rotating a key are covered (and not only
+/// the ones emitting a key rotation event).
+fn handle_key_rotations(
+    mempool: &Arc<Mutex<CoreMempool>>,
+    db: &Arc<dyn DbReader>,
+    senders: &HashSet<AccountAddress>,
+    next_version: &mut Option<Version>,
+) {
+    match get_key_rotations(mempool, db, senders, next_version) {
+        Ok(key_rotations) if !key_rotations.is_empty() => {
+            let num_rejected_txns = mempool
+                .lock()
+                .reject_transactions_with_rotated_keys(&key_rotations);
+            debug!(
+                num_key_rotations = key_rotations.len(),
+                num_rejected_txns = num_rejected_txns,
+                "Removed the transactions invalidated by key rotations."
+            );
+        },
+        Ok(_) => {}, // No key rotations were committed
+        Err(error) => {
+            warn!(
+                error = ?error,
+                "Failed to identify the key rotations of the committed transactions!"
+            );
+        },
+    }
+}
+
+/// Returns the key rotations (of the given senders with transactions in mempool) committed
+/// since `next_version`, and moves `next_version` past the latest committed version.
+fn get_key_rotations(
+    mempool: &Arc<Mutex<CoreMempool>>,
+    db: &Arc<dyn DbReader>,
+    senders: &HashSet<AccountAddress>,
+    next_version: &mut Option<Version>,
+) -> anyhow::Result<Vec<KeyRotation>> {
+    let latest_version = db.get_latest_version()?;
+    let Some(start_version) = next_version.replace(latest_version + 1) else {
+        return Ok(vec![]); // The first commit only determines where to start checking
+    };
+    let Some(previous_version) = start_version.checked_sub(1) else {
+        return Ok(vec![]); // Accounts can't be rotated in genesis
+    };
+    if start_version > latest_version {
+        return Ok(vec![]);
+    }
+
+    // Only the senders with transactions in mempool are relevant
+    let accounts: Vec<AccountAddress> = {
+        let mempool = mempool.lock();
+        senders
+            .iter()
+            .filter(|account| mempool.has_transactions(account))
+            .cloned()
+            .collect()
+    };
+
+    // Compare the authentication keys before and after the committed versions
+    let mut key_rotations = vec![];
+    for account in accounts {
+        let state_key = StateKey::access_path(AccessPath::resource_access_path(
+            account,
+            AccountResource::struct_tag(),
+        )?);
+        let old_authentication_key = get_authentication_key(db, &state_key, previous_version)?;
+        let new_authentication_key = get_authentication_key(db, &state_key, latest_version)?;
+        if let (Some(old_authentication_key), Some(new_authentication_key)) =
+            (old_authentication_key, new_authentication_key)
+        {
+            if old_authentication_key != new_authentication_key {
+                key_rotations.push(KeyRotation::new(
+                    account,
+                    old_authentication_key,
+                    new_authentication_key,
+                ));
+            }
+        }
+    }
+    Ok(key_rotations)
+}
+
+/// Returns the authentication key in the given `Account` resource at `version` (if any)
+fn get_authentication_key(
+    db: &Arc<dyn DbReader>,
+    state_key: &StateKey,
+    version: Version,
+) -> anyhow::Result<Option<Vec<u8>>> {
+    db.get_state_value_by_version(state_key, version)?
+        .map(|state_value| {
+            let account_resource = bcs::from_bytes::<AccountResource>(state_value.bytes())?;
+            Ok(account_resource.authentication_key().to_vec())
+        })
+        .transpose()
+}
+
 /// Spawn a task for processing `MempoolClientRequest`s from a client such as API service
 async fn handle_client_request<NetworkClient, TransactionValidator>(
     smp: &mut SharedMempool<NetworkClient, TransactionValidator>,
@@ -195,6 +366,13 @@ async fn handle_client_request<NetworkClient, TransactionValidator>(
                 ))
                 .await;
         },
+        MempoolClientRequest::GetProvenanceStats(callback) => {
+            // The stats are cheap to fetch, so there's no need to spawn a task
+            let provenance_stats = smp.mempool.lock().get_provenance_stats();
+            if callback.send(provenance_stats).is_err() {
+                counters::CLIENT_CALLBACK_FAIL.inc();
+            }
+        },
     }
 }
 
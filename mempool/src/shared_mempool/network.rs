@@ -82,6 +82,7 @@ pub(crate) struct MempoolNetworkInterface<NetworkClient> {
     network_client: NetworkClient,
     sync_states: Arc<RwLock<HashMap<PeerNetworkId, PeerSyncState>>>,
     prioritized_peers: Arc<Mutex<Vec<PeerNetworkId>>>,
+    preferred_upstream_peers: Arc<Mutex<Vec<PeerNetworkId>>>, // The preferred upstream peers used for the last prioritization
     role: RoleType,
     mempool_config: MempoolConfig,
     prioritized_peers_comparator: PrioritizedPeersComparator,
@@ -97,6 +98,7 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
             network_client,
             sync_states: Arc::new(RwLock::new(HashMap::new())),
             prioritized_peers: Arc::new(Mutex::new(Vec::new())),
+            preferred_upstream_peers: Arc::new(Mutex::new(Vec::new())),
             role,
             mempool_config,
             prioritized_peers_comparator: PrioritizedPeersComparator::new(),
@@ -154,6 +156,10 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
         // Get the upstream peers to add or disable, using a read lock
         let (to_add, to_disable) = self.get_upstream_peers_to_add_and_disable(all_connected_peers);
         if to_add.is_empty() && to_disable.is_empty() {
+            // The peers haven't changed, but the preferred upstream peers may have
+            if self.preferred_upstream_peers_changed() {
+                self.update_prioritized_peers();
+            }
             return (vec![], vec![]);
         }
         // If there are updates, apply using a write lock
@@ -178,16 +184,35 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
                 .collect()
         };
 
-        // Order peers by network and by type
+        // Get the preferred upstream peers (selected using the measured peer quality)
+        let preferred_upstream_peers = self
+            .network_client
+            .get_peers_and_metadata()
+            .get_preferred_upstream_peers();
+
+        // Order peers by preference, then by network and by type
         // Origin doesn't matter at this point, only inserted ones into peer_states are upstream
         // Validators will always have the full set
         let mut prioritized_peers = self.prioritized_peers.lock();
-        let peers: Vec<_> = peers
-            .iter()
-            .sorted_by(|peer_a, peer_b| self.prioritized_peers_comparator.compare(peer_a, peer_b))
-            .map(|(peer, _)| *peer)
-            .collect();
+        let peers = self
+            .prioritized_peers_comparator
+            .prioritize_peers(&peers, &preferred_upstream_peers);
         let _ = std::mem::replace(&mut *prioritized_peers, peers);
+        *self.preferred_upstream_peers.lock() = preferred_upstream_peers;
+    }
+
+    /// Returns true iff the preferred upstream peers have changed
+    /// since the peers were last prioritized.
+    fn preferred_upstream_peers_changed(&self) -> bool {
+        if self.role.is_validator() {
+            return false; // Validators don't prioritize peers
+        }
+
+        let preferred_upstream_peers = self
+            .network_client
+            .get_peers_and_metadata()
+            .get_preferred_upstream_peers();
+        *self.preferred_upstream_peers.lock() != preferred_upstream_peers
     }
 
     pub fn is_validator(&self) -> bool {
@@ -401,6 +426,32 @@ impl<NetworkClient: NetworkClientInterface<MempoolSyncMsg>> MempoolNetworkInterf
             return Err(BroadcastError::NoTransactions(peer));
         }
 
+        // Skip the transactions that were received from the peer (on any network)
+        let transactions = if self.mempool_config.enable_cross_network_dedup {
+            let num_transactions = transactions.len();
+            let transactions: Vec<_> = transactions
+                .into_iter()
+                .filter(|transaction| !mempool.was_received_from(transaction, &peer))
+                .collect();
+            let num_skipped = num_transactions - transactions.len();
+            if num_skipped > 0 {
+                counters::SHARED_MEMPOOL_SKIPPED_BROADCAST_TXNS
+                    .with_label_values(&[peer.network_id().as_str()])
+                    .inc_by(num_skipped as u64);
+            }
+            transactions
+        } else {
+            transactions
+        };
+
+        // If all transactions were skipped, the batch is complete (without being sent)
+        if transactions.is_empty() {
+            state.timeline_id.update(&batch_id);
+            state.broadcast_info.retry_batches.remove(&batch_id);
+            state.broadcast_info.sent_batches.remove(&batch_id);
+            return Err(BroadcastError::NoTransactions(peer));
+        }
+
         Ok((batch_id, transactions, metric_label))
     }
 
@@ -555,6 +606,30 @@ impl PrioritizedPeersComparator {
         }
     }
 
+    /// Orders the given peers for sending transactions. The preferred upstream
+    /// peers come first (in order of preference), followed by all other peers.
+    fn prioritize_peers(
+        &self,
+        peers: &[(PeerNetworkId, PeerRole)],
+        preferred_upstream_peers: &[PeerNetworkId],
+    ) -> Vec<PeerNetworkId> {
+        let get_preference_index = |peer: &PeerNetworkId| {
+            preferred_upstream_peers
+                .iter()
+                .position(|preferred_peer| preferred_peer == peer)
+                .unwrap_or(usize::MAX)
+        };
+        peers
+            .iter()
+            .sorted_by(|peer_a, peer_b| {
+                get_preference_index(&peer_a.0)
+                    .cmp(&get_preference_index(&peer_b.0))
+                    .then_with(|| self.compare(peer_a, peer_b))
+            })
+            .map(|(peer, _)| *peer)
+            .collect()
+    }
+
     /// Stable within a mempool instance but random between instances.
     fn hash_peer_id(&self, peer_id: &PeerId) -> u64 {
         let mut hasher = self.random_state.build_hasher();
@@ -610,4 +685,40 @@ mod test {
         // Same the only equal case
         assert_eq!(Ordering::Equal, comparator.compare(&val_1, &val_1));
     }
+
+    #[test]
+    fn check_preferred_upstream_peer_prioritization() {
+        let comparator = PrioritizedPeersComparator::new();
+
+        let peer_id_1 = PeerId::from_hex_literal("0x1").unwrap();
+        let peer_id_2 = PeerId::from_hex_literal("0x2").unwrap();
+        let peer_id_3 = PeerId::from_hex_literal("0x3").unwrap();
+        let vfn_1 = (
+            PeerNetworkId::new(NetworkId::Public, peer_id_1),
+            PeerRole::ValidatorFullNode,
+        );
+        let upstream_2 = (
+            PeerNetworkId::new(NetworkId::Public, peer_id_2),
+            PeerRole::Upstream,
+        );
+        let upstream_3 = (
+            PeerNetworkId::new(NetworkId::Public, peer_id_3),
+            PeerRole::Upstream,
+        );
+        let peers = vec![upstream_2, vfn_1, upstream_3];
+
+        // Without preferred upstream peers, the comparator ordering is used
+        let prioritized_peers = comparator.prioritize_peers(&peers, &[]);
+        assert_eq!(prioritized_peers[2], vfn_1.0);
+
+        // Preferred upstream peers come first (in order of preference)
+        let prioritized_peers = comparator.prioritize_peers(&peers, &[vfn_1.0, upstream_3.0]);
+        assert_eq!(prioritized_peers, vec![vfn_1.0, upstream_3.0, upstream_2.0]);
+
+        // Preferred peers that are not upstream peers are ignored
+        let unknown_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let prioritized_peers = comparator.prioritize_peers(&peers, &[unknown_peer, vfn_1.0]);
+        assert_eq!(prioritized_peers[0], vfn_1.0);
+        assert_eq!(prioritized_peers.len(), 3);
+    }
 }
@@ -21,6 +21,7 @@ use aptos_network::application::{
     storage::PeersAndMetadata,
 };
 use aptos_storage_interface::DbReader;
+use aptos_time_service::TimeService;
 use aptos_types::on_chain_config::OnChainConfigProvider;
 use aptos_vm_validator::vm_validator::{TransactionValidation, VMValidator};
 use futures::channel::mpsc::{Receiver, UnboundedSender};
@@ -96,9 +97,13 @@ pub fn bootstrap(
     mempool_listener: MempoolNotificationListener,
     mempool_reconfig_events: ReconfigNotificationListener<DbBackedOnChainConfig>,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    time_service: TimeService,
 ) -> Runtime {
     let runtime = aptos_runtimes::spawn_named_runtime("shared-mem".into(), None);
-    let mempool = Arc::new(Mutex::new(CoreMempool::new(config)));
+    let mempool = Arc::new(Mutex::new(CoreMempool::new_with_time_service(
+        config,
+        time_service,
+    )));
     let vm_validator = Arc::new(RwLock::new(VMValidator::new(Arc::clone(&db))));
     start_shared_mempool(
         runtime.handle(),
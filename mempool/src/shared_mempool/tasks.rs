@@ -4,7 +4,7 @@
 
 //! Tasks that are executed by coordinators (short-lived compared to coordinators)
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{CoreMempool, TimelineState, TransactionProvenance},
     counters,
     logging::{LogEntry, LogEvent, LogSchema},
     network::{BroadcastError, MempoolSyncMsg},
@@ -126,7 +126,12 @@ pub(crate) async fn process_client_transaction_submission<NetworkClient, Transac
     } else {
         TimelineState::NotReady
     };
-    let statuses = process_incoming_transactions(&smp, vec![transaction], timeline_state, true);
+    let statuses = process_incoming_transactions(
+        &smp,
+        vec![transaction],
+        timeline_state,
+        TransactionProvenance::Client,
+    );
     log_txn_process_results(&statuses, None);
 
     if let Some(status) = statuses.first() {
@@ -177,7 +182,15 @@ pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionVali
 {
     timer.stop_and_record();
     let _timer = counters::process_txn_submit_latency_timer(peer.network_id());
-    let results = process_incoming_transactions(&smp, transactions, timeline_state, false);
+
+    // Skip the transactions that were already received from another network
+    let transactions = filter_cross_network_duplicates(&smp, transactions, peer);
+    let results = process_incoming_transactions(
+        &smp,
+        transactions,
+        timeline_state,
+        TransactionProvenance::Peer(peer),
+    );
     log_txn_process_results(&results, Some(peer));
 
     let ack_response = gen_ack_response(request_id, results, &peer);
@@ -200,6 +213,42 @@ pub(crate) async fn process_transaction_broadcast<NetworkClient, TransactionVali
     notify_subscribers(SharedMempoolNotification::ACK, &smp.subscribers);
 }
 
+/// Removes (and counts) the transactions that are already in mempool, having been
+/// received from a different network. This avoids redundant validation for nodes
+/// that receive the same transactions on multiple networks.
+fn filter_cross_network_duplicates<NetworkClient, TransactionValidator>(
+    smp: &SharedMempool<NetworkClient, TransactionValidator>,
+    transactions: Vec<SignedTransaction>,
+    peer: PeerNetworkId,
+) -> Vec<SignedTransaction>
+where
+    NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
+    TransactionValidator: TransactionValidation,
+{
+    if !smp.config.enable_cross_network_dedup {
+        return transactions;
+    }
+
+    let num_transactions = transactions.len();
+    let transactions: Vec<_> = {
+        let mut mempool = smp.mempool.lock();
+        transactions
+            .into_iter()
+            .filter(|transaction| {
+                !mempool.record_cross_network_duplicate(&transaction.committed_hash(), peer)
+            })
+            .collect()
+    };
+
+    let num_duplicates = num_transactions - transactions.len();
+    if num_duplicates > 0 {
+        counters::SHARED_MEMPOOL_CROSS_NETWORK_DUPLICATE_TXNS
+            .with_label_values(&[peer.network_id().as_str()])
+            .inc_by(num_duplicates as u64);
+    }
+    transactions
+}
+
 /// If `MempoolIsFull` on any of the transactions, provide backpressure to the downstream peer.
 fn gen_ack_response(
     request_id: MultiBatchId,
@@ -255,12 +304,27 @@ pub(crate) fn process_incoming_transactions<NetworkClient, TransactionValidator>
     smp: &SharedMempool<NetworkClient, TransactionValidator>,
     transactions: Vec<SignedTransaction>,
     timeline_state: TimelineState,
-    client_submitted: bool,
+    provenance: TransactionProvenance,
 ) -> Vec<SubmissionStatusBundle>
 where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
     TransactionValidator: TransactionValidation,
 {
+    // If transaction validation is disabled, reject all transactions
+    if !smp.config.enable_transaction_validation {
+        return transactions
+            .into_iter()
+            .map(|transaction| {
+                let mempool_status = MempoolStatus::new(MempoolStatusCode::UnknownStatus)
+                    .with_message(
+                        "Transaction validation is disabled on this node (e.g., its state is filtered)!"
+                            .into(),
+                    );
+                (transaction, (mempool_status, None))
+            })
+            .collect();
+    }
+
     let mut statuses = vec![];
 
     let start_storage_read = Instant::now();
@@ -318,13 +382,7 @@ where
         })
         .collect();
 
-    validate_and_add_transactions(
-        transactions,
-        smp,
-        timeline_state,
-        &mut statuses,
-        client_submitted,
-    );
+    validate_and_add_transactions(transactions, smp, timeline_state, &mut statuses, provenance);
     notify_subscribers(SharedMempoolNotification::NewTransactions, &smp.subscribers);
     statuses
 }
@@ -337,7 +395,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
     smp: &SharedMempool<NetworkClient, TransactionValidator>,
     timeline_state: TimelineState,
     statuses: &mut Vec<(SignedTransaction, (MempoolStatus, Option<StatusCode>))>,
-    client_submitted: bool,
+    provenance: TransactionProvenance,
 ) where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
     TransactionValidator: TransactionValidation,
@@ -363,7 +421,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
                             ranking_score,
                             sequence_info,
                             timeline_state,
-                            client_submitted,
+                            provenance,
                         );
                         statuses.push((transaction, (mempool_status, None)));
                     },
@@ -403,7 +461,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
     smp: &SharedMempool<NetworkClient, TransactionValidator>,
     timeline_state: TimelineState,
     statuses: &mut Vec<(SignedTransaction, (MempoolStatus, Option<StatusCode>))>,
-    client_submitted: bool,
+    provenance: TransactionProvenance,
 ) where
     NetworkClient: NetworkClientInterface<MempoolSyncMsg>,
     TransactionValidator: TransactionValidation,
@@ -415,7 +473,7 @@ fn validate_and_add_transactions<NetworkClient, TransactionValidator>(
             0,
             sequence_info,
             timeline_state,
-            client_submitted,
+            provenance,
         );
         statuses.push((transaction, (mempool_status, None)));
     }
@@ -4,7 +4,7 @@
 
 //! Objects used by/related to shared mempool
 use crate::{
-    core_mempool::CoreMempool,
+    core_mempool::{CoreMempool, ProvenanceStats},
     network::{MempoolNetworkInterface, MempoolSyncMsg},
 };
 use anyhow::Result;
@@ -232,6 +232,7 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    GetProvenanceStats(oneshot::Sender<Vec<ProvenanceStats>>),
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
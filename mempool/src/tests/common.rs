@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{CoreMempool, TimelineState, TransactionProvenance},
     network::MempoolSyncMsg,
 };
 use anyhow::{format_err, Result};
@@ -81,20 +81,22 @@ impl TestTransaction {
         self.make_signed_transaction_impl(100, u64::MAX)
     }
 
+    pub(crate) fn make_signed_transaction_with_private_key(
+        &self,
+        private_key: &Ed25519PrivateKey,
+    ) -> SignedTransaction {
+        self.make_raw_transaction(100, u64::MAX)
+            .sign(private_key, private_key.public_key())
+            .expect("Failed to sign raw transaction.")
+            .into_inner()
+    }
+
     fn make_signed_transaction_impl(
         &self,
         max_gas_amount: u64,
         exp_timestamp_secs: u64,
     ) -> SignedTransaction {
-        let raw_txn = RawTransaction::new_script(
-            TestTransaction::get_address(self.address),
-            self.sequence_number,
-            Script::new(vec![], vec![], vec![]),
-            max_gas_amount,
-            self.gas_price,
-            exp_timestamp_secs,
-            ChainId::test(),
-        );
+        let raw_txn = self.make_raw_transaction(max_gas_amount, exp_timestamp_secs);
         let mut seed: [u8; 32] = [0u8; 32];
         seed[..4].copy_from_slice(&[1, 2, 3, 4]);
         let mut rng: StdRng = StdRng::from_seed(seed);
@@ -105,6 +107,18 @@ impl TestTransaction {
             .into_inner()
     }
 
+    fn make_raw_transaction(&self, max_gas_amount: u64, exp_timestamp_secs: u64) -> RawTransaction {
+        RawTransaction::new_script(
+            TestTransaction::get_address(self.address),
+            self.sequence_number,
+            Script::new(vec![], vec![], vec![]),
+            max_gas_amount,
+            self.gas_price,
+            exp_timestamp_secs,
+            ChainId::test(),
+        )
+    }
+
     pub(crate) fn get_address(address: usize) -> AccountAddress {
         ACCOUNTS[address]
     }
@@ -122,7 +136,7 @@ pub(crate) fn add_txns_to_mempool(
             txn.gas_unit_price(),
             transaction.account_seqno,
             TimelineState::NotReady,
-            false,
+            TransactionProvenance::Unknown,
         );
         transactions.push(txn);
     }
@@ -145,7 +159,7 @@ pub(crate) fn add_signed_txn(pool: &mut CoreMempool, transaction: SignedTransact
             transaction.gas_unit_price(),
             0,
             TimelineState::NotReady,
-            false,
+            TransactionProvenance::Unknown,
         )
         .code
     {
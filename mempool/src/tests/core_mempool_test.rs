@@ -3,17 +3,27 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    core_mempool::{CoreMempool, MempoolTransaction, SubmittedBy, TimelineState},
+    core_mempool::{
+        CoreMempool, MempoolTransaction, SubmittedBy, TimelineState, TransactionProvenance,
+    },
     tests::common::{
         add_signed_txn, add_txn, add_txns_to_mempool, setup_mempool,
         setup_mempool_with_broadcast_buckets, txn_bytes_len, TestTransaction,
     },
 };
-use aptos_config::config::NodeConfig;
+use aptos_config::{
+    config::NodeConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
 use aptos_consensus_types::common::{TransactionInProgress, TransactionSummary};
-use aptos_crypto::HashValue;
+use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, Uniform};
+use aptos_time_service::TimeService;
 use aptos_types::{
-    mempool_status::MempoolStatusCode, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
+    account_address::AccountAddress,
+    account_config::KeyRotation,
+    mempool_status::MempoolStatusCode,
+    transaction::{authenticator::AuthenticationKey, SignedTransaction},
+    vm_status::DiscardedVMStatus,
 };
 use itertools::Itertools;
 use maplit::btreemap;
@@ -74,7 +84,7 @@ fn test_transaction_metrics() {
         txn.gas_unit_price(),
         0,
         TimelineState::NotReady,
-        false,
+        TransactionProvenance::Unknown,
     );
     let txn = TestTransaction::new(1, 0, 1).make_signed_transaction();
     mempool.add_txn(
@@ -82,7 +92,7 @@ fn test_transaction_metrics() {
         txn.gas_unit_price(),
         0,
         TimelineState::NonQualified,
-        false,
+        TransactionProvenance::Unknown,
     );
     let txn = TestTransaction::new(2, 0, 1).make_signed_transaction();
     mempool.add_txn(
@@ -90,7 +100,7 @@ fn test_transaction_metrics() {
         txn.gas_unit_price(),
         0,
         TimelineState::NotReady,
-        true,
+        TransactionProvenance::Client,
     );
 
     // Check timestamp returned as end-to-end for broadcast-able transaction
@@ -299,6 +309,30 @@ fn test_system_ttl() {
     assert_eq!(vec![transaction.make_signed_transaction()], batch);
 }
 
+#[test]
+fn test_system_ttl_with_mock_time() {
+    // Create a mempool that uses a mock time service
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.system_transaction_timeout_secs = 10;
+    let time_service = TimeService::mock();
+    let mut mempool = CoreMempool::new_with_time_service(&config, time_service.clone());
+
+    // Add a transaction and verify it survives GC before the system ttl expires
+    let transaction = TestTransaction::new(0, 0, 1);
+    add_txn(&mut mempool, transaction.clone()).unwrap();
+    time_service.clone().into_mock().advance_secs(5);
+    mempool.gc();
+    let batch = mempool.get_batch(1, 1024, true, false, btreemap![]);
+    assert_eq!(vec![transaction.make_signed_transaction()], batch);
+
+    // Advance the time past the system ttl and verify the transaction is evicted
+    time_service.into_mock().advance_secs(6);
+    mempool.gc();
+    assert!(mempool
+        .get_batch(1, 1024, true, false, btreemap![])
+        .is_empty());
+}
+
 #[test]
 fn test_commit_callback() {
     // Consensus commit callback should unlock txns in parking lot.
@@ -568,7 +602,7 @@ fn test_capacity_bytes() {
                 txn.ranking_score,
                 txn.sequence_info.account_sequence_number,
                 txn.timeline_state,
-                false,
+                TransactionProvenance::Unknown,
             );
             assert_eq!(status.code, MempoolStatusCode::Accepted);
         });
@@ -579,7 +613,7 @@ fn test_capacity_bytes() {
                 txn.ranking_score,
                 txn.sequence_info.account_sequence_number,
                 txn.timeline_state,
-                false,
+                TransactionProvenance::Unknown,
             );
             assert_eq!(status.code, MempoolStatusCode::MempoolIsFull);
         }
@@ -597,7 +631,7 @@ fn new_test_mempool_transaction(address: usize, sequence_number: u64) -> Mempool
         TimelineState::NotReady,
         0,
         SystemTime::now(),
-        false,
+        TransactionProvenance::Unknown,
     )
 }
 
@@ -666,7 +700,13 @@ fn test_gc_ready_transaction() {
 
     // Insert in the middle transaction that's going to be expired.
     let txn = TestTransaction::new(1, 1, 1).make_signed_transaction_with_expiration_time(0);
-    pool.add_txn(txn, 1, 0, TimelineState::NotReady, false);
+    pool.add_txn(
+        txn,
+        1,
+        0,
+        TimelineState::NotReady,
+        TransactionProvenance::Unknown,
+    );
 
     // Insert few transactions after it.
     // They are supposed to be ready because there's a sequential path from 0 to them.
@@ -705,7 +745,13 @@ fn test_clean_stuck_transactions() {
     }
     let db_sequence_number = 10;
     let txn = TestTransaction::new(0, db_sequence_number, 1).make_signed_transaction();
-    pool.add_txn(txn, 1, db_sequence_number, TimelineState::NotReady, false);
+    pool.add_txn(
+        txn,
+        1,
+        db_sequence_number,
+        TimelineState::NotReady,
+        TransactionProvenance::Unknown,
+    );
     let block = pool.get_batch(1, 1024, true, false, btreemap![]);
     assert_eq!(block.len(), 1);
     assert_eq!(block[0].sequence_number(), 10);
@@ -721,7 +767,7 @@ fn test_get_transaction_by_hash() {
         1,
         db_sequence_number,
         TimelineState::NotReady,
-        false,
+        TransactionProvenance::Unknown,
     );
     let hash = txn.clone().committed_hash();
     let ret = pool.get_by_hash(hash);
@@ -741,7 +787,7 @@ fn test_get_transaction_by_hash_after_the_txn_is_updated() {
         1,
         db_sequence_number,
         TimelineState::NotReady,
-        false,
+        TransactionProvenance::Unknown,
     );
     let hash = txn.committed_hash();
 
@@ -752,7 +798,7 @@ fn test_get_transaction_by_hash_after_the_txn_is_updated() {
         1,
         db_sequence_number,
         TimelineState::NotReady,
-        false,
+        TransactionProvenance::Unknown,
     );
     let new_txn_hash = new_txn.clone().committed_hash();
 
@@ -959,3 +1005,215 @@ fn test_include_gas_upgraded() {
     });
     assert_eq!(batch.len(), 0);
 }
+
+#[test]
+fn test_provenance_stats() {
+    let mut pool = setup_mempool().0;
+    let peer = PeerNetworkId::new(NetworkId::Public, AccountAddress::random());
+
+    // Add transactions from a client and a peer
+    add_txn_with_provenance(
+        &mut pool,
+        TestTransaction::new(0, 0, 1),
+        TransactionProvenance::Client,
+    );
+    add_txn_with_provenance(
+        &mut pool,
+        TestTransaction::new(1, 0, 1),
+        TransactionProvenance::Peer(peer),
+    );
+    let rejected_txn = add_txn_with_provenance(
+        &mut pool,
+        TestTransaction::new(1, 1, 1),
+        TransactionProvenance::Peer(peer),
+    );
+
+    // Commit and reject the transactions from the peer
+    pool.commit_transaction(&TestTransaction::get_address(1), 0);
+    pool.reject_transaction(
+        &TestTransaction::get_address(1),
+        1,
+        &rejected_txn.committed_hash(),
+        &DiscardedVMStatus::MALFORMED,
+    );
+
+    // Verify the provenance stats
+    let provenance_stats = pool.get_provenance_stats();
+    assert_eq!(provenance_stats.len(), 2);
+    let peer_stats = &provenance_stats[0];
+    assert_eq!(
+        peer_stats.provenance,
+        TransactionProvenance::Peer(peer).to_string()
+    );
+    assert_eq!(peer_stats.num_inserted, 2);
+    assert_eq!(peer_stats.num_committed, 1);
+    assert_eq!(peer_stats.num_rejected, 1);
+    assert_eq!(peer_stats.num_in_mempool, 0);
+    let client_stats = &provenance_stats[1];
+    assert_eq!(
+        client_stats.provenance,
+        TransactionProvenance::Client.to_string()
+    );
+    assert_eq!(client_stats.num_inserted, 1);
+    assert_eq!(client_stats.num_in_mempool, 1);
+}
+
+#[test]
+fn test_provenance_weighted_eviction() {
+    let mut config = NodeConfig::generate_random_config();
+    config.mempool.capacity = 4;
+    config.mempool.provenance_reputation_min_samples = 1;
+    config.mempool.provenance_eviction_reputation_threshold_pct = Some(50);
+    config.mempool.provenance_eviction_num_candidates = 100;
+    let mut pool = CoreMempool::new(&config);
+    let bad_peer = TransactionProvenance::Peer(PeerNetworkId::new(
+        NetworkId::Public,
+        AccountAddress::random(),
+    ));
+    let good_peer = TransactionProvenance::Peer(PeerNetworkId::new(
+        NetworkId::Public,
+        AccountAddress::random(),
+    ));
+
+    // Give the bad peer a rejected transaction, and the good peer a committed one
+    let rejected_txn = add_txn_with_provenance(&mut pool, TestTransaction::new(0, 0, 1), bad_peer);
+    pool.reject_transaction(
+        &TestTransaction::get_address(0),
+        0,
+        &rejected_txn.committed_hash(),
+        &DiscardedVMStatus::MALFORMED,
+    );
+    add_txn_with_provenance(&mut pool, TestTransaction::new(1, 0, 1), good_peer);
+    pool.commit_transaction(&TestTransaction::get_address(1), 0);
+
+    // Fill mempool with non-ready transactions (mostly from the good peer)
+    add_txn_with_provenance(&mut pool, TestTransaction::new(2, 5, 1), bad_peer);
+    for sequence_number in [5, 6, 7] {
+        add_txn_with_provenance(
+            &mut pool,
+            TestTransaction::new(3, sequence_number, 1),
+            good_peer,
+        );
+    }
+    assert_eq!(pool.get_parking_lot_size(), 4);
+
+    // Insert a ready transaction and verify the bad peer's transaction was evicted
+    add_txn(&mut pool, TestTransaction::new(0, 0, 1)).unwrap();
+    assert!(pool
+        .get_transaction_store()
+        .get(&TestTransaction::get_address(2), 5)
+        .is_none());
+    let bad_peer_stats = pool
+        .get_provenance_stats()
+        .into_iter()
+        .find(|stats| stats.provenance == bad_peer.to_string())
+        .unwrap();
+    assert_eq!(bad_peer_stats.num_evicted, 1);
+}
+
+#[test]
+fn test_cross_network_duplicates() {
+    let mut pool = setup_mempool().0;
+    let vfn_peer = PeerNetworkId::new(NetworkId::Vfn, AccountAddress::random());
+    let public_peer = PeerNetworkId::new(NetworkId::Public, AccountAddress::random());
+    let other_public_peer = PeerNetworkId::new(NetworkId::Public, AccountAddress::random());
+
+    // Verify unknown transactions are not duplicates
+    let txn = TestTransaction::new(0, 0, 1).make_signed_transaction();
+    assert!(!pool.record_cross_network_duplicate(&txn.committed_hash(), public_peer));
+
+    // Add the transaction from the VFN peer and verify it's only a duplicate on other networks
+    let txn = add_txn_with_provenance(
+        &mut pool,
+        TestTransaction::new(0, 0, 1),
+        TransactionProvenance::Peer(vfn_peer),
+    );
+    let txn_hash = txn.committed_hash();
+    assert!(!pool.record_cross_network_duplicate(&txn_hash, vfn_peer));
+    assert!(pool.record_cross_network_duplicate(&txn_hash, public_peer));
+
+    // Verify the transaction is only marked as received from the senders
+    assert!(pool.was_received_from(&txn, &vfn_peer));
+    assert!(pool.was_received_from(&txn, &public_peer));
+    assert!(!pool.was_received_from(&txn, &other_public_peer));
+
+    // Commit the transaction and verify it's no longer tracked
+    pool.commit_transaction(&TestTransaction::get_address(0), 0);
+    assert!(!pool.record_cross_network_duplicate(&txn_hash, public_peer));
+    assert!(!pool.was_received_from(&txn, &public_peer));
+}
+
+#[test]
+fn test_reject_transactions_with_rotated_keys() {
+    let mut pool = setup_mempool().0;
+
+    // Add transactions (signed by the old keys) for two accounts
+    let txns = add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(0, 0, 1),
+        TestTransaction::new(0, 1, 1),
+        TestTransaction::new(1, 0, 1),
+    ]);
+    let old_authentication_key = txns[0].authenticator_ref().sender().authentication_key();
+
+    // Add a transaction for the first account, signed by the new key
+    let new_private_key = Ed25519PrivateKey::generate_for_testing();
+    let new_key_txn =
+        TestTransaction::new(0, 2, 1).make_signed_transaction_with_private_key(&new_private_key);
+    add_signed_txn(&mut pool, new_key_txn.clone()).unwrap();
+    let new_authentication_key = new_key_txn
+        .authenticator_ref()
+        .sender()
+        .authentication_key();
+
+    // Rotate the key of the first account twice (only the latest key should be used)
+    let account = TestTransaction::get_address(0);
+    let intermediate_authentication_key = AuthenticationKey::random();
+    let key_rotations = vec![
+        KeyRotation::new(
+            account,
+            old_authentication_key.to_vec(),
+            intermediate_authentication_key.to_vec(),
+        ),
+        KeyRotation::new(
+            account,
+            intermediate_authentication_key.to_vec(),
+            new_authentication_key.to_vec(),
+        ),
+    ];
+    assert_eq!(
+        pool.reject_transactions_with_rotated_keys(&key_rotations),
+        2
+    );
+
+    // Verify that only the transactions signed by the old key were removed
+    let transaction_store = pool.get_transaction_store();
+    assert!(transaction_store.get(&account, 0).is_none());
+    assert!(transaction_store.get(&account, 1).is_none());
+    assert!(transaction_store.get(&account, 2).is_some());
+    assert!(transaction_store
+        .get(&TestTransaction::get_address(1), 0)
+        .is_some());
+
+    // Verify the transaction signed by the new key is parked (i.e., it's not ready)
+    let block = pool.get_batch(10, 1024, true, false, btreemap![]);
+    assert_eq!(block, vec![txns[2].clone()]);
+    let (timeline, _) = pool.read_timeline(&vec![0].into(), 10);
+    assert_eq!(timeline.len(), 1);
+}
+
+fn add_txn_with_provenance(
+    pool: &mut CoreMempool,
+    transaction: TestTransaction,
+    provenance: TransactionProvenance,
+) -> SignedTransaction {
+    let txn = transaction.make_signed_transaction();
+    let status = pool.add_txn(
+        txn.clone(),
+        txn.gas_unit_price(),
+        0,
+        TimelineState::NotReady,
+        provenance,
+    );
+    assert_eq!(status.code, MempoolStatusCode::Accepted);
+    txn
+}
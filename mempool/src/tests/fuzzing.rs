@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{CoreMempool, TimelineState, TransactionProvenance},
     network::MempoolSyncMsg,
     shared_mempool::{tasks, types::SharedMempool},
 };
@@ -57,7 +57,12 @@ pub fn test_mempool_process_incoming_transactions_impl(
         config.base.role,
     );
 
-    let _ = tasks::process_incoming_transactions(&smp, txns, timeline_state, false);
+    let _ = tasks::process_incoming_transactions(
+        &smp,
+        txns,
+        timeline_state,
+        TransactionProvenance::Unknown,
+    );
 }
 
 proptest! {
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    core_mempool::{CoreMempool, TimelineState},
+    core_mempool::{CoreMempool, TimelineState, TransactionProvenance},
     network::MempoolSyncMsg,
     shared_mempool::{start_shared_mempool, types::SharedMempoolNotification},
     tests::common::TestTransaction,
@@ -382,7 +382,7 @@ impl Node {
                 transaction.gas_unit_price(),
                 0,
                 TimelineState::NotReady,
-                false,
+                TransactionProvenance::Unknown,
             );
         }
     }
@@ -590,7 +590,7 @@ fn setup_node_network_interface(
 
 /// Starts up the mempool resources for a single node
 fn start_node_mempool(
-    config: NodeConfig,
+    mut config: NodeConfig,
     network_client: NetworkClient<MempoolSyncMsg>,
     network_service_events: NetworkServiceEvents<MempoolSyncMsg>,
     peers_and_metadata: Arc<PeersAndMetadata>,
@@ -599,6 +599,7 @@ fn start_node_mempool(
     Runtime,
     UnboundedReceiver<SharedMempoolNotification>,
 ) {
+    config.mempool.enable_key_rotation_eviction = false; // The mock db has no account state
     let mempool = Arc::new(Mutex::new(CoreMempool::new(&config)));
     let (sender, subscriber) = unbounded();
     let (_ac_endpoint_sender, ac_endpoint_receiver) = mpsc::channel(1_024);
@@ -571,7 +571,7 @@ fn aptos_channel<K: Eq + Hash + Clone, T>(
 
 /// Creates a full [`SharedMempool`] and mocks all of the database information.
 fn setup_mempool(
-    config: NodeConfig,
+    mut config: NodeConfig,
     network_client: NetworkClient<MempoolSyncMsg>,
     network_service_events: NetworkServiceEvents<MempoolSyncMsg>,
     peers_and_metadata: Arc<PeersAndMetadata>,
@@ -587,6 +587,7 @@ fn setup_mempool(
     let (mempool_notifier, mempool_listener) =
         aptos_mempool_notifications::new_mempool_notifier_listener_pair(100);
 
+    config.mempool.enable_key_rotation_eviction = false; // The mock db has no account state
     let mempool = Arc::new(Mutex::new(CoreMempool::new(&config)));
     let vm_validator = Arc::new(RwLock::new(MockVMValidator));
     let db_ro = Arc::new(MockDbReaderWriter);
@@ -34,6 +34,7 @@ use aptos_network::{
         builder::{AuthenticationMode, PeerManagerBuilder},
         ConnectionRequestSender,
     },
+    port_mapping::PortMapper,
     protocols::{
         health_checker::{self, builder::HealthCheckerBuilder},
         network::{
@@ -70,6 +71,7 @@ pub struct NetworkBuilder {
     health_checker_builder: Option<HealthCheckerBuilder>,
     peer_manager_builder: PeerManagerBuilder,
     peers_and_metadata: Arc<PeersAndMetadata>,
+    port_mapper: Option<PortMapper>,
 }
 
 impl NetworkBuilder {
@@ -119,6 +121,7 @@ impl NetworkBuilder {
             health_checker_builder: None,
             peer_manager_builder,
             peers_and_metadata,
+            port_mapper: None,
         }
     }
 
@@ -228,6 +231,17 @@ impl NetworkBuilder {
             config.enable_latency_aware_dialing,
         );
 
+        // Map the listen port on the NAT gateway (if enabled)
+        if config.port_mapping.enable {
+            network_builder.port_mapper = Some(PortMapper::new(
+                network_context,
+                config.port_mapping.clone(),
+                pubkey,
+                network_builder.peers_and_metadata.clone(),
+                network_builder.time_service.clone(),
+            ));
+        }
+
         network_builder.discovery_listeners = Some(Vec::new());
         for discovery_method in config.discovery_methods() {
             let reconfig_listener = if *discovery_method == DiscoveryMethod::Onchain {
@@ -308,6 +322,15 @@ impl NetworkBuilder {
                 .into_iter()
                 .for_each(|listener| listener.start(executor))
         }
+
+        if let Some(port_mapper) = self.port_mapper.take() {
+            let listen_address = self.peer_manager_builder.listen_address();
+            port_mapper.start(executor, listen_address);
+            debug!(
+                NetworkSchema::new(&self.network_context),
+                "{} Started port mapper", self.network_context
+            );
+        }
         self
     }
 
@@ -325,6 +348,15 @@ impl NetworkBuilder {
         self.peer_manager_builder.listen_address()
     }
 
+    /// Returns the address that other peers should use to dial this node. This is the
+    /// external address mapped on the NAT gateway (if port mapping succeeded), and
+    /// the listen address otherwise.
+    pub fn advertised_address(&self) -> NetworkAddress {
+        self.peers_and_metadata
+            .get_external_address(&self.network_context.network_id())
+            .unwrap_or_else(|| self.listen_address())
+    }
+
     /// Add a `network::connectivity_manager::ConnectivityManager` to the network.
     ///
     /// `network::connectivity_manager::ConnectivityManager` is responsible for ensuring that we are connected
@@ -390,6 +422,7 @@ impl NetworkBuilder {
                     self.network_context,
                     conn_mgr_reqs_tx,
                     pubkey,
+                    self.peers_and_metadata.clone(),
                     reconfig_events,
                 )
             },
@@ -3,16 +3,76 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Integration tests for validator_network.
-use crate::dummy::{setup_network, DummyMsg};
-use aptos_network::{application::interface::NetworkClientInterface, protocols::network::Event};
+use crate::{
+    builder::NetworkBuilder,
+    dummy::{setup_network, DummyMsg},
+};
+use aptos_config::{
+    config::{PortMappingProtocol, HANDSHAKE_VERSION},
+    network_id::NetworkContext,
+};
+use aptos_crypto::{test_utils::TEST_SEED, x25519, Uniform};
+use aptos_network::{
+    application::{interface::NetworkClientInterface, storage::PeersAndMetadata},
+    peer_manager::builder::AuthenticationMode,
+    port_mapping::PortMapping,
+    protocols::network::Event,
+};
+use aptos_time_service::TimeService;
+use aptos_types::{chain_id::ChainId, network_address::NetworkAddress};
 use futures::{future::join, StreamExt};
-use std::time::Duration;
+use rand::{rngs::StdRng, SeedableRng};
+use std::{collections::HashMap, net::SocketAddrV4, time::Duration};
 
 #[test]
 fn test_network_builder() {
     setup_network();
 }
 
+#[test]
+fn test_advertised_address() {
+    // Create a network builder
+    let network_context = NetworkContext::mock();
+    let network_id = network_context.network_id();
+    let peers_and_metadata = PeersAndMetadata::new(&[network_id]);
+    let private_key = x25519::PrivateKey::generate(&mut StdRng::from_seed(TEST_SEED));
+    let public_key = private_key.public_key();
+    let listen_address: NetworkAddress = "/ip4/127.0.0.1/tcp/6180".parse().unwrap();
+    let network_builder = NetworkBuilder::new_for_test(
+        ChainId::test(),
+        HashMap::new(),
+        network_context,
+        TimeService::mock(),
+        listen_address.clone(),
+        AuthenticationMode::Mutual(private_key),
+        peers_and_metadata.clone(),
+    );
+
+    // Verify the listen address is advertised if no port is mapped
+    assert_eq!(network_builder.advertised_address(), listen_address);
+
+    // Map the listen port and verify the external address is advertised
+    let port_mapping = PortMapping {
+        protocol: PortMappingProtocol::NatPmp,
+        external_address: SocketAddrV4::new([203, 0, 113, 7].into(), 40000),
+        lifetime_secs: 7200,
+    };
+    let external_address = port_mapping.to_network_address(public_key);
+    peers_and_metadata.set_external_address(network_id, Some(external_address.clone()));
+    assert_eq!(network_builder.advertised_address(), external_address);
+    assert_eq!(
+        external_address,
+        "/ip4/203.0.113.7/tcp/40000"
+            .parse::<NetworkAddress>()
+            .unwrap()
+            .append_prod_protos(public_key, HANDSHAKE_VERSION)
+    );
+
+    // Remove the mapping and verify the listen address is advertised again
+    peers_and_metadata.set_external_address(network_id, None);
+    assert_eq!(network_builder.advertised_address(), listen_address);
+}
+
 #[test]
 fn test_direct_send() {
     ::aptos_logger::Logger::init_for_testing();
@@ -36,3 +36,12 @@ pub static NETWORK_KEY_MISMATCH: Lazy<IntGaugeVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static EXTERNAL_ADDRESS_MISMATCH: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_network_external_address_mismatch",
+        "Gauge of whether the external (port mapped) address is missing from the onchain addresses",
+        &["role_type", "network_id", "peer_id"]
+    )
+    .unwrap()
+});
@@ -11,6 +11,7 @@ use aptos_crypto::x25519;
 use aptos_event_notifications::ReconfigNotificationListener;
 use aptos_logger::prelude::*;
 use aptos_network::{
+    application::storage::PeersAndMetadata,
     connectivity_manager::{ConnectivityRequest, DiscoverySource},
     counters::inc_by_with_context,
     logging::NetworkSchema,
@@ -21,6 +22,7 @@ use futures::{Stream, StreamExt};
 use std::{
     path::Path,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
     time::Duration,
 };
@@ -69,11 +71,13 @@ impl<P: OnChainConfigProvider> DiscoveryChangeListener<P> {
         network_context: NetworkContext,
         update_channel: aptos_channels::Sender<ConnectivityRequest>,
         expected_pubkey: x25519::PublicKey,
+        peers_and_metadata: Arc<PeersAndMetadata>,
         reconfig_events: ReconfigNotificationListener<P>,
     ) -> Self {
         let source_stream = DiscoveryChangeStream::ValidatorSet(ValidatorSetStream::new(
             network_context,
             expected_pubkey,
+            peers_and_metadata,
             reconfig_events,
         ));
         DiscoveryChangeListener {
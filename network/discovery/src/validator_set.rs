@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    counters::{DISCOVERY_COUNTS, EVENT_PROCESSING_LOOP_BUSY_DURATION_S, NETWORK_KEY_MISMATCH},
+    counters::{
+        DISCOVERY_COUNTS, EVENT_PROCESSING_LOOP_BUSY_DURATION_S, EXTERNAL_ADDRESS_MISMATCH,
+        NETWORK_KEY_MISMATCH,
+    },
     DiscoveryError,
 };
 use aptos_config::{
@@ -13,19 +16,26 @@ use aptos_config::{
 use aptos_crypto::x25519;
 use aptos_event_notifications::ReconfigNotificationListener;
 use aptos_logger::prelude::*;
-use aptos_network::{counters::inc_by_with_context, logging::NetworkSchema};
+use aptos_network::{
+    application::storage::PeersAndMetadata, counters::inc_by_with_context, logging::NetworkSchema,
+};
 use aptos_short_hex_str::AsShortHexStr;
-use aptos_types::on_chain_config::{OnChainConfigPayload, OnChainConfigProvider, ValidatorSet};
+use aptos_types::{
+    network_address::NetworkAddress,
+    on_chain_config::{OnChainConfigPayload, OnChainConfigProvider, ValidatorSet},
+};
 use futures::Stream;
 use std::{
     collections::HashSet,
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
 pub struct ValidatorSetStream<P: OnChainConfigProvider> {
     pub(crate) network_context: NetworkContext,
     expected_pubkey: x25519::PublicKey,
+    peers_and_metadata: Arc<PeersAndMetadata>,
     reconfig_events: ReconfigNotificationListener<P>,
 }
 
@@ -33,11 +43,13 @@ impl<P: OnChainConfigProvider> ValidatorSetStream<P> {
     pub(crate) fn new(
         network_context: NetworkContext,
         expected_pubkey: x25519::PublicKey,
+        peers_and_metadata: Arc<PeersAndMetadata>,
         reconfig_events: ReconfigNotificationListener<P>,
     ) -> Self {
         Self {
             network_context,
             expected_pubkey,
+            peers_and_metadata,
             reconfig_events,
         }
     }
@@ -66,6 +78,38 @@ impl<P: OnChainConfigProvider> ValidatorSetStream<P> {
             .set(mismatch);
     }
 
+    /// Ensures the external address of the node (i.e., the address mapped on the NAT
+    /// gateway, if any) is advertised onchain. Otherwise, peers cannot dial the node
+    /// using it, and the onchain addresses must be updated by the operator.
+    fn find_external_address_mismatches(&self, onchain_addresses: Option<&Vec<NetworkAddress>>) {
+        let external_address = self
+            .peers_and_metadata
+            .get_external_address(&self.network_context.network_id());
+        let mismatch = match (external_address, onchain_addresses) {
+            (Some(external_address), Some(onchain_addresses))
+                if !onchain_addresses.contains(&external_address) =>
+            {
+                warn!(
+                    NetworkSchema::new(&self.network_context),
+                    "The external address {} is not advertised onchain (onchain addresses: {:?})! \
+                     Update the onchain network addresses so that peers can dial this node.",
+                    external_address,
+                    onchain_addresses
+                );
+                1
+            },
+            _ => 0,
+        };
+
+        EXTERNAL_ADDRESS_MISMATCH
+            .with_label_values(&[
+                self.network_context.role().as_str(),
+                self.network_context.network_id().as_str(),
+                self.network_context.peer_id().short_str().as_str(),
+            ])
+            .set(mismatch);
+    }
+
     fn extract_updates(&mut self, payload: OnChainConfigPayload<P>) -> PeerSet {
         let _process_timer = EVENT_PROCESSING_LOOP_BUSY_DURATION_S.start_timer();
 
@@ -75,11 +119,9 @@ impl<P: OnChainConfigProvider> ValidatorSetStream<P> {
 
         let peer_set = extract_validator_set_updates(self.network_context, node_set);
         // Ensure that the public key matches what's onchain for this peer
-        self.find_key_mismatches(
-            peer_set
-                .get(&self.network_context.peer_id())
-                .map(|peer| &peer.keys),
-        );
+        let own_peer = peer_set.get(&self.network_context.peer_id());
+        self.find_key_mismatches(own_peer.map(|peer| &peer.keys));
+        self.find_external_address_mismatches(own_peer.map(|peer| &peer.addresses));
 
         inc_by_with_context(
             &DISCOVERY_COUNTS,
@@ -194,6 +236,7 @@ mod tests {
             network_context,
             conn_mgr_reqs_tx,
             pubkey,
+            PeersAndMetadata::new(&[network_context.network_id()]),
             reconfig_listener,
         );
 
@@ -221,6 +264,71 @@ mod tests {
         check_network_key_mismatch_metric(1, &network_context);
     }
 
+    #[test]
+    fn metric_if_external_address_not_onchain() {
+        aptos_logger::Logger::init_for_testing();
+        let runtime = Runtime::new().unwrap();
+        let consensus_private_key = bls12381::PrivateKey::generate_for_testing();
+        let consensus_pubkey = consensus_private_key.public_key();
+        let pubkey = test_pubkey([2u8; 32]);
+        let peer_id = aptos_types::account_address::from_identity_public_key(pubkey);
+
+        // Map an external address that differs from the onchain address
+        let network_context = NetworkContext::mock_with_peer_id(peer_id);
+        let peers_and_metadata = PeersAndMetadata::new(&[network_context.network_id()]);
+        let external_address: NetworkAddress = "/ip4/203.0.113.7/tcp/40000".parse().unwrap();
+        peers_and_metadata.set_external_address(
+            network_context.network_id(),
+            Some(external_address.append_prod_protos(pubkey, HANDSHAKE_VERSION)),
+        );
+
+        // Build up the Reconfig Listener
+        let (conn_mgr_reqs_tx, _rx) = aptos_channels::new_test(1);
+        let (mut reconfig_sender, reconfig_events) = aptos_channel::new(QueueStyle::LIFO, 1, None);
+        let reconfig_listener = ReconfigNotificationListener {
+            notification_receiver: reconfig_events,
+        };
+        let listener = DiscoveryChangeListener::validator_set(
+            network_context,
+            conn_mgr_reqs_tx,
+            pubkey,
+            peers_and_metadata,
+            reconfig_listener,
+        );
+
+        // Send an update with the (mock) onchain address
+        send_pubkey_update(peer_id, consensus_pubkey, pubkey, &mut reconfig_sender);
+
+        let listener_future = async move {
+            // Run the test, ensuring we actually stop after a couple seconds in case it fails to fail
+            timeout_at(
+                tokio::time::Instant::from(Instant::now() + Duration::from_secs(1)),
+                Box::pin(listener).run(),
+            )
+            .await
+            .expect_err("Expect timeout");
+        };
+
+        // Ensure the metric is updated
+        check_external_address_mismatch_metric(0, &network_context);
+        block_on(runtime.spawn(listener_future)).unwrap();
+        check_external_address_mismatch_metric(1, &network_context);
+    }
+
+    fn check_external_address_mismatch_metric(expected: i64, network_context: &NetworkContext) {
+        assert_eq!(
+            expected,
+            EXTERNAL_ADDRESS_MISMATCH
+                .get_metric_with_label_values(&[
+                    network_context.role().as_str(),
+                    network_context.network_id().as_str(),
+                    network_context.peer_id().short_str().as_str()
+                ])
+                .unwrap()
+                .get()
+        )
+    }
+
     fn check_network_key_mismatch_metric(expected: i64, network_context: &NetworkContext) {
         assert_eq!(
             expected,
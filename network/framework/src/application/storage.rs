@@ -16,7 +16,7 @@ use aptos_config::{
 };
 use aptos_infallible::RwLock;
 use aptos_peer_monitoring_service_types::PeerMonitoringMetadata;
-use aptos_types::{account_address::AccountAddress, PeerId};
+use aptos_types::{account_address::AccountAddress, network_address::NetworkAddress, PeerId};
 use arc_swap::ArcSwap;
 use std::{
     collections::{hash_map::Entry, HashMap},
@@ -39,6 +39,16 @@ pub struct PeersAndMetadata {
     //
     // TODO: should we remove this when generational versioning is supported?
     cached_peers_and_metadata: Arc<ArcSwap<HashMap<NetworkId, HashMap<PeerId, PeerMetadata>>>>,
+
+    // The preferred upstream peers of the node (ordered by preference). These
+    // are selected by the peer monitoring client (using the measured peer
+    // quality) and prioritized by applications (e.g., mempool and state sync).
+    preferred_upstream_peers: ArcSwap<Vec<PeerNetworkId>>,
+
+    // The externally reachable addresses of the node (per network). These are
+    // discovered by mapping the listen port on the local NAT gateway (if port
+    // mapping is enabled), and are advertised to other peers and operators.
+    external_addresses: ArcSwap<HashMap<NetworkId, NetworkAddress>>,
 }
 
 impl PeersAndMetadata {
@@ -48,6 +58,8 @@ impl PeersAndMetadata {
             peers_and_metadata: RwLock::new(HashMap::new()),
             trusted_peers: HashMap::new(),
             cached_peers_and_metadata: Arc::new(ArcSwap::from(Arc::new(HashMap::new()))),
+            preferred_upstream_peers: ArcSwap::from(Arc::new(vec![])),
+            external_addresses: ArcSwap::from(Arc::new(HashMap::new())),
         };
 
         // Initialize each network mapping and trusted peer set
@@ -345,6 +357,50 @@ impl PeersAndMetadata {
         Ok(())
     }
 
+    /// Returns the preferred upstream peers (ordered by preference)
+    pub fn get_preferred_upstream_peers(&self) -> Vec<PeerNetworkId> {
+        self.preferred_upstream_peers.load().deref().deref().clone()
+    }
+
+    /// Returns true iff the given peer is a preferred upstream peer
+    pub fn is_preferred_upstream_peer(&self, peer_network_id: &PeerNetworkId) -> bool {
+        self.preferred_upstream_peers
+            .load()
+            .contains(peer_network_id)
+    }
+
+    /// Updates the preferred upstream peers (ordered by preference)
+    pub fn set_preferred_upstream_peers(&self, preferred_upstream_peers: Vec<PeerNetworkId>) {
+        self.preferred_upstream_peers
+            .store(Arc::new(preferred_upstream_peers));
+    }
+
+    /// Returns the external (advertised) address of the node for the given network
+    pub fn get_external_address(&self, network_id: &NetworkId) -> Option<NetworkAddress> {
+        self.external_addresses.load().get(network_id).cloned()
+    }
+
+    /// Updates the external (advertised) address of the node for the given network.
+    /// If no address is given, the existing external address is removed.
+    pub fn set_external_address(
+        &self,
+        network_id: NetworkId,
+        external_address: Option<NetworkAddress>,
+    ) {
+        self.external_addresses.rcu(|external_addresses| {
+            let mut external_addresses = HashMap::clone(external_addresses);
+            match &external_address {
+                Some(external_address) => {
+                    external_addresses.insert(network_id, external_address.clone());
+                },
+                None => {
+                    external_addresses.remove(&network_id);
+                },
+            }
+            external_addresses
+        });
+    }
+
     #[cfg(test)]
     /// Returns all internal maps (for testing purposes only)
     pub(crate) fn get_all_internal_maps(
@@ -138,6 +138,8 @@ pub struct ConnectivityManager<TBackoff> {
 #[derive(Copy, Clone, Eq, Hash, PartialEq, Ord, PartialOrd, NumVariants, Serialize)]
 pub enum DiscoverySource {
     OnChainValidatorSet,
+    // The validators joining in the next epoch (dialed before the reconfiguration commits)
+    NextValidatorSet,
     File,
     Rest,
     Config,
@@ -153,6 +155,7 @@ impl fmt::Display for DiscoverySource {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", match self {
             DiscoverySource::OnChainValidatorSet => "OnChainValidatorSet",
+            DiscoverySource::NextValidatorSet => "NextValidatorSet",
             DiscoverySource::File => "File",
             DiscoverySource::Config => "Config",
             DiscoverySource::Rest => "Rest",
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::protocols::wire::handshake::v1::ProtocolId;
-use aptos_config::network_id::NetworkContext;
+use aptos_config::{config::PortMappingProtocol, network_id::NetworkContext};
 use aptos_metrics_core::{
     exponential_buckets, register_histogram_vec, register_int_counter_vec, register_int_gauge,
     register_int_gauge_vec, Histogram, HistogramTimer, HistogramVec, IntCounter, IntCounterVec,
@@ -622,6 +622,35 @@ pub fn observe_pre_dial_ping_time(network_context: &NetworkContext, ping_latency
     observe_ping_time(network_context, ping_latency_secs, PRE_DIAL_LABEL);
 }
 
+/// Counters related to port mapping attempts on the NAT gateway
+pub static NETWORK_PORT_MAPPING_ATTEMPTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_network_port_mapping_attempts",
+        "Counters related to port mapping attempts on the NAT gateway",
+        &["network_id", "protocol", "result"],
+    )
+    .unwrap()
+});
+
+/// Increments the port mapping attempts for the given protocol and result
+pub fn port_mapping_attempt(
+    network_context: &NetworkContext,
+    protocol: &PortMappingProtocol,
+    result: &str,
+) {
+    let protocol_label = match protocol {
+        PortMappingProtocol::NatPmp => "nat_pmp",
+        PortMappingProtocol::Upnp => "upnp",
+    };
+    NETWORK_PORT_MAPPING_ATTEMPTS
+        .with_label_values(&[
+            network_context.network_id().as_str(),
+            protocol_label,
+            result,
+        ])
+        .inc();
+}
+
 /// Observes the ping time for the given label
 fn observe_ping_time(network_context: &NetworkContext, ping_latency_secs: f64, label: &str) {
     NETWORK_PEER_PING_TIMES
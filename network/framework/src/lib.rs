@@ -20,6 +20,7 @@ pub mod logging;
 pub mod noise;
 pub mod peer;
 pub mod peer_manager;
+pub mod port_mapping;
 pub mod protocols;
 pub mod transport;
 
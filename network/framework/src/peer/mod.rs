@@ -36,7 +36,7 @@ use crate::{
 };
 use aptos_channels::aptos_channel;
 use aptos_config::network_id::NetworkContext;
-use aptos_logger::prelude::*;
+use aptos_logger::{debug_toggles, prelude::*};
 use aptos_short_hex_str::AsShortHexStr;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::PeerId;
@@ -435,6 +435,17 @@ where
         &mut self,
         message: NetworkMessage,
     ) -> Result<(), PeerManagerError> {
+        if debug_toggles::NETWORK_MESSAGE_DUMP.should_dump(self.remote_peer_id()) {
+            info!(
+                NetworkSchema::new(&self.network_context)
+                    .connection_metadata(&self.connection_metadata),
+                "{} [Message dump] Received message from peer {}: {:?}",
+                self.network_context,
+                self.remote_peer_id().short_str(),
+                message,
+            );
+        }
+
         match message {
             NetworkMessage::DirectSendMsg(message) => self.handle_inbound_direct_send(message),
             NetworkMessage::Error(error_msg) => {
@@ -585,6 +596,16 @@ where
             self.remote_peer_id().short_str(),
             request
         );
+        if debug_toggles::NETWORK_MESSAGE_DUMP.should_dump(self.remote_peer_id()) {
+            info!(
+                NetworkSchema::new(&self.network_context)
+                    .connection_metadata(&self.connection_metadata),
+                "{} [Message dump] Sending request to peer {}: {:?}",
+                self.network_context,
+                self.remote_peer_id().short_str(),
+                request,
+            );
+        }
         match request {
             // To send an outbound DirectSendMsg, we just bump some counters and
             // push it onto our outbound writer queue.
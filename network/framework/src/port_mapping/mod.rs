@@ -0,0 +1,212 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Port mapping (NAT traversal) for the listen address of the node.
+//!
+//! Nodes run behind a NAT (e.g., a home router) are not dialable by other peers
+//! unless the listen port is forwarded by the NAT gateway. If enabled, the
+//! [`PortMapper`] periodically asks the gateway to map the listen port (using
+//! NAT-PMP or UPnP IGD), discovers the external address of the node and
+//! publishes it (see [`PeersAndMetadata::get_external_address`]).
+
+use crate::{application::storage::PeersAndMetadata, counters, logging::NetworkSchema};
+use aptos_config::{
+    config::{PortMappingConfig, PortMappingProtocol, HANDSHAKE_VERSION},
+    network_id::NetworkContext,
+};
+use aptos_crypto::x25519;
+use aptos_logger::prelude::*;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::network_address::{parse_ip_tcp, NetworkAddress};
+use std::{
+    io,
+    net::{IpAddr, SocketAddr, SocketAddrV4},
+    sync::Arc,
+    time::Duration,
+};
+use thiserror::Error;
+use tokio::runtime::Handle;
+
+mod natpmp;
+mod upnp;
+
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("The NAT gateway returned an error: {0}")]
+    GatewayError(String),
+
+    #[error("IO error: {0}")]
+    IoError(#[from] io::Error),
+
+    #[error("No NAT gateway was found: {0}")]
+    NoGatewayFound(String),
+
+    #[error("The request to the NAT gateway timed out: {0}")]
+    Timeout(String),
+
+    #[error("Unexpected response from the NAT gateway: {0}")]
+    UnexpectedResponse(String),
+}
+
+/// A port mapping created on the NAT gateway
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PortMapping {
+    pub protocol: PortMappingProtocol, // The protocol used to create the mapping
+    pub external_address: SocketAddrV4, // The external address (and port) of the mapping
+    pub lifetime_secs: u32,            // The lifetime of the mapping (granted by the gateway)
+}
+
+impl PortMapping {
+    /// Returns the (advertisable) network address of the port mapping
+    pub fn to_network_address(&self, network_public_key: x25519::PublicKey) -> NetworkAddress {
+        NetworkAddress::from(SocketAddr::V4(self.external_address))
+            .append_prod_protos(network_public_key, HANDSHAKE_VERSION)
+    }
+}
+
+/// Periodically maps the listen port on the NAT gateway and
+/// publishes the external address of the node.
+pub struct PortMapper {
+    network_context: NetworkContext,
+    config: PortMappingConfig,
+    network_public_key: x25519::PublicKey,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+    time_service: TimeService,
+}
+
+impl PortMapper {
+    pub fn new(
+        network_context: NetworkContext,
+        config: PortMappingConfig,
+        network_public_key: x25519::PublicKey,
+        peers_and_metadata: Arc<PeersAndMetadata>,
+        time_service: TimeService,
+    ) -> Self {
+        Self {
+            network_context,
+            config,
+            network_public_key,
+            peers_and_metadata,
+            time_service,
+        }
+    }
+
+    /// Starts the port mapper for the given (bound) listen address
+    pub fn start(self, executor: &Handle, listen_address: NetworkAddress) {
+        executor.spawn(self.run(listen_address));
+    }
+
+    async fn run(self, listen_address: NetworkAddress) {
+        // Only IPv4 TCP listen addresses can be mapped
+        let internal_port = match parse_ip_tcp(listen_address.as_slice()) {
+            Some(((IpAddr::V4(_), port), _)) => port,
+            _ => {
+                warn!(
+                    NetworkSchema::new(&self.network_context).network_address(&listen_address),
+                    "{} Port mapping is only supported for IPv4 TCP listen addresses!",
+                    self.network_context
+                );
+                return;
+            },
+        };
+
+        let network_id = self.network_context.network_id();
+        loop {
+            let next_attempt_delay = match self.map_port(internal_port).await {
+                Some(port_mapping) => {
+                    let external_address = port_mapping.to_network_address(self.network_public_key);
+                    if self.peers_and_metadata.get_external_address(&network_id)
+                        != Some(external_address.clone())
+                    {
+                        info!(
+                            NetworkSchema::new(&self.network_context)
+                                .network_address(&external_address),
+                            "{} Mapped the listen port on the NAT gateway using {:?}. Advertising the external address: {}",
+                            self.network_context,
+                            port_mapping.protocol,
+                            external_address
+                        );
+                    }
+                    self.peers_and_metadata
+                        .set_external_address(network_id, Some(external_address));
+
+                    // Refresh the mapping at half-life
+                    Duration::from_secs(u64::from(port_mapping.lifetime_secs / 2).max(1))
+                },
+                None => {
+                    self.peers_and_metadata
+                        .set_external_address(network_id, None);
+                    Duration::from_secs(self.config.retry_interval_secs)
+                },
+            };
+            self.time_service.sleep(next_attempt_delay).await;
+        }
+    }
+
+    /// Attempts to map the given port using each of the configured
+    /// protocols (in order). Returns the first successful mapping.
+    async fn map_port(&self, internal_port: u16) -> Option<PortMapping> {
+        let request_timeout = Duration::from_millis(self.config.request_timeout_ms);
+        for protocol in &self.config.protocols {
+            let result = match protocol {
+                PortMappingProtocol::NatPmp => {
+                    match self
+                        .config
+                        .gateway_address
+                        .or_else(natpmp::get_default_gateway)
+                    {
+                        Some(gateway_address) => {
+                            natpmp::map_port(
+                                gateway_address,
+                                internal_port,
+                                self.config.mapping_lifetime_secs,
+                                request_timeout,
+                            )
+                            .await
+                        },
+                        None => Err(Error::NoGatewayFound(
+                            "Unable to find the default gateway!".into(),
+                        )),
+                    }
+                },
+                PortMappingProtocol::Upnp => {
+                    upnp::map_port(
+                        internal_port,
+                        self.config.mapping_lifetime_secs,
+                        request_timeout,
+                    )
+                    .await
+                },
+            };
+
+            match result {
+                Ok(port_mapping) => {
+                    counters::port_mapping_attempt(
+                        &self.network_context,
+                        protocol,
+                        counters::SUCCEEDED_LABEL,
+                    );
+                    return Some(port_mapping);
+                },
+                Err(error) => {
+                    counters::port_mapping_attempt(
+                        &self.network_context,
+                        protocol,
+                        counters::FAILED_LABEL,
+                    );
+                    warn!(
+                        NetworkSchema::new(&self.network_context),
+                        "{} Failed to map the listen port using {:?}! Error: {}",
+                        self.network_context,
+                        protocol,
+                        error
+                    );
+                },
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal NAT Port Mapping Protocol (NAT-PMP) client. See RFC 6886.
+
+use crate::port_mapping::{Error, PortMapping};
+use aptos_config::config::PortMappingProtocol;
+use std::{
+    net::{Ipv4Addr, SocketAddrV4},
+    time::Duration,
+};
+use tokio::net::UdpSocket;
+
+// The port on which the NAT gateway listens for NAT-PMP requests
+const NAT_PMP_PORT: u16 = 5351;
+const NAT_PMP_VERSION: u8 = 0;
+
+// Request opcodes (the response opcodes are offset by 128)
+pub(crate) const EXTERNAL_ADDRESS_OPCODE: u8 = 0;
+pub(crate) const MAP_TCP_OPCODE: u8 = 2;
+const RESPONSE_OPCODE_OFFSET: u8 = 128;
+
+// Response sizes (in bytes)
+const EXTERNAL_ADDRESS_RESPONSE_SIZE: usize = 12;
+const MAP_TCP_RESPONSE_SIZE: usize = 16;
+
+// The number of times each request is sent before giving up
+const NUM_REQUEST_ATTEMPTS: u32 = 3;
+
+// The location of the kernel routing table (used to find the default gateway)
+const ROUTING_TABLE_PATH: &str = "/proc/net/route";
+
+/// Maps the given TCP port on the NAT gateway, and returns the
+/// external address (and port) of the mapping.
+pub(crate) async fn map_port(
+    gateway_address: Ipv4Addr,
+    internal_port: u16,
+    lifetime_secs: u32,
+    request_timeout: Duration,
+) -> Result<PortMapping, Error> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    socket.connect((gateway_address, NAT_PMP_PORT)).await?;
+
+    // Discover the external address of the gateway
+    let response =
+        send_request(&socket, &encode_external_address_request(), request_timeout).await?;
+    let external_ip = decode_external_address_response(&response)?;
+
+    // Map the port (requesting the same external port)
+    let request = encode_map_tcp_request(internal_port, internal_port, lifetime_secs);
+    let response = send_request(&socket, &request, request_timeout).await?;
+    let (external_port, lifetime_secs) = decode_map_tcp_response(&response, internal_port)?;
+
+    Ok(PortMapping {
+        protocol: PortMappingProtocol::NatPmp,
+        external_address: SocketAddrV4::new(external_ip, external_port),
+        lifetime_secs,
+    })
+}
+
+/// Sends the request to the gateway and waits for the response (retrying on timeouts)
+async fn send_request(
+    socket: &UdpSocket,
+    request: &[u8],
+    request_timeout: Duration,
+) -> Result<Vec<u8>, Error> {
+    let mut buffer = [0u8; MAP_TCP_RESPONSE_SIZE];
+    for _ in 0..NUM_REQUEST_ATTEMPTS {
+        socket.send(request).await?;
+        if let Ok(result) = tokio::time::timeout(request_timeout, socket.recv(&mut buffer)).await {
+            let num_bytes = result?;
+            return Ok(buffer[..num_bytes].to_vec());
+        }
+    }
+
+    Err(Error::Timeout(format!(
+        "No NAT-PMP response after {} attempts!",
+        NUM_REQUEST_ATTEMPTS
+    )))
+}
+
+/// Returns the default gateway of the host (by reading the kernel routing table)
+pub(crate) fn get_default_gateway() -> Option<Ipv4Addr> {
+    let routing_table = std::fs::read_to_string(ROUTING_TABLE_PATH).ok()?;
+    parse_default_gateway(&routing_table)
+}
+
+/// Parses the default gateway from the given (Linux) routing table. Each entry
+/// holds the destination and gateway as hex-encoded values (in host byte order).
+pub(crate) fn parse_default_gateway(routing_table: &str) -> Option<Ipv4Addr> {
+    routing_table.lines().skip(1).find_map(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        match fields.as_slice() {
+            [_interface, "00000000", gateway, ..] => {
+                let gateway = u32::from_str_radix(gateway, 16).ok()?;
+                let gateway = Ipv4Addr::from(gateway.to_ne_bytes());
+                (!gateway.is_unspecified()).then_some(gateway)
+            },
+            _ => None,
+        }
+    })
+}
+
+/// Encodes an external address request
+pub(crate) fn encode_external_address_request() -> Vec<u8> {
+    vec![NAT_PMP_VERSION, EXTERNAL_ADDRESS_OPCODE]
+}
+
+/// Encodes a TCP port mapping request
+pub(crate) fn encode_map_tcp_request(
+    internal_port: u16,
+    external_port: u16,
+    lifetime_secs: u32,
+) -> Vec<u8> {
+    let mut request = vec![NAT_PMP_VERSION, MAP_TCP_OPCODE, 0, 0]; // Version, opcode, reserved
+    request.extend_from_slice(&internal_port.to_be_bytes());
+    request.extend_from_slice(&external_port.to_be_bytes());
+    request.extend_from_slice(&lifetime_secs.to_be_bytes());
+    request
+}
+
+/// Decodes an external address response, and returns the external address
+pub(crate) fn decode_external_address_response(response: &[u8]) -> Result<Ipv4Addr, Error> {
+    verify_response_header(
+        response,
+        EXTERNAL_ADDRESS_OPCODE,
+        EXTERNAL_ADDRESS_RESPONSE_SIZE,
+    )?;
+    Ok(Ipv4Addr::new(
+        response[8],
+        response[9],
+        response[10],
+        response[11],
+    ))
+}
+
+/// Decodes a TCP port mapping response, and returns the mapped
+/// external port and the lifetime of the mapping.
+pub(crate) fn decode_map_tcp_response(
+    response: &[u8],
+    internal_port: u16,
+) -> Result<(u16, u32), Error> {
+    verify_response_header(response, MAP_TCP_OPCODE, MAP_TCP_RESPONSE_SIZE)?;
+
+    let response_internal_port = u16::from_be_bytes([response[8], response[9]]);
+    if response_internal_port != internal_port {
+        return Err(Error::UnexpectedResponse(format!(
+            "The mapped internal port ({}) does not match the requested port ({})!",
+            response_internal_port, internal_port
+        )));
+    }
+    let external_port = u16::from_be_bytes([response[10], response[11]]);
+    let lifetime_secs =
+        u32::from_be_bytes([response[12], response[13], response[14], response[15]]);
+
+    Ok((external_port, lifetime_secs))
+}
+
+/// Verifies the size, version, opcode and result code of the response
+fn verify_response_header(
+    response: &[u8],
+    request_opcode: u8,
+    expected_size: usize,
+) -> Result<(), Error> {
+    if response.len() < expected_size {
+        return Err(Error::UnexpectedResponse(format!(
+            "The response is too short! Got {} bytes, expected {}.",
+            response.len(),
+            expected_size
+        )));
+    }
+    if response[0] != NAT_PMP_VERSION || response[1] != request_opcode + RESPONSE_OPCODE_OFFSET {
+        return Err(Error::UnexpectedResponse(format!(
+            "Unexpected version ({}) or opcode ({})!",
+            response[0], response[1]
+        )));
+    }
+
+    let result_code = u16::from_be_bytes([response[2], response[3]]);
+    if result_code != 0 {
+        return Err(Error::GatewayError(format!(
+            "NAT-PMP result code: {}",
+            result_code
+        )));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::port_mapping::{natpmp, upnp, Error, PortMapping};
+use aptos_config::config::PortMappingProtocol;
+use aptos_crypto::{x25519, Uniform};
+use rand::{rngs::StdRng, SeedableRng};
+use std::net::{Ipv4Addr, SocketAddrV4};
+
+#[test]
+fn test_natpmp_requests() {
+    // Verify the external address request
+    assert_eq!(natpmp::encode_external_address_request(), vec![0, 0]);
+
+    // Verify the port mapping request
+    let request = natpmp::encode_map_tcp_request(6182, 6183, 7200);
+    assert_eq!(request, vec![
+        0, 2, 0, 0, 0x18, 0x26, 0x18, 0x27, 0, 0, 0x1C, 0x20
+    ]);
+}
+
+#[test]
+fn test_natpmp_responses() {
+    // Verify a valid external address response
+    let response = [0, 128, 0, 0, 0, 0, 0, 1, 203, 0, 113, 7];
+    assert_eq!(
+        natpmp::decode_external_address_response(&response).unwrap(),
+        Ipv4Addr::new(203, 0, 113, 7)
+    );
+
+    // Verify that an error result code is rejected
+    let response = [0, 128, 0, 3, 0, 0, 0, 1, 0, 0, 0, 0];
+    assert!(matches!(
+        natpmp::decode_external_address_response(&response),
+        Err(Error::GatewayError(_))
+    ));
+
+    // Verify that a short response is rejected
+    assert!(matches!(
+        natpmp::decode_external_address_response(&response[..8]),
+        Err(Error::UnexpectedResponse(_))
+    ));
+
+    // Verify a valid port mapping response
+    let response = [
+        0, 130, 0, 0, 0, 0, 0, 1, 0x18, 0x26, 0x18, 0x30, 0, 0, 0x0E, 0x10,
+    ];
+    assert_eq!(
+        natpmp::decode_map_tcp_response(&response, 6182).unwrap(),
+        (6192, 3600)
+    );
+
+    // Verify that a mismatched internal port is rejected
+    assert!(matches!(
+        natpmp::decode_map_tcp_response(&response, 6180),
+        Err(Error::UnexpectedResponse(_))
+    ));
+
+    // Verify that a mismatched opcode is rejected
+    let response = [
+        0, 129, 0, 0, 0, 0, 0, 1, 0x18, 0x26, 0x18, 0x30, 0, 0, 0x0E, 0x10,
+    ];
+    assert!(matches!(
+        natpmp::decode_map_tcp_response(&response, 6182),
+        Err(Error::UnexpectedResponse(_))
+    ));
+}
+
+#[test]
+fn test_parse_default_gateway() {
+    let gateway = u32::from_ne_bytes([192, 168, 1, 1]);
+    let routing_table = format!(
+        "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT\n\
+         eth0\t0001A8C0\t00000000\t0001\t0\t0\t0\t00FFFFFF\t0\t0\t0\n\
+         eth0\t00000000\t{:08X}\t0003\t0\t0\t0\t00000000\t0\t0\t0\n",
+        gateway
+    );
+    assert_eq!(
+        natpmp::parse_default_gateway(&routing_table),
+        Some(Ipv4Addr::new(192, 168, 1, 1))
+    );
+
+    // Verify that no gateway is found without a default route
+    let routing_table = routing_table.lines().take(2).collect::<Vec<_>>().join("\n");
+    assert_eq!(natpmp::parse_default_gateway(&routing_table), None);
+}
+
+#[test]
+fn test_parse_ssdp_location() {
+    let response = "HTTP/1.1 200 OK\r\nCACHE-CONTROL: max-age=120\r\nLocation: http://192.168.1.1:5000/rootDesc.xml\r\nST: urn:schemas-upnp-org:device:InternetGatewayDevice:1\r\n\r\n";
+    assert_eq!(
+        upnp::parse_ssdp_location(response).unwrap(),
+        "http://192.168.1.1:5000/rootDesc.xml"
+    );
+    assert!(upnp::parse_ssdp_location("HTTP/1.1 200 OK\r\n\r\n").is_none());
+}
+
+#[test]
+fn test_parse_http_url() {
+    let (address, path) = upnp::parse_http_url("http://192.168.1.1:5000/rootDesc.xml").unwrap();
+    assert_eq!(address, "192.168.1.1:5000".parse().unwrap());
+    assert_eq!(path, "/rootDesc.xml");
+
+    let (address, path) = upnp::parse_http_url("http://10.0.0.1").unwrap();
+    assert_eq!(address, "10.0.0.1:80".parse().unwrap());
+    assert_eq!(path, "/");
+
+    assert!(upnp::parse_http_url("https://10.0.0.1/desc.xml").is_err());
+    assert!(upnp::parse_http_url("http://router.local/desc.xml").is_err());
+}
+
+#[test]
+fn test_parse_http_response() {
+    let response = "HTTP/1.1 200 OK\r\nContent-Length: 4\r\n\r\nbody";
+    assert_eq!(upnp::parse_http_response(response).unwrap(), "body");
+
+    let response = "HTTP/1.1 500 Internal Server Error\r\n\r\n<errorCode>718</errorCode>";
+    assert!(matches!(
+        upnp::parse_http_response(response),
+        Err(Error::GatewayError(_))
+    ));
+}
+
+#[test]
+fn test_find_wan_connection_service() {
+    let description = "<root><device><serviceList>\
+        <service><serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType>\
+        <controlURL>/ctl/L3F</controlURL></service>\
+        <service><serviceType>urn:schemas-upnp-org:service:WANIPConnection:1</serviceType>\
+        <controlURL>/ctl/IPConn</controlURL></service>\
+        </serviceList></device></root>";
+    assert_eq!(
+        upnp::find_wan_connection_service(description).unwrap(),
+        (
+            "urn:schemas-upnp-org:service:WANIPConnection:1".to_string(),
+            "/ctl/IPConn".to_string()
+        )
+    );
+
+    let description = "<root><service><serviceType>urn:schemas-upnp-org:service:Layer3Forwarding:1</serviceType></service></root>";
+    assert!(upnp::find_wan_connection_service(description).is_none());
+}
+
+#[test]
+fn test_extract_xml_value() {
+    let response = "<s:Body><u:GetExternalIPAddressResponse><NewExternalIPAddress> 203.0.113.7 </NewExternalIPAddress></u:GetExternalIPAddressResponse></s:Body>";
+    assert_eq!(
+        upnp::extract_xml_value(response, "NewExternalIPAddress").unwrap(),
+        "203.0.113.7"
+    );
+    assert!(upnp::extract_xml_value(response, "NewExternalPort").is_none());
+}
+
+#[test]
+fn test_port_mapping_network_address() {
+    let mut rng = StdRng::from_seed([0u8; 32]);
+    let network_public_key = x25519::PrivateKey::generate(&mut rng).public_key();
+    let port_mapping = PortMapping {
+        protocol: PortMappingProtocol::NatPmp,
+        external_address: SocketAddrV4::new(Ipv4Addr::new(203, 0, 113, 7), 6182),
+        lifetime_secs: 7200,
+    };
+
+    let network_address = port_mapping.to_network_address(network_public_key);
+    assert!(network_address.is_aptosnet_addr());
+    assert!(network_address
+        .to_string()
+        .starts_with("/ip4/203.0.113.7/tcp/6182/noise-ik/"));
+    assert_eq!(network_address.find_noise_proto(), Some(network_public_key));
+}
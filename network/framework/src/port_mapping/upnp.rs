@@ -0,0 +1,286 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A minimal UPnP Internet Gateway Device (IGD) client. The gateway is
+//! discovered using SSDP, and ports are mapped using the SOAP actions of
+//! the WAN connection service of the gateway.
+
+use crate::port_mapping::{Error, PortMapping};
+use aptos_config::config::PortMappingProtocol;
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr, SocketAddrV4},
+    time::Duration,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpStream, UdpSocket},
+};
+
+// The SSDP multicast address (and search target) used to discover the gateway
+const SSDP_MULTICAST_ADDRESS: SocketAddrV4 =
+    SocketAddrV4::new(Ipv4Addr::new(239, 255, 255, 250), 1900);
+const SSDP_SEARCH_TARGET: &str = "urn:schemas-upnp-org:device:InternetGatewayDevice:1";
+
+// The WAN connection services that support port mapping
+const WAN_CONNECTION_SERVICE_TYPES: [&str; 3] = [
+    "urn:schemas-upnp-org:service:WANIPConnection:1",
+    "urn:schemas-upnp-org:service:WANIPConnection:2",
+    "urn:schemas-upnp-org:service:WANPPPConnection:1",
+];
+
+// The description of the port mappings created by the node
+const PORT_MAPPING_DESCRIPTION: &str = "aptos-node";
+
+// The maximum size of an SSDP response (in bytes)
+const MAX_SSDP_RESPONSE_SIZE: usize = 2048;
+
+/// Discovers the gateway and maps the given TCP port, and returns
+/// the external address (and port) of the mapping.
+pub(crate) async fn map_port(
+    internal_port: u16,
+    lifetime_secs: u32,
+    request_timeout: Duration,
+) -> Result<PortMapping, Error> {
+    // Discover the gateway and its WAN connection service
+    let location = discover_gateway(request_timeout).await?;
+    let (gateway_address, description_path) = parse_http_url(&location)?;
+    let description = send_http_request(
+        gateway_address,
+        "GET",
+        &description_path,
+        &[],
+        "",
+        request_timeout,
+    )
+    .await?;
+    let (service_type, control_url) =
+        find_wan_connection_service(&description).ok_or_else(|| {
+            Error::UnexpectedResponse("The gateway has no WAN connection service!".into())
+        })?;
+    let control_path = if control_url.starts_with("http://") {
+        parse_http_url(&control_url)?.1
+    } else {
+        control_url
+    };
+
+    // Discover the external address of the gateway
+    let response = send_soap_request(
+        gateway_address,
+        &control_path,
+        &service_type,
+        "GetExternalIPAddress",
+        &[],
+        request_timeout,
+    )
+    .await?;
+    let external_ip = extract_xml_value(&response, "NewExternalIPAddress")
+        .and_then(|external_ip| external_ip.parse::<Ipv4Addr>().ok())
+        .ok_or_else(|| {
+            Error::UnexpectedResponse("The gateway returned no external address!".into())
+        })?;
+
+    // Map the port (to the same external port)
+    let internal_ip = get_local_address(gateway_address).await?;
+    let port = internal_port.to_string();
+    let lease_duration = lifetime_secs.to_string();
+    send_soap_request(
+        gateway_address,
+        &control_path,
+        &service_type,
+        "AddPortMapping",
+        &[
+            ("NewRemoteHost", ""),
+            ("NewExternalPort", &port),
+            ("NewProtocol", "TCP"),
+            ("NewInternalPort", &port),
+            ("NewInternalClient", &internal_ip.to_string()),
+            ("NewEnabled", "1"),
+            ("NewPortMappingDescription", PORT_MAPPING_DESCRIPTION),
+            ("NewLeaseDuration", &lease_duration),
+        ],
+        request_timeout,
+    )
+    .await?;
+
+    Ok(PortMapping {
+        protocol: PortMappingProtocol::Upnp,
+        external_address: SocketAddrV4::new(external_ip, internal_port),
+        lifetime_secs,
+    })
+}
+
+/// Discovers the gateway using SSDP, and returns the location of its description
+async fn discover_gateway(request_timeout: Duration) -> Result<String, Error> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    let search_request = format!(
+        "M-SEARCH * HTTP/1.1\r\nHOST: {}\r\nMAN: \"ssdp:discover\"\r\nMX: 2\r\nST: {}\r\n\r\n",
+        SSDP_MULTICAST_ADDRESS, SSDP_SEARCH_TARGET
+    );
+    socket
+        .send_to(search_request.as_bytes(), SSDP_MULTICAST_ADDRESS)
+        .await?;
+
+    let mut buffer = [0u8; MAX_SSDP_RESPONSE_SIZE];
+    loop {
+        let (num_bytes, _) = tokio::time::timeout(request_timeout, socket.recv_from(&mut buffer))
+            .await
+            .map_err(|_| Error::NoGatewayFound("No response to the SSDP search!".into()))??;
+        let response = String::from_utf8_lossy(&buffer[..num_bytes]);
+        if let Some(location) = parse_ssdp_location(&response) {
+            return Ok(location);
+        }
+    }
+}
+
+/// Returns the local address used to reach the gateway
+async fn get_local_address(gateway_address: SocketAddr) -> Result<IpAddr, Error> {
+    let socket = UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await?;
+    socket.connect(gateway_address).await?;
+    Ok(socket.local_addr()?.ip())
+}
+
+/// Sends a SOAP request for the given action, and returns the response body
+async fn send_soap_request(
+    gateway_address: SocketAddr,
+    control_path: &str,
+    service_type: &str,
+    action: &str,
+    arguments: &[(&str, &str)],
+    request_timeout: Duration,
+) -> Result<String, Error> {
+    let arguments: String = arguments
+        .iter()
+        .map(|(name, value)| format!("<{name}>{value}</{name}>"))
+        .collect();
+    let body = format!(
+        "<?xml version=\"1.0\"?>\r\n\
+         <s:Envelope xmlns:s=\"http://schemas.xmlsoap.org/soap/envelope/\" \
+         s:encodingStyle=\"http://schemas.xmlsoap.org/soap/encoding/\">\
+         <s:Body><u:{action} xmlns:u=\"{service_type}\">{arguments}</u:{action}></s:Body>\
+         </s:Envelope>\r\n"
+    );
+    let headers = [
+        ("Content-Type", "text/xml; charset=\"utf-8\"".to_string()),
+        ("SOAPAction", format!("\"{}#{}\"", service_type, action)),
+    ];
+    send_http_request(
+        gateway_address,
+        "POST",
+        control_path,
+        &headers,
+        &body,
+        request_timeout,
+    )
+    .await
+}
+
+/// Sends an HTTP request to the gateway, and returns the response body
+async fn send_http_request(
+    gateway_address: SocketAddr,
+    method: &str,
+    path: &str,
+    headers: &[(&str, String)],
+    body: &str,
+    request_timeout: Duration,
+) -> Result<String, Error> {
+    let headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}: {}\r\n", name, value))
+        .collect();
+    let request = format!(
+        "{} {} HTTP/1.1\r\nHost: {}\r\nConnection: close\r\nContent-Length: {}\r\n{}\r\n{}",
+        method,
+        path,
+        gateway_address,
+        body.len(),
+        headers,
+        body
+    );
+
+    let response = tokio::time::timeout(request_timeout, async {
+        let mut stream = TcpStream::connect(gateway_address).await?;
+        stream.write_all(request.as_bytes()).await?;
+        let mut response = vec![];
+        stream.read_to_end(&mut response).await?;
+        Ok::<_, Error>(response)
+    })
+    .await
+    .map_err(|_| {
+        Error::Timeout(format!(
+            "HTTP {} request to {}{}",
+            method, gateway_address, path
+        ))
+    })??;
+
+    parse_http_response(&String::from_utf8_lossy(&response))
+}
+
+/// Parses the HTTP response, and returns the body (if the request was successful)
+pub(crate) fn parse_http_response(response: &str) -> Result<String, Error> {
+    let (head, body) = response.split_once("\r\n\r\n").ok_or_else(|| {
+        Error::UnexpectedResponse("The HTTP response has no header terminator!".into())
+    })?;
+    let status_line = head.lines().next().unwrap_or_default();
+    match status_line.split_whitespace().nth(1) {
+        Some("200") => Ok(body.to_string()),
+        _ => Err(Error::GatewayError(format!(
+            "HTTP request failed with status: {}",
+            status_line
+        ))),
+    }
+}
+
+/// Parses the location of the gateway description from the SSDP response
+pub(crate) fn parse_ssdp_location(response: &str) -> Option<String> {
+    response.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("location")
+            .then(|| value.trim().to_string())
+    })
+}
+
+/// Parses the given HTTP URL into the (socket) address of
+/// the host, and the path. Only IP hosts are supported.
+pub(crate) fn parse_http_url(url: &str) -> Result<(SocketAddr, String), Error> {
+    let invalid_url_error = || Error::UnexpectedResponse(format!("Invalid gateway URL: {}", url));
+
+    let url = url.strip_prefix("http://").ok_or_else(invalid_url_error)?;
+    let (authority, path) = match url.find('/') {
+        Some(index) => url.split_at(index),
+        None => (url, "/"),
+    };
+    let address = match authority.parse::<SocketAddr>() {
+        Ok(address) => address,
+        Err(_) => {
+            let ip_address = authority
+                .parse::<IpAddr>()
+                .map_err(|_| invalid_url_error())?;
+            SocketAddr::new(ip_address, 80)
+        },
+    };
+
+    Ok((address, path.to_string()))
+}
+
+/// Finds the first WAN connection service in the gateway description,
+/// and returns the service type and the control URL of the service.
+pub(crate) fn find_wan_connection_service(description: &str) -> Option<(String, String)> {
+    description.split("<service>").skip(1).find_map(|service| {
+        let service_type = extract_xml_value(service, "serviceType")?;
+        if !WAN_CONNECTION_SERVICE_TYPES.contains(&service_type.as_str()) {
+            return None;
+        }
+        let control_url = extract_xml_value(service, "controlURL")?;
+        Some((service_type, control_url))
+    })
+}
+
+/// Extracts the (trimmed) value of the first element with the given tag
+pub(crate) fn extract_xml_value(xml: &str, tag: &str) -> Option<String> {
+    let start_tag = format!("<{}>", tag);
+    let end_tag = format!("</{}>", tag);
+    let start_index = xml.find(&start_tag)? + start_tag.len();
+    let end_index = start_index + xml[start_index..].find(&end_tag)?;
+    Some(xml[start_index..end_index].trim().to_string())
+}
@@ -31,6 +31,7 @@ mod network;
 pub mod peer_states;
 #[cfg(test)]
 mod tests;
+pub mod upstream_selection;
 
 /// A simple container that holds the state of the peer monitor
 #[derive(Clone, Debug, Default)]
@@ -75,6 +76,24 @@ pub async fn start_peer_monitor(
         runtime.clone(),
     );
 
+    // Spawn the upstream peer selector (only PFNs select upstream peers)
+    let upstream_selection_config = node_config.peer_monitoring_service.upstream_selection;
+    let is_vfn = node_config
+        .full_node_networks
+        .iter()
+        .any(|network_config| network_config.network_id.is_vfn_network());
+    if upstream_selection_config.enable_upstream_selection
+        && !node_config.base.role.is_validator()
+        && !is_vfn
+    {
+        upstream_selection::spawn_upstream_peer_selector(
+            upstream_selection_config,
+            peer_monitoring_client.get_peers_and_metadata(),
+            time_service.clone(),
+            runtime.clone(),
+        );
+    }
+
     // Start the peer monitor
     start_peer_monitor_with_state(
         node_config,
@@ -46,6 +46,7 @@ pub enum LogEntry {
     NodeInfoRequest,
     PeerMonitorLoop,
     SendRequest,
+    UpstreamSelectionLoop,
 
     #[cfg(feature = "network-perf-test")] // Disabled by default
     PerformanceMonitoringRequest,
@@ -62,6 +63,8 @@ pub enum LogEvent {
     SendRequest,
     StartedMetadataUpdaterLoop,
     StartedPeerMonitorLoop,
+    StartedUpstreamSelectionLoop,
     TooManyPingFailures,
     UnexpectedErrorEncountered,
+    UpdatedPreferredUpstreamPeers,
 }
@@ -4,6 +4,7 @@
 mod mock;
 mod multiple_peers;
 mod single_peer;
+mod upstream_selection;
 mod utils;
 
 #[cfg(feature = "network-perf-test")] // Disabled by default
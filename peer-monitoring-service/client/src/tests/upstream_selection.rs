@@ -0,0 +1,203 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    tests::utils::{create_network_info_response, create_node_info_response},
+    upstream_selection::UpstreamPeerSelector,
+};
+use aptos_config::{
+    config::{PeerRole, UpstreamSelectionConfig},
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_netcore::transport::ConnectionOrigin;
+use aptos_network::{application::metadata::PeerMetadata, transport::ConnectionMetadata};
+use aptos_peer_monitoring_service_types::PeerMonitoringMetadata;
+use aptos_time_service::TimeService;
+use aptos_types::PeerId;
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::Duration,
+};
+
+// Useful test constants
+const LEDGER_TIMESTAMP_USECS: u64 = 1_000_000_000;
+const MIN_SELECTION_DURATION_MS: u64 = 10_000;
+
+#[test]
+fn test_upstream_selection_ranking() {
+    // Create an upstream peer selector
+    let mut upstream_peer_selector =
+        UpstreamPeerSelector::new(create_upstream_selection_config(3), TimeService::mock());
+
+    // Create several outbound peers with different qualities
+    let peer_far_away = create_peer_network_id();
+    let peer_slow = create_peer_network_id();
+    let peer_fast = create_peer_network_id();
+    let peer_no_latency = create_peer_network_id();
+    let peer_best = create_peer_network_id();
+    let mut connected_peers = hashmap_of_peers(vec![
+        (peer_far_away, create_peer_metadata(true, 3, Some(0.01), 0)),
+        (peer_slow, create_peer_metadata(true, 1, Some(0.5), 0)),
+        (peer_fast, create_peer_metadata(true, 1, Some(0.1), 0)),
+        (peer_no_latency, create_peer_metadata(true, 1, None, 0)),
+        (peer_best, create_peer_metadata(true, 0, Some(0.2), 0)),
+    ]);
+
+    // Create an inbound peer (that should never be selected)
+    let peer_inbound = create_peer_network_id();
+    connected_peers.insert(peer_inbound, create_peer_metadata(false, 0, Some(0.01), 0));
+
+    // Verify the peers are selected by distance, then latency
+    let selected_peers = upstream_peer_selector.rebalance(&connected_peers);
+    assert_eq!(selected_peers, vec![peer_best, peer_fast, peer_slow]);
+    assert_eq!(upstream_peer_selector.get_selected_peers(), selected_peers);
+}
+
+#[test]
+fn test_upstream_selection_ledger_lag() {
+    // Create an upstream peer selector
+    let mut upstream_peer_selector =
+        UpstreamPeerSelector::new(create_upstream_selection_config(2), TimeService::mock());
+
+    // Create several outbound peers that are all synced
+    let peer_1 = create_peer_network_id();
+    let peer_2 = create_peer_network_id();
+    let peer_3 = create_peer_network_id();
+    let mut connected_peers = hashmap_of_peers(vec![
+        (peer_1, create_peer_metadata(true, 1, Some(0.1), 0)),
+        (peer_2, create_peer_metadata(true, 1, Some(0.2), 0)),
+        (peer_3, create_peer_metadata(true, 1, Some(0.3), 0)),
+    ]);
+
+    // Verify the best peers are selected
+    let selected_peers = upstream_peer_selector.rebalance(&connected_peers);
+    assert_eq!(selected_peers, vec![peer_1, peer_2]);
+
+    // Make the first peer fall too far behind (and verify it is replaced immediately)
+    connected_peers.insert(peer_1, create_peer_metadata(true, 1, Some(0.1), 11));
+    let selected_peers = upstream_peer_selector.rebalance(&connected_peers);
+    assert_eq!(selected_peers, vec![peer_2, peer_3]);
+
+    // Disconnect the second peer (and verify it is replaced immediately)
+    connected_peers.remove(&peer_2);
+    let selected_peers = upstream_peer_selector.rebalance(&connected_peers);
+    assert_eq!(selected_peers, vec![peer_3]);
+
+    // Make the first peer catch up again (and verify it fills the empty slot)
+    connected_peers.insert(peer_1, create_peer_metadata(true, 1, Some(0.1), 5));
+    let selected_peers = upstream_peer_selector.rebalance(&connected_peers);
+    assert_eq!(selected_peers, vec![peer_3, peer_1]);
+}
+
+#[test]
+fn test_upstream_selection_stickiness() {
+    // Create an upstream peer selector
+    let time_service = TimeService::mock();
+    let mut upstream_peer_selector =
+        UpstreamPeerSelector::new(create_upstream_selection_config(1), time_service.clone());
+
+    // Create a single outbound peer and verify it is selected
+    let selected_peer = create_peer_network_id();
+    let mut connected_peers = hashmap_of_peers(vec![(
+        selected_peer,
+        create_peer_metadata(true, 2, Some(0.2), 0),
+    )]);
+    let selected_peers = upstream_peer_selector.rebalance(&connected_peers);
+    assert_eq!(selected_peers, vec![selected_peer]);
+
+    // Connect to a significantly better peer and verify the selection is
+    // unchanged (the selected peer hasn't been selected for long enough).
+    let better_peer = create_peer_network_id();
+    connected_peers.insert(better_peer, create_peer_metadata(true, 2, Some(0.1), 0));
+    let selected_peers = upstream_peer_selector.rebalance(&connected_peers);
+    assert_eq!(selected_peers, vec![selected_peer]);
+
+    // Elapse the min selection duration and make the better peer only slightly
+    // better. Verify the selection is unchanged (the improvement is too small).
+    let mock_time = time_service.into_mock();
+    mock_time.advance(Duration::from_millis(MIN_SELECTION_DURATION_MS));
+    connected_peers.insert(better_peer, create_peer_metadata(true, 2, Some(0.19), 0));
+    let selected_peers = upstream_peer_selector.rebalance(&connected_peers);
+    assert_eq!(selected_peers, vec![selected_peer]);
+
+    // Make the better peer significantly better and verify it replaces the selected peer
+    connected_peers.insert(better_peer, create_peer_metadata(true, 2, Some(0.1), 0));
+    let selected_peers = upstream_peer_selector.rebalance(&connected_peers);
+    assert_eq!(selected_peers, vec![better_peer]);
+
+    // Connect to a peer that is closer to the validators (but slower) and verify
+    // the selection is unchanged (the new selection must be sticky).
+    let closer_peer = create_peer_network_id();
+    connected_peers.insert(closer_peer, create_peer_metadata(true, 1, Some(0.5), 0));
+    let selected_peers = upstream_peer_selector.rebalance(&connected_peers);
+    assert_eq!(selected_peers, vec![better_peer]);
+
+    // Elapse the min selection duration and verify the closer peer is selected
+    mock_time.advance(Duration::from_millis(MIN_SELECTION_DURATION_MS));
+    let selected_peers = upstream_peer_selector.rebalance(&connected_peers);
+    assert_eq!(selected_peers, vec![closer_peer]);
+}
+
+/// Creates an upstream selection config with the given number of preferred peers
+fn create_upstream_selection_config(num_preferred_upstream_peers: u64) -> UpstreamSelectionConfig {
+    UpstreamSelectionConfig {
+        max_ledger_lag_secs: 10,
+        min_latency_improvement_percent: 25,
+        min_selection_duration_ms: MIN_SELECTION_DURATION_MS,
+        num_preferred_upstream_peers,
+        ..Default::default()
+    }
+}
+
+/// Creates a new public peer network ID
+fn create_peer_network_id() -> PeerNetworkId {
+    PeerNetworkId::new(NetworkId::Public, PeerId::random())
+}
+
+/// Creates the peer metadata for a peer with the given quality. The
+/// ledger lag specifies how many seconds the peer is behind the ledger.
+fn create_peer_metadata(
+    outbound_connection: bool,
+    distance_from_validators: u64,
+    average_ping_latency_secs: Option<f64>,
+    ledger_lag_secs: u64,
+) -> PeerMetadata {
+    // Create the connection metadata
+    let (peer_role, connection_origin) = if outbound_connection {
+        (PeerRole::Upstream, ConnectionOrigin::Outbound)
+    } else {
+        (PeerRole::Unknown, ConnectionOrigin::Inbound)
+    };
+    let connection_metadata = ConnectionMetadata::mock_with_role_and_origin(
+        PeerId::random(),
+        peer_role,
+        connection_origin,
+    );
+
+    // Create the peer monitoring metadata
+    let network_info_response =
+        create_network_info_response(&BTreeMap::new(), distance_from_validators);
+    let node_info_response = create_node_info_response(
+        BTreeMap::new(),
+        0,
+        0,
+        LEDGER_TIMESTAMP_USECS - (ledger_lag_secs * 1_000_000),
+        0,
+        Duration::from_secs(0),
+    );
+    let peer_monitoring_metadata = PeerMonitoringMetadata::new(
+        average_ping_latency_secs,
+        Some(network_info_response),
+        Some(node_info_response),
+        None,
+    );
+
+    PeerMetadata::new_for_test(connection_metadata, peer_monitoring_metadata)
+}
+
+/// Returns a map of the given peers and metadata
+fn hashmap_of_peers(
+    peers_and_metadata: Vec<(PeerNetworkId, PeerMetadata)>,
+) -> HashMap<PeerNetworkId, PeerMetadata> {
+    peers_and_metadata.into_iter().collect()
+}
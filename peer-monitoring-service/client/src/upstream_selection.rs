@@ -0,0 +1,298 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::logging::{LogEntry, LogEvent, LogSchema};
+use aptos_config::{config::UpstreamSelectionConfig, network_id::PeerNetworkId};
+use aptos_logger::{info, warn};
+use aptos_network::application::{metadata::PeerMetadata, storage::PeersAndMetadata};
+use aptos_peer_monitoring_service_types::MAX_DISTANCE_FROM_VALIDATORS;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use futures::StreamExt;
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::{runtime::Handle, task::JoinHandle};
+
+/// The measured quality of a potential upstream peer
+#[derive(Clone, Debug)]
+struct UpstreamCandidate {
+    peer_network_id: PeerNetworkId,
+    distance_from_validators: u64,
+    average_ping_latency_secs: Option<f64>,
+    ledger_timestamp_usecs: Option<u64>,
+}
+
+impl UpstreamCandidate {
+    fn new(peer_network_id: PeerNetworkId, peer_metadata: &PeerMetadata) -> Self {
+        let peer_monitoring_metadata = peer_metadata.get_peer_monitoring_metadata();
+        let distance_from_validators = peer_monitoring_metadata
+            .latest_network_info_response
+            .map(|response| response.distance_from_validators)
+            .unwrap_or(MAX_DISTANCE_FROM_VALIDATORS);
+        let ledger_timestamp_usecs = peer_monitoring_metadata
+            .latest_node_info_response
+            .map(|response| response.ledger_timestamp_usecs);
+
+        Self {
+            peer_network_id,
+            distance_from_validators,
+            average_ping_latency_secs: peer_monitoring_metadata.average_ping_latency_secs,
+            ledger_timestamp_usecs,
+        }
+    }
+
+    /// Compares the quality of the candidates. Peers closer to the validators
+    /// are better, followed by peers with lower latencies (peers without a
+    /// latency measurement are the worst). Ties are broken by peer ID.
+    fn compare_quality(&self, other: &Self) -> Ordering {
+        self.distance_from_validators
+            .cmp(&other.distance_from_validators)
+            .then_with(|| {
+                match (
+                    self.average_ping_latency_secs,
+                    other.average_ping_latency_secs,
+                ) {
+                    (Some(latency), Some(other_latency)) => latency.total_cmp(&other_latency),
+                    (Some(_), None) => Ordering::Less,
+                    (None, Some(_)) => Ordering::Greater,
+                    (None, None) => Ordering::Equal,
+                }
+            })
+            .then_with(|| self.peer_network_id.cmp(&other.peer_network_id))
+    }
+}
+
+/// Selects the preferred upstream peers of a PFN using the measured peer
+/// quality (i.e., the distance from the validators, the ping latency and
+/// the freshness of the advertised ledger). To avoid churn, selected peers
+/// are only replaced if they fall behind (or disconnect), or if they have
+/// been selected for long enough and a significantly better peer is found.
+pub struct UpstreamPeerSelector {
+    config: UpstreamSelectionConfig,
+    selected_peers: Vec<(PeerNetworkId, Instant)>, // The selected peers (and their selection times)
+    time_service: TimeService,
+}
+
+impl UpstreamPeerSelector {
+    pub fn new(config: UpstreamSelectionConfig, time_service: TimeService) -> Self {
+        Self {
+            config,
+            selected_peers: vec![],
+            time_service,
+        }
+    }
+
+    /// Returns the currently selected peers (ordered by preference)
+    pub fn get_selected_peers(&self) -> Vec<PeerNetworkId> {
+        self.selected_peers
+            .iter()
+            .map(|(peer_network_id, _)| *peer_network_id)
+            .collect()
+    }
+
+    /// Rebalances the selected peers using the given connected peers
+    /// and returns the new selection (ordered by preference).
+    pub fn rebalance(
+        &mut self,
+        connected_peers_and_metadata: &HashMap<PeerNetworkId, PeerMetadata>,
+    ) -> Vec<PeerNetworkId> {
+        // Rank the eligible candidates (from best to worst)
+        let ranked_candidates = self.get_ranked_candidates(connected_peers_and_metadata);
+        let get_candidate = |peer_network_id: &PeerNetworkId| {
+            ranked_candidates
+                .iter()
+                .find(|candidate| &candidate.peer_network_id == peer_network_id)
+        };
+
+        // Unselect any peers that are no longer eligible
+        self.selected_peers
+            .retain(|(peer_network_id, _)| get_candidate(peer_network_id).is_some());
+
+        // Replace any selected peers that are significantly worse than the
+        // best unselected candidate (if they have been selected long enough).
+        let now = self.time_service.now();
+        let min_selection_duration = Duration::from_millis(self.config.min_selection_duration_ms);
+        for index in 0..self.selected_peers.len() {
+            let (selected_peer, selection_time) = self.selected_peers[index];
+            if now.duration_since(selection_time) < min_selection_duration {
+                continue; // The peer has not been selected for long enough
+            }
+
+            let best_unselected_candidate = ranked_candidates
+                .iter()
+                .find(|candidate| !self.is_selected(&candidate.peer_network_id));
+            if let (Some(best_unselected_candidate), Some(selected_candidate)) =
+                (best_unselected_candidate, get_candidate(&selected_peer))
+            {
+                if self.is_significantly_better(best_unselected_candidate, selected_candidate) {
+                    self.selected_peers[index] = (best_unselected_candidate.peer_network_id, now);
+                }
+            }
+        }
+
+        // Fill any empty slots with the best unselected candidates
+        let num_preferred_peers = self.config.num_preferred_upstream_peers as usize;
+        self.selected_peers.truncate(num_preferred_peers);
+        for candidate in ranked_candidates.iter() {
+            if self.selected_peers.len() >= num_preferred_peers {
+                break;
+            }
+            if !self.is_selected(&candidate.peer_network_id) {
+                self.selected_peers.push((candidate.peer_network_id, now));
+            }
+        }
+
+        self.get_selected_peers()
+    }
+
+    /// Returns the eligible upstream candidates, ordered from best to worst.
+    /// Only outbound connections are candidates, and candidates must not lag
+    /// too far behind the freshest candidate. If the ledger timestamps of the
+    /// candidates are unknown, all candidates are eligible.
+    fn get_ranked_candidates(
+        &self,
+        connected_peers_and_metadata: &HashMap<PeerNetworkId, PeerMetadata>,
+    ) -> Vec<UpstreamCandidate> {
+        // Gather all outbound connections
+        let candidates: Vec<_> = connected_peers_and_metadata
+            .iter()
+            .filter(|(_, peer_metadata)| {
+                peer_metadata
+                    .get_connection_metadata()
+                    .is_outbound_connection()
+            })
+            .map(|(peer_network_id, peer_metadata)| {
+                UpstreamCandidate::new(*peer_network_id, peer_metadata)
+            })
+            .collect();
+
+        // Remove the candidates that lag too far behind the freshest candidate
+        let mut ranked_candidates = match candidates
+            .iter()
+            .filter_map(|candidate| candidate.ledger_timestamp_usecs)
+            .max()
+        {
+            Some(freshest_timestamp_usecs) => {
+                let max_ledger_lag_usecs = self.config.max_ledger_lag_secs * 1_000_000;
+                candidates
+                    .into_iter()
+                    .filter(|candidate| {
+                        candidate
+                            .ledger_timestamp_usecs
+                            .map(|timestamp_usecs| {
+                                freshest_timestamp_usecs.saturating_sub(timestamp_usecs)
+                                    <= max_ledger_lag_usecs
+                            })
+                            .unwrap_or(false)
+                    })
+                    .collect()
+            },
+            None => candidates, // No ledger timestamps are known yet
+        };
+
+        // Rank the candidates by quality
+        ranked_candidates.sort_by(|candidate, other| candidate.compare_quality(other));
+        ranked_candidates
+    }
+
+    /// Returns true iff the given peer is currently selected
+    fn is_selected(&self, peer_network_id: &PeerNetworkId) -> bool {
+        self.selected_peers
+            .iter()
+            .any(|(selected_peer, _)| selected_peer == peer_network_id)
+    }
+
+    /// Returns true iff the candidate is significantly better than the selected
+    /// peer, i.e., it is closer to the validators, or it is equally close and
+    /// its latency is lower by at least the configured improvement percentage.
+    fn is_significantly_better(
+        &self,
+        candidate: &UpstreamCandidate,
+        selected_candidate: &UpstreamCandidate,
+    ) -> bool {
+        match candidate
+            .distance_from_validators
+            .cmp(&selected_candidate.distance_from_validators)
+        {
+            Ordering::Less => true,
+            Ordering::Greater => false,
+            Ordering::Equal => match (
+                candidate.average_ping_latency_secs,
+                selected_candidate.average_ping_latency_secs,
+            ) {
+                (Some(latency), Some(selected_latency)) => {
+                    let improvement_percent =
+                        self.config.min_latency_improvement_percent.min(100) as f64;
+                    latency <= selected_latency * (100.0 - improvement_percent) / 100.0
+                },
+                (Some(_), None) => true,
+                (None, _) => false,
+            },
+        }
+    }
+}
+
+/// Spawns a task that periodically rebalances the preferred upstream
+/// peers of the node (and stores them in the peers and metadata struct).
+pub(crate) fn spawn_upstream_peer_selector(
+    upstream_selection_config: UpstreamSelectionConfig,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+    time_service: TimeService,
+    runtime: Option<Handle>,
+) -> JoinHandle<()> {
+    // Create the upstream peer selector task
+    let upstream_peer_selector = async move {
+        // Create an interval ticker for the rebalance loop
+        let rebalance_loop_duration =
+            Duration::from_millis(upstream_selection_config.rebalance_interval_ms);
+        let rebalance_loop_ticker = time_service.interval(rebalance_loop_duration);
+        futures::pin_mut!(rebalance_loop_ticker);
+
+        // Start the rebalance loop
+        info!(LogSchema::new(LogEntry::UpstreamSelectionLoop)
+            .event(LogEvent::StartedUpstreamSelectionLoop)
+            .message("Starting the upstream peer selector!"));
+        let mut upstream_peer_selector =
+            UpstreamPeerSelector::new(upstream_selection_config, time_service.clone());
+        loop {
+            // Wait for the next round before rebalancing the peers
+            rebalance_loop_ticker.next().await;
+
+            // Get all connected peers
+            let connected_peers_and_metadata =
+                match peers_and_metadata.get_connected_peers_and_metadata() {
+                    Ok(connected_peers_and_metadata) => connected_peers_and_metadata,
+                    Err(error) => {
+                        warn!(LogSchema::new(LogEntry::UpstreamSelectionLoop)
+                            .event(LogEvent::UnexpectedErrorEncountered)
+                            .error(&error.into())
+                            .message("Failed to get connected peers and metadata!"));
+                        continue; // Move to the next loop iteration
+                    },
+                };
+
+            // Rebalance the preferred upstream peers
+            let preferred_upstream_peers =
+                upstream_peer_selector.rebalance(&connected_peers_and_metadata);
+            if preferred_upstream_peers != peers_and_metadata.get_preferred_upstream_peers() {
+                info!(LogSchema::new(LogEntry::UpstreamSelectionLoop)
+                    .event(LogEvent::UpdatedPreferredUpstreamPeers)
+                    .message(&format!(
+                        "Updated the preferred upstream peers: {:?}",
+                        preferred_upstream_peers
+                    )));
+                peers_and_metadata.set_preferred_upstream_peers(preferred_upstream_peers);
+            }
+        }
+    };
+
+    // Spawn the upstream peer selector task
+    if let Some(runtime) = runtime {
+        runtime.spawn(upstream_peer_selector)
+    } else {
+        tokio::spawn(upstream_peer_selector)
+    }
+}
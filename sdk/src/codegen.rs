@@ -0,0 +1,615 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Generates typed Rust builders for the entry and view functions of on-chain
+//! Move modules, using the module ABIs fetched from a node. The generated
+//! builders handle the BCS encoding of arguments and the type arguments, so
+//! integrators don't need to hand-encode `TransactionPayload`s.
+//!
+//! The utility is build-script friendly. For example, in `build.rs`:
+//!
+//! ```ignore
+//! let client = Client::new(node_url);
+//! let modules = runtime.block_on(fetch_module_abis(&client, address, &["my_module"]))?;
+//! let output_path = Path::new(&env::var("OUT_DIR")?).join("my_module.rs");
+//! write_module_builders(&modules, &CodegenOptions::default(), output_path)?;
+//! ```
+//!
+//! And the generated builders can then be included with
+//! `include!(concat!(env!("OUT_DIR"), "/my_module.rs"));`.
+
+use crate::{move_types::account_address::AccountAddress, rest_client::Client};
+use anyhow::{anyhow, bail, Context, Result};
+use aptos_api_types::{MoveFunction, MoveModule, MoveStructTag, MoveType};
+use std::{fmt::Write, fs, path::Path};
+
+/// Rust keywords that must be escaped when used as identifiers
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in", "let", "loop",
+    "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+    "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+    "where", "while", "yield",
+];
+
+/// Rust keywords that cannot be used as raw identifiers
+const RESERVED_RUST_KEYWORDS: &[&str] = &["crate", "self", "super", "Self"];
+
+/// Options for generating the module builders
+#[derive(Clone, Debug)]
+pub struct CodegenOptions {
+    /// The path of the SDK crate used by the generated code (e.g.,
+    /// `aptos_sdk` or `crate` if the code is generated inside the SDK).
+    pub sdk_crate_path: String,
+    /// Whether or not to generate builders for view functions
+    pub generate_view_functions: bool,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self {
+            sdk_crate_path: "aptos_sdk".into(),
+            generate_view_functions: true,
+        }
+    }
+}
+
+/// Fetches the ABIs of the specified modules published at the given address.
+/// If no module names are specified, the ABIs of all modules are fetched.
+pub async fn fetch_module_abis(
+    client: &Client,
+    address: AccountAddress,
+    module_names: &[&str],
+) -> Result<Vec<MoveModule>> {
+    // Fetch the module bytecode from the node
+    let modules = if module_names.is_empty() {
+        client
+            .get_account_modules(address)
+            .await
+            .with_context(|| format!("Failed to fetch the modules at {}", address))?
+            .into_inner()
+    } else {
+        let mut modules = vec![];
+        for module_name in module_names {
+            let module = client
+                .get_account_module(address, module_name)
+                .await
+                .with_context(|| {
+                    format!("Failed to fetch the module {}::{}", address, module_name)
+                })?
+                .into_inner();
+            modules.push(module);
+        }
+        modules
+    };
+
+    // Parse the ABIs from the bytecode
+    let mut module_abis = vec![];
+    for module in modules {
+        let module_abi = module
+            .try_parse_abi()?
+            .abi
+            .ok_or_else(|| anyhow!("Failed to parse the module ABI at {}", address))?;
+        module_abis.push(module_abi);
+    }
+
+    // Sort the modules by name (so that the generated code is deterministic)
+    module_abis.sort_by_key(|module_abi| module_abi.name.to_string());
+    Ok(module_abis)
+}
+
+/// Generates the module builders and writes them to the given output path.
+/// The file is only written if the contents changed (to avoid unnecessary
+/// rebuilds when this is called from a build script).
+pub fn write_module_builders(
+    modules: &[MoveModule],
+    options: &CodegenOptions,
+    output_path: impl AsRef<Path>,
+) -> Result<()> {
+    let output_path = output_path.as_ref();
+    let generated_code = generate_module_builders(modules, options)?;
+    if fs::read_to_string(output_path).ok().as_deref() == Some(generated_code.as_str()) {
+        return Ok(());
+    }
+
+    fs::write(output_path, generated_code)
+        .with_context(|| format!("Failed to write the module builders to {:?}", output_path))
+}
+
+/// Generates the Rust source code for the builders of the given modules.
+/// Each module is generated as a Rust module (of the same name), with an
+/// `entry` submodule holding the entry function builders and a `view`
+/// submodule holding the view function builders.
+pub fn generate_module_builders(
+    modules: &[MoveModule],
+    options: &CodegenOptions,
+) -> Result<String> {
+    let mut output = String::new();
+    writeln!(
+        output,
+        "// This file was generated from on-chain module ABIs. Do not edit it manually."
+    )?;
+    for module in modules {
+        writeln!(output)?;
+        generate_module(&mut output, module, options)?;
+    }
+    Ok(output)
+}
+
+/// Generates the Rust module for the given Move module
+fn generate_module(
+    output: &mut String,
+    module: &MoveModule,
+    options: &CodegenOptions,
+) -> Result<()> {
+    let sdk = &options.sdk_crate_path;
+    let module_address = module.address.inner().to_hex_literal();
+    let module_name = module.name.as_str();
+
+    // Generate the module header, shared imports and module identifier
+    write!(
+        output,
+        r#"/// Builders for the module `{module_address}::{module_name}`.
+pub mod {rust_module_name} {{
+    #![allow(clippy::too_many_arguments, dead_code, unused_imports)]
+
+    use {sdk}::{{
+        bcs,
+        move_types::{{
+            account_address::AccountAddress,
+            identifier::Identifier,
+            language_storage::{{ModuleId, TypeTag}},
+            u256::U256,
+        }},
+        rest_client::aptos_api_types::ViewFunction,
+        types::transaction::{{EntryFunction, TransactionPayload}},
+    }};
+
+    /// Returns the identifier of the module
+    pub fn module_id() -> ModuleId {{
+        ModuleId::new(
+            AccountAddress::from_hex_literal("{module_address}").unwrap(),
+            Identifier::new("{module_name}").unwrap(),
+        )
+    }}
+"#,
+        module_address = module_address,
+        module_name = module_name,
+        rust_module_name = to_rust_identifier(module_name),
+        sdk = sdk,
+    )?;
+
+    // Generate the entry function builders
+    writeln!(output)?;
+    writeln!(
+        output,
+        "    /// Builders for the entry functions of the module"
+    )?;
+    writeln!(output, "    pub mod entry {{")?;
+    writeln!(output, "        use super::*;")?;
+    for function in module.exposed_functions.iter().filter(|f| f.is_entry) {
+        writeln!(output)?;
+        generate_entry_function(output, &module_address, module_name, function)?;
+    }
+    writeln!(output, "    }}")?;
+
+    // Generate the view function builders
+    if options.generate_view_functions {
+        writeln!(output)?;
+        writeln!(
+            output,
+            "    /// Builders for the view functions of the module"
+        )?;
+        writeln!(output, "    pub mod view {{")?;
+        writeln!(output, "        use super::*;")?;
+        for function in module.exposed_functions.iter().filter(|f| f.is_view) {
+            writeln!(output)?;
+            generate_view_function(output, &module_address, module_name, function)?;
+        }
+        writeln!(output, "    }}")?;
+    }
+
+    writeln!(output, "}}")?;
+    Ok(())
+}
+
+/// Generates the builder for the given entry function. The builder
+/// returns the `TransactionPayload` that calls the function.
+fn generate_entry_function(
+    output: &mut String,
+    module_address: &str,
+    module_name: &str,
+    function: &MoveFunction,
+) -> Result<()> {
+    let function_name = function.name.as_str();
+    let signature = match FunctionSignature::new(function) {
+        Ok(signature) => signature,
+        Err(error) => {
+            return write_skipped_function(output, module_address, module_name, function, error);
+        },
+    };
+
+    writeln!(
+        output,
+        "        /// Builds the payload for the entry function `{}::{}::{}`.",
+        module_address, module_name, function_name
+    )?;
+    writeln!(
+        output,
+        "        pub fn {}({}) -> TransactionPayload {{",
+        to_rust_identifier(function_name),
+        signature.get_parameters()
+    )?;
+    writeln!(
+        output,
+        "            TransactionPayload::EntryFunction(EntryFunction::new("
+    )?;
+    signature.write_call_body(output, function_name, "                ")?;
+    writeln!(output, "            ))")?;
+    writeln!(output, "        }}")?;
+    Ok(())
+}
+
+/// Generates the builder for the given view function. The builder returns
+/// the `ViewFunction` that can be sent to the node (e.g., using `view_bcs`).
+fn generate_view_function(
+    output: &mut String,
+    module_address: &str,
+    module_name: &str,
+    function: &MoveFunction,
+) -> Result<()> {
+    let function_name = function.name.as_str();
+    let signature = match FunctionSignature::new(function) {
+        Ok(signature) => signature,
+        Err(error) => {
+            return write_skipped_function(output, module_address, module_name, function, error);
+        },
+    };
+
+    let return_types = function
+        .return_
+        .iter()
+        .map(|return_type| format!("`{}`", return_type))
+        .collect::<Vec<_>>();
+    writeln!(
+        output,
+        "        /// Builds the request for the view function `{}::{}::{}`.",
+        module_address, module_name, function_name
+    )?;
+    if !return_types.is_empty() {
+        writeln!(output, "        ///")?;
+        writeln!(
+            output,
+            "        /// The function returns: {}.",
+            return_types.join(", ")
+        )?;
+    }
+    writeln!(
+        output,
+        "        pub fn {}({}) -> ViewFunction {{",
+        to_rust_identifier(function_name),
+        signature.get_parameters()
+    )?;
+    writeln!(output, "            ViewFunction {{")?;
+    writeln!(output, "                module: module_id(),")?;
+    writeln!(
+        output,
+        "                function: Identifier::new(\"{}\").unwrap(),",
+        function_name
+    )?;
+    writeln!(
+        output,
+        "                ty_args: vec![{}],",
+        signature.type_arguments.join(", ")
+    )?;
+    writeln!(
+        output,
+        "                args: vec![{}],",
+        signature.get_encoded_arguments()
+    )?;
+    writeln!(output, "            }}")?;
+    writeln!(output, "        }}")?;
+    Ok(())
+}
+
+/// Writes a comment noting that a builder couldn't be generated for the
+/// function (e.g., because it takes arguments that can't be encoded).
+fn write_skipped_function(
+    output: &mut String,
+    module_address: &str,
+    module_name: &str,
+    function: &MoveFunction,
+    error: anyhow::Error,
+) -> Result<()> {
+    writeln!(
+        output,
+        "        // Skipped `{}::{}::{}`: {}",
+        module_address,
+        module_name,
+        function.name.as_str(),
+        error
+    )?;
+    Ok(())
+}
+
+/// The Rust signature of a generated builder
+struct FunctionSignature {
+    type_arguments: Vec<String>,      // The names of the type arguments
+    arguments: Vec<(String, String)>, // The names and Rust types of the arguments
+}
+
+impl FunctionSignature {
+    fn new(function: &MoveFunction) -> Result<Self> {
+        // Name the type arguments (the ABI doesn't include their names)
+        let type_arguments = (0..function.generic_type_params.len())
+            .map(|index| format!("type_arg_{}", index))
+            .collect();
+
+        // Identify the arguments (ignoring any signer arguments, which
+        // are provided by the transaction sender).
+        let mut arguments = vec![];
+        for param in &function.params {
+            if is_signer(param) {
+                continue;
+            }
+            let argument_name = format!("arg_{}", arguments.len());
+            arguments.push((argument_name, to_rust_type(param)?));
+        }
+
+        Ok(Self {
+            type_arguments,
+            arguments,
+        })
+    }
+
+    /// Returns the parameters of the builder
+    fn get_parameters(&self) -> String {
+        let type_parameters = self
+            .type_arguments
+            .iter()
+            .map(|type_argument| format!("{}: TypeTag", type_argument));
+        let parameters = self
+            .arguments
+            .iter()
+            .map(|(argument, rust_type)| format!("{}: {}", argument, rust_type));
+        type_parameters
+            .chain(parameters)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Returns the BCS encoded arguments
+    fn get_encoded_arguments(&self) -> String {
+        self.arguments
+            .iter()
+            .map(|(argument, _)| format!("bcs::to_bytes(&{}).unwrap()", argument))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Writes the arguments of the `EntryFunction::new()` call
+    fn write_call_body(
+        &self,
+        output: &mut String,
+        function_name: &str,
+        indent: &str,
+    ) -> Result<()> {
+        writeln!(output, "{}module_id(),", indent)?;
+        writeln!(
+            output,
+            "{}Identifier::new(\"{}\").unwrap(),",
+            indent, function_name
+        )?;
+        writeln!(
+            output,
+            "{}vec![{}],",
+            indent,
+            self.type_arguments.join(", ")
+        )?;
+        writeln!(output, "{}vec![{}],", indent, self.get_encoded_arguments())?;
+        Ok(())
+    }
+}
+
+/// Returns true iff the given type is a signer (or a reference to one)
+fn is_signer(move_type: &MoveType) -> bool {
+    match move_type {
+        MoveType::Signer => true,
+        MoveType::Reference { to, .. } => is_signer(to),
+        _ => false,
+    }
+}
+
+/// Returns the Rust type used to pass the given Move type as an argument.
+/// The BCS encoding of the Rust type matches that of the Move type.
+fn to_rust_type(move_type: &MoveType) -> Result<String> {
+    let rust_type = match move_type {
+        MoveType::Bool => "bool".into(),
+        MoveType::U8 => "u8".into(),
+        MoveType::U16 => "u16".into(),
+        MoveType::U32 => "u32".into(),
+        MoveType::U64 => "u64".into(),
+        MoveType::U128 => "u128".into(),
+        MoveType::U256 => "U256".into(),
+        MoveType::Address => "AccountAddress".into(),
+        MoveType::Vector { items } => format!("Vec<{}>", to_rust_type(items)?),
+        MoveType::Struct(struct_tag) => struct_to_rust_type(struct_tag)?,
+        MoveType::Signer
+        | MoveType::GenericTypeParam { .. }
+        | MoveType::Reference { .. }
+        | MoveType::Unparsable(_) => {
+            bail!("Unsupported argument type: {}", move_type)
+        },
+    };
+    Ok(rust_type)
+}
+
+/// Returns the Rust type used to pass the given struct as an argument.
+/// Only the structs that can be passed to entry functions are supported.
+fn struct_to_rust_type(struct_tag: &MoveStructTag) -> Result<String> {
+    if struct_tag.address.inner() != &AccountAddress::ONE {
+        bail!("Unsupported argument type: {}", struct_tag);
+    }
+
+    let rust_type = match (struct_tag.module.as_str(), struct_tag.name.as_str()) {
+        ("string", "String") => "String".into(),
+        ("object", "Object") => "AccountAddress".into(), // Objects are encoded as addresses
+        ("option", "Option") => {
+            let inner_type = struct_tag
+                .generic_type_params
+                .first()
+                .ok_or_else(|| anyhow!("Missing the type parameter for: {}", struct_tag))?;
+            format!("Option<{}>", to_rust_type(inner_type)?)
+        },
+        _ => bail!("Unsupported argument type: {}", struct_tag),
+    };
+    Ok(rust_type)
+}
+
+/// Converts the given Move identifier into a valid Rust identifier
+fn to_rust_identifier(identifier: &str) -> String {
+    if RESERVED_RUST_KEYWORDS.contains(&identifier) {
+        format!("{}_", identifier)
+    } else if RUST_KEYWORDS.contains(&identifier) {
+        format!("r#{}", identifier)
+    } else {
+        identifier.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::move_types::identifier::Identifier;
+    use aptos_api_types::{MoveFunctionGenericTypeParam, MoveFunctionVisibility};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_generate_entry_function() {
+        // Create a module with an entry function
+        let function = create_function("transfer", true, false, 1, vec![
+            MoveType::Reference {
+                mutable: false,
+                to: Box::new(MoveType::Signer),
+            },
+            MoveType::Address,
+            MoveType::U64,
+        ]);
+        let module = create_module("coin", vec![function]);
+
+        // Generate the builders and verify the entry function
+        let generated_code =
+            generate_module_builders(&[module], &CodegenOptions::default()).unwrap();
+        assert!(generated_code.contains("pub mod coin {"));
+        assert!(generated_code.contains(
+            "pub fn transfer(type_arg_0: TypeTag, arg_0: AccountAddress, arg_1: u64) -> TransactionPayload"
+        ));
+        assert!(generated_code.contains("Identifier::new(\"transfer\").unwrap(),"));
+        assert!(generated_code
+            .contains("vec![bcs::to_bytes(&arg_0).unwrap(), bcs::to_bytes(&arg_1).unwrap()],"));
+    }
+
+    #[test]
+    fn test_generate_view_function() {
+        // Create a module with a view function
+        let function = create_function("balance", false, true, 0, vec![MoveType::Address]);
+        let module = create_module("coin", vec![function]);
+
+        // Generate the builders and verify the view function
+        let generated_code =
+            generate_module_builders(&[module.clone()], &CodegenOptions::default()).unwrap();
+        assert!(generated_code.contains("pub fn balance(arg_0: AccountAddress) -> ViewFunction"));
+        assert!(generated_code.contains("/// The function returns: `u64`."));
+
+        // Verify view functions are skipped if disabled
+        let options = CodegenOptions {
+            generate_view_functions: false,
+            ..Default::default()
+        };
+        let generated_code = generate_module_builders(&[module], &options).unwrap();
+        assert!(!generated_code.contains("pub fn balance"));
+    }
+
+    #[test]
+    fn test_unsupported_arguments() {
+        // Create a module with an entry function that takes an unsupported struct
+        let struct_type = MoveType::from_str("0x1::account::Account").unwrap();
+        let function = create_function("unsupported", true, false, 0, vec![struct_type]);
+        let module = create_module("account", vec![function]);
+
+        // Verify the function is skipped
+        let generated_code =
+            generate_module_builders(&[module], &CodegenOptions::default()).unwrap();
+        assert!(generated_code.contains("// Skipped `0x1::account::unsupported`"));
+        assert!(!generated_code.contains("pub fn unsupported"));
+    }
+
+    #[test]
+    fn test_to_rust_type() {
+        // Verify the supported types
+        for (move_type, expected_rust_type) in [
+            ("vector<u8>", "Vec<u8>"),
+            ("0x1::string::String", "String"),
+            ("0x1::option::Option<u64>", "Option<u64>"),
+            (
+                "0x1::object::Object<0x1::fungible_asset::Metadata>",
+                "AccountAddress",
+            ),
+            (
+                "vector<0x1::option::Option<address>>",
+                "Vec<Option<AccountAddress>>",
+            ),
+        ] {
+            let move_type = MoveType::from_str(move_type).unwrap();
+            assert_eq!(to_rust_type(&move_type).unwrap(), expected_rust_type);
+        }
+
+        // Verify the unsupported types
+        for move_type in ["signer", "0x1::coin::Coin<u8>", "0x2::string::String"] {
+            let move_type = MoveType::from_str(move_type).unwrap();
+            assert!(to_rust_type(&move_type).is_err());
+        }
+    }
+
+    #[test]
+    fn test_to_rust_identifier() {
+        assert_eq!(to_rust_identifier("transfer"), "transfer");
+        assert_eq!(to_rust_identifier("type"), "r#type");
+        assert_eq!(to_rust_identifier("self"), "self_");
+    }
+
+    /// Creates a function with the specified properties
+    fn create_function(
+        name: &str,
+        is_entry: bool,
+        is_view: bool,
+        num_type_params: usize,
+        params: Vec<MoveType>,
+    ) -> MoveFunction {
+        MoveFunction {
+            name: Identifier::new(name).unwrap().into(),
+            visibility: MoveFunctionVisibility::Public,
+            is_entry,
+            is_view,
+            generic_type_params: vec![
+                MoveFunctionGenericTypeParam {
+                    constraints: vec![]
+                };
+                num_type_params
+            ],
+            params,
+            return_: if is_view { vec![MoveType::U64] } else { vec![] },
+        }
+    }
+
+    /// Creates a module (at 0x1) with the specified functions
+    fn create_module(name: &str, exposed_functions: Vec<MoveFunction>) -> MoveModule {
+        MoveModule {
+            address: AccountAddress::ONE.into(),
+            name: Identifier::new(name).unwrap().into(),
+            friends: vec![],
+            exposed_functions,
+            structs: vec![],
+        }
+    }
+}
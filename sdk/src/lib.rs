@@ -8,6 +8,7 @@
 //!
 //! This SDK provides all the necessary components for building on top of the Aptos Blockchain. Some of the important modules are:
 //!
+//! * `codegen` - Generates typed transaction builders from on-chain module ABIs
 //! * `crypto` - Types used for signing and verifying
 //! * `move_types` - Includes types used when interacting with the Move VM
 //! * `rest_client` - The Aptos API Client, used for sending requests to the Aptos Blockchain.
@@ -22,6 +23,8 @@
 
 pub use bcs;
 
+pub mod codegen;
+
 pub mod coin_client;
 
 pub mod crypto {
@@ -20,7 +20,7 @@ use crate::{
     utils,
 };
 use aptos_config::{
-    config::{AptosDataClientConfig, BaseConfig},
+    config::{AptosDataClientConfig, BaseConfig, TrustedPeerOnlyConfig},
     network_id::PeerNetworkId,
 };
 use aptos_id_generator::{IdGenerator, U64IdGenerator};
@@ -33,21 +33,27 @@ use aptos_network::{
 use aptos_storage_interface::DbReader;
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_storage_service_types::{
+    bandwidth::BandwidthBudget,
     requests::{
-        DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
-        NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
+        DataRequest, EpochEndingLedgerInfoRequest, EventsByKeyWithProofRequest,
+        NewTransactionOutputsWithProofRequest, NewTransactionsOrOutputsWithProofRequest,
+        NewTransactionsWithProofRequest, StateValueChangesWithProofRequest,
         StateValuesWithProofRequest, StorageServiceRequest,
         SubscribeTransactionOutputsWithProofRequest,
         SubscribeTransactionsOrOutputsWithProofRequest, SubscribeTransactionsWithProofRequest,
         SubscriptionStreamMetadata, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
-    responses::{StorageServerSummary, StorageServiceResponse, TransactionOrOutputListWithProof},
+    responses::{
+        DataWindows, EventsByKeyWithProof, StateValueChangesWithProof, StorageServerSummary,
+        StorageServiceResponse, TransactionOrOutputListWithProof,
+    },
     Epoch, StorageServiceMessage,
 };
-use aptos_time_service::TimeService;
+use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{
     epoch_change::EpochChangeProof,
+    event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     state_store::state_value::StateValueChunkWithProof,
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
@@ -58,11 +64,11 @@ use futures::{stream::FuturesUnordered, StreamExt};
 use maplit::hashset;
 use std::{
     cmp::min,
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt,
     ops::Deref,
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 use tokio::runtime::Handle;
 
@@ -89,6 +95,8 @@ const PEER_METRICS_FREQ_SECS: u64 = 5; // The frequency to update peer metrics a
 /// and/or threads.
 #[derive(Clone, Debug)]
 pub struct AptosDataClient {
+    /// The bandwidth budget shared with the local storage service.
+    bandwidth_budget: Arc<BandwidthBudget>,
     /// The base config of the node.
     base_config: Arc<BaseConfig>,
     /// The config for the AptosNet data client.
@@ -97,6 +105,8 @@ pub struct AptosDataClient {
     storage_service_client: StorageServiceClient<NetworkClient<StorageServiceMessage>>,
     /// The state of the active subscription stream.
     active_subscription_state: Arc<Mutex<Option<SubscriptionState>>>,
+    /// The peers excluded from servicing requests (and the exclusion expiry times).
+    excluded_peers: Arc<Mutex<HashMap<PeerNetworkId, Instant>>>,
     /// All of the data-client specific data we have on each network peer.
     peer_states: Arc<PeerStates>,
     /// A cached, aggregate data summary of all unbanned peers' data summaries.
@@ -105,6 +115,8 @@ pub struct AptosDataClient {
     response_id_generator: Arc<U64IdGenerator>,
     /// Time service used for calculating peer lag
     time_service: TimeService,
+    /// The config for the trusted-peer-only mode (i.e., the peer allowlist).
+    trusted_peer_only_config: Arc<TrustedPeerOnlyConfig>,
 }
 
 impl AptosDataClient {
@@ -115,6 +127,8 @@ impl AptosDataClient {
         storage: Arc<dyn DbReader>,
         storage_service_client: StorageServiceClient<NetworkClient<StorageServiceMessage>>,
         runtime: Option<Handle>,
+        bandwidth_budget: Arc<BandwidthBudget>,
+        trusted_peer_only_config: TrustedPeerOnlyConfig,
     ) -> (Self, DataSummaryPoller) {
         // Wrap the configs in an Arc (to be shared across components)
         let base_config = Arc::new(base_config);
@@ -122,14 +136,17 @@ impl AptosDataClient {
 
         // Create the data client
         let data_client = Self {
+            bandwidth_budget,
             base_config,
             data_client_config: data_client_config.clone(),
             storage_service_client: storage_service_client.clone(),
             active_subscription_state: Arc::new(Mutex::new(None)),
+            excluded_peers: Arc::new(Mutex::new(HashMap::new())),
             peer_states: Arc::new(PeerStates::new(data_client_config.clone())),
             global_summary_cache: Arc::new(ArcSwap::from(Arc::new(GlobalDataSummary::empty()))),
             response_id_generator: Arc::new(U64IdGenerator::new()),
             time_service: time_service.clone(),
+            trusted_peer_only_config: Arc::new(trusted_peer_only_config),
         };
 
         // Create the data summary poller
@@ -155,12 +172,20 @@ impl AptosDataClient {
         self.storage_service_client.get_peers_and_metadata()
     }
 
+    /// Returns the bandwidth budget (e.g., to cap syncing at runtime)
+    pub fn get_bandwidth_budget(&self) -> Arc<BandwidthBudget> {
+        self.bandwidth_budget.clone()
+    }
+
     /// Updates the metrics and logs for peer states. This includes
     /// peer priorities and request distributions.
     pub fn update_peer_metrics_and_logs(&self) {
         // Update the peer request logs and metrics
         self.peer_states.update_peer_request_logs_and_metrics();
 
+        // Update the bandwidth usage metrics
+        self.update_bandwidth_metrics();
+
         // Update the peer priority metrics and logs (infrequently)
         sample!(
             SampleRate::Duration(Duration::from_secs(PEER_METRICS_FREQ_SECS)),
@@ -200,7 +225,28 @@ impl AptosDataClient {
 
     /// Update a peer's storage summary
     pub fn update_peer_storage_summary(&self, peer: PeerNetworkId, summary: StorageServerSummary) {
-        self.peer_states.update_summary(peer, summary)
+        self.peer_states
+            .update_summary(peer, summary, self.time_service.clone())
+    }
+
+    /// Returns true iff the data windows have been polled for the peer
+    pub fn has_peer_data_windows(&self, peer: &PeerNetworkId) -> bool {
+        self.peer_states.has_data_windows(peer)
+    }
+
+    /// Updates the data windows for the peer
+    pub fn update_peer_data_windows(&self, peer: PeerNetworkId, data_windows: DataWindows) {
+        self.peer_states.update_data_windows(peer, data_windows)
+    }
+
+    /// Returns true iff the compression codec has been negotiated with the peer
+    pub fn is_compression_negotiated(&self, peer: &PeerNetworkId) -> bool {
+        self.peer_states.is_compression_negotiated(peer)
+    }
+
+    /// Marks the compression codec as negotiated with the peer
+    pub fn mark_compression_negotiated(&self, peer: PeerNetworkId) {
+        self.peer_states.mark_compression_negotiated(peer)
     }
 
     /// Recompute and update the global data summary cache
@@ -210,7 +256,9 @@ impl AptosDataClient {
         self.garbage_collect_peer_states()?;
 
         // Calculate the global data summary
-        let global_data_summary = self.peer_states.calculate_global_data_summary();
+        let global_data_summary = self
+            .peer_states
+            .calculate_global_data_summary(self.time_service.clone());
 
         // Update the cached data summary
         self.global_summary_cache
@@ -255,18 +303,31 @@ impl AptosDataClient {
         &self,
         request: &StorageServiceRequest,
     ) -> crate::error::Result<HashSet<PeerNetworkId>, Error> {
-        // Get all peers grouped by priorities
-        let peers_by_priorities = self.get_peers_by_priorities()?;
+        // Identify the peers that can service the request (ordered by priority).
+        // If the trusted-peer-only mode is enabled, the priorities are defined
+        // by the order of the trusted peers (i.e., one trusted peer per priority).
+        let trusted_peer_only = self.trusted_peer_only_config.enable;
+        let serviceable_peers_by_priorities = if trusted_peer_only {
+            self.identify_serviceable_trusted_peers(request)?
+        } else {
+            // Get all peers grouped by priorities
+            let peers_by_priorities = self.get_peers_by_priorities()?;
 
-        // Identify the peers that can service the request (ordered by priority)
-        let mut serviceable_peers_by_priorities = vec![];
-        for priority in PeerPriority::get_all_ordered_priorities() {
-            // Identify the serviceable peers for the priority
-            let peers = self.identify_serviceable(&peers_by_priorities, priority, request);
+            // Identify the serviceable peers for each priority
+            let mut serviceable_peers_by_priorities = vec![];
+            for priority in PeerPriority::get_all_ordered_priorities() {
+                // Identify the serviceable peers for the priority
+                let peers = self.identify_serviceable(&peers_by_priorities, priority, request);
 
-            // Add the serviceable peers to the ordered list
-            serviceable_peers_by_priorities.push(peers);
-        }
+                // Add the serviceable peers to the ordered list
+                serviceable_peers_by_priorities.push(peers);
+            }
+            serviceable_peers_by_priorities
+        };
+
+        // Remove any excluded peers from the serviceable peers
+        let serviceable_peers_by_priorities =
+            self.remove_excluded_peers(serviceable_peers_by_priorities);
 
         // If the request is a subscription request, select a single
         // peer (as we can only subscribe to a single peer at a time).
@@ -283,7 +344,9 @@ impl AptosDataClient {
             for (index, peers) in serviceable_peers_by_priorities.iter().enumerate() {
                 // Only include the lowest priority peers if no other peers are
                 // available (the lowest priority peers are generally unreliable).
-                if (num_serviceable_peers == 0)
+                // Note: this doesn't apply to trusted peers (which are all reliable).
+                if trusted_peer_only
+                    || (num_serviceable_peers == 0)
                     || (index < serviceable_peers_by_priorities.len() - 1)
                 {
                     num_serviceable_peers += peers.len();
@@ -332,6 +395,45 @@ impl AptosDataClient {
         }
     }
 
+    /// Removes all excluded peers from the given serviceable peers. If this
+    /// would leave no serviceable peers, the exclusions are ignored (it is
+    /// better to use an excluded peer than to fail the request entirely).
+    fn remove_excluded_peers(
+        &self,
+        serviceable_peers_by_priorities: Vec<HashSet<PeerNetworkId>>,
+    ) -> Vec<HashSet<PeerNetworkId>> {
+        // Garbage collect any expired exclusions
+        let mut excluded_peers = self.excluded_peers.lock();
+        let time_now = self.time_service.now();
+        excluded_peers.retain(|_, expiry_time| *expiry_time > time_now);
+        if excluded_peers.is_empty() {
+            return serviceable_peers_by_priorities;
+        }
+
+        // Remove the excluded peers
+        let filtered_peers_by_priorities: Vec<HashSet<PeerNetworkId>> =
+            serviceable_peers_by_priorities
+                .iter()
+                .map(|peers| {
+                    peers
+                        .iter()
+                        .filter(|peer| !excluded_peers.contains_key(peer))
+                        .cloned()
+                        .collect()
+                })
+                .collect();
+
+        // Only use the filtered peers if some peers remain
+        if filtered_peers_by_priorities
+            .iter()
+            .all(|peers| peers.is_empty())
+        {
+            serviceable_peers_by_priorities
+        } else {
+            filtered_peers_by_priorities
+        }
+    }
+
     /// Chooses several peers to service the given optimistic fetch
     /// request. Peers are selected first by priority, and then by
     /// validator distance and latency (within priority groups).
@@ -383,9 +485,13 @@ impl AptosDataClient {
         // Select peers by priority (starting with the highest priority first)
         let mut selected_peers = HashSet::new();
         for serviceable_peers in serviceable_peers_by_priorities {
-            // Select peers by distance and latency
+            // Select peers by throughput (for large output chunks) or latency
             let num_peers_remaining = num_peers_for_request.saturating_sub(selected_peers.len());
-            let peers = self.choose_random_peers_by_latency(serviceable_peers, num_peers_remaining);
+            let peers = if self.use_throughput_for_peer_selection(request) {
+                self.choose_random_peers_by_throughput(serviceable_peers, num_peers_remaining)
+            } else {
+                self.choose_random_peers_by_latency(serviceable_peers, num_peers_remaining)
+            };
 
             // Add the peers to the entire set
             selected_peers.extend(peers);
@@ -454,6 +560,9 @@ impl AptosDataClient {
             DataRequest::SubscribeTransactionsOrOutputsWithProof(request) => {
                 request.subscription_stream_metadata.subscription_stream_id
             },
+            DataRequest::SubscribeFilteredTransactionOutputsWithProof(request) => {
+                request.subscription_stream_metadata.subscription_stream_id
+            },
             data_request => {
                 return Err(Error::UnexpectedErrorEncountered(format!(
                     "Invalid subscription request type found: {:?}",
@@ -522,6 +631,44 @@ impl AptosDataClient {
         utils::extend_with_random_peers(selected_peers, serviceable_peers, num_peers_to_choose)
     }
 
+    /// Chooses several peers weighted by their measured throughput. If no
+    /// throughput measurements exist for the peers, latency is used instead.
+    fn choose_random_peers_by_throughput(
+        &self,
+        serviceable_peers: HashSet<PeerNetworkId>,
+        num_peers_to_choose: usize,
+    ) -> HashSet<PeerNetworkId> {
+        // Choose peers weighted by throughput
+        let selected_peers = match utils::choose_peers_by_throughput(
+            self.data_client_config.clone(),
+            num_peers_to_choose as u64,
+            serviceable_peers.clone(),
+            self.peer_states.clone(),
+        ) {
+            Some(selected_peers) => selected_peers,
+            None => {
+                return self.choose_random_peers_by_latency(serviceable_peers, num_peers_to_choose)
+            },
+        };
+
+        // Extend the selected peers with random peers (if necessary)
+        utils::extend_with_random_peers(selected_peers, serviceable_peers, num_peers_to_choose)
+    }
+
+    /// Returns true iff peers should be selected for the given request using
+    /// their measured throughput. This is only done for transaction output
+    /// requests, as these chunks are large and dominated by transfer time.
+    fn use_throughput_for_peer_selection(&self, request: &StorageServiceRequest) -> bool {
+        self.data_client_config
+            .throughput_scoring_config
+            .enable_throughput_scoring
+            && matches!(
+                request.data_request,
+                DataRequest::GetTransactionOutputsWithProof(_)
+                    | DataRequest::GetTransactionsOrOutputsWithProof(_)
+            )
+    }
+
     /// Identifies the peers with the specified priority that can service the given request
     fn identify_serviceable(
         &self,
@@ -545,9 +692,49 @@ impl AptosDataClient {
             .collect()
     }
 
-    /// Returns all peers connected to us
+    /// Identifies the trusted peers that can service the given request. The
+    /// peers are grouped by trusted peer, and ordered by the trusted peer order
+    /// (a trusted peer may be connected to us on several networks).
+    fn identify_serviceable_trusted_peers(
+        &self,
+        request: &StorageServiceRequest,
+    ) -> crate::error::Result<Vec<HashSet<PeerNetworkId>>, Error> {
+        // Get all connected (trusted) peers
+        let all_connected_peers = self.get_all_connected_peers()?;
+
+        // Identify the serviceable peers for each trusted peer (in order)
+        let serviceable_peers_by_trusted_peers = self
+            .trusted_peer_only_config
+            .trusted_peers
+            .iter()
+            .map(|trusted_peer| {
+                all_connected_peers
+                    .iter()
+                    .filter(|peer| peer.peer_id() == *trusted_peer)
+                    .filter(|peer| {
+                        self.peer_states.can_service_request(
+                            peer,
+                            self.time_service.clone(),
+                            request,
+                        )
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .collect();
+
+        Ok(serviceable_peers_by_trusted_peers)
+    }
+
+    /// Returns all peers connected to us. If the trusted-peer-only
+    /// mode is enabled, only the connected trusted peers are returned.
     fn get_all_connected_peers(&self) -> crate::error::Result<HashSet<PeerNetworkId>, Error> {
-        let connected_peers = self.storage_service_client.get_available_peers()?;
+        let mut connected_peers = self.storage_service_client.get_available_peers()?;
+        if self.trusted_peer_only_config.enable {
+            let trusted_peers = &self.trusted_peer_only_config.trusted_peers;
+            connected_peers.retain(|peer| trusted_peers.contains(&peer.peer_id()));
+        }
+
         if connected_peers.is_empty() {
             return Err(Error::NoConnectedPeers(
                 "No available peers found!".to_owned(),
@@ -610,6 +797,55 @@ impl AptosDataClient {
         Ok((priority_peers, regular_peers))
     }
 
+    /// Waits for the bandwidth budget to allow the given request to be
+    /// sent. This ensures that syncing does not starve the storage service
+    /// when the node is serving many downstream peers.
+    async fn wait_for_bandwidth_budget(&self, request: &StorageServiceRequest) {
+        if let Some(throttle_delay) = self.bandwidth_budget.get_syncing_throttle_delay() {
+            // Update the throttled request metrics
+            metrics::THROTTLED_REQUESTS
+                .with_label_values(&[&request.get_label()])
+                .inc();
+
+            // Log the throttling event (infrequently)
+            sample!(
+                SampleRate::Duration(Duration::from_secs(PEER_METRICS_FREQ_SECS)),
+                info!(
+                    (LogSchema::new(LogEntry::BandwidthBudget)
+                        .request_type(&request.get_label())
+                        .message(&format!(
+                            "Throttling request due to the bandwidth budget. Delay: {:?}",
+                            throttle_delay
+                        )))
+                );
+            );
+
+            // Wait for the throttle delay to elapse
+            self.time_service.sleep(throttle_delay).await;
+        }
+    }
+
+    /// Updates the bandwidth usage metrics
+    fn update_bandwidth_metrics(&self) {
+        set_gauge(
+            &metrics::BANDWIDTH_USAGE,
+            metrics::SERVED_BYTES_PER_SECOND_LABEL,
+            self.bandwidth_budget.get_served_bytes_per_second(),
+        );
+        set_gauge(
+            &metrics::BANDWIDTH_USAGE,
+            metrics::SYNCED_BYTES_PER_SECOND_LABEL,
+            self.bandwidth_budget.get_synced_bytes_per_second(),
+        );
+        set_gauge(
+            &metrics::BANDWIDTH_USAGE,
+            metrics::SYNCING_LIMIT_BYTES_PER_SECOND_LABEL,
+            self.bandwidth_budget
+                .get_syncing_bytes_per_second_limit()
+                .unwrap_or(0), // A limit of 0 means syncing is unlimited
+        );
+    }
+
     /// Sends the specified storage request to a number of peers
     /// in the network and decodes the first successful response.
     async fn send_request_and_decode<T, E>(
@@ -632,6 +868,9 @@ impl AptosDataClient {
             )));
         }
 
+        // Wait for the bandwidth budget before sending the request
+        self.wait_for_bandwidth_budget(&request).await;
+
         // Update the metrics for the number of selected peers (for the request)
         metrics::observe_value_with_label(
             &metrics::MULTI_FETCHES_PER_REQUEST,
@@ -768,6 +1007,7 @@ impl AptosDataClient {
         self.update_sent_request_metrics(peer, &request);
 
         // Send the request and process the result
+        let request_start_time = self.time_service.now();
         let result = self
             .storage_service_client
             .send_request(
@@ -789,6 +1029,31 @@ impl AptosDataClient {
                 // Update the received response metrics
                 self.update_received_response_metrics(peer, &request);
 
+                // Record the synced bytes against the bandwidth budget (if
+                // syncing is limited) and update the peer's measured throughput
+                // (if throughput scoring is enabled).
+                let record_synced_bytes = self
+                    .bandwidth_budget
+                    .get_syncing_bytes_per_second_limit()
+                    .is_some();
+                let update_throughput = self
+                    .data_client_config
+                    .throughput_scoring_config
+                    .enable_throughput_scoring;
+                if record_synced_bytes || update_throughput {
+                    if let Ok(num_bytes) = response.get_num_bytes() {
+                        if record_synced_bytes {
+                            self.bandwidth_budget.record_synced_bytes(num_bytes);
+                        }
+                        if update_throughput {
+                            let response_duration =
+                                self.time_service.now().duration_since(request_start_time);
+                            self.peer_states
+                                .update_throughput(peer, num_bytes, response_duration);
+                        }
+                    }
+                }
+
                 // For now, record all responses that at least pass the data
                 // client layer successfully. An alternative might also have the
                 // consumer notify both success and failure via the callback.
@@ -913,6 +1178,21 @@ impl AptosDataClientInterface for AptosDataClient {
         self.global_summary_cache.load().clone().deref().clone()
     }
 
+    fn exclude_peer(&self, peer: PeerNetworkId, exclusion_duration: Duration) {
+        info!(
+            (LogSchema::new(LogEntry::PeerStates)
+                .event(LogEvent::PeerExcluded)
+                .peer(&peer)
+                .message(&format!(
+                    "Excluding the peer from servicing requests for: {:?}",
+                    exclusion_duration
+                )))
+        );
+
+        let expiry_time = self.time_service.now() + exclusion_duration;
+        self.excluded_peers.lock().insert(peer, expiry_time);
+    }
+
     async fn get_epoch_ending_ledger_infos(
         &self,
         start_epoch: Epoch,
@@ -929,6 +1209,24 @@ impl AptosDataClientInterface for AptosDataClient {
         Ok(response.map(|epoch_change| epoch_change.ledger_info_with_sigs))
     }
 
+    async fn get_events_by_key_with_proof(
+        &self,
+        event_key: EventKey,
+        start_sequence_number: u64,
+        end_sequence_number: u64,
+        proof_version: Version,
+        request_timeout_ms: u64,
+    ) -> crate::error::Result<Response<EventsByKeyWithProof>> {
+        let data_request = DataRequest::GetEventsByKeyWithProof(EventsByKeyWithProofRequest {
+            event_key,
+            start_sequence_number,
+            end_sequence_number,
+            proof_version,
+        });
+        self.create_and_send_storage_request(request_timeout_ms, data_request)
+            .await
+    }
+
     async fn get_new_transaction_outputs_with_proof(
         &self,
         known_version: Version,
@@ -1008,6 +1306,23 @@ impl AptosDataClientInterface for AptosDataClient {
             .await
     }
 
+    async fn get_state_value_changes_with_proof(
+        &self,
+        proof_version: Version,
+        start_version: Version,
+        end_version: Version,
+        request_timeout_ms: u64,
+    ) -> crate::error::Result<Response<StateValueChangesWithProof>> {
+        let data_request =
+            DataRequest::GetStateValueChangesWithProof(StateValueChangesWithProofRequest {
+                proof_version,
+                start_version,
+                end_version,
+            });
+        self.create_and_send_storage_request(request_timeout_ms, data_request)
+            .await
+    }
+
     async fn get_transaction_outputs_with_proof(
         &self,
         proof_version: Version,
@@ -1144,6 +1459,10 @@ impl ResponseCallback for AptosNetResponseCallback {
         self.data_client
             .notify_bad_response(self.id, self.peer, &self.request, error_type);
     }
+
+    fn get_peer(&self) -> Option<PeerNetworkId> {
+        Some(self.peer)
+    }
 }
 
 impl fmt::Debug for AptosNetResponseCallback {
@@ -2,15 +2,25 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{error, error::Error, global_summary::GlobalDataSummary};
-use aptos_storage_service_types::{responses::TransactionOrOutputListWithProof, Epoch};
+use aptos_config::network_id::PeerNetworkId;
+use aptos_storage_service_types::{
+    responses::{
+        EventsByKeyWithProof, StateValueChangesWithProof, TransactionOrOutputListWithProof,
+    },
+    Epoch,
+};
 use aptos_types::{
+    event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     state_store::state_value::StateValueChunkWithProof,
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
-use std::{fmt, time::Instant};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
 
 /// The API offered by the Aptos Data Client.
 #[async_trait]
@@ -21,6 +31,12 @@ pub trait AptosDataClientInterface {
     /// cached view of this data client's available data.
     fn get_global_data_summary(&self) -> GlobalDataSummary;
 
+    /// Excludes the given peer from servicing requests for the specified
+    /// duration (e.g., because the peer has degraded and the caller is
+    /// failing over to other peers). If no other peers can service a
+    /// request, the excluded peer may still be selected.
+    fn exclude_peer(&self, _peer: PeerNetworkId, _exclusion_duration: Duration) {}
+
     /// Fetches the epoch ending ledger infos between start and end
     /// (inclusive). In some cases, fewer ledger infos may be returned (e.g.,
     /// to tolerate network or chunk limits). If the data cannot be fetched,
@@ -32,6 +48,21 @@ pub trait AptosDataClientInterface {
         request_timeout_ms: u64,
     ) -> error::Result<Response<Vec<LedgerInfoWithSignatures>>>;
 
+    /// Fetches the events emitted for the given event key, from the start to
+    /// the end sequence numbers (inclusive). Each event is returned with all
+    /// events emitted by the same transaction and a proof of the transaction
+    /// info relative to the specified `proof_version`. In some cases, fewer
+    /// events may be returned (e.g., to tolerate network or chunk limits). If
+    /// the data cannot be fetched, an error is returned.
+    async fn get_events_by_key_with_proof(
+        &self,
+        event_key: EventKey,
+        start_sequence_number: u64,
+        end_sequence_number: u64,
+        proof_version: Version,
+        request_timeout_ms: u64,
+    ) -> error::Result<Response<EventsByKeyWithProof>>;
+
     /// Fetches a new transaction output list with proof. Versions start at
     /// `known_version + 1` and `known_epoch` (inclusive). The end version
     /// and proof version are specified by the server. If the data cannot be
@@ -87,6 +118,21 @@ pub trait AptosDataClientInterface {
         request_timeout_ms: u64,
     ) -> error::Result<Response<StateValueChunkWithProof>>;
 
+    /// Fetches the state values that changed between the start version
+    /// (exclusive) and the end version (inclusive), each with a proof at
+    /// the end version. The transaction info at the end version is proven
+    /// relative to the specified `proof_version`. This allows nodes that are
+    /// only slightly behind to incrementally update their state snapshot. The
+    /// response is never truncated: if the changes cannot be served in a
+    /// single response, an error is returned.
+    async fn get_state_value_changes_with_proof(
+        &self,
+        proof_version: Version,
+        start_version: Version,
+        end_version: Version,
+        request_timeout_ms: u64,
+    ) -> error::Result<Response<StateValueChangesWithProof>>;
+
     /// Fetches a transaction output list with proof, with transaction
     /// outputs from start to end versions (inclusive). The proof is relative
     /// to the specified `proof_version`. In some cases, fewer outputs may be
@@ -202,6 +248,11 @@ pub trait ResponseCallback: fmt::Debug + Send + Sync + 'static {
     // consume the callback, which better communicates that you should only report
     // an error once. however, the current state-sync-v2 code makes this difficult...
     fn notify_bad_response(&self, error: ResponseError);
+
+    /// Returns the peer that served the response (if known)
+    fn get_peer(&self) -> Option<PeerNetworkId> {
+        None
+    }
 }
 
 /// A unique identifier for each response
@@ -264,6 +315,7 @@ impl<T> Response<T> {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum ResponsePayload {
     EpochEndingLedgerInfos(Vec<LedgerInfoWithSignatures>),
+    EventsByKeyWithProof(EventsByKeyWithProof),
     NewTransactionOutputsWithProof((TransactionOutputListWithProof, LedgerInfoWithSignatures)),
     NewTransactionsWithProof((TransactionListWithProof, LedgerInfoWithSignatures)),
     NumberOfStates(u64),
@@ -278,6 +330,7 @@ impl ResponsePayload {
     pub fn get_label(&self) -> &'static str {
         match self {
             Self::EpochEndingLedgerInfos(_) => "epoch_ending_ledger_infos",
+            Self::EventsByKeyWithProof(_) => "events_by_key_with_proof",
             Self::NewTransactionOutputsWithProof(_) => "new_transaction_outputs_with_proof",
             Self::NewTransactionsWithProof(_) => "new_transactions_with_proof",
             Self::NumberOfStates(_) => "number_of_states",
@@ -294,6 +347,9 @@ impl ResponsePayload {
             Self::EpochEndingLedgerInfos(epoch_ending_ledger_infos) => {
                 epoch_ending_ledger_infos.len()
             },
+            Self::EventsByKeyWithProof(events_by_key_with_proof) => {
+                events_by_key_with_proof.get_num_events()
+            },
             Self::NewTransactionOutputsWithProof((outputs_with_proof, _)) => {
                 outputs_with_proof.transactions_and_outputs.len()
             },
@@ -328,6 +384,12 @@ impl From<Vec<LedgerInfoWithSignatures>> for ResponsePayload {
     }
 }
 
+impl From<EventsByKeyWithProof> for ResponsePayload {
+    fn from(inner: EventsByKeyWithProof) -> Self {
+        Self::EventsByKeyWithProof(inner)
+    }
+}
+
 impl From<(TransactionOutputListWithProof, LedgerInfoWithSignatures)> for ResponsePayload {
     fn from(inner: (TransactionOutputListWithProof, LedgerInfoWithSignatures)) -> Self {
         Self::NewTransactionOutputsWithProof(inner)
@@ -40,7 +40,10 @@ impl<'a> LogSchema<'a> {
 #[derive(Clone, Copy, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LogEntry {
+    BandwidthBudget,
+    CompressionNegotiation,
     DataSummaryPoller,
+    DataWindows,
     LatencyMonitor,
     PeerStates,
     StorageServiceRequest,
@@ -53,7 +56,12 @@ pub enum LogEntry {
 pub enum LogEvent {
     AggregateSummary,
     CaughtUpToLatest,
+    CompressionNegotiationError,
+    CompressionNegotiationSuccess,
+    DataWindowsPollingError,
+    DataWindowsPollingSuccess,
     NoPeersToPoll,
+    PeerExcluded,
     PeerIgnored,
     PeerNoLongerIgnored,
     PeerPollingError,
@@ -64,6 +72,7 @@ pub enum LogEvent {
     ResponseError,
     ResponseSuccess,
     SendRequest,
+    StaleStorageSummary,
     StorageReadFailed,
     UnexpectedError,
     WaitingForCatchup,
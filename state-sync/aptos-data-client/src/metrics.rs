@@ -15,6 +15,9 @@ pub const PROPOSE_TO_SEEN_LATENCY_LABEL: &str = "propose_to_seen_latency";
 pub const PROPOSE_TO_SYNC_LATENCY_LABEL: &str = "propose_to_sync_latency";
 pub const REGULAR_PEER: &str = "regular_peer";
 pub const SEEN_TO_SYNC_LATENCY_LABEL: &str = "seen_to_sync_latency";
+pub const SERVED_BYTES_PER_SECOND_LABEL: &str = "served_bytes_per_second";
+pub const SYNCED_BYTES_PER_SECOND_LABEL: &str = "synced_bytes_per_second";
+pub const SYNCING_LIMIT_BYTES_PER_SECOND_LABEL: &str = "syncing_limit_bytes_per_second";
 pub const TOTAL_COUNT_LABEL: &str = "TOTAL_COUNT";
 
 // TOOD(joshlind): add peer priorities back to the requests
@@ -179,6 +182,62 @@ pub static RECEIVED_RESPONSES_BY_PEER_BUCKET: Lazy<IntGaugeVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Gauge for tracking the bandwidth usage (bytes per second) of state sync
+pub static BANDWIDTH_USAGE: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_data_client_bandwidth_usage",
+        "Gauge related to the bandwidth usage (bytes per second) of state sync",
+        &["label"]
+    )
+    .unwrap()
+});
+
+/// Counter for tracking the number of throttled data client requests
+pub static THROTTLED_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_data_client_throttled_requests",
+        "Counters related to requests throttled by the bandwidth budget",
+        &["request_type"]
+    )
+    .unwrap()
+});
+
+/// Counter for tracking the number of storage summaries rejected or ignored
+/// because they are stale (e.g., their timestamps regressed or expired).
+pub static STALE_STORAGE_SUMMARIES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_data_client_stale_storage_summaries",
+        "Counters related to stale storage summaries advertised by peers",
+        &["reason"]
+    )
+    .unwrap()
+});
+
+// Buckets for tracking peer response throughputs (bytes per second)
+const THROUGHPUT_BUCKETS_BYTES_PER_SEC: &[f64] = &[
+    16_384.0,
+    65_536.0,
+    262_144.0,
+    1_048_576.0,
+    2_097_152.0,
+    4_194_304.0,
+    8_388_608.0,
+    16_777_216.0,
+    33_554_432.0,
+    67_108_864.0,
+    134_217_728.0,
+];
+
+/// Counter for tracking the measured response throughputs of peers
+pub static PEER_THROUGHPUT_BYTES_PER_SEC: Lazy<HistogramVec> = Lazy::new(|| {
+    let histogram_opts = histogram_opts!(
+        "aptos_data_client_peer_throughput_bytes_per_sec",
+        "Counters related to the measured response throughputs of peers",
+        THROUGHPUT_BUCKETS_BYTES_PER_SEC.to_vec()
+    );
+    register_histogram_vec!(histogram_opts, &["network"]).unwrap()
+});
+
 /// An enum representing the various types of data that can be
 /// fetched via the data client.
 pub enum DataType {
@@ -10,15 +10,16 @@ use crate::{
 use aptos_config::{config::AptosDataClientConfig, network_id::PeerNetworkId};
 use aptos_logger::prelude::*;
 use aptos_storage_service_types::{
-    requests::StorageServiceRequest, responses::StorageServerSummary,
+    requests::StorageServiceRequest,
+    responses::{DataWindows, StorageServerSummary},
 };
-use aptos_time_service::TimeService;
+use aptos_time_service::{TimeService, TimeServiceTrait};
 use dashmap::DashMap;
 use std::{
     cmp::min,
     collections::{BTreeMap, HashSet},
     sync::Arc,
-    time::Duration,
+    time::{Duration, Instant},
 };
 
 // Useful constants
@@ -26,6 +27,10 @@ const LOGS_FREQUENCY_SECS: u64 = 120; // 2 minutes
 const METRICS_FREQUENCY_SECS: u64 = 15; // 15 seconds
 const NUM_PEER_BUCKETS_FOR_METRICS: u8 = 4; // To avoid metric explosion, we bucket peers into groups
 
+// Useful labels for the stale storage summary metrics
+const EXPIRED_SUMMARY_LABEL: &str = "expired";
+const REGRESSED_SUMMARY_LABEL: &str = "regressed";
+
 /// Scores for peer rankings based on preferences and behavior.
 const MAX_SCORE: f64 = 100.0;
 const MIN_SCORE: f64 = 0.0;
@@ -38,6 +43,8 @@ const NOT_USEFUL_MULTIPLIER: f64 = 0.95;
 const MALICIOUS_MULTIPLIER: f64 = 0.8;
 /// Ignore a peer when their score dips below this threshold.
 const IGNORE_PEER_THRESHOLD: f64 = 25.0;
+/// The minimum response duration used when measuring throughput.
+const MIN_RESPONSE_DURATION_SECS: f64 = 0.001; // 1 ms
 
 pub enum ErrorType {
     /// A response or error that's not actively malicious but also doesn't help
@@ -61,6 +68,12 @@ impl From<ResponseError> for ErrorType {
 
 #[derive(Clone, Debug)]
 pub struct PeerState {
+    /// Whether or not the compression codec has been negotiated with this
+    /// peer (on the current connection).
+    compression_negotiated: bool,
+    /// The data windows advertised by this peer, or `None` if we haven't
+    /// polled them yet (only pruned peers are polled for their windows).
+    data_windows: Option<DataWindows>,
     /// The number of responses received from this peer (by data request label)
     received_responses_by_type: Arc<DashMap<String, u64>>,
     /// The number of requests sent to this peer (by data request label)
@@ -68,17 +81,27 @@ pub struct PeerState {
     /// The latest observed advertised data for this peer, or `None` if we
     /// haven't polled them yet.
     storage_summary: Option<StorageServerSummary>,
+    /// The time at which the storage summary was last updated, or `None`
+    /// if we haven't received a summary yet.
+    storage_summary_update_time: Option<Instant>,
     /// For now, a simplified port of the original state-sync v1 scoring system.
     score: f64,
+    /// The moving average (EWMA) throughput of the peer's responses (in
+    /// bytes per second), or `None` if it hasn't been measured yet.
+    throughput_bytes_per_sec: Option<f64>,
 }
 
 impl Default for PeerState {
     fn default() -> Self {
         Self {
+            compression_negotiated: false,
+            data_windows: None,
             received_responses_by_type: Arc::new(DashMap::new()),
             sent_requests_by_type: Arc::new(DashMap::new()),
             storage_summary: None,
+            storage_summary_update_time: None,
             score: STARTING_SCORE,
+            throughput_bytes_per_sec: None,
         }
     }
 }
@@ -100,11 +123,26 @@ impl PeerState {
             .or_insert(1);
     }
 
+    /// Returns true iff the compression codec has been negotiated with the peer
+    pub fn is_compression_negotiated(&self) -> bool {
+        self.compression_negotiated
+    }
+
+    /// Returns the data windows advertised by the peer
+    pub fn get_data_windows(&self) -> Option<DataWindows> {
+        self.data_windows
+    }
+
     /// Returns the peer's score
     pub fn get_score(&self) -> f64 {
         self.score
     }
 
+    /// Returns the peer's measured throughput (in bytes per second)
+    pub fn get_throughput_bytes_per_sec(&self) -> Option<f64> {
+        self.throughput_bytes_per_sec
+    }
+
     /// Returns the storage summary for the peer
     pub fn get_storage_summary(&self) -> Option<StorageServerSummary> {
         self.storage_summary.clone()
@@ -138,6 +176,23 @@ impl PeerState {
         }
     }
 
+    /// Returns the storage summary iff the peer is not below the ignore
+    /// threshold and the summary is not older than the given max age.
+    fn get_fresh_storage_summary_if_not_ignored(
+        &self,
+        time_now: Instant,
+        max_storage_summary_age: Duration,
+    ) -> Option<&StorageServerSummary> {
+        let storage_summary = self.get_storage_summary_if_not_ignored()?;
+        let storage_summary_update_time = self.storage_summary_update_time?;
+        if time_now.saturating_duration_since(storage_summary_update_time) > max_storage_summary_age
+        {
+            None // The summary has expired
+        } else {
+            Some(storage_summary)
+        }
+    }
+
     /// Updates the score of the peer according to a successful operation
     fn update_score_success(&mut self) {
         self.score = f64::min(self.score + SUCCESSFUL_RESPONSE_DELTA, MAX_SCORE);
@@ -152,9 +207,74 @@ impl PeerState {
         self.score = f64::max(self.score * multiplier, MIN_SCORE);
     }
 
-    /// Updates the storage summary for the peer
-    fn update_storage_summary(&mut self, storage_summary: StorageServerSummary) {
+    /// Updates the measured throughput of the peer using the given
+    /// throughput sample and new sample weight (between 0 and 1).
+    fn update_throughput(&mut self, sample_bytes_per_sec: f64, new_sample_weight: f64) {
+        let throughput_bytes_per_sec = match self.throughput_bytes_per_sec {
+            Some(throughput_bytes_per_sec) => {
+                (new_sample_weight * sample_bytes_per_sec)
+                    + ((1.0 - new_sample_weight) * throughput_bytes_per_sec)
+            },
+            None => sample_bytes_per_sec, // This is the first sample
+        };
+        self.throughput_bytes_per_sec = Some(throughput_bytes_per_sec);
+    }
+
+    /// Updates the data windows for the peer
+    fn update_data_windows(&mut self, data_windows: DataWindows) {
+        self.data_windows = Some(data_windows);
+    }
+
+    /// Returns the timestamp (in microseconds) of the synced ledger info
+    /// advertised by the peer, or `None` if the peer hasn't advertised one.
+    fn get_synced_ledger_info_timestamp_usecs(&self) -> Option<u64> {
+        self.storage_summary
+            .as_ref()
+            .and_then(|storage_summary| storage_summary.data_summary.synced_ledger_info.as_ref())
+            .map(|synced_ledger_info| synced_ledger_info.ledger_info().timestamp_usecs())
+    }
+
+    /// Updates the storage summary for the peer. If the peer has advertised
+    /// its data windows, the lowest versions of the pruned data ranges are
+    /// raised by the safety margin (to avoid requesting data that is likely
+    /// to be pruned before the requests are served).
+    ///
+    /// Note: the synced ledger info is signed by the validators, so its
+    /// timestamp can't be forged. Summaries with a synced ledger info older
+    /// than the current one are rejected (to avoid regressing our view of
+    /// the peer), and false is returned.
+    fn update_storage_summary(
+        &mut self,
+        mut storage_summary: StorageServerSummary,
+        data_window_safety_margin_percent: u64,
+        time_now: Instant,
+    ) -> bool {
+        // Verify the synced ledger info timestamp hasn't regressed
+        let current_timestamp_usecs = self.get_synced_ledger_info_timestamp_usecs();
+        let new_timestamp_usecs = storage_summary
+            .data_summary
+            .synced_ledger_info
+            .as_ref()
+            .map(|synced_ledger_info| synced_ledger_info.ledger_info().timestamp_usecs());
+        if let (Some(current_timestamp_usecs), Some(new_timestamp_usecs)) =
+            (current_timestamp_usecs, new_timestamp_usecs)
+        {
+            if new_timestamp_usecs < current_timestamp_usecs {
+                return false;
+            }
+        }
+
+        // Update the storage summary
+        if let Some(data_windows) = &self.data_windows {
+            storage_summary.data_summary = data_windows.apply_to_data_summary(
+                &storage_summary.data_summary,
+                data_window_safety_margin_percent,
+            );
+        }
         self.storage_summary = Some(storage_summary);
+        self.storage_summary_update_time = Some(time_now);
+
+        true
     }
 }
 
@@ -187,17 +307,21 @@ impl PeerStates {
         // requests to new peers (who don't have a peer state yet).
         if request.data_request.is_storage_summary_request()
             || request.data_request.is_protocol_version_request()
+            || request.data_request.is_data_windows_request()
         {
             return true;
         }
 
         // Check if the peer can service the request
         if let Some(peer_state) = self.peer_to_state.get(peer) {
-            return match peer_state.get_storage_summary_if_not_ignored() {
+            return match peer_state.get_fresh_storage_summary_if_not_ignored(
+                time_service.now(),
+                self.get_max_storage_summary_age(),
+            ) {
                 Some(storage_summary) => {
                     storage_summary.can_service(&self.data_client_config, time_service, request)
                 },
-                None => false, // The peer is temporarily ignored
+                None => false, // The peer is temporarily ignored (or its summary is stale)
             };
         }
 
@@ -294,12 +418,131 @@ impl PeerStates {
         }
     }
 
+    /// Returns the measured throughput (in bytes per second) of the given peer
+    pub fn get_throughput_bytes_per_sec(&self, peer: &PeerNetworkId) -> Option<f64> {
+        self.peer_to_state
+            .get(peer)
+            .and_then(|peer_state| peer_state.get_throughput_bytes_per_sec())
+    }
+
+    /// Updates the measured throughput of the given peer using a response
+    /// of the specified size. Small responses are ignored, as their duration
+    /// is dominated by the network latency (and not the peer's throughput).
+    pub fn update_throughput(
+        &self,
+        peer: PeerNetworkId,
+        num_response_bytes: u64,
+        response_duration: Duration,
+    ) {
+        // Verify the response can be used to measure throughput
+        let throughput_scoring_config = &self.data_client_config.throughput_scoring_config;
+        if !throughput_scoring_config.enable_throughput_scoring
+            || num_response_bytes < throughput_scoring_config.min_response_bytes_for_scoring
+        {
+            return;
+        }
+
+        // Calculate the throughput sample (avoid dividing by zero)
+        let response_duration_secs = response_duration
+            .as_secs_f64()
+            .max(MIN_RESPONSE_DURATION_SECS);
+        let sample_bytes_per_sec = num_response_bytes as f64 / response_duration_secs;
+
+        // Update the peer's throughput
+        if let Some(mut entry) = self.peer_to_state.get_mut(&peer) {
+            let new_sample_weight = min(
+                throughput_scoring_config.ewma_new_sample_weight_percent,
+                100,
+            ) as f64
+                / 100.0;
+            entry.update_throughput(sample_bytes_per_sec, new_sample_weight);
+            metrics::observe_value_with_label(
+                &metrics::PEER_THROUGHPUT_BYTES_PER_SEC,
+                &peer.network_id().to_string(),
+                sample_bytes_per_sec,
+            );
+        }
+    }
+
     /// Updates the storage summary for the given peer
-    pub fn update_summary(&self, peer: PeerNetworkId, storage_summary: StorageServerSummary) {
+    pub fn update_summary(
+        &self,
+        peer: PeerNetworkId,
+        storage_summary: StorageServerSummary,
+        time_service: TimeService,
+    ) {
+        let data_window_safety_margin_percent = self
+            .data_client_config
+            .data_poller_config
+            .data_window_safety_margin_percent
+            .min(100);
+        let summary_updated = self
+            .peer_to_state
+            .entry(peer)
+            .or_default()
+            .update_storage_summary(
+                storage_summary,
+                data_window_safety_margin_percent,
+                time_service.now(),
+            );
+
+        // If the summary was rejected, log the stale summary and update the metrics
+        if !summary_updated {
+            sample!(
+                SampleRate::Duration(Duration::from_secs(LOGS_FREQUENCY_SECS)),
+                warn!(LogSchema::new(LogEntry::PeerStates)
+                    .event(LogEvent::StaleStorageSummary)
+                    .peer(&peer)
+                    .message("Ignoring a storage summary with a regressed synced ledger info!"))
+            );
+            metrics::STALE_STORAGE_SUMMARIES
+                .with_label_values(&[REGRESSED_SUMMARY_LABEL])
+                .inc();
+        }
+    }
+
+    /// Returns the maximum age of a storage summary before it is considered stale
+    fn get_max_storage_summary_age(&self) -> Duration {
+        Duration::from_secs(
+            self.data_client_config
+                .data_poller_config
+                .max_storage_summary_age_secs,
+        )
+    }
+
+    /// Returns true iff the data windows have been polled for the given peer
+    pub fn has_data_windows(&self, peer: &PeerNetworkId) -> bool {
+        self.peer_to_state
+            .get(peer)
+            .map(|peer_state| peer_state.get_data_windows().is_some())
+            .unwrap_or(false)
+    }
+
+    /// Updates the data windows for the given peer. Note: the windows are
+    /// applied to the storage summaries received after this update.
+    pub fn update_data_windows(&self, peer: PeerNetworkId, data_windows: DataWindows) {
+        self.peer_to_state
+            .entry(peer)
+            .or_default()
+            .update_data_windows(data_windows);
+    }
+
+    /// Returns true iff the compression codec has been negotiated with the given peer
+    pub fn is_compression_negotiated(&self, peer: &PeerNetworkId) -> bool {
+        self.peer_to_state
+            .get(peer)
+            .map(|peer_state| peer_state.is_compression_negotiated())
+            .unwrap_or(false)
+    }
+
+    /// Marks the compression codec as negotiated with the given peer. Note: the
+    /// peer state is garbage collected when the peer disconnects, so the codec
+    /// will be renegotiated on the next connection.
+    pub fn mark_compression_negotiated(&self, peer: PeerNetworkId) {
         self.peer_to_state
             .entry(peer)
             .or_default()
-            .update_storage_summary(storage_summary);
+            .compression_negotiated = true;
     }
 
     /// Garbage collects the peer states to remove data for disconnected peers
@@ -309,16 +552,38 @@ impl PeerStates {
     }
 
     /// Calculates a global data summary using all known storage summaries
-    pub fn calculate_global_data_summary(&self) -> GlobalDataSummary {
+    pub fn calculate_global_data_summary(&self, time_service: TimeService) -> GlobalDataSummary {
         // Gather all storage summaries, but exclude peers that are ignored
+        // and peers with stale summaries.
+        let time_now = time_service.now();
+        let max_storage_summary_age = self.get_max_storage_summary_age();
         let storage_summaries: Vec<StorageServerSummary> = self
             .peer_to_state
             .iter()
             .filter_map(|peer_state| {
-                peer_state
-                    .value()
-                    .get_storage_summary_if_not_ignored()
-                    .cloned()
+                let peer_state = peer_state.value();
+                let storage_summary = peer_state.get_storage_summary_if_not_ignored()?;
+                match peer_state
+                    .get_fresh_storage_summary_if_not_ignored(time_now, max_storage_summary_age)
+                {
+                    Some(storage_summary) => Some(storage_summary.clone()),
+                    None => {
+                        metrics::STALE_STORAGE_SUMMARIES
+                            .with_label_values(&[EXPIRED_SUMMARY_LABEL])
+                            .inc();
+                        trace!(LogSchema::new(LogEntry::PeerStates)
+                            .event(LogEvent::StaleStorageSummary)
+                            .message(&format!(
+                                "Ignoring an expired storage summary with synced version: {:?}",
+                                storage_summary
+                                    .data_summary
+                                    .synced_ledger_info
+                                    .as_ref()
+                                    .map(|ledger_info| ledger_info.ledger_info().version())
+                            )));
+                        None
+                    },
+                }
             })
             .collect();
 
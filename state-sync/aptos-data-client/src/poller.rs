@@ -12,6 +12,7 @@ use crate::{
     metrics::{set_gauge, DataType, PRIORITIZED_PEER, REGULAR_PEER},
     utils,
 };
+use aptos_compression::codec::CompressionCodec;
 use aptos_config::{
     config::{AptosDataClientConfig, AptosDataPollerConfig},
     network_id::PeerNetworkId,
@@ -20,8 +21,8 @@ use aptos_logger::{debug, error, info, sample, sample::SampleRate, warn};
 use aptos_network::application::storage::PeersAndMetadata;
 use aptos_storage_interface::DbReader;
 use aptos_storage_service_types::{
-    requests::{DataRequest, StorageServiceRequest},
-    responses::StorageServerSummary,
+    requests::{CompressionNegotiationRequest, DataRequest, StorageServiceRequest},
+    responses::{DataWindows, StorageServerSummary},
 };
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use dashmap::DashSet;
@@ -402,9 +403,21 @@ pub(crate) fn poll_peer(
     // Create the poller for the peer
     let runtime = data_summary_poller.runtime.clone();
     let poller = async move {
+        // Negotiate the compression codec with the peer (if required)
+        let use_compression = data_summary_poller.data_client_config.use_compression;
+        if use_compression
+            && data_summary_poller
+                .data_client_config
+                .enable_compression_negotiation
+            && !data_summary_poller
+                .data_client
+                .is_compression_negotiated(&peer)
+        {
+            negotiate_compression_codec(&data_summary_poller, peer).await;
+        }
+
         // Construct the request for polling
         let data_request = DataRequest::GetStorageServerSummary;
-        let use_compression = data_summary_poller.data_client_config.use_compression;
         let storage_request = StorageServiceRequest::new(data_request, use_compression);
 
         // Fetch the storage summary for the peer and stop the timer
@@ -433,6 +446,17 @@ pub(crate) fn poll_peer(
             },
         };
 
+        // Poll the data windows of the peer (if the peer is pruned)
+        if data_summary_poller
+            .data_client_config
+            .data_poller_config
+            .enable_data_window_polling
+            && is_pruned_storage_summary(&storage_summary)
+            && !data_summary_poller.data_client.has_peer_data_windows(&peer)
+        {
+            poll_data_windows(&data_summary_poller, peer).await;
+        }
+
         // Update the summary for the peer
         data_summary_poller
             .data_client
@@ -465,6 +489,115 @@ pub(crate) fn poll_peer(
     }
 }
 
+/// Negotiates the compression codec with the given peer. The codec only
+/// needs to be negotiated once per connection, as the peer state is
+/// garbage collected when the peer disconnects.
+async fn negotiate_compression_codec(data_summary_poller: &DataSummaryPoller, peer: PeerNetworkId) {
+    // Construct the negotiation request
+    let data_request = DataRequest::NegotiateCompression(CompressionNegotiationRequest {
+        supported_codecs: CompressionCodec::all(),
+    });
+    let storage_request = StorageServiceRequest::new(data_request, true);
+
+    // Send the request to the peer
+    let request_timeout = data_summary_poller.data_client_config.response_timeout_ms;
+    let result: crate::error::Result<CompressionCodec> = data_summary_poller
+        .data_client
+        .send_request_to_peer_and_decode(peer, storage_request, request_timeout)
+        .await
+        .map(Response::into_payload);
+
+    // Log the result. If the negotiation failed (e.g., because the
+    // peer doesn't support negotiation), the default codec is used.
+    match result {
+        Ok(compression_codec) => {
+            debug!(
+                (LogSchema::new(LogEntry::CompressionNegotiation)
+                    .event(LogEvent::CompressionNegotiationSuccess)
+                    .message(&format!(
+                        "Negotiated compression codec: {:?}",
+                        compression_codec
+                    ))
+                    .peer(&peer))
+            );
+        },
+        Err(error) => {
+            warn!(
+                (LogSchema::new(LogEntry::CompressionNegotiation)
+                    .event(LogEvent::CompressionNegotiationError)
+                    .message("Failed to negotiate the compression codec with the peer!")
+                    .error(&error)
+                    .peer(&peer))
+            );
+        },
+    }
+
+    // Mark the codec as negotiated (to avoid renegotiating on every poll)
+    data_summary_poller
+        .data_client
+        .mark_compression_negotiated(peer);
+}
+
+/// Returns true iff the storage summary indicates that the peer is pruned
+/// (i.e., the ledger or state data does not start at genesis).
+fn is_pruned_storage_summary(storage_summary: &StorageServerSummary) -> bool {
+    let data_summary = &storage_summary.data_summary;
+    [
+        data_summary.states,
+        data_summary.transactions,
+        data_summary.transaction_outputs,
+    ]
+    .iter()
+    .flatten()
+    .any(|data_range| data_range.lowest() > 0)
+}
+
+/// Polls the data windows of the given peer. The windows only need to be
+/// polled once per connection, as the peer state is garbage collected
+/// when the peer disconnects.
+async fn poll_data_windows(data_summary_poller: &DataSummaryPoller, peer: PeerNetworkId) {
+    // Construct the data windows request
+    let use_compression = data_summary_poller.data_client_config.use_compression;
+    let storage_request = StorageServiceRequest::new(DataRequest::GetDataWindows, use_compression);
+
+    // Send the request to the peer
+    let request_timeout = data_summary_poller.data_client_config.response_timeout_ms;
+    let result: crate::error::Result<DataWindows> = data_summary_poller
+        .data_client
+        .send_request_to_peer_and_decode(peer, storage_request, request_timeout)
+        .await
+        .map(Response::into_payload);
+
+    // Log the result. If polling failed (e.g., because the peer doesn't
+    // support data windows), the peer is treated as having no windows.
+    let data_windows = match result {
+        Ok(data_windows) => {
+            debug!(
+                (LogSchema::new(LogEntry::DataWindows)
+                    .event(LogEvent::DataWindowsPollingSuccess)
+                    .message(&format!("Polled data windows: {:?}", data_windows))
+                    .peer(&peer))
+            );
+            data_windows
+        },
+        Err(error) => {
+            warn!(
+                (LogSchema::new(LogEntry::DataWindows)
+                    .event(LogEvent::DataWindowsPollingError)
+                    .message("Failed to poll the data windows of the peer!")
+                    .error(&error)
+                    .peer(&peer))
+            );
+            DataWindows::default()
+        },
+    };
+
+    // Update the data windows (to avoid polling them again)
+    data_summary_poller
+        .data_client
+        .update_peer_data_windows(peer, data_windows);
+}
+
 /// Spawns the dedicated latency monitor
 fn start_latency_monitor(
     data_client_config: Arc<AptosDataClientConfig>,
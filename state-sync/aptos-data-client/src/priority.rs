@@ -101,10 +101,21 @@ pub fn get_peer_priority(
         };
     }
 
-    // Otherwise, this node is a PFN. PFNs should highly
-    // prioritize trusted peers (i.e., VFNs and seed peers).
-    if is_trusted_peer(peers_and_metadata.clone(), peer) {
+    // Otherwise, this node is a PFN. If preferred upstream peers have been
+    // selected (using the measured peer quality), PFNs should highly prioritize
+    // them, and all other trusted and outbound peers have medium priority.
+    let preferred_upstream_peers = peers_and_metadata.get_preferred_upstream_peers();
+    let (trusted_peer_priority, outbound_peer_priority) = if preferred_upstream_peers.is_empty() {
+        (PeerPriority::HighPriority, PeerPriority::HighPriority)
+    } else if preferred_upstream_peers.contains(peer) {
         return PeerPriority::HighPriority;
+    } else {
+        (PeerPriority::MediumPriority, PeerPriority::MediumPriority)
+    };
+
+    // PFNs should prioritize trusted peers (i.e., VFNs and seed peers)
+    if is_trusted_peer(peers_and_metadata.clone(), peer) {
+        return trusted_peer_priority;
     }
 
     // Outbound connections should be prioritized. This prioritizes
@@ -112,7 +123,7 @@ pub fn get_peer_priority(
     // are always low priority (as they are generally unreliable).
     if let Some(metadata) = utils::get_metadata_for_peer(&peers_and_metadata, *peer) {
         if metadata.get_connection_metadata().is_outbound_connection() {
-            PeerPriority::HighPriority
+            outbound_peer_priority
         } else {
             PeerPriority::LowPriority
         }
@@ -465,6 +476,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_pfn_priorities_preferred_upstream_peers() {
+        // Create a base config for a PFN
+        let base_config = Arc::new(BaseConfig {
+            role: RoleType::FullNode,
+            ..Default::default()
+        });
+
+        // Create a peers and metadata struct with the public networks registered
+        let peers_and_metadata = PeersAndMetadata::new(&[NetworkId::Public]);
+
+        // Create several PFN peers (with outbound and inbound connections)
+        let preferred_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let outbound_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let inbound_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        create_connection_metadata(
+            &peers_and_metadata,
+            preferred_peer,
+            ConnectionOrigin::Outbound,
+        );
+        create_connection_metadata(
+            &peers_and_metadata,
+            outbound_peer,
+            ConnectionOrigin::Outbound,
+        );
+        create_connection_metadata(&peers_and_metadata, inbound_peer, ConnectionOrigin::Inbound);
+
+        // Select the preferred upstream peer
+        peers_and_metadata.set_preferred_upstream_peers(vec![preferred_peer]);
+
+        // Verify the preferred upstream peer is highly prioritized
+        assert_eq!(
+            get_peer_priority(
+                base_config.clone(),
+                peers_and_metadata.clone(),
+                &preferred_peer
+            ),
+            PeerPriority::HighPriority
+        );
+
+        // Verify the other outbound peer is medium prioritized
+        assert_eq!(
+            get_peer_priority(
+                base_config.clone(),
+                peers_and_metadata.clone(),
+                &outbound_peer
+            ),
+            PeerPriority::MediumPriority
+        );
+
+        // Verify the inbound peer is low prioritized
+        assert_eq!(
+            get_peer_priority(
+                base_config.clone(),
+                peers_and_metadata.clone(),
+                &inbound_peer
+            ),
+            PeerPriority::LowPriority
+        );
+
+        // Clear the preferred upstream peers and verify all outbound peers are highly prioritized
+        peers_and_metadata.set_preferred_upstream_peers(vec![]);
+        for peer in [preferred_peer, outbound_peer] {
+            assert_eq!(
+                get_peer_priority(base_config.clone(), peers_and_metadata.clone(), &peer),
+                PeerPriority::HighPriority
+            );
+        }
+    }
+
     /// Adds the given peer to the trusted peers set
     fn add_to_trusted_peers(peers_and_metadata: &Arc<PeersAndMetadata>, peer: PeerNetworkId) {
         peers_and_metadata
@@ -5,18 +5,25 @@ use crate::{
     client::AptosDataClient,
     error::Error,
     interface::AptosDataClientInterface,
-    peer_states::calculate_optimal_chunk_sizes,
+    peer_states::{calculate_optimal_chunk_sizes, PeerStates},
     poller,
     priority::PeerPriority,
     tests::{mock::MockNetwork, utils},
 };
-use aptos_config::{config::AptosDataClientConfig, network_id::PeerNetworkId};
+use aptos_config::{
+    config::AptosDataClientConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
 use aptos_storage_service_types::{
-    requests::{DataRequest, TransactionsWithProofRequest},
-    responses::{CompleteDataRange, DataResponse, StorageServerSummary, StorageServiceResponse},
+    requests::{DataRequest, StorageServiceRequest, TransactionsWithProofRequest},
+    responses::{
+        CompleteDataRange, DataResponse, DataWindows, StorageServerSummary, StorageServiceResponse,
+    },
 };
+use aptos_time_service::TimeService;
 use aptos_types::transaction::{TransactionListWithProof, Version};
 use claims::assert_matches;
+use std::sync::Arc;
 
 #[tokio::test]
 async fn request_works_only_when_data_available() {
@@ -259,6 +266,131 @@ async fn update_peer_states() {
     verify_peer_state(&client, low_priority_peer, low_priority_storage_summary);
 }
 
+#[tokio::test]
+async fn peer_states_respect_data_windows() {
+    // Create the peer states with a 10% data window safety margin
+    let mut data_client_config = AptosDataClientConfig::default();
+    data_client_config
+        .data_poller_config
+        .data_window_safety_margin_percent = 10;
+    let peer_states = PeerStates::new(Arc::new(data_client_config));
+
+    // Create a storage summary for a pruned peer (holding versions 500 -> 1000)
+    let peer = PeerNetworkId::random_with_network(NetworkId::Public);
+    let mut storage_summary = utils::create_storage_summary(1000);
+    storage_summary.data_summary.transactions = Some(CompleteDataRange::new(500, 1000).unwrap());
+
+    // Update the peer's storage summary and verify the lowest versions can be serviced
+    let time_service = TimeService::mock();
+    peer_states.update_summary(peer, storage_summary.clone(), time_service.clone());
+    assert!(can_service_transactions(
+        &peer_states,
+        peer,
+        time_service.clone(),
+        500,
+        600
+    ));
+
+    // Update the peer's data windows and storage summary
+    peer_states.update_data_windows(peer, DataWindows {
+        ledger_window: Some(501),
+        state_window: None,
+    });
+    peer_states.update_summary(peer, storage_summary, time_service.clone());
+    assert!(peer_states.has_data_windows(&peer));
+
+    // Verify that data within the safety margin can no longer be serviced
+    for (start_version, can_service) in [(500, false), (549, false), (550, true)] {
+        assert_eq!(
+            can_service_transactions(&peer_states, peer, time_service.clone(), start_version, 600),
+            can_service
+        );
+    }
+
+    // Verify that the global data summary respects the data windows
+    let global_data_summary = peer_states.calculate_global_data_summary(time_service);
+    assert_eq!(global_data_summary.advertised_data.transactions, vec![
+        CompleteDataRange::new(550, 1000).unwrap()
+    ]);
+}
+
+#[tokio::test]
+async fn peer_states_ignore_stale_summaries() {
+    // Create the peer states with a small max storage summary age
+    let max_storage_summary_age_secs = 10;
+    let mut data_client_config = AptosDataClientConfig::default();
+    data_client_config
+        .data_poller_config
+        .max_storage_summary_age_secs = max_storage_summary_age_secs;
+    let peer_states = PeerStates::new(Arc::new(data_client_config));
+
+    // Update the peer's storage summary and verify the data can be serviced
+    let peer = PeerNetworkId::random_with_network(NetworkId::Public);
+    let time_service = TimeService::mock();
+    peer_states.update_summary(
+        peer,
+        utils::create_storage_summary_with_timestamp(1000, 2000),
+        time_service.clone(),
+    );
+    assert!(can_service_transactions(
+        &peer_states,
+        peer,
+        time_service.clone(),
+        0,
+        1000
+    ));
+
+    // Attempt to update the summary with an older synced ledger info
+    peer_states.update_summary(
+        peer,
+        utils::create_storage_summary_with_timestamp(500, 1000),
+        time_service.clone(),
+    );
+
+    // Verify the older summary was rejected
+    let synced_ledger_info = peer_states
+        .get_peer_to_states()
+        .get(&peer)
+        .unwrap()
+        .get_storage_summary()
+        .unwrap()
+        .data_summary
+        .synced_ledger_info
+        .unwrap();
+    assert_eq!(synced_ledger_info.ledger_info().version(), 1000);
+
+    // Elapse the max storage summary age and verify the summary is now stale
+    time_service
+        .clone()
+        .into_mock()
+        .advance_secs(max_storage_summary_age_secs + 1);
+    assert!(!can_service_transactions(
+        &peer_states,
+        peer,
+        time_service.clone(),
+        0,
+        1000
+    ));
+    let global_data_summary = peer_states.calculate_global_data_summary(time_service.clone());
+    assert!(global_data_summary.is_empty());
+
+    // Update the summary again and verify the data can be serviced
+    peer_states.update_summary(
+        peer,
+        utils::create_storage_summary_with_timestamp(1100, 3000),
+        time_service.clone(),
+    );
+    assert!(can_service_transactions(
+        &peer_states,
+        peer,
+        time_service.clone(),
+        0,
+        1000
+    ));
+    let global_data_summary = peer_states.calculate_global_data_summary(time_service);
+    assert!(!global_data_summary.is_empty());
+}
+
 #[tokio::test]
 async fn optimal_chunk_size_calculations() {
     // Create a test storage service config
@@ -372,6 +504,24 @@ fn verify_advertised_transaction_data(
     }
 }
 
+/// Returns true iff the peer can service the given transactions request
+fn can_service_transactions(
+    peer_states: &PeerStates,
+    peer: PeerNetworkId,
+    time_service: TimeService,
+    start_version: Version,
+    end_version: Version,
+) -> bool {
+    let data_request = DataRequest::GetTransactionsWithProof(TransactionsWithProofRequest {
+        proof_version: end_version,
+        start_version,
+        end_version,
+        include_events: false,
+    });
+    let storage_request = StorageServiceRequest::new(data_request, true);
+    peer_states.can_service_request(&peer, time_service, &storage_request)
+}
+
 /// Verifies that the peer's state is valid (i.e., the storage summary is correct)
 fn verify_peer_state(
     client: &AptosDataClient,
@@ -11,7 +11,7 @@ use crate::{
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_config::{
-    config::{AptosDataClientConfig, BaseConfig, RoleType},
+    config::{AptosDataClientConfig, BaseConfig, RoleType, TrustedPeerOnlyConfig},
     network_id::{NetworkId, PeerNetworkId},
 };
 use aptos_netcore::transport::ConnectionOrigin;
@@ -31,10 +31,15 @@ use aptos_storage_interface::DbReader;
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_storage_service_server::network::{NetworkRequest, ResponseSender};
 use aptos_storage_service_types::{
-    responses::TransactionOrOutputListWithProof, Epoch, StorageServiceMessage,
+    bandwidth::BandwidthBudget,
+    responses::{
+        EventsByKeyWithProof, StateValueChangesWithProof, TransactionOrOutputListWithProof,
+    },
+    Epoch, StorageServiceMessage,
 };
 use aptos_time_service::{MockTimeService, TimeService};
 use aptos_types::{
+    event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     state_store::state_value::StateValueChunkWithProof,
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
@@ -60,6 +65,21 @@ impl MockNetwork {
         base_config: Option<BaseConfig>,
         data_client_config: Option<AptosDataClientConfig>,
         networks: Option<Vec<NetworkId>>,
+    ) -> (Self, MockTimeService, AptosDataClient, DataSummaryPoller) {
+        Self::new_with_trusted_peer_only_config(
+            base_config,
+            data_client_config,
+            networks,
+            TrustedPeerOnlyConfig::default(),
+        )
+    }
+
+    /// Creates a new mock network (and client) with the given trusted peer only config
+    pub fn new_with_trusted_peer_only_config(
+        base_config: Option<BaseConfig>,
+        data_client_config: Option<AptosDataClientConfig>,
+        networks: Option<Vec<NetworkId>>,
+        trusted_peer_only_config: TrustedPeerOnlyConfig,
     ) -> (Self, MockTimeService, AptosDataClient, DataSummaryPoller) {
         // Initialize the logger for testing
         ::aptos_logger::Logger::init_for_testing();
@@ -104,6 +124,10 @@ impl MockNetwork {
         let mock_time = TimeService::mock();
         let base_config = base_config.unwrap_or_default();
         let data_client_config = data_client_config.unwrap_or_default();
+        let bandwidth_budget = Arc::new(BandwidthBudget::new(
+            data_client_config.data_bandwidth_config,
+            mock_time.clone(),
+        ));
         let (client, poller) = AptosDataClient::new(
             data_client_config,
             base_config.clone(),
@@ -111,6 +135,8 @@ impl MockNetwork {
             create_mock_db_reader(),
             storage_service_client,
             None,
+            bandwidth_budget,
+            trusted_peer_only_config,
         );
 
         // Create the mock network
@@ -167,9 +193,18 @@ impl MockNetwork {
         &mut self,
         network_id: NetworkId,
         outbound_connection: bool,
+    ) -> PeerNetworkId {
+        self.add_peer_with_network_and_peer_id(network_id, PeerId::random(), outbound_connection)
+    }
+
+    /// Add a new peer to the network peer DB with the specified network and peer ID
+    pub fn add_peer_with_network_and_peer_id(
+        &mut self,
+        network_id: NetworkId,
+        peer_id: PeerId,
+        outbound_connection: bool,
     ) -> PeerNetworkId {
         // Create a new peer
-        let peer_id = PeerId::random();
         let peer_network_id = PeerNetworkId::new(network_id, peer_id);
 
         // Create and save a new connection metadata
@@ -273,6 +308,15 @@ mock! {
             request_timeout_ms: u64,
         ) -> Result<Response<Vec<LedgerInfoWithSignatures>>>;
 
+        async fn get_events_by_key_with_proof(
+            &self,
+            event_key: EventKey,
+            start_sequence_number: u64,
+            end_sequence_number: u64,
+            proof_version: Version,
+            request_timeout_ms: u64,
+        ) -> Result<Response<EventsByKeyWithProof>>;
+
         async fn get_new_transaction_outputs_with_proof(
             &self,
             known_version: Version,
@@ -310,6 +354,14 @@ mock! {
             request_timeout_ms: u64,
         ) -> Result<Response<StateValueChunkWithProof>>;
 
+        async fn get_state_value_changes_with_proof(
+            &self,
+            proof_version: Version,
+            start_version: Version,
+            end_version: Version,
+            request_timeout_ms: u64,
+        ) -> Result<Response<StateValueChangesWithProof>>;
+
         async fn get_transaction_outputs_with_proof(
             &self,
             proof_version: Version,
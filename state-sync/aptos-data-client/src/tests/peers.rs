@@ -16,7 +16,7 @@ use aptos_config::{
 };
 use aptos_storage_service_server::network::NetworkRequest;
 use aptos_storage_service_types::{
-    requests::DataRequest,
+    requests::{DataRequest, StorageServiceRequest, TransactionsWithProofRequest},
     responses::{CompleteDataRange, DataResponse, StorageServerSummary, StorageServiceResponse},
     StorageServiceError,
 };
@@ -421,6 +421,56 @@ async fn disconnected_peers_garbage_collection() {
     }
 }
 
+#[tokio::test]
+async fn excluded_peer_is_not_selected() {
+    // Create the mock network, time service and client
+    let (mut mock_network, time_service, client, _) = MockNetwork::new(None, None, None);
+
+    // Add several peers and advertise data for all of them
+    let peers = utils::add_several_peers(&mut mock_network, 3, PeerPriority::HighPriority);
+    let storage_summary = utils::create_storage_summary(1000);
+    for peer in &peers {
+        client.update_peer_storage_summary(*peer, storage_summary.clone());
+    }
+    client.update_global_summary_cache().unwrap();
+
+    // Exclude a single peer from servicing requests
+    let excluded_peer = *peers.iter().next().unwrap();
+    let exclusion_duration = Duration::from_secs(10);
+    client.exclude_peer(excluded_peer, exclusion_duration);
+
+    // Verify the excluded peer is never selected
+    let storage_request = StorageServiceRequest::new(
+        DataRequest::GetTransactionsWithProof(TransactionsWithProofRequest {
+            proof_version: 100,
+            start_version: 0,
+            end_version: 100,
+            include_events: false,
+        }),
+        true,
+    );
+    let mut remaining_peers = peers.clone();
+    remaining_peers.remove(&excluded_peer);
+    for _ in 0..50 {
+        utils::verify_selected_peer_from_set(&client, &storage_request, &remaining_peers);
+    }
+
+    // Exclude all peers and verify that peers are still selected
+    for peer in &peers {
+        client.exclude_peer(*peer, exclusion_duration);
+    }
+    utils::verify_selected_peer_from_set(&client, &storage_request, &peers);
+
+    // Elapse the exclusion duration and verify all peers can be selected again
+    time_service.advance_async(exclusion_duration).await;
+    let mut selected_peers = HashSet::new();
+    for _ in 0..500 {
+        let peers_for_request = client.choose_peers_for_request(&storage_request).unwrap();
+        selected_peers.extend(peers_for_request);
+    }
+    assert_eq!(selected_peers, peers);
+}
+
 #[tokio::test]
 async fn single_good_peer() {
     // Ensure the properties hold for all peer priorities
@@ -0,0 +1,183 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    client::AptosDataClient,
+    error::Error,
+    interface::AptosDataClientInterface,
+    priority::PeerPriority,
+    tests::{mock::MockNetwork, utils},
+};
+use aptos_config::{
+    config::{AptosDataClientConfig, AptosDataMultiFetchConfig, TrustedPeerOnlyConfig},
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_storage_service_types::requests::{
+    DataRequest, StorageServiceRequest, TransactionsWithProofRequest,
+};
+use aptos_types::PeerId;
+use claims::assert_matches;
+use maplit::hashset;
+use std::{collections::HashSet, time::Duration};
+
+#[tokio::test]
+async fn trusted_peer_only_failover() {
+    // Create a data client config with multi-fetch disabled
+    let data_client_config = AptosDataClientConfig {
+        data_multi_fetch_config: AptosDataMultiFetchConfig {
+            enable_multi_fetch: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // Create the mock network, client and trusted peers
+    let (mut mock_network, client, trusted_peers, _) =
+        create_network_with_trusted_peers(data_client_config, 3, 10);
+
+    // Verify that the most preferred trusted peer is always selected
+    let storage_request = create_transactions_request();
+    for _ in 0..50 {
+        verify_selected_peers(&client, &storage_request, hashset![trusted_peers[0]]);
+    }
+
+    // Disconnect the most preferred trusted peer and verify the next peer is selected
+    mock_network.disconnect_peer(trusted_peers[0]);
+    verify_selected_peers(&client, &storage_request, hashset![trusted_peers[1]]);
+
+    // Exclude the next trusted peer and verify the last peer is selected
+    client.exclude_peer(trusted_peers[1], Duration::from_secs(10));
+    verify_selected_peers(&client, &storage_request, hashset![trusted_peers[2]]);
+
+    // Reconnect the most preferred trusted peer and verify it is selected again
+    mock_network.reconnect_peer(trusted_peers[0]);
+    verify_selected_peers(&client, &storage_request, hashset![trusted_peers[0]]);
+
+    // Disconnect all trusted peers and verify that no (untrusted) peers are selected
+    for peer in &trusted_peers {
+        mock_network.disconnect_peer(*peer);
+    }
+    assert_matches!(
+        client.choose_peers_for_request(&storage_request),
+        Err(Error::NoConnectedPeers(_))
+    );
+}
+
+#[tokio::test]
+async fn trusted_peer_only_multi_fetch() {
+    // Create the mock network, client and trusted peers (with multi-fetch enabled)
+    let data_client_config = AptosDataClientConfig::default();
+    let (mut mock_network, client, trusted_peers, _) =
+        create_network_with_trusted_peers(data_client_config, 4, 10);
+
+    // Verify that the most preferred trusted peers are selected
+    let num_peers_for_request = data_client_config
+        .data_multi_fetch_config
+        .min_peers_for_multi_fetch as usize;
+    let storage_request = create_transactions_request();
+    let expected_peers: HashSet<_> = trusted_peers[..num_peers_for_request]
+        .iter()
+        .cloned()
+        .collect();
+    for _ in 0..50 {
+        verify_selected_peers(&client, &storage_request, expected_peers.clone());
+    }
+
+    // Disconnect the most preferred trusted peer and verify the next peers are selected
+    mock_network.disconnect_peer(trusted_peers[0]);
+    let expected_peers: HashSet<_> = trusted_peers[1..=num_peers_for_request]
+        .iter()
+        .cloned()
+        .collect();
+    verify_selected_peers(&client, &storage_request, expected_peers);
+}
+
+#[tokio::test]
+async fn trusted_peer_only_polling() {
+    // Create the mock network, client and trusted peers
+    let (_, client, trusted_peers, _) =
+        create_network_with_trusted_peers(AptosDataClientConfig::default(), 3, 10);
+
+    // Verify that only the trusted peers will be polled
+    let (priority_peers, regular_peers) = client.get_priority_and_regular_peers().unwrap();
+    let all_peers: HashSet<_> = priority_peers.union(&regular_peers).cloned().collect();
+    assert_eq!(all_peers, trusted_peers.into_iter().collect());
+}
+
+/// Creates a mock network and client (for a PFN) with the trusted peer only
+/// mode enabled. Returns the trusted peers (in order) and the untrusted peers.
+fn create_network_with_trusted_peers(
+    data_client_config: AptosDataClientConfig,
+    num_trusted_peers: usize,
+    num_untrusted_peers: u64,
+) -> (
+    MockNetwork,
+    AptosDataClient,
+    Vec<PeerNetworkId>,
+    HashSet<PeerNetworkId>,
+) {
+    // Create the trusted peer only config
+    let trusted_peer_ids: Vec<_> = (0..num_trusted_peers).map(|_| PeerId::random()).collect();
+    let trusted_peer_only_config = TrustedPeerOnlyConfig {
+        enable: true,
+        trusted_peers: trusted_peer_ids.clone(),
+    };
+
+    // Create the mock network and client
+    let (mut mock_network, _, client, _) = MockNetwork::new_with_trusted_peer_only_config(
+        Some(utils::create_fullnode_base_config()),
+        Some(data_client_config),
+        Some(vec![NetworkId::Public]),
+        trusted_peer_only_config,
+    );
+
+    // Add the untrusted peers
+    let untrusted_peers = utils::add_several_peers(
+        &mut mock_network,
+        num_untrusted_peers,
+        PeerPriority::HighPriority,
+    );
+
+    // Add the trusted peers (in reverse order, so that the order is defined by the config)
+    let mut trusted_peers: Vec<_> = trusted_peer_ids
+        .iter()
+        .rev()
+        .map(|peer_id| {
+            mock_network.add_peer_with_network_and_peer_id(NetworkId::Public, *peer_id, true)
+        })
+        .collect();
+    trusted_peers.reverse();
+
+    // Advertise data for all peers
+    let storage_summary = utils::create_storage_summary(1000);
+    for peer in trusted_peers.iter().chain(untrusted_peers.iter()) {
+        client.update_peer_storage_summary(*peer, storage_summary.clone());
+    }
+    client.update_global_summary_cache().unwrap();
+
+    (mock_network, client, trusted_peers, untrusted_peers)
+}
+
+/// Creates a transactions request that can be serviced by all peers
+fn create_transactions_request() -> StorageServiceRequest {
+    StorageServiceRequest::new(
+        DataRequest::GetTransactionsWithProof(TransactionsWithProofRequest {
+            proof_version: 100,
+            start_version: 0,
+            end_version: 100,
+            include_events: false,
+        }),
+        true,
+    )
+}
+
+/// Selects peers to service the given request and verifies
+/// that the selected peers match the expected peers.
+fn verify_selected_peers(
+    client: &AptosDataClient,
+    storage_request: &StorageServiceRequest,
+    expected_peers: HashSet<PeerNetworkId>,
+) {
+    let selected_peers = client.choose_peers_for_request(storage_request).unwrap();
+    assert_eq!(selected_peers, expected_peers);
+}
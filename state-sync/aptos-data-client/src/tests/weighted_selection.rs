@@ -10,12 +10,17 @@ use aptos_config::{
     config::{AptosDataClientConfig, AptosDataMultiFetchConfig, AptosLatencyFilteringConfig},
     network_id::{NetworkId, PeerNetworkId},
 };
-use aptos_storage_service_types::requests::{DataRequest, StorageServiceRequest};
+use aptos_storage_service_types::requests::{
+    DataRequest, StorageServiceRequest, TransactionOutputsWithProofRequest,
+};
 use aptos_time_service::TimeServiceTrait;
 use maplit::hashset;
 use ordered_float::OrderedFloat;
 use rand::Rng;
-use std::collections::{HashMap, HashSet};
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
 
 // Useful test constants
 const NUM_PEERS_TO_ADD: u64 = 50;
@@ -748,6 +753,54 @@ async fn request_no_latencies() {
     }
 }
 
+#[tokio::test]
+async fn request_throughput_selection() {
+    // Create the data client config with multi-fetch enabled
+    let num_peers_for_multi_fetch = 1;
+    let data_client_config = AptosDataClientConfig {
+        data_multi_fetch_config: AptosDataMultiFetchConfig {
+            enable_multi_fetch: true,
+            min_peers_for_multi_fetch: num_peers_for_multi_fetch,
+            max_peers_for_multi_fetch: num_peers_for_multi_fetch,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // Create the mock network and client
+    let (mut mock_network, _, client, _) = MockNetwork::new(None, Some(data_client_config), None);
+
+    // Add several peers and advertise the transaction outputs
+    let peers = utils::add_several_peers(&mut mock_network, 10, PeerPriority::HighPriority);
+    utils::update_storage_summaries_for_peers(&client, &peers, 1000, 0);
+
+    // Measure the throughput of all peers (except one), with one slow peer
+    let mut peers = peers.into_iter();
+    let slow_peer = peers.next().unwrap();
+    let unmeasured_peer = peers.next().unwrap();
+    let num_response_bytes = 1024 * 1024; // 1 MiB
+    let peer_states = client.get_peer_states();
+    peer_states.update_throughput(slow_peer, num_response_bytes, Duration::from_secs(10));
+    for peer in peers {
+        peer_states.update_throughput(peer, num_response_bytes, Duration::from_millis(100));
+    }
+
+    // Select peers to service a transaction output request multiple times
+    let data_request =
+        DataRequest::GetTransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+            proof_version: 1000,
+            start_version: 0,
+            end_version: 100,
+        });
+    let storage_request = StorageServiceRequest::new(data_request, true);
+    let peers_and_selection_counts =
+        utils::select_peers_multiple_times(&client, num_peers_for_multi_fetch, &storage_request);
+
+    // Verify the slow peer is never selected, but the unmeasured peer is
+    verify_zero_selection_counts(&vec![slow_peer], &peers_and_selection_counts);
+    assert!(peers_and_selection_counts.get(&unmeasured_peer).unwrap() > &0);
+}
+
 #[tokio::test]
 async fn subscription_distance_latency_weights() {
     // Create a data client with a max lag of 500
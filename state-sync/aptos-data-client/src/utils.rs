@@ -4,6 +4,7 @@
 use crate::{
     error::Error,
     logging::{LogEntry, LogEvent, LogSchema},
+    peer_states::PeerStates,
 };
 use aptos_config::{config::AptosDataClientConfig, network_id::PeerNetworkId};
 use aptos_logger::{sample, sample::SampleRate, warn};
@@ -120,6 +121,58 @@ pub fn choose_peers_by_latency(
     choose_random_peers_by_weight(num_peers_to_choose, potential_peers_and_latency_weights)
 }
 
+/// Chooses peers weighted by their measured response throughput. Peers
+/// that have not yet been measured are given the median throughput of the
+/// measured peers (to ensure they are still explored), and peers that are
+/// significantly slower than the fastest peer are not considered. If no
+/// potential peers have been measured, None is returned.
+pub fn choose_peers_by_throughput(
+    data_client_config: Arc<AptosDataClientConfig>,
+    num_peers_to_choose: u64,
+    potential_peers: HashSet<PeerNetworkId>,
+    peer_states: Arc<PeerStates>,
+) -> Option<HashSet<PeerNetworkId>> {
+    // If no peers can be chosen, return an empty set
+    if num_peers_to_choose == 0 || potential_peers.is_empty() {
+        return Some(hashset![]);
+    }
+
+    // Gather the measured throughputs for all potential peers
+    let peers_and_throughputs: Vec<_> = potential_peers
+        .into_iter()
+        .map(|peer| (peer, peer_states.get_throughput_bytes_per_sec(&peer)))
+        .collect();
+
+    // Calculate the median and max throughputs of the measured peers
+    let mut measured_throughputs: Vec<_> = peers_and_throughputs
+        .iter()
+        .filter_map(|(_, throughput)| throughput.map(OrderedFloat))
+        .collect();
+    if measured_throughputs.is_empty() {
+        return None; // No peers have been measured yet
+    }
+    measured_throughputs.sort();
+    let median_throughput = measured_throughputs[measured_throughputs.len() / 2].into_inner();
+    let max_throughput = measured_throughputs[measured_throughputs.len() - 1].into_inner();
+
+    // Filter out the slow peers and use the throughputs as weights
+    let min_relative_throughput_percent = data_client_config
+        .throughput_scoring_config
+        .min_relative_throughput_percent;
+    let min_throughput = max_throughput * (min_relative_throughput_percent.min(100) as f64) / 100.0;
+    let potential_peers_and_throughput_weights = peers_and_throughputs
+        .into_iter()
+        .map(|(peer, throughput)| (peer, throughput.unwrap_or(median_throughput)))
+        .filter(|(_, throughput)| *throughput >= min_throughput && *throughput > 0.0)
+        .collect::<Vec<_>>();
+
+    // Select the peers by throughput weights
+    Some(choose_random_peers_by_weight(
+        num_peers_to_choose,
+        potential_peers_and_throughput_weights,
+    ))
+}
+
 /// Selects a single peer randomly from the list of specified peers
 pub fn choose_random_peer(peers: HashSet<PeerNetworkId>) -> Option<PeerNetworkId> {
     peers.into_iter().choose(&mut rand::thread_rng())
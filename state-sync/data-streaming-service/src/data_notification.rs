@@ -4,7 +4,9 @@
 
 use crate::streaming_client::Epoch;
 use aptos_data_client::interface::{Response, ResponsePayload};
+use aptos_storage_service_types::responses::EventsByKeyWithProof;
 use aptos_types::{
+    event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
     state_store::state_value::StateValueChunkWithProof,
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
@@ -43,6 +45,7 @@ pub enum DataPayload {
     ContinuousTransactionsWithProof(LedgerInfoWithSignatures, TransactionListWithProof),
     EpochEndingLedgerInfos(Vec<LedgerInfoWithSignatures>),
     EndOfStream,
+    EventsByKeyWithProof(EventsByKeyWithProof),
     StateValuesWithProof(StateValueChunkWithProof),
     TransactionOutputsWithProof(TransactionOutputListWithProof),
     TransactionsWithProof(TransactionListWithProof),
@@ -52,6 +55,7 @@ pub enum DataPayload {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataClientRequest {
     EpochEndingLedgerInfos(EpochEndingLedgerInfosRequest),
+    EventsByKeyWithProof(EventsByKeyWithProofRequest),
     NewTransactionOutputsWithProof(NewTransactionOutputsWithProofRequest),
     NewTransactionsWithProof(NewTransactionsWithProofRequest),
     NumberOfStates(NumberOfStatesRequest),
@@ -70,6 +74,7 @@ impl DataClientRequest {
     pub fn get_label(&self) -> &'static str {
         match self {
             Self::EpochEndingLedgerInfos(_) => "epoch_ending_ledger_infos",
+            Self::EventsByKeyWithProof(_) => "events_by_key_with_proof",
             Self::NewTransactionOutputsWithProof(_) => "new_transaction_outputs_with_proof",
             Self::NewTransactionsWithProof(_) => "new_transactions_with_proof",
             Self::NumberOfStates(_) => "number_of_states",
@@ -132,6 +137,15 @@ pub struct EpochEndingLedgerInfosRequest {
     pub end_epoch: Epoch,
 }
 
+/// A client request for fetching the events of an event key with proofs.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventsByKeyWithProofRequest {
+    pub event_key: EventKey,
+    pub start_sequence_number: u64,
+    pub end_sequence_number: u64,
+    pub proof_version: Version,
+}
+
 /// A client request for fetching new transactions with proofs.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct NewTransactionsWithProofRequest {
@@ -6,9 +6,10 @@ use crate::{
     data_notification,
     data_notification::{
         DataClientRequest, DataNotification, DataPayload, EpochEndingLedgerInfosRequest,
-        NewTransactionOutputsWithProofRequest, NewTransactionsOrOutputsWithProofRequest,
-        NewTransactionsWithProofRequest, NotificationId, NumberOfStatesRequest,
-        StateValuesWithProofRequest, SubscribeTransactionOutputsWithProofRequest,
+        EventsByKeyWithProofRequest, NewTransactionOutputsWithProofRequest,
+        NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest, NotificationId,
+        NumberOfStatesRequest, StateValuesWithProofRequest,
+        SubscribeTransactionOutputsWithProofRequest,
         SubscribeTransactionsOrOutputsWithProofRequest, SubscribeTransactionsWithProofRequest,
         TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
         TransactionsWithProofRequest,
@@ -18,12 +19,20 @@ use crate::{
     logging::{LogEntry, LogEvent, LogSchema},
     metrics,
     metrics::{increment_counter, increment_counter_multiple_labels, start_timer},
+    request_scheduler::RequestScheduler,
+    stream_audit::StreamAuditor,
+    stream_diagnostics::{
+        DataStreamDiagnostics, SentRequestDiagnostics, SentRequestStatus, StreamErrorDiagnostics,
+    },
     stream_engine::{DataStreamEngine, StreamEngine},
     streaming_client::{NotificationFeedback, StreamRequest},
     streaming_service::StreamUpdateNotification,
 };
 use aptos_channels::aptos_channel;
-use aptos_config::config::{AptosDataClientConfig, DataStreamingServiceConfig};
+use aptos_config::{
+    config::{AptosDataClientConfig, DataStreamingServiceConfig},
+    network_id::PeerNetworkId,
+};
 use aptos_data_client::{
     global_summary::{AdvertisedData, GlobalDataSummary},
     interface::{
@@ -74,6 +83,9 @@ pub struct DataStream<T> {
     // The unique ID for this data stream. This is useful for logging.
     data_stream_id: DataStreamId,
 
+    // The original stream request made by the client
+    stream_request: StreamRequest,
+
     // The data client through which to fetch data from the Aptos network
     aptos_data_client: T,
 
@@ -123,6 +135,22 @@ pub struct DataStream<T> {
 
     // The dynamic prefetching state (if enabled)
     dynamic_prefetching_state: DynamicPrefetchingState,
+
+    // The auditor that records the audit log of this stream
+    stream_auditor: StreamAuditor,
+
+    // The peer that most recently served the stream (if known). If the
+    // stream degrades, this is the peer that the stream fails over from.
+    primary_peer: Option<PeerNetworkId>,
+
+    // The number of times the stream has failed over to alternate peers
+    num_stream_failovers: u64,
+
+    // The scheduler that dispatches the data client requests of the stream
+    request_scheduler: RequestScheduler,
+
+    // The last error encountered by the stream (if any)
+    last_error: Option<StreamErrorDiagnostics>,
 }
 
 impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
@@ -136,6 +164,8 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         notification_id_generator: Arc<U64IdGenerator>,
         advertised_data: &AdvertisedData,
         time_service: TimeService,
+        stream_auditor: StreamAuditor,
+        request_scheduler: RequestScheduler,
     ) -> Result<(Self, DataStreamListener), Error> {
         // Create a new data stream listener
         let (notification_sender, notification_receiver) =
@@ -154,6 +184,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             data_client_config,
             streaming_service_config: data_stream_config,
             data_stream_id,
+            stream_request: stream_request.clone(),
             aptos_data_client,
             stream_engine,
             stream_update_notifier,
@@ -168,6 +199,11 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             subscription_stream_lag: None,
             time_service,
             dynamic_prefetching_state,
+            stream_auditor,
+            primary_peer: None,
+            num_stream_failovers: 0,
+            request_scheduler,
+            last_error: None,
         };
 
         Ok((data_stream, data_stream_listener))
@@ -219,6 +255,70 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         self.create_and_send_client_requests(&global_data_summary)
     }
 
+    /// Records the given error as the last error encountered by the stream
+    pub fn update_last_error(&mut self, error: String) {
+        self.last_error = Some(StreamErrorDiagnostics {
+            timestamp_usecs: self.time_service.now_unix_time().as_micros() as u64,
+            error,
+        });
+    }
+
+    /// Returns a snapshot of the live state of the stream (for diagnostics)
+    pub fn get_stream_diagnostics(&self) -> DataStreamDiagnostics {
+        // Summarize the sent requests (and the peers that served the responses)
+        let mut num_in_flight_requests = 0;
+        let mut requests_per_peer = BTreeMap::new();
+        let mut sent_requests = vec![];
+        for pending_response in self.sent_data_requests.iter().flatten() {
+            let pending_response = pending_response.lock();
+            let (status, peer) = match &pending_response.client_response {
+                None => {
+                    num_in_flight_requests += 1;
+                    (SentRequestStatus::InFlight, None)
+                },
+                Some(Ok(response)) => {
+                    let peer = response
+                        .context
+                        .response_callback
+                        .get_peer()
+                        .map(|peer| peer.to_string());
+                    if let Some(peer) = &peer {
+                        *requests_per_peer.entry(peer.clone()).or_insert(0) += 1;
+                    }
+                    (SentRequestStatus::ResponseReceived, peer)
+                },
+                Some(Err(_)) => (SentRequestStatus::RequestFailed, None),
+            };
+            sent_requests.push(SentRequestDiagnostics {
+                request: format!("{:?}", pending_response.client_request),
+                status,
+                peer,
+            });
+        }
+
+        DataStreamDiagnostics {
+            data_stream_id: self.data_stream_id,
+            stream_request: format!("{:?}", self.stream_request),
+            stream_progress: self.stream_engine.get_stream_progress(),
+            num_in_flight_requests,
+            requests_per_peer,
+            sent_requests,
+            request_failure_count: self.request_failure_count,
+            max_request_retry: self.streaming_service_config.max_request_retry,
+            max_concurrent_requests: self
+                .dynamic_prefetching_state
+                .get_max_concurrent_requests(&self.stream_engine),
+            num_stream_failovers: self.num_stream_failovers,
+            primary_peer: self.primary_peer.map(|peer| peer.to_string()),
+            last_error: self.last_error.clone(),
+        }
+    }
+
+    /// Records the termination of the stream (for the given reason) in the audit log
+    pub fn record_stream_termination(&self, reason: &str) {
+        self.stream_auditor.record_stream_terminated(reason);
+    }
+
     /// Returns true iff the given `notification_id` was sent by this stream
     pub fn sent_notification(&self, notification_id: &NotificationId) -> bool {
         if let Some(stream_end_notification_id) = self.stream_end_notification_id {
@@ -380,6 +480,8 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         };
 
         // Send the request to the network
+        self.stream_auditor
+            .record_request_sent(&data_client_request, request_retry);
         let join_handle = spawn_request_task(
             self.data_stream_id,
             data_client_request,
@@ -387,6 +489,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             pending_client_response.clone(),
             request_timeout_ms,
             self.stream_update_notifier.clone(),
+            self.request_scheduler.clone(),
         );
         self.spawned_tasks.push(join_handle);
 
@@ -523,6 +626,10 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
                     }
                 },
                 Err(error) => {
+                    // Record the failure in the audit log
+                    self.stream_auditor
+                        .record_request_failed(client_request, &error);
+
                     // Handle the error depending on the request type
                     if client_request.is_new_data_request() {
                         // The request was for new data. We should notify the
@@ -600,9 +707,21 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         }
 
         // Otherwise, the stream is lagging behind the advertised version.
-        // Check if the stream is beyond recovery (i.e., has failed).
+        // If this is a follow stream, verify the lag is within its tolerance.
         let current_stream_lag =
             highest_advertised_version.saturating_sub(highest_response_version);
+        if let Some(max_lag_versions) = self.stream_engine.get_max_lag_versions() {
+            if current_stream_lag > max_lag_versions {
+                return Err(
+                    aptos_data_client::error::Error::SubscriptionStreamIsLagging(format!(
+                        "The follow stream exceeded the max lag! Current lag: {:?}, max lag: {:?}",
+                        current_stream_lag, max_lag_versions
+                    )),
+                );
+            }
+        }
+
+        // Check if the stream is beyond recovery (i.e., has failed).
         if let Some(mut subscription_stream_lag) = self.subscription_stream_lag.take() {
             // Check if the stream lag is beyond recovery
             if subscription_stream_lag
@@ -634,6 +753,8 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         client_request: &DataClientRequest,
         error: aptos_data_client::error::Error,
     ) -> Result<(), Error> {
+        self.update_last_error(error.to_string());
+
         // Notify the stream engine and clear the requests queue
         self.stream_engine
             .notify_new_data_request_error(client_request, error)?;
@@ -649,10 +770,27 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         data_client_request: &DataClientRequest,
         response_payload: &ResponsePayload,
     ) -> Result<bool, Error> {
+        // Identify the expected vs. received items of the request
+        let request_cursor = match RequestCursor::new(data_client_request, response_payload)? {
+            Some(request_cursor) => request_cursor,
+            None => return Ok(false), // The request was trivially satisfied
+        };
+
         // Identify if any missing data needs to be requested
         if let Some(missing_data_request) =
-            create_missing_data_request(data_client_request, response_payload)?
+            request_cursor.create_continuation_request(data_client_request)?
         {
+            // Record the partial response
+            increment_counter(
+                &metrics::PARTIAL_DATA_RESPONSES,
+                data_client_request.get_label(),
+            );
+            self.stream_auditor.record_partial_response(
+                data_client_request,
+                request_cursor.get_num_expected_items()?,
+                request_cursor.num_received_items,
+            );
+
             // Increment the missing client request counter
             increment_counter(
                 &metrics::SENT_DATA_REQUESTS_FOR_MISSING_DATA,
@@ -700,8 +838,15 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             .stream_id(self.data_stream_id)
             .event(LogEvent::Error)
             .message("Encountered a client response that failed the sanity checks!"));
+        self.update_last_error(format!(
+            "The client response failed the sanity checks! Request: {:?}",
+            data_client_request
+        ));
 
         self.notify_bad_response(response_context, ResponseError::InvalidPayloadDataType);
+
+        // The peer that served the invalid response is the degraded peer
+        self.update_primary_peer(response_context);
         self.resend_data_client_request(data_client_request)
     }
 
@@ -717,6 +862,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
             .event(LogEvent::Error)
             .error(&data_client_error.clone().into())
             .message("Encountered a data client error!"));
+        self.update_last_error(data_client_error.to_string());
 
         // TODO(joshlind): can we identify the best way to react to the error?
         self.resend_data_client_request(data_client_request)
@@ -731,6 +877,11 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         // Increment the number of client failures for this request
         self.request_failure_count += 1;
 
+        // If the primary peer has degraded, fail over to alternate peers
+        if let Some(primary_peer) = self.get_degraded_primary_peer() {
+            return self.fail_over_to_alternate_peers(primary_peer, data_client_request);
+        }
+
         // Resend the client request
         let pending_client_response = self.send_client_request(true, data_client_request.clone());
 
@@ -741,12 +892,106 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         Ok(())
     }
 
+    /// Returns the primary peer iff the stream should fail over from it (i.e.,
+    /// failover is enabled, the primary peer is known, and the request at the
+    /// head of the stream has failed too many times).
+    fn get_degraded_primary_peer(&self) -> Option<PeerNetworkId> {
+        let failover_config = self.streaming_service_config.stream_failover;
+        if !failover_config.enable_stream_failover
+            || self.request_failure_count < failover_config.failover_failure_threshold
+            || self.num_stream_failovers >= failover_config.max_failovers_per_stream
+        {
+            return None;
+        }
+        self.primary_peer
+    }
+
+    /// Fails over the stream from the given (degraded) primary peer. The
+    /// primary peer is excluded from servicing requests, and the failed request
+    /// (along with all in-flight requests) is re-sent to alternate peers.
+    /// Responses that have already been received are kept and notification
+    /// IDs are only assigned once notifications are sent, so the failover is
+    /// invisible to the stream listener.
+    fn fail_over_to_alternate_peers(
+        &mut self,
+        primary_peer: PeerNetworkId,
+        data_client_request: &DataClientRequest,
+    ) -> Result<(), Error> {
+        // Exclude the primary peer from servicing further requests
+        let peer_exclusion_duration = Duration::from_millis(
+            self.streaming_service_config
+                .stream_failover
+                .peer_exclusion_duration_ms,
+        );
+        self.aptos_data_client
+            .exclude_peer(primary_peer, peer_exclusion_duration);
+
+        // Re-send all in-flight requests (i.e., requests without responses).
+        // Note: the original requests are left to complete (or time out),
+        // but their responses are ignored.
+        let in_flight_requests: Vec<(usize, DataClientRequest)> = self
+            .get_sent_data_requests()?
+            .iter()
+            .enumerate()
+            .filter_map(|(index, pending_response)| {
+                let pending_response = pending_response.lock();
+                if pending_response.client_response.is_none() {
+                    Some((index, pending_response.client_request.clone()))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        let num_resent_requests = in_flight_requests.len() as u64 + 1;
+        for (index, client_request) in in_flight_requests {
+            let pending_client_response = self.send_client_request(false, client_request);
+            self.get_sent_data_requests()?[index] = pending_client_response;
+        }
+
+        // Re-send the failed request and push it to the head of the queue
+        let pending_client_response = self.send_client_request(false, data_client_request.clone());
+        self.get_sent_data_requests()?
+            .push_front(pending_client_response);
+
+        // Reset the failure count (the alternate peers have a fresh retry budget)
+        self.request_failure_count = 0;
+        self.num_stream_failovers += 1;
+        self.primary_peer = None;
+
+        // Update the logs, metrics and audit log
+        warn!(LogSchema::new(LogEntry::StreamFailover)
+            .stream_id(self.data_stream_id)
+            .event(LogEvent::Pending)
+            .message(&format!(
+                "Failing over the stream from degraded peer: {:?}. Number of re-sent requests: {:?}",
+                primary_peer, num_resent_requests
+            )));
+        increment_counter(
+            &metrics::DATA_STREAM_FAILOVERS,
+            data_client_request.get_label(),
+        );
+        self.stream_auditor
+            .record_stream_failover(&primary_peer, num_resent_requests);
+
+        Ok(())
+    }
+
+    /// Updates the primary peer to the peer that served the given response (if known)
+    fn update_primary_peer(&mut self, response_context: &ResponseContext) {
+        if let Some(peer) = response_context.response_callback.get_peer() {
+            self.primary_peer = Some(peer);
+        }
+    }
+
     /// Notifies the Aptos data client of a bad client response
     fn notify_bad_response(
         &self,
         response_context: &ResponseContext,
         response_error: ResponseError,
     ) {
+        self.stream_auditor
+            .record_response_rejected(response_context, &response_error);
+
         let response_id = response_context.id;
         info!(LogSchema::new(LogEntry::ReceivedDataResponse)
             .stream_id(self.data_stream_id)
@@ -787,6 +1032,12 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
 
             // Save the response context for this notification ID
             let notification_id = data_notification.notification_id;
+            self.stream_auditor.record_response_received(
+                data_client_request,
+                &response_context,
+                Some(notification_id),
+            );
+            self.update_primary_peer(&response_context);
             self.insert_notification_response_mapping(notification_id, response_context)?;
 
             // Send the notification along the stream
@@ -803,6 +1054,13 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
 
             // Reset the failure count. We've sent a notification and can move on.
             self.request_failure_count = 0;
+        } else {
+            // No notification was created (e.g., the response only contained metadata)
+            self.stream_auditor.record_response_received(
+                data_client_request,
+                &response_context,
+                None,
+            );
         }
 
         Ok(())
@@ -928,6 +1186,12 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStream<T> {
         (sent_requests, sent_notifications)
     }
 
+    #[cfg(test)]
+    /// Returns the data client used by the stream (for testing)
+    pub fn get_data_client(&self) -> &T {
+        &self.aptos_data_client
+    }
+
     #[cfg(test)]
     /// Returns the subscription stream lag (for testing)
     pub fn get_subscription_stream_lag(&self) -> Option<SubscriptionStreamLag> {
@@ -1037,265 +1301,184 @@ impl FusedStream for DataStreamListener {
     }
 }
 
-/// Creates and returns a missing data request if the given client response
-/// doesn't satisfy the original request. If the request is satisfied,
-/// None is returned.
-pub(crate) fn create_missing_data_request(
-    data_client_request: &DataClientRequest,
-    response_payload: &ResponsePayload,
-) -> Result<Option<DataClientRequest>, Error> {
-    // Determine if the request was satisfied, and if not, create
-    // a missing data request to satisfy the original request.
-    match data_client_request {
-        DataClientRequest::EpochEndingLedgerInfos(request) => {
-            create_missing_epoch_ending_ledger_infos_request(request, response_payload)
-        },
-        DataClientRequest::StateValuesWithProof(request) => {
-            create_missing_state_values_request(request, response_payload)
-        },
-        DataClientRequest::TransactionsWithProof(request) => {
-            create_missing_transactions_request(request, response_payload)
-        },
-        DataClientRequest::TransactionOutputsWithProof(request) => {
-            create_missing_transaction_outputs_request(request, response_payload)
-        },
-        DataClientRequest::TransactionsOrOutputsWithProof(request) => {
-            create_missing_transactions_or_outputs_request(request, response_payload)
-        },
-        _ => Ok(None), // The request was trivially satisfied (based on the type)
-    }
+/// The cursor of a data client request for a bounded range of items (e.g.,
+/// epochs, versions or state indices). This tracks the expected vs. received
+/// items, as serving peers may return fewer items than requested (e.g., because
+/// of their own size limits). In that case, the response is partial and a
+/// continuation request should be sent for the remaining items.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RequestCursor {
+    pub start_index: u64,
+    pub end_index: u64, // Inclusive
+    pub num_received_items: u64,
 }
 
-/// Creates and returns a missing epoch ending ledger info request if the
-/// given client response doesn't satisfy the original request. If the request
-/// is satisfied, None is returned.
-fn create_missing_epoch_ending_ledger_infos_request(
-    request: &EpochEndingLedgerInfosRequest,
-    response_payload: &ResponsePayload,
-) -> Result<Option<DataClientRequest>, Error> {
-    // Determine the number of requested ledger infos
-    let num_requested_ledger_infos = request
-        .end_epoch
-        .checked_sub(request.start_epoch)
-        .and_then(|v| v.checked_add(1))
-        .ok_or_else(|| {
-            Error::IntegerOverflow("Number of requested ledger infos has overflown!".into())
-        })?;
-
-    // Identify the missing data if the request was not satisfied
-    match response_payload {
-        ResponsePayload::EpochEndingLedgerInfos(ledger_infos) => {
-            // Check if the request was satisfied
-            let num_received_ledger_infos = ledger_infos.len() as u64;
-            if num_received_ledger_infos < num_requested_ledger_infos {
-                let start_epoch = request
-                    .start_epoch
-                    .checked_add(num_received_ledger_infos)
-                    .ok_or_else(|| Error::IntegerOverflow("Start epoch has overflown!".into()))?;
-                Ok(Some(DataClientRequest::EpochEndingLedgerInfos(
-                    EpochEndingLedgerInfosRequest {
-                        start_epoch,
-                        end_epoch: request.end_epoch,
-                    },
+impl RequestCursor {
+    /// Creates the cursor for the given client request and response payload.
+    /// If the request is not for a bounded range of items (e.g., optimistic
+    /// fetch and subscription requests), None is returned.
+    pub fn new(
+        data_client_request: &DataClientRequest,
+        response_payload: &ResponsePayload,
+    ) -> Result<Option<Self>, Error> {
+        // Identify the requested indices
+        let (start_index, end_index) = match data_client_request {
+            DataClientRequest::EpochEndingLedgerInfos(request) => {
+                (request.start_epoch, request.end_epoch)
+            },
+            DataClientRequest::EventsByKeyWithProof(request) => {
+                (request.start_sequence_number, request.end_sequence_number)
+            },
+            DataClientRequest::StateValuesWithProof(request) => {
+                (request.start_index, request.end_index)
+            },
+            DataClientRequest::TransactionsWithProof(request) => {
+                (request.start_version, request.end_version)
+            },
+            DataClientRequest::TransactionOutputsWithProof(request) => {
+                (request.start_version, request.end_version)
+            },
+            DataClientRequest::TransactionsOrOutputsWithProof(request) => {
+                (request.start_version, request.end_version)
+            },
+            _ => return Ok(None), // The request is trivially satisfied (based on the type)
+        };
+
+        // Identify the number of received items
+        let num_received_items = match (data_client_request, response_payload) {
+            (
+                DataClientRequest::EpochEndingLedgerInfos(_),
+                ResponsePayload::EpochEndingLedgerInfos(ledger_infos),
+            ) => ledger_infos.len(),
+            (
+                DataClientRequest::EventsByKeyWithProof(_),
+                ResponsePayload::EventsByKeyWithProof(events_by_key_with_proof),
+            ) => events_by_key_with_proof.get_num_events(),
+            (
+                DataClientRequest::StateValuesWithProof(_),
+                ResponsePayload::StateValuesWithProof(state_values_with_proof),
+            ) => state_values_with_proof.raw_values.len(),
+            (
+                DataClientRequest::TransactionsWithProof(_)
+                | DataClientRequest::TransactionsOrOutputsWithProof(_),
+                ResponsePayload::TransactionsWithProof(transactions_with_proof),
+            ) => transactions_with_proof.transactions.len(),
+            (
+                DataClientRequest::TransactionOutputsWithProof(_)
+                | DataClientRequest::TransactionsOrOutputsWithProof(_),
+                ResponsePayload::TransactionOutputsWithProof(outputs_with_proof),
+            ) => outputs_with_proof.transactions_and_outputs.len(),
+            (request, payload) => {
+                return Err(Error::AptosDataClientResponseIsInvalid(format!(
+                    "Invalid response payload found for {:?} request: {:?}",
+                    request.get_label(),
+                    payload
                 )))
-            } else {
-                Ok(None) // The request was satisfied!
-            }
-        },
-        payload => Err(Error::AptosDataClientResponseIsInvalid(format!(
-            "Invalid response payload found for epoch ending ledger info request: {:?}",
-            payload
-        ))),
+            },
+        };
+
+        Ok(Some(Self {
+            start_index,
+            end_index,
+            num_received_items: num_received_items as u64,
+        }))
     }
-}
 
-/// Creates and returns a missing state values request if the given client
-/// response doesn't satisfy the original request. If the request is satisfied,
-/// None is returned.
-fn create_missing_state_values_request(
-    request: &StateValuesWithProofRequest,
-    response_payload: &ResponsePayload,
-) -> Result<Option<DataClientRequest>, Error> {
-    // Determine the number of requested state values
-    let num_requested_state_values = request
-        .end_index
-        .checked_sub(request.start_index)
-        .and_then(|v| v.checked_add(1))
-        .ok_or_else(|| {
-            Error::IntegerOverflow("Number of requested state values has overflown!".into())
-        })?;
-
-    // Identify the missing data if the request was not satisfied
-    match response_payload {
-        ResponsePayload::StateValuesWithProof(state_values_with_proof) => {
-            // Check if the request was satisfied
-            let num_received_state_values = state_values_with_proof.raw_values.len() as u64;
-            if num_received_state_values < num_requested_state_values {
-                let start_index = request
-                    .start_index
-                    .checked_add(num_received_state_values)
-                    .ok_or_else(|| Error::IntegerOverflow("Start index has overflown!".into()))?;
-                Ok(Some(DataClientRequest::StateValuesWithProof(
-                    StateValuesWithProofRequest {
-                        version: request.version,
-                        start_index,
-                        end_index: request.end_index,
-                    },
-                )))
-            } else {
-                Ok(None) // The request was satisfied!
-            }
-        },
-        payload => Err(Error::AptosDataClientResponseIsInvalid(format!(
-            "Invalid response payload found for state values request: {:?}",
-            payload
-        ))),
+    /// Returns the number of items expected by the request
+    pub fn get_num_expected_items(&self) -> Result<u64, Error> {
+        self.end_index
+            .checked_sub(self.start_index)
+            .and_then(|v| v.checked_add(1))
+            .ok_or_else(|| {
+                Error::IntegerOverflow("Number of requested items has overflown!".into())
+            })
     }
-}
 
-/// Creates and returns a missing transactions request if the given client
-/// response doesn't satisfy the original request. If the request is satisfied,
-/// None is returned.
-fn create_missing_transactions_request(
-    request: &TransactionsWithProofRequest,
-    response_payload: &ResponsePayload,
-) -> Result<Option<DataClientRequest>, Error> {
-    // Determine the number of requested transactions
-    let num_requested_transactions = request
-        .end_version
-        .checked_sub(request.start_version)
-        .and_then(|v| v.checked_add(1))
-        .ok_or_else(|| {
-            Error::IntegerOverflow("Number of requested transactions has overflown!".into())
-        })?;
-
-    // Identify the missing data if the request was not satisfied
-    match response_payload {
-        ResponsePayload::TransactionsWithProof(transactions_with_proof) => {
-            // Check if the request was satisfied
-            let num_received_transactions = transactions_with_proof.transactions.len() as u64;
-            if num_received_transactions < num_requested_transactions {
-                let start_version = request
-                    .start_version
-                    .checked_add(num_received_transactions)
-                    .ok_or_else(|| Error::IntegerOverflow("Start version has overflown!".into()))?;
-                Ok(Some(DataClientRequest::TransactionsWithProof(
-                    TransactionsWithProofRequest {
-                        start_version,
-                        end_version: request.end_version,
-                        proof_version: request.proof_version,
-                        include_events: request.include_events,
-                    },
-                )))
-            } else {
-                Ok(None) // The request was satisfied!
-            }
-        },
-        payload => Err(Error::AptosDataClientResponseIsInvalid(format!(
-            "Invalid response payload found for transactions request: {:?}",
-            payload
-        ))),
+    /// Returns true iff the response only satisfied part of the request
+    pub fn is_partial_response(&self) -> Result<bool, Error> {
+        Ok(self.num_received_items < self.get_num_expected_items()?)
     }
-}
 
-/// Creates and returns a missing transaction outputs request if the given client
-/// response doesn't satisfy the original request. If the request is satisfied,
-/// None is returned.
-fn create_missing_transaction_outputs_request(
-    request: &TransactionOutputsWithProofRequest,
-    response_payload: &ResponsePayload,
-) -> Result<Option<DataClientRequest>, Error> {
-    // Determine the number of requested transaction outputs
-    let num_requested_outputs = request
-        .end_version
-        .checked_sub(request.start_version)
-        .and_then(|v| v.checked_add(1))
-        .ok_or_else(|| {
-            Error::IntegerOverflow("Number of requested transaction outputs has overflown!".into())
-        })?;
-
-    // Identify the missing data if the request was not satisfied
-    match response_payload {
-        ResponsePayload::TransactionOutputsWithProof(transaction_outputs_with_proof) => {
-            // Check if the request was satisfied
-            let num_received_outputs = transaction_outputs_with_proof
-                .transactions_and_outputs
-                .len() as u64;
-            if num_received_outputs < num_requested_outputs {
-                let start_version = request
-                    .start_version
-                    .checked_add(num_received_outputs)
-                    .ok_or_else(|| Error::IntegerOverflow("Start version has overflown!".into()))?;
-                Ok(Some(DataClientRequest::TransactionOutputsWithProof(
-                    TransactionOutputsWithProofRequest {
-                        start_version,
-                        end_version: request.end_version,
-                        proof_version: request.proof_version,
+    /// Returns the index of the first item that has not yet been received
+    pub fn get_next_index(&self) -> Result<u64, Error> {
+        self.start_index
+            .checked_add(self.num_received_items)
+            .ok_or_else(|| Error::IntegerOverflow("Next request index has overflown!".into()))
+    }
+
+    /// Creates and returns a continuation request (i.e., for the remaining
+    /// items) if the response only satisfied part of the given request. If the
+    /// request is satisfied, None is returned.
+    pub fn create_continuation_request(
+        &self,
+        data_client_request: &DataClientRequest,
+    ) -> Result<Option<DataClientRequest>, Error> {
+        if !self.is_partial_response()? {
+            return Ok(None); // The request was satisfied!
+        }
+
+        // Create the continuation request (starting at the next index)
+        let next_index = self.get_next_index()?;
+        let continuation_request = match data_client_request {
+            DataClientRequest::EpochEndingLedgerInfos(request) => {
+                DataClientRequest::EpochEndingLedgerInfos(EpochEndingLedgerInfosRequest {
+                    start_epoch: next_index,
+                    ..request.clone()
+                })
+            },
+            DataClientRequest::EventsByKeyWithProof(request) => {
+                DataClientRequest::EventsByKeyWithProof(EventsByKeyWithProofRequest {
+                    start_sequence_number: next_index,
+                    ..request.clone()
+                })
+            },
+            DataClientRequest::StateValuesWithProof(request) => {
+                DataClientRequest::StateValuesWithProof(StateValuesWithProofRequest {
+                    start_index: next_index,
+                    ..request.clone()
+                })
+            },
+            DataClientRequest::TransactionsWithProof(request) => {
+                DataClientRequest::TransactionsWithProof(TransactionsWithProofRequest {
+                    start_version: next_index,
+                    ..request.clone()
+                })
+            },
+            DataClientRequest::TransactionOutputsWithProof(request) => {
+                DataClientRequest::TransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+                    start_version: next_index,
+                    ..request.clone()
+                })
+            },
+            DataClientRequest::TransactionsOrOutputsWithProof(request) => {
+                DataClientRequest::TransactionsOrOutputsWithProof(
+                    TransactionsOrOutputsWithProofRequest {
+                        start_version: next_index,
+                        ..request.clone()
                     },
+                )
+            },
+            request => {
+                return Err(Error::UnexpectedErrorEncountered(format!(
+                    "Unable to create a continuation request for: {:?}",
+                    request
                 )))
-            } else {
-                Ok(None) // The request was satisfied!
-            }
-        },
-        payload => Err(Error::AptosDataClientResponseIsInvalid(format!(
-            "Invalid response payload found for transaction outputs request: {:?}",
-            payload
-        ))),
+            },
+        };
+        Ok(Some(continuation_request))
     }
 }
 
-/// Creates and returns a missing transactions or outputs request if the
-/// given client response doesn't satisfy the original request. If the request
-/// is satisfied, None is returned.
-fn create_missing_transactions_or_outputs_request(
-    request: &TransactionsOrOutputsWithProofRequest,
+/// Creates and returns a missing data request if the given client response
+/// doesn't satisfy the original request. If the request is satisfied,
+/// None is returned.
+pub(crate) fn create_missing_data_request(
+    data_client_request: &DataClientRequest,
     response_payload: &ResponsePayload,
 ) -> Result<Option<DataClientRequest>, Error> {
-    // Determine the number of requested transactions or outputs
-    let num_request_data_items = request
-        .end_version
-        .checked_sub(request.start_version)
-        .and_then(|v| v.checked_add(1))
-        .ok_or_else(|| {
-            Error::IntegerOverflow(
-                "Number of requested transactions or outputs has overflown!".into(),
-            )
-        })?;
-
-    // Calculate the number of received data items
-    let num_received_data_items = match response_payload {
-        ResponsePayload::TransactionsWithProof(transactions_with_proof) => {
-            transactions_with_proof.transactions.len() as u64
-        },
-        ResponsePayload::TransactionOutputsWithProof(transaction_outputs_with_proof) => {
-            transaction_outputs_with_proof
-                .transactions_and_outputs
-                .len() as u64
-        },
-        payload => {
-            return Err(Error::AptosDataClientResponseIsInvalid(format!(
-                "Invalid response payload found for transactions or outputs request: {:?}",
-                payload
-            )))
-        },
-    };
-
-    // Identify the missing data if the request was not satisfied
-    if num_received_data_items < num_request_data_items {
-        let start_version = request
-            .start_version
-            .checked_add(num_received_data_items)
-            .ok_or_else(|| Error::IntegerOverflow("Start version has overflown!".into()))?;
-        Ok(Some(DataClientRequest::TransactionsOrOutputsWithProof(
-            TransactionsOrOutputsWithProofRequest {
-                start_version,
-                end_version: request.end_version,
-                proof_version: request.proof_version,
-                include_events: request.include_events,
-            },
-        )))
-    } else {
-        Ok(None) // The request was satisfied!
+    match RequestCursor::new(data_client_request, response_payload)? {
+        Some(request_cursor) => request_cursor.create_continuation_request(data_client_request),
+        None => Ok(None), // The request was trivially satisfied (based on the type)
     }
 }
 
@@ -1312,6 +1495,12 @@ fn sanity_check_client_response_type(
                 ResponsePayload::EpochEndingLedgerInfos(_)
             )
         },
+        DataClientRequest::EventsByKeyWithProof(_) => {
+            matches!(
+                data_client_response.payload,
+                ResponsePayload::EventsByKeyWithProof(_)
+            )
+        },
         DataClientRequest::NewTransactionOutputsWithProof(_) => {
             matches!(
                 data_client_response.payload,
@@ -1413,6 +1602,7 @@ fn spawn_request_task<T: AptosDataClientInterface + Send + Clone + 'static>(
     pending_response: PendingClientResponse,
     request_timeout_ms: u64,
     stream_update_notifier: aptos_channel::Sender<(), StreamUpdateNotification>,
+    request_scheduler: RequestScheduler,
 ) -> JoinHandle<()> {
     // Update the requests sent counter
     increment_counter(
@@ -1422,6 +1612,10 @@ fn spawn_request_task<T: AptosDataClientInterface + Send + Clone + 'static>(
 
     // Spawn the request
     tokio::spawn(async move {
+        // Wait until the request can be dispatched (the permit is
+        // held until the response is received).
+        let _permit = request_scheduler.acquire_permit(&data_client_request).await;
+
         // Time the request (the timer will stop when it's dropped)
         let _timer = start_timer(
             &metrics::DATA_REQUEST_PROCESSING_LATENCY,
@@ -1433,6 +1627,9 @@ fn spawn_request_task<T: AptosDataClientInterface + Send + Clone + 'static>(
             DataClientRequest::EpochEndingLedgerInfos(request) => {
                 get_epoch_ending_ledger_infos(aptos_data_client, request, request_timeout_ms).await
             },
+            DataClientRequest::EventsByKeyWithProof(request) => {
+                get_events_by_key_with_proof(aptos_data_client, request, request_timeout_ms).await
+            },
             DataClientRequest::NewTransactionsWithProof(request) => {
                 get_new_transactions_with_proof(aptos_data_client, request, request_timeout_ms)
                     .await
@@ -1534,6 +1731,23 @@ async fn get_states_values_with_proof<T: AptosDataClientInterface + Send + Clone
         .map(|response| response.map(ResponsePayload::from))
 }
 
+async fn get_events_by_key_with_proof<T: AptosDataClientInterface + Send + Clone + 'static>(
+    aptos_data_client: T,
+    request: EventsByKeyWithProofRequest,
+    request_timeout_ms: u64,
+) -> Result<Response<ResponsePayload>, aptos_data_client::error::Error> {
+    let client_response = aptos_data_client.get_events_by_key_with_proof(
+        request.event_key,
+        request.start_sequence_number,
+        request.end_sequence_number,
+        request.proof_version,
+        request_timeout_ms,
+    );
+    client_response
+        .await
+        .map(|response| response.map(ResponsePayload::from))
+}
+
 async fn get_epoch_ending_ledger_infos<T: AptosDataClientInterface + Send + Clone + 'static>(
     aptos_data_client: T,
     request: EpochEndingLedgerInfosRequest,
@@ -1736,6 +1950,7 @@ mod test {
     use super::*;
     use crate::tests::utils::MockAptosDataClient;
     use aptos_channels::message_queues::QueueStyle;
+    use aptos_config::config::RequestSchedulerConfig;
     use futures::StreamExt;
     use tokio::time::timeout;
 
@@ -1771,6 +1986,7 @@ mod test {
             pending_client_response.clone(),
             1000,
             stream_update_notifier.clone(),
+            RequestScheduler::new(RequestSchedulerConfig::default()),
         );
 
         // Wait for the request to complete
@@ -10,6 +10,9 @@ mod dynamic_prefetching;
 pub mod error;
 mod logging;
 mod metrics;
+pub mod request_scheduler;
+pub mod stream_audit;
+pub mod stream_diagnostics;
 mod stream_engine;
 pub mod streaming_client;
 pub mod streaming_service;
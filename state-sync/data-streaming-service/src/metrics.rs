@@ -63,6 +63,15 @@ pub static CREATE_SUBSCRIPTION_STREAM: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for follow streams that fell back to optimistic fetch (e.g., due to lag)
+pub static FOLLOW_STREAM_FALLBACKS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_data_streaming_service_follow_stream_fallbacks",
+        "Counters related to follow streams that fell back to optimistic fetch",
+    )
+    .unwrap()
+});
+
 /// Counter for the termination of existing data streams
 pub static TERMINATE_DATA_STREAM: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -103,6 +112,16 @@ pub static GLOBAL_DATA_SUMMARY_ERROR: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Histogram for tracking the time data requests are queued by the request scheduler
+pub static REQUEST_SCHEDULER_QUEUE_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_data_streaming_service_request_scheduler_queue_latency",
+        "Latencies of data requests queued by the request scheduler (per lane)",
+        &["request_lane"]
+    )
+    .unwrap()
+});
+
 /// Counter for tracking sent data requests
 pub static SENT_DATA_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -123,6 +142,16 @@ pub static SENT_DATA_REQUESTS_FOR_MISSING_DATA: Lazy<IntCounterVec> = Lazy::new(
     .unwrap()
 });
 
+/// Counter for tracking responses that only satisfied part of the request
+pub static PARTIAL_DATA_RESPONSES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_data_streaming_service_partial_data_responses",
+        "Counters related to responses that only satisfied part of the request",
+        &["request_type"]
+    )
+    .unwrap()
+});
+
 /// Counter for tracking data requests that were retried (including
 /// the new timeouts).
 pub static RETRIED_DATA_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
@@ -134,6 +163,16 @@ pub static RETRIED_DATA_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for tracking mid-stream failovers to alternate peers
+pub static DATA_STREAM_FAILOVERS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_data_streaming_service_data_stream_failovers",
+        "Counters related to data stream failovers to alternate peers",
+        &["request_type"]
+    )
+    .unwrap()
+});
+
 /// Counter for the number of max concurrent prefetching requests
 pub static MAX_CONCURRENT_PREFETCHING_REQUESTS: Lazy<IntGauge> = Lazy::new(|| {
     register_int_gauge!(
@@ -0,0 +1,259 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{data_notification::DataClientRequest, metrics};
+use aptos_config::config::RequestSchedulerConfig;
+use aptos_infallible::Mutex;
+use futures::channel::oneshot;
+use std::{
+    cmp::max,
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::Instant,
+};
+
+// The cost of dispatching a single request (before it is divided by the lane weight).
+// This is large enough to avoid precision loss for all reasonable lane weights.
+const REQUEST_COST: u128 = 1_000_000;
+
+/// The lanes of the request scheduler. Each data client request is dispatched
+/// through the lane of its request type, and the lanes share the available
+/// dispatch slots according to their weights (i.e., small, high priority
+/// requests are not queued behind large requests).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum RequestLane {
+    EpochEndingLedgerInfos,
+    NumberOfStates,
+    StateValues,
+    TransactionOutputs,
+    Transactions,
+    TransactionsOrOutputs,
+}
+
+impl RequestLane {
+    /// Returns the lane for the given request. Optimistic fetch and subscription
+    /// requests have no lane (they wait for new data at the peer, so they must
+    /// not occupy dispatch slots).
+    pub fn from_request(request: &DataClientRequest) -> Option<Self> {
+        match request {
+            DataClientRequest::EpochEndingLedgerInfos(_) => Some(Self::EpochEndingLedgerInfos),
+            DataClientRequest::EventsByKeyWithProof(_) => Some(Self::Transactions), // Events are served with their transactions
+            DataClientRequest::NumberOfStates(_) => Some(Self::NumberOfStates),
+            DataClientRequest::StateValuesWithProof(_) => Some(Self::StateValues),
+            DataClientRequest::TransactionOutputsWithProof(_) => Some(Self::TransactionOutputs),
+            DataClientRequest::TransactionsWithProof(_) => Some(Self::Transactions),
+            DataClientRequest::TransactionsOrOutputsWithProof(_) => {
+                Some(Self::TransactionsOrOutputs)
+            },
+            DataClientRequest::NewTransactionOutputsWithProof(_)
+            | DataClientRequest::NewTransactionsWithProof(_)
+            | DataClientRequest::NewTransactionsOrOutputsWithProof(_)
+            | DataClientRequest::SubscribeTransactionOutputsWithProof(_)
+            | DataClientRequest::SubscribeTransactionsWithProof(_)
+            | DataClientRequest::SubscribeTransactionsOrOutputsWithProof(_) => None,
+        }
+    }
+
+    /// Returns a summary label for the lane
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            Self::EpochEndingLedgerInfos => "epoch_ending_ledger_infos",
+            Self::NumberOfStates => "number_of_states",
+            Self::StateValues => "state_values",
+            Self::TransactionOutputs => "transaction_outputs",
+            Self::Transactions => "transactions",
+            Self::TransactionsOrOutputs => "transactions_or_outputs",
+        }
+    }
+
+    /// Returns the weight of the lane (as specified by the given config)
+    fn get_weight(&self, config: &RequestSchedulerConfig) -> u64 {
+        let weight = match self {
+            Self::EpochEndingLedgerInfos => config.epoch_ending_ledger_infos_weight,
+            Self::NumberOfStates => config.number_of_states_weight,
+            Self::StateValues => config.state_values_weight,
+            Self::TransactionOutputs => config.transaction_outputs_weight,
+            Self::Transactions => config.transactions_weight,
+            Self::TransactionsOrOutputs => config.transactions_or_outputs_weight,
+        };
+        max(weight, 1) // Every lane must make progress
+    }
+}
+
+/// A weighted fair queue that limits the number of in-flight data client
+/// requests across all data streams. Once the limit is reached, requests are
+/// queued in their lanes and dispatched in order of their virtual finish times
+/// (i.e., each lane receives a share of the dispatch slots proportional to its
+/// weight, and requests within a lane are dispatched in FIFO order).
+#[derive(Clone, Debug)]
+pub struct RequestScheduler {
+    config: RequestSchedulerConfig,
+    scheduler_state: Arc<Mutex<SchedulerState>>,
+}
+
+impl RequestScheduler {
+    pub fn new(config: RequestSchedulerConfig) -> Self {
+        Self {
+            config,
+            scheduler_state: Arc::new(Mutex::new(SchedulerState::default())),
+        }
+    }
+
+    /// Waits until the given request can be dispatched and returns a permit
+    /// that holds the dispatch slot (until the permit is dropped).
+    pub async fn acquire_permit(&self, request: &DataClientRequest) -> RequestPermit {
+        // Identify the lane of the request (requests without a lane are never queued)
+        let lane = match RequestLane::from_request(request) {
+            Some(lane) if self.config.enable_request_scheduler => lane,
+            _ => return RequestPermit::unscheduled(),
+        };
+
+        // Dispatch the request immediately, or queue it in its lane
+        let queue_start_time = Instant::now();
+        let mut queued_request = {
+            let mut scheduler_state = self.scheduler_state.lock();
+            let finish_tag = scheduler_state.next_finish_tag(lane, lane.get_weight(&self.config));
+            if scheduler_state.queued_requests.is_empty()
+                && scheduler_state.num_in_flight_requests < self.config.max_in_flight_requests
+            {
+                scheduler_state.num_in_flight_requests += 1;
+                scheduler_state.virtual_time = finish_tag;
+                return RequestPermit::new(self.clone());
+            }
+            QueuedRequest::new(scheduler_state.enqueue_request(finish_tag), self.clone())
+        };
+
+        // Wait until the request is dispatched (i.e., the dispatch slot is handed over)
+        if let Some(receiver) = queued_request.receiver.as_mut() {
+            let _ = receiver.await;
+        }
+        queued_request.receiver = None;
+        metrics::observe_duration(
+            &metrics::REQUEST_SCHEDULER_QUEUE_LATENCY,
+            lane.get_label(),
+            queue_start_time,
+        );
+
+        RequestPermit::new(self.clone())
+    }
+
+    /// Returns the number of in-flight (i.e., dispatched) requests
+    pub fn get_num_in_flight_requests(&self) -> u64 {
+        self.scheduler_state.lock().num_in_flight_requests
+    }
+
+    /// Returns the number of queued requests
+    pub fn get_num_queued_requests(&self) -> usize {
+        self.scheduler_state.lock().queued_requests.len()
+    }
+
+    /// Releases a dispatch slot by handing it over to the next queued request
+    /// (if any). Otherwise, the slot is freed.
+    fn release_dispatch_slot(&self) {
+        let mut scheduler_state = self.scheduler_state.lock();
+        while let Some(((finish_tag, _), sender)) = scheduler_state.queued_requests.pop_first() {
+            if sender.send(()).is_ok() {
+                scheduler_state.virtual_time = finish_tag;
+                return; // The dispatch slot was handed over
+            }
+        }
+        scheduler_state.num_in_flight_requests =
+            scheduler_state.num_in_flight_requests.saturating_sub(1);
+    }
+}
+
+/// The internal (mutable) state of the request scheduler
+#[derive(Debug, Default)]
+struct SchedulerState {
+    // The finish tag of the most recent request in each lane
+    lane_finish_tags: HashMap<RequestLane, u128>,
+
+    // A counter used to break ties between equal finish tags (in FIFO order)
+    next_queue_index: u64,
+
+    // The number of requests that currently hold a dispatch slot
+    num_in_flight_requests: u64,
+
+    // The requests waiting for a dispatch slot, ordered by finish tag
+    queued_requests: BTreeMap<(u128, u64), oneshot::Sender<()>>,
+
+    // The virtual time of the scheduler (i.e., the finish tag of the last dispatched request)
+    virtual_time: u128,
+}
+
+impl SchedulerState {
+    /// Calculates (and records) the virtual finish tag for a new request in the given lane
+    fn next_finish_tag(&mut self, lane: RequestLane, weight: u64) -> u128 {
+        let lane_finish_tag = self.lane_finish_tags.entry(lane).or_default();
+        let finish_tag = max(self.virtual_time, *lane_finish_tag) + REQUEST_COST / weight as u128;
+        *lane_finish_tag = finish_tag;
+        finish_tag
+    }
+
+    /// Queues a request with the given finish tag and returns the dispatch receiver
+    fn enqueue_request(&mut self, finish_tag: u128) -> oneshot::Receiver<()> {
+        let (sender, receiver) = oneshot::channel();
+        self.queued_requests
+            .insert((finish_tag, self.next_queue_index), sender);
+        self.next_queue_index += 1;
+        receiver
+    }
+}
+
+/// A request waiting in the queue for a dispatch slot. If the request is
+/// dropped (e.g., the request task is aborted) after it was handed a slot,
+/// the slot is released.
+struct QueuedRequest {
+    receiver: Option<oneshot::Receiver<()>>,
+    request_scheduler: RequestScheduler,
+}
+
+impl QueuedRequest {
+    fn new(receiver: oneshot::Receiver<()>, request_scheduler: RequestScheduler) -> Self {
+        Self {
+            receiver: Some(receiver),
+            request_scheduler,
+        }
+    }
+}
+
+impl Drop for QueuedRequest {
+    fn drop(&mut self) {
+        if let Some(mut receiver) = self.receiver.take() {
+            receiver.close(); // Ensure no slot is handed over after this point
+            if let Ok(Some(())) = receiver.try_recv() {
+                self.request_scheduler.release_dispatch_slot();
+            }
+        }
+    }
+}
+
+/// A permit to dispatch a data client request. The dispatch slot (if any)
+/// is released when the permit is dropped.
+#[must_use = "The dispatch slot is released when the permit is dropped"]
+pub struct RequestPermit {
+    request_scheduler: Option<RequestScheduler>,
+}
+
+impl RequestPermit {
+    fn new(request_scheduler: RequestScheduler) -> Self {
+        Self {
+            request_scheduler: Some(request_scheduler),
+        }
+    }
+
+    /// Creates a permit for a request that bypasses the scheduler
+    fn unscheduled() -> Self {
+        Self {
+            request_scheduler: None,
+        }
+    }
+}
+
+impl Drop for RequestPermit {
+    fn drop(&mut self) {
+        if let Some(request_scheduler) = self.request_scheduler.take() {
+            request_scheduler.release_dispatch_slot();
+        }
+    }
+}
@@ -0,0 +1,347 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Records an audit log for each data stream (e.g., the requested data ranges,
+//! the peers that served each response, response failures and retries). This
+//! allows operators investigating state sync anomalies to reconstruct exactly
+//! which peers supplied which data. Note: the data streaming service is only
+//! notified about bad responses (e.g., responses that failed proof verification).
+//! Thus, any response without negative feedback was accepted by the client.
+
+use crate::{
+    data_notification::{DataClientRequest, NotificationId},
+    data_stream::DataStreamId,
+    streaming_client::StreamRequest,
+};
+use aptos_config::{config::DataStreamingServiceConfig, network_id::PeerNetworkId};
+use aptos_data_client::interface::{ResponseContext, ResponseError, ResponseId};
+use aptos_infallible::Mutex;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, VecDeque},
+    sync::Arc,
+};
+
+// The peer label used when the serving peer of a response is unknown
+const UNKNOWN_PEER: &str = "unknown";
+
+/// A single event recorded in a stream audit log
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "event")]
+pub enum StreamAuditEvent {
+    /// A data client request was sent to the network
+    RequestSent { request: String, retry: bool },
+    /// A data client request failed (e.g., timed out)
+    RequestFailed { request: String, error: String },
+    /// A response was received and sent along the stream
+    ResponseReceived {
+        request: String,
+        response_id: ResponseId,
+        peer: String,
+        notification_id: Option<NotificationId>,
+    },
+    /// A response only satisfied part of the request (and the remaining items were requested)
+    PartialResponse {
+        request: String,
+        num_expected_items: u64,
+        num_received_items: u64,
+    },
+    /// A response was found to be invalid (e.g., the proof verification failed)
+    ResponseRejected {
+        response_id: ResponseId,
+        peer: String,
+        error: String,
+    },
+    /// The stream failed over from a degraded peer (and re-sent its in-flight requests)
+    StreamFailover {
+        peer: String,
+        num_resent_requests: u64,
+    },
+    /// The stream was terminated
+    StreamTerminated { reason: String },
+}
+
+/// A timestamped entry in a stream audit log
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct StreamAuditEntry {
+    pub timestamp_usecs: u64,
+    #[serde(flatten)]
+    pub event: StreamAuditEvent,
+}
+
+/// A summary of the responses served by a single peer along a stream
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct PeerAuditSummary {
+    pub num_responses: u64,
+    pub num_rejected_responses: u64,
+}
+
+/// The audit log of a single data stream
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct StreamAuditLog {
+    pub data_stream_id: DataStreamId,
+    pub stream_request: String,
+    pub creation_time_usecs: u64,
+    pub termination_reason: Option<String>,
+    pub num_requests_sent: u64,
+    pub num_requests_retried: u64,
+    pub num_requests_failed: u64,
+    pub num_responses: u64,
+    pub num_partial_responses: u64,
+    pub num_rejected_responses: u64,
+    pub num_failovers: u64,
+    pub peer_summaries: BTreeMap<String, PeerAuditSummary>,
+    pub num_dropped_entries: u64, // The number of entries dropped (to bound memory usage)
+    pub entries: VecDeque<StreamAuditEntry>,
+}
+
+impl StreamAuditLog {
+    fn new(
+        data_stream_id: DataStreamId,
+        stream_request: &StreamRequest,
+        creation_time_usecs: u64,
+    ) -> Self {
+        Self {
+            data_stream_id,
+            stream_request: format!("{:?}", stream_request),
+            creation_time_usecs,
+            termination_reason: None,
+            num_requests_sent: 0,
+            num_requests_retried: 0,
+            num_requests_failed: 0,
+            num_responses: 0,
+            num_partial_responses: 0,
+            num_rejected_responses: 0,
+            num_failovers: 0,
+            peer_summaries: BTreeMap::new(),
+            num_dropped_entries: 0,
+            entries: VecDeque::new(),
+        }
+    }
+
+    /// Updates the summaries and appends the event to the log
+    fn record_event(&mut self, event: StreamAuditEvent, timestamp_usecs: u64, max_entries: usize) {
+        // Update the summaries
+        match &event {
+            StreamAuditEvent::RequestSent { retry, .. } => {
+                self.num_requests_sent += 1;
+                if *retry {
+                    self.num_requests_retried += 1;
+                }
+            },
+            StreamAuditEvent::RequestFailed { .. } => self.num_requests_failed += 1,
+            StreamAuditEvent::ResponseReceived { peer, .. } => {
+                self.num_responses += 1;
+                self.peer_summaries
+                    .entry(peer.clone())
+                    .or_default()
+                    .num_responses += 1;
+            },
+            StreamAuditEvent::PartialResponse { .. } => self.num_partial_responses += 1,
+            StreamAuditEvent::ResponseRejected { peer, .. } => {
+                self.num_rejected_responses += 1;
+                self.peer_summaries
+                    .entry(peer.clone())
+                    .or_default()
+                    .num_rejected_responses += 1;
+            },
+            StreamAuditEvent::StreamFailover { .. } => self.num_failovers += 1,
+            StreamAuditEvent::StreamTerminated { reason } => {
+                self.termination_reason = Some(reason.clone());
+            },
+        }
+
+        // Append the entry (and drop the oldest entries if required)
+        self.entries.push_back(StreamAuditEntry {
+            timestamp_usecs,
+            event,
+        });
+        while self.entries.len() > max_entries {
+            self.entries.pop_front();
+            self.num_dropped_entries += 1;
+        }
+    }
+}
+
+/// Records audit events for a single data stream. If stream
+/// auditing is disabled, all recorded events are ignored.
+#[derive(Clone, Debug)]
+pub struct StreamAuditor {
+    audit_log: Option<Arc<Mutex<StreamAuditLog>>>,
+    max_entries: usize,
+    time_service: TimeService,
+}
+
+impl StreamAuditor {
+    /// Creates an auditor that ignores all events
+    pub fn disabled(time_service: TimeService) -> Self {
+        Self {
+            audit_log: None,
+            max_entries: 0,
+            time_service,
+        }
+    }
+
+    /// Records that the given request was sent to the network
+    pub fn record_request_sent(&self, request: &DataClientRequest, retry: bool) {
+        self.record_event(|| StreamAuditEvent::RequestSent {
+            request: format!("{:?}", request),
+            retry,
+        });
+    }
+
+    /// Records that the given request failed with the specified error
+    pub fn record_request_failed(
+        &self,
+        request: &DataClientRequest,
+        error: &aptos_data_client::error::Error,
+    ) {
+        self.record_event(|| StreamAuditEvent::RequestFailed {
+            request: format!("{:?}", request),
+            error: error.to_string(),
+        });
+    }
+
+    /// Records that a response was received for the given request
+    pub fn record_response_received(
+        &self,
+        request: &DataClientRequest,
+        response_context: &ResponseContext,
+        notification_id: Option<NotificationId>,
+    ) {
+        self.record_event(|| StreamAuditEvent::ResponseReceived {
+            request: format!("{:?}", request),
+            response_id: response_context.id,
+            peer: get_peer_label(response_context),
+            notification_id,
+        });
+    }
+
+    /// Records that a response only satisfied part of the given request
+    pub fn record_partial_response(
+        &self,
+        request: &DataClientRequest,
+        num_expected_items: u64,
+        num_received_items: u64,
+    ) {
+        self.record_event(|| StreamAuditEvent::PartialResponse {
+            request: format!("{:?}", request),
+            num_expected_items,
+            num_received_items,
+        });
+    }
+
+    /// Records that the given response was rejected
+    pub fn record_response_rejected(
+        &self,
+        response_context: &ResponseContext,
+        response_error: &ResponseError,
+    ) {
+        self.record_event(|| StreamAuditEvent::ResponseRejected {
+            response_id: response_context.id,
+            peer: get_peer_label(response_context),
+            error: format!("{:?}", response_error),
+        });
+    }
+
+    /// Records that the stream failed over from the given (degraded) peer
+    pub fn record_stream_failover(&self, peer: &PeerNetworkId, num_resent_requests: u64) {
+        self.record_event(|| StreamAuditEvent::StreamFailover {
+            peer: peer.to_string(),
+            num_resent_requests,
+        });
+    }
+
+    /// Records that the stream was terminated for the given reason
+    pub fn record_stream_terminated(&self, reason: &str) {
+        self.record_event(|| StreamAuditEvent::StreamTerminated {
+            reason: reason.into(),
+        });
+    }
+
+    /// Records the event produced by the given function (if auditing is enabled)
+    fn record_event<F: FnOnce() -> StreamAuditEvent>(&self, create_event: F) {
+        if let Some(audit_log) = &self.audit_log {
+            let timestamp_usecs = self.time_service.now_unix_time().as_micros() as u64;
+            audit_log
+                .lock()
+                .record_event(create_event(), timestamp_usecs, self.max_entries);
+        }
+    }
+}
+
+/// Holds the audit logs of the most recent data streams. This is shared
+/// between the data streaming service and any external readers (e.g.,
+/// the inspection service).
+#[derive(Clone, Debug)]
+pub struct StreamAuditLogs {
+    audit_logs: Arc<Mutex<BTreeMap<DataStreamId, Arc<Mutex<StreamAuditLog>>>>>,
+    max_audited_streams: usize,
+    max_entries_per_stream: usize,
+    time_service: TimeService,
+}
+
+impl StreamAuditLogs {
+    pub fn new(config: DataStreamingServiceConfig, time_service: TimeService) -> Self {
+        Self {
+            audit_logs: Arc::new(Mutex::new(BTreeMap::new())),
+            max_audited_streams: config.max_audited_streams as usize,
+            max_entries_per_stream: config.max_stream_audit_log_entries as usize,
+            time_service,
+        }
+    }
+
+    /// Creates a new audit log (and auditor) for the given stream. If the
+    /// number of audit logs exceeds the maximum, the oldest logs are dropped.
+    pub fn create_stream_auditor(
+        &self,
+        data_stream_id: DataStreamId,
+        stream_request: &StreamRequest,
+    ) -> StreamAuditor {
+        // Check if auditing is disabled
+        if self.max_audited_streams == 0 {
+            return StreamAuditor::disabled(self.time_service.clone());
+        }
+
+        // Create and store the audit log
+        let creation_time_usecs = self.time_service.now_unix_time().as_micros() as u64;
+        let audit_log = Arc::new(Mutex::new(StreamAuditLog::new(
+            data_stream_id,
+            stream_request,
+            creation_time_usecs,
+        )));
+        let mut audit_logs = self.audit_logs.lock();
+        audit_logs.insert(data_stream_id, audit_log.clone());
+
+        // Drop the oldest audit logs (stream IDs are monotonically increasing)
+        while audit_logs.len() > self.max_audited_streams {
+            audit_logs.pop_first();
+        }
+
+        StreamAuditor {
+            audit_log: Some(audit_log),
+            max_entries: self.max_entries_per_stream,
+            time_service: self.time_service.clone(),
+        }
+    }
+
+    /// Returns a snapshot of all audit logs (ordered from newest to oldest)
+    pub fn get_audit_logs(&self) -> Vec<StreamAuditLog> {
+        self.audit_logs
+            .lock()
+            .values()
+            .rev()
+            .map(|audit_log| audit_log.lock().clone())
+            .collect()
+    }
+}
+
+/// Returns the label of the peer that served the given response
+fn get_peer_label(response_context: &ResponseContext) -> String {
+    response_context
+        .response_callback
+        .get_peer()
+        .map(|peer| peer.to_string())
+        .unwrap_or_else(|| UNKNOWN_PEER.into())
+}
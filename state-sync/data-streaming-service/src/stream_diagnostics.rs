@@ -0,0 +1,103 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Exposes the live state of all active data streams (e.g., the stream
+//! targets, the in-flight requests, retry counts and the last errors). The
+//! state is refreshed by the data streaming service every time it checks the
+//! progress of the streams, which allows operators to debug stuck streams
+//! without trace logging. Note: the peer that serves a request is only known
+//! once the response has been received (the data client selects the peer).
+
+use crate::data_stream::DataStreamId;
+use aptos_infallible::Mutex;
+use aptos_types::transaction::Version;
+use serde::Serialize;
+use std::{collections::BTreeMap, sync::Arc};
+
+/// The progress of a data stream. Depending on the stream type, the
+/// indices are versions, epochs or state indices.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct StreamProgress {
+    pub target_version: Option<Version>, // The version the stream is syncing to (if known)
+    pub next_request_index: u64,         // All data before this index has been requested
+    pub next_stream_index: u64,          // All data before this index has been sent to the client
+    pub end_index: Option<u64>,          // The last index of the stream (if known)
+    pub stream_is_complete: bool,
+}
+
+/// The status of a request sent by a data stream
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SentRequestStatus {
+    /// The request is still waiting for a response
+    InFlight,
+    /// A response was received, but it has not yet been processed
+    ResponseReceived,
+    /// The request failed, but the failure has not yet been processed
+    RequestFailed,
+}
+
+/// A request sent by a data stream (that has not yet been processed)
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct SentRequestDiagnostics {
+    pub request: String,
+    pub status: SentRequestStatus,
+    pub peer: Option<String>, // The peer that served the response (if known)
+}
+
+/// The last error encountered by a data stream
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct StreamErrorDiagnostics {
+    pub timestamp_usecs: u64,
+    pub error: String,
+}
+
+/// A snapshot of the live state of a single data stream
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct DataStreamDiagnostics {
+    pub data_stream_id: DataStreamId,
+    pub stream_request: String,
+    pub stream_progress: StreamProgress,
+    pub num_in_flight_requests: u64,
+    pub requests_per_peer: BTreeMap<String, u64>, // The unprocessed responses served by each peer
+    pub sent_requests: Vec<SentRequestDiagnostics>, // Ordered from the head of the request queue
+    pub request_failure_count: u64, // The retry count of the request at the head of the queue
+    pub max_request_retry: u64,
+    pub max_concurrent_requests: u64,
+    pub num_stream_failovers: u64,
+    pub primary_peer: Option<String>,
+    pub last_error: Option<StreamErrorDiagnostics>,
+}
+
+/// Holds the diagnostics of all active data streams. This is shared
+/// between the data streaming service and any external readers (e.g.,
+/// the inspection service).
+#[derive(Clone, Debug, Default)]
+pub struct StreamDiagnostics {
+    stream_diagnostics: Arc<Mutex<BTreeMap<DataStreamId, DataStreamDiagnostics>>>,
+}
+
+impl StreamDiagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the diagnostics with those of the currently active streams
+    pub fn update_stream_diagnostics(&self, stream_diagnostics: Vec<DataStreamDiagnostics>) {
+        *self.stream_diagnostics.lock() = stream_diagnostics
+            .into_iter()
+            .map(|diagnostics| (diagnostics.data_stream_id, diagnostics))
+            .collect();
+    }
+
+    /// Returns a snapshot of the diagnostics of all active streams
+    /// (ordered from newest to oldest).
+    pub fn get_stream_diagnostics(&self) -> Vec<DataStreamDiagnostics> {
+        self.stream_diagnostics
+            .lock()
+            .values()
+            .rev()
+            .cloned()
+            .collect()
+    }
+}
@@ -6,13 +6,13 @@ use crate::{
     data_notification::{
         DataClientRequest,
         DataClientRequest::{
-            EpochEndingLedgerInfos, NewTransactionOutputsWithProof,
+            EpochEndingLedgerInfos, EventsByKeyWithProof, NewTransactionOutputsWithProof,
             NewTransactionsOrOutputsWithProof, NewTransactionsWithProof, NumberOfStates,
             StateValuesWithProof, SubscribeTransactionOutputsWithProof,
             SubscribeTransactionsOrOutputsWithProof, SubscribeTransactionsWithProof,
             TransactionOutputsWithProof, TransactionsOrOutputsWithProof, TransactionsWithProof,
         },
-        DataNotification, DataPayload, EpochEndingLedgerInfosRequest,
+        DataNotification, DataPayload, EpochEndingLedgerInfosRequest, EventsByKeyWithProofRequest,
         NewTransactionOutputsWithProofRequest, NewTransactionsOrOutputsWithProofRequest,
         NewTransactionsWithProofRequest, NumberOfStatesRequest, StateValuesWithProofRequest,
         SubscribeTransactionOutputsWithProofRequest,
@@ -23,8 +23,10 @@ use crate::{
     error::Error,
     logging::{LogEntry, LogEvent, LogSchema},
     metrics,
+    stream_diagnostics::StreamProgress,
     streaming_client::{
-        Epoch, GetAllEpochEndingLedgerInfosRequest, GetAllStatesRequest, StreamRequest,
+        Epoch, GetAllEpochEndingLedgerInfosRequest, GetAllEventsByKeyRequest, GetAllStatesRequest,
+        StreamRequest,
     },
 };
 use aptos_config::config::DataStreamingServiceConfig;
@@ -81,6 +83,9 @@ pub trait DataStreamEngine {
     /// available in the given advertised data.
     fn is_remaining_data_available(&self, advertised_data: &AdvertisedData) -> Result<bool, Error>;
 
+    /// Returns the progress of the stream (e.g., for stream diagnostics).
+    fn get_stream_progress(&self) -> StreamProgress;
+
     /// Returns true iff the stream has sent all data to the stream listener.
     fn is_stream_complete(&self) -> bool;
 
@@ -120,6 +125,7 @@ pub trait DataStreamEngine {
 pub enum StreamEngine {
     ContinuousTransactionStreamEngine,
     EpochEndingStreamEngine,
+    EventsByKeyStreamEngine,
     StateStreamEngine,
     TransactionStreamEngine,
 }
@@ -131,7 +137,8 @@ impl StreamEngine {
         advertised_data: &AdvertisedData,
     ) -> Result<Self, Error> {
         match stream_request {
-            StreamRequest::ContinuouslyStreamTransactionOutputs(_) => Ok(
+            StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+            | StreamRequest::FollowTransactionOutputs(_) => Ok(
                 ContinuousTransactionStreamEngine::new(data_stream_config, stream_request)?.into(),
             ),
             StreamRequest::ContinuouslyStreamTransactions(_) => Ok(
@@ -144,6 +151,9 @@ impl StreamEngine {
             StreamRequest::GetAllEpochEndingLedgerInfos(request) => {
                 Ok(EpochEndingStreamEngine::new(request, advertised_data)?.into())
             },
+            StreamRequest::GetAllEventsByKey(request) => {
+                Ok(EventsByKeyStreamEngine::new(request)?.into())
+            },
             StreamRequest::GetAllTransactionOutputs(_) => {
                 Ok(TransactionStreamEngine::new(stream_request)?.into())
             },
@@ -159,6 +169,22 @@ impl StreamEngine {
             ))),
         }
     }
+
+    /// Returns the max lag (in versions) tolerated by the stream before it
+    /// falls back to optimistic fetch. This is only set for follow streams.
+    pub fn get_max_lag_versions(&self) -> Option<u64> {
+        match self {
+            StreamEngine::ContinuousTransactionStreamEngine(stream_engine) => {
+                match &stream_engine.request {
+                    StreamRequest::FollowTransactionOutputs(request) => {
+                        Some(request.max_lag_versions)
+                    },
+                    _ => None,
+                }
+            },
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -274,6 +300,18 @@ impl DataStreamEngine for StateStreamEngine {
         ))
     }
 
+    fn get_stream_progress(&self) -> StreamProgress {
+        StreamProgress {
+            target_version: Some(self.request.version),
+            next_request_index: self.next_request_index,
+            next_stream_index: self.next_stream_index,
+            end_index: self
+                .number_of_states
+                .map(|number_of_states| number_of_states.saturating_sub(1)),
+            stream_is_complete: self.stream_is_complete,
+        }
+    }
+
     fn is_stream_complete(&self) -> bool {
         self.stream_is_complete
     }
@@ -390,6 +428,10 @@ pub struct ContinuousTransactionStreamEngine {
     // The active subscription stream (if it exists)
     active_subscription_stream: Option<SubscriptionStream>,
 
+    // True iff the stream is a follow stream that exceeded its max lag and
+    // fell back to optimistic fetch (until it catches up again).
+    pub follow_stream_fallback: bool,
+
     // The next version and epoch that we're waiting to send to the
     // client along the stream. All versions before this have been sent.
     pub next_stream_version_and_epoch: (Version, Epoch),
@@ -415,6 +457,9 @@ impl ContinuousTransactionStreamEngine {
             StreamRequest::ContinuouslyStreamTransactionOutputs(request) => {
                 Self::calculate_next_version_and_epoch(request.known_version, request.known_epoch)?
             },
+            StreamRequest::FollowTransactionOutputs(request) => {
+                Self::calculate_next_version_and_epoch(request.known_version, request.known_epoch)?
+            },
             StreamRequest::ContinuouslyStreamTransactionsOrOutputs(request) => {
                 Self::calculate_next_version_and_epoch(request.known_version, request.known_epoch)?
             },
@@ -428,6 +473,7 @@ impl ContinuousTransactionStreamEngine {
             end_of_epoch_requested: false,
             optimistic_fetch_requested: false,
             active_subscription_stream: None,
+            follow_stream_fallback: false,
             next_stream_version_and_epoch: (next_version, next_epoch),
             next_request_version_and_epoch: (next_version, next_epoch),
             stream_is_complete: false,
@@ -465,6 +511,9 @@ impl ContinuousTransactionStreamEngine {
                     return Ok(Some(target.clone()));
                 }
             },
+            StreamRequest::FollowTransactionOutputs(_) => {
+                // Follow streams never have a final target
+            },
             request => invalid_stream_request!(request),
         };
 
@@ -483,6 +532,17 @@ impl ContinuousTransactionStreamEngine {
         }
     }
 
+    /// Returns true iff new data should be fetched using subscription streams
+    /// (instead of optimistic fetch requests). Follow streams always use
+    /// subscriptions, unless they have fallen back to optimistic fetch.
+    fn use_subscription_streaming(&self) -> bool {
+        if matches!(self.request, StreamRequest::FollowTransactionOutputs(_)) {
+            !self.follow_stream_fallback
+        } else {
+            self.data_streaming_config.enable_subscription_streaming
+        }
+    }
+
     fn get_target_ledger_info(&self) -> Result<&LedgerInfoWithSignatures, Error> {
         self.current_target_ledger_info.as_ref().ok_or_else(|| {
             Error::UnexpectedErrorEncountered("No current target ledger info found!".into())
@@ -642,7 +702,8 @@ impl ContinuousTransactionStreamEngine {
                     include_events: request.include_events,
                 })
             },
-            StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
+            StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+            | StreamRequest::FollowTransactionOutputs(_) => {
                 NewTransactionOutputsWithProof(NewTransactionOutputsWithProofRequest {
                     known_version,
                     known_epoch,
@@ -711,7 +772,8 @@ impl ContinuousTransactionStreamEngine {
                         subscription_stream_index,
                     })
                 },
-                StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
+                StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+                | StreamRequest::FollowTransactionOutputs(_) => {
                     SubscribeTransactionOutputsWithProof(
                         SubscribeTransactionOutputsWithProofRequest {
                             known_version,
@@ -831,6 +893,7 @@ impl ContinuousTransactionStreamEngine {
         } else if matches!(
             self.request,
             StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+                | StreamRequest::FollowTransactionOutputs(_)
         ) && matches!(
             client_request,
             DataClientRequest::NewTransactionOutputsWithProof(_)
@@ -882,6 +945,23 @@ impl ContinuousTransactionStreamEngine {
         self.active_subscription_stream = None;
         update_terminated_subscription_metrics(request_error.get_label());
 
+        // If a follow stream is lagging, fall back to optimistic fetch
+        if matches!(self.request, StreamRequest::FollowTransactionOutputs(_))
+            && matches!(
+                request_error,
+                aptos_data_client::error::Error::SubscriptionStreamIsLagging(_)
+            )
+        {
+            info!(
+                (LogSchema::new(LogEntry::RequestError).message(&format!(
+                    "The follow stream is lagging. Falling back to optimistic fetch: {:?}",
+                    request_error
+                )))
+            );
+            self.follow_stream_fallback = true;
+            metrics::FOLLOW_STREAM_FALLBACKS.inc();
+        }
+
         // Log the error based on the request type
         if matches!(
             self.request,
@@ -899,6 +979,7 @@ impl ContinuousTransactionStreamEngine {
         } else if matches!(
             self.request,
             StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+                | StreamRequest::FollowTransactionOutputs(_)
         ) && matches!(
             client_request,
             DataClientRequest::SubscribeTransactionOutputsWithProof(_)
@@ -997,6 +1078,7 @@ impl ContinuousTransactionStreamEngine {
             StreamRequest::ContinuouslyStreamTransactionsOrOutputs(request) => {
                 request.target.clone()
             },
+            StreamRequest::FollowTransactionOutputs(_) => None,
             request => invalid_stream_request!(request),
         };
         if let Some(target) = stream_request_target {
@@ -1057,7 +1139,8 @@ impl ContinuousTransactionStreamEngine {
                     }
                 }
             },
-            StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
+            StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+            | StreamRequest::FollowTransactionOutputs(_) => {
                 for client_request in client_requests {
                     match client_request {
                         DataClientRequest::TransactionOutputsWithProof(request) => {
@@ -1160,7 +1243,8 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
                         .optimal_chunk_sizes
                         .transaction_chunk_size
                 },
-                StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
+                StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+                | StreamRequest::FollowTransactionOutputs(_) => {
                     global_data_summary
                         .optimal_chunk_sizes
                         .transaction_output_chunk_size
@@ -1184,7 +1268,7 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
         } else {
             // We don't have a target. We should either send an optimistic
             // fetch request or start a new subscription stream.
-            if self.data_streaming_config.enable_subscription_streaming {
+            if self.use_subscription_streaming() {
                 // Start a new subscription stream and send the first set of requests
                 self.start_active_subscription_stream(unique_id_generator)?;
                 self.create_subscription_stream_requests(max_number_of_requests)?
@@ -1202,9 +1286,8 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
     fn is_remaining_data_available(&self, advertised_data: &AdvertisedData) -> Result<bool, Error> {
         let advertised_ranges = match &self.request {
             StreamRequest::ContinuouslyStreamTransactions(_) => &advertised_data.transactions,
-            StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
-                &advertised_data.transaction_outputs
-            },
+            StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+            | StreamRequest::FollowTransactionOutputs(_) => &advertised_data.transaction_outputs,
             StreamRequest::ContinuouslyStreamTransactionsOrOutputs(_) => {
                 &advertised_data.transaction_outputs
             },
@@ -1220,6 +1303,20 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
         ))
     }
 
+    fn get_stream_progress(&self) -> StreamProgress {
+        let target_version = self
+            .current_target_ledger_info
+            .as_ref()
+            .map(|target_ledger_info| target_ledger_info.ledger_info().version());
+        StreamProgress {
+            target_version,
+            next_request_index: self.next_request_version_and_epoch.0,
+            next_stream_index: self.next_stream_version_and_epoch.0,
+            end_index: target_version,
+            stream_is_complete: self.stream_is_complete,
+        }
+    }
+
     fn is_stream_complete(&self) -> bool {
         self.stream_is_complete
     }
@@ -1229,9 +1326,9 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
         client_request: &DataClientRequest,
         request_error: aptos_data_client::error::Error,
     ) -> Result<(), Error> {
-        // If subscription streaming is enabled, the timeout should be for
+        // If subscription streaming is in use, the timeout should be for
         // subscription data. Otherwise, it should be for optimistic fetch data.
-        if self.data_streaming_config.enable_subscription_streaming {
+        if client_request.is_subscription_request() {
             self.handle_subscription_error(client_request, request_error)
         } else {
             self.handle_optimistic_fetch_error(client_request, request_error)
@@ -1251,6 +1348,9 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
             self.end_of_epoch_requested = false;
         } else if self.optimistic_fetch_requested {
             self.optimistic_fetch_requested = false;
+
+            // The stream has caught up, so follow streams can resume subscriptions
+            self.follow_stream_fallback = false;
         }
 
         // Update the metrics for the number of received items
@@ -1274,7 +1374,8 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
                 request => invalid_stream_request!(request),
             },
             NewTransactionOutputsWithProof(request) => match &self.request {
-                StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
+                StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+                | StreamRequest::FollowTransactionOutputs(_) => {
                     let data_notification = self.create_notification_for_optimistic_fetch_data(
                         request.known_version,
                         client_response_payload,
@@ -1296,7 +1397,8 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
                 request => invalid_stream_request!(request),
             },
             SubscribeTransactionOutputsWithProof(request) => match &self.request {
-                StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
+                StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+                | StreamRequest::FollowTransactionOutputs(_) => {
                     let data_notification = self.create_notification_for_subscription_data(
                         request.subscription_stream_index,
                         client_response_payload,
@@ -1341,7 +1443,8 @@ impl DataStreamEngine for ContinuousTransactionStreamEngine {
                 request => invalid_stream_request!(request),
             },
             TransactionOutputsWithProof(request) => match &self.request {
-                StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
+                StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+                | StreamRequest::FollowTransactionOutputs(_) => {
                     let data_notification = self.create_notification_for_continuous_data(
                         request.start_version,
                         request.end_version,
@@ -1477,6 +1580,16 @@ impl DataStreamEngine for EpochEndingStreamEngine {
         ))
     }
 
+    fn get_stream_progress(&self) -> StreamProgress {
+        StreamProgress {
+            target_version: None,
+            next_request_index: self.next_request_epoch,
+            next_stream_index: self.next_stream_epoch,
+            end_index: Some(self.end_epoch),
+            stream_is_complete: self.stream_is_complete,
+        }
+    }
+
     fn is_stream_complete(&self) -> bool {
         self.stream_is_complete
     }
@@ -1546,6 +1659,185 @@ impl DataStreamEngine for EpochEndingStreamEngine {
     }
 }
 
+#[derive(Clone, Debug)]
+pub struct EventsByKeyStreamEngine {
+    // The original events by key request made by the client
+    pub request: GetAllEventsByKeyRequest,
+
+    // The next sequence number that we're waiting to send to the client
+    // along the stream. All sequence numbers before this have been sent.
+    pub next_stream_sequence_number: u64,
+
+    // The next sequence number that we're waiting to request from the
+    // network. All sequence numbers before this have already been requested.
+    pub next_request_sequence_number: u64,
+
+    // True iff all data has been sent across the stream.
+    pub stream_is_complete: bool,
+}
+
+impl EventsByKeyStreamEngine {
+    fn new(request: &GetAllEventsByKeyRequest) -> Result<Self, Error> {
+        if request.end_sequence_number < request.start_sequence_number {
+            return Err(Error::UnsupportedRequestEncountered(format!(
+                "The end sequence number is lower than the start sequence number! Start: {:?}, end: {:?}",
+                request.start_sequence_number, request.end_sequence_number
+            )));
+        }
+
+        Ok(EventsByKeyStreamEngine {
+            request: request.clone(),
+            next_stream_sequence_number: request.start_sequence_number,
+            next_request_sequence_number: request.start_sequence_number,
+            stream_is_complete: false,
+        })
+    }
+
+    fn update_request_tracking(
+        &mut self,
+        client_requests: &[DataClientRequest],
+    ) -> Result<(), Error> {
+        for client_request in client_requests {
+            match client_request {
+                EventsByKeyWithProof(request) => {
+                    self.next_request_sequence_number =
+                        request.end_sequence_number.checked_add(1).ok_or_else(|| {
+                            Error::IntegerOverflow(
+                                "Next request sequence number has overflown!".into(),
+                            )
+                        })?;
+                },
+                request => invalid_client_request!(request, self),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl DataStreamEngine for EventsByKeyStreamEngine {
+    fn create_data_client_requests(
+        &mut self,
+        max_number_of_requests: u64,
+        global_data_summary: &GlobalDataSummary,
+        _unique_id_generator: Arc<U64IdGenerator>,
+    ) -> Result<Vec<DataClientRequest>, Error> {
+        // Create the client requests. Each event may have been emitted by a
+        // different transaction, so we use the transaction chunk size.
+        let client_requests = create_data_client_request_batch(
+            self.next_request_sequence_number,
+            self.request.end_sequence_number,
+            max_number_of_requests,
+            global_data_summary
+                .optimal_chunk_sizes
+                .transaction_chunk_size,
+            self.clone().into(),
+        )?;
+        self.update_request_tracking(&client_requests)?;
+
+        Ok(client_requests)
+    }
+
+    fn is_remaining_data_available(&self, advertised_data: &AdvertisedData) -> Result<bool, Error> {
+        // The versions of the remaining events are unknown, so we can only
+        // verify that transactions are advertised at the proof version.
+        let proof_version = self.request.proof_version;
+        Ok(AdvertisedData::contains_range(
+            proof_version,
+            proof_version,
+            &advertised_data.transactions,
+        ))
+    }
+
+    fn get_stream_progress(&self) -> StreamProgress {
+        StreamProgress {
+            target_version: Some(self.request.proof_version),
+            next_request_index: self.next_request_sequence_number,
+            next_stream_index: self.next_stream_sequence_number,
+            end_index: Some(self.request.end_sequence_number),
+            stream_is_complete: self.stream_is_complete,
+        }
+    }
+
+    fn is_stream_complete(&self) -> bool {
+        self.stream_is_complete
+    }
+
+    fn transform_client_response_into_notification(
+        &mut self,
+        client_request: &DataClientRequest,
+        client_response_payload: ResponsePayload,
+        notification_id_generator: Arc<U64IdGenerator>,
+    ) -> Result<Option<DataNotification>, Error> {
+        // Update the metrics for the number of received items
+        update_response_chunk_size_metrics(client_request, &client_response_payload);
+
+        // Handle and transform the response
+        match client_request {
+            EventsByKeyWithProof(request) => {
+                // Verify the client request indices
+                verify_client_request_indices(
+                    self.next_stream_sequence_number,
+                    request.start_sequence_number,
+                    request.end_sequence_number,
+                )?;
+
+                // Identify the last received sequence number and bound it appropriately
+                let last_received_sequence_number = match &client_response_payload {
+                    ResponsePayload::EventsByKeyWithProof(events_by_key_with_proof) => {
+                        // Verify that we received at least one event
+                        let num_received_events = events_by_key_with_proof.get_num_events() as u64;
+                        if num_received_events == 0 {
+                            return Err(Error::AptosDataClientResponseIsInvalid(format!(
+                                "Received an empty events by key response! Request: {:?}",
+                                client_request
+                            )));
+                        }
+
+                        // Return the last sequence number
+                        request
+                            .start_sequence_number
+                            .checked_add(num_received_events - 1)
+                            .ok_or_else(|| {
+                                Error::IntegerOverflow(
+                                    "Last received sequence number has overflown!".into(),
+                                )
+                            })?
+                    },
+                    _ => invalid_response_type!(client_response_payload),
+                };
+                let last_received_sequence_number = bound_by_range(
+                    last_received_sequence_number,
+                    request.start_sequence_number,
+                    request.end_sequence_number,
+                );
+
+                // Update the local stream notification tracker
+                self.next_stream_sequence_number = last_received_sequence_number
+                    .checked_add(1)
+                    .ok_or_else(|| {
+                        Error::IntegerOverflow("Next stream sequence number has overflown!".into())
+                    })?;
+
+                // Check if the stream is complete
+                if last_received_sequence_number >= self.request.end_sequence_number {
+                    self.stream_is_complete = true;
+                }
+
+                // Create a new data notification
+                let data_notification = create_data_notification(
+                    notification_id_generator,
+                    client_response_payload,
+                    None,
+                    self.clone().into(),
+                )?;
+                Ok(Some(data_notification))
+            },
+            request => invalid_client_request!(request, self),
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct TransactionStreamEngine {
     // The original stream request made by the client (e.g., a transaction or
@@ -1755,6 +2047,28 @@ impl DataStreamEngine for TransactionStreamEngine {
         ))
     }
 
+    fn get_stream_progress(&self) -> StreamProgress {
+        let (end_version, proof_version) = match &self.request {
+            StreamRequest::GetAllTransactions(request) => {
+                (Some(request.end_version), Some(request.proof_version))
+            },
+            StreamRequest::GetAllTransactionOutputs(request) => {
+                (Some(request.end_version), Some(request.proof_version))
+            },
+            StreamRequest::GetAllTransactionsOrOutputs(request) => {
+                (Some(request.end_version), Some(request.proof_version))
+            },
+            _ => (None, None),
+        };
+        StreamProgress {
+            target_version: proof_version,
+            next_request_index: self.next_request_version,
+            next_stream_index: self.next_stream_version,
+            end_index: end_version,
+            stream_is_complete: self.stream_is_complete,
+        }
+    }
+
     fn is_stream_complete(&self) -> bool {
         self.stream_is_complete
     }
@@ -2001,7 +2315,8 @@ fn create_data_client_request(
                         include_events: request.include_events,
                     })
                 },
-                StreamRequest::ContinuouslyStreamTransactionOutputs(_) => {
+                StreamRequest::ContinuouslyStreamTransactionOutputs(_)
+                | StreamRequest::FollowTransactionOutputs(_) => {
                     TransactionOutputsWithProof(TransactionOutputsWithProofRequest {
                         start_version: start_index,
                         end_version: end_index,
@@ -2025,6 +2340,14 @@ fn create_data_client_request(
                 end_epoch: end_index,
             })
         },
+        StreamEngine::EventsByKeyStreamEngine(stream_engine) => {
+            EventsByKeyWithProof(EventsByKeyWithProofRequest {
+                event_key: stream_engine.request.event_key,
+                start_sequence_number: start_index,
+                end_sequence_number: end_index,
+                proof_version: stream_engine.request.proof_version,
+            })
+        },
         StreamEngine::TransactionStreamEngine(stream_engine) => match &stream_engine.request {
             StreamRequest::GetAllTransactions(request) => {
                 TransactionsWithProof(TransactionsWithProofRequest {
@@ -2074,6 +2397,9 @@ fn create_data_notification(
         ResponsePayload::EpochEndingLedgerInfos(ledger_infos) => {
             DataPayload::EpochEndingLedgerInfos(ledger_infos)
         },
+        ResponsePayload::EventsByKeyWithProof(events_by_key_with_proof) => {
+            DataPayload::EventsByKeyWithProof(events_by_key_with_proof)
+        },
         ResponsePayload::NewTransactionsWithProof((transactions_chunk, target_ledger_info)) => {
             match stream_engine {
                 StreamEngine::ContinuousTransactionStreamEngine(_) => {
@@ -7,7 +7,7 @@ use crate::{
     data_stream::{DataStreamId, DataStreamListener},
     error::Error,
 };
-use aptos_types::{ledger_info::LedgerInfoWithSignatures, transaction::Version};
+use aptos_types::{event::EventKey, ledger_info::LedgerInfoWithSignatures, transaction::Version};
 use async_trait::async_trait;
 use futures::{
     channel::{mpsc, oneshot},
@@ -52,6 +52,19 @@ pub trait DataStreamingClient {
         start_epoch: Epoch,
     ) -> Result<DataStreamListener, Error>;
 
+    /// Fetches all events emitted for the given `event_key`, from
+    /// `start_sequence_number` to `end_sequence_number` (inclusive), with
+    /// proofs at the specified `proof_version`. Each event notification also
+    /// contains the other events emitted by the same transactions (so that
+    /// the events can be verified against the transaction infos).
+    async fn get_all_events_by_key(
+        &self,
+        event_key: EventKey,
+        start_sequence_number: u64,
+        end_sequence_number: u64,
+        proof_version: Version,
+    ) -> Result<DataStreamListener, Error>;
+
     /// Fetches all transaction outputs with proofs from `start_version` to
     /// `end_version` (inclusive) at the specified `proof_version`.
     async fn get_all_transaction_outputs(
@@ -142,6 +155,22 @@ pub trait DataStreamingClient {
         target: Option<LedgerInfoWithSignatures>,
     ) -> Result<DataStreamListener, Error>;
 
+    /// Follows the blockchain by continuously streaming transaction outputs
+    /// with proofs as they are committed by upstream peers. The stream starts
+    /// at `known_version + 1` (inclusive) and `known_epoch` (as for
+    /// `continuously_stream_transaction_outputs`), and never terminates.
+    ///
+    /// Once the stream has caught up, new outputs are pushed to it using
+    /// subscriptions. If the stream falls more than `max_lag_versions` behind
+    /// the highest advertised version, it falls back to optimistic fetch (until
+    /// it has caught up again).
+    async fn follow_transaction_outputs(
+        &self,
+        known_version: Version,
+        known_epoch: Epoch,
+        max_lag_versions: u64,
+    ) -> Result<DataStreamListener, Error>;
+
     /// Terminates the stream with the given stream id and (optionally) provides
     /// feedback about the notification and the termination reason.
     ///
@@ -172,6 +201,7 @@ pub struct StreamRequestMessage {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum StreamRequest {
     GetAllEpochEndingLedgerInfos(GetAllEpochEndingLedgerInfosRequest),
+    GetAllEventsByKey(GetAllEventsByKeyRequest),
     GetAllStates(GetAllStatesRequest),
     GetAllTransactions(GetAllTransactionsRequest),
     GetAllTransactionOutputs(GetAllTransactionOutputsRequest),
@@ -179,6 +209,7 @@ pub enum StreamRequest {
     ContinuouslyStreamTransactions(ContinuouslyStreamTransactionsRequest),
     ContinuouslyStreamTransactionOutputs(ContinuouslyStreamTransactionOutputsRequest),
     ContinuouslyStreamTransactionsOrOutputs(ContinuouslyStreamTransactionsOrOutputsRequest),
+    FollowTransactionOutputs(FollowTransactionOutputsRequest),
     TerminateStream(TerminateStreamRequest),
 }
 
@@ -187,6 +218,7 @@ impl StreamRequest {
     pub fn get_label(&self) -> &'static str {
         match self {
             Self::GetAllEpochEndingLedgerInfos(_) => "get_all_epoch_ending_ledger_infos",
+            Self::GetAllEventsByKey(_) => "get_all_events_by_key",
             Self::GetAllStates(_) => "get_all_states",
             Self::GetAllTransactions(_) => "get_all_transactions",
             Self::GetAllTransactionOutputs(_) => "get_all_transaction_outputs",
@@ -198,6 +230,7 @@ impl StreamRequest {
             Self::ContinuouslyStreamTransactionsOrOutputs(_) => {
                 "continuously_stream_transactions_or_outputs"
             },
+            Self::FollowTransactionOutputs(_) => "follow_transaction_outputs",
             Self::TerminateStream(_) => "terminate_stream",
         }
     }
@@ -209,6 +242,15 @@ pub struct GetAllEpochEndingLedgerInfosRequest {
     pub start_epoch: Epoch,
 }
 
+/// A client request for fetching all events emitted for an event key.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GetAllEventsByKeyRequest {
+    pub event_key: EventKey,
+    pub start_sequence_number: u64,
+    pub end_sequence_number: u64,
+    pub proof_version: Version,
+}
+
 /// A client request for fetching all states at a specified version.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GetAllStatesRequest {
@@ -268,6 +310,14 @@ pub struct ContinuouslyStreamTransactionsOrOutputsRequest {
     pub target: Option<LedgerInfoWithSignatures>,
 }
 
+/// A client request for following the blockchain by streaming transaction outputs
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FollowTransactionOutputsRequest {
+    pub known_version: Version,
+    pub known_epoch: Epoch,
+    pub max_lag_versions: u64,
+}
+
 /// A client request for terminating a stream and providing payload feedback.
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TerminateStreamRequest {
@@ -359,6 +409,22 @@ impl DataStreamingClient for StreamingServiceClient {
         self.send_request_and_await_response(client_request).await
     }
 
+    async fn get_all_events_by_key(
+        &self,
+        event_key: EventKey,
+        start_sequence_number: u64,
+        end_sequence_number: u64,
+        proof_version: u64,
+    ) -> Result<DataStreamListener, Error> {
+        let client_request = StreamRequest::GetAllEventsByKey(GetAllEventsByKeyRequest {
+            event_key,
+            start_sequence_number,
+            end_sequence_number,
+            proof_version,
+        });
+        self.send_request_and_await_response(client_request).await
+    }
+
     async fn get_all_transaction_outputs(
         &self,
         start_version: u64,
@@ -458,6 +524,21 @@ impl DataStreamingClient for StreamingServiceClient {
         self.send_request_and_await_response(client_request).await
     }
 
+    async fn follow_transaction_outputs(
+        &self,
+        known_version: u64,
+        known_epoch: u64,
+        max_lag_versions: u64,
+    ) -> Result<DataStreamListener, Error> {
+        let client_request =
+            StreamRequest::FollowTransactionOutputs(FollowTransactionOutputsRequest {
+                known_version,
+                known_epoch,
+                max_lag_versions,
+            });
+        self.send_request_and_await_response(client_request).await
+    }
+
     async fn terminate_stream_with_feedback(
         &self,
         data_stream_id: DataStreamId,
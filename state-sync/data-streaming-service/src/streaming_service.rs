@@ -7,6 +7,9 @@ use crate::{
     error::Error,
     logging::{LogEntry, LogEvent, LogSchema},
     metrics,
+    request_scheduler::RequestScheduler,
+    stream_audit::StreamAuditLogs,
+    stream_diagnostics::StreamDiagnostics,
     streaming_client::{
         StreamRequest, StreamRequestMessage, StreamingServiceListener, TerminateStreamRequest,
     },
@@ -36,6 +39,7 @@ const STREAM_PROGRESS_UPDATE_CHANNEL_SIZE: usize = 1;
 const GLOBAL_DATA_REFRESH_LOG_FREQ_SECS: u64 = 3;
 const NO_DATA_TO_FETCH_LOG_FREQ_SECS: u64 = 3;
 const STREAM_REQUEST_ERROR_LOG_FREQ_SECS: u64 = 3;
+const SEND_FAILURE_TERMINATION: &str = "send_failure";
 const TERMINATE_NO_FEEDBACK: &str = "no_feedback";
 
 /// A simple notification sent to the storage service when a
@@ -85,6 +89,15 @@ pub struct DataStreamingService<T> {
 
     // The time service used to track elapsed time (e.g., for stream progress checks)
     time_service: TimeService,
+
+    // The audit logs of the most recent data streams
+    stream_audit_logs: StreamAuditLogs,
+
+    // The diagnostics of all active data streams
+    stream_diagnostics: StreamDiagnostics,
+
+    // The scheduler for data client requests (shared by all data streams)
+    request_scheduler: RequestScheduler,
 }
 
 impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<T> {
@@ -99,6 +112,13 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         let (stream_update_notifier, stream_update_listener) =
             aptos_channel::new(QueueStyle::LIFO, STREAM_PROGRESS_UPDATE_CHANNEL_SIZE, None);
 
+        // Create the stream audit logs
+        let stream_audit_logs =
+            StreamAuditLogs::new(streaming_service_config, time_service.clone());
+
+        // Create the request scheduler
+        let request_scheduler = RequestScheduler::new(streaming_service_config.request_scheduler);
+
         // Create the streaming service
         Self {
             data_client_config,
@@ -112,9 +132,22 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
             stream_id_generator: U64IdGenerator::new(),
             notification_id_generator: Arc::new(U64IdGenerator::new()),
             time_service,
+            stream_audit_logs,
+            stream_diagnostics: StreamDiagnostics::new(),
+            request_scheduler,
         }
     }
 
+    /// Returns a handle to the audit logs of the most recent data streams
+    pub fn get_stream_audit_logs(&self) -> StreamAuditLogs {
+        self.stream_audit_logs.clone()
+    }
+
+    /// Returns a handle to the diagnostics of all active data streams
+    pub fn get_stream_diagnostics(&self) -> StreamDiagnostics {
+        self.stream_diagnostics.clone()
+    }
+
     /// Starts the dedicated streaming service
     pub async fn start_service(mut self) {
         // Spawn a dedicated task that refreshes the global data summary
@@ -226,6 +259,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
                     "Terminating the data stream with ID: {:?}. Notification and feedback: {:?}",
                     data_stream_id, notification_and_feedback,
                 )));
+            data_stream.record_stream_termination(feedback_label);
 
             // Handle any notification feedback
             if let Some(notification_and_feedback) = notification_and_feedback {
@@ -272,6 +306,9 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         // Create a new data stream
         let stream_id = self.stream_id_generator.next();
         let advertised_data = self.get_global_data_summary().advertised_data.clone();
+        let stream_auditor = self
+            .stream_audit_logs
+            .create_stream_auditor(stream_id, &request_message.stream_request);
         let (data_stream, stream_listener) = DataStream::new(
             self.data_client_config,
             self.streaming_service_config,
@@ -282,6 +319,8 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
             self.notification_id_generator.clone(),
             &advertised_data,
             self.time_service.clone(),
+            stream_auditor,
+            self.request_scheduler.clone(),
         )?;
 
         // Verify the data stream can be fulfilled using the currently advertised data
@@ -312,6 +351,9 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
         let data_stream_ids = self.get_all_data_stream_ids();
         for data_stream_id in &data_stream_ids {
             if let Err(error) = self.update_progress_of_data_stream(data_stream_id).await {
+                if let Some(data_stream) = self.data_streams.get_mut(data_stream_id) {
+                    data_stream.update_last_error(error.to_string());
+                }
                 if matches!(error, Error::NoDataToFetch(_)) {
                     sample!(
                         SampleRate::Duration(Duration::from_secs(NO_DATA_TO_FETCH_LOG_FREQ_SECS)),
@@ -335,6 +377,20 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
 
         // Update the metrics
         metrics::set_active_data_streams(data_stream_ids.len());
+
+        // Refresh the diagnostics of all active streams
+        self.update_stream_diagnostics();
+    }
+
+    /// Refreshes the diagnostics of all active data streams
+    fn update_stream_diagnostics(&self) {
+        let stream_diagnostics = self
+            .data_streams
+            .values()
+            .map(|data_stream| data_stream.get_stream_diagnostics())
+            .collect();
+        self.stream_diagnostics
+            .update_stream_diagnostics(stream_diagnostics);
     }
 
     /// Ensures that a data stream has in-flight data requests and handles
@@ -355,6 +411,7 @@ impl<T: AptosDataClientInterface + Send + Clone + 'static> DataStreamingService<
                     .message("There was a send failure, terminating the stream."))
             );
             metrics::DATA_STREAM_SEND_FAILURE.inc();
+            data_stream.record_stream_termination(SEND_FAILURE_TERMINATION);
             if self.data_streams.remove(data_stream_id).is_none() {
                 return Err(Error::UnexpectedErrorEncountered(format!(
                     "Failed to terminate stream id {:?} for send failure! Stream not found.",
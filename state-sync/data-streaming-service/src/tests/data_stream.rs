@@ -13,26 +13,30 @@ use crate::{
         TransactionsWithProofRequest,
     },
     data_stream::{DataStream, DataStreamListener},
+    request_scheduler::RequestScheduler,
+    stream_audit::StreamAuditor,
+    stream_diagnostics::SentRequestStatus,
     streaming_client::{
         ContinuouslyStreamTransactionOutputsRequest,
         ContinuouslyStreamTransactionsOrOutputsRequest, ContinuouslyStreamTransactionsRequest,
-        GetAllEpochEndingLedgerInfosRequest, GetAllStatesRequest, GetAllTransactionOutputsRequest,
-        GetAllTransactionsOrOutputsRequest, GetAllTransactionsRequest, NotificationFeedback,
-        StreamRequest,
+        FollowTransactionOutputsRequest, GetAllEpochEndingLedgerInfosRequest, GetAllStatesRequest,
+        GetAllTransactionOutputsRequest, GetAllTransactionsOrOutputsRequest,
+        GetAllTransactionsRequest, NotificationFeedback, StreamRequest,
     },
     streaming_service::StreamUpdateNotification,
     tests::utils::{
-        create_data_client_response, create_ledger_info, create_output_list_with_proof,
-        create_random_u64, create_transaction_list_with_proof, get_data_notification,
-        initialize_logger, MockAptosDataClient, NoopResponseCallback, MAX_ADVERTISED_EPOCH_END,
-        MAX_ADVERTISED_STATES, MAX_ADVERTISED_TRANSACTION, MAX_ADVERTISED_TRANSACTION_OUTPUT,
-        MAX_NOTIFICATION_TIMEOUT_SECS, MIN_ADVERTISED_EPOCH_END, MIN_ADVERTISED_STATES,
-        MIN_ADVERTISED_TRANSACTION, MIN_ADVERTISED_TRANSACTION_OUTPUT,
+        create_data_client_response, create_data_client_response_from_peer, create_ledger_info,
+        create_output_list_with_proof, create_random_u64, create_transaction_list_with_proof,
+        get_data_notification, initialize_logger, MockAptosDataClient, NoopResponseCallback,
+        MAX_ADVERTISED_EPOCH_END, MAX_ADVERTISED_STATES, MAX_ADVERTISED_TRANSACTION,
+        MAX_ADVERTISED_TRANSACTION_OUTPUT, MAX_NOTIFICATION_TIMEOUT_SECS, MIN_ADVERTISED_EPOCH_END,
+        MIN_ADVERTISED_STATES, MIN_ADVERTISED_TRANSACTION, MIN_ADVERTISED_TRANSACTION_OUTPUT,
     },
 };
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
-use aptos_config::config::{
-    AptosDataClientConfig, DataStreamingServiceConfig, DynamicPrefetchingConfig,
+use aptos_config::{
+    config::{AptosDataClientConfig, DataStreamingServiceConfig, DynamicPrefetchingConfig},
+    network_id::PeerNetworkId,
 };
 use aptos_data_client::{
     global_summary::{AdvertisedData, GlobalDataSummary, OptimalChunkSizes},
@@ -217,6 +221,161 @@ async fn test_stream_data_error() {
     verify_client_request_resubmitted(&mut data_stream, client_request);
 }
 
+#[tokio::test]
+async fn test_stream_failover() {
+    // Create an epoch ending data stream with dynamic prefetching disabled
+    let max_concurrent_requests = 3;
+    let dynamic_prefetching_config = DynamicPrefetchingConfig {
+        enable_dynamic_prefetching: false,
+        ..Default::default()
+    };
+    let streaming_service_config = DataStreamingServiceConfig {
+        dynamic_prefetching: dynamic_prefetching_config,
+        max_concurrent_requests,
+        ..Default::default()
+    };
+    let (mut data_stream, mut stream_listener) = create_epoch_ending_stream(
+        AptosDataClientConfig::default(),
+        streaming_service_config,
+        MIN_ADVERTISED_EPOCH_END,
+    );
+
+    // Initialize the data stream and verify the number of sent requests
+    let global_data_summary = create_global_data_summary(1);
+    initialize_data_requests(&mut data_stream, &global_data_summary);
+    verify_num_sent_requests(&mut data_stream, max_concurrent_requests);
+
+    // Set a response (served by the primary peer) for the first request and verify the notification
+    let primary_peer = PeerNetworkId::random();
+    set_epoch_ending_response_from_peer_in_queue(&mut data_stream, 0, primary_peer);
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    verify_epoch_ending_notification(
+        &mut stream_listener,
+        create_ledger_info(0, MIN_ADVERTISED_EPOCH_END, true),
+    )
+    .await;
+
+    // Fail the request at the head of the queue (until the failover threshold is reached)
+    let failover_failure_threshold = streaming_service_config
+        .stream_failover
+        .failover_failure_threshold;
+    for _ in 0..failover_failure_threshold - 1 {
+        set_failure_response_in_queue(&mut data_stream, 0);
+        process_data_responses(&mut data_stream, &global_data_summary).await;
+        assert!(data_stream
+            .get_data_client()
+            .excluded_peers
+            .lock()
+            .is_empty());
+    }
+
+    // Fail the request again and verify the stream failed over from the primary peer
+    let sent_client_requests = get_sent_client_requests(&mut data_stream);
+    set_failure_response_in_queue(&mut data_stream, 0);
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    assert_eq!(
+        data_stream.get_data_client().excluded_peers.lock().clone(),
+        vec![primary_peer]
+    );
+
+    // Verify the same requests were re-sent (in the same order)
+    assert_eq!(
+        get_sent_client_requests(&mut data_stream),
+        sent_client_requests
+    );
+
+    // Set a response for the first request and verify the stream continues
+    set_epoch_ending_response_in_queue(&mut data_stream, 0, 0);
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    verify_epoch_ending_notification(
+        &mut stream_listener,
+        create_ledger_info(0, MIN_ADVERTISED_EPOCH_END, true),
+    )
+    .await;
+}
+
+#[tokio::test]
+async fn test_stream_diagnostics() {
+    // Create an epoch ending data stream with dynamic prefetching disabled
+    let max_concurrent_requests = 3;
+    let dynamic_prefetching_config = DynamicPrefetchingConfig {
+        enable_dynamic_prefetching: false,
+        ..Default::default()
+    };
+    let streaming_service_config = DataStreamingServiceConfig {
+        dynamic_prefetching: dynamic_prefetching_config,
+        max_concurrent_requests,
+        ..Default::default()
+    };
+    let (mut data_stream, _stream_listener) = create_epoch_ending_stream(
+        AptosDataClientConfig::default(),
+        streaming_service_config,
+        MIN_ADVERTISED_EPOCH_END,
+    );
+
+    // Verify the diagnostics of the uninitialized stream
+    let stream_diagnostics = data_stream.get_stream_diagnostics();
+    assert!(stream_diagnostics.sent_requests.is_empty());
+    assert_eq!(
+        stream_diagnostics.stream_progress.next_stream_index,
+        MIN_ADVERTISED_EPOCH_END
+    );
+    assert_eq!(
+        stream_diagnostics.stream_progress.end_index,
+        Some(MAX_ADVERTISED_EPOCH_END)
+    );
+
+    // Initialize the data stream
+    let global_data_summary = create_global_data_summary(1);
+    initialize_data_requests(&mut data_stream, &global_data_summary);
+
+    // Set a pending request, a response (served by a known peer) and a failure in the queue
+    let peer = PeerNetworkId::random();
+    set_pending_response_in_queue(&mut data_stream, 0);
+    set_epoch_ending_response_from_peer_in_queue(&mut data_stream, 1, peer);
+    set_failure_response_in_queue(&mut data_stream, 2);
+
+    // Verify the diagnostics of the sent requests
+    let stream_diagnostics = data_stream.get_stream_diagnostics();
+    assert_eq!(
+        stream_diagnostics.stream_progress.next_request_index,
+        MIN_ADVERTISED_EPOCH_END + max_concurrent_requests
+    );
+    assert_eq!(stream_diagnostics.num_in_flight_requests, 1);
+    let request_statuses: Vec<_> = stream_diagnostics
+        .sent_requests
+        .iter()
+        .map(|sent_request| sent_request.status)
+        .collect();
+    assert_eq!(request_statuses, vec![
+        SentRequestStatus::InFlight,
+        SentRequestStatus::ResponseReceived,
+        SentRequestStatus::RequestFailed
+    ]);
+    assert_eq!(
+        stream_diagnostics.sent_requests[1].peer,
+        Some(peer.to_string())
+    );
+    assert_eq!(
+        stream_diagnostics.requests_per_peer.get(&peer.to_string()),
+        Some(&1)
+    );
+    assert_none!(stream_diagnostics.last_error);
+
+    // Fail the request at the head of the queue and process the responses
+    set_failure_response_in_queue(&mut data_stream, 0);
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+
+    // Verify the retry count and the last error
+    let stream_diagnostics = data_stream.get_stream_diagnostics();
+    assert_eq!(stream_diagnostics.request_failure_count, 1);
+    assert!(stream_diagnostics
+        .last_error
+        .unwrap()
+        .error
+        .contains("Oops!"));
+}
+
 #[tokio::test]
 async fn test_stream_invalid_response() {
     // Create an epoch ending data stream
@@ -1758,6 +1917,127 @@ async fn test_continuous_stream_subscription_lag() {
     }
 }
 
+#[tokio::test]
+async fn test_follow_stream_lag_fallback() {
+    // Create a test streaming service config with subscriptions disabled
+    let max_concurrent_requests = 3;
+    let streaming_service_config = DataStreamingServiceConfig {
+        enable_subscription_streaming: false,
+        max_concurrent_requests,
+        ..Default::default()
+    };
+
+    // Create a follow stream
+    let max_lag_versions = 2;
+    let (mut data_stream, mut stream_listener, _) = create_follow_stream(
+        AptosDataClientConfig::default(),
+        streaming_service_config,
+        MAX_ADVERTISED_TRANSACTION_OUTPUT,
+        MAX_ADVERTISED_EPOCH_END,
+        max_lag_versions,
+    );
+
+    // Initialize the data stream and verify subscriptions are
+    // used (even though they're disabled in the config).
+    let mut global_data_summary = create_global_data_summary(1);
+    initialize_data_requests(&mut data_stream, &global_data_summary);
+    let subscription_stream_id = get_subscription_stream_id(&mut data_stream, 0);
+    verify_pending_subscription_requests(
+        &mut data_stream,
+        max_concurrent_requests,
+        false,
+        false,
+        0,
+        subscription_stream_id,
+        0,
+    );
+
+    // Set a valid response for the first subscription request and process it
+    set_new_data_response_in_queue(
+        &mut data_stream,
+        0,
+        MAX_ADVERTISED_TRANSACTION_OUTPUT + 1,
+        false,
+    );
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    assert_some!(stream_listener.select_next_some().now_or_never());
+
+    // Update the global data summary to be ahead of the stream (beyond the max lag)
+    let highest_advertised_version = MAX_ADVERTISED_TRANSACTION_OUTPUT + 5;
+    global_data_summary.advertised_data.synced_ledger_infos = vec![create_ledger_info(
+        highest_advertised_version,
+        MAX_ADVERTISED_EPOCH_END,
+        false,
+    )];
+
+    // Set a valid (but lagging) response for the next subscription request and process it
+    set_new_data_response_in_queue(
+        &mut data_stream,
+        0,
+        MAX_ADVERTISED_TRANSACTION_OUTPUT + 2,
+        false,
+    );
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    assert_some!(stream_listener.select_next_some().now_or_never());
+
+    // Verify the subscription was terminated and the missing data is requested
+    verify_num_sent_requests(&mut data_stream, max_concurrent_requests);
+    for request_index in 0..max_concurrent_requests {
+        let version = MAX_ADVERTISED_TRANSACTION_OUTPUT + 3 + request_index;
+        let client_request = get_pending_client_request(&mut data_stream, request_index as usize);
+        assert_eq!(
+            client_request,
+            DataClientRequest::TransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+                start_version: version,
+                end_version: version,
+                proof_version: highest_advertised_version,
+            })
+        );
+
+        // Set a valid response for the request
+        let (sent_requests, _) = data_stream.get_sent_requests_and_notifications();
+        let pending_response = sent_requests
+            .as_mut()
+            .unwrap()
+            .get_mut(request_index as usize)
+            .unwrap();
+        pending_response.lock().client_response = Some(Ok(create_data_client_response(
+            ResponsePayload::TransactionOutputsWithProof(create_output_list_with_proof(
+                version, version,
+            )),
+        )));
+    }
+
+    // Process the responses and verify the stream falls back to optimistic fetch
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    for _ in 0..max_concurrent_requests {
+        assert_some!(stream_listener.select_next_some().now_or_never());
+    }
+    verify_pending_optimistic_fetch(&mut data_stream, false, false, 5);
+
+    // Set a valid response for the optimistic fetch request and process it
+    set_new_data_response_in_queue(
+        &mut data_stream,
+        0,
+        MAX_ADVERTISED_TRANSACTION_OUTPUT + 6,
+        false,
+    );
+    process_data_responses(&mut data_stream, &global_data_summary).await;
+    assert_some!(stream_listener.select_next_some().now_or_never());
+
+    // Verify the stream has caught up and resumed subscriptions
+    let subscription_stream_id = get_subscription_stream_id(&mut data_stream, 0);
+    verify_pending_subscription_requests(
+        &mut data_stream,
+        max_concurrent_requests,
+        false,
+        false,
+        0,
+        subscription_stream_id,
+        6,
+    );
+}
+
 #[tokio::test]
 async fn test_continuous_stream_subscription_lag_bounded() {
     // Create a test streaming service config with subscriptions enabled
@@ -2637,6 +2917,27 @@ fn create_continuous_transaction_output_stream(
     create_data_stream(data_client_config, streaming_service_config, stream_request)
 }
 
+/// Creates a follow stream for the given `version` and max lag
+fn create_follow_stream(
+    data_client_config: AptosDataClientConfig,
+    streaming_service_config: DataStreamingServiceConfig,
+    known_version: Version,
+    known_epoch: Version,
+    max_lag_versions: u64,
+) -> (
+    DataStream<MockAptosDataClient>,
+    DataStreamListener,
+    TimeService,
+) {
+    // Create a follow stream request
+    let stream_request = StreamRequest::FollowTransactionOutputs(FollowTransactionOutputsRequest {
+        known_version,
+        known_epoch,
+        max_lag_versions,
+    });
+    create_data_stream(data_client_config, streaming_service_config, stream_request)
+}
+
 /// Creates a continuous transaction stream for the given `version`.
 fn create_continuous_transaction_stream(
     data_client_config: AptosDataClientConfig,
@@ -2772,6 +3073,8 @@ fn create_data_stream(
         notification_generator,
         &advertised_data,
         time_service.clone(),
+        StreamAuditor::disabled(time_service.clone()),
+        RequestScheduler::new(streaming_service_config.request_scheduler),
     )
     .unwrap();
 
@@ -2937,6 +3240,26 @@ fn set_epoch_ending_response_in_queue(
     pending_response.lock().client_response = client_response;
 }
 
+/// Sets the client response at the index in the pending queue to contain an
+/// epoch ending data response (served by the given peer).
+fn set_epoch_ending_response_from_peer_in_queue(
+    data_stream: &mut DataStream<MockAptosDataClient>,
+    index: usize,
+    peer: PeerNetworkId,
+) {
+    let (sent_requests, _) = data_stream.get_sent_requests_and_notifications();
+    let pending_response = sent_requests.as_mut().unwrap().get_mut(index).unwrap();
+    let client_response = Some(Ok(create_data_client_response_from_peer(
+        ResponsePayload::EpochEndingLedgerInfos(vec![create_ledger_info(
+            0,
+            MIN_ADVERTISED_EPOCH_END,
+            true,
+        )]),
+        peer,
+    )));
+    pending_response.lock().client_response = client_response;
+}
+
 /// Sets the client response at the index in the pending queue to contain a
 /// number of state values response.
 fn set_num_state_values_response_in_queue(
@@ -3194,6 +3517,19 @@ async fn verify_epoch_ending_notification(
     }
 }
 
+/// Returns the client requests in the sent requests queue (in order)
+fn get_sent_client_requests(
+    data_stream: &mut DataStream<MockAptosDataClient>,
+) -> Vec<DataClientRequest> {
+    let (sent_requests, _) = data_stream.get_sent_requests_and_notifications();
+    sent_requests
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|pending_response| pending_response.lock().client_request.clone())
+        .collect()
+}
+
 /// Helper function to initialize the data requests
 fn initialize_data_requests(
     data_stream: &mut DataStream<MockAptosDataClient>,
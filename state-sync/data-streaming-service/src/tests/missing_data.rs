@@ -11,7 +11,8 @@ use crate::{
         TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
         TransactionsWithProofRequest,
     },
-    data_stream::create_missing_data_request,
+    data_stream::{create_missing_data_request, RequestCursor},
+    error::Error,
     stream_engine::{bound_by_range, DataStreamEngine, StreamEngine},
     streaming_client::{
         ContinuouslyStreamTransactionOutputsRequest, GetAllEpochEndingLedgerInfosRequest,
@@ -413,6 +414,61 @@ fn create_missing_data_request_transactions_or_outputs() {
     }
 }
 
+#[test]
+fn request_cursor_partial_responses() {
+    // Create the data client request
+    let start_version = 100;
+    let end_version = 199;
+    let data_client_request =
+        DataClientRequest::TransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+            start_version,
+            end_version,
+            proof_version: end_version,
+        });
+
+    // Verify the cursor of a partial response
+    let request_cursor = RequestCursor::new(
+        &data_client_request,
+        &create_outputs_payload(start_version, 40),
+    )
+    .unwrap()
+    .unwrap();
+    assert_eq!(request_cursor.get_num_expected_items().unwrap(), 100);
+    assert_eq!(request_cursor.num_received_items, 40);
+    assert!(request_cursor.is_partial_response().unwrap());
+    assert_eq!(request_cursor.get_next_index().unwrap(), start_version + 40);
+
+    // Verify the cursor of a complete response
+    let request_cursor = RequestCursor::new(
+        &data_client_request,
+        &create_outputs_payload(start_version, 100),
+    )
+    .unwrap()
+    .unwrap();
+    assert!(!request_cursor.is_partial_response().unwrap());
+    assert!(request_cursor
+        .create_continuation_request(&data_client_request)
+        .unwrap()
+        .is_none());
+
+    // Verify that a mismatched response payload is rejected
+    let result = RequestCursor::new(&data_client_request, &ResponsePayload::NumberOfStates(10));
+    assert!(matches!(
+        result,
+        Err(Error::AptosDataClientResponseIsInvalid(_))
+    ));
+
+    // Verify that requests for unbounded data have no cursor
+    let data_client_request =
+        DataClientRequest::NewTransactionOutputsWithProof(NewTransactionOutputsWithProofRequest {
+            known_version: 0,
+            known_epoch: 0,
+        });
+    let request_cursor =
+        RequestCursor::new(&data_client_request, &create_outputs_payload(0, 10)).unwrap();
+    assert!(request_cursor.is_none());
+}
+
 #[test]
 fn transform_epoch_ending_stream_notifications() {
     // Create an epoch ending stream request
@@ -861,6 +917,18 @@ fn create_state_value_chunk(
 }
 
 /// Returns a dummy transaction for testing purposes
+/// Creates a transaction outputs payload with the given number of outputs
+fn create_outputs_payload(start_version: u64, num_outputs: u64) -> ResponsePayload {
+    let transactions_and_outputs = (0..num_outputs)
+        .map(|_| (create_test_transaction(), create_test_transaction_output()))
+        .collect::<Vec<_>>();
+    ResponsePayload::TransactionOutputsWithProof(TransactionOutputListWithProof {
+        transactions_and_outputs,
+        proof: TransactionInfoListWithProof::new_empty(),
+        first_transaction_output_version: Some(start_version),
+    })
+}
+
 fn create_test_transaction() -> Transaction {
     Transaction::StateCheckpoint(HashValue::zero())
 }
@@ -4,6 +4,8 @@
 
 mod data_stream;
 mod missing_data;
+mod request_scheduler;
+mod stream_audit;
 mod stream_engine;
 mod streaming_client;
 pub mod streaming_service;
@@ -0,0 +1,144 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    data_notification::{
+        DataClientRequest, EpochEndingLedgerInfosRequest, NewTransactionOutputsWithProofRequest,
+        TransactionOutputsWithProofRequest,
+    },
+    request_scheduler::RequestScheduler,
+};
+use aptos_config::config::RequestSchedulerConfig;
+use futures::{poll, task::Poll};
+
+#[tokio::test]
+async fn test_request_lane_priorities() {
+    // Create a request scheduler that dispatches a single request at a time
+    let request_scheduler = create_request_scheduler(true, 1);
+
+    // Dispatch a transaction output request
+    let output_request = create_output_request();
+    let output_permit = request_scheduler.acquire_permit(&output_request).await;
+    assert_eq!(request_scheduler.get_num_in_flight_requests(), 1);
+
+    // Queue several transaction output requests, followed by an epoch ending request
+    let mut queued_output_requests: Vec<_> = (0..3)
+        .map(|_| Box::pin(request_scheduler.acquire_permit(&output_request)))
+        .collect();
+    for queued_output_request in queued_output_requests.iter_mut() {
+        assert!(poll!(queued_output_request).is_pending());
+    }
+    let epoch_ending_request = create_epoch_ending_request();
+    let mut queued_epoch_ending_request =
+        Box::pin(request_scheduler.acquire_permit(&epoch_ending_request));
+    assert!(poll!(&mut queued_epoch_ending_request).is_pending());
+    assert_eq!(request_scheduler.get_num_queued_requests(), 4);
+
+    // Complete the in-flight request and verify the epoch ending request is dispatched first
+    drop(output_permit);
+    let epoch_ending_permit = match poll!(&mut queued_epoch_ending_request) {
+        Poll::Ready(permit) => permit,
+        Poll::Pending => panic!("The epoch ending request should have been dispatched!"),
+    };
+    for queued_output_request in queued_output_requests.iter_mut() {
+        assert!(poll!(queued_output_request).is_pending());
+    }
+
+    // Verify the output requests are then dispatched in order
+    drop(epoch_ending_permit);
+    for (index, mut queued_output_request) in queued_output_requests.into_iter().enumerate() {
+        let output_permit = match poll!(&mut queued_output_request) {
+            Poll::Ready(permit) => permit,
+            Poll::Pending => panic!("Output request {} should have been dispatched!", index),
+        };
+        assert_eq!(request_scheduler.get_num_in_flight_requests(), 1);
+        drop(output_permit);
+    }
+
+    // Verify all dispatch slots are free
+    assert_eq!(request_scheduler.get_num_in_flight_requests(), 0);
+    assert_eq!(request_scheduler.get_num_queued_requests(), 0);
+}
+
+#[tokio::test]
+async fn test_unscheduled_requests() {
+    // Create a request scheduler that dispatches a single request at a time
+    let request_scheduler = create_request_scheduler(true, 1);
+
+    // Dispatch a transaction output request
+    let output_request = create_output_request();
+    let _output_permit = request_scheduler.acquire_permit(&output_request).await;
+
+    // Verify optimistic fetch requests are never queued
+    let optimistic_fetch_request =
+        DataClientRequest::NewTransactionOutputsWithProof(NewTransactionOutputsWithProofRequest {
+            known_version: 10,
+            known_epoch: 1,
+        });
+    let mut optimistic_fetch =
+        Box::pin(request_scheduler.acquire_permit(&optimistic_fetch_request));
+    assert!(poll!(&mut optimistic_fetch).is_ready());
+    assert_eq!(request_scheduler.get_num_in_flight_requests(), 1);
+
+    // Verify requests are never queued if the scheduler is disabled
+    let request_scheduler = create_request_scheduler(false, 1);
+    let _output_permit = request_scheduler.acquire_permit(&output_request).await;
+    let mut output_request_future = Box::pin(request_scheduler.acquire_permit(&output_request));
+    assert!(poll!(&mut output_request_future).is_ready());
+    assert_eq!(request_scheduler.get_num_in_flight_requests(), 0);
+}
+
+#[tokio::test]
+async fn test_dropped_queued_requests() {
+    // Create a request scheduler that dispatches a single request at a time
+    let request_scheduler = create_request_scheduler(true, 1);
+
+    // Dispatch a transaction output request and queue two more
+    let output_request = create_output_request();
+    let output_permit = request_scheduler.acquire_permit(&output_request).await;
+    let mut first_queued_request = Box::pin(request_scheduler.acquire_permit(&output_request));
+    let mut second_queued_request = Box::pin(request_scheduler.acquire_permit(&output_request));
+    assert!(poll!(&mut first_queued_request).is_pending());
+    assert!(poll!(&mut second_queued_request).is_pending());
+
+    // Drop the second queued request (e.g., the request task was aborted)
+    drop(second_queued_request);
+
+    // Complete the in-flight request (handing the slot over to the first queued
+    // request) and then drop the first queued request before it is polled.
+    drop(output_permit);
+    drop(first_queued_request);
+
+    // Verify the dispatch slot was released
+    assert_eq!(request_scheduler.get_num_in_flight_requests(), 0);
+    assert_eq!(request_scheduler.get_num_queued_requests(), 0);
+}
+
+/// Creates a request scheduler with the given config values
+fn create_request_scheduler(
+    enable_request_scheduler: bool,
+    max_in_flight_requests: u64,
+) -> RequestScheduler {
+    RequestScheduler::new(RequestSchedulerConfig {
+        enable_request_scheduler,
+        max_in_flight_requests,
+        ..Default::default()
+    })
+}
+
+/// Creates an epoch ending ledger info request
+fn create_epoch_ending_request() -> DataClientRequest {
+    DataClientRequest::EpochEndingLedgerInfos(EpochEndingLedgerInfosRequest {
+        start_epoch: 0,
+        end_epoch: 10,
+    })
+}
+
+/// Creates a transaction output request
+fn create_output_request() -> DataClientRequest {
+    DataClientRequest::TransactionOutputsWithProof(TransactionOutputsWithProofRequest {
+        start_version: 0,
+        end_version: 100,
+        proof_version: 100,
+    })
+}
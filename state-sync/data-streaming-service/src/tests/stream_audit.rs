@@ -0,0 +1,110 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    data_notification::{DataClientRequest, NumberOfStatesRequest},
+    stream_audit::{StreamAuditEvent, StreamAuditLogs},
+    streaming_client::{GetAllStatesRequest, StreamRequest},
+    tests::utils::NoopResponseCallback,
+};
+use aptos_config::config::DataStreamingServiceConfig;
+use aptos_data_client::{
+    error::Error,
+    interface::{ResponseContext, ResponseError},
+};
+use aptos_time_service::TimeService;
+
+#[test]
+fn test_stream_audit_log_pruning() {
+    // Create the stream audit logs with a small limit
+    let streaming_service_config = DataStreamingServiceConfig {
+        max_audited_streams: 2,
+        ..Default::default()
+    };
+    let stream_audit_logs = StreamAuditLogs::new(streaming_service_config, TimeService::mock());
+
+    // Create several stream auditors
+    for data_stream_id in 0..5 {
+        let stream_auditor =
+            stream_audit_logs.create_stream_auditor(data_stream_id, &create_stream_request());
+        stream_auditor.record_stream_terminated("end_of_stream");
+    }
+
+    // Verify that only the newest audit logs are held
+    let audit_logs = stream_audit_logs.get_audit_logs();
+    let data_stream_ids: Vec<_> = audit_logs.iter().map(|log| log.data_stream_id).collect();
+    assert_eq!(data_stream_ids, vec![4, 3]);
+    for audit_log in audit_logs {
+        assert_eq!(audit_log.termination_reason, Some("end_of_stream".into()));
+    }
+
+    // Disable auditing and verify that no audit logs are held
+    let streaming_service_config = DataStreamingServiceConfig {
+        max_audited_streams: 0,
+        ..Default::default()
+    };
+    let stream_audit_logs = StreamAuditLogs::new(streaming_service_config, TimeService::mock());
+    let stream_auditor = stream_audit_logs.create_stream_auditor(0, &create_stream_request());
+    stream_auditor.record_stream_terminated("end_of_stream");
+    assert!(stream_audit_logs.get_audit_logs().is_empty());
+}
+
+#[test]
+fn test_stream_audit_log_events() {
+    // Create the stream audit logs with a small number of entries per stream
+    let max_stream_audit_log_entries = 3;
+    let streaming_service_config = DataStreamingServiceConfig {
+        max_stream_audit_log_entries,
+        ..Default::default()
+    };
+    let stream_audit_logs = StreamAuditLogs::new(streaming_service_config, TimeService::mock());
+    let stream_auditor = stream_audit_logs.create_stream_auditor(0, &create_stream_request());
+
+    // Record a failed request and a retry
+    let client_request = DataClientRequest::NumberOfStates(NumberOfStatesRequest { version: 10 });
+    stream_auditor.record_request_sent(&client_request, false);
+    stream_auditor.record_request_failed(
+        &client_request,
+        &Error::TimeoutWaitingForResponse("timeout".into()),
+    );
+    stream_auditor.record_request_sent(&client_request, true);
+
+    // Record a response that was later rejected
+    let response_context = ResponseContext::new(100, Box::new(NoopResponseCallback));
+    stream_auditor.record_response_received(&client_request, &response_context, Some(1));
+    stream_auditor
+        .record_response_rejected(&response_context, &ResponseError::ProofVerificationError);
+
+    // Verify the summaries of the audit log
+    let audit_log = stream_audit_logs.get_audit_logs().pop().unwrap();
+    assert_eq!(audit_log.num_requests_sent, 2);
+    assert_eq!(audit_log.num_requests_retried, 1);
+    assert_eq!(audit_log.num_requests_failed, 1);
+    assert_eq!(audit_log.num_responses, 1);
+    assert_eq!(audit_log.num_rejected_responses, 1);
+    assert_eq!(audit_log.termination_reason, None);
+
+    // Verify the peer summaries (the serving peer is unknown for the noop callback)
+    let peer_summary = audit_log.peer_summaries.get("unknown").unwrap();
+    assert_eq!(peer_summary.num_responses, 1);
+    assert_eq!(peer_summary.num_rejected_responses, 1);
+
+    // Verify that the oldest entries were dropped
+    assert_eq!(audit_log.entries.len() as u64, max_stream_audit_log_entries);
+    assert_eq!(audit_log.num_dropped_entries, 2);
+    assert!(matches!(
+        audit_log.entries.back().unwrap().event,
+        StreamAuditEvent::ResponseRejected {
+            response_id: 100,
+            ..
+        }
+    ));
+}
+
+/// Creates a simple stream request for all states
+fn create_stream_request() -> StreamRequest {
+    StreamRequest::GetAllStates(GetAllStatesRequest {
+        version: 100,
+        start_index: 0,
+    })
+}
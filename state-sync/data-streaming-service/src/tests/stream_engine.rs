@@ -3,10 +3,14 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    data_notification::{DataClientRequest, EpochEndingLedgerInfosRequest},
+    data_notification::{
+        DataClientRequest, EpochEndingLedgerInfosRequest, EventsByKeyWithProofRequest,
+    },
     error::Error,
     stream_engine::{DataStreamEngine, EpochEndingStreamEngine, StreamEngine},
-    streaming_client::{GetAllEpochEndingLedgerInfosRequest, StreamRequest},
+    streaming_client::{
+        GetAllEpochEndingLedgerInfosRequest, GetAllEventsByKeyRequest, StreamRequest,
+    },
     tests::{
         utils,
         utils::{create_ledger_info, initialize_logger},
@@ -18,7 +22,8 @@ use aptos_data_client::{
     interface::ResponsePayload,
 };
 use aptos_id_generator::U64IdGenerator;
-use aptos_storage_service_types::responses::CompleteDataRange;
+use aptos_storage_service_types::responses::{CompleteDataRange, EventsByKeyWithProof};
+use aptos_types::{account_address::AccountAddress, event::EventKey};
 use claims::{assert_matches, assert_ok};
 use std::{cmp, sync::Arc};
 
@@ -232,6 +237,96 @@ fn test_epoch_ending_stream_engine() {
     }
 }
 
+#[test]
+fn test_events_by_key_stream_engine() {
+    // Try to create a stream engine with an invalid sequence number range
+    let event_key = EventKey::new(0, AccountAddress::ONE);
+    let data_streaming_config = DataStreamingServiceConfig::default();
+    let mut global_data_summary = GlobalDataSummary::empty();
+    let stream_request = StreamRequest::GetAllEventsByKey(GetAllEventsByKeyRequest {
+        event_key,
+        start_sequence_number: 10,
+        end_sequence_number: 9,
+        proof_version: 1000,
+    });
+    let result = StreamEngine::new(
+        data_streaming_config,
+        &stream_request,
+        &global_data_summary.advertised_data,
+    );
+    assert_matches!(result, Err(Error::UnsupportedRequestEncountered(_)));
+
+    // Create a valid events by key stream engine
+    let stream_request = StreamRequest::GetAllEventsByKey(GetAllEventsByKeyRequest {
+        event_key,
+        start_sequence_number: 0,
+        end_sequence_number: 99,
+        proof_version: 1000,
+    });
+    let mut stream_engine = match StreamEngine::new(
+        data_streaming_config,
+        &stream_request,
+        &global_data_summary.advertised_data,
+    )
+    .unwrap()
+    {
+        StreamEngine::EventsByKeyStreamEngine(stream_engine) => stream_engine,
+        unexpected_engine => {
+            panic!(
+                "Expected events by key stream engine but got {:?}",
+                unexpected_engine
+            );
+        },
+    };
+
+    // Verify the remaining data is only available if the proof version is advertised
+    let advertised_data = &mut global_data_summary.advertised_data;
+    assert!(!stream_engine
+        .is_remaining_data_available(advertised_data)
+        .unwrap());
+    advertised_data.transactions = vec![CompleteDataRange::new(0, 999).unwrap()];
+    assert!(!stream_engine
+        .is_remaining_data_available(advertised_data)
+        .unwrap());
+    advertised_data.transactions = vec![CompleteDataRange::new(500, 2000).unwrap()];
+    assert!(stream_engine
+        .is_remaining_data_available(advertised_data)
+        .unwrap());
+
+    // Create the client requests (chunked by the transaction chunk size)
+    global_data_summary
+        .optimal_chunk_sizes
+        .transaction_chunk_size = 50;
+    let client_requests = stream_engine
+        .create_data_client_requests(5, &global_data_summary, create_notification_id_generator())
+        .unwrap();
+    let expected_requests: Vec<_> = [(0, 49), (50, 99)]
+        .into_iter()
+        .map(|(start_sequence_number, end_sequence_number)| {
+            DataClientRequest::EventsByKeyWithProof(EventsByKeyWithProofRequest {
+                event_key,
+                start_sequence_number,
+                end_sequence_number,
+                proof_version: 1000,
+            })
+        })
+        .collect();
+    assert_eq!(client_requests, expected_requests);
+
+    // Verify that an empty response is rejected
+    let result = stream_engine.transform_client_response_into_notification(
+        &client_requests[0],
+        ResponsePayload::EventsByKeyWithProof(EventsByKeyWithProof {
+            event_key,
+            start_sequence_number: 0,
+            transaction_events: vec![],
+        }),
+        create_notification_id_generator(),
+    );
+    assert_matches!(result, Err(Error::AptosDataClientResponseIsInvalid(_)));
+    assert_eq!(stream_engine.next_stream_sequence_number, 0);
+}
+
 #[test]
 fn test_update_epoch_ending_stream_progress() {
     // Create a new data stream engine
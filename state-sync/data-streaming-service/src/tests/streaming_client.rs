@@ -9,12 +9,14 @@ use crate::{
     streaming_client::{
         new_streaming_service_client_listener_pair, ContinuouslyStreamTransactionOutputsRequest,
         ContinuouslyStreamTransactionsRequest, DataStreamingClient,
-        GetAllEpochEndingLedgerInfosRequest, GetAllStatesRequest, GetAllTransactionOutputsRequest,
+        FollowTransactionOutputsRequest, GetAllEpochEndingLedgerInfosRequest,
+        GetAllEventsByKeyRequest, GetAllStatesRequest, GetAllTransactionOutputsRequest,
         GetAllTransactionsRequest, NotificationAndFeedback, NotificationFeedback, StreamRequest,
         StreamingServiceListener, TerminateStreamRequest,
     },
     tests::utils::{create_ledger_info, initialize_logger},
 };
+use aptos_types::{account_address::AccountAddress, event::EventKey};
 use claims::assert_ok;
 use futures::{channel::mpsc, executor::block_on, FutureExt, StreamExt};
 use std::thread::JoinHandle;
@@ -78,6 +80,37 @@ fn test_get_all_epoch_ending_ledger_infos() {
     assert_ok!(response);
 }
 
+#[test]
+fn test_get_all_events_by_key() {
+    // Create a new streaming service client and listener
+    let (streaming_service_client, streaming_service_listener) =
+        new_streaming_service_client_listener_pair();
+
+    // Note the request we expect to receive on the streaming service side
+    let event_key = EventKey::new(0, AccountAddress::ONE);
+    let start_sequence_number = 10;
+    let end_sequence_number = 100;
+    let proof_version = 1000;
+    let expected_request = StreamRequest::GetAllEventsByKey(GetAllEventsByKeyRequest {
+        event_key,
+        start_sequence_number,
+        end_sequence_number,
+        proof_version,
+    });
+
+    // Spawn a new server thread to handle any events by key stream requests
+    let _handler = spawn_service_and_expect_request(streaming_service_listener, expected_request);
+
+    // Send an events by key stream request and verify we get a data stream listener
+    let response = block_on(streaming_service_client.get_all_events_by_key(
+        event_key,
+        start_sequence_number,
+        end_sequence_number,
+        proof_version,
+    ));
+    assert_ok!(response);
+}
+
 #[test]
 fn test_get_all_transactions() {
     // Create a new streaming service client and listener
@@ -202,6 +235,35 @@ fn test_continuously_stream_transaction_outputs() {
     assert_ok!(response);
 }
 
+#[test]
+fn test_follow_transaction_outputs() {
+    // Create a new streaming service client and listener
+    let (streaming_service_client, streaming_service_listener) =
+        new_streaming_service_client_listener_pair();
+
+    // Note the request we expect to receive on the streaming service side
+    let known_version = 101;
+    let known_epoch = 2;
+    let max_lag_versions = 500;
+    let expected_request =
+        StreamRequest::FollowTransactionOutputs(FollowTransactionOutputsRequest {
+            known_version,
+            known_epoch,
+            max_lag_versions,
+        });
+
+    // Spawn a new server thread to handle any follow stream requests
+    let _handler = spawn_service_and_expect_request(streaming_service_listener, expected_request);
+
+    // Send a follow stream request and verify we get a data stream listener
+    let response = block_on(streaming_service_client.follow_transaction_outputs(
+        known_version,
+        known_epoch,
+        max_lag_versions,
+    ));
+    assert_ok!(response);
+}
+
 #[test]
 fn test_terminate_stream() {
     // Create a new streaming service client and listener
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{data_notification::DataNotification, data_stream::DataStreamListener, error::Error};
-use aptos_config::config::AptosDataClientConfig;
+use aptos_config::{config::AptosDataClientConfig, network_id::PeerNetworkId};
 use aptos_crypto::{ed25519::Ed25519PrivateKey, HashValue, PrivateKey, SigningKey, Uniform};
 use aptos_data_client::{
     global_summary::{AdvertisedData, GlobalDataSummary, OptimalChunkSizes},
@@ -23,7 +23,10 @@ use aptos_storage_service_types::{
         SubscriptionStreamMetadata, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
-    responses::{CompleteDataRange, TransactionOrOutputListWithProof},
+    responses::{
+        CompleteDataRange, EventsByKeyWithProof, StateValueChangesWithProof,
+        TransactionOrOutputListWithProof,
+    },
     Epoch,
 };
 use aptos_types::{
@@ -32,6 +35,7 @@ use aptos_types::{
     block_info::BlockInfo,
     chain_id::ChainId,
     epoch_state::EpochState,
+    event::EventKey,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     proof::SparseMerkleRangeProof,
     state_store::{
@@ -88,6 +92,7 @@ pub struct MockAptosDataClient {
     pub advertised_synced_ledger_infos: Vec<LedgerInfoWithSignatures>,
     pub data_beyond_highest_advertised: bool, // If true, data exists beyond the highest advertised
     pub data_request_counter: Arc<Mutex<HashMap<DataRequest, u64>>>, // Tracks the number of times the same data request was made
+    pub excluded_peers: Arc<Mutex<Vec<PeerNetworkId>>>, // Tracks the peers excluded from servicing requests
     pub highest_epoch_ending_ledger_infos: BTreeMap<Epoch, LedgerInfoWithSignatures>,
     pub limit_chunk_sizes: bool, // If true, responses will be truncated to emulate chunk and network limits
     pub skip_emulate_network_latencies: bool, // If true, skips network latency emulation
@@ -125,8 +130,9 @@ impl MockAptosDataClient {
             MAX_REAL_TRANSACTION,
         );
 
-        // Create the data request counter
+        // Create the data request counter and excluded peers
         let data_request_counter = Arc::new(Mutex::new(HashMap::new()));
+        let excluded_peers = Arc::new(Mutex::new(vec![]));
 
         Self {
             aptos_data_client_config,
@@ -134,6 +140,7 @@ impl MockAptosDataClient {
             advertised_synced_ledger_infos,
             data_beyond_highest_advertised,
             data_request_counter,
+            excluded_peers,
             highest_epoch_ending_ledger_infos,
             limit_chunk_sizes,
             skip_emulate_network_latencies,
@@ -282,6 +289,10 @@ impl AptosDataClientInterface for MockAptosDataClient {
         }
     }
 
+    fn exclude_peer(&self, peer: PeerNetworkId, _exclusion_duration: Duration) {
+        self.excluded_peers.lock().push(peer);
+    }
+
     async fn get_state_values_with_proof(
         &self,
         version: Version,
@@ -327,6 +338,27 @@ impl AptosDataClientInterface for MockAptosDataClient {
         Ok(create_data_client_response(state_value_chunk_with_proof))
     }
 
+    async fn get_state_value_changes_with_proof(
+        &self,
+        _proof_version: Version,
+        _start_version: Version,
+        _end_version: Version,
+        _request_timeout_ms: u64,
+    ) -> Result<Response<StateValueChangesWithProof>, aptos_data_client::error::Error> {
+        unimplemented!("State value changes are not requested by data streams!")
+    }
+
+    async fn get_events_by_key_with_proof(
+        &self,
+        _event_key: EventKey,
+        _start_sequence_number: u64,
+        _end_sequence_number: u64,
+        _proof_version: Version,
+        _request_timeout_ms: u64,
+    ) -> Result<Response<EventsByKeyWithProof>, aptos_data_client::error::Error> {
+        unimplemented!("Events by key are not yet requested by data stream tests!")
+    }
+
     async fn get_epoch_ending_ledger_infos(
         &self,
         start_epoch: Epoch,
@@ -860,6 +892,20 @@ impl ResponseCallback for NoopResponseCallback {
     }
 }
 
+/// A response callback that reports the peer that served the response
+#[derive(Debug)]
+pub struct PeerResponseCallback {
+    pub peer: PeerNetworkId,
+}
+
+impl ResponseCallback for PeerResponseCallback {
+    fn notify_bad_response(&self, _error: ResponseError) {}
+
+    fn get_peer(&self) -> Option<PeerNetworkId> {
+        Some(self.peer)
+    }
+}
+
 /// Creates a data client response using a specified payload and random id
 pub fn create_data_client_response<T>(payload: T) -> Response<T> {
     let id = create_random_u64(MAX_RESPONSE_ID);
@@ -867,6 +913,13 @@ pub fn create_data_client_response<T>(payload: T) -> Response<T> {
     Response::new(context, payload)
 }
 
+/// Creates a data client response (served by the given peer) using a specified payload
+pub fn create_data_client_response_from_peer<T>(payload: T, peer: PeerNetworkId) -> Response<T> {
+    let id = create_random_u64(MAX_RESPONSE_ID);
+    let context = ResponseContext::new(id, Box::new(PeerResponseCallback { peer }));
+    Response::new(context, payload)
+}
+
 /// Creates a ledger info with the given version and epoch. If `epoch_ending`
 /// is true, makes the ledger info an epoch ending ledger info.
 pub fn create_ledger_info(
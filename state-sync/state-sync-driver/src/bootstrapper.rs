@@ -5,11 +5,12 @@
 use crate::{
     driver::DriverConfiguration,
     error::Error,
+    local_file_data_stream::LocalFileDataStream,
     logging::{LogEntry, LogSchema},
     metadata_storage::MetadataStorageInterface,
     metrics,
     metrics::ExecutingComponent,
-    storage_synchronizer::{NotificationMetadata, StorageSynchronizerInterface},
+    storage_synchronizer::{NotificationMetadata, ResetReason, StorageSynchronizerInterface},
     utils,
     utils::{OutputFallbackHandler, SpeculativeStreamState, PENDING_DATA_LOG_FREQ_SECS},
 };
@@ -252,6 +253,10 @@ pub(crate) struct StateValueSyncer {
     // processed -- i.e., sent to the storage synchronizer).
     next_state_index_to_process: u64,
 
+    // Whether or not the state snapshot receiver should resume a snapshot sync
+    // that was interrupted (e.g., by a process restart).
+    resume_state_snapshot_receiver: bool,
+
     // The transaction output (inc. info and proof) for the version we're syncing
     transaction_output_to_sync: Option<TransactionOutputListWithProof>,
 }
@@ -262,6 +267,7 @@ impl StateValueSyncer {
             initialized_state_snapshot_receiver: false,
             ledger_info_to_sync: None,
             next_state_index_to_process: 0,
+            resume_state_snapshot_receiver: false,
             transaction_output_to_sync: None,
         }
     }
@@ -290,6 +296,10 @@ pub struct Bootstrapper<MetadataStorage, StorageSyncer, StreamingClient> {
     // The currently active data stream (provided by the data streaming service)
     active_data_stream: Option<DataStreamListener>,
 
+    // The end version of the active data stream (if the stream is served by
+    // the local file data stream, instead of the data streaming service).
+    active_local_data_stream_end_version: Option<Version>,
+
     // The channel used to notify a listener of successful bootstrapping
     bootstrap_notifier_channel: Option<oneshot::Sender<Result<(), Error>>>,
 
@@ -302,9 +312,19 @@ pub struct Bootstrapper<MetadataStorage, StorageSyncer, StreamingClient> {
     // The storage to write metadata about the syncing progress
     metadata_storage: MetadataStorage,
 
+    // The data stream used to serve transaction outputs from a local backup (if any)
+    local_file_data_stream: Option<LocalFileDataStream>,
+
     // The handler for output fallback behaviour
     output_fallback_handler: OutputFallbackHandler,
 
+    // If the node has synced a state snapshot and is now applying transaction
+    // outputs (only used by the DownloadLatestStatesThenApplyOutputs mode).
+    replaying_outputs_after_snapshot: bool,
+
+    // The reason the chunk executor will be reset when the next stream is initialized
+    reset_reason: ResetReason,
+
     // The speculative state tracking the active data stream
     speculative_stream_state: Option<SpeculativeStreamState>,
 
@@ -343,14 +363,37 @@ impl<
             .expect("Unable to fetch latest epoch state!");
         let verified_epoch_states = VerifiedEpochStates::new(latest_epoch_state);
 
+        // Create the local file data stream (if a local backup is configured)
+        let local_file_data_stream =
+            driver_configuration
+                .local_backup_dir
+                .as_ref()
+                .and_then(|local_backup_dir| {
+                    match LocalFileDataStream::new(local_backup_dir.clone()) {
+                        Ok(local_file_data_stream) => Some(local_file_data_stream),
+                        Err(error) => {
+                            error!(LogSchema::new(LogEntry::Bootstrapper)
+                                .error(&error)
+                                .message(
+                                    "Failed to read the local backup! Falling back to the network."
+                                ));
+                            None
+                        },
+                    }
+                });
+
         Self {
             state_value_syncer: StateValueSyncer::new(),
             active_data_stream: None,
+            active_local_data_stream_end_version: None,
             bootstrap_notifier_channel: None,
             bootstrapped: false,
             driver_configuration,
+            local_file_data_stream,
             metadata_storage,
             output_fallback_handler,
+            replaying_outputs_after_snapshot: false,
+            reset_reason: ResetReason::StreamInitialization,
             speculative_stream_state: None,
             streaming_client,
             storage,
@@ -359,9 +402,19 @@ impl<
         }
     }
 
-    /// Returns the bootstrapping mode of the node
+    /// Returns the bootstrapping mode of the node. If the node is configured
+    /// to fast sync and then apply outputs, the mode depends on the current phase.
     fn get_bootstrapping_mode(&self) -> BootstrappingMode {
-        self.driver_configuration.config.bootstrapping_mode
+        match self.driver_configuration.config.bootstrapping_mode {
+            BootstrappingMode::DownloadLatestStatesThenApplyOutputs => {
+                if self.replaying_outputs_after_snapshot {
+                    BootstrappingMode::ApplyTransactionOutputsFromGenesis
+                } else {
+                    BootstrappingMode::DownloadLatestStates
+                }
+            },
+            bootstrapping_mode => bootstrapping_mode,
+        }
     }
 
     /// Returns true iff the node has already completed bootstrapping
@@ -455,7 +508,9 @@ impl<
         global_data_summary: &GlobalDataSummary,
     ) -> Result<(), Error> {
         // Reset the chunk executor to flush any invalid state currently held in-memory
-        self.storage_synchronizer.reset_chunk_executor()?;
+        self.storage_synchronizer
+            .reset_chunk_executor(self.reset_reason)?;
+        self.reset_reason = ResetReason::StreamInitialization;
 
         // Always fetch the new epoch ending ledger infos first
         if self.should_fetch_epoch_ending_ledger_infos() {
@@ -568,6 +623,22 @@ impl<
                 // continuous syncer will take control and get the node up-to-date. If this is a
                 // validator, consensus will take control and sync depending on how it sees fit.
                 self.bootstrapping_complete().await
+            } else if self.driver_configuration.config.bootstrapping_mode
+                == BootstrappingMode::DownloadLatestStatesThenApplyOutputs
+            {
+                // We've already synced a state snapshot. Apply the missing transaction
+                // outputs to reach the highest known version (without restarting the node).
+                info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                    "The node is {} versions behind the highest known version ({}). \
+                    Switching from fast sync to applying transaction outputs!",
+                    num_versions_behind, highest_known_ledger_version
+                )));
+                self.replaying_outputs_after_snapshot = true;
+                self.fetch_missing_transaction_data(
+                    highest_synced_version,
+                    highest_known_ledger_info,
+                )
+                .await
             } else {
                 panic!("Fast syncing is currently unsupported for nodes with existing state! \
                         You are currently {:?} versions behind the latest snapshot version ({:?}). Either \
@@ -591,6 +662,7 @@ impl<
             // If the stream has timed out too many times, we need to reset it
             warn!("Resetting the currently active data stream due to too many timeouts!");
             self.reset_active_stream(None).await?;
+            self.reset_reason = ResetReason::StreamTimeout;
         }
         result
     }
@@ -602,6 +674,18 @@ impl<
             .config
             .max_consecutive_stream_notifications
         {
+            // Stop processing notifications if the storage synchronizer is backed up
+            if self.storage_synchronizer.should_throttle() {
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(PENDING_DATA_LOG_FREQ_SECS)),
+                    info!(
+                        "Pausing stream notifications! Pending data chunks: {}",
+                        self.storage_synchronizer.pending_data_chunk_count()
+                    )
+                );
+                break;
+            }
+
             // Fetch and process any data notifications
             let data_notification = self.fetch_next_data_notification().await?;
             match data_notification.data_payload {
@@ -630,6 +714,7 @@ impl<
                         Some(transactions_with_proof),
                         None,
                         payload_start_version,
+                        None,
                     )
                     .await?;
                 },
@@ -645,6 +730,22 @@ impl<
                         None,
                         Some(transaction_outputs_with_proof),
                         payload_start_version,
+                        None,
+                    )
+                    .await?;
+                },
+                DataPayload::ContinuousTransactionOutputsWithProof(
+                    ledger_info_with_signatures,
+                    transaction_outputs_with_proof,
+                ) if self.active_local_data_stream_end_version.is_some() => {
+                    let notification_metadata = NotificationMetadata::new(
+                        data_notification.creation_time,
+                        data_notification.notification_id,
+                    );
+                    self.process_local_transaction_output_payload(
+                        notification_metadata,
+                        ledger_info_with_signatures,
+                        transaction_outputs_with_proof,
                     )
                     .await?;
                 },
@@ -714,6 +815,14 @@ impl<
                 0 // We need to start the snapshot sync from index 0
             };
 
+            // If the state snapshot receiver hasn't been initialized yet, but there's
+            // existing snapshot progress, the receiver should resume the snapshot sync.
+            if existing_snapshot_progress
+                && !self.state_value_syncer.initialized_state_snapshot_receiver
+            {
+                self.state_value_syncer.resume_state_snapshot_receiver = true;
+            }
+
             // Fetch the missing state values
             self.state_value_syncer
                 .update_next_state_index_to_process(next_state_index_to_process);
@@ -747,13 +856,24 @@ impl<
             })?;
         let data_stream = match self.get_bootstrapping_mode() {
             BootstrappingMode::ApplyTransactionOutputsFromGenesis => {
-                self.streaming_client
-                    .get_all_transaction_outputs(
-                        next_version,
-                        end_version,
-                        highest_known_ledger_version,
-                    )
-                    .await?
+                match &self.local_file_data_stream {
+                    Some(local_file_data_stream)
+                        if local_file_data_stream.contains_version(next_version) =>
+                    {
+                        // Stream the transaction outputs from the local backup
+                        self.active_local_data_stream_end_version = Some(end_version);
+                        local_file_data_stream.stream_transaction_outputs(next_version, end_version)
+                    },
+                    _ => {
+                        self.streaming_client
+                            .get_all_transaction_outputs(
+                                next_version,
+                                end_version,
+                                highest_known_ledger_version,
+                            )
+                            .await?
+                    },
+                }
             },
             BootstrappingMode::ExecuteTransactionsFromGenesis => {
                 self.streaming_client
@@ -987,12 +1107,26 @@ impl<
                 self.verified_epoch_states.all_epoch_ending_ledger_infos() // Sync beyond genesis
             };
 
-            // Initialize the state value synchronizer
-            let _join_handle = self.storage_synchronizer.initialize_state_synchronizer(
-                epoch_change_proofs,
-                ledger_info_to_sync,
-                transaction_output_to_sync.clone(),
-            )?;
+            // Initialize (or resume) the state value synchronizer
+            let _join_handle = if self.state_value_syncer.resume_state_snapshot_receiver {
+                let (join_handle, resumed_state_index) =
+                    self.storage_synchronizer.resume_state_synchronizer(
+                        epoch_change_proofs,
+                        ledger_info_to_sync,
+                        transaction_output_to_sync.clone(),
+                    )?;
+                info!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                    "Resumed the state value synchronizer at state index: {:?}",
+                    resumed_state_index
+                )));
+                join_handle
+            } else {
+                self.storage_synchronizer.initialize_state_synchronizer(
+                    epoch_change_proofs,
+                    ledger_info_to_sync,
+                    transaction_output_to_sync.clone(),
+                )?
+            };
             self.state_value_syncer.initialized_state_snapshot_receiver = true;
         }
 
@@ -1106,13 +1240,59 @@ impl<
         Ok(())
     }
 
-    /// Process a single transaction or transaction output data payload
+    /// Process a single transaction output data payload served from the local
+    /// backup. Each payload carries the ledger info that proves the outputs,
+    /// which must be a verified epoch ending ledger info.
+    async fn process_local_transaction_output_payload(
+        &mut self,
+        notification_metadata: NotificationMetadata,
+        ledger_info_with_signatures: LedgerInfoWithSignatures,
+        transaction_outputs_with_proof: TransactionOutputListWithProof,
+    ) -> Result<(), Error> {
+        // Verify the ledger info is a known epoch ending ledger info
+        let ledger_info_version = ledger_info_with_signatures.ledger_info().version();
+        if self
+            .verified_epoch_states
+            .get_epoch_ending_ledger_info(ledger_info_version)
+            .as_ref()
+            != Some(&ledger_info_with_signatures)
+        {
+            self.disable_local_file_data_stream(
+                "The local backup ledger info is not a verified epoch ending ledger info!",
+            );
+            self.reset_active_stream(Some(NotificationAndFeedback::new(
+                notification_metadata.notification_id,
+                NotificationFeedback::InvalidPayloadData,
+            )))
+            .await?;
+            return Err(Error::VerificationError(format!(
+                "The local backup ledger info could not be verified! Version: {:?}",
+                ledger_info_version
+            )));
+        }
+
+        // Process the payload using the given ledger info as the proof
+        let payload_start_version = transaction_outputs_with_proof.first_transaction_output_version;
+        self.process_transaction_or_output_payload(
+            notification_metadata,
+            None,
+            Some(transaction_outputs_with_proof),
+            payload_start_version,
+            Some(ledger_info_with_signatures),
+        )
+        .await
+    }
+
+    /// Process a single transaction or transaction output data payload. If
+    /// a proof ledger info is given, it is used to verify the payload (instead
+    /// of the proof ledger info of the speculative stream state).
     async fn process_transaction_or_output_payload(
         &mut self,
         notification_metadata: NotificationMetadata,
         transaction_list_with_proof: Option<TransactionListWithProof>,
         transaction_outputs_with_proof: Option<TransactionOutputListWithProof>,
         payload_start_version: Option<Version>,
+        proof_ledger_info: Option<LedgerInfoWithSignatures>,
     ) -> Result<(), Error> {
         // Verify that we're expecting transaction or output payloads
         let bootstrapping_mode = self.get_bootstrapping_mode();
@@ -1154,9 +1334,12 @@ impl<
             .await?;
 
         // Get the expected proof ledger info for the payload
-        let proof_ledger_info = self
-            .get_speculative_stream_state()?
-            .get_proof_ledger_info()?;
+        let proof_ledger_info = match proof_ledger_info {
+            Some(proof_ledger_info) => proof_ledger_info,
+            None => self
+                .get_speculative_stream_state()?
+                .get_proof_ledger_info()?,
+        };
 
         // Get the end of epoch ledger info if the payload ends the epoch
         let end_of_epoch_ledger_info = self
@@ -1466,6 +1649,18 @@ impl<
         let notification_and_feedback =
             NotificationAndFeedback::new(data_notification.notification_id, notification_feedback);
 
+        // If the local backup was unable to serve the entire stream, fall back to the network
+        if let Some(end_version) = self.active_local_data_stream_end_version {
+            let expected_next_version = self
+                .get_speculative_stream_state()?
+                .expected_next_version()?;
+            if expected_next_version <= end_version {
+                self.disable_local_file_data_stream(
+                    "The local backup was unable to serve all requested transaction outputs!",
+                );
+            }
+        }
+
         // Reset the stream
         self.reset_active_stream(Some(notification_and_feedback))
             .await?;
@@ -1507,6 +1702,13 @@ impl<
         &mut self,
         notification_and_feedback: NotificationAndFeedback,
     ) -> Result<(), Error> {
+        // If the failed data was served from the local backup, fall back to the network
+        if self.active_local_data_stream_end_version.is_some() {
+            self.disable_local_file_data_stream(
+                "Failed to process the data served from the local backup!",
+            );
+        }
+
         // Reset the active stream
         self.reset_active_stream(Some(notification_and_feedback))
             .await?;
@@ -1524,26 +1726,54 @@ impl<
         Ok(())
     }
 
+    /// Handles a local storage synchronizer error (i.e., the data was valid)
+    /// sent by the driver. The active stream is reset without feedback.
+    pub async fn handle_storage_synchronizer_local_error(&mut self) -> Result<(), Error> {
+        self.reset_active_stream(None).await?;
+        self.reset_reason = ResetReason::SyncError;
+        Ok(())
+    }
+
     /// Resets the currently active data stream and speculative state
     pub async fn reset_active_stream(
         &mut self,
         notification_and_feedback: Option<NotificationAndFeedback>,
     ) -> Result<(), Error> {
+        if utils::is_sync_error_feedback(&notification_and_feedback) {
+            self.reset_reason = ResetReason::SyncError;
+        }
+
+        // Local data streams are not managed by the data streaming service
+        // (dropping the stream is sufficient to terminate it).
         if let Some(active_data_stream) = &self.active_data_stream {
-            let data_stream_id = active_data_stream.data_stream_id;
-            utils::terminate_stream_with_feedback(
-                &mut self.streaming_client,
-                data_stream_id,
-                notification_and_feedback,
-            )
-            .await?;
+            if self.active_local_data_stream_end_version.is_none() {
+                let data_stream_id = active_data_stream.data_stream_id;
+                utils::terminate_stream_with_feedback(
+                    &mut self.streaming_client,
+                    data_stream_id,
+                    notification_and_feedback,
+                )
+                .await?;
+            }
         }
 
         self.active_data_stream = None;
+        self.active_local_data_stream_end_version = None;
         self.speculative_stream_state = None;
         Ok(())
     }
 
+    /// Disables the local file data stream. All remaining data will be
+    /// fetched from the network.
+    fn disable_local_file_data_stream(&mut self, reason: &str) {
+        if self.local_file_data_stream.take().is_some() {
+            warn!(LogSchema::new(LogEntry::Bootstrapper).message(&format!(
+                "Disabling the local file data stream and falling back to the network! Reason: {}",
+                reason
+            )));
+        }
+    }
+
     /// Returns the verified epoch states struct for testing purposes
     #[cfg(test)]
     pub(crate) fn get_verified_epoch_states(&mut self) -> &mut VerifiedEpochStates {
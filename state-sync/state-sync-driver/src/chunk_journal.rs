@@ -0,0 +1,645 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A debug journal that records every data chunk sent to the storage
+//! synchronizer executor, so that execution bugs can be reproduced offline.
+//!
+//! The journal is written to a rotating set of files in the journal directory.
+//! Each file holds repeated `len(record) + record` entries, where `len` is a
+//! little-endian u64 and `record` is the BCS serialized [`JournaledChunk`].
+//! The journal can be replayed into a fresh storage synchronizer (e.g., on top
+//! of a database checkpoint) using [`replay_chunk_journal`].
+
+use crate::{
+    error::Error,
+    logging::{LogEntry, LogSchema},
+    metadata_storage::MetadataStorageInterface,
+    notification_handlers::{
+        CommitNotificationListener, ErrorNotificationListener, MempoolNotificationHandler,
+        StorageServiceNotificationHandler,
+    },
+    storage_synchronizer::{
+        take_chunk_payload, NotificationMetadata, ResetReason, StorageDataChunk,
+        StorageSynchronizer, StorageSynchronizerInterface,
+    },
+    sync_progress::SyncProgressReporter,
+    utils,
+};
+use aptos_config::config::{ChunkJournalConfig, StateSyncDriverConfig};
+use aptos_data_streaming_service::data_notification::NotificationId;
+use aptos_event_notifications::EventSubscriptionService;
+use aptos_executor_types::ChunkExecutorTrait;
+use aptos_infallible::{Mutex, RwLock};
+use aptos_logger::prelude::*;
+use aptos_storage_interface::DbReaderWriter;
+use aptos_time_service::TimeService;
+use aptos_types::{
+    ledger_info::LedgerInfoWithSignatures,
+    transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
+    waypoint::Waypoint,
+};
+use futures::{FutureExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::VecDeque,
+    fs::{self, File, OpenOptions},
+    io::{BufReader, ErrorKind, Read, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+// The prefix and extension of the journal file names
+const JOURNAL_FILE_PREFIX: &str = "chunk_journal_";
+const JOURNAL_FILE_EXTENSION: &str = "bcs";
+
+// The number of mempool notifications that can be buffered during a replay
+const MAX_PENDING_MEMPOOL_NOTIFICATIONS: u64 = 100;
+
+// The interval (ms) at which a replay checks if the pipeline has drained
+const REPLAY_DRAIN_CHECK_INTERVAL_MS: u64 = 100;
+
+/// A data chunk recorded in the journal. This mirrors the executable
+/// variants of [`StorageDataChunk`] (the notification creation times are
+/// not recorded, as they are only used for metrics).
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum JournaledChunk {
+    Transactions(
+        NotificationId,
+        TransactionListWithProof,
+        LedgerInfoWithSignatures,
+        Option<LedgerInfoWithSignatures>,
+    ),
+    TransactionOutputs(
+        NotificationId,
+        TransactionOutputListWithProof,
+        LedgerInfoWithSignatures,
+        Option<LedgerInfoWithSignatures>,
+    ),
+    Hybrid(
+        NotificationId,
+        TransactionOutputListWithProof,
+        LedgerInfoWithSignatures,
+        Option<LedgerInfoWithSignatures>,
+    ),
+}
+
+/// A borrowed view of a [`JournaledChunk`] (with an identical BCS layout),
+/// used to record chunks without cloning them.
+#[derive(Serialize)]
+enum JournaledChunkRef<'a> {
+    Transactions(
+        NotificationId,
+        &'a TransactionListWithProof,
+        &'a LedgerInfoWithSignatures,
+        &'a Option<LedgerInfoWithSignatures>,
+    ),
+    TransactionOutputs(
+        NotificationId,
+        &'a TransactionOutputListWithProof,
+        &'a LedgerInfoWithSignatures,
+        &'a Option<LedgerInfoWithSignatures>,
+    ),
+    Hybrid(
+        NotificationId,
+        &'a TransactionOutputListWithProof,
+        &'a LedgerInfoWithSignatures,
+        &'a Option<LedgerInfoWithSignatures>,
+    ),
+}
+
+impl<'a> JournaledChunkRef<'a> {
+    /// Returns a view of the given storage data chunk (if it can be journaled)
+    fn new(storage_data_chunk: &'a StorageDataChunk) -> Option<Self> {
+        let chunk_ref = match storage_data_chunk {
+            StorageDataChunk::States(..) => return None, // States are not sent to the executor
+            StorageDataChunk::Transactions(
+                notification_metadata,
+                transactions_with_proof,
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ) => JournaledChunkRef::Transactions(
+                notification_metadata.notification_id,
+                transactions_with_proof,
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ),
+            StorageDataChunk::TransactionOutputs(
+                notification_metadata,
+                outputs_with_proof,
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ) => JournaledChunkRef::TransactionOutputs(
+                notification_metadata.notification_id,
+                outputs_with_proof,
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ),
+            StorageDataChunk::Hybrid(
+                notification_metadata,
+                outputs_with_proof,
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ) => JournaledChunkRef::Hybrid(
+                notification_metadata.notification_id,
+                outputs_with_proof,
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ),
+        };
+        Some(chunk_ref)
+    }
+}
+
+impl JournaledChunk {
+    /// Returns the first version and number of transactions in the chunk
+    pub fn get_version_range(&self) -> (Option<Version>, usize) {
+        match self {
+            JournaledChunk::Transactions(_, transactions_with_proof, _, _) => (
+                transactions_with_proof.first_transaction_version,
+                transactions_with_proof.transactions.len(),
+            ),
+            JournaledChunk::TransactionOutputs(_, outputs_with_proof, _, _)
+            | JournaledChunk::Hybrid(_, outputs_with_proof, _, _) => (
+                outputs_with_proof.first_transaction_output_version,
+                outputs_with_proof.transactions_and_outputs.len(),
+            ),
+        }
+    }
+
+    /// Converts the journaled chunk back into a storage data chunk
+    pub fn into_storage_data_chunk(self) -> StorageDataChunk {
+        match self {
+            JournaledChunk::Transactions(
+                notification_id,
+                transactions_with_proof,
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ) => StorageDataChunk::Transactions(
+                NotificationMetadata::new(Instant::now(), notification_id),
+                Arc::new(transactions_with_proof),
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ),
+            JournaledChunk::TransactionOutputs(
+                notification_id,
+                outputs_with_proof,
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ) => StorageDataChunk::TransactionOutputs(
+                NotificationMetadata::new(Instant::now(), notification_id),
+                Arc::new(outputs_with_proof),
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ),
+            JournaledChunk::Hybrid(
+                notification_id,
+                outputs_with_proof,
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ) => StorageDataChunk::Hybrid(
+                NotificationMetadata::new(Instant::now(), notification_id),
+                Arc::new(outputs_with_proof),
+                target_ledger_info,
+                end_of_epoch_ledger_info,
+            ),
+        }
+    }
+}
+
+/// A writer that records storage data chunks to the journal files
+pub struct ChunkJournal {
+    current_file: File,
+    current_file_index: u64,
+    current_file_size_bytes: u64,
+    journal_dir: PathBuf,
+    max_file_size_bytes: u64,
+    max_num_files: u64,
+}
+
+impl ChunkJournal {
+    /// Creates a new chunk journal for the given config. If the journal is
+    /// disabled, None is returned. Existing journal files are kept, and new
+    /// chunks are recorded to a new file (after the existing ones).
+    pub fn new(config: &ChunkJournalConfig) -> Result<Option<Self>, Error> {
+        let journal_dir = match &config.journal_dir {
+            Some(journal_dir) => journal_dir.clone(),
+            None => return Ok(None),
+        };
+        fs::create_dir_all(&journal_dir).map_err(|error| {
+            Error::ChunkJournalError(format!(
+                "Failed to create the journal directory: {:?}, error: {:?}",
+                journal_dir, error
+            ))
+        })?;
+
+        // Start a new file after the existing ones
+        let next_file_index = list_journal_files(&journal_dir)?
+            .last()
+            .map(|(file_index, _)| file_index + 1)
+            .unwrap_or(0);
+        let current_file = create_journal_file(&journal_dir, next_file_index)?;
+
+        let chunk_journal = Self {
+            current_file,
+            current_file_index: next_file_index,
+            current_file_size_bytes: 0,
+            journal_dir,
+            max_file_size_bytes: config.max_file_size_bytes,
+            max_num_files: config.max_num_files.max(1),
+        };
+        chunk_journal.prune_files()?;
+
+        Ok(Some(chunk_journal))
+    }
+
+    /// Records the given storage data chunk to the journal
+    pub fn record_chunk(&mut self, storage_data_chunk: &StorageDataChunk) -> Result<(), Error> {
+        let chunk_ref = match JournaledChunkRef::new(storage_data_chunk) {
+            Some(chunk_ref) => chunk_ref,
+            None => return Ok(()),
+        };
+        let record = bcs::to_bytes(&chunk_ref).map_err(|error| {
+            Error::ChunkJournalError(format!("Failed to serialize the chunk: {:?}", error))
+        })?;
+
+        // Rotate the journal file (if the current file is full)
+        if self.current_file_size_bytes > 0
+            && self.current_file_size_bytes + record.len() as u64 > self.max_file_size_bytes
+        {
+            self.rotate_file()?;
+        }
+
+        // Write the record to the current file
+        let mut entry = Vec::with_capacity(record.len() + 8);
+        entry.extend_from_slice(&(record.len() as u64).to_le_bytes());
+        entry.extend_from_slice(&record);
+        self.current_file.write_all(&entry).map_err(|error| {
+            Error::ChunkJournalError(format!(
+                "Failed to write the chunk to the journal: {:?}",
+                error
+            ))
+        })?;
+        self.current_file_size_bytes += entry.len() as u64;
+
+        Ok(())
+    }
+
+    /// Starts a new journal file (and deletes the oldest files, if required)
+    fn rotate_file(&mut self) -> Result<(), Error> {
+        let next_file_index = self.current_file_index + 1;
+        self.current_file = create_journal_file(&self.journal_dir, next_file_index)?;
+        self.current_file_index = next_file_index;
+        self.current_file_size_bytes = 0;
+        self.prune_files()
+    }
+
+    /// Deletes the oldest journal files so that at most `max_num_files` remain
+    fn prune_files(&self) -> Result<(), Error> {
+        let journal_files = list_journal_files(&self.journal_dir)?;
+        let num_files_to_delete = journal_files
+            .len()
+            .saturating_sub(self.max_num_files as usize);
+        for (_, file_path) in journal_files.into_iter().take(num_files_to_delete) {
+            fs::remove_file(&file_path).map_err(|error| {
+                Error::ChunkJournalError(format!(
+                    "Failed to delete the journal file: {:?}, error: {:?}",
+                    file_path, error
+                ))
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// A reader that iterates over the chunks in the journal files (oldest first)
+pub struct ChunkJournalReader {
+    current_reader: Option<(PathBuf, BufReader<File>)>,
+    remaining_files: VecDeque<PathBuf>,
+}
+
+impl ChunkJournalReader {
+    pub fn new(journal_dir: &Path) -> Result<Self, Error> {
+        let remaining_files = list_journal_files(journal_dir)?
+            .into_iter()
+            .map(|(_, file_path)| file_path)
+            .collect();
+        Ok(Self {
+            current_reader: None,
+            remaining_files,
+        })
+    }
+
+    /// Reads the next chunk from the journal. Returns None once all files have
+    /// been read. A truncated record at the end of a file (e.g., because the
+    /// node crashed mid-write) is skipped.
+    fn read_next_chunk(&mut self) -> Result<Option<JournaledChunk>, Error> {
+        loop {
+            // Open the next file (if required)
+            let (file_path, reader) = match &mut self.current_reader {
+                Some((file_path, reader)) => (file_path.clone(), reader),
+                None => match self.remaining_files.pop_front() {
+                    Some(file_path) => {
+                        let file = File::open(&file_path).map_err(|error| {
+                            Error::ChunkJournalError(format!(
+                                "Failed to open the journal file: {:?}, error: {:?}",
+                                file_path, error
+                            ))
+                        })?;
+                        self.current_reader = Some((file_path, BufReader::new(file)));
+                        continue;
+                    },
+                    None => return Ok(None),
+                },
+            };
+
+            // Read the next record from the file
+            match read_record(reader) {
+                Ok(Some(record)) => {
+                    let chunk = bcs::from_bytes(&record).map_err(|error| {
+                        Error::ChunkJournalError(format!(
+                            "Failed to deserialize a chunk in the journal file: {:?}, error: {:?}",
+                            file_path, error
+                        ))
+                    })?;
+                    return Ok(Some(chunk));
+                },
+                Ok(None) => {
+                    self.current_reader = None; // The file has been fully read
+                },
+                Err(error) if error.kind() == ErrorKind::UnexpectedEof => {
+                    warn!(
+                        LogSchema::new(LogEntry::StorageSynchronizer).message(&format!(
+                            "Skipping a truncated record at the end of the journal file: {:?}",
+                            file_path
+                        ))
+                    );
+                    self.current_reader = None;
+                },
+                Err(error) => {
+                    return Err(Error::ChunkJournalError(format!(
+                        "Failed to read the journal file: {:?}, error: {:?}",
+                        file_path, error
+                    )))
+                },
+            }
+        }
+    }
+}
+
+impl Iterator for ChunkJournalReader {
+    type Item = Result<JournaledChunk, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_next_chunk().transpose()
+    }
+}
+
+/// The summary of a chunk journal replay
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+pub struct ChunkJournalReplaySummary {
+    pub num_chunks_replayed: u64,
+    pub num_chunks_skipped: u64, // Chunks that were already committed to storage
+    pub synced_version: Option<Version>,
+}
+
+/// Replays the chunk journal in the given directory into a fresh storage
+/// synchronizer (built on the given chunk executor and storage). Chunks that
+/// have already been committed to storage are skipped, so the storage should
+/// be a checkpoint of the node database taken at (or before) the start of the
+/// journal. The replay stops at the first chunk that fails to execute (or
+/// commit), and the error is returned.
+pub async fn replay_chunk_journal<
+    ChunkExecutor: ChunkExecutorTrait + 'static,
+    MetadataStorage: MetadataStorageInterface + Clone + Send + Sync + 'static,
+>(
+    journal_dir: &Path,
+    driver_config: StateSyncDriverConfig,
+    chunk_executor: Arc<ChunkExecutor>,
+    metadata_storage: MetadataStorage,
+    storage: DbReaderWriter,
+) -> Result<ChunkJournalReplaySummary, Error> {
+    // Create the notification handlers (the notifications are dropped)
+    let (commit_notification_sender, mut commit_notification_listener) =
+        CommitNotificationListener::new();
+    let (error_notification_sender, mut error_notification_listener) =
+        ErrorNotificationListener::new();
+    let event_subscription_service = Arc::new(Mutex::new(EventSubscriptionService::new(Arc::new(
+        RwLock::new(storage.clone()),
+    ))));
+    let (mempool_notifier, mut mempool_listener) =
+        aptos_mempool_notifications::new_mempool_notifier_listener_pair(
+            MAX_PENDING_MEMPOOL_NOTIFICATIONS,
+        );
+    let (storage_service_notifier, mut storage_service_listener) =
+        aptos_storage_service_notifications::new_storage_service_notifier_listener_pair();
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                Some(_) = commit_notification_listener.next() => {},
+                Some(_) = mempool_listener.next() => {},
+                Some(_) = storage_service_listener.next() => {},
+                else => return,
+            }
+        }
+    });
+
+    // Create the storage synchronizer
+    let (mut storage_synchronizer, _) = StorageSynchronizer::new(
+        driver_config,
+        chunk_executor,
+        commit_notification_sender,
+        error_notification_sender,
+        event_subscription_service,
+        MempoolNotificationHandler::new(mempool_notifier),
+        StorageServiceNotificationHandler::new(storage_service_notifier),
+        metadata_storage,
+        storage.clone(),
+        SyncProgressReporter::new(TimeService::real()),
+        Waypoint::default(),
+        None,
+    );
+    storage_synchronizer.reset_chunk_executor(ResetReason::ChunkJournalReplay)?;
+
+    // Feed the journaled chunks into the storage synchronizer
+    let mut summary = ChunkJournalReplaySummary::default();
+    let mut next_version = utils::fetch_latest_synced_version(storage.reader.clone())? + 1;
+    for chunk in ChunkJournalReader::new(journal_dir)? {
+        // Skip chunks that have already been committed (or are empty)
+        let chunk = chunk?;
+        let (first_version, num_transactions) = chunk.get_version_range();
+        let first_version = match first_version {
+            Some(first_version) if num_transactions > 0 => first_version,
+            _ => {
+                summary.num_chunks_skipped += 1;
+                continue;
+            },
+        };
+        if first_version + (num_transactions as u64) <= next_version {
+            summary.num_chunks_skipped += 1;
+            continue;
+        }
+        if first_version > next_version {
+            return Err(Error::ChunkJournalError(format!(
+                "The journal is missing data! Expected version: {}, found chunk at version: {}",
+                next_version, first_version
+            )));
+        }
+
+        // Replay the chunk
+        replay_chunk(&mut storage_synchronizer, chunk.into_storage_data_chunk()).await?;
+        next_version = first_version + num_transactions as u64;
+        summary.num_chunks_replayed += 1;
+        check_for_replay_error(&mut error_notification_listener)?;
+    }
+
+    // Wait for the pipeline to drain
+    while storage_synchronizer.pending_storage_data() {
+        check_for_replay_error(&mut error_notification_listener)?;
+        tokio::time::sleep(Duration::from_millis(REPLAY_DRAIN_CHECK_INTERVAL_MS)).await;
+    }
+    check_for_replay_error(&mut error_notification_listener)?;
+
+    summary.synced_version = Some(utils::fetch_latest_synced_version(storage.reader.clone())?);
+    Ok(summary)
+}
+
+/// Sends the given storage data chunk to the storage synchronizer
+async fn replay_chunk<StorageSyncer: StorageSynchronizerInterface>(
+    storage_synchronizer: &mut StorageSyncer,
+    storage_data_chunk: StorageDataChunk,
+) -> Result<(), Error> {
+    match storage_data_chunk {
+        StorageDataChunk::Transactions(
+            notification_metadata,
+            transactions_with_proof,
+            target_ledger_info,
+            end_of_epoch_ledger_info,
+        ) => {
+            storage_synchronizer
+                .execute_transactions(
+                    notification_metadata,
+                    take_chunk_payload(transactions_with_proof),
+                    target_ledger_info,
+                    end_of_epoch_ledger_info,
+                )
+                .await
+        },
+        StorageDataChunk::TransactionOutputs(
+            notification_metadata,
+            outputs_with_proof,
+            target_ledger_info,
+            end_of_epoch_ledger_info,
+        ) => {
+            storage_synchronizer
+                .apply_transaction_outputs(
+                    notification_metadata,
+                    take_chunk_payload(outputs_with_proof),
+                    target_ledger_info,
+                    end_of_epoch_ledger_info,
+                )
+                .await
+        },
+        StorageDataChunk::Hybrid(
+            notification_metadata,
+            outputs_with_proof,
+            target_ledger_info,
+            end_of_epoch_ledger_info,
+        ) => {
+            storage_synchronizer
+                .verify_transaction_outputs_by_execution(
+                    notification_metadata,
+                    take_chunk_payload(outputs_with_proof),
+                    target_ledger_info,
+                    end_of_epoch_ledger_info,
+                )
+                .await
+        },
+        StorageDataChunk::States(..) => Err(Error::ChunkJournalError(
+            "State value chunks cannot be replayed!".into(),
+        )),
+    }
+}
+
+/// Returns an error if the storage synchronizer has reported one
+fn check_for_replay_error(
+    error_notification_listener: &mut ErrorNotificationListener,
+) -> Result<(), Error> {
+    if let Some(Some(error_notification)) = error_notification_listener.next().now_or_never() {
+        return Err(Error::ChunkJournalError(format!(
+            "Failed to replay the chunk with notification ID: {}, error: {:?}",
+            error_notification.notification_id, error_notification.error
+        )));
+    }
+    Ok(())
+}
+
+/// Returns the journal files in the given directory, ordered by file index
+pub fn list_journal_files(journal_dir: &Path) -> Result<Vec<(u64, PathBuf)>, Error> {
+    let directory_entries = fs::read_dir(journal_dir).map_err(|error| {
+        Error::ChunkJournalError(format!(
+            "Failed to read the journal directory: {:?}, error: {:?}",
+            journal_dir, error
+        ))
+    })?;
+
+    let mut journal_files = vec![];
+    for directory_entry in directory_entries.flatten() {
+        let file_path = directory_entry.path();
+        if file_path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            != Some(JOURNAL_FILE_EXTENSION)
+        {
+            continue;
+        }
+        let file_index = file_path
+            .file_stem()
+            .and_then(|file_stem| file_stem.to_str())
+            .and_then(|file_stem| file_stem.strip_prefix(JOURNAL_FILE_PREFIX))
+            .and_then(|file_index| file_index.parse::<u64>().ok());
+        if let Some(file_index) = file_index {
+            journal_files.push((file_index, file_path));
+        }
+    }
+    journal_files.sort();
+
+    Ok(journal_files)
+}
+
+/// Creates the journal file with the given index
+fn create_journal_file(journal_dir: &Path, file_index: u64) -> Result<File, Error> {
+    let file_path = journal_dir.join(format!(
+        "{}{:010}.{}",
+        JOURNAL_FILE_PREFIX, file_index, JOURNAL_FILE_EXTENSION
+    ));
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&file_path)
+        .map_err(|error| {
+            Error::ChunkJournalError(format!(
+                "Failed to create the journal file: {:?}, error: {:?}",
+                file_path, error
+            ))
+        })
+}
+
+/// Reads the next `len(record) + record` entry from the given reader. Returns
+/// None if the reader is at the end of the file.
+fn read_record<R: Read>(reader: &mut R) -> std::io::Result<Option<Vec<u8>>> {
+    let mut length_bytes = [0u8; 8];
+    let mut num_bytes_read = 0;
+    while num_bytes_read < length_bytes.len() {
+        match reader.read(&mut length_bytes[num_bytes_read..])? {
+            0 if num_bytes_read == 0 => return Ok(None),
+            0 => return Err(ErrorKind::UnexpectedEof.into()),
+            num_bytes => num_bytes_read += num_bytes,
+        }
+    }
+
+    let mut record = vec![0u8; u64::from_le_bytes(length_bytes) as usize];
+    reader.read_exact(&mut record)?;
+    Ok(Some(record))
+}
@@ -8,7 +8,7 @@ use crate::{
     metrics,
     metrics::ExecutingComponent,
     notification_handlers::ConsensusSyncRequest,
-    storage_synchronizer::{NotificationMetadata, StorageSynchronizerInterface},
+    storage_synchronizer::{NotificationMetadata, ResetReason, StorageSynchronizerInterface},
     utils,
     utils::{OutputFallbackHandler, SpeculativeStreamState, PENDING_DATA_LOG_FREQ_SECS},
 };
@@ -38,6 +38,9 @@ pub struct ContinuousSyncer<StorageSyncer, StreamingClient> {
     // The handler for output fallback behaviour
     output_fallback_handler: OutputFallbackHandler,
 
+    // The reason the chunk executor will be reset when the next stream is initialized
+    reset_reason: ResetReason,
+
     // The speculative state tracking the active data stream
     speculative_stream_state: Option<SpeculativeStreamState>,
 
@@ -67,6 +70,7 @@ impl<
             active_data_stream: None,
             driver_configuration,
             output_fallback_handler,
+            reset_reason: ResetReason::StreamInitialization,
             speculative_stream_state: None,
             streaming_client,
             storage,
@@ -103,7 +107,15 @@ impl<
         consensus_sync_request: Arc<Mutex<Option<ConsensusSyncRequest>>>,
     ) -> Result<(), Error> {
         // Reset the chunk executor to flush any invalid state currently held in-memory
-        self.storage_synchronizer.reset_chunk_executor()?;
+        let reset_reason = match self.reset_reason {
+            ResetReason::StreamInitialization if consensus_sync_request.lock().is_some() => {
+                ResetReason::ConsensusSyncRequest
+            },
+            reset_reason => reset_reason,
+        };
+        self.storage_synchronizer
+            .reset_chunk_executor(reset_reason)?;
+        self.reset_reason = ResetReason::StreamInitialization;
 
         // Fetch the highest synced version and epoch (in storage)
         let (highest_synced_version, highest_synced_epoch) =
@@ -120,7 +132,8 @@ impl<
 
         // Initialize a new active data stream
         let active_data_stream = match self.get_continuous_syncing_mode() {
-            ContinuousSyncingMode::ApplyTransactionOutputs => {
+            ContinuousSyncingMode::ApplyTransactionOutputs
+            | ContinuousSyncingMode::ApplyWithSpotVerification { .. } => {
                 self.streaming_client
                     .continuously_stream_transaction_outputs(
                         highest_synced_version,
@@ -194,6 +207,7 @@ impl<
             // If the stream has timed out too many times, we need to reset it
             warn!("Resetting the currently active data stream due to too many timeouts!");
             self.reset_active_stream(None).await?;
+            self.reset_reason = ResetReason::StreamTimeout;
         }
         result
     }
@@ -208,6 +222,18 @@ impl<
             .config
             .max_consecutive_stream_notifications
         {
+            // Stop processing notifications if the storage synchronizer is backed up
+            if self.storage_synchronizer.should_throttle() {
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(PENDING_DATA_LOG_FREQ_SECS)),
+                    info!(
+                        "Pausing stream notifications! Pending data chunks: {}",
+                        self.storage_synchronizer.pending_data_chunk_count()
+                    )
+                );
+                break;
+            }
+
             // Fetch and process any data notifications
             let data_notification = self.fetch_next_data_notification().await?;
             match data_notification.data_payload {
@@ -375,6 +401,38 @@ impl<
                     ));
                 }
             },
+            ContinuousSyncingMode::ApplyWithSpotVerification { sample_rate } => {
+                if let Some(transaction_outputs_with_proof) = transaction_outputs_with_proof {
+                    if utils::should_spot_verify_chunk(sample_rate) {
+                        utils::verify_transaction_outputs_by_execution(
+                            self.storage_synchronizer.clone(),
+                            notification_metadata,
+                            ledger_info_with_signatures.clone(),
+                            None,
+                            transaction_outputs_with_proof,
+                        )
+                        .await?
+                    } else {
+                        utils::apply_transaction_outputs(
+                            self.storage_synchronizer.clone(),
+                            notification_metadata,
+                            ledger_info_with_signatures.clone(),
+                            None,
+                            transaction_outputs_with_proof,
+                        )
+                        .await?
+                    }
+                } else {
+                    self.reset_active_stream(Some(NotificationAndFeedback::new(
+                        notification_metadata.notification_id,
+                        NotificationFeedback::PayloadTypeIsIncorrect,
+                    )))
+                    .await?;
+                    return Err(Error::InvalidPayload(
+                        "Did not receive transaction outputs with proof!".into(),
+                    ));
+                }
+            },
         };
         let synced_version = payload_start_version
             .checked_add(num_transactions_or_outputs as u64)
@@ -524,11 +582,23 @@ impl<
         Ok(())
     }
 
+    /// Handles a local storage synchronizer error (i.e., the data was valid)
+    /// sent by the driver. The active stream is reset without feedback.
+    pub async fn handle_storage_synchronizer_local_error(&mut self) -> Result<(), Error> {
+        self.reset_active_stream(None).await?;
+        self.reset_reason = ResetReason::SyncError;
+        Ok(())
+    }
+
     /// Resets the currently active data stream and speculative state
     pub async fn reset_active_stream(
         &mut self,
         notification_and_feedback: Option<NotificationAndFeedback>,
     ) -> Result<(), Error> {
+        if utils::is_sync_error_feedback(&notification_and_feedback) {
+            self.reset_reason = ResetReason::SyncError;
+        }
+
         if let Some(active_data_stream) = &self.active_data_stream {
             let data_stream_id = active_data_stream.data_stream_id;
             utils::terminate_stream_with_feedback(
@@ -20,13 +20,13 @@ use crate::{
     utils,
     utils::{OutputFallbackHandler, PENDING_DATA_LOG_FREQ_SECS},
 };
-use aptos_config::config::{RoleType, StateSyncDriverConfig};
+use aptos_config::config::{CommitPostProcessorConfig, RoleType, StateSyncDriverConfig};
 use aptos_consensus_notifications::{
     ConsensusCommitNotification, ConsensusNotification, ConsensusSyncNotification,
 };
 use aptos_data_client::interface::AptosDataClientInterface;
 use aptos_data_streaming_service::streaming_client::{
-    DataStreamingClient, NotificationAndFeedback, NotificationFeedback,
+    DataStreamingClient, NotificationAndFeedback,
 };
 use aptos_event_notifications::EventSubscriptionService;
 use aptos_infallible::Mutex;
@@ -37,7 +37,7 @@ use aptos_storage_service_notifications::StorageServiceNotificationSender;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{contract_event::ContractEvent, waypoint::Waypoint};
 use futures::StreamExt;
-use std::{sync::Arc, time::Instant};
+use std::{path::PathBuf, sync::Arc, time::Instant};
 use tokio::{
     task::yield_now,
     time::{interval, Duration},
@@ -53,6 +53,9 @@ pub struct DriverConfiguration {
     // The config file of the driver
     pub config: StateSyncDriverConfig,
 
+    // The local backup directory from which to bootstrap (if any)
+    pub local_backup_dir: Option<PathBuf>,
+
     // The role of the node
     pub role: RoleType,
 
@@ -61,9 +64,15 @@ pub struct DriverConfiguration {
 }
 
 impl DriverConfiguration {
-    pub fn new(config: StateSyncDriverConfig, role: RoleType, waypoint: Waypoint) -> Self {
+    pub fn new(
+        config: StateSyncDriverConfig,
+        local_backup_dir: Option<PathBuf>,
+        role: RoleType,
+        waypoint: Waypoint,
+    ) -> Self {
         Self {
             config,
+            local_backup_dir,
             role,
             waypoint,
         }
@@ -311,12 +320,14 @@ impl<
             events: consensus_commit_notification.subscribable_events.clone(),
             transactions: consensus_commit_notification.transactions.clone(),
         };
+        // Note: consensus commits must always notify all components (e.g., mempool)
         utils::handle_committed_transactions(
             committed_transactions,
             self.storage.clone(),
             self.mempool_notification_handler.clone(),
             self.event_subscription_service.clone(),
             self.storage_service_notification_handler.clone(),
+            CommitPostProcessorConfig::default(),
         )
         .await;
 
@@ -434,6 +445,9 @@ impl<
             self.mempool_notification_handler.clone(),
             self.event_subscription_service.clone(),
             self.storage_service_notification_handler.clone(),
+            self.driver_configuration
+                .config
+                .commit_post_processor_config,
         )
         .await;
     }
@@ -444,9 +458,32 @@ impl<
             .error_notification(error_notification.clone())
             .message("Received an error notification from the storage synchronizer!"));
 
-        // Terminate the currently active streams
+        // If the error was caused by a local failure (e.g., a failed storage
+        // write), the data was valid. Reset the active stream (without feedback)
+        // so that the data is refetched, but don't penalize the peers or fallback.
         let notification_id = error_notification.notification_id;
-        let notification_feedback = NotificationFeedback::InvalidPayloadData;
+        let notification_feedback = match error_notification.get_notification_feedback() {
+            Some(notification_feedback) => notification_feedback,
+            None => {
+                let result = if self.bootstrapper.is_bootstrapped() {
+                    self.continuous_syncer
+                        .handle_storage_synchronizer_local_error()
+                        .await
+                } else {
+                    self.bootstrapper
+                        .handle_storage_synchronizer_local_error()
+                        .await
+                };
+                if let Err(error) = result {
+                    error!(LogSchema::new(LogEntry::SynchronizerNotification)
+                        .error(&error)
+                        .message("Failed to reset the active stream after a local error!"));
+                }
+                return;
+            },
+        };
+
+        // Otherwise, terminate the currently active streams with feedback
         if self.bootstrapper.is_bootstrapped() {
             if let Err(error) = self
                 .continuous_syncer
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    chunk_journal::ChunkJournal,
     driver::{DriverConfiguration, StateSyncDriver},
     driver_client::{ClientNotificationListener, DriverClient, DriverNotification},
     metadata_storage::MetadataStorageInterface,
@@ -10,7 +11,9 @@ use crate::{
         CommitNotification, CommitNotificationListener, ConsensusNotificationHandler,
         ErrorNotificationListener, MempoolNotificationHandler, StorageServiceNotificationHandler,
     },
+    state_snapshot_filter::StateSnapshotFilter,
     storage_synchronizer::StorageSynchronizer,
+    sync_progress::SyncProgressReporter,
 };
 use aptos_config::config::NodeConfig;
 use aptos_consensus_notifications::ConsensusNotificationListener;
@@ -34,6 +37,7 @@ use tokio::runtime::Runtime;
 /// Creates a new state sync driver and client
 pub struct DriverFactory {
     client_notification_sender: mpsc::UnboundedSender<DriverNotification>,
+    sync_progress_reporter: SyncProgressReporter,
     _driver_runtime: Option<Runtime>,
 }
 
@@ -137,9 +141,12 @@ impl DriverFactory {
             None
         };
 
+        // Create the sync progress reporter
+        let sync_progress_reporter = SyncProgressReporter::new(time_service.clone());
+
         // Create the storage synchronizer
         let event_subscription_service = Arc::new(Mutex::new(event_subscription_service));
-        let (storage_synchronizer, _) = StorageSynchronizer::new(
+        let (mut storage_synchronizer, _) = StorageSynchronizer::new(
             node_config.state_sync.state_sync_driver,
             chunk_executor,
             commit_notification_sender.clone(),
@@ -149,12 +156,21 @@ impl DriverFactory {
             storage_service_notification_handler.clone(),
             metadata_storage.clone(),
             storage.clone(),
+            sync_progress_reporter.clone(),
+            waypoint,
             driver_runtime.as_ref(),
         );
+        storage_synchronizer.set_state_snapshot_filter(StateSnapshotFilter::new(
+            &node_config.state_sync.state_snapshot_filter,
+        ));
+        let chunk_journal = ChunkJournal::new(&node_config.state_sync.chunk_journal)
+            .expect("Failed to create the chunk journal!");
+        storage_synchronizer.set_chunk_journal(chunk_journal);
 
         // Create the driver configuration
         let driver_configuration = DriverConfiguration::new(
             node_config.state_sync.state_sync_driver,
+            node_config.state_sync.local_backup_dir.clone(),
             node_config.base.role,
             waypoint,
         );
@@ -187,6 +203,7 @@ impl DriverFactory {
         // Create the driver factory
         let driver_factory = Self {
             client_notification_sender,
+            sync_progress_reporter,
             _driver_runtime: driver_runtime,
         };
 
@@ -197,6 +214,11 @@ impl DriverFactory {
     pub fn create_driver_client(&self) -> DriverClient {
         DriverClient::new(self.client_notification_sender.clone())
     }
+
+    /// Returns a (read-only) handle to the sync progress reporter
+    pub fn get_sync_progress_reporter(&self) -> SyncProgressReporter {
+        self.sync_progress_reporter.clone()
+    }
 }
 
 /// A struct for holding the various runtimes required by state sync v2.
@@ -224,6 +246,11 @@ impl StateSyncRuntimes {
         }
     }
 
+    /// Returns a new client that can be used to communicate with the driver
+    pub fn create_driver_client(&self) -> DriverClient {
+        self.state_sync.create_driver_client()
+    }
+
     pub fn block_until_initialized(&self) {
         let state_sync_client = self.state_sync.create_driver_client();
         block_on(state_sync_client.notify_once_bootstrapped())
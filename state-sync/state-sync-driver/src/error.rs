@@ -17,18 +17,26 @@ pub enum Error {
     BootstrapNotComplete(String),
     #[error("Failed to send callback: {0}")]
     CallbackSendFailed(String),
+    #[error("A channel between components was closed: {0}")]
+    ChannelClosed(String),
+    #[error("Chunk journal error: {0}")]
+    ChunkJournalError(String),
     #[error("Timed-out waiting for a data stream too many times. Times: {0}")]
     CriticalDataStreamTimeout(String),
     #[error("Timed-out waiting for a notification from the data stream. Timeout: {0}")]
     DataStreamNotificationTimeout(String),
     #[error("Error encountered in the event subscription service: {0}")]
     EventNotificationError(String),
+    #[error("Failed to execute or apply the data: {0}")]
+    ExecutionFailure(String),
     #[error("A consensus notification was sent to a full node: {0}")]
     FullNodeConsensusNotification(String),
     #[error("An integer overflow has occurred: {0}")]
     IntegerOverflow(String),
     #[error("An invalid payload was received: {0}")]
     InvalidPayload(String),
+    #[error("Failed to read data from the local backup: {0}")]
+    LocalBackupError(String),
     #[error("Failed to notify mempool of the new commit: {0}")]
     NotifyMempoolError(String),
     #[error("Failed to notify the storage service of the new commit: {0}")]
@@ -39,6 +47,8 @@ pub enum Error {
     SenderDroppedError(String),
     #[error("Unexpected storage error: {0}")]
     StorageError(String),
+    #[error("The storage synchronizer pipeline has stalled! Stuck channel: {0}")]
+    StorageSynchronizerStalled(String),
     #[error("Synced beyond the target version. Committed version: {0}, target version: {1}")]
     SyncedBeyondTarget(Version, Version),
     #[error("Verification error: {0}")]
@@ -57,17 +67,22 @@ impl Error {
             Error::AdvertisedDataError(_) => "advertised_data_error",
             Error::BootstrapNotComplete(_) => "bootstrap_not_complete",
             Error::CallbackSendFailed(_) => "callback_send_failed",
+            Error::ChannelClosed(_) => "channel_closed",
+            Error::ChunkJournalError(_) => "chunk_journal_error",
             Error::CriticalDataStreamTimeout(_) => "critical_data_stream_timeout",
             Error::DataStreamNotificationTimeout(_) => "data_stream_notification_timeout",
             Error::EventNotificationError(_) => "event_notification_error",
+            Error::ExecutionFailure(_) => "execution_failure",
             Error::FullNodeConsensusNotification(_) => "full_node_consensus_notification",
             Error::IntegerOverflow(_) => "integer_overflow",
             Error::InvalidPayload(_) => "invalid_payload",
+            Error::LocalBackupError(_) => "local_backup_error",
             Error::NotifyMempoolError(_) => "notify_mempool_error",
             Error::NotifyStorageServiceError(_) => "notify_storage_service_error",
             Error::OldSyncRequest(_, _) => "old_sync_request",
             Error::SenderDroppedError(_) => "sender_dropped_error",
             Error::StorageError(_) => "storage_error",
+            Error::StorageSynchronizerStalled(_) => "storage_synchronizer_stalled",
             Error::SyncedBeyondTarget(_, _) => "synced_beyond_target",
             Error::VerificationError(_) => "verification_error",
             Error::UnexpectedError(_) => "unexpected_error",
@@ -76,6 +91,55 @@ impl Error {
     }
 }
 
+/// The class of an error. This is the only classification of errors: the
+/// class determines how the error is handled (e.g., if the failed operation
+/// is retried, or if the peers that served the data are penalized).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorClass {
+    ChannelClosed,       // A channel between components was closed
+    ExecutorBusy,        // The executor (or storage synchronizer pipeline) is busy or stalled
+    StorageIo,           // A storage read or write failed (e.g., due to a transient IO failure)
+    VerificationFailure, // The data failed verification (or execution)
+    Unclassified,        // Any other error
+}
+
+impl ErrorClass {
+    /// Returns a summary label for the error class
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ErrorClass::ChannelClosed => "channel_closed",
+            ErrorClass::ExecutorBusy => "executor_busy",
+            ErrorClass::StorageIo => "storage_io",
+            ErrorClass::VerificationFailure => "verification_failure",
+            ErrorClass::Unclassified => "unclassified",
+        }
+    }
+
+    /// Returns true iff the error was caused by a local failure (i.e.,
+    /// the data itself was not at fault).
+    pub fn is_local_failure(&self) -> bool {
+        matches!(
+            self,
+            ErrorClass::ChannelClosed | ErrorClass::ExecutorBusy | ErrorClass::StorageIo
+        )
+    }
+}
+
+impl Error {
+    /// Returns the class of the error
+    pub fn get_error_class(&self) -> ErrorClass {
+        match self {
+            Error::ChannelClosed(_) | Error::SenderDroppedError(_) => ErrorClass::ChannelClosed,
+            Error::StorageSynchronizerStalled(_) => ErrorClass::ExecutorBusy,
+            Error::StorageError(_) => ErrorClass::StorageIo,
+            Error::ExecutionFailure(_) | Error::InvalidPayload(_) | Error::VerificationError(_) => {
+                ErrorClass::VerificationFailure
+            },
+            _ => ErrorClass::Unclassified,
+        }
+    }
+}
+
 impl From<Canceled> for Error {
     fn from(canceled: Canceled) -> Self {
         Error::SenderDroppedError(canceled.to_string())
@@ -0,0 +1,190 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A deterministic fault injection harness for the storage synchronizer pipeline.
+//!
+//! When enabled, each channel between the pipeline stages (i.e., the executor,
+//! ledger updater, committer and commit post-processor) is interposed by a
+//! forwarding task that can drop, delay or panic on specific messages. Faults
+//! are scripted by message index, so a given fault schedule always produces the
+//! same interleaving of failures. The pipeline state can then be checked for
+//! invariants (e.g., the pending data chunk counter is accurate and no chunks
+//! are lost).
+
+use aptos_infallible::Mutex;
+use futures::{channel::mpsc, SinkExt, StreamExt};
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+/// The channels between the storage synchronizer pipeline stages
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PipelineChannel {
+    ExecutorToLedgerUpdater,
+    LedgerUpdaterToCommitter,
+    CommitterToCommitPostProcessor,
+}
+
+/// The faults that can be injected into a pipeline channel
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PipelineFault {
+    DropMessage,         // The message is silently dropped (i.e., lost)
+    DelaySend(Duration), // The message is delayed before being forwarded
+    Panic,               // The forwarding task panics (closing the channel)
+}
+
+/// Simple statistics for the messages sent along a single pipeline channel
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PipelineChannelStats {
+    pub received: u64,  // The number of messages sent by the upstream stage
+    pub forwarded: u64, // The number of messages forwarded to the downstream stage
+    pub dropped: u64,   // The number of messages dropped by an injected fault
+}
+
+/// Injects scripted faults into the storage synchronizer pipeline channels
+#[derive(Clone, Default)]
+pub struct PipelineFaultInjector {
+    channel_stats: Arc<Mutex<HashMap<PipelineChannel, PipelineChannelStats>>>,
+    faults: Arc<Mutex<HashMap<(PipelineChannel, u64), PipelineFault>>>,
+}
+
+impl PipelineFaultInjector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Injects the given fault for the message at the specified index
+    /// (starting at 0) sent along the given channel.
+    pub fn inject_fault(&self, channel: PipelineChannel, message_index: u64, fault: PipelineFault) {
+        self.faults.lock().insert((channel, message_index), fault);
+    }
+
+    /// Returns the message statistics for the given channel
+    pub fn get_channel_stats(&self, channel: PipelineChannel) -> PipelineChannelStats {
+        self.channel_stats
+            .lock()
+            .get(&channel)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Creates a new channel (with the given buffer size) for the specified
+    /// pipeline channel. Messages sent along the channel are forwarded by a
+    /// dedicated task that injects the scripted faults.
+    pub(crate) fn create_channel<T: Send + 'static>(
+        &self,
+        channel: PipelineChannel,
+        buffer: usize,
+    ) -> (mpsc::Sender<T>, mpsc::Receiver<T>) {
+        let (sender, mut forwarder_listener) = mpsc::channel(buffer);
+        let (mut forwarder_sender, listener) = mpsc::channel(buffer);
+
+        // Spawn the forwarding task that injects the faults
+        let fault_injector = self.clone();
+        tokio::spawn(async move {
+            let mut message_index = 0;
+            while let Some(message) = forwarder_listener.next().await {
+                let fault = fault_injector
+                    .faults
+                    .lock()
+                    .remove(&(channel, message_index));
+                message_index += 1;
+
+                // Close the channel before the panic is observable (via the
+                // stats), so that any subsequent sends fail deterministically.
+                if fault == Some(PipelineFault::Panic) {
+                    forwarder_listener.close();
+                    fault_injector.update_channel_stats(channel, |stats| stats.received += 1);
+                    panic!("Injected a panic on the pipeline channel: {:?}", channel);
+                }
+                fault_injector.update_channel_stats(channel, |stats| stats.received += 1);
+
+                // Inject the fault (if any)
+                match fault {
+                    Some(PipelineFault::DropMessage) => {
+                        fault_injector.update_channel_stats(channel, |stats| stats.dropped += 1);
+                        continue;
+                    },
+                    Some(PipelineFault::DelaySend(delay)) => tokio::time::sleep(delay).await,
+                    _ => {},
+                }
+
+                // Forward the message to the downstream stage
+                if forwarder_sender.send(message).await.is_err() {
+                    return; // The downstream stage has been dropped
+                }
+                fault_injector.update_channel_stats(channel, |stats| stats.forwarded += 1);
+            }
+        });
+
+        (sender, listener)
+    }
+
+    /// Updates the statistics for the given channel
+    fn update_channel_stats(
+        &self,
+        channel: PipelineChannel,
+        update: impl FnOnce(&mut PipelineChannelStats),
+    ) {
+        update(self.channel_stats.lock().entry(channel).or_default());
+    }
+}
+
+/// The externally observed state of a drained storage synchronizer pipeline
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PipelineObservations {
+    pub chunks_sent: u64,         // The chunks successfully sent to the executor
+    pub chunks_committed: u64,    // The chunks forwarded to the commit post-processor
+    pub error_notifications: u64, // The error notifications received by the driver
+    pub pending_data_chunks: u64, // The value of the pending data chunk counter
+}
+
+/// The invariants that can be violated by the storage synchronizer pipeline
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PipelineInvariantViolation {
+    DuplicateNotifications {
+        chunks_sent: u64,
+        chunks_notified: u64,
+    },
+    InaccuratePendingCounter {
+        expected: u64,
+        actual: u64,
+    },
+    LostNotifications {
+        num_lost_chunks: u64,
+    },
+}
+
+/// Checks the invariants of a drained storage synchronizer pipeline (i.e., once
+/// all stages are idle), and returns all invariant violations:
+/// 1. Every chunk results in at most one notification (commit or error).
+/// 2. The pending data chunk counter matches the number of unnotified chunks.
+/// 3. No chunks are lost, i.e., every chunk results in a notification.
+pub fn check_pipeline_invariants(
+    observations: &PipelineObservations,
+) -> Vec<PipelineInvariantViolation> {
+    let mut violations = vec![];
+
+    let chunks_sent = observations.chunks_sent;
+    let chunks_notified = observations.chunks_committed + observations.error_notifications;
+    if chunks_notified > chunks_sent {
+        violations.push(PipelineInvariantViolation::DuplicateNotifications {
+            chunks_sent,
+            chunks_notified,
+        });
+    }
+
+    let unnotified_chunks = chunks_sent.saturating_sub(chunks_notified);
+    if observations.pending_data_chunks != unnotified_chunks {
+        violations.push(PipelineInvariantViolation::InaccuratePendingCounter {
+            expected: unnotified_chunks,
+            actual: observations.pending_data_chunks,
+        });
+    }
+
+    if unnotified_chunks > 0 {
+        violations.push(PipelineInvariantViolation::LostNotifications {
+            num_lost_chunks: unnotified_chunks,
+        });
+    }
+
+    violations
+}
@@ -5,16 +5,24 @@
 #![forbid(unsafe_code)]
 
 mod bootstrapper;
+pub mod chunk_journal;
 mod continuous_syncer;
 mod driver;
 mod driver_client;
 pub mod driver_factory;
-mod error;
+pub mod error;
+#[cfg(any(test, feature = "fault-injection"))]
+pub mod fault_injection;
+mod local_file_data_stream;
 mod logging;
 pub mod metadata_storage;
 pub mod metrics;
-mod notification_handlers;
-mod storage_synchronizer;
+pub mod notification_handlers;
+mod pipeline_watchdog;
+mod retry_policy;
+pub mod state_snapshot_filter;
+pub mod storage_synchronizer;
+pub mod sync_progress;
 mod utils;
 
 #[cfg(test)]
@@ -0,0 +1,411 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    logging::{LogEntry, LogSchema},
+};
+use aptos_crypto::hash::CryptoHash;
+use aptos_data_streaming_service::{
+    data_notification::{DataNotification, DataPayload},
+    data_stream::{DataStreamId, DataStreamListener},
+};
+use aptos_logger::prelude::*;
+use aptos_types::{
+    contract_event::ContractEvent,
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{TransactionAccumulatorRangeProof, TransactionInfoListWithProof},
+    transaction::{
+        Transaction, TransactionAuxiliaryData, TransactionInfo, TransactionOutput,
+        TransactionOutputListWithProof, TransactionStatus, Version,
+    },
+    write_set::WriteSet,
+};
+use futures::{channel::mpsc, executor::block_on, SinkExt};
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+// The name of the metadata directory in a backup (as written by the db-backup tool)
+const METADATA_DIR: &str = "metadata";
+
+// The maximum number of notifications that can be pending on a local file data stream
+const MAX_PENDING_NOTIFICATIONS: usize = 10;
+
+/// A transaction backup entry in the backup metadata files (this mirrors
+/// the format written by the db-backup tool).
+#[derive(Clone, Debug, Deserialize)]
+struct TransactionBackupMeta {
+    manifest: String,
+}
+
+/// A transaction backup manifest (this mirrors the format written by the
+/// db-backup tool).
+#[derive(Clone, Debug, Deserialize)]
+struct TransactionBackup {
+    chunks: Vec<TransactionChunk>,
+}
+
+/// A single chunk in a transaction backup manifest, representing the
+/// transactions in the [`first_version`, `last_version`] range.
+#[derive(Clone, Debug, Deserialize)]
+struct TransactionChunk {
+    first_version: Version,
+    last_version: Version,
+    /// Repeated `len(record) + record`, where `record` is the BCS serialized
+    /// tuple `(Transaction, TransactionInfo, Vec<ContractEvent>, WriteSet)`.
+    transactions: String,
+    /// The BCS serialized `(TransactionAccumulatorRangeProof, LedgerInfoWithSignatures)`
+    proof: String,
+}
+
+/// A data stream that serves transaction outputs (with proofs) from a local
+/// backup directory (i.e., the format produced by the db-backup tool) instead
+/// of the network. This allows operators to bootstrap many nodes from the same
+/// backup without re-downloading all transaction outputs over the network.
+///
+/// Note: each backup chunk is proven by the ledger info stored alongside it,
+/// so each notification carries the ledger info that proves its outputs. It is
+/// the responsibility of the listener to verify these ledger infos. If a chunk
+/// cannot be served (e.g., it is missing from the backup), the stream ends early
+/// and the remaining data must be fetched from the network.
+#[derive(Clone)]
+pub struct LocalFileDataStream {
+    backup_dir: PathBuf,
+    chunks: Arc<Vec<TransactionChunk>>, // All transaction chunks (sorted by first version)
+    next_data_stream_id: Arc<AtomicU64>,
+}
+
+impl LocalFileDataStream {
+    /// Creates a new local file data stream by reading all transaction backup
+    /// manifests in the given backup directory.
+    pub fn new(backup_dir: PathBuf) -> Result<Self, Error> {
+        let mut chunks = vec![];
+        for manifest in read_transaction_backup_manifests(&backup_dir)? {
+            let manifest_path = backup_dir.join(&manifest);
+            let transaction_backup: TransactionBackup =
+                serde_json::from_slice(&read_file(&manifest_path)?).map_err(|error| {
+                    Error::LocalBackupError(format!(
+                        "Failed to parse the transaction backup manifest: {:?}. Error: {:?}",
+                        manifest_path, error
+                    ))
+                })?;
+            for chunk in transaction_backup.chunks {
+                if chunk.first_version > chunk.last_version {
+                    return Err(Error::LocalBackupError(format!(
+                        "Invalid chunk range: [{:?}, {:?}] in manifest: {:?}",
+                        chunk.first_version, chunk.last_version, manifest_path
+                    )));
+                }
+                chunks.push(chunk);
+            }
+        }
+        chunks.sort_by_key(|chunk| chunk.first_version);
+
+        info!(
+            LogSchema::new(LogEntry::LocalFileDataStream).message(&format!(
+                "Found {} transaction chunks in the local backup directory: {:?}",
+                chunks.len(),
+                backup_dir
+            ))
+        );
+
+        Ok(Self {
+            backup_dir,
+            chunks: Arc::new(chunks),
+            next_data_stream_id: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Returns true iff the backup contains the transaction output at the given version
+    pub fn contains_version(&self, version: Version) -> bool {
+        self.find_chunk(version).is_some()
+    }
+
+    /// Returns the chunk (if any) that contains the given version
+    fn find_chunk(&self, version: Version) -> Option<TransactionChunk> {
+        find_chunk(&self.chunks, version)
+    }
+
+    /// Streams all transaction outputs (with proofs) in the [`start_version`,
+    /// `end_version`] range from the backup. Each notification contains the
+    /// ledger info that proves the outputs. The stream is terminated once the
+    /// end version is reached, or once a chunk can no longer be served.
+    pub fn stream_transaction_outputs(
+        &self,
+        start_version: Version,
+        end_version: Version,
+    ) -> DataStreamListener {
+        let data_stream_id = self.next_data_stream_id.fetch_add(1, Ordering::Relaxed);
+        let (notification_sender, notification_receiver) = mpsc::channel(MAX_PENDING_NOTIFICATIONS);
+
+        // Read the chunks on a dedicated thread (to avoid blocking the driver)
+        let backup_dir = self.backup_dir.clone();
+        let chunks = self.chunks.clone();
+        thread::Builder::new()
+            .name("local-file-stream".into())
+            .spawn(move || {
+                stream_transaction_outputs(
+                    data_stream_id,
+                    backup_dir,
+                    chunks,
+                    start_version,
+                    end_version,
+                    notification_sender,
+                )
+            })
+            .expect("Failed to spawn the local file data stream thread!");
+
+        DataStreamListener::new(data_stream_id, notification_receiver)
+    }
+}
+
+/// Sends the transaction outputs in the [`start_version`, `end_version`] range
+/// along the notification sender (followed by an end of stream notification).
+fn stream_transaction_outputs(
+    data_stream_id: DataStreamId,
+    backup_dir: PathBuf,
+    chunks: Arc<Vec<TransactionChunk>>,
+    start_version: Version,
+    end_version: Version,
+    mut notification_sender: mpsc::Sender<DataNotification>,
+) {
+    let mut notification_id = 0;
+    let mut next_version = start_version;
+    while next_version <= end_version {
+        // Identify the chunk containing the next version
+        let chunk = match find_chunk(&chunks, next_version) {
+            Some(chunk) => chunk,
+            None => {
+                info!(
+                    LogSchema::new(LogEntry::LocalFileDataStream).message(&format!(
+                        "The local backup does not contain version: {:?}. Ending the stream: {:?}",
+                        next_version, data_stream_id
+                    ))
+                );
+                break;
+            },
+        };
+
+        // Read the transaction outputs from the chunk
+        let (ledger_info, outputs_with_proof) =
+            match read_transaction_outputs(&backup_dir, &chunk, next_version, end_version) {
+                Ok(ledger_info_and_outputs) => ledger_info_and_outputs,
+                Err(error) => {
+                    warn!(LogSchema::new(LogEntry::LocalFileDataStream)
+                        .error(&error)
+                        .message(&format!(
+                    "Failed to read the chunk starting at version: {:?}. Ending the stream: {:?}",
+                    chunk.first_version, data_stream_id
+                )));
+                    break;
+                },
+            };
+
+        // Send the notification to the listener
+        let num_outputs = outputs_with_proof.transactions_and_outputs.len() as u64;
+        let data_payload =
+            DataPayload::ContinuousTransactionOutputsWithProof(ledger_info, outputs_with_proof);
+        if block_on(notification_sender.send(DataNotification::new(notification_id, data_payload)))
+            .is_err()
+        {
+            return; // The listener has been dropped (i.e., the stream was terminated)
+        }
+        notification_id += 1;
+        next_version += num_outputs;
+    }
+
+    // Send the end of stream notification
+    let _ = block_on(notification_sender.send(DataNotification::new(
+        notification_id,
+        DataPayload::EndOfStream,
+    )));
+}
+
+/// Returns the chunk (if any) that contains the given version
+fn find_chunk(chunks: &[TransactionChunk], version: Version) -> Option<TransactionChunk> {
+    chunks
+        .iter()
+        .find(|chunk| chunk.first_version <= version && version <= chunk.last_version)
+        .cloned()
+}
+
+/// Reads the transaction outputs in the [`start_version`, `end_version`] range
+/// from the given chunk. Returns the outputs (with a proof for the range) and
+/// the ledger info that proves them.
+fn read_transaction_outputs(
+    backup_dir: &Path,
+    chunk: &TransactionChunk,
+    start_version: Version,
+    end_version: Version,
+) -> Result<(LedgerInfoWithSignatures, TransactionOutputListWithProof), Error> {
+    // Read the transaction records
+    let records = read_file(&backup_dir.join(&chunk.transactions))?;
+    let mut transactions = vec![];
+    let mut transaction_infos = vec![];
+    let mut outputs = vec![];
+    for record in split_records(&records)? {
+        let (transaction, transaction_info, events, write_set): (
+            Transaction,
+            TransactionInfo,
+            Vec<ContractEvent>,
+            WriteSet,
+        ) = bcs::from_bytes(record).map_err(|error| {
+            Error::LocalBackupError(format!(
+                "Failed to deserialize a transaction record: {:?}",
+                error
+            ))
+        })?;
+        outputs.push(TransactionOutput::new(
+            write_set,
+            events,
+            transaction_info.gas_used(),
+            TransactionStatus::Keep(transaction_info.status().clone()),
+            TransactionAuxiliaryData::default(),
+        ));
+        transactions.push(transaction);
+        transaction_infos.push(transaction_info);
+    }
+
+    // Verify the number of records matches the chunk
+    let num_records = chunk.last_version - chunk.first_version + 1;
+    if transactions.len() as u64 != num_records {
+        return Err(Error::LocalBackupError(format!(
+            "Number of records doesn't match the chunk! Expected: {:?}, found: {:?}",
+            num_records,
+            transactions.len()
+        )));
+    }
+
+    // Read the range proof and ledger info
+    let (range_proof, ledger_info): (TransactionAccumulatorRangeProof, LedgerInfoWithSignatures) =
+        bcs::from_bytes(&read_file(&backup_dir.join(&chunk.proof))?).map_err(|error| {
+            Error::LocalBackupError(format!(
+                "Failed to deserialize the chunk proof: {:?}",
+                error
+            ))
+        })?;
+
+    // Identify the requested sub-range of the chunk and derive the range proof
+    let first_index = (start_version.max(chunk.first_version) - chunk.first_version) as usize;
+    let last_index = (end_version.min(chunk.last_version) - chunk.first_version) as usize;
+    let range_proof = if first_index == 0 && last_index + 1 == transactions.len() {
+        range_proof
+    } else {
+        let leaf_hashes: Vec<_> = transaction_infos.iter().map(CryptoHash::hash).collect();
+        range_proof
+            .get_sub_range_proof(
+                chunk.first_version,
+                &leaf_hashes,
+                first_index..last_index + 1,
+            )
+            .map_err(|error| {
+                Error::LocalBackupError(format!(
+                    "Failed to derive the sub-range proof: {:?}",
+                    error
+                ))
+            })?
+    };
+
+    // Create the transaction output list with proof
+    let transactions_and_outputs = transactions
+        .into_iter()
+        .zip(outputs)
+        .skip(first_index)
+        .take(last_index - first_index + 1)
+        .collect();
+    let transaction_infos = transaction_infos
+        .into_iter()
+        .skip(first_index)
+        .take(last_index - first_index + 1)
+        .collect();
+    let outputs_with_proof = TransactionOutputListWithProof::new(
+        transactions_and_outputs,
+        Some(chunk.first_version + first_index as u64),
+        TransactionInfoListWithProof::new(range_proof, transaction_infos),
+    );
+
+    Ok((ledger_info, outputs_with_proof))
+}
+
+/// Returns the manifests of all transaction backups in the backup metadata
+fn read_transaction_backup_manifests(backup_dir: &Path) -> Result<Vec<String>, Error> {
+    let metadata_dir = backup_dir.join(METADATA_DIR);
+    let metadata_files = fs::read_dir(&metadata_dir).map_err(|error| {
+        Error::LocalBackupError(format!(
+            "Failed to read the backup metadata directory: {:?}. Error: {:?}",
+            metadata_dir, error
+        ))
+    })?;
+
+    let mut manifests = vec![];
+    for metadata_file in metadata_files {
+        let metadata_path = metadata_file
+            .map_err(|error| Error::LocalBackupError(error.to_string()))?
+            .path();
+        let metadata = String::from_utf8(read_file(&metadata_path)?)
+            .map_err(|error| Error::LocalBackupError(error.to_string()))?;
+
+        // Each line in a metadata file holds a single (JSON serialized) metadata entry
+        for line in metadata.lines().filter(|line| !line.trim().is_empty()) {
+            let entry: serde_json::Value = serde_json::from_str(line).map_err(|error| {
+                Error::LocalBackupError(format!(
+                    "Failed to parse the metadata file: {:?}. Error: {:?}",
+                    metadata_path, error
+                ))
+            })?;
+            if let Some(transaction_backup) = entry.get("TransactionBackup") {
+                let transaction_backup: TransactionBackupMeta =
+                    serde_json::from_value(transaction_backup.clone())
+                        .map_err(|error| Error::LocalBackupError(error.to_string()))?;
+                manifests.push(transaction_backup.manifest);
+            }
+        }
+    }
+    manifests.sort();
+    manifests.dedup();
+
+    Ok(manifests)
+}
+
+/// Splits the given bytes into records (each record is prefixed by its length)
+fn split_records(bytes: &[u8]) -> Result<Vec<&[u8]>, Error> {
+    let mut records = vec![];
+    let mut remaining_bytes = bytes;
+    while !remaining_bytes.is_empty() {
+        if remaining_bytes.len() < 4 {
+            return Err(Error::LocalBackupError(
+                "Hit the end of the file when reading the record size!".into(),
+            ));
+        }
+        let (size_bytes, rest) = remaining_bytes.split_at(4);
+        let record_size =
+            u32::from_be_bytes(size_bytes.try_into().expect("The size must be 4 bytes!")) as usize;
+        if rest.len() < record_size {
+            return Err(Error::LocalBackupError(
+                "Hit the end of the file when reading the record!".into(),
+            ));
+        }
+        let (record, rest) = rest.split_at(record_size);
+        records.push(record);
+        remaining_bytes = rest;
+    }
+    Ok(records)
+}
+
+/// Reads the entire contents of the given file
+fn read_file(path: &Path) -> Result<Vec<u8>, Error> {
+    fs::read(path).map_err(|error| {
+        Error::LocalBackupError(format!(
+            "Failed to read the file: {:?}. Error: {:?}",
+            path, error
+        ))
+    })
+}
@@ -1,7 +1,9 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{error::Error, notification_handlers::ErrorNotification};
+use crate::{
+    error::Error, notification_handlers::ErrorNotification, storage_synchronizer::ResetReason,
+};
 use aptos_logger::Schema;
 use serde::Serialize;
 
@@ -11,6 +13,7 @@ pub struct LogSchema<'a> {
     error: Option<&'a Error>,
     error_notification: Option<ErrorNotification>,
     message: Option<&'a str>,
+    reset_reason: Option<ResetReason>,
 }
 
 impl<'a> LogSchema<'a> {
@@ -20,6 +23,7 @@ impl<'a> LogSchema<'a> {
             error: None,
             error_notification: None,
             message: None,
+            reset_reason: None,
         }
     }
 }
@@ -32,6 +36,7 @@ pub enum LogEntry {
     ClientNotification,
     ConsensusNotification,
     Driver,
+    LocalFileDataStream,
     NotificationHandler,
     StorageSynchronizer,
     SynchronizerNotification,
@@ -4,6 +4,7 @@
 use crate::{
     error::Error,
     metadata_storage::database_schema::{MetadataKey, MetadataSchema, MetadataValue},
+    state_snapshot_filter::StateSnapshotFilter,
 };
 use anyhow::{anyhow, Result};
 use aptos_logger::prelude::*;
@@ -39,6 +40,17 @@ pub trait MetadataStorageInterface {
     /// started. If no snapshot sync started, None is returned.
     fn previous_snapshot_sync_target(&self) -> Result<Option<LedgerInfoWithSignatures>, Error>;
 
+    /// Returns the state value filter recorded for the state snapshot sync that
+    /// previously started (if any). If the snapshot sync is unfiltered (or no
+    /// snapshot sync started), None is returned.
+    fn get_state_snapshot_filter(&self) -> Result<Option<StateSnapshotFilter>, Error>;
+
+    /// Records the state value filter used by the state snapshot sync
+    fn update_state_snapshot_filter(
+        &self,
+        state_snapshot_filter: Option<StateSnapshotFilter>,
+    ) -> Result<(), Error>;
+
     /// Updates the last persisted state value index for the state snapshot
     /// sync at the specified target ledger info.
     fn update_last_persisted_state_value_index(
@@ -106,10 +118,13 @@ impl PersistentMetadataStorage {
                     ))
                 })?;
         match maybe_metadata_value {
-            Some(metadata_value) => {
-                let MetadataValue::StateSnapshotSync(snapshot_progress) = metadata_value;
+            Some(MetadataValue::StateSnapshotSync(snapshot_progress)) => {
                 Ok(Some(snapshot_progress))
             },
+            Some(metadata_value) => Err(Error::StorageError(format!(
+                "Unexpected metadata value for key: {:?}. Value: {:?}",
+                metadata_key, metadata_value
+            ))),
             None => Ok(None),
         }
     }
@@ -197,6 +212,38 @@ impl MetadataStorageInterface for PersistentMetadataStorage {
             .map(|snapshot_progress| snapshot_progress.target_ledger_info))
     }
 
+    fn get_state_snapshot_filter(&self) -> Result<Option<StateSnapshotFilter>, Error> {
+        let metadata_key = MetadataKey::StateSnapshotFilter;
+        let maybe_metadata_value =
+            self.database
+                .get::<MetadataSchema>(&metadata_key)
+                .map_err(|error| {
+                    Error::StorageError(format!(
+                        "Failed to read metadata value for key: {:?}. Error: {:?}",
+                        metadata_key, error
+                    ))
+                })?;
+        match maybe_metadata_value {
+            Some(MetadataValue::StateSnapshotFilter(state_snapshot_filter)) => {
+                Ok(state_snapshot_filter)
+            },
+            Some(metadata_value) => Err(Error::StorageError(format!(
+                "Unexpected metadata value for key: {:?}. Value: {:?}",
+                metadata_key, metadata_value
+            ))),
+            None => Ok(None),
+        }
+    }
+
+    fn update_state_snapshot_filter(
+        &self,
+        state_snapshot_filter: Option<StateSnapshotFilter>,
+    ) -> Result<(), Error> {
+        let metadata_key = MetadataKey::StateSnapshotFilter;
+        let metadata_value = MetadataValue::StateSnapshotFilter(state_snapshot_filter);
+        self.commit_key_value(metadata_key, metadata_value)
+    }
+
     fn update_last_persisted_state_value_index(
         &self,
         target_ledger_info: &LedgerInfoWithSignatures,
@@ -251,7 +298,8 @@ pub mod database_schema {
     #[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
     #[repr(u8)]
     pub enum MetadataKey {
-        StateSnapshotSync, // A state snapshot sync that was started
+        StateSnapshotSync,   // A state snapshot sync that was started
+        StateSnapshotFilter, // The state value filter of the state snapshot sync
     }
 
     /// A metadata value that can be inserted into the database
@@ -259,6 +307,7 @@ pub mod database_schema {
     #[repr(u8)]
     pub enum MetadataValue {
         StateSnapshotSync(StateSnapshotProgress), // A state snapshot sync progress marker
+        StateSnapshotFilter(Option<StateSnapshotFilter>), // The state snapshot sync filter
     }
 
     impl KeyCodec<MetadataSchema> for MetadataKey {
@@ -24,12 +24,20 @@ pub const NOTIFICATION_CREATE_TO_UPDATE_LEDGER: &str = "notification_create_to_u
 
 /// Storage synchronizer metric labels
 pub const STORAGE_SYNCHRONIZER_PENDING_DATA: &str = "storage_synchronizer_pending_data";
+pub const STORAGE_SYNCHRONIZER_EXECUTOR_QUEUE: &str = "storage_synchronizer_executor_queue";
+pub const STORAGE_SYNCHRONIZER_LEDGER_UPDATER_QUEUE: &str =
+    "storage_synchronizer_ledger_updater_queue";
+pub const STORAGE_SYNCHRONIZER_COMMITTER_QUEUE: &str = "storage_synchronizer_committer_queue";
+pub const STORAGE_SYNCHRONIZER_COMMIT_POST_PROCESSOR_QUEUE: &str =
+    "storage_synchronizer_commit_post_processor_queue";
+pub const STORAGE_SYNCHRONIZER_THROTTLED: &str = "storage_synchronizer_throttled";
 pub const STORAGE_SYNCHRONIZER_APPLY_CHUNK: &str = "apply_chunk";
 pub const STORAGE_SYNCHRONIZER_EXECUTE_CHUNK: &str = "execute_chunk";
 pub const STORAGE_SYNCHRONIZER_UPDATE_LEDGER: &str = "update_ledger";
 pub const STORAGE_SYNCHRONIZER_COMMIT_CHUNK: &str = "commit_chunk";
 pub const STORAGE_SYNCHRONIZER_COMMIT_POST_PROCESS: &str = "commit_post_process";
 pub const STORAGE_SYNCHRONIZER_STATE_VALUE_CHUNK: &str = "state_value_chunk";
+pub const STORAGE_SYNCHRONIZER_VERIFY_STATE_VALUE_CHUNK: &str = "verify_state_value_chunk";
 
 /// An enum representing the component currently executing
 pub enum ExecutingComponent {
@@ -50,10 +58,11 @@ impl ExecutingComponent {
 
 /// An enum of storage synchronizer operations performed by state sync
 pub enum StorageSynchronizerOperations {
-    AppliedTransactionOutputs, // Applied a chunk of transactions outputs.
-    ExecutedTransactions,      // Executed a chunk of transactions.
-    Synced,                    // Wrote a chunk of transactions and outputs to storage.
-    SyncedStates,              // Wrote a chunk of state values to storage.
+    AppliedTransactionOutputs,      // Applied a chunk of transactions outputs.
+    ExecutedTransactions,           // Executed a chunk of transactions.
+    SpotVerifiedTransactionOutputs, // Re-executed a chunk of transaction outputs (for verification).
+    Synced,                         // Wrote a chunk of transactions and outputs to storage.
+    SyncedStates,                   // Wrote a chunk of state values to storage.
     SyncedEpoch, // Wrote a chunk of transactions and outputs to storage that resulted in a new epoch.
 }
 
@@ -64,6 +73,9 @@ impl StorageSynchronizerOperations {
                 "applied_transaction_outputs"
             },
             StorageSynchronizerOperations::ExecutedTransactions => "executed_transactions",
+            StorageSynchronizerOperations::SpotVerifiedTransactionOutputs => {
+                "spot_verified_transaction_outputs"
+            },
             StorageSynchronizerOperations::Synced => "synced",
             StorageSynchronizerOperations::SyncedEpoch => "synced_epoch",
             StorageSynchronizerOperations::SyncedStates => "synced_states",
@@ -88,6 +100,16 @@ pub static BOOTSTRAPPER_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for chunk executor resets (by reset reason)
+pub static CHUNK_EXECUTOR_RESETS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_state_sync_chunk_executor_resets",
+        "Counters for the chunk executor resets (by reset reason)",
+        &["reset_reason"]
+    )
+    .unwrap()
+});
+
 /// Gauge for state sync continuous syncer fallback mode
 pub static CONTINUOUS_SYNCER_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -190,6 +212,36 @@ pub static STORAGE_SYNCHRONIZER_OPERATIONS: Lazy<IntGaugeVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Counter for tracking the progress made by the storage synchronizer stages
+pub static STORAGE_SYNCHRONIZER_PROGRESS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_state_sync_storage_synchronizer_progress",
+        "Counters for the chunks processed by the storage synchronizer stages",
+        &["label"]
+    )
+    .unwrap()
+});
+
+/// Counter for storage synchronizer operation retries (by error class)
+pub static STORAGE_SYNCHRONIZER_RETRIES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_state_sync_storage_synchronizer_retries",
+        "Counters for the failed storage synchronizer operations that were retried",
+        &["error_class"]
+    )
+    .unwrap()
+});
+
+/// Counter for storage synchronizer pipeline stalls (by stuck channel)
+pub static STORAGE_SYNCHRONIZER_STALLS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_state_sync_storage_synchronizer_stalls",
+        "Counters for the stalls detected in the storage synchronizer pipeline",
+        &["stuck_channel"]
+    )
+    .unwrap()
+});
+
 /// Increments the given counter with the provided label values.
 pub fn increment_counter(counter: &Lazy<IntCounterVec>, label: &str) {
     counter.with_label_values(&[label]).inc();
@@ -222,6 +274,24 @@ pub fn read_gauge(gauge: &Lazy<IntGaugeVec>, label: &str) -> i64 {
     gauge.with_label_values(&[label]).get()
 }
 
+/// Reads the total progress made by the storage synchronizer stages
+pub fn read_pipeline_progress() -> u64 {
+    [
+        STORAGE_SYNCHRONIZER_EXECUTOR_QUEUE,
+        STORAGE_SYNCHRONIZER_LEDGER_UPDATER_QUEUE,
+        STORAGE_SYNCHRONIZER_COMMITTER_QUEUE,
+        STORAGE_SYNCHRONIZER_COMMIT_POST_PROCESSOR_QUEUE,
+        STORAGE_SYNCHRONIZER_PENDING_DATA,
+    ]
+    .iter()
+    .map(|label| {
+        STORAGE_SYNCHRONIZER_PROGRESS
+            .with_label_values(&[label])
+            .get()
+    })
+    .sum()
+}
+
 /// Sets the gauge with the specific label to the given value
 pub fn set_gauge(gauge: &Lazy<IntGaugeVec>, label: &str, value: u64) {
     gauge.with_label_values(&[label]).set(value as i64);
@@ -6,11 +6,14 @@ use crate::{
     error::Error,
     logging::{LogEntry, LogSchema},
 };
+use aptos_config::config::CommitPostProcessorConfig;
 use aptos_consensus_notifications::{
     ConsensusCommitNotification, ConsensusNotification, ConsensusNotificationListener,
     ConsensusSyncNotification,
 };
-use aptos_data_streaming_service::data_notification::NotificationId;
+use aptos_data_streaming_service::{
+    data_notification::NotificationId, streaming_client::NotificationFeedback,
+};
 use aptos_event_notifications::{EventNotificationSender, EventSubscriptionService};
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
@@ -82,6 +85,7 @@ impl CommitNotification {
         mut mempool_notification_handler: MempoolNotificationHandler<M>,
         event_subscription_service: Arc<Mutex<EventSubscriptionService>>,
         mut storage_service_notification_handler: StorageServiceNotificationHandler<S>,
+        commit_post_processor_config: CommitPostProcessorConfig,
     ) -> Result<(), Error> {
         // Log the highest synced version and timestamp
         let blockchain_timestamp_usecs = latest_synced_ledger_info.ledger_info().timestamp_usecs();
@@ -93,22 +97,25 @@ impl CommitNotification {
         );
 
         // Notify the storage service of the committed transactions
-        storage_service_notification_handler
-            .notify_storage_service_of_committed_transactions(latest_synced_version)
-            .await?;
+        if commit_post_processor_config.notify_storage_service {
+            storage_service_notification_handler
+                .notify_storage_service_of_committed_transactions(latest_synced_version)
+                .await?;
+        }
 
         // Notify mempool of the committed transactions
-        mempool_notification_handler
-            .notify_mempool_of_committed_transactions(
-                transactions.clone(),
-                blockchain_timestamp_usecs,
-            )
-            .await?;
+        if commit_post_processor_config.notify_mempool {
+            mempool_notification_handler
+                .notify_mempool_of_committed_transactions(transactions, blockchain_timestamp_usecs)
+                .await?;
+        }
 
         // Notify the event subscription service of the events
-        event_subscription_service
-            .lock()
-            .notify_events(latest_synced_version, events.clone())?;
+        if commit_post_processor_config.notify_event_subscribers {
+            event_subscription_service
+                .lock()
+                .notify_events(latest_synced_version, events)?;
+        }
 
         Ok(())
     }
@@ -360,6 +367,23 @@ pub struct ErrorNotification {
     pub notification_id: NotificationId,
 }
 
+impl ErrorNotification {
+    /// Returns the feedback to send to the data streaming service for the
+    /// error (if any). Errors caused by local failures (e.g., failed storage
+    /// writes, closed channels or a busy executor) are not the fault of the data
+    /// itself, so no feedback is returned (to avoid penalizing the peers that served it).
+    pub fn get_notification_feedback(&self) -> Option<NotificationFeedback> {
+        if self.error.get_error_class().is_local_failure() {
+            return None;
+        }
+
+        match self.error {
+            Error::VerificationError(_) => Some(NotificationFeedback::PayloadProofFailed),
+            _ => Some(NotificationFeedback::InvalidPayloadData),
+        }
+    }
+}
+
 /// A simple wrapper for an error notification listener
 pub struct ErrorNotificationListener {
     // The listener for error notifications
@@ -0,0 +1,232 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A watchdog for the storage synchronizer pipeline. The watchdog periodically
+//! samples the pipeline state, and reports a stall when data chunks are pending
+//! but no stage has made progress (i.e., received or completed a chunk) for the
+//! configured duration. Each stall is logged with a diagnosis of the channel
+//! that appears to be stuck and (optionally) the pipeline is reset.
+
+use crate::{
+    error::Error,
+    logging::{LogEntry, LogSchema},
+    metrics,
+    notification_handlers::ErrorNotification,
+};
+use aptos_config::config::StateSyncDriverConfig;
+use aptos_logger::prelude::*;
+use futures::{channel::mpsc, SinkExt};
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Weak,
+    },
+    time::{Duration, Instant},
+};
+
+// The interval (ms) at which the watchdog samples the pipeline state
+const WATCHDOG_CHECK_INTERVAL_MS: u64 = 1000;
+
+// The pipeline channels, ordered from the most upstream to the most downstream
+const PIPELINE_QUEUE_LABELS: [&str; 4] = [
+    metrics::STORAGE_SYNCHRONIZER_EXECUTOR_QUEUE,
+    metrics::STORAGE_SYNCHRONIZER_LEDGER_UPDATER_QUEUE,
+    metrics::STORAGE_SYNCHRONIZER_COMMITTER_QUEUE,
+    metrics::STORAGE_SYNCHRONIZER_COMMIT_POST_PROCESSOR_QUEUE,
+];
+
+// The label used when no channel holds data (i.e., a chunk is in-flight or lost)
+const IN_FLIGHT_CHUNK_LABEL: &str = "in_flight_chunk";
+
+/// A snapshot of the observable storage synchronizer pipeline state
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct PipelineSnapshot {
+    pub pending_data_chunks: u64,
+    pub progress: u64, // The total number of stage operations (monotonically increasing)
+    pub queue_depths: Vec<(&'static str, u64)>, // The channel depths (upstream first)
+}
+
+impl PipelineSnapshot {
+    /// Captures the current pipeline state
+    pub fn capture(pending_data_chunks: &Arc<AtomicU64>) -> Self {
+        let queue_depths = PIPELINE_QUEUE_LABELS
+            .iter()
+            .map(|label| {
+                let depth = metrics::read_gauge(&metrics::STORAGE_SYNCHRONIZER_GAUGES, label);
+                (*label, depth.max(0) as u64)
+            })
+            .collect();
+
+        Self {
+            pending_data_chunks: pending_data_chunks.load(Ordering::Relaxed),
+            progress: metrics::read_pipeline_progress(),
+            queue_depths,
+        }
+    }
+}
+
+/// The diagnosis of a stalled pipeline
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) struct PipelineStallDiagnosis {
+    pub pending_data_chunks: u64,
+    pub queue_depths: Vec<(&'static str, u64)>,
+    pub stalled_duration: Duration,
+    pub stuck_channel: &'static str,
+}
+
+impl PipelineStallDiagnosis {
+    fn new(snapshot: &PipelineSnapshot, stalled_duration: Duration) -> Self {
+        // The stuck channel is the most downstream channel holding data (the
+        // stage consuming it isn't making progress). If no channel holds data,
+        // a chunk is either being processed by a stage, or was lost.
+        let stuck_channel = snapshot
+            .queue_depths
+            .iter()
+            .rev()
+            .find(|(_, depth)| *depth > 0)
+            .map(|(label, _)| *label)
+            .unwrap_or(IN_FLIGHT_CHUNK_LABEL);
+
+        Self {
+            pending_data_chunks: snapshot.pending_data_chunks,
+            queue_depths: snapshot.queue_depths.clone(),
+            stalled_duration,
+            stuck_channel,
+        }
+    }
+
+    /// Returns the number of chunks that are still held in the pipeline channels
+    pub fn num_queued_chunks(&self) -> u64 {
+        self.queue_depths.iter().map(|(_, depth)| depth).sum()
+    }
+}
+
+/// Detects stalls given a series of pipeline snapshots
+pub(crate) struct PipelineStallDetector {
+    last_progress: u64,
+    last_progress_time: Instant,
+    max_stall_duration: Duration,
+}
+
+impl PipelineStallDetector {
+    pub fn new(max_stall_duration: Duration, time_now: Instant) -> Self {
+        Self {
+            last_progress: 0,
+            last_progress_time: time_now,
+            max_stall_duration,
+        }
+    }
+
+    /// Checks the given snapshot for a stall. If the pipeline has stalled, a
+    /// diagnosis is returned (and the detector is re-armed, so that a stall
+    /// that persists is reported again after another stall duration).
+    pub fn check_snapshot(
+        &mut self,
+        snapshot: &PipelineSnapshot,
+        time_now: Instant,
+    ) -> Option<PipelineStallDiagnosis> {
+        // If there's no pending data, or progress was made, there's no stall
+        if snapshot.pending_data_chunks == 0 || snapshot.progress != self.last_progress {
+            self.last_progress = snapshot.progress;
+            self.last_progress_time = time_now;
+            return None;
+        }
+
+        // Otherwise, check if the pipeline has been stalled for too long
+        let stalled_duration = time_now.saturating_duration_since(self.last_progress_time);
+        if stalled_duration < self.max_stall_duration {
+            return None;
+        }
+        self.last_progress_time = time_now;
+        Some(PipelineStallDiagnosis::new(snapshot, stalled_duration))
+    }
+}
+
+/// Runs the pipeline watchdog for the given pending data chunk counter. The
+/// watchdog terminates once the pipeline (i.e., the counter) has been dropped.
+pub(crate) async fn run_pipeline_watchdog(
+    driver_config: StateSyncDriverConfig,
+    pending_data_chunks: Weak<AtomicU64>,
+    mut error_notification_sender: mpsc::UnboundedSender<ErrorNotification>,
+) {
+    let max_stall_duration = Duration::from_secs(driver_config.max_pipeline_stall_secs);
+    let mut stall_detector = PipelineStallDetector::new(max_stall_duration, Instant::now());
+
+    let mut check_interval =
+        tokio::time::interval(Duration::from_millis(WATCHDOG_CHECK_INTERVAL_MS));
+    loop {
+        check_interval.tick().await;
+
+        // Check the pipeline for a stall
+        let pending_data_chunks = match pending_data_chunks.upgrade() {
+            Some(pending_data_chunks) => pending_data_chunks,
+            None => return, // The pipeline has been dropped
+        };
+        let snapshot = PipelineSnapshot::capture(&pending_data_chunks);
+        let diagnosis = match stall_detector.check_snapshot(&snapshot, Instant::now()) {
+            Some(diagnosis) => diagnosis,
+            None => continue,
+        };
+
+        // Log the diagnosis and update the metrics
+        error!(
+            LogSchema::new(LogEntry::StorageSynchronizer).message(&format!(
+                "The storage synchronizer pipeline has stalled! Diagnosis: {:?}",
+                diagnosis
+            ))
+        );
+        metrics::increment_counter(
+            &metrics::STORAGE_SYNCHRONIZER_STALLS,
+            diagnosis.stuck_channel,
+        );
+
+        // Reset the pipeline (if configured)
+        if driver_config.reset_stalled_pipeline {
+            reset_stalled_pipeline(
+                &diagnosis,
+                &pending_data_chunks,
+                &mut error_notification_sender,
+            )
+            .await;
+        }
+    }
+}
+
+/// Resets a stalled pipeline by dropping any chunks that are no longer held
+/// in the pipeline channels (e.g., lost chunks) from the pending data chunk
+/// counter, and notifying the driver (so that the active stream is reset and
+/// the missing data is refetched).
+async fn reset_stalled_pipeline(
+    diagnosis: &PipelineStallDiagnosis,
+    pending_data_chunks: &Arc<AtomicU64>,
+    error_notification_sender: &mut mpsc::UnboundedSender<ErrorNotification>,
+) {
+    // Re-synchronize the pending data chunk counter with the queued chunks
+    let num_queued_chunks = diagnosis.num_queued_chunks();
+    let previous_pending_chunks = pending_data_chunks.swap(num_queued_chunks, Ordering::Relaxed);
+    metrics::set_gauge(
+        &metrics::STORAGE_SYNCHRONIZER_GAUGES,
+        metrics::STORAGE_SYNCHRONIZER_PENDING_DATA,
+        num_queued_chunks,
+    );
+    warn!(
+        LogSchema::new(LogEntry::StorageSynchronizer).message(&format!(
+            "Reset the stalled storage synchronizer pipeline! Pending data chunks: {} -> {}",
+            previous_pending_chunks, num_queued_chunks
+        ))
+    );
+
+    // Notify the driver of the stall
+    let error_notification = ErrorNotification {
+        error: Error::StorageSynchronizerStalled(diagnosis.stuck_channel.into()),
+        notification_id: 0, // The stall isn't caused by a specific notification
+    };
+    if let Err(error) = error_notification_sender.send(error_notification).await {
+        error!(
+            LogSchema::new(LogEntry::StorageSynchronizer).message(&format!(
+                "Failed to send the pipeline stall notification! Error: {:?}",
+                error
+            ))
+        );
+    }
+}
@@ -0,0 +1,79 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::{Error, ErrorClass},
+    logging::{LogEntry, LogSchema},
+    metrics,
+};
+use aptos_config::config::{ErrorRetryConfig, ErrorRetryPolicy};
+use aptos_logger::prelude::*;
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+/// Returns the retry policy for the given error class (if any)
+pub fn get_retry_policy(
+    error_retry_config: &ErrorRetryConfig,
+    error_class: ErrorClass,
+) -> Option<ErrorRetryPolicy> {
+    match error_class {
+        ErrorClass::ChannelClosed => Some(error_retry_config.channel_closed),
+        ErrorClass::ExecutorBusy => Some(error_retry_config.executor_busy),
+        ErrorClass::StorageIo => Some(error_retry_config.storage_io),
+        ErrorClass::VerificationFailure => Some(error_retry_config.verification_failure),
+        ErrorClass::Unclassified => None, // Unclassified errors are never retried
+    }
+}
+
+/// Runs the given operation and retries it (with exponential backoff) if it
+/// fails, according to the retry policy for the class of the error. If a
+/// deadline is specified, the operation is not retried once the next backoff
+/// would exceed the deadline. The operation must be safe to retry (e.g., an
+/// idempotent storage write).
+pub async fn run_with_retries<T, F: Future<Output = Result<T, Error>>>(
+    error_retry_config: &ErrorRetryConfig,
+    operation_name: &str,
+    deadline: Option<Instant>,
+    mut operation: impl FnMut() -> F,
+) -> Result<T, Error> {
+    let mut num_retries = 0;
+    loop {
+        // Attempt to run the operation
+        let error = match operation().await {
+            Ok(result) => return Ok(result),
+            Err(error) => error,
+        };
+
+        // Check if we've exhausted the retries for the error class
+        let error_class = error.get_error_class();
+        let retry_policy = match get_retry_policy(error_retry_config, error_class) {
+            Some(retry_policy) if num_retries < retry_policy.max_retries => retry_policy,
+            _ => return Err(error),
+        };
+
+        // Check if we've exhausted the deadline
+        let retry_backoff = Duration::from_millis(retry_policy.retry_backoff_ms)
+            .saturating_mul(2u32.saturating_pow(num_retries as u32));
+        if let Some(deadline) = deadline {
+            if Instant::now() + retry_backoff > deadline {
+                return Err(error);
+            }
+        }
+
+        // Backoff and retry the operation
+        num_retries += 1;
+        warn!(LogSchema::new(LogEntry::StorageSynchronizer)
+            .error(&error)
+            .message(&format!(
+                "Failed to run the operation: {}! Retrying in {:?} (retry {} of {}).",
+                operation_name, retry_backoff, num_retries, retry_policy.max_retries
+            )));
+        metrics::increment_counter(
+            &metrics::STORAGE_SYNCHRONIZER_RETRIES,
+            error_class.get_label(),
+        );
+        tokio::time::sleep(retry_backoff).await;
+    }
+}
@@ -0,0 +1,60 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::config::StateSnapshotFilterConfig;
+use aptos_storage_interface::StateValueFilter;
+use aptos_types::{
+    account_address::AccountAddress,
+    state_store::{
+        state_key::{StateKey, StateKeyInner},
+        table::TableHandle,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, sync::Arc};
+
+/// A filter for the state values persisted by a state snapshot sync. Only the
+/// values of state keys that match the allowlists are persisted (the state tree
+/// is still fully restored). As the filtered state values are missing from
+/// storage, the node can only apply transaction outputs (and not execute
+/// transactions) once the snapshot has been synced.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateSnapshotFilter {
+    account_allowlist: BTreeSet<AccountAddress>,
+    table_handle_allowlist: BTreeSet<TableHandle>,
+}
+
+impl StateSnapshotFilter {
+    /// Creates a new filter from the given config. If the config does
+    /// not enable filtering, None is returned.
+    pub fn new(config: &StateSnapshotFilterConfig) -> Option<Self> {
+        if !config.is_enabled() {
+            return None;
+        }
+
+        Some(Self {
+            account_allowlist: config.account_allowlist.iter().copied().collect(),
+            table_handle_allowlist: config
+                .table_handle_allowlist
+                .iter()
+                .map(|handle| TableHandle(*handle))
+                .collect(),
+        })
+    }
+
+    /// Returns true iff the value of the given state key should be persisted
+    pub fn matches(&self, state_key: &StateKey) -> bool {
+        match state_key.inner() {
+            StateKeyInner::AccessPath(access_path) => {
+                self.account_allowlist.contains(&access_path.address)
+            },
+            StateKeyInner::TableItem { handle, .. } => self.table_handle_allowlist.contains(handle),
+            StateKeyInner::Raw(_) => false,
+        }
+    }
+
+    /// Converts the filter into a value filter for the state snapshot receiver
+    pub fn into_value_filter(self) -> StateValueFilter<StateKey> {
+        Arc::new(move |state_key| self.matches(state_key))
+    }
+}
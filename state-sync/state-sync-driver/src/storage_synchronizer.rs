@@ -2,7 +2,27 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+//! The storage synchronizer verifies and persists the data fetched by state sync.
+//!
+//! Embedders (e.g., light clients or custom indexers) can integrate with the
+//! driver in one of several ways:
+//! 1. Implement [`StorageSynchronizerInterface`] directly, e.g., to write the
+//!    synced data to a custom storage backend.
+//! 2. Use [`StorageSynchronizer`] with a custom `ChunkExecutorTrait` and a
+//!    `DbReaderWriter` wrapping custom `DbReader` and `DbWriter` implementations.
+//! 3. Assemble a custom pipeline from the stage spawn helpers (i.e.,
+//!    [`spawn_executor`], [`spawn_ledger_updater`], [`spawn_committer`] and
+//!    [`spawn_commit_post_processor`]), connected by [`PipelineChannels`].
+//!
+//! All pipeline stages share a pending data chunk counter, which must be
+//! incremented for each [`StorageDataChunk`] sent to the executor. Each stage
+//! decrements the counter once a chunk is fully processed (or fails).
+
+#[cfg(any(test, feature = "fault-injection"))]
+use crate::fault_injection::{PipelineChannel, PipelineFaultInjector};
 use crate::{
+    bootstrapper::GENESIS_TRANSACTION_VERSION,
+    chunk_journal::ChunkJournal,
     error::Error,
     logging::{LogEntry, LogSchema},
     metadata_storage::MetadataStorageInterface,
@@ -11,12 +31,20 @@ use crate::{
         CommitNotification, CommittedTransactions, ErrorNotification, MempoolNotificationHandler,
         StorageServiceNotificationHandler,
     },
+    pipeline_watchdog, retry_policy,
+    state_snapshot_filter::StateSnapshotFilter,
+    sync_progress::SyncProgressReporter,
     utils,
 };
-use aptos_config::config::StateSyncDriverConfig;
+use aptos_config::config::{
+    CommitPostProcessorConfig, ErrorRetryConfig, ErrorRetryPolicy, StateSyncDriverConfig,
+};
+use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_data_streaming_service::data_notification::NotificationId;
 use aptos_event_notifications::EventSubscriptionService;
-use aptos_executor_types::{ChunkCommitNotification, ChunkExecutorTrait};
+use aptos_executor_types::{
+    memory_budget::MemoryBudget, ChunkCommitNotification, ChunkExecutorTrait,
+};
 use aptos_infallible::Mutex;
 use aptos_logger::prelude::*;
 use aptos_mempool_notifications::MempoolNotificationSender;
@@ -24,31 +52,39 @@ use aptos_metrics_core::HistogramTimer;
 use aptos_storage_interface::{DbReader, DbReaderWriter, StateSnapshotReceiver};
 use aptos_storage_service_notifications::StorageServiceNotificationSender;
 use aptos_types::{
+    epoch_change::{EpochChangeProof, Verifier},
     ledger_info::LedgerInfoWithSignatures,
     state_store::{
         state_key::StateKey,
         state_value::{StateValue, StateValueChunkWithProof},
     },
-    transaction::{
-        Transaction, TransactionListWithProof, TransactionOutput, TransactionOutputListWithProof,
-        Version,
-    },
+    transaction::{Transaction, TransactionListWithProof, TransactionOutputListWithProof, Version},
+    waypoint::Waypoint,
 };
 use async_trait::async_trait;
-use futures::{channel::mpsc, SinkExt, StreamExt};
+use futures::{
+    channel::{mpsc, oneshot},
+    future, FutureExt, SinkExt, StreamExt,
+};
+use rayon::ThreadPool;
+use serde::Serialize;
 use std::{
     future::Future,
     sync::{
-        atomic::{AtomicU64, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
         Arc,
     },
-    time::Instant,
+    time::{Duration, Instant},
 };
 use tokio::{
     runtime::{Handle, Runtime},
     task::JoinHandle,
+    time::timeout,
 };
 
+// The frequency (secs) at which to log chunk journal errors
+const JOURNAL_ERROR_LOG_FREQ_SECS: u64 = 3;
+
 /// Synchronizes the storage of the node by verifying and storing new data
 /// (e.g., transactions and outputs).
 #[async_trait]
@@ -75,6 +111,19 @@ pub trait StorageSynchronizerInterface {
         end_of_epoch_ledger_info: Option<LedgerInfoWithSignatures>,
     ) -> Result<(), Error>;
 
+    /// Re-executes the transactions of a batch of transaction outputs (instead
+    /// of applying the outputs directly). This spot verifies the outputs by
+    /// ensuring that local execution matches the proven transaction infos.
+    ///
+    /// Note: this assumes that the ledger infos have already been verified.
+    async fn verify_transaction_outputs_by_execution(
+        &mut self,
+        notification_metadata: NotificationMetadata,
+        output_list_with_proof: TransactionOutputListWithProof,
+        target_ledger_info: LedgerInfoWithSignatures,
+        end_of_epoch_ledger_info: Option<LedgerInfoWithSignatures>,
+    ) -> Result<(), Error>;
+
     /// Initializes a state synchronizer with the specified
     /// `target_ledger_info` and `target_output_with_proof` at the target
     /// syncing version. Returns a join handle to the state synchronizer.
@@ -88,29 +137,82 @@ pub trait StorageSynchronizerInterface {
         target_output_with_proof: TransactionOutputListWithProof,
     ) -> Result<JoinHandle<()>, Error>;
 
+    /// Resumes a state synchronizer for a snapshot sync that was interrupted
+    /// (e.g., by a process restart mid-snapshot). The snapshot progress is read
+    /// from the metadata storage and verified against the given target. Returns
+    /// a join handle to the state synchronizer and the state value index from
+    /// which the snapshot sync should continue.
+    ///
+    /// Note: this assumes that `epoch_change_proofs`, `target_ledger_info`,
+    /// and `target_output_with_proof` have already been verified.
+    fn resume_state_synchronizer(
+        &mut self,
+        epoch_change_proofs: Vec<LedgerInfoWithSignatures>,
+        target_ledger_info: LedgerInfoWithSignatures,
+        target_output_with_proof: TransactionOutputListWithProof,
+    ) -> Result<(JoinHandle<()>, u64), Error>;
+
     /// Returns true iff there is storage data that is still waiting
     /// to be executed/applied or committed.
     fn pending_storage_data(&self) -> bool;
 
+    /// Returns the number of data chunks that are still waiting
+    /// to be executed/applied or committed.
+    fn pending_data_chunk_count(&self) -> u64;
+
+    /// Returns true iff the driver should stop sending new data to the
+    /// storage synchronizer (i.e., apply backpressure to the data stream).
+    /// Throttling starts once the number of pending data chunks hits the
+    /// high watermark, and stops once it drains to the low watermark.
+    /// Throttling also occurs while the node memory budget is applying
+    /// backpressure (i.e., the node is approaching its target RSS).
+    fn should_throttle(&self) -> bool;
+
     /// Saves the given state values to storage.
     ///
-    /// Note: this requires that `initialize_state_synchronizer` has been
-    /// called.
+    /// Note: this requires that `initialize_state_synchronizer` (or
+    /// `resume_state_synchronizer`) has been called.
     async fn save_state_values(
         &mut self,
         notification_id: NotificationId,
         state_value_chunk_with_proof: StateValueChunkWithProof,
     ) -> Result<(), Error>;
 
-    /// Resets the chunk executor. This is required to support continuous
-    /// interaction between consensus and state sync.
-    fn reset_chunk_executor(&self) -> Result<(), Error>;
+    /// Resets the chunk executor (for the given reason). This is required to
+    /// support continuous interaction between consensus and state sync.
+    fn reset_chunk_executor(&self, reset_reason: ResetReason) -> Result<(), Error>;
 
     /// Finish the chunk executor at this round of state sync by releasing
     /// any in-memory resources to prevent memory leak.
     fn finish_chunk_executor(&self);
 }
 
+/// The reason for resetting the chunk executor
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResetReason {
+    ChunkJournalReplay,     // The chunk journal is about to be replayed
+    ConsensusSyncRequest,   // Consensus handed control to state sync (to satisfy a sync request)
+    StateSnapshotCompleted, // The state snapshot was synced and finalized
+    StreamInitialization,   // A new data stream is initialized (e.g., the previous one ended)
+    StreamTimeout,          // The previous data stream timed out too many times
+    SyncError,              // The previous data stream was terminated due to a sync error
+}
+
+impl ResetReason {
+    /// Returns a summary label for the reset reason
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            Self::ChunkJournalReplay => "chunk_journal_replay",
+            Self::ConsensusSyncRequest => "consensus_sync_request",
+            Self::StateSnapshotCompleted => "state_snapshot_completed",
+            Self::StreamInitialization => "stream_initialization",
+            Self::StreamTimeout => "stream_timeout",
+            Self::SyncError => "sync_error",
+        }
+    }
+}
+
 /// A simple struct that holds metadata related to data notifications
 #[derive(Copy, Clone, Debug)]
 pub struct NotificationMetadata {
@@ -138,6 +240,9 @@ pub struct StorageSynchronizer<ChunkExecutor, MetadataStorage> {
     // The executor for transaction and transaction output chunks
     chunk_executor: Arc<ChunkExecutor>,
 
+    // The journal to which all chunks sent to the executor are recorded (if enabled)
+    chunk_journal: Option<Arc<Mutex<ChunkJournal>>>,
+
     // A channel through which to notify the driver of committed data
     commit_notification_sender: mpsc::UnboundedSender<CommitNotification>,
 
@@ -156,14 +261,26 @@ pub struct StorageSynchronizer<ChunkExecutor, MetadataStorage> {
     // The number of storage data chunks pending execute/apply, or commit
     pending_data_chunks: Arc<AtomicU64>,
 
+    // Whether or not the storage synchronizer is currently throttling new data
+    throttling_data: Arc<AtomicBool>,
+
     // An optional runtime on which to spawn the storage synchronizer threads
     runtime: Option<Handle>,
 
+    // The filter for the state values persisted by a state snapshot sync (if any)
+    state_snapshot_filter: Option<StateSnapshotFilter>,
+
     // The channel through which to notify the state snapshot receiver of new data chunks
     state_snapshot_notifier: Option<mpsc::Sender<StorageDataChunk>>,
 
     // The reader and writer for storage (required for state syncing)
     storage: DbReaderWriter,
+
+    // The reporter for the sync progress (e.g., the synced and highest known versions)
+    sync_progress_reporter: SyncProgressReporter,
+
+    // The trusted waypoint (used to re-verify epoch change proofs, if enabled)
+    waypoint: Waypoint,
 }
 
 // TODO(joshlind): this cannot currently be derived because of limitations around
@@ -176,15 +293,20 @@ impl<
     fn clone(&self) -> Self {
         Self {
             chunk_executor: self.chunk_executor.clone(),
+            chunk_journal: self.chunk_journal.clone(),
             commit_notification_sender: self.commit_notification_sender.clone(),
             driver_config: self.driver_config,
             error_notification_sender: self.error_notification_sender.clone(),
             executor_notifier: self.executor_notifier.clone(),
             pending_data_chunks: self.pending_data_chunks.clone(),
+            throttling_data: self.throttling_data.clone(),
             metadata_storage: self.metadata_storage.clone(),
             runtime: self.runtime.clone(),
+            state_snapshot_filter: self.state_snapshot_filter.clone(),
             state_snapshot_notifier: self.state_snapshot_notifier.clone(),
             storage: self.storage.clone(),
+            sync_progress_reporter: self.sync_progress_reporter.clone(),
+            waypoint: self.waypoint,
         }
     }
 }
@@ -210,30 +332,118 @@ impl<
         >,
         metadata_storage: MetadataStorage,
         storage: DbReaderWriter,
+        sync_progress_reporter: SyncProgressReporter,
+        waypoint: Waypoint,
         runtime: Option<&Runtime>,
     ) -> (Self, StorageSynchronizerHandles) {
-        // Create a channel to notify the executor when data chunks are ready
-        let max_pending_data_chunks = driver_config.max_pending_data_chunks as usize;
-        let (executor_notifier, executor_listener) = mpsc::channel(max_pending_data_chunks);
-
-        // Create a channel to notify the ledger updater when executed chunks are ready
-        let (ledger_updater_notifier, ledger_updater_listener) =
-            mpsc::channel(max_pending_data_chunks);
+        let pipeline_channels =
+            PipelineChannels::new(driver_config.max_pending_data_chunks as usize);
+        Self::new_with_pipeline_channels(
+            driver_config,
+            chunk_executor,
+            commit_notification_sender,
+            error_notification_sender,
+            event_subscription_service,
+            mempool_notification_handler,
+            storage_service_notification_handler,
+            metadata_storage,
+            storage,
+            sync_progress_reporter,
+            waypoint,
+            runtime,
+            pipeline_channels,
+        )
+    }
 
-        // Create a channel to notify the committer when the ledger has been updated
-        let (committer_notifier, committer_listener) = mpsc::channel(max_pending_data_chunks);
+    /// Returns a new storage synchronizer (alongside the handles) where all
+    /// channels between the pipeline stages are interposed by the given
+    /// fault injector. This should only be used for testing.
+    #[cfg(any(test, feature = "fault-injection"))]
+    pub fn new_with_fault_injector<
+        MempoolNotifier: MempoolNotificationSender,
+        StorageServiceNotifier: StorageServiceNotificationSender,
+    >(
+        driver_config: StateSyncDriverConfig,
+        chunk_executor: Arc<ChunkExecutor>,
+        commit_notification_sender: mpsc::UnboundedSender<CommitNotification>,
+        error_notification_sender: mpsc::UnboundedSender<ErrorNotification>,
+        event_subscription_service: Arc<Mutex<EventSubscriptionService>>,
+        mempool_notification_handler: MempoolNotificationHandler<MempoolNotifier>,
+        storage_service_notification_handler: StorageServiceNotificationHandler<
+            StorageServiceNotifier,
+        >,
+        metadata_storage: MetadataStorage,
+        storage: DbReaderWriter,
+        sync_progress_reporter: SyncProgressReporter,
+        waypoint: Waypoint,
+        runtime: Option<&Runtime>,
+        fault_injector: &PipelineFaultInjector,
+    ) -> (Self, StorageSynchronizerHandles) {
+        let pipeline_channels = PipelineChannels::new_with_fault_injector(
+            driver_config.max_pending_data_chunks as usize,
+            fault_injector,
+        );
+        Self::new_with_pipeline_channels(
+            driver_config,
+            chunk_executor,
+            commit_notification_sender,
+            error_notification_sender,
+            event_subscription_service,
+            mempool_notification_handler,
+            storage_service_notification_handler,
+            metadata_storage,
+            storage,
+            sync_progress_reporter,
+            waypoint,
+            runtime,
+            pipeline_channels,
+        )
+    }
 
-        // Create a channel to notify the commit post-processor when a chunk has been committed
-        let (commit_post_processor_notifier, commit_post_processor_listener) =
-            mpsc::channel(max_pending_data_chunks);
+    /// Returns a new storage synchronizer (alongside the handles) that uses
+    /// the given channels to connect the pipeline stages.
+    fn new_with_pipeline_channels<
+        MempoolNotifier: MempoolNotificationSender,
+        StorageServiceNotifier: StorageServiceNotificationSender,
+    >(
+        driver_config: StateSyncDriverConfig,
+        chunk_executor: Arc<ChunkExecutor>,
+        commit_notification_sender: mpsc::UnboundedSender<CommitNotification>,
+        error_notification_sender: mpsc::UnboundedSender<ErrorNotification>,
+        event_subscription_service: Arc<Mutex<EventSubscriptionService>>,
+        mempool_notification_handler: MempoolNotificationHandler<MempoolNotifier>,
+        storage_service_notification_handler: StorageServiceNotificationHandler<
+            StorageServiceNotifier,
+        >,
+        metadata_storage: MetadataStorage,
+        storage: DbReaderWriter,
+        sync_progress_reporter: SyncProgressReporter,
+        waypoint: Waypoint,
+        runtime: Option<&Runtime>,
+        pipeline_channels: PipelineChannels,
+    ) -> (Self, StorageSynchronizerHandles) {
+        let PipelineChannels {
+            executor_notifier,
+            executor_listener,
+            ledger_updater_notifier,
+            ledger_updater_listener,
+            committer_notifier,
+            committer_listener,
+            commit_post_processor_notifier,
+            commit_post_processor_listener,
+        } = pipeline_channels;
 
         // Create a shared pending data chunk counter
         let pending_data_chunks = Arc::new(AtomicU64::new(0));
 
+        // Create the dedicated chunk executor thread pool (if configured)
+        let chunk_executor_pool = create_chunk_executor_pool(&driver_config);
+
         // Spawn the executor that executes/applies storage data chunks
         let runtime = runtime.map(|runtime| runtime.handle().clone());
         let executor_handle = spawn_executor(
             chunk_executor.clone(),
+            chunk_executor_pool,
             error_notification_sender.clone(),
             executor_listener,
             ledger_updater_notifier,
@@ -253,6 +463,7 @@ impl<
 
         // Spawn the committer that commits executed (but pending) chunks
         let committer_handle = spawn_committer(
+            driver_config,
             chunk_executor.clone(),
             error_notification_sender.clone(),
             committer_listener,
@@ -263,6 +474,7 @@ impl<
 
         // Spawn the commit post-processor that handles commit notifications
         let commit_post_processor_handle = spawn_commit_post_processor(
+            driver_config,
             commit_post_processor_listener,
             event_subscription_service,
             mempool_notification_handler,
@@ -270,24 +482,47 @@ impl<
             pending_data_chunks.clone(),
             runtime.clone(),
             storage.reader.clone(),
+            sync_progress_reporter.clone(),
         );
 
-        // Initialize the metric gauges
+        // Spawn the pipeline watchdog that detects stalls (if enabled)
+        let watchdog_handle = if driver_config.max_pipeline_stall_secs > 0 {
+            Some(spawn(
+                runtime.clone(),
+                pipeline_watchdog::run_pipeline_watchdog(
+                    driver_config,
+                    Arc::downgrade(&pending_data_chunks),
+                    error_notification_sender.clone(),
+                ),
+            ))
+        } else {
+            None
+        };
+
+        // Initialize the metric gauges and the sync progress
         utils::initialize_sync_gauges(storage.reader.clone())
             .expect("Failed to initialize the metric gauges!");
+        if let Ok(synced_version) = utils::fetch_latest_synced_version(storage.reader.clone()) {
+            sync_progress_reporter.update_synced_version(synced_version);
+        }
 
         // Create the storage synchronizer
         let storage_synchronizer = Self {
             chunk_executor,
+            chunk_journal: None,
             commit_notification_sender,
             driver_config,
             error_notification_sender,
             executor_notifier,
             pending_data_chunks,
+            throttling_data: Arc::new(AtomicBool::new(false)),
             metadata_storage,
             runtime,
+            state_snapshot_filter: None,
             state_snapshot_notifier: None,
             storage,
+            sync_progress_reporter,
+            waypoint,
         };
 
         // Create the storage synchronizer handles
@@ -296,15 +531,52 @@ impl<
             ledger_updater: ledger_updater_handle,
             committer: committer_handle,
             commit_post_processor: commit_post_processor_handle,
+            watchdog: watchdog_handle,
         };
 
         (storage_synchronizer, storage_synchronizer_handles)
     }
 
+    /// Sets the filter for the state values persisted by state snapshot syncs.
+    /// This must be done before any state snapshot sync is initialized.
+    pub fn set_state_snapshot_filter(
+        &mut self,
+        state_snapshot_filter: Option<StateSnapshotFilter>,
+    ) {
+        self.state_snapshot_filter = state_snapshot_filter;
+    }
+
+    /// Sets the journal to which all chunks sent to the executor are
+    /// recorded. This is a debug feature (the journal can be replayed offline).
+    pub fn set_chunk_journal(&mut self, chunk_journal: Option<ChunkJournal>) {
+        self.chunk_journal = chunk_journal.map(|chunk_journal| Arc::new(Mutex::new(chunk_journal)));
+    }
+
     /// Notifies the executor of new data chunks
     async fn notify_executor(&mut self, storage_data_chunk: StorageDataChunk) -> Result<(), Error> {
+        // Record the chunk in the journal (if enabled). Journal failures are
+        // logged, but never prevent the chunk from being executed.
+        if let Some(chunk_journal) = &self.chunk_journal {
+            if let Err(error) = chunk_journal.lock().record_chunk(&storage_data_chunk) {
+                sample!(
+                    SampleRate::Duration(Duration::from_secs(JOURNAL_ERROR_LOG_FREQ_SECS)),
+                    warn!(LogSchema::new(LogEntry::StorageSynchronizer)
+                        .error(&error)
+                        .message("Failed to record the chunk in the journal!"));
+                );
+            }
+        }
+
+        // Update the highest known version using the chunk target
+        if let Some(target_version) = storage_data_chunk.get_target_version() {
+            self.sync_progress_reporter
+                .update_highest_known_version(target_version);
+        }
+
+        increment_queue_depth(metrics::STORAGE_SYNCHRONIZER_EXECUTOR_QUEUE);
         if let Err(error) = self.executor_notifier.send(storage_data_chunk).await {
-            Err(Error::UnexpectedError(format!(
+            decrement_queue_depth(metrics::STORAGE_SYNCHRONIZER_EXECUTOR_QUEUE);
+            Err(Error::ChannelClosed(format!(
                 "Failed to send storage data chunk to executor: {:?}",
                 error
             )))
@@ -316,6 +588,64 @@ impl<
 }
 
 #[async_trait]
+impl<
+        ChunkExecutor: ChunkExecutorTrait + 'static,
+        MetadataStorage: MetadataStorageInterface + Clone + Send + Sync + 'static,
+    > StorageSynchronizer<ChunkExecutor, MetadataStorage>
+{
+    /// Spawns a new state synchronizer (i.e., state snapshot receiver) for the
+    /// given target. If the snapshot sync is being resumed, the state value
+    /// index to resume from should be specified.
+    fn spawn_state_synchronizer(
+        &mut self,
+        epoch_change_proofs: Vec<LedgerInfoWithSignatures>,
+        target_ledger_info: LedgerInfoWithSignatures,
+        target_output_with_proof: TransactionOutputListWithProof,
+        resumed_state_index: Option<u64>,
+    ) -> JoinHandle<()> {
+        // Create a channel to notify the state snapshot receiver when data chunks are ready
+        let max_pending_data_chunks = self.driver_config.max_pending_data_chunks as usize;
+        let (state_snapshot_notifier, state_snapshot_listener) =
+            mpsc::channel(max_pending_data_chunks);
+
+        // Update the highest known version using the snapshot target
+        self.sync_progress_reporter
+            .update_highest_known_version(target_ledger_info.ledger_info().version());
+
+        // Identify the waypoint against which to re-verify the epoch change proofs (if enabled)
+        let trusted_waypoint = if self.driver_config.verify_epoch_proofs_on_commit {
+            Some(self.waypoint)
+        } else {
+            None
+        };
+
+        // Spawn the state snapshot receiver that commits state values
+        let receiver_handle = spawn_state_snapshot_receiver(
+            self.chunk_executor.clone(),
+            state_snapshot_listener,
+            self.commit_notification_sender.clone(),
+            self.error_notification_sender.clone(),
+            self.pending_data_chunks.clone(),
+            self.metadata_storage.clone(),
+            self.storage.clone(),
+            epoch_change_proofs,
+            target_ledger_info,
+            target_output_with_proof,
+            resumed_state_index,
+            self.driver_config.max_state_commit_workers,
+            self.driver_config.state_value_verifier_threads,
+            self.state_snapshot_filter.clone(),
+            trusted_waypoint,
+            self.driver_config.error_retry_config,
+            self.sync_progress_reporter.clone(),
+            self.runtime.clone(),
+        );
+        self.state_snapshot_notifier = Some(state_snapshot_notifier);
+
+        receiver_handle
+    }
+}
+
 impl<
         ChunkExecutor: ChunkExecutorTrait + 'static,
         MetadataStorage: MetadataStorageInterface + Clone + Send + Sync + 'static,
@@ -338,7 +668,7 @@ impl<
         // Notify the executor of the new transaction output chunk
         let storage_data_chunk = StorageDataChunk::TransactionOutputs(
             notification_metadata,
-            output_list_with_proof,
+            Arc::new(output_list_with_proof),
             target_ledger_info,
             end_of_epoch_ledger_info,
         );
@@ -362,7 +692,31 @@ impl<
         // Notify the executor of the new transaction chunk
         let storage_data_chunk = StorageDataChunk::Transactions(
             notification_metadata,
-            transaction_list_with_proof,
+            Arc::new(transaction_list_with_proof),
+            target_ledger_info,
+            end_of_epoch_ledger_info,
+        );
+        self.notify_executor(storage_data_chunk).await
+    }
+
+    async fn verify_transaction_outputs_by_execution(
+        &mut self,
+        notification_metadata: NotificationMetadata,
+        output_list_with_proof: TransactionOutputListWithProof,
+        target_ledger_info: LedgerInfoWithSignatures,
+        end_of_epoch_ledger_info: Option<LedgerInfoWithSignatures>,
+    ) -> Result<(), Error> {
+        // Update the metrics for the data notification execute latency
+        metrics::observe_duration(
+            &metrics::DATA_NOTIFICATION_LATENCIES,
+            metrics::NOTIFICATION_CREATE_TO_EXECUTE,
+            notification_metadata.creation_time,
+        );
+
+        // Notify the executor of the new hybrid chunk
+        let storage_data_chunk = StorageDataChunk::Hybrid(
+            notification_metadata,
+            Arc::new(output_list_with_proof),
             target_ledger_info,
             end_of_epoch_ledger_info,
         );
@@ -375,32 +729,121 @@ impl<
         target_ledger_info: LedgerInfoWithSignatures,
         target_output_with_proof: TransactionOutputListWithProof,
     ) -> Result<JoinHandle<()>, Error> {
-        // Create a channel to notify the state snapshot receiver when data chunks are ready
-        let max_pending_data_chunks = self.driver_config.max_pending_data_chunks as usize;
-        let (state_snapshot_notifier, state_snapshot_listener) =
-            mpsc::channel(max_pending_data_chunks);
+        // Record the state value filter (so that the snapshot sync can only
+        // be resumed with the same filter).
+        self.metadata_storage
+            .update_state_snapshot_filter(self.state_snapshot_filter.clone())?;
 
-        // Spawn the state snapshot receiver that commits state values
-        let receiver_handle = spawn_state_snapshot_receiver(
-            self.chunk_executor.clone(),
-            state_snapshot_listener,
-            self.commit_notification_sender.clone(),
-            self.error_notification_sender.clone(),
-            self.pending_data_chunks.clone(),
-            self.metadata_storage.clone(),
-            self.storage.clone(),
+        Ok(self.spawn_state_synchronizer(
             epoch_change_proofs,
             target_ledger_info,
             target_output_with_proof,
-            self.runtime.clone(),
+            None,
+        ))
+    }
+
+    fn resume_state_synchronizer(
+        &mut self,
+        epoch_change_proofs: Vec<LedgerInfoWithSignatures>,
+        target_ledger_info: LedgerInfoWithSignatures,
+        target_output_with_proof: TransactionOutputListWithProof,
+    ) -> Result<(JoinHandle<()>, u64), Error> {
+        // Verify that the previous snapshot sync was for the same target
+        let previous_target = self.metadata_storage.previous_snapshot_sync_target()?;
+        if previous_target.as_ref() != Some(&target_ledger_info) {
+            return Err(Error::UnexpectedError(format!(
+                "Unable to resume the state synchronizer! The previous snapshot sync target: {:?} \
+                does not match the given target: {:?}",
+                previous_target, target_ledger_info
+            )));
+        }
+
+        // Verify that the previous snapshot sync has not already completed
+        if self
+            .metadata_storage
+            .is_snapshot_sync_complete(&target_ledger_info)?
+        {
+            return Err(Error::UnexpectedError(format!(
+                "Unable to resume the state synchronizer! The snapshot sync is already complete \
+                for the target: {:?}",
+                target_ledger_info
+            )));
+        }
+
+        // Verify that the previous snapshot sync used the same state value filter
+        let previous_filter = self.metadata_storage.get_state_snapshot_filter()?;
+        if previous_filter != self.state_snapshot_filter {
+            return Err(Error::UnexpectedError(format!(
+                "Unable to resume the state synchronizer! The previous state snapshot filter: {:?} \
+                does not match the configured filter: {:?}",
+                previous_filter, self.state_snapshot_filter
+            )));
+        }
+
+        // Identify the state value index to continue from. The state snapshot
+        // receiver requires that the last persisted index is rewritten after a
+        // restart (the last persisted leaf is only frozen once the next leaf
+        // arrives), so we resume exactly at the last persisted index. All other
+        // persisted state values are verified against the proof of the first
+        // chunk that is committed after resuming.
+        let last_persisted_state_value_index = self
+            .metadata_storage
+            .get_last_persisted_state_value_index(&target_ledger_info)?;
+        info!(
+            LogSchema::new(LogEntry::StorageSynchronizer).message(&format!(
+                "Resuming the state synchronizer from state value index: {:?}, target: {:?}",
+                last_persisted_state_value_index, target_ledger_info
+            ))
         );
-        self.state_snapshot_notifier = Some(state_snapshot_notifier);
 
-        Ok(receiver_handle)
+        // Spawn the state synchronizer
+        let receiver_handle = self.spawn_state_synchronizer(
+            epoch_change_proofs,
+            target_ledger_info,
+            target_output_with_proof,
+            Some(last_persisted_state_value_index),
+        );
+        Ok((receiver_handle, last_persisted_state_value_index))
     }
 
     fn pending_storage_data(&self) -> bool {
-        load_pending_data_chunks(self.pending_data_chunks.clone()) > 0
+        self.pending_data_chunk_count() > 0
+    }
+
+    fn pending_data_chunk_count(&self) -> u64 {
+        load_pending_data_chunks(self.pending_data_chunks.clone())
+    }
+
+    fn should_throttle(&self) -> bool {
+        let pending_data_chunks = self.pending_data_chunk_count();
+        let throttling_data = self.throttling_data.load(Ordering::Relaxed);
+
+        // Determine if we should throttle (using the watermarks for hysteresis)
+        let should_throttle = if throttling_data {
+            pending_data_chunks > self.driver_config.pending_data_chunks_low_watermark
+        } else {
+            pending_data_chunks >= self.driver_config.pending_data_chunks_high_watermark
+        };
+
+        // Update the throttling state (if it has changed)
+        if should_throttle != throttling_data {
+            self.throttling_data
+                .store(should_throttle, Ordering::Relaxed);
+            metrics::set_gauge(
+                &metrics::STORAGE_SYNCHRONIZER_GAUGES,
+                metrics::STORAGE_SYNCHRONIZER_THROTTLED,
+                should_throttle as u64,
+            );
+            info!(
+                LogSchema::new(LogEntry::StorageSynchronizer).message(&format!(
+                "Storage synchronizer throttling updated! Throttling: {}, pending data chunks: {}",
+                should_throttle, pending_data_chunks
+            ))
+            );
+        }
+
+        // Also throttle if the node is approaching its memory budget
+        should_throttle || MemoryBudget::global().should_apply_backpressure()
     }
 
     async fn save_state_values(
@@ -417,7 +860,7 @@ impl<
 
         // Notify the snapshot receiver of the storage data chunk
         if let Err(error) = state_snapshot_notifier.send(storage_data_chunk).await {
-            Err(Error::UnexpectedError(format!(
+            Err(Error::ChannelClosed(format!(
                 "Failed to send storage data chunk to state snapshot listener: {:?}",
                 error
             )))
@@ -427,51 +870,181 @@ impl<
         }
     }
 
-    fn reset_chunk_executor(&self) -> Result<(), Error> {
+    fn reset_chunk_executor(&self, reset_reason: ResetReason) -> Result<(), Error> {
+        log_and_count_chunk_executor_reset(reset_reason);
         self.chunk_executor.reset().map_err(|error| {
-            Error::UnexpectedError(format!(
+            Error::StorageError(format!(
                 "Failed to reset the chunk executor! Error: {:?}",
                 error
             ))
         })
     }
 
-    fn finish_chunk_executor(&self) {
-        self.chunk_executor.finish()
+    fn finish_chunk_executor(&self) {
+        self.chunk_executor.finish()
+    }
+}
+
+/// A simple container that holds the handles to the spawned storage synchronizer threads
+pub struct StorageSynchronizerHandles {
+    pub executor: JoinHandle<()>,
+    pub ledger_updater: JoinHandle<()>,
+    pub committer: JoinHandle<()>,
+    pub commit_post_processor: JoinHandle<()>,
+    pub watchdog: Option<JoinHandle<()>>, // The pipeline watchdog (if enabled)
+}
+
+/// A chunk of data to be executed and/or committed to storage (i.e., states,
+/// transactions or outputs). Hybrid chunks contain transaction outputs that
+/// are re-executed (rather than applied) to spot verify the outputs.
+///
+/// Note: transaction and output payloads can be several megabytes, so they
+/// are held behind an `Arc`. This allows the payloads to be handed between
+/// the pipeline stages (and the chunk journal) without copying them.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
+pub enum StorageDataChunk {
+    States(NotificationId, StateValueChunkWithProof),
+    Transactions(
+        NotificationMetadata,
+        Arc<TransactionListWithProof>,
+        LedgerInfoWithSignatures,
+        Option<LedgerInfoWithSignatures>,
+    ),
+    TransactionOutputs(
+        NotificationMetadata,
+        Arc<TransactionOutputListWithProof>,
+        LedgerInfoWithSignatures,
+        Option<LedgerInfoWithSignatures>,
+    ),
+    Hybrid(
+        NotificationMetadata,
+        Arc<TransactionOutputListWithProof>,
+        LedgerInfoWithSignatures,
+        Option<LedgerInfoWithSignatures>,
+    ),
+}
+
+/// The channels that connect the stages of the storage synchronizer pipeline
+pub struct PipelineChannels {
+    pub executor_notifier: mpsc::Sender<StorageDataChunk>,
+    pub executor_listener: mpsc::Receiver<StorageDataChunk>,
+    pub ledger_updater_notifier: mpsc::Sender<NotificationMetadata>,
+    pub ledger_updater_listener: mpsc::Receiver<NotificationMetadata>,
+    pub committer_notifier: mpsc::Sender<NotificationMetadata>,
+    pub committer_listener: mpsc::Receiver<NotificationMetadata>,
+    pub commit_post_processor_notifier: mpsc::Sender<ChunkCommitNotification>,
+    pub commit_post_processor_listener: mpsc::Receiver<ChunkCommitNotification>,
+}
+
+impl PipelineChannels {
+    /// Creates the pipeline channels, where each channel buffers
+    /// at most `max_pending_data_chunks` messages.
+    pub fn new(max_pending_data_chunks: usize) -> Self {
+        // Create a channel to notify the executor when data chunks are ready
+        let (executor_notifier, executor_listener) = mpsc::channel(max_pending_data_chunks);
+
+        // Create a channel to notify the ledger updater when executed chunks are ready
+        let (ledger_updater_notifier, ledger_updater_listener) =
+            mpsc::channel(max_pending_data_chunks);
+
+        // Create a channel to notify the committer when the ledger has been updated
+        let (committer_notifier, committer_listener) = mpsc::channel(max_pending_data_chunks);
+
+        // Create a channel to notify the commit post-processor when a chunk has been committed
+        let (commit_post_processor_notifier, commit_post_processor_listener) =
+            mpsc::channel(max_pending_data_chunks);
+
+        Self {
+            executor_notifier,
+            executor_listener,
+            ledger_updater_notifier,
+            ledger_updater_listener,
+            committer_notifier,
+            committer_listener,
+            commit_post_processor_notifier,
+            commit_post_processor_listener,
+        }
+    }
+
+    /// Creates the pipeline channels such that all channels between the
+    /// pipeline stages are interposed by the given fault injector. Note:
+    /// this must be called from within a tokio runtime.
+    #[cfg(any(test, feature = "fault-injection"))]
+    fn new_with_fault_injector(
+        max_pending_data_chunks: usize,
+        fault_injector: &PipelineFaultInjector,
+    ) -> Self {
+        let (executor_notifier, executor_listener) = mpsc::channel(max_pending_data_chunks);
+        let (ledger_updater_notifier, ledger_updater_listener) = fault_injector.create_channel(
+            PipelineChannel::ExecutorToLedgerUpdater,
+            max_pending_data_chunks,
+        );
+        let (committer_notifier, committer_listener) = fault_injector.create_channel(
+            PipelineChannel::LedgerUpdaterToCommitter,
+            max_pending_data_chunks,
+        );
+        let (commit_post_processor_notifier, commit_post_processor_listener) = fault_injector
+            .create_channel(
+                PipelineChannel::CommitterToCommitPostProcessor,
+                max_pending_data_chunks,
+            );
+
+        Self {
+            executor_notifier,
+            executor_listener,
+            ledger_updater_notifier,
+            ledger_updater_listener,
+            committer_notifier,
+            committer_listener,
+            commit_post_processor_notifier,
+            commit_post_processor_listener,
+        }
+    }
+}
+
+impl StorageDataChunk {
+    /// Returns the version of the target ledger info (if the chunk has one)
+    pub fn get_target_version(&self) -> Option<Version> {
+        match self {
+            StorageDataChunk::States(..) => None,
+            StorageDataChunk::Transactions(_, _, target_ledger_info, _)
+            | StorageDataChunk::TransactionOutputs(_, _, target_ledger_info, _)
+            | StorageDataChunk::Hybrid(_, _, target_ledger_info, _) => {
+                Some(target_ledger_info.ledger_info().version())
+            },
+        }
     }
 }
 
-/// A simple container that holds the handles to the spawned storage synchronizer threads
-pub struct StorageSynchronizerHandles {
-    pub executor: JoinHandle<()>,
-    pub ledger_updater: JoinHandle<()>,
-    pub committer: JoinHandle<()>,
-    pub commit_post_processor: JoinHandle<()>,
+/// Takes ownership of the given chunk payload. The payload is only cloned
+/// if it is still shared (e.g., if a reference is held elsewhere).
+pub fn take_chunk_payload<T: Clone>(payload: Arc<T>) -> T {
+    Arc::try_unwrap(payload).unwrap_or_else(|payload| payload.as_ref().clone())
 }
 
-/// A chunk of data to be executed and/or committed to storage (i.e., states,
-/// transactions or outputs).
-#[allow(clippy::large_enum_variant)]
-#[derive(Debug)]
-enum StorageDataChunk {
-    States(NotificationId, StateValueChunkWithProof),
-    Transactions(
-        NotificationMetadata,
-        TransactionListWithProof,
-        LedgerInfoWithSignatures,
-        Option<LedgerInfoWithSignatures>,
-    ),
-    TransactionOutputs(
-        NotificationMetadata,
-        TransactionOutputListWithProof,
-        LedgerInfoWithSignatures,
-        Option<LedgerInfoWithSignatures>,
-    ),
+/// Creates the dedicated thread pool used to execute/apply storage data
+/// chunks. If no threads are configured, None is returned (and chunks
+/// are executed on the shared tokio blocking pool).
+pub fn create_chunk_executor_pool(
+    driver_config: &StateSyncDriverConfig,
+) -> Option<Arc<ThreadPool>> {
+    let num_chunk_executor_threads = driver_config.num_chunk_executor_threads as usize;
+    if num_chunk_executor_threads == 0 {
+        return None;
+    }
+
+    let chunk_executor_pool = aptos_runtimes::spawn_rayon_thread_pool(
+        "chunk-exec".into(),
+        Some(num_chunk_executor_threads),
+    );
+    Some(Arc::new(chunk_executor_pool))
 }
 
 /// Spawns a dedicated executor that executes/applies storage data chunks
-fn spawn_executor<ChunkExecutor: ChunkExecutorTrait + 'static>(
+pub fn spawn_executor<ChunkExecutor: ChunkExecutorTrait + 'static>(
     chunk_executor: Arc<ChunkExecutor>,
+    chunk_executor_pool: Option<Arc<ThreadPool>>,
     error_notification_sender: mpsc::UnboundedSender<ErrorNotification>,
     mut executor_listener: mpsc::Receiver<StorageDataChunk>,
     mut ledger_updater_notifier: mpsc::Sender<NotificationMetadata>,
@@ -481,6 +1054,8 @@ fn spawn_executor<ChunkExecutor: ChunkExecutorTrait + 'static>(
     // Create an executor
     let executor = async move {
         while let Some(storage_data_chunk) = executor_listener.next().await {
+            decrement_queue_depth(metrics::STORAGE_SYNCHRONIZER_EXECUTOR_QUEUE);
+
             // Start the execute/apply timer
             let _timer = start_execute_apply_timer(&storage_data_chunk);
 
@@ -495,9 +1070,29 @@ fn spawn_executor<ChunkExecutor: ChunkExecutorTrait + 'static>(
                     // Execute the storage data chunk
                     let result = execute_transaction_chunk(
                         chunk_executor.clone(),
+                        chunk_executor_pool.clone(),
                         transactions_with_proof,
                         target_ledger_info,
                         end_of_epoch_ledger_info,
+                        metrics::StorageSynchronizerOperations::ExecutedTransactions,
+                    )
+                    .await;
+                    (notification_metadata, result, true)
+                },
+                StorageDataChunk::Hybrid(
+                    notification_metadata,
+                    outputs_with_proof,
+                    target_ledger_info,
+                    end_of_epoch_ledger_info,
+                ) => {
+                    // Re-execute the transactions of the storage data chunk
+                    let result = execute_transaction_chunk(
+                        chunk_executor.clone(),
+                        chunk_executor_pool.clone(),
+                        transaction_list_from_outputs(outputs_with_proof),
+                        target_ledger_info,
+                        end_of_epoch_ledger_info,
+                        metrics::StorageSynchronizerOperations::SpotVerifiedTransactionOutputs,
                     )
                     .await;
                     (notification_metadata, result, true)
@@ -511,6 +1106,7 @@ fn spawn_executor<ChunkExecutor: ChunkExecutorTrait + 'static>(
                     // Apply the storage data chunk
                     let result = apply_output_chunk(
                         chunk_executor.clone(),
+                        chunk_executor_pool.clone(),
                         outputs_with_proof,
                         target_ledger_info,
                         end_of_epoch_ledger_info,
@@ -537,10 +1133,15 @@ fn spawn_executor<ChunkExecutor: ChunkExecutorTrait + 'static>(
                     );
 
                     // Notify the ledger updater
+                    increment_queue_depth(metrics::STORAGE_SYNCHRONIZER_LEDGER_UPDATER_QUEUE);
                     if let Err(error) = ledger_updater_notifier.send(notification_metadata).await {
+                        decrement_queue_depth(metrics::STORAGE_SYNCHRONIZER_LEDGER_UPDATER_QUEUE);
+
                         // Send an error notification to the driver (we failed to notify the ledger updater)
-                        let error =
-                            format!("Failed to notify the ledger updater! Error: {:?}", error);
+                        let error = Error::ChannelClosed(format!(
+                            "Failed to notify the ledger updater! Error: {:?}",
+                            error
+                        ));
                         handle_storage_synchronizer_error(
                             notification_metadata,
                             error,
@@ -557,6 +1158,7 @@ fn spawn_executor<ChunkExecutor: ChunkExecutorTrait + 'static>(
                     } else {
                         format!("Failed to apply the data chunk! Error: {:?}", error)
                     };
+                    let error = Error::ExecutionFailure(error);
                     handle_storage_synchronizer_error(
                         notification_metadata,
                         error,
@@ -577,7 +1179,9 @@ fn spawn_executor<ChunkExecutor: ChunkExecutorTrait + 'static>(
 fn start_execute_apply_timer(storage_data_chunk: &StorageDataChunk) -> HistogramTimer {
     // Get the timer label
     let label = match storage_data_chunk {
-        StorageDataChunk::Transactions(_, _, _, _) => metrics::STORAGE_SYNCHRONIZER_EXECUTE_CHUNK,
+        StorageDataChunk::Transactions(_, _, _, _) | StorageDataChunk::Hybrid(_, _, _, _) => {
+            metrics::STORAGE_SYNCHRONIZER_EXECUTE_CHUNK
+        },
         StorageDataChunk::TransactionOutputs(_, _, _, _) => {
             metrics::STORAGE_SYNCHRONIZER_APPLY_CHUNK
         },
@@ -592,7 +1196,7 @@ fn start_execute_apply_timer(storage_data_chunk: &StorageDataChunk) -> Histogram
 }
 
 /// Spawns a dedicated updater that updates the ledger after chunk execution/application
-fn spawn_ledger_updater<ChunkExecutor: ChunkExecutorTrait + 'static>(
+pub fn spawn_ledger_updater<ChunkExecutor: ChunkExecutorTrait + 'static>(
     chunk_executor: Arc<ChunkExecutor>,
     error_notification_sender: mpsc::UnboundedSender<ErrorNotification>,
     mut ledger_updater_listener: mpsc::Receiver<NotificationMetadata>,
@@ -603,6 +1207,8 @@ fn spawn_ledger_updater<ChunkExecutor: ChunkExecutorTrait + 'static>(
     // Create a ledger updater
     let ledger_updater = async move {
         while let Some(notification_metadata) = ledger_updater_listener.next().await {
+            decrement_queue_depth(metrics::STORAGE_SYNCHRONIZER_LEDGER_UPDATER_QUEUE);
+
             // Start the update ledger timer
             let _timer = metrics::start_timer(
                 &metrics::STORAGE_SYNCHRONIZER_LATENCIES,
@@ -631,9 +1237,15 @@ fn spawn_ledger_updater<ChunkExecutor: ChunkExecutorTrait + 'static>(
                     );
 
                     // Notify the committer of the update
+                    increment_queue_depth(metrics::STORAGE_SYNCHRONIZER_COMMITTER_QUEUE);
                     if let Err(error) = committer_notifier.send(notification_metadata).await {
+                        decrement_queue_depth(metrics::STORAGE_SYNCHRONIZER_COMMITTER_QUEUE);
+
                         // Send an error notification to the driver (we failed to notify the committer)
-                        let error = format!("Failed to notify the committer! Error: {:?}", error);
+                        let error = Error::ChannelClosed(format!(
+                            "Failed to notify the committer! Error: {:?}",
+                            error
+                        ));
                         handle_storage_synchronizer_error(
                             notification_metadata,
                             error,
@@ -645,7 +1257,10 @@ fn spawn_ledger_updater<ChunkExecutor: ChunkExecutorTrait + 'static>(
                 },
                 Err(error) => {
                     // Send an error notification to the driver (we failed to update the ledger)
-                    let error = format!("Failed to update the ledger! Error: {:?}", error);
+                    let error = Error::ExecutionFailure(format!(
+                        "Failed to update the ledger! Error: {:?}",
+                        error
+                    ));
                     handle_storage_synchronizer_error(
                         notification_metadata,
                         error,
@@ -663,7 +1278,8 @@ fn spawn_ledger_updater<ChunkExecutor: ChunkExecutorTrait + 'static>(
 }
 
 /// Spawns a dedicated committer that commits executed (but pending) chunks
-fn spawn_committer<ChunkExecutor: ChunkExecutorTrait + 'static>(
+pub fn spawn_committer<ChunkExecutor: ChunkExecutorTrait + 'static>(
+    driver_config: StateSyncDriverConfig,
     chunk_executor: Arc<ChunkExecutor>,
     error_notification_sender: mpsc::UnboundedSender<ErrorNotification>,
     mut committer_listener: mpsc::Receiver<NotificationMetadata>,
@@ -674,14 +1290,16 @@ fn spawn_committer<ChunkExecutor: ChunkExecutorTrait + 'static>(
     // Create a committer
     let committer = async move {
         while let Some(notification_metadata) = committer_listener.next().await {
+            decrement_queue_depth(metrics::STORAGE_SYNCHRONIZER_COMMITTER_QUEUE);
+
             // Start the commit timer
             let _timer = metrics::start_timer(
                 &metrics::STORAGE_SYNCHRONIZER_LATENCIES,
                 metrics::STORAGE_SYNCHRONIZER_COMMIT_CHUNK,
             );
 
-            // Commit the executed chunk
-            let result = commit_chunk(chunk_executor.clone()).await;
+            // Commit the executed chunk (retrying transient failures)
+            let result = commit_chunk_with_retries(chunk_executor.clone(), &driver_config).await;
 
             // Notify the commit post-processor of the committed chunk
             match result {
@@ -714,12 +1332,19 @@ fn spawn_committer<ChunkExecutor: ChunkExecutorTrait + 'static>(
                     );
 
                     // Notify the commit post-processor of the committed chunk
+                    increment_queue_depth(
+                        metrics::STORAGE_SYNCHRONIZER_COMMIT_POST_PROCESSOR_QUEUE,
+                    );
                     if let Err(error) = commit_post_processor_notifier.send(notification).await {
+                        decrement_queue_depth(
+                            metrics::STORAGE_SYNCHRONIZER_COMMIT_POST_PROCESSOR_QUEUE,
+                        );
+
                         // Send an error notification to the driver (we failed to notify the commit post-processor)
-                        let error = format!(
+                        let error = Error::ChannelClosed(format!(
                             "Failed to notify the commit post-processor! Error: {:?}",
                             error
-                        );
+                        ));
                         handle_storage_synchronizer_error(
                             notification_metadata,
                             error,
@@ -731,7 +1356,6 @@ fn spawn_committer<ChunkExecutor: ChunkExecutorTrait + 'static>(
                 },
                 Err(error) => {
                     // Send an error notification to the driver (we failed to commit the chunk)
-                    let error = format!("Failed to commit executed chunk! Error: {:?}", error);
                     handle_storage_synchronizer_error(
                         notification_metadata,
                         error,
@@ -749,10 +1373,11 @@ fn spawn_committer<ChunkExecutor: ChunkExecutorTrait + 'static>(
 }
 
 /// Spawns a dedicated commit post-processor that handles commit notifications
-fn spawn_commit_post_processor<
+pub fn spawn_commit_post_processor<
     MempoolNotifier: MempoolNotificationSender,
     StorageServiceNotifier: StorageServiceNotificationSender,
 >(
+    driver_config: StateSyncDriverConfig,
     mut commit_post_processor_listener: mpsc::Receiver<ChunkCommitNotification>,
     event_subscription_service: Arc<Mutex<EventSubscriptionService>>,
     mempool_notification_handler: MempoolNotificationHandler<MempoolNotifier>,
@@ -760,44 +1385,223 @@ fn spawn_commit_post_processor<
     pending_data_chunks: Arc<AtomicU64>,
     runtime: Option<Handle>,
     storage: Arc<dyn DbReader>,
+    sync_progress_reporter: SyncProgressReporter,
 ) -> JoinHandle<()> {
     // Create a commit post-processor
+    let commit_post_processor_config = driver_config.commit_post_processor_config;
     let commit_post_processor = async move {
-        while let Some(notification) = commit_post_processor_listener.next().await {
+        let mut mempool_notification_batch = MempoolNotificationBatch::new(&driver_config);
+        loop {
+            // Wait for the next notification (or until the mempool batch must be flushed)
+            let notification = match mempool_notification_batch.get_time_until_flush() {
+                Some(time_until_flush) => {
+                    match timeout(time_until_flush, commit_post_processor_listener.next()).await {
+                        Ok(notification) => notification,
+                        Err(_) => {
+                            flush_mempool_notification_batch(
+                                &mut mempool_notification_batch,
+                                storage.clone(),
+                                mempool_notification_handler.clone(),
+                                event_subscription_service.clone(),
+                                storage_service_notification_handler.clone(),
+                            )
+                            .await;
+                            continue;
+                        },
+                    }
+                },
+                None => commit_post_processor_listener.next().await,
+            };
+            let notification = match notification {
+                Some(notification) => notification,
+                None => break, // The committer has been dropped
+            };
+            decrement_queue_depth(metrics::STORAGE_SYNCHRONIZER_COMMIT_POST_PROCESSOR_QUEUE);
+
             // Start the commit post-process timer
             let _timer = metrics::start_timer(
                 &metrics::STORAGE_SYNCHRONIZER_LATENCIES,
                 metrics::STORAGE_SYNCHRONIZER_COMMIT_POST_PROCESS,
             );
 
-            // Handle the committed transaction notification (e.g., notify mempool)
-            let committed_transactions = CommittedTransactions {
-                events: notification.subscribable_events,
-                transactions: notification.committed_transactions,
-            };
-            utils::handle_committed_transactions(
-                committed_transactions,
-                storage.clone(),
-                mempool_notification_handler.clone(),
-                event_subscription_service.clone(),
-                storage_service_notification_handler.clone(),
-            )
-            .await;
+            // If the node is far behind, add the committed transactions to the
+            // mempool batch (instead of notifying mempool for every chunk). The
+            // batch is flushed early on reconfigurations and once we catch up.
+            let mut post_processor_config = commit_post_processor_config;
+            let mut committed_transactions = notification.committed_transactions;
+            let mut flush_mempool_batch = false;
+            if post_processor_config.notify_mempool && mempool_notification_batch.is_enabled() {
+                let is_far_behind = sync_progress_reporter
+                    .get_num_versions_behind()
+                    .unwrap_or(0)
+                    >= driver_config.mempool_notification_batching_lag;
+                if is_far_behind || !mempool_notification_batch.is_empty() {
+                    mempool_notification_batch
+                        .add_transactions(std::mem::take(&mut committed_transactions));
+                    post_processor_config.notify_mempool = false;
+                    flush_mempool_batch = !is_far_behind
+                        || notification.reconfiguration_occurred
+                        || mempool_notification_batch.is_full();
+                }
+            }
+
+            // Handle the committed transaction notification (e.g., notify mempool).
+            // If all notifications are disabled, the post-processing is bypassed.
+            if post_processor_config.is_enabled() {
+                let committed_transactions = CommittedTransactions {
+                    events: notification.subscribable_events,
+                    transactions: committed_transactions,
+                };
+                utils::handle_committed_transactions(
+                    committed_transactions,
+                    storage.clone(),
+                    mempool_notification_handler.clone(),
+                    event_subscription_service.clone(),
+                    storage_service_notification_handler.clone(),
+                    post_processor_config,
+                )
+                .await;
+            }
+            if flush_mempool_batch {
+                flush_mempool_notification_batch(
+                    &mut mempool_notification_batch,
+                    storage.clone(),
+                    mempool_notification_handler.clone(),
+                    event_subscription_service.clone(),
+                    storage_service_notification_handler.clone(),
+                )
+                .await;
+            }
+
+            // Update the sync progress with the new synced version
+            if let Ok(synced_version) = utils::fetch_latest_synced_version(storage.clone()) {
+                sync_progress_reporter.update_synced_version(synced_version);
+            }
             decrement_pending_data_chunks(pending_data_chunks.clone());
         }
+
+        // Flush any remaining transactions before exiting
+        flush_mempool_notification_batch(
+            &mut mempool_notification_batch,
+            storage.clone(),
+            mempool_notification_handler.clone(),
+            event_subscription_service.clone(),
+            storage_service_notification_handler.clone(),
+        )
+        .await;
     };
 
     // Spawn the commit post-processor
     spawn(runtime, commit_post_processor)
 }
 
+/// A batch of committed transactions pending a mempool notification. This is
+/// used by the commit post-processor to coalesce mempool notifications across
+/// chunks (e.g., when the node is far behind and catching up).
+struct MempoolNotificationBatch {
+    batch_start_time: Option<Instant>, // The time the first chunk was added to the batch
+    max_batch_delay: Duration,
+    max_batch_size: u64,
+    num_batched_chunks: u64,
+    transactions: Vec<Transaction>,
+}
+
+impl MempoolNotificationBatch {
+    fn new(driver_config: &StateSyncDriverConfig) -> Self {
+        Self {
+            batch_start_time: None,
+            max_batch_delay: Duration::from_millis(driver_config.mempool_notification_max_delay_ms),
+            max_batch_size: driver_config.mempool_notification_batch_size,
+            num_batched_chunks: 0,
+            transactions: vec![],
+        }
+    }
+
+    /// Returns true iff mempool notifications should be batched
+    fn is_enabled(&self) -> bool {
+        self.max_batch_size > 1
+    }
+
+    /// Returns true iff the batch is empty
+    fn is_empty(&self) -> bool {
+        self.num_batched_chunks == 0
+    }
+
+    /// Returns true iff the batch has reached the maximum size
+    fn is_full(&self) -> bool {
+        self.num_batched_chunks >= self.max_batch_size
+    }
+
+    /// Adds the committed transactions of a single chunk to the batch
+    fn add_transactions(&mut self, transactions: Vec<Transaction>) {
+        if self.batch_start_time.is_none() {
+            self.batch_start_time = Some(Instant::now());
+        }
+        self.num_batched_chunks += 1;
+        self.transactions.extend(transactions);
+    }
+
+    /// Returns the time remaining until the batch must be flushed
+    /// (or None, if the batch is empty).
+    fn get_time_until_flush(&self) -> Option<Duration> {
+        self.batch_start_time.map(|batch_start_time| {
+            self.max_batch_delay
+                .saturating_sub(batch_start_time.elapsed())
+        })
+    }
+
+    /// Takes all transactions from the batch (and resets the batch)
+    fn take_transactions(&mut self) -> Vec<Transaction> {
+        self.batch_start_time = None;
+        self.num_batched_chunks = 0;
+        std::mem::take(&mut self.transactions)
+    }
+}
+
+/// Notifies mempool of all transactions in the given batch (if any)
+async fn flush_mempool_notification_batch<
+    MempoolNotifier: MempoolNotificationSender,
+    StorageServiceNotifier: StorageServiceNotificationSender,
+>(
+    mempool_notification_batch: &mut MempoolNotificationBatch,
+    storage: Arc<dyn DbReader>,
+    mempool_notification_handler: MempoolNotificationHandler<MempoolNotifier>,
+    event_subscription_service: Arc<Mutex<EventSubscriptionService>>,
+    storage_service_notification_handler: StorageServiceNotificationHandler<StorageServiceNotifier>,
+) {
+    if mempool_notification_batch.is_empty() {
+        return;
+    }
+
+    // Only notify mempool (the other components were notified for each chunk)
+    let committed_transactions = CommittedTransactions {
+        events: vec![],
+        transactions: mempool_notification_batch.take_transactions(),
+    };
+    let mempool_only_config = CommitPostProcessorConfig {
+        notify_event_subscribers: false,
+        notify_mempool: true,
+        notify_storage_service: false,
+    };
+    utils::handle_committed_transactions(
+        committed_transactions,
+        storage,
+        mempool_notification_handler,
+        event_subscription_service,
+        storage_service_notification_handler,
+        mempool_only_config,
+    )
+    .await;
+}
+
 /// Spawns a dedicated receiver that commits state values from a state snapshot
+#[allow(clippy::too_many_arguments)]
 fn spawn_state_snapshot_receiver<
     ChunkExecutor: ChunkExecutorTrait + 'static,
     MetadataStorage: MetadataStorageInterface + Clone + Send + Sync + 'static,
 >(
     chunk_executor: Arc<ChunkExecutor>,
-    mut state_snapshot_listener: mpsc::Receiver<StorageDataChunk>,
+    state_snapshot_listener: mpsc::Receiver<StorageDataChunk>,
     mut commit_notification_sender: mpsc::UnboundedSender<CommitNotification>,
     error_notification_sender: mpsc::UnboundedSender<ErrorNotification>,
     pending_data_chunks: Arc<AtomicU64>,
@@ -806,6 +1610,13 @@ fn spawn_state_snapshot_receiver<
     epoch_change_proofs: Vec<LedgerInfoWithSignatures>,
     target_ledger_info: LedgerInfoWithSignatures,
     target_output_with_proof: TransactionOutputListWithProof,
+    resumed_state_index: Option<u64>,
+    max_state_commit_workers: u64,
+    state_value_verifier_threads: u64,
+    state_snapshot_filter: Option<StateSnapshotFilter>,
+    trusted_waypoint: Option<Waypoint>,
+    error_retry_config: ErrorRetryConfig,
+    sync_progress_reporter: SyncProgressReporter,
     runtime: Option<Handle>,
 ) -> JoinHandle<()> {
     // Create a state snapshot receiver
@@ -826,8 +1637,50 @@ fn spawn_state_snapshot_receiver<
             .get_state_snapshot_receiver(version, expected_root_hash)
             .expect("Failed to initialize the state snapshot receiver!");
 
+        // Filter the persisted state values (if configured)
+        if let Some(state_snapshot_filter) = state_snapshot_filter {
+            info!(
+                LogSchema::new(LogEntry::StorageSynchronizer).message(&format!(
+                    "Filtering the state values persisted by the snapshot sync: {:?}",
+                    state_snapshot_filter
+                ))
+            );
+            state_snapshot_receiver
+                .set_value_filter(state_snapshot_filter.into_value_filter())
+                .expect("Failed to set the state value filter!");
+        }
+
+        // Verify the state value chunks using the verifier tasks. This allows
+        // the verification of upcoming chunks to overlap with the commit of
+        // the current chunk (which writes the chunk to the database).
+        let use_verifier_threads = state_value_verifier_threads > 0;
+        let state_value_verifier_threads = state_value_verifier_threads.max(1) as usize;
+        let verified_chunks = state_snapshot_listener
+            .map(|storage_data_chunk| {
+                verify_state_value_chunk(storage_data_chunk, use_verifier_threads)
+            })
+            .buffered(state_value_verifier_threads);
+
+        // Prepare the state value chunks using the commit workers. Chunks must
+        // still be committed sequentially (and in order), but the workers can
+        // compute the state value hashes for multiple chunks concurrently.
+        let max_state_commit_workers = max_state_commit_workers.max(1) as usize;
+        let use_commit_workers = max_state_commit_workers > 1;
+        let mut prepared_chunks = verified_chunks
+            .map(
+                move |(storage_data_chunk, verification_result)| async move {
+                    let (storage_data_chunk, value_hashes) =
+                        prepare_state_value_chunk(storage_data_chunk, use_commit_workers).await;
+                    (storage_data_chunk, value_hashes, verification_result)
+                },
+            )
+            .buffered(max_state_commit_workers);
+
         // Handle state value chunks
-        while let Some(storage_data_chunk) = state_snapshot_listener.next().await {
+        let mut resumed_state_index = resumed_state_index;
+        while let Some((storage_data_chunk, value_hashes, verification_result)) =
+            prepared_chunks.next().await
+        {
             // Start the snapshot timer for the state value chunk
             let _timer = metrics::start_timer(
                 &metrics::STORAGE_SYNCHRONIZER_LATENCIES,
@@ -837,14 +1690,60 @@ fn spawn_state_snapshot_receiver<
             // Commit the state value chunk
             match storage_data_chunk {
                 StorageDataChunk::States(notification_id, states_with_proof) => {
+                    // Verify that the state value chunk passed verification
+                    if let Err(error) = verification_result {
+                        let error = Error::VerificationError(format!(
+                            "The state value chunk failed verification! Error: {:?}",
+                            error
+                        ));
+                        send_storage_synchronizer_error(
+                            error_notification_sender.clone(),
+                            notification_id,
+                            error,
+                        )
+                        .await;
+                        decrement_pending_data_chunks(pending_data_chunks.clone());
+                        continue; // Wait for the next chunk
+                    }
+
+                    // If the snapshot sync was resumed, verify that the first
+                    // chunk continues from the resumed state value index.
+                    if let Some(state_index) = resumed_state_index {
+                        if states_with_proof.first_index > state_index
+                            || states_with_proof.last_index < state_index
+                        {
+                            let error = Error::InvalidPayload(format!(
+                                "The state value chunk does not continue from the resumed state index: {:?}! \
+                                First index: {:?}, last index: {:?}",
+                                state_index, states_with_proof.first_index, states_with_proof.last_index
+                            ));
+                            send_storage_synchronizer_error(
+                                error_notification_sender.clone(),
+                                notification_id,
+                                error,
+                            )
+                            .await;
+                            decrement_pending_data_chunks(pending_data_chunks.clone());
+                            continue; // Wait for the next chunk
+                        }
+                        resumed_state_index = None;
+                    }
+
                     // Commit the state value chunk
                     let all_states_synced = states_with_proof.is_last_chunk();
                     let last_committed_state_index = states_with_proof.last_index;
                     let num_state_values = states_with_proof.raw_values.len();
-                    let result = state_snapshot_receiver.add_chunk(
-                        states_with_proof.raw_values,
-                        states_with_proof.proof.clone(),
-                    );
+                    let result = match value_hashes {
+                        Some(value_hashes) => state_snapshot_receiver.add_chunk_with_value_hashes(
+                            states_with_proof.raw_values,
+                            value_hashes,
+                            states_with_proof.proof.clone(),
+                        ),
+                        None => state_snapshot_receiver.add_chunk(
+                            states_with_proof.raw_values,
+                            states_with_proof.proof.clone(),
+                        ),
+                    };
 
                     // Handle the commit result
                     match result {
@@ -871,18 +1770,29 @@ fn spawn_state_snapshot_receiver<
                                 operation_label,
                                 num_state_values as u64,
                             );
+                            sync_progress_reporter
+                                .update_state_snapshot_index(last_committed_state_index);
 
                             if !all_states_synced {
                                 // Update the metadata storage with the last committed state index
-                                if let Err(error) = metadata_storage
-                                    .clone()
-                                    .update_last_persisted_state_value_index(
-                                        &target_ledger_info,
-                                        last_committed_state_index,
-                                        all_states_synced,
-                                    )
+                                if let Err(error) = retry_policy::run_with_retries(
+                                    &error_retry_config,
+                                    "update_last_persisted_state_value_index",
+                                    None,
+                                    || {
+                                        future::ready(metadata_storage
+                                            .update_last_persisted_state_value_index(
+                                                &target_ledger_info,
+                                                last_committed_state_index,
+                                                all_states_synced,
+                                            )
+                                            .map_err(|error| {
+                                                Error::StorageError(format!("Failed to update the last persisted state index at version: {:?}! Error: {:?}", version, error))
+                                            }))
+                                    },
+                                )
+                                .await
                                 {
-                                    let error = format!("Failed to update the last persisted state index at version: {:?}! Error: {:?}", version, error);
                                     send_storage_synchronizer_error(
                                         error_notification_sender.clone(),
                                         notification_id,
@@ -906,6 +1816,8 @@ fn spawn_state_snapshot_receiver<
                                 version,
                                 &target_ledger_info,
                                 last_committed_state_index,
+                                trusted_waypoint,
+                                &error_retry_config,
                             )
                             .await
                             {
@@ -915,13 +1827,17 @@ fn spawn_state_snapshot_receiver<
                                     error,
                                 )
                                 .await;
+                            } else {
+                                sync_progress_reporter.update_synced_version(version);
                             }
                             decrement_pending_data_chunks(pending_data_chunks.clone());
                             return; // There's nothing left to do!
                         },
                         Err(error) => {
-                            let error =
-                                format!("Failed to commit state value chunk! Error: {:?}", error);
+                            let error = Error::StorageError(format!(
+                                "Failed to commit state value chunk! Error: {:?}",
+                                error
+                            ));
                             send_storage_synchronizer_error(
                                 error_notification_sender.clone(),
                                 notification_id,
@@ -946,26 +1862,129 @@ fn spawn_state_snapshot_receiver<
     spawn(runtime, receiver)
 }
 
+/// Prepares the given storage data chunk for commit. If commit workers are
+/// used, the hashes of all state values in the chunk are computed on a
+/// dedicated blocking task (so that multiple chunks can be hashed in parallel).
+/// Spawns a dedicated verifier task that verifies the given state value chunk.
+/// Note: the chunk proof can only be fully verified against the partially
+/// restored state tree (i.e., it depends on all previous chunks), so this is
+/// done by the snapshot receiver when the chunk is committed. The verifier
+/// tasks verify everything that is independent of the previous chunks.
+async fn verify_state_value_chunk(
+    storage_data_chunk: StorageDataChunk,
+    use_verifier_threads: bool,
+) -> (StorageDataChunk, Result<(), String>) {
+    if !use_verifier_threads {
+        return (storage_data_chunk, Ok(()));
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let _timer = metrics::start_timer(
+            &metrics::STORAGE_SYNCHRONIZER_LATENCIES,
+            metrics::STORAGE_SYNCHRONIZER_VERIFY_STATE_VALUE_CHUNK,
+        );
+        let verification_result = match &storage_data_chunk {
+            StorageDataChunk::States(_, states_with_proof) => {
+                verify_state_value_chunk_with_proof(states_with_proof)
+            },
+            _ => Ok(()),
+        };
+        (storage_data_chunk, verification_result)
+    })
+    .await
+    .expect("Failed to verify the state value chunk!")
+}
+
+/// Verifies the state keys and proof of the given state value chunk
+/// (independently of any previous chunks). Note: the chunk indices and
+/// root hash are already verified by the bootstrapper.
+fn verify_state_value_chunk_with_proof(
+    states_with_proof: &StateValueChunkWithProof,
+) -> Result<(), String> {
+    // Verify the proof does not contain too many siblings
+    let num_right_siblings = states_with_proof.proof.right_siblings().len();
+    if num_right_siblings > HashValue::LENGTH_IN_BITS {
+        return Err(format!(
+            "The proof contains too many right siblings: {:?}",
+            num_right_siblings
+        ));
+    }
+
+    // Verify the hashed state keys are strictly increasing
+    let key_hashes: Vec<HashValue> = states_with_proof
+        .raw_values
+        .iter()
+        .map(|(state_key, _)| state_key.hash())
+        .collect();
+    if let Some(index) = key_hashes
+        .windows(2)
+        .position(|key_hashes| key_hashes[0] >= key_hashes[1])
+    {
+        return Err(format!(
+            "The hashed state keys are not strictly increasing at index: {:?}",
+            index + 1
+        ));
+    }
+
+    // Verify the hashed state keys match the first and last keys of the chunk
+    if let (Some(first_key), Some(last_key)) = (key_hashes.first(), key_hashes.last()) {
+        if *first_key != states_with_proof.first_key || *last_key != states_with_proof.last_key {
+            return Err(format!(
+                "The hashed state keys do not match the chunk! First key: {:?}, last key: {:?}, \
+                expected first key: {:?}, expected last key: {:?}",
+                first_key, last_key, states_with_proof.first_key, states_with_proof.last_key
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+async fn prepare_state_value_chunk(
+    storage_data_chunk: StorageDataChunk,
+    use_commit_workers: bool,
+) -> (StorageDataChunk, Option<Vec<HashValue>>) {
+    if !use_commit_workers {
+        return (storage_data_chunk, None);
+    }
+
+    tokio::task::spawn_blocking(move || {
+        let value_hashes = match &storage_data_chunk {
+            StorageDataChunk::States(_, states_with_proof) => Some(
+                states_with_proof
+                    .raw_values
+                    .iter()
+                    .map(|(_, state_value)| state_value.hash())
+                    .collect(),
+            ),
+            _ => None,
+        };
+        (storage_data_chunk, value_hashes)
+    })
+    .await
+    .expect("Failed to prepare the state value chunk!")
+}
+
 /// Spawns a dedicated task that applies the given output chunk. We use
-/// `spawn_blocking` so that the heavy synchronous function doesn't
-/// block the async thread.
+/// the chunk executor pool (or `spawn_blocking`) so that the heavy
+/// synchronous function doesn't block the async thread.
 async fn apply_output_chunk<ChunkExecutor: ChunkExecutorTrait + 'static>(
     chunk_executor: Arc<ChunkExecutor>,
-    outputs_with_proof: TransactionOutputListWithProof,
+    chunk_executor_pool: Option<Arc<ThreadPool>>,
+    outputs_with_proof: Arc<TransactionOutputListWithProof>,
     target_ledger_info: LedgerInfoWithSignatures,
     end_of_epoch_ledger_info: Option<LedgerInfoWithSignatures>,
 ) -> anyhow::Result<()> {
     // Apply the output chunk
     let num_outputs = outputs_with_proof.transactions_and_outputs.len();
-    let result = tokio::task::spawn_blocking(move || {
+    let result = run_on_chunk_executor_pool(chunk_executor_pool, "apply_output_chunk", move || {
         chunk_executor.enqueue_chunk_by_transaction_outputs(
-            outputs_with_proof,
+            take_chunk_payload(outputs_with_proof),
             &target_ledger_info,
             end_of_epoch_ledger_info.as_ref(),
         )
     })
-    .await
-    .expect("Spawn_blocking(apply_output_chunk) failed!");
+    .await;
 
     // Update the logs and metrics if the chunk was applied successfully
     if result.is_ok() {
@@ -987,25 +2006,30 @@ async fn apply_output_chunk<ChunkExecutor: ChunkExecutorTrait + 'static>(
 }
 
 /// Spawns a dedicated task that executes the given transaction chunk.
-/// We use `spawn_blocking` so that the heavy synchronous function
-/// doesn't block the async thread.
+/// We use the chunk executor pool (or `spawn_blocking`) so that the
+/// heavy synchronous function doesn't block the async thread.
 async fn execute_transaction_chunk<ChunkExecutor: ChunkExecutorTrait + 'static>(
     chunk_executor: Arc<ChunkExecutor>,
-    transactions_with_proof: TransactionListWithProof,
+    chunk_executor_pool: Option<Arc<ThreadPool>>,
+    transactions_with_proof: Arc<TransactionListWithProof>,
     target_ledger_info: LedgerInfoWithSignatures,
     end_of_epoch_ledger_info: Option<LedgerInfoWithSignatures>,
+    operation: metrics::StorageSynchronizerOperations,
 ) -> anyhow::Result<()> {
     // Execute the transaction chunk
     let num_transactions = transactions_with_proof.transactions.len();
-    let result = tokio::task::spawn_blocking(move || {
-        chunk_executor.enqueue_chunk_by_execution(
-            transactions_with_proof,
-            &target_ledger_info,
-            end_of_epoch_ledger_info.as_ref(),
-        )
-    })
-    .await
-    .expect("Spawn_blocking(execute_transaction_chunk) failed!");
+    let result = run_on_chunk_executor_pool(
+        chunk_executor_pool,
+        "execute_transaction_chunk",
+        move || {
+            chunk_executor.enqueue_chunk_by_execution(
+                take_chunk_payload(transactions_with_proof),
+                &target_ledger_info,
+                end_of_epoch_ledger_info.as_ref(),
+            )
+        },
+    )
+    .await;
 
     // Update the logs and metrics if the chunk was executed successfully
     if result.is_ok() {
@@ -1018,14 +2042,77 @@ async fn execute_transaction_chunk<ChunkExecutor: ChunkExecutorTrait + 'static>(
         );
 
         // Update the chunk metrics
-        let operation_label =
-            metrics::StorageSynchronizerOperations::ExecutedTransactions.get_label();
-        update_synchronizer_chunk_metrics(num_transactions, operation_label);
+        update_synchronizer_chunk_metrics(num_transactions, operation.get_label());
     }
 
     result
 }
 
+/// Runs the given (heavy synchronous) function on the dedicated chunk
+/// executor pool and waits for the result. If there is no dedicated pool,
+/// the function is run on the shared tokio blocking pool instead.
+async fn run_on_chunk_executor_pool<T: Send + 'static>(
+    chunk_executor_pool: Option<Arc<ThreadPool>>,
+    operation_name: &str,
+    function: impl FnOnce() -> T + Send + 'static,
+) -> T {
+    match chunk_executor_pool {
+        Some(chunk_executor_pool) => {
+            let (result_sender, result_receiver) = oneshot::channel();
+            chunk_executor_pool.spawn(move || {
+                let _ = result_sender.send(function());
+            });
+            result_receiver
+                .await
+                .unwrap_or_else(|_| panic!("Chunk executor pool({}) failed!", operation_name))
+        },
+        None => tokio::task::spawn_blocking(function)
+            .await
+            .unwrap_or_else(|_| panic!("Spawn_blocking({}) failed!", operation_name)),
+    }
+}
+
+/// Converts the given transaction output list into a transaction list (with
+/// the same proof) so that the transactions can be re-executed and verified.
+fn transaction_list_from_outputs(
+    outputs_with_proof: Arc<TransactionOutputListWithProof>,
+) -> Arc<TransactionListWithProof> {
+    // If the outputs are still shared, only the transactions and proof are cloned
+    let transaction_list_with_proof = match Arc::try_unwrap(outputs_with_proof) {
+        Ok(outputs_with_proof) => {
+            let TransactionOutputListWithProof {
+                transactions_and_outputs,
+                first_transaction_output_version,
+                proof,
+            } = outputs_with_proof;
+            let transactions = transactions_and_outputs
+                .into_iter()
+                .map(|(transaction, _)| transaction)
+                .collect();
+            TransactionListWithProof::new(
+                transactions,
+                None,
+                first_transaction_output_version,
+                proof,
+            )
+        },
+        Err(outputs_with_proof) => {
+            let transactions = outputs_with_proof
+                .transactions_and_outputs
+                .iter()
+                .map(|(transaction, _)| transaction.clone())
+                .collect();
+            TransactionListWithProof::new(
+                transactions,
+                None,
+                outputs_with_proof.first_transaction_output_version,
+                outputs_with_proof.proof.clone(),
+            )
+        },
+    };
+    Arc::new(transaction_list_with_proof)
+}
+
 /// Updates the storage synchronizer chunk metrics
 fn update_synchronizer_chunk_metrics(num_items: usize, operation_label: &str) {
     metrics::increment_gauge(
@@ -1062,6 +2149,43 @@ async fn commit_chunk<ChunkExecutor: ChunkExecutorTrait + 'static>(
         .expect("Spawn_blocking(commit_chunk) failed!")
 }
 
+/// Commits the next executed chunk. If the commit fails (e.g., due to a
+/// transient storage error), the commit is retried (with the commit retry
+/// policy of the driver config), until the retries are exhausted or the commit
+/// deadline for the chunk elapses. This is safe because failed commits leave
+/// the chunk at the head of the executor's commit queue.
+async fn commit_chunk_with_retries<ChunkExecutor: ChunkExecutorTrait + 'static>(
+    chunk_executor: Arc<ChunkExecutor>,
+    driver_config: &StateSyncDriverConfig,
+) -> Result<ChunkCommitNotification, Error> {
+    // Failed commits are storage IO errors, so the commit knobs override that policy
+    let error_retry_config = ErrorRetryConfig {
+        storage_io: ErrorRetryPolicy {
+            max_retries: driver_config.max_commit_retries,
+            retry_backoff_ms: driver_config.commit_retry_backoff_ms,
+        },
+        ..driver_config.error_retry_config
+    };
+    let commit_deadline =
+        Instant::now() + Duration::from_millis(driver_config.max_chunk_commit_time_ms);
+    retry_policy::run_with_retries(
+        &error_retry_config,
+        "commit_chunk",
+        Some(commit_deadline),
+        || {
+            commit_chunk(chunk_executor.clone()).map(|result| {
+                result.map_err(|error| {
+                    Error::StorageError(format!(
+                        "Failed to commit executed chunk! Error: {:?}",
+                        error
+                    ))
+                })
+            })
+        },
+    )
+    .await
+}
+
 /// Finalizes storage once all state values have been committed
 /// and sends a commit notification to the driver.
 async fn finalize_storage_and_send_commit<
@@ -1081,84 +2205,190 @@ async fn finalize_storage_and_send_commit<
     version: Version,
     target_ledger_info: &LedgerInfoWithSignatures,
     last_committed_state_index: u64,
-) -> Result<(), String> {
+    trusted_waypoint: Option<Waypoint>,
+    error_retry_config: &ErrorRetryConfig,
+) -> Result<(), Error> {
     // Finalize the state snapshot
     state_snapshot_receiver.finish_box().map_err(|error| {
-        format!(
+        Error::StorageError(format!(
             "Failed to finish the state value synchronization! Error: {:?}",
             error
-        )
+        ))
     })?;
+
+    // Re-verify the epoch change proofs against the trusted waypoint (if enabled)
+    if let Some(trusted_waypoint) = trusted_waypoint {
+        verify_epoch_change_proofs(
+            storage.reader.clone(),
+            &trusted_waypoint,
+            epoch_change_proofs,
+            target_ledger_info,
+        )?;
+    }
+
+    // Create the commit notification (before the target outputs are handed to storage)
+    let commit_notification = create_commit_notification(
+        &target_output_with_proof,
+        last_committed_state_index,
+        version,
+    );
+
     storage
         .writer
-        .finalize_state_snapshot(
-            version,
-            target_output_with_proof.clone(),
-            epoch_change_proofs,
-        )
-        .map_err(|error| format!("Failed to finalize the state snapshot! Error: {:?}", error))?;
+        .finalize_state_snapshot(version, target_output_with_proof, epoch_change_proofs)
+        .map_err(|error| {
+            Error::StorageError(format!(
+                "Failed to finalize the state snapshot! Error: {:?}",
+                error
+            ))
+        })?;
 
     info!("All states have synced, version: {}", version);
 
     // Update the metadata storage
-    metadata_storage.update_last_persisted_state_value_index(
+    retry_policy::run_with_retries(error_retry_config, "update_last_persisted_state_value_index", None, || {
+        future::ready(metadata_storage.update_last_persisted_state_value_index(
             target_ledger_info,
             last_committed_state_index,
             true,
         ).map_err(|error| {
-        format!("All states have synced, but failed to update the metadata storage at version {:?}! Error: {:?}", version, error)
-    })?;
+            Error::StorageError(format!("All states have synced, but failed to update the metadata storage at version {:?}! Error: {:?}", version, error))
+        }))
+    })
+    .await?;
 
     // Reset the chunk executor
-    chunk_executor.reset().map_err(|error| {
-        format!(
-            "Failed to reset the chunk executor after state snapshot synchronization! Error: {:?}",
-            error
-        )
-    })?;
+    log_and_count_chunk_executor_reset(ResetReason::StateSnapshotCompleted);
+    retry_policy::run_with_retries(error_retry_config, "reset_chunk_executor", None, || {
+        future::ready(chunk_executor.reset().map_err(|error| {
+            Error::StorageError(format!(
+                "Failed to reset the chunk executor after state snapshot synchronization! Error: {:?}",
+                error
+            ))
+        }))
+    })
+    .await?;
 
-    // Create and send the commit notification
-    let commit_notification = create_commit_notification(
-        &target_output_with_proof,
-        last_committed_state_index,
-        version,
-    );
+    // Send the commit notification
     commit_notification_sender
         .send(commit_notification)
         .await
         .map_err(|error| {
-            format!(
+            Error::StorageError(format!(
                 "Failed to send the final state commit notification! Error: {:?}",
                 error
-            )
+            ))
         })?;
 
     // Update the counters
     utils::initialize_sync_gauges(storage.reader).map_err(|error| {
-        format!(
+        Error::StorageError(format!(
             "Failed to initialize the state sync version gauges! Error: {:?}",
             error
-        )
+        ))
     })?;
 
     Ok(())
 }
 
+/// Re-verifies the given epoch change proofs and target ledger info before a
+/// state snapshot is finalized. The proof chain must extend the latest epoch
+/// state in storage, and must contain the trusted waypoint (if the waypoint is
+/// ahead of storage). Note: this duplicates the verification already performed
+/// by the driver, and acts only as a defense-in-depth check.
+fn verify_epoch_change_proofs(
+    storage: Arc<dyn DbReader>,
+    trusted_waypoint: &Waypoint,
+    epoch_change_proofs: &[LedgerInfoWithSignatures],
+    target_ledger_info: &LedgerInfoWithSignatures,
+) -> Result<(), Error> {
+    // If we're syncing to genesis, the target must match the waypoint
+    if target_ledger_info.ledger_info().version() == GENESIS_TRANSACTION_VERSION {
+        return trusted_waypoint
+            .verify(target_ledger_info.ledger_info())
+            .map_err(|error| {
+                Error::VerificationError(format!(
+                    "The genesis ledger info failed waypoint verification! Error: {:?}",
+                    error
+                ))
+            });
+    }
+
+    // Verify the epoch change proof chain using the latest epoch state in storage
+    let mut latest_epoch_state = utils::fetch_latest_epoch_state(storage.clone())?;
+    if !epoch_change_proofs.is_empty() {
+        let epoch_change_proof = EpochChangeProof::new(epoch_change_proofs.to_vec(), false);
+        let last_ledger_info = epoch_change_proof
+            .verify(&latest_epoch_state)
+            .map_err(|error| {
+                Error::VerificationError(format!(
+                    "The epoch change proofs failed verification! Error: {:?}",
+                    error
+                ))
+            })?;
+        latest_epoch_state = last_ledger_info
+            .ledger_info()
+            .next_epoch_state()
+            .cloned()
+            .ok_or_else(|| {
+                Error::VerificationError(
+                    "The last epoch change proof does not end the epoch!".into(),
+                )
+            })?;
+    }
+
+    // Verify the waypoint (if it's ahead of storage) is found in the proof chain
+    let waypoint_version = trusted_waypoint.version();
+    if waypoint_version > utils::fetch_latest_synced_version(storage)? {
+        let waypoint_ledger_info = epoch_change_proofs
+            .iter()
+            .find(|ledger_info| ledger_info.ledger_info().version() == waypoint_version)
+            .ok_or_else(|| {
+                Error::VerificationError(format!(
+                    "The epoch change proofs do not contain the waypoint version: {:?}",
+                    waypoint_version
+                ))
+            })?;
+        trusted_waypoint
+            .verify(waypoint_ledger_info.ledger_info())
+            .map_err(|error| {
+                Error::VerificationError(format!(
+                    "The epoch change proofs failed waypoint verification! Error: {:?}",
+                    error
+                ))
+            })?;
+    }
+
+    // Verify the target ledger info (if it's not one of the epoch change proofs)
+    if !epoch_change_proofs.contains(target_ledger_info) {
+        latest_epoch_state
+            .verify(target_ledger_info)
+            .map_err(|error| {
+                Error::VerificationError(format!(
+                    "The target ledger info failed verification! Error: {:?}",
+                    error
+                ))
+            })?;
+    }
+
+    Ok(())
+}
+
 /// Creates a commit notification for the new committed state snapshot
 fn create_commit_notification(
     target_output_with_proof: &TransactionOutputListWithProof,
     last_committed_state_index: u64,
     version: u64,
 ) -> CommitNotification {
-    let (transactions, outputs): (Vec<Transaction>, Vec<TransactionOutput>) =
-        target_output_with_proof
-            .transactions_and_outputs
-            .clone()
-            .into_iter()
-            .unzip();
-    let events = outputs
-        .into_iter()
-        .flat_map(|output| output.events().to_vec())
+    // Only clone the transactions and events (not the entire outputs)
+    let transactions_and_outputs = &target_output_with_proof.transactions_and_outputs;
+    let transactions = transactions_and_outputs
+        .iter()
+        .map(|(transaction, _)| transaction.clone())
+        .collect::<Vec<_>>();
+    let events = transactions_and_outputs
+        .iter()
+        .flat_map(|(_, output)| output.events().iter().cloned())
         .collect::<Vec<_>>();
     CommitNotification::new_committed_state_snapshot(
         events,
@@ -1199,20 +2429,48 @@ fn increment_pending_data_chunks(pending_data_chunks: Arc<AtomicU64>) {
 
 /// Decrements the pending data chunks
 fn decrement_pending_data_chunks(atomic_u64: Arc<AtomicU64>) {
+    // Decrement the counter (saturating, as the pipeline watchdog may have
+    // reset the counter while the chunk was still in-flight).
     let delta = 1;
-    atomic_u64.fetch_sub(delta, Ordering::Relaxed);
-    metrics::decrement_gauge(
-        &metrics::STORAGE_SYNCHRONIZER_GAUGES,
+    let decremented = atomic_u64
+        .fetch_update(
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+            |pending_data_chunks| pending_data_chunks.checked_sub(delta),
+        )
+        .is_ok();
+    if decremented {
+        metrics::decrement_gauge(
+            &metrics::STORAGE_SYNCHRONIZER_GAUGES,
+            metrics::STORAGE_SYNCHRONIZER_PENDING_DATA,
+            delta,
+        );
+    }
+
+    // Update the pipeline progress
+    metrics::increment_counter(
+        &metrics::STORAGE_SYNCHRONIZER_PROGRESS,
         metrics::STORAGE_SYNCHRONIZER_PENDING_DATA,
-        delta,
     );
 }
 
+/// Increments the queue depth gauge of the given pipeline stage
+fn increment_queue_depth(queue_label: &str) {
+    metrics::increment_gauge(&metrics::STORAGE_SYNCHRONIZER_GAUGES, queue_label, 1);
+}
+
+/// Decrements the queue depth gauge of the given pipeline stage
+/// (and updates the progress made by the stage).
+fn decrement_queue_depth(queue_label: &str) {
+    metrics::decrement_gauge(&metrics::STORAGE_SYNCHRONIZER_GAUGES, queue_label, 1);
+    metrics::increment_counter(&metrics::STORAGE_SYNCHRONIZER_PROGRESS, queue_label);
+}
+
 /// Handles a storage synchronizer error by sending a notification to the driver
 /// and decrementing the number of pending data chunks in the pipeline.
 async fn handle_storage_synchronizer_error(
     notification_metadata: NotificationMetadata,
-    error: String,
+    error: Error,
     error_notification_sender: &mpsc::UnboundedSender<ErrorNotification>,
     pending_data_chunks: &Arc<AtomicU64>,
 ) {
@@ -1232,14 +2490,14 @@ async fn handle_storage_synchronizer_error(
 async fn send_storage_synchronizer_error(
     mut error_notification_sender: mpsc::UnboundedSender<ErrorNotification>,
     notification_id: NotificationId,
-    error_message: String,
+    error: Error,
 ) {
     // Log the storage synchronizer error
-    let error_message = format!("Storage synchronizer error: {:?}", error_message);
-    error!(LogSchema::new(LogEntry::StorageSynchronizer).message(&error_message));
+    error!(LogSchema::new(LogEntry::StorageSynchronizer)
+        .error(&error)
+        .message("Storage synchronizer error!"));
 
     // Update the storage synchronizer error metrics
-    let error = Error::UnexpectedError(error_message);
     metrics::increment_counter(&metrics::STORAGE_SYNCHRONIZER_ERRORS, error.get_label());
 
     // Send an error notification to the driver
@@ -1256,3 +2514,11 @@ async fn send_storage_synchronizer_error(
         );
     }
 }
+
+/// Logs the chunk executor reset and updates the reset counters
+fn log_and_count_chunk_executor_reset(reset_reason: ResetReason) {
+    info!(LogSchema::new(LogEntry::StorageSynchronizer)
+        .reset_reason(reset_reason)
+        .message("Resetting the chunk executor!"));
+    metrics::increment_counter(&metrics::CHUNK_EXECUTOR_RESETS, reset_reason.get_label());
+}
@@ -0,0 +1,145 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_infallible::RwLock;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use aptos_types::transaction::Version;
+use serde::Serialize;
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+// The window over which the recent sync throughput is calculated
+const THROUGHPUT_WINDOW_SECS: u64 = 60;
+
+// The maximum number of synced version samples to hold in the window
+const MAX_THROUGHPUT_SAMPLES: usize = 1000;
+
+/// A snapshot of the current state sync progress (e.g., for operators)
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct SyncProgress {
+    pub synced_version: Option<Version>,
+    pub highest_known_version: Option<Version>,
+    pub state_snapshot_index: Option<u64>,
+    pub transactions_per_second: Option<f64>,
+    pub estimated_seconds_remaining: Option<u64>,
+}
+
+/// The progress state updated by the storage synchronizer
+#[derive(Default)]
+struct SyncProgressState {
+    synced_version: Option<Version>,
+    highest_known_version: Option<Version>,
+    state_snapshot_index: Option<u64>,
+    synced_version_samples: VecDeque<(Instant, Version)>, // The recent (time, synced version) samples
+}
+
+/// A simple reporter that tracks the state sync progress. The reporter is
+/// updated by the storage synchronizer and can be queried (read-only) by
+/// other components (e.g., the node inspection service).
+#[derive(Clone)]
+pub struct SyncProgressReporter {
+    state: Arc<RwLock<SyncProgressState>>,
+    time_service: TimeService,
+}
+
+impl SyncProgressReporter {
+    pub fn new(time_service: TimeService) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(SyncProgressState::default())),
+            time_service,
+        }
+    }
+
+    /// Updates the highest known version (i.e., the highest sync target seen)
+    pub fn update_highest_known_version(&self, highest_known_version: Version) {
+        let mut state = self.state.write();
+        if state.highest_known_version < Some(highest_known_version) {
+            state.highest_known_version = Some(highest_known_version);
+        }
+    }
+
+    /// Updates the index of the last state value committed for the state snapshot
+    pub fn update_state_snapshot_index(&self, state_snapshot_index: u64) {
+        self.state.write().state_snapshot_index = Some(state_snapshot_index);
+    }
+
+    /// Updates the synced version and records a throughput sample
+    pub fn update_synced_version(&self, synced_version: Version) {
+        let now = self.time_service.now();
+        let mut state = self.state.write();
+        state.synced_version = Some(synced_version);
+
+        // Record the sample and garbage collect any old samples
+        let samples = &mut state.synced_version_samples;
+        samples.push_back((now, synced_version));
+        let throughput_window = Duration::from_secs(THROUGHPUT_WINDOW_SECS);
+        while let Some((sample_time, _)) = samples.front() {
+            if samples.len() > MAX_THROUGHPUT_SAMPLES
+                || now.duration_since(*sample_time) > throughput_window
+            {
+                samples.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+
+    /// Returns the number of versions the node is behind the highest
+    /// known version (if both versions are known).
+    pub fn get_num_versions_behind(&self) -> Option<u64> {
+        let state = self.state.read();
+        match (state.synced_version, state.highest_known_version) {
+            (Some(synced_version), Some(highest_known_version)) => {
+                Some(highest_known_version.saturating_sub(synced_version))
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns a snapshot of the current sync progress
+    pub fn get_sync_progress(&self) -> SyncProgress {
+        let state = self.state.read();
+
+        // Calculate the recent throughput (if we have enough samples)
+        let transactions_per_second = match (
+            state.synced_version_samples.front(),
+            state.synced_version_samples.back(),
+        ) {
+            (Some((first_time, first_version)), Some((last_time, last_version))) => {
+                let elapsed_secs = last_time.duration_since(*first_time).as_secs_f64();
+                if elapsed_secs > 0.0 {
+                    Some(last_version.saturating_sub(*first_version) as f64 / elapsed_secs)
+                } else {
+                    None
+                }
+            },
+            _ => None,
+        };
+
+        // Estimate the time remaining to reach the highest known version
+        let estimated_seconds_remaining = match (
+            state.synced_version,
+            state.highest_known_version,
+            transactions_per_second,
+        ) {
+            (Some(synced_version), Some(highest_known_version), Some(transactions_per_second))
+                if transactions_per_second > 0.0 =>
+            {
+                let remaining_versions = highest_known_version.saturating_sub(synced_version);
+                Some((remaining_versions as f64 / transactions_per_second).ceil() as u64)
+            },
+            _ => None,
+        };
+
+        SyncProgress {
+            synced_version: state.synced_version,
+            highest_known_version: state.highest_known_version,
+            state_snapshot_index: state.state_snapshot_index,
+            transactions_per_second,
+            estimated_seconds_remaining,
+        }
+    }
+}
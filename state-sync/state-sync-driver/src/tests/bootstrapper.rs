@@ -1234,6 +1234,67 @@ async fn test_snapshot_sync_lag() {
     assert!(bootstrapper.is_bootstrapped());
 }
 
+#[tokio::test]
+async fn test_snapshot_sync_lag_apply_outputs() {
+    // Create test data
+    let num_versions_behind = 10000;
+    let highest_version = 1000000;
+    let synced_version = highest_version - num_versions_behind;
+    let highest_ledger_info = create_random_epoch_ending_ledger_info(highest_version, 1);
+
+    // Create a driver configuration with a genesis waypoint and state syncing (then output syncing)
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.bootstrapping_mode =
+        BootstrappingMode::DownloadLatestStatesThenApplyOutputs;
+    driver_configuration
+        .config
+        .num_versions_to_skip_snapshot_sync = num_versions_behind;
+
+    // Create the mock streaming client (expecting an output stream from the synced version)
+    let mut mock_streaming_client = create_mock_streaming_client();
+    let (_notification_sender, data_stream_listener) = create_data_stream_listener();
+    mock_streaming_client
+        .expect_get_all_transaction_outputs()
+        .times(1)
+        .with(
+            eq(synced_version + 1),
+            eq(highest_version),
+            eq(highest_version),
+        )
+        .return_once(move |_, _, _| Ok(data_stream_listener));
+
+    // Create the mock metadata storage
+    let mut metadata_storage = MockMetadataStorage::new();
+    metadata_storage
+        .expect_previous_snapshot_sync_target()
+        .returning(|| Ok(None));
+
+    // Create the bootstrapper
+    let mut bootstrapper = create_bootstrapper_with_storage(
+        driver_configuration,
+        mock_streaming_client,
+        metadata_storage,
+        None,
+        synced_version,
+        true,
+    );
+
+    // Insert an epoch ending ledger info into the verified states of the bootstrapper
+    manipulate_verified_epoch_states(&mut bootstrapper, true, true, Some(highest_version));
+
+    // Create a global data summary
+    let mut global_data_summary = create_global_summary(1);
+    global_data_summary.advertised_data.synced_ledger_infos = vec![highest_ledger_info.clone()];
+
+    // Drive progress to initialize the transaction output stream (instead of panicking)
+    drive_progress(&mut bootstrapper, &global_data_summary, false)
+        .await
+        .unwrap();
+
+    // Verify the bootstrapper has not completed (the outputs must still be applied)
+    assert!(!bootstrapper.is_bootstrapped());
+}
+
 #[tokio::test]
 #[should_panic(expected = "Fast syncing is currently unsupported for nodes with existing state!")]
 async fn test_snapshot_sync_lag_panic() {
@@ -0,0 +1,180 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    chunk_journal::{list_journal_files, ChunkJournal, ChunkJournalReader, JournaledChunk},
+    storage_synchronizer::{NotificationMetadata, StorageDataChunk},
+    tests::utils::{
+        create_epoch_ending_ledger_info, create_ledger_info_at_version,
+        create_output_list_with_proof, create_state_value_chunk_with_proof,
+        create_transaction_list_with_proof,
+    },
+};
+use aptos_config::config::ChunkJournalConfig;
+use aptos_temppath::TempPath;
+use std::{fs::OpenOptions, io::Write, sync::Arc};
+
+#[test]
+fn test_record_and_read_journal() {
+    // Create a chunk journal
+    let journal_dir = TempPath::new();
+    let mut chunk_journal = create_chunk_journal(&journal_dir, 1024 * 1024, 10);
+
+    // Record several chunks (including a state value chunk, which is ignored)
+    let target_ledger_info = create_ledger_info_at_version(100);
+    let end_of_epoch_ledger_info = Some(create_epoch_ending_ledger_info());
+    let transaction_list_with_proof = create_transaction_list_with_proof();
+    let output_list_with_proof = create_output_list_with_proof();
+    let storage_data_chunks = vec![
+        StorageDataChunk::Transactions(
+            NotificationMetadata::new_for_test(0),
+            Arc::new(transaction_list_with_proof.clone()),
+            target_ledger_info.clone(),
+            None,
+        ),
+        StorageDataChunk::States(1, create_state_value_chunk_with_proof(false)),
+        StorageDataChunk::TransactionOutputs(
+            NotificationMetadata::new_for_test(2),
+            Arc::new(output_list_with_proof.clone()),
+            target_ledger_info.clone(),
+            end_of_epoch_ledger_info.clone(),
+        ),
+        StorageDataChunk::Hybrid(
+            NotificationMetadata::new_for_test(3),
+            Arc::new(output_list_with_proof.clone()),
+            target_ledger_info.clone(),
+            None,
+        ),
+    ];
+    for storage_data_chunk in &storage_data_chunks {
+        chunk_journal.record_chunk(storage_data_chunk).unwrap();
+    }
+
+    // Verify the chunks are read back in order
+    let journaled_chunks = read_journal(&journal_dir);
+    assert_eq!(journaled_chunks, vec![
+        JournaledChunk::Transactions(
+            0,
+            transaction_list_with_proof,
+            target_ledger_info.clone(),
+            None,
+        ),
+        JournaledChunk::TransactionOutputs(
+            2,
+            output_list_with_proof.clone(),
+            target_ledger_info.clone(),
+            end_of_epoch_ledger_info,
+        ),
+        JournaledChunk::Hybrid(3, output_list_with_proof, target_ledger_info, None),
+    ]);
+
+    // Verify the version ranges of the chunks
+    for journaled_chunk in journaled_chunks {
+        assert_eq!(journaled_chunk.get_version_range(), (Some(0), 1));
+    }
+}
+
+#[test]
+fn test_journal_rotation() {
+    // Create a chunk journal that rotates on every chunk and keeps 3 files
+    let journal_dir = TempPath::new();
+    let max_num_files = 3;
+    let mut chunk_journal = create_chunk_journal(&journal_dir, 1, max_num_files);
+
+    // Record several chunks and verify the oldest files are deleted
+    for notification_id in 0..10 {
+        chunk_journal
+            .record_chunk(&create_transaction_chunk(notification_id))
+            .unwrap();
+        let journal_files = list_journal_files(journal_dir.path()).unwrap();
+        assert!(journal_files.len() <= max_num_files as usize);
+    }
+
+    // Verify only the latest chunks remain in the journal
+    let notification_ids: Vec<_> = read_journal(&journal_dir)
+        .iter()
+        .map(get_notification_id)
+        .collect();
+    assert_eq!(notification_ids, vec![7, 8, 9]);
+
+    // Re-open the journal and verify new chunks are recorded to a new file
+    // (after the existing ones, which are pruned as required).
+    drop(chunk_journal);
+    let mut chunk_journal = create_chunk_journal(&journal_dir, 1, max_num_files);
+    chunk_journal
+        .record_chunk(&create_transaction_chunk(10))
+        .unwrap();
+    let notification_ids: Vec<_> = read_journal(&journal_dir)
+        .iter()
+        .map(get_notification_id)
+        .collect();
+    assert_eq!(notification_ids, vec![8, 9, 10]);
+}
+
+#[test]
+fn test_truncated_journal() {
+    // Create a chunk journal and record several chunks
+    let journal_dir = TempPath::new();
+    let mut chunk_journal = create_chunk_journal(&journal_dir, 1024 * 1024, 10);
+    for notification_id in 0..3 {
+        chunk_journal
+            .record_chunk(&create_transaction_chunk(notification_id))
+            .unwrap();
+    }
+
+    // Truncate the last record (e.g., as if the node crashed mid-write)
+    let (_, journal_file_path) = list_journal_files(journal_dir.path())
+        .unwrap()
+        .pop()
+        .unwrap();
+    let mut journal_file = OpenOptions::new()
+        .append(true)
+        .open(journal_file_path)
+        .unwrap();
+    journal_file.write_all(&1000u64.to_le_bytes()).unwrap();
+    journal_file.write_all(&[0; 10]).unwrap();
+
+    // Verify the complete records are still read
+    assert_eq!(read_journal(&journal_dir).len(), 3);
+}
+
+/// Creates a chunk journal in the given directory
+fn create_chunk_journal(
+    journal_dir: &TempPath,
+    max_file_size_bytes: u64,
+    max_num_files: u64,
+) -> ChunkJournal {
+    let chunk_journal_config = ChunkJournalConfig {
+        journal_dir: Some(journal_dir.path().to_path_buf()),
+        max_file_size_bytes,
+        max_num_files,
+    };
+    ChunkJournal::new(&chunk_journal_config).unwrap().unwrap()
+}
+
+/// Creates a transaction chunk with the given notification ID
+fn create_transaction_chunk(notification_id: u64) -> StorageDataChunk {
+    StorageDataChunk::Transactions(
+        NotificationMetadata::new_for_test(notification_id),
+        Arc::new(create_transaction_list_with_proof()),
+        create_ledger_info_at_version(0),
+        None,
+    )
+}
+
+/// Returns the notification ID of the given journaled chunk
+fn get_notification_id(journaled_chunk: &JournaledChunk) -> u64 {
+    match journaled_chunk {
+        JournaledChunk::Transactions(notification_id, ..)
+        | JournaledChunk::TransactionOutputs(notification_id, ..)
+        | JournaledChunk::Hybrid(notification_id, ..) => *notification_id,
+    }
+}
+
+/// Reads all chunks in the journal
+fn read_journal(journal_dir: &TempPath) -> Vec<JournaledChunk> {
+    ChunkJournalReader::new(journal_dir.path())
+        .unwrap()
+        .map(|journaled_chunk| journaled_chunk.unwrap())
+        .collect()
+}
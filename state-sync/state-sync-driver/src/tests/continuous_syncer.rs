@@ -6,10 +6,11 @@ use crate::{
     driver::DriverConfiguration,
     error::Error,
     notification_handlers::ConsensusSyncRequest,
+    storage_synchronizer::ResetReason,
     tests::{
         mocks::{
-            create_mock_db_reader, create_mock_streaming_client, create_ready_storage_synchronizer,
-            MockStorageSynchronizer, MockStreamingClient,
+            create_mock_db_reader, create_mock_storage_synchronizer, create_mock_streaming_client,
+            create_ready_storage_synchronizer, MockStorageSynchronizer, MockStreamingClient,
         },
         utils::{
             create_data_stream_listener, create_epoch_ending_ledger_info, create_epoch_state,
@@ -110,6 +111,86 @@ async fn test_critical_timeout() {
     assert_matches!(error, Error::DataStreamNotificationTimeout(_));
 }
 
+#[tokio::test]
+async fn test_chunk_executor_reset_reasons() {
+    // Create test data
+    let current_synced_epoch = 10;
+    let current_synced_version = 1000;
+
+    // Create a driver configuration where every stream timeout is critical
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.continuous_syncing_mode =
+        ContinuousSyncingMode::ApplyTransactionOutputs;
+    driver_configuration.config.max_stream_wait_time_ms = 100;
+    driver_configuration.config.max_num_stream_timeouts = 1;
+
+    // Create the mock streaming client
+    let mut mock_streaming_client = create_mock_streaming_client();
+    let (_notification_sender_1, data_stream_listener_1) = create_data_stream_listener();
+    let (_notification_sender_2, data_stream_listener_2) = create_data_stream_listener();
+    let data_stream_id_1 = data_stream_listener_1.data_stream_id;
+    let mut expectation_sequence = Sequence::new();
+    for data_stream_listener in [data_stream_listener_1, data_stream_listener_2] {
+        mock_streaming_client
+            .expect_continuously_stream_transaction_outputs()
+            .times(1)
+            .return_once(move |_, _, _| Ok(data_stream_listener))
+            .in_sequence(&mut expectation_sequence);
+    }
+    mock_streaming_client
+        .expect_terminate_stream_with_feedback()
+        .with(eq(data_stream_id_1), eq(None))
+        .return_const(Ok(()));
+
+    // Create the mock storage synchronizer and expect the reset reasons in order
+    let mut mock_storage_synchronizer = create_mock_storage_synchronizer();
+    mock_storage_synchronizer
+        .expect_pending_storage_data()
+        .return_const(false);
+    mock_storage_synchronizer
+        .expect_pending_data_chunk_count()
+        .return_const(0u64);
+    mock_storage_synchronizer
+        .expect_should_throttle()
+        .return_const(false);
+    let mut expectation_sequence = Sequence::new();
+    for reset_reason in [
+        ResetReason::StreamInitialization,
+        ResetReason::StreamTimeout,
+    ] {
+        mock_storage_synchronizer
+            .expect_reset_chunk_executor()
+            .times(1)
+            .with(eq(reset_reason))
+            .return_const(Ok(()))
+            .in_sequence(&mut expectation_sequence);
+    }
+
+    // Create the continuous syncer
+    let (mut continuous_syncer, _) = create_continuous_syncer_with_storage_synchronizer(
+        driver_configuration,
+        mock_streaming_client,
+        None,
+        mock_storage_synchronizer,
+        current_synced_version,
+        current_synced_epoch,
+    );
+
+    // Drive progress to initialize the transaction output stream
+    let no_sync_request = Arc::new(Mutex::new(None));
+    drive_progress(&mut continuous_syncer, &no_sync_request).await;
+
+    // Drive progress again and verify we get a critical timeout
+    let error = continuous_syncer
+        .drive_progress(no_sync_request.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(error, Error::CriticalDataStreamTimeout(_));
+
+    // Drive progress to initialize the stream again (after the timeout)
+    drive_progress(&mut continuous_syncer, &no_sync_request).await;
+}
+
 #[tokio::test]
 async fn test_data_stream_transactions_with_target() {
     // Create test data
@@ -270,6 +351,85 @@ async fn test_data_stream_transaction_outputs() {
     drive_progress(&mut continuous_syncer, &no_sync_request).await;
 }
 
+#[tokio::test]
+async fn test_data_stream_transaction_outputs_with_spot_verification() {
+    // Create test data
+    let current_synced_epoch = 100;
+    let current_synced_version = 5;
+    let notification_id = 1235;
+
+    // Create a driver configuration
+    let mut driver_configuration = create_full_node_driver_configuration();
+    driver_configuration.config.continuous_syncing_mode =
+        ContinuousSyncingMode::ApplyWithSpotVerification { sample_rate: 50 };
+
+    // Create the mock streaming client
+    let mut mock_streaming_client = create_mock_streaming_client();
+    let mut expectation_sequence = Sequence::new();
+    let (mut notification_sender_1, data_stream_listener_1) = create_data_stream_listener();
+    let (_notification_sender_2, data_stream_listener_2) = create_data_stream_listener();
+    let data_stream_id_1 = data_stream_listener_1.data_stream_id;
+    for data_stream_listener in [data_stream_listener_1, data_stream_listener_2] {
+        mock_streaming_client
+            .expect_continuously_stream_transaction_outputs()
+            .times(1)
+            .with(
+                eq(current_synced_version),
+                eq(current_synced_epoch),
+                eq(None),
+            )
+            .return_once(move |_, _, _| Ok(data_stream_listener))
+            .in_sequence(&mut expectation_sequence);
+    }
+    mock_streaming_client
+        .expect_terminate_stream_with_feedback()
+        .with(
+            eq(data_stream_id_1),
+            eq(Some(NotificationAndFeedback::new(
+                notification_id,
+                NotificationFeedback::InvalidPayloadData,
+            ))),
+        )
+        .return_const(Ok(()));
+
+    // Create the continuous syncer
+    let (mut continuous_syncer, _) = create_continuous_syncer(
+        driver_configuration,
+        mock_streaming_client,
+        None,
+        true,
+        current_synced_version,
+        current_synced_epoch,
+    );
+
+    // Drive progress to initialize the transaction output stream
+    let no_sync_request = Arc::new(Mutex::new(None));
+    drive_progress(&mut continuous_syncer, &no_sync_request).await;
+
+    // Send an invalid output along the stream
+    let mut transaction_output_with_proof = TransactionOutputListWithProof::new_empty();
+    transaction_output_with_proof.first_transaction_output_version =
+        Some(current_synced_version - 1);
+    let data_notification = DataNotification::new(
+        notification_id,
+        DataPayload::ContinuousTransactionOutputsWithProof(
+            create_epoch_ending_ledger_info(),
+            transaction_output_with_proof,
+        ),
+    );
+    notification_sender_1.send(data_notification).await.unwrap();
+
+    // Drive progress again and ensure we get a verification error
+    let error = continuous_syncer
+        .drive_progress(no_sync_request.clone())
+        .await
+        .unwrap_err();
+    assert_matches!(error, Error::VerificationError(_));
+
+    // Drive progress to initialize the transaction output stream
+    drive_progress(&mut continuous_syncer, &no_sync_request).await;
+}
+
 #[tokio::test]
 async fn test_data_stream_transactions_or_outputs_with_target() {
     // Create test data
@@ -493,12 +653,34 @@ fn create_continuous_syncer(
     ContinuousSyncer<MockStorageSynchronizer, MockStreamingClient>,
     OutputFallbackHandler,
 ) {
-    // Initialize the logger for tests
-    aptos_logger::Logger::init_for_testing();
-
     // Create the mock storage synchronizer
     let mock_storage_synchronizer = create_ready_storage_synchronizer(expect_reset_executor);
 
+    create_continuous_syncer_with_storage_synchronizer(
+        driver_configuration,
+        mock_streaming_client,
+        time_service,
+        mock_storage_synchronizer,
+        synced_version,
+        current_epoch,
+    )
+}
+
+/// Creates a continuous syncer (with the given storage synchronizer) for testing
+fn create_continuous_syncer_with_storage_synchronizer(
+    driver_configuration: DriverConfiguration,
+    mock_streaming_client: MockStreamingClient,
+    time_service: Option<TimeService>,
+    mock_storage_synchronizer: MockStorageSynchronizer,
+    synced_version: Version,
+    current_epoch: Epoch,
+) -> (
+    ContinuousSyncer<MockStorageSynchronizer, MockStreamingClient>,
+    OutputFallbackHandler,
+) {
+    // Initialize the logger for tests
+    aptos_logger::Logger::init_for_testing();
+
     // Create the mock db reader with the given synced version
     let mut mock_database_reader = create_mock_db_reader();
     mock_database_reader
@@ -27,6 +27,7 @@ use aptos_network::application::{interface::NetworkClient, storage::PeersAndMeta
 use aptos_storage_interface::DbReaderWriter;
 use aptos_storage_service_client::StorageServiceClient;
 use aptos_storage_service_notifications::StorageServiceNotificationListener;
+use aptos_storage_service_types::bandwidth::BandwidthBudget;
 use aptos_time_service::TimeService;
 use aptos_types::{
     event::EventKey,
@@ -378,6 +379,13 @@ async fn create_driver_for_tests(
         HashMap::new(),
         PeersAndMetadata::new(&[]),
     ));
+    let bandwidth_budget = Arc::new(BandwidthBudget::new(
+        node_config
+            .state_sync
+            .aptos_data_client
+            .data_bandwidth_config,
+        time_service.clone(),
+    ));
     let (aptos_data_client, _) = AptosDataClient::new(
         node_config.state_sync.aptos_data_client,
         node_config.base.clone(),
@@ -385,6 +393,8 @@ async fn create_driver_for_tests(
         db_rw.reader.clone(),
         network_client,
         None,
+        bandwidth_budget,
+        node_config.state_sync.trusted_peer_only.clone(),
     );
 
     // Create the metadata storage
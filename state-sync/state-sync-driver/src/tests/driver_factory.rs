@@ -22,6 +22,7 @@ use aptos_mempool_notifications::new_mempool_notifier_listener_pair;
 use aptos_network::application::{interface::NetworkClient, storage::PeersAndMetadata};
 use aptos_storage_interface::DbReaderWriter;
 use aptos_storage_service_client::StorageServiceClient;
+use aptos_storage_service_types::bandwidth::BandwidthBudget;
 use aptos_temppath::TempPath;
 use aptos_time_service::TimeService;
 use aptos_vm::AptosVM;
@@ -73,6 +74,13 @@ fn test_new_initialized_configs() {
         HashMap::new(),
         PeersAndMetadata::new(&[]),
     ));
+    let bandwidth_budget = Arc::new(BandwidthBudget::new(
+        node_config
+            .state_sync
+            .aptos_data_client
+            .data_bandwidth_config,
+        TimeService::mock(),
+    ));
     let (aptos_data_client, _) = AptosDataClient::new(
         node_config.state_sync.aptos_data_client,
         node_config.base.clone(),
@@ -80,6 +88,8 @@ fn test_new_initialized_configs() {
         db_rw.reader.clone(),
         network_client,
         None,
+        bandwidth_budget,
+        node_config.state_sync.trusted_peer_only.clone(),
     );
 
     // Create the state sync driver factory
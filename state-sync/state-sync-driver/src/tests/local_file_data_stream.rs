@@ -0,0 +1,190 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    local_file_data_stream::LocalFileDataStream,
+    tests::utils::{create_ledger_info_at_version, create_transaction, create_transaction_info},
+};
+use aptos_crypto::hash::CryptoHash;
+use aptos_data_streaming_service::data_notification::DataPayload;
+use aptos_temppath::TempPath;
+use aptos_types::{
+    contract_event::ContractEvent,
+    ledger_info::LedgerInfoWithSignatures,
+    proof::{accumulator::InMemoryTransactionAccumulator, TransactionAccumulatorRangeProof},
+    transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
+};
+use futures::StreamExt;
+use serde_json::json;
+use std::{fs, path::Path};
+
+#[tokio::test]
+async fn test_stream_transaction_outputs() {
+    // Create a local backup with a single chunk (versions 0 to 3)
+    let backup_dir = TempPath::new();
+    backup_dir.create_as_dir().unwrap();
+    let transactions: Vec<_> = (0..4).map(|_| create_transaction()).collect();
+    let transaction_infos: Vec<_> = (0..4).map(|_| create_transaction_info()).collect();
+    let ledger_info = create_ledger_info_at_version(10);
+    write_transaction_backup(
+        backup_dir.path(),
+        0,
+        &transactions,
+        &transaction_infos,
+        &ledger_info,
+    );
+
+    // Verify the versions contained in the backup
+    let local_file_data_stream = LocalFileDataStream::new(backup_dir.path().to_path_buf()).unwrap();
+    for version in 0..=3 {
+        assert!(local_file_data_stream.contains_version(version));
+    }
+    assert!(!local_file_data_stream.contains_version(4));
+
+    // Stream the entire chunk and verify the notifications
+    let mut data_stream = local_file_data_stream.stream_transaction_outputs(0, 3);
+    match data_stream.next().await.unwrap().data_payload {
+        DataPayload::ContinuousTransactionOutputsWithProof(
+            ledger_info_with_signatures,
+            outputs_with_proof,
+        ) => {
+            assert_eq!(ledger_info_with_signatures, ledger_info);
+            assert_eq!(outputs_with_proof.first_transaction_output_version, Some(0));
+            assert_eq!(
+                outputs_with_proof.proof.transaction_infos,
+                transaction_infos
+            );
+            for (index, (transaction, output)) in outputs_with_proof
+                .transactions_and_outputs
+                .iter()
+                .enumerate()
+            {
+                assert_eq!(transaction, &transactions[index]);
+                assert_eq!(output.gas_used(), transaction_infos[index].gas_used());
+            }
+        },
+        data_payload => panic!("Unexpected data payload: {:?}", data_payload),
+    }
+    assert_eq!(
+        data_stream.next().await.unwrap().data_payload,
+        DataPayload::EndOfStream
+    );
+
+    // Stream a sub-range of the chunk and verify the derived proof
+    let mut data_stream = local_file_data_stream.stream_transaction_outputs(2, 2);
+    match data_stream.next().await.unwrap().data_payload {
+        DataPayload::ContinuousTransactionOutputsWithProof(_, outputs_with_proof) => {
+            assert_eq!(outputs_with_proof.first_transaction_output_version, Some(2));
+            assert_eq!(outputs_with_proof.transactions_and_outputs.len(), 1);
+            assert_eq!(outputs_with_proof.proof.transaction_infos, vec![
+                transaction_infos[2].clone()
+            ]);
+
+            let leaf_hashes: Vec<_> = transaction_infos.iter().map(CryptoHash::hash).collect();
+            let root_hash = InMemoryTransactionAccumulator::from_leaves(&leaf_hashes).root_hash();
+            outputs_with_proof
+                .proof
+                .ledger_info_to_transaction_infos_proof
+                .verify(root_hash, Some(2), &leaf_hashes[2..3])
+                .unwrap();
+        },
+        data_payload => panic!("Unexpected data payload: {:?}", data_payload),
+    }
+    assert_eq!(
+        data_stream.next().await.unwrap().data_payload,
+        DataPayload::EndOfStream
+    );
+
+    // Stream beyond the end of the backup and verify the stream ends early
+    let mut data_stream = local_file_data_stream.stream_transaction_outputs(3, 100);
+    match data_stream.next().await.unwrap().data_payload {
+        DataPayload::ContinuousTransactionOutputsWithProof(_, outputs_with_proof) => {
+            assert_eq!(outputs_with_proof.first_transaction_output_version, Some(3));
+            assert_eq!(outputs_with_proof.transactions_and_outputs.len(), 1);
+        },
+        data_payload => panic!("Unexpected data payload: {:?}", data_payload),
+    }
+    assert_eq!(
+        data_stream.next().await.unwrap().data_payload,
+        DataPayload::EndOfStream
+    );
+}
+
+#[test]
+fn test_missing_backup_metadata() {
+    // Verify that creating a stream for an empty directory fails
+    let backup_dir = TempPath::new();
+    backup_dir.create_as_dir().unwrap();
+    assert!(LocalFileDataStream::new(backup_dir.path().to_path_buf()).is_err());
+}
+
+/// Writes a transaction backup (with a single chunk) to the given backup
+/// directory using the format produced by the db-backup tool. The chunk
+/// uses an empty range proof, so the chunk must contain all leaves of the
+/// accumulator and the number of leaves must be a power of two.
+fn write_transaction_backup(
+    backup_dir: &Path,
+    first_version: Version,
+    transactions: &[Transaction],
+    transaction_infos: &[TransactionInfo],
+    ledger_info: &LedgerInfoWithSignatures,
+) {
+    let last_version = first_version + transactions.len() as u64 - 1;
+
+    // Write the transaction records
+    let mut records = vec![];
+    for (transaction, transaction_info) in transactions.iter().zip(transaction_infos) {
+        let record = bcs::to_bytes(&(
+            transaction,
+            transaction_info,
+            Vec::<ContractEvent>::new(),
+            WriteSet::default(),
+        ))
+        .unwrap();
+        records.extend((record.len() as u32).to_be_bytes());
+        records.extend(record);
+    }
+    fs::create_dir_all(backup_dir.join("transactions")).unwrap();
+    fs::write(backup_dir.join("transactions/0.chunk"), records).unwrap();
+
+    // Write the chunk proof
+    let proof = bcs::to_bytes(&(
+        TransactionAccumulatorRangeProof::new_empty(),
+        ledger_info.clone(),
+    ))
+    .unwrap();
+    fs::write(backup_dir.join("transactions/0.proof"), proof).unwrap();
+
+    // Write the manifest
+    let manifest = json!({
+        "first_version": first_version,
+        "last_version": last_version,
+        "chunks": [{
+            "first_version": first_version,
+            "last_version": last_version,
+            "transactions": "transactions/0.chunk",
+            "proof": "transactions/0.proof",
+        }],
+    });
+    fs::write(
+        backup_dir.join("transactions/transaction.manifest"),
+        manifest.to_string(),
+    )
+    .unwrap();
+
+    // Write the metadata
+    let metadata = json!({
+        "TransactionBackup": {
+            "first_version": first_version,
+            "last_version": last_version,
+            "manifest": "transactions/transaction.manifest",
+        },
+    });
+    fs::create_dir_all(backup_dir.join("metadata")).unwrap();
+    fs::write(
+        backup_dir.join("metadata/transaction_backup.meta"),
+        format!("{}\n", metadata),
+    )
+    .unwrap();
+}
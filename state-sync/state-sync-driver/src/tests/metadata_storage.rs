@@ -6,10 +6,13 @@ use crate::{
         database_schema::{MetadataKey, MetadataSchema, MetadataValue},
         MetadataStorageInterface, PersistentMetadataStorage, StateSnapshotProgress,
     },
+    state_snapshot_filter::StateSnapshotFilter,
     tests::utils::{create_epoch_ending_ledger_info, create_ledger_info_at_version},
 };
+use aptos_config::config::StateSnapshotFilterConfig;
 use aptos_schemadb::schema::fuzzing::assert_encode_decode;
 use aptos_temppath::TempPath;
+use aptos_types::account_address::AccountAddress;
 use claims::{assert_err, assert_none};
 
 #[test]
@@ -167,3 +170,33 @@ fn test_writes_to_different_targets() {
         .update_last_persisted_state_value_index(&target_ledger_info, 10101, false)
         .unwrap_err();
 }
+
+#[test]
+fn test_state_snapshot_filter_reads_and_writes() {
+    // Create a new metadata storage
+    let tmp_dir = TempPath::new();
+    let metadata_storage = PersistentMetadataStorage::new(tmp_dir.path());
+
+    // Verify no filter is recorded
+    assert_none!(metadata_storage.get_state_snapshot_filter().unwrap());
+
+    // Record a filter and verify it is returned
+    let state_snapshot_filter = StateSnapshotFilter::new(&StateSnapshotFilterConfig {
+        account_allowlist: vec![AccountAddress::ONE],
+        ..Default::default()
+    });
+    metadata_storage
+        .update_state_snapshot_filter(state_snapshot_filter.clone())
+        .unwrap();
+    assert_eq!(
+        metadata_storage.get_state_snapshot_filter().unwrap(),
+        state_snapshot_filter
+    );
+
+    // Clear the filter and verify it is removed
+    metadata_storage.update_state_snapshot_filter(None).unwrap();
+    assert_none!(metadata_storage.get_state_snapshot_filter().unwrap());
+
+    // Verify the snapshot progress is unaffected
+    assert_none!(metadata_storage.previous_snapshot_sync_target().unwrap());
+}
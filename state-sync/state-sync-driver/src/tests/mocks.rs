@@ -4,7 +4,8 @@
 use crate::{
     error::Error,
     metadata_storage::MetadataStorageInterface,
-    storage_synchronizer::{NotificationMetadata, StorageSynchronizerInterface},
+    state_snapshot_filter::StateSnapshotFilter,
+    storage_synchronizer::{NotificationMetadata, ResetReason, StorageSynchronizerInterface},
     tests::utils::{create_empty_epoch_state, create_epoch_ending_ledger_info},
 };
 use anyhow::Result as AnyhowResult;
@@ -17,7 +18,7 @@ use aptos_data_streaming_service::{
 use aptos_executor_types::{ChunkCommitNotification, ChunkExecutorTrait};
 use aptos_storage_interface::{
     cached_state_view::ShardedStateCache, state_delta::StateDelta, DbReader, DbReaderWriter,
-    DbWriter, ExecutedTrees, Order, Result, StateSnapshotReceiver,
+    DbWriter, ExecutedTrees, Order, Result, StateSnapshotReceiver, StateValueFilter,
 };
 use aptos_types::{
     account_address::AccountAddress,
@@ -119,6 +120,12 @@ pub fn create_ready_storage_synchronizer(expect_reset_executor: bool) -> MockSto
     mock_storage_synchronizer
         .expect_pending_storage_data()
         .return_const(false);
+    mock_storage_synchronizer
+        .expect_pending_data_chunk_count()
+        .return_const(0u64);
+    mock_storage_synchronizer
+        .expect_should_throttle()
+        .return_const(false);
     if expect_reset_executor {
         mock_storage_synchronizer
             .expect_finish_chunk_executor()
@@ -347,6 +354,13 @@ mock! {
 
         fn previous_snapshot_sync_target(&self) -> Result<Option<LedgerInfoWithSignatures>, Error>;
 
+        fn get_state_snapshot_filter(&self) -> Result<Option<StateSnapshotFilter>, Error>;
+
+        fn update_state_snapshot_filter(
+            &self,
+            state_snapshot_filter: Option<StateSnapshotFilter>,
+        ) -> Result<(), Error>;
+
         fn update_last_persisted_state_value_index(
             &self,
             target_ledger_info: &LedgerInfoWithSignatures,
@@ -366,6 +380,15 @@ mock! {
     impl StateSnapshotReceiver<StateKey, StateValue> for SnapshotReceiver {
         fn add_chunk(&mut self, chunk: Vec<(StateKey, StateValue)>, proof: SparseMerkleRangeProof) -> Result<()>;
 
+        fn add_chunk_with_value_hashes(
+            &mut self,
+            chunk: Vec<(StateKey, StateValue)>,
+            value_hashes: Vec<HashValue>,
+            proof: SparseMerkleRangeProof,
+        ) -> Result<()>;
+
+        fn set_value_filter(&mut self, value_filter: StateValueFilter<StateKey>) -> Result<()>;
+
         fn finish(self) -> Result<()>;
 
         fn finish_box(self: Box<Self>) -> Result<()>;
@@ -388,6 +411,14 @@ mock! {
             start_epoch: Epoch,
         ) -> AnyhowResult<DataStreamListener, aptos_data_streaming_service::error::Error>;
 
+        async fn get_all_events_by_key(
+            &self,
+            event_key: EventKey,
+            start_sequence_number: u64,
+            end_sequence_number: u64,
+            proof_version: Version,
+        ) -> AnyhowResult<DataStreamListener, aptos_data_streaming_service::error::Error>;
+
         async fn get_all_transaction_outputs(
             &self,
             start_version: Version,
@@ -434,6 +465,13 @@ mock! {
             target: Option<LedgerInfoWithSignatures>,
         ) -> AnyhowResult<DataStreamListener, aptos_data_streaming_service::error::Error>;
 
+        async fn follow_transaction_outputs(
+            &self,
+            known_version: Version,
+            known_epoch: Epoch,
+            max_lag_versions: u64,
+        ) -> AnyhowResult<DataStreamListener, aptos_data_streaming_service::error::Error>;
+
         async fn terminate_stream_with_feedback(
             &self,
             data_stream_id: DataStreamId,
@@ -466,6 +504,14 @@ mock! {
             end_of_epoch_ledger_info: Option<LedgerInfoWithSignatures>,
         ) -> AnyhowResult<(), crate::error::Error>;
 
+        async fn verify_transaction_outputs_by_execution(
+            &mut self,
+            notification_metadata: NotificationMetadata,
+            output_list_with_proof: TransactionOutputListWithProof,
+            target_ledger_info: LedgerInfoWithSignatures,
+            end_of_epoch_ledger_info: Option<LedgerInfoWithSignatures>,
+        ) -> AnyhowResult<(), crate::error::Error>;
+
         fn initialize_state_synchronizer(
             &mut self,
             epoch_change_proofs: Vec<LedgerInfoWithSignatures>,
@@ -473,15 +519,26 @@ mock! {
             target_output_with_proof: TransactionOutputListWithProof,
         ) -> AnyhowResult<JoinHandle<()>, crate::error::Error>;
 
+        fn resume_state_synchronizer(
+            &mut self,
+            epoch_change_proofs: Vec<LedgerInfoWithSignatures>,
+            target_ledger_info: LedgerInfoWithSignatures,
+            target_output_with_proof: TransactionOutputListWithProof,
+        ) -> AnyhowResult<(JoinHandle<()>, u64), crate::error::Error>;
+
         fn pending_storage_data(&self) -> bool;
 
+        fn pending_data_chunk_count(&self) -> u64;
+
+        fn should_throttle(&self) -> bool;
+
         async fn save_state_values(
             &mut self,
             notification_id: NotificationId,
             state_value_chunk_with_proof: StateValueChunkWithProof,
         ) -> AnyhowResult<(), crate::error::Error>;
 
-        fn reset_chunk_executor(&self) -> AnyhowResult<(), crate::error::Error>;
+        fn reset_chunk_executor(&self, reset_reason: ResetReason) -> AnyhowResult<(), crate::error::Error>;
 
         fn finish_chunk_executor(&self);
     }
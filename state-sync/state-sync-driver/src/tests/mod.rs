@@ -3,10 +3,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod bootstrapper;
+mod chunk_journal;
 mod continuous_syncer;
 mod driver;
 mod driver_factory;
+mod local_file_data_stream;
 mod metadata_storage;
 mod mocks;
+mod pipeline_watchdog;
+mod retry_policy;
 mod storage_synchronizer;
+mod sync_progress;
 mod utils;
@@ -0,0 +1,84 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    metrics,
+    pipeline_watchdog::{PipelineSnapshot, PipelineStallDetector},
+};
+use std::time::{Duration, Instant};
+
+#[test]
+fn test_stall_detector_progress() {
+    // Create a stall detector with a max stall duration of 10 seconds
+    let time_now = Instant::now();
+    let mut stall_detector = PipelineStallDetector::new(Duration::from_secs(10), time_now);
+
+    // Verify no stall is reported while progress is being made
+    for progress in 0..20 {
+        let snapshot = create_snapshot(10, progress, [1, 2, 3, 4]);
+        let time_now = time_now + Duration::from_secs(progress * 5);
+        assert_eq!(stall_detector.check_snapshot(&snapshot, time_now), None);
+    }
+
+    // Verify no stall is reported if there's no pending data
+    let snapshot = create_snapshot(0, 19, [0, 0, 0, 0]);
+    let time_now = time_now + Duration::from_secs(1000);
+    assert_eq!(stall_detector.check_snapshot(&snapshot, time_now), None);
+}
+
+#[test]
+fn test_stall_detector_stalls() {
+    // Create a stall detector with a max stall duration of 10 seconds
+    let time_now = Instant::now();
+    let mut stall_detector = PipelineStallDetector::new(Duration::from_secs(10), time_now);
+
+    // Verify no stall is reported before the max stall duration
+    let snapshot = create_snapshot(5, 0, [0, 3, 2, 0]);
+    let time_now = time_now + Duration::from_secs(9);
+    assert_eq!(stall_detector.check_snapshot(&snapshot, time_now), None);
+
+    // Verify a stall is reported (for the most downstream non-empty channel)
+    let time_now = time_now + Duration::from_secs(1);
+    let diagnosis = stall_detector.check_snapshot(&snapshot, time_now).unwrap();
+    assert_eq!(
+        diagnosis.stuck_channel,
+        metrics::STORAGE_SYNCHRONIZER_COMMITTER_QUEUE
+    );
+    assert_eq!(diagnosis.stalled_duration, Duration::from_secs(10));
+    assert_eq!(diagnosis.pending_data_chunks, 5);
+    assert_eq!(diagnosis.num_queued_chunks(), 5);
+
+    // Verify the detector is re-armed after the stall is reported
+    let time_now = time_now + Duration::from_secs(5);
+    assert_eq!(stall_detector.check_snapshot(&snapshot, time_now), None);
+    let time_now = time_now + Duration::from_secs(5);
+    assert!(stall_detector.check_snapshot(&snapshot, time_now).is_some());
+
+    // Verify a stall with empty channels is reported as an in-flight chunk
+    let snapshot = create_snapshot(2, 1, [0, 0, 0, 0]);
+    let time_now = time_now + Duration::from_secs(1);
+    assert_eq!(stall_detector.check_snapshot(&snapshot, time_now), None);
+    let time_now = time_now + Duration::from_secs(10);
+    let diagnosis = stall_detector.check_snapshot(&snapshot, time_now).unwrap();
+    assert_eq!(diagnosis.stuck_channel, "in_flight_chunk");
+    assert_eq!(diagnosis.num_queued_chunks(), 0);
+}
+
+/// Creates a pipeline snapshot with the given state
+fn create_snapshot(
+    pending_data_chunks: u64,
+    progress: u64,
+    queue_depths: [u64; 4],
+) -> PipelineSnapshot {
+    let queue_labels = [
+        metrics::STORAGE_SYNCHRONIZER_EXECUTOR_QUEUE,
+        metrics::STORAGE_SYNCHRONIZER_LEDGER_UPDATER_QUEUE,
+        metrics::STORAGE_SYNCHRONIZER_COMMITTER_QUEUE,
+        metrics::STORAGE_SYNCHRONIZER_COMMIT_POST_PROCESSOR_QUEUE,
+    ];
+    PipelineSnapshot {
+        pending_data_chunks,
+        progress,
+        queue_depths: queue_labels.into_iter().zip(queue_depths).collect(),
+    }
+}
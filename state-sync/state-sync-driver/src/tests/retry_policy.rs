@@ -0,0 +1,81 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{error::Error, retry_policy::run_with_retries};
+use aptos_config::config::{ErrorRetryConfig, ErrorRetryPolicy};
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+#[tokio::test]
+async fn test_run_with_retries_by_class() {
+    // Create a config that retries storage IO failures (but not verification failures)
+    let error_retry_config = ErrorRetryConfig {
+        storage_io: ErrorRetryPolicy {
+            max_retries: 2,
+            retry_backoff_ms: 1,
+        },
+        verification_failure: ErrorRetryPolicy::no_retries(),
+        ..Default::default()
+    };
+
+    // Verify that storage IO failures are retried until the retries are exhausted
+    let num_attempts = AtomicU64::new(0);
+    let result: Result<(), Error> = run_with_retries(&error_retry_config, "test", None, || {
+        num_attempts.fetch_add(1, Ordering::Relaxed);
+        async { Err(Error::StorageError("Transient IO failure!".into())) }
+    })
+    .await;
+    assert!(matches!(result, Err(Error::StorageError(_))));
+    assert_eq!(num_attempts.load(Ordering::Relaxed), 3);
+
+    // Verify that a storage IO failure followed by a success is retried
+    let num_attempts = AtomicU64::new(0);
+    let result = run_with_retries(&error_retry_config, "test", None, || {
+        let attempt = num_attempts.fetch_add(1, Ordering::Relaxed);
+        async move {
+            if attempt == 0 {
+                Err(Error::StorageError("Transient IO failure!".into()))
+            } else {
+                Ok(attempt)
+            }
+        }
+    })
+    .await;
+    assert_eq!(result.unwrap(), 1);
+
+    // Verify that verification failures are never retried
+    let num_attempts = AtomicU64::new(0);
+    let result: Result<(), Error> = run_with_retries(&error_retry_config, "test", None, || {
+        num_attempts.fetch_add(1, Ordering::Relaxed);
+        async { Err(Error::VerificationError("Invalid proof!".into())) }
+    })
+    .await;
+    assert!(matches!(result, Err(Error::VerificationError(_))));
+    assert_eq!(num_attempts.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn test_run_with_retries_deadline() {
+    // Create a config with a long backoff for storage IO failures
+    let error_retry_config = ErrorRetryConfig {
+        storage_io: ErrorRetryPolicy {
+            max_retries: 10,
+            retry_backoff_ms: 60_000,
+        },
+        ..Default::default()
+    };
+
+    // Verify that the operation isn't retried once the backoff would exceed the deadline
+    let num_attempts = AtomicU64::new(0);
+    let deadline = Instant::now() + Duration::from_millis(10);
+    let result: Result<(), Error> =
+        run_with_retries(&error_retry_config, "test", Some(deadline), || {
+            num_attempts.fetch_add(1, Ordering::Relaxed);
+            async { Err(Error::StorageError("Transient IO failure!".into())) }
+        })
+        .await;
+    assert!(matches!(result, Err(Error::StorageError(_))));
+    assert_eq!(num_attempts.load(Ordering::Relaxed), 1);
+}
@@ -3,29 +3,39 @@
 
 use crate::{
     error::Error,
-    metadata_storage::PersistentMetadataStorage,
+    fault_injection::{
+        check_pipeline_invariants, PipelineChannel, PipelineFault, PipelineFaultInjector,
+        PipelineInvariantViolation, PipelineObservations,
+    },
+    metadata_storage::{MetadataStorageInterface, PersistentMetadataStorage},
     notification_handlers::{
         CommitNotification, CommitNotificationListener, CommittedTransactions,
         ErrorNotificationListener, MempoolNotificationHandler, StorageServiceNotificationHandler,
     },
+    state_snapshot_filter::StateSnapshotFilter,
     storage_synchronizer::{
         NotificationMetadata, StorageSynchronizer, StorageSynchronizerHandles,
         StorageSynchronizerInterface,
     },
+    sync_progress::SyncProgressReporter,
     tests::{
         mocks::{
             create_mock_db_writer, create_mock_executor, create_mock_reader_writer,
             create_mock_reader_writer_with_version, create_mock_receiver, MockChunkExecutor,
         },
         utils::{
-            create_epoch_ending_ledger_info, create_event, create_output_list_with_proof,
+            create_epoch_ending_ledger_info, create_epoch_ending_ledger_info_for_epoch,
+            create_event, create_ledger_info_at_version, create_output_list_with_proof,
             create_state_value_chunk_with_proof, create_transaction,
             create_transaction_list_with_proof, verify_commit_notification,
         },
     },
 };
 use anyhow::format_err;
-use aptos_config::config::StateSyncDriverConfig;
+use aptos_config::config::{
+    CommitPostProcessorConfig, StateSnapshotFilterConfig, StateSyncDriverConfig,
+};
+use aptos_crypto::hash::CryptoHash;
 use aptos_data_streaming_service::data_notification::NotificationId;
 use aptos_event_notifications::EventSubscriptionService;
 use aptos_executor_types::ChunkCommitNotification;
@@ -33,14 +43,18 @@ use aptos_infallible::{Mutex, RwLock};
 use aptos_mempool_notifications::MempoolNotificationListener;
 use aptos_storage_interface::{AptosDbError, DbReaderWriter};
 use aptos_storage_service_notifications::StorageServiceNotificationListener;
+use aptos_time_service::TimeService;
 use aptos_types::{
+    account_address::AccountAddress,
     ledger_info::LedgerInfoWithSignatures,
+    state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{TransactionOutputListWithProof, Version},
+    waypoint::Waypoint,
 };
-use claims::assert_matches;
-use futures::StreamExt;
-use mockall::predicate::always;
-use std::{sync::Arc, time::Duration};
+use claims::{assert_matches, assert_none};
+use futures::{FutureExt, StreamExt};
+use mockall::{predicate::always, Sequence};
+use std::{cmp::Reverse, sync::Arc, time::Duration};
 use tokio::time::timeout;
 
 // Useful test constants
@@ -142,7 +156,12 @@ async fn test_apply_outputs_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::ExecutionFailure(String::new()),
+    )
+    .await;
     verify_no_pending_data(&storage_synchronizer);
 }
 
@@ -176,7 +195,12 @@ async fn test_apply_outputs_send_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::ChannelClosed(String::new()),
+    )
+    .await;
     verify_no_pending_data(&storage_synchronizer);
 }
 
@@ -209,7 +233,12 @@ async fn test_apply_outputs_update_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::ExecutionFailure(String::new()),
+    )
+    .await;
     verify_no_pending_data(&storage_synchronizer);
 }
 
@@ -244,7 +273,12 @@ async fn test_apply_outputs_update_send_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::ChannelClosed(String::new()),
+    )
+    .await;
     verify_no_pending_data(&storage_synchronizer);
 }
 
@@ -257,9 +291,11 @@ async fn test_apply_outputs_commit_error() {
         .with(always(), always(), always())
         .returning(|_, _, _| Ok(()));
     chunk_executor.expect_update_ledger().returning(|| Ok(()));
+    let max_commit_attempts = StateSyncDriverConfig::default().max_commit_retries as usize + 1;
     chunk_executor
         .expect_commit_chunk()
-        .return_once(|| Err(format_err!("Failed to commit chunk!")));
+        .times(max_commit_attempts)
+        .returning(|| Err(format_err!("Failed to commit chunk!")));
 
     // Create the storage synchronizer
     let (_, mut error_listener, _, _, _, mut storage_synchronizer, _) =
@@ -278,10 +314,142 @@ async fn test_apply_outputs_commit_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::StorageError(String::new()),
+    )
+    .await;
+    verify_no_pending_data(&storage_synchronizer);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_apply_outputs_commit_retry() {
+    // Create test data
+    let transaction_to_commit = create_transaction();
+    let event_to_commit = create_event(None);
+
+    // Setup the mock executor to fail the first commits (but not the last)
+    let mut chunk_executor = create_mock_executor();
+    chunk_executor
+        .expect_enqueue_chunk_by_transaction_outputs()
+        .with(always(), always(), always())
+        .returning(|_, _, _| Ok(()));
+    chunk_executor.expect_update_ledger().returning(|| Ok(()));
+    let mut expectation_sequence = Sequence::new();
+    chunk_executor
+        .expect_commit_chunk()
+        .times(2)
+        .returning(|| Err(format_err!("Failed to commit chunk!")))
+        .in_sequence(&mut expectation_sequence);
+    let expected_commit_return = Ok(ChunkCommitNotification {
+        subscribable_events: vec![event_to_commit.clone()],
+        committed_transactions: vec![transaction_to_commit.clone()],
+        reconfiguration_occurred: false,
+    });
+    chunk_executor
+        .expect_commit_chunk()
+        .times(1)
+        .return_once(move || expected_commit_return)
+        .in_sequence(&mut expectation_sequence);
+
+    // Create the storage synchronizer with a small retry backoff
+    let highest_synced_version = 1090;
+    let driver_config = StateSyncDriverConfig {
+        commit_retry_backoff_ms: 1,
+        max_commit_retries: 2,
+        ..Default::default()
+    };
+    let db_path = aptos_temppath::TempPath::new();
+    let (_, _, _, mut mempool_listener, mut storage_service_listener, mut storage_synchronizer, _) =
+        create_storage_synchronizer_with_config(
+            driver_config,
+            PersistentMetadataStorage::new(db_path.path()),
+            chunk_executor,
+            create_mock_reader_writer_with_version(None, None, highest_synced_version),
+        );
+
+    // Attempt to apply a chunk of outputs
+    storage_synchronizer
+        .apply_transaction_outputs(
+            NotificationMetadata::new_for_test(0),
+            create_output_list_with_proof(),
+            create_epoch_ending_ledger_info(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Verify that the chunk was eventually committed
+    verify_commit_notification(
+        None,
+        &mut mempool_listener,
+        &mut storage_service_listener,
+        vec![transaction_to_commit],
+        vec![event_to_commit],
+        highest_synced_version,
+    )
+    .await;
+
+    // Verify there's no pending data
     verify_no_pending_data(&storage_synchronizer);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_apply_outputs_commit_post_processor_bypass() {
+    // Setup the mock executor
+    let mut chunk_executor = create_mock_executor();
+    chunk_executor
+        .expect_enqueue_chunk_by_transaction_outputs()
+        .with(always(), always(), always())
+        .returning(|_, _, _| Ok(()));
+    chunk_executor.expect_update_ledger().returning(|| Ok(()));
+    let expected_commit_return = Ok(ChunkCommitNotification {
+        subscribable_events: vec![create_event(None)],
+        committed_transactions: vec![create_transaction()],
+        reconfiguration_occurred: false,
+    });
+    chunk_executor
+        .expect_commit_chunk()
+        .return_once(move || expected_commit_return);
+
+    // Create the storage synchronizer with all commit notifications disabled
+    let driver_config = StateSyncDriverConfig {
+        commit_post_processor_config: CommitPostProcessorConfig {
+            notify_event_subscribers: false,
+            notify_mempool: false,
+            notify_storage_service: false,
+        },
+        ..Default::default()
+    };
+    let db_path = aptos_temppath::TempPath::new();
+    let (_, _, _, mut mempool_listener, mut storage_service_listener, mut storage_synchronizer, _) =
+        create_storage_synchronizer_with_config(
+            driver_config,
+            PersistentMetadataStorage::new(db_path.path()),
+            chunk_executor,
+            create_mock_reader_writer(None, None),
+        );
+
+    // Attempt to apply a chunk of outputs
+    storage_synchronizer
+        .apply_transaction_outputs(
+            NotificationMetadata::new_for_test(0),
+            create_output_list_with_proof(),
+            create_epoch_ending_ledger_info(),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // Verify that the chunk was committed and that there's no pending data
+    verify_no_pending_data(&storage_synchronizer);
+
+    // Verify that mempool and the storage service were not notified
+    assert_none!(mempool_listener.select_next_some().now_or_never());
+    assert_none!(storage_service_listener.select_next_some().now_or_never());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_apply_outputs_commit_send_error() {
     // Create test data
@@ -330,8 +498,168 @@ async fn test_apply_outputs_commit_send_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::ChannelClosed(String::new()),
+    )
+    .await;
+    verify_no_pending_data(&storage_synchronizer);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_apply_outputs_mempool_notification_batching() {
+    // Setup the mock executor to commit two chunks (each with a single transaction)
+    let transactions_to_commit = vec![create_transaction(), create_transaction()];
+    let mut chunk_executor = create_mock_executor();
+    chunk_executor
+        .expect_enqueue_chunk_by_transaction_outputs()
+        .with(always(), always(), always())
+        .returning(|_, _, _| Ok(()));
+    chunk_executor.expect_update_ledger().returning(|| Ok(()));
+    let mut expectation_sequence = Sequence::new();
+    for transaction_to_commit in transactions_to_commit.clone() {
+        chunk_executor
+            .expect_commit_chunk()
+            .times(1)
+            .return_once(move || {
+                Ok(ChunkCommitNotification {
+                    subscribable_events: vec![],
+                    committed_transactions: vec![transaction_to_commit],
+                    reconfiguration_occurred: false,
+                })
+            })
+            .in_sequence(&mut expectation_sequence);
+    }
+
+    // Create a driver config that always batches two chunks per mempool notification
+    let driver_config = StateSyncDriverConfig {
+        mempool_notification_batch_size: 2,
+        mempool_notification_batching_lag: 0,
+        mempool_notification_max_delay_ms: TEST_TIMEOUT_SECS * 1000,
+        ..Default::default()
+    };
+
+    // Create the storage synchronizer
+    let highest_synced_version = 50;
+    let db_path = aptos_temppath::TempPath::new();
+    let (_, _, _, mut mempool_listener, mut storage_service_listener, mut storage_synchronizer, _) =
+        create_storage_synchronizer_with_config(
+            driver_config,
+            PersistentMetadataStorage::new(db_path.path()),
+            chunk_executor,
+            create_mock_reader_writer_with_version(None, None, highest_synced_version),
+        );
+
+    // Apply two chunks of outputs
+    for notification_id in 0..2 {
+        storage_synchronizer
+            .apply_transaction_outputs(
+                NotificationMetadata::new_for_test(notification_id),
+                create_output_list_with_proof(),
+                create_epoch_ending_ledger_info(),
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    // Verify that the storage service is notified of each chunk
+    for _ in 0..2 {
+        let storage_service_notification = timeout(
+            Duration::from_secs(TEST_TIMEOUT_SECS),
+            storage_service_listener.select_next_some(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            storage_service_notification.highest_synced_version,
+            highest_synced_version
+        );
+    }
+
+    // Verify that mempool is notified of both chunks in a single notification
+    let mempool_notification = timeout(
+        Duration::from_secs(TEST_TIMEOUT_SECS),
+        mempool_listener.select_next_some(),
+    )
+    .await
+    .unwrap();
+    assert_eq!(mempool_notification.transactions.len(), 2);
+    for (committed_transaction, transaction) in mempool_notification
+        .transactions
+        .iter()
+        .zip(transactions_to_commit)
+    {
+        assert_eq!(
+            committed_transaction.sender,
+            transaction.try_as_signed_user_txn().unwrap().sender()
+        );
+    }
+    verify_no_pending_data(&storage_synchronizer);
+    assert_none!(mempool_listener.select_next_some().now_or_never());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_apply_outputs_throttling() {
+    // Setup the mock executor to block until each chunk is released
+    let (release_chunk_sender, release_chunk_receiver) = std::sync::mpsc::channel();
+    let release_chunk_receiver = Arc::new(Mutex::new(release_chunk_receiver));
+    let mut chunk_executor = create_mock_executor();
+    chunk_executor
+        .expect_enqueue_chunk_by_transaction_outputs()
+        .with(always(), always(), always())
+        .returning(move |_, _, _| {
+            release_chunk_receiver.lock().recv().unwrap();
+            Ok(())
+        });
+    chunk_executor.expect_update_ledger().returning(|| Ok(()));
+    chunk_executor.expect_commit_chunk().returning(|| {
+        Ok(ChunkCommitNotification {
+            subscribable_events: vec![],
+            committed_transactions: vec![],
+            reconfiguration_occurred: false,
+        })
+    });
+
+    // Create a driver config with small watermarks
+    let driver_config = StateSyncDriverConfig {
+        pending_data_chunks_high_watermark: 3,
+        pending_data_chunks_low_watermark: 1,
+        ..Default::default()
+    };
+
+    // Create the storage synchronizer
+    let db_path = aptos_temppath::TempPath::new();
+    let (_, _, _, _, _, mut storage_synchronizer, _) = create_storage_synchronizer_with_config(
+        driver_config,
+        PersistentMetadataStorage::new(db_path.path()),
+        chunk_executor,
+        create_mock_reader_writer(None, None),
+    );
+
+    // Apply chunks of outputs and verify throttling starts at the high watermark
+    for notification_id in 0..3 {
+        assert!(!storage_synchronizer.should_throttle());
+        storage_synchronizer
+            .apply_transaction_outputs(
+                NotificationMetadata::new_for_test(notification_id),
+                create_output_list_with_proof(),
+                create_epoch_ending_ledger_info(),
+                None,
+            )
+            .await
+            .unwrap();
+    }
+    assert_eq!(storage_synchronizer.pending_data_chunk_count(), 3);
+    assert!(storage_synchronizer.should_throttle());
+
+    // Release all chunks and verify throttling stops once the data drains
+    for _ in 0..3 {
+        release_chunk_sender.send(()).unwrap();
+    }
     verify_no_pending_data(&storage_synchronizer);
+    assert!(!storage_synchronizer.should_throttle());
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -430,7 +758,12 @@ async fn test_execute_transactions_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::ExecutionFailure(String::new()),
+    )
+    .await;
     verify_no_pending_data(&storage_synchronizer);
 }
 
@@ -464,7 +797,12 @@ async fn test_execute_transactions_send_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::ChannelClosed(String::new()),
+    )
+    .await;
     verify_no_pending_data(&storage_synchronizer);
 }
 
@@ -497,7 +835,12 @@ async fn test_execute_transactions_update_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::ExecutionFailure(String::new()),
+    )
+    .await;
     verify_no_pending_data(&storage_synchronizer);
 }
 
@@ -532,7 +875,12 @@ async fn test_execute_transactions_update_send_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::ChannelClosed(String::new()),
+    )
+    .await;
     verify_no_pending_data(&storage_synchronizer);
 }
 
@@ -545,9 +893,11 @@ async fn test_execute_transactions_commit_error() {
         .with(always(), always(), always())
         .returning(|_, _, _| Ok(()));
     chunk_executor.expect_update_ledger().returning(|| Ok(()));
+    let max_commit_attempts = StateSyncDriverConfig::default().max_commit_retries as usize + 1;
     chunk_executor
         .expect_commit_chunk()
-        .return_once(|| Err(format_err!("Failed to commit chunk!")));
+        .times(max_commit_attempts)
+        .returning(|| Err(format_err!("Failed to commit chunk!")));
 
     // Create the storage synchronizer
     let (_, mut error_listener, _, _, _, mut storage_synchronizer, _) =
@@ -566,7 +916,12 @@ async fn test_execute_transactions_commit_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::StorageError(String::new()),
+    )
+    .await;
     verify_no_pending_data(&storage_synchronizer);
 }
 
@@ -618,8 +973,74 @@ async fn test_execute_transactions_commit_send_error() {
         .unwrap();
 
     // Verify we get an error notification and that there's no pending data
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::ChannelClosed(String::new()),
+    )
+    .await;
+    verify_no_pending_data(&storage_synchronizer);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_execute_transactions_chunk_executor_pool() {
+    // Setup the mock executor (and record the threads that execute chunks)
+    let execution_threads = Arc::new(Mutex::new(vec![]));
+    let mut chunk_executor = create_mock_executor();
+    let execution_threads_clone = execution_threads.clone();
+    chunk_executor
+        .expect_enqueue_chunk_by_execution()
+        .with(always(), always(), always())
+        .returning(move |_, _, _| {
+            let thread_name = std::thread::current().name().map(|name| name.to_string());
+            execution_threads_clone.lock().push(thread_name);
+            Ok(())
+        });
+    chunk_executor.expect_update_ledger().returning(|| Ok(()));
+    chunk_executor.expect_commit_chunk().returning(|| {
+        Ok(ChunkCommitNotification {
+            subscribable_events: vec![],
+            committed_transactions: vec![create_transaction()],
+            reconfiguration_occurred: false,
+        })
+    });
+
+    // Create the storage synchronizer with a dedicated chunk executor pool
+    let driver_config = StateSyncDriverConfig {
+        num_chunk_executor_threads: 2,
+        ..Default::default()
+    };
+    let db_path = aptos_temppath::TempPath::new();
+    let (_, _, _, _, _, mut storage_synchronizer, _) = create_storage_synchronizer_with_config(
+        driver_config,
+        PersistentMetadataStorage::new(db_path.path()),
+        chunk_executor,
+        create_mock_reader_writer(None, None),
+    );
+
+    // Execute several chunks of transactions
+    let num_chunks = 5;
+    for notification_id in 0..num_chunks {
+        storage_synchronizer
+            .execute_transactions(
+                NotificationMetadata::new_for_test(notification_id),
+                create_transaction_list_with_proof(),
+                create_epoch_ending_ledger_info(),
+                None,
+            )
+            .await
+            .unwrap();
+    }
+
+    // Verify that all chunks were committed and that there's no pending data
     verify_no_pending_data(&storage_synchronizer);
+
+    // Verify that all chunks were executed on the dedicated pool
+    let execution_threads = execution_threads.lock().clone();
+    assert_eq!(execution_threads.len(), num_chunks as usize);
+    for thread_name in execution_threads {
+        assert!(thread_name.unwrap().starts_with("chunk-exec"));
+    }
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -681,27 +1102,17 @@ async fn test_initialize_state_synchronizer_receiver_error() {
 }
 
 #[tokio::test(flavor = "multi_thread")]
-async fn test_save_states_completion() {
+async fn test_resume_state_synchronizer() {
     // Create test data
     let target_ledger_info = create_epoch_ending_ledger_info();
-    let epoch_change_proofs = [
-        create_epoch_ending_ledger_info(),
-        create_epoch_ending_ledger_info(),
-        target_ledger_info.clone(),
-    ];
-    let output_list_with_proof = create_output_list_with_proof();
+    let last_persisted_index = 500;
 
-    // Setup the mock snapshot receiver
+    // Setup the mock snapshot receiver (only the resumed chunk should be committed)
     let mut snapshot_receiver = create_mock_receiver();
     snapshot_receiver
         .expect_add_chunk()
-        .with(always(), always())
+        .times(1)
         .returning(|_, _| Ok(()));
-    snapshot_receiver.expect_finish_box().returning(|| Ok(()));
-
-    // Setup the mock executor
-    let mut chunk_executor = create_mock_executor();
-    chunk_executor.expect_reset().returning(|| Ok(()));
 
     // Setup the mock db writer
     let mut db_writer = create_mock_db_writer();
@@ -709,63 +1120,215 @@ async fn test_save_states_completion() {
         .expect_get_state_snapshot_receiver()
         .with(always(), always())
         .return_once(move |_, _| Ok(Box::new(snapshot_receiver)));
-    let target_ledger_info_clone = target_ledger_info.clone();
-    let output_list_with_proof_clone = output_list_with_proof.clone();
-    let epoch_change_proofs_clone = epoch_change_proofs.clone();
-    db_writer
-        .expect_finalize_state_snapshot()
-        .withf(
-            move |version: &Version,
-                  output_with_proof: &TransactionOutputListWithProof,
-                  ledger_infos: &[LedgerInfoWithSignatures]| {
-                version == &target_ledger_info_clone.ledger_info().version()
-                    && output_with_proof == &output_list_with_proof_clone
-                    && ledger_infos == epoch_change_proofs_clone
-            },
-        )
-        .returning(|_, _, _| Ok(()));
 
     // Create the storage synchronizer
-    let (mut commit_listener, _, _, _, _, mut storage_synchronizer, _) =
-        create_storage_synchronizer(
-            chunk_executor,
+    let db_path = aptos_temppath::TempPath::new();
+    let metadata_storage = PersistentMetadataStorage::new(db_path.path());
+    let (_, mut error_listener, _, _, _, mut storage_synchronizer, _) =
+        create_storage_synchronizer_with_config(
+            StateSyncDriverConfig::default(),
+            metadata_storage.clone(),
+            create_mock_executor(),
             create_mock_reader_writer(None, Some(db_writer)),
         );
 
-    // Subscribe to the expected event
-    let expected_event = output_list_with_proof.transactions_and_outputs[0]
-        .1
-        .events()[0]
-        .clone();
+    // Verify the state synchronizer can't be resumed without existing progress
+    let result = storage_synchronizer.resume_state_synchronizer(
+        vec![target_ledger_info.clone()],
+        target_ledger_info.clone(),
+        create_output_list_with_proof(),
+    );
+    assert_matches!(result, Err(Error::UnexpectedError(_)));
 
-    // Initialize the state synchronizer
-    let state_synchronizer_handle = storage_synchronizer
-        .initialize_state_synchronizer(
-            epoch_change_proofs.to_vec(),
+    // Persist some snapshot progress and resume the state synchronizer
+    metadata_storage
+        .update_last_persisted_state_value_index(&target_ledger_info, last_persisted_index, false)
+        .unwrap();
+    let (_, resumed_state_index) = storage_synchronizer
+        .resume_state_synchronizer(
+            vec![target_ledger_info.clone()],
             target_ledger_info,
-            output_list_with_proof.clone(),
+            create_output_list_with_proof(),
         )
         .unwrap();
+    assert_eq!(resumed_state_index, last_persisted_index);
 
-    // Save multiple state chunks (including the last chunk)
+    // Save a state chunk that doesn't continue from the resumed index and verify an error
+    let notification_id = 100;
     storage_synchronizer
-        .save_state_values(0, create_state_value_chunk_with_proof(false))
+        .save_state_values(notification_id, create_state_value_chunk_with_proof(false))
         .await
         .unwrap();
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::InvalidPayload(String::new()),
+    )
+    .await;
+
+    // Save a state chunk that continues from the resumed index
+    let mut state_value_chunk_with_proof = create_state_value_chunk_with_proof(false);
+    state_value_chunk_with_proof.first_index = last_persisted_index;
+    state_value_chunk_with_proof.last_index = last_persisted_index + 100;
     storage_synchronizer
-        .save_state_values(1, create_state_value_chunk_with_proof(true))
+        .save_state_values(notification_id + 1, state_value_chunk_with_proof)
         .await
         .unwrap();
 
-    // Verify we get a commit notification
-    let expected_transaction = output_list_with_proof.transactions_and_outputs[0].0.clone();
-    let expected_committed_transactions = CommittedTransactions {
-        events: vec![expected_event.clone()],
-        transactions: vec![expected_transaction.clone()],
-    };
-    verify_snapshot_commit_notification(
-        &mut commit_listener,
-        expected_committed_transactions.clone(),
+    // Verify there's no pending data (the chunk was committed)
+    verify_no_pending_data(&storage_synchronizer);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_state_synchronizer_with_filter() {
+    // Create test data
+    let target_ledger_info = create_epoch_ending_ledger_info();
+
+    // Setup the mock snapshot receiver (the value filter should be set)
+    let mut snapshot_receiver = create_mock_receiver();
+    snapshot_receiver
+        .expect_set_value_filter()
+        .times(1)
+        .returning(|_| Ok(()));
+
+    // Setup the mock db writer
+    let mut db_writer = create_mock_db_writer();
+    db_writer
+        .expect_get_state_snapshot_receiver()
+        .with(always(), always())
+        .return_once(move |_, _| Ok(Box::new(snapshot_receiver)));
+
+    // Create the storage synchronizer with a state snapshot filter
+    let db_path = aptos_temppath::TempPath::new();
+    let metadata_storage = PersistentMetadataStorage::new(db_path.path());
+    let (_, _, _, _, _, mut storage_synchronizer, _) = create_storage_synchronizer_with_config(
+        StateSyncDriverConfig::default(),
+        metadata_storage.clone(),
+        create_mock_executor(),
+        create_mock_reader_writer(None, Some(db_writer)),
+    );
+    let state_snapshot_filter = StateSnapshotFilter::new(&StateSnapshotFilterConfig {
+        account_allowlist: vec![AccountAddress::ONE],
+        ..Default::default()
+    });
+    storage_synchronizer.set_state_snapshot_filter(state_snapshot_filter.clone());
+
+    // Initialize the state synchronizer and verify the filter was recorded
+    let state_synchronizer_handle = storage_synchronizer
+        .initialize_state_synchronizer(
+            vec![target_ledger_info.clone()],
+            target_ledger_info.clone(),
+            create_output_list_with_proof(),
+        )
+        .unwrap();
+    assert_eq!(
+        metadata_storage.get_state_snapshot_filter().unwrap(),
+        state_snapshot_filter
+    );
+
+    // Verify the state synchronizer can't be resumed with a different filter
+    metadata_storage
+        .update_last_persisted_state_value_index(&target_ledger_info, 10, false)
+        .unwrap();
+    storage_synchronizer.set_state_snapshot_filter(None);
+    let result = storage_synchronizer.resume_state_synchronizer(
+        vec![target_ledger_info.clone()],
+        target_ledger_info,
+        create_output_list_with_proof(),
+    );
+    assert_matches!(result, Err(Error::UnexpectedError(_)));
+
+    // Drop the storage synchronizer and verify the receiver terminates cleanly
+    drop(storage_synchronizer);
+    state_synchronizer_handle.await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_save_states_completion() {
+    // Create test data
+    let target_ledger_info = create_epoch_ending_ledger_info();
+    let epoch_change_proofs = [
+        create_epoch_ending_ledger_info(),
+        create_epoch_ending_ledger_info(),
+        target_ledger_info.clone(),
+    ];
+    let output_list_with_proof = create_output_list_with_proof();
+
+    // Setup the mock snapshot receiver
+    let mut snapshot_receiver = create_mock_receiver();
+    snapshot_receiver
+        .expect_add_chunk()
+        .with(always(), always())
+        .returning(|_, _| Ok(()));
+    snapshot_receiver.expect_finish_box().returning(|| Ok(()));
+
+    // Setup the mock executor
+    let mut chunk_executor = create_mock_executor();
+    chunk_executor.expect_reset().returning(|| Ok(()));
+
+    // Setup the mock db writer
+    let mut db_writer = create_mock_db_writer();
+    db_writer
+        .expect_get_state_snapshot_receiver()
+        .with(always(), always())
+        .return_once(move |_, _| Ok(Box::new(snapshot_receiver)));
+    let target_ledger_info_clone = target_ledger_info.clone();
+    let output_list_with_proof_clone = output_list_with_proof.clone();
+    let epoch_change_proofs_clone = epoch_change_proofs.clone();
+    db_writer
+        .expect_finalize_state_snapshot()
+        .withf(
+            move |version: &Version,
+                  output_with_proof: &TransactionOutputListWithProof,
+                  ledger_infos: &[LedgerInfoWithSignatures]| {
+                version == &target_ledger_info_clone.ledger_info().version()
+                    && output_with_proof == &output_list_with_proof_clone
+                    && ledger_infos == epoch_change_proofs_clone
+            },
+        )
+        .returning(|_, _, _| Ok(()));
+
+    // Create the storage synchronizer
+    let (mut commit_listener, _, _, _, _, mut storage_synchronizer, _) =
+        create_storage_synchronizer(
+            chunk_executor,
+            create_mock_reader_writer(None, Some(db_writer)),
+        );
+
+    // Subscribe to the expected event
+    let expected_event = output_list_with_proof.transactions_and_outputs[0]
+        .1
+        .events()[0]
+        .clone();
+
+    // Initialize the state synchronizer
+    let state_synchronizer_handle = storage_synchronizer
+        .initialize_state_synchronizer(
+            epoch_change_proofs.to_vec(),
+            target_ledger_info,
+            output_list_with_proof.clone(),
+        )
+        .unwrap();
+
+    // Save multiple state chunks (including the last chunk)
+    storage_synchronizer
+        .save_state_values(0, create_state_value_chunk_with_proof(false))
+        .await
+        .unwrap();
+    storage_synchronizer
+        .save_state_values(1, create_state_value_chunk_with_proof(true))
+        .await
+        .unwrap();
+
+    // Verify we get a commit notification
+    let expected_transaction = output_list_with_proof.transactions_and_outputs[0].0.clone();
+    let expected_committed_transactions = CommittedTransactions {
+        events: vec![expected_event.clone()],
+        transactions: vec![expected_transaction.clone()],
+    };
+    verify_snapshot_commit_notification(
+        &mut commit_listener,
+        expected_committed_transactions.clone(),
     )
     .await;
 
@@ -774,6 +1337,163 @@ async fn test_save_states_completion() {
     verify_no_pending_data(&storage_synchronizer);
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_save_states_completion_reset_retry() {
+    // Create test data
+    let target_ledger_info = create_epoch_ending_ledger_info();
+    let output_list_with_proof = create_output_list_with_proof();
+
+    // Setup the mock snapshot receiver
+    let mut snapshot_receiver = create_mock_receiver();
+    snapshot_receiver
+        .expect_add_chunk()
+        .with(always(), always())
+        .returning(|_, _| Ok(()));
+    snapshot_receiver.expect_finish_box().returning(|| Ok(()));
+
+    // Setup the mock executor to fail the first reset (e.g., a transient IO failure)
+    let mut chunk_executor = create_mock_executor();
+    let mut expectation_sequence = Sequence::new();
+    chunk_executor
+        .expect_reset()
+        .times(1)
+        .returning(|| Err(format_err!("Transient IO failure!")))
+        .in_sequence(&mut expectation_sequence);
+    chunk_executor
+        .expect_reset()
+        .times(1)
+        .returning(|| Ok(()))
+        .in_sequence(&mut expectation_sequence);
+
+    // Setup the mock db writer
+    let mut db_writer = create_mock_db_writer();
+    db_writer
+        .expect_get_state_snapshot_receiver()
+        .with(always(), always())
+        .return_once(move |_, _| Ok(Box::new(snapshot_receiver)));
+    db_writer
+        .expect_finalize_state_snapshot()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+
+    // Create the storage synchronizer (storage IO failures are retried by default)
+    let (mut commit_listener, mut error_listener, _, _, _, mut storage_synchronizer, _) =
+        create_storage_synchronizer(
+            chunk_executor,
+            create_mock_reader_writer(None, Some(db_writer)),
+        );
+
+    // Initialize the state synchronizer and save the last state chunk
+    let state_synchronizer_handle = storage_synchronizer
+        .initialize_state_synchronizer(
+            vec![target_ledger_info.clone()],
+            target_ledger_info,
+            output_list_with_proof.clone(),
+        )
+        .unwrap();
+    storage_synchronizer
+        .save_state_values(0, create_state_value_chunk_with_proof(true))
+        .await
+        .unwrap();
+
+    // Verify we get a commit notification (the reset was retried)
+    let expected_committed_transactions = CommittedTransactions {
+        events: vec![output_list_with_proof.transactions_and_outputs[0]
+            .1
+            .events()[0]
+            .clone()],
+        transactions: vec![output_list_with_proof.transactions_and_outputs[0].0.clone()],
+    };
+    verify_snapshot_commit_notification(&mut commit_listener, expected_committed_transactions)
+        .await;
+
+    // Verify no error notification was sent
+    state_synchronizer_handle.await.unwrap();
+    assert_none!(error_listener.select_next_some().now_or_never());
+    verify_no_pending_data(&storage_synchronizer);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_save_states_completion_commit_workers() {
+    // Create test data
+    let target_ledger_info = create_epoch_ending_ledger_info();
+    let output_list_with_proof = create_output_list_with_proof();
+
+    // Setup the mock snapshot receiver (the value hashes should be precomputed)
+    let num_state_chunks = 10;
+    let mut snapshot_receiver = create_mock_receiver();
+    snapshot_receiver
+        .expect_add_chunk_with_value_hashes()
+        .withf(|chunk, value_hashes, _| chunk.len() == value_hashes.len())
+        .times(num_state_chunks)
+        .returning(|_, _, _| Ok(()));
+    snapshot_receiver.expect_finish_box().returning(|| Ok(()));
+
+    // Setup the mock executor
+    let mut chunk_executor = create_mock_executor();
+    chunk_executor.expect_reset().returning(|| Ok(()));
+
+    // Setup the mock db writer
+    let mut db_writer = create_mock_db_writer();
+    db_writer
+        .expect_get_state_snapshot_receiver()
+        .with(always(), always())
+        .return_once(move |_, _| Ok(Box::new(snapshot_receiver)));
+    db_writer
+        .expect_finalize_state_snapshot()
+        .returning(|_, _, _| Ok(()));
+
+    // Create the storage synchronizer with multiple commit workers
+    let driver_config = StateSyncDriverConfig {
+        max_state_commit_workers: 4,
+        ..Default::default()
+    };
+    let db_path = aptos_temppath::TempPath::new();
+    let (mut commit_listener, _, _, _, _, mut storage_synchronizer, _) =
+        create_storage_synchronizer_with_config(
+            driver_config,
+            PersistentMetadataStorage::new(db_path.path()),
+            chunk_executor,
+            create_mock_reader_writer(None, Some(db_writer)),
+        );
+
+    // Initialize the state synchronizer
+    let state_synchronizer_handle = storage_synchronizer
+        .initialize_state_synchronizer(
+            vec![target_ledger_info.clone()],
+            target_ledger_info,
+            output_list_with_proof.clone(),
+        )
+        .unwrap();
+
+    // Save multiple state chunks (including the last chunk)
+    for notification_id in 0..num_state_chunks {
+        let last_chunk = notification_id == num_state_chunks - 1;
+        storage_synchronizer
+            .save_state_values(
+                notification_id as NotificationId,
+                create_state_value_chunk_with_proof(last_chunk),
+            )
+            .await
+            .unwrap();
+    }
+
+    // Verify we get a commit notification
+    let expected_committed_transactions = CommittedTransactions {
+        events: output_list_with_proof.transactions_and_outputs[0]
+            .1
+            .events()
+            .to_vec(),
+        transactions: vec![output_list_with_proof.transactions_and_outputs[0].0.clone()],
+    };
+    verify_snapshot_commit_notification(&mut commit_listener, expected_committed_transactions)
+        .await;
+
+    // The handler should return as we've finished writing all states
+    state_synchronizer_handle.await.unwrap();
+    verify_no_pending_data(&storage_synchronizer);
+}
+
 #[tokio::test(flavor = "multi_thread")]
 #[should_panic]
 async fn test_save_states_dropped_error_listener() {
@@ -854,7 +1574,218 @@ async fn test_save_states_invalid_chunk() {
         .save_state_values(notification_id, create_state_value_chunk_with_proof(false))
         .await
         .unwrap();
-    verify_error_notification(&mut error_listener, notification_id).await;
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::StorageError(String::new()),
+    )
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_save_states_verification_error() {
+    // Setup the mock snapshot receiver (no chunks should be committed)
+    let mut snapshot_receiver = create_mock_receiver();
+    snapshot_receiver.expect_add_chunk().never();
+    snapshot_receiver
+        .expect_add_chunk_with_value_hashes()
+        .never();
+
+    // Setup the mock db writer
+    let mut db_writer = create_mock_db_writer();
+    db_writer
+        .expect_get_state_snapshot_receiver()
+        .with(always(), always())
+        .return_once(move |_, _| Ok(Box::new(snapshot_receiver)));
+
+    // Create the storage synchronizer (with the state value verifiers enabled)
+    let driver_config = StateSyncDriverConfig {
+        state_value_verifier_threads: 2,
+        ..Default::default()
+    };
+    let db_path = aptos_temppath::TempPath::new();
+    let (_, mut error_listener, _, _, _, mut storage_synchronizer, _) =
+        create_storage_synchronizer_with_config(
+            driver_config,
+            PersistentMetadataStorage::new(db_path.path()),
+            create_mock_executor(),
+            create_mock_reader_writer(None, Some(db_writer)),
+        );
+
+    // Initialize the state synchronizer
+    let _join_handle = storage_synchronizer
+        .initialize_state_synchronizer(
+            vec![create_epoch_ending_ledger_info()],
+            create_epoch_ending_ledger_info(),
+            create_output_list_with_proof(),
+        )
+        .unwrap();
+
+    // Create a state value chunk where the hashed state keys are not increasing
+    let mut raw_values: Vec<_> = (0..10u8)
+        .map(|index| {
+            (
+                StateKey::raw(vec![index]),
+                StateValue::new_legacy(vec![index].into()),
+            )
+        })
+        .collect();
+    raw_values.sort_by_key(|(state_key, _)| Reverse(state_key.hash()));
+    let mut state_value_chunk_with_proof = create_state_value_chunk_with_proof(false);
+    state_value_chunk_with_proof.first_key = raw_values.first().unwrap().0.hash();
+    state_value_chunk_with_proof.last_key = raw_values.last().unwrap().0.hash();
+    state_value_chunk_with_proof.raw_values = raw_values;
+
+    // Save the state chunk and verify we get an error notification
+    let notification_id = 0;
+    storage_synchronizer
+        .save_state_values(notification_id, state_value_chunk_with_proof)
+        .await
+        .unwrap();
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::VerificationError(String::new()),
+    )
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_save_states_verify_epoch_proofs() {
+    // Create test data (where the target ends the epoch at the waypoint)
+    let target_ledger_info = create_epoch_ending_ledger_info_for_epoch(0, 10);
+    let waypoint = Waypoint::new_any(target_ledger_info.ledger_info());
+    let output_list_with_proof = create_output_list_with_proof();
+
+    // Setup the mock snapshot receiver
+    let mut snapshot_receiver = create_mock_receiver();
+    snapshot_receiver
+        .expect_add_chunk()
+        .with(always(), always())
+        .returning(|_, _| Ok(()));
+    snapshot_receiver.expect_finish_box().returning(|| Ok(()));
+
+    // Setup the mock executor
+    let mut chunk_executor = create_mock_executor();
+    chunk_executor.expect_reset().returning(|| Ok(()));
+
+    // Setup the mock db writer (the snapshot should be finalized)
+    let mut db_writer = create_mock_db_writer();
+    db_writer
+        .expect_get_state_snapshot_receiver()
+        .with(always(), always())
+        .return_once(move |_, _| Ok(Box::new(snapshot_receiver)));
+    db_writer
+        .expect_finalize_state_snapshot()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+
+    // Create the storage synchronizer (with epoch proof verification enabled)
+    let driver_config = StateSyncDriverConfig {
+        verify_epoch_proofs_on_commit: true,
+        ..Default::default()
+    };
+    let db_path = aptos_temppath::TempPath::new();
+    let (mut commit_listener, _, _, _, _, mut storage_synchronizer, _) =
+        create_storage_synchronizer_with_fault_injector(
+            driver_config,
+            PersistentMetadataStorage::new(db_path.path()),
+            chunk_executor,
+            create_mock_reader_writer(None, Some(db_writer)),
+            waypoint,
+            None,
+        );
+
+    // Initialize the state synchronizer
+    let state_synchronizer_handle = storage_synchronizer
+        .initialize_state_synchronizer(
+            vec![target_ledger_info.clone()],
+            target_ledger_info,
+            output_list_with_proof.clone(),
+        )
+        .unwrap();
+
+    // Save the last state chunk
+    storage_synchronizer
+        .save_state_values(0, create_state_value_chunk_with_proof(true))
+        .await
+        .unwrap();
+
+    // Verify we get a commit notification
+    let expected_committed_transactions = CommittedTransactions {
+        events: output_list_with_proof.transactions_and_outputs[0]
+            .1
+            .events()
+            .to_vec(),
+        transactions: vec![output_list_with_proof.transactions_and_outputs[0].0.clone()],
+    };
+    verify_snapshot_commit_notification(&mut commit_listener, expected_committed_transactions)
+        .await;
+
+    // The handler should return as we've finished writing all states
+    state_synchronizer_handle.await.unwrap();
+    verify_no_pending_data(&storage_synchronizer);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_save_states_verify_epoch_proofs_error() {
+    // Create test data (where the waypoint doesn't match the epoch change proofs)
+    let target_ledger_info = create_epoch_ending_ledger_info_for_epoch(0, 10);
+    let waypoint = Waypoint::new_any(create_ledger_info_at_version(10).ledger_info());
+
+    // Setup the mock snapshot receiver
+    let mut snapshot_receiver = create_mock_receiver();
+    snapshot_receiver
+        .expect_add_chunk()
+        .with(always(), always())
+        .returning(|_, _| Ok(()));
+    snapshot_receiver.expect_finish_box().returning(|| Ok(()));
+
+    // Setup the mock db writer (the snapshot should never be finalized)
+    let mut db_writer = create_mock_db_writer();
+    db_writer
+        .expect_get_state_snapshot_receiver()
+        .with(always(), always())
+        .return_once(move |_, _| Ok(Box::new(snapshot_receiver)));
+    db_writer.expect_finalize_state_snapshot().never();
+
+    // Create the storage synchronizer (with epoch proof verification enabled)
+    let driver_config = StateSyncDriverConfig {
+        verify_epoch_proofs_on_commit: true,
+        ..Default::default()
+    };
+    let db_path = aptos_temppath::TempPath::new();
+    let (_, mut error_listener, _, _, _, mut storage_synchronizer, _) =
+        create_storage_synchronizer_with_fault_injector(
+            driver_config,
+            PersistentMetadataStorage::new(db_path.path()),
+            create_mock_executor(),
+            create_mock_reader_writer(None, Some(db_writer)),
+            waypoint,
+            None,
+        );
+
+    // Initialize the state synchronizer
+    let _join_handle = storage_synchronizer
+        .initialize_state_synchronizer(
+            vec![target_ledger_info.clone()],
+            target_ledger_info,
+            create_output_list_with_proof(),
+        )
+        .unwrap();
+
+    // Save the last state chunk and verify we get an error notification
+    let notification_id = 0;
+    storage_synchronizer
+        .save_state_values(notification_id, create_state_value_chunk_with_proof(true))
+        .await
+        .unwrap();
+    verify_error_notification(
+        &mut error_listener,
+        notification_id,
+        Error::VerificationError(String::new()),
+    )
+    .await;
 }
 
 #[tokio::test]
@@ -874,6 +1805,233 @@ async fn test_save_states_without_initialize() {
         .unwrap();
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fault_injection_delayed_sends() {
+    // Create a fault injector that delays a message on every pipeline channel
+    let fault_injector = PipelineFaultInjector::new();
+    for (message_index, channel) in [
+        PipelineChannel::ExecutorToLedgerUpdater,
+        PipelineChannel::LedgerUpdaterToCommitter,
+        PipelineChannel::CommitterToCommitPostProcessor,
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        fault_injector.inject_fault(
+            channel,
+            message_index as u64,
+            PipelineFault::DelaySend(Duration::from_millis(200)),
+        );
+    }
+
+    // Create the storage synchronizer
+    let (mut error_listener, mut storage_synchronizer) =
+        create_fault_injected_storage_synchronizer(&fault_injector);
+
+    // Apply several chunks of outputs
+    let num_chunks = 5;
+    for notification_id in 0..num_chunks {
+        apply_output_chunk(&mut storage_synchronizer, notification_id).await;
+    }
+
+    // Verify that all chunks are committed and that no invariants are violated
+    verify_pipeline_invariants(
+        &fault_injector,
+        &mut error_listener,
+        &storage_synchronizer,
+        num_chunks,
+        vec![],
+    )
+    .await;
+    for channel in [
+        PipelineChannel::ExecutorToLedgerUpdater,
+        PipelineChannel::LedgerUpdaterToCommitter,
+        PipelineChannel::CommitterToCommitPostProcessor,
+    ] {
+        let channel_stats = fault_injector.get_channel_stats(channel);
+        assert_eq!(channel_stats.forwarded, num_chunks);
+        assert_eq!(channel_stats.dropped, 0);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fault_injection_dropped_message() {
+    // Create a fault injector that drops a message sent to the committer
+    let fault_injector = PipelineFaultInjector::new();
+    fault_injector.inject_fault(
+        PipelineChannel::LedgerUpdaterToCommitter,
+        1,
+        PipelineFault::DropMessage,
+    );
+
+    // Create the storage synchronizer
+    let (mut error_listener, mut storage_synchronizer) =
+        create_fault_injected_storage_synchronizer(&fault_injector);
+
+    // Apply several chunks of outputs
+    let num_chunks = 3;
+    for notification_id in 0..num_chunks {
+        apply_output_chunk(&mut storage_synchronizer, notification_id).await;
+    }
+
+    // Verify that the lost chunk is detected (and that the pending counter is accurate)
+    verify_pipeline_invariants(
+        &fault_injector,
+        &mut error_listener,
+        &storage_synchronizer,
+        num_chunks,
+        vec![PipelineInvariantViolation::LostNotifications { num_lost_chunks: 1 }],
+    )
+    .await;
+    let channel_stats = fault_injector.get_channel_stats(PipelineChannel::LedgerUpdaterToCommitter);
+    assert_eq!(channel_stats.received, num_chunks);
+    assert_eq!(channel_stats.dropped, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_fault_injection_panic() {
+    // Create a fault injector that panics on a message sent to the ledger updater
+    let fault_injector = PipelineFaultInjector::new();
+    fault_injector.inject_fault(
+        PipelineChannel::ExecutorToLedgerUpdater,
+        1,
+        PipelineFault::Panic,
+    );
+
+    // Create the storage synchronizer
+    let (mut error_listener, mut storage_synchronizer) =
+        create_fault_injected_storage_synchronizer(&fault_injector);
+
+    // Apply two chunks of outputs and wait for the injected panic
+    apply_output_chunk(&mut storage_synchronizer, 0).await;
+    apply_output_chunk(&mut storage_synchronizer, 1).await;
+    timeout(Duration::from_secs(TEST_TIMEOUT_SECS), async {
+        while fault_injector
+            .get_channel_stats(PipelineChannel::ExecutorToLedgerUpdater)
+            .received
+            < 2
+        {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap();
+
+    // Apply another chunk (which should fail to reach the ledger updater)
+    apply_output_chunk(&mut storage_synchronizer, 2).await;
+
+    // Verify that the chunk lost in the panic is detected
+    verify_pipeline_invariants(
+        &fault_injector,
+        &mut error_listener,
+        &storage_synchronizer,
+        3,
+        vec![PipelineInvariantViolation::LostNotifications { num_lost_chunks: 1 }],
+    )
+    .await;
+    let channel_stats =
+        fault_injector.get_channel_stats(PipelineChannel::CommitterToCommitPostProcessor);
+    assert_eq!(channel_stats.forwarded, 1);
+}
+
+/// Applies a single chunk of outputs using the given storage synchronizer
+async fn apply_output_chunk(
+    storage_synchronizer: &mut StorageSynchronizer<MockChunkExecutor, PersistentMetadataStorage>,
+    notification_id: NotificationId,
+) {
+    storage_synchronizer
+        .apply_transaction_outputs(
+            NotificationMetadata::new_for_test(notification_id),
+            create_output_list_with_proof(),
+            create_epoch_ending_ledger_info(),
+            None,
+        )
+        .await
+        .unwrap();
+}
+
+/// Creates a storage synchronizer (with a mock executor that always succeeds)
+/// where all pipeline channels are interposed by the given fault injector.
+fn create_fault_injected_storage_synchronizer(
+    fault_injector: &PipelineFaultInjector,
+) -> (
+    ErrorNotificationListener,
+    StorageSynchronizer<MockChunkExecutor, PersistentMetadataStorage>,
+) {
+    // Setup the mock executor
+    let mut chunk_executor = create_mock_executor();
+    chunk_executor
+        .expect_enqueue_chunk_by_transaction_outputs()
+        .with(always(), always(), always())
+        .returning(|_, _, _| Ok(()));
+    chunk_executor.expect_update_ledger().returning(|| Ok(()));
+    chunk_executor.expect_commit_chunk().returning(|| {
+        Ok(ChunkCommitNotification {
+            subscribable_events: vec![],
+            committed_transactions: vec![],
+            reconfiguration_occurred: false,
+        })
+    });
+
+    // Create the storage synchronizer with all commit notifications disabled
+    let driver_config = StateSyncDriverConfig {
+        commit_post_processor_config: CommitPostProcessorConfig {
+            notify_event_subscribers: false,
+            notify_mempool: false,
+            notify_storage_service: false,
+        },
+        ..Default::default()
+    };
+    let db_path = aptos_temppath::TempPath::new();
+    let (_, error_listener, _, _, _, storage_synchronizer, _) =
+        create_storage_synchronizer_with_fault_injector(
+            driver_config,
+            PersistentMetadataStorage::new(db_path.path()),
+            chunk_executor,
+            create_mock_reader_writer(None, None),
+            Waypoint::default(),
+            Some(fault_injector),
+        );
+
+    (error_listener, storage_synchronizer)
+}
+
+/// Verifies that the pipeline invariant violations (once the pipeline
+/// has drained) match the expected violations.
+async fn verify_pipeline_invariants(
+    fault_injector: &PipelineFaultInjector,
+    error_listener: &mut ErrorNotificationListener,
+    storage_synchronizer: &StorageSynchronizer<MockChunkExecutor, PersistentMetadataStorage>,
+    chunks_sent: u64,
+    expected_violations: Vec<PipelineInvariantViolation>,
+) {
+    let mut observations = PipelineObservations {
+        chunks_sent,
+        ..Default::default()
+    };
+    let max_drain_time_secs = 10;
+    for _ in 0..max_drain_time_secs * 10 {
+        // Collect the error notifications
+        while let Some(Some(_)) = error_listener.next().now_or_never() {
+            observations.error_notifications += 1;
+        }
+
+        // Check the pipeline invariants
+        observations.chunks_committed = fault_injector
+            .get_channel_stats(PipelineChannel::CommitterToCommitPostProcessor)
+            .forwarded;
+        observations.pending_data_chunks = storage_synchronizer.pending_data_chunk_count();
+        if check_pipeline_invariants(&observations) == expected_violations {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    panic!(
+        "Unexpected pipeline invariant violations! Observations: {:?}",
+        observations
+    );
+}
+
 /// Creates a storage synchronizer for testing
 fn create_storage_synchronizer(
     mock_chunk_executor: MockChunkExecutor,
@@ -886,6 +2044,60 @@ fn create_storage_synchronizer(
     StorageServiceNotificationListener,
     StorageSynchronizer<MockChunkExecutor, PersistentMetadataStorage>,
     StorageSynchronizerHandles,
+) {
+    let db_path = aptos_temppath::TempPath::new();
+    create_storage_synchronizer_with_config(
+        StateSyncDriverConfig::default(),
+        PersistentMetadataStorage::new(db_path.path()),
+        mock_chunk_executor,
+        mock_reader_writer,
+    )
+}
+
+/// Creates a storage synchronizer for testing with the given
+/// driver config and metadata storage.
+fn create_storage_synchronizer_with_config(
+    driver_config: StateSyncDriverConfig,
+    metadata_storage: PersistentMetadataStorage,
+    mock_chunk_executor: MockChunkExecutor,
+    mock_reader_writer: DbReaderWriter,
+) -> (
+    CommitNotificationListener,
+    ErrorNotificationListener,
+    Arc<Mutex<EventSubscriptionService>>,
+    MempoolNotificationListener,
+    StorageServiceNotificationListener,
+    StorageSynchronizer<MockChunkExecutor, PersistentMetadataStorage>,
+    StorageSynchronizerHandles,
+) {
+    create_storage_synchronizer_with_fault_injector(
+        driver_config,
+        metadata_storage,
+        mock_chunk_executor,
+        mock_reader_writer,
+        Waypoint::default(),
+        None,
+    )
+}
+
+/// Creates a storage synchronizer for testing with the given driver config,
+/// metadata storage and waypoint. If a fault injector is specified, all channels
+/// between the pipeline stages are interposed by the fault injector.
+fn create_storage_synchronizer_with_fault_injector(
+    driver_config: StateSyncDriverConfig,
+    metadata_storage: PersistentMetadataStorage,
+    mock_chunk_executor: MockChunkExecutor,
+    mock_reader_writer: DbReaderWriter,
+    waypoint: Waypoint,
+    fault_injector: Option<&PipelineFaultInjector>,
+) -> (
+    CommitNotificationListener,
+    ErrorNotificationListener,
+    Arc<Mutex<EventSubscriptionService>>,
+    MempoolNotificationListener,
+    StorageServiceNotificationListener,
+    StorageSynchronizer<MockChunkExecutor, PersistentMetadataStorage>,
+    StorageSynchronizerHandles,
 ) {
     aptos_logger::Logger::init_for_testing();
 
@@ -910,23 +2122,38 @@ fn create_storage_synchronizer(
     let storage_service_notification_handler =
         StorageServiceNotificationHandler::new(storage_service_notifier);
 
-    // Create the metadata storage
-    let db_path = aptos_temppath::TempPath::new();
-    let metadata_storage = PersistentMetadataStorage::new(db_path.path());
-
     // Create the storage synchronizer
-    let (storage_synchronizer, storage_synchronizer_handles) = StorageSynchronizer::new(
-        StateSyncDriverConfig::default(),
-        Arc::new(mock_chunk_executor),
-        commit_notification_sender,
-        error_notification_sender,
-        event_subscription_service.clone(),
-        mempool_notification_handler,
-        storage_service_notification_handler,
-        metadata_storage,
-        mock_reader_writer,
-        None,
-    );
+    let (storage_synchronizer, storage_synchronizer_handles) = match fault_injector {
+        Some(fault_injector) => StorageSynchronizer::new_with_fault_injector(
+            driver_config,
+            Arc::new(mock_chunk_executor),
+            commit_notification_sender,
+            error_notification_sender,
+            event_subscription_service.clone(),
+            mempool_notification_handler,
+            storage_service_notification_handler,
+            metadata_storage,
+            mock_reader_writer,
+            SyncProgressReporter::new(TimeService::mock()),
+            waypoint,
+            None,
+            fault_injector,
+        ),
+        None => StorageSynchronizer::new(
+            driver_config,
+            Arc::new(mock_chunk_executor),
+            commit_notification_sender,
+            error_notification_sender,
+            event_subscription_service.clone(),
+            mempool_notification_handler,
+            storage_service_notification_handler,
+            metadata_storage,
+            mock_reader_writer,
+            SyncProgressReporter::new(TimeService::mock()),
+            waypoint,
+            None,
+        ),
+    };
 
     (
         commit_notification_listener,
@@ -960,6 +2187,7 @@ async fn verify_snapshot_commit_notification(
 async fn verify_error_notification(
     error_listener: &mut ErrorNotificationListener,
     expected_notification_id: NotificationId,
+    expected_error: Error,
 ) {
     let error_notification = timeout(
         Duration::from_secs(TEST_TIMEOUT_SECS),
@@ -968,7 +2196,10 @@ async fn verify_error_notification(
     .await
     .unwrap();
     assert_eq!(error_notification.notification_id, expected_notification_id);
-    assert_matches!(error_notification.error, Error::UnexpectedError(_));
+    assert_eq!(
+        error_notification.error.get_label(),
+        expected_error.get_label()
+    );
 }
 
 /// Verifies that no pending data remains in the storage synchronizer.
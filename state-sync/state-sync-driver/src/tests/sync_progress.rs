@@ -0,0 +1,76 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::sync_progress::{SyncProgress, SyncProgressReporter};
+use aptos_time_service::TimeService;
+use std::time::Duration;
+
+#[test]
+fn test_sync_progress_reporter() {
+    // Create a sync progress reporter
+    let time_service = TimeService::mock();
+    let sync_progress_reporter = SyncProgressReporter::new(time_service.clone());
+
+    // Verify that no progress is reported initially
+    assert_eq!(
+        sync_progress_reporter.get_sync_progress(),
+        SyncProgress::default()
+    );
+
+    // Update the synced and highest known versions
+    sync_progress_reporter.update_synced_version(100);
+    sync_progress_reporter.update_highest_known_version(1100);
+    sync_progress_reporter.update_highest_known_version(500); // Lower versions are ignored
+
+    // Verify that no throughput or ETA is reported with a single sample
+    let sync_progress = sync_progress_reporter.get_sync_progress();
+    assert_eq!(sync_progress.synced_version, Some(100));
+    assert_eq!(sync_progress.highest_known_version, Some(1100));
+    assert_eq!(sync_progress.transactions_per_second, None);
+    assert_eq!(sync_progress.estimated_seconds_remaining, None);
+
+    // Sync 100 transactions per second for 5 seconds
+    let mock_time_service = time_service.into_mock();
+    for i in 1..=5 {
+        mock_time_service.advance(Duration::from_secs(1));
+        sync_progress_reporter.update_synced_version(100 + (i * 100));
+    }
+
+    // Verify the throughput and ETA
+    let sync_progress = sync_progress_reporter.get_sync_progress();
+    assert_eq!(sync_progress.synced_version, Some(600));
+    assert_eq!(sync_progress.transactions_per_second, Some(100.0));
+    assert_eq!(sync_progress.estimated_seconds_remaining, Some(5));
+
+    // Update the state snapshot index and verify the progress
+    sync_progress_reporter.update_state_snapshot_index(10);
+    assert_eq!(
+        sync_progress_reporter
+            .get_sync_progress()
+            .state_snapshot_index,
+        Some(10)
+    );
+}
+
+#[test]
+fn test_sync_progress_throughput_window() {
+    // Create a sync progress reporter
+    let time_service = TimeService::mock();
+    let sync_progress_reporter = SyncProgressReporter::new(time_service.clone());
+    sync_progress_reporter.update_highest_known_version(10_000);
+
+    // Sync quickly for a short time, then slowly for a long time
+    let mock_time_service = time_service.into_mock();
+    sync_progress_reporter.update_synced_version(0);
+    mock_time_service.advance(Duration::from_secs(1));
+    sync_progress_reporter.update_synced_version(5000);
+    for i in 1..=100 {
+        mock_time_service.advance(Duration::from_secs(1));
+        sync_progress_reporter.update_synced_version(5000 + i);
+    }
+
+    // Verify that only the recent (slow) samples are used for the throughput
+    let sync_progress = sync_progress_reporter.get_sync_progress();
+    assert_eq!(sync_progress.transactions_per_second, Some(1.0));
+    assert_eq!(sync_progress.estimated_seconds_remaining, Some(4900));
+}
@@ -88,6 +88,7 @@ pub fn create_full_node_driver_configuration() -> DriverConfiguration {
 
     DriverConfiguration {
         config,
+        local_backup_dir: None,
         role,
         waypoint,
     }
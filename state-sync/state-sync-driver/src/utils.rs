@@ -13,10 +13,11 @@ use crate::{
     },
     storage_synchronizer::{NotificationMetadata, StorageSynchronizerInterface},
 };
+use aptos_config::config::CommitPostProcessorConfig;
 use aptos_data_streaming_service::{
     data_notification::DataNotification,
     data_stream::{DataStreamId, DataStreamListener},
-    streaming_client::{DataStreamingClient, NotificationAndFeedback},
+    streaming_client::{DataStreamingClient, NotificationAndFeedback, NotificationFeedback},
 };
 use aptos_event_notifications::EventSubscriptionService;
 use aptos_infallible::Mutex;
@@ -32,6 +33,7 @@ use aptos_types::{
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use futures::StreamExt;
+use rand::Rng;
 use std::{
     sync::Arc,
     time::{Duration, Instant},
@@ -238,6 +240,16 @@ pub async fn get_data_notification(
     }
 }
 
+/// Returns true iff the given feedback indicates that the stream is being
+/// terminated due to a sync error (i.e., not simply because the stream ended).
+pub fn is_sync_error_feedback(notification_and_feedback: &Option<NotificationAndFeedback>) -> bool {
+    notification_and_feedback
+        .as_ref()
+        .map_or(false, |notification_and_feedback| {
+            notification_and_feedback.notification_feedback != NotificationFeedback::EndOfStream
+        })
+}
+
 /// Terminates the stream with the provided notification ID and feedback
 pub async fn terminate_stream_with_feedback<StreamingClient: DataStreamingClient + Clone>(
     streaming_client: &mut StreamingClient,
@@ -325,6 +337,7 @@ pub async fn handle_committed_transactions<
     mempool_notification_handler: MempoolNotificationHandler<M>,
     event_subscription_service: Arc<Mutex<EventSubscriptionService>>,
     storage_service_notification_handler: StorageServiceNotificationHandler<S>,
+    commit_post_processor_config: CommitPostProcessorConfig,
 ) {
     // Fetch the latest synced version and ledger info from storage
     let (latest_synced_version, latest_synced_ledger_info) =
@@ -355,6 +368,7 @@ pub async fn handle_committed_transactions<
         mempool_notification_handler,
         event_subscription_service,
         storage_service_notification_handler,
+        commit_post_processor_config,
     )
     .await
     {
@@ -417,3 +431,34 @@ pub async fn apply_transaction_outputs<StorageSyncer: StorageSynchronizerInterfa
         .await?;
     Ok(num_transaction_outputs)
 }
+
+/// Re-executes the transactions in the given list of transaction outputs
+/// (to spot verify the outputs) and returns the number of outputs in the list.
+pub async fn verify_transaction_outputs_by_execution<
+    StorageSyncer: StorageSynchronizerInterface + Clone,
+>(
+    mut storage_synchronizer: StorageSyncer,
+    notification_metadata: NotificationMetadata,
+    proof_ledger_info: LedgerInfoWithSignatures,
+    end_of_epoch_ledger_info: Option<LedgerInfoWithSignatures>,
+    transaction_outputs_with_proof: TransactionOutputListWithProof,
+) -> Result<usize, Error> {
+    let num_transaction_outputs = transaction_outputs_with_proof
+        .transactions_and_outputs
+        .len();
+    storage_synchronizer
+        .verify_transaction_outputs_by_execution(
+            notification_metadata,
+            transaction_outputs_with_proof,
+            proof_ledger_info,
+            end_of_epoch_ledger_info,
+        )
+        .await?;
+    Ok(num_transaction_outputs)
+}
+
+/// Returns true iff the next chunk should be spot verified (i.e.,
+/// re-executed) given the sample rate (as a percentage).
+pub fn should_spot_verify_chunk(sample_rate: u64) -> bool {
+    sample_rate > 0 && rand::thread_rng().gen_range(0..100) < sample_rate
+}
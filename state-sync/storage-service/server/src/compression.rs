@@ -0,0 +1,222 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    logging::{LogEntry, LogSchema},
+    metrics,
+};
+use aptos_compression::codec::CompressionCodec;
+use aptos_config::{config::StorageServiceCompressionConfig, network_id::PeerNetworkId};
+use aptos_logger::debug;
+use aptos_network::application::storage::PeersAndMetadata;
+use aptos_storage_service_types::requests::{DataRequest, StorageServiceRequest};
+use dashmap::DashMap;
+use std::{net::IpAddr, sync::Arc};
+
+/// The compression negotiator is responsible for negotiating the compression
+/// codec used for the responses sent to each peer. Peers negotiate a codec
+/// once per connection (by sending a compression negotiation request), and
+/// the negotiated codec is used for all compressed responses sent to the
+/// peer. Peers that never negotiate a codec receive LZ4 compressed responses.
+pub struct CompressionNegotiator {
+    compression_config: StorageServiceCompressionConfig,
+    negotiated_codecs: Arc<DashMap<PeerNetworkId, CompressionCodec>>,
+    peers_and_metadata: Arc<PeersAndMetadata>,
+}
+
+impl CompressionNegotiator {
+    pub fn new(
+        compression_config: StorageServiceCompressionConfig,
+        peers_and_metadata: Arc<PeersAndMetadata>,
+    ) -> Self {
+        Self {
+            compression_config,
+            negotiated_codecs: Arc::new(DashMap::new()),
+            peers_and_metadata,
+        }
+    }
+
+    /// Processes the given inbound request from the peer. If the request
+    /// is a compression negotiation request, the codec is negotiated. The
+    /// request is then updated to hold the codec negotiated with the peer.
+    pub fn process_inbound_request(
+        &self,
+        peer_network_id: &PeerNetworkId,
+        request: StorageServiceRequest,
+    ) -> StorageServiceRequest {
+        // If compression negotiation is disabled, there's nothing to do
+        if !self.compression_config.enable_compression_negotiation {
+            return request;
+        }
+
+        // Negotiate the codec (if required)
+        if let DataRequest::NegotiateCompression(negotiation_request) = &request.data_request {
+            let compression_codec =
+                self.negotiate_codec(peer_network_id, &negotiation_request.supported_codecs);
+            self.negotiated_codecs
+                .insert(*peer_network_id, compression_codec);
+        }
+
+        // Update the request with the negotiated codec
+        let compression_codec = self
+            .negotiated_codecs
+            .get(peer_network_id)
+            .map(|compression_codec| *compression_codec);
+        request.with_compression_codec(compression_codec)
+    }
+
+    /// Negotiates the codec for the peer (from the codecs it supports)
+    fn negotiate_codec(
+        &self,
+        peer_network_id: &PeerNetworkId,
+        supported_codecs: &[CompressionCodec],
+    ) -> CompressionCodec {
+        // Identify the preferred codec for the peer
+        let is_local_network_peer = self.is_local_network_peer(peer_network_id);
+        let preferred_codec = if is_local_network_peer {
+            self.compression_config.lan_compression_codec
+        } else {
+            self.compression_config.wan_compression_codec
+        };
+
+        // Select the codec and update the metrics
+        let compression_codec = select_compression_codec(preferred_codec, supported_codecs);
+        metrics::increment_counter(
+            &metrics::COMPRESSION_NEGOTIATIONS,
+            peer_network_id.network_id(),
+            compression_codec.get_label().into(),
+        );
+        debug!(LogSchema::new(LogEntry::CompressionNegotiation)
+            .peer_network_id(peer_network_id)
+            .message(&format!(
+                "Negotiated compression codec: {:?}, local network peer: {}, supported codecs: {:?}",
+                compression_codec, is_local_network_peer, supported_codecs
+            )));
+
+        compression_codec
+    }
+
+    /// Returns true iff the peer is connected via a local network address
+    fn is_local_network_peer(&self, peer_network_id: &PeerNetworkId) -> bool {
+        self.peers_and_metadata
+            .get_metadata_for_peer(*peer_network_id)
+            .ok()
+            .and_then(|peer_metadata| peer_metadata.get_connection_metadata().addr.find_ip_addr())
+            .map(|ip_address| is_local_network_address(&ip_address))
+            .unwrap_or(false)
+    }
+
+    /// Garbage collects the negotiated codecs of disconnected peers
+    /// (peers must negotiate a codec for every new connection).
+    pub fn garbage_collect_negotiated_codecs(&self) -> Result<(), Error> {
+        // Get the currently connected peers
+        let connected_peers_and_metadata = self
+            .peers_and_metadata
+            .get_connected_peers_and_metadata()
+            .map_err(|error| {
+                Error::UnexpectedErrorEncountered(format!(
+                    "Unable to get connected peers and metadata: {}",
+                    error
+                ))
+            })?;
+
+        // Remove the codecs of disconnected peers
+        self.negotiated_codecs.retain(|peer_network_id, _| {
+            connected_peers_and_metadata.contains_key(peer_network_id)
+        });
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    /// Returns a copy of the negotiated codecs for testing
+    pub(crate) fn get_negotiated_codecs(&self) -> Arc<DashMap<PeerNetworkId, CompressionCodec>> {
+        self.negotiated_codecs.clone()
+    }
+}
+
+/// Selects the codec to use given the preferred codec and the codecs
+/// supported by the peer. If the peer doesn't support the preferred codec,
+/// LZ4 is used (if supported). Otherwise, the data is not compressed.
+fn select_compression_codec(
+    preferred_codec: CompressionCodec,
+    supported_codecs: &[CompressionCodec],
+) -> CompressionCodec {
+    let is_supported = |compression_codec: &CompressionCodec| {
+        supported_codecs
+            .iter()
+            .any(|supported_codec| supported_codec.is_same_algorithm(compression_codec))
+    };
+
+    if is_supported(&preferred_codec) {
+        preferred_codec
+    } else if is_supported(&CompressionCodec::Lz4) {
+        CompressionCodec::Lz4
+    } else {
+        CompressionCodec::None
+    }
+}
+
+/// Returns true iff the given address belongs to a local network
+/// (e.g., loopback, private or link-local addresses).
+fn is_local_network_address(ip_address: &IpAddr) -> bool {
+    match ip_address {
+        IpAddr::V4(ip_address) => {
+            ip_address.is_loopback() || ip_address.is_private() || ip_address.is_link_local()
+        },
+        IpAddr::V6(ip_address) => {
+            let first_segment = ip_address.segments()[0];
+            ip_address.is_loopback()
+                || (first_segment & 0xfe00) == 0xfc00 // Unique local addresses (fc00::/7)
+                || (first_segment & 0xffc0) == 0xfe80 // Link-local addresses (fe80::/10)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_compression::codec::DEFAULT_ZSTD_COMPRESSION_LEVEL;
+
+    #[test]
+    fn test_select_compression_codec() {
+        // Create test data
+        let zstd_codec = CompressionCodec::Zstd {
+            level: DEFAULT_ZSTD_COMPRESSION_LEVEL + 1,
+        };
+
+        // Verify the preferred codec is selected if it is supported (regardless of level)
+        let selected_codec = select_compression_codec(zstd_codec, &CompressionCodec::all());
+        assert_eq!(selected_codec, zstd_codec);
+
+        // Verify LZ4 is selected if the preferred codec is unsupported
+        let supported_codecs = vec![CompressionCodec::None, CompressionCodec::Lz4];
+        let selected_codec = select_compression_codec(zstd_codec, &supported_codecs);
+        assert_eq!(selected_codec, CompressionCodec::Lz4);
+
+        // Verify no compression is selected if neither codec is supported
+        let selected_codec = select_compression_codec(zstd_codec, &[CompressionCodec::None]);
+        assert_eq!(selected_codec, CompressionCodec::None);
+    }
+
+    #[test]
+    fn test_is_local_network_address() {
+        // Verify local network addresses
+        for ip_address in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.5.4",
+            "192.168.1.1",
+            "::1",
+            "fd00::1",
+        ] {
+            assert!(is_local_network_address(&ip_address.parse().unwrap()));
+        }
+
+        // Verify wide area network addresses
+        for ip_address in ["8.8.8.8", "34.120.0.1", "2001:4860::8888"] {
+            assert!(!is_local_network_address(&ip_address.parse().unwrap()));
+        }
+    }
+}
@@ -10,6 +10,8 @@ pub enum Error {
     InvalidRequest(String),
     #[error("Storage error encountered: {0}")]
     StorageErrorEncountered(String),
+    #[error("Too many concurrent requests: {0}")]
+    TooManyConcurrentRequests(String),
     #[error("Too many invalid requests: {0}")]
     TooManyInvalidRequests(String),
     #[error("Unexpected error encountered: {0}")]
@@ -22,6 +24,7 @@ impl Error {
         match self {
             Error::InvalidRequest(_) => "invalid_request",
             Error::StorageErrorEncountered(_) => "storage_error",
+            Error::TooManyConcurrentRequests(_) => "too_many_concurrent_requests",
             Error::TooManyInvalidRequests(_) => "too_many_invalid_requests",
             Error::UnexpectedErrorEncountered(_) => "unexpected_error",
         }
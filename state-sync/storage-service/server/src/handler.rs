@@ -12,21 +12,27 @@ use crate::{
     moderator::RequestModerator,
     network::ResponseSender,
     optimistic_fetch::OptimisticFetchRequest,
+    pagination,
+    pagination::RangeRead,
     storage::StorageReaderInterface,
     subscription::{SubscriptionRequest, SubscriptionStreamRequests},
     utils,
 };
+use aptos_compression::codec::CompressionCodec;
 use aptos_config::{config::StorageServiceConfig, network_id::PeerNetworkId};
 use aptos_logger::{debug, sample, sample::SampleRate, trace, warn};
 use aptos_network::protocols::wire::handshake::v1::ProtocolId;
 use aptos_storage_service_types::{
     requests::{
-        DataRequest, EpochEndingLedgerInfoRequest, StateValuesWithProofRequest,
-        StorageServiceRequest, TransactionOutputsWithProofRequest,
-        TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
+        DataRequest, EpochEndingLedgerInfoRequest, EventsByKeyWithProofRequest,
+        PaginatedStateValuesWithProofRequest, PaginatedTransactionsWithProofRequest,
+        StateValueChangesWithProofRequest, StateValuesWithProofRequest, StorageServiceRequest,
+        TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
+        TransactionsWithProofRequest,
     },
     responses::{
-        DataResponse, ServerProtocolVersion, StorageServerSummary, StorageServiceResponse,
+        DataResponse, PaginatedResponse, ServerProtocolVersion, StorageServerSummary,
+        StorageServiceResponse,
     },
     StorageServiceError,
 };
@@ -181,6 +187,9 @@ impl<T: StorageReaderInterface> Handler<T> {
         // Transform the request error into a storage service error (for the client)
         process_result.map_err(|error| match error {
             Error::InvalidRequest(error) => StorageServiceError::InvalidRequest(error),
+            Error::TooManyConcurrentRequests(error) => {
+                StorageServiceError::TooManyConcurrentRequests(error)
+            },
             Error::TooManyInvalidRequests(error) => {
                 StorageServiceError::TooManyInvalidRequests(error)
             },
@@ -202,13 +211,39 @@ impl<T: StorageReaderInterface> Handler<T> {
         match &request.data_request {
             DataRequest::GetServerProtocolVersion => {
                 let data_response = self.get_server_protocol_version();
-                StorageServiceResponse::new(data_response, request.use_compression)
-                    .map_err(|error| error.into())
+                StorageServiceResponse::new_with_codec(
+                    data_response,
+                    request.use_compression,
+                    request.compression_codec,
+                )
+                .map_err(|error| error.into())
             },
             DataRequest::GetStorageServerSummary => {
                 let data_response = self.get_storage_server_summary();
-                StorageServiceResponse::new(data_response, request.use_compression)
-                    .map_err(|error| error.into())
+                StorageServiceResponse::new_with_codec(
+                    data_response,
+                    request.use_compression,
+                    request.compression_codec,
+                )
+                .map_err(|error| error.into())
+            },
+            DataRequest::GetDataWindows => {
+                let data_response = self.get_data_windows()?;
+                StorageServiceResponse::new_with_codec(
+                    data_response,
+                    request.use_compression,
+                    request.compression_codec,
+                )
+                .map_err(|error| error.into())
+            },
+            DataRequest::NegotiateCompression(_) => {
+                let data_response = self.get_negotiated_compression_codec(request);
+                StorageServiceResponse::new_with_codec(
+                    data_response,
+                    request.use_compression,
+                    request.compression_codec,
+                )
+                .map_err(|error| error.into())
             },
             _ => self.process_cachable_request(peer_network_id, request),
         }
@@ -394,12 +429,24 @@ impl<T: StorageReaderInterface> Handler<T> {
             DataRequest::GetStateValuesWithProof(request) => {
                 self.get_state_value_chunk_with_proof(request)
             },
+            DataRequest::GetStateValueChangesWithProof(request) => {
+                self.get_state_value_changes_with_proof(request)
+            },
             DataRequest::GetEpochEndingLedgerInfos(request) => {
                 self.get_epoch_ending_ledger_infos(request)
             },
+            DataRequest::GetEventsByKeyWithProof(request) => {
+                self.get_events_by_key_with_proof(request)
+            },
             DataRequest::GetNumberOfStatesAtVersion(version) => {
                 self.get_number_of_states_at_version(*version)
             },
+            DataRequest::GetPaginatedStateValuesWithProof(request) => {
+                self.get_paginated_state_values_with_proof(request)
+            },
+            DataRequest::GetPaginatedTransactionsWithProof(request) => {
+                self.get_paginated_transactions_with_proof(request)
+            },
             DataRequest::GetTransactionOutputsWithProof(request) => {
                 self.get_transaction_outputs_with_proof(request)
             },
@@ -424,8 +471,12 @@ impl<T: StorageReaderInterface> Handler<T> {
 
         // Create the storage response and time the operation
         let create_storage_response = || {
-            StorageServiceResponse::new(data_response, request.use_compression)
-                .map_err(|error| error.into())
+            StorageServiceResponse::new_with_codec(
+                data_response,
+                request.use_compression,
+                request.compression_codec,
+            )
+            .map_err(|error| error.into())
         };
         let storage_response = utils::execute_and_time_duration(
             &metrics::STORAGE_RESPONSE_CREATION_LATENCY,
@@ -458,6 +509,35 @@ impl<T: StorageReaderInterface> Handler<T> {
         ))
     }
 
+    fn get_state_value_changes_with_proof(
+        &self,
+        request: &StateValueChangesWithProofRequest,
+    ) -> aptos_storage_service_types::Result<DataResponse, Error> {
+        let state_value_changes_with_proof = self.storage.get_state_value_changes_with_proof(
+            request.proof_version,
+            request.start_version,
+            request.end_version,
+        )?;
+
+        Ok(DataResponse::StateValueChangesWithProof(
+            state_value_changes_with_proof,
+        ))
+    }
+
+    fn get_events_by_key_with_proof(
+        &self,
+        request: &EventsByKeyWithProofRequest,
+    ) -> aptos_storage_service_types::Result<DataResponse, Error> {
+        let events_by_key_with_proof = self.storage.get_events_by_key_with_proof(
+            &request.event_key,
+            request.start_sequence_number,
+            request.end_sequence_number,
+            request.proof_version,
+        )?;
+
+        Ok(DataResponse::EventsByKeyWithProof(events_by_key_with_proof))
+    }
+
     fn get_epoch_ending_ledger_infos(
         &self,
         request: &EpochEndingLedgerInfoRequest,
@@ -478,6 +558,91 @@ impl<T: StorageReaderInterface> Handler<T> {
         Ok(DataResponse::NumberOfStatesAtVersion(number_of_states))
     }
 
+    fn get_paginated_state_values_with_proof(
+        &self,
+        request: &PaginatedStateValuesWithProofRequest,
+    ) -> aptos_storage_service_types::Result<DataResponse, Error> {
+        // Identify where the range read should start
+        let range_read = RangeRead::StateValues {
+            version: request.version,
+            end_index: request.end_index,
+        };
+        let start_index = pagination::get_start_position(
+            &range_read,
+            request.start_index,
+            request.end_index,
+            request.continuation_token.as_ref(),
+        )?;
+
+        // Fetch the state values and create a token for the next page (if required)
+        let state_value_chunk_with_proof = self.storage.get_state_value_chunk_with_proof(
+            request.version,
+            start_index,
+            request.end_index,
+        )?;
+        let continuation_token = pagination::create_continuation_token(
+            range_read,
+            state_value_chunk_with_proof.last_index,
+            request.end_index,
+        )?;
+
+        Ok(DataResponse::PaginatedStateValueChunkWithProof(
+            PaginatedResponse::new(state_value_chunk_with_proof, continuation_token),
+        ))
+    }
+
+    fn get_paginated_transactions_with_proof(
+        &self,
+        request: &PaginatedTransactionsWithProofRequest,
+    ) -> aptos_storage_service_types::Result<DataResponse, Error> {
+        // Identify where the range read should start
+        let range_read = RangeRead::Transactions {
+            proof_version: request.proof_version,
+            end_version: request.end_version,
+            include_events: request.include_events,
+        };
+        let start_version = pagination::get_start_position(
+            &range_read,
+            request.start_version,
+            request.end_version,
+            request.continuation_token.as_ref(),
+        )?;
+
+        // Fetch the transactions and create a token for the next page (if required)
+        let transactions_with_proof = self.storage.get_transactions_with_proof(
+            request.proof_version,
+            start_version,
+            request.end_version,
+            request.include_events,
+        )?;
+        let num_transactions = transactions_with_proof.transactions.len() as u64;
+        if num_transactions == 0 {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "No transactions were fetched for the range read! Start version: {}",
+                start_version
+            )));
+        }
+        let last_version = start_version + num_transactions - 1;
+        let continuation_token =
+            pagination::create_continuation_token(range_read, last_version, request.end_version)?;
+
+        Ok(DataResponse::PaginatedTransactionsWithProof(
+            PaginatedResponse::new(transactions_with_proof, continuation_token),
+        ))
+    }
+
+    fn get_data_windows(&self) -> aptos_storage_service_types::Result<DataResponse, Error> {
+        let data_windows = self.storage.get_data_windows()?;
+        Ok(DataResponse::DataWindows(data_windows))
+    }
+
+    fn get_negotiated_compression_codec(&self, request: &StorageServiceRequest) -> DataResponse {
+        // The codec is negotiated when the request is received (see the compression
+        // negotiator). If no codec was negotiated, the default codec (LZ4) is used.
+        let compression_codec = request.compression_codec.unwrap_or(CompressionCodec::Lz4);
+        DataResponse::NegotiatedCompressionCodec(compression_codec)
+    }
+
     fn get_server_protocol_version(&self) -> DataResponse {
         let server_protocol_version = ServerProtocolVersion {
             protocol_version: STORAGE_SERVER_VERSION,
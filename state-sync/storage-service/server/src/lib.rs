@@ -5,8 +5,11 @@
 #![forbid(unsafe_code)]
 
 use crate::{
+    compression::CompressionNegotiator,
+    limiter::RequestLimiter,
     logging::{LogEntry, LogSchema},
-    network::StorageServiceNetworkEvents,
+    network::{ResponseSender, StorageServiceNetworkEvents},
+    scheduler::RequestScheduler,
     subscription::SubscriptionStreamRequests,
 };
 use aptos_bounded_executor::BoundedExecutor;
@@ -19,8 +22,10 @@ use aptos_logger::prelude::*;
 use aptos_network::application::storage::PeersAndMetadata;
 use aptos_storage_service_notifications::StorageServiceNotificationListener;
 use aptos_storage_service_types::{
+    bandwidth::BandwidthBudget,
     requests::StorageServiceRequest,
     responses::{ProtocolMetadata, StorageServerSummary, StorageServiceResponse},
+    StorageServiceError,
 };
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use arc_swap::ArcSwap;
@@ -31,18 +36,27 @@ use handler::Handler;
 use mini_moka::sync::Cache;
 use moderator::RequestModerator;
 use optimistic_fetch::OptimisticFetchRequest;
-use std::{ops::Deref, sync::Arc, time::Duration};
+use std::{
+    ops::Deref,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 use storage::StorageReaderInterface;
 use thiserror::Error;
 use tokio::runtime::Handle;
 
+mod compression;
 mod error;
 mod handler;
+mod limiter;
 mod logging;
 pub mod metrics;
 mod moderator;
 pub mod network;
 mod optimistic_fetch;
+mod pagination;
+mod scheduler;
+pub mod snapshot_artifact;
 pub mod storage;
 mod subscription;
 mod utils;
@@ -56,10 +70,15 @@ mod tests;
 // the next X-1 will execute with an unchanged version (thus, becoming a no-op and wasting the CPU).
 const CACHED_SUMMARY_UPDATE_CHANNEL_SIZE: usize = 1;
 
+// The frequency (secs) to log rejected requests
+const REJECTED_REQUEST_LOG_FREQUENCY_SECS: u64 = 5;
+
 /// The server-side actor for the storage service. Handles inbound storage
 /// service requests from clients.
 pub struct StorageServiceServer<T> {
+    bandwidth_budget: Arc<BandwidthBudget>,
     bounded_executor: BoundedExecutor,
+    compression_negotiator: Arc<CompressionNegotiator>,
     network_requests: StorageServiceNetworkEvents,
     storage: T,
     storage_service_config: StorageServiceConfig,
@@ -83,6 +102,12 @@ pub struct StorageServiceServer<T> {
     // A moderator for incoming peer requests
     request_moderator: Arc<RequestModerator>,
 
+    // A limiter for the in-flight requests of each peer
+    request_limiter: Arc<RequestLimiter>,
+
+    // A scheduler that serves requests by peer class (if enabled)
+    request_scheduler: Option<RequestScheduler>,
+
     // The listener for notifications from state sync
     storage_service_listener: Option<StorageServiceNotificationListener>,
 }
@@ -96,6 +121,7 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
         peers_and_metadata: Arc<PeersAndMetadata>,
         network_requests: StorageServiceNetworkEvents,
         storage_service_listener: StorageServiceNotificationListener,
+        bandwidth_budget: Arc<BandwidthBudget>,
     ) -> Self {
         // Extract the individual component configs
         let aptos_data_client_config = config.aptos_data_client;
@@ -104,13 +130,17 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
         // Create the required components
         let bounded_executor = BoundedExecutor::new(
             storage_service_config.max_concurrent_requests as usize,
-            executor,
+            executor.clone(),
         );
         let cached_storage_server_summary =
             Arc::new(ArcSwap::from(Arc::new(StorageServerSummary::default())));
         let optimistic_fetches = Arc::new(DashMap::new());
         let lru_response_cache = Cache::new(storage_service_config.max_lru_cache_size);
         let subscriptions = Arc::new(DashMap::new());
+        let compression_negotiator = Arc::new(CompressionNegotiator::new(
+            storage_service_config.compression_config,
+            peers_and_metadata.clone(),
+        ));
         let request_moderator = Arc::new(RequestModerator::new(
             aptos_data_client_config,
             cached_storage_server_summary.clone(),
@@ -118,10 +148,23 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             storage_service_config,
             time_service.clone(),
         ));
+        let request_limiter = Arc::new(RequestLimiter::new(storage_service_config));
+        let priority_scheduling_config = storage_service_config.priority_scheduling_config;
+        let request_scheduler = if priority_scheduling_config.enable_priority_scheduling {
+            Some(RequestScheduler::new(
+                priority_scheduling_config,
+                bounded_executor.clone(),
+                &executor,
+            ))
+        } else {
+            None
+        };
         let storage_service_listener = Some(storage_service_listener);
 
         Self {
+            bandwidth_budget,
             bounded_executor,
+            compression_negotiator,
             network_requests,
             storage,
             storage_service_config,
@@ -131,6 +174,8 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             optimistic_fetches,
             subscriptions,
             request_moderator,
+            request_limiter,
+            request_scheduler,
             storage_service_listener,
         }
     }
@@ -162,6 +207,15 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
 
         // Spawn the refresher for the request moderator
         self.spawn_moderator_peer_refresher().await;
+
+        // Spawn the refresher for the snapshot artifact (if enabled)
+        if self
+            .storage_service_config
+            .snapshot_artifact_config
+            .enable_snapshot_artifacts
+        {
+            self.spawn_snapshot_artifact_refresher().await;
+        }
     }
 
     /// Spawns a non-terminating task that refreshes the cached storage server summary
@@ -189,17 +243,31 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                 let ticker = time_service.interval(duration);
                 futures::pin_mut!(ticker);
 
-                // Continuously refresh the cache
+                // Continuously refresh the cache. The cache is refreshed on every commit
+                // notification, so the periodic refresh is only a fallback (e.g., for
+                // when the node isn't committing new data, but the data is pruned).
+                let mut last_refresh_time: Option<Instant> = None;
                 loop {
                     futures::select! {
                         _ = ticker.select_next_some() => {
+                            // Only refresh the cache if it wasn't refreshed recently
+                            let refreshed_recently = last_refresh_time
+                                .map(|last_refresh_time| {
+                                    time_service.now().duration_since(last_refresh_time) < duration
+                                })
+                                .unwrap_or(false);
+                            if refreshed_recently {
+                                continue;
+                            }
+
                             // Refresh the cache periodically
                             refresh_cached_storage_summary(
                                 cached_storage_server_summary.clone(),
                                 storage.clone(),
                                 config,
                                 cache_update_notifiers.clone(),
-                            )
+                            );
+                            last_refresh_time = Some(time_service.now());
                         },
                         notification = storage_service_listener.select_next_some() => {
                             trace!(LogSchema::new(LogEntry::ReceivedCommitNotification)
@@ -215,7 +283,8 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                                 storage.clone(),
                                 config,
                                 cache_update_notifiers.clone(),
-                            )
+                            );
+                            last_refresh_time = Some(time_service.now());
                         },
                     }
                 }
@@ -363,6 +432,7 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
     /// peer states in the request moderator.
     async fn spawn_moderator_peer_refresher(&mut self) {
         // Clone all required components for the task
+        let compression_negotiator = self.compression_negotiator.clone();
         let config = self.storage_service_config;
         let request_moderator = self.request_moderator.clone();
         let time_service = self.time_service.clone();
@@ -385,6 +455,76 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
                             .error(&error)
                             .message("Failed to refresh the request moderator!"));
                     }
+
+                    // Garbage collect the negotiated codecs of disconnected peers
+                    if let Err(error) = compression_negotiator.garbage_collect_negotiated_codecs() {
+                        error!(LogSchema::new(LogEntry::CompressionNegotiation)
+                            .error(&error)
+                            .message("Failed to garbage collect the negotiated codecs!"));
+                    }
+                }
+            })
+            .await;
+    }
+
+    /// Spawns a non-terminating task that periodically regenerates the
+    /// state snapshot artifact (when a new epoch ending version exists).
+    async fn spawn_snapshot_artifact_refresher(&mut self) {
+        // Clone all required components for the task
+        let config = self.storage_service_config.snapshot_artifact_config;
+        let storage = self.storage.clone();
+        let time_service = self.time_service.clone();
+
+        // Spawn the task
+        self.bounded_executor
+            .spawn(async move {
+                // Create a ticker for the refresh interval
+                let duration = Duration::from_secs(config.snapshot_artifact_refresh_interval_secs);
+                let ticker = time_service.interval(duration);
+                futures::pin_mut!(ticker);
+
+                // Periodically refresh the snapshot artifact
+                loop {
+                    ticker.next().await;
+
+                    // Generating the artifact is expensive, so we do it on the blocking thread pool
+                    let storage = storage.clone();
+                    let start_time = Instant::now();
+                    let result =
+                        tokio::task::spawn_blocking(move || storage.refresh_snapshot_artifact())
+                            .await
+                            .unwrap_or_else(|error| {
+                                Err(Error::UnexpectedErrorEncountered(format!(
+                                    "The snapshot artifact refresher panicked: {:?}",
+                                    error
+                                )))
+                            });
+
+                    // Log the result and update the metrics
+                    match result {
+                        Ok(Some(version)) => {
+                            metrics::observe_duration(
+                                &metrics::SNAPSHOT_ARTIFACT_GENERATION_LATENCY,
+                                vec![metrics::RESULT_SUCCESS.into()],
+                                start_time,
+                            );
+                            info!(LogSchema::new(LogEntry::SnapshotArtifact).message(&format!(
+                                "Generated a new snapshot artifact for version: {:?}",
+                                version
+                            )));
+                        },
+                        Ok(None) => {}, // The artifact is already up-to-date
+                        Err(error) => {
+                            metrics::observe_duration(
+                                &metrics::SNAPSHOT_ARTIFACT_GENERATION_LATENCY,
+                                vec![metrics::RESULT_FAILURE.into()],
+                                start_time,
+                            );
+                            error!(LogSchema::new(LogEntry::SnapshotArtifact)
+                                .error(&error)
+                                .message("Failed to refresh the snapshot artifact!"));
+                        },
+                    }
                 }
             })
             .await;
@@ -408,29 +548,94 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
             let lru_response_cache = self.lru_response_cache.clone();
             let request_moderator = self.request_moderator.clone();
             let time_service = self.time_service.clone();
-            self.bounded_executor
-                .spawn_blocking(move || {
-                    Handler::new(
-                        cached_storage_server_summary,
-                        optimistic_fetches,
-                        lru_response_cache,
-                        request_moderator,
-                        storage,
-                        subscriptions,
-                        time_service,
-                    )
-                    .process_request_and_respond(
-                        config,
-                        network_request.peer_network_id,
-                        network_request.protocol_id,
-                        network_request.storage_service_request,
-                        network_request.response_sender,
+
+            // Negotiate the compression codec (if required) and attach the
+            // codec negotiated with the peer to the request.
+            let storage_service_request = self.compression_negotiator.process_inbound_request(
+                &network_request.peer_network_id,
+                network_request.storage_service_request,
+            );
+
+            // Track the bytes served to the peer against the bandwidth budget
+            let response_sender = network_request
+                .response_sender
+                .with_bandwidth_budget(self.bandwidth_budget.clone());
+
+            // Verify the peer is within its concurrency limits. If not, the request
+            // is rejected immediately (instead of waiting for executor capacity).
+            let request_permit = match self
+                .request_limiter
+                .try_acquire_permit(&network_request.peer_network_id, &storage_service_request)
+            {
+                Ok(request_permit) => request_permit,
+                Err(error) => {
+                    handle_rejected_request(
+                        &network_request.peer_network_id,
+                        &storage_service_request,
+                        error,
+                        response_sender,
                     );
-                })
-                .await;
+                    continue;
+                },
+            };
+
+            // Reserve a slot in the scheduler queue for the peer class (if enabled).
+            // If the queue is full, the request is rejected immediately.
+            let scheduling_permit = match &self.request_scheduler {
+                Some(request_scheduler) => {
+                    match request_scheduler.try_reserve(&network_request.peer_network_id) {
+                        Ok(scheduling_permit) => Some(scheduling_permit),
+                        Err(error) => {
+                            handle_rejected_request(
+                                &network_request.peer_network_id,
+                                &storage_service_request,
+                                error,
+                                response_sender,
+                            );
+                            continue;
+                        },
+                    }
+                },
+                None => None,
+            };
+
+            let process_request = move || {
+                // Hold the permit until the request has been processed
+                let _request_permit = request_permit;
+                Handler::new(
+                    cached_storage_server_summary,
+                    optimistic_fetches,
+                    lru_response_cache,
+                    request_moderator,
+                    storage,
+                    subscriptions,
+                    time_service,
+                )
+                .process_request_and_respond(
+                    config,
+                    network_request.peer_network_id,
+                    network_request.protocol_id,
+                    storage_service_request,
+                    response_sender,
+                );
+            };
+
+            // Schedule the request by peer class, or spawn it directly
+            match scheduling_permit {
+                Some(scheduling_permit) => scheduling_permit.schedule(process_request),
+                None => {
+                    self.bounded_executor.spawn_blocking(process_request).await;
+                },
+            }
         }
     }
 
+    #[cfg(test)]
+    /// Returns a copy of the compression negotiator for test purposes
+    pub(crate) fn get_compression_negotiator(&self) -> Arc<CompressionNegotiator> {
+        self.compression_negotiator.clone()
+    }
+
     #[cfg(test)]
     /// Returns a copy of the request moderator for test purposes
     pub(crate) fn get_request_moderator(&self) -> Arc<RequestModerator> {
@@ -454,6 +659,37 @@ impl<T: StorageReaderInterface + Send + Sync> StorageServiceServer<T> {
     }
 }
 
+/// Handles a request that was rejected due to the concurrency limits
+/// (or a full scheduler queue), and notifies the peer of the error.
+fn handle_rejected_request(
+    peer_network_id: &PeerNetworkId,
+    request: &StorageServiceRequest,
+    error: Error,
+    response_sender: ResponseSender,
+) {
+    // Update the error counter
+    metrics::increment_counter(
+        &metrics::STORAGE_ERRORS_ENCOUNTERED,
+        peer_network_id.network_id(),
+        error.get_label().into(),
+    );
+
+    // Periodically log the rejection
+    sample!(
+        SampleRate::Duration(Duration::from_secs(REJECTED_REQUEST_LOG_FREQUENCY_SECS)),
+        warn!(LogSchema::new(LogEntry::StorageServiceError)
+            .error(&error)
+            .peer_network_id(peer_network_id)
+            .request(request)
+            .message("Rejected the request due to the concurrency limits!"));
+    );
+
+    // Notify the peer of the error (so that it can back off)
+    response_sender.send(Err(StorageServiceError::TooManyConcurrentRequests(
+        error.to_string(),
+    )));
+}
+
 /// Handles the active optimistic fetches and logs any
 /// errors that were encountered.
 async fn handle_active_optimistic_fetches<T: StorageReaderInterface>(
@@ -0,0 +1,357 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{error::Error, metrics};
+use aptos_config::{
+    config::{StorageServiceConcurrencyLimitsConfig, StorageServiceConfig},
+    network_id::PeerNetworkId,
+};
+use aptos_storage_service_types::requests::StorageServiceRequest;
+use dashmap::DashMap;
+use std::sync::Arc;
+
+// Useful labels for the rejected request metrics
+const BYTES_IN_FLIGHT_LIMIT_LABEL: &str = "bytes_in_flight";
+const CONCURRENT_REQUESTS_LIMIT_LABEL: &str = "concurrent_requests";
+
+/// A simple struct that tracks the in-flight requests of a peer
+#[derive(Clone, Copy, Debug, Default)]
+struct InFlightRequests {
+    num_bytes: u64,    // The estimated number of bytes in flight
+    num_requests: u64, // The number of requests in flight
+}
+
+/// The request limiter enforces the per-peer concurrency and bytes
+/// in flight limits. This prevents a single aggressive peer from
+/// saturating the storage service. To ensure fairness across peers,
+/// each peer is also limited to a fair share of the total concurrent
+/// requests (i.e., the max concurrent requests divided by the number
+/// of peers with requests in flight).
+pub struct RequestLimiter {
+    limits_config: StorageServiceConcurrencyLimitsConfig,
+    max_concurrent_requests: u64,
+    max_network_chunk_bytes: u64,
+    in_flight_requests: Arc<DashMap<PeerNetworkId, InFlightRequests>>,
+}
+
+impl RequestLimiter {
+    pub fn new(storage_service_config: StorageServiceConfig) -> Self {
+        Self {
+            limits_config: storage_service_config.concurrency_limits_config,
+            max_concurrent_requests: storage_service_config.max_concurrent_requests,
+            max_network_chunk_bytes: storage_service_config.max_network_chunk_bytes,
+            in_flight_requests: Arc::new(DashMap::new()),
+        }
+    }
+
+    /// Attempts to acquire a permit for the given request from the peer.
+    /// If the peer has reached its limits, an error is returned. Otherwise,
+    /// the returned permit releases the reserved capacity when dropped.
+    pub fn try_acquire_permit(
+        self: &Arc<Self>,
+        peer_network_id: &PeerNetworkId,
+        request: &StorageServiceRequest,
+    ) -> Result<Option<RequestPermit>, Error> {
+        // If the per-peer limits are disabled, there's nothing to do
+        if !self.limits_config.enable_per_peer_limits {
+            return Ok(None);
+        }
+
+        // Calculate the concurrent request limit for the peer. Note: we
+        // must do this before locking the peer entry (to avoid deadlocks).
+        let max_concurrent_requests = self.get_max_concurrent_requests_for_peer(peer_network_id);
+
+        // Verify the peer is within its limits and reserve the capacity
+        let num_bytes = self.estimate_response_bytes(request);
+        let mut in_flight_requests = self.in_flight_requests.entry(*peer_network_id).or_default();
+        if in_flight_requests.num_requests >= max_concurrent_requests {
+            metrics::increment_counter(
+                &metrics::REJECTED_CONCURRENT_REQUESTS,
+                peer_network_id.network_id(),
+                CONCURRENT_REQUESTS_LIMIT_LABEL.into(),
+            );
+            return Err(Error::TooManyConcurrentRequests(format!(
+                "Peer has too many requests in flight: {}, max: {}",
+                in_flight_requests.num_requests, max_concurrent_requests
+            )));
+        }
+        let max_bytes_in_flight = self.limits_config.max_bytes_in_flight_per_peer;
+        if in_flight_requests.num_requests > 0
+            && in_flight_requests.num_bytes.saturating_add(num_bytes) > max_bytes_in_flight
+        {
+            metrics::increment_counter(
+                &metrics::REJECTED_CONCURRENT_REQUESTS,
+                peer_network_id.network_id(),
+                BYTES_IN_FLIGHT_LIMIT_LABEL.into(),
+            );
+            return Err(Error::TooManyConcurrentRequests(format!(
+                "Peer has too many bytes in flight: {}, max: {}",
+                in_flight_requests.num_bytes, max_bytes_in_flight
+            )));
+        }
+        in_flight_requests.num_bytes += num_bytes;
+        in_flight_requests.num_requests += 1;
+
+        Ok(Some(RequestPermit {
+            num_bytes,
+            peer_network_id: *peer_network_id,
+            request_limiter: self.clone(),
+        }))
+    }
+
+    /// Returns the max number of concurrent requests for the peer. This is
+    /// the smaller of the configured per-peer limit and the peer's fair share.
+    fn get_max_concurrent_requests_for_peer(&self, peer_network_id: &PeerNetworkId) -> u64 {
+        // Calculate the number of active peers (including the given peer)
+        let mut num_active_peers = self.in_flight_requests.len() as u64;
+        if !self.in_flight_requests.contains_key(peer_network_id) {
+            num_active_peers += 1;
+        }
+
+        // Calculate the fair share of the peer (every peer can have at least one request)
+        let fair_share = (self.max_concurrent_requests / num_active_peers).max(1);
+        fair_share.min(self.limits_config.max_concurrent_requests_per_peer)
+    }
+
+    /// Returns the estimated number of bytes for the response of the given
+    /// request. Metadata requests have small responses, so these are ignored.
+    /// All other requests are estimated using the max network chunk size.
+    fn estimate_response_bytes(&self, request: &StorageServiceRequest) -> u64 {
+        let data_request = &request.data_request;
+        if data_request.is_protocol_version_request()
+            || data_request.is_storage_summary_request()
+            || data_request.is_compression_negotiation_request()
+            || data_request.is_data_windows_request()
+        {
+            0
+        } else {
+            self.max_network_chunk_bytes
+        }
+    }
+
+    /// Releases the capacity reserved for a request from the given peer
+    fn release(&self, peer_network_id: &PeerNetworkId, num_bytes: u64) {
+        // Update the in-flight requests for the peer
+        let remove_peer_entry = match self.in_flight_requests.get_mut(peer_network_id) {
+            Some(mut in_flight_requests) => {
+                in_flight_requests.num_bytes =
+                    in_flight_requests.num_bytes.saturating_sub(num_bytes);
+                in_flight_requests.num_requests = in_flight_requests.num_requests.saturating_sub(1);
+                in_flight_requests.num_requests == 0
+            },
+            None => false,
+        };
+
+        // Remove the peer entry if there are no more requests in flight (so
+        // that the peer is no longer considered active for the fair share).
+        if remove_peer_entry {
+            self.in_flight_requests
+                .remove_if(peer_network_id, |_, in_flight_requests| {
+                    in_flight_requests.num_requests == 0
+                });
+        }
+    }
+
+    #[cfg(test)]
+    /// Returns the number of requests and bytes in flight for the given peer
+    pub(crate) fn get_in_flight_requests(&self, peer_network_id: &PeerNetworkId) -> (u64, u64) {
+        self.in_flight_requests
+            .get(peer_network_id)
+            .map(|in_flight_requests| {
+                (
+                    in_flight_requests.num_requests,
+                    in_flight_requests.num_bytes,
+                )
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// A permit for a single in-flight request. The reserved
+/// capacity is released when the permit is dropped.
+pub struct RequestPermit {
+    num_bytes: u64,
+    peer_network_id: PeerNetworkId,
+    request_limiter: Arc<RequestLimiter>,
+}
+
+impl Drop for RequestPermit {
+    fn drop(&mut self) {
+        self.request_limiter
+            .release(&self.peer_network_id, self.num_bytes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_config::network_id::NetworkId;
+    use aptos_storage_service_types::requests::{DataRequest, TransactionsWithProofRequest};
+    use aptos_types::PeerId;
+
+    #[test]
+    fn test_concurrent_requests_limit() {
+        // Create a request limiter with a small per-peer limit
+        let max_concurrent_requests_per_peer = 3;
+        let request_limiter =
+            create_request_limiter(100, max_concurrent_requests_per_peer, u64::MAX);
+
+        // Acquire the max number of permits for a peer
+        let peer_network_id = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let request = create_data_request();
+        let mut permits = vec![];
+        for _ in 0..max_concurrent_requests_per_peer {
+            let permit = request_limiter
+                .try_acquire_permit(&peer_network_id, &request)
+                .unwrap();
+            permits.push(permit);
+        }
+
+        // Verify the next request is rejected
+        let result = request_limiter.try_acquire_permit(&peer_network_id, &request);
+        assert!(matches!(result, Err(Error::TooManyConcurrentRequests(_))));
+
+        // Verify requests from other peers are not affected
+        let other_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let permit = request_limiter.try_acquire_permit(&other_peer, &request);
+        assert!(permit.is_ok());
+
+        // Drop a permit and verify the peer can send another request
+        permits.pop();
+        let permit = request_limiter.try_acquire_permit(&peer_network_id, &request);
+        assert!(permit.is_ok());
+    }
+
+    #[test]
+    fn test_bytes_in_flight_limit() {
+        // Create a request limiter that allows two data requests in flight
+        let max_bytes_in_flight = 2 * StorageServiceConfig::default().max_network_chunk_bytes;
+        let request_limiter = create_request_limiter(100, 100, max_bytes_in_flight);
+
+        // Acquire two permits for the peer
+        let peer_network_id = PeerNetworkId::new(NetworkId::Vfn, PeerId::random());
+        let request = create_data_request();
+        let permit_1 = request_limiter.try_acquire_permit(&peer_network_id, &request);
+        let _permit_2 = request_limiter.try_acquire_permit(&peer_network_id, &request);
+        assert_eq!(
+            request_limiter.get_in_flight_requests(&peer_network_id),
+            (2, max_bytes_in_flight)
+        );
+
+        // Verify the next data request is rejected
+        let result = request_limiter.try_acquire_permit(&peer_network_id, &request);
+        assert!(matches!(result, Err(Error::TooManyConcurrentRequests(_))));
+
+        // Verify metadata requests are still allowed
+        let summary_request =
+            StorageServiceRequest::new(DataRequest::GetStorageServerSummary, true);
+        let permit = request_limiter.try_acquire_permit(&peer_network_id, &summary_request);
+        assert!(permit.is_ok());
+        drop(permit);
+
+        // Drop a permit and verify the bytes in flight are released
+        drop(permit_1);
+        assert_eq!(
+            request_limiter.get_in_flight_requests(&peer_network_id),
+            (1, max_bytes_in_flight / 2)
+        );
+    }
+
+    #[test]
+    fn test_fair_share() {
+        // Create a request limiter with a small total number of concurrent requests
+        let request_limiter = create_request_limiter(4, 100, u64::MAX);
+
+        // Verify a single peer can use all the concurrent requests
+        let peer_1 = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let request = create_data_request();
+        let mut permits_1 = vec![];
+        for _ in 0..4 {
+            permits_1.push(
+                request_limiter
+                    .try_acquire_permit(&peer_1, &request)
+                    .unwrap(),
+            );
+        }
+        assert!(request_limiter
+            .try_acquire_permit(&peer_1, &request)
+            .is_err());
+
+        // Verify a second peer is limited to its fair share
+        let peer_2 = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        let mut permits_2 = vec![];
+        for _ in 0..2 {
+            permits_2.push(
+                request_limiter
+                    .try_acquire_permit(&peer_2, &request)
+                    .unwrap(),
+            );
+        }
+        assert!(request_limiter
+            .try_acquire_permit(&peer_2, &request)
+            .is_err());
+
+        // Verify the first peer is now also limited to its fair share
+        permits_1.pop();
+        assert!(request_limiter
+            .try_acquire_permit(&peer_1, &request)
+            .is_err());
+
+        // Drop all permits and verify the peer entries are removed
+        drop(permits_1);
+        drop(permits_2);
+        assert!(request_limiter.in_flight_requests.is_empty());
+    }
+
+    #[test]
+    fn test_limits_disabled() {
+        // Create a request limiter with the per-peer limits disabled
+        let storage_service_config = StorageServiceConfig {
+            concurrency_limits_config: StorageServiceConcurrencyLimitsConfig {
+                enable_per_peer_limits: false,
+                max_concurrent_requests_per_peer: 1,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let request_limiter = Arc::new(RequestLimiter::new(storage_service_config));
+
+        // Verify permits are never required
+        let peer_network_id = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+        for _ in 0..10 {
+            let permit = request_limiter
+                .try_acquire_permit(&peer_network_id, &create_data_request())
+                .unwrap();
+            assert!(permit.is_none());
+        }
+    }
+
+    /// Creates a request limiter with the given limits
+    fn create_request_limiter(
+        max_concurrent_requests: u64,
+        max_concurrent_requests_per_peer: u64,
+        max_bytes_in_flight_per_peer: u64,
+    ) -> Arc<RequestLimiter> {
+        let storage_service_config = StorageServiceConfig {
+            concurrency_limits_config: StorageServiceConcurrencyLimitsConfig {
+                enable_per_peer_limits: true,
+                max_bytes_in_flight_per_peer,
+                max_concurrent_requests_per_peer,
+            },
+            max_concurrent_requests,
+            ..Default::default()
+        };
+        Arc::new(RequestLimiter::new(storage_service_config))
+    }
+
+    /// Creates a simple data request
+    fn create_data_request() -> StorageServiceRequest {
+        let data_request = DataRequest::GetTransactionsWithProof(TransactionsWithProofRequest {
+            proof_version: 100,
+            start_version: 0,
+            end_version: 10,
+            include_events: false,
+        });
+        StorageServiceRequest::new(data_request, true)
+    }
+}
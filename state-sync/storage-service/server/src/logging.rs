@@ -36,6 +36,7 @@ impl<'a> LogSchema<'a> {
 #[derive(Clone, Copy, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum LogEntry {
+    CompressionNegotiation,
     OptimisticFetchRefresh,
     OptimisticFetchRequest,
     OptimisticFetchResponse,
@@ -45,6 +46,7 @@ pub enum LogEntry {
     RequestModeratorIgnoredPeer,
     RequestModeratorRefresh,
     SentStorageResponse,
+    SnapshotArtifact,
     StorageServiceError,
     StorageSummaryRefresh,
     SubscriptionRefresh,
@@ -4,8 +4,8 @@
 
 use aptos_config::network_id::NetworkId;
 use aptos_metrics_core::{
-    register_histogram_vec, register_int_counter_vec, register_int_gauge_vec, HistogramVec,
-    IntCounterVec, IntGaugeVec,
+    register_histogram_vec, register_int_counter_vec, register_int_gauge, register_int_gauge_vec,
+    HistogramVec, IntCounterVec, IntGauge, IntGaugeVec,
 };
 use once_cell::sync::Lazy;
 use std::time::Instant;
@@ -17,6 +17,9 @@ pub const OPTIMISTIC_FETCH_ADD: &str = "optimistic_fetch_add";
 pub const OPTIMISTIC_FETCH_EXPIRE: &str = "optimistic_fetch_expire";
 pub const RESULT_SUCCESS: &str = "success";
 pub const RESULT_FAILURE: &str = "failure";
+pub const SNAPSHOT_ARTIFACT_ERROR: &str = "snapshot_artifact_error";
+pub const SNAPSHOT_ARTIFACT_HIT: &str = "snapshot_artifact_hit";
+pub const SNAPSHOT_ARTIFACT_MISS: &str = "snapshot_artifact_miss";
 pub const SUBSCRIPTION_ADD: &str = "subscription_add";
 pub const SUBSCRIPTION_EXPIRE: &str = "subscription_expire";
 pub const SUBSCRIPTION_FAILURE: &str = "subscription_failure";
@@ -28,6 +31,16 @@ const REQUEST_PROCESSING_LATENCY_BUCKETS_SECS: &[f64] = &[
     60.0, 120.0, 180.0, 240.0, 300.0,
 ];
 
+/// Counter for the compression codecs negotiated with peers
+pub static COMPRESSION_NEGOTIATIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_storage_service_server_compression_negotiations",
+        "Counters for the compression codecs negotiated with peers",
+        &["network_id", "codec"]
+    )
+    .unwrap()
+});
+
 /// Gauge for tracking the number of actively ignored peers
 pub static IGNORED_PEER_COUNT: Lazy<IntGaugeVec> = Lazy::new(|| {
     register_int_gauge_vec!(
@@ -90,6 +103,16 @@ pub static OPTIMISTIC_FETCH_LATENCIES: Lazy<HistogramVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Gauge for the number of requests waiting to be scheduled (by peer class)
+pub static PENDING_SCHEDULED_REQUESTS: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        "aptos_storage_service_server_pending_scheduled_requests",
+        "Gauge for the number of requests waiting to be scheduled",
+        &["peer_class"]
+    )
+    .unwrap()
+});
+
 /// Counter for pending network events to the storage service (server-side)
 pub static PENDING_STORAGE_SERVER_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -100,6 +123,46 @@ pub static PENDING_STORAGE_SERVER_NETWORK_EVENTS: Lazy<IntCounterVec> = Lazy::ne
     .unwrap()
 });
 
+/// Counter for requests rejected due to the per-peer concurrency limits
+pub static REJECTED_CONCURRENT_REQUESTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_storage_service_server_rejected_concurrent_requests",
+        "Counter for requests rejected due to the per-peer concurrency limits",
+        &["network_id", "limit"]
+    )
+    .unwrap()
+});
+
+/// Counter for state snapshot artifact events (e.g., hits and misses)
+pub static SNAPSHOT_ARTIFACT_EVENTS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_storage_service_server_snapshot_artifact_events",
+        "Counters for state snapshot artifact events in the storage server",
+        &["event"]
+    )
+    .unwrap()
+});
+
+/// Time it takes to generate a state snapshot artifact
+pub static SNAPSHOT_ARTIFACT_GENERATION_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_storage_service_server_snapshot_artifact_generation_latency",
+        "Time it takes to generate a state snapshot artifact",
+        &["result"],
+        REQUEST_PROCESSING_LATENCY_BUCKETS_SECS.to_vec(),
+    )
+    .unwrap()
+});
+
+/// Gauge for tracking the version of the current state snapshot artifact
+pub static SNAPSHOT_ARTIFACT_VERSION: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_storage_service_server_snapshot_artifact_version",
+        "Gauge for tracking the version of the current state snapshot artifact"
+    )
+    .unwrap()
+});
+
 /// Counter for storage service errors encountered
 pub static STORAGE_ERRORS_ENCOUNTERED: Lazy<IntCounterVec> = Lazy::new(|| {
     register_int_counter_vec!(
@@ -171,6 +234,28 @@ pub static STORAGE_REQUEST_PROCESSING_LATENCY: Lazy<HistogramVec> = Lazy::new(||
     .unwrap()
 });
 
+/// Time a storage request waits to be scheduled (by peer class)
+pub static STORAGE_REQUEST_QUEUE_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_storage_service_server_request_queue_latency",
+        "Time a storage service request waits before it is processed",
+        &["peer_class"],
+        REQUEST_PROCESSING_LATENCY_BUCKETS_SECS.to_vec(),
+    )
+    .unwrap()
+});
+
+/// Time it takes to schedule and process a storage request (by peer class)
+pub static STORAGE_REQUEST_TOTAL_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "aptos_storage_service_server_request_total_latency",
+        "Time it takes to schedule and process a storage service request",
+        &["peer_class"],
+        REQUEST_PROCESSING_LATENCY_BUCKETS_SECS.to_vec(),
+    )
+    .unwrap()
+});
+
 /// Time it takes to validate a storage request
 pub static STORAGE_REQUEST_VALIDATION_LATENCY: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
@@ -220,6 +305,11 @@ pub fn increment_network_frame_overflow(response_type: &str) {
         .inc()
 }
 
+/// Increments the snapshot artifact event counter for the given event
+pub fn increment_snapshot_artifact_event(event: &str) {
+    SNAPSHOT_ARTIFACT_EVENTS.with_label_values(&[event]).inc()
+}
+
 /// Increments the given counter with the provided label values.
 pub fn increment_counter(counter: &Lazy<IntCounterVec>, network_id: NetworkId, label: String) {
     counter
@@ -9,8 +9,8 @@ use aptos_network::{
     ProtocolId,
 };
 use aptos_storage_service_types::{
-    requests::StorageServiceRequest, responses::StorageServiceResponse, Result,
-    StorageServiceMessage,
+    bandwidth::BandwidthBudget, requests::StorageServiceRequest, responses::StorageServiceResponse,
+    Result, StorageServiceMessage,
 };
 use bytes::Bytes;
 use futures::{
@@ -20,6 +20,7 @@ use futures::{
 };
 use std::{
     pin::Pin,
+    sync::Arc,
     task::{Context, Poll},
 };
 
@@ -97,11 +98,21 @@ impl Stream for StorageServiceNetworkEvents {
 /// Provides a more strongly typed interface around the raw RPC response channel.
 pub struct ResponseSender {
     response_tx: oneshot::Sender<Result<Bytes, RpcError>>,
+    bandwidth_budget: Option<Arc<BandwidthBudget>>,
 }
 
 impl ResponseSender {
     pub fn new(response_tx: oneshot::Sender<Result<Bytes, RpcError>>) -> Self {
-        Self { response_tx }
+        Self {
+            response_tx,
+            bandwidth_budget: None,
+        }
+    }
+
+    /// Records the bytes of the sent response against the given bandwidth budget
+    pub fn with_bandwidth_budget(mut self, bandwidth_budget: Arc<BandwidthBudget>) -> Self {
+        self.bandwidth_budget = Some(bandwidth_budget);
+        self
     }
 
     pub fn send(self, response: Result<StorageServiceResponse>) {
@@ -109,6 +120,12 @@ impl ResponseSender {
         let result = bcs::to_bytes(&msg)
             .map(Bytes::from)
             .map_err(RpcError::BcsError);
+
+        // Record the served bytes (if a bandwidth budget is being tracked)
+        if let (Some(bandwidth_budget), Ok(bytes)) = (&self.bandwidth_budget, &result) {
+            bandwidth_budget.record_served_bytes(bytes.len() as u64);
+        }
+
         let _ = self.response_tx.send(result);
     }
 }
@@ -20,7 +20,7 @@ use aptos_infallible::Mutex;
 use aptos_logger::{error, warn};
 use aptos_storage_service_types::{
     requests::{
-        DataRequest, StorageServiceRequest, TransactionOutputsWithProofRequest,
+        DataRequest, EventFilter, StorageServiceRequest, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
     responses::{StorageServerSummary, StorageServiceResponse},
@@ -98,7 +98,8 @@ impl OptimisticFetchRequest {
 
         // Create the storage request
         let data_request = match &self.request.data_request {
-            DataRequest::GetNewTransactionOutputsWithProof(_) => {
+            DataRequest::GetNewTransactionOutputsWithProof(_)
+            | DataRequest::GetNewFilteredTransactionOutputsWithProof(_) => {
                 DataRequest::GetTransactionOutputsWithProof(TransactionOutputsWithProofRequest {
                     proof_version: target_version,
                     start_version,
@@ -127,7 +128,8 @@ impl OptimisticFetchRequest {
             request => unreachable!("Unexpected optimistic fetch request: {:?}", request),
         };
         let storage_request =
-            StorageServiceRequest::new(data_request, self.request.use_compression);
+            StorageServiceRequest::new(data_request, self.request.use_compression)
+                .with_compression_codec(self.request.compression_codec);
         Ok(storage_request)
     }
 
@@ -135,6 +137,9 @@ impl OptimisticFetchRequest {
     fn highest_known_version(&self) -> u64 {
         match &self.request.data_request {
             DataRequest::GetNewTransactionOutputsWithProof(request) => request.known_version,
+            DataRequest::GetNewFilteredTransactionOutputsWithProof(request) => {
+                request.known_version
+            },
             DataRequest::GetNewTransactionsWithProof(request) => request.known_version,
             DataRequest::GetNewTransactionsOrOutputsWithProof(request) => request.known_version,
             request => unreachable!("Unexpected optimistic fetch request: {:?}", request),
@@ -145,6 +150,7 @@ impl OptimisticFetchRequest {
     fn highest_known_epoch(&self) -> u64 {
         match &self.request.data_request {
             DataRequest::GetNewTransactionOutputsWithProof(request) => request.known_epoch,
+            DataRequest::GetNewFilteredTransactionOutputsWithProof(request) => request.known_epoch,
             DataRequest::GetNewTransactionsWithProof(request) => request.known_epoch,
             DataRequest::GetNewTransactionsOrOutputsWithProof(request) => request.known_epoch,
             request => unreachable!("Unexpected optimistic fetch request: {:?}", request),
@@ -155,7 +161,8 @@ impl OptimisticFetchRequest {
     /// on the request type.
     fn max_chunk_size_for_request(&self, config: StorageServiceConfig) -> u64 {
         match &self.request.data_request {
-            DataRequest::GetNewTransactionOutputsWithProof(_) => {
+            DataRequest::GetNewTransactionOutputsWithProof(_)
+            | DataRequest::GetNewFilteredTransactionOutputsWithProof(_) => {
                 config.max_transaction_output_chunk_size
             },
             DataRequest::GetNewTransactionsWithProof(_) => config.max_transaction_chunk_size,
@@ -166,6 +173,16 @@ impl OptimisticFetchRequest {
         }
     }
 
+    /// Returns the event filter for the request (if any)
+    fn event_filter(&self) -> Option<EventFilter> {
+        match &self.request.data_request {
+            DataRequest::GetNewFilteredTransactionOutputsWithProof(request) => {
+                Some(request.event_filter.clone())
+            },
+            _ => None,
+        }
+    }
+
     /// Returns true iff the optimistic fetch has expired
     fn is_expired(&self, timeout_ms: u64) -> bool {
         let current_time = self.time_service.now();
@@ -275,6 +292,7 @@ pub(crate) async fn handle_ready_optimistic_fetches<T: StorageReaderInterface>(
                         // Get the storage service request for the missing data
                         let missing_data_request = optimistic_fetch
                             .get_storage_request_for_missing_data(config, &target_ledger_info)?;
+                        let event_filter = optimistic_fetch.event_filter();
 
                         // Notify the peer of the new data
                         utils::notify_peer_of_new_data(
@@ -287,6 +305,7 @@ pub(crate) async fn handle_ready_optimistic_fetches<T: StorageReaderInterface>(
                             time_service.clone(),
                             &peer_network_id,
                             missing_data_request,
+                            event_filter,
                             target_ledger_info,
                             optimistic_fetch.take_response_sender(),
                         )
@@ -0,0 +1,153 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use aptos_storage_service_types::responses::ContinuationToken;
+use serde::{Deserialize, Serialize};
+
+/// The range read that a continuation token belongs to. This prevents
+/// clients from resuming a different range read using the token.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum RangeRead {
+    StateValues {
+        version: u64,   // The version of the state values
+        end_index: u64, // The last index of the range read (inclusive)
+    },
+    Transactions {
+        proof_version: u64,   // The version the proof is relative to
+        end_version: u64,     // The last version of the range read (inclusive)
+        include_events: bool, // Whether or not events are included
+    },
+}
+
+/// The cursor of a paginated range read. The cursor is serialized into
+/// the continuation token sent to the client (the token is opaque to the
+/// client, so the cursor format can change without breaking clients).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+struct RangeCursor {
+    range_read: RangeRead,
+    next_position: u64, // The next index or version to read (inclusive)
+}
+
+/// Returns the position at which to start the given range read. If a
+/// continuation token is provided, the read resumes at the cursor in the
+/// token (after verifying the token belongs to the range read). Otherwise,
+/// the read starts at the given start position.
+pub fn get_start_position(
+    range_read: &RangeRead,
+    start_position: u64,
+    end_position: u64,
+    continuation_token: Option<&ContinuationToken>,
+) -> Result<u64, Error> {
+    let continuation_token = match continuation_token {
+        Some(continuation_token) => continuation_token,
+        None => return Ok(start_position),
+    };
+
+    // Deserialize the cursor
+    let range_cursor =
+        bcs::from_bytes::<RangeCursor>(continuation_token.as_bytes()).map_err(|error| {
+            Error::InvalidRequest(format!("Invalid continuation token: {:?}", error))
+        })?;
+
+    // Verify the cursor belongs to the range read
+    if &range_cursor.range_read != range_read {
+        return Err(Error::InvalidRequest(format!(
+            "The continuation token does not match the request! Token: {:?}, request: {:?}",
+            range_cursor.range_read, range_read
+        )));
+    }
+
+    // Verify the cursor is within the range
+    let next_position = range_cursor.next_position;
+    if next_position < start_position || next_position > end_position {
+        return Err(Error::InvalidRequest(format!(
+            "The continuation token is outside the requested range! Position: {}, range: [{}, {}]",
+            next_position, start_position, end_position
+        )));
+    }
+
+    Ok(next_position)
+}
+
+/// Creates the continuation token for the given range read, where
+/// `last_position` is the last index or version included in the response.
+/// If the range read is complete, no token is returned.
+pub fn create_continuation_token(
+    range_read: RangeRead,
+    last_position: u64,
+    end_position: u64,
+) -> Result<Option<ContinuationToken>, Error> {
+    // Check if the range read is complete
+    if last_position >= end_position {
+        return Ok(None);
+    }
+
+    // Serialize the cursor into the token
+    let range_cursor = RangeCursor {
+        range_read,
+        next_position: last_position + 1, // This cannot overflow (last_position < end_position)
+    };
+    let cursor_bytes = bcs::to_bytes(&range_cursor)
+        .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))?;
+    Ok(Some(ContinuationToken::new(cursor_bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use claims::assert_matches;
+
+    #[test]
+    fn test_continuation_token_round_trip() {
+        // Create a continuation token for a truncated range read
+        let range_read = RangeRead::StateValues {
+            version: 100,
+            end_index: 999,
+        };
+        let continuation_token = create_continuation_token(range_read.clone(), 499, 999)
+            .unwrap()
+            .unwrap();
+
+        // Verify the range read resumes after the last position
+        let start_position =
+            get_start_position(&range_read, 0, 999, Some(&continuation_token)).unwrap();
+        assert_eq!(start_position, 500);
+
+        // Verify no token is created once the range read is complete
+        assert!(create_continuation_token(range_read, 999, 999)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_invalid_continuation_token() {
+        // Create a continuation token for a truncated range read
+        let range_read = RangeRead::Transactions {
+            proof_version: 1000,
+            end_version: 200,
+            include_events: true,
+        };
+        let continuation_token = create_continuation_token(range_read.clone(), 149, 200)
+            .unwrap()
+            .unwrap();
+
+        // Verify the token is rejected for a different range read
+        let different_range_read = RangeRead::Transactions {
+            proof_version: 1000,
+            end_version: 200,
+            include_events: false,
+        };
+        let result = get_start_position(&different_range_read, 100, 200, Some(&continuation_token));
+        assert_matches!(result, Err(Error::InvalidRequest(_)));
+
+        // Verify the token is rejected if it's outside the requested range
+        let result = get_start_position(&range_read, 160, 200, Some(&continuation_token));
+        assert_matches!(result, Err(Error::InvalidRequest(_)));
+
+        // Verify malformed tokens are rejected
+        let malformed_token = ContinuationToken::new(vec![0xFF; 3]);
+        let result = get_start_position(&range_read, 100, 200, Some(&malformed_token));
+        assert_matches!(result, Err(Error::InvalidRequest(_)));
+    }
+}
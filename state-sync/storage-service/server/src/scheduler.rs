@@ -0,0 +1,182 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{error::Error, metrics};
+use aptos_bounded_executor::BoundedExecutor;
+use aptos_config::{
+    config::StorageServicePrioritySchedulingConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
+use std::time::Instant;
+use tokio::{runtime::Handle, sync::mpsc};
+
+// Useful label for the rejected request metrics
+const PENDING_REQUESTS_LIMIT_LABEL: &str = "pending_requests";
+
+/// The class of a peer (as determined by the network of the peer).
+/// Requests from validators and VFNs are consensus-critical (e.g.,
+/// block retrieval fallbacks and recent data), so they are served
+/// ahead of requests from public peers (e.g., bulk historical syncing).
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PeerClass {
+    Validator,
+    ValidatorFullnode,
+    Public,
+}
+
+impl PeerClass {
+    pub fn new(network_id: NetworkId) -> Self {
+        match network_id {
+            NetworkId::Validator => PeerClass::Validator,
+            NetworkId::Vfn => PeerClass::ValidatorFullnode,
+            NetworkId::Public => PeerClass::Public,
+        }
+    }
+
+    /// Returns a summary label for the peer class
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            PeerClass::Validator => "validator",
+            PeerClass::ValidatorFullnode => "vfn",
+            PeerClass::Public => "public",
+        }
+    }
+
+    /// Returns true iff requests from the peer class should be
+    /// served ahead of requests from other peer classes.
+    pub fn is_high_priority(&self) -> bool {
+        matches!(self, PeerClass::Validator | PeerClass::ValidatorFullnode)
+    }
+}
+
+/// A request task (and metadata) waiting to be scheduled
+struct ScheduledRequest {
+    peer_class: PeerClass,
+    scheduled_time: Instant,
+    task: Box<dyn FnOnce() + Send + 'static>,
+}
+
+/// The request scheduler queues requests by peer class, and dispatches
+/// them to the bounded executor (as capacity becomes available). Pending
+/// high priority requests are always dispatched before low priority ones.
+pub struct RequestScheduler {
+    high_priority_sender: mpsc::Sender<ScheduledRequest>,
+    low_priority_sender: mpsc::Sender<ScheduledRequest>,
+}
+
+impl RequestScheduler {
+    /// Creates a new request scheduler and spawns the request
+    /// dispatcher onto the given runtime.
+    pub fn new(
+        config: StorageServicePrioritySchedulingConfig,
+        bounded_executor: BoundedExecutor,
+        executor: &Handle,
+    ) -> Self {
+        let max_pending_requests = config.max_pending_requests_per_class.max(1) as usize;
+        let (high_priority_sender, high_priority_receiver) = mpsc::channel(max_pending_requests);
+        let (low_priority_sender, low_priority_receiver) = mpsc::channel(max_pending_requests);
+        executor.spawn(dispatch_scheduled_requests(
+            bounded_executor,
+            high_priority_receiver,
+            low_priority_receiver,
+        ));
+
+        Self {
+            high_priority_sender,
+            low_priority_sender,
+        }
+    }
+
+    /// Attempts to reserve a slot in the queue of the given peer's class.
+    /// If the queue is full, an error is returned. Otherwise, the returned
+    /// permit can be used to schedule the request task.
+    pub fn try_reserve(
+        &self,
+        peer_network_id: &PeerNetworkId,
+    ) -> Result<SchedulingPermit<'_>, Error> {
+        let peer_class = PeerClass::new(peer_network_id.network_id());
+        let sender = if peer_class.is_high_priority() {
+            &self.high_priority_sender
+        } else {
+            &self.low_priority_sender
+        };
+
+        match sender.try_reserve() {
+            Ok(permit) => Ok(SchedulingPermit { peer_class, permit }),
+            Err(error) => {
+                metrics::increment_counter(
+                    &metrics::REJECTED_CONCURRENT_REQUESTS,
+                    peer_network_id.network_id(),
+                    PENDING_REQUESTS_LIMIT_LABEL.into(),
+                );
+                Err(Error::TooManyConcurrentRequests(format!(
+                    "Unable to schedule the request for peer class: {:?}, error: {:?}",
+                    peer_class, error
+                )))
+            },
+        }
+    }
+}
+
+/// A reserved slot in the queue of a peer class
+pub struct SchedulingPermit<'a> {
+    peer_class: PeerClass,
+    permit: mpsc::Permit<'a, ScheduledRequest>,
+}
+
+impl SchedulingPermit<'_> {
+    /// Schedules the given task for execution on the bounded executor
+    pub fn schedule<F: FnOnce() + Send + 'static>(self, task: F) {
+        metrics::PENDING_SCHEDULED_REQUESTS
+            .with_label_values(&[self.peer_class.get_label()])
+            .inc();
+        self.permit.send(ScheduledRequest {
+            peer_class: self.peer_class,
+            scheduled_time: Instant::now(),
+            task: Box::new(task),
+        });
+    }
+}
+
+/// Continuously dispatches the scheduled requests to the bounded
+/// executor (prioritizing the high priority requests).
+async fn dispatch_scheduled_requests(
+    bounded_executor: BoundedExecutor,
+    mut high_priority_receiver: mpsc::Receiver<ScheduledRequest>,
+    mut low_priority_receiver: mpsc::Receiver<ScheduledRequest>,
+) {
+    loop {
+        let scheduled_request = tokio::select! {
+            biased;
+            Some(scheduled_request) = high_priority_receiver.recv() => scheduled_request,
+            Some(scheduled_request) = low_priority_receiver.recv() => scheduled_request,
+            else => break, // The scheduler has been dropped
+        };
+
+        // Wait for executor capacity and process the request
+        let ScheduledRequest {
+            peer_class,
+            scheduled_time,
+            task,
+        } = scheduled_request;
+        metrics::PENDING_SCHEDULED_REQUESTS
+            .with_label_values(&[peer_class.get_label()])
+            .dec();
+        bounded_executor
+            .spawn_blocking(move || {
+                let peer_class_label = peer_class.get_label().to_string();
+                metrics::observe_duration(
+                    &metrics::STORAGE_REQUEST_QUEUE_LATENCY,
+                    vec![peer_class_label.clone()],
+                    scheduled_time,
+                );
+                task();
+                metrics::observe_duration(
+                    &metrics::STORAGE_REQUEST_TOTAL_LATENCY,
+                    vec![peer_class_label],
+                    scheduled_time,
+                );
+            })
+            .await;
+    }
+}
@@ -0,0 +1,584 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    error::Error,
+    logging::{LogEntry, LogSchema},
+    metrics,
+};
+use aptos_logger::{error, info, warn};
+use aptos_types::{state_store::state_value::StateValueChunkWithProof, transaction::Version};
+use arc_swap::ArcSwapOption;
+use std::{
+    fs,
+    fs::File,
+    io::{BufWriter, Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// The name of the directory (in the storage directory) holding the artifacts
+pub const SNAPSHOT_ARTIFACT_DIRECTORY_NAME: &str = "state_snapshot_artifacts";
+
+// Useful constants for the snapshot artifact files
+const SNAPSHOT_ARTIFACT_FILE_EXTENSION: &str = "artifact";
+const SNAPSHOT_ARTIFACT_FILE_PREFIX: &str = "state_snapshot_";
+const SNAPSHOT_ARTIFACT_MAGIC: &[u8; 8] = b"APTSNAP1";
+const SNAPSHOT_ARTIFACT_TEMP_FILE_EXTENSION: &str = "tmp";
+
+// The sizes (bytes) of the artifact footer and chunk index entries
+const FOOTER_SIZE_BYTES: u64 = 32; // Version, index offset, number of chunks and magic
+const INDEX_ENTRY_SIZE_BYTES: u64 = 32; // First index, last index, offset and length
+
+/// A single entry in the chunk index of a snapshot artifact
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct ChunkIndexEntry {
+    first_index: u64, // The index of the first state value in the chunk
+    last_index: u64,  // The index of the last state value in the chunk
+    offset: u64,      // The offset (bytes) of the serialized chunk in the file
+    length: u64,      // The length (bytes) of the serialized chunk
+}
+
+impl ChunkIndexEntry {
+    /// Returns the number of state values in the chunk
+    fn num_state_values(&self) -> u64 {
+        self.last_index.saturating_sub(self.first_index) + 1
+    }
+}
+
+/// A snapshot artifact holds the BCS serialized state value chunks (with
+/// proofs) of the entire state at a single (epoch ending) version. The file
+/// layout is: the serialized chunks, followed by the chunk index, followed
+/// by a fixed size footer. The chunk index is held in memory, and chunks
+/// are read directly from the file (and thus served from the page cache).
+#[derive(Debug)]
+struct SnapshotArtifact {
+    chunk_index: Vec<ChunkIndexEntry>,
+    path: PathBuf,
+    version: Version,
+}
+
+impl SnapshotArtifact {
+    /// Opens the snapshot artifact at the given path and loads the chunk index
+    fn open(path: &Path) -> Result<Self, Error> {
+        let mut file = File::open(path).map_err(|error| {
+            Error::StorageErrorEncountered(format!(
+                "Failed to open the snapshot artifact at {:?}: {:?}",
+                path, error
+            ))
+        })?;
+
+        // Read and verify the footer
+        let file_length = file
+            .metadata()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?
+            .len();
+        if file_length < FOOTER_SIZE_BYTES {
+            return Err(Error::StorageErrorEncountered(format!(
+                "The snapshot artifact is too small! Path: {:?}, length: {}",
+                path, file_length
+            )));
+        }
+        let footer = read_bytes_at(
+            &mut file,
+            file_length - FOOTER_SIZE_BYTES,
+            FOOTER_SIZE_BYTES,
+        )?;
+        if &footer[24..32] != SNAPSHOT_ARTIFACT_MAGIC {
+            return Err(Error::StorageErrorEncountered(format!(
+                "The snapshot artifact has an invalid magic value! Path: {:?}",
+                path
+            )));
+        }
+        let version = read_u64(&footer, 0);
+        let index_offset = read_u64(&footer, 8);
+        let num_chunks = read_u64(&footer, 16);
+
+        // Read the chunk index
+        let index_length = num_chunks
+            .checked_mul(INDEX_ENTRY_SIZE_BYTES)
+            .filter(|index_length| {
+                index_offset
+                    .checked_add(*index_length)
+                    .and_then(|length| length.checked_add(FOOTER_SIZE_BYTES))
+                    == Some(file_length)
+            })
+            .ok_or_else(|| {
+                Error::StorageErrorEncountered(format!(
+                    "The snapshot artifact has an invalid chunk index! Path: {:?}",
+                    path
+                ))
+            })?;
+        let index_bytes = read_bytes_at(&mut file, index_offset, index_length)?;
+        let chunk_index = index_bytes
+            .chunks_exact(INDEX_ENTRY_SIZE_BYTES as usize)
+            .map(|entry_bytes| ChunkIndexEntry {
+                first_index: read_u64(entry_bytes, 0),
+                last_index: read_u64(entry_bytes, 8),
+                offset: read_u64(entry_bytes, 16),
+                length: read_u64(entry_bytes, 24),
+            })
+            .collect();
+
+        Ok(Self {
+            chunk_index,
+            path: path.to_path_buf(),
+            version,
+        })
+    }
+
+    /// Returns the chunk index entry that starts at `start_index` and
+    /// fits within the request (if one exists).
+    fn get_chunk_index_entry(
+        &self,
+        start_index: u64,
+        end_index: u64,
+        max_state_chunk_size: u64,
+        max_network_chunk_bytes: u64,
+    ) -> Option<ChunkIndexEntry> {
+        let position = self
+            .chunk_index
+            .binary_search_by_key(&start_index, |entry| entry.first_index)
+            .ok()?;
+        let entry = self.chunk_index[position];
+
+        // Only serve the chunk if it respects the request and the current limits
+        if entry.last_index <= end_index
+            && entry.num_state_values() <= max_state_chunk_size
+            && entry.length <= max_network_chunk_bytes
+        {
+            Some(entry)
+        } else {
+            None
+        }
+    }
+
+    /// Reads and deserializes the chunk for the given index entry
+    fn read_chunk(&self, entry: &ChunkIndexEntry) -> Result<StateValueChunkWithProof, Error> {
+        let mut file = File::open(&self.path)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let chunk_bytes = read_bytes_at(&mut file, entry.offset, entry.length)?;
+        bcs::from_bytes(&chunk_bytes).map_err(|error| {
+            Error::UnexpectedErrorEncountered(format!(
+                "Failed to deserialize the snapshot artifact chunk: {:?}",
+                error
+            ))
+        })
+    }
+}
+
+/// The snapshot artifact store manages the state snapshot artifact used to
+/// serve state value chunk requests. The artifact is (re)generated by a
+/// background task (see `StorageReaderInterface::refresh_snapshot_artifact`)
+/// and requests that cannot be served by the artifact (e.g., requests for
+/// other versions, or unaligned indices) fall back to the database.
+pub struct SnapshotArtifactStore {
+    artifact: ArcSwapOption<SnapshotArtifact>,
+    artifact_directory: PathBuf,
+}
+
+impl SnapshotArtifactStore {
+    /// Creates a new artifact store in the given directory and loads the
+    /// most recent artifact (if one exists).
+    pub fn new(artifact_directory: PathBuf) -> Self {
+        let store = Self {
+            artifact: ArcSwapOption::from(None),
+            artifact_directory,
+        };
+
+        // Load the latest existing artifact
+        match store.load_latest_artifact() {
+            Ok(Some(artifact)) => {
+                info!(LogSchema::new(LogEntry::SnapshotArtifact).message(&format!(
+                    "Loaded the existing snapshot artifact for version: {:?}",
+                    artifact.version
+                )));
+                store.update_artifact(artifact);
+            },
+            Ok(None) => {}, // No artifact exists yet
+            Err(error) => {
+                warn!(LogSchema::new(LogEntry::SnapshotArtifact)
+                    .error(&error)
+                    .message("Failed to load the existing snapshot artifact!"));
+            },
+        }
+
+        store
+    }
+
+    /// Returns the version of the current artifact (if one exists)
+    pub fn get_artifact_version(&self) -> Option<Version> {
+        self.artifact
+            .load()
+            .as_ref()
+            .map(|artifact| artifact.version)
+    }
+
+    /// Returns the state value chunk that starts at `start_index` for the
+    /// given version, if the chunk can be served by the current artifact.
+    /// Any errors are logged and `None` is returned (so that the caller
+    /// can fall back to the database).
+    pub fn get_state_value_chunk_with_proof(
+        &self,
+        version: Version,
+        start_index: u64,
+        end_index: u64,
+        max_state_chunk_size: u64,
+        max_network_chunk_bytes: u64,
+    ) -> Option<StateValueChunkWithProof> {
+        // Identify the chunk in the artifact
+        let artifact = self.artifact.load_full()?;
+        let chunk_index_entry = if artifact.version == version {
+            artifact.get_chunk_index_entry(
+                start_index,
+                end_index,
+                max_state_chunk_size,
+                max_network_chunk_bytes,
+            )
+        } else {
+            None
+        };
+        let chunk_index_entry = match chunk_index_entry {
+            Some(chunk_index_entry) => chunk_index_entry,
+            None => {
+                metrics::increment_snapshot_artifact_event(metrics::SNAPSHOT_ARTIFACT_MISS);
+                return None;
+            },
+        };
+
+        // Read the chunk from the artifact
+        match artifact.read_chunk(&chunk_index_entry) {
+            Ok(state_value_chunk_with_proof) => {
+                metrics::increment_snapshot_artifact_event(metrics::SNAPSHOT_ARTIFACT_HIT);
+                Some(state_value_chunk_with_proof)
+            },
+            Err(error) => {
+                metrics::increment_snapshot_artifact_event(metrics::SNAPSHOT_ARTIFACT_ERROR);
+                error!(LogSchema::new(LogEntry::SnapshotArtifact)
+                    .error(&error)
+                    .message("Failed to read a chunk from the snapshot artifact!"));
+                None
+            },
+        }
+    }
+
+    /// Writes a new artifact for the given version (holding `num_states`
+    /// state values) using the given chunk fetcher, and replaces the
+    /// current artifact. The chunk fetcher is expected to return the
+    /// chunk that starts at the given index.
+    pub fn write_artifact<F>(
+        &self,
+        version: Version,
+        num_states: u64,
+        fetch_chunk: F,
+    ) -> Result<(), Error>
+    where
+        F: FnMut(u64) -> Result<StateValueChunkWithProof, Error>,
+    {
+        // Write the artifact to a temporary file
+        fs::create_dir_all(&self.artifact_directory)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let artifact_path = self.get_artifact_path(version);
+        let temp_path = artifact_path.with_extension(SNAPSHOT_ARTIFACT_TEMP_FILE_EXTENSION);
+        if let Err(error) = write_artifact_file(&temp_path, version, num_states, fetch_chunk) {
+            let _ = fs::remove_file(&temp_path); // Clean up the partially written file
+            return Err(error);
+        }
+
+        // Move the artifact into place
+        fs::rename(&temp_path, &artifact_path)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+
+        // Update the current artifact and remove any stale artifacts
+        let artifact = SnapshotArtifact::open(&artifact_path)?;
+        self.update_artifact(artifact);
+        self.remove_stale_artifacts(version);
+
+        Ok(())
+    }
+
+    /// Returns the path of the artifact for the given version
+    fn get_artifact_path(&self, version: Version) -> PathBuf {
+        self.artifact_directory.join(format!(
+            "{}{}.{}",
+            SNAPSHOT_ARTIFACT_FILE_PREFIX, version, SNAPSHOT_ARTIFACT_FILE_EXTENSION
+        ))
+    }
+
+    /// Returns the versions of all artifacts in the artifact directory
+    fn get_artifact_versions(&self) -> Result<Vec<Version>, Error> {
+        if !self.artifact_directory.exists() {
+            return Ok(vec![]);
+        }
+
+        let directory_entries = fs::read_dir(&self.artifact_directory)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let versions = directory_entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .and_then(|file_name| file_name.strip_prefix(SNAPSHOT_ARTIFACT_FILE_PREFIX))
+                    .and_then(|file_name| {
+                        file_name.strip_suffix(&format!(".{}", SNAPSHOT_ARTIFACT_FILE_EXTENSION))
+                    })
+                    .and_then(|version| version.parse::<Version>().ok())
+            })
+            .collect();
+        Ok(versions)
+    }
+
+    /// Loads the artifact with the highest version (if one exists)
+    fn load_latest_artifact(&self) -> Result<Option<SnapshotArtifact>, Error> {
+        let latest_version = self.get_artifact_versions()?.into_iter().max();
+        latest_version
+            .map(|version| SnapshotArtifact::open(&self.get_artifact_path(version)))
+            .transpose()
+    }
+
+    /// Removes all artifacts not for the given version
+    fn remove_stale_artifacts(&self, current_version: Version) {
+        let stale_versions = match self.get_artifact_versions() {
+            Ok(versions) => versions,
+            Err(error) => {
+                warn!(LogSchema::new(LogEntry::SnapshotArtifact)
+                    .error(&error)
+                    .message("Failed to identify the stale snapshot artifacts!"));
+                return;
+            },
+        };
+
+        for version in stale_versions {
+            if version != current_version {
+                let artifact_path = self.get_artifact_path(version);
+                if let Err(error) = fs::remove_file(&artifact_path) {
+                    warn!(LogSchema::new(LogEntry::SnapshotArtifact).message(&format!(
+                        "Failed to remove the stale snapshot artifact at {:?}: {:?}",
+                        artifact_path, error
+                    )));
+                }
+            }
+        }
+    }
+
+    /// Updates the current artifact and the artifact metrics
+    fn update_artifact(&self, artifact: SnapshotArtifact) {
+        metrics::SNAPSHOT_ARTIFACT_VERSION.set(artifact.version as i64);
+        self.artifact.store(Some(Arc::new(artifact)));
+    }
+}
+
+/// Writes a snapshot artifact file for the given version at the path
+fn write_artifact_file<F>(
+    path: &Path,
+    version: Version,
+    num_states: u64,
+    mut fetch_chunk: F,
+) -> Result<(), Error>
+where
+    F: FnMut(u64) -> Result<StateValueChunkWithProof, Error>,
+{
+    let file =
+        File::create(path).map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+    let mut writer = BufWriter::new(file);
+
+    // Write each chunk (in order)
+    let mut chunk_index = vec![];
+    let mut offset = 0;
+    let mut next_index = 0;
+    while next_index < num_states {
+        let state_value_chunk_with_proof = fetch_chunk(next_index)?;
+        if state_value_chunk_with_proof.first_index != next_index
+            || state_value_chunk_with_proof.last_index < next_index
+        {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Unexpected chunk fetched for the snapshot artifact! Expected first index: {}, \
+                    found: [{}, {}]",
+                next_index,
+                state_value_chunk_with_proof.first_index,
+                state_value_chunk_with_proof.last_index
+            )));
+        }
+
+        // Serialize and write the chunk
+        let chunk_bytes = bcs::to_bytes(&state_value_chunk_with_proof)
+            .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))?;
+        write_bytes(&mut writer, &chunk_bytes)?;
+        chunk_index.push(ChunkIndexEntry {
+            first_index: state_value_chunk_with_proof.first_index,
+            last_index: state_value_chunk_with_proof.last_index,
+            offset,
+            length: chunk_bytes.len() as u64,
+        });
+
+        offset += chunk_bytes.len() as u64;
+        next_index = state_value_chunk_with_proof.last_index + 1;
+    }
+
+    // Write the chunk index and the footer
+    for entry in chunk_index.iter() {
+        for value in [
+            entry.first_index,
+            entry.last_index,
+            entry.offset,
+            entry.length,
+        ] {
+            write_bytes(&mut writer, &value.to_le_bytes())?;
+        }
+    }
+    for value in [version, offset, chunk_index.len() as u64] {
+        write_bytes(&mut writer, &value.to_le_bytes())?;
+    }
+    write_bytes(&mut writer, SNAPSHOT_ARTIFACT_MAGIC)?;
+
+    // Persist the artifact
+    let file = writer
+        .into_inner()
+        .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+    file.sync_all()
+        .map_err(|error| Error::StorageErrorEncountered(error.to_string()))
+}
+
+/// Reads `length` bytes from the file at the given offset
+fn read_bytes_at(file: &mut File, offset: u64, length: u64) -> Result<Vec<u8>, Error> {
+    let mut bytes = vec![0; length as usize];
+    file.seek(SeekFrom::Start(offset))
+        .and_then(|_| file.read_exact(&mut bytes))
+        .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+    Ok(bytes)
+}
+
+/// Reads a little endian u64 from the bytes at the given offset
+fn read_u64(bytes: &[u8], offset: usize) -> u64 {
+    let mut value_bytes = [0; 8];
+    value_bytes.copy_from_slice(&bytes[offset..offset + 8]);
+    u64::from_le_bytes(value_bytes)
+}
+
+/// Writes the given bytes to the writer
+fn write_bytes<W: Write>(writer: &mut W, bytes: &[u8]) -> Result<(), Error> {
+    writer
+        .write_all(bytes)
+        .map_err(|error| Error::StorageErrorEncountered(error.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::HashValue;
+    use aptos_temppath::TempPath;
+    use aptos_types::proof::SparseMerkleRangeProof;
+
+    #[test]
+    fn test_write_and_serve_artifact() {
+        // Create an artifact store
+        let artifact_directory = TempPath::new();
+        artifact_directory.create_as_dir().unwrap();
+        let artifact_store = SnapshotArtifactStore::new(artifact_directory.path().to_path_buf());
+        assert_eq!(artifact_store.get_artifact_version(), None);
+
+        // Write an artifact with chunks of 10 state values
+        let version = 100;
+        let num_states = 35;
+        artifact_store
+            .write_artifact(version, num_states, |start_index| {
+                Ok(create_state_value_chunk(
+                    start_index,
+                    (start_index + 9).min(num_states - 1),
+                ))
+            })
+            .unwrap();
+        assert_eq!(artifact_store.get_artifact_version(), Some(version));
+
+        // Verify aligned requests are served by the artifact
+        for start_index in [0, 10, 20, 30] {
+            let state_value_chunk_with_proof = artifact_store
+                .get_state_value_chunk_with_proof(version, start_index, 1000, 1000, u64::MAX)
+                .unwrap();
+            assert_eq!(state_value_chunk_with_proof.first_index, start_index);
+            assert_eq!(
+                state_value_chunk_with_proof.last_index,
+                (start_index + 9).min(num_states - 1)
+            );
+        }
+
+        // Verify unaligned, truncated and other version requests are not served
+        assert!(artifact_store
+            .get_state_value_chunk_with_proof(version, 5, 1000, 1000, u64::MAX)
+            .is_none());
+        assert!(artifact_store
+            .get_state_value_chunk_with_proof(version, 10, 15, 1000, u64::MAX)
+            .is_none());
+        assert!(artifact_store
+            .get_state_value_chunk_with_proof(version + 1, 0, 1000, 1000, u64::MAX)
+            .is_none());
+
+        // Verify chunks that exceed the current limits are not served
+        assert!(artifact_store
+            .get_state_value_chunk_with_proof(version, 0, 1000, 5, u64::MAX)
+            .is_none());
+        assert!(artifact_store
+            .get_state_value_chunk_with_proof(version, 0, 1000, 1000, 1)
+            .is_none());
+    }
+
+    #[test]
+    fn test_artifact_reload_and_replacement() {
+        // Create an artifact store and write an artifact
+        let artifact_directory = TempPath::new();
+        artifact_directory.create_as_dir().unwrap();
+        let artifact_store = SnapshotArtifactStore::new(artifact_directory.path().to_path_buf());
+        artifact_store
+            .write_artifact(10, 5, |start_index| {
+                Ok(create_state_value_chunk(start_index, 4))
+            })
+            .unwrap();
+
+        // Verify a new store loads the existing artifact
+        let artifact_store = SnapshotArtifactStore::new(artifact_directory.path().to_path_buf());
+        assert_eq!(artifact_store.get_artifact_version(), Some(10));
+        assert!(artifact_store
+            .get_state_value_chunk_with_proof(10, 0, 4, 10, u64::MAX)
+            .is_some());
+
+        // Write an artifact for a new version and verify the old one is removed
+        artifact_store
+            .write_artifact(20, 5, |start_index| {
+                Ok(create_state_value_chunk(start_index, 4))
+            })
+            .unwrap();
+        assert_eq!(artifact_store.get_artifact_versions().unwrap(), vec![20]);
+        assert!(artifact_store
+            .get_state_value_chunk_with_proof(10, 0, 4, 10, u64::MAX)
+            .is_none());
+    }
+
+    #[test]
+    fn test_write_artifact_invalid_chunk() {
+        // Create an artifact store
+        let artifact_directory = TempPath::new();
+        artifact_directory.create_as_dir().unwrap();
+        let artifact_store = SnapshotArtifactStore::new(artifact_directory.path().to_path_buf());
+
+        // Verify that writing an artifact with a gap fails
+        let result = artifact_store.write_artifact(10, 10, |start_index| {
+            Ok(create_state_value_chunk(start_index + 1, start_index + 5))
+        });
+        assert!(result.is_err());
+        assert_eq!(artifact_store.get_artifact_version(), None);
+    }
+
+    /// Creates a state value chunk for the given index range
+    fn create_state_value_chunk(first_index: u64, last_index: u64) -> StateValueChunkWithProof {
+        StateValueChunkWithProof {
+            first_index,
+            last_index,
+            first_key: HashValue::random(),
+            last_key: HashValue::random(),
+            raw_values: vec![],
+            proof: SparseMerkleRangeProof::new(vec![]),
+            root_hash: HashValue::random(),
+        }
+    }
+}
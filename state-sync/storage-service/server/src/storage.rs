@@ -1,21 +1,37 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{error::Error, metrics::increment_network_frame_overflow};
+use crate::{
+    error::Error, metrics::increment_network_frame_overflow,
+    snapshot_artifact::SnapshotArtifactStore,
+};
 use aptos_config::config::StorageServiceConfig;
 use aptos_logger::debug;
-use aptos_storage_interface::{AptosDbError, DbReader, Result as StorageResult};
+use aptos_storage_interface::{
+    read_context::{ReadContext, ReadSubsystem},
+    AptosDbError, DbReader, Order, Result as StorageResult,
+};
 use aptos_storage_service_types::responses::{
-    CompleteDataRange, DataResponse, DataSummary, TransactionOrOutputListWithProof,
+    CompleteDataRange, DataResponse, DataSummary, DataWindows, EventsByKeyWithProof,
+    StateValueChangesWithProof, TransactionEventsWithProof, TransactionOrOutputListWithProof,
 };
 use aptos_types::{
+    contract_event::EventWithVersion,
     epoch_change::EpochChangeProof,
+    event::EventKey,
     ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
-    transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
+    proof::SparseMerkleProof,
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValue, StateValueChunkWithProof},
+    },
+    transaction::{
+        TransactionListWithProof, TransactionOutputListWithProof, TransactionWithProof, Version,
+    },
+    write_set::WriteSet,
 };
 use serde::Serialize;
-use std::{cmp::min, sync::Arc};
+use std::{cmp::min, collections::BTreeSet, path::PathBuf, sync::Arc};
 
 /// The interface into local storage (e.g., the Aptos DB) used by the storage
 /// server to handle client requests and responses.
@@ -23,6 +39,10 @@ pub trait StorageReaderInterface: Clone + Send + 'static {
     /// Returns a data summary of the underlying storage state.
     fn get_data_summary(&self) -> aptos_storage_service_types::Result<DataSummary, Error>;
 
+    /// Returns the data windows retained by the underlying storage (i.e.,
+    /// the number of versions held for each data type before pruning).
+    fn get_data_windows(&self) -> aptos_storage_service_types::Result<DataWindows, Error>;
+
     /// Returns a list of transactions with a proof relative to the
     /// `proof_version`. The transaction list is expected to start at
     /// `start_version` and end at `end_version` (inclusive). In some cases,
@@ -89,6 +109,42 @@ pub trait StorageReaderInterface: Clone + Send + 'static {
         start_index: u64,
         end_index: u64,
     ) -> aptos_storage_service_types::Result<StateValueChunkWithProof, Error>;
+
+    /// Refreshes the state snapshot artifact used to serve state value
+    /// chunks (if snapshot artifacts are enabled). If a newer epoch ending
+    /// version is available, a new artifact is generated for it (and the
+    /// version of the new artifact is returned).
+    fn refresh_snapshot_artifact(
+        &self,
+    ) -> aptos_storage_service_types::Result<Option<Version>, Error>;
+
+    /// Returns the state values that changed between `start_version`
+    /// (exclusive) and `end_version` (inclusive), each with a proof against
+    /// the state root at `end_version`. The transaction info at `end_version`
+    /// is proven relative to the `proof_version`. Unlike other requests, the
+    /// response cannot be truncated: if the changes exceed the chunk or
+    /// network limits, an error is returned (and the client is expected to
+    /// fall back to transaction replay or a full state snapshot).
+    fn get_state_value_changes_with_proof(
+        &self,
+        proof_version: u64,
+        start_version: u64,
+        end_version: u64,
+    ) -> aptos_storage_service_types::Result<StateValueChangesWithProof, Error>;
+
+    /// Returns the events emitted for the given `event_key`, starting at
+    /// `start_sequence_number` and ending at `end_sequence_number` (inclusive).
+    /// Each event is returned with all events emitted by the same transaction
+    /// and a proof of the transaction info relative to the `proof_version`.
+    /// In some cases, less events may be returned (e.g., due to network or
+    /// chunk limits, or if fewer events have been emitted).
+    fn get_events_by_key_with_proof(
+        &self,
+        event_key: &EventKey,
+        start_sequence_number: u64,
+        end_sequence_number: u64,
+        proof_version: u64,
+    ) -> aptos_storage_service_types::Result<EventsByKeyWithProof, Error>;
 }
 
 /// The underlying implementation of the StorageReaderInterface, used by the
@@ -97,6 +153,7 @@ pub trait StorageReaderInterface: Clone + Send + 'static {
 pub struct StorageReader {
     config: StorageServiceConfig,
     storage: Arc<dyn DbReader>,
+    snapshot_artifact_store: Option<Arc<SnapshotArtifactStore>>,
 }
 
 impl StorageReader {
@@ -104,7 +161,19 @@ impl StorageReader {
         // Create a timed storage reader
         let storage = Arc::new(TimedStorageReader::new(storage));
 
-        Self { config, storage }
+        Self {
+            config,
+            storage,
+            snapshot_artifact_store: None,
+        }
+    }
+
+    /// Serves state value chunks from the precomputed snapshot artifacts
+    /// held in the given directory (where new artifacts are also written).
+    pub fn with_snapshot_artifact_store(mut self, artifact_directory: PathBuf) -> Self {
+        self.snapshot_artifact_store =
+            Some(Arc::new(SnapshotArtifactStore::new(artifact_directory)));
+        self
     }
 
     /// Returns the state values range held in the database (lowest to highest).
@@ -148,6 +217,88 @@ impl StorageReader {
         Ok(*transactions_range)
     }
 
+    /// Returns the version of the latest epoch ending ledger info (if any)
+    fn fetch_latest_epoch_ending_version(
+        &self,
+    ) -> aptos_storage_service_types::Result<Option<Version>, Error> {
+        // If the latest ledger info ends the epoch, use it
+        let latest_ledger_info_with_sigs = self
+            .storage
+            .get_latest_ledger_info()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let latest_ledger_info = latest_ledger_info_with_sigs.ledger_info();
+        if latest_ledger_info.ends_epoch() {
+            return Ok(Some(latest_ledger_info.version()));
+        }
+
+        // Otherwise, fetch the ledger info that ended the previous epoch
+        let current_epoch = latest_ledger_info.epoch();
+        if current_epoch == 0 {
+            return Ok(None);
+        }
+        let epoch_change_proof = self
+            .storage
+            .get_epoch_ending_ledger_infos(current_epoch - 1, current_epoch)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        Ok(epoch_change_proof
+            .ledger_info_with_sigs
+            .last()
+            .map(|ledger_info_with_sigs| ledger_info_with_sigs.ledger_info().version()))
+    }
+
+    /// Returns a chunk holding a list of state values (fetched from the
+    /// database) starting at `start_index` and ending at `end_index`.
+    fn fetch_state_value_chunk_with_proof(
+        &self,
+        version: u64,
+        start_index: u64,
+        end_index: u64,
+    ) -> aptos_storage_service_types::Result<StateValueChunkWithProof, Error> {
+        // Calculate the number of state values to fetch
+        let expected_num_state_values = inclusive_range_len(start_index, end_index)?;
+        let max_num_state_values = self.config.max_state_chunk_size;
+        let mut num_state_values_to_fetch = min(expected_num_state_values, max_num_state_values);
+
+        // Attempt to serve the request
+        while num_state_values_to_fetch >= 1 {
+            let state_value_chunk_with_proof = self
+                .storage
+                .get_state_value_chunk_with_proof(
+                    version,
+                    start_index as usize,
+                    num_state_values_to_fetch as usize,
+                )
+                .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+            if num_state_values_to_fetch == 1 {
+                return Ok(state_value_chunk_with_proof); // We cannot return less than a single item
+            }
+
+            // Attempt to divide up the request if it overflows the message size
+            let (overflow_frame, num_bytes) = check_overflow_network_frame(
+                &state_value_chunk_with_proof,
+                self.config.max_network_chunk_bytes,
+            )?;
+            if !overflow_frame {
+                return Ok(state_value_chunk_with_proof);
+            } else {
+                increment_network_frame_overflow(
+                    DataResponse::StateValueChunkWithProof(state_value_chunk_with_proof)
+                        .get_label(),
+                );
+                let new_num_state_values_to_fetch = num_state_values_to_fetch / 2;
+                debug!("The request for {:?} state values was too large (num bytes: {:?}). Retrying with {:?}.",
+                    num_state_values_to_fetch, num_bytes, new_num_state_values_to_fetch);
+                num_state_values_to_fetch = new_num_state_values_to_fetch; // Try again with half the amount of data
+            }
+        }
+
+        Err(Error::UnexpectedErrorEncountered(format!(
+            "Unable to serve the get_state_value_chunk_with_proof request! Version: {:?}, \
+            start index: {:?}, end index: {:?}. The data cannot fit into a single network frame!",
+            version, start_index, end_index
+        )))
+    }
+
     /// Returns the transaction range held in the database (lowest to highest).
     fn fetch_transaction_range(
         &self,
@@ -184,6 +335,59 @@ impl StorageReader {
             Ok(None)
         }
     }
+
+    /// Fetches the given number of events for the event key (starting at the
+    /// specified sequence number), along with all events and the transaction
+    /// info proofs of the transactions that emitted them.
+    fn fetch_events_by_key_with_proof(
+        &self,
+        event_key: &EventKey,
+        start_sequence_number: u64,
+        num_events: u64,
+        proof_version: u64,
+    ) -> aptos_storage_service_types::Result<EventsByKeyWithProof, Error> {
+        // Fetch the events and identify the versions that emitted them
+        let events = self
+            .storage
+            .get_events(
+                event_key,
+                start_sequence_number,
+                Order::Ascending,
+                num_events,
+                proof_version,
+            )
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let versions: BTreeSet<_> = events
+            .iter()
+            .map(|event| event.transaction_version)
+            .collect();
+
+        // Fetch the events and transaction info (with a proof) for each version
+        let mut transaction_events = vec![];
+        for version in versions {
+            let transaction_with_proof = self
+                .storage
+                .get_transaction_by_version(version, proof_version, true)
+                .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+            let events = transaction_with_proof.events.ok_or_else(|| {
+                Error::StorageErrorEncountered(format!(
+                    "The events are missing for the transaction at version: {:?}",
+                    version
+                ))
+            })?;
+            transaction_events.push(TransactionEventsWithProof {
+                version,
+                events,
+                transaction_info_with_proof: transaction_with_proof.proof,
+            });
+        }
+
+        Ok(EventsByKeyWithProof {
+            event_key: *event_key,
+            start_sequence_number,
+            transaction_events,
+        })
+    }
 }
 
 impl StorageReaderInterface for StorageReader {
@@ -229,6 +433,44 @@ impl StorageReaderInterface for StorageReader {
         Ok(data_summary)
     }
 
+    fn get_data_windows(&self) -> aptos_storage_service_types::Result<DataWindows, Error> {
+        // Fetch the ledger window (if the ledger pruner is enabled)
+        let ledger_pruner_enabled = self
+            .storage
+            .is_ledger_pruner_enabled()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let ledger_window = if ledger_pruner_enabled {
+            let ledger_prune_window = self
+                .storage
+                .get_ledger_prune_window()
+                .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+            Some(ledger_prune_window as u64)
+        } else {
+            None
+        };
+
+        // Fetch the state window (if the state pruner is enabled). Note: this
+        // matches the window used to calculate the state values range.
+        let state_pruner_enabled = self
+            .storage
+            .is_state_merkle_pruner_enabled()
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let state_window = if state_pruner_enabled {
+            let state_prune_window = self
+                .storage
+                .get_epoch_snapshot_prune_window()
+                .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+            Some(state_prune_window as u64)
+        } else {
+            None
+        };
+
+        Ok(DataWindows {
+            ledger_window,
+            state_window,
+        })
+    }
+
     fn get_transactions_with_proof(
         &self,
         proof_version: u64,
@@ -454,53 +696,202 @@ impl StorageReaderInterface for StorageReader {
         start_index: u64,
         end_index: u64,
     ) -> aptos_storage_service_types::Result<StateValueChunkWithProof, Error> {
-        // Calculate the number of state values to fetch
-        let expected_num_state_values = inclusive_range_len(start_index, end_index)?;
-        let max_num_state_values = self.config.max_state_chunk_size;
-        let mut num_state_values_to_fetch = min(expected_num_state_values, max_num_state_values);
-
-        // Attempt to serve the request
-        while num_state_values_to_fetch >= 1 {
-            let state_value_chunk_with_proof = self
-                .storage
+        // Attempt to serve the request from the snapshot artifact
+        if let Some(snapshot_artifact_store) = &self.snapshot_artifact_store {
+            if let Some(state_value_chunk_with_proof) = snapshot_artifact_store
                 .get_state_value_chunk_with_proof(
                     version,
-                    start_index as usize,
-                    num_state_values_to_fetch as usize,
+                    start_index,
+                    end_index,
+                    self.config.max_state_chunk_size,
+                    self.config.max_network_chunk_bytes,
                 )
+            {
+                return Ok(state_value_chunk_with_proof);
+            }
+        }
+
+        // Otherwise, fetch the state values from the database
+        self.fetch_state_value_chunk_with_proof(version, start_index, end_index)
+    }
+
+    fn refresh_snapshot_artifact(
+        &self,
+    ) -> aptos_storage_service_types::Result<Option<Version>, Error> {
+        // If snapshot artifacts are disabled, there's nothing to do
+        let snapshot_artifact_store = match &self.snapshot_artifact_store {
+            Some(snapshot_artifact_store) => snapshot_artifact_store,
+            None => return Ok(None),
+        };
+
+        // Identify the latest epoch ending version (and check if the artifact is stale)
+        let version = match self.fetch_latest_epoch_ending_version()? {
+            Some(version) => version,
+            None => return Ok(None), // The genesis epoch hasn't ended yet
+        };
+        if snapshot_artifact_store.get_artifact_version() == Some(version) {
+            return Ok(None); // The artifact is already up-to-date
+        }
+
+        // Generate the new artifact from the database
+        let num_states = self.get_number_of_states(version)?;
+        let end_index = num_states.saturating_sub(1);
+        snapshot_artifact_store.write_artifact(version, num_states, |start_index| {
+            self.fetch_state_value_chunk_with_proof(version, start_index, end_index)
+        })?;
+
+        Ok(Some(version))
+    }
+
+    fn get_state_value_changes_with_proof(
+        &self,
+        proof_version: u64,
+        start_version: u64,
+        end_version: u64,
+    ) -> aptos_storage_service_types::Result<StateValueChangesWithProof, Error> {
+        // Verify the number of versions to process is within the chunk limits
+        let first_version = start_version.checked_add(1).ok_or_else(|| {
+            Error::InvalidRequest(format!(
+                "start version ({}) must not be u64::MAX",
+                start_version
+            ))
+        })?;
+        let num_versions = inclusive_range_len(first_version, end_version)?;
+        let max_num_versions = self.config.max_transaction_output_chunk_size;
+        if num_versions > max_num_versions {
+            return Err(Error::InvalidRequest(format!(
+                "The number of versions to process ({}) exceeds the max chunk size ({})!",
+                num_versions, max_num_versions
+            )));
+        }
+        if end_version > proof_version {
+            return Err(Error::InvalidRequest(format!(
+                "end version ({}) must be <= proof version ({})",
+                end_version, proof_version
+            )));
+        }
+
+        // Identify the state keys that changed in the version range
+        let mut changed_state_keys = BTreeSet::new();
+        let write_set_iterator = self
+            .storage
+            .get_write_set_iterator(first_version, num_versions)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        for write_set in write_set_iterator {
+            let write_set =
+                write_set.map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+            changed_state_keys.extend(write_set.iter().map(|(state_key, _)| state_key.clone()));
+        }
+
+        // Verify the number of changed state values is within the chunk limits
+        let num_changed_state_keys = changed_state_keys.len() as u64;
+        let max_num_state_values = self.config.max_state_chunk_size;
+        if num_changed_state_keys > max_num_state_values {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Unable to serve the get_state_value_changes_with_proof request! The number \
+                of changed state values ({}) exceeds the max chunk size ({})!",
+                num_changed_state_keys, max_num_state_values
+            )));
+        }
+
+        // Fetch the state value changes (with proofs) at the end version
+        let mut state_value_changes = vec![];
+        for state_key in changed_state_keys {
+            let (state_value, proof) = self
+                .storage
+                .get_state_value_with_proof_by_version(&state_key, end_version)
                 .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
-            if num_state_values_to_fetch == 1 {
-                return Ok(state_value_chunk_with_proof); // We cannot return less than a single item
+            state_value_changes.push((state_key, state_value, proof));
+        }
+
+        // Fetch the transaction info (with a proof) at the end version
+        let transaction_with_proof = self
+            .storage
+            .get_transaction_by_version(end_version, proof_version, false)
+            .map_err(|error| Error::StorageErrorEncountered(error.to_string()))?;
+        let state_value_changes_with_proof = StateValueChangesWithProof {
+            start_version,
+            end_version,
+            state_value_changes,
+            transaction_info_with_proof: transaction_with_proof.proof,
+        };
+
+        // Verify the response fits into a single network frame
+        let (overflow_frame, num_bytes) = check_overflow_network_frame(
+            &state_value_changes_with_proof,
+            self.config.max_network_chunk_bytes,
+        )?;
+        if overflow_frame {
+            increment_network_frame_overflow(
+                DataResponse::StateValueChangesWithProof(state_value_changes_with_proof)
+                    .get_label(),
+            );
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Unable to serve the get_state_value_changes_with_proof request! Start version: \
+                {:?}, end version: {:?}. The data cannot fit into a single network frame (num \
+                bytes: {:?})!",
+                start_version, end_version, num_bytes
+            )));
+        }
+
+        Ok(state_value_changes_with_proof)
+    }
+
+    fn get_events_by_key_with_proof(
+        &self,
+        event_key: &EventKey,
+        start_sequence_number: u64,
+        end_sequence_number: u64,
+        proof_version: u64,
+    ) -> aptos_storage_service_types::Result<EventsByKeyWithProof, Error> {
+        // Calculate the number of events to fetch. Each event may have been
+        // emitted by a different transaction, so we bound the number of events
+        // by the transaction chunk size.
+        let expected_num_events = inclusive_range_len(start_sequence_number, end_sequence_number)?;
+        let max_num_events = self.config.max_transaction_chunk_size;
+        let mut num_events_to_fetch = min(expected_num_events, max_num_events);
+
+        // Attempt to serve the request
+        while num_events_to_fetch >= 1 {
+            let events_by_key_with_proof = self.fetch_events_by_key_with_proof(
+                event_key,
+                start_sequence_number,
+                num_events_to_fetch,
+                proof_version,
+            )?;
+            if num_events_to_fetch == 1 {
+                return Ok(events_by_key_with_proof); // We cannot return less than a single item
             }
 
             // Attempt to divide up the request if it overflows the message size
             let (overflow_frame, num_bytes) = check_overflow_network_frame(
-                &state_value_chunk_with_proof,
+                &events_by_key_with_proof,
                 self.config.max_network_chunk_bytes,
             )?;
             if !overflow_frame {
-                return Ok(state_value_chunk_with_proof);
+                return Ok(events_by_key_with_proof);
             } else {
                 increment_network_frame_overflow(
-                    DataResponse::StateValueChunkWithProof(state_value_chunk_with_proof)
-                        .get_label(),
+                    DataResponse::EventsByKeyWithProof(events_by_key_with_proof).get_label(),
                 );
-                let new_num_state_values_to_fetch = num_state_values_to_fetch / 2;
-                debug!("The request for {:?} state values was too large (num bytes: {:?}). Retrying with {:?}.",
-                    num_state_values_to_fetch, num_bytes, new_num_state_values_to_fetch);
-                num_state_values_to_fetch = new_num_state_values_to_fetch; // Try again with half the amount of data
+                let new_num_events_to_fetch = num_events_to_fetch / 2;
+                debug!("The request for {:?} events was too large (num bytes: {:?}). Retrying with {:?}.",
+                    num_events_to_fetch, num_bytes, new_num_events_to_fetch);
+                num_events_to_fetch = new_num_events_to_fetch; // Try again with half the amount of data
             }
         }
 
         Err(Error::UnexpectedErrorEncountered(format!(
-            "Unable to serve the get_state_value_chunk_with_proof request! Version: {:?}, \
-            start index: {:?}, end index: {:?}. The data cannot fit into a single network frame!",
-            version, start_index, end_index
+            "Unable to serve the get_events_by_key_with_proof request! Event key: {:?}, start \
+            sequence number: {:?}, end sequence number: {:?}. The data cannot fit into a single \
+            network frame!",
+            event_key, start_sequence_number, end_sequence_number
         )))
     }
 }
 
-// A simple macro that wraps each storage read call with a timer
+// A simple macro that wraps each storage read call with a timer (and
+// attributes all reads to the storage service).
 macro_rules! timed_read {
     ($(
         $(#[$($attr:meta)*])*
@@ -510,6 +901,7 @@ macro_rules! timed_read {
             $(#[$($attr)*])*
             fn $name(&self, $($arg: $ty),*) -> $return_type {
                 let read_operation = || {
+                    let _read_context = ReadContext::enter(ReadSubsystem::StorageService);
                     self.storage.$name($($arg),*).map_err(|e| e.into())
                 };
                 let result = crate::utils::execute_and_time_duration(
@@ -543,6 +935,10 @@ impl DbReader for TimedStorageReader {
 
         fn get_epoch_snapshot_prune_window(&self) -> StorageResult<usize>;
 
+        fn is_ledger_pruner_enabled(&self) -> StorageResult<bool>;
+
+        fn get_ledger_prune_window(&self) -> StorageResult<usize>;
+
         fn get_first_txn_version(&self) -> StorageResult<Option<Version>>;
 
         fn get_first_write_set_version(&self) -> StorageResult<Option<Version>>;
@@ -578,6 +974,34 @@ impl DbReader for TimedStorageReader {
             start_idx: usize,
             chunk_size: usize,
         ) -> StorageResult<StateValueChunkWithProof>;
+
+        fn get_write_set_iterator(
+            &self,
+            start_version: Version,
+            limit: u64,
+        ) -> StorageResult<Box<dyn Iterator<Item = StorageResult<WriteSet>> + '_>>;
+
+        fn get_state_value_with_proof_by_version(
+            &self,
+            state_key: &StateKey,
+            version: Version,
+        ) -> StorageResult<(Option<StateValue>, SparseMerkleProof)>;
+
+        fn get_transaction_by_version(
+            &self,
+            version: Version,
+            ledger_version: Version,
+            fetch_events: bool,
+        ) -> StorageResult<TransactionWithProof>;
+
+        fn get_events(
+            &self,
+            event_key: &EventKey,
+            start: u64,
+            order: Order,
+            limit: u64,
+            ledger_version: Version,
+        ) -> StorageResult<Vec<EventWithVersion>>;
     );
 }
 
@@ -20,7 +20,7 @@ use aptos_infallible::Mutex;
 use aptos_logger::{error, warn};
 use aptos_storage_service_types::{
     requests::{
-        DataRequest, StorageServiceRequest, SubscriptionStreamMetadata,
+        DataRequest, EventFilter, StorageServiceRequest, SubscriptionStreamMetadata,
         TransactionOutputsWithProofRequest, TransactionsOrOutputsWithProofRequest,
         TransactionsWithProofRequest,
     },
@@ -96,7 +96,8 @@ impl SubscriptionRequest {
 
         // Create the storage request
         let data_request = match &self.request.data_request {
-            DataRequest::SubscribeTransactionOutputsWithProof(_) => {
+            DataRequest::SubscribeTransactionOutputsWithProof(_)
+            | DataRequest::SubscribeFilteredTransactionOutputsWithProof(_) => {
                 DataRequest::GetTransactionOutputsWithProof(TransactionOutputsWithProofRequest {
                     proof_version: target_version,
                     start_version,
@@ -125,7 +126,8 @@ impl SubscriptionRequest {
             request => unreachable!("Unexpected subscription request: {:?}", request),
         };
         let storage_request =
-            StorageServiceRequest::new(data_request, self.request.use_compression);
+            StorageServiceRequest::new(data_request, self.request.use_compression)
+                .with_compression_codec(self.request.compression_codec);
         Ok(storage_request)
     }
 
@@ -137,6 +139,11 @@ impl SubscriptionRequest {
                     .subscription_stream_metadata
                     .known_version_at_stream_start
             },
+            DataRequest::SubscribeFilteredTransactionOutputsWithProof(request) => {
+                request
+                    .subscription_stream_metadata
+                    .known_version_at_stream_start
+            },
             DataRequest::SubscribeTransactionsWithProof(request) => {
                 request
                     .subscription_stream_metadata
@@ -159,6 +166,11 @@ impl SubscriptionRequest {
                     .subscription_stream_metadata
                     .known_epoch_at_stream_start
             },
+            DataRequest::SubscribeFilteredTransactionOutputsWithProof(request) => {
+                request
+                    .subscription_stream_metadata
+                    .known_epoch_at_stream_start
+            },
             DataRequest::SubscribeTransactionsWithProof(request) => {
                 request
                     .subscription_stream_metadata
@@ -177,7 +189,8 @@ impl SubscriptionRequest {
     /// depending on the request type.
     fn max_chunk_size_for_request(&self, config: StorageServiceConfig) -> u64 {
         match &self.request.data_request {
-            DataRequest::SubscribeTransactionOutputsWithProof(_) => {
+            DataRequest::SubscribeTransactionOutputsWithProof(_)
+            | DataRequest::SubscribeFilteredTransactionOutputsWithProof(_) => {
                 config.max_transaction_output_chunk_size
             },
             DataRequest::SubscribeTransactionsWithProof(_) => config.max_transaction_chunk_size,
@@ -194,6 +207,9 @@ impl SubscriptionRequest {
             DataRequest::SubscribeTransactionOutputsWithProof(request) => {
                 request.subscription_stream_metadata.subscription_stream_id
             },
+            DataRequest::SubscribeFilteredTransactionOutputsWithProof(request) => {
+                request.subscription_stream_metadata.subscription_stream_id
+            },
             DataRequest::SubscribeTransactionsWithProof(request) => {
                 request.subscription_stream_metadata.subscription_stream_id
             },
@@ -210,6 +226,9 @@ impl SubscriptionRequest {
             DataRequest::SubscribeTransactionOutputsWithProof(request) => {
                 request.subscription_stream_index
             },
+            DataRequest::SubscribeFilteredTransactionOutputsWithProof(request) => {
+                request.subscription_stream_index
+            },
             DataRequest::SubscribeTransactionsWithProof(request) => {
                 request.subscription_stream_index
             },
@@ -226,6 +245,9 @@ impl SubscriptionRequest {
             DataRequest::SubscribeTransactionOutputsWithProof(request) => {
                 request.subscription_stream_metadata
             },
+            DataRequest::SubscribeFilteredTransactionOutputsWithProof(request) => {
+                request.subscription_stream_metadata
+            },
             DataRequest::SubscribeTransactionsWithProof(request) => {
                 request.subscription_stream_metadata
             },
@@ -236,6 +258,16 @@ impl SubscriptionRequest {
         }
     }
 
+    /// Returns the event filter for the request (if any)
+    fn event_filter(&self) -> Option<EventFilter> {
+        match &self.request.data_request {
+            DataRequest::SubscribeFilteredTransactionOutputsWithProof(request) => {
+                Some(request.event_filter.clone())
+            },
+            _ => None,
+        }
+    }
+
     /// Returns the response sender and consumes the request
     pub fn take_response_sender(self) -> ResponseSender {
         self.response_sender
@@ -617,6 +649,7 @@ async fn handle_ready_subscriptions<T: StorageReaderInterface>(
                                 known_version,
                                 &target_ledger_info,
                             )?;
+                        let event_filter = subscription_request.event_filter();
 
                         // Notify the peer of the new data
                         let data_response = utils::notify_peer_of_new_data(
@@ -629,6 +662,7 @@ async fn handle_ready_subscriptions<T: StorageReaderInterface>(
                             time_service.clone(),
                             &peer_network_id,
                             missing_data_request,
+                            event_filter,
                             target_ledger_info,
                             subscription_request.take_response_sender(),
                         )?;
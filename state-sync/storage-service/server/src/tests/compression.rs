@@ -0,0 +1,124 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tests::mock::MockClient;
+use aptos_compression::codec::{CompressionCodec, DEFAULT_ZSTD_COMPRESSION_LEVEL};
+use aptos_config::{
+    config::{StorageServiceCompressionConfig, StorageServiceConfig},
+    network_id::NetworkId,
+};
+use aptos_storage_service_types::{
+    requests::{CompressionNegotiationRequest, DataRequest, StorageServiceRequest},
+    responses::{DataResponse, StorageServiceResponse},
+};
+use aptos_types::PeerId;
+use claims::assert_matches;
+
+#[tokio::test]
+async fn test_compression_negotiation() {
+    // Create the storage client and server
+    let (mut mock_client, service, _, _, _) = MockClient::new(None, None);
+    let compression_negotiator = service.get_compression_negotiator();
+    tokio::spawn(service.start());
+
+    // Negotiate the compression codec for a peer (the peer is not on a local network)
+    let peer_id = PeerId::random();
+    let response = send_request_for_peer(
+        &mut mock_client,
+        peer_id,
+        DataRequest::NegotiateCompression(CompressionNegotiationRequest {
+            supported_codecs: CompressionCodec::all(),
+        }),
+    )
+    .await;
+
+    // Verify the wide area network codec was negotiated
+    let expected_codec = CompressionCodec::Zstd {
+        level: DEFAULT_ZSTD_COMPRESSION_LEVEL,
+    };
+    assert_eq!(
+        response.get_data_response().unwrap(),
+        DataResponse::NegotiatedCompressionCodec(expected_codec)
+    );
+    assert_eq!(compression_negotiator.get_negotiated_codecs().len(), 1);
+
+    // Verify subsequent responses for the peer use the negotiated codec
+    let response = send_request_for_peer(
+        &mut mock_client,
+        peer_id,
+        DataRequest::GetServerProtocolVersion,
+    )
+    .await;
+    assert_matches!(
+        response,
+        StorageServiceResponse::CodecCompressedResponse(_, codec, _) if codec == expected_codec
+    );
+    assert_matches!(
+        response.get_data_response().unwrap(),
+        DataResponse::ServerProtocolVersion(_)
+    );
+
+    // Verify responses for other peers still use the default compression
+    let response = send_request_for_peer(
+        &mut mock_client,
+        PeerId::random(),
+        DataRequest::GetServerProtocolVersion,
+    )
+    .await;
+    assert_matches!(response, StorageServiceResponse::CompressedResponse(_, _));
+}
+
+#[tokio::test]
+async fn test_compression_negotiation_disabled() {
+    // Create a storage config with compression negotiation disabled
+    let storage_config = StorageServiceConfig {
+        compression_config: StorageServiceCompressionConfig {
+            enable_compression_negotiation: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    // Create the storage client and server
+    let (mut mock_client, service, _, _, _) = MockClient::new(None, Some(storage_config));
+    tokio::spawn(service.start());
+
+    // Attempt to negotiate the compression codec for a peer
+    let peer_id = PeerId::random();
+    let response = send_request_for_peer(
+        &mut mock_client,
+        peer_id,
+        DataRequest::NegotiateCompression(CompressionNegotiationRequest {
+            supported_codecs: CompressionCodec::all(),
+        }),
+    )
+    .await;
+
+    // Verify the default codec is returned
+    assert_eq!(
+        response.get_data_response().unwrap(),
+        DataResponse::NegotiatedCompressionCodec(CompressionCodec::Lz4)
+    );
+
+    // Verify subsequent responses for the peer use the default compression
+    let response = send_request_for_peer(
+        &mut mock_client,
+        peer_id,
+        DataRequest::GetServerProtocolVersion,
+    )
+    .await;
+    assert_matches!(response, StorageServiceResponse::CompressedResponse(_, _));
+}
+
+/// Sends a compressed data request for the given peer and returns the response
+async fn send_request_for_peer(
+    mock_client: &mut MockClient,
+    peer_id: PeerId,
+    data_request: DataRequest,
+) -> StorageServiceResponse {
+    let storage_request = StorageServiceRequest::new(data_request, true);
+    let receiver = mock_client
+        .send_request(storage_request, Some(peer_id), Some(NetworkId::Public))
+        .await;
+    mock_client.wait_for_response(receiver).await.unwrap()
+}
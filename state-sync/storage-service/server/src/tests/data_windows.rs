@@ -0,0 +1,60 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tests::{mock, mock::MockClient, utils};
+use aptos_storage_service_types::{
+    requests::DataRequest,
+    responses::{DataResponse, DataWindows},
+};
+
+#[tokio::test]
+async fn test_get_data_windows() {
+    // Test both pruned and unpruned storage
+    for pruner_enabled in [true, false] {
+        // Create the mock db reader
+        let ledger_prune_window = 1000;
+        let state_prune_window = 200;
+        let mut db_reader = mock::create_mock_db_reader();
+        db_reader
+            .expect_is_ledger_pruner_enabled()
+            .returning(move || Ok(pruner_enabled));
+        db_reader
+            .expect_get_ledger_prune_window()
+            .returning(move || Ok(ledger_prune_window));
+        db_reader
+            .expect_is_state_merkle_pruner_enabled()
+            .returning(move || Ok(pruner_enabled));
+        db_reader
+            .expect_get_epoch_snapshot_prune_window()
+            .returning(move || Ok(state_prune_window));
+
+        // Create the storage client and server
+        let (mut mock_client, service, _, _, _) = MockClient::new(Some(db_reader), None);
+        tokio::spawn(service.start());
+
+        // Process a request to fetch the data windows
+        for use_compression in [true, false] {
+            let response = utils::send_storage_request(
+                &mut mock_client,
+                use_compression,
+                DataRequest::GetDataWindows,
+            )
+            .await
+            .unwrap();
+
+            // Verify the response is correct
+            let expected_data_windows = if pruner_enabled {
+                DataWindows {
+                    ledger_window: Some(ledger_prune_window as u64),
+                    state_window: Some(state_prune_window as u64),
+                }
+            } else {
+                DataWindows::default()
+            };
+            assert_eq!(
+                response.get_data_response().unwrap(),
+                DataResponse::DataWindows(expected_data_windows)
+            );
+        }
+    }
+}
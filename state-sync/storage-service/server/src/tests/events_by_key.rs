@@ -0,0 +1,173 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tests::{mock, mock::MockClient, utils};
+use aptos_crypto::HashValue;
+use aptos_storage_interface::Order;
+use aptos_storage_service_types::{
+    requests::{DataRequest, EventsByKeyWithProofRequest},
+    responses::{
+        DataResponse, EventsByKeyWithProof, StorageServiceResponse, TransactionEventsWithProof,
+    },
+    StorageServiceError,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    contract_event::ContractEvent,
+    event::EventKey,
+    proof::{TransactionAccumulatorProof, TransactionInfoWithProof},
+    transaction::{ExecutionStatus, Transaction, TransactionInfo, TransactionWithProof, Version},
+};
+use claims::assert_matches;
+use mockall::predicate::eq;
+
+#[tokio::test]
+async fn test_get_events_by_key_with_proof() {
+    // Create test data
+    let event_key = EventKey::new(0, AccountAddress::random());
+    let other_event_key = EventKey::new(1, AccountAddress::random());
+    let proof_version = 1000;
+
+    // Create the transaction events (the event key emits 5 events over 3 versions)
+    let transaction_events = vec![
+        create_transaction_events(10, vec![
+            create_event(event_key, 0),
+            create_event(event_key, 1),
+        ]),
+        create_transaction_events(11, vec![
+            create_event(other_event_key, 0),
+            create_event(event_key, 2),
+        ]),
+        create_transaction_events(12, vec![
+            create_event(event_key, 3),
+            create_event(event_key, 4),
+        ]),
+    ];
+    let events_by_key_with_proof = EventsByKeyWithProof {
+        event_key,
+        start_sequence_number: 0,
+        transaction_events: transaction_events.clone(),
+    };
+
+    // Create the mock db reader
+    let mut db_reader = mock::create_mock_db_reader();
+    let events = events_by_key_with_proof.get_events();
+    db_reader
+        .expect_get_events()
+        .times(1)
+        .withf(move |key, start, order, limit, ledger_version| {
+            *key == event_key
+                && *start == 0
+                && *order == Order::Ascending
+                && *limit == 5
+                && *ledger_version == proof_version
+        })
+        .returning(move |_, _, _, _, _| Ok(events.clone()));
+    for transaction_events in transaction_events {
+        let version = transaction_events.version;
+        db_reader
+            .expect_get_transaction_by_version()
+            .times(1)
+            .with(eq(version), eq(proof_version), eq(true))
+            .returning(move |_, _, _| Ok(create_transaction_with_proof(&transaction_events)));
+    }
+
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(Some(db_reader), None);
+    utils::update_storage_server_summary(&mut service, proof_version, 10);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the events
+    let response = get_events_by_key_with_proof(&mut mock_client, event_key, 0, 4, proof_version)
+        .await
+        .unwrap();
+
+    // Verify the response is correct
+    match response.get_data_response().unwrap() {
+        DataResponse::EventsByKeyWithProof(response) => {
+            assert_eq!(response, events_by_key_with_proof);
+            assert_eq!(response.get_num_events(), 5);
+        },
+        _ => panic!("Expected events by key with proof but got: {:?}", response),
+    };
+}
+
+#[tokio::test]
+async fn test_get_events_by_key_with_proof_invalid() {
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(None, None);
+    utils::update_storage_server_summary(&mut service, 1000, 10);
+    tokio::spawn(service.start());
+
+    // Test invalid sequence number ranges
+    let event_key = EventKey::new(0, AccountAddress::random());
+    for (start_sequence_number, end_sequence_number) in [(100, 99), (1, 0)] {
+        let response = get_events_by_key_with_proof(
+            &mut mock_client,
+            event_key,
+            start_sequence_number,
+            end_sequence_number,
+            1000,
+        )
+        .await
+        .unwrap_err();
+        assert_matches!(response, StorageServiceError::InvalidRequest(_));
+    }
+}
+
+/// Creates a test event for the given event key and sequence number
+fn create_event(event_key: EventKey, sequence_number: u64) -> ContractEvent {
+    ContractEvent::new_v1(event_key, sequence_number, "u64".parse().unwrap(), vec![])
+}
+
+/// Creates the events (with an empty proof) for a transaction at the given version
+fn create_transaction_events(
+    version: Version,
+    events: Vec<ContractEvent>,
+) -> TransactionEventsWithProof {
+    let transaction_info = TransactionInfo::new(
+        HashValue::random(),
+        HashValue::random(),
+        HashValue::random(),
+        None,
+        0,
+        ExecutionStatus::Success,
+    );
+    TransactionEventsWithProof {
+        version,
+        events,
+        transaction_info_with_proof: TransactionInfoWithProof::new(
+            TransactionAccumulatorProof::new(vec![]),
+            transaction_info,
+        ),
+    }
+}
+
+/// Creates a transaction with proof that holds the given transaction events
+fn create_transaction_with_proof(
+    transaction_events: &TransactionEventsWithProof,
+) -> TransactionWithProof {
+    TransactionWithProof::new(
+        transaction_events.version,
+        Transaction::StateCheckpoint(HashValue::random()),
+        Some(transaction_events.events.clone()),
+        transaction_events.transaction_info_with_proof.clone(),
+    )
+}
+
+/// Sends an events by key with proof request and processes the response
+async fn get_events_by_key_with_proof(
+    mock_client: &mut MockClient,
+    event_key: EventKey,
+    start_sequence_number: u64,
+    end_sequence_number: u64,
+    proof_version: u64,
+) -> Result<StorageServiceResponse, StorageServiceError> {
+    let data_request = DataRequest::GetEventsByKeyWithProof(EventsByKeyWithProofRequest {
+        event_key,
+        start_sequence_number,
+        end_sequence_number,
+        proof_version,
+    });
+    utils::send_storage_request(mock_client, true, data_request).await
+}
@@ -24,8 +24,8 @@ use aptos_network::{
 use aptos_storage_interface::{DbReader, ExecutedTrees, Order};
 use aptos_storage_service_notifications::StorageServiceNotifier;
 use aptos_storage_service_types::{
-    requests::StorageServiceRequest, responses::StorageServiceResponse, StorageServiceError,
-    StorageServiceMessage,
+    bandwidth::BandwidthBudget, requests::StorageServiceRequest, responses::StorageServiceResponse,
+    StorageServiceError, StorageServiceMessage,
 };
 use aptos_time_service::{MockTimeService, TimeService};
 use aptos_types::{
@@ -118,6 +118,10 @@ impl MockClient {
         let peers_and_metadata = create_peers_and_metadata(network_ids);
         let executor = tokio::runtime::Handle::current();
         let mock_time_service = TimeService::mock();
+        let bandwidth_budget = Arc::new(BandwidthBudget::new(
+            state_sync_config.aptos_data_client.data_bandwidth_config,
+            mock_time_service.clone(),
+        ));
         let storage_server = StorageServiceServer::new(
             state_sync_config,
             executor,
@@ -126,6 +130,7 @@ impl MockClient {
             peers_and_metadata.clone(),
             storage_service_network_events,
             storage_service_listener,
+            bandwidth_budget,
         );
 
         // Return the client and service
@@ -351,6 +356,10 @@ mock! {
         fn get_epoch_snapshot_prune_window(&self) -> aptos_storage_interface::Result<usize>;
 
         fn is_state_merkle_pruner_enabled(&self) -> aptos_storage_interface::Result<bool>;
+
+        fn get_ledger_prune_window(&self) -> aptos_storage_interface::Result<usize>;
+
+        fn is_ledger_pruner_enabled(&self) -> aptos_storage_interface::Result<bool>;
     }
 }
 
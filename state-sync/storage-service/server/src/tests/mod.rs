@@ -2,15 +2,21 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod cache;
+mod compression;
+mod data_windows;
 mod epoch_ending;
+mod events_by_key;
 mod mock;
 mod new_transaction_outputs;
 mod new_transactions;
 mod new_transactions_or_outputs;
 mod number_of_states;
 mod optimistic_fetch;
+mod pagination;
 mod protocol_version;
 mod request_moderator;
+mod scheduler;
+mod state_value_changes;
 mod state_values;
 mod storage_summary;
 mod subscribe_transaction_outputs;
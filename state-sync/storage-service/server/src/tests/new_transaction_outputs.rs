@@ -7,9 +7,15 @@ use aptos_config::{
     network_id::{NetworkId, PeerNetworkId},
 };
 use aptos_storage_service_types::requests::{
-    DataRequest, NewTransactionOutputsWithProofRequest, StorageServiceRequest,
+    DataRequest, EventFilter, NewFilteredTransactionOutputsWithProofRequest,
+    NewTransactionOutputsWithProofRequest, StorageServiceRequest,
+};
+use aptos_types::{
+    contract_event::ContractEvent,
+    epoch_change::EpochChangeProof,
+    transaction::{TransactionOutput, TransactionOutputListWithProof},
+    PeerId,
 };
-use aptos_types::{epoch_change::EpochChangeProof, PeerId};
 use claims::assert_none;
 use futures::channel::oneshot::Receiver;
 
@@ -77,6 +83,73 @@ async fn test_get_new_transaction_outputs() {
     }
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_get_new_filtered_transaction_outputs() {
+    // Create test data
+    let highest_version = 5060;
+    let highest_epoch = 30;
+    let lowest_version = 101;
+    let peer_version = highest_version - 100;
+    let highest_ledger_info =
+        utils::create_test_ledger_info_with_sigs(highest_epoch, highest_version);
+    let output_list_with_proof = create_output_list_with_events(
+        utils::create_output_list_with_proof(peer_version + 1, highest_version, highest_version),
+    );
+
+    // Create the mock db reader
+    let mut db_reader =
+        mock::create_mock_db_with_summary_updates(highest_ledger_info.clone(), lowest_version);
+    utils::expect_get_transaction_outputs(
+        &mut db_reader,
+        peer_version + 1,
+        highest_version - peer_version,
+        highest_version,
+        output_list_with_proof.clone(),
+    );
+
+    // Create the storage client and server
+    let (mut mock_client, service, storage_service_notifier, mock_time, _) =
+        MockClient::new(Some(db_reader), None);
+    let active_optimistic_fetches = service.get_optimistic_fetches();
+    tokio::spawn(service.start());
+
+    // Send a request to optimistically fetch new transaction outputs (without events)
+    let data_request = DataRequest::GetNewFilteredTransactionOutputsWithProof(
+        NewFilteredTransactionOutputsWithProofRequest {
+            known_version: peer_version,
+            known_epoch: highest_epoch,
+            event_filter: EventFilter::StripAllEvents,
+        },
+    );
+    let storage_request = StorageServiceRequest::new(data_request, true);
+    let response_receiver = mock_client.send_request(storage_request, None, None).await;
+
+    // Wait until the optimistic fetch is active
+    utils::wait_for_active_optimistic_fetches(active_optimistic_fetches.clone(), 1).await;
+
+    // Force the optimistic fetch handler to work
+    utils::force_optimistic_fetch_handler_to_run(
+        &mut mock_client,
+        &mock_time,
+        &storage_service_notifier,
+    )
+    .await;
+
+    // Verify a response is received and that the events were removed
+    let expected_output_list_with_proof =
+        EventFilter::StripAllEvents.filter_outputs(output_list_with_proof);
+    for (_, output) in &expected_output_list_with_proof.transactions_and_outputs {
+        assert!(output.events().is_empty());
+    }
+    utils::verify_new_transaction_outputs_with_proof(
+        &mut mock_client,
+        response_receiver,
+        expected_output_list_with_proof,
+        highest_ledger_info,
+    )
+    .await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_get_new_transaction_outputs_different_networks() {
     // Test small and large chunk sizes
@@ -325,6 +398,31 @@ async fn get_new_outputs_with_proof(
     get_new_outputs_with_proof_for_peer(mock_client, known_version, known_epoch, None).await
 }
 
+/// Adds a test event to each output in the given output list
+fn create_output_list_with_events(
+    output_list_with_proof: TransactionOutputListWithProof,
+) -> TransactionOutputListWithProof {
+    let transactions_and_outputs = output_list_with_proof
+        .transactions_and_outputs
+        .into_iter()
+        .map(|(transaction, output)| {
+            let (write_set, _, gas_used, status, auxiliary_data) = output.unpack();
+            let events = vec![ContractEvent::new_v2_with_type_tag_str(
+                "0x1::coin::CoinDeposit",
+                vec![],
+            )];
+            let output =
+                TransactionOutput::new(write_set, events, gas_used, status, auxiliary_data);
+            (transaction, output)
+        })
+        .collect();
+    TransactionOutputListWithProof::new(
+        transactions_and_outputs,
+        output_list_with_proof.first_transaction_output_version,
+        output_list_with_proof.proof,
+    )
+}
+
 /// Creates and sends a request for new transaction outputs for the specified peer
 async fn get_new_outputs_with_proof_for_peer(
     mock_client: &mut MockClient,
@@ -0,0 +1,231 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tests::{mock, mock::MockClient, utils};
+use aptos_config::config::StorageServiceConfig;
+use aptos_crypto::HashValue;
+use aptos_storage_service_types::{
+    requests::{
+        DataRequest, PaginatedStateValuesWithProofRequest, PaginatedTransactionsWithProofRequest,
+    },
+    responses::{ContinuationToken, DataResponse, PaginatedResponse},
+    StorageServiceError,
+};
+use aptos_types::{
+    proof::definition::SparseMerkleRangeProof, state_store::state_value::StateValueChunkWithProof,
+    transaction::TransactionListWithProof,
+};
+use claims::assert_matches;
+use mockall::predicate::eq;
+
+#[tokio::test]
+async fn test_get_paginated_state_values_with_proof() {
+    // Create test data (the requested range is twice the max chunk size)
+    let max_state_chunk_size = StorageServiceConfig::default().max_state_chunk_size;
+    let version = 101;
+    let start_index = 100;
+    let end_index = start_index + (max_state_chunk_size * 2) - 1;
+    let first_chunk = create_state_value_chunk(start_index, start_index + max_state_chunk_size - 1);
+    let second_chunk = create_state_value_chunk(start_index + max_state_chunk_size, end_index);
+
+    // Create the mock db reader
+    let mut db_reader = mock::create_mock_db_reader();
+    for state_value_chunk in [first_chunk.clone(), second_chunk.clone()] {
+        db_reader
+            .expect_get_state_value_chunk_with_proof()
+            .times(1)
+            .with(
+                eq(version),
+                eq(state_value_chunk.first_index as usize),
+                eq(max_state_chunk_size as usize),
+            )
+            .returning(move |_, _, _| Ok(state_value_chunk.clone()));
+    }
+
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(Some(db_reader), None);
+    utils::update_storage_server_summary(&mut service, version, 10);
+    tokio::spawn(service.start());
+
+    // Fetch the first page and verify it was truncated
+    let response = get_paginated_state_values_with_proof(
+        &mut mock_client,
+        version,
+        start_index,
+        end_index,
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.data, first_chunk);
+    assert!(!response.is_complete());
+
+    // Fetch the second page using the continuation token and verify the read is complete
+    let response = get_paginated_state_values_with_proof(
+        &mut mock_client,
+        version,
+        start_index,
+        end_index,
+        response.continuation_token,
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.data, second_chunk);
+    assert!(response.is_complete());
+}
+
+#[tokio::test]
+async fn test_get_paginated_transactions_with_proof() {
+    // Test event inclusion
+    for include_events in [true, false] {
+        // Create test data (the requested range is twice the max chunk size)
+        let max_transaction_chunk_size = StorageServiceConfig::default().max_transaction_chunk_size;
+        let start_version = 0;
+        let end_version = start_version + (max_transaction_chunk_size * 2) - 1;
+        let proof_version = end_version;
+        let first_transactions = utils::create_transaction_list_with_proof(
+            start_version,
+            start_version + max_transaction_chunk_size - 1,
+            proof_version,
+            include_events,
+        );
+        let second_transactions = utils::create_transaction_list_with_proof(
+            start_version + max_transaction_chunk_size,
+            end_version,
+            proof_version,
+            include_events,
+        );
+
+        // Create the mock db reader
+        let mut db_reader = mock::create_mock_db_reader();
+        for transaction_list_with_proof in [first_transactions.clone(), second_transactions.clone()]
+        {
+            utils::expect_get_transactions(
+                &mut db_reader,
+                transaction_list_with_proof
+                    .first_transaction_version
+                    .unwrap(),
+                max_transaction_chunk_size,
+                proof_version,
+                include_events,
+                transaction_list_with_proof,
+            );
+        }
+
+        // Create the storage client and server
+        let (mut mock_client, mut service, _, _, _) = MockClient::new(Some(db_reader), None);
+        utils::update_storage_server_summary(&mut service, proof_version, 10);
+        tokio::spawn(service.start());
+
+        // Fetch the first page and verify it was truncated
+        let response = get_paginated_transactions_with_proof(
+            &mut mock_client,
+            start_version,
+            end_version,
+            proof_version,
+            include_events,
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.data, first_transactions);
+        assert!(!response.is_complete());
+
+        // Fetch the second page using the continuation token and verify the read is complete
+        let response = get_paginated_transactions_with_proof(
+            &mut mock_client,
+            start_version,
+            end_version,
+            proof_version,
+            include_events,
+            response.continuation_token,
+        )
+        .await
+        .unwrap();
+        assert_eq!(response.data, second_transactions);
+        assert!(response.is_complete());
+    }
+}
+
+#[tokio::test]
+async fn test_get_paginated_transactions_with_proof_invalid_token() {
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(None, None);
+    utils::update_storage_server_summary(&mut service, 1000, 10);
+    tokio::spawn(service.start());
+
+    // Send a request with a malformed continuation token
+    let data_request =
+        DataRequest::GetPaginatedTransactionsWithProof(PaginatedTransactionsWithProofRequest {
+            proof_version: 1000,
+            start_version: 0,
+            end_version: 100,
+            include_events: false,
+            continuation_token: Some(ContinuationToken::new(vec![0xFF; 3])),
+        });
+    let response = utils::send_storage_request(&mut mock_client, false, data_request)
+        .await
+        .unwrap_err();
+
+    // Verify the request is rejected
+    assert_matches!(response, StorageServiceError::InvalidRequest(_));
+}
+
+/// Creates a test state value chunk for the given index range
+fn create_state_value_chunk(first_index: u64, last_index: u64) -> StateValueChunkWithProof {
+    StateValueChunkWithProof {
+        first_index,
+        last_index,
+        first_key: HashValue::random(),
+        last_key: HashValue::random(),
+        raw_values: vec![],
+        proof: SparseMerkleRangeProof::new(vec![]),
+        root_hash: HashValue::random(),
+    }
+}
+
+/// Sends a paginated state values with proof request and processes the response
+async fn get_paginated_state_values_with_proof(
+    mock_client: &mut MockClient,
+    version: u64,
+    start_index: u64,
+    end_index: u64,
+    continuation_token: Option<ContinuationToken>,
+) -> Result<PaginatedResponse<StateValueChunkWithProof>, StorageServiceError> {
+    let data_request =
+        DataRequest::GetPaginatedStateValuesWithProof(PaginatedStateValuesWithProofRequest {
+            version,
+            start_index,
+            end_index,
+            continuation_token,
+        });
+    let response = utils::send_storage_request(mock_client, true, data_request).await?;
+    match response.get_data_response().unwrap() {
+        DataResponse::PaginatedStateValueChunkWithProof(response) => Ok(response),
+        response => panic!("Expected paginated state values but got: {:?}", response),
+    }
+}
+
+/// Sends a paginated transactions with proof request and processes the response
+async fn get_paginated_transactions_with_proof(
+    mock_client: &mut MockClient,
+    start_version: u64,
+    end_version: u64,
+    proof_version: u64,
+    include_events: bool,
+    continuation_token: Option<ContinuationToken>,
+) -> Result<PaginatedResponse<TransactionListWithProof>, StorageServiceError> {
+    let data_request =
+        DataRequest::GetPaginatedTransactionsWithProof(PaginatedTransactionsWithProofRequest {
+            proof_version,
+            start_version,
+            end_version,
+            include_events,
+            continuation_token,
+        });
+    let response = utils::send_storage_request(mock_client, true, data_request).await?;
+    match response.get_data_response().unwrap() {
+        DataResponse::PaginatedTransactionsWithProof(response) => Ok(response),
+        response => panic!("Expected paginated transactions but got: {:?}", response),
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::scheduler::{PeerClass, RequestScheduler};
+use aptos_bounded_executor::BoundedExecutor;
+use aptos_config::{
+    config::StorageServicePrioritySchedulingConfig,
+    network_id::{NetworkId, PeerNetworkId},
+};
+use aptos_types::PeerId;
+use claims::assert_matches;
+use tokio::{runtime::Handle, sync::mpsc};
+
+#[test]
+fn test_peer_classes() {
+    // Verify the peer class of each network
+    assert_eq!(PeerClass::new(NetworkId::Validator), PeerClass::Validator);
+    assert_eq!(PeerClass::new(NetworkId::Vfn), PeerClass::ValidatorFullnode);
+    assert_eq!(PeerClass::new(NetworkId::Public), PeerClass::Public);
+
+    // Verify that only validators and VFNs are high priority
+    assert!(PeerClass::Validator.is_high_priority());
+    assert!(PeerClass::ValidatorFullnode.is_high_priority());
+    assert!(!PeerClass::Public.is_high_priority());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_high_priority_requests_served_first() {
+    // Create a scheduler with a single executor slot
+    let bounded_executor = BoundedExecutor::new(1, Handle::current());
+    let request_scheduler = RequestScheduler::new(
+        StorageServicePrioritySchedulingConfig::default(),
+        bounded_executor,
+        &Handle::current(),
+    );
+
+    // Schedule a request that blocks the executor (until it is released)
+    let validator_peer = PeerNetworkId::new(NetworkId::Validator, PeerId::random());
+    let public_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    let (started_sender, mut started_receiver) = mpsc::unbounded_channel();
+    let (release_sender, release_receiver) = std::sync::mpsc::channel::<()>();
+    let (processed_sender, mut processed_receiver) = mpsc::unbounded_channel();
+    let blocking_processed_sender = processed_sender.clone();
+    request_scheduler
+        .try_reserve(&public_peer)
+        .unwrap()
+        .schedule(move || {
+            started_sender.send(()).unwrap();
+            release_receiver.recv().unwrap();
+            blocking_processed_sender
+                .send((PeerClass::Public, 0))
+                .unwrap();
+        });
+    started_receiver.recv().await.unwrap();
+
+    // Schedule several low and high priority requests
+    for (peer_network_id, peer_class) in [
+        (public_peer, PeerClass::Public),
+        (validator_peer, PeerClass::Validator),
+    ] {
+        for index in 1..=3 {
+            let processed_sender = processed_sender.clone();
+            request_scheduler
+                .try_reserve(&peer_network_id)
+                .unwrap()
+                .schedule(move || processed_sender.send((peer_class, index)).unwrap());
+        }
+    }
+
+    // Release the executor and collect the processing order
+    release_sender.send(()).unwrap();
+    let mut processed_requests = vec![];
+    for _ in 0..7 {
+        processed_requests.push(processed_receiver.recv().await.unwrap());
+    }
+
+    // Verify the blocking request was processed first. Note: the dispatcher may
+    // have already taken the first public request (while waiting for capacity).
+    assert_eq!(processed_requests[0], (PeerClass::Public, 0));
+    let validator_requests: Vec<_> = processed_requests
+        .iter()
+        .filter(|(peer_class, _)| *peer_class == PeerClass::Validator)
+        .map(|(_, index)| *index)
+        .collect();
+    assert_eq!(validator_requests, vec![1, 2, 3]);
+
+    // Verify the remaining public requests were processed after the validator requests
+    assert_eq!(processed_requests[5..], [
+        (PeerClass::Public, 2),
+        (PeerClass::Public, 3)
+    ]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_full_queue_rejects_requests() {
+    // Create a scheduler with a single executor slot and a small queue
+    let bounded_executor = BoundedExecutor::new(1, Handle::current());
+    let request_scheduler = RequestScheduler::new(
+        StorageServicePrioritySchedulingConfig {
+            max_pending_requests_per_class: 1,
+            ..Default::default()
+        },
+        bounded_executor,
+        &Handle::current(),
+    );
+
+    // Reserve the only public slot and verify other public requests are rejected
+    let public_peer = PeerNetworkId::new(NetworkId::Public, PeerId::random());
+    let scheduling_permit = request_scheduler.try_reserve(&public_peer).unwrap();
+    assert_matches!(request_scheduler.try_reserve(&public_peer), Err(_));
+
+    // Verify high priority requests can still be scheduled
+    let vfn_peer = PeerNetworkId::new(NetworkId::Vfn, PeerId::random());
+    assert_matches!(request_scheduler.try_reserve(&vfn_peer), Ok(_));
+
+    // Release the public slot and verify it can be reserved again
+    drop(scheduling_permit);
+    assert_matches!(request_scheduler.try_reserve(&public_peer), Ok(_));
+}
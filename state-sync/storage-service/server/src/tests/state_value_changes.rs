@@ -0,0 +1,107 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::tests::{mock::MockClient, utils};
+use aptos_config::config::StorageServiceConfig;
+use aptos_storage_service_types::{
+    requests::{DataRequest, StateValueChangesWithProofRequest},
+    responses::StorageServiceResponse,
+    StorageServiceError,
+};
+use claims::assert_matches;
+
+#[tokio::test]
+async fn test_get_state_value_changes_with_proof_chunk_limit() {
+    // Create test data (request more versions than the max chunk size)
+    let max_output_chunk_size = StorageServiceConfig::default().max_transaction_output_chunk_size;
+    let start_version = 100;
+    let end_version = start_version + max_output_chunk_size + 1;
+
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(None, None);
+    utils::update_storage_server_summary(&mut service, end_version, 10);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the state value changes
+    for use_compression in [true, false] {
+        let response = get_state_value_changes_with_proof(
+            &mut mock_client,
+            end_version,
+            start_version,
+            end_version,
+            use_compression,
+        )
+        .await
+        .unwrap_err();
+
+        // Verify the request is rejected (the changes cannot be truncated)
+        assert_matches!(response, StorageServiceError::InvalidRequest(_));
+    }
+}
+
+#[tokio::test]
+async fn test_get_state_value_changes_with_proof_invalid() {
+    // Create the storage client and server
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(None, None);
+    utils::update_storage_server_summary(&mut service, 1000, 10);
+    tokio::spawn(service.start());
+
+    // Test invalid ranges (proof version, start version, end version)
+    for (proof_version, start_version, end_version) in
+        [(1000, 100, 99), (1000, 100, 100), (500, 100, 600)]
+    {
+        let response = get_state_value_changes_with_proof(
+            &mut mock_client,
+            proof_version,
+            start_version,
+            end_version,
+            false,
+        )
+        .await
+        .unwrap_err();
+        assert_matches!(response, StorageServiceError::InvalidRequest(_));
+    }
+}
+
+#[tokio::test]
+async fn test_get_state_value_changes_with_proof_not_serviceable() {
+    // Create test data
+    let start_version = 100;
+    let end_version = 200;
+
+    // Create the storage client and server (that cannot service the request)
+    let (mut mock_client, mut service, _, _, _) = MockClient::new(None, None);
+    utils::update_storage_server_summary(&mut service, end_version - 1, 10);
+    tokio::spawn(service.start());
+
+    // Process a request to fetch the state value changes
+    let response = get_state_value_changes_with_proof(
+        &mut mock_client,
+        end_version,
+        start_version,
+        end_version,
+        false,
+    )
+    .await
+    .unwrap_err();
+
+    // Verify the request is not serviceable
+    assert_matches!(response, StorageServiceError::InvalidRequest(_));
+}
+
+/// Sends a state value changes with proof request and processes the response
+async fn get_state_value_changes_with_proof(
+    mock_client: &mut MockClient,
+    proof_version: u64,
+    start_version: u64,
+    end_version: u64,
+    use_compression: bool,
+) -> Result<StorageServiceResponse, StorageServiceError> {
+    let data_request =
+        DataRequest::GetStateValueChangesWithProof(StateValueChangesWithProofRequest {
+            proof_version,
+            start_version,
+            end_version,
+        });
+    utils::send_storage_request(mock_client, use_compression, data_request).await
+}
@@ -9,7 +9,7 @@ use crate::{
 use aptos_config::network_id::PeerNetworkId;
 use aptos_metrics_core::HistogramVec;
 use aptos_storage_service_types::{
-    requests::{DataRequest, EpochEndingLedgerInfoRequest, StorageServiceRequest},
+    requests::{DataRequest, EpochEndingLedgerInfoRequest, EventFilter, StorageServiceRequest},
     responses::{DataResponse, StorageServerSummary, StorageServiceResponse},
 };
 use aptos_time_service::TimeService;
@@ -79,7 +79,8 @@ pub fn get_epoch_ending_ledger_info<T: StorageReaderInterface>(
 }
 
 /// Notifies a peer of new data according to the target ledger info
-/// and returns a copy of the raw data response that was sent.
+/// and returns a copy of the raw data response that was sent. If an
+/// event filter is given, it is applied to any transaction outputs.
 ///
 /// Note: we don't need to check the size of the response because:
 /// (i) each sub-part should already be checked; and (ii) responses
@@ -93,11 +94,13 @@ pub fn notify_peer_of_new_data<T: StorageReaderInterface>(
     time_service: TimeService,
     peer_network_id: &PeerNetworkId,
     missing_data_request: StorageServiceRequest,
+    event_filter: Option<EventFilter>,
     target_ledger_info: LedgerInfoWithSignatures,
     response_sender: ResponseSender,
 ) -> aptos_storage_service_types::Result<DataResponse, Error> {
     // Handle the storage service request to fetch the missing data
     let use_compression = missing_data_request.use_compression;
+    let compression_codec = missing_data_request.compression_codec;
     let handler = Handler::new(
         cached_storage_server_summary,
         optimistic_fetches,
@@ -120,6 +123,10 @@ pub fn notify_peer_of_new_data<T: StorageReaderInterface>(
                 ))
             },
             Ok(DataResponse::TransactionOutputsWithProof(outputs_with_proof)) => {
+                let outputs_with_proof = match &event_filter {
+                    Some(event_filter) => event_filter.filter_outputs(outputs_with_proof),
+                    None => outputs_with_proof,
+                };
                 DataResponse::NewTransactionOutputsWithProof((
                     outputs_with_proof,
                     target_ledger_info,
@@ -161,16 +168,19 @@ pub fn notify_peer_of_new_data<T: StorageReaderInterface>(
     };
 
     // Create the storage service response
-    let storage_response =
-        match StorageServiceResponse::new(transformed_data_response.clone(), use_compression) {
-            Ok(storage_response) => storage_response,
-            Err(error) => {
-                return Err(Error::UnexpectedErrorEncountered(format!(
-                    "Failed to create transformed response! Error: {:?}",
-                    error
-                )));
-            },
-        };
+    let storage_response = match StorageServiceResponse::new_with_codec(
+        transformed_data_response.clone(),
+        use_compression,
+        compression_codec,
+    ) {
+        Ok(storage_response) => storage_response,
+        Err(error) => {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Failed to create transformed response! Error: {:?}",
+                error
+            )));
+        },
+    };
 
     // Send the response to the peer
     handler.send_response(missing_data_request, Ok(storage_response), response_sender);
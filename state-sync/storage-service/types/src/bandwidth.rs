@@ -0,0 +1,248 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_config::config::AptosDataBandwidthConfig;
+use aptos_infallible::Mutex;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use serde::Serialize;
+use std::{
+    cmp::{max, min},
+    time::{Duration, Instant},
+};
+
+// The number of seconds in an hour and a day (used to derive the UTC hour of the day)
+const SECONDS_PER_HOUR: u64 = 60 * 60;
+const HOURS_PER_DAY: u64 = 24;
+
+/// A snapshot of the bandwidth budget state (e.g., for operators)
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct BandwidthBudgetSummary {
+    pub config: AptosDataBandwidthConfig,
+    pub runtime_syncing_limit_bytes_per_second: Option<u64>,
+    pub syncing_limit_bytes_per_second: Option<u64>,
+    pub is_off_peak: bool,
+    pub served_bytes_per_second: u64,
+    pub synced_bytes_per_second: u64,
+}
+
+/// A bandwidth budget shared between the state sync data client (i.e.,
+/// data synced from upstream peers) and the storage service (i.e., data
+/// served to downstream peers). The budget is used to cap the bandwidth
+/// consumed by syncing when the node is simultaneously serving many peers.
+/// Syncing may also be capped outright (e.g., for nodes on metered links),
+/// either by the config (per time of day) or at runtime.
+#[derive(Debug)]
+pub struct BandwidthBudget {
+    bandwidth_config: AptosDataBandwidthConfig,
+    runtime_syncing_limit: Mutex<Option<u64>>,
+    served_bytes: Mutex<BandwidthUsageWindow>,
+    synced_bytes: Mutex<BandwidthUsageWindow>,
+    time_service: TimeService,
+}
+
+impl BandwidthBudget {
+    pub fn new(bandwidth_config: AptosDataBandwidthConfig, time_service: TimeService) -> Self {
+        let window_duration = Duration::from_millis(max(bandwidth_config.usage_window_ms, 1));
+        let start_time = time_service.now();
+
+        Self {
+            bandwidth_config,
+            runtime_syncing_limit: Mutex::new(None),
+            served_bytes: Mutex::new(BandwidthUsageWindow::new(window_duration, start_time)),
+            synced_bytes: Mutex::new(BandwidthUsageWindow::new(window_duration, start_time)),
+            time_service,
+        }
+    }
+
+    /// Records the number of bytes served to downstream peers
+    pub fn record_served_bytes(&self, num_bytes: u64) {
+        let time_now = self.time_service.now();
+        self.served_bytes.lock().record_bytes(num_bytes, time_now);
+    }
+
+    /// Records the number of bytes synced from upstream peers
+    pub fn record_synced_bytes(&self, num_bytes: u64) {
+        let time_now = self.time_service.now();
+        self.synced_bytes.lock().record_bytes(num_bytes, time_now);
+    }
+
+    /// Returns the estimated number of bytes per second served to downstream peers
+    pub fn get_served_bytes_per_second(&self) -> u64 {
+        let time_now = self.time_service.now();
+        self.served_bytes.lock().get_bytes_per_second(time_now)
+    }
+
+    /// Returns the estimated number of bytes per second synced from upstream peers
+    pub fn get_synced_bytes_per_second(&self) -> u64 {
+        let time_now = self.time_service.now();
+        self.synced_bytes.lock().get_bytes_per_second(time_now)
+    }
+
+    /// Sets (or clears) the runtime syncing cap (in bytes per second). While
+    /// set, the runtime cap overrides the configured caps (0 is uncapped).
+    pub fn set_runtime_syncing_limit(&self, runtime_syncing_limit: Option<u64>) {
+        *self.runtime_syncing_limit.lock() = runtime_syncing_limit;
+    }
+
+    /// Returns the cap (in bytes per second) on syncing that is currently
+    /// in effect, or None if syncing is not capped.
+    pub fn get_syncing_cap_bytes_per_second(&self) -> Option<u64> {
+        let syncing_cap = match *self.runtime_syncing_limit.lock() {
+            Some(runtime_syncing_limit) => runtime_syncing_limit,
+            None if self.is_off_peak() => {
+                self.bandwidth_config.off_peak_max_syncing_bytes_per_second
+            },
+            None => self.bandwidth_config.max_syncing_bytes_per_second,
+        };
+        (syncing_cap > 0).then_some(syncing_cap)
+    }
+
+    /// Returns the maximum number of bytes per second that syncing may
+    /// currently consume (or None, if syncing is unlimited). This is the
+    /// lower of the budgeted limit (if budgeting is enabled) and the cap.
+    pub fn get_syncing_bytes_per_second_limit(&self) -> Option<u64> {
+        let budgeted_limit = self
+            .bandwidth_config
+            .enable_bandwidth_budgeting
+            .then(|| self.get_budgeted_syncing_bytes_per_second());
+        match (budgeted_limit, self.get_syncing_cap_bytes_per_second()) {
+            (Some(budgeted_limit), Some(syncing_cap)) => Some(min(budgeted_limit, syncing_cap)),
+            (budgeted_limit, syncing_cap) => budgeted_limit.or(syncing_cap),
+        }
+    }
+
+    /// Returns a snapshot of the bandwidth budget state
+    pub fn get_summary(&self) -> BandwidthBudgetSummary {
+        BandwidthBudgetSummary {
+            config: self.bandwidth_config,
+            runtime_syncing_limit_bytes_per_second: *self.runtime_syncing_limit.lock(),
+            syncing_limit_bytes_per_second: self.get_syncing_bytes_per_second_limit(),
+            is_off_peak: self.is_off_peak(),
+            served_bytes_per_second: self.get_served_bytes_per_second(),
+            synced_bytes_per_second: self.get_synced_bytes_per_second(),
+        }
+    }
+
+    /// Returns the maximum number of bytes per second that syncing may
+    /// consume according to the budget. Serving may claim up to its priority
+    /// share of the total budget; any unclaimed bandwidth is available to syncing.
+    fn get_budgeted_syncing_bytes_per_second(&self) -> u64 {
+        let total_bytes_per_second = self.bandwidth_config.max_total_bytes_per_second;
+
+        // Calculate the share of the budget reserved for serving
+        let serving_weight = self.bandwidth_config.serving_priority_weight as u128;
+        let syncing_weight = self.bandwidth_config.syncing_priority_weight as u128;
+        let total_weight = serving_weight + syncing_weight;
+        let serving_share = if total_weight == 0 {
+            total_bytes_per_second / 2 // Treat the priorities as equal
+        } else {
+            ((total_bytes_per_second as u128 * serving_weight) / total_weight) as u64
+        };
+
+        // Serving only claims the bandwidth it is actually using (up to its share)
+        let serving_claim = min(self.get_served_bytes_per_second(), serving_share);
+        let syncing_limit = total_bytes_per_second.saturating_sub(serving_claim);
+
+        max(
+            syncing_limit,
+            self.bandwidth_config.min_syncing_bytes_per_second,
+        )
+    }
+
+    /// Returns the delay that should be applied before sending the next
+    /// data client request (or None, if the request can be sent immediately).
+    pub fn get_syncing_throttle_delay(&self) -> Option<Duration> {
+        // If syncing is unlimited, never throttle
+        let syncing_limit = max(self.get_syncing_bytes_per_second_limit()?, 1);
+
+        // If syncing is within the limit, there's no need to throttle
+        let synced_bytes_per_second = self.get_synced_bytes_per_second();
+        if synced_bytes_per_second < syncing_limit {
+            return None;
+        }
+
+        // Otherwise, delay proportionally to the amount of excess bandwidth
+        let excess_bytes = synced_bytes_per_second - syncing_limit;
+        let delay_ms = ((excess_bytes as u128 * 1000) / syncing_limit as u128) as u64;
+        let delay_ms = min(
+            max(delay_ms, 1),
+            self.bandwidth_config.max_throttle_delay_ms,
+        );
+        Some(Duration::from_millis(delay_ms))
+    }
+
+    /// Returns true iff the current (UTC) hour is in the off-peak window
+    fn is_off_peak(&self) -> bool {
+        let hour_utc =
+            (self.time_service.now_unix_time().as_secs() / SECONDS_PER_HOUR) % HOURS_PER_DAY;
+        self.bandwidth_config.is_off_peak_hour(hour_utc)
+    }
+}
+
+/// A simple sliding window counter used to estimate bandwidth usage.
+/// The estimate is computed by weighting the bytes of the previous
+/// window by the fraction of the window that still overlaps.
+#[derive(Debug)]
+struct BandwidthUsageWindow {
+    current_window_bytes: u64,
+    current_window_start: Instant,
+    previous_window_bytes: u64,
+    window_duration: Duration,
+}
+
+impl BandwidthUsageWindow {
+    fn new(window_duration: Duration, start_time: Instant) -> Self {
+        Self {
+            current_window_bytes: 0,
+            current_window_start: start_time,
+            previous_window_bytes: 0,
+            window_duration,
+        }
+    }
+
+    /// Records the given number of bytes in the current window
+    fn record_bytes(&mut self, num_bytes: u64, time_now: Instant) {
+        self.advance_window(time_now);
+        self.current_window_bytes = self.current_window_bytes.saturating_add(num_bytes);
+    }
+
+    /// Returns the estimated number of bytes per second
+    fn get_bytes_per_second(&mut self, time_now: Instant) -> u64 {
+        self.advance_window(time_now);
+
+        // Calculate the weighted number of bytes in the sliding window
+        let window_ms = self.window_duration.as_millis();
+        let elapsed_ms = min(
+            time_now
+                .saturating_duration_since(self.current_window_start)
+                .as_millis(),
+            window_ms,
+        );
+        let previous_weight_ms = window_ms - elapsed_ms;
+        let window_bytes = (self.previous_window_bytes as u128 * previous_weight_ms) / window_ms
+            + self.current_window_bytes as u128;
+
+        // Normalize the number of bytes to a per second rate
+        ((window_bytes * 1000) / window_ms) as u64
+    }
+
+    /// Advances the window (if the current window has expired)
+    fn advance_window(&mut self, time_now: Instant) {
+        let elapsed = time_now.saturating_duration_since(self.current_window_start);
+        if elapsed < self.window_duration {
+            return; // The current window is still active
+        }
+
+        // If more than one window has elapsed, the previous window is empty
+        self.previous_window_bytes = if elapsed < self.window_duration * 2 {
+            self.current_window_bytes
+        } else {
+            0
+        };
+        self.current_window_bytes = 0;
+
+        // Align the window start to the window duration
+        let num_windows = (elapsed.as_millis() / self.window_duration.as_millis()) as u32;
+        self.current_window_start += self.window_duration * num_windows;
+    }
+}
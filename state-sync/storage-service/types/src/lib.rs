@@ -9,6 +9,7 @@ use responses::StorageServiceResponse;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+pub mod bandwidth;
 pub mod requests;
 pub mod responses;
 
@@ -35,6 +36,8 @@ pub enum StorageServiceError {
     InvalidRequest(String),
     #[error("Too many invalid requests! Back off required: {0}")]
     TooManyInvalidRequests(String),
+    #[error("Too many concurrent requests! Back off required: {0}")]
+    TooManyConcurrentRequests(String),
 }
 
 /// A single storage service message sent or received over AptosNet.
@@ -1,8 +1,14 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::COMPRESSION_SUFFIX_LABEL;
-use aptos_types::transaction::Version;
+use crate::{responses::ContinuationToken, COMPRESSION_SUFFIX_LABEL};
+use aptos_compression::codec::CompressionCodec;
+use aptos_types::{
+    contract_event::ContractEvent,
+    event::EventKey,
+    transaction::{TransactionOutput, TransactionOutputListWithProof, Version},
+};
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag};
 use serde::{Deserialize, Serialize};
 
 /// A storage service request.
@@ -10,6 +16,12 @@ use serde::{Deserialize, Serialize};
 pub struct StorageServiceRequest {
     pub data_request: DataRequest, // The data to fetch from the storage service
     pub use_compression: bool,     // Whether or not the client wishes data to be compressed
+
+    // The compression codec negotiated with the client (if any). This is never
+    // sent over the wire: the server sets it for every inbound request from a
+    // peer that has negotiated a codec (see `DataRequest::NegotiateCompression`).
+    #[serde(skip)]
+    pub compression_codec: Option<CompressionCodec>,
 }
 
 impl StorageServiceRequest {
@@ -17,9 +29,16 @@ impl StorageServiceRequest {
         Self {
             data_request,
             use_compression,
+            compression_codec: None,
         }
     }
 
+    /// Sets the compression codec negotiated with the client
+    pub fn with_compression_codec(mut self, compression_codec: Option<CompressionCodec>) -> Self {
+        self.compression_codec = compression_codec;
+        self
+    }
+
     /// Returns a summary label for the request
     pub fn get_label(&self) -> String {
         let mut label = self.data_request.get_label().to_string();
@@ -47,6 +66,16 @@ pub enum DataRequest {
     SubscribeTransactionOutputsWithProof(SubscribeTransactionOutputsWithProofRequest), // Subscribes to transaction outputs with a proof
     SubscribeTransactionsOrOutputsWithProof(SubscribeTransactionsOrOutputsWithProofRequest), // Subscribes to transactions or outputs with a proof
     SubscribeTransactionsWithProof(SubscribeTransactionsWithProofRequest), // Subscribes to transactions with a proof
+    GetStateValueChangesWithProof(StateValueChangesWithProofRequest), // Fetches the state values changed between two versions with a proof
+    GetEventsByKeyWithProof(EventsByKeyWithProofRequest), // Fetches a list of events for an event key with a proof
+    GetPaginatedStateValuesWithProof(PaginatedStateValuesWithProofRequest), // Fetches a page of states with a proof (resumable via a continuation token)
+    GetPaginatedTransactionsWithProof(PaginatedTransactionsWithProofRequest), // Fetches a page of transactions with a proof (resumable via a continuation token)
+    NegotiateCompression(CompressionNegotiationRequest), // Negotiates the compression codec for the connection
+    GetDataWindows, // Fetches the data windows retained by the server (e.g., if the server is pruned)
+    GetNewFilteredTransactionOutputsWithProof(NewFilteredTransactionOutputsWithProofRequest), // Optimistically fetches new transaction outputs (with filtered events)
+    SubscribeFilteredTransactionOutputsWithProof(
+        SubscribeFilteredTransactionOutputsWithProofRequest,
+    ), // Subscribes to transaction outputs with a proof (with filtered events)
 }
 
 impl DataRequest {
@@ -73,6 +102,18 @@ impl DataRequest {
                 "subscribe_transactions_or_outputs_with_proof"
             },
             Self::SubscribeTransactionsWithProof(_) => "subscribe_transactions_with_proof",
+            Self::GetStateValueChangesWithProof(_) => "get_state_value_changes_with_proof",
+            Self::GetEventsByKeyWithProof(_) => "get_events_by_key_with_proof",
+            Self::GetPaginatedStateValuesWithProof(_) => "get_paginated_state_values_with_proof",
+            Self::GetPaginatedTransactionsWithProof(_) => "get_paginated_transactions_with_proof",
+            Self::NegotiateCompression(_) => "negotiate_compression",
+            Self::GetDataWindows => "get_data_windows",
+            Self::GetNewFilteredTransactionOutputsWithProof(_) => {
+                "get_new_filtered_transaction_outputs_with_proof"
+            },
+            Self::SubscribeFilteredTransactionOutputsWithProof(_) => {
+                "subscribe_filtered_transaction_outputs_with_proof"
+            },
         }
     }
 
@@ -80,6 +121,15 @@ impl DataRequest {
         matches!(self, &Self::GetNewTransactionOutputsWithProof(_))
             || matches!(self, &Self::GetNewTransactionsWithProof(_))
             || matches!(self, Self::GetNewTransactionsOrOutputsWithProof(_))
+            || matches!(self, Self::GetNewFilteredTransactionOutputsWithProof(_))
+    }
+
+    pub fn is_data_windows_request(&self) -> bool {
+        matches!(self, &Self::GetDataWindows)
+    }
+
+    pub fn is_compression_negotiation_request(&self) -> bool {
+        matches!(self, &Self::NegotiateCompression(_))
     }
 
     pub fn is_protocol_version_request(&self) -> bool {
@@ -94,9 +144,17 @@ impl DataRequest {
         matches!(self, &Self::SubscribeTransactionOutputsWithProof(_))
             || matches!(self, &Self::SubscribeTransactionsWithProof(_))
             || matches!(self, Self::SubscribeTransactionsOrOutputsWithProof(_))
+            || matches!(self, Self::SubscribeFilteredTransactionOutputsWithProof(_))
     }
 }
 
+/// A storage service request for negotiating the compression codec used
+/// for all compressed responses sent to the client (on the connection).
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct CompressionNegotiationRequest {
+    pub supported_codecs: Vec<CompressionCodec>, // The codecs supported by the client
+}
+
 /// A storage service request for fetching a list of epoch ending ledger infos.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 pub struct EpochEndingLedgerInfoRequest {
@@ -112,6 +170,16 @@ pub struct NewTransactionOutputsWithProofRequest {
     pub known_epoch: u64,   // The highest known epoch
 }
 
+/// A storage service request for fetching a new transaction output list
+/// beyond the already known version and epoch, where the events in the
+/// outputs are filtered by the server (before the response is sent).
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct NewFilteredTransactionOutputsWithProofRequest {
+    pub known_version: u64,        // The highest known output version
+    pub known_epoch: u64,          // The highest known epoch
+    pub event_filter: EventFilter, // The filter to apply to the events in the outputs
+}
+
 /// A storage service request for fetching a new transaction list
 /// beyond the already known version and epoch.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -130,6 +198,52 @@ pub struct StateValuesWithProofRequest {
     pub end_index: u64,   // The index to stop fetching state values (inclusive)
 }
 
+/// A storage service request for fetching the state values that changed
+/// between two versions (i.e., a state delta), with a proof for each value
+/// at the end version. This allows nodes that are only slightly behind to
+/// incrementally update their state snapshot.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct StateValueChangesWithProofRequest {
+    pub proof_version: u64, // The version the proof should be relative to
+    pub start_version: u64, // The version already synced by the client (exclusive)
+    pub end_version: u64,   // The version to fetch the state value changes up to (inclusive)
+}
+
+/// A storage service request for fetching the events emitted for a single
+/// event key (i.e., event handle), by sequence number, with a proof.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct EventsByKeyWithProofRequest {
+    pub event_key: EventKey,        // The event key to fetch the events for
+    pub start_sequence_number: u64, // The sequence number of the first event to fetch
+    pub end_sequence_number: u64,   // The sequence number of the last event to fetch (inclusive)
+    pub proof_version: u64,         // The version the proof should be relative to
+}
+
+/// A storage service request for fetching a page of state values at a
+/// specified version. If a previous response for the same range was truncated,
+/// the continuation token of that response resumes the read exactly where the
+/// response ended (instead of at `start_index`).
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct PaginatedStateValuesWithProofRequest {
+    pub version: u64,     // The version to fetch the state values at
+    pub start_index: u64, // The index to start fetching state values (inclusive)
+    pub end_index: u64,   // The index to stop fetching state values (inclusive)
+    pub continuation_token: Option<ContinuationToken>, // The token to resume a truncated read (if any)
+}
+
+/// A storage service request for fetching a page of transactions with a
+/// corresponding proof. If a previous response for the same range was
+/// truncated, the continuation token of that response resumes the read
+/// exactly where the response ended (instead of at `start_version`).
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct PaginatedTransactionsWithProofRequest {
+    pub proof_version: u64,   // The version the proof should be relative to
+    pub start_version: u64,   // The starting version of the transaction list
+    pub end_version: u64,     // The ending version of the transaction list (inclusive)
+    pub include_events: bool, // Whether or not to include events in the response
+    pub continuation_token: Option<ContinuationToken>, // The token to resume a truncated read (if any)
+}
+
 /// A storage service request for fetching a transaction output list with a
 /// corresponding proof.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -178,6 +292,16 @@ pub struct SubscribeTransactionOutputsWithProofRequest {
     pub subscription_stream_index: u64, // The request index of the subscription stream
 }
 
+/// A storage service request for subscribing to transaction outputs
+/// with a corresponding proof, where the events in the outputs are
+/// filtered by the server (before the response is sent).
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct SubscribeFilteredTransactionOutputsWithProofRequest {
+    pub subscription_stream_metadata: SubscriptionStreamMetadata, // The metadata for the subscription stream request
+    pub subscription_stream_index: u64, // The request index of the subscription stream
+    pub event_filter: EventFilter,      // The filter to apply to the events in the outputs
+}
+
 /// A storage service request for subscribing to transactions
 /// or outputs with a corresponding proof.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
@@ -203,3 +327,60 @@ pub struct SubscriptionStreamMetadata {
     pub known_epoch_at_stream_start: u64,   // The highest known epoch at stream start
     pub subscription_stream_id: u64,        // The unique id of the subscription stream
 }
+
+/// A filter for the events in transaction outputs. This allows clients that
+/// only apply write sets to avoid downloading (unused) events. Note: the
+/// filtered events can no longer be verified against the event root hashes
+/// in the proofs, so clients must only verify the write sets of the outputs.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum EventFilter {
+    StripAllEvents,                     // Removes all events from the outputs
+    IncludeEventTypes(Vec<StructTag>),  // Only includes events with the given struct types
+    IncludeEventModules(Vec<ModuleId>), // Only includes events with types declared in the given modules
+}
+
+impl EventFilter {
+    /// Returns true iff the given event should be included in the outputs
+    pub fn includes_event(&self, event: &ContractEvent) -> bool {
+        let struct_tag = match event.type_tag() {
+            TypeTag::Struct(struct_tag) => struct_tag,
+            _ => return false, // Only struct events can be filtered by type
+        };
+        match self {
+            Self::StripAllEvents => false,
+            Self::IncludeEventTypes(struct_tags) => struct_tags.contains(struct_tag),
+            Self::IncludeEventModules(module_ids) => module_ids.contains(&struct_tag.module_id()),
+        }
+    }
+
+    /// Applies the filter to the events of the given outputs
+    pub fn filter_outputs(
+        &self,
+        outputs_with_proof: TransactionOutputListWithProof,
+    ) -> TransactionOutputListWithProof {
+        let TransactionOutputListWithProof {
+            transactions_and_outputs,
+            first_transaction_output_version,
+            proof,
+        } = outputs_with_proof;
+        let transactions_and_outputs = transactions_and_outputs
+            .into_iter()
+            .map(|(transaction, output)| (transaction, self.filter_output(output)))
+            .collect();
+        TransactionOutputListWithProof::new(
+            transactions_and_outputs,
+            first_transaction_output_version,
+            proof,
+        )
+    }
+
+    /// Applies the filter to the events of the given output
+    fn filter_output(&self, output: TransactionOutput) -> TransactionOutput {
+        let (write_set, events, gas_used, status, auxiliary_data) = output.unpack();
+        let events = events
+            .into_iter()
+            .filter(|event| self.includes_event(event))
+            .collect();
+        TransactionOutput::new(write_set, events, gas_used, status, auxiliary_data)
+    }
+}
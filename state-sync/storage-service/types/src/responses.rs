@@ -3,25 +3,35 @@
 
 use crate::{
     requests::DataRequest::{
-        GetEpochEndingLedgerInfos, GetNewTransactionOutputsWithProof,
+        GetDataWindows, GetEpochEndingLedgerInfos, GetEventsByKeyWithProof,
+        GetNewFilteredTransactionOutputsWithProof, GetNewTransactionOutputsWithProof,
         GetNewTransactionsOrOutputsWithProof, GetNewTransactionsWithProof,
-        GetNumberOfStatesAtVersion, GetServerProtocolVersion, GetStateValuesWithProof,
-        GetStorageServerSummary, GetTransactionOutputsWithProof, GetTransactionsOrOutputsWithProof,
-        GetTransactionsWithProof, SubscribeTransactionOutputsWithProof,
+        GetNumberOfStatesAtVersion, GetPaginatedStateValuesWithProof,
+        GetPaginatedTransactionsWithProof, GetServerProtocolVersion, GetStateValueChangesWithProof,
+        GetStateValuesWithProof, GetStorageServerSummary, GetTransactionOutputsWithProof,
+        GetTransactionsOrOutputsWithProof, GetTransactionsWithProof, NegotiateCompression,
+        SubscribeFilteredTransactionOutputsWithProof, SubscribeTransactionOutputsWithProof,
         SubscribeTransactionsOrOutputsWithProof, SubscribeTransactionsWithProof,
     },
     responses::Error::DegenerateRangeError,
     Epoch, StorageServiceRequest, COMPRESSION_SUFFIX_LABEL,
 };
-use aptos_compression::{client::CompressionClient, CompressedData};
+use aptos_compression::{client::CompressionClient, codec::CompressionCodec, CompressedData};
 use aptos_config::config::{
     AptosDataClientConfig, StorageServiceConfig, MAX_APPLICATION_MESSAGE_SIZE,
 };
+use aptos_crypto::hash::CryptoHash;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{
+    contract_event::{ContractEvent, EventWithVersion},
     epoch_change::EpochChangeProof,
-    ledger_info::LedgerInfoWithSignatures,
-    state_store::state_value::StateValueChunkWithProof,
+    event::EventKey,
+    ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
+    proof::{accumulator::InMemoryEventAccumulator, SparseMerkleProof, TransactionInfoWithProof},
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValue, StateValueChunkWithProof},
+    },
     transaction::{TransactionListWithProof, TransactionOutputListWithProof, Version},
 };
 use num_traits::{PrimInt, Zero};
@@ -59,6 +69,7 @@ impl From<aptos_compression::Error> for Error {
 pub enum StorageServiceResponse {
     CompressedResponse(String, CompressedData), // Store the label and the data (e.g., for logging/metrics)
     RawResponse(DataResponse),
+    CodecCompressedResponse(String, CompressionCodec, CompressedData), // Store the label, the negotiated codec and the data
 }
 
 impl StorageServiceResponse {
@@ -85,6 +96,38 @@ impl StorageServiceResponse {
         }
     }
 
+    /// Creates a new response and performs compression if required. If a
+    /// compression codec has been negotiated with the client, the codec is
+    /// used. Otherwise, the default compression (i.e., LZ4) is used.
+    pub fn new_with_codec(
+        data_response: DataResponse,
+        perform_compression: bool,
+        compression_codec: Option<CompressionCodec>,
+    ) -> Result<Self, Error> {
+        let compression_codec = match compression_codec {
+            Some(compression_codec) if perform_compression => compression_codec,
+            _ => return Self::new(data_response, perform_compression),
+        };
+
+        // Serialize and compress the raw data using the codec
+        let raw_data = bcs::to_bytes(&data_response)
+            .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))?;
+        let compressed_data = aptos_compression::compress_with_codec(
+            raw_data,
+            compression_codec,
+            CompressionClient::StateSync,
+            MAX_APPLICATION_MESSAGE_SIZE,
+        )?;
+
+        // Create the compressed response
+        let label = data_response.get_label().to_string() + COMPRESSION_SUFFIX_LABEL;
+        Ok(StorageServiceResponse::CodecCompressedResponse(
+            label,
+            compression_codec,
+            compressed_data,
+        ))
+    }
+
     /// Returns the data response regardless of the inner format
     pub fn get_data_response(&self) -> Result<DataResponse, Error> {
         match self {
@@ -99,6 +142,21 @@ impl StorageServiceResponse {
                 Ok(data_response)
             },
             StorageServiceResponse::RawResponse(data_response) => Ok(data_response.clone()),
+            StorageServiceResponse::CodecCompressedResponse(
+                _,
+                compression_codec,
+                compressed_data,
+            ) => {
+                let raw_data = aptos_compression::decompress_with_codec(
+                    compressed_data,
+                    *compression_codec,
+                    CompressionClient::StateSync,
+                    MAX_APPLICATION_MESSAGE_SIZE,
+                )?;
+                let data_response = bcs::from_bytes::<DataResponse>(&raw_data)
+                    .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string()))?;
+                Ok(data_response)
+            },
         }
     }
 
@@ -109,12 +167,31 @@ impl StorageServiceResponse {
             StorageServiceResponse::RawResponse(data_response) => {
                 data_response.get_label().to_string()
             },
+            StorageServiceResponse::CodecCompressedResponse(label, _, _) => label.clone(),
         }
     }
 
     /// Returns true iff the data response is compressed
     pub fn is_compressed(&self) -> bool {
-        matches!(self, Self::CompressedResponse(_, _))
+        matches!(
+            self,
+            Self::CompressedResponse(_, _) | Self::CodecCompressedResponse(_, _, _)
+        )
+    }
+
+    /// Returns the number of bytes in the response. For compressed responses,
+    /// this is the size of the compressed data (i.e., the bytes received over
+    /// the wire), which avoids re-serializing the response.
+    pub fn get_num_bytes(&self) -> Result<u64, Error> {
+        match self {
+            StorageServiceResponse::CompressedResponse(_, compressed_data)
+            | StorageServiceResponse::CodecCompressedResponse(_, _, compressed_data) => {
+                Ok(compressed_data.len() as u64)
+            },
+            StorageServiceResponse::RawResponse(_) => bcs::serialized_size(self)
+                .map(|num_bytes| num_bytes as u64)
+                .map_err(|error| Error::UnexpectedErrorEncountered(error.to_string())),
+        }
     }
 }
 
@@ -124,6 +201,206 @@ pub type TransactionOrOutputListWithProof = (
     Option<TransactionOutputListWithProof>,
 );
 
+/// A list of state values that changed between two versions (i.e., from
+/// `start_version` (exclusive) to `end_version` (inclusive)), each with a
+/// sparse merkle proof against the state root at `end_version`. Deleted
+/// state values are represented as `None`.
+///
+/// Note: the proofs authenticate each returned state value, but not the
+/// completeness of the changes. Clients must also verify that applying the
+/// changes to their local state produces the expected state root.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateValueChangesWithProof {
+    pub start_version: Version, // The version the changes apply on top of (exclusive)
+    pub end_version: Version,   // The version the changes end at (inclusive)
+    pub state_value_changes: Vec<(StateKey, Option<StateValue>, SparseMerkleProof)>,
+    pub transaction_info_with_proof: TransactionInfoWithProof, // The transaction info at `end_version`
+}
+
+impl StateValueChangesWithProof {
+    /// Verifies the transaction info at the end version against the given
+    /// ledger info, and each state value change against the state root.
+    pub fn verify(&self, ledger_info: &LedgerInfo) -> crate::Result<(), Error> {
+        // Verify the transaction info at the end version
+        self.transaction_info_with_proof
+            .verify(ledger_info, self.end_version)
+            .map_err(|error| {
+                Error::UnexpectedResponseError(format!(
+                    "Failed to verify the transaction info at version {}: {:?}",
+                    self.end_version, error
+                ))
+            })?;
+
+        // Verify each state value change against the state root
+        let state_root_hash = self
+            .transaction_info_with_proof
+            .transaction_info()
+            .ensure_state_checkpoint_hash()
+            .map_err(|error| Error::UnexpectedResponseError(error.to_string()))?;
+        for (state_key, state_value, proof) in &self.state_value_changes {
+            proof
+                .verify(state_root_hash, state_key.hash(), state_value.as_ref())
+                .map_err(|error| {
+                    Error::UnexpectedResponseError(format!(
+                        "Failed to verify the state value change for key {:?}: {:?}",
+                        state_key, error
+                    ))
+                })?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The events emitted by a single transaction, with a proof of the
+/// transaction info (which authenticates the events via the event root hash).
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct TransactionEventsWithProof {
+    pub version: Version,           // The version of the transaction
+    pub events: Vec<ContractEvent>, // All events emitted by the transaction
+    pub transaction_info_with_proof: TransactionInfoWithProof, // The transaction info at `version`
+}
+
+/// A list of events emitted for a single event key, starting at
+/// `start_sequence_number`. Each event is returned along with all other
+/// events emitted by the same transaction, so that the events can be
+/// authenticated against the event root hash of the transaction info.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct EventsByKeyWithProof {
+    pub event_key: EventKey,        // The event key the events were emitted for
+    pub start_sequence_number: u64, // The sequence number of the first event
+    pub transaction_events: Vec<TransactionEventsWithProof>, // Ordered by version
+}
+
+impl EventsByKeyWithProof {
+    /// Returns the events emitted for the event key (ordered by sequence number)
+    pub fn get_events(&self) -> Vec<EventWithVersion> {
+        self.transaction_events
+            .iter()
+            .flat_map(|transaction_events| {
+                transaction_events
+                    .events
+                    .iter()
+                    .filter(|event| event.event_key() == Some(&self.event_key))
+                    .map(|event| EventWithVersion::new(transaction_events.version, event.clone()))
+            })
+            .collect()
+    }
+
+    /// Returns the number of events emitted for the event key
+    pub fn get_num_events(&self) -> usize {
+        self.get_events().len()
+    }
+
+    /// Verifies the transaction info of each transaction against the given
+    /// ledger info, and the events of each transaction against the event root
+    /// hash. Also verifies that the events emitted for the event key have
+    /// contiguous sequence numbers, starting at `start_sequence_number`.
+    pub fn verify(&self, ledger_info: &LedgerInfo) -> crate::Result<(), Error> {
+        let mut last_version = None;
+        for transaction_events in &self.transaction_events {
+            // Verify the transactions are ordered by version
+            let version = transaction_events.version;
+            if last_version.map_or(false, |last_version| version <= last_version) {
+                return Err(Error::UnexpectedResponseError(format!(
+                    "The transactions are not ordered by version! Version: {}, last version: {:?}",
+                    version, last_version
+                )));
+            }
+            last_version = Some(version);
+
+            // Verify the transaction info
+            transaction_events
+                .transaction_info_with_proof
+                .verify(ledger_info, version)
+                .map_err(|error| {
+                    Error::UnexpectedResponseError(format!(
+                        "Failed to verify the transaction info at version {}: {:?}",
+                        version, error
+                    ))
+                })?;
+
+            // Verify the events against the event root hash
+            let event_hashes: Vec<_> = transaction_events
+                .events
+                .iter()
+                .map(CryptoHash::hash)
+                .collect();
+            let event_root_hash = InMemoryEventAccumulator::from_leaves(&event_hashes).root_hash();
+            let expected_event_root_hash = transaction_events
+                .transaction_info_with_proof
+                .transaction_info()
+                .event_root_hash();
+            if event_root_hash != expected_event_root_hash {
+                return Err(Error::UnexpectedResponseError(format!(
+                    "The event root hash does not match for version {}! Calculated: {:?}, expected: {:?}",
+                    version, event_root_hash, expected_event_root_hash
+                )));
+            }
+        }
+
+        // Verify the sequence numbers of the events are contiguous
+        let mut expected_sequence_number = self.start_sequence_number;
+        for event in self.get_events() {
+            let sequence_number = event
+                .event
+                .v1()
+                .map_err(|error| Error::UnexpectedResponseError(error.to_string()))?
+                .sequence_number();
+            if sequence_number != expected_sequence_number {
+                return Err(Error::UnexpectedResponseError(format!(
+                    "The event sequence numbers are not contiguous! Found: {}, expected: {}",
+                    sequence_number, expected_sequence_number
+                )));
+            }
+            expected_sequence_number = expected_sequence_number.saturating_add(1);
+        }
+
+        Ok(())
+    }
+}
+
+/// An opaque token that allows clients to resume a truncated range read
+/// exactly where the previous response ended. The contents of the token are
+/// defined (and validated) by the server, so clients should simply return
+/// the token to the server unmodified.
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct ContinuationToken(Vec<u8>);
+
+impl ContinuationToken {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the raw bytes of the token
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+/// A single page of a paginated range read. If the response was truncated
+/// (e.g., because the requested range exceeded the server's chunk or network
+/// limits), the continuation token can be used to fetch the next page.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct PaginatedResponse<T> {
+    pub data: T,                                       // The data of the page
+    pub continuation_token: Option<ContinuationToken>, // The token to fetch the next page (None iff the read is complete)
+}
+
+impl<T> PaginatedResponse<T> {
+    pub fn new(data: T, continuation_token: Option<ContinuationToken>) -> Self {
+        Self {
+            data,
+            continuation_token,
+        }
+    }
+
+    /// Returns true iff the range read is complete (i.e., there are no more pages)
+    pub fn is_complete(&self) -> bool {
+        self.continuation_token.is_none()
+    }
+}
+
 /// A single data response.
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[allow(clippy::large_enum_variant)]
@@ -139,6 +416,12 @@ pub enum DataResponse {
     TransactionsWithProof(TransactionListWithProof),
     NewTransactionsOrOutputsWithProof((TransactionOrOutputListWithProof, LedgerInfoWithSignatures)),
     TransactionsOrOutputsWithProof(TransactionOrOutputListWithProof),
+    StateValueChangesWithProof(StateValueChangesWithProof),
+    EventsByKeyWithProof(EventsByKeyWithProof),
+    PaginatedStateValueChunkWithProof(PaginatedResponse<StateValueChunkWithProof>),
+    PaginatedTransactionsWithProof(PaginatedResponse<TransactionListWithProof>),
+    NegotiatedCompressionCodec(CompressionCodec),
+    DataWindows(DataWindows),
 }
 
 impl DataResponse {
@@ -156,6 +439,12 @@ impl DataResponse {
             Self::TransactionsWithProof(_) => "transactions_with_proof",
             Self::NewTransactionsOrOutputsWithProof(_) => "new_transactions_or_outputs_with_proof",
             Self::TransactionsOrOutputsWithProof(_) => "transactions_or_outputs_with_proof",
+            Self::StateValueChangesWithProof(_) => "state_value_changes_with_proof",
+            Self::EventsByKeyWithProof(_) => "events_by_key_with_proof",
+            Self::PaginatedStateValueChunkWithProof(_) => "paginated_state_value_chunk_with_proof",
+            Self::PaginatedTransactionsWithProof(_) => "paginated_transactions_with_proof",
+            Self::NegotiatedCompressionCodec(_) => "negotiated_compression_codec",
+            Self::DataWindows(_) => "data_windows",
         }
     }
 }
@@ -193,6 +482,96 @@ impl TryFrom<StorageServiceResponse> for StateValueChunkWithProof {
     }
 }
 
+impl TryFrom<StorageServiceResponse> for StateValueChangesWithProof {
+    type Error = crate::responses::Error;
+
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::StateValueChangesWithProof(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected state_value_changes_with_proof, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
+impl TryFrom<StorageServiceResponse> for EventsByKeyWithProof {
+    type Error = crate::responses::Error;
+
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::EventsByKeyWithProof(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected events_by_key_with_proof, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
+impl TryFrom<StorageServiceResponse> for PaginatedResponse<StateValueChunkWithProof> {
+    type Error = crate::responses::Error;
+
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::PaginatedStateValueChunkWithProof(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected paginated_state_value_chunk_with_proof, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
+impl TryFrom<StorageServiceResponse> for PaginatedResponse<TransactionListWithProof> {
+    type Error = crate::responses::Error;
+
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::PaginatedTransactionsWithProof(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected paginated_transactions_with_proof, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
+impl TryFrom<StorageServiceResponse> for CompressionCodec {
+    type Error = crate::responses::Error;
+
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::NegotiatedCompressionCodec(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected negotiated_compression_codec, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
+impl TryFrom<StorageServiceResponse> for DataWindows {
+    type Error = crate::responses::Error;
+
+    fn try_from(response: StorageServiceResponse) -> crate::Result<Self, Self::Error> {
+        let data_response = response.get_data_response()?;
+        match data_response {
+            DataResponse::DataWindows(inner) => Ok(inner),
+            _ => Err(Error::UnexpectedResponseError(format!(
+                "expected data_windows, found {}",
+                data_response.get_label()
+            ))),
+        }
+    }
+}
+
 impl TryFrom<StorageServiceResponse> for EpochChangeProof {
     type Error = crate::responses::Error;
 
@@ -410,6 +789,78 @@ impl Default for ProtocolMetadata {
     }
 }
 
+/// The data windows retained by a storage server, i.e., the number of
+/// versions held for each data type before the data is pruned. Pruned
+/// servers advertise their windows so that clients can predict when the
+/// lowest versions in the data summary will be pruned.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub struct DataWindows {
+    /// The number of versions of ledger data (i.e., transactions, outputs
+    /// and events) retained, or `None` if the ledger data is not pruned.
+    pub ledger_window: Option<u64>,
+    /// The number of versions of states retained, or `None` if the states
+    /// are not pruned.
+    pub state_window: Option<u64>,
+}
+
+impl DataWindows {
+    /// Returns a copy of the given data summary where the lowest versions of
+    /// the pruned data ranges are raised by the safety margin (specified as
+    /// a percentage of the corresponding window). This prevents clients from
+    /// requesting data that is likely to be pruned before it can be served.
+    pub fn apply_to_data_summary(
+        &self,
+        data_summary: &DataSummary,
+        safety_margin_percent: u64,
+    ) -> DataSummary {
+        let ledger_window = self.ledger_window;
+        let state_window = self.state_window;
+        DataSummary {
+            synced_ledger_info: data_summary.synced_ledger_info.clone(),
+            epoch_ending_ledger_infos: data_summary.epoch_ending_ledger_infos,
+            states: apply_window_to_range(data_summary.states, state_window, safety_margin_percent),
+            transactions: apply_window_to_range(
+                data_summary.transactions,
+                ledger_window,
+                safety_margin_percent,
+            ),
+            transaction_outputs: apply_window_to_range(
+                data_summary.transaction_outputs,
+                ledger_window,
+                safety_margin_percent,
+            ),
+        }
+    }
+}
+
+/// Raises the lowest version of the given data range by the safety margin
+/// (if the data has started to be pruned, i.e., the range fills the window).
+/// If the range is smaller than the safety margin, `None` is returned.
+fn apply_window_to_range(
+    data_range: Option<CompleteDataRange<Version>>,
+    window: Option<u64>,
+    safety_margin_percent: u64,
+) -> Option<CompleteDataRange<Version>> {
+    let (data_range, window) = match (data_range, window) {
+        (Some(data_range), Some(window)) => (data_range, window),
+        _ => return data_range, // The data is not pruned
+    };
+
+    // If the range doesn't fill the window, no data has been pruned yet
+    let num_versions = data_range
+        .highest()
+        .saturating_sub(data_range.lowest())
+        .saturating_add(1);
+    if num_versions < window {
+        return Some(data_range);
+    }
+
+    // Otherwise, raise the lowest version by the safety margin
+    let safety_margin = window.saturating_mul(safety_margin_percent) / 100;
+    let safe_lowest_version = data_range.lowest().saturating_add(safety_margin);
+    CompleteDataRange::new(safe_lowest_version, data_range.highest()).ok()
+}
+
 /// A summary of the data actually held by the storage service instance.
 #[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub struct DataSummary {
@@ -442,7 +893,10 @@ impl DataSummary {
         request: &StorageServiceRequest,
     ) -> bool {
         match &request.data_request {
-            GetServerProtocolVersion | GetStorageServerSummary => true,
+            GetServerProtocolVersion
+            | GetStorageServerSummary
+            | NegotiateCompression(_)
+            | GetDataWindows => true,
             GetEpochEndingLedgerInfos(request) => {
                 let desired_range =
                     match CompleteDataRange::new(request.start_epoch, request.expected_end_epoch) {
@@ -453,6 +907,28 @@ impl DataSummary {
                     .map(|range| range.superset_of(&desired_range))
                     .unwrap_or(false)
             },
+            GetEventsByKeyWithProof(request) => {
+                // The versions of the events are unknown until they are fetched,
+                // so we can only verify that the proof can be created (and that
+                // transactions are held up to the proof version).
+                let can_serve_txns = self
+                    .transactions
+                    .map(|range| range.contains(request.proof_version))
+                    .unwrap_or(false);
+
+                let can_create_proof = self
+                    .synced_ledger_info
+                    .as_ref()
+                    .map(|li| li.ledger_info().version() >= request.proof_version)
+                    .unwrap_or(false);
+
+                can_serve_txns && can_create_proof
+            },
+            GetNewFilteredTransactionOutputsWithProof(_) => can_service_optimistic_request(
+                aptos_data_client_config,
+                time_service,
+                self.synced_ledger_info.as_ref(),
+            ),
             GetNewTransactionOutputsWithProof(_) => can_service_optimistic_request(
                 aptos_data_client_config,
                 time_service,
@@ -472,21 +948,61 @@ impl DataSummary {
                 .states
                 .map(|range| range.contains(*version))
                 .unwrap_or(false),
-            GetStateValuesWithProof(request) => {
-                let proof_version = request.version;
+            GetPaginatedStateValuesWithProof(request) => {
+                self.can_service_states_at_version(request.version)
+            },
+            GetPaginatedTransactionsWithProof(request) => {
+                let desired_range =
+                    match CompleteDataRange::new(request.start_version, request.end_version) {
+                        Ok(desired_range) => desired_range,
+                        Err(_) => return false,
+                    };
+
+                let can_serve_txns = self
+                    .transactions
+                    .map(|range| range.superset_of(&desired_range))
+                    .unwrap_or(false);
+
+                let can_create_proof = self
+                    .synced_ledger_info
+                    .as_ref()
+                    .map(|li| li.ledger_info().version() >= request.proof_version)
+                    .unwrap_or(false);
+
+                can_serve_txns && can_create_proof
+            },
+            GetStateValuesWithProof(request) => self.can_service_states_at_version(request.version),
+            GetStateValueChangesWithProof(request) => {
+                // The changes are derived from the write sets (i.e., outputs)
+                // after the start version, and proven against the end version.
+                let desired_range =
+                    match request
+                        .start_version
+                        .checked_add(1)
+                        .and_then(|start_version| {
+                            CompleteDataRange::new(start_version, request.end_version).ok()
+                        }) {
+                        Some(desired_range) => desired_range,
+                        None => return false,
+                    };
+
+                let can_serve_outputs = self
+                    .transaction_outputs
+                    .map(|range| range.superset_of(&desired_range))
+                    .unwrap_or(false);
 
                 let can_serve_states = self
                     .states
-                    .map(|range| range.contains(request.version))
+                    .map(|range| range.contains(request.end_version))
                     .unwrap_or(false);
 
                 let can_create_proof = self
                     .synced_ledger_info
                     .as_ref()
-                    .map(|li| li.ledger_info().version() >= proof_version)
+                    .map(|li| li.ledger_info().version() >= request.proof_version)
                     .unwrap_or(false);
 
-                can_serve_states && can_create_proof
+                can_serve_outputs && can_serve_states && can_create_proof
             },
             GetTransactionOutputsWithProof(request) => {
                 let desired_range =
@@ -553,6 +1069,11 @@ impl DataSummary {
 
                 can_serve_txns && can_serve_outputs && can_create_proof
             },
+            SubscribeFilteredTransactionOutputsWithProof(_) => can_service_subscription_request(
+                aptos_data_client_config,
+                time_service,
+                self.synced_ledger_info.as_ref(),
+            ),
             SubscribeTransactionOutputsWithProof(_) => can_service_subscription_request(
                 aptos_data_client_config,
                 time_service,
@@ -571,6 +1092,23 @@ impl DataSummary {
         }
     }
 
+    /// Returns true iff the states at the given version can be served
+    /// (and proven using the synced ledger info).
+    fn can_service_states_at_version(&self, version: Version) -> bool {
+        let can_serve_states = self
+            .states
+            .map(|range| range.contains(version))
+            .unwrap_or(false);
+
+        let can_create_proof = self
+            .synced_ledger_info
+            .as_ref()
+            .map(|li| li.ledger_info().version() >= version)
+            .unwrap_or(false);
+
+        can_serve_states && can_create_proof
+    }
+
     /// Returns the version of the synced ledger info (if one exists)
     pub fn get_synced_ledger_info_version(&self) -> Option<u64> {
         self.synced_ledger_info
@@ -2,29 +2,270 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    bandwidth::BandwidthBudget,
     requests::{
-        DataRequest, EpochEndingLedgerInfoRequest, NewTransactionOutputsWithProofRequest,
+        DataRequest, EpochEndingLedgerInfoRequest, EventFilter, EventsByKeyWithProofRequest,
+        NewFilteredTransactionOutputsWithProofRequest, NewTransactionOutputsWithProofRequest,
         NewTransactionsOrOutputsWithProofRequest, NewTransactionsWithProofRequest,
-        StateValuesWithProofRequest, SubscribeTransactionOutputsWithProofRequest,
+        StateValueChangesWithProofRequest, StateValuesWithProofRequest,
+        SubscribeFilteredTransactionOutputsWithProofRequest,
+        SubscribeTransactionOutputsWithProofRequest,
         SubscribeTransactionsOrOutputsWithProofRequest, SubscribeTransactionsWithProofRequest,
         SubscriptionStreamMetadata, TransactionOutputsWithProofRequest,
         TransactionsOrOutputsWithProofRequest, TransactionsWithProofRequest,
     },
-    responses::{CompleteDataRange, DataSummary, ProtocolMetadata},
+    responses::{
+        CompleteDataRange, DataResponse, DataSummary, DataWindows, ProtocolMetadata,
+        StorageServiceResponse,
+    },
     Epoch, StorageServiceRequest,
 };
-use aptos_config::config::AptosDataClientConfig;
+use aptos_compression::codec::CompressionCodec;
+use aptos_config::config::{AptosDataBandwidthConfig, AptosDataClientConfig};
 use aptos_crypto::hash::HashValue;
 use aptos_time_service::{TimeService, TimeServiceTrait};
 use aptos_types::{
+    account_address::AccountAddress,
     aggregate_signature::AggregateSignature,
     block_info::BlockInfo,
+    contract_event::ContractEvent,
+    event::EventKey,
     ledger_info::{LedgerInfo, LedgerInfoWithSignatures},
     transaction::Version,
 };
 use claims::{assert_err, assert_ok};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag},
+};
 use proptest::{arbitrary::any, prelude::*};
 use rand::{thread_rng, Rng};
+use std::{str::FromStr, time::Duration};
+
+#[test]
+fn test_bandwidth_budget_throttling() {
+    // Create a bandwidth budget with a small total budget
+    let bandwidth_config = AptosDataBandwidthConfig {
+        enable_bandwidth_budgeting: true,
+        max_total_bytes_per_second: 1000,
+        min_syncing_bytes_per_second: 100,
+        max_throttle_delay_ms: 1000,
+        serving_priority_weight: 1,
+        syncing_priority_weight: 1,
+        usage_window_ms: 1000,
+        ..Default::default()
+    };
+    let time_service = TimeService::mock();
+    let bandwidth_budget = BandwidthBudget::new(bandwidth_config, time_service.clone());
+
+    // Sync some data and verify that no throttling occurs (nothing is being served)
+    bandwidth_budget.record_synced_bytes(500);
+    assert_eq!(
+        bandwidth_budget.get_syncing_bytes_per_second_limit(),
+        Some(1000)
+    );
+    assert_eq!(bandwidth_budget.get_syncing_throttle_delay(), None);
+
+    // Serve a lot of data and verify that serving only claims its share
+    bandwidth_budget.record_served_bytes(2000);
+    assert_eq!(bandwidth_budget.get_served_bytes_per_second(), 2000);
+    assert_eq!(
+        bandwidth_budget.get_syncing_bytes_per_second_limit(),
+        Some(500)
+    );
+
+    // Sync more data and verify that syncing is now throttled
+    bandwidth_budget.record_synced_bytes(1000);
+    assert_eq!(
+        bandwidth_budget.get_syncing_throttle_delay(),
+        Some(Duration::from_millis(1000))
+    );
+
+    // Elapse several windows and verify that the throttling is lifted
+    time_service.into_mock().advance_ms(2000);
+    assert_eq!(bandwidth_budget.get_served_bytes_per_second(), 0);
+    assert_eq!(bandwidth_budget.get_synced_bytes_per_second(), 0);
+    assert_eq!(bandwidth_budget.get_syncing_throttle_delay(), None);
+}
+
+#[test]
+fn test_bandwidth_budget_disabled() {
+    // Create a bandwidth budget with budgeting disabled
+    let bandwidth_config = AptosDataBandwidthConfig {
+        enable_bandwidth_budgeting: false,
+        max_total_bytes_per_second: 1000,
+        ..Default::default()
+    };
+    let bandwidth_budget = BandwidthBudget::new(bandwidth_config, TimeService::mock());
+
+    // Exceed the budget and verify that no throttling occurs
+    bandwidth_budget.record_served_bytes(10_000);
+    bandwidth_budget.record_synced_bytes(10_000);
+    assert_eq!(bandwidth_budget.get_syncing_throttle_delay(), None);
+}
+
+#[test]
+fn test_bandwidth_budget_syncing_cap() {
+    // Create a bandwidth budget with a syncing cap (but budgeting disabled)
+    let bandwidth_config = AptosDataBandwidthConfig {
+        enable_bandwidth_budgeting: false,
+        max_syncing_bytes_per_second: 1000,
+        max_throttle_delay_ms: 1000,
+        usage_window_ms: 1000,
+        ..Default::default()
+    };
+    let bandwidth_budget = BandwidthBudget::new(bandwidth_config, TimeService::mock());
+
+    // Verify that syncing is capped (regardless of the serving load)
+    assert_eq!(
+        bandwidth_budget.get_syncing_bytes_per_second_limit(),
+        Some(1000)
+    );
+    bandwidth_budget.record_synced_bytes(500);
+    assert_eq!(bandwidth_budget.get_syncing_throttle_delay(), None);
+    bandwidth_budget.record_synced_bytes(1000);
+    assert_eq!(
+        bandwidth_budget.get_syncing_throttle_delay(),
+        Some(Duration::from_millis(500))
+    );
+
+    // Enable budgeting with a higher budgeted limit and verify the cap still applies
+    let bandwidth_config = AptosDataBandwidthConfig {
+        enable_bandwidth_budgeting: true,
+        max_syncing_bytes_per_second: 1000,
+        max_total_bytes_per_second: 10_000,
+        min_syncing_bytes_per_second: 100,
+        ..Default::default()
+    };
+    let bandwidth_budget = BandwidthBudget::new(bandwidth_config, TimeService::mock());
+    assert_eq!(
+        bandwidth_budget.get_syncing_bytes_per_second_limit(),
+        Some(1000)
+    );
+}
+
+#[test]
+fn test_bandwidth_budget_off_peak_window() {
+    // Create a bandwidth budget with an off-peak window from 02:00 to 04:00 (UTC)
+    let bandwidth_config = AptosDataBandwidthConfig {
+        max_syncing_bytes_per_second: 1000,
+        off_peak_max_syncing_bytes_per_second: 5000,
+        off_peak_start_hour_utc: 2,
+        off_peak_end_hour_utc: 4,
+        ..Default::default()
+    };
+    let time_service = TimeService::mock();
+    let bandwidth_budget = BandwidthBudget::new(bandwidth_config, time_service.clone());
+    let mock_time_service = time_service.into_mock();
+
+    // Verify the cap is the peak cap outside of the window
+    assert_eq!(
+        bandwidth_budget.get_syncing_cap_bytes_per_second(),
+        Some(1000)
+    );
+    assert!(!bandwidth_budget.get_summary().is_off_peak);
+
+    // Verify the cap is the off-peak cap inside of the window
+    mock_time_service.advance_secs(2 * 60 * 60);
+    assert_eq!(
+        bandwidth_budget.get_syncing_cap_bytes_per_second(),
+        Some(5000)
+    );
+    assert!(bandwidth_budget.get_summary().is_off_peak);
+
+    // Verify the cap returns to the peak cap after the window
+    mock_time_service.advance_secs(2 * 60 * 60);
+    assert_eq!(
+        bandwidth_budget.get_syncing_cap_bytes_per_second(),
+        Some(1000)
+    );
+}
+
+#[test]
+fn test_bandwidth_budget_runtime_syncing_limit() {
+    // Create a bandwidth budget without any limits
+    let bandwidth_budget =
+        BandwidthBudget::new(AptosDataBandwidthConfig::default(), TimeService::mock());
+
+    // Verify that syncing is never throttled
+    assert_eq!(bandwidth_budget.get_syncing_bytes_per_second_limit(), None);
+    bandwidth_budget.record_synced_bytes(u32::MAX as u64);
+    assert_eq!(bandwidth_budget.get_syncing_throttle_delay(), None);
+
+    // Set a runtime limit and verify that syncing is throttled
+    bandwidth_budget.set_runtime_syncing_limit(Some(100));
+    assert_eq!(
+        bandwidth_budget.get_syncing_bytes_per_second_limit(),
+        Some(100)
+    );
+    assert!(bandwidth_budget.get_syncing_throttle_delay().is_some());
+
+    // Set an unlimited runtime limit and verify that syncing isn't throttled
+    bandwidth_budget.set_runtime_syncing_limit(Some(0));
+    assert_eq!(bandwidth_budget.get_syncing_bytes_per_second_limit(), None);
+    assert_eq!(bandwidth_budget.get_syncing_throttle_delay(), None);
+
+    // Clear the runtime limit and verify the configured limits are used
+    bandwidth_budget.set_runtime_syncing_limit(None);
+    let summary = bandwidth_budget.get_summary();
+    assert_eq!(summary.runtime_syncing_limit_bytes_per_second, None);
+    assert_eq!(summary.syncing_limit_bytes_per_second, None);
+}
+
+#[test]
+fn test_codec_compressed_responses() {
+    // Verify that responses can be created and decoded using every codec
+    let data_response = DataResponse::NumberOfStatesAtVersion(100);
+    for compression_codec in CompressionCodec::all() {
+        let response = StorageServiceResponse::new_with_codec(
+            data_response.clone(),
+            true,
+            Some(compression_codec),
+        )
+        .unwrap();
+        assert!(response.is_compressed());
+        assert_eq!(response.get_data_response().unwrap(), data_response);
+    }
+
+    // Verify that the codec is ignored if compression is not required
+    let response = StorageServiceResponse::new_with_codec(
+        data_response.clone(),
+        false,
+        Some(CompressionCodec::Lz4),
+    )
+    .unwrap();
+    assert_eq!(response, StorageServiceResponse::RawResponse(data_response));
+
+    // Verify that the negotiated codec is never serialized with the request
+    let request = StorageServiceRequest::new(DataRequest::GetServerProtocolVersion, true)
+        .with_compression_codec(Some(CompressionCodec::Lz4));
+    let deserialized_request: StorageServiceRequest =
+        bcs::from_bytes(&bcs::to_bytes(&request).unwrap()).unwrap();
+    assert_eq!(deserialized_request.compression_codec, None);
+}
+
+#[test]
+fn test_response_num_bytes() {
+    // Verify the size of a raw response is the serialized size
+    let data_response = DataResponse::NumberOfStatesAtVersion(100);
+    let response = StorageServiceResponse::new(data_response.clone(), false).unwrap();
+    assert_eq!(
+        response.get_num_bytes().unwrap(),
+        bcs::serialized_size(&response).unwrap() as u64
+    );
+
+    // Verify the size of a compressed response is the size of the compressed data
+    let response = StorageServiceResponse::new(data_response, true).unwrap();
+    match &response {
+        StorageServiceResponse::CompressedResponse(_, compressed_data) => {
+            assert_eq!(
+                response.get_num_bytes().unwrap(),
+                compressed_data.len() as u64
+            );
+        },
+        response => panic!("Expected a compressed response, but got: {:?}", response),
+    }
+}
 
 #[test]
 fn test_complete_data_ranges() {
@@ -466,6 +707,162 @@ fn test_data_summary_can_service_state_chunk_request() {
     }
 }
 
+#[test]
+fn test_data_summary_can_service_events_by_key_request() {
+    // Create a data client config and data summary
+    let data_client_config = AptosDataClientConfig::default();
+    let data_summary = DataSummary {
+        synced_ledger_info: Some(create_ledger_info_at_version(250)),
+        transactions: Some(create_data_range(100, 300)),
+        ..Default::default()
+    };
+
+    // Verify the different requests that can be serviced
+    for compression in [true, false] {
+        // Test the valid requests (i.e., the proof versions)
+        for proof_version in [100, 200, 250] {
+            let request = create_events_by_key_request(proof_version, compression);
+            verify_serviceability(&data_client_config, &data_summary, None, request, true);
+        }
+
+        // Test the invalid requests (i.e., the proof versions)
+        for proof_version in [0, 99, 251, 301] {
+            let request = create_events_by_key_request(proof_version, compression);
+            verify_serviceability(&data_client_config, &data_summary, None, request, false);
+        }
+    }
+}
+
+#[test]
+fn test_data_summary_can_service_state_value_changes_request() {
+    // Create a data client config and data summary
+    let data_client_config = AptosDataClientConfig::default();
+    let data_summary = DataSummary {
+        synced_ledger_info: Some(create_ledger_info_at_version(250)),
+        states: Some(create_data_range(200, 300)),
+        transaction_outputs: Some(create_data_range(100, 300)),
+        ..Default::default()
+    };
+
+    // Verify the different requests that can be serviced
+    for compression in [true, false] {
+        // Test the valid requests (proof version, start version, end version)
+        let valid_requests = vec![(250, 99, 200), (250, 150, 250), (250, 199, 200)];
+        for (proof_version, start_version, end_version) in valid_requests {
+            let request = create_state_value_changes_request(
+                proof_version,
+                start_version,
+                end_version,
+                compression,
+            );
+            verify_serviceability(&data_client_config, &data_summary, None, request, true);
+        }
+
+        // Test the invalid requests (proof version, start version, end version)
+        let invalid_requests = vec![
+            (251, 150, 250), // The proof version is too high
+            (250, 98, 200),  // The write sets after the start version are missing
+            (250, 150, 199), // The states at the end version are missing
+            (250, 200, 200), // The range is empty
+            (250, 250, 301), // The end version is too high
+        ];
+        for (proof_version, start_version, end_version) in invalid_requests {
+            let request = create_state_value_changes_request(
+                proof_version,
+                start_version,
+                end_version,
+                compression,
+            );
+            verify_serviceability(&data_client_config, &data_summary, None, request, false);
+        }
+    }
+}
+
+#[test]
+fn test_data_windows_apply_to_data_summary() {
+    // Create a data summary for a pruned node
+    let data_summary = DataSummary {
+        synced_ledger_info: Some(create_ledger_info_at_version(1000)),
+        epoch_ending_ledger_infos: Some(create_data_range(0, 10)),
+        states: Some(create_data_range(801, 1000)),
+        transactions: Some(create_data_range(501, 1000)),
+        transaction_outputs: Some(create_data_range(900, 1000)),
+    };
+
+    // Apply the data windows with a 10% safety margin
+    let data_windows = DataWindows {
+        ledger_window: Some(500),
+        state_window: Some(200),
+    };
+    let safe_data_summary = data_windows.apply_to_data_summary(&data_summary, 10);
+
+    // Verify the pruned ranges are raised by the safety margin
+    assert_eq!(safe_data_summary.states, Some(create_data_range(821, 1000)));
+    assert_eq!(
+        safe_data_summary.transactions,
+        Some(create_data_range(551, 1000))
+    );
+
+    // Verify the ranges that haven't filled their window are unchanged
+    assert_eq!(
+        safe_data_summary.transaction_outputs,
+        data_summary.transaction_outputs
+    );
+    assert_eq!(
+        safe_data_summary.epoch_ending_ledger_infos,
+        data_summary.epoch_ending_ledger_infos
+    );
+    assert_eq!(
+        safe_data_summary.synced_ledger_info,
+        data_summary.synced_ledger_info
+    );
+
+    // Verify that unpruned data is unchanged
+    let safe_data_summary = DataWindows::default().apply_to_data_summary(&data_summary, 10);
+    assert_eq!(safe_data_summary, data_summary);
+}
+
+#[test]
+fn test_event_filter() {
+    // Create several test events
+    let deposit_event = ContractEvent::new_v2_with_type_tag_str("0x1::coin::CoinDeposit", vec![]);
+    let withdraw_event = ContractEvent::new_v2_with_type_tag_str("0x1::coin::CoinWithdraw", vec![]);
+    let block_event = ContractEvent::new_v2_with_type_tag_str("0x1::block::NewBlock", vec![]);
+    let primitive_event = ContractEvent::new_v2_with_type_tag_str("u64", vec![]);
+    let events = [
+        &deposit_event,
+        &withdraw_event,
+        &block_event,
+        &primitive_event,
+    ];
+
+    // Verify that all events are stripped
+    for event in events {
+        assert!(!EventFilter::StripAllEvents.includes_event(event));
+    }
+
+    // Verify that only the specified event types are included
+    let event_filter = EventFilter::IncludeEventTypes(vec![StructTag::from_str(
+        "0x1::coin::CoinDeposit",
+    )
+    .unwrap()]);
+    assert!(event_filter.includes_event(&deposit_event));
+    for event in [&withdraw_event, &block_event, &primitive_event] {
+        assert!(!event_filter.includes_event(event));
+    }
+
+    // Verify that only the events in the specified modules are included
+    let event_filter = EventFilter::IncludeEventModules(vec![ModuleId::new(
+        AccountAddress::ONE,
+        Identifier::new("coin").unwrap(),
+    )]);
+    assert!(event_filter.includes_event(&deposit_event));
+    assert!(event_filter.includes_event(&withdraw_event));
+    for event in [&block_event, &primitive_event] {
+        assert!(!event_filter.includes_event(event));
+    }
+}
+
 #[test]
 fn test_protocol_metadata_service() {
     // Create the protocol metadata
@@ -519,6 +916,20 @@ fn create_epoch_ending_request(
     StorageServiceRequest::new(data_request, use_compression)
 }
 
+/// Creates a request for the events of an event key
+fn create_events_by_key_request(
+    proof_version: Version,
+    use_compression: bool,
+) -> StorageServiceRequest {
+    let data_request = DataRequest::GetEventsByKeyWithProof(EventsByKeyWithProofRequest {
+        event_key: EventKey::new(0, AccountAddress::ONE),
+        start_sequence_number: 0,
+        end_sequence_number: 100,
+        proof_version,
+    });
+    StorageServiceRequest::new(data_request, use_compression)
+}
+
 /// Creates a new ledger info at the given version
 fn create_ledger_info_at_version(version: Version) -> LedgerInfoWithSignatures {
     create_ledger_info_at_version_and_timestamp(version, 0)
@@ -555,17 +966,25 @@ fn create_optimistic_fetch_request(
     let random_number = get_random_u64();
 
     // Determine the data request type based on the random number
-    let data_request = if random_number % 3 == 0 {
+    let data_request = if random_number % 4 == 0 {
         DataRequest::GetNewTransactionsWithProof(NewTransactionsWithProofRequest {
             known_version,
             known_epoch: get_random_u64(),
             include_events: false,
         })
-    } else if random_number % 3 == 1 {
+    } else if random_number % 4 == 1 {
         DataRequest::GetNewTransactionOutputsWithProof(NewTransactionOutputsWithProofRequest {
             known_version,
             known_epoch: get_random_u64(),
         })
+    } else if random_number % 4 == 2 {
+        DataRequest::GetNewFilteredTransactionOutputsWithProof(
+            NewFilteredTransactionOutputsWithProofRequest {
+                known_version,
+                known_epoch: get_random_u64(),
+                event_filter: EventFilter::StripAllEvents,
+            },
+        )
     } else {
         DataRequest::GetNewTransactionsOrOutputsWithProof(
             NewTransactionsOrOutputsWithProofRequest {
@@ -608,19 +1027,27 @@ fn create_subscription_request(known_version: u64, use_compression: bool) -> Sto
     let random_number = get_random_u64();
 
     // Determine the data request type based on the random number
-    let data_request = if random_number % 3 == 0 {
+    let data_request = if random_number % 4 == 0 {
         DataRequest::SubscribeTransactionsWithProof(SubscribeTransactionsWithProofRequest {
             subscription_stream_metadata,
             include_events: false,
             subscription_stream_index: get_random_u64(),
         })
-    } else if random_number % 3 == 1 {
+    } else if random_number % 4 == 1 {
         DataRequest::SubscribeTransactionOutputsWithProof(
             SubscribeTransactionOutputsWithProofRequest {
                 subscription_stream_metadata,
                 subscription_stream_index: get_random_u64(),
             },
         )
+    } else if random_number % 4 == 2 {
+        DataRequest::SubscribeFilteredTransactionOutputsWithProof(
+            SubscribeFilteredTransactionOutputsWithProofRequest {
+                subscription_stream_metadata,
+                subscription_stream_index: get_random_u64(),
+                event_filter: EventFilter::StripAllEvents,
+            },
+        )
     } else {
         DataRequest::SubscribeTransactionsOrOutputsWithProof(
             SubscribeTransactionsOrOutputsWithProofRequest {
@@ -683,6 +1110,22 @@ fn create_state_values_request(
     StorageServiceRequest::new(data_request, use_compression)
 }
 
+/// Creates a request for the state value changes between the given versions
+fn create_state_value_changes_request(
+    proof_version: Version,
+    start_version: Version,
+    end_version: Version,
+    use_compression: bool,
+) -> StorageServiceRequest {
+    let data_request =
+        DataRequest::GetStateValueChangesWithProof(StateValueChangesWithProofRequest {
+            proof_version,
+            start_version,
+            end_version,
+        });
+    StorageServiceRequest::new(data_request, use_compression)
+}
+
 /// Creates a request for state values at a given version
 fn create_state_values_request_at_version(
     version: Version,
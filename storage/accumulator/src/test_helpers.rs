@@ -271,6 +271,56 @@ pub fn test_range_proof_impl(
     proof.verify(root_hash, first_leaf_index, &batch2).unwrap();
 }
 
+pub fn test_sub_range_proof_impl(
+    (batch1, batch2, batch3): (Vec<HashValue>, Vec<HashValue>, Vec<HashValue>),
+    (start, end): (prop::sample::Index, prop::sample::Index),
+) {
+    let mut store = MockHashStore::new();
+
+    let mut all_hashes = vec![];
+    all_hashes.extend_from_slice(&batch1);
+    all_hashes.extend_from_slice(&batch2);
+    all_hashes.extend_from_slice(&batch3);
+
+    let (root_hash, writes) = TestAccumulator::append(&store, 0, &all_hashes).unwrap();
+    store.put_many(&writes);
+
+    // Get the range proof for the second batch
+    let first_leaf_index = batch1.len() as u64;
+    let proof = TestAccumulator::get_range_proof(
+        &store,
+        all_hashes.len() as LeafCount,
+        Some(first_leaf_index),
+        batch2.len() as LeafCount,
+    )
+    .unwrap();
+
+    // Derive the proof for a sub-range of the second batch
+    let start = start.index(batch2.len());
+    let end = start + 1 + end.index(batch2.len() - start);
+    let sub_range_proof = proof
+        .get_sub_range_proof(first_leaf_index, &batch2, start..end)
+        .unwrap();
+
+    // Verify the derived proof matches the proof fetched from the accumulator
+    let sub_range_first_leaf_index = first_leaf_index + start as u64;
+    let expected_proof = TestAccumulator::get_range_proof(
+        &store,
+        all_hashes.len() as LeafCount,
+        Some(sub_range_first_leaf_index),
+        (end - start) as LeafCount,
+    )
+    .unwrap();
+    assert_eq!(sub_range_proof, expected_proof);
+    sub_range_proof
+        .verify(
+            root_hash,
+            Some(sub_range_first_leaf_index),
+            &batch2[start..end],
+        )
+        .unwrap();
+}
+
 prop_compose! {
     pub fn arb_hash_batch(length: usize)(
         batch in vec(any::<HashValue>(), 0..length),
@@ -5,7 +5,7 @@
 use super::*;
 use crate::test_helpers::{
     arb_three_hash_batches, arb_two_hash_batches, test_consistency_proof_impl, test_proof_impl,
-    test_range_proof_impl, verify, MockHashStore, TestAccumulator,
+    test_range_proof_impl, test_sub_range_proof_impl, verify, MockHashStore, TestAccumulator,
 };
 
 #[test]
@@ -42,4 +42,13 @@ proptest! {
     fn test_range_proof((batch1, batch2, batch3) in arb_three_hash_batches(100)) {
         test_range_proof_impl((batch1, batch2, batch3));
     }
+
+    #[test]
+    fn test_sub_range_proof(
+        (batch1, batch2, batch3) in arb_three_hash_batches(100),
+        start in any::<prop::sample::Index>(),
+        end in any::<prop::sample::Index>(),
+    ) {
+        test_sub_range_proof_impl((batch1, batch2, batch3), (start, end));
+    }
 }
@@ -11,7 +11,11 @@ use crate::{
     state_store::StateStore,
 };
 use aptos_crypto::hash::HashValue;
-use aptos_storage_interface::{db_ensure as ensure, AptosDbError, Result};
+use aptos_storage_interface::{
+    db_ensure as ensure,
+    read_context::{ReadContext, ReadContextIterator, ReadSubsystem},
+    AptosDbError, Result,
+};
 use aptos_types::{
     contract_event::ContractEvent,
     ledger_info::LedgerInfoWithSignatures,
@@ -21,9 +25,10 @@ use aptos_types::{
     write_set::WriteSet,
 };
 use serde::{Deserialize, Serialize};
-use std::{fmt, sync::Arc};
+use std::{fmt, sync::Arc, time::Duration};
 
-/// `BackupHandler` provides functionalities for AptosDB data backup.
+/// `BackupHandler` provides functionalities for AptosDB data backup. All reads (including
+/// those of the returned iterators) are attributed to the backup subsystem.
 #[derive(Clone)]
 pub struct BackupHandler {
     state_store: Arc<StateStore>,
@@ -46,6 +51,7 @@ impl BackupHandler {
     ) -> Result<
         impl Iterator<Item = Result<(Transaction, TransactionInfo, Vec<ContractEvent>, WriteSet)>> + '_,
     > {
+        let _read_context = ReadContext::enter(ReadSubsystem::Backup);
         let txn_iter = self
             .ledger_db
             .transaction_db()
@@ -88,7 +94,7 @@ impl BackupHandler {
             BACKUP_TXN_VERSION.set(version as i64);
             Ok((txn, txn_info, event_vec, write_set))
         });
-        Ok(zipped)
+        Ok(ReadContextIterator::new(zipped, ReadSubsystem::Backup))
     }
 
     /// Gets the proof for a transaction chunk.
@@ -98,6 +104,7 @@ impl BackupHandler {
         first_version: Version,
         last_version: Version,
     ) -> Result<(TransactionAccumulatorRangeProof, LedgerInfoWithSignatures)> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Backup);
         ensure!(
             last_version >= first_version,
             "Bad transaction range: [{}, {}]",
@@ -124,6 +131,7 @@ impl BackupHandler {
         &self,
         version: Version,
     ) -> Result<Box<dyn Iterator<Item = Result<(StateKey, StateValue)>> + Send + Sync>> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Backup);
         let iterator = self
             .state_store
             .get_state_key_and_value_iter(version, HashValue::zero())?
@@ -133,7 +141,28 @@ impl BackupHandler {
                 BACKUP_STATE_SNAPSHOT_LEAF_IDX.set(idx as i64);
                 res
             });
-        Ok(Box::new(iterator))
+        Ok(Box::new(ReadContextIterator::new(
+            iterator,
+            ReadSubsystem::Backup,
+        )))
+    }
+
+    /// Gets an iterator which yields the accounts in the state tree, starting
+    /// from the given hashed key (inclusive). This allows the state tree to
+    /// be scanned in parallel (e.g., by partitioning the hashed key space).
+    pub fn get_account_iter_from(
+        &self,
+        version: Version,
+        start_hashed_key: HashValue,
+    ) -> Result<Box<dyn Iterator<Item = Result<(StateKey, StateValue)>> + Send + Sync>> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Backup);
+        let iterator = self
+            .state_store
+            .get_state_key_and_value_iter(version, start_hashed_key)?;
+        Ok(Box::new(ReadContextIterator::new(
+            iterator,
+            ReadSubsystem::Backup,
+        )))
     }
 
     /// Gets the proof that proves a range of accounts.
@@ -142,12 +171,14 @@ impl BackupHandler {
         rightmost_key: HashValue,
         version: Version,
     ) -> Result<SparseMerkleRangeProof> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Backup);
         self.state_store
             .get_value_range_proof(rightmost_key, version)
     }
 
     /// Gets the epoch, committed version, and synced version of the DB.
     pub fn get_db_state(&self) -> Result<Option<DbState>> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Backup);
         Ok(self
             .ledger_db
             .metadata_db()
@@ -158,12 +189,30 @@ impl BackupHandler {
             }))
     }
 
+    /// Waits until the DB has committed a version higher than `known_version` (or the timeout
+    /// elapses), and returns the latest DB state. This blocks the calling thread.
+    pub fn wait_for_db_state(
+        &self,
+        known_version: Option<Version>,
+        timeout: Duration,
+    ) -> Result<Option<DbState>> {
+        Ok(self
+            .ledger_db
+            .metadata_db()
+            .wait_for_latest_ledger_info(known_version, timeout)
+            .map(|li| DbState {
+                epoch: li.ledger_info().epoch(),
+                committed_version: li.ledger_info().version(),
+            }))
+    }
+
     /// Gets the proof of the state root at specified version.
     /// N.B. the `LedgerInfo` returned will always be in the same epoch of the version.
     pub fn get_state_root_proof(
         &self,
         version: Version,
     ) -> Result<(TransactionInfoWithProof, LedgerInfoWithSignatures)> {
+        let _read_context = ReadContext::enter(ReadSubsystem::Backup);
         let ledger_metadata_db = self.ledger_db.metadata_db();
         let epoch = ledger_metadata_db.get_epoch(version)?;
         let ledger_info = ledger_metadata_db.get_latest_ledger_info_in_epoch(epoch)?;
@@ -184,7 +233,8 @@ impl BackupHandler {
         start_epoch: u64,
         end_epoch: u64,
     ) -> Result<impl Iterator<Item = Result<LedgerInfoWithSignatures>> + '_> {
-        Ok(self
+        let _read_context = ReadContext::enter(ReadSubsystem::Backup);
+        let iterator = self
             .ledger_db
             .metadata_db()
             .get_epoch_ending_ledger_info_iter(start_epoch, end_epoch)?
@@ -192,7 +242,8 @@ impl BackupHandler {
             .map(move |(idx, li)| {
                 BACKUP_EPOCH_ENDING_EPOCH.set((start_epoch + idx as u64) as i64);
                 li
-            }))
+            });
+        Ok(ReadContextIterator::new(iterator, ReadSubsystem::Backup))
     }
 }
 
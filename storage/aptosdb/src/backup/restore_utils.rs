@@ -233,6 +233,11 @@ pub(crate) fn save_transactions_impl(
             ws,
             &ledger_db_batch.write_set_db_batches,
         )?;
+        ledger_db.write_set_db().put_write_set_index(
+            first_version + idx as Version,
+            ws,
+            &ledger_db_batch.write_set_db_batches,
+        )?;
     }
 
     if kv_replay && first_version > 0 && state_store.get_usage(Some(first_version - 1)).is_ok() {
@@ -0,0 +1,438 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! The consistency scrubber incrementally re-verifies persisted data in the background, in order
+//! to detect silent disk corruption before it propagates into served proofs. Two kinds of data
+//! are scrubbed (in small, throttled batches):
+//! 1. The transaction accumulator: ranges of transaction infos are verified against the root
+//!    hash of the latest ledger info (using range proofs read from the accumulator).
+//! 2. The state merkle tree: the nodes of the latest state snapshot are walked (from the root)
+//!    and their hashes are verified against the state checkpoint hash of the snapshot version.
+//!    The value hash of each leaf is also verified against the value in the state kv db.
+//!
+//! The scrubbing progress is persisted in the ledger metadata db, so that scrubbing resumes
+//! (rather than restarts) across node restarts. Note: an in-flight pass over a state snapshot
+//! is not persisted, and restarts from the root (once a newer snapshot exists).
+//!
+//! Data that is pruned while being scrubbed is skipped (and not reported as corrupted).
+//! Corruption is reported via the `aptos_storage_consistency_scrubber_corruptions` metric and
+//! the logs.
+
+use crate::{
+    ledger_db::LedgerDb,
+    metrics::{
+        CONSISTENCY_SCRUBBER_CORRUPTIONS, CONSISTENCY_SCRUBBER_PASSES,
+        CONSISTENCY_SCRUBBER_SCRUBBED_ITEMS,
+    },
+    pruner::pruner_utils::get_state_kv_pruner_progress,
+    schema::state_value::StateValueSchema,
+    state_kv_db::StateKvDb,
+    state_merkle_db::{LeafNode, Node, StateMerkleDb},
+};
+use aptos_config::config::ConsistencyScrubberConfig;
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_infallible::Mutex;
+use aptos_jellyfish_merkle::node_type::NodeKey;
+use aptos_logger::prelude::*;
+use aptos_storage_interface::{db_ensure as ensure, AptosDbError, Result};
+use aptos_types::transaction::Version;
+use std::{
+    sync::{mpsc, Arc},
+    thread,
+    thread::JoinHandle,
+    time::Duration,
+};
+
+const TRANSACTION_ACCUMULATOR: &str = "transaction_accumulator";
+const STATE_MERKLE: &str = "state_merkle";
+const STATE_KV: &str = "state_kv";
+
+/// The progress of an in-flight scrubbing pass over a state snapshot
+struct StateSnapshotScrub {
+    version: Version,
+    // The nodes left to verify (i.e., the node keys and their expected hashes)
+    pending_nodes: Vec<(NodeKey, HashValue)>,
+}
+
+struct ScrubProgress {
+    next_transaction_version: Version,
+    last_scrubbed_snapshot_version: Option<Version>,
+    state_snapshot: Option<StateSnapshotScrub>,
+}
+
+/// Verifies batches of the persisted transaction accumulator and state merkle tree. The
+/// progress lock is never held across db reads (only while reading or updating the progress).
+pub(crate) struct ConsistencyScrubber {
+    ledger_db: Arc<LedgerDb>,
+    state_merkle_db: Arc<StateMerkleDb>,
+    state_kv_db: Arc<StateKvDb>,
+    config: ConsistencyScrubberConfig,
+    progress: Mutex<ScrubProgress>,
+}
+
+impl ConsistencyScrubber {
+    /// Creates a scrubber that resumes from the persisted progress
+    pub(crate) fn new(
+        ledger_db: Arc<LedgerDb>,
+        state_merkle_db: Arc<StateMerkleDb>,
+        state_kv_db: Arc<StateKvDb>,
+        config: ConsistencyScrubberConfig,
+    ) -> Result<Self> {
+        let metadata_db = ledger_db.metadata_db();
+        let progress = ScrubProgress {
+            next_transaction_version: metadata_db.get_consistency_scrubber_ledger_progress()?,
+            last_scrubbed_snapshot_version: metadata_db
+                .get_consistency_scrubber_state_merkle_progress()?,
+            state_snapshot: None,
+        };
+
+        Ok(Self {
+            ledger_db,
+            state_merkle_db,
+            state_kv_db,
+            config,
+            progress: Mutex::new(progress),
+        })
+    }
+
+    /// Verifies the next batch of transaction infos against the root hash of the latest ledger
+    /// info. Returns the number of corruptions detected.
+    pub(crate) fn scrub_transaction_accumulator(&self) -> Result<u64> {
+        let ledger_info_with_sigs =
+            match self.ledger_db.metadata_db().get_latest_ledger_info_option() {
+                Some(ledger_info_with_sigs) => ledger_info_with_sigs,
+                None => return Ok(0), // Nothing has been committed yet
+            };
+        let ledger_info = ledger_info_with_sigs.ledger_info();
+        let ledger_version = ledger_info.version();
+        let min_readable_version = self.get_min_readable_ledger_version()?;
+
+        let next_transaction_version = self.progress.lock().next_transaction_version;
+        let start_version = next_transaction_version.max(min_readable_version);
+        if start_version > ledger_version {
+            CONSISTENCY_SCRUBBER_PASSES
+                .with_label_values(&[TRANSACTION_ACCUMULATOR])
+                .inc();
+            info!(
+                ledger_version = ledger_version,
+                "Finished scrubbing the transaction accumulator."
+            );
+            self.update_transaction_progress(0)?;
+            return Ok(0);
+        }
+
+        let num_transactions = self
+            .config
+            .transaction_batch_size
+            .min(ledger_version - start_version + 1);
+        let mut num_corruptions = 0;
+        if let Err(error) = self.verify_transaction_infos(
+            start_version,
+            num_transactions,
+            ledger_version,
+            ledger_info.transaction_accumulator_hash(),
+        ) {
+            // The range might have been pruned while it was being verified
+            if self.get_min_readable_ledger_version()? <= start_version {
+                report_corruption(TRANSACTION_ACCUMULATOR, start_version, &error);
+                num_corruptions += 1;
+            }
+        }
+        CONSISTENCY_SCRUBBER_SCRUBBED_ITEMS
+            .with_label_values(&[TRANSACTION_ACCUMULATOR])
+            .inc_by(num_transactions);
+        self.update_transaction_progress(start_version + num_transactions)?;
+
+        Ok(num_corruptions)
+    }
+
+    /// Verifies the next batch of nodes of the latest state snapshot (against the state
+    /// checkpoint hash of the snapshot version), and the values of the leaves (against the
+    /// state kv db). Returns the number of corruptions detected.
+    pub(crate) fn scrub_state_merkle(&self) -> Result<u64> {
+        let snapshot_scrub = self.progress.lock().state_snapshot.take();
+        let mut snapshot_scrub = match snapshot_scrub {
+            Some(snapshot_scrub) => snapshot_scrub,
+            None => match self.start_state_snapshot_scrub()? {
+                Some(snapshot_scrub) => snapshot_scrub,
+                None => return Ok(0),
+            },
+        };
+
+        let mut num_nodes = 0;
+        let mut num_corruptions = 0;
+        while num_nodes < self.config.state_merkle_batch_size {
+            let (node_key, expected_hash) = match snapshot_scrub.pending_nodes.pop() {
+                Some(pending_node) => pending_node,
+                None => break,
+            };
+            num_nodes += 1;
+
+            match self.state_merkle_db.get_node_option_from_db(&node_key)? {
+                Some(node) => {
+                    let hash = node.hash();
+                    if hash != expected_hash {
+                        // Don't descend into a corrupted node (its children can't be trusted)
+                        let error = AptosDbError::Other(format!(
+                            "Hash mismatch for node {:?}. Expected: {}, found: {}.",
+                            node_key, expected_hash, hash
+                        ));
+                        report_corruption(STATE_MERKLE, snapshot_scrub.version, &error);
+                        num_corruptions += 1;
+                        continue;
+                    }
+                    match &node {
+                        Node::Internal(internal_node) => {
+                            for (nibble, child) in internal_node.children_sorted() {
+                                snapshot_scrub.pending_nodes.push((
+                                    node_key.gen_child_node_key(child.version, *nibble),
+                                    child.hash,
+                                ));
+                            }
+                        },
+                        Node::Leaf(leaf_node) => {
+                            if let Err(error) = self.verify_leaf_value(leaf_node) {
+                                // The value might have been pruned (after being overwritten)
+                                let state_kv_pruner_progress =
+                                    get_state_kv_pruner_progress(&self.state_kv_db)?;
+                                if state_kv_pruner_progress <= snapshot_scrub.version {
+                                    report_corruption(STATE_KV, snapshot_scrub.version, &error);
+                                    num_corruptions += 1;
+                                }
+                            }
+                        },
+                        Node::Null => {},
+                    }
+                },
+                None => {
+                    // The snapshot might have been pruned while it was being scrubbed
+                    if !self.state_snapshot_exists(snapshot_scrub.version)? {
+                        info!(
+                            version = snapshot_scrub.version,
+                            "State snapshot was pruned while being scrubbed. Restarting."
+                        );
+                        CONSISTENCY_SCRUBBER_SCRUBBED_ITEMS
+                            .with_label_values(&[STATE_MERKLE])
+                            .inc_by(num_nodes);
+                        return Ok(num_corruptions);
+                    }
+                    let error = AptosDbError::NotFound(format!("State merkle node {:?}", node_key));
+                    report_corruption(STATE_MERKLE, snapshot_scrub.version, &error);
+                    num_corruptions += 1;
+                },
+            }
+        }
+        CONSISTENCY_SCRUBBER_SCRUBBED_ITEMS
+            .with_label_values(&[STATE_MERKLE])
+            .inc_by(num_nodes);
+
+        if snapshot_scrub.pending_nodes.is_empty() {
+            CONSISTENCY_SCRUBBER_PASSES
+                .with_label_values(&[STATE_MERKLE])
+                .inc();
+            info!(
+                version = snapshot_scrub.version,
+                "Finished scrubbing the state snapshot."
+            );
+            self.ledger_db
+                .metadata_db()
+                .write_consistency_scrubber_state_merkle_progress(snapshot_scrub.version)?;
+            self.progress.lock().last_scrubbed_snapshot_version = Some(snapshot_scrub.version);
+        } else {
+            self.progress.lock().state_snapshot = Some(snapshot_scrub);
+        }
+
+        Ok(num_corruptions)
+    }
+
+    /// Persists the given transaction accumulator progress (and updates the in-memory progress)
+    fn update_transaction_progress(&self, next_transaction_version: Version) -> Result<()> {
+        self.ledger_db
+            .metadata_db()
+            .write_consistency_scrubber_ledger_progress(next_transaction_version)?;
+        self.progress.lock().next_transaction_version = next_transaction_version;
+        Ok(())
+    }
+
+    /// Verifies the transaction infos in the given range using a range proof (read from the
+    /// transaction accumulator) against the expected accumulator root hash.
+    fn verify_transaction_infos(
+        &self,
+        start_version: Version,
+        num_transactions: u64,
+        ledger_version: Version,
+        expected_root_hash: HashValue,
+    ) -> Result<()> {
+        let transaction_info_hashes = self
+            .ledger_db
+            .transaction_info_db()
+            .get_transaction_info_iter(start_version, num_transactions as usize)?
+            .map(|transaction_info| transaction_info.map(|info| info.hash()))
+            .collect::<Result<Vec<_>>>()?;
+        ensure!(
+            transaction_info_hashes.len() as u64 == num_transactions,
+            "Expected {} transaction infos starting at version {}, found {}.",
+            num_transactions,
+            start_version,
+            transaction_info_hashes.len()
+        );
+
+        let range_proof = self
+            .ledger_db
+            .transaction_accumulator_db()
+            .get_transaction_range_proof(Some(start_version), num_transactions, ledger_version)?;
+        range_proof.verify(
+            expected_root_hash,
+            Some(start_version),
+            &transaction_info_hashes,
+        )?;
+        Ok(())
+    }
+
+    /// Verifies the value hash of the given leaf against the value in the state kv db
+    fn verify_leaf_value(&self, leaf_node: &LeafNode) -> Result<()> {
+        let (state_key, value_version) = leaf_node.value_index();
+        let state_value = self
+            .state_kv_db
+            .db_shard(state_key.get_shard_id())
+            .get::<StateValueSchema>(&(state_key.clone(), *value_version))?
+            .flatten()
+            .ok_or_else(|| {
+                AptosDbError::NotFound(format!(
+                    "State value of {:?} at version {}",
+                    state_key, value_version
+                ))
+            })?;
+        let value_hash = state_value.hash();
+        ensure!(
+            value_hash == leaf_node.value_hash(),
+            "Value hash mismatch for {:?} at version {}. Expected: {}, found: {}.",
+            state_key,
+            value_version,
+            leaf_node.value_hash(),
+            value_hash
+        );
+        Ok(())
+    }
+
+    /// Starts a new scrubbing pass over the latest state snapshot (if it hasn't been scrubbed)
+    fn start_state_snapshot_scrub(&self) -> Result<Option<StateSnapshotScrub>> {
+        let version = match self
+            .state_merkle_db
+            .get_state_snapshot_version_before(Version::MAX)?
+        {
+            Some(version) => version,
+            None => return Ok(None),
+        };
+        if self.progress.lock().last_scrubbed_snapshot_version == Some(version) {
+            return Ok(None); // Wait for a new snapshot
+        }
+
+        let state_checkpoint_hash = match self
+            .ledger_db
+            .transaction_info_db()
+            .get_transaction_info(version)
+            .map(|transaction_info| transaction_info.state_checkpoint_hash())
+        {
+            Ok(Some(state_checkpoint_hash)) => state_checkpoint_hash,
+            Ok(None) | Err(AptosDbError::NotFound(_)) => {
+                warn!(
+                    version = version,
+                    "No state checkpoint hash found for the latest state snapshot. Skipping."
+                );
+                return Ok(None);
+            },
+            Err(error) => return Err(error),
+        };
+
+        Ok(Some(StateSnapshotScrub {
+            version,
+            pending_nodes: vec![(NodeKey::new_empty_path(version), state_checkpoint_hash)],
+        }))
+    }
+
+    fn state_snapshot_exists(&self, version: Version) -> Result<bool> {
+        Ok(self
+            .state_merkle_db
+            .get_node_option_from_db(&NodeKey::new_empty_path(version))?
+            .is_some())
+    }
+
+    fn get_min_readable_ledger_version(&self) -> Result<Version> {
+        match self.ledger_db.metadata_db().get_pruner_progress() {
+            Ok(version) => Ok(version),
+            Err(AptosDbError::NotFound(_)) => Ok(0), // Nothing has been pruned yet
+            Err(error) => Err(error),
+        }
+    }
+}
+
+fn report_corruption(kind: &str, version: Version, error: &AptosDbError) {
+    CONSISTENCY_SCRUBBER_CORRUPTIONS
+        .with_label_values(&[kind])
+        .inc();
+    error!(
+        kind = kind,
+        version = version,
+        error = ?error,
+        "Consistency scrubber detected corrupted data!"
+    );
+}
+
+/// Periodically runs the consistency scrubber (on a dedicated thread)
+pub(crate) struct ConsistencyScrubberManager {
+    sender: Mutex<mpsc::Sender<()>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl ConsistencyScrubberManager {
+    pub(crate) fn new(
+        ledger_db: Arc<LedgerDb>,
+        state_merkle_db: Arc<StateMerkleDb>,
+        state_kv_db: Arc<StateKvDb>,
+        config: ConsistencyScrubberConfig,
+    ) -> Result<Self> {
+        let scrub_interval = Duration::from_millis(config.scrub_interval_ms);
+        let scrubber = ConsistencyScrubber::new(ledger_db, state_merkle_db, state_kv_db, config)?;
+
+        let (sender, receiver) = mpsc::channel();
+        let join_handle = Some(
+            thread::Builder::new()
+                .name("db-scrubber".into())
+                .spawn(move || loop {
+                    match receiver.recv_timeout(scrub_interval) {
+                        Ok(_) => break,
+                        Err(mpsc::RecvTimeoutError::Timeout) => (),
+                        Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                    }
+                    if let Err(error) = scrubber.scrub_transaction_accumulator() {
+                        warn!(
+                            error = ?error,
+                            "Failed to scrub the transaction accumulator."
+                        );
+                    }
+                    if let Err(error) = scrubber.scrub_state_merkle() {
+                        warn!(error = ?error, "Failed to scrub the state merkle tree.");
+                    }
+                })
+                .expect("Failed to spawn the consistency scrubber thread!"),
+        );
+
+        Ok(Self {
+            sender: Mutex::new(sender),
+            join_handle,
+        })
+    }
+}
+
+impl Drop for ConsistencyScrubberManager {
+    fn drop(&mut self) {
+        // Notify the scrubber thread to exit
+        self.sender.lock().send(()).unwrap();
+        self.join_handle
+            .take()
+            .expect("Consistency scrubber thread must exist.")
+            .join()
+            .expect("Consistency scrubber thread should join peacefully.");
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    consistency_scrubber::ConsistencyScrubber,
     db::{
         get_first_seq_num_and_limit, test_helper,
         test_helper::{
@@ -11,30 +12,46 @@ use crate::{
         AptosDB,
     },
     pruner::{LedgerPrunerManager, PrunerManager, StateMerklePrunerManager},
-    schema::stale_node_index::StaleNodeIndexSchema,
+    schema::{
+        db_metadata::{DbMetadataKey, DbMetadataSchema},
+        jellyfish_merkle_node::JellyfishMerkleNodeSchema,
+        stale_node_index::StaleNodeIndexSchema,
+        state_value::StateValueSchema,
+        transaction_accumulator::TransactionAccumulatorSchema,
+    },
+    state_merkle_db::Node,
 };
 use aptos_config::config::{
-    EpochSnapshotPrunerConfig, LedgerPrunerConfig, PrunerConfig, RocksdbConfigs,
-    StateMerklePrunerConfig, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
-    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    ColdStorageBackendConfig, ConsistencyScrubberConfig, EpochSnapshotPrunerConfig,
+    LedgerPrunerConfig, PrunerConfig, RocksdbConfigs, StateMerklePrunerConfig, StorageDirPaths,
+    TieredStorageConfig, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
 };
-use aptos_crypto::{hash::CryptoHash, HashValue};
-use aptos_storage_interface::{DbReader, ExecutedTrees, Order};
+use aptos_crypto::{ed25519::Ed25519PrivateKey, hash::CryptoHash, HashValue, PrivateKey, Uniform};
+use aptos_jellyfish_merkle::node_type::NodeKey;
+use aptos_schemadb::{ReadOptions, SchemaBatch};
+use aptos_storage_interface::{DbReader, ExecutedTrees, Order, Result};
 use aptos_temppath::TempPath;
 use aptos_types::{
+    account_address::AccountAddress,
     ledger_info::LedgerInfoWithSignatures,
-    proof::SparseMerkleLeafNode,
+    proof::{position::Position, SparseMerkleLeafNode},
     state_store::{
-        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+        create_empty_sharded_state_updates, state_key::StateKey,
+        state_storage_usage::StateStorageUsage, state_value::StateValue,
     },
+    test_helpers::transaction_test_helpers::get_test_signed_txn,
     transaction::{
-        ExecutionStatus, TransactionAuxiliaryData, TransactionAuxiliaryDataV1, TransactionInfo,
-        TransactionToCommit, VMErrorDetail, Version,
+        ExecutionStatus, Transaction, TransactionAuxiliaryData, TransactionAuxiliaryDataV1,
+        TransactionInfo, TransactionToCommit, VMErrorDetail, Version,
     },
     vm_status::StatusCode,
+    write_set::WriteSet,
 };
 use proptest::prelude::*;
-use std::{collections::HashSet, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+};
 use test_helper::{test_save_blocks_impl, test_sync_transactions_impl};
 
 proptest! {
@@ -207,6 +224,124 @@ fn test_get_latest_executed_trees() {
     );
 }
 
+#[test]
+fn test_epoch_ledger_stats() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let (sender_1, sender_2, sender_3) = (
+        AccountAddress::random(),
+        AccountAddress::random(),
+        AccountAddress::random(),
+    );
+
+    // Commit the first batch of transactions (all in epoch 0)
+    let first_batch = vec![
+        create_user_txn_to_commit(sender_1, 0, 10, true, false),
+        create_user_txn_to_commit(sender_2, 0, 5, false, false),
+        create_user_txn_to_commit(sender_1, 1, 10, true, false),
+        TransactionToCommit::dummy(),
+    ];
+    commit_epoch_ledger_stats(&db, &first_batch, 0);
+
+    // Verify the ledger stats of epoch 0
+    let epoch_ledger_stats = db.get_epoch_ledger_stats(0).unwrap().unwrap();
+    assert_eq!(epoch_ledger_stats.first_version, 0);
+    assert_eq!(epoch_ledger_stats.last_version, 3);
+    assert_eq!(epoch_ledger_stats.num_transactions, 4);
+    assert_eq!(
+        epoch_ledger_stats.num_transactions_by_type,
+        BTreeMap::from([
+            ("state_checkpoint".to_string(), 1),
+            ("user_transaction".to_string(), 3)
+        ])
+    );
+    assert_eq!(epoch_ledger_stats.num_failed_transactions, 1);
+    assert_eq!(epoch_ledger_stats.total_gas_used, 25);
+    assert_eq!(epoch_ledger_stats.num_unique_senders, 2);
+    assert!(db.get_epoch_ledger_stats(1).unwrap().is_none());
+
+    // Commit a second batch of transactions that ends epoch 0 and starts epoch 1
+    let second_batch = vec![
+        create_user_txn_to_commit(sender_1, 2, 10, true, false),
+        create_user_txn_to_commit(sender_3, 0, 20, true, true),
+        create_user_txn_to_commit(sender_1, 3, 10, true, false),
+        create_user_txn_to_commit(sender_2, 1, 10, false, false),
+        create_user_txn_to_commit(sender_2, 2, 10, true, false),
+    ];
+    commit_epoch_ledger_stats(&db, &second_batch, 4);
+
+    // Verify the ledger stats of epoch 0 were updated
+    let epoch_ledger_stats = db.get_epoch_ledger_stats(0).unwrap().unwrap();
+    assert_eq!(epoch_ledger_stats.last_version, 5);
+    assert_eq!(epoch_ledger_stats.num_transactions, 6);
+    assert_eq!(epoch_ledger_stats.num_failed_transactions, 1);
+    assert_eq!(epoch_ledger_stats.total_gas_used, 55);
+    assert_eq!(epoch_ledger_stats.num_unique_senders, 3);
+
+    // Verify the ledger stats of epoch 1
+    let epoch_ledger_stats = db.get_epoch_ledger_stats(1).unwrap().unwrap();
+    assert_eq!(epoch_ledger_stats.first_version, 6);
+    assert_eq!(epoch_ledger_stats.last_version, 8);
+    assert_eq!(epoch_ledger_stats.num_transactions, 3);
+    assert_eq!(
+        epoch_ledger_stats.num_transactions_by_type,
+        BTreeMap::from([("user_transaction".to_string(), 3)])
+    );
+    assert_eq!(epoch_ledger_stats.num_failed_transactions, 1);
+    assert_eq!(epoch_ledger_stats.total_gas_used, 30);
+    assert_eq!(epoch_ledger_stats.num_unique_senders, 2);
+}
+
+/// Updates the epoch ledger stats (and the account transaction
+/// index) for the given transactions.
+fn commit_epoch_ledger_stats(
+    db: &AptosDB,
+    txns_to_commit: &[TransactionToCommit],
+    first_version: Version,
+) {
+    let batch = SchemaBatch::new();
+    db.put_epoch_ledger_stats(txns_to_commit, first_version, &batch)
+        .unwrap();
+    db.ledger_db.metadata_db().write_schemas(batch).unwrap();
+    db.ledger_db
+        .transaction_db()
+        .commit_transactions(txns_to_commit, first_version, false)
+        .unwrap();
+}
+
+/// Creates a user transaction to commit with the given sender and outputs
+fn create_user_txn_to_commit(
+    sender: AccountAddress,
+    sequence_number: u64,
+    gas_used: u64,
+    success: bool,
+    is_reconfig: bool,
+) -> TransactionToCommit {
+    let private_key = Ed25519PrivateKey::generate_for_testing();
+    let user_txn = get_test_signed_txn(
+        sender,
+        sequence_number,
+        &private_key,
+        private_key.public_key(),
+        None,
+    );
+    let status = if success {
+        ExecutionStatus::Success
+    } else {
+        ExecutionStatus::MiscellaneousError(None)
+    };
+
+    TransactionToCommit::new(
+        Transaction::UserTransaction(user_txn),
+        TransactionInfo::new_placeholder(gas_used, None, status),
+        create_empty_sharded_state_updates(),
+        WriteSet::default(),
+        vec![],
+        is_reconfig,
+        TransactionAuxiliaryData::default(),
+    )
+}
+
 pub fn test_state_merkle_pruning_impl(
     input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
 ) {
@@ -323,3 +458,491 @@ proptest! {
         test_state_merkle_pruning_impl(input);
     }
 }
+
+pub fn test_create_checkpoint_at_version_impl(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+) {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+
+    // Commit all blocks (and record the last version of each block)
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut next_ver: Version = 0;
+    let mut block_end_versions = vec![];
+    for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+        test_helper::update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions_for_test(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        next_ver += txns_to_commit.len() as u64;
+        block_end_versions.push(next_ver - 1);
+    }
+    let latest_version = db.get_latest_version().unwrap();
+
+    // Verify checkpoints can't be created for future versions
+    let checkpoint_dir = TempPath::new();
+    assert!(db
+        .create_checkpoint_at_version(checkpoint_dir.path(), latest_version + 1)
+        .is_err());
+
+    // Create a checkpoint at an older version and verify its contents
+    let checkpoint_version = block_end_versions[block_end_versions.len() / 2];
+    db.create_checkpoint_at_version(checkpoint_dir.path(), checkpoint_version)
+        .unwrap();
+    let checkpoint_db = AptosDB::new_for_test(&checkpoint_dir);
+    assert_eq!(
+        checkpoint_db.get_latest_version().unwrap(),
+        checkpoint_version
+    );
+    assert_eq!(
+        checkpoint_db
+            .get_accumulator_root_hash(checkpoint_version)
+            .unwrap(),
+        db.get_accumulator_root_hash(checkpoint_version).unwrap()
+    );
+
+    // Verify checkpoints can't be created in an existing directory
+    assert!(db
+        .create_checkpoint_at_version(checkpoint_dir.path(), latest_version)
+        .is_err());
+
+    // Verify checkpoints can't be created for pruned versions
+    db.ledger_pruner
+        .save_min_readable_version(checkpoint_version + 1)
+        .unwrap();
+    let pruned_checkpoint_dir = TempPath::new();
+    assert!(db
+        .create_checkpoint_at_version(pruned_checkpoint_dir.path(), checkpoint_version)
+        .is_err());
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1))]
+
+    #[test]
+    fn test_create_checkpoint_at_version(input in arb_blocks_to_commit()) {
+        test_create_checkpoint_at_version_impl(input);
+    }
+}
+
+pub fn test_open_as_secondary_impl(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+) {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let secondary_dir = TempPath::new();
+    let mut secondary_db = None;
+
+    // Commit all blocks, and open the secondary instance halfway through
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut next_ver: Version = 0;
+    let num_blocks_before_open = (input.len() + 1) / 2;
+    for (index, (txns_to_commit, ledger_info_with_sigs)) in input.iter().enumerate() {
+        test_helper::update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions_for_test(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        next_ver += txns_to_commit.len() as u64;
+
+        if index + 1 == num_blocks_before_open {
+            let secondary = AptosDB::open_as_secondary(
+                StorageDirPaths::from_path(&tmp_dir),
+                secondary_dir.path(),
+                RocksdbConfigs::default(),
+                /*max_num_nodes_per_lru_cache_shard=*/ 0,
+            )
+            .unwrap();
+            assert_eq!(
+                secondary.get_latest_ledger_info().unwrap(),
+                *ledger_info_with_sigs
+            );
+            secondary_db = Some(secondary);
+        }
+    }
+    let secondary_db = secondary_db.unwrap();
+
+    // Verify the secondary instance observes the new commits after catching up
+    secondary_db.try_catch_up_with_primary().unwrap();
+    let latest_version = db.get_latest_version().unwrap();
+    assert_eq!(secondary_db.get_latest_version().unwrap(), latest_version);
+    assert_eq!(
+        secondary_db.get_latest_ledger_info().unwrap(),
+        db.get_latest_ledger_info().unwrap()
+    );
+    assert_eq!(
+        secondary_db
+            .get_accumulator_root_hash(latest_version)
+            .unwrap(),
+        db.get_accumulator_root_hash(latest_version).unwrap()
+    );
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1))]
+
+    #[test]
+    fn test_open_as_secondary(input in arb_blocks_to_commit()) {
+        test_open_as_secondary_impl(input);
+    }
+}
+
+pub fn test_tiered_storage_impl(input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>) {
+    let tmp_dir = TempPath::new();
+    let mut db = AptosDB::new_for_test(&tmp_dir);
+
+    // Commit all blocks
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut next_ver: Version = 0;
+    for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+        test_helper::update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions_for_test(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        next_ver += txns_to_commit.len() as u64;
+    }
+    let txns_to_commit: Vec<_> = input
+        .iter()
+        .flat_map(|(txns_to_commit, _)| txns_to_commit.iter())
+        .collect();
+
+    // Enable tiered storage (with a long migration interval, so we can migrate manually)
+    let cold_storage_dir = TempPath::new();
+    let config = TieredStorageConfig {
+        enable: true,
+        cold_version_horizon: 2,
+        segment_num_versions: 3,
+        migration_interval_ms: 3_600_000, // 1 hour
+        max_num_cached_segments: 2,
+        backend: ColdStorageBackendConfig::LocalDirectory {
+            path: cold_storage_dir.path().to_path_buf(),
+        },
+    };
+    db.enable_tiered_storage(config, tmp_dir.path()).unwrap();
+
+    // Migrate all complete segments older than the horizon
+    let tiered_storage = db.get_tiered_storage().unwrap();
+    let migration_progress = next_ver.saturating_sub(2) / 3 * 3;
+    assert_eq!(
+        tiered_storage.migrate(next_ver - 1).unwrap() as u64,
+        migration_progress / 3
+    );
+    assert_eq!(tiered_storage.get_migration_progress(), migration_progress);
+    assert_eq!(tiered_storage.migrate(next_ver - 1).unwrap(), 0);
+
+    // Verify that the migrated data was deleted from the ledger db
+    for version in 0..migration_progress {
+        assert!(db
+            .ledger_db
+            .transaction_db()
+            .get_transaction(version)
+            .is_err());
+        assert!(db.ledger_db.write_set_db().get_write_set(version).is_err());
+        assert!(db
+            .ledger_db
+            .event_db()
+            .get_events_by_version(version)
+            .unwrap()
+            .is_empty());
+    }
+
+    // Verify that all data can still be read (across both tiers)
+    for (version, txn_to_commit) in txns_to_commit.iter().enumerate() {
+        let version = version as Version;
+        assert_eq!(
+            &db.read_transaction(version).unwrap(),
+            txn_to_commit.transaction()
+        );
+        assert_eq!(
+            &db.read_write_set(version).unwrap(),
+            txn_to_commit.write_set()
+        );
+        assert_eq!(
+            db.read_events(version).unwrap().as_slice(),
+            txn_to_commit.events()
+        );
+    }
+    let transactions = db
+        .get_transaction_iterator(0, next_ver)
+        .unwrap()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    let write_sets = db
+        .get_write_set_iterator(0, next_ver)
+        .unwrap()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    let events = db
+        .get_events_iterator(0, next_ver)
+        .unwrap()
+        .collect::<Result<Vec<_>>>()
+        .unwrap();
+    for (version, txn_to_commit) in txns_to_commit.iter().enumerate() {
+        assert_eq!(&transactions[version], txn_to_commit.transaction());
+        assert_eq!(&write_sets[version], txn_to_commit.write_set());
+        assert_eq!(events[version].as_slice(), txn_to_commit.events());
+    }
+}
+
+pub fn test_tiered_storage_skips_pruned_versions_impl(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+) {
+    let tmp_dir = TempPath::new();
+    let mut db = AptosDB::new_for_test(&tmp_dir);
+
+    // Commit all blocks
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut next_ver: Version = 0;
+    for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+        test_helper::update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions_for_test(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        next_ver += txns_to_commit.len() as u64;
+    }
+
+    // Mark the first half of the ledger as pruned
+    let min_readable_version = next_ver / 2;
+    db.ledger_db
+        .write_pruner_progress(min_readable_version)
+        .unwrap();
+
+    // Enable tiered storage (with a long migration interval, so we can migrate manually)
+    let cold_storage_dir = TempPath::new();
+    let config = TieredStorageConfig {
+        enable: true,
+        cold_version_horizon: 2,
+        segment_num_versions: 3,
+        migration_interval_ms: 3_600_000, // 1 hour
+        max_num_cached_segments: 2,
+        backend: ColdStorageBackendConfig::LocalDirectory {
+            path: cold_storage_dir.path().to_path_buf(),
+        },
+    };
+    db.enable_tiered_storage(config, tmp_dir.path()).unwrap();
+
+    // Verify that migration starts at the min readable version (instead of failing on the
+    // pruned versions)
+    let tiered_storage = db.get_tiered_storage().unwrap();
+    let num_segments = next_ver
+        .saturating_sub(2)
+        .saturating_sub(min_readable_version)
+        / 3;
+    assert_eq!(
+        tiered_storage.migrate(next_ver - 1).unwrap() as u64,
+        num_segments
+    );
+    assert_eq!(
+        tiered_storage.get_migration_progress(),
+        min_readable_version + num_segments * 3
+    );
+
+    // Verify that the unpruned data can still be read (across both tiers)
+    let txns_to_commit: Vec<_> = input
+        .iter()
+        .flat_map(|(txns_to_commit, _)| txns_to_commit.iter())
+        .collect();
+    for version in min_readable_version..next_ver {
+        let txn_to_commit = txns_to_commit[version as usize];
+        assert_eq!(
+            &db.read_transaction(version).unwrap(),
+            txn_to_commit.transaction()
+        );
+        assert_eq!(
+            &db.read_write_set(version).unwrap(),
+            txn_to_commit.write_set()
+        );
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1))]
+
+    #[test]
+    fn test_tiered_storage(input in arb_blocks_to_commit()) {
+        test_tiered_storage_impl(input);
+    }
+
+    #[test]
+    fn test_tiered_storage_skips_pruned_versions(input in arb_blocks_to_commit()) {
+        test_tiered_storage_skips_pruned_versions_impl(input);
+    }
+}
+
+pub fn test_consistency_scrubber_impl(
+    input: Vec<(Vec<TransactionToCommit>, LedgerInfoWithSignatures)>,
+) {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+
+    // Commit all blocks
+    let mut in_memory_state = db
+        .state_store
+        .buffered_state()
+        .lock()
+        .current_state()
+        .clone();
+    let mut next_ver: Version = 0;
+    for (txns_to_commit, ledger_info_with_sigs) in input.iter() {
+        test_helper::update_in_memory_state(&mut in_memory_state, txns_to_commit.as_slice());
+        db.save_transactions_for_test(
+            txns_to_commit,
+            next_ver,                /* first_version */
+            next_ver.checked_sub(1), /* base_state_version */
+            Some(ledger_info_with_sigs),
+            true, /* sync_commit */
+            in_memory_state.clone(),
+        )
+        .unwrap();
+        next_ver += txns_to_commit.len() as u64;
+    }
+
+    // Create scrubbers that verify a single transaction info and the whole state tree per
+    // batch (so that every accumulator leaf is read as the sibling of a neighbour).
+    let new_scrubber = || {
+        ConsistencyScrubber::new(
+            Arc::clone(&db.ledger_db),
+            db.state_merkle_db(),
+            Arc::clone(&db.state_kv_db),
+            ConsistencyScrubberConfig {
+                enable: true,
+                transaction_batch_size: 1,
+                state_merkle_batch_size: 1_000_000,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    };
+    let scrub_transaction_accumulator = |scrubber: &ConsistencyScrubber| {
+        (0..=next_ver)
+            .map(|_| scrubber.scrub_transaction_accumulator().unwrap())
+            .sum::<u64>()
+    };
+    // Forgets the last scrubbed state snapshot (so that a new scrubber scrubs it again)
+    let reset_state_merkle_progress = || {
+        let batch = SchemaBatch::new();
+        batch
+            .delete::<DbMetadataSchema>(&DbMetadataKey::ConsistencyScrubberStateMerkleProgress)
+            .unwrap();
+        db.ledger_db.metadata_db_arc().write_schemas(batch).unwrap();
+    };
+
+    // Verify that the scrubbing progress is persisted (and resumed by a new scrubber)
+    let scrubber = new_scrubber();
+    assert_eq!(scrubber.scrub_transaction_accumulator().unwrap(), 0);
+    let metadata_db = db.ledger_db.metadata_db();
+    assert_eq!(
+        metadata_db
+            .get_consistency_scrubber_ledger_progress()
+            .unwrap(),
+        1
+    );
+    let scrubber = new_scrubber();
+
+    // Verify that no corruptions are detected in a consistent db
+    let snapshot_version = db
+        .state_merkle_db()
+        .get_state_snapshot_version_before(Version::MAX)
+        .unwrap()
+        .unwrap();
+    assert_eq!(scrub_transaction_accumulator(&scrubber), 0);
+    assert_eq!(scrubber.scrub_state_merkle().unwrap(), 0);
+    assert_eq!(
+        metadata_db
+            .get_consistency_scrubber_state_merkle_progress()
+            .unwrap(),
+        Some(snapshot_version)
+    );
+
+    // Verify that a fully scrubbed snapshot is not scrubbed again
+    assert_eq!(new_scrubber().scrub_state_merkle().unwrap(), 0);
+
+    // Corrupt the first accumulator leaf and verify the corruption is detected
+    if next_ver > 1 {
+        db.ledger_db
+            .transaction_accumulator_db_raw()
+            .put::<TransactionAccumulatorSchema>(
+                &Position::from_leaf_index(0),
+                &HashValue::random(),
+            )
+            .unwrap();
+        assert!(scrub_transaction_accumulator(&scrubber) > 0);
+    }
+
+    // Corrupt the state values and verify the leaf value corruptions are detected
+    let corrupted_value = StateValue::from(b"corrupted".to_vec());
+    for shard_id in 0..db.state_kv_db.num_shards() {
+        let db_shard = db.state_kv_db.db_shard(shard_id);
+        let mut iter = db_shard
+            .iter::<StateValueSchema>(ReadOptions::default())
+            .unwrap();
+        iter.seek_to_first();
+        for ((state_key, version), _) in iter.map(|result| result.unwrap()).collect::<Vec<_>>() {
+            db_shard
+                .put::<StateValueSchema>(&(state_key, version), &Some(corrupted_value.clone()))
+                .unwrap();
+        }
+    }
+    reset_state_merkle_progress();
+    assert!(new_scrubber().scrub_state_merkle().unwrap() > 0);
+
+    // Corrupt the root node of the latest state snapshot and verify the corruption is detected
+    reset_state_merkle_progress();
+    db.state_merkle_db()
+        .metadata_db()
+        .put::<JellyfishMerkleNodeSchema>(&NodeKey::new_empty_path(snapshot_version), &Node::Null)
+        .unwrap();
+    assert_eq!(new_scrubber().scrub_state_merkle().unwrap(), 1);
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(1))]
+
+    #[test]
+    fn test_consistency_scrubber(input in arb_blocks_to_commit()) {
+        test_consistency_scrubber_impl(input);
+    }
+}
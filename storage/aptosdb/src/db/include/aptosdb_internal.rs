@@ -11,6 +11,7 @@ impl AptosDB {
         hack_for_tests: bool,
         empty_buffered_state_for_restore: bool,
         skip_index_and_usage: bool,
+        is_secondary: bool,
     ) -> Self {
         let ledger_db = Arc::new(ledger_db);
         let state_merkle_db = Arc::new(state_merkle_db);
@@ -36,6 +37,7 @@ impl AptosDB {
             hack_for_tests,
             empty_buffered_state_for_restore,
             skip_index_and_usage,
+            is_secondary,
         ));
 
         let ledger_pruner =
@@ -55,6 +57,8 @@ impl AptosDB {
             ),
             ledger_commit_lock: std::sync::Mutex::new(()),
             indexer: None,
+            tiered_storage: None,
+            consistency_scrubber: None,
             skip_index_and_usage,
         }
     }
@@ -76,7 +80,7 @@ impl AptosDB {
 
         let (ledger_db, state_merkle_db, state_kv_db) = Self::open_dbs(
             db_paths,
-            rocksdb_configs,
+            rocksdb_configs.clone(),
             readonly,
             max_num_nodes_per_lru_cache_shard,
         )?;
@@ -90,6 +94,7 @@ impl AptosDB {
             readonly,
             empty_buffered_state_for_restore,
             rocksdb_configs.enable_storage_sharding,
+            /*is_secondary=*/ false,
         );
 
         if !readonly && enable_indexer {
@@ -164,6 +169,112 @@ impl AptosDB {
         .expect("Unable to open AptosDB")
     }
 
+    fn get_tiered_storage(&self) -> Option<&TieredStorage> {
+        self.tiered_storage
+            .as_ref()
+            .map(|tiered_storage| tiered_storage.tiered_storage())
+    }
+
+    /// Reads the ledger data at the given version, from cold storage if the version has been
+    /// migrated (and from the ledger db otherwise).
+    fn read_ledger_data<T>(
+        &self,
+        version: Version,
+        read_cold: impl FnOnce(&TieredStorage) -> Result<T>,
+        read_hot: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        match self.get_tiered_storage() {
+            Some(tiered_storage) if tiered_storage.is_cold(version) => read_cold(tiered_storage),
+            Some(tiered_storage) => {
+                let result = read_hot();
+                // The version may have been migrated (and deleted) while reading it, in which
+                // case it's read from cold storage instead.
+                if tiered_storage.is_cold(version) {
+                    read_cold(tiered_storage)
+                } else {
+                    result
+                }
+            },
+            None => read_hot(),
+        }
+    }
+
+    fn read_transaction(&self, version: Version) -> Result<Transaction> {
+        self.read_ledger_data(
+            version,
+            |tiered_storage| tiered_storage.get_transaction(version),
+            || self.ledger_db.transaction_db().get_transaction(version),
+        )
+    }
+
+    fn read_events(&self, version: Version) -> Result<Vec<ContractEvent>> {
+        self.read_ledger_data(
+            version,
+            |tiered_storage| tiered_storage.get_events(version),
+            || self.ledger_db.event_db().get_events_by_version(version),
+        )
+    }
+
+    fn read_write_set(&self, version: Version) -> Result<WriteSet> {
+        self.read_ledger_data(
+            version,
+            |tiered_storage| tiered_storage.get_write_set(version),
+            || self.ledger_db.write_set_db().get_write_set(version),
+        )
+    }
+
+    fn read_write_op(&self, state_key: &StateKey, version: Version) -> Result<WriteOp> {
+        self.read_write_set(version)?
+            .get(state_key)
+            .cloned()
+            .ok_or_else(|| {
+                AptosDbError::NotFound(format!("WriteOp of {:?} at version {}", state_key, version))
+            })
+    }
+
+    fn read_event_by_version_and_index(
+        &self,
+        version: Version,
+        index: u64,
+    ) -> Result<ContractEvent> {
+        self.read_ledger_data(
+            version,
+            |tiered_storage| {
+                tiered_storage
+                    .get_events(version)?
+                    .into_iter()
+                    .nth(index as usize)
+                    .ok_or_else(|| {
+                        AptosDbError::NotFound(format!("Event {} of Txn {}", index, version))
+                    })
+            },
+            || {
+                self.event_store
+                    .get_event_by_version_and_index(version, index)
+            },
+        )
+    }
+
+    /// Returns an iterator over the ledger data in [start_version, start_version + limit). The
+    /// migrated versions are read from cold storage, followed by the ledger db for the rest.
+    fn read_ledger_data_iter<'a, T: 'a, I: Iterator<Item = Result<T>> + 'a>(
+        &'a self,
+        start_version: Version,
+        limit: u64,
+        read_cold: impl FnOnce(&TieredStorage) -> Result<Vec<T>>,
+        read_hot: impl FnOnce(Version, u64) -> Result<I>,
+    ) -> Result<Box<dyn Iterator<Item = Result<T>> + 'a>> {
+        let cold_items = match self.get_tiered_storage() {
+            Some(tiered_storage) if tiered_storage.is_cold(start_version) => {
+                read_cold(tiered_storage)?
+            },
+            _ => vec![],
+        };
+        let num_cold_items = cold_items.len() as u64;
+        let hot_iter = read_hot(start_version + num_cold_items, limit - num_cold_items)?;
+        Ok(Box::new(cold_items.into_iter().map(Ok).chain(hot_iter)))
+    }
+
     fn error_if_ledger_pruned(&self, data_type: &str, version: Version) -> Result<()> {
         let min_readable_version = self.ledger_pruner.get_min_readable_version();
         ensure!(
@@ -176,6 +287,27 @@ impl AptosDB {
         Ok(())
     }
 
+    fn error_if_write_set_index_unavailable(&self, start_version: Version) -> Result<()> {
+        let write_set_db = self.ledger_db.write_set_db();
+        ensure!(write_set_db.is_index_enabled(), "The write set index is not enabled.");
+        match write_set_db.get_index_start_version()? {
+            Some(index_start_version) => {
+                ensure!(
+                    start_version >= index_start_version,
+                    "Write sets at version {} are not indexed, the index starts at version {}.",
+                    start_version,
+                    index_start_version
+                );
+                Ok(())
+            },
+            None => Err(AptosDbError::Other(
+                "The write set index has gaps (i.e., some write sets were committed without \
+                 being indexed)."
+                    .to_string(),
+            )),
+        }
+    }
+
     fn error_if_state_merkle_pruned(&self, data_type: &str, version: Version) -> Result<()> {
         let min_readable_version = self
             .state_store
@@ -156,7 +156,7 @@ impl DbReader for AptosDB {
             let limit = std::cmp::min(limit, ledger_version - start_version + 1);
 
             let txns = (start_version..start_version + limit)
-                .map(|version| self.ledger_db.transaction_db().get_transaction(version))
+                .map(|version| self.read_transaction(version))
                 .collect::<Result<Vec<_>>>()?;
             let txn_infos = (start_version..start_version + limit)
                 .map(|version| {
@@ -168,7 +168,7 @@ impl DbReader for AptosDB {
             let events = if fetch_events {
                 Some(
                     (start_version..start_version + limit)
-                        .map(|version| self.ledger_db.event_db().get_events_by_version(version))
+                        .map(|version| self.read_events(version))
                         .collect::<Result<Vec<_>>>()?,
                 )
             } else {
@@ -206,7 +206,7 @@ impl DbReader for AptosDB {
                     .event_store
                     .lookup_event_at_or_after_version(&new_block_event_key(), min_version)?
                     .ok_or_else(|| AptosDbError::NotFound(format!("NewBlockEvent at or after version {}", min_version)))?;
-                let event = self.event_store.get_event_by_version_and_index(block_version, index)?;
+                let event = self.read_event_by_version_and_index(block_version, index)?;
                 return Ok((block_version, event.expect_new_block_event()?.height()));
             }
 
@@ -253,9 +253,9 @@ impl DbReader for AptosDB {
                         .ledger_db
                         .transaction_info_db()
                         .get_transaction_info(version)?;
-                    let events = self.ledger_db.event_db().get_events_by_version(version)?;
-                    let write_set = self.ledger_db.write_set_db().get_write_set(version)?;
-                    let txn = self.ledger_db.transaction_db().get_transaction(version)?;
+                    let events = self.read_events(version)?;
+                    let write_set = self.read_write_set(version)?;
+                    let txn = self.read_transaction(version)?;
                     let auxiliary_data = self.ledger_db.transaction_auxiliary_data_db().get_transaction_auxiliary_data(version).unwrap_or_default();
                     let txn_output = TransactionOutput::new(
                         write_set,
@@ -306,11 +306,16 @@ impl DbReader for AptosDB {
             error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
             self.error_if_ledger_pruned("Transaction", start_version)?;
 
-            let iter = self
-                .ledger_db
-                .transaction_db()
-                .get_transaction_iter(start_version, limit as usize)?;
-            Ok(Box::new(iter) as Box<dyn Iterator<Item = Result<Transaction>> + '_>)
+            self.read_ledger_data_iter(
+                start_version,
+                limit,
+                |tiered_storage| tiered_storage.get_transactions(start_version, limit),
+                |start_version, limit| {
+                    self.ledger_db
+                        .transaction_db()
+                        .get_transaction_iter(start_version, limit as usize)
+                },
+            )
         })
     }
 
@@ -340,14 +345,16 @@ impl DbReader for AptosDB {
             error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
             self.error_if_ledger_pruned("Transaction", start_version)?;
 
-            let iter = self
-                .ledger_db
-                .event_db()
-                .get_events_by_version_iter(start_version, limit as usize)?;
-            Ok(Box::new(iter)
-                as Box<
-                    dyn Iterator<Item = Result<Vec<ContractEvent>>> + '_,
-                >)
+            self.read_ledger_data_iter(
+                start_version,
+                limit,
+                |tiered_storage| tiered_storage.get_events_range(start_version, limit),
+                |start_version, limit| {
+                    self.ledger_db
+                        .event_db()
+                        .get_events_by_version_iter(start_version, limit as usize)
+                },
+            )
         })
     }
 
@@ -360,11 +367,77 @@ impl DbReader for AptosDB {
             error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
             self.error_if_ledger_pruned("Transaction", start_version)?;
 
+            self.read_ledger_data_iter(
+                start_version,
+                limit,
+                |tiered_storage| tiered_storage.get_write_sets(start_version, limit),
+                |start_version, limit| {
+                    self.ledger_db
+                        .write_set_db()
+                        .get_write_set_iter(start_version, limit as usize)
+                },
+            )
+        })
+    }
+
+    fn get_write_op_iterator(
+        &self,
+        state_key: &StateKey,
+        start_version: Version,
+        end_version: Version,
+    ) -> Result<Box<dyn Iterator<Item = Result<(Version, WriteOp)>> + '_>> {
+        gauged_api("get_write_op_iterator", || {
+            self.error_if_ledger_pruned("WriteSet", start_version)?;
+            self.error_if_write_set_index_unavailable(start_version)?;
+            let end_version = std::cmp::min(
+                end_version,
+                self.ledger_db.metadata_db().get_latest_version()? + 1,
+            );
+
+            let state_key = state_key.clone();
             let iter = self
                 .ledger_db
                 .write_set_db()
-                .get_write_set_iter(start_version, limit as usize)?;
-            Ok(Box::new(iter) as Box<dyn Iterator<Item = Result<WriteSet>> + '_>)
+                .get_write_set_version_iter(&state_key, start_version, end_version)?
+                .map(move |version| {
+                    let version = version?;
+                    Ok((version, self.read_write_op(&state_key, version)?))
+                });
+            Ok(Box::new(iter) as Box<dyn Iterator<Item = Result<(Version, WriteOp)>>>)
+        })
+    }
+
+    fn get_prefixed_write_op_iterator(
+        &self,
+        key_prefix: &StateKeyPrefix,
+        start_version: Version,
+        end_version: Version,
+    ) -> Result<Box<dyn Iterator<Item = Result<(StateKey, Version, WriteOp)>> + '_>> {
+        gauged_api("get_prefixed_write_op_iterator", || {
+            self.error_if_ledger_pruned("WriteSet", start_version)?;
+            self.error_if_write_set_index_unavailable(start_version)?;
+            let end_version = std::cmp::min(
+                end_version,
+                self.ledger_db.metadata_db().get_latest_version()? + 1,
+            );
+
+            let iter = self
+                .ledger_db
+                .write_set_db()
+                .get_prefixed_write_set_version_iter(
+                    key_prefix.clone(),
+                    start_version,
+                    end_version,
+                )?
+                .map(move |item| {
+                    let (state_key, version) = item?;
+                    let write_op = self.read_write_op(&state_key, version)?;
+                    Ok((state_key, version, write_op))
+                });
+            Ok(Box::new(iter)
+                as Box<
+                    dyn Iterator<Item = Result<(StateKey, Version, WriteOp)>>,
+                >)
         })
     }
 
@@ -644,9 +717,7 @@ impl DbReader for AptosDB {
 
             // TODO(grao): Consider return BlockInfo instead of NewBlockEvent.
             let new_block_event = self
-                .ledger_db
-                .event_db()
-                .get_events_by_version(first_version)?
+                .read_events(first_version)?
                 .into_iter()
                 .find(|event| {
                     if let Some(key) = event.event_key() {
@@ -765,6 +836,22 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_state_merkle_pruner_progress(&self) -> Result<PrunerProgress> {
+        gauged_api("get_state_merkle_pruner_progress", || {
+            Ok(self
+                .state_store
+                .state_db
+                .state_merkle_pruner
+                .get_pruner_progress())
+        })
+    }
+
+    fn create_checkpoint_at_version(&self, path: &Path, version: Version) -> Result<()> {
+        gauged_api("create_checkpoint_at_version", || {
+            self.create_checkpoint_from_live_db(path, version)
+        })
+    }
+
     fn get_epoch_snapshot_prune_window(&self) -> Result<usize> {
         gauged_api("get_state_prune_window", || {
             Ok(self
@@ -807,6 +894,12 @@ impl DbReader for AptosDB {
             self.state_store.get_usage(version)
         })
     }
+
+    fn get_epoch_ledger_stats(&self, epoch: u64) -> Result<Option<EpochLedgerStats>> {
+        gauged_api("get_epoch_ledger_stats", || {
+            self.ledger_db.metadata_db().get_epoch_ledger_stats(epoch)
+        })
+    }
 }
 
 impl AptosDB {
@@ -894,11 +987,11 @@ impl AptosDB {
                 ledger_version,
                 self.ledger_db.transaction_accumulator_db(),
             )?;
-        let transaction = self.ledger_db.transaction_db().get_transaction(version)?;
+        let transaction = self.read_transaction(version)?;
 
         // If events were requested, also fetch those.
         let events = if fetch_events {
-            Some(self.ledger_db.event_db().get_events_by_version(version)?)
+            Some(self.read_events(version)?)
         } else {
             None
         };
@@ -960,7 +1053,7 @@ impl AptosDB {
         let mut events_with_version = event_indices
             .into_iter()
             .map(|(seq, ver, idx)| {
-                let event = self.event_store.get_event_by_version_and_index(ver, idx)?;
+                let event = self.read_event_by_version_and_index(ver, idx)?;
                 let v0 = match &event {
                     ContractEvent::V1(event) => event,
                     ContractEvent::V2(_) => bail!("Unexpected module event"),
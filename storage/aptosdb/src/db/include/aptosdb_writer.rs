@@ -197,6 +197,26 @@ impl DbWriter for AptosDB {
             Ok(())
         })
     }
+
+    fn import_state_kv_shard_set(&self, import_dir: &Path) -> Result<()> {
+        gauged_api("import_state_kv_shard_set", || {
+            state_kv_shard_set::import_state_kv_shard_set(self, import_dir).map(|_manifest| ())
+        })
+    }
+
+    fn set_state_merkle_pruner_throttle(&self, max_versions_per_sec: Option<u64>) -> Result<()> {
+        gauged_api("set_state_merkle_pruner_throttle", || {
+            info!(
+                max_versions_per_sec = max_versions_per_sec,
+                "Updating the state merkle pruner throttle."
+            );
+            self.state_store
+                .state_db
+                .state_merkle_pruner
+                .set_max_versions_per_sec(max_versions_per_sec);
+            Ok(())
+        })
+    }
 }
 
 impl AptosDB {
@@ -290,7 +310,7 @@ impl AptosDB {
             s.spawn(|_| {
                 self.ledger_db
                     .write_set_db()
-                    .commit_write_sets(txns_to_commit, first_version)
+                    .commit_write_sets(txns_to_commit, first_version, skip_index_and_usage)
                     .unwrap()
             });
             s.spawn(|_| {
@@ -383,6 +403,12 @@ impl AptosDB {
             }
         }
 
+        // Update the ledger stats of the committed epoch(s). These rely on the
+        // account transaction index, so they are skipped along with the index.
+        if !skip_index_and_usage {
+            self.put_epoch_ledger_stats(txns_to_commit, first_version, &ledger_metadata_batch)?;
+        }
+
         let last_version = first_version + txns_to_commit.len() as u64 - 1;
         ledger_metadata_batch
             .put::<DbMetadataSchema>(
@@ -415,6 +441,88 @@ impl AptosDB {
         Ok(())
     }
 
+    /// Updates the ledger stats of the epochs containing the given transactions,
+    /// and writes them to `batch`.
+    fn put_epoch_ledger_stats(
+        &self,
+        txns_to_commit: &[TransactionToCommit],
+        first_version: Version,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        let _timer = OTHER_TIMERS_SECONDS.timer_with(&["put_epoch_ledger_stats"]);
+
+        // Fetch the existing stats of the current epoch (if any)
+        let ledger_metadata_db = self.ledger_db.metadata_db();
+        let epoch = ledger_metadata_db.get_epoch(first_version)?;
+        let mut epoch_ledger_stats = ledger_metadata_db
+            .get_epoch_ledger_stats(epoch)?
+            .unwrap_or_else(|| EpochLedgerStats::new(epoch, first_version));
+
+        let mut senders_in_batch = HashSet::new();
+        for (i, txn_to_commit) in txns_to_commit.iter().enumerate() {
+            let version = first_version + i as Version;
+
+            // Identify if this is the first transaction of the sender in the epoch
+            let is_new_sender = match txn_to_commit.transaction().try_as_signed_user_txn() {
+                Some(user_txn) => {
+                    senders_in_batch.insert(user_txn.sender())
+                        && !self.sent_transaction_in_epoch(
+                            user_txn.sender(),
+                            user_txn.sequence_number(),
+                            epoch_ledger_stats.first_version,
+                            first_version,
+                        )?
+                },
+                None => false,
+            };
+            epoch_ledger_stats.add_transaction(
+                version,
+                txn_to_commit.transaction(),
+                txn_to_commit.status(),
+                txn_to_commit.gas_used(),
+                is_new_sender,
+            );
+
+            // Reconfiguration transactions end the epoch
+            if txn_to_commit.is_reconfig() {
+                LedgerMetadataDb::put_epoch_ledger_stats(&epoch_ledger_stats, batch)?;
+                epoch_ledger_stats =
+                    EpochLedgerStats::new(epoch_ledger_stats.epoch + 1, version + 1);
+                senders_in_batch.clear();
+            }
+        }
+
+        if !epoch_ledger_stats.is_empty() {
+            LedgerMetadataDb::put_epoch_ledger_stats(&epoch_ledger_stats, batch)?;
+        }
+        Ok(())
+    }
+
+    /// Returns true iff the sender committed its previous transaction (i.e., the
+    /// one with the previous sequence number) in the epoch covered by the stats,
+    /// before the given batch.
+    fn sent_transaction_in_epoch(
+        &self,
+        sender: AccountAddress,
+        sequence_number: u64,
+        epoch_first_version: Version,
+        batch_first_version: Version,
+    ) -> Result<bool> {
+        let (Some(previous_sequence_number), Some(latest_version)) = (
+            sequence_number.checked_sub(1),
+            batch_first_version.checked_sub(1),
+        ) else {
+            return Ok(false);
+        };
+
+        // Note: if the previous transaction has been pruned, it's
+        // treated as having been committed in an earlier epoch.
+        Ok(self
+            .transaction_store
+            .get_account_transaction_version(sender, previous_sequence_number, latest_version)?
+            .map_or(false, |version| version >= epoch_first_version))
+    }
+
     fn commit_events(
         &self,
         txns_to_commit: &[TransactionToCommit],
@@ -5,6 +5,8 @@
 use crate::{
     backup::{backup_handler::BackupHandler, restore_utils},
     common::MAX_NUM_EPOCH_ENDING_LEDGER_INFO,
+    consistency_scrubber::ConsistencyScrubberManager,
+    db_options::DbOpenMode,
     event_store::EventStore,
     ledger_db::{
         ledger_metadata_db::LedgerMetadataDb,
@@ -22,13 +24,16 @@ use crate::{
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
     },
     state_kv_db::StateKvDb,
+    state_kv_shard_set,
     state_merkle_db::StateMerkleDb,
     state_store::StateStore,
+    tiered_storage::{TieredStorage, TieredStorageManager},
     transaction_store::TransactionStore,
-    utils::new_sharded_kv_schema_batch,
+    utils::{new_sharded_kv_schema_batch, truncation_helper::truncate_dbs_to_version},
 };
 use aptos_config::config::{
-    PrunerConfig, RocksdbConfig, RocksdbConfigs, StorageDirPaths, NO_OP_STORAGE_PRUNER_CONFIG,
+    ConsistencyScrubberConfig, PrunerConfig, RocksdbConfig, RocksdbConfigs, StorageDirPaths,
+    TieredStorageConfig, BUFFERED_STATE_TARGET_ITEMS, NO_OP_STORAGE_PRUNER_CONFIG,
 };
 use aptos_crypto::HashValue;
 use aptos_db_indexer::Indexer;
@@ -39,8 +44,9 @@ use aptos_schemadb::{ReadOptions, SchemaBatch};
 use aptos_scratchpad::SparseMerkleTree;
 use aptos_storage_interface::{
     cached_state_view::ShardedStateCache, db_anyhow as anyhow, db_ensure as ensure,
-    db_other_bail as bail, state_delta::StateDelta, AptosDbError, DbReader, DbWriter,
-    ExecutedTrees, Order, Result, StateSnapshotReceiver, MAX_REQUEST_LIMIT,
+    db_other_bail as bail, epoch_ledger_stats::EpochLedgerStats, pruner_progress::PrunerProgress,
+    state_delta::StateDelta, AptosDbError, DbReader, DbWriter, ExecutedTrees, Order, Result,
+    StateSnapshotReceiver, MAX_REQUEST_LIMIT,
 };
 use aptos_types::{
     account_address::AccountAddress,
@@ -69,13 +75,14 @@ use aptos_types::{
         TransactionListWithProof, TransactionOutput, TransactionOutputListWithProof,
         TransactionToCommit, TransactionWithProof, Version,
     },
-    write_set::WriteSet,
+    write_set::{WriteOp, WriteSet},
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use move_resource_viewer::MoveValueAnnotator;
 use rayon::prelude::*;
 use std::{
     cell::Cell,
+    collections::HashSet,
     fmt::{Debug, Formatter},
     iter::Iterator,
     path::Path,
@@ -100,6 +107,8 @@ pub struct AptosDB {
     _rocksdb_property_reporter: RocksdbPropertyReporter,
     ledger_commit_lock: std::sync::Mutex<()>,
     indexer: Option<Indexer>,
+    tiered_storage: Option<TieredStorageManager>,
+    consistency_scrubber: Option<ConsistencyScrubberManager>,
     skip_index_and_usage: bool,
 }
 
@@ -156,23 +165,142 @@ impl AptosDB {
         )
     }
 
+    /// Opens the db as a RocksDB secondary instance, following the (running) primary instance at
+    /// `db_paths`. The secondary instance is read-only, and keeps its own (private) files under
+    /// `secondary_db_root_path`. Use `try_catch_up_with_primary` to observe new commits of the
+    /// primary instance (see also `SecondaryAptosDB`, which catches up periodically).
+    pub fn open_as_secondary(
+        db_paths: StorageDirPaths,
+        secondary_db_root_path: impl AsRef<Path>,
+        rocksdb_configs: RocksdbConfigs,
+        max_num_nodes_per_lru_cache_shard: usize,
+    ) -> Result<Self> {
+        let enable_storage_sharding = rocksdb_configs.enable_storage_sharding;
+        let (ledger_db, state_merkle_db, state_kv_db) = Self::open_dbs_with_mode(
+            &db_paths,
+            rocksdb_configs,
+            &DbOpenMode::Secondary(secondary_db_root_path.as_ref().to_path_buf()),
+            max_num_nodes_per_lru_cache_shard,
+        )?;
+
+        Ok(Self::new_with_dbs(
+            ledger_db,
+            state_merkle_db,
+            state_kv_db,
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            BUFFERED_STATE_TARGET_ITEMS,
+            /*hack_for_tests=*/ false,
+            /*empty_buffered_state_for_restore=*/ false,
+            enable_storage_sharding,
+            /*is_secondary=*/ true,
+        ))
+    }
+
+    /// Catches up with the primary instance (only valid for dbs opened as secondary). Note: the
+    /// in-memory state is reset to the latest state snapshot, so the latest state checkpoint
+    /// served by a secondary instance may lag behind the latest committed version.
+    pub fn try_catch_up_with_primary(&self) -> Result<()> {
+        let _timer = OTHER_TIMERS_SECONDS.timer_with(&["try_catch_up_with_primary"]);
+
+        // The primary commits the data of the sub dbs before the overall commit progress (and
+        // the latest ledger info). So, the ledger metadata db is caught up first, so that the
+        // latest ledger info never exceeds the data visible in the other (sub) dbs.
+        self.ledger_db.try_catch_up_metadata_db_with_primary()?;
+        self.state_store
+            .state_db
+            .state_merkle_db
+            .try_catch_up_with_primary()?;
+        self.state_kv_db.try_catch_up_with_primary()?;
+        self.ledger_db.try_catch_up_sub_dbs_with_primary()?;
+        self.state_store.reset_to_latest_snapshot()
+    }
+
+    /// Enables tiered storage: transactions, events and write sets older than the configured
+    /// version horizon are migrated to cold storage in the background, and reads of migrated
+    /// versions are transparently served from cold storage. Relative cold storage directories
+    /// are resolved against `storage_dir`. Note: the backup service only serves data that has
+    /// not been migrated.
+    pub fn enable_tiered_storage(
+        &mut self,
+        config: TieredStorageConfig,
+        storage_dir: &Path,
+    ) -> Result<()> {
+        ensure!(
+            self.tiered_storage.is_none(),
+            "Tiered storage has already been enabled."
+        );
+        self.tiered_storage = Some(TieredStorageManager::new(
+            Arc::clone(&self.ledger_db),
+            config,
+            storage_dir,
+        )?);
+        Ok(())
+    }
+
+    /// Enables the consistency scrubber: the transaction accumulator and the latest state
+    /// snapshot are periodically re-verified (in small batches) in the background, and any
+    /// corruption is reported via metrics and logs.
+    pub fn enable_consistency_scrubber(&mut self, config: ConsistencyScrubberConfig) -> Result<()> {
+        ensure!(
+            self.consistency_scrubber.is_none(),
+            "Consistency scrubber has already been enabled."
+        );
+        self.consistency_scrubber = Some(ConsistencyScrubberManager::new(
+            Arc::clone(&self.ledger_db),
+            Arc::clone(&self.state_store.state_db.state_merkle_db),
+            Arc::clone(&self.state_kv_db),
+            config,
+        )?);
+        Ok(())
+    }
+
+    /// Enables the write set index by state key: the write sets committed from now on are
+    /// indexed, and write ops can be queried by state key (prefix) from the version the index
+    /// starts at.
+    pub fn enable_write_set_index(&self) -> Result<()> {
+        let next_version = match self.ledger_db.metadata_db().get_latest_version() {
+            Ok(version) => version + 1,
+            Err(AptosDbError::NotFound(_)) => 0,
+            Err(error) => return Err(error),
+        };
+        self.ledger_db.write_set_db().enable_index(next_version)
+    }
+
     pub fn open_dbs(
         db_paths: &StorageDirPaths,
         rocksdb_configs: RocksdbConfigs,
         readonly: bool,
         max_num_nodes_per_lru_cache_shard: usize,
     ) -> Result<(LedgerDb, StateMerkleDb, StateKvDb)> {
-        let ledger_db = LedgerDb::new(db_paths.ledger_db_root_path(), rocksdb_configs, readonly)?;
-        let state_kv_db = StateKvDb::new(
+        Self::open_dbs_with_mode(
             db_paths,
             rocksdb_configs,
-            readonly,
+            &DbOpenMode::new(readonly),
+            max_num_nodes_per_lru_cache_shard,
+        )
+    }
+
+    fn open_dbs_with_mode(
+        db_paths: &StorageDirPaths,
+        rocksdb_configs: RocksdbConfigs,
+        open_mode: &DbOpenMode,
+        max_num_nodes_per_lru_cache_shard: usize,
+    ) -> Result<(LedgerDb, StateMerkleDb, StateKvDb)> {
+        let ledger_db = LedgerDb::new(
+            db_paths.ledger_db_root_path(),
+            rocksdb_configs.clone(),
+            open_mode,
+        )?;
+        let state_kv_db = StateKvDb::new(
+            db_paths,
+            rocksdb_configs.clone(),
+            open_mode,
             ledger_db.metadata_db_arc(),
         )?;
         let state_merkle_db = StateMerkleDb::new(
             db_paths,
             rocksdb_configs,
-            readonly,
+            open_mode,
             max_num_nodes_per_lru_cache_shard,
         )?;
 
@@ -209,6 +337,88 @@ impl AptosDB {
         Ok(())
     }
 
+    /// Creates a physical checkpoint of this (running) DB in the directory specified
+    /// by `cp_path`, and truncates the checkpoint to the given version. Commits are not
+    /// blocked: the ledger metadata db (which holds the overall commit progress) is
+    /// checkpointed first, so all other dbs in the checkpoint contain (at least) the
+    /// data up to the overall commit progress, and anything newer is truncated.
+    fn create_checkpoint_from_live_db(&self, cp_path: &Path, version: Version) -> Result<()> {
+        let start = Instant::now();
+
+        ensure!(
+            !cp_path.exists(),
+            "Checkpoint dir already exists: {:?}",
+            cp_path
+        );
+        let latest_version = self.get_latest_version()?;
+        ensure!(
+            version <= latest_version,
+            "Unable to create a checkpoint at version {}, the latest version is {}.",
+            version,
+            latest_version
+        );
+
+        // Pause the pruners until the checkpoint is truncated, so that the data at the version
+        // can't be pruned from any of the dbs in the meantime
+        let _pruner_pause_guards = [
+            self.ledger_pruner.pause_pruner(),
+            self.state_store.state_db.state_merkle_pruner.pause_pruner(),
+            self.state_store
+                .state_db
+                .epoch_snapshot_pruner
+                .pause_pruner(),
+            self.state_store.state_kv_pruner.pause_pruner(),
+        ];
+        self.error_if_ledger_pruned("Transaction", version)?;
+        self.error_if_state_kv_pruned("StateValue", version)?;
+        self.error_if_state_merkle_pruned("State merkle", version)?;
+
+        let sharding = self.state_kv_db.enabled_sharding();
+        info!(
+            sharding = sharding,
+            version = version,
+            "Creating checkpoint for AptosDB at version."
+        );
+
+        // Checkpoint all dbs (the ledger metadata db must be first)
+        std::fs::create_dir_all(cp_path)?;
+        self.ledger_db
+            .create_checkpoint_from_db(cp_path, sharding)?;
+        if sharding {
+            self.state_kv_db.create_checkpoint_from_db(cp_path)?;
+        }
+        self.state_store
+            .state_db
+            .state_merkle_db
+            .create_checkpoint_from_db(cp_path)?;
+
+        // Truncate the checkpoint to the requested version
+        let rocksdb_configs = RocksdbConfigs {
+            enable_storage_sharding: sharding,
+            ..Default::default()
+        };
+        let (ledger_db, state_merkle_db, state_kv_db) = Self::open_dbs(
+            &StorageDirPaths::from_path(cp_path),
+            rocksdb_configs,
+            /*readonly=*/ false,
+            /*max_num_nodes_per_lru_cache_shard=*/ 0,
+        )?;
+        truncate_dbs_to_version(
+            Arc::new(ledger_db),
+            Arc::new(state_kv_db),
+            &state_merkle_db,
+            version,
+        )?;
+
+        info!(
+            cp_path = cp_path,
+            version = version,
+            time_ms = %start.elapsed().as_millis(),
+            "Made AptosDB checkpoint at version."
+        );
+        Ok(())
+    }
+
     pub fn commit_genesis_ledger_info(&self, genesis_li: &LedgerInfoWithSignatures) -> Result<()> {
         let ledger_metadata_db = self.ledger_db.metadata_db();
         let current_epoch = ledger_metadata_db
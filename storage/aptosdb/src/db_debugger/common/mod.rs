@@ -1,7 +1,10 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{db_debugger::ShardingConfig, ledger_db::LedgerDb, state_merkle_db::StateMerkleDb};
+use crate::{
+    db_debugger::ShardingConfig, db_options::DbOpenMode, ledger_db::LedgerDb,
+    state_merkle_db::StateMerkleDb,
+};
 use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
 use aptos_storage_interface::Result;
 use aptos_types::nibble::{nibble_path::NibblePath, Nibble};
@@ -29,7 +32,7 @@ impl DbDir {
                 enable_storage_sharding: self.sharding_config.enable_storage_sharding,
                 ..Default::default()
             },
-            false,
+            &DbOpenMode::ReadWrite,
             0,
         )
     }
@@ -41,7 +44,7 @@ impl DbDir {
                 enable_storage_sharding: self.sharding_config.enable_storage_sharding,
                 ..Default::default()
             },
-            true,
+            &DbOpenMode::ReadOnly,
         )
     }
 }
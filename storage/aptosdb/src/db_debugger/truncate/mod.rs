@@ -4,23 +4,16 @@
 use crate::{
     db::AptosDB,
     db_debugger::ShardingConfig,
-    schema::{
-        db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
-        epoch_by_version::EpochByVersionSchema,
-        jellyfish_merkle_node::JellyfishMerkleNodeSchema,
-    },
-    state_merkle_db::StateMerkleDb,
+    schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
     state_store::StateStore,
     utils::truncation_helper::{
-        find_closest_node_version_at_or_before, get_current_version_in_state_merkle_db,
+        find_tree_root_at_or_before, get_current_version_in_state_merkle_db,
         get_state_kv_commit_progress, truncate_state_merkle_db,
     },
 };
 use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
-use aptos_jellyfish_merkle::node_type::NodeKey;
-use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
+use aptos_schemadb::SchemaBatch;
 use aptos_storage_interface::{db_ensure as ensure, AptosDbError, Result};
-use aptos_types::transaction::Version;
 use claims::assert_le;
 use clap::Parser;
 use std::{fs, path::PathBuf, sync::Arc};
@@ -129,7 +122,7 @@ impl Cmd {
         // TODO(grao): We are using a brute force implementation for now. We might be able to make
         // it faster, since our data is append only.
         if target_version < state_merkle_db_version {
-            let state_merkle_target_version = Self::find_tree_root_at_or_before(
+            let state_merkle_target_version = find_tree_root_at_or_before(
                 &ledger_db.metadata_db_arc(),
                 &state_merkle_db,
                 target_version,
@@ -182,41 +175,6 @@ impl Cmd {
 
         Ok(())
     }
-
-    fn find_tree_root_at_or_before(
-        ledger_metadata_db: &DB,
-        state_merkle_db: &StateMerkleDb,
-        version: Version,
-    ) -> Result<Option<Version>> {
-        match find_closest_node_version_at_or_before(state_merkle_db, version)? {
-            Some(closest_version) => {
-                if Self::root_exists_at_version(state_merkle_db, closest_version)? {
-                    return Ok(Some(closest_version));
-                }
-                let mut iter =
-                    ledger_metadata_db.iter::<EpochByVersionSchema>(ReadOptions::default())?;
-                iter.seek_for_prev(&version)?;
-                match iter.next().transpose()? {
-                    Some((closest_epoch_version, _)) => {
-                        if Self::root_exists_at_version(state_merkle_db, closest_epoch_version)? {
-                            Ok(Some(closest_epoch_version))
-                        } else {
-                            Ok(None)
-                        }
-                    },
-                    None => Ok(None),
-                }
-            },
-            None => Ok(None),
-        }
-    }
-
-    fn root_exists_at_version(state_merkle_db: &StateMerkleDb, version: Version) -> Result<bool> {
-        Ok(state_merkle_db
-            .metadata_db()
-            .get::<JellyfishMerkleNodeSchema>(&NodeKey::new_empty_path(version))?
-            .is_some())
-    }
 }
 
 #[cfg(test)]
@@ -229,7 +187,8 @@ mod test {
             AptosDB,
         },
         schema::{
-            epoch_by_version::EpochByVersionSchema, ledger_info::LedgerInfoSchema,
+            epoch_by_version::EpochByVersionSchema,
+            jellyfish_merkle_node::JellyfishMerkleNodeSchema, ledger_info::LedgerInfoSchema,
             stale_node_index::StaleNodeIndexSchema,
             stale_node_index_cross_epoch::StaleNodeIndexCrossEpochSchema,
             stale_state_value_index::StaleStateValueIndexSchema, state_value::StateValueSchema,
@@ -239,6 +198,7 @@ mod test {
         },
         utils::truncation_helper::num_frozen_nodes_in_accumulator,
     };
+    use aptos_schemadb::ReadOptions;
     use aptos_storage_interface::DbReader;
     use aptos_temppath::TempPath;
     use proptest::prelude::*;
@@ -2,21 +2,50 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::schema::*;
-use aptos_config::config::RocksdbConfig;
+use aptos_config::config::{
+    RocksdbColumnFamilyConfig, RocksdbCompactionStyle, RocksdbCompressionType, RocksdbConfig,
+};
 use aptos_schemadb::{
-    BlockBasedOptions, Cache, ColumnFamilyDescriptor, ColumnFamilyName, DBCompressionType, Options,
-    SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
+    BlockBasedOptions, Cache, ColumnFamilyDescriptor, ColumnFamilyName, DBCompactionStyle,
+    DBCompressionType, Options, SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
 };
 use aptos_types::transaction::Version;
+use std::{collections::HashMap, path::PathBuf};
 
 const VERSION_SIZE: usize = std::mem::size_of::<Version>();
 
+/// The mode in which the underlying RocksDB instances are opened.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(crate) enum DbOpenMode {
+    ReadWrite,
+    ReadOnly,
+    /// A RocksDB secondary instance, following the primary instance at the regular db path.
+    /// Each secondary instance keeps its own (private) files under `<root>/<db_name>`.
+    Secondary(PathBuf),
+}
+
+impl DbOpenMode {
+    pub(crate) fn new(readonly: bool) -> Self {
+        if readonly {
+            Self::ReadOnly
+        } else {
+            Self::ReadWrite
+        }
+    }
+
+    pub(crate) fn is_secondary(&self) -> bool {
+        matches!(self, Self::Secondary(_))
+    }
+}
+
 pub(super) fn ledger_db_column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
         BLOCK_BY_VERSION_CF_NAME,
         BLOCK_INFO_CF_NAME,
+        COLD_SEGMENT_CF_NAME,
         EPOCH_BY_VERSION_CF_NAME,
+        EPOCH_LEDGER_STATS_CF_NAME,
         EVENT_ACCUMULATOR_CF_NAME,
         EVENT_BY_KEY_CF_NAME,
         EVENT_BY_VERSION_CF_NAME,
@@ -32,6 +61,8 @@ pub(super) fn ledger_db_column_families() -> Vec<ColumnFamilyName> {
         TRANSACTION_INFO_CF_NAME,
         VERSION_DATA_CF_NAME,
         WRITE_SET_CF_NAME,
+        WRITE_SET_BY_STATE_KEY_CF_NAME,
+        WRITE_SET_INDEX_BY_VERSION_CF_NAME,
         DB_METADATA_CF_NAME,
     ]
 }
@@ -86,6 +117,8 @@ pub(super) fn write_set_db_column_families() -> Vec<ColumnFamilyName> {
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
         DB_METADATA_CF_NAME,
         WRITE_SET_CF_NAME,
+        WRITE_SET_BY_STATE_KEY_CF_NAME,
+        WRITE_SET_INDEX_BY_VERSION_CF_NAME,
     ]
 }
 
@@ -94,8 +127,10 @@ pub(super) fn ledger_metadata_db_column_families() -> Vec<ColumnFamilyName> {
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
         BLOCK_BY_VERSION_CF_NAME,
         BLOCK_INFO_CF_NAME,
+        COLD_SEGMENT_CF_NAME,
         DB_METADATA_CF_NAME,
         EPOCH_BY_VERSION_CF_NAME,
+        EPOCH_LEDGER_STATS_CF_NAME,
         LEDGER_INFO_CF_NAME,
         VERSION_DATA_CF_NAME,
     ]
@@ -125,23 +160,71 @@ pub(super) fn state_kv_db_column_families() -> Vec<ColumnFamilyName> {
     ]
 }
 
+/// The block caches of a db, shared by the column families of all its (sub) dbs and shards.
+/// There is one cache per level of the config: the default cache of the db, the cache of
+/// `column_family_config` (if it sets a block cache size) and one cache per column family
+/// override that sets a block cache size.
+pub(crate) struct BlockCaches {
+    default_cache: Cache,
+    column_family_cache: Option<Cache>,
+    override_caches: HashMap<String, Cache>,
+}
+
+impl BlockCaches {
+    pub(crate) fn new(rocksdb_config: &RocksdbConfig) -> Self {
+        let new_cache = |block_cache_size: u64| Cache::new_lru_cache(block_cache_size as usize);
+        Self {
+            default_cache: new_cache(rocksdb_config.block_cache_size),
+            column_family_cache: rocksdb_config
+                .column_family_config
+                .block_cache_size
+                .map(new_cache),
+            override_caches: rocksdb_config
+                .column_family_overrides
+                .iter()
+                .filter_map(|(cf_name, cf_config)| {
+                    cf_config
+                        .block_cache_size
+                        .map(|block_cache_size| (cf_name.clone(), new_cache(block_cache_size)))
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the block cache of the given column family
+    fn get(&self, cf_name: &str) -> &Cache {
+        self.override_caches
+            .get(cf_name)
+            .or(self.column_family_cache.as_ref())
+            .unwrap_or(&self.default_cache)
+    }
+}
+
 fn gen_cfds<F>(
     rocksdb_config: &RocksdbConfig,
+    block_caches: &BlockCaches,
     cfs: Vec<ColumnFamilyName>,
     cf_opts_post_processor: F,
 ) -> Vec<ColumnFamilyDescriptor>
 where
     F: Fn(ColumnFamilyName, &mut Options),
 {
-    let mut table_options = BlockBasedOptions::default();
-    table_options.set_cache_index_and_filter_blocks(rocksdb_config.cache_index_and_filter_blocks);
-    table_options.set_block_size(rocksdb_config.block_size as usize);
-    let cache = Cache::new_lru_cache(rocksdb_config.block_cache_size as usize);
-    table_options.set_block_cache(&cache);
     let mut cfds = Vec::with_capacity(cfs.len());
     for cf_name in cfs {
+        let cf_config = rocksdb_config.get_column_family_config(cf_name);
+
+        let mut table_options = BlockBasedOptions::default();
+        table_options
+            .set_cache_index_and_filter_blocks(rocksdb_config.cache_index_and_filter_blocks);
+        table_options.set_block_size(rocksdb_config.block_size as usize);
+        table_options.set_block_cache(block_caches.get(cf_name));
+        if let Some(bloom_filter_bits_per_key) = cf_config.bloom_filter_bits_per_key {
+            table_options.set_bloom_filter(bloom_filter_bits_per_key as f64, false);
+        }
+
         let mut cf_opts = Options::default();
         cf_opts.set_compression_type(DBCompressionType::Lz4);
+        apply_column_family_config(&cf_config, &mut cf_opts);
         cf_opts.set_block_based_table_factory(&table_options);
         cf_opts_post_processor(cf_name, &mut cf_opts);
         cfds.push(ColumnFamilyDescriptor::new((*cf_name).to_string(), cf_opts));
@@ -149,6 +232,32 @@ where
     cfds
 }
 
+/// Applies the column family overrides that are not part of the block based table options.
+fn apply_column_family_config(cf_config: &RocksdbColumnFamilyConfig, cf_opts: &mut Options) {
+    if let Some(compression_per_level) = &cf_config.compression_per_level {
+        let compression_per_level: Vec<_> = compression_per_level
+            .iter()
+            .map(|compression_type| match compression_type {
+                RocksdbCompressionType::None => DBCompressionType::None,
+                RocksdbCompressionType::Snappy => DBCompressionType::Snappy,
+                RocksdbCompressionType::Zlib => DBCompressionType::Zlib,
+                RocksdbCompressionType::Bz2 => DBCompressionType::Bz2,
+                RocksdbCompressionType::Lz4 => DBCompressionType::Lz4,
+                RocksdbCompressionType::Lz4hc => DBCompressionType::Lz4hc,
+                RocksdbCompressionType::Zstd => DBCompressionType::Zstd,
+            })
+            .collect();
+        cf_opts.set_compression_per_level(&compression_per_level);
+    }
+    if let Some(compaction_style) = cf_config.compaction_style {
+        cf_opts.set_compaction_style(match compaction_style {
+            RocksdbCompactionStyle::Level => DBCompactionStyle::Level,
+            RocksdbCompactionStyle::Universal => DBCompactionStyle::Universal,
+            RocksdbCompactionStyle::Fifo => DBCompactionStyle::Fifo,
+        });
+    }
+}
+
 fn with_state_key_extractor_processor(cf_name: ColumnFamilyName, cf_opts: &mut Options) {
     if cf_name == STATE_VALUE_CF_NAME {
         let prefix_extractor =
@@ -157,61 +266,93 @@ fn with_state_key_extractor_processor(cf_name: ColumnFamilyName, cf_opts: &mut O
     }
 }
 
-pub(super) fn gen_event_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_event_cfds(
+    rocksdb_config: &RocksdbConfig,
+    block_caches: &BlockCaches,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = event_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, block_caches, cfs, |_, _| {})
 }
 
 pub(super) fn gen_transaction_accumulator_cfds(
     rocksdb_config: &RocksdbConfig,
+    block_caches: &BlockCaches,
 ) -> Vec<ColumnFamilyDescriptor> {
     let cfs = transaction_accumulator_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, block_caches, cfs, |_, _| {})
 }
 
 pub(super) fn gen_transaction_auxiliary_data_cfds(
     rocksdb_config: &RocksdbConfig,
+    block_caches: &BlockCaches,
 ) -> Vec<ColumnFamilyDescriptor> {
     let cfs = transaction_auxiliary_data_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, block_caches, cfs, |_, _| {})
 }
-pub(super) fn gen_transaction_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_transaction_cfds(
+    rocksdb_config: &RocksdbConfig,
+    block_caches: &BlockCaches,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = transaction_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, block_caches, cfs, |_, _| {})
 }
 
 pub(super) fn gen_transaction_info_cfds(
     rocksdb_config: &RocksdbConfig,
+    block_caches: &BlockCaches,
 ) -> Vec<ColumnFamilyDescriptor> {
     let cfs = transaction_info_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, block_caches, cfs, |_, _| {})
 }
 
-pub(super) fn gen_write_set_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_write_set_cfds(
+    rocksdb_config: &RocksdbConfig,
+    block_caches: &BlockCaches,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = write_set_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, block_caches, cfs, |_, _| {})
 }
 
 pub(super) fn gen_ledger_metadata_cfds(
     rocksdb_config: &RocksdbConfig,
+    block_caches: &BlockCaches,
 ) -> Vec<ColumnFamilyDescriptor> {
     let cfs = ledger_metadata_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, block_caches, cfs, |_, _| {})
 }
 
-pub(super) fn gen_ledger_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_ledger_cfds(
+    rocksdb_config: &RocksdbConfig,
+    block_caches: &BlockCaches,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = ledger_db_column_families();
-    gen_cfds(rocksdb_config, cfs, with_state_key_extractor_processor)
+    gen_cfds(
+        rocksdb_config,
+        block_caches,
+        cfs,
+        with_state_key_extractor_processor,
+    )
 }
 
-pub(super) fn gen_state_merkle_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_state_merkle_cfds(
+    rocksdb_config: &RocksdbConfig,
+    block_caches: &BlockCaches,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = state_merkle_db_column_families();
-    gen_cfds(rocksdb_config, cfs, |_, _| {})
+    gen_cfds(rocksdb_config, block_caches, cfs, |_, _| {})
 }
 
-pub(super) fn gen_state_kv_cfds(rocksdb_config: &RocksdbConfig) -> Vec<ColumnFamilyDescriptor> {
+pub(super) fn gen_state_kv_cfds(
+    rocksdb_config: &RocksdbConfig,
+    block_caches: &BlockCaches,
+) -> Vec<ColumnFamilyDescriptor> {
     let cfs = state_kv_db_column_families();
-    gen_cfds(rocksdb_config, cfs, with_state_key_extractor_processor)
+    gen_cfds(
+        rocksdb_config,
+        block_caches,
+        cfs,
+        with_state_key_extractor_processor,
+    )
 }
 
 fn state_key_extractor(state_value_raw_key: &[u8]) -> &[u8] {
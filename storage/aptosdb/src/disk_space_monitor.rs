@@ -0,0 +1,179 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::metrics::{DISK_AVAILABLE_SPACE_PERCENT, DISK_SPACE_STATUS};
+use aptos_config::config::DiskSpaceGuardrailsConfig;
+use aptos_logger::{error, info, warn};
+use aptos_storage_interface::disk_space_guardrails::{DiskSpaceStatus, DISK_SPACE_GUARDRAILS};
+use std::{
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+use sysinfo::{DiskExt, RefreshKind, System, SystemExt};
+
+/// Spawns a thread that periodically checks the available disk space of the
+/// disks holding the given database paths, and updates the global disk space
+/// guardrails accordingly. The monitor runs for the lifetime of the process.
+pub fn start_disk_space_monitor(config: DiskSpaceGuardrailsConfig, db_paths: Vec<PathBuf>) {
+    if !config.enable {
+        info!("The disk space monitor is disabled!");
+        return;
+    }
+
+    // Canonicalize the database paths (so they can be matched to mount points)
+    let db_paths: Vec<PathBuf> = db_paths
+        .into_iter()
+        .map(|db_path| db_path.canonicalize().unwrap_or(db_path))
+        .collect();
+
+    thread::Builder::new()
+        .name("disk-space-monitor".into())
+        .spawn(move || {
+            let mut system = System::new_with_specifics(RefreshKind::new().with_disks_list());
+            loop {
+                system.refresh_disks_list();
+                system.refresh_disks();
+                check_available_disk_space(&config, &system, &db_paths);
+                thread::sleep(Duration::from_millis(config.check_interval_ms));
+            }
+        })
+        .expect("Failed to spawn the disk space monitor thread!");
+}
+
+/// Checks the available disk space and updates the disk space guardrails
+fn check_available_disk_space(
+    config: &DiskSpaceGuardrailsConfig,
+    system: &System,
+    db_paths: &[PathBuf],
+) {
+    // Identify the lowest available space percentage across all disks
+    let disks: Vec<(&Path, u64, u64)> = system
+        .disks()
+        .iter()
+        .map(|disk| {
+            (
+                disk.mount_point(),
+                disk.available_space(),
+                disk.total_space(),
+            )
+        })
+        .collect();
+    let available_space_percent = match get_min_available_space_percent(&disks, db_paths) {
+        Some(available_space_percent) => available_space_percent,
+        None => {
+            warn!(
+                "Unable to identify the disks for the database paths: {:?}",
+                db_paths
+            );
+            return;
+        },
+    };
+    DISK_AVAILABLE_SPACE_PERCENT.set(available_space_percent as i64);
+
+    // Update the disk space status (and raise alerts if the status changes)
+    let status = get_disk_space_status(config, available_space_percent);
+    let previous_status = DISK_SPACE_GUARDRAILS.get_status();
+    if status != previous_status {
+        match status {
+            DiskSpaceStatus::Healthy => info!(
+                "The available disk space has recovered: {}%",
+                available_space_percent
+            ),
+            DiskSpaceStatus::Warning => warn!(
+                "The available disk space is running low: {}%! Warning threshold: {}%",
+                available_space_percent, config.warning_available_space_percent
+            ),
+            DiskSpaceStatus::Low => error!(
+                "The available disk space is low: {}%! Non-critical operations will be paused. Low threshold: {}%",
+                available_space_percent, config.low_available_space_percent
+            ),
+        }
+    }
+    DISK_SPACE_STATUS.set(status as i64);
+    DISK_SPACE_GUARDRAILS.update_status(status);
+}
+
+/// Returns the lowest available space percentage across the disks that hold
+/// the given paths. Each disk is given as a (mount point, available space,
+/// total space) tuple, and each path is matched to the disk with the longest
+/// mount point that prefixes the path.
+fn get_min_available_space_percent(
+    disks: &[(&Path, u64, u64)],
+    db_paths: &[PathBuf],
+) -> Option<u64> {
+    db_paths
+        .iter()
+        .filter_map(|db_path| {
+            disks
+                .iter()
+                .filter(|(mount_point, _, _)| db_path.starts_with(mount_point))
+                .max_by_key(|(mount_point, _, _)| mount_point.as_os_str().len())
+        })
+        .filter(|(_, _, total_space)| *total_space > 0)
+        .map(|(_, available_space, total_space)| {
+            (*available_space as u128 * 100 / *total_space as u128) as u64
+        })
+        .min()
+}
+
+/// Returns the disk space status for the given available space percentage
+fn get_disk_space_status(
+    config: &DiskSpaceGuardrailsConfig,
+    available_space_percent: u64,
+) -> DiskSpaceStatus {
+    if available_space_percent < config.low_available_space_percent {
+        DiskSpaceStatus::Low
+    } else if available_space_percent < config.warning_available_space_percent {
+        DiskSpaceStatus::Warning
+    } else {
+        DiskSpaceStatus::Healthy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_disk_space_status() {
+        let config = DiskSpaceGuardrailsConfig {
+            warning_available_space_percent: 20,
+            low_available_space_percent: 10,
+            ..Default::default()
+        };
+        assert_eq!(get_disk_space_status(&config, 50), DiskSpaceStatus::Healthy);
+        assert_eq!(get_disk_space_status(&config, 20), DiskSpaceStatus::Healthy);
+        assert_eq!(get_disk_space_status(&config, 19), DiskSpaceStatus::Warning);
+        assert_eq!(get_disk_space_status(&config, 10), DiskSpaceStatus::Warning);
+        assert_eq!(get_disk_space_status(&config, 9), DiskSpaceStatus::Low);
+    }
+
+    #[test]
+    fn test_get_min_available_space_percent() {
+        let disks = vec![
+            (Path::new("/"), 50, 100),
+            (Path::new("/data"), 30, 100),
+            (Path::new("/data/shards"), 5, 100),
+        ];
+
+        // Verify that paths are matched to the disk with the longest mount point
+        let db_paths = vec![PathBuf::from("/opt/aptos/db")];
+        assert_eq!(get_min_available_space_percent(&disks, &db_paths), Some(50));
+        let db_paths = vec![PathBuf::from("/data/db")];
+        assert_eq!(get_min_available_space_percent(&disks, &db_paths), Some(30));
+
+        // Verify that the lowest available space is returned
+        let db_paths = vec![
+            PathBuf::from("/data/db"),
+            PathBuf::from("/data/shards/0"),
+            PathBuf::from("/opt/aptos/db"),
+        ];
+        assert_eq!(get_min_available_space_percent(&disks, &db_paths), Some(5));
+
+        // Verify that no percentage is returned if no disks match
+        let disks = vec![(Path::new("/data"), 30, 100)];
+        let db_paths = vec![PathBuf::from("/opt/aptos/db")];
+        assert_eq!(get_min_available_space_percent(&disks, &db_paths), None);
+    }
+}
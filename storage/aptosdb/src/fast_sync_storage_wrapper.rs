@@ -41,16 +41,28 @@ impl FastSyncStorageWrapper {
     /// If the db is empty and configured to do fast sync, we return a FastSyncStorageWrapper
     /// Otherwise, we returns AptosDB directly and the FastSyncStorageWrapper is None
     pub fn initialize_dbs(config: &NodeConfig) -> Result<Either<AptosDB, Self>> {
-        let db_main = AptosDB::open(
+        let mut db_main = AptosDB::open(
             config.storage.get_dir_paths(),
             /*readonly=*/ false,
             config.storage.storage_pruner_config,
-            config.storage.rocksdb_configs,
+            config.storage.rocksdb_configs.clone(),
             config.storage.enable_indexer,
             config.storage.buffered_state_target_items,
             config.storage.max_num_nodes_per_lru_cache_shard,
         )
         .map_err(|err| anyhow!("fast sync DB failed to open {}", err))?;
+        if config.storage.tiered_storage_config.enable {
+            db_main.enable_tiered_storage(
+                config.storage.tiered_storage_config.clone(),
+                &config.storage.dir(),
+            )?;
+        }
+        if config.storage.consistency_scrubber_config.enable {
+            db_main.enable_consistency_scrubber(config.storage.consistency_scrubber_config)?;
+        }
+        if config.storage.enable_write_set_index {
+            db_main.enable_write_set_index()?;
+        }
 
         let mut db_dir = config.storage.dir();
         // when the db is empty and configured to do fast sync, we will create a second DB
@@ -71,7 +83,7 @@ impl FastSyncStorageWrapper {
                 StorageDirPaths::from_path(db_dir.as_path()),
                 /*readonly=*/ false,
                 config.storage.storage_pruner_config,
-                config.storage.rocksdb_configs,
+                config.storage.rocksdb_configs.clone(),
                 config.storage.enable_indexer,
                 config.storage.buffered_state_target_items,
                 config.storage.max_num_nodes_per_lru_cache_shard,
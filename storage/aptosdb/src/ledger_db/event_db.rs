@@ -183,6 +183,24 @@ impl EventDb {
         Ok(())
     }
 
+    /// Deletes the events in the range of version in [begin, end), but keeps all related indices
+    /// and the event accumulator (e.g., after the events have been migrated to cold storage).
+    pub(crate) fn delete_events(
+        &self,
+        start: Version,
+        end: Version,
+        db_batch: &SchemaBatch,
+    ) -> Result<()> {
+        let mut current_version = start;
+        for events in self.get_events_by_version_iter(start, (end - start) as usize)? {
+            for idx in 0..events?.len() {
+                db_batch.delete::<EventSchema>(&(current_version, idx as u64))?;
+            }
+            current_version += 1;
+        }
+        Ok(())
+    }
+
     /// Deletes a set of events in the range of version in [begin, end), and all related indices.
     pub(crate) fn prune_events(
         &self,
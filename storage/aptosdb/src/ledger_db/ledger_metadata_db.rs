@@ -5,18 +5,23 @@ use crate::{
     schema::{
         block_by_version::BlockByVersionSchema,
         block_info::BlockInfoSchema,
+        cold_segment::{ColdSegmentInfo, ColdSegmentSchema},
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         epoch_by_version::EpochByVersionSchema,
+        epoch_ledger_stats::EpochLedgerStatsSchema,
         ledger_info::LedgerInfoSchema,
         version_data::VersionDataSchema,
     },
     utils::{get_progress, iterators::EpochEndingLedgerInfoIter},
 };
 use anyhow::anyhow;
+use aptos_infallible::Mutex;
 use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
 use aptos_storage_interface::{
     block_info::{BlockInfo, BlockInfoV0},
-    db_ensure as ensure, AptosDbError, Result,
+    db_ensure as ensure,
+    epoch_ledger_stats::EpochLedgerStats,
+    AptosDbError, Result,
 };
 use aptos_types::{
     account_config::NewBlockEvent, block_info::BlockHeight, contract_event::ContractEvent,
@@ -24,7 +29,12 @@ use aptos_types::{
     state_store::state_storage_usage::StateStorageUsage, transaction::Version,
 };
 use arc_swap::ArcSwap;
-use std::{ops::Deref, path::Path, sync::Arc};
+use std::{
+    ops::Deref,
+    path::Path,
+    sync::{Arc, Condvar},
+    time::{Duration, Instant},
+};
 
 fn get_latest_ledger_info_in_db_impl(db: &DB) -> Result<Option<LedgerInfoWithSignatures>> {
     let mut iter = db.iter::<LedgerInfoSchema>(ReadOptions::default())?;
@@ -40,6 +50,10 @@ pub(crate) struct LedgerMetadataDb {
     /// cache it in memory in order to avoid reading DB and deserializing the object frequently. It
     /// should be updated every time new ledger info and signatures are persisted.
     latest_ledger_info: ArcSwap<Option<LedgerInfoWithSignatures>>,
+
+    /// Used to wake up the readers waiting for new ledger infos (i.e., new commits).
+    latest_ledger_info_lock: Mutex<()>,
+    latest_ledger_info_updated: Condvar,
 }
 
 impl LedgerMetadataDb {
@@ -50,6 +64,8 @@ impl LedgerMetadataDb {
         Self {
             db,
             latest_ledger_info: ArcSwap::from(Arc::new(ledger_info)),
+            latest_ledger_info_lock: Mutex::new(()),
+            latest_ledger_info_updated: Condvar::new(),
         }
     }
 
@@ -57,6 +73,16 @@ impl LedgerMetadataDb {
         self.db.create_checkpoint(path)
     }
 
+    /// Catches up with the primary instance (only valid for dbs opened as secondary), and
+    /// refreshes the cached latest ledger info.
+    pub(super) fn try_catch_up_with_primary(&self) -> Result<()> {
+        self.db.try_catch_up_with_primary()?;
+        if let Some(ledger_info) = get_latest_ledger_info_in_db_impl(&self.db)? {
+            self.set_latest_ledger_info(ledger_info);
+        }
+        Ok(())
+    }
+
     pub(super) fn write_pruner_progress(&self, version: Version) -> Result<()> {
         self.db.put::<DbMetadataSchema>(
             &DbMetadataKey::LedgerPrunerProgress,
@@ -179,10 +205,39 @@ impl LedgerMetadataDb {
         Ok(li)
     }
 
+    /// Blocks until the latest ledger info is at a version higher than `known_version` (or
+    /// until the timeout elapses), and returns the latest ledger info. This allows readers to
+    /// be notified of new commits, instead of polling.
+    pub(crate) fn wait_for_latest_ledger_info(
+        &self,
+        known_version: Option<Version>,
+        timeout: Duration,
+    ) -> Option<LedgerInfoWithSignatures> {
+        let deadline = Instant::now() + timeout;
+        let mut guard = self.latest_ledger_info_lock.lock();
+        loop {
+            let ledger_info = self.get_latest_ledger_info_option();
+            let latest_version = ledger_info.as_ref().map(|li| li.ledger_info().version());
+            let now = Instant::now();
+            if latest_version > known_version || now >= deadline {
+                return ledger_info;
+            }
+            guard = self
+                .latest_ledger_info_updated
+                .wait_timeout(guard, deadline - now)
+                .expect("Cannot currently handle a poisoned lock")
+                .0;
+        }
+    }
+
     /// Stores the latest ledger info in memory.
     pub(crate) fn set_latest_ledger_info(&self, ledger_info_with_sigs: LedgerInfoWithSignatures) {
         self.latest_ledger_info
             .store(Arc::new(Some(ledger_info_with_sigs)));
+
+        // Wake up any readers waiting for the new ledger info
+        let _guard = self.latest_ledger_info_lock.lock();
+        self.latest_ledger_info_updated.notify_all();
     }
 
     /// Writes `ledger_info_with_sigs` to `batch`.
@@ -362,3 +417,113 @@ impl LedgerMetadataDb {
         }
     }
 }
+
+/// Epoch ledger stats APIs.
+impl LedgerMetadataDb {
+    /// Returns the ledger stats of the given epoch, or None if they don't exist in database.
+    pub(crate) fn get_epoch_ledger_stats(&self, epoch: u64) -> Result<Option<EpochLedgerStats>> {
+        self.db.get::<EpochLedgerStatsSchema>(&epoch)
+    }
+
+    /// Writes the ledger stats of an epoch to `batch`.
+    pub(crate) fn put_epoch_ledger_stats(
+        epoch_ledger_stats: &EpochLedgerStats,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        batch.put::<EpochLedgerStatsSchema>(&epoch_ledger_stats.epoch, epoch_ledger_stats)
+    }
+}
+
+/// Cold storage APIs.
+impl LedgerMetadataDb {
+    /// Returns the first version that has not been migrated to cold storage.
+    pub(crate) fn get_cold_storage_migration_progress(&self) -> Result<Version> {
+        Ok(get_progress(&self.db, &DbMetadataKey::ColdStorageMigrationProgress)?.unwrap_or(0))
+    }
+
+    /// Returns the first version and info of the cold segment holding the given version, or None
+    /// if the version has not been migrated to cold storage.
+    pub(crate) fn get_cold_segment_info(
+        &self,
+        version: Version,
+    ) -> Result<Option<(Version, ColdSegmentInfo)>> {
+        let mut iter = self.db.iter::<ColdSegmentSchema>(ReadOptions::default())?;
+        iter.seek_for_prev(&version)?;
+        Ok(iter
+            .next()
+            .transpose()?
+            .filter(|(first_version, segment_info)| {
+                version < segment_info.end_version(*first_version)
+            }))
+    }
+
+    /// Returns the first version and info of the last cold segment, or None if nothing has been
+    /// migrated to cold storage.
+    pub(crate) fn get_last_cold_segment_info(&self) -> Result<Option<(Version, ColdSegmentInfo)>> {
+        let mut iter = self.db.iter::<ColdSegmentSchema>(ReadOptions::default())?;
+        iter.seek_to_last();
+        Ok(iter.next().transpose()?)
+    }
+
+    /// Writes the given cold storage migration progress to `batch` (e.g., when skipping versions
+    /// that have already been pruned, and can't be migrated).
+    pub(crate) fn put_cold_storage_migration_progress(
+        version: Version,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        batch.put::<DbMetadataSchema>(
+            &DbMetadataKey::ColdStorageMigrationProgress,
+            &DbMetadataValue::Version(version),
+        )
+    }
+
+    /// Writes the info of a new cold segment (and the new migration progress) to `batch`.
+    pub(crate) fn put_cold_segment_info(
+        first_version: Version,
+        segment_info: &ColdSegmentInfo,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        batch.put::<ColdSegmentSchema>(&first_version, segment_info)?;
+        batch.put::<DbMetadataSchema>(
+            &DbMetadataKey::ColdStorageMigrationProgress,
+            &DbMetadataValue::Version(segment_info.end_version(first_version)),
+        )
+    }
+}
+
+/// Consistency scrubber APIs.
+impl LedgerMetadataDb {
+    /// Returns the first version of the transaction accumulator that has not been scrubbed (in
+    /// the current pass).
+    pub(crate) fn get_consistency_scrubber_ledger_progress(&self) -> Result<Version> {
+        Ok(get_progress(&self.db, &DbMetadataKey::ConsistencyScrubberLedgerProgress)?.unwrap_or(0))
+    }
+
+    pub(crate) fn write_consistency_scrubber_ledger_progress(
+        &self,
+        version: Version,
+    ) -> Result<()> {
+        self.db.put::<DbMetadataSchema>(
+            &DbMetadataKey::ConsistencyScrubberLedgerProgress,
+            &DbMetadataValue::Version(version),
+        )
+    }
+
+    /// Returns the version of the last state snapshot that was fully scrubbed (if any).
+    pub(crate) fn get_consistency_scrubber_state_merkle_progress(&self) -> Result<Option<Version>> {
+        get_progress(
+            &self.db,
+            &DbMetadataKey::ConsistencyScrubberStateMerkleProgress,
+        )
+    }
+
+    pub(crate) fn write_consistency_scrubber_state_merkle_progress(
+        &self,
+        version: Version,
+    ) -> Result<()> {
+        self.db.put::<DbMetadataSchema>(
+            &DbMetadataKey::ConsistencyScrubberStateMerkleProgress,
+            &DbMetadataValue::Version(version),
+        )
+    }
+}
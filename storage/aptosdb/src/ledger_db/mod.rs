@@ -12,7 +12,7 @@ use crate::{
         ledger_db_column_families, ledger_metadata_db_column_families,
         transaction_accumulator_db_column_families, transaction_auxiliary_data_db_column_families,
         transaction_db_column_families, transaction_info_db_column_families,
-        write_set_db_column_families,
+        write_set_db_column_families, BlockCaches, DbOpenMode,
     },
     event_store::EventStore,
     ledger_db::{
@@ -109,9 +109,10 @@ impl LedgerDb {
     pub(crate) fn new<P: AsRef<Path>>(
         db_root_path: P,
         rocksdb_configs: RocksdbConfigs,
-        readonly: bool,
+        open_mode: &DbOpenMode,
     ) -> Result<Self> {
         let sharding = rocksdb_configs.enable_storage_sharding;
+        let block_caches = BlockCaches::new(&rocksdb_configs.ledger_db_config);
         let ledger_metadata_db_path = Self::metadata_db_path(db_root_path.as_ref(), sharding);
         let ledger_metadata_db = Arc::new(Self::open_rocksdb(
             ledger_metadata_db_path.clone(),
@@ -121,7 +122,8 @@ impl LedgerDb {
                 LEDGER_DB_NAME
             },
             &rocksdb_configs.ledger_db_config,
-            readonly,
+            &block_caches,
+            open_mode,
         )?);
 
         info!(
@@ -156,7 +158,8 @@ impl LedgerDb {
             ledger_db_folder.join(EVENT_DB_NAME),
             EVENT_DB_NAME,
             &rocksdb_configs.ledger_db_config,
-            readonly,
+            &block_caches,
+            open_mode,
         )?);
         let event_db = EventDb::new(event_db_raw.clone(), EventStore::new(event_db_raw));
 
@@ -165,7 +168,8 @@ impl LedgerDb {
                 ledger_db_folder.join(TRANSACTION_ACCUMULATOR_DB_NAME),
                 TRANSACTION_ACCUMULATOR_DB_NAME,
                 &rocksdb_configs.ledger_db_config,
-                readonly,
+                &block_caches,
+                open_mode,
             )?));
 
         let transaction_auxiliary_data_db =
@@ -173,27 +177,31 @@ impl LedgerDb {
                 ledger_db_folder.join(TRANSACTION_AUXILIARY_DATA_DB_NAME),
                 TRANSACTION_AUXILIARY_DATA_DB_NAME,
                 &rocksdb_configs.ledger_db_config,
-                readonly,
+                &block_caches,
+                open_mode,
             )?));
         let transaction_db = TransactionDb::new(Arc::new(Self::open_rocksdb(
             ledger_db_folder.join(TRANSACTION_DB_NAME),
             TRANSACTION_DB_NAME,
             &rocksdb_configs.ledger_db_config,
-            readonly,
+            &block_caches,
+            open_mode,
         )?));
 
         let transaction_info_db = TransactionInfoDb::new(Arc::new(Self::open_rocksdb(
             ledger_db_folder.join(TRANSACTION_INFO_DB_NAME),
             TRANSACTION_INFO_DB_NAME,
             &rocksdb_configs.ledger_db_config,
-            readonly,
+            &block_caches,
+            open_mode,
         )?));
 
         let write_set_db = WriteSetDb::new(Arc::new(Self::open_rocksdb(
             ledger_db_folder.join(WRITE_SET_DB_NAME),
             WRITE_SET_DB_NAME,
             &rocksdb_configs.ledger_db_config,
-            readonly,
+            &block_caches,
+            open_mode,
         )?));
 
         // TODO(grao): Handle data inconsistency.
@@ -218,7 +226,18 @@ impl LedgerDb {
             enable_storage_sharding: sharding,
             ..Default::default()
         };
-        let ledger_db = Self::new(db_root_path, rocksdb_configs, /*readonly=*/ false)?;
+        let ledger_db = Self::new(db_root_path, rocksdb_configs, &DbOpenMode::ReadWrite)?;
+        ledger_db.create_checkpoint_from_db(cp_root_path, sharding)
+    }
+
+    /// Creates a checkpoint of the (open) ledger db. Note: the metadata db is
+    /// always checkpointed first, so that the overall commit progress in the
+    /// checkpoint never exceeds the data in the other (sub) dbs.
+    pub(crate) fn create_checkpoint_from_db(
+        &self,
+        cp_root_path: impl AsRef<Path>,
+        sharding: bool,
+    ) -> Result<()> {
         let cp_ledger_db_folder = cp_root_path.as_ref().join(LEDGER_DB_FOLDER_NAME);
 
         info!(
@@ -231,34 +250,48 @@ impl LedgerDb {
             std::fs::create_dir_all(&cp_ledger_db_folder).unwrap_or(());
         }
 
-        ledger_db
-            .metadata_db()
+        self.metadata_db()
             .create_checkpoint(Self::metadata_db_path(cp_root_path.as_ref(), sharding))?;
 
         if sharding {
-            ledger_db
-                .event_db()
+            self.event_db()
                 .create_checkpoint(cp_ledger_db_folder.join(EVENT_DB_NAME))?;
-            ledger_db
-                .transaction_accumulator_db()
+            self.transaction_accumulator_db()
                 .create_checkpoint(cp_ledger_db_folder.join(TRANSACTION_ACCUMULATOR_DB_NAME))?;
-            ledger_db
-                .transaction_auxiliary_data_db()
+            self.transaction_auxiliary_data_db()
                 .create_checkpoint(cp_ledger_db_folder.join(TRANSACTION_AUXILIARY_DATA_DB_NAME))?;
-            ledger_db
-                .transaction_db()
+            self.transaction_db()
                 .create_checkpoint(cp_ledger_db_folder.join(TRANSACTION_DB_NAME))?;
-            ledger_db
-                .transaction_info_db()
+            self.transaction_info_db()
                 .create_checkpoint(cp_ledger_db_folder.join(TRANSACTION_INFO_DB_NAME))?;
-            ledger_db
-                .write_set_db()
+            self.write_set_db()
                 .create_checkpoint(cp_ledger_db_folder.join(WRITE_SET_DB_NAME))?;
         }
 
         Ok(())
     }
 
+    /// Catches up the metadata db with the primary instance (only valid for dbs opened as
+    /// secondary). Note: the primary commits the metadata (i.e., the overall commit progress and
+    /// the latest ledger info) last, so the metadata db must be caught up before the other (sub)
+    /// dbs, so that the latest ledger info never exceeds the data visible in the sub dbs.
+    pub(crate) fn try_catch_up_metadata_db_with_primary(&self) -> Result<()> {
+        self.metadata_db().try_catch_up_with_primary()
+    }
+
+    /// Catches up the (sub) dbs, other than the metadata db, with the primary instance (only
+    /// valid for dbs opened as secondary). See `try_catch_up_metadata_db_with_primary`.
+    pub(crate) fn try_catch_up_sub_dbs_with_primary(&self) -> Result<()> {
+        self.event_db_raw().try_catch_up_with_primary()?;
+        self.transaction_accumulator_db_raw()
+            .try_catch_up_with_primary()?;
+        self.transaction_auxiliary_data_db_raw()
+            .try_catch_up_with_primary()?;
+        self.transaction_db_raw().try_catch_up_with_primary()?;
+        self.transaction_info_db_raw().try_catch_up_with_primary()?;
+        self.write_set_db_raw().try_catch_up_with_primary()
+    }
+
     // Only expect to be used by fast sync when it is finished.
     pub(crate) fn write_pruner_progress(&self, version: Version) -> Result<()> {
         info!("Fast sync is done, writing pruner progress {version} for all ledger sub pruners.");
@@ -338,22 +371,29 @@ impl LedgerDb {
         path: PathBuf,
         name: &str,
         db_config: &RocksdbConfig,
-        readonly: bool,
+        block_caches: &BlockCaches,
+        open_mode: &DbOpenMode,
     ) -> Result<DB> {
-        let db = if readonly {
-            DB::open_cf_readonly(
+        let db = match open_mode {
+            DbOpenMode::ReadWrite => DB::open_cf(
+                &gen_rocksdb_options(db_config, false),
+                path.clone(),
+                name,
+                Self::gen_cfds_by_name(db_config, block_caches, name),
+            )?,
+            DbOpenMode::ReadOnly => DB::open_cf_readonly(
                 &gen_rocksdb_options(db_config, true),
                 path.clone(),
                 name,
                 Self::get_column_families_by_name(name),
-            )?
-        } else {
-            DB::open_cf(
-                &gen_rocksdb_options(db_config, false),
+            )?,
+            DbOpenMode::Secondary(secondary_root_path) => DB::open_cf_as_secondary(
+                &gen_rocksdb_options(db_config, true),
                 path.clone(),
+                secondary_root_path.join(name),
                 name,
-                Self::gen_cfds_by_name(db_config, name),
-            )?
+                Self::get_column_families_by_name(name),
+            )?,
         };
 
         info!("Opened {name} at {path:?}!");
@@ -375,16 +415,24 @@ impl LedgerDb {
         }
     }
 
-    fn gen_cfds_by_name(db_config: &RocksdbConfig, name: &str) -> Vec<ColumnFamilyDescriptor> {
+    fn gen_cfds_by_name(
+        db_config: &RocksdbConfig,
+        block_caches: &BlockCaches,
+        name: &str,
+    ) -> Vec<ColumnFamilyDescriptor> {
         match name {
-            LEDGER_DB_NAME => gen_ledger_cfds(db_config),
-            LEDGER_METADATA_DB_NAME => gen_ledger_metadata_cfds(db_config),
-            EVENT_DB_NAME => gen_event_cfds(db_config),
-            TRANSACTION_ACCUMULATOR_DB_NAME => gen_transaction_accumulator_cfds(db_config),
-            TRANSACTION_AUXILIARY_DATA_DB_NAME => gen_transaction_auxiliary_data_cfds(db_config),
-            TRANSACTION_DB_NAME => gen_transaction_cfds(db_config),
-            TRANSACTION_INFO_DB_NAME => gen_transaction_info_cfds(db_config),
-            WRITE_SET_DB_NAME => gen_write_set_cfds(db_config),
+            LEDGER_DB_NAME => gen_ledger_cfds(db_config, block_caches),
+            LEDGER_METADATA_DB_NAME => gen_ledger_metadata_cfds(db_config, block_caches),
+            EVENT_DB_NAME => gen_event_cfds(db_config, block_caches),
+            TRANSACTION_ACCUMULATOR_DB_NAME => {
+                gen_transaction_accumulator_cfds(db_config, block_caches)
+            },
+            TRANSACTION_AUXILIARY_DATA_DB_NAME => {
+                gen_transaction_auxiliary_data_cfds(db_config, block_caches)
+            },
+            TRANSACTION_DB_NAME => gen_transaction_cfds(db_config, block_caches),
+            TRANSACTION_INFO_DB_NAME => gen_transaction_info_cfds(db_config, block_caches),
+            WRITE_SET_DB_NAME => gen_write_set_cfds(db_config, block_caches),
             _ => unreachable!(),
         }
     }
@@ -6,27 +6,48 @@ use crate::{
     schema::{
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         write_set::WriteSetSchema,
+        write_set_by_state_key::WriteSetByStateKeySchema,
+        write_set_index_by_version::WriteSetIndexByVersionSchema,
     },
-    utils::iterators::ExpectContinuousVersions,
+    utils::iterators::{ExpectContinuousVersions, PrefixedWriteSetVersionIter},
 };
 use aptos_experimental_runtimes::thread_manager::optimal_min_len;
 use aptos_schemadb::{ReadOptions, SchemaBatch, DB};
 use aptos_storage_interface::{db_ensure as ensure, AptosDbError, Result};
 use aptos_types::{
+    state_store::{state_key::StateKey, state_key_prefix::StateKeyPrefix},
     transaction::{TransactionToCommit, Version},
     write_set::WriteSet,
 };
 use rayon::prelude::*;
-use std::{path::Path, sync::Arc};
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
 
 #[derive(Debug)]
 pub(crate) struct WriteSetDb {
     db: Arc<DB>,
+    /// Whether the write sets are indexed by the state keys they write to
+    enable_index: AtomicBool,
+    /// Whether the index start version is persisted (i.e., the index has no gaps)
+    index_start_version_persisted: AtomicBool,
 }
 
 impl WriteSetDb {
     pub(super) fn new(db: Arc<DB>) -> Self {
-        Self { db }
+        let index_start_version_persisted = !matches!(
+            db.get::<DbMetadataSchema>(&DbMetadataKey::WriteSetIndexStartVersion),
+            Ok(None)
+        );
+        Self {
+            db,
+            enable_index: AtomicBool::new(false),
+            index_start_version_persisted: AtomicBool::new(index_start_version_persisted),
+        }
     }
 
     pub(super) fn create_checkpoint(&self, path: impl AsRef<Path>) -> Result<()> {
@@ -47,6 +68,64 @@ impl WriteSetDb {
     pub(crate) fn write_schemas(&self, batch: SchemaBatch) -> Result<()> {
         self.db.write_schemas(batch)
     }
+
+    /// Indexes the write sets committed from `next_version` on by the state keys they write
+    /// to. If the index has no gaps (i.e., it was enabled when the previous write sets were
+    /// committed), its existing start version is kept.
+    pub(crate) fn enable_index(&self, next_version: Version) -> Result<()> {
+        if self.get_index_start_version()?.is_none() {
+            self.db.put::<DbMetadataSchema>(
+                &DbMetadataKey::WriteSetIndexStartVersion,
+                &DbMetadataValue::Version(next_version),
+            )?;
+            self.index_start_version_persisted
+                .store(true, Ordering::Relaxed);
+        }
+        self.enable_index.store(true, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub(crate) fn is_index_enabled(&self) -> bool {
+        self.enable_index.load(Ordering::Relaxed)
+    }
+
+    /// Returns the version from which on all write sets are indexed (if any).
+    pub(crate) fn get_index_start_version(&self) -> Result<Option<Version>> {
+        Ok(self
+            .db
+            .get::<DbMetadataSchema>(&DbMetadataKey::WriteSetIndexStartVersion)?
+            .map(|value| value.expect_version()))
+    }
+
+    /// Moves the index start version to `version` (if it's currently earlier), e.g., once the
+    /// indices of the write sets before `version` are deleted.
+    pub(crate) fn advance_index_start_version(
+        &self,
+        version: Version,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        if let Some(index_start_version) = self.get_index_start_version()? {
+            if index_start_version < version {
+                batch.put::<DbMetadataSchema>(
+                    &DbMetadataKey::WriteSetIndexStartVersion,
+                    &DbMetadataValue::Version(version),
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Deletes the index start version (if it's persisted), as the write set being committed is
+    /// not indexed and the index would otherwise have a gap.
+    fn invalidate_index(&self, batch: &SchemaBatch) -> Result<()> {
+        if self
+            .index_start_version_persisted
+            .swap(false, Ordering::Relaxed)
+        {
+            batch.delete::<DbMetadataSchema>(&DbMetadataKey::WriteSetIndexStartVersion)?;
+        }
+        Ok(())
+    }
 }
 
 impl WriteSetDb {
@@ -109,11 +188,56 @@ impl WriteSetDb {
         Ok(ret)
     }
 
+    /// Returns an iterator that yields the versions in `[start_version, end_version)` at which
+    /// `state_key` was written, in increasing order.
+    pub(crate) fn get_write_set_version_iter(
+        &self,
+        state_key: &StateKey,
+        start_version: Version,
+        end_version: Version,
+    ) -> Result<impl Iterator<Item = Result<Version>> + '_> {
+        let mut iter = self
+            .db
+            .iter::<WriteSetByStateKeySchema>(ReadOptions::default())?;
+        iter.seek(&(state_key.clone(), start_version))?;
+
+        let state_key = state_key.clone();
+        Ok(iter.map_while(move |item| match item {
+            Ok(((key, version), ())) => {
+                (key == state_key && version < end_version).then_some(Ok(version))
+            },
+            Err(error) => Some(Err(error)),
+        }))
+    }
+
+    /// Returns an iterator that yields the (state key, version) pairs of all the writes to state
+    /// keys with `key_prefix` in `[start_version, end_version)`, ordered by state key and then by
+    /// version.
+    pub(crate) fn get_prefixed_write_set_version_iter(
+        &self,
+        key_prefix: StateKeyPrefix,
+        start_version: Version,
+        end_version: Version,
+    ) -> Result<PrefixedWriteSetVersionIter> {
+        let mut read_opts = ReadOptions::default();
+        read_opts.set_total_order_seek(true);
+        let mut iter = self.db.iter::<WriteSetByStateKeySchema>(read_opts)?;
+        iter.seek(&&key_prefix)?;
+
+        Ok(PrefixedWriteSetVersionIter::new(
+            iter,
+            key_prefix,
+            start_version,
+            end_version,
+        ))
+    }
+
     /// Commits write sets starting from `first_version` to the database.
     pub(crate) fn commit_write_sets(
         &self,
         txns_to_commit: &[TransactionToCommit],
         first_version: Version,
+        skip_index: bool,
     ) -> Result<()> {
         let _timer = OTHER_TIMERS_SECONDS
             .with_label_values(&["commit_write_sets"])
@@ -125,7 +249,13 @@ impl WriteSetDb {
             .with_min_len(optimal_min_len(num_txns, 128))
             .enumerate()
             .try_for_each(|(i, txn_to_commit)| -> Result<()> {
-                Self::put_write_set(first_version + i as u64, txn_to_commit.write_set(), &batch)?;
+                let version = first_version + i as u64;
+                Self::put_write_set(version, txn_to_commit.write_set(), &batch)?;
+                if skip_index {
+                    self.invalidate_index(&batch)?;
+                } else {
+                    self.put_write_set_index(version, txn_to_commit.write_set(), &batch)?;
+                }
 
                 Ok(())
             })?;
@@ -144,6 +274,46 @@ impl WriteSetDb {
         batch.put::<WriteSetSchema>(&version, write_set)
     }
 
+    /// Indexes the write set at `version` by the state keys it writes to (if the index is
+    /// enabled).
+    pub(crate) fn put_write_set_index(
+        &self,
+        version: Version,
+        write_set: &WriteSet,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        if !self.is_index_enabled() {
+            return self.invalidate_index(batch);
+        }
+        write_set.iter().try_for_each(|(state_key, _write_op)| {
+            batch.put::<WriteSetByStateKeySchema>(&(state_key.clone(), version), &())?;
+            batch.put::<WriteSetIndexByVersionSchema>(&(version, state_key.clone()), &())
+        })
+    }
+
+    /// Deletes the state key indices of the write sets between a range of version in [begin, end).
+    /// The indices are found via the reverse index, so the write sets are not read.
+    pub(crate) fn prune_write_set_indices(
+        &self,
+        begin: Version,
+        end: Version,
+        db_batch: &SchemaBatch,
+    ) -> Result<()> {
+        let mut iter = self
+            .db
+            .iter::<WriteSetIndexByVersionSchema>(ReadOptions::default())?;
+        iter.seek(&begin)?;
+        for item in iter {
+            let ((version, state_key), ()) = item?;
+            if version >= end {
+                break;
+            }
+            db_batch.delete::<WriteSetByStateKeySchema>(&(state_key.clone(), version))?;
+            db_batch.delete::<WriteSetIndexByVersionSchema>(&(version, state_key))?;
+        }
+        Ok(())
+    }
+
     /// Deletes the write sets between a range of version in [begin, end).
     pub(crate) fn prune(begin: Version, end: Version, db_batch: &SchemaBatch) -> Result<()> {
         for version in begin..end {
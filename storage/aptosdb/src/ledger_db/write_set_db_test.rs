@@ -6,10 +6,15 @@ use aptos_schemadb::SchemaBatch;
 use aptos_storage_interface::Result;
 use aptos_temppath::TempPath;
 use aptos_types::{
+    state_store::{
+        state_key::{StateKey, StateKeyInner},
+        state_key_prefix::StateKeyPrefix,
+    },
     transaction::{TransactionToCommit, Version},
-    write_set::WriteSet,
+    write_set::{WriteOp, WriteSet, WriteSetMut},
 };
 use proptest::{collection::vec, prelude::*};
+use std::collections::BTreeMap;
 
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10))]
@@ -87,6 +92,88 @@ proptest! {
         prop_assert!(write_set_db.get_write_set_iter(10, usize::max_value()).is_err());
     }
 
+    #[test]
+    fn test_get_write_set_version_iter(
+        write_sets in vec(
+            any::<WriteSet>(),
+            1..10
+        ),
+    ) {
+        let tmp_dir = TempPath::new();
+        let db = AptosDB::new_for_test(&tmp_dir);
+        let write_set_db  = db.ledger_db.write_set_db();
+        init_db(&write_sets, write_set_db);
+
+        let mut versions_by_key: BTreeMap<StateKey, Vec<Version>> = BTreeMap::new();
+        for (version, write_set) in write_sets.iter().enumerate() {
+            for (state_key, _write_op) in write_set.iter() {
+                versions_by_key
+                    .entry(state_key.clone())
+                    .or_default()
+                    .push(version as Version);
+            }
+        }
+
+        let num_write_sets = write_sets.len() as Version;
+        for (state_key, versions) in &versions_by_key {
+            let actual = write_set_db
+                .get_write_set_version_iter(state_key, 0, num_write_sets)
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            prop_assert_eq!(&actual, versions);
+
+            let actual = write_set_db
+                .get_write_set_version_iter(state_key, 1, num_write_sets - 1)
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            let expected = versions
+                .iter()
+                .copied()
+                .filter(|version| *version >= 1 && *version < num_write_sets - 1)
+                .collect::<Vec<_>>();
+            prop_assert_eq!(actual, expected);
+        }
+
+        for state_key in versions_by_key.keys() {
+            let address = match state_key.inner() {
+                StateKeyInner::AccessPath(access_path) => access_path.address,
+                _ => unreachable!("Generated write sets only contain access paths."),
+            };
+            let key_prefix = StateKeyPrefix::from(address);
+            let mut actual = write_set_db
+                .get_prefixed_write_set_version_iter(key_prefix.clone(), 0, num_write_sets)
+                .unwrap()
+                .collect::<Result<Vec<_>>>()
+                .unwrap();
+            actual.sort();
+            let expected = versions_by_key
+                .iter()
+                .filter(|(key, _)| key_prefix.is_prefix(key).unwrap())
+                .flat_map(|(key, versions)| {
+                    versions.iter().map(move |version| (key.clone(), *version))
+                })
+                .collect::<Vec<_>>();
+            prop_assert_eq!(actual, expected);
+        }
+
+        let batch = SchemaBatch::new();
+        write_set_db
+            .prune_write_set_indices(0, num_write_sets, &batch)
+            .unwrap();
+        write_set_db.write_schemas(batch).unwrap();
+        for state_key in versions_by_key.keys() {
+            prop_assert_eq!(
+                write_set_db
+                    .get_write_set_version_iter(state_key, 0, num_write_sets)
+                    .unwrap()
+                    .count(),
+                0
+            );
+        }
+    }
+
     #[test]
     fn test_prune(
         write_sets in vec(
@@ -109,9 +196,67 @@ proptest! {
     }
 }
 
+#[test]
+fn test_write_set_index_start_version() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let write_set_db = db.ledger_db.write_set_db();
+    let state_key = StateKey::raw(vec![1, 2, 3]);
+    let write_sets = vec![
+        WriteSetMut::new(vec![(state_key.clone(), WriteOp::legacy_deletion())])
+            .freeze()
+            .unwrap();
+        2
+    ];
+    let get_versions = |start_version| {
+        write_set_db
+            .get_write_set_version_iter(&state_key, start_version, Version::MAX)
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap()
+    };
+
+    // Write sets committed before the index is enabled are not indexed
+    commit_write_sets(&write_sets, 0, false, write_set_db);
+    assert!(!write_set_db.is_index_enabled());
+    assert_eq!(write_set_db.get_index_start_version().unwrap(), None);
+
+    // Enable the index and verify that only the new write sets are indexed
+    write_set_db.enable_index(2).unwrap();
+    commit_write_sets(&write_sets, 2, false, write_set_db);
+    assert_eq!(write_set_db.get_index_start_version().unwrap(), Some(2));
+    assert_eq!(get_versions(0), vec![2, 3]);
+
+    // Prune the index (as done by the cold storage migration) and verify the start version
+    let batch = SchemaBatch::new();
+    write_set_db.prune_write_set_indices(0, 3, &batch).unwrap();
+    write_set_db.advance_index_start_version(3, &batch).unwrap();
+    write_set_db.write_schemas(batch).unwrap();
+    assert_eq!(write_set_db.get_index_start_version().unwrap(), Some(3));
+    assert_eq!(get_versions(0), vec![3]);
+
+    // Commit write sets without indexing them and verify the index is no longer continuous
+    commit_write_sets(&write_sets, 4, true, write_set_db);
+    assert_eq!(write_set_db.get_index_start_version().unwrap(), None);
+
+    // Re-enable the index and verify it restarts at the next version
+    write_set_db.enable_index(6).unwrap();
+    assert_eq!(write_set_db.get_index_start_version().unwrap(), Some(6));
+}
+
 fn init_db(write_sets: &[WriteSet], write_set_db: &WriteSetDb) {
     assert!(write_set_db.get_write_set(0).is_err());
 
+    write_set_db.enable_index(0).unwrap();
+    commit_write_sets(write_sets, 0, false, write_set_db);
+}
+
+fn commit_write_sets(
+    write_sets: &[WriteSet],
+    first_version: Version,
+    skip_index: bool,
+    write_set_db: &WriteSetDb,
+) {
     write_set_db
         .commit_write_sets(
             &write_sets
@@ -121,7 +266,8 @@ fn init_db(write_sets: &[WriteSet], write_set_db: &WriteSetDb) {
                     ..TransactionToCommit::dummy()
                 })
                 .collect::<Vec<_>>(),
-            0,
+            first_version,
+            skip_index,
         )
         .unwrap();
 }
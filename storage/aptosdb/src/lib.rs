@@ -19,17 +19,22 @@ pub mod fake_aptosdb;
 pub mod backup;
 pub mod common;
 pub mod db;
+pub mod disk_space_monitor;
 pub mod get_restore_handler;
 pub mod metrics;
 pub(crate) mod rocksdb_property_reporter;
 pub mod schema;
+pub mod secondary_db;
+pub mod state_kv_shard_set;
 pub mod state_restore;
+pub mod state_snapshot_container;
 pub mod utils;
 
 #[cfg(feature = "db-debugger")]
 pub mod db_debugger;
 pub mod fast_sync_storage_wrapper;
 
+mod consistency_scrubber;
 mod db_options;
 mod event_store;
 mod ledger_db;
@@ -38,5 +43,6 @@ mod pruner;
 mod state_kv_db;
 mod state_merkle_db;
 mod state_store;
+mod tiered_storage;
 mod transaction_store;
 mod versioned_node_cache;
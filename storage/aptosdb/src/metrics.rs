@@ -3,8 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_metrics_core::{
-    exponential_buckets, register_histogram_vec, register_int_counter, register_int_gauge,
-    register_int_gauge_vec, HistogramVec, IntCounter, IntGauge, IntGaugeVec,
+    exponential_buckets, register_histogram_vec, register_int_counter, register_int_counter_vec,
+    register_int_gauge, register_int_gauge_vec, HistogramVec, IntCounter, IntCounterVec, IntGauge,
+    IntGaugeVec,
 };
 use once_cell::sync::Lazy;
 
@@ -103,6 +104,19 @@ pub static PRUNER_BATCH_SIZE: Lazy<IntGaugeVec> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Max number of versions pruned per second (0 means the pruner is not throttled).
+pub static PRUNER_MAX_VERSIONS_PER_SEC: Lazy<IntGaugeVec> = Lazy::new(|| {
+    register_int_gauge_vec!(
+        // metric name
+        "aptos_pruner_max_versions_per_sec",
+        // metric description
+        "Aptos pruner max versions pruned per second",
+        // metric labels (dimensions)
+        &["pruner_name",]
+    )
+    .unwrap()
+});
+
 pub static API_LATENCY_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
         // metric name
@@ -229,3 +243,62 @@ pub(crate) static BACKUP_STATE_SNAPSHOT_LEAF_IDX: Lazy<IntGauge> = Lazy::new(||
     )
     .unwrap()
 });
+
+pub(crate) static DISK_AVAILABLE_SPACE_PERCENT: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_storage_disk_available_space_percent",
+        "Lowest available space (percentage) across the disks holding the databases."
+    )
+    .unwrap()
+});
+
+pub(crate) static COLD_STORAGE_MIGRATION_PROGRESS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_storage_cold_storage_migration_progress",
+        "The first version not yet migrated to cold storage."
+    )
+    .unwrap()
+});
+
+pub(crate) static COLD_STORAGE_SEGMENT_FETCHES: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "aptos_storage_cold_storage_segment_fetches",
+        "Number of segments fetched from the cold storage backend (i.e., segment cache misses)."
+    )
+    .unwrap()
+});
+
+pub(crate) static DISK_SPACE_STATUS: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!(
+        "aptos_storage_disk_space_status",
+        "Disk space status of the databases (0: healthy, 1: warning, 2: low)."
+    )
+    .unwrap()
+});
+
+pub(crate) static CONSISTENCY_SCRUBBER_SCRUBBED_ITEMS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_storage_consistency_scrubber_scrubbed_items",
+        "Number of items (transaction infos or state merkle nodes) verified by the scrubber.",
+        &["kind"]
+    )
+    .unwrap()
+});
+
+pub(crate) static CONSISTENCY_SCRUBBER_PASSES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_storage_consistency_scrubber_passes",
+        "Number of complete passes made by the consistency scrubber.",
+        &["kind"]
+    )
+    .unwrap()
+});
+
+pub(crate) static CONSISTENCY_SCRUBBER_CORRUPTIONS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "aptos_storage_consistency_scrubber_corruptions",
+        "Number of corruptions detected by the consistency scrubber.",
+        &["kind"]
+    )
+    .unwrap()
+});
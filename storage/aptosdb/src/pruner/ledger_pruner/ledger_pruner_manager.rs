@@ -5,8 +5,10 @@ use crate::{
     ledger_db::LedgerDb,
     metrics::{PRUNER_BATCH_SIZE, PRUNER_VERSIONS, PRUNER_WINDOW},
     pruner::{
-        ledger_pruner::LedgerPruner, pruner_manager::PrunerManager, pruner_utils,
-        pruner_worker::PrunerWorker,
+        ledger_pruner::LedgerPruner,
+        pruner_manager::PrunerManager,
+        pruner_utils,
+        pruner_worker::{PrunerPauseGuard, PrunerWorker},
     },
 };
 use aptos_config::config::LedgerPrunerConfig;
@@ -93,6 +95,10 @@ impl PrunerManager for LedgerPrunerManager {
             .map_or(false, |w| w.is_pruning_pending())
     }
 
+    fn pause_pruner(&self) -> Option<PrunerPauseGuard<'_>> {
+        self.pruner_worker.as_ref().map(|w| w.pause())
+    }
+
     #[cfg(test)]
     fn set_worker_target_version(&self, target_version: Version) {
         self.pruner_worker
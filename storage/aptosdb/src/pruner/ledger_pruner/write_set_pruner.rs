@@ -24,6 +24,11 @@ impl DBSubPruner for WriteSetPruner {
 
     fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
         let batch = SchemaBatch::new();
+        self.ledger_db.write_set_db().prune_write_set_indices(
+            current_progress,
+            target_version,
+            &batch,
+        )?;
         WriteSetDb::prune(current_progress, target_version, &batch)?;
         batch.put::<DbMetadataSchema>(
             &DbMetadataKey::WriteSetPrunerProgress,
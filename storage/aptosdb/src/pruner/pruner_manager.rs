@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::pruner::db_pruner::DBPruner;
+use crate::pruner::{db_pruner::DBPruner, pruner_worker::PrunerPauseGuard};
 use aptos_storage_interface::Result;
 use aptos_types::transaction::Version;
 
@@ -36,6 +36,10 @@ pub trait PrunerManager: Sync {
 
     fn is_pruning_pending(&self) -> bool;
 
+    /// Pauses the pruner until the returned guard is dropped (returns None if the pruner is
+    /// not enabled).
+    fn pause_pruner(&self) -> Option<PrunerPauseGuard<'_>>;
+
     /// (For tests only.) Notifies the worker thread and waits for it to finish its job by polling
     /// an internal counter.
     #[cfg(test)]
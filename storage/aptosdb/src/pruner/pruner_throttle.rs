@@ -0,0 +1,75 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    thread::sleep,
+    time::Duration,
+};
+
+// The weight of the latest observation when updating the observed pruning rate
+const PRUNING_RATE_SMOOTHING_FACTOR: f64 = 0.2;
+
+/// Limits the number of versions a pruner prunes per second, and keeps track of
+/// the observed pruning rate. The limit can be updated at runtime (e.g., to slow
+/// down pruning, and the IO it generates, during peak traffic).
+#[derive(Debug, Default)]
+pub struct PrunerThrottle {
+    /// The max number of versions to prune per second (0 means unthrottled)
+    max_versions_per_sec: AtomicU64,
+    /// The observed number of versions pruned per second (stored as f64 bits,
+    /// where 0 means no observations yet)
+    observed_versions_per_sec: AtomicU64,
+}
+
+impl PrunerThrottle {
+    /// Sets the max number of versions to prune per second (or removes the limit if `None`)
+    pub fn set_max_versions_per_sec(&self, max_versions_per_sec: Option<u64>) {
+        self.max_versions_per_sec
+            .store(max_versions_per_sec.unwrap_or(0), Ordering::SeqCst);
+    }
+
+    /// Returns the max number of versions to prune per second (if throttled)
+    pub fn max_versions_per_sec(&self) -> Option<u64> {
+        let max_versions_per_sec = self.max_versions_per_sec.load(Ordering::SeqCst);
+        (max_versions_per_sec > 0).then_some(max_versions_per_sec)
+    }
+
+    /// Returns the observed number of versions pruned per second (if known)
+    pub fn observed_versions_per_sec(&self) -> Option<f64> {
+        let observed_versions_per_sec =
+            f64::from_bits(self.observed_versions_per_sec.load(Ordering::SeqCst));
+        (observed_versions_per_sec > 0.0).then_some(observed_versions_per_sec)
+    }
+
+    /// Notifies the throttle that the pruner pruned `num_versions` versions in the
+    /// given duration. If the pruner is throttled, this blocks until the pruning
+    /// rate is within the limit.
+    pub fn on_versions_pruned(&self, num_versions: u64, mut elapsed: Duration) {
+        if num_versions == 0 {
+            return;
+        }
+
+        // Wait until the pruning rate is within the limit
+        if let Some(max_versions_per_sec) = self.max_versions_per_sec() {
+            let min_duration =
+                Duration::from_secs_f64(num_versions as f64 / max_versions_per_sec as f64);
+            if let Some(remaining_duration) = min_duration.checked_sub(elapsed) {
+                sleep(remaining_duration);
+                elapsed = min_duration;
+            }
+        }
+
+        // Update the observed pruning rate
+        let versions_per_sec = num_versions as f64 / elapsed.as_secs_f64().max(f64::EPSILON);
+        let observed_versions_per_sec = match self.observed_versions_per_sec() {
+            Some(observed_versions_per_sec) => {
+                observed_versions_per_sec * (1.0 - PRUNING_RATE_SMOOTHING_FACTOR)
+                    + versions_per_sec * PRUNING_RATE_SMOOTHING_FACTOR
+            },
+            None => versions_per_sec,
+        };
+        self.observed_versions_per_sec
+            .store(observed_versions_per_sec.to_bits(), Ordering::SeqCst);
+    }
+}
@@ -2,20 +2,26 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::pruner::db_pruner::DBPruner;
+use aptos_infallible::{Mutex, MutexGuard};
 use aptos_logger::{
     error,
     prelude::{sample, SampleRate},
 };
+use aptos_storage_interface::disk_space_guardrails::DISK_SPACE_GUARDRAILS;
 use aptos_types::transaction::Version;
 use std::{
     sync::{
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
     },
     thread::{sleep, JoinHandle},
     time::Duration,
 };
 
+// The multiplier applied to the batch size while the disk space guardrails
+// are active (i.e., the available disk space is low). This accelerates pruning.
+const LOW_DISK_SPACE_BATCH_SIZE_MULTIPLIER: usize = 4;
+
 /// Maintains the pruner and periodically calls the db_pruner's prune method to prune the DB.
 /// This also exposes API to report the progress to the parent thread.
 pub struct PrunerWorker {
@@ -37,6 +43,10 @@ pub struct PrunerWorkerInner {
     /// Indicates whether the pruning loop should be running. Will only be set to true on pruner
     /// destruction.
     quit_worker: AtomicBool,
+    /// Held while pruning a batch (and while the pruner is paused).
+    prune_lock: Mutex<()>,
+    /// The number of callers waiting to pause (or pausing) the pruner.
+    num_pause_requests: AtomicUsize,
 }
 
 impl PrunerWorkerInner {
@@ -46,13 +56,27 @@ impl PrunerWorkerInner {
             pruner,
             batch_size,
             quit_worker: AtomicBool::new(false),
+            prune_lock: Mutex::new(()),
+            num_pause_requests: AtomicUsize::new(0),
         })
     }
 
     // Loop that does the real pruning job.
     fn work(&self) {
         while !self.quit_worker.load(Ordering::SeqCst) {
-            let pruner_result = self.pruner.prune(self.batch_size);
+            if self.num_pause_requests.load(Ordering::SeqCst) > 0 {
+                sleep(Duration::from_millis(self.pruning_time_interval_in_ms));
+                continue;
+            }
+            let batch_size = if DISK_SPACE_GUARDRAILS.are_guardrails_active() {
+                self.batch_size * LOW_DISK_SPACE_BATCH_SIZE_MULTIPLIER
+            } else {
+                self.batch_size
+            };
+            let pruner_result = {
+                let _prune_guard = self.prune_lock.lock();
+                self.pruner.prune(batch_size)
+            };
             if pruner_result.is_err() {
                 sample!(
                     SampleRate::Duration(Duration::from_secs(1)),
@@ -99,6 +123,38 @@ impl PrunerWorker {
     pub fn is_pruning_pending(&self) -> bool {
         self.inner.pruner.is_pruning_pending()
     }
+
+    /// Returns the version up to which the pruner has pruned
+    pub fn progress(&self) -> Version {
+        self.inner.pruner.progress()
+    }
+
+    /// Returns the version the pruner is working towards
+    pub fn target_version(&self) -> Version {
+        self.inner.pruner.target_version()
+    }
+
+    /// Pauses the pruner until the returned guard is dropped. Blocks until the batch being
+    /// pruned (if any) is done, so no data is deleted while the guard is held.
+    pub fn pause(&self) -> PrunerPauseGuard<'_> {
+        self.inner.num_pause_requests.fetch_add(1, Ordering::SeqCst);
+        PrunerPauseGuard {
+            _prune_guard: self.inner.prune_lock.lock(),
+            num_pause_requests: &self.inner.num_pause_requests,
+        }
+    }
+}
+
+/// Keeps a pruner worker paused (see `PrunerWorker::pause`)
+pub struct PrunerPauseGuard<'a> {
+    _prune_guard: MutexGuard<'a, ()>,
+    num_pause_requests: &'a AtomicUsize,
+}
+
+impl Drop for PrunerPauseGuard<'_> {
+    fn drop(&mut self) {
+        self.num_pause_requests.fetch_sub(1, Ordering::SeqCst);
+    }
 }
 
 impl Drop for PrunerWorker {
@@ -4,7 +4,9 @@
 use crate::{
     metrics::{PRUNER_BATCH_SIZE, PRUNER_VERSIONS, PRUNER_WINDOW},
     pruner::{
-        pruner_manager::PrunerManager, pruner_utils, pruner_worker::PrunerWorker,
+        pruner_manager::PrunerManager,
+        pruner_utils,
+        pruner_worker::{PrunerPauseGuard, PrunerWorker},
         state_kv_pruner::StateKvPruner,
     },
     state_kv_db::StateKvDb,
@@ -71,6 +73,10 @@ impl PrunerManager for StateKvPrunerManager {
             .map_or(false, |w| w.is_pruning_pending())
     }
 
+    fn pause_pruner(&self) -> Option<PrunerPauseGuard<'_>> {
+        self.pruner_worker.as_ref().map(|w| w.pause())
+    }
+
     #[cfg(test)]
     fn set_worker_target_version(&self, target_version: Version) {
         self.pruner_worker
@@ -12,6 +12,7 @@ use crate::{
     metrics::{OTHER_TIMERS_SECONDS, PRUNER_VERSIONS},
     pruner::{
         db_pruner::DBPruner,
+        pruner_throttle::PrunerThrottle,
         state_merkle_pruner::{
             generics::StaleNodeIndexSchemaTrait,
             state_merkle_metadata_pruner::StateMerkleMetadataPruner,
@@ -31,6 +32,7 @@ use rayon::prelude::*;
 use std::{
     marker::PhantomData,
     sync::{atomic::Ordering, Arc},
+    time::Instant,
 };
 
 /// Responsible for pruning the state tree.
@@ -43,6 +45,8 @@ pub struct StateMerklePruner<S> {
     metadata_pruner: StateMerkleMetadataPruner<S>,
     // Non-empty iff sharding is enabled.
     shard_pruners: Vec<StateMerkleShardPruner<S>>,
+    /// Limits the pruning rate (and tracks the observed rate).
+    throttle: Arc<PrunerThrottle>,
 
     _phantom: PhantomData<S>,
 }
@@ -75,11 +79,16 @@ where
         );
 
         while progress < target_version {
+            let round_start_time = Instant::now();
             if let Some(target_version_for_this_round) = self
                 .metadata_pruner
                 .maybe_prune_single_version(progress, target_version)?
             {
                 self.prune_shards(progress, target_version_for_this_round)?;
+                self.throttle.on_versions_pruned(
+                    target_version_for_this_round - progress,
+                    round_start_time.elapsed(),
+                );
                 progress = target_version_for_this_round;
                 info!(name = S::name(), progress = progress);
                 self.record_progress(target_version_for_this_round);
@@ -121,7 +130,7 @@ impl<S: StaleNodeIndexSchemaTrait> StateMerklePruner<S>
 where
     StaleNodeIndex: KeyCodec<S>,
 {
-    pub fn new(state_merkle_db: Arc<StateMerkleDb>) -> Result<Self> {
+    pub fn new(state_merkle_db: Arc<StateMerkleDb>, throttle: Arc<PrunerThrottle>) -> Result<Self> {
         info!(name = S::name(), "Initializing...");
 
         let metadata_pruner = StateMerkleMetadataPruner::new(state_merkle_db.metadata_db_arc());
@@ -153,6 +162,7 @@ where
             progress: AtomicVersion::new(metadata_progress),
             metadata_pruner,
             shard_pruners,
+            throttle,
             _phantom: std::marker::PhantomData,
         };
 
@@ -5,11 +5,12 @@
 //! meant to be triggered by other threads as they commit new data to the DB.
 
 use crate::{
-    metrics::{PRUNER_BATCH_SIZE, PRUNER_VERSIONS, PRUNER_WINDOW},
+    metrics::{PRUNER_BATCH_SIZE, PRUNER_MAX_VERSIONS_PER_SEC, PRUNER_VERSIONS, PRUNER_WINDOW},
     pruner::{
         pruner_manager::PrunerManager,
+        pruner_throttle::PrunerThrottle,
         pruner_utils,
-        pruner_worker::PrunerWorker,
+        pruner_worker::{PrunerPauseGuard, PrunerWorker},
         state_merkle_pruner::{generics::StaleNodeIndexSchemaTrait, StateMerklePruner},
     },
     state_merkle_db::StateMerkleDb,
@@ -17,7 +18,7 @@ use crate::{
 use aptos_config::config::StateMerklePrunerConfig;
 use aptos_jellyfish_merkle::StaleNodeIndex;
 use aptos_schemadb::schema::KeyCodec;
-use aptos_storage_interface::Result;
+use aptos_storage_interface::{pruner_progress::PrunerProgress, Result};
 use aptos_types::transaction::{AtomicVersion, Version};
 use std::{
     marker::PhantomData,
@@ -41,6 +42,8 @@ where
     pruner_worker: Option<PrunerWorker>,
     /// The minimal readable version for the state merkle data.
     min_readable_version: AtomicVersion,
+    /// Limits the pruning rate of the pruner (can be updated at runtime).
+    pruner_throttle: Arc<PrunerThrottle>,
 
     _phantom: PhantomData<S>,
 }
@@ -89,6 +92,10 @@ where
             .map_or(false, |w| w.is_pruning_pending())
     }
 
+    fn pause_pruner(&self) -> Option<PrunerPauseGuard<'_>> {
+        self.pruner_worker.as_ref().map(|w| w.pause())
+    }
+
     #[cfg(test)]
     fn set_worker_target_version(&self, target_version: Version) {
         self.pruner_worker
@@ -107,10 +114,12 @@ where
         state_merkle_db: Arc<StateMerkleDb>,
         state_merkle_pruner_config: StateMerklePrunerConfig,
     ) -> Self {
+        let pruner_throttle = Arc::new(PrunerThrottle::default());
         let pruner_worker = if state_merkle_pruner_config.enable {
             Some(Self::init_pruner(
                 Arc::clone(&state_merkle_db),
                 state_merkle_pruner_config,
+                Arc::clone(&pruner_throttle),
             ))
         } else {
             None
@@ -128,16 +137,53 @@ where
             prune_window: state_merkle_pruner_config.prune_window,
             pruner_worker,
             min_readable_version: AtomicVersion::new(min_readable_version),
+            pruner_throttle,
             _phantom: PhantomData,
         }
     }
 
+    /// Returns the current progress of the pruner
+    pub fn get_pruner_progress(&self) -> PrunerProgress {
+        let pruner_worker = match &self.pruner_worker {
+            Some(pruner_worker) => pruner_worker,
+            None => return PrunerProgress::default(),
+        };
+
+        let pruned_version = pruner_worker.progress();
+        let target_version = pruner_worker.target_version();
+        let backlog = target_version.saturating_sub(pruned_version);
+        let observed_versions_per_sec = self.pruner_throttle.observed_versions_per_sec();
+        PrunerProgress {
+            enabled: true,
+            min_readable_version: self.get_min_readable_version(),
+            pruned_version,
+            target_version,
+            backlog,
+            observed_versions_per_sec,
+            max_versions_per_sec: self.pruner_throttle.max_versions_per_sec(),
+            estimated_catch_up_time: PrunerProgress::estimate_catch_up_time(
+                backlog,
+                observed_versions_per_sec,
+            ),
+        }
+    }
+
+    /// Limits the number of versions pruned per second (or removes the limit if `None`)
+    pub fn set_max_versions_per_sec(&self, max_versions_per_sec: Option<u64>) {
+        self.pruner_throttle
+            .set_max_versions_per_sec(max_versions_per_sec);
+        PRUNER_MAX_VERSIONS_PER_SEC
+            .with_label_values(&[S::name()])
+            .set(max_versions_per_sec.unwrap_or(0) as i64);
+    }
+
     fn init_pruner(
         state_merkle_db: Arc<StateMerkleDb>,
         state_merkle_pruner_config: StateMerklePrunerConfig,
+        pruner_throttle: Arc<PrunerThrottle>,
     ) -> PrunerWorker {
         let pruner = Arc::new(
-            StateMerklePruner::<S>::new(Arc::clone(&state_merkle_db))
+            StateMerklePruner::<S>::new(Arc::clone(&state_merkle_db), pruner_throttle)
                 .expect("Failed to create state merkle pruner."),
         );
 
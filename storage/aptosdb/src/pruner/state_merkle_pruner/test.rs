@@ -18,7 +18,9 @@ use crate::{
 use aptos_config::config::{LedgerPrunerConfig, StateMerklePrunerConfig};
 use aptos_crypto::HashValue;
 use aptos_schemadb::{ReadOptions, SchemaBatch};
-use aptos_storage_interface::{jmt_update_refs, jmt_updates, DbReader};
+use aptos_storage_interface::{
+    jmt_update_refs, jmt_updates, pruner_progress::PrunerProgress, DbReader,
+};
 use aptos_temppath::TempPath;
 use aptos_types::{
     state_store::{
@@ -30,7 +32,7 @@ use aptos_types::{
 };
 use arr_macro::arr;
 use proptest::{prelude::*, proptest};
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 fn put_value_set(
     state_store: &StateStore,
@@ -171,6 +173,68 @@ fn test_state_store_pruner() {
     }
 }
 
+#[test]
+fn test_state_store_pruner_progress_and_throttle() {
+    let key = StateKey::raw(String::from("test_key1").into_bytes());
+
+    let num_versions = 25;
+    let tmp_dir = TempPath::new();
+    let aptos_db = AptosDB::new_for_test_no_cache(&tmp_dir);
+    let state_store = &aptos_db.state_store;
+
+    // Insert 25 values in the db
+    for i in 0..num_versions {
+        let value = StateValue::from(vec![i as u8]);
+        put_value_set(
+            state_store,
+            vec![(key.clone(), value)],
+            i, /* version */
+        );
+    }
+
+    // Create a pruner and verify there's no progress or throttle yet
+    let pruner = create_state_merkle_pruner_manager(&aptos_db.state_merkle_db(), 10);
+    let progress = pruner.get_pruner_progress();
+    assert!(progress.enabled);
+    assert_eq!(progress.pruned_version, 0);
+    assert_eq!(progress.backlog, 0);
+    assert_eq!(progress.observed_versions_per_sec, None);
+    assert_eq!(progress.max_versions_per_sec, None);
+
+    // Throttle the pruner and prune until version 10
+    let max_versions_per_sec = 1000;
+    pruner.set_max_versions_per_sec(Some(max_versions_per_sec));
+    pruner
+        .wake_and_wait_pruner(10 /* latest_version */)
+        .unwrap();
+
+    // Verify the progress of the pruner
+    let progress = pruner.get_pruner_progress();
+    assert_eq!(progress.min_readable_version, 10);
+    assert_eq!(progress.pruned_version, 10);
+    assert_eq!(progress.target_version, 10);
+    assert_eq!(progress.backlog, 0);
+    assert_eq!(progress.max_versions_per_sec, Some(max_versions_per_sec));
+    assert_eq!(progress.estimated_catch_up_time, Some(Duration::ZERO));
+    let observed_versions_per_sec = progress.observed_versions_per_sec.unwrap();
+    assert!(observed_versions_per_sec <= max_versions_per_sec as f64);
+
+    // Remove the throttle and verify the progress is updated
+    pruner.set_max_versions_per_sec(None);
+    assert_eq!(pruner.get_pruner_progress().max_versions_per_sec, None);
+
+    // Verify a disabled pruner reports no progress
+    let pruner = StateMerklePrunerManager::<StaleNodeIndexSchema>::new(
+        aptos_db.state_merkle_db(),
+        StateMerklePrunerConfig {
+            enable: false,
+            prune_window: 0,
+            batch_size: 10,
+        },
+    );
+    assert_eq!(pruner.get_pruner_progress(), PrunerProgress::default());
+}
+
 #[test]
 fn test_state_store_pruner_partial_version() {
     // ```text
@@ -0,0 +1,76 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the segments of ledger data (transactions,
+//! events and write sets) that were migrated to cold storage.
+//!
+//! ```text
+//! |<-----key----->|<------value------>|
+//! | first_version |  cold_segment_info |
+//! ```
+//!
+//! `Version` is serialized in big endian so that records in RocksDB will be in order of it's
+//! numeric value.
+
+use super::COLD_SEGMENT_CF_NAME;
+use crate::schema::ensure_slice_len_eq;
+use anyhow::Result;
+use aptos_crypto::HashValue;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt};
+#[cfg(any(test, feature = "fuzzing"))]
+use proptest_derive::Arbitrary;
+use serde::{Deserialize, Serialize};
+use std::mem::size_of;
+
+/// The metadata of a segment in cold storage
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
+pub struct ColdSegmentInfo {
+    /// The number of versions in the segment (starting at the first version)
+    pub num_versions: u64,
+    /// The name of the segment in the cold storage backend
+    pub segment_name: String,
+    /// The sha3-256 checksum of the (compressed) segment
+    pub checksum: HashValue,
+}
+
+impl ColdSegmentInfo {
+    /// Returns the version after the last version in the segment
+    pub fn end_version(&self, first_version: Version) -> Version {
+        first_version + self.num_versions
+    }
+}
+
+type Key = Version;
+type Value = ColdSegmentInfo;
+
+define_schema!(ColdSegmentSchema, Key, Value, COLD_SEGMENT_CF_NAME);
+
+impl KeyCodec<ColdSegmentSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_key(mut data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>())?;
+        Ok(data.read_u64::<BigEndian>()?)
+    }
+}
+
+impl ValueCodec<ColdSegmentSchema> for Value {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self).map_err(Into::into)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        bcs::from_bytes(data).map_err(Into::into)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,18 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        first_version in any::<Version>(),
+        cold_segment_info in any::<ColdSegmentInfo>(),
+    ) {
+        assert_encode_decode::<ColdSegmentSchema>(&first_version, &cold_segment_info);
+    }
+}
+
+test_no_panic_decoding!(ColdSegmentSchema);
@@ -67,6 +67,10 @@ pub enum DbMetadataKey {
     StateKvShardPrunerProgress(ShardId),
     StateMerkleShardRestoreProgress(ShardId, Version),
     TransactionAuxiliaryDataPrunerProgress,
+    ColdStorageMigrationProgress,
+    WriteSetIndexStartVersion,
+    ConsistencyScrubberLedgerProgress,
+    ConsistencyScrubberStateMerkleProgress,
 }
 
 define_schema!(
@@ -8,8 +8,10 @@
 
 pub(crate) mod block_by_version;
 pub(crate) mod block_info;
+pub(crate) mod cold_segment;
 pub(crate) mod db_metadata;
 pub(crate) mod epoch_by_version;
+pub(crate) mod epoch_ledger_stats;
 pub(crate) mod event;
 pub(crate) mod event_accumulator;
 pub(crate) mod event_by_key;
@@ -29,14 +31,18 @@ pub(crate) mod transaction_by_hash;
 pub(crate) mod transaction_info;
 pub(crate) mod version_data;
 pub(crate) mod write_set;
+pub(crate) mod write_set_by_state_key;
+pub(crate) mod write_set_index_by_version;
 
 use anyhow::{ensure, Result};
 use aptos_schemadb::ColumnFamilyName;
 
 pub const BLOCK_BY_VERSION_CF_NAME: ColumnFamilyName = "block_by_version";
 pub const BLOCK_INFO_CF_NAME: ColumnFamilyName = "block_info";
+pub const COLD_SEGMENT_CF_NAME: ColumnFamilyName = "cold_segment";
 pub const DB_METADATA_CF_NAME: ColumnFamilyName = "db_metadata";
 pub const EPOCH_BY_VERSION_CF_NAME: ColumnFamilyName = "epoch_by_version";
+pub const EPOCH_LEDGER_STATS_CF_NAME: ColumnFamilyName = "epoch_ledger_stats";
 pub const EVENT_ACCUMULATOR_CF_NAME: ColumnFamilyName = "event_accumulator";
 pub const EVENT_BY_KEY_CF_NAME: ColumnFamilyName = "event_by_key";
 pub const EVENT_BY_VERSION_CF_NAME: ColumnFamilyName = "event_by_version";
@@ -56,6 +62,8 @@ pub const TRANSACTION_BY_HASH_CF_NAME: ColumnFamilyName = "transaction_by_hash";
 pub const TRANSACTION_INFO_CF_NAME: ColumnFamilyName = "transaction_info";
 pub const VERSION_DATA_CF_NAME: ColumnFamilyName = "version_data";
 pub const WRITE_SET_CF_NAME: ColumnFamilyName = "write_set";
+pub const WRITE_SET_BY_STATE_KEY_CF_NAME: ColumnFamilyName = "write_set_by_state_key";
+pub const WRITE_SET_INDEX_BY_VERSION_CF_NAME: ColumnFamilyName = "write_set_index_by_version";
 
 fn ensure_slice_len_eq(data: &[u8], len: usize) -> Result<()> {
     ensure!(
@@ -86,7 +94,9 @@ pub mod fuzzing {
         {
             assert_no_panic_decoding::<super::block_by_version::BlockByVersionSchema>(data);
             assert_no_panic_decoding::<super::block_info::BlockInfoSchema>(data);
+            assert_no_panic_decoding::<super::cold_segment::ColdSegmentSchema>(data);
             assert_no_panic_decoding::<super::epoch_by_version::EpochByVersionSchema>(data);
+            assert_no_panic_decoding::<super::epoch_ledger_stats::EpochLedgerStatsSchema>(data);
             assert_no_panic_decoding::<super::event::EventSchema>(data);
             assert_no_panic_decoding::<super::event_accumulator::EventAccumulatorSchema>(data);
             assert_no_panic_decoding::<super::event_by_key::EventByKeySchema>(data);
@@ -119,6 +129,12 @@ pub mod fuzzing {
             assert_no_panic_decoding::<super::transaction_info::TransactionInfoSchema>(data);
             assert_no_panic_decoding::<super::version_data::VersionDataSchema>(data);
             assert_no_panic_decoding::<super::write_set::WriteSetSchema>(data);
+            assert_no_panic_decoding::<super::write_set_by_state_key::WriteSetByStateKeySchema>(
+                data,
+            );
+            assert_no_panic_decoding::<
+                super::write_set_index_by_version::WriteSetIndexByVersionSchema,
+            >(data);
         }
     }
 }
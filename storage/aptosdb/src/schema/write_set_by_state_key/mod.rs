@@ -0,0 +1,75 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for an index of the write sets by the state keys
+//! they write to, which allows the history of a state key (or of all the state keys with a given
+//! prefix) to be read without replaying all the transaction outputs.
+//!
+//! ```text
+//! |<--------key-------->|<-value->|
+//! | state_key | version |  empty  |
+//! ```
+//!
+//! `Version` is serialized in big endian so that the records of a state key in RocksDB will be in
+//! increasing order of their numeric value.
+
+use crate::schema::{ensure_slice_len_eq, ensure_slice_len_gt, WRITE_SET_BY_STATE_KEY_CF_NAME};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, SeekKeyCodec, ValueCodec},
+};
+use aptos_types::{
+    state_store::{state_key::StateKey, state_key_prefix::StateKeyPrefix},
+    transaction::Version,
+};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::{io::Write, mem::size_of};
+
+type Key = (StateKey, Version);
+
+define_schema!(
+    WriteSetByStateKeySchema,
+    Key,
+    (),
+    WRITE_SET_BY_STATE_KEY_CF_NAME
+);
+
+impl KeyCodec<WriteSetByStateKeySchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded = vec![];
+        encoded.write_all(&self.0.encode()?)?;
+        encoded.write_u64::<BigEndian>(self.1)?;
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const VERSION_SIZE: usize = size_of::<Version>();
+
+        ensure_slice_len_gt(data, VERSION_SIZE)?;
+        let state_key_len = data.len() - VERSION_SIZE;
+        let state_key: StateKey = StateKey::decode(&data[..state_key_len])?;
+        let version = (&data[state_key_len..]).read_u64::<BigEndian>()?;
+        Ok((state_key, version))
+    }
+}
+
+impl ValueCodec<WriteSetByStateKeySchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, 0)?;
+        Ok(())
+    }
+}
+
+impl SeekKeyCodec<WriteSetByStateKeySchema> for &StateKeyPrefix {
+    fn encode_seek_key(&self) -> Result<Vec<u8>> {
+        self.encode()
+    }
+}
+
+#[cfg(test)]
+mod test;
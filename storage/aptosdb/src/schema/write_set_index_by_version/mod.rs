@@ -0,0 +1,71 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for the reverse of the write set index by state
+//! key (see `write_set_by_state_key`), i.e., the state keys written at each version. This allows
+//! the index to be pruned by version without reading the (much larger) write sets.
+//!
+//! ```text
+//! |<--------key-------->|<-value->|
+//! | version | state_key |  empty  |
+//! ```
+//!
+//! `Version` is serialized in big endian so that records in RocksDB will be in order of its
+//! numeric value.
+
+use crate::schema::{ensure_slice_len_eq, ensure_slice_len_gt, WRITE_SET_INDEX_BY_VERSION_CF_NAME};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, SeekKeyCodec, ValueCodec},
+};
+use aptos_types::{state_store::state_key::StateKey, transaction::Version};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::{io::Write, mem::size_of};
+
+type Key = (Version, StateKey);
+
+define_schema!(
+    WriteSetIndexByVersionSchema,
+    Key,
+    (),
+    WRITE_SET_INDEX_BY_VERSION_CF_NAME
+);
+
+impl KeyCodec<WriteSetIndexByVersionSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded = vec![];
+        encoded.write_u64::<BigEndian>(self.0)?;
+        encoded.write_all(&self.1.encode()?)?;
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const VERSION_SIZE: usize = size_of::<Version>();
+
+        ensure_slice_len_gt(data, VERSION_SIZE)?;
+        let version = (&data[..VERSION_SIZE]).read_u64::<BigEndian>()?;
+        let state_key = StateKey::decode(&data[VERSION_SIZE..])?;
+        Ok((version, state_key))
+    }
+}
+
+impl ValueCodec<WriteSetIndexByVersionSchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, 0)?;
+        Ok(())
+    }
+}
+
+impl SeekKeyCodec<WriteSetIndexByVersionSchema> for Version {
+    fn encode_seek_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+}
+
+#[cfg(test)]
+mod test;
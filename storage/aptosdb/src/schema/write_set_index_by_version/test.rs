@@ -0,0 +1,18 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        version in any::<Version>(),
+        state_key in any::<StateKey>(),
+    ) {
+        assert_encode_decode::<WriteSetIndexByVersionSchema>(&(version, state_key), &());
+    }
+}
+
+test_no_panic_decoding!(WriteSetIndexByVersionSchema);
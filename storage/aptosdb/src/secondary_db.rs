@@ -0,0 +1,90 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::AptosDB;
+use aptos_config::config::{RocksdbConfigs, StorageDirPaths};
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_storage_interface::{DbReader, Result};
+use std::{
+    path::Path,
+    sync::{mpsc, Arc},
+    thread,
+    thread::JoinHandle,
+    time::Duration,
+};
+
+/// This is a read-only wrapper around [AptosDB], opened as a RocksDB secondary instance of a
+/// (running) primary db on the same host. It periodically catches up with the primary instance
+/// in the background, so that indexers and analytics jobs can read recent data without going
+/// through the REST API (or copying the db).
+pub struct SecondaryAptosDB {
+    db: Arc<AptosDB>,
+    sender: Mutex<mpsc::Sender<()>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl SecondaryAptosDB {
+    /// Opens the db at `db_paths` as a secondary instance (see `AptosDB::open_as_secondary`),
+    /// and catches up with the primary instance every `catch_up_interval`.
+    pub fn open(
+        db_paths: StorageDirPaths,
+        secondary_db_root_path: impl AsRef<Path>,
+        rocksdb_configs: RocksdbConfigs,
+        max_num_nodes_per_lru_cache_shard: usize,
+        catch_up_interval: Duration,
+    ) -> Result<Self> {
+        let db = Arc::new(AptosDB::open_as_secondary(
+            db_paths,
+            secondary_db_root_path,
+            rocksdb_configs,
+            max_num_nodes_per_lru_cache_shard,
+        )?);
+
+        let (sender, receiver) = mpsc::channel();
+        let db_for_catch_up = Arc::clone(&db);
+        let join_handle = Some(thread::spawn(move || loop {
+            match receiver.recv_timeout(catch_up_interval) {
+                Ok(_) => break,
+                Err(mpsc::RecvTimeoutError::Timeout) => (),
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+            if let Err(error) = db_for_catch_up.try_catch_up_with_primary() {
+                warn!(
+                    error = ?error,
+                    "Failed to catch up with the primary db."
+                );
+            }
+        }));
+
+        Ok(Self {
+            db,
+            sender: Mutex::new(sender),
+            join_handle,
+        })
+    }
+
+    /// Catches up with the primary instance immediately (instead of waiting for the next
+    /// periodic catch up).
+    pub fn try_catch_up_with_primary(&self) -> Result<()> {
+        self.db.try_catch_up_with_primary()
+    }
+}
+
+impl DbReader for SecondaryAptosDB {
+    fn get_read_delegatee(&self) -> &dyn DbReader {
+        self.db.as_ref()
+    }
+}
+
+impl Drop for SecondaryAptosDB {
+    fn drop(&mut self) {
+        // Notify the catch up thread to exit
+        self.sender.lock().send(()).unwrap();
+        self.join_handle
+            .take()
+            .expect("Secondary db catch up thread must exist.")
+            .join()
+            .expect("Secondary db catch up thread should join peacefully.");
+    }
+}
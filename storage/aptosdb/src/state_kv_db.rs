@@ -5,7 +5,7 @@
 
 use crate::{
     common::NUM_STATE_SHARDS,
-    db_options::{gen_state_kv_cfds, state_kv_db_column_families},
+    db_options::{gen_state_kv_cfds, state_kv_db_column_families, BlockCaches, DbOpenMode},
     metrics::OTHER_TIMERS_SECONDS,
     schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
     utils::truncation_helper::{get_state_kv_commit_progress, truncate_state_kv_db_shards},
@@ -36,7 +36,7 @@ impl StateKvDb {
     pub(crate) fn new(
         db_paths: &StorageDirPaths,
         rocksdb_configs: RocksdbConfigs,
-        readonly: bool,
+        open_mode: &DbOpenMode,
         ledger_db: Arc<DB>,
     ) -> Result<Self> {
         let sharding = rocksdb_configs.enable_storage_sharding;
@@ -49,14 +49,15 @@ impl StateKvDb {
             });
         }
 
-        Self::open(db_paths, rocksdb_configs.state_kv_db_config, readonly)
+        Self::open(db_paths, rocksdb_configs.state_kv_db_config, open_mode)
     }
 
     pub(crate) fn open(
         db_paths: &StorageDirPaths,
         state_kv_db_config: RocksdbConfig,
-        readonly: bool,
+        open_mode: &DbOpenMode,
     ) -> Result<Self> {
+        let block_caches = BlockCaches::new(&state_kv_db_config);
         let state_kv_metadata_db_path =
             Self::metadata_db_path(db_paths.state_kv_db_metadata_root_path());
 
@@ -64,7 +65,8 @@ impl StateKvDb {
             state_kv_metadata_db_path.clone(),
             STATE_KV_METADATA_DB_NAME,
             &state_kv_db_config,
-            readonly,
+            &block_caches,
+            open_mode,
         )?);
 
         info!(
@@ -76,7 +78,7 @@ impl StateKvDb {
         let state_kv_db_shards = {
             arr![{
                 let shard_root_path = db_paths.state_kv_db_shard_root_path(shard_id as u8);
-                let db = Self::open_shard(shard_root_path, shard_id as u8, &state_kv_db_config, readonly)?;
+                let db = Self::open_shard(shard_root_path, shard_id as u8, &state_kv_db_config, &block_caches, open_mode)?;
                 shard_id += 1;
                 Arc::new(db)
             }; 16]
@@ -88,6 +90,11 @@ impl StateKvDb {
             enabled_sharding: true,
         };
 
+        // Secondary instances can't write, so the truncation is left to the primary instance.
+        if open_mode.is_secondary() {
+            return Ok(state_kv_db);
+        }
+
         if let Some(overall_kv_commit_progress) = get_state_kv_commit_progress(&state_kv_db)? {
             truncate_state_kv_db_shards(&state_kv_db, overall_kv_commit_progress, None)?;
         }
@@ -154,8 +161,13 @@ impl StateKvDb {
         let state_kv_db = Self::open(
             &StorageDirPaths::from_path(db_root_path),
             RocksdbConfig::default(),
-            false,
+            &DbOpenMode::ReadWrite,
         )?;
+        state_kv_db.create_checkpoint_from_db(cp_root_path)
+    }
+
+    /// Creates a checkpoint of the (open) sharded state kv db
+    pub(crate) fn create_checkpoint_from_db(&self, cp_root_path: impl AsRef<Path>) -> Result<()> {
         let cp_state_kv_db_path = cp_root_path.as_ref().join(STATE_KV_DB_FOLDER_NAME);
 
         info!("Creating state_kv_db checkpoint at: {cp_state_kv_db_path:?}");
@@ -163,19 +175,33 @@ impl StateKvDb {
         std::fs::remove_dir_all(&cp_state_kv_db_path).unwrap_or(());
         std::fs::create_dir_all(&cp_state_kv_db_path).unwrap_or(());
 
-        state_kv_db
-            .metadata_db()
+        self.metadata_db()
             .create_checkpoint(Self::metadata_db_path(cp_root_path.as_ref()))?;
 
         for shard_id in 0..NUM_STATE_SHARDS {
-            state_kv_db
-                .db_shard(shard_id as u8)
+            self.db_shard(shard_id as u8)
                 .create_checkpoint(Self::db_shard_path(cp_root_path.as_ref(), shard_id as u8))?;
         }
 
         Ok(())
     }
 
+    /// Catches up with the primary instance (only valid for dbs opened as secondary). Note: the
+    /// primary commits the shards before the metadata db (i.e., the commit progress), so the
+    /// metadata db is caught up first, so that the commit progress never exceeds the data visible
+    /// in the shards. If sharding is disabled, the state kv db is part of the ledger db, and is
+    /// caught up with it.
+    pub(crate) fn try_catch_up_with_primary(&self) -> Result<()> {
+        if !self.enabled_sharding {
+            return Ok(());
+        }
+        self.metadata_db().try_catch_up_with_primary()?;
+        for shard_id in 0..NUM_STATE_SHARDS {
+            self.db_shard(shard_id as u8).try_catch_up_with_primary()?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn metadata_db(&self) -> &DB {
         &self.state_kv_metadata_db
     }
@@ -213,14 +239,16 @@ impl StateKvDb {
         db_root_path: P,
         shard_id: u8,
         state_kv_db_config: &RocksdbConfig,
-        readonly: bool,
+        block_caches: &BlockCaches,
+        open_mode: &DbOpenMode,
     ) -> Result<DB> {
         let db_name = format!("state_kv_db_shard_{}", shard_id);
         Self::open_db(
             Self::db_shard_path(db_root_path, shard_id),
             &db_name,
             state_kv_db_config,
-            readonly,
+            block_caches,
+            open_mode,
         )
     }
 
@@ -228,22 +256,29 @@ impl StateKvDb {
         path: PathBuf,
         name: &str,
         state_kv_db_config: &RocksdbConfig,
-        readonly: bool,
+        block_caches: &BlockCaches,
+        open_mode: &DbOpenMode,
     ) -> Result<DB> {
-        Ok(if readonly {
-            DB::open_cf_readonly(
+        Ok(match open_mode {
+            DbOpenMode::ReadWrite => DB::open_cf(
+                &gen_rocksdb_options(state_kv_db_config, false),
+                path,
+                name,
+                gen_state_kv_cfds(state_kv_db_config, block_caches),
+            )?,
+            DbOpenMode::ReadOnly => DB::open_cf_readonly(
                 &gen_rocksdb_options(state_kv_db_config, true),
                 path,
                 name,
                 state_kv_db_column_families(),
-            )?
-        } else {
-            DB::open_cf(
-                &gen_rocksdb_options(state_kv_db_config, false),
+            )?,
+            DbOpenMode::Secondary(secondary_root_path) => DB::open_cf_as_secondary(
+                &gen_rocksdb_options(state_kv_db_config, true),
                 path,
+                secondary_root_path.join(name),
                 name,
-                gen_state_kv_cfds(state_kv_db_config),
-            )?
+                state_kv_db_column_families(),
+            )?,
         })
     }
 
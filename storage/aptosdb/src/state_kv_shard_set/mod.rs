@@ -0,0 +1,447 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A native export format for the sharded state KV DB. A state KV shard set is
+//! a directory holding a JSON manifest and, for each state KV shard, a list of
+//! files with the state values of the shard (at a single version). The files of
+//! a shard are sorted by key hash, so shards can be exported and imported in
+//! parallel, directly into (and out of) the corresponding DB shards.
+//!
+//! Unlike state snapshot containers, the files carry no range proofs. Instead,
+//! the state tree is rebuilt from the imported values, and the import fails if
+//! the resulting root hash doesn't match the root hash in the manifest. This
+//! makes the shard set the fastest way to clone the state of a node out-of-band
+//! (e.g., compared to fast sync or restoring from a backup).
+
+use crate::{
+    common::NUM_STATE_SHARDS,
+    schema::db_metadata::{DbMetadataKey, DbMetadataSchema},
+    state_restore::StateValueBatch,
+    state_snapshot_container::write_file_atomically,
+    utils::new_sharded_kv_schema_batch,
+    AptosDB,
+};
+use aptos_compression::client::CompressionClient;
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
+use aptos_jellyfish_merkle::{node_type::Node, Key, Value};
+use aptos_logger::prelude::*;
+use aptos_schemadb::SchemaBatch;
+use aptos_storage_interface::{db_ensure as ensure, AptosDbError, Result};
+use aptos_types::{
+    state_store::{
+        state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
+    },
+    transaction::Version,
+};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs, path::Path};
+
+#[cfg(test)]
+mod shard_set_test;
+
+/// The current version of the shard set format. This must be bumped
+/// whenever the layout of the manifest or the shard files changes.
+pub const STATE_KV_SHARD_SET_FORMAT_VERSION: u32 = 1;
+
+/// The name of the manifest file in the shard set directory
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+// The maximum size of a single (compressed or uncompressed) shard file
+const MAX_SHARD_FILE_BYTES: usize = 1024 * 1024 * 1024; // 1 GiB
+
+/// The manifest of a state KV shard set
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateKvShardSetManifest {
+    /// The version of the shard set format
+    pub format_version: u32,
+    /// The version of the exported state
+    pub version: Version,
+    /// The root hash of the state tree at the exported version
+    pub root_hash: HashValue,
+    /// The total number of state values (across all shards)
+    pub num_state_values: u64,
+    /// All shards (ordered by shard id)
+    pub shards: Vec<StateKvShardManifest>,
+}
+
+/// The files of a single state KV shard
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateKvShardManifest {
+    /// The id of the shard
+    pub shard_id: u8,
+    /// The number of state values in the shard
+    pub num_state_values: u64,
+    /// All files of the shard (ordered by key hash)
+    pub files: Vec<StateKvShardFile>,
+}
+
+/// A single file of a state KV shard
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateKvShardFile {
+    /// The name of the file (relative to the shard set directory)
+    pub file_name: String,
+    /// The number of state values in the file
+    pub num_state_values: u64,
+    /// The SHA3-256 checksum of the file contents
+    pub checksum: HashValue,
+}
+
+/// Exports the state at the given version (from the given database) into a new
+/// state KV shard set. All shards are exported in parallel, with at most
+/// `max_values_per_file` state values per file.
+pub fn export_state_kv_shard_set(
+    db: &AptosDB,
+    version: Version,
+    export_dir: &Path,
+    max_values_per_file: usize,
+) -> Result<StateKvShardSetManifest> {
+    ensure!(
+        max_values_per_file > 0,
+        "The max number of values per file must be greater than zero!"
+    );
+    ensure!(
+        !export_dir.join(MANIFEST_FILE_NAME).exists(),
+        "A state KV shard set already exists at {:?}!",
+        export_dir
+    );
+    fs::create_dir_all(export_dir)?;
+
+    // Identify the state to export
+    let root_hash = db.state_store.get_root_hash(version)?;
+    let num_state_values = db.state_store.get_value_count(version)? as u64;
+    info!(
+        version = version,
+        root_hash = root_hash,
+        num_state_values = num_state_values,
+        "Exporting the state KV shard set."
+    );
+
+    // Export all shards in parallel
+    let shards = THREAD_MANAGER.get_io_pool().install(|| {
+        (0..NUM_STATE_SHARDS as u8)
+            .into_par_iter()
+            .map(|shard_id| export_shard(db, version, export_dir, shard_id, max_values_per_file))
+            .collect::<Result<Vec<_>>>()
+    })?;
+
+    // Verify all state values were exported
+    let num_exported_values: u64 = shards.iter().map(|shard| shard.num_state_values).sum();
+    ensure!(
+        num_exported_values == num_state_values,
+        "The number of exported state values does not match the state tree! Expected: {}, found: {}",
+        num_state_values,
+        num_exported_values
+    );
+
+    // Write the manifest (completing the shard set)
+    let manifest = StateKvShardSetManifest {
+        format_version: STATE_KV_SHARD_SET_FORMAT_VERSION,
+        version,
+        root_hash,
+        num_state_values,
+        shards,
+    };
+    let manifest_bytes = serde_json::to_vec_pretty(&manifest)
+        .map_err(|error| AptosDbError::Other(error.to_string()))?;
+    write_file_atomically(&export_dir.join(MANIFEST_FILE_NAME), &manifest_bytes)?;
+
+    Ok(manifest)
+}
+
+/// Imports the state KV shard set in the given directory into the given (empty)
+/// database. All shards are imported in parallel, and the state tree is rebuilt
+/// (and verified against the root hash in the manifest) from the imported values.
+///
+/// Note: this only imports the state (similar to restoring a state snapshot).
+/// If the import fails, the database must be discarded.
+pub fn import_state_kv_shard_set(
+    db: &AptosDB,
+    import_dir: &Path,
+) -> Result<StateKvShardSetManifest> {
+    let manifest = read_manifest(import_dir)?;
+    ensure!(
+        manifest.shards.len() == NUM_STATE_SHARDS
+            && manifest
+                .shards
+                .iter()
+                .enumerate()
+                .all(|(index, shard)| shard.shard_id as usize == index),
+        "The manifest must contain exactly {} shards (ordered by shard id)!",
+        NUM_STATE_SHARDS
+    );
+    ensure!(
+        db.state_kv_db
+            .metadata_db()
+            .get::<DbMetadataSchema>(&DbMetadataKey::StateKvCommitProgress)?
+            .is_none(),
+        "State KV shard sets can only be imported into an empty DB!"
+    );
+
+    let version = manifest.version;
+    info!(
+        version = version,
+        root_hash = manifest.root_hash,
+        num_state_values = manifest.num_state_values,
+        "Importing the state KV shard set."
+    );
+
+    // Import all shards in parallel (and calculate the state tree nodes of each shard)
+    let imported_shards = THREAD_MANAGER.get_io_pool().install(|| {
+        manifest
+            .shards
+            .par_iter()
+            .map(|shard| import_shard(db, import_dir, version, shard))
+            .collect::<Result<Vec<_>>>()
+    })?;
+    let mut shard_root_nodes = Vec::with_capacity(NUM_STATE_SHARDS);
+    let mut shard_tree_batches = Vec::with_capacity(NUM_STATE_SHARDS);
+    let mut usage = StateStorageUsage::zero();
+    for (shard_root_node, shard_tree_batch, shard_usage) in imported_shards {
+        shard_root_nodes.push(shard_root_node);
+        shard_tree_batches.push(shard_tree_batch);
+        usage = StateStorageUsage::new(
+            usage.items() + shard_usage.items(),
+            usage.bytes() + shard_usage.bytes(),
+        );
+    }
+
+    // Verify the root hash of the rebuilt state tree before committing it
+    let state_merkle_db = &db.state_store.state_merkle_db;
+    let (root_hash, top_levels_batch) = state_merkle_db.calculate_top_levels(
+        shard_root_nodes,
+        version,
+        /*base_version=*/ None,
+        /*previous_epoch_ending_version=*/ None,
+    )?;
+    ensure!(
+        root_hash == manifest.root_hash,
+        "The root hash of the imported state does not match the manifest! Expected: {}, found: {}",
+        manifest.root_hash,
+        root_hash
+    );
+    state_merkle_db.commit(version, top_levels_batch, shard_tree_batches)?;
+
+    // Complete the import
+    db.state_kv_db.write_progress(version)?;
+    db.ledger_db.metadata_db().put_usage(version, usage)?;
+    info!(
+        version = version,
+        root_hash = root_hash,
+        "Imported the state KV shard set."
+    );
+
+    Ok(manifest)
+}
+
+/// Reads (and validates) the manifest of the shard set in the given directory
+pub fn read_manifest(shard_set_dir: &Path) -> Result<StateKvShardSetManifest> {
+    let manifest_bytes = fs::read(shard_set_dir.join(MANIFEST_FILE_NAME))?;
+    let manifest: StateKvShardSetManifest = serde_json::from_slice(&manifest_bytes)
+        .map_err(|error| AptosDbError::Other(format!("Invalid manifest: {}", error)))?;
+    ensure!(
+        manifest.format_version == STATE_KV_SHARD_SET_FORMAT_VERSION,
+        "Unsupported state KV shard set format version: {}. Supported version: {}",
+        manifest.format_version,
+        STATE_KV_SHARD_SET_FORMAT_VERSION
+    );
+    Ok(manifest)
+}
+
+/// Exports the state values of a single shard (in key hash order)
+fn export_shard(
+    db: &AptosDB,
+    version: Version,
+    export_dir: &Path,
+    shard_id: u8,
+    max_values_per_file: usize,
+) -> Result<StateKvShardManifest> {
+    let mut shard = StateKvShardManifest {
+        shard_id,
+        num_state_values: 0,
+        files: vec![],
+    };
+
+    // Iterate over the state tree, starting at the first key hash of the shard
+    let mut state_values = Vec::with_capacity(max_values_per_file);
+    for result in db
+        .state_store
+        .get_state_key_and_value_iter(version, shard_start_key_hash(shard_id))?
+    {
+        let (state_key, state_value) = result?;
+        if state_key.get_shard_id() != shard_id {
+            break; // We've reached the next shard
+        }
+
+        state_values.push((state_key, state_value));
+        if state_values.len() == max_values_per_file {
+            write_shard_file(export_dir, &mut shard, &state_values)?;
+            state_values.clear();
+        }
+    }
+    if !state_values.is_empty() {
+        write_shard_file(export_dir, &mut shard, &state_values)?;
+    }
+
+    info!(
+        shard_id = shard_id,
+        num_state_values = shard.num_state_values,
+        "Exported state KV shard."
+    );
+    Ok(shard)
+}
+
+/// Imports the state values of a single shard, and returns the root node and
+/// the (uncommitted) node batch of the shard's state tree, and the shard usage.
+fn import_shard(
+    db: &AptosDB,
+    import_dir: &Path,
+    version: Version,
+    shard: &StateKvShardManifest,
+) -> Result<(Node, SchemaBatch, StateStorageUsage)> {
+    let shard_id = shard.shard_id;
+    let mut usage = StateStorageUsage::zero();
+    let mut leaves = Vec::with_capacity(shard.num_state_values as usize);
+    let mut previous_key_hash = None;
+
+    for file in &shard.files {
+        let state_values = read_shard_file(import_dir, file)?;
+
+        // Verify the state values belong to the shard and are sorted
+        let mut kv_batch: StateValueBatch<StateKey, Option<StateValue>> = HashMap::new();
+        for (state_key, state_value) in state_values {
+            let key_hash = CryptoHash::hash(&state_key);
+            ensure!(
+                key_hash.nibble(0) == shard_id,
+                "Shard file {} contains a state key of a different shard!",
+                file.file_name
+            );
+            ensure!(
+                previous_key_hash.map_or(true, |previous_key_hash| previous_key_hash < key_hash),
+                "The state values of shard {} are not sorted by key hash!",
+                shard_id
+            );
+            previous_key_hash = Some(key_hash);
+
+            usage.add_item(state_key.key_size() + state_value.value_size());
+            leaves.push((
+                key_hash,
+                (CryptoHash::hash(&state_value), state_key.clone()),
+            ));
+            kv_batch.insert((state_key, version), Some(state_value));
+        }
+
+        // Write the state values directly into the shard
+        let metadata_batch = SchemaBatch::new();
+        let sharded_kv_batches = new_sharded_kv_schema_batch();
+        db.state_store
+            .shard_state_value_batch(&metadata_batch, &sharded_kv_batches, &kv_batch)?;
+        db.state_kv_db.metadata_db().write_schemas(metadata_batch)?;
+        let shard_kv_batch = sharded_kv_batches
+            .into_iter()
+            .nth(shard_id as usize)
+            .expect("The shard batch must exist!");
+        db.state_kv_db
+            .commit_single_shard(version, shard_id, shard_kv_batch)?;
+    }
+    ensure!(
+        leaves.len() as u64 == shard.num_state_values,
+        "The number of state values in shard {} does not match the manifest! Expected: {}, found: {}",
+        shard_id,
+        shard.num_state_values,
+        leaves.len()
+    );
+
+    // Calculate the state tree nodes of the shard
+    let value_set = leaves
+        .iter()
+        .map(|(key_hash, leaf)| (*key_hash, Some(leaf)))
+        .collect();
+    let (shard_root_node, shard_tree_batch) = db
+        .state_store
+        .state_merkle_db
+        .merklize_value_set_for_shard(
+            shard_id, value_set, /*node_hashes=*/ None, version, /*base_version=*/ None,
+            /*shard_persisted_version=*/ None, /*previous_epoch_ending_version=*/ None,
+        )?;
+
+    info!(
+        shard_id = shard_id,
+        num_state_values = shard.num_state_values,
+        "Imported state KV shard."
+    );
+    Ok((shard_root_node, shard_tree_batch, usage))
+}
+
+/// Writes the given state values into a new file of the shard
+fn write_shard_file(
+    export_dir: &Path,
+    shard: &mut StateKvShardManifest,
+    state_values: &[(StateKey, StateValue)],
+) -> Result<()> {
+    // Serialize and compress the state values
+    let bytes = bcs::to_bytes(state_values)?;
+    let compressed_bytes = aptos_compression::compress(
+        bytes,
+        CompressionClient::StateSnapshot,
+        MAX_SHARD_FILE_BYTES,
+    )
+    .map_err(|error| AptosDbError::Other(error.to_string()))?;
+
+    // Write the file
+    let file_name = format!(
+        "shard_{:02}_{:08}.bcs.lz4",
+        shard.shard_id,
+        shard.files.len()
+    );
+    write_file_atomically(&export_dir.join(&file_name), &compressed_bytes)?;
+
+    shard.files.push(StateKvShardFile {
+        file_name,
+        num_state_values: state_values.len() as u64,
+        checksum: HashValue::sha3_256_of(&compressed_bytes),
+    });
+    shard.num_state_values += state_values.len() as u64;
+
+    Ok(())
+}
+
+/// Reads (and verifies) the state values in the given shard file
+fn read_shard_file(
+    import_dir: &Path,
+    file: &StateKvShardFile,
+) -> Result<Vec<(StateKey, StateValue)>> {
+    let compressed_bytes = fs::read(import_dir.join(&file.file_name))?;
+    let checksum = HashValue::sha3_256_of(&compressed_bytes);
+    ensure!(
+        checksum == file.checksum,
+        "The checksum of shard file {} does not match the manifest! Expected: {}, found: {}",
+        file.file_name,
+        file.checksum,
+        checksum
+    );
+
+    let bytes = aptos_compression::decompress(
+        &compressed_bytes,
+        CompressionClient::StateSnapshot,
+        MAX_SHARD_FILE_BYTES,
+    )
+    .map_err(|error| AptosDbError::Other(error.to_string()))?;
+    let state_values: Vec<(StateKey, StateValue)> = bcs::from_bytes(&bytes)?;
+    ensure!(
+        state_values.len() as u64 == file.num_state_values,
+        "The number of state values in shard file {} does not match the manifest!",
+        file.file_name
+    );
+
+    Ok(state_values)
+}
+
+/// Returns the first key hash of the given shard (i.e., the shard id is the
+/// first nibble of the key hash).
+fn shard_start_key_hash(shard_id: u8) -> HashValue {
+    let mut bytes = [0u8; HashValue::LENGTH];
+    bytes[0] = shard_id << 4;
+    HashValue::new(bytes)
+}
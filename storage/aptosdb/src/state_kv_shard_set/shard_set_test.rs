@@ -0,0 +1,149 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db::test_helper::update_store,
+    state_kv_shard_set::{
+        export_state_kv_shard_set, import_state_kv_shard_set, read_manifest,
+        StateKvShardSetManifest, MANIFEST_FILE_NAME, STATE_KV_SHARD_SET_FORMAT_VERSION,
+    },
+    AptosDB,
+};
+use aptos_crypto::HashValue;
+use aptos_storage_interface::DbReader;
+use aptos_temppath::TempPath;
+use aptos_types::{
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::Version,
+};
+use std::{fs, path::Path};
+
+#[test]
+fn test_shard_set_round_trip() {
+    // Create a DB with some state
+    let (_source_db_dir, source_db, state_values, version) = create_db_with_state(100);
+
+    // Export the state KV shard set
+    let shard_set_dir = TempPath::new();
+    let manifest = export_state_kv_shard_set(&source_db, version, shard_set_dir.path(), 7).unwrap();
+    assert_eq!(manifest.format_version, STATE_KV_SHARD_SET_FORMAT_VERSION);
+    assert_eq!(manifest.version, version);
+    assert_eq!(
+        manifest.root_hash,
+        source_db.state_store.get_root_hash(version).unwrap()
+    );
+    assert_eq!(manifest.num_state_values, state_values.len() as u64);
+    for shard in &manifest.shards {
+        assert!(shard
+            .files
+            .iter()
+            .all(|file| file.num_state_values > 0 && file.num_state_values <= 7));
+    }
+
+    // Verify the shard set can't be exported over an existing one
+    assert!(export_state_kv_shard_set(&source_db, version, shard_set_dir.path(), 7).is_err());
+
+    // Import the shard set into a new DB and verify the state
+    let target_db_dir = TempPath::new();
+    let target_db = AptosDB::new_for_test(&target_db_dir);
+    assert_eq!(
+        import_state_kv_shard_set(&target_db, shard_set_dir.path()).unwrap(),
+        manifest
+    );
+    assert_eq!(
+        target_db.state_store.get_root_hash(version).unwrap(),
+        manifest.root_hash
+    );
+    assert_eq!(
+        target_db
+            .state_store
+            .get_usage(Some(version))
+            .unwrap()
+            .items(),
+        state_values.len()
+    );
+    for (state_key, state_value) in &state_values {
+        assert_eq!(
+            target_db
+                .get_state_value_by_version(state_key, version)
+                .unwrap()
+                .as_ref(),
+            Some(state_value)
+        );
+    }
+
+    // Verify the shard set can't be imported into a non-empty DB
+    assert!(import_state_kv_shard_set(&target_db, shard_set_dir.path()).is_err());
+}
+
+#[test]
+fn test_shard_set_import_verification() {
+    let (_source_db_dir, source_db, _, version) = create_db_with_state(50);
+    let shard_set_dir = TempPath::new();
+    let manifest =
+        export_state_kv_shard_set(&source_db, version, shard_set_dir.path(), 10).unwrap();
+
+    // Corrupt the root hash in the manifest and verify the import fails
+    let mut invalid_manifest = manifest.clone();
+    invalid_manifest.root_hash = HashValue::random();
+    write_manifest(shard_set_dir.path(), &invalid_manifest);
+    let target_db_dir = TempPath::new();
+    let target_db = AptosDB::new_for_test(&target_db_dir);
+    assert!(import_state_kv_shard_set(&target_db, shard_set_dir.path()).is_err());
+
+    // Corrupt a shard file and verify the import fails
+    write_manifest(shard_set_dir.path(), &manifest);
+    let file = manifest
+        .shards
+        .iter()
+        .flat_map(|shard| shard.files.iter())
+        .next()
+        .unwrap();
+    fs::write(shard_set_dir.path().join(&file.file_name), b"corrupted").unwrap();
+    let target_db_dir = TempPath::new();
+    let target_db = AptosDB::new_for_test(&target_db_dir);
+    assert!(import_state_kv_shard_set(&target_db, shard_set_dir.path()).is_err());
+
+    // Verify that unsupported format versions are rejected
+    let mut invalid_manifest = manifest;
+    invalid_manifest.format_version = STATE_KV_SHARD_SET_FORMAT_VERSION + 1;
+    write_manifest(shard_set_dir.path(), &invalid_manifest);
+    assert!(read_manifest(shard_set_dir.path()).is_err());
+}
+
+/// Creates a DB with the given number of (distinct) state values, and
+/// returns the DB, the state values and the latest version.
+fn create_db_with_state(
+    num_state_values: usize,
+) -> (TempPath, AptosDB, Vec<(StateKey, StateValue)>, Version) {
+    let db_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&db_dir);
+
+    let state_values: Vec<_> = (0..num_state_values)
+        .map(|index| {
+            (
+                StateKey::raw(format!("key_{}", index).into_bytes()),
+                StateValue::from(format!("value_{}", index).into_bytes()),
+            )
+        })
+        .collect();
+    update_store(
+        &db.state_store,
+        state_values
+            .iter()
+            .map(|(state_key, state_value)| (state_key.clone(), Some(state_value.clone()))),
+        0,
+    );
+
+    let version = num_state_values as Version - 1;
+    (db_dir, db, state_values, version)
+}
+
+/// Overwrites the manifest of the shard set in the given directory
+fn write_manifest(shard_set_dir: &Path, manifest: &StateKvShardSetManifest) {
+    fs::write(
+        shard_set_dir.join(MANIFEST_FILE_NAME),
+        serde_json::to_vec_pretty(manifest).unwrap(),
+    )
+    .unwrap();
+}
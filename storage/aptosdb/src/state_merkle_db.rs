@@ -3,7 +3,7 @@
 
 use crate::{
     common::NUM_STATE_SHARDS,
-    db_options::{gen_state_merkle_cfds, state_merkle_db_column_families},
+    db_options::{gen_state_merkle_cfds, state_merkle_db_column_families, BlockCaches, DbOpenMode},
     lru_node_cache::LruNodeCache,
     metrics::{NODE_CACHE_SECONDS, OTHER_TIMERS_SECONDS},
     schema::{
@@ -68,7 +68,7 @@ impl StateMerkleDb {
     pub(crate) fn new(
         db_paths: &StorageDirPaths,
         rocksdb_configs: RocksdbConfigs,
-        readonly: bool,
+        open_mode: &DbOpenMode,
         max_nodes_per_lru_cache_shard: usize,
     ) -> Result<Self> {
         let sharding = rocksdb_configs.enable_storage_sharding;
@@ -89,7 +89,8 @@ impl StateMerkleDb {
                 state_merkle_db_path,
                 STATE_MERKLE_DB_NAME,
                 &state_merkle_db_config,
-                readonly,
+                &BlockCaches::new(&state_merkle_db_config),
+                open_mode,
             )?);
             return Ok(Self {
                 state_merkle_metadata_db: Arc::clone(&db),
@@ -104,7 +105,7 @@ impl StateMerkleDb {
         Self::open(
             db_paths,
             state_merkle_db_config,
-            readonly,
+            open_mode,
             enable_cache,
             version_caches,
             lru_cache,
@@ -176,9 +177,15 @@ impl StateMerkleDb {
         let state_merkle_db = Self::new(
             &StorageDirPaths::from_path(db_root_path),
             rocksdb_configs,
-            /*readonly=*/ false,
+            &DbOpenMode::ReadWrite,
             /*max_nodes_per_lru_cache_shard=*/ 0,
         )?;
+        state_merkle_db.create_checkpoint_from_db(cp_root_path)
+    }
+
+    /// Creates a checkpoint of the (open) state merkle db
+    pub(crate) fn create_checkpoint_from_db(&self, cp_root_path: impl AsRef<Path>) -> Result<()> {
+        let sharding = self.enable_sharding;
         let cp_state_merkle_db_path = cp_root_path.as_ref().join(STATE_MERKLE_DB_FOLDER_NAME);
 
         info!("Creating state_merkle_db checkpoint at: {cp_state_merkle_db_path:?}");
@@ -188,14 +195,12 @@ impl StateMerkleDb {
             std::fs::create_dir_all(&cp_state_merkle_db_path).unwrap_or(());
         }
 
-        state_merkle_db
-            .metadata_db()
+        self.metadata_db()
             .create_checkpoint(Self::metadata_db_path(cp_root_path.as_ref(), sharding))?;
 
         if sharding {
             for shard_id in 0..NUM_STATE_SHARDS {
-                state_merkle_db
-                    .db_shard(shard_id as u8)
+                self.db_shard(shard_id as u8)
                     .create_checkpoint(Self::db_shard_path(
                         cp_root_path.as_ref(),
                         shard_id as u8,
@@ -206,6 +211,20 @@ impl StateMerkleDb {
         Ok(())
     }
 
+    /// Catches up with the primary instance (only valid for dbs opened as secondary). Note: the
+    /// primary commits the shards before the metadata db (i.e., the root nodes and the commit
+    /// progress), so the metadata db is caught up first, so that every root node visible in the
+    /// metadata db has all of its (sharded) descendants visible.
+    pub(crate) fn try_catch_up_with_primary(&self) -> Result<()> {
+        self.metadata_db().try_catch_up_with_primary()?;
+        if self.enable_sharding {
+            for shard_id in 0..NUM_STATE_SHARDS {
+                self.db_shard(shard_id as u8).try_catch_up_with_primary()?;
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn metadata_db(&self) -> &DB {
         &self.state_merkle_metadata_db
     }
@@ -550,6 +569,12 @@ impl StateMerkleDb {
         NUM_STATE_SHARDS as u8
     }
 
+    /// Reads the node directly from the db (bypassing all node caches).
+    pub(crate) fn get_node_option_from_db(&self, node_key: &NodeKey) -> Result<Option<Node>> {
+        self.db_by_key(node_key)
+            .get::<JellyfishMerkleNodeSchema>(node_key)
+    }
+
     fn db_by_key(&self, node_key: &NodeKey) -> &DB {
         if let Some(shard_id) = node_key.get_shard_id() {
             self.db_shard(shard_id)
@@ -561,11 +586,12 @@ impl StateMerkleDb {
     fn open(
         db_paths: &StorageDirPaths,
         state_merkle_db_config: RocksdbConfig,
-        readonly: bool,
+        open_mode: &DbOpenMode,
         enable_cache: bool,
         version_caches: HashMap<Option<u8>, VersionedNodeCache>,
         lru_cache: LruNodeCache,
     ) -> Result<Self> {
+        let block_caches = BlockCaches::new(&state_merkle_db_config);
         let state_merkle_metadata_db_path = Self::metadata_db_path(
             db_paths.state_merkle_db_metadata_root_path(),
             /*sharding=*/ true,
@@ -575,7 +601,8 @@ impl StateMerkleDb {
             state_merkle_metadata_db_path.clone(),
             STATE_MERKLE_METADATA_DB_NAME,
             &state_merkle_db_config,
-            readonly,
+            &block_caches,
+            open_mode,
         )?);
 
         info!(
@@ -586,7 +613,7 @@ impl StateMerkleDb {
         let mut shard_id: usize = 0;
         let state_merkle_db_shards = arr![{
             let shard_root_path = db_paths.state_merkle_db_shard_root_path(shard_id as u8);
-            let db = Self::open_shard(shard_root_path, shard_id as u8, &state_merkle_db_config, readonly)?;
+            let db = Self::open_shard(shard_root_path, shard_id as u8, &state_merkle_db_config, &block_caches, open_mode)?;
             shard_id += 1;
             Arc::new(db)
         }; 16];
@@ -600,6 +627,11 @@ impl StateMerkleDb {
             lru_cache,
         };
 
+        // Secondary instances can't write, so the truncation is left to the primary instance.
+        if open_mode.is_secondary() {
+            return Ok(state_merkle_db);
+        }
+
         if let Some(overall_state_merkle_commit_progress) =
             get_state_merkle_commit_progress(&state_merkle_db)?
         {
@@ -616,14 +648,16 @@ impl StateMerkleDb {
         db_root_path: P,
         shard_id: u8,
         state_merkle_db_config: &RocksdbConfig,
-        readonly: bool,
+        block_caches: &BlockCaches,
+        open_mode: &DbOpenMode,
     ) -> Result<DB> {
         let db_name = format!("state_merkle_db_shard_{}", shard_id);
         Self::open_db(
             Self::db_shard_path(db_root_path, shard_id),
             &db_name,
             state_merkle_db_config,
-            readonly,
+            block_caches,
+            open_mode,
         )
     }
 
@@ -631,22 +665,29 @@ impl StateMerkleDb {
         path: PathBuf,
         name: &str,
         state_merkle_db_config: &RocksdbConfig,
-        readonly: bool,
+        block_caches: &BlockCaches,
+        open_mode: &DbOpenMode,
     ) -> Result<DB> {
-        Ok(if readonly {
-            DB::open_cf_readonly(
+        Ok(match open_mode {
+            DbOpenMode::ReadWrite => DB::open_cf(
+                &gen_rocksdb_options(state_merkle_db_config, false),
+                path,
+                name,
+                gen_state_merkle_cfds(state_merkle_db_config, block_caches),
+            )?,
+            DbOpenMode::ReadOnly => DB::open_cf_readonly(
                 &gen_rocksdb_options(state_merkle_db_config, true),
                 path,
                 name,
                 state_merkle_db_column_families(),
-            )?
-        } else {
-            DB::open_cf(
-                &gen_rocksdb_options(state_merkle_db_config, false),
+            )?,
+            DbOpenMode::Secondary(secondary_root_path) => DB::open_cf_as_secondary(
+                &gen_rocksdb_options(state_merkle_db_config, true),
                 path,
+                secondary_root_path.join(name),
                 name,
-                gen_state_merkle_cfds(state_merkle_db_config),
-            )?
+                state_merkle_db_column_families(),
+            )?,
         })
     }
 
@@ -6,7 +6,7 @@ use anyhow::anyhow;
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_infallible::Mutex;
 use aptos_jellyfish_merkle::{restore::JellyfishMerkleRestore, Key, TreeReader, TreeWriter, Value};
-use aptos_storage_interface::{Result, StateSnapshotReceiver};
+use aptos_storage_interface::{Result, StateSnapshotReceiver, StateValueFilter};
 use aptos_types::{
     proof::SparseMerkleRangeProof, state_store::state_storage_usage::StateStorageUsage,
     transaction::Version,
@@ -89,11 +89,20 @@ impl FromStr for StateSnapshotRestoreMode {
 struct StateValueRestore<K, V> {
     version: Version,
     db: Arc<dyn StateValueWriter<K, V>>,
+    value_filter: Option<StateValueFilter<K>>,
 }
 
 impl<K: Key + CryptoHash + Eq + Hash, V: Value> StateValueRestore<K, V> {
     pub fn new<D: 'static + StateValueWriter<K, V>>(db: Arc<D>, version: Version) -> Self {
-        Self { version, db }
+        Self {
+            version,
+            db,
+            value_filter: None,
+        }
+    }
+
+    pub fn set_value_filter(&mut self, value_filter: StateValueFilter<K>) {
+        self.value_filter = Some(value_filter);
     }
 
     pub fn add_chunk(&mut self, mut chunk: Vec<(K, V)>) -> Result<()> {
@@ -124,9 +133,15 @@ impl<K: Key + CryptoHash + Eq + Hash, V: Value> StateValueRestore<K, V> {
             usage.add_item(k.key_size() + v.value_size());
         }
 
-        // prepare the sharded kv batch
+        // prepare the sharded kv batch (dropping any filtered values). Note: the
+        // usage still accounts for the filtered values, as they exist in the state.
         let kv_batch: StateValueBatch<K, Option<V>> = chunk
             .into_iter()
+            .filter(|(k, _v)| {
+                self.value_filter
+                    .as_ref()
+                    .map_or(true, |value_filter| value_filter(k))
+            })
             .map(|(k, v)| ((k, self.version), Some(v)))
             .collect();
 
@@ -238,6 +253,25 @@ impl<K: Key + CryptoHash + Hash + Eq, V: Value> StateSnapshotReceiver<K, V>
     for StateSnapshotRestore<K, V>
 {
     fn add_chunk(&mut self, chunk: Vec<(K, V)>, proof: SparseMerkleRangeProof) -> Result<()> {
+        let value_hashes = chunk.iter().map(|(_, v)| v.hash()).collect();
+        self.add_chunk_with_value_hashes(chunk, value_hashes, proof)
+    }
+
+    fn add_chunk_with_value_hashes(
+        &mut self,
+        chunk: Vec<(K, V)>,
+        value_hashes: Vec<HashValue>,
+        proof: SparseMerkleRangeProof,
+    ) -> Result<()> {
+        if chunk.len() != value_hashes.len() {
+            return Err(anyhow!(
+                "The number of value hashes ({}) does not match the chunk size ({})!",
+                value_hashes.len(),
+                chunk.len()
+            )
+            .into());
+        }
+
         let kv_fn = || {
             let _timer = OTHER_TIMERS_SECONDS
                 .with_label_values(&["state_value_add_chunk"])
@@ -253,11 +287,10 @@ impl<K: Key + CryptoHash + Hash + Eq, V: Value> StateSnapshotReceiver<K, V>
             let _timer = OTHER_TIMERS_SECONDS
                 .with_label_values(&["jmt_add_chunk"])
                 .start_timer();
-            self.tree_restore
-                .lock()
-                .as_mut()
-                .unwrap()
-                .add_chunk_impl(chunk.iter().map(|(k, v)| (k, v.hash())).collect(), proof)
+            self.tree_restore.lock().as_mut().unwrap().add_chunk_impl(
+                chunk.iter().map(|(k, _)| k).zip(value_hashes).collect(),
+                proof,
+            )
         };
         // Write KV out first because we are likely to resume according to the rightmost key in the
         // tree after crashing.
@@ -275,6 +308,15 @@ impl<K: Key + CryptoHash + Hash + Eq, V: Value> StateSnapshotReceiver<K, V>
         Ok(())
     }
 
+    fn set_value_filter(&mut self, value_filter: StateValueFilter<K>) -> Result<()> {
+        self.kv_restore
+            .lock()
+            .as_mut()
+            .unwrap()
+            .set_value_filter(value_filter);
+        Ok(())
+    }
+
     fn finish(self) -> Result<()> {
         match self.restore_mode {
             StateSnapshotRestoreMode::KvOnly => self.kv_restore.lock().take().unwrap().finish()?,
@@ -216,6 +216,39 @@ proptest! {
         assert_success(&restore_db, expected_root_hash, &all, version);
     }
 
+    #[test]
+    fn test_restore_with_value_filter(
+        btree in arb_btree_map(1),
+        target_version in 0u64..2000,
+    ) {
+        let (db, version) = init_mock_store(&btree.clone().into_values().collect());
+        let tree = JellyfishMerkleTree::new(&db);
+        let expected_root_hash = tree.get_root_hash(version).unwrap();
+
+        // Only persist the values of keys with an even (hashed) first byte
+        let restore_db = Arc::new(MockSnapshotStore::default());
+        let mut restore = StateSnapshotRestore::new(&restore_db, &restore_db, target_version, expected_root_hash, true /* async_commit */, StateSnapshotRestoreMode::Default).unwrap();
+        restore.set_value_filter(Arc::new(|key: &ValueBlob| CryptoHash::hash(key)[0] % 2 == 0)).unwrap();
+        for (hashed_key, (k, v)) in &btree {
+            let proof = tree.get_range_proof(*hashed_key, version).unwrap();
+            restore.add_chunk(vec![(k.clone(), v.clone())], proof).unwrap();
+        }
+        restore.finish().unwrap();
+
+        // Verify the full tree was restored, but only the filtered values were persisted
+        let restored_tree = JellyfishMerkleTree::new(&*restore_db);
+        prop_assert_eq!(restored_tree.get_root_hash(target_version).unwrap(), expected_root_hash);
+        for (hashed_key, (k, v)) in &btree {
+            let value_in_db = restore_db.get_value_at_version(&(k.clone(), target_version));
+            if hashed_key[0] % 2 == 0 {
+                prop_assert_eq!(value_in_db.as_ref(), Some(v));
+            } else {
+                prop_assert_eq!(value_in_db, None);
+            }
+        }
+        prop_assert_eq!(restore_db.get_stored_usage(target_version).items(), btree.len());
+    }
+
     #[test]
     fn test_overwrite(
         btree in arb_btree_map(1),
@@ -0,0 +1,120 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::state_snapshot_container::{
+    StateSnapshotContainerReader, StateSnapshotContainerWriter,
+    STATE_SNAPSHOT_CONTAINER_FORMAT_VERSION,
+};
+use aptos_crypto::HashValue;
+use aptos_temppath::TempPath;
+use aptos_types::{
+    proof::SparseMerkleRangeProof,
+    state_store::{state_key::StateKey, state_value::StateValueChunkWithProof},
+};
+use std::fs;
+
+#[test]
+fn test_container_round_trip() {
+    // Write a container with several chunks
+    let container_dir = TempPath::new();
+    let root_hash = HashValue::random();
+    let chunks = create_state_value_chunks(root_hash, 5, 10);
+    let mut writer =
+        StateSnapshotContainerWriter::new(container_dir.path(), 100, root_hash).unwrap();
+    for chunk in &chunks {
+        writer.write_chunk(chunk).unwrap();
+    }
+    let manifest = writer.finish().unwrap();
+    assert_eq!(
+        manifest.format_version,
+        STATE_SNAPSHOT_CONTAINER_FORMAT_VERSION
+    );
+    assert_eq!(manifest.num_state_values, 50);
+
+    // Read the container and verify the chunks are identical
+    let reader = StateSnapshotContainerReader::open(container_dir.path()).unwrap();
+    assert_eq!(reader.manifest(), &manifest);
+    let read_chunks: Vec<_> = reader.chunks().map(|chunk| chunk.unwrap()).collect();
+    assert_eq!(read_chunks, chunks);
+
+    // Verify that a container can't be written over an existing one
+    assert!(StateSnapshotContainerWriter::new(container_dir.path(), 100, root_hash).is_err());
+}
+
+#[test]
+fn test_container_invalid_chunks() {
+    let container_dir = TempPath::new();
+    let root_hash = HashValue::random();
+    let mut writer =
+        StateSnapshotContainerWriter::new(container_dir.path(), 100, root_hash).unwrap();
+
+    // Verify that chunks for a different root hash are rejected
+    let chunk = create_state_value_chunks(HashValue::random(), 1, 10).remove(0);
+    assert!(writer.write_chunk(&chunk).is_err());
+
+    // Verify that non-contiguous chunks are rejected
+    let chunk = create_state_value_chunks(root_hash, 2, 10).remove(1);
+    assert!(writer.write_chunk(&chunk).is_err());
+}
+
+#[test]
+fn test_container_corruption() {
+    // Write a container with a single chunk
+    let container_dir = TempPath::new();
+    let root_hash = HashValue::random();
+    let mut writer =
+        StateSnapshotContainerWriter::new(container_dir.path(), 100, root_hash).unwrap();
+    writer
+        .write_chunk(&create_state_value_chunks(root_hash, 1, 10).remove(0))
+        .unwrap();
+    let manifest = writer.finish().unwrap();
+
+    // Corrupt the chunk file and verify the checksum mismatch is detected
+    let chunk_path = container_dir.path().join(&manifest.chunks[0].file_name);
+    let mut chunk_bytes = fs::read(&chunk_path).unwrap();
+    let last_byte = chunk_bytes.last_mut().unwrap();
+    *last_byte = last_byte.wrapping_add(1);
+    fs::write(&chunk_path, chunk_bytes).unwrap();
+    let reader = StateSnapshotContainerReader::open(container_dir.path()).unwrap();
+    assert!(reader.read_chunk(0).is_err());
+    assert!(reader.read_chunk(1).is_err());
+
+    // Bump the format version and verify the container can no longer be opened
+    let mut manifest = manifest;
+    manifest.format_version += 1;
+    fs::write(
+        container_dir.path().join(super::MANIFEST_FILE_NAME),
+        serde_json::to_vec(&manifest).unwrap(),
+    )
+    .unwrap();
+    assert!(StateSnapshotContainerReader::open(container_dir.path()).is_err());
+}
+
+/// Creates a list of contiguous state value chunks for the given root hash
+fn create_state_value_chunks(
+    root_hash: HashValue,
+    num_chunks: u64,
+    chunk_size: u64,
+) -> Vec<StateValueChunkWithProof> {
+    (0..num_chunks)
+        .map(|chunk_index| {
+            let first_index = chunk_index * chunk_size;
+            let last_index = first_index + chunk_size - 1;
+            let raw_values = (first_index..=last_index)
+                .map(|index| {
+                    let state_key = StateKey::raw(index.to_be_bytes().to_vec());
+                    (state_key, vec![index as u8; 32].into())
+                })
+                .collect();
+            StateValueChunkWithProof {
+                first_index,
+                last_index,
+                first_key: HashValue::random(),
+                last_key: HashValue::random(),
+                raw_values,
+                proof: SparseMerkleRangeProof::new(vec![HashValue::random()]),
+                root_hash,
+            }
+        })
+        .collect()
+}
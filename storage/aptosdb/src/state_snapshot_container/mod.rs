@@ -0,0 +1,312 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! A portable, versioned container format for state snapshots. A container is
+//! a directory holding a JSON manifest and a list of chunk files. Each chunk
+//! file holds a single BCS serialized (and LZ4 compressed) `StateValueChunkWithProof`,
+//! and is checksummed in the manifest. Because every chunk carries its range
+//! proof, containers can be shared between operators and ingested by any
+//! `StateSnapshotReceiver` (e.g., the one used by fast sync) without trusting
+//! the source of the container (only the root hash must be trusted).
+
+use aptos_compression::client::CompressionClient;
+use aptos_crypto::HashValue;
+use aptos_logger::prelude::*;
+use aptos_storage_interface::{
+    db_ensure as ensure, AptosDbError, DbReader, Result, StateSnapshotReceiver,
+};
+use aptos_types::{
+    state_store::{
+        state_key::StateKey,
+        state_value::{StateValue, StateValueChunkWithProof},
+    },
+    transaction::Version,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+#[cfg(test)]
+mod container_test;
+
+/// The current version of the container format. This must be bumped
+/// whenever the layout of the manifest or the chunk files changes.
+pub const STATE_SNAPSHOT_CONTAINER_FORMAT_VERSION: u32 = 1;
+
+/// The name of the manifest file in the container directory
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+// The maximum size of a single (compressed or uncompressed) chunk file
+const MAX_CHUNK_FILE_BYTES: usize = 1024 * 1024 * 1024; // 1 GiB
+
+/// The manifest of a state snapshot container
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateSnapshotContainerManifest {
+    /// The version of the container format
+    pub format_version: u32,
+    /// The version at which the state snapshot was taken
+    pub version: Version,
+    /// The root hash of the state tree at the snapshot version
+    pub root_hash: HashValue,
+    /// The total number of state values in the snapshot
+    pub num_state_values: u64,
+    /// All chunks of the snapshot (ordered by state index)
+    pub chunks: Vec<StateSnapshotContainerChunk>,
+}
+
+/// A single chunk file in the container
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct StateSnapshotContainerChunk {
+    /// The name of the chunk file (relative to the container directory)
+    pub file_name: String,
+    /// The index of the first state value in the chunk
+    pub first_index: u64,
+    /// The index of the last state value in the chunk (inclusive)
+    pub last_index: u64,
+    /// The SHA3-256 checksum of the chunk file contents
+    pub checksum: HashValue,
+}
+
+/// Writes a state snapshot container, one chunk at a time. The manifest is
+/// only written once all chunks have been written (see `finish()`), so
+/// partially written containers can never be opened.
+pub struct StateSnapshotContainerWriter {
+    container_dir: PathBuf,
+    version: Version,
+    root_hash: HashValue,
+    chunks: Vec<StateSnapshotContainerChunk>,
+    num_state_values: u64,
+}
+
+impl StateSnapshotContainerWriter {
+    pub fn new(container_dir: &Path, version: Version, root_hash: HashValue) -> Result<Self> {
+        ensure!(
+            !container_dir.join(MANIFEST_FILE_NAME).exists(),
+            "A state snapshot container already exists at {:?}!",
+            container_dir
+        );
+        fs::create_dir_all(container_dir)?;
+
+        Ok(Self {
+            container_dir: container_dir.to_path_buf(),
+            version,
+            root_hash,
+            chunks: vec![],
+            num_state_values: 0,
+        })
+    }
+
+    /// Appends the given chunk to the container. Chunks must be
+    /// written in order and belong to the snapshot's state tree.
+    pub fn write_chunk(&mut self, chunk: &StateValueChunkWithProof) -> Result<()> {
+        ensure!(
+            chunk.root_hash == self.root_hash,
+            "The chunk root hash does not match the snapshot! Expected: {}, found: {}",
+            self.root_hash,
+            chunk.root_hash
+        );
+        ensure!(
+            chunk.first_index == self.num_state_values,
+            "The chunk is not contiguous with the previous chunks! Expected first index: {}, found: {}",
+            self.num_state_values,
+            chunk.first_index
+        );
+        ensure!(
+            !chunk.raw_values.is_empty()
+                && chunk.last_index + 1 - chunk.first_index == chunk.raw_values.len() as u64,
+            "The chunk indices do not match the number of state values! First index: {}, last index: {}, num values: {}",
+            chunk.first_index,
+            chunk.last_index,
+            chunk.raw_values.len()
+        );
+
+        // Serialize and compress the chunk
+        let chunk_bytes = bcs::to_bytes(chunk)?;
+        let compressed_bytes = aptos_compression::compress(
+            chunk_bytes,
+            CompressionClient::StateSnapshot,
+            MAX_CHUNK_FILE_BYTES,
+        )
+        .map_err(|error| AptosDbError::Other(error.to_string()))?;
+
+        // Write the chunk file
+        let file_name = format!("chunk_{:08}.bcs.lz4", self.chunks.len());
+        write_file_atomically(&self.container_dir.join(&file_name), &compressed_bytes)?;
+
+        self.chunks.push(StateSnapshotContainerChunk {
+            file_name,
+            first_index: chunk.first_index,
+            last_index: chunk.last_index,
+            checksum: HashValue::sha3_256_of(&compressed_bytes),
+        });
+        self.num_state_values = chunk.last_index + 1;
+
+        Ok(())
+    }
+
+    /// Writes the manifest (completing the container) and returns it
+    pub fn finish(self) -> Result<StateSnapshotContainerManifest> {
+        let manifest = StateSnapshotContainerManifest {
+            format_version: STATE_SNAPSHOT_CONTAINER_FORMAT_VERSION,
+            version: self.version,
+            root_hash: self.root_hash,
+            num_state_values: self.num_state_values,
+            chunks: self.chunks,
+        };
+        let manifest_bytes = serde_json::to_vec_pretty(&manifest)
+            .map_err(|error| AptosDbError::Other(error.to_string()))?;
+        write_file_atomically(
+            &self.container_dir.join(MANIFEST_FILE_NAME),
+            &manifest_bytes,
+        )?;
+
+        Ok(manifest)
+    }
+}
+
+/// Reads a state snapshot container. All chunk files are verified against
+/// the checksums in the manifest when they are read.
+pub struct StateSnapshotContainerReader {
+    container_dir: PathBuf,
+    manifest: StateSnapshotContainerManifest,
+}
+
+impl StateSnapshotContainerReader {
+    /// Opens the container in the given directory
+    pub fn open(container_dir: &Path) -> Result<Self> {
+        let manifest_bytes = fs::read(container_dir.join(MANIFEST_FILE_NAME))?;
+        let manifest: StateSnapshotContainerManifest = serde_json::from_slice(&manifest_bytes)
+            .map_err(|error| AptosDbError::Other(format!("Invalid manifest: {}", error)))?;
+        ensure!(
+            manifest.format_version == STATE_SNAPSHOT_CONTAINER_FORMAT_VERSION,
+            "Unsupported state snapshot container format version: {}. Supported version: {}",
+            manifest.format_version,
+            STATE_SNAPSHOT_CONTAINER_FORMAT_VERSION
+        );
+
+        Ok(Self {
+            container_dir: container_dir.to_path_buf(),
+            manifest,
+        })
+    }
+
+    pub fn manifest(&self) -> &StateSnapshotContainerManifest {
+        &self.manifest
+    }
+
+    /// Reads and verifies the chunk at the given position in the manifest
+    pub fn read_chunk(&self, chunk_index: usize) -> Result<StateValueChunkWithProof> {
+        let chunk = self.manifest.chunks.get(chunk_index).ok_or_else(|| {
+            AptosDbError::NotFound(format!("State snapshot container chunk {}", chunk_index))
+        })?;
+
+        // Read the chunk file and verify the checksum
+        let compressed_bytes = fs::read(self.container_dir.join(&chunk.file_name))?;
+        let checksum = HashValue::sha3_256_of(&compressed_bytes);
+        ensure!(
+            checksum == chunk.checksum,
+            "The checksum of chunk file {} does not match the manifest! Expected: {}, found: {}",
+            chunk.file_name,
+            chunk.checksum,
+            checksum
+        );
+
+        // Decompress and deserialize the chunk
+        let chunk_bytes = aptos_compression::decompress(
+            &compressed_bytes,
+            CompressionClient::StateSnapshot,
+            MAX_CHUNK_FILE_BYTES,
+        )
+        .map_err(|error| AptosDbError::Other(error.to_string()))?;
+        let state_value_chunk: StateValueChunkWithProof = bcs::from_bytes(&chunk_bytes)?;
+
+        // Verify the chunk matches the manifest
+        ensure!(
+            state_value_chunk.root_hash == self.manifest.root_hash
+                && state_value_chunk.first_index == chunk.first_index
+                && state_value_chunk.last_index == chunk.last_index,
+            "The contents of chunk file {} do not match the manifest!",
+            chunk.file_name
+        );
+
+        Ok(state_value_chunk)
+    }
+
+    /// Returns an iterator over all (verified) chunks in the container
+    pub fn chunks(&self) -> impl Iterator<Item = Result<StateValueChunkWithProof>> + '_ {
+        (0..self.manifest.chunks.len()).map(|chunk_index| self.read_chunk(chunk_index))
+    }
+}
+
+/// Exports the state snapshot at the given version (from the given
+/// database) into a new container, using the specified chunk size.
+pub fn export_state_snapshot(
+    db: &dyn DbReader,
+    version: Version,
+    container_dir: &Path,
+    chunk_size: usize,
+) -> Result<StateSnapshotContainerManifest> {
+    ensure!(chunk_size > 0, "The chunk size must be greater than zero!");
+    let num_state_values = db.get_state_leaf_count(version)?;
+    ensure!(
+        num_state_values > 0,
+        "There are no state values to export at version {}!",
+        version
+    );
+
+    // Create the container writer using the root hash of the first chunk
+    let first_chunk = db.get_state_value_chunk_with_proof(version, 0, chunk_size)?;
+    let mut writer =
+        StateSnapshotContainerWriter::new(container_dir, version, first_chunk.root_hash)?;
+
+    // Export all chunks
+    let mut next_chunk = Some(first_chunk);
+    while let Some(chunk) = next_chunk.take() {
+        writer.write_chunk(&chunk)?;
+
+        let next_index = chunk.last_index as usize + 1;
+        info!(
+            "Exported {} of {} state values at version {}.",
+            next_index, num_state_values, version
+        );
+        if next_index < num_state_values {
+            next_chunk =
+                Some(db.get_state_value_chunk_with_proof(version, next_index, chunk_size)?);
+        }
+    }
+
+    writer.finish()
+}
+
+/// Imports all chunks in the container into the given snapshot receiver.
+/// Each chunk is verified against the root hash by the receiver.
+pub fn import_state_snapshot(
+    container: &StateSnapshotContainerReader,
+    mut receiver: Box<dyn StateSnapshotReceiver<StateKey, StateValue>>,
+) -> Result<()> {
+    for chunk in container.chunks() {
+        let chunk = chunk?;
+        let last_index = chunk.last_index;
+        receiver.add_chunk(chunk.raw_values, chunk.proof)?;
+
+        info!(
+            "Imported {} of {} state values at version {}.",
+            last_index + 1,
+            container.manifest().num_state_values,
+            container.manifest().version
+        );
+    }
+
+    receiver.finish_box()
+}
+
+/// Writes the given bytes to a temporary file and renames it to the given
+/// path (to avoid leaving partially written files behind on failures).
+pub(crate) fn write_file_atomically(path: &Path, bytes: &[u8]) -> Result<()> {
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, bytes)?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
@@ -290,8 +290,10 @@ impl StateStore {
         hack_for_tests: bool,
         empty_buffered_state_for_restore: bool,
         skip_usage: bool,
+        is_secondary: bool,
     ) -> Self {
-        if !hack_for_tests && !empty_buffered_state_for_restore {
+        // Note: secondary instances are read-only, so the commit progress can't be synced
+        if !hack_for_tests && !empty_buffered_state_for_restore && !is_secondary {
             Self::sync_commit_progress(
                 Arc::clone(&ledger_db),
                 Arc::clone(&state_kv_db),
@@ -317,7 +319,7 @@ impl StateStore {
             Self::create_buffered_state_from_latest_snapshot(
                 &state_db,
                 buffered_state_target_items,
-                hack_for_tests,
+                /*skip_write_set_replay=*/ hack_for_tests || is_secondary,
                 /*check_max_versions_after_snapshot=*/ true,
             )
             .expect("buffered state creation failed.")
@@ -422,7 +424,7 @@ impl StateStore {
             skip_usage: false,
         });
         let (buffered_state, _) = Self::create_buffered_state_from_latest_snapshot(
-            &state_db, 0, /*hack_for_tests=*/ false,
+            &state_db, 0, /*skip_write_set_replay=*/ false,
             /*check_max_versions_after_snapshot=*/ false,
         )?;
         Ok(buffered_state.current_state().base_version)
@@ -431,7 +433,7 @@ impl StateStore {
     fn create_buffered_state_from_latest_snapshot(
         state_db: &Arc<StateDb>,
         buffered_state_target_items: usize,
-        hack_for_tests: bool,
+        skip_write_set_replay: bool,
         check_max_versions_after_snapshot: bool,
     ) -> Result<(BufferedState, SmtAncestors<StateValue>)> {
         let num_transactions = state_db
@@ -469,8 +471,9 @@ impl StateStore {
             buffered_state_target_items,
         );
 
-        // In some backup-restore tests we hope to open the db without consistency check.
-        if hack_for_tests {
+        // In some backup-restore tests we hope to open the db without consistency check. Also,
+        // secondary instances can't replay the write sets (which would write to the db).
+        if skip_write_set_replay {
             return Ok((buffered_state, smt_ancestors));
         }
 
@@ -563,6 +566,21 @@ impl StateStore {
         *self.smt_ancestors.lock() = smt_ancestors;
     }
 
+    /// Resets the buffered state to the latest snapshot in the db, without replaying the write
+    /// sets committed after the snapshot (which would require writing to the db). This is used
+    /// by secondary instances, after catching up with the primary instance.
+    pub(crate) fn reset_to_latest_snapshot(&self) -> Result<()> {
+        let (buffered_state, smt_ancestors) = Self::create_buffered_state_from_latest_snapshot(
+            &self.state_db,
+            self.buffered_state_target_items,
+            /*skip_write_set_replay=*/ true,
+            /*check_max_versions_after_snapshot=*/ false,
+        )?;
+        *self.buffered_state.lock() = buffered_state;
+        *self.smt_ancestors.lock() = smt_ancestors;
+        Ok(())
+    }
+
     pub fn buffered_state(&self) -> &Mutex<BufferedState> {
         &self.buffered_state
     }
@@ -0,0 +1,173 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    state_snapshot_container::write_file_atomically,
+    tiered_storage::object_store::ObjectStoreBackend,
+};
+use aptos_config::config::ColdStorageBackendConfig;
+use aptos_storage_interface::{db_ensure as ensure, AptosDbError, Result};
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::Arc,
+};
+
+/// The environment variable holding the segment name (for command backends)
+const SEGMENT_NAME_ENV_VAR: &str = "SEGMENT_NAME";
+
+/// A (pluggable) backend that stores the cold storage segments
+pub(crate) trait ColdStorageBackend: Send + Sync {
+    /// Stores the given segment bytes under the segment name
+    fn put_segment(&self, segment_name: &str, bytes: &[u8]) -> Result<()>;
+
+    /// Returns the bytes of the segment with the given name
+    fn get_segment(&self, segment_name: &str) -> Result<Vec<u8>>;
+}
+
+/// Creates the cold storage backend specified by the config. Relative
+/// directories are resolved against the given storage directory.
+pub(crate) fn create_backend(
+    config: &ColdStorageBackendConfig,
+    storage_dir: &Path,
+) -> Result<Arc<dyn ColdStorageBackend>> {
+    let backend: Arc<dyn ColdStorageBackend> = match config {
+        ColdStorageBackendConfig::LocalDirectory { path } => {
+            Arc::new(LocalDirectoryBackend::new(storage_dir.join(path))?)
+        },
+        ColdStorageBackendConfig::Command {
+            put_segment_command,
+            get_segment_command,
+        } => Arc::new(CommandBackend::new(
+            put_segment_command.clone(),
+            get_segment_command.clone(),
+        )),
+        ColdStorageBackendConfig::S3 {
+            bucket,
+            region,
+            prefix,
+            endpoint,
+        } => Arc::new(ObjectStoreBackend::new_s3(
+            bucket.clone(),
+            region.clone(),
+            prefix.clone(),
+            endpoint.clone(),
+        )?),
+        ColdStorageBackendConfig::Gcs { bucket, prefix } => {
+            Arc::new(ObjectStoreBackend::new_gcs(bucket.clone(), prefix.clone())?)
+        },
+    };
+    Ok(backend)
+}
+
+/// A backend that stores the segments as files in a local (or mounted) directory
+pub(crate) struct LocalDirectoryBackend {
+    dir: PathBuf,
+}
+
+impl LocalDirectoryBackend {
+    pub(crate) fn new(dir: PathBuf) -> Result<Self> {
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+}
+
+impl ColdStorageBackend for LocalDirectoryBackend {
+    fn put_segment(&self, segment_name: &str, bytes: &[u8]) -> Result<()> {
+        write_file_atomically(&self.dir.join(segment_name), bytes)
+    }
+
+    fn get_segment(&self, segment_name: &str) -> Result<Vec<u8>> {
+        Ok(fs::read(self.dir.join(segment_name))?)
+    }
+}
+
+/// A backend that stores and fetches the segments using shell commands (e.g.,
+/// the S3 or GCS CLIs). The segment name is passed to the commands via the
+/// `$SEGMENT_NAME` environment variable.
+pub(crate) struct CommandBackend {
+    put_segment_command: String,
+    get_segment_command: String,
+}
+
+impl CommandBackend {
+    pub(crate) fn new(put_segment_command: String, get_segment_command: String) -> Self {
+        Self {
+            put_segment_command,
+            get_segment_command,
+        }
+    }
+
+    /// Spawns the given command for the segment
+    fn spawn(
+        &self,
+        command: &str,
+        segment_name: &str,
+        stdin: Stdio,
+    ) -> Result<std::process::Child> {
+        Command::new("bash")
+            .args(["-c", command])
+            .env(SEGMENT_NAME_ENV_VAR, segment_name)
+            .stdin(stdin)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|error| {
+                AptosDbError::Other(format!(
+                    "Failed to spawn cold storage command {}: {}",
+                    command, error
+                ))
+            })
+    }
+}
+
+impl ColdStorageBackend for CommandBackend {
+    fn put_segment(&self, segment_name: &str, bytes: &[u8]) -> Result<()> {
+        let mut child = self.spawn(&self.put_segment_command, segment_name, Stdio::piped())?;
+        let mut stdin = child
+            .stdin
+            .take()
+            .expect("The stdin of the command is piped!");
+
+        // Write the segment on a separate thread while the output is drained, otherwise
+        // the command can block on a full stdout/stderr pipe (and never consume its stdin).
+        let (write_result, output) = std::thread::scope(|scope| {
+            let writer = scope.spawn(move || stdin.write_all(bytes)); // stdin is closed on drop
+            let output = child.wait_with_output();
+            let write_result = writer
+                .join()
+                .expect("The cold storage stdin writer should not panic!");
+            (write_result, output)
+        });
+        let output = output?;
+        ensure!(
+            write_result.is_ok() || !output.status.success(),
+            "Failed to write cold storage segment {} to the command! Error: {:?}",
+            segment_name,
+            write_result
+        );
+        ensure!(
+            output.status.success(),
+            "Failed to put cold storage segment {}! Status: {}, stderr: {}",
+            segment_name,
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Ok(())
+    }
+
+    fn get_segment(&self, segment_name: &str) -> Result<Vec<u8>> {
+        let child = self.spawn(&self.get_segment_command, segment_name, Stdio::null())?;
+        let output = child.wait_with_output()?;
+        ensure!(
+            output.status.success(),
+            "Failed to get cold storage segment {}! Status: {}, stderr: {}",
+            segment_name,
+            output.status,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        Ok(output.stdout)
+    }
+}
@@ -0,0 +1,497 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Tiered storage offloads cold ledger data (i.e., transactions, events and write sets older
+//! than a configurable version horizon) to a cold storage backend (e.g., an object store), in
+//! compressed segments. Migrated versions are removed from the ledger db, and reads of those
+//! versions are transparently served from cold storage (just slower).
+//!
+//! Note: only the transactions, events and write sets themselves are migrated. All indices
+//! (except for the write set index by state key, which is pruned with the write sets),
+//! transaction infos and accumulators are kept in the ledger db, so proofs can still be served
+//! without touching cold storage.
+
+use crate::{
+    ledger_db::{ledger_metadata_db::LedgerMetadataDb, write_set_db::WriteSetDb, LedgerDb},
+    metrics::{
+        COLD_STORAGE_MIGRATION_PROGRESS, COLD_STORAGE_SEGMENT_FETCHES, OTHER_TIMERS_SECONDS,
+    },
+    schema::cold_segment::ColdSegmentInfo,
+};
+use aptos_compression::client::CompressionClient;
+use aptos_config::config::TieredStorageConfig;
+use aptos_crypto::HashValue;
+use aptos_infallible::Mutex;
+use aptos_logger::prelude::*;
+use aptos_metrics_core::TimerHelper;
+use aptos_schemadb::SchemaBatch;
+use aptos_storage_interface::{db_ensure as ensure, AptosDbError, Result};
+use aptos_types::{
+    contract_event::ContractEvent,
+    transaction::{Transaction, Version},
+    write_set::WriteSet,
+};
+use backend::ColdStorageBackend;
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        mpsc, Arc,
+    },
+    thread,
+    thread::JoinHandle,
+    time::Duration,
+};
+
+pub(crate) mod backend;
+pub(crate) mod object_store;
+
+/// The max size of a (raw or compressed) segment
+const MAX_SEGMENT_BYTES: usize = 1024 * 1024 * 1024; // 1 GiB
+
+/// A segment of consecutive versions of ledger data, as stored in cold storage
+#[derive(Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct ColdSegment {
+    first_version: Version,
+    transactions: Vec<Transaction>,
+    events: Vec<Vec<ContractEvent>>,
+    write_sets: Vec<WriteSet>,
+}
+
+impl ColdSegment {
+    /// Serializes and compresses the segment
+    fn encode(&self) -> Result<Vec<u8>> {
+        let bytes = bcs::to_bytes(self)?;
+        aptos_compression::compress(bytes, CompressionClient::ColdStorage, MAX_SEGMENT_BYTES)
+            .map_err(|error| AptosDbError::Other(error.to_string()))
+    }
+
+    /// Decompresses and deserializes the segment
+    fn decode(compressed_bytes: Vec<u8>) -> Result<Self> {
+        let bytes = aptos_compression::decompress(
+            &compressed_bytes,
+            CompressionClient::ColdStorage,
+            MAX_SEGMENT_BYTES,
+        )
+        .map_err(|error| AptosDbError::Other(error.to_string()))?;
+        Ok(bcs::from_bytes(&bytes)?)
+    }
+
+    fn num_versions(&self) -> u64 {
+        self.transactions.len() as u64
+    }
+
+    /// Returns the index of the given version in the segment
+    fn index_of(&self, version: Version) -> Result<usize> {
+        ensure!(
+            version >= self.first_version && version - self.first_version < self.num_versions(),
+            "Version {} is not in the cold segment starting at version {}.",
+            version,
+            self.first_version
+        );
+        Ok((version - self.first_version) as usize)
+    }
+}
+
+/// Migrates cold ledger data to the cold storage backend, and serves reads of migrated versions
+pub(crate) struct TieredStorage {
+    ledger_db: Arc<LedgerDb>,
+    backend: Arc<dyn ColdStorageBackend>,
+    config: TieredStorageConfig,
+    /// The first version that has not been migrated to cold storage (i.e., all versions below
+    /// this one are served from cold storage).
+    migration_progress: AtomicU64,
+    /// The most recently read segments (keyed by first version)
+    segment_cache: Mutex<LruCache<Version, Arc<ColdSegment>>>,
+}
+
+impl TieredStorage {
+    pub(crate) fn new(
+        ledger_db: Arc<LedgerDb>,
+        backend: Arc<dyn ColdStorageBackend>,
+        config: TieredStorageConfig,
+    ) -> Result<Self> {
+        let migration_progress = ledger_db
+            .metadata_db()
+            .get_cold_storage_migration_progress()?;
+        COLD_STORAGE_MIGRATION_PROGRESS.set(migration_progress as i64);
+
+        let tiered_storage = Self {
+            ledger_db,
+            backend,
+            segment_cache: Mutex::new(LruCache::new(config.max_num_cached_segments.max(1))),
+            config,
+            migration_progress: AtomicU64::new(migration_progress),
+        };
+
+        // The hot data is only deleted after the migration progress is persisted. So, if the
+        // node crashed in between, the hot data of the last migrated segment may still exist.
+        if let Some((first_version, segment_info)) = tiered_storage
+            .ledger_db
+            .metadata_db()
+            .get_last_cold_segment_info()?
+        {
+            tiered_storage
+                .delete_hot_data(first_version, segment_info.end_version(first_version))?;
+        }
+
+        Ok(tiered_storage)
+    }
+
+    /// Returns the first version that has not been migrated to cold storage
+    pub(crate) fn get_migration_progress(&self) -> Version {
+        self.migration_progress.load(Ordering::Acquire)
+    }
+
+    /// Returns true iff the given version has been migrated to cold storage
+    pub(crate) fn is_cold(&self, version: Version) -> bool {
+        version < self.get_migration_progress()
+    }
+
+    /// Returns the transaction at the given (cold) version
+    pub(crate) fn get_transaction(&self, version: Version) -> Result<Transaction> {
+        let segment = self.get_segment(version)?;
+        Ok(segment.transactions[segment.index_of(version)?].clone())
+    }
+
+    /// Returns the events at the given (cold) version
+    pub(crate) fn get_events(&self, version: Version) -> Result<Vec<ContractEvent>> {
+        let segment = self.get_segment(version)?;
+        Ok(segment.events[segment.index_of(version)?].clone())
+    }
+
+    /// Returns the write set at the given (cold) version
+    pub(crate) fn get_write_set(&self, version: Version) -> Result<WriteSet> {
+        let segment = self.get_segment(version)?;
+        Ok(segment.write_sets[segment.index_of(version)?].clone())
+    }
+
+    /// Returns the transactions in the given range of versions, truncated at the
+    /// migration progress (i.e., only cold versions are returned).
+    pub(crate) fn get_transactions(
+        &self,
+        start_version: Version,
+        num_versions: u64,
+    ) -> Result<Vec<Transaction>> {
+        self.get_cold_range(start_version, num_versions, |segment, index| {
+            segment.transactions[index].clone()
+        })
+    }
+
+    /// Returns the events in the given range of versions, truncated at the
+    /// migration progress (i.e., only cold versions are returned).
+    pub(crate) fn get_events_range(
+        &self,
+        start_version: Version,
+        num_versions: u64,
+    ) -> Result<Vec<Vec<ContractEvent>>> {
+        self.get_cold_range(start_version, num_versions, |segment, index| {
+            segment.events[index].clone()
+        })
+    }
+
+    /// Returns the write sets in the given range of versions, truncated at the
+    /// migration progress (i.e., only cold versions are returned).
+    pub(crate) fn get_write_sets(
+        &self,
+        start_version: Version,
+        num_versions: u64,
+    ) -> Result<Vec<WriteSet>> {
+        self.get_cold_range(start_version, num_versions, |segment, index| {
+            segment.write_sets[index].clone()
+        })
+    }
+
+    fn get_cold_range<T>(
+        &self,
+        start_version: Version,
+        num_versions: u64,
+        get_item: impl Fn(&ColdSegment, usize) -> T,
+    ) -> Result<Vec<T>> {
+        let end_version = start_version
+            .saturating_add(num_versions)
+            .min(self.get_migration_progress());
+
+        let mut items = Vec::with_capacity(end_version.saturating_sub(start_version) as usize);
+        let mut version = start_version;
+        while version < end_version {
+            let segment = self.get_segment(version)?;
+            let segment_end_version =
+                (segment.first_version + segment.num_versions()).min(end_version);
+            for version in version..segment_end_version {
+                items.push(get_item(&segment, segment.index_of(version)?));
+            }
+            version = segment_end_version;
+        }
+        Ok(items)
+    }
+
+    /// Returns the segment holding the given (cold) version
+    fn get_segment(&self, version: Version) -> Result<Arc<ColdSegment>> {
+        ensure!(
+            self.is_cold(version),
+            "Version {} has not been migrated to cold storage.",
+            version
+        );
+
+        // Identify the segment holding the version
+        let (first_version, segment_info) = self
+            .ledger_db
+            .metadata_db()
+            .get_cold_segment_info(version)?
+            .ok_or_else(|| AptosDbError::NotFound(format!("Cold segment for version {version}")))?;
+
+        // Check the cache before fetching the segment from the backend
+        if let Some(segment) = self.segment_cache.lock().get(&first_version) {
+            return Ok(Arc::clone(segment));
+        }
+
+        let _timer = OTHER_TIMERS_SECONDS.timer_with(&["fetch_cold_segment"]);
+        COLD_STORAGE_SEGMENT_FETCHES.inc();
+        let compressed_bytes = self.backend.get_segment(&segment_info.segment_name)?;
+        let checksum = HashValue::sha3_256_of(&compressed_bytes);
+        ensure!(
+            checksum == segment_info.checksum,
+            "The checksum of cold segment {} does not match! Expected: {}, found: {}",
+            segment_info.segment_name,
+            segment_info.checksum,
+            checksum
+        );
+        let segment = ColdSegment::decode(compressed_bytes)?;
+        ensure!(
+            segment.first_version == first_version
+                && segment.num_versions() == segment_info.num_versions
+                && segment.events.len() == segment.transactions.len()
+                && segment.write_sets.len() == segment.transactions.len(),
+            "Cold segment {} is malformed!",
+            segment_info.segment_name
+        );
+
+        let segment = Arc::new(segment);
+        self.segment_cache
+            .lock()
+            .put(first_version, Arc::clone(&segment));
+        Ok(segment)
+    }
+
+    /// Migrates all complete segments older than the cold version horizon (relative to the
+    /// given latest version) to cold storage. Returns the number of migrated segments.
+    ///
+    /// Note: versions that have already been pruned by the ledger pruner can't be migrated,
+    /// so migration always starts at (or after) the min readable version of the ledger.
+    pub(crate) fn migrate(&self, latest_version: Version) -> Result<usize> {
+        let mut num_migrated_segments = 0;
+        loop {
+            let first_version = self.skip_pruned_versions()?;
+            let end_version = first_version + self.config.segment_num_versions;
+            let hot_version_start =
+                (latest_version + 1).saturating_sub(self.config.cold_version_horizon);
+            if end_version > hot_version_start {
+                return Ok(num_migrated_segments);
+            }
+
+            if let Err(error) = self.migrate_segment(first_version, end_version) {
+                // The segment might have been pruned while it was being migrated
+                if self.get_min_readable_version()? > first_version {
+                    continue;
+                }
+                return Err(error);
+            }
+            num_migrated_segments += 1;
+        }
+    }
+
+    /// Advances the migration progress past the versions that have already been pruned by the
+    /// ledger pruner (if any). Returns the (new) migration progress.
+    fn skip_pruned_versions(&self) -> Result<Version> {
+        let migration_progress = self.get_migration_progress();
+        let min_readable_version = self.get_min_readable_version()?;
+        if migration_progress >= min_readable_version {
+            return Ok(migration_progress);
+        }
+
+        let batch = SchemaBatch::new();
+        LedgerMetadataDb::put_cold_storage_migration_progress(min_readable_version, &batch)?;
+        self.ledger_db.metadata_db().write_schemas(batch)?;
+        self.migration_progress
+            .store(min_readable_version, Ordering::Release);
+        COLD_STORAGE_MIGRATION_PROGRESS.set(min_readable_version as i64);
+        info!(
+            migration_progress = migration_progress,
+            min_readable_version = min_readable_version,
+            "Skipped pruned versions during cold storage migration."
+        );
+        Ok(min_readable_version)
+    }
+
+    /// Returns the min readable version of the ledger (i.e., the ledger pruner progress)
+    fn get_min_readable_version(&self) -> Result<Version> {
+        match self.ledger_db.metadata_db().get_pruner_progress() {
+            Ok(version) => Ok(version),
+            Err(AptosDbError::NotFound(_)) => Ok(0), // Nothing has been pruned yet
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Migrates cold data relative to the latest committed version (if any)
+    fn migrate_to_latest_version(&self) -> Result<usize> {
+        match self.ledger_db.metadata_db().get_latest_version() {
+            Ok(latest_version) => self.migrate(latest_version),
+            Err(AptosDbError::NotFound(_)) => Ok(0), // Nothing has been committed yet
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Migrates the ledger data in [first_version, end_version) to cold storage
+    fn migrate_segment(&self, first_version: Version, end_version: Version) -> Result<()> {
+        let _timer = OTHER_TIMERS_SECONDS.timer_with(&["migrate_cold_segment"]);
+        let num_versions = end_version - first_version;
+
+        // Read the hot data
+        let transactions = self
+            .ledger_db
+            .transaction_db()
+            .get_transaction_iter(first_version, num_versions as usize)?
+            .collect::<Result<Vec<_>>>()?;
+        let events = self
+            .ledger_db
+            .event_db()
+            .get_events_by_version_iter(first_version, num_versions as usize)?
+            .collect::<Result<Vec<_>>>()?;
+        let write_sets = self
+            .ledger_db
+            .write_set_db()
+            .get_write_sets(first_version, end_version)?;
+        ensure!(
+            transactions.len() as u64 == num_versions,
+            "Transactions missing for versions [{}, {}), found {}.",
+            first_version,
+            end_version,
+            transactions.len()
+        );
+        let segment = ColdSegment {
+            first_version,
+            transactions,
+            events,
+            write_sets,
+        };
+
+        // Store the segment in the cold storage backend
+        let compressed_bytes = segment.encode()?;
+        let segment_info = ColdSegmentInfo {
+            num_versions,
+            segment_name: format!("ledger_segment_{:020}.bcs.lz4", first_version),
+            checksum: HashValue::sha3_256_of(&compressed_bytes),
+        };
+        self.backend
+            .put_segment(&segment_info.segment_name, &compressed_bytes)?;
+
+        // Persist the segment info and the migration progress
+        let batch = SchemaBatch::new();
+        LedgerMetadataDb::put_cold_segment_info(first_version, &segment_info, &batch)?;
+        self.ledger_db.metadata_db().write_schemas(batch)?;
+        self.migration_progress
+            .store(end_version, Ordering::Release);
+        COLD_STORAGE_MIGRATION_PROGRESS.set(end_version as i64);
+
+        // Delete the (now cold) hot data
+        self.delete_hot_data(first_version, end_version)?;
+
+        info!(
+            first_version = first_version,
+            end_version = end_version,
+            segment_name = segment_info.segment_name,
+            num_bytes = compressed_bytes.len(),
+            "Migrated ledger data to cold storage."
+        );
+        Ok(())
+    }
+
+    /// Deletes the transactions, events and write sets in [first_version, end_version) from
+    /// the ledger db. Note: indices are kept, so that cold data can still be looked up (except
+    /// for the write set index, which now only starts at `end_version`).
+    fn delete_hot_data(&self, first_version: Version, end_version: Version) -> Result<()> {
+        let batch = SchemaBatch::new();
+        self.ledger_db
+            .transaction_db()
+            .prune_transactions(first_version, end_version, &batch)?;
+        self.ledger_db.transaction_db().write_schemas(batch)?;
+
+        let batch = SchemaBatch::new();
+        self.ledger_db
+            .event_db()
+            .delete_events(first_version, end_version, &batch)?;
+        self.ledger_db.event_db().write_schemas(batch)?;
+
+        let write_set_db = self.ledger_db.write_set_db();
+        let batch = SchemaBatch::new();
+        write_set_db.prune_write_set_indices(first_version, end_version, &batch)?;
+        write_set_db.advance_index_start_version(end_version, &batch)?;
+        WriteSetDb::prune(first_version, end_version, &batch)?;
+        write_set_db.write_schemas(batch)
+    }
+}
+
+/// Periodically migrates cold ledger data to cold storage (on a dedicated thread)
+pub(crate) struct TieredStorageManager {
+    tiered_storage: Arc<TieredStorage>,
+    sender: Mutex<mpsc::Sender<()>>,
+    join_handle: Option<JoinHandle<()>>,
+}
+
+impl TieredStorageManager {
+    pub(crate) fn new(
+        ledger_db: Arc<LedgerDb>,
+        config: TieredStorageConfig,
+        storage_dir: &Path,
+    ) -> Result<Self> {
+        let backend = backend::create_backend(&config.backend, storage_dir)?;
+        let migration_interval = Duration::from_millis(config.migration_interval_ms);
+        let tiered_storage = Arc::new(TieredStorage::new(ledger_db, backend, config)?);
+
+        let (sender, receiver) = mpsc::channel();
+        let tiered_storage_for_migration = Arc::clone(&tiered_storage);
+        let join_handle = Some(
+            thread::Builder::new()
+                .name("cold-storage-migration".into())
+                .spawn(move || loop {
+                    match receiver.recv_timeout(migration_interval) {
+                        Ok(_) => break,
+                        Err(mpsc::RecvTimeoutError::Timeout) => (),
+                        Err(mpsc::RecvTimeoutError::Disconnected) => break,
+                    }
+                    if let Err(error) = tiered_storage_for_migration.migrate_to_latest_version() {
+                        warn!(
+                            error = ?error,
+                            "Failed to migrate ledger data to cold storage."
+                        );
+                    }
+                })
+                .expect("Failed to spawn the cold storage migration thread!"),
+        );
+
+        Ok(Self {
+            tiered_storage,
+            sender: Mutex::new(sender),
+            join_handle,
+        })
+    }
+
+    pub(crate) fn tiered_storage(&self) -> &TieredStorage {
+        &self.tiered_storage
+    }
+}
+
+impl Drop for TieredStorageManager {
+    fn drop(&mut self) {
+        // Notify the migration thread to exit
+        self.sender.lock().send(()).unwrap();
+        self.join_handle
+            .take()
+            .expect("Cold storage migration thread must exist.")
+            .join()
+            .expect("Cold storage migration thread should join peacefully.");
+    }
+}
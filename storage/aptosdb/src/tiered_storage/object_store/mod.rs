@@ -0,0 +1,304 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Object store backends (i.e., Amazon S3, S3-compatible stores and Google Cloud Storage) for
+//! tiered storage. All stores are accessed via the S3-compatible REST API (GCS via its XML API,
+//! using HMAC keys), and requests are authenticated using AWS Signature Version 4.
+
+use crate::tiered_storage::backend::ColdStorageBackend;
+use aptos_storage_interface::{AptosDbError, Result};
+use reqwest::{
+    blocking::{Client, Response},
+    Method, StatusCode, Url,
+};
+use ring::{digest, hmac};
+use std::{collections::BTreeMap, env, time::Duration};
+
+/// The endpoint of the GCS XML API
+const GCS_ENDPOINT: &str = "https://storage.googleapis.com";
+/// The region used to sign GCS requests
+const GCS_REGION: &str = "auto";
+
+/// The environment variables holding the object store credentials
+const AWS_ACCESS_KEY_ID_ENV_VAR: &str = "AWS_ACCESS_KEY_ID";
+const AWS_SECRET_ACCESS_KEY_ENV_VAR: &str = "AWS_SECRET_ACCESS_KEY";
+const AWS_SESSION_TOKEN_ENV_VAR: &str = "AWS_SESSION_TOKEN";
+const GCS_HMAC_ACCESS_KEY_ID_ENV_VAR: &str = "GCS_HMAC_ACCESS_KEY_ID";
+const GCS_HMAC_SECRET_ENV_VAR: &str = "GCS_HMAC_SECRET";
+
+/// The timeout of a single object store request (segments can be large)
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// The max number of bytes of an error response that are logged
+const MAX_ERROR_RESPONSE_BYTES: usize = 1024;
+
+/// The credentials used to sign object store requests
+#[derive(Clone)]
+pub(crate) struct ObjectStoreCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub session_token: Option<String>,
+}
+
+impl ObjectStoreCredentials {
+    /// Reads the credentials from the given environment variables
+    fn from_env(
+        access_key_id_env_var: &str,
+        secret_access_key_env_var: &str,
+        session_token_env_var: Option<&str>,
+    ) -> Result<Self> {
+        let read_env_var = |env_var: &str| {
+            env::var(env_var).map_err(|error| {
+                AptosDbError::Other(format!(
+                    "Failed to read the object store credentials from {}: {}",
+                    env_var, error
+                ))
+            })
+        };
+        Ok(Self {
+            access_key_id: read_env_var(access_key_id_env_var)?,
+            secret_access_key: read_env_var(secret_access_key_env_var)?,
+            session_token: session_token_env_var.and_then(|env_var| env::var(env_var).ok()),
+        })
+    }
+}
+
+/// A backend that stores the segments as objects in an (S3-compatible) object store
+pub(crate) struct ObjectStoreBackend {
+    client: Client,
+    endpoint: Url,
+    host: String,
+    bucket: String,
+    prefix: String,
+    region: String,
+    credentials: ObjectStoreCredentials,
+}
+
+impl ObjectStoreBackend {
+    /// Creates a backend for the given S3 bucket (using the credentials in the environment)
+    pub(crate) fn new_s3(
+        bucket: String,
+        region: String,
+        prefix: String,
+        endpoint: Option<String>,
+    ) -> Result<Self> {
+        let endpoint = endpoint.unwrap_or_else(|| format!("https://s3.{}.amazonaws.com", region));
+        let credentials = ObjectStoreCredentials::from_env(
+            AWS_ACCESS_KEY_ID_ENV_VAR,
+            AWS_SECRET_ACCESS_KEY_ENV_VAR,
+            Some(AWS_SESSION_TOKEN_ENV_VAR),
+        )?;
+        Self::new(&endpoint, bucket, prefix, region, credentials)
+    }
+
+    /// Creates a backend for the given GCS bucket (using the HMAC key in the environment)
+    pub(crate) fn new_gcs(bucket: String, prefix: String) -> Result<Self> {
+        let credentials = ObjectStoreCredentials::from_env(
+            GCS_HMAC_ACCESS_KEY_ID_ENV_VAR,
+            GCS_HMAC_SECRET_ENV_VAR,
+            None,
+        )?;
+        Self::new(GCS_ENDPOINT, bucket, prefix, GCS_REGION.into(), credentials)
+    }
+
+    pub(crate) fn new(
+        endpoint: &str,
+        bucket: String,
+        prefix: String,
+        region: String,
+        credentials: ObjectStoreCredentials,
+    ) -> Result<Self> {
+        let endpoint = Url::parse(endpoint).map_err(|error| {
+            AptosDbError::Other(format!(
+                "Invalid object store endpoint {}: {}",
+                endpoint, error
+            ))
+        })?;
+        let host = match (endpoint.host_str(), endpoint.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            (None, _) => {
+                return Err(AptosDbError::Other(format!(
+                    "The object store endpoint {} has no host!",
+                    endpoint
+                )))
+            },
+        };
+        let client = Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .map_err(|error| AptosDbError::Other(error.to_string()))?;
+
+        Ok(Self {
+            client,
+            endpoint,
+            host,
+            bucket,
+            prefix,
+            region,
+            credentials,
+        })
+    }
+
+    /// Returns the (path-style) path of the object holding the given segment
+    fn object_path(&self, segment_name: &str) -> String {
+        format!(
+            "/{}/{}",
+            uri_encode(&self.bucket, false),
+            uri_encode(&format!("{}{}", self.prefix, segment_name), true)
+        )
+    }
+
+    /// Sends a signed request for the given segment
+    fn send_request(&self, method: Method, segment_name: &str, body: Vec<u8>) -> Result<Response> {
+        let path = self.object_path(segment_name);
+        let payload_hash = hex::encode(digest::digest(&digest::SHA256, &body));
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+        let mut headers = BTreeMap::new();
+        headers.insert("host".to_string(), self.host.clone());
+        headers.insert("x-amz-content-sha256".to_string(), payload_hash.clone());
+        headers.insert("x-amz-date".to_string(), amz_date.clone());
+        if let Some(session_token) = &self.credentials.session_token {
+            headers.insert("x-amz-security-token".to_string(), session_token.clone());
+        }
+        let authorization = sign_request(
+            &self.credentials,
+            &self.region,
+            method.as_str(),
+            &path,
+            &headers,
+            &payload_hash,
+            &amz_date,
+        );
+
+        let mut url = self.endpoint.clone();
+        url.set_path(&path);
+        let mut request = self
+            .client
+            .request(method, url)
+            .header("authorization", authorization)
+            .body(body);
+        for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request.send().map_err(|error| {
+            AptosDbError::Other(format!(
+                "Object store request for segment {} failed: {}",
+                segment_name, error
+            ))
+        })
+    }
+}
+
+impl ColdStorageBackend for ObjectStoreBackend {
+    fn put_segment(&self, segment_name: &str, bytes: &[u8]) -> Result<()> {
+        let response = self.send_request(Method::PUT, segment_name, bytes.to_vec())?;
+        check_response_status(response, segment_name).map(|_| ())
+    }
+
+    fn get_segment(&self, segment_name: &str) -> Result<Vec<u8>> {
+        let response = self.send_request(Method::GET, segment_name, vec![])?;
+        if response.status() == StatusCode::NOT_FOUND {
+            return Err(AptosDbError::NotFound(format!(
+                "Cold storage segment {}",
+                segment_name
+            )));
+        }
+        let response = check_response_status(response, segment_name)?;
+        let bytes = response.bytes().map_err(|error| {
+            AptosDbError::Other(format!(
+                "Failed to read cold storage segment {}: {}",
+                segment_name, error
+            ))
+        })?;
+        Ok(bytes.to_vec())
+    }
+}
+
+/// Returns an error if the response is not successful
+fn check_response_status(response: Response, segment_name: &str) -> Result<Response> {
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+    let mut error_message = response.text().unwrap_or_default();
+    error_message.truncate(MAX_ERROR_RESPONSE_BYTES);
+    Err(AptosDbError::Other(format!(
+        "Object store request for segment {} failed! Status: {}, response: {}",
+        segment_name, status, error_message
+    )))
+}
+
+/// Signs the request using AWS Signature Version 4 (for the S3 service), and returns the value
+/// of the authorization header. The headers must have lowercase names and include the host.
+/// Note: requests with query strings are not supported.
+fn sign_request(
+    credentials: &ObjectStoreCredentials,
+    region: &str,
+    method: &str,
+    path: &str,
+    headers: &BTreeMap<String, String>,
+    payload_hash: &str,
+    amz_date: &str,
+) -> String {
+    let date = &amz_date[..8];
+    let signed_headers = headers.keys().cloned().collect::<Vec<_>>().join(";");
+    let canonical_headers: String = headers
+        .iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let canonical_request = format!(
+        "{}\n{}\n\n{}\n{}\n{}",
+        method, path, canonical_headers, signed_headers, payload_hash
+    );
+
+    let scope = format!("{}/{}/s3/aws4_request", date, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex::encode(digest::digest(
+            &digest::SHA256,
+            canonical_request.as_bytes()
+        ))
+    );
+
+    let signing_key = [region, "s3", "aws4_request"].iter().fold(
+        hmac_sha256(
+            format!("AWS4{}", credentials.secret_access_key).as_bytes(),
+            date.as_bytes(),
+        ),
+        |key, data| hmac_sha256(&key, data.as_bytes()),
+    );
+    let signature = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id, scope, signed_headers, signature
+    )
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+    hmac::sign(&key, data).as_ref().to_vec()
+}
+
+/// URI-encodes the given string (as required by AWS Signature Version 4). Slashes are only
+/// kept if `keep_slashes` is true (e.g., for object keys).
+fn uri_encode(input: &str, keep_slashes: bool) -> String {
+    let mut encoded = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            },
+            b'/' if keep_slashes => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod test;
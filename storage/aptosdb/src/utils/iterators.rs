@@ -6,6 +6,7 @@ use crate::{
         event::EventSchema, ledger_info::LedgerInfoSchema, state_value::StateValueSchema,
         state_value_index::StateValueIndexSchema,
         transaction_by_account::TransactionByAccountSchema,
+        write_set_by_state_key::WriteSetByStateKeySchema,
     },
     state_kv_db::StateKvDb,
 };
@@ -246,6 +247,70 @@ impl<'a> Iterator for PrefixedStateValueIterator<'a> {
     }
 }
 
+pub struct PrefixedWriteSetVersionIter<'a> {
+    inner: SchemaIterator<'a, WriteSetByStateKeySchema>,
+    key_prefix: StateKeyPrefix,
+    start_version: Version,
+    end_version: Version,
+    is_finished: bool,
+}
+
+impl<'a> PrefixedWriteSetVersionIter<'a> {
+    pub(crate) fn new(
+        inner: SchemaIterator<'a, WriteSetByStateKeySchema>,
+        key_prefix: StateKeyPrefix,
+        start_version: Version,
+        end_version: Version,
+    ) -> Self {
+        Self {
+            inner,
+            key_prefix,
+            start_version,
+            end_version,
+            is_finished: false,
+        }
+    }
+
+    fn next_impl(&mut self) -> Result<Option<(StateKey, Version)>> {
+        if self.is_finished {
+            return Ok(None);
+        }
+
+        while let Some(((state_key, version), ())) = self.inner.next().transpose()? {
+            // Check if the key_prefix is a valid prefix of the state_key we got from DB.
+            if !self.key_prefix.is_prefix(&state_key)? {
+                // No more keys matching the key_prefix.
+                self.is_finished = true;
+                break;
+            }
+
+            if version < self.start_version {
+                // Skip to the first version in range of the current key.
+                self.inner.seek(&(state_key, self.start_version))?;
+                continue;
+            }
+            if version >= self.end_version {
+                // Skip to the next key.
+                if version < Version::MAX {
+                    self.inner.seek(&(state_key, Version::MAX))?;
+                }
+                continue;
+            }
+
+            return Ok(Some((state_key, version)));
+        }
+        Ok(None)
+    }
+}
+
+impl<'a> Iterator for PrefixedWriteSetVersionIter<'a> {
+    type Item = Result<(StateKey, Version)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_impl().transpose()
+    }
+}
+
 pub struct AccountTransactionVersionIter<'a> {
     inner: SchemaIterator<'a, TransactionByAccountSchema>,
     address: AccountAddress,
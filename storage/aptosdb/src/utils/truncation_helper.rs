@@ -9,6 +9,7 @@ use crate::{
     schema::{
         db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
         epoch_by_version::EpochByVersionSchema,
+        epoch_ledger_stats::EpochLedgerStatsSchema,
         jellyfish_merkle_node::JellyfishMerkleNodeSchema,
         ledger_info::LedgerInfoSchema,
         stale_node_index::StaleNodeIndexSchema,
@@ -23,7 +24,7 @@ use crate::{
     },
     state_kv_db::StateKvDb,
     state_merkle_db::StateMerkleDb,
-    state_store::MAX_COMMIT_PROGRESS_DIFFERENCE,
+    state_store::{StateStore, MAX_COMMIT_PROGRESS_DIFFERENCE},
     transaction_store::TransactionStore,
     utils::get_progress,
 };
@@ -33,7 +34,7 @@ use aptos_schemadb::{
     schema::{Schema, SeekKeyCodec},
     ReadOptions, SchemaBatch, DB,
 };
-use aptos_storage_interface::Result;
+use aptos_storage_interface::{db_ensure as ensure, AptosDbError, Result};
 use aptos_types::{proof::position::Position, transaction::Version};
 use claims::{assert_ge, assert_lt};
 use rayon::prelude::*;
@@ -204,6 +205,95 @@ pub(crate) fn find_closest_node_version_at_or_before(
     Ok(iter.next().transpose()?.map(|item| item.0.version()))
 }
 
+/// Returns the closest version (at or before the given version) that has a tree
+/// root in the state merkle db (falling back to the closest epoch ending version).
+pub(crate) fn find_tree_root_at_or_before(
+    ledger_metadata_db: &DB,
+    state_merkle_db: &StateMerkleDb,
+    version: Version,
+) -> Result<Option<Version>> {
+    match find_closest_node_version_at_or_before(state_merkle_db, version)? {
+        Some(closest_version) => {
+            if root_exists_at_version(state_merkle_db, closest_version)? {
+                return Ok(Some(closest_version));
+            }
+            let mut iter =
+                ledger_metadata_db.iter::<EpochByVersionSchema>(ReadOptions::default())?;
+            iter.seek_for_prev(&version)?;
+            match iter.next().transpose()? {
+                Some((closest_epoch_version, _)) => {
+                    if root_exists_at_version(state_merkle_db, closest_epoch_version)? {
+                        Ok(Some(closest_epoch_version))
+                    } else {
+                        Ok(None)
+                    }
+                },
+                None => Ok(None),
+            }
+        },
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn root_exists_at_version(
+    state_merkle_db: &StateMerkleDb,
+    version: Version,
+) -> Result<bool> {
+    Ok(state_merkle_db
+        .metadata_db()
+        .get::<JellyfishMerkleNodeSchema>(&NodeKey::new_empty_path(version))?
+        .is_some())
+}
+
+/// Truncates the given dbs to the target version (i.e., deletes all data after
+/// the target version). The state merkle db is truncated to the closest tree
+/// root at or before the target version, and the remaining versions are
+/// replayed from the ledger db when the dbs are next opened.
+pub(crate) fn truncate_dbs_to_version(
+    ledger_db: Arc<LedgerDb>,
+    state_kv_db: Arc<StateKvDb>,
+    state_merkle_db: &StateMerkleDb,
+    target_version: Version,
+) -> Result<()> {
+    ensure!(
+        ledger_db.metadata_db().get_usage(target_version).is_ok(),
+        "Unable to truncate to version {}, since there is no VersionData on that version.",
+        target_version
+    );
+
+    if let Some(state_merkle_db_version) = get_current_version_in_state_merkle_db(state_merkle_db)?
+    {
+        if target_version < state_merkle_db_version {
+            let state_merkle_target_version = find_tree_root_at_or_before(
+                &ledger_db.metadata_db_arc(),
+                state_merkle_db,
+                target_version,
+            )?
+            .ok_or_else(|| {
+                AptosDbError::Other(format!(
+                    "Could not find a valid root before or at version {}, maybe it was pruned?",
+                    target_version
+                ))
+            })?;
+            truncate_state_merkle_db(state_merkle_db, state_merkle_target_version)?;
+        }
+    }
+
+    let batch = SchemaBatch::new();
+    batch.put::<DbMetadataSchema>(
+        &DbMetadataKey::OverallCommitProgress,
+        &DbMetadataValue::Version(target_version),
+    )?;
+    ledger_db.metadata_db().write_schemas(batch)?;
+
+    StateStore::sync_commit_progress(
+        ledger_db,
+        state_kv_db,
+        /*crash_if_difference_is_too_large=*/ false,
+    );
+    Ok(())
+}
+
 pub(crate) fn num_frozen_nodes_in_accumulator(num_leaves: u64) -> u64 {
     2 * num_leaves - num_leaves.count_ones() as u64
 }
@@ -332,6 +422,19 @@ fn delete_per_epoch_data(
         batch.delete::<LedgerInfoSchema>(&epoch)?;
     }
 
+    // The stats can't be partially reverted (e.g., the unique senders), so the
+    // stats of any epoch that covers the truncated versions are deleted.
+    let mut iter = ledger_db.rev_iter::<EpochLedgerStatsSchema>(ReadOptions::default())?;
+    iter.seek_to_last();
+    for item in iter {
+        let (epoch, epoch_ledger_stats) = item?;
+        if epoch_ledger_stats.last_version < start_version {
+            break;
+        }
+        info!(epoch = epoch, "Truncate epoch ledger stats.");
+        batch.delete::<EpochLedgerStatsSchema>(&epoch)?;
+    }
+
     Ok(())
 }
 
@@ -355,6 +458,11 @@ fn delete_per_version_data(
         start_version,
         &batch.ledger_metadata_db_batches,
     )?;
+    ledger_db.write_set_db().prune_write_set_indices(
+        start_version,
+        Version::MAX,
+        &batch.write_set_db_batches,
+    )?;
     delete_per_version_data_impl::<WriteSetSchema>(
         ledger_db.write_set_db_raw(),
         start_version,
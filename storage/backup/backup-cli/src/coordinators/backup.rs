@@ -69,6 +69,23 @@ pub struct BackupCoordinatorOpt {
         many small files. "
     )]
     pub transaction_batch_size: usize,
+    #[clap(
+        long,
+        help = "Run the coordinator in continuous mode. Instead of polling the DB state every \
+        second, the coordinator waits for the backup service to notify it of new commits. And \
+        instead of waiting for a full transaction batch, newly committed transactions are backed \
+        up incrementally (without crossing the boundaries of `--transaction-batch-size`), as soon \
+        as there are at least `--continuous-min-transactions` of them. This reduces the backup lag \
+        to seconds and smooths the IO load, at the cost of more (and smaller) backup files."
+    )]
+    pub continuous: bool,
+    #[clap(
+        long,
+        default_value_t = 10000,
+        help = "In continuous mode, the minimum number of newly committed transactions to back up \
+        in a single incremental transaction backup (unless the backup completes a batch)."
+    )]
+    pub continuous_min_transactions: usize,
     #[clap(flatten)]
     pub concurrent_downloads: ConcurrentDownloadsOpt,
 }
@@ -79,6 +96,10 @@ impl BackupCoordinatorOpt {
             self.state_snapshot_interval_epochs > 0 && self.transaction_batch_size > 0,
             "Backup interval and batch size must be greater than 0."
         );
+        ensure!(
+            self.continuous_min_transactions > 0,
+            "The minimum number of transactions in continuous mode must be greater than 0."
+        );
         Ok(())
     }
 }
@@ -90,10 +111,15 @@ pub struct BackupCoordinator {
     metadata_cache_opt: MetadataCacheOpt,
     state_snapshot_interval_epochs: usize,
     transaction_batch_size: usize,
+    continuous: bool,
+    continuous_min_transactions: usize,
     concurrent_downloads: usize,
 }
 
 impl BackupCoordinator {
+    /// How long to wait for a new commit (in continuous mode) before re-requesting the DB state
+    const DB_STATE_WAIT_SECS: u64 = 10;
+
     pub fn new(
         opt: BackupCoordinatorOpt,
         global_opt: GlobalBackupOpt,
@@ -108,6 +134,8 @@ impl BackupCoordinator {
             metadata_cache_opt: opt.metadata_cache_opt,
             state_snapshot_interval_epochs: opt.state_snapshot_interval_epochs,
             transaction_batch_size: opt.transaction_batch_size,
+            continuous: opt.continuous,
+            continuous_min_transactions: opt.continuous_min_transactions,
             concurrent_downloads: opt.concurrent_downloads.get(),
         }
     }
@@ -130,10 +158,20 @@ impl BackupCoordinator {
         let (tx1, rx1) = watch::channel::<Option<DbState>>(None);
         let (tx2, rx2) = watch::channel::<Option<DbState>>(None);
 
-        // Schedule work streams.
-        let watch_db_state = IntervalStream::new(interval(Duration::from_secs(1)))
-            .then(|_| self.try_refresh_db_state(&tx1))
-            .boxed_local();
+        // Schedule work streams. In continuous mode, the DB state is refreshed as soon as
+        // the backup service notifies us of new commits (instead of on a fixed interval).
+        let watch_db_state = if self.continuous {
+            let tx1 = &tx1;
+            stream::unfold(None, move |known_version| async move {
+                let known_version = self.wait_for_db_state_change(tx1, known_version).await;
+                Some(((), known_version))
+            })
+            .boxed_local()
+        } else {
+            IntervalStream::new(interval(Duration::from_secs(1)))
+                .then(|_| self.try_refresh_db_state(&tx1))
+                .boxed_local()
+        };
 
         let backup_epoch_endings = self
             .backup_work_stream(
@@ -197,6 +235,45 @@ impl BackupCoordinator {
         };
     }
 
+    /// Waits for the DB to commit a version higher than `known_version` (if any), and
+    /// broadcasts the new DB state. Returns the latest known committed version.
+    async fn wait_for_db_state_change(
+        &self,
+        db_state_broadcast: &watch::Sender<Option<DbState>>,
+        known_version: Option<Version>,
+    ) -> Option<Version> {
+        let db_state = match known_version {
+            Some(known_version) => {
+                self.client
+                    .wait_for_db_state(known_version, Duration::from_secs(Self::DB_STATE_WAIT_SECS))
+                    .await
+            },
+            None => self.client.get_db_state().await,
+        };
+
+        match db_state {
+            Ok(Some(db_state)) => {
+                HEARTBEAT_TS.set(unix_timestamp_sec());
+                if Some(db_state.committed_version) != known_version {
+                    db_state_broadcast
+                        .send(Some(db_state))
+                        .map_err(|e| anyhow!("Receivers should not be cancelled: {}", e))
+                        .unwrap()
+                }
+                return Some(db_state.committed_version);
+            },
+            Ok(None) => warn!("DB not bootstrapped."),
+            Err(e) => warn!(
+                "Failed waiting for DbState from local node: {}. Will keep trying.",
+                e
+            ),
+        }
+
+        // Back off before trying again
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        known_version
+    }
+
     async fn backup_epoch_endings(
         &self,
         mut last_epoch_ending_epoch_in_backup: Option<u64>,
@@ -276,15 +353,28 @@ impl BackupCoordinator {
             if let Some(version) = last_transaction_version_in_backup {
                 TRANSACTION_VERSION.set(version as i64);
             }
-            let (first, last) = get_batch_range(
-                last_transaction_version_in_backup,
-                self.transaction_batch_size,
-            );
-
-            if db_state.committed_version < last {
-                // wait for the next db_state update
-                return Ok(last_transaction_version_in_backup);
-            }
+            let batch_range = if self.continuous {
+                get_continuous_batch_range(
+                    last_transaction_version_in_backup,
+                    self.transaction_batch_size,
+                    db_state.committed_version,
+                    self.continuous_min_transactions,
+                )
+            } else {
+                let (first, last) = get_batch_range(
+                    last_transaction_version_in_backup,
+                    self.transaction_batch_size,
+                );
+                (db_state.committed_version >= last).then_some((first, last))
+            };
+
+            let (first, last) = match batch_range {
+                Some(batch_range) => batch_range,
+                None => {
+                    // wait for the next db_state update
+                    return Ok(last_transaction_version_in_backup);
+                },
+            };
 
             TransactionBackupController::new(
                 TransactionBackupOpt {
@@ -491,6 +581,28 @@ fn get_batch_range(last_in_backup: Option<u64>, batch_size: usize) -> (u64, u64)
     })
 }
 
+fn get_continuous_batch_range(
+    last_in_backup: Option<u64>,
+    batch_size: usize,
+    committed_version: Version,
+    min_transactions: usize,
+) -> Option<(u64, u64)> {
+    // In continuous mode, we back up the committed part of the current batch (instead of waiting
+    // for the entire batch to be committed), as long as it contains enough transactions. Batch
+    // boundaries are still respected, so later backups realign with the batches. For example,
+    // with batch size 100 and minimum 10, if 7 is in backup and 50 is committed, we return (8, 50),
+    // and next time, if 150 is committed, we return (51, 100).
+    let (first, last) = get_batch_range(last_in_backup, batch_size);
+    if committed_version >= last {
+        Some((first, last))
+    } else if committed_version >= first && committed_version - first + 1 >= min_transactions as u64
+    {
+        Some((first, committed_version))
+    } else {
+        None
+    }
+}
+
 fn get_next_snapshot(last_in_backup: Option<u64>, db_state: DbState, interval: usize) -> u64 {
     // We don't try to guarantee snapshots are taken at each applicable interval: when the backup
     // progress can't keep up with the ledger growth, we favor timeliness over completeness.
@@ -510,7 +622,9 @@ fn get_next_snapshot(last_in_backup: Option<u64>, db_state: DbState, interval: u
 
 #[cfg(test)]
 mod tests {
-    use crate::coordinators::backup::{get_batch_range, get_next_snapshot};
+    use crate::coordinators::backup::{
+        get_batch_range, get_continuous_batch_range, get_next_snapshot,
+    };
     use aptos_db::backup::backup_handler::DbState;
 
     #[test]
@@ -522,6 +636,29 @@ mod tests {
         assert_eq!(get_batch_range(Some(200), 100), (201, 300));
     }
 
+    #[test]
+    fn test_get_continuous_batch_range() {
+        assert_eq!(get_continuous_batch_range(None, 100, 0, 10), Some((0, 0)));
+        assert_eq!(get_continuous_batch_range(Some(0), 100, 5, 10), None);
+        assert_eq!(
+            get_continuous_batch_range(Some(0), 100, 10, 10),
+            Some((1, 10))
+        );
+        assert_eq!(
+            get_continuous_batch_range(Some(10), 100, 50, 10),
+            Some((11, 50))
+        );
+        assert_eq!(
+            get_continuous_batch_range(Some(50), 100, 150, 10),
+            Some((51, 100))
+        );
+        assert_eq!(
+            get_continuous_batch_range(Some(95), 100, 100, 10),
+            Some((96, 100))
+        );
+        assert_eq!(get_continuous_batch_range(Some(100), 100, 100, 10), None);
+    }
+
     #[test]
     fn test_get_next_snapshot() {
         let _state = |epoch| DbState {
@@ -76,6 +76,25 @@ impl BackupServiceClient {
         Ok(bcs::from_bytes(&buf)?)
     }
 
+    /// Waits (for at most `timeout`) until the DB commits a version higher than
+    /// `known_version`, and returns the latest DB state.
+    pub async fn wait_for_db_state(
+        &self,
+        known_version: Version,
+        timeout: Duration,
+    ) -> Result<Option<DbState>> {
+        let mut buf = Vec::new();
+        self.get(&format!(
+            "db_state_after/{}/{}",
+            known_version,
+            timeout.as_millis()
+        ))
+        .await?
+        .read_to_end(&mut buf)
+        .await?;
+        Ok(bcs::from_bytes(&buf)?)
+    }
+
     pub async fn get_account_range_proof(
         &self,
         key: HashValue,
@@ -10,10 +10,16 @@ use crate::handlers::utils::{
 };
 use aptos_crypto::hash::HashValue;
 use aptos_db::backup::backup_handler::BackupHandler;
+use aptos_storage_interface::{disk_space_guardrails::DISK_SPACE_GUARDRAILS, AptosDbError};
 use aptos_types::transaction::Version;
-use warp::{filters::BoxedFilter, reply::Reply, Filter};
+use std::{convert::Infallible, time::Duration};
+use warp::{filters::BoxedFilter, http::StatusCode, reply::Reply, Filter};
+
+/// The maximum time a request may wait for the DB state to change
+const MAX_DB_STATE_WAIT_MS: u64 = 30_000;
 
 static DB_STATE: &str = "db_state";
+static DB_STATE_AFTER: &str = "db_state_after";
 static STATE_RANGE_PROOF: &str = "state_range_proof";
 static STATE_SNAPSHOT: &str = "state_snapshot";
 static STATE_ROOT_PROOF: &str = "state_root_proof";
@@ -29,6 +35,25 @@ pub(crate) fn get_routes(backup_handler: BackupHandler) -> BoxedFilter<(impl Rep
         .map(unwrap_or_500)
         .recover(handle_rejection);
 
+    // GET db_state_after/<known_version>/<timeout_ms>
+    // Waits until the DB commits a version higher than `known_version` (or the timeout elapses).
+    let bh = backup_handler.clone();
+    let db_state_after = warp::path!(Version / u64)
+        .and_then(move |known_version, timeout_ms: u64| {
+            let bh = bh.clone();
+            async move {
+                let timeout = Duration::from_millis(timeout_ms.min(MAX_DB_STATE_WAIT_MS));
+                let result = tokio::task::spawn_blocking(move || {
+                    bh.wait_for_db_state(Some(known_version), timeout)
+                })
+                .await
+                .map_err(|e| AptosDbError::Other(e.to_string()))
+                .and_then(|db_state| reply_with_bcs_bytes(DB_STATE_AFTER, &db_state?));
+                Ok::<_, Infallible>(unwrap_or_500(result))
+            }
+        })
+        .recover(handle_rejection);
+
     // GET state_range_proof/<version>/<end_key>
     let bh = backup_handler.clone();
     let state_range_proof = warp::path!(Version / HashValue)
@@ -111,6 +136,7 @@ pub(crate) fn get_routes(backup_handler: BackupHandler) -> BoxedFilter<(impl Rep
     // Route by endpoint name.
     let routes = warp::any()
         .and(warp::path(DB_STATE).and(db_state))
+        .or(warp::path(DB_STATE_AFTER).and(db_state_after))
         .or(warp::path(STATE_RANGE_PROOF).and(state_range_proof))
         .or(warp::path(STATE_SNAPSHOT).and(state_snapshot))
         .or(warp::path(STATE_ROOT_PROOF).and(state_root_proof))
@@ -118,9 +144,20 @@ pub(crate) fn get_routes(backup_handler: BackupHandler) -> BoxedFilter<(impl Rep
         .or(warp::path(TRANSACTIONS).and(transactions))
         .or(warp::path(TRANSACTION_RANGE_PROOF).and(transaction_range_proof));
 
+    // Refuse to serve backup reads while the disk space guardrails are
+    // active (i.e., the node is low on disk space). Otherwise, fall
+    // through to the routes.
+    let disk_space_guardrails = warp::any().and_then(|| async {
+        if DISK_SPACE_GUARDRAILS.are_guardrails_active() {
+            Ok(StatusCode::SERVICE_UNAVAILABLE)
+        } else {
+            Err(warp::reject::not_found())
+        }
+    });
+
     // Serve all routes for GET only.
     warp::get()
-        .and(routes)
+        .and(disk_space_guardrails.or(routes))
         .with(warp::log::custom(|info| {
             let endpoint = info.path().split('/').nth(1).unwrap_or("-");
             LATENCY_HISTOGRAM
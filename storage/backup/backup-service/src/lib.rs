@@ -68,6 +68,8 @@ mod tests {
         assert_eq!(resp.status(), 400);
         let resp = get(format!("http://127.0.0.1:{}/state_snapshot", port)).unwrap();
         assert_eq!(resp.status(), 400);
+        let resp = get(format!("http://127.0.0.1:{}/db_state_after/1", port)).unwrap();
+        assert_eq!(resp.status(), 400);
 
         // Params fail to parse (HashValue)
         let resp = get(format!("http://127.0.0.1:{}/state_range_proof/1/ff", port)).unwrap();
@@ -84,6 +86,11 @@ mod tests {
         let resp = get(format!("http://127.0.0.1:{}/state_root_proof/0", port)).unwrap();
         assert_eq!(resp.status(), 500);
 
+        // Waiting for a new DB state times out (non-bootstrapped DB), returning no DB state
+        let resp = get(format!("http://127.0.0.1:{}/db_state_after/0/10", port)).unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(resp.bytes().unwrap().as_ref(), &[0u8]);
+
         // In an endpoint handled by `reply_with_async_channel_writer', connection terminates
         // prematurely when the channel writer errors. However a 200 is either returned or not
         // before the termination of the connection, resulting in slightly different behavior:
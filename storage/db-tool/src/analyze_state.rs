@@ -0,0 +1,449 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, format_err, Result};
+use aptos_backup_cli::utils::RocksdbOpt;
+use aptos_config::config::{
+    StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_crypto::{hash::CryptoHash, HashValue};
+use aptos_db::{backup::backup_handler::BackupHandler, AptosDB};
+use aptos_logger::info;
+use aptos_storage_interface::DbReader;
+use aptos_types::{
+    access_path::Path,
+    state_store::{
+        state_key::{StateKey, StateKeyInner},
+        state_value::StateValue,
+    },
+    transaction::Version,
+};
+use clap::{Parser, ValueEnum};
+use rayon::prelude::*;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{stdout, Write},
+    path::PathBuf,
+};
+
+// Labels for state items that are not owned by a module
+const CODE_LABEL: &str = "code";
+const TABLE_ITEM_LABEL: &str = "table_item";
+const RAW_LABEL: &str = "raw";
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum OutputFormat {
+    Csv,
+    Json,
+}
+
+#[derive(Parser)]
+#[clap(
+    about = "Analyzes a state snapshot and reports the storage usage grouped by owning address, \
+    module and resource type."
+)]
+pub struct Command {
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+
+    #[clap(
+        long,
+        help = "The version of the state snapshot to analyze. Defaults to the latest snapshot."
+    )]
+    version: Option<Version>,
+
+    #[clap(
+        long,
+        default_value_t = 16,
+        help = "The number of partitions of the state to scan in parallel."
+    )]
+    num_partitions: usize,
+
+    #[clap(
+        long,
+        default_value_t = 100,
+        help = "The maximum number of groups to report per grouping (largest first). \
+        If 0, all groups are reported."
+    )]
+    max_groups: usize,
+
+    #[clap(long, value_enum, default_value = "csv")]
+    output_format: OutputFormat,
+
+    #[clap(
+        long,
+        value_parser,
+        help = "The file to write the report to. Defaults to stdout."
+    )]
+    output_path: Option<PathBuf>,
+}
+
+impl Command {
+    pub fn run(self) -> Result<()> {
+        ensure!(
+            self.num_partitions > 0 && self.num_partitions <= 256,
+            "The number of partitions must be between 1 and 256!"
+        );
+
+        let db = AptosDB::open(
+            StorageDirPaths::from_path(&self.db_dir),
+            true,                        /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+            self.rocksdb_opt.into(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )?;
+        let version = match self.version {
+            Some(version) => version,
+            None => db
+                .get_state_snapshot_before(Version::MAX)?
+                .map(|(version, _)| version)
+                .ok_or_else(|| format_err!("No state snapshot was found in the DB!"))?,
+        };
+
+        // Scan the partitions of the state in parallel and merge the results
+        let backup_handler = db.get_backup_handler();
+        let partition_start_keys = get_partition_start_keys(self.num_partitions);
+        let partition_analyses = (0..self.num_partitions)
+            .into_par_iter()
+            .map(|index| {
+                analyze_partition(
+                    &backup_handler,
+                    version,
+                    partition_start_keys[index],
+                    partition_start_keys.get(index + 1).copied(),
+                )
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let mut state_analysis = StateAnalysis::new(version);
+        for partition_analysis in partition_analyses {
+            state_analysis.merge(partition_analysis);
+        }
+        info!(
+            "Analyzed {} state items ({} bytes) at version {}.",
+            state_analysis.totals.num_items, state_analysis.totals.total_bytes, version
+        );
+
+        // Write the report
+        let report = state_analysis.into_report(self.max_groups);
+        let mut writer: Box<dyn Write> = match &self.output_path {
+            Some(output_path) => Box::new(File::create(output_path)?),
+            None => Box::new(stdout()),
+        };
+        match self.output_format {
+            OutputFormat::Csv => report.write_csv(&mut writer)?,
+            OutputFormat::Json => serde_json::to_writer_pretty(&mut writer, &report)?,
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Returns the (inclusive) start keys of the partitions. The hashed
+/// key space is evenly split (by the first byte of the hashed keys).
+fn get_partition_start_keys(num_partitions: usize) -> Vec<HashValue> {
+    (0..num_partitions)
+        .map(|index| {
+            let mut start_key = [0u8; HashValue::LENGTH];
+            start_key[0] = (index * 256 / num_partitions) as u8;
+            HashValue::new(start_key)
+        })
+        .collect()
+}
+
+/// Analyzes all state items with hashed keys in the range [start_key, end_key)
+fn analyze_partition(
+    backup_handler: &BackupHandler,
+    version: Version,
+    start_key: HashValue,
+    end_key: Option<HashValue>,
+) -> Result<StateAnalysis> {
+    let mut state_analysis = StateAnalysis::new(version);
+    for result in backup_handler.get_account_iter_from(version, start_key)? {
+        let (state_key, state_value) = result?;
+        if let Some(end_key) = end_key {
+            if CryptoHash::hash(&state_key) >= end_key {
+                break; // We've reached the next partition
+            }
+        }
+        state_analysis.add_state_item(&state_key, &state_value);
+    }
+    Ok(state_analysis)
+}
+
+/// The storage usage of a group of state items
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
+struct StorageUsage {
+    num_items: u64,
+    key_bytes: u64,
+    value_bytes: u64,
+    total_bytes: u64,
+}
+
+impl StorageUsage {
+    fn add_item(&mut self, key_bytes: usize, value_bytes: usize) {
+        self.num_items += 1;
+        self.key_bytes += key_bytes as u64;
+        self.value_bytes += value_bytes as u64;
+        self.total_bytes += (key_bytes + value_bytes) as u64;
+    }
+
+    fn merge(&mut self, other: &StorageUsage) {
+        self.num_items += other.num_items;
+        self.key_bytes += other.key_bytes;
+        self.value_bytes += other.value_bytes;
+        self.total_bytes += other.total_bytes;
+    }
+}
+
+/// The storage usage of the state, grouped by owning address,
+/// module (e.g., `0x1::coin`) and resource type (e.g., `0x1::coin::CoinStore`).
+/// Resource types are grouped without their type arguments.
+struct StateAnalysis {
+    version: Version,
+    totals: StorageUsage,
+    by_address: HashMap<String, StorageUsage>,
+    by_module: HashMap<String, StorageUsage>,
+    by_resource_type: HashMap<String, StorageUsage>,
+}
+
+impl StateAnalysis {
+    fn new(version: Version) -> Self {
+        Self {
+            version,
+            totals: StorageUsage::default(),
+            by_address: HashMap::new(),
+            by_module: HashMap::new(),
+            by_resource_type: HashMap::new(),
+        }
+    }
+
+    /// Adds the given state item to the analysis
+    fn add_state_item(&mut self, state_key: &StateKey, state_value: &StateValue) {
+        let (address, module, resource_type) = get_state_key_groups(state_key);
+        let (key_bytes, value_bytes) = (state_key.size(), state_value.size());
+
+        self.totals.add_item(key_bytes, value_bytes);
+        for (groups, group) in [
+            (&mut self.by_address, address),
+            (&mut self.by_module, module),
+            (&mut self.by_resource_type, resource_type),
+        ] {
+            groups
+                .entry(group)
+                .or_default()
+                .add_item(key_bytes, value_bytes);
+        }
+    }
+
+    /// Merges the given analysis into this analysis
+    fn merge(&mut self, other: StateAnalysis) {
+        self.totals.merge(&other.totals);
+        for (groups, other_groups) in [
+            (&mut self.by_address, other.by_address),
+            (&mut self.by_module, other.by_module),
+            (&mut self.by_resource_type, other.by_resource_type),
+        ] {
+            for (group, usage) in other_groups {
+                groups.entry(group).or_default().merge(&usage);
+            }
+        }
+    }
+
+    /// Converts the analysis into a report that contains (at most) the
+    /// given number of groups per grouping, ordered by the total bytes.
+    fn into_report(self, max_groups: usize) -> StateAnalysisReport {
+        StateAnalysisReport {
+            version: self.version,
+            totals: self.totals,
+            by_address: get_largest_groups(self.by_address, max_groups),
+            by_module: get_largest_groups(self.by_module, max_groups),
+            by_resource_type: get_largest_groups(self.by_resource_type, max_groups),
+        }
+    }
+}
+
+/// Returns the owning address, module and resource type groups of the
+/// given state key. Table items are grouped by their table handle, as
+/// the owner of a table isn't known without decoding the parent resource.
+fn get_state_key_groups(state_key: &StateKey) -> (String, String, String) {
+    match state_key.inner() {
+        StateKeyInner::AccessPath(access_path) => {
+            let address = access_path.address.to_hex_literal();
+            match access_path.get_path() {
+                Path::Code(module_id) => (
+                    address,
+                    format!("{}::{}", module_id.address.to_hex_literal(), module_id.name),
+                    CODE_LABEL.into(),
+                ),
+                Path::Resource(struct_tag) | Path::ResourceGroup(struct_tag) => {
+                    let module = format!(
+                        "{}::{}",
+                        struct_tag.address.to_hex_literal(),
+                        struct_tag.module
+                    );
+                    let resource_type = format!("{}::{}", module, struct_tag.name);
+                    (address, module, resource_type)
+                },
+            }
+        },
+        StateKeyInner::TableItem { handle, .. } => (
+            handle.0.to_hex_literal(),
+            TABLE_ITEM_LABEL.into(),
+            TABLE_ITEM_LABEL.into(),
+        ),
+        StateKeyInner::Raw(_) => (RAW_LABEL.into(), RAW_LABEL.into(), RAW_LABEL.into()),
+    }
+}
+
+/// Returns (at most) the given number of largest groups (by total bytes)
+fn get_largest_groups(
+    groups: HashMap<String, StorageUsage>,
+    max_groups: usize,
+) -> Vec<GroupStorageUsage> {
+    let mut groups: Vec<_> = groups
+        .into_iter()
+        .map(|(group, usage)| GroupStorageUsage { group, usage })
+        .collect();
+    groups.sort_by(|group, other| {
+        other
+            .usage
+            .total_bytes
+            .cmp(&group.usage.total_bytes)
+            .then_with(|| group.group.cmp(&other.group))
+    });
+    if max_groups > 0 {
+        groups.truncate(max_groups);
+    }
+    groups
+}
+
+/// The storage usage of a single group
+#[derive(Debug, Serialize)]
+struct GroupStorageUsage {
+    group: String,
+    #[serde(flatten)]
+    usage: StorageUsage,
+}
+
+/// The report of a state analysis
+#[derive(Debug, Serialize)]
+struct StateAnalysisReport {
+    version: Version,
+    totals: StorageUsage,
+    by_address: Vec<GroupStorageUsage>,
+    by_module: Vec<GroupStorageUsage>,
+    by_resource_type: Vec<GroupStorageUsage>,
+}
+
+impl StateAnalysisReport {
+    /// Writes the report as CSV (with one row per group)
+    fn write_csv(&self, writer: &mut dyn Write) -> Result<()> {
+        writeln!(
+            writer,
+            "grouping,group,num_items,key_bytes,value_bytes,total_bytes"
+        )?;
+        write_csv_row(writer, "total", "", &self.totals)?;
+        for (grouping, groups) in [
+            ("address", &self.by_address),
+            ("module", &self.by_module),
+            ("resource_type", &self.by_resource_type),
+        ] {
+            for group in groups {
+                write_csv_row(writer, grouping, &group.group, &group.usage)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes a single CSV row (groups never contain commas or quotes)
+fn write_csv_row(
+    writer: &mut dyn Write,
+    grouping: &str,
+    group: &str,
+    usage: &StorageUsage,
+) -> Result<()> {
+    writeln!(
+        writer,
+        "{},{},{},{},{},{}",
+        grouping, group, usage.num_items, usage.key_bytes, usage.value_bytes, usage.total_bytes
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_types::{
+        access_path::AccessPath, account_address::AccountAddress, state_store::table::TableHandle,
+    };
+    use move_core_types::{
+        identifier::Identifier,
+        language_storage::{StructTag, TypeTag},
+    };
+
+    #[test]
+    fn test_state_analysis() {
+        // Create the state keys for two accounts and a table item
+        let account_1 = AccountAddress::random();
+        let account_2 = AccountAddress::random();
+        let coin_store = |account| {
+            let struct_tag = StructTag {
+                address: AccountAddress::ONE,
+                module: Identifier::new("coin").unwrap(),
+                name: Identifier::new("CoinStore").unwrap(),
+                type_params: vec![TypeTag::U64],
+            };
+            StateKey::access_path(AccessPath::resource_access_path(account, struct_tag).unwrap())
+        };
+        let table_item = StateKey::table_item(TableHandle(account_1), vec![1, 2, 3]);
+
+        // Analyze the state items (split across two partitions)
+        let mut state_analysis = StateAnalysis::new(10);
+        state_analysis.add_state_item(&coin_store(account_1), &StateValue::from(vec![0; 100]));
+        state_analysis.add_state_item(&table_item, &StateValue::from(vec![0; 10]));
+        let mut other_state_analysis = StateAnalysis::new(10);
+        other_state_analysis.add_state_item(&coin_store(account_2), &StateValue::from(vec![0; 50]));
+        state_analysis.merge(other_state_analysis);
+
+        // Verify the report
+        let report = state_analysis.into_report(0);
+        assert_eq!(report.totals.num_items, 3);
+        assert_eq!(report.totals.value_bytes, 160);
+        assert_eq!(report.by_address.len(), 2);
+        assert_eq!(report.by_address[0].group, account_1.to_hex_literal());
+        assert_eq!(report.by_address[0].usage.num_items, 2);
+        assert_eq!(report.by_module.len(), 2);
+        assert_eq!(report.by_module[0].group, "0x1::coin");
+        assert_eq!(report.by_module[0].usage.value_bytes, 150);
+        assert_eq!(report.by_resource_type[0].group, "0x1::coin::CoinStore");
+        assert_eq!(report.by_resource_type[1].group, TABLE_ITEM_LABEL);
+
+        // Verify the report respects the max number of groups
+        let mut state_analysis = StateAnalysis::new(10);
+        state_analysis.add_state_item(&coin_store(account_1), &StateValue::from(vec![0; 100]));
+        state_analysis.add_state_item(&coin_store(account_2), &StateValue::from(vec![0; 50]));
+        let report = state_analysis.into_report(1);
+        assert_eq!(report.by_address.len(), 1);
+        assert_eq!(report.by_address[0].group, account_1.to_hex_literal());
+    }
+
+    #[test]
+    fn test_partition_start_keys() {
+        let start_keys = get_partition_start_keys(4);
+        assert_eq!(start_keys.len(), 4);
+        assert_eq!(start_keys[0], HashValue::zero());
+        assert_eq!(start_keys[1][0], 64);
+        assert_eq!(start_keys[3][0], 192);
+        assert_eq!(get_partition_start_keys(256)[255][0], 255);
+    }
+}
@@ -3,11 +3,15 @@
 
 extern crate core;
 
+mod analyze_state;
 mod backup;
 mod backup_maintenance;
 mod bootstrap;
+mod replay_chunk_journal;
 mod replay_verify;
 pub mod restore;
+mod state_kv_shard_set;
+mod state_snapshot;
 #[cfg(test)]
 mod tests;
 mod utils;
@@ -20,6 +24,8 @@ use clap::Parser;
 #[derive(Parser)]
 #[clap(name = "Aptos db tool", author, disable_version_flag = true)]
 pub enum DBTool {
+    AnalyzeState(analyze_state::Command),
+
     #[clap(subcommand)]
     Backup(backup::Command),
 
@@ -31,25 +37,37 @@ pub enum DBTool {
     #[clap(subcommand)]
     Debug(db_debugger::Cmd),
 
+    ReplayChunkJournal(replay_chunk_journal::Command),
+
     ReplayVerify(replay_verify::Opt),
 
     #[clap(subcommand)]
     Restore(restore::Command),
+
+    #[clap(subcommand)]
+    StateKvShardSet(state_kv_shard_set::Command),
+
+    #[clap(subcommand)]
+    StateSnapshot(state_snapshot::Command),
 }
 
 impl DBTool {
     pub async fn run(self) -> Result<()> {
         match self {
+            DBTool::AnalyzeState(cmd) => cmd.run(),
             DBTool::Backup(cmd) => cmd.run().await,
             DBTool::BackupMaintenance(cmd) => cmd.run().await,
             DBTool::Bootstrap(cmd) => cmd.run(),
             DBTool::Debug(cmd) => Ok(cmd.run()?),
+            DBTool::ReplayChunkJournal(cmd) => cmd.run().await,
             DBTool::ReplayVerify(cmd) => {
                 let ret = cmd.run().await;
                 info!("Replay verify result: {:?}", ret);
                 ret
             },
             DBTool::Restore(cmd) => cmd.run().await,
+            DBTool::StateKvShardSet(cmd) => cmd.run(),
+            DBTool::StateSnapshot(cmd) => cmd.run(),
         }
     }
 }
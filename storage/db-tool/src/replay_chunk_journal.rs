@@ -0,0 +1,72 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::Result;
+use aptos_config::config::{
+    RocksdbConfigs, StateSyncDriverConfig, StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS,
+    DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_db::AptosDB;
+use aptos_executor::chunk_executor::ChunkExecutor;
+use aptos_state_sync_driver::{
+    chunk_journal::replay_chunk_journal, metadata_storage::PersistentMetadataStorage,
+};
+use aptos_storage_interface::DbReaderWriter;
+use aptos_vm::AptosVM;
+use clap::Parser;
+use std::{path::PathBuf, sync::Arc};
+
+#[derive(Parser)]
+#[clap(
+    name = "aptos-db-replay-chunk-journal",
+    about = "Replays a state sync chunk journal into a local DB (e.g., to reproduce execution bugs offline)."
+)]
+pub struct Command {
+    /// The directory of the chunk journal (as recorded by a node with the chunk journal enabled)
+    #[clap(long, value_parser)]
+    journal_dir: PathBuf,
+
+    /// The DB to replay the journal into. This should be a checkpoint of the node DB
+    /// taken at (or before) the start of the journal. Chunks that have already been
+    /// committed to the DB are skipped.
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        // Opening the DB exclusively, it's not allowed to run this tool alongside a running node which
+        // operates on the same DB.
+        let db = AptosDB::open(
+            StorageDirPaths::from_path(&self.db_dir),
+            false,
+            NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+            RocksdbConfigs::default(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )
+        .expect("Failed to open DB.");
+        let db = DbReaderWriter::new(db);
+        let chunk_executor = Arc::new(ChunkExecutor::<AptosVM>::new(db.clone()));
+        let metadata_storage = PersistentMetadataStorage::new(&self.db_dir);
+
+        let summary = replay_chunk_journal(
+            &self.journal_dir,
+            StateSyncDriverConfig::default(),
+            chunk_executor,
+            metadata_storage,
+            db,
+        )
+        .await?;
+        println!("Successfully replayed the chunk journal: {:?}", summary);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn verify_tool() {
+    use clap::CommandFactory;
+    Command::command().debug_assert()
+}
@@ -0,0 +1,133 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use aptos_backup_cli::utils::RocksdbOpt;
+use aptos_config::config::{
+    StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_crypto::HashValue;
+use aptos_db::{state_kv_shard_set, AptosDB};
+use aptos_storage_interface::DbWriter;
+use aptos_types::transaction::Version;
+use clap::{Parser, Subcommand};
+use std::path::PathBuf;
+
+/// Export and import the state KV shards of a DB (for cloning the state of a node out-of-band)
+#[derive(Subcommand)]
+pub enum Command {
+    #[clap(about = "Export the state KV shards at a version into a shard set directory")]
+    Export(ExportOpt),
+    #[clap(about = "Import a state KV shard set into an empty DB")]
+    Import(ImportOpt),
+}
+
+#[derive(Parser)]
+pub struct ExportOpt {
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+    #[clap(long, help = "The version of the state to export.")]
+    version: Version,
+    #[clap(
+        long,
+        value_parser,
+        help = "The (new) directory to write the shard set to."
+    )]
+    shard_set_dir: PathBuf,
+    #[clap(
+        long,
+        default_value_t = 100_000,
+        help = "The max number of state values per shard file."
+    )]
+    max_values_per_file: usize,
+}
+
+#[derive(Parser)]
+pub struct ImportOpt {
+    #[clap(long = "target-db-dir", value_parser)]
+    db_dir: PathBuf,
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+    #[clap(long, value_parser, help = "The directory of the shard set to import.")]
+    shard_set_dir: PathBuf,
+    #[clap(
+        long,
+        help = "The trusted root hash of the state. If specified, the import fails \
+        if the root hash in the shard set manifest does not match."
+    )]
+    expected_root_hash: Option<HashValue>,
+}
+
+impl Command {
+    pub fn run(self) -> Result<()> {
+        match self {
+            Command::Export(opt) => opt.run(),
+            Command::Import(opt) => opt.run(),
+        }
+    }
+}
+
+impl ExportOpt {
+    fn run(self) -> Result<()> {
+        let db = AptosDB::open(
+            StorageDirPaths::from_path(&self.db_dir),
+            true,                        /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+            self.rocksdb_opt.into(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )?;
+        let manifest = state_kv_shard_set::export_state_kv_shard_set(
+            &db,
+            self.version,
+            &self.shard_set_dir,
+            self.max_values_per_file,
+        )?;
+
+        println!(
+            "Exported {} state values ({} shards) at version {} with root hash {}.",
+            manifest.num_state_values,
+            manifest.shards.len(),
+            manifest.version,
+            manifest.root_hash
+        );
+        Ok(())
+    }
+}
+
+impl ImportOpt {
+    fn run(self) -> Result<()> {
+        let manifest = state_kv_shard_set::read_manifest(&self.shard_set_dir)?;
+        if let Some(expected_root_hash) = self.expected_root_hash {
+            ensure!(
+                manifest.root_hash == expected_root_hash,
+                "The root hash of the shard set does not match! Expected: {}, found: {}",
+                expected_root_hash,
+                manifest.root_hash
+            );
+        }
+
+        // Opening the DB exclusively, it's not allowed to run this tool alongside a running node which
+        // operates on the same DB.
+        let db = AptosDB::open_kv_only(
+            StorageDirPaths::from_path(&self.db_dir),
+            false,                       /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+            self.rocksdb_opt.into(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )?;
+        db.import_state_kv_shard_set(&self.shard_set_dir)?;
+
+        println!(
+            "Imported {} state values at version {} with root hash {}.",
+            manifest.num_state_values, manifest.version, manifest.root_hash
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use aptos_backup_cli::utils::RocksdbOpt;
+use aptos_config::config::{
+    StorageDirPaths, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_crypto::HashValue;
+use aptos_db::{
+    get_restore_handler::GetRestoreHandler,
+    state_restore::StateSnapshotRestoreMode,
+    state_snapshot_container::{self, StateSnapshotContainerReader},
+    AptosDB,
+};
+use aptos_types::transaction::Version;
+use clap::{Parser, Subcommand};
+use std::{path::PathBuf, sync::Arc};
+
+/// Export and import state snapshots using the portable container format
+#[derive(Subcommand)]
+pub enum Command {
+    #[clap(about = "Export the state snapshot at a version into a container")]
+    Export(ExportOpt),
+    #[clap(about = "Import a state snapshot container into a DB")]
+    Import(ImportOpt),
+}
+
+#[derive(Parser)]
+pub struct ExportOpt {
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+    #[clap(long, help = "The version of the state snapshot to export.")]
+    version: Version,
+    #[clap(
+        long,
+        value_parser,
+        help = "The (new) directory to write the container to."
+    )]
+    container_dir: PathBuf,
+    #[clap(
+        long,
+        default_value_t = 100_000,
+        help = "The number of state values per chunk."
+    )]
+    chunk_size: usize,
+}
+
+#[derive(Parser)]
+pub struct ImportOpt {
+    #[clap(long = "target-db-dir", value_parser)]
+    db_dir: PathBuf,
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+    #[clap(long, value_parser, help = "The directory of the container to import.")]
+    container_dir: PathBuf,
+    #[clap(
+        long,
+        help = "The trusted root hash of the state snapshot. If specified, the import fails \
+        if the root hash in the container manifest does not match."
+    )]
+    expected_root_hash: Option<HashValue>,
+}
+
+impl Command {
+    pub fn run(self) -> Result<()> {
+        match self {
+            Command::Export(opt) => opt.run(),
+            Command::Import(opt) => opt.run(),
+        }
+    }
+}
+
+impl ExportOpt {
+    fn run(self) -> Result<()> {
+        let db = AptosDB::open(
+            StorageDirPaths::from_path(&self.db_dir),
+            true,                        /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+            self.rocksdb_opt.into(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )?;
+        let manifest = state_snapshot_container::export_state_snapshot(
+            &db,
+            self.version,
+            &self.container_dir,
+            self.chunk_size,
+        )?;
+
+        println!(
+            "Exported {} state values ({} chunks) at version {} with root hash {}.",
+            manifest.num_state_values,
+            manifest.chunks.len(),
+            manifest.version,
+            manifest.root_hash
+        );
+        Ok(())
+    }
+}
+
+impl ImportOpt {
+    fn run(self) -> Result<()> {
+        let container = StateSnapshotContainerReader::open(&self.container_dir)?;
+        let manifest = container.manifest().clone();
+        if let Some(expected_root_hash) = self.expected_root_hash {
+            ensure!(
+                manifest.root_hash == expected_root_hash,
+                "The root hash of the container does not match! Expected: {}, found: {}",
+                expected_root_hash,
+                manifest.root_hash
+            );
+        }
+
+        // Opening the DB exclusively, it's not allowed to run this tool alongside a running node which
+        // operates on the same DB.
+        let restore_handler = Arc::new(AptosDB::open_kv_only(
+            StorageDirPaths::from_path(&self.db_dir),
+            false,                       /* readonly */
+            NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
+            self.rocksdb_opt.into(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )?)
+        .get_restore_handler();
+        let receiver = restore_handler.get_state_restore_receiver(
+            manifest.version,
+            manifest.root_hash,
+            StateSnapshotRestoreMode::Default,
+        )?;
+        state_snapshot_container::import_state_snapshot(&container, Box::new(receiver))?;
+
+        println!(
+            "Imported {} state values at version {} with root hash {}.",
+            manifest.num_state_values, manifest.version, manifest.root_hash
+        );
+        Ok(())
+    }
+}
@@ -47,6 +47,14 @@ fn test_various_cmd_parsing() {
     ]);
 
     run_cmd(&["aptos-db-tool", "backup", "verify", "--local-fs-dir", "."]);
+    run_cmd(&[
+        "aptos-db-tool",
+        "analyze-state",
+        "--db-dir",
+        ".",
+        "--output-format",
+        "json",
+    ]);
     run_cmd(&[
         "aptos-db-tool",
         "replay-verify",
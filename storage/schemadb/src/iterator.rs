@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    KeyCodec, Schema, SeekKeyCodec, ValueCodec, APTOS_SCHEMADB_ITER_BYTES,
+    metrics::record_read, KeyCodec, Schema, SeekKeyCodec, ValueCodec, APTOS_SCHEMADB_ITER_BYTES,
     APTOS_SCHEMADB_ITER_LATENCY_SECONDS, APTOS_SCHEMADB_SEEK_LATENCY_SECONDS,
 };
 use std::marker::PhantomData;
@@ -37,6 +37,7 @@ where
         let _timer = APTOS_SCHEMADB_SEEK_LATENCY_SECONDS
             .with_label_values(&[S::COLUMN_FAMILY_NAME, "seek_to_first"])
             .start_timer();
+        record_read(S::COLUMN_FAMILY_NAME, "seek_to_first", 0);
         self.db_iter.seek_to_first();
     }
 
@@ -45,6 +46,7 @@ where
         let _timer = APTOS_SCHEMADB_SEEK_LATENCY_SECONDS
             .with_label_values(&[S::COLUMN_FAMILY_NAME, "seek_to_last"])
             .start_timer();
+        record_read(S::COLUMN_FAMILY_NAME, "seek_to_last", 0);
         self.db_iter.seek_to_last();
     }
 
@@ -57,6 +59,7 @@ where
         let _timer = APTOS_SCHEMADB_SEEK_LATENCY_SECONDS
             .with_label_values(&[S::COLUMN_FAMILY_NAME, "seek"])
             .start_timer();
+        record_read(S::COLUMN_FAMILY_NAME, "seek", 0);
         let key = <SK as SeekKeyCodec<S>>::encode_seek_key(seek_key)?;
         self.db_iter.seek(&key);
         Ok(())
@@ -73,6 +76,7 @@ where
         let _timer = APTOS_SCHEMADB_SEEK_LATENCY_SECONDS
             .with_label_values(&[S::COLUMN_FAMILY_NAME, "seek_for_prev"])
             .start_timer();
+        record_read(S::COLUMN_FAMILY_NAME, "seek_for_prev", 0);
         let key = <SK as SeekKeyCodec<S>>::encode_seek_key(seek_key)?;
         self.db_iter.seek_for_prev(&key);
         Ok(())
@@ -90,9 +94,11 @@ where
 
         let raw_key = self.db_iter.key().expect("db_iter.key() failed.");
         let raw_value = self.db_iter.value().expect("db_iter.value(0 failed.");
+        let num_bytes = raw_key.len() + raw_value.len();
         APTOS_SCHEMADB_ITER_BYTES
             .with_label_values(&[S::COLUMN_FAMILY_NAME])
-            .observe((raw_key.len() + raw_value.len()) as f64);
+            .observe(num_bytes as f64);
+        record_read(S::COLUMN_FAMILY_NAME, "iter", num_bytes);
 
         let key = <S::Key as KeyCodec<S>>::decode_key(raw_key)?;
         let value = <S::Value as ValueCodec<S>>::decode_value(raw_value)?;
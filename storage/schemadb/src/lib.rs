@@ -21,9 +21,9 @@ pub mod iterator;
 
 use crate::{
     metrics::{
-        APTOS_SCHEMADB_BATCH_COMMIT_BYTES, APTOS_SCHEMADB_BATCH_COMMIT_LATENCY_SECONDS,
-        APTOS_SCHEMADB_DELETES_SAMPLED, APTOS_SCHEMADB_GET_BYTES,
-        APTOS_SCHEMADB_GET_LATENCY_SECONDS, APTOS_SCHEMADB_ITER_BYTES,
+        record_read, APTOS_SCHEMADB_BATCH_COMMIT_BYTES,
+        APTOS_SCHEMADB_BATCH_COMMIT_LATENCY_SECONDS, APTOS_SCHEMADB_DELETES_SAMPLED,
+        APTOS_SCHEMADB_GET_BYTES, APTOS_SCHEMADB_GET_LATENCY_SECONDS, APTOS_SCHEMADB_ITER_BYTES,
         APTOS_SCHEMADB_ITER_LATENCY_SECONDS, APTOS_SCHEMADB_PUT_BYTES_SAMPLED,
         APTOS_SCHEMADB_SEEK_LATENCY_SECONDS,
     },
@@ -37,8 +37,8 @@ use iterator::{ScanDirection, SchemaIterator};
 use rand::Rng;
 /// Type alias to `rocksdb::ReadOptions`. See [`rocksdb doc`](https://github.com/pingcap/rust-rocksdb/blob/master/src/rocksdb_options.rs)
 pub use rocksdb::{
-    BlockBasedOptions, Cache, ColumnFamilyDescriptor, DBCompressionType, Options, ReadOptions,
-    SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
+    BlockBasedOptions, Cache, ColumnFamilyDescriptor, DBCompactionStyle, DBCompressionType,
+    Options, ReadOptions, SliceTransform, DEFAULT_COLUMN_FAMILY_NAME,
 };
 use std::{collections::HashMap, iter::Iterator, path::Path};
 
@@ -192,9 +192,11 @@ impl DB {
         let cf_handle = self.get_cf_handle(S::COLUMN_FAMILY_NAME)?;
 
         let result = self.inner.get_cf(cf_handle, k)?;
+        let num_bytes = result.as_ref().map_or(0, |v| v.len());
         APTOS_SCHEMADB_GET_BYTES
             .with_label_values(&[S::COLUMN_FAMILY_NAME])
-            .observe(result.as_ref().map_or(0.0, |v| v.len() as f64));
+            .observe(num_bytes as f64);
+        record_read(S::COLUMN_FAMILY_NAME, "get", num_bytes);
 
         result
             .map(|raw_value| <S::Value as ValueCodec<S>>::decode_value(&raw_value))
@@ -328,6 +330,12 @@ impl DB {
         rocksdb::checkpoint::Checkpoint::new(&self.inner)?.create_checkpoint(path)?;
         Ok(())
     }
+
+    /// Catches up with the primary instance (only valid for dbs opened as secondary).
+    /// See `open_cf_as_secondary`.
+    pub fn try_catch_up_with_primary(&self) -> DbResult<()> {
+        Ok(self.inner.try_catch_up_with_primary()?)
+    }
 }
 
 impl Drop for DB {
@@ -4,9 +4,11 @@
 
 use aptos_metrics_core::{
     exponential_buckets, register_histogram_vec, register_int_counter_vec, HistogramVec,
-    IntCounterVec,
+    IntCounter, IntCounterVec,
 };
+use aptos_storage_interface::read_context::ReadContext;
 use once_cell::sync::Lazy;
+use std::{cell::RefCell, collections::HashMap};
 
 pub static APTOS_SCHEMADB_SEEK_LATENCY_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
     register_histogram_vec!(
@@ -116,3 +118,60 @@ pub static APTOS_SCHEMADB_DELETES_SAMPLED: Lazy<IntCounterVec> = Lazy::new(|| {
     )
     .unwrap()
 });
+
+pub static APTOS_SCHEMADB_READ_BYTES: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        // metric name
+        "aptos_schemadb_read_bytes",
+        // metric description
+        "Aptos schemadb bytes read, attributed to the calling subsystem",
+        // metric labels (dimensions)
+        &["subsystem", "cf_name"]
+    )
+    .unwrap()
+});
+
+pub static APTOS_SCHEMADB_READ_OPS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        // metric name
+        "aptos_schemadb_read_ops",
+        // metric description
+        "Aptos schemadb read operations, attributed to the calling subsystem",
+        // metric labels (dimensions)
+        &["subsystem", "cf_name", "op"]
+    )
+    .unwrap()
+});
+
+/// The read counters of a single (subsystem, cf_name, op) label combination
+struct ReadCounters {
+    ops: IntCounter,
+    bytes: IntCounter,
+}
+
+thread_local! {
+    // Reads are on the hot path, so the read counters are cached (per thread)
+    // instead of being looked up by their label values on every read.
+    static READ_COUNTERS: RefCell<
+        HashMap<(&'static str, &'static str, &'static str), ReadCounters>,
+    > = RefCell::new(HashMap::new());
+}
+
+/// Records a read operation (and the number of bytes read) against
+/// the subsystem of the current read context.
+pub(crate) fn record_read(cf_name: &'static str, op: &'static str, num_bytes: usize) {
+    let subsystem = ReadContext::current().get_label();
+    READ_COUNTERS.with(|read_counters| {
+        let mut read_counters = read_counters.borrow_mut();
+        let counters = read_counters
+            .entry((subsystem, cf_name, op))
+            .or_insert_with(|| ReadCounters {
+                ops: APTOS_SCHEMADB_READ_OPS.with_label_values(&[subsystem, cf_name, op]),
+                bytes: APTOS_SCHEMADB_READ_BYTES.with_label_values(&[subsystem, cf_name]),
+            });
+        counters.ops.inc();
+        if num_bytes > 0 {
+            counters.bytes.inc_by(num_bytes as u64);
+        }
+    });
+}
@@ -323,6 +323,15 @@ fn test_open_as_secondary() {
         db_sec.get::<TestSchema1>(&TestField(0)).unwrap(),
         Some(TestField(0)),
     );
+
+    // Verify that new writes are only visible after catching up with the primary
+    db.put::<TestSchema1>(&TestField(1), &TestField(1)).unwrap();
+    assert_eq!(db_sec.get::<TestSchema1>(&TestField(1)).unwrap(), None);
+    db_sec.try_catch_up_with_primary().unwrap();
+    assert_eq!(
+        db_sec.get::<TestSchema1>(&TestField(1)).unwrap(),
+        Some(TestField(1)),
+    );
 }
 
 #[test]
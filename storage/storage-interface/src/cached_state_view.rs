@@ -2,7 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    async_proof_fetcher::AsyncProofFetcher, metrics::TIMER, state_view::DbStateView, DbReader,
+    async_proof_fetcher::AsyncProofFetcher,
+    metrics::TIMER,
+    read_context::{ReadContext, ReadSubsystem},
+    state_view::DbStateView,
+    DbReader,
 };
 use aptos_crypto::{hash::CryptoHash, HashValue};
 use aptos_experimental_runtimes::thread_manager::THREAD_MANAGER;
@@ -135,6 +139,10 @@ pub struct CachedStateView {
     /// in JMT node.
     sharded_state_cache: ShardedStateCache,
     proof_fetcher: Arc<AsyncProofFetcher>,
+
+    /// The subsystem to which DB reads are attributed. This is captured when the view is
+    /// created, as the view is often read from other threads (e.g., by the VM executor).
+    read_subsystem: ReadSubsystem,
 }
 
 impl Debug for CachedStateView {
@@ -183,6 +191,7 @@ impl CachedStateView {
             speculative_state,
             sharded_state_cache: ShardedStateCache::default(),
             proof_fetcher,
+            read_subsystem: ReadContext::current(),
         }
     }
 
@@ -227,6 +236,7 @@ impl CachedStateView {
             // former case, we don't have the blob data but only its hash.
             StateStoreStatus::ExistsInDB | StateStoreStatus::Unknown => match self.snapshot {
                 Some((version, root_hash)) => {
+                    let _read_context = ReadContext::enter(self.read_subsystem);
                     let version_and_value_opt = self
                         .proof_fetcher
                         .fetch_state_value_with_version_and_schedule_proof_read(
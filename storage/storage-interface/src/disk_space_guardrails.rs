@@ -0,0 +1,86 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Disk space guardrails shared across the node. The storage disk space
+//! monitor updates the status as the available disk space changes, and
+//! other components consult the guardrails to decide whether non-critical
+//! operations should be paused (e.g., serving backup reads or heavy API
+//! queries). This helps the node avoid running out of disk space, which
+//! can corrupt RocksDB.
+
+use once_cell::sync::Lazy;
+use parking_lot::RwLock;
+use serde::Serialize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The global disk space guardrails
+pub static DISK_SPACE_GUARDRAILS: Lazy<DiskSpaceGuardrails> = Lazy::new(DiskSpaceGuardrails::new);
+
+/// The disk space status (as determined by the configured thresholds)
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiskSpaceStatus {
+    #[default]
+    Healthy, // There is sufficient available disk space
+    Warning, // The available disk space is running low (alerts are raised)
+    Low,     // The available disk space is low (non-critical operations are paused)
+}
+
+/// A snapshot of the disk space guardrails (e.g., for operators)
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct DiskSpaceGuardrailsSummary {
+    pub status: DiskSpaceStatus,
+    pub admin_override: bool,
+    pub guardrails_active: bool,
+}
+
+pub struct DiskSpaceGuardrails {
+    status: RwLock<DiskSpaceStatus>,
+    admin_override: AtomicBool, // If set, the guardrails are never activated
+}
+
+impl DiskSpaceGuardrails {
+    fn new() -> Self {
+        Self {
+            status: RwLock::new(DiskSpaceStatus::Healthy),
+            admin_override: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns the current disk space status
+    pub fn get_status(&self) -> DiskSpaceStatus {
+        *self.status.read()
+    }
+
+    /// Updates the current disk space status
+    pub fn update_status(&self, status: DiskSpaceStatus) {
+        *self.status.write() = status;
+    }
+
+    /// Returns true iff the guardrails have been overridden by an admin
+    pub fn is_admin_override_enabled(&self) -> bool {
+        self.admin_override.load(Ordering::Relaxed)
+    }
+
+    /// Sets (or clears) the admin override. While the override is set,
+    /// non-critical operations are never paused (regardless of the status).
+    pub fn set_admin_override(&self, admin_override: bool) {
+        self.admin_override.store(admin_override, Ordering::Relaxed);
+    }
+
+    /// Returns true iff the guardrails are active, i.e., the available disk
+    /// space is low and the guardrails haven't been overridden by an admin.
+    /// When active, non-critical operations should be paused.
+    pub fn are_guardrails_active(&self) -> bool {
+        self.get_status() == DiskSpaceStatus::Low && !self.is_admin_override_enabled()
+    }
+
+    /// Returns a summary of the disk space guardrails
+    pub fn get_summary(&self) -> DiskSpaceGuardrailsSummary {
+        DiskSpaceGuardrailsSummary {
+            status: self.get_status(),
+            admin_override: self.is_admin_override_enabled(),
+            guardrails_active: self.are_guardrails_active(),
+        }
+    }
+}
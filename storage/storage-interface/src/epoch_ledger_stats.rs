@@ -0,0 +1,74 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::transaction::{ExecutionStatus, Transaction, Version};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Aggregate statistics for the transactions committed in a single epoch.
+/// The statistics are computed at commit time, so they only cover the
+/// transactions committed by this node (e.g., if the node was bootstrapped
+/// from a state snapshot in the middle of an epoch, the statistics of that
+/// epoch start at `first_version`).
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[cfg_attr(any(test, feature = "fuzzing"), derive(proptest_derive::Arbitrary))]
+pub struct EpochLedgerStats {
+    /// The epoch of the statistics.
+    pub epoch: u64,
+    /// The first version covered by the statistics.
+    pub first_version: Version,
+    /// The last version covered by the statistics.
+    pub last_version: Version,
+    /// The total number of transactions.
+    pub num_transactions: u64,
+    /// The number of transactions by type (e.g., `user_transaction`).
+    pub num_transactions_by_type: BTreeMap<String, u64>,
+    /// The number of transactions that failed (i.e., the execution status is not success).
+    pub num_failed_transactions: u64,
+    /// The total gas used by all transactions.
+    pub total_gas_used: u64,
+    /// The number of unique senders of user transactions.
+    pub num_unique_senders: u64,
+}
+
+impl EpochLedgerStats {
+    pub fn new(epoch: u64, first_version: Version) -> Self {
+        Self {
+            epoch,
+            first_version,
+            last_version: first_version,
+            ..Default::default()
+        }
+    }
+
+    /// Adds the given committed transaction to the statistics. `is_new_sender`
+    /// indicates whether the sender of the transaction (if it's a user
+    /// transaction) hasn't yet sent a transaction in this epoch.
+    pub fn add_transaction(
+        &mut self,
+        version: Version,
+        transaction: &Transaction,
+        status: &ExecutionStatus,
+        gas_used: u64,
+        is_new_sender: bool,
+    ) {
+        self.last_version = version;
+        self.num_transactions += 1;
+        *self
+            .num_transactions_by_type
+            .entry(transaction.type_name().to_string())
+            .or_default() += 1;
+        if !status.is_success() {
+            self.num_failed_transactions += 1;
+        }
+        self.total_gas_used = self.total_gas_used.saturating_add(gas_used);
+        if is_new_sender {
+            self.num_unique_senders += 1;
+        }
+    }
+
+    /// Returns true iff the statistics don't cover any transactions
+    pub fn is_empty(&self) -> bool {
+        self.num_transactions == 0
+    }
+}
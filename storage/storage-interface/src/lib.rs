@@ -34,24 +34,30 @@ use aptos_types::{
         TransactionListWithProof, TransactionOutputListWithProof, TransactionToCommit,
         TransactionWithProof, Version,
     },
-    write_set::WriteSet,
+    write_set::{WriteOp, WriteSet},
 };
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::HashMap, path::Path, sync::Arc};
 use thiserror::Error;
 
 pub mod async_proof_fetcher;
 pub mod block_info;
 pub mod cached_state_view;
+pub mod disk_space_guardrails;
+pub mod epoch_ledger_stats;
 pub mod errors;
 mod executed_trees;
 mod metrics;
 #[cfg(any(test, feature = "fuzzing"))]
 pub mod mock;
+pub mod pruner_progress;
+pub mod read_context;
 pub mod state_delta;
 pub mod state_view;
 
-use crate::state_delta::StateDelta;
+use crate::{
+    epoch_ledger_stats::EpochLedgerStats, pruner_progress::PrunerProgress, state_delta::StateDelta,
+};
 use aptos_scratchpad::SparseMerkleTree;
 pub use aptos_types::block_info::BlockHeight;
 pub use errors::AptosDbError;
@@ -62,9 +68,33 @@ pub type Result<T, E = AptosDbError> = std::result::Result<T, E>;
 // like the API and State Sync, etc.
 pub const MAX_REQUEST_LIMIT: u64 = 20_000;
 
+/// A filter for the state values persisted by a state snapshot receiver. The
+/// value of a key is only persisted if the filter returns true for the key.
+pub type StateValueFilter<K> = Arc<dyn Fn(&K) -> bool + Send + Sync>;
+
 pub trait StateSnapshotReceiver<K, V>: Send {
     fn add_chunk(&mut self, chunk: Vec<(K, V)>, proof: SparseMerkleRangeProof) -> Result<()>;
 
+    /// Identical to `add_chunk`, but the hashes of the values in the chunk have already
+    /// been computed by the caller (e.g., in parallel, ahead of the commit). By default,
+    /// the given hashes are ignored and recomputed.
+    fn add_chunk_with_value_hashes(
+        &mut self,
+        chunk: Vec<(K, V)>,
+        _value_hashes: Vec<HashValue>,
+        proof: SparseMerkleRangeProof,
+    ) -> Result<()> {
+        self.add_chunk(chunk, proof)
+    }
+
+    /// Sets the filter for the state values persisted by the receiver. The state
+    /// tree is still restored from all keys (so that the chunk proofs can be
+    /// verified), but the values of keys rejected by the filter are dropped.
+    /// By default, filtering is unsupported.
+    fn set_value_filter(&mut self, _value_filter: StateValueFilter<K>) -> Result<()> {
+        Err(anyhow!("State value filtering is not supported by this receiver!").into())
+    }
+
     fn finish(self) -> Result<()>;
 
     fn finish_box(self: Box<Self>) -> Result<()>;
@@ -235,6 +265,27 @@ pub trait DbReader: Send + Sync {
             limit: u64,
         ) -> Result<Box<dyn Iterator<Item = Result<WriteSet>> + '_>>;
 
+        /// Returns the (version, write op) iterator of all the writes to a particular state key in
+        /// [start_version, end_version), in increasing order of version. This API can be used to
+        /// get the history of a resource without replaying the transaction outputs.
+        fn get_write_op_iterator(
+            &self,
+            state_key: &StateKey,
+            start_version: Version,
+            end_version: Version,
+        ) -> Result<Box<dyn Iterator<Item = Result<(Version, WriteOp)>> + '_>>;
+
+        /// Returns the (key, version, write op) iterator of all the writes to the state keys with a
+        /// particular prefix in [start_version, end_version), ordered by state key and then by
+        /// version. This API can be used to get the history of all resources of an account by
+        /// passing the account address as the key prefix.
+        fn get_prefixed_write_op_iterator(
+            &self,
+            key_prefix: &StateKeyPrefix,
+            start_version: Version,
+            end_version: Version,
+        ) -> Result<Box<dyn Iterator<Item = Result<(StateKey, Version, WriteOp)>> + '_>>;
+
         fn get_transaction_accumulator_range_proof(
             &self,
             start_version: Version,
@@ -440,6 +491,15 @@ pub trait DbReader: Send + Sync {
         /// Returns if the state store pruner is enabled.
         fn is_state_merkle_pruner_enabled(&self) -> Result<bool>;
 
+        /// Returns the progress of the state merkle pruner (e.g., the versions
+        /// pruned, the backlog and the estimated catch up time).
+        fn get_state_merkle_pruner_progress(&self) -> Result<PrunerProgress>;
+
+        /// Creates a consistent physical checkpoint of the DB (at the given
+        /// version) in the specified (new) directory. This doesn't block
+        /// commits, so it can be used to take hot backups of a running node.
+        fn create_checkpoint_at_version(&self, path: &Path, version: Version) -> Result<()>;
+
         /// Get the state prune window config value.
         fn get_epoch_snapshot_prune_window(&self) -> Result<usize>;
 
@@ -457,6 +517,11 @@ pub trait DbReader: Send + Sync {
 
         /// Returns state storage usage at the end of an epoch.
         fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage>;
+
+        /// Returns the ledger statistics of the given epoch (i.e., the aggregates
+        /// computed when the transactions of the epoch were committed), or None
+        /// if no statistics exist for the epoch.
+        fn get_epoch_ledger_stats(&self, epoch: u64) -> Result<Option<EpochLedgerStats>>;
     ); // end delegated
 
     /// Returns the latest ledger info.
@@ -572,6 +637,21 @@ pub trait DbWriter: Send + Sync {
     ) -> Result<()> {
         unimplemented!()
     }
+
+    /// Imports a state KV shard set (i.e., the state values of all state KV shards
+    /// at a single version, exported to a directory of sorted files) into the DB.
+    /// The state tree is rebuilt from the imported values and verified against the
+    /// root hash of the shard set. This is only supported for empty DBs.
+    fn import_state_kv_shard_set(&self, import_dir: &Path) -> Result<()> {
+        unimplemented!()
+    }
+
+    /// Throttles the state merkle pruner to prune at most `max_versions_per_sec`
+    /// versions per second (or removes the throttle if `None`). This takes effect
+    /// immediately, e.g., to slow down pruning during peak traffic.
+    fn set_state_merkle_pruner_throttle(&self, max_versions_per_sec: Option<u64>) -> Result<()> {
+        unimplemented!()
+    }
 }
 
 #[derive(Clone)]
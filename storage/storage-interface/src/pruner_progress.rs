@@ -0,0 +1,43 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::transaction::Version;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// A snapshot of the progress of a (background) DB pruner.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct PrunerProgress {
+    /// Whether the pruner is enabled. If not, all other fields are zero or unset.
+    pub enabled: bool,
+    /// The min readable version (i.e., all data below this version is pruned or
+    /// is scheduled to be pruned).
+    pub min_readable_version: Version,
+    /// The version up to which the data has already been pruned.
+    pub pruned_version: Version,
+    /// The version the pruner is working towards.
+    pub target_version: Version,
+    /// The number of versions that still have to be pruned to reach the target.
+    pub backlog: u64,
+    /// The recently observed pruning rate (in versions per second), if known.
+    pub observed_versions_per_sec: Option<f64>,
+    /// The max pruning rate (in versions per second), if the pruner is throttled.
+    pub max_versions_per_sec: Option<u64>,
+    /// The estimated time to prune the backlog (based on the observed pruning rate).
+    pub estimated_catch_up_time: Option<Duration>,
+}
+
+impl PrunerProgress {
+    /// Returns the estimated time to prune the given backlog at the given rate
+    pub fn estimate_catch_up_time(
+        backlog: u64,
+        observed_versions_per_sec: Option<f64>,
+    ) -> Option<Duration> {
+        if backlog == 0 {
+            return Some(Duration::ZERO);
+        }
+        observed_versions_per_sec
+            .filter(|versions_per_sec| *versions_per_sec > 0.0)
+            .map(|versions_per_sec| Duration::from_secs_f64(backlog as f64 / versions_per_sec))
+    }
+}
@@ -0,0 +1,129 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Attributes storage reads to the subsystem that issued them (e.g., the API
+//! or the storage service). The context is tracked per thread: reads issued
+//! on a thread without an active context are attributed to
+//! [`ReadSubsystem::Unknown`].
+
+use std::{cell::Cell, marker::PhantomData};
+
+thread_local! {
+    static CURRENT_READ_SUBSYSTEM: Cell<ReadSubsystem> = Cell::new(ReadSubsystem::Unknown);
+}
+
+/// The subsystems to which storage reads can be attributed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReadSubsystem {
+    Api,
+    Backup,
+    Execution,
+    StorageService,
+    Unknown,
+}
+
+impl ReadSubsystem {
+    /// Returns the label used to identify the subsystem in metrics
+    pub fn get_label(&self) -> &'static str {
+        match self {
+            ReadSubsystem::Api => "api",
+            ReadSubsystem::Backup => "backup",
+            ReadSubsystem::Execution => "execution",
+            ReadSubsystem::StorageService => "storage_service",
+            ReadSubsystem::Unknown => "unknown",
+        }
+    }
+}
+
+/// A guard that attributes all reads on the current thread to a subsystem.
+/// The previous subsystem is restored when the guard is dropped.
+#[must_use = "The read context is exited when the guard is dropped"]
+pub struct ReadContext {
+    previous_subsystem: ReadSubsystem,
+    // The context is thread-local, so the guard must not leave the thread
+    _not_send: PhantomData<*const ()>,
+}
+
+impl ReadContext {
+    /// Enters a read context for the given subsystem on the current thread
+    pub fn enter(subsystem: ReadSubsystem) -> Self {
+        let previous_subsystem = CURRENT_READ_SUBSYSTEM.with(|current| current.replace(subsystem));
+        Self {
+            previous_subsystem,
+            _not_send: PhantomData,
+        }
+    }
+
+    /// Returns the subsystem to which reads on the current thread are attributed
+    pub fn current() -> ReadSubsystem {
+        CURRENT_READ_SUBSYSTEM.with(|current| current.get())
+    }
+}
+
+impl Drop for ReadContext {
+    fn drop(&mut self) {
+        CURRENT_READ_SUBSYSTEM.with(|current| current.set(self.previous_subsystem));
+    }
+}
+
+/// An iterator that attributes all reads of the inner iterator to a
+/// subsystem. This is useful for iterators that are returned to the caller
+/// and lazily consumed elsewhere (e.g., on another thread).
+pub struct ReadContextIterator<I> {
+    inner: I,
+    subsystem: ReadSubsystem,
+}
+
+impl<I> ReadContextIterator<I> {
+    pub fn new(inner: I, subsystem: ReadSubsystem) -> Self {
+        Self { inner, subsystem }
+    }
+}
+
+impl<I: Iterator> Iterator for ReadContextIterator<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let _read_context = ReadContext::enter(self.subsystem);
+        self.inner.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_context_nesting() {
+        assert_eq!(ReadContext::current(), ReadSubsystem::Unknown);
+        {
+            let _api_context = ReadContext::enter(ReadSubsystem::Api);
+            assert_eq!(ReadContext::current(), ReadSubsystem::Api);
+            {
+                let _backup_context = ReadContext::enter(ReadSubsystem::Backup);
+                assert_eq!(ReadContext::current(), ReadSubsystem::Backup);
+            }
+            assert_eq!(ReadContext::current(), ReadSubsystem::Api);
+        }
+        assert_eq!(ReadContext::current(), ReadSubsystem::Unknown);
+    }
+
+    #[test]
+    fn test_read_context_is_thread_local() {
+        let _context = ReadContext::enter(ReadSubsystem::StorageService);
+        let other_thread_subsystem = std::thread::spawn(ReadContext::current).join().unwrap();
+        assert_eq!(other_thread_subsystem, ReadSubsystem::Unknown);
+        assert_eq!(ReadContext::current(), ReadSubsystem::StorageService);
+    }
+
+    #[test]
+    fn test_read_context_iterator() {
+        let subsystems: Vec<_> = ReadContextIterator::new(
+            (0..3).map(|_| ReadContext::current()),
+            ReadSubsystem::Execution,
+        )
+        .collect();
+        assert_eq!(subsystems, vec![ReadSubsystem::Execution; 3]);
+        assert_eq!(ReadContext::current(), ReadSubsystem::Unknown);
+    }
+}
@@ -24,6 +24,7 @@ use std::{
     path::PathBuf,
     process::{Child, Command},
     str::FromStr,
+    time::Duration,
 };
 use url::Url;
 
@@ -207,6 +208,25 @@ impl LocalNode {
         fs::read_to_string(self.log_path()).map_err(Into::into)
     }
 
+    /// Advances the mock time of the node by the given duration (via the admin
+    /// service). This requires the node to be configured with a mock time
+    /// service (see `NodeStartupConfig::use_mock_time_service`).
+    pub async fn advance_time(&self, duration: Duration) -> Result<()> {
+        let url = format!(
+            "http://127.0.0.1:{}/debug/time/advance?duration_ms={}",
+            self.config.admin_service.port,
+            duration.as_millis()
+        );
+        let response = reqwest::Client::new().post(&url).send().await?;
+        ensure!(
+            response.status().is_success(),
+            "Failed to advance the time of node '{}': {}",
+            self.name,
+            response.text().await?
+        );
+        Ok(())
+    }
+
     pub async fn health_check(&mut self) -> Result<(), HealthCheckError> {
         debug!("Health check on node '{}'", self.name);
 
@@ -464,6 +464,17 @@ impl LocalSwarm {
     pub fn dir(&self) -> &Path {
         self.dir.as_ref()
     }
+
+    /// Advances the mock time of all nodes in the swarm by the given duration.
+    /// This allows tests of time-dependent behaviour (e.g., round timeouts, mempool
+    /// expiration and state sync timeouts) to run deterministically, without real
+    /// sleeps. All nodes must be configured with a mock time service.
+    pub async fn advance_time(&self, duration: Duration) -> Result<()> {
+        for node in self.validators().chain(self.fullnodes()) {
+            node.advance_time(duration).await?;
+        }
+        Ok(())
+    }
 }
 
 impl Drop for LocalSwarm {
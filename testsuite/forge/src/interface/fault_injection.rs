@@ -0,0 +1,83 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use std::fmt::{Display, Formatter};
+
+/// The fault injection hooks that can be enabled on a node. Each hook configures
+/// a failpoint in the node (via the failpoint API), so the node must be built with
+/// failpoints and have failpoints enabled in the API config.
+#[derive(Eq, Hash, PartialEq, Debug, Clone)]
+pub enum FaultInjectionHook {
+    /// Fails the next `count` storage commits (of the block executor)
+    DropStorageCommits { count: u64 },
+    /// Delays outbound consensus messages by `delay_ms` (for the next
+    /// `count` messages, or all messages if no count is specified)
+    DelayConsensusMessages { delay_ms: u64, count: Option<u64> },
+    /// Fails the next chunk commit of the chunk executor
+    FailChunkExecutor,
+}
+
+impl FaultInjectionHook {
+    /// Returns the name of the failpoint configured by the hook
+    pub fn failpoint_name(&self) -> &'static str {
+        match self {
+            FaultInjectionHook::DropStorageCommits { .. } => "executor::commit_blocks",
+            FaultInjectionHook::DelayConsensusMessages { .. } => "consensus::send::any",
+            FaultInjectionHook::FailChunkExecutor => "executor::commit_chunk",
+        }
+    }
+
+    /// Returns the failpoint actions that enable the hook
+    pub fn failpoint_actions(&self) -> String {
+        match self {
+            FaultInjectionHook::DropStorageCommits { count } => format!("{}*return", count),
+            FaultInjectionHook::DelayConsensusMessages { delay_ms, count } => match count {
+                Some(count) => format!("{}*delay({})", count, delay_ms),
+                None => format!("delay({})", delay_ms),
+            },
+            FaultInjectionHook::FailChunkExecutor => "1*return".into(),
+        }
+    }
+}
+
+impl Display for FaultInjectionHook {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(
+            f,
+            "Fault injection hook {} ({})",
+            self.failpoint_name(),
+            self.failpoint_actions()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FaultInjectionHook;
+
+    #[test]
+    fn test_failpoint_actions() {
+        let drop_storage_commits = FaultInjectionHook::DropStorageCommits { count: 3 };
+        assert_eq!(drop_storage_commits.failpoint_actions(), "3*return");
+        let delay_consensus_messages = FaultInjectionHook::DelayConsensusMessages {
+            delay_ms: 500,
+            count: Some(100),
+        };
+        assert_eq!(
+            delay_consensus_messages.failpoint_actions(),
+            "100*delay(500)"
+        );
+        let delay_all_consensus_messages = FaultInjectionHook::DelayConsensusMessages {
+            delay_ms: 500,
+            count: None,
+        };
+        assert_eq!(
+            delay_all_consensus_messages.failpoint_actions(),
+            "delay(500)"
+        );
+        assert_eq!(
+            FaultInjectionHook::FailChunkExecutor.failpoint_actions(),
+            "1*return"
+        );
+    }
+}
@@ -16,6 +16,8 @@ mod swarm;
 pub use swarm::*;
 mod chaos;
 pub use chaos::*;
+mod fault_injection;
+pub use fault_injection::*;
 mod node;
 pub use node::*;
 mod chain_info;
@@ -2,7 +2,7 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{Result, Version};
+use crate::{FaultInjectionHook, Result, Version};
 use anyhow::anyhow;
 use aptos_config::{config::NodeConfig, network_id::NetworkId};
 use aptos_inspection_service::inspection_client::InspectionClient;
@@ -163,6 +163,24 @@ pub trait NodeExt: Node {
         InspectionClient::new(self.inspection_service_endpoint())
     }
 
+    /// Enables the given fault injection hook on this Node (via the failpoint API)
+    async fn enable_fault_injection(&self, hook: &FaultInjectionHook) -> Result<()> {
+        self.rest_client()
+            .set_failpoint(hook.failpoint_name().to_string(), hook.failpoint_actions())
+            .await
+            .map_err(|error| anyhow!("Failed to enable {} on {}: {}", hook, self.name(), error))?;
+        Ok(())
+    }
+
+    /// Disables the given fault injection hook on this Node (via the failpoint API)
+    async fn disable_fault_injection(&self, hook: &FaultInjectionHook) -> Result<()> {
+        self.rest_client()
+            .set_failpoint(hook.failpoint_name().to_string(), "off".to_string())
+            .await
+            .map_err(|error| anyhow!("Failed to disable {} on {}: {}", hook, self.name(), error))?;
+        Ok(())
+    }
+
     /// Restarts this Node by calling Node::Stop followed by Node::Start
     async fn restart(&mut self) -> Result<()> {
         self.stop().await?;
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{smoke_test_environment::SwarmBuilder, utils::get_current_version};
-use aptos_forge::{NodeExt, SwarmExt};
+use aptos_forge::{FaultInjectionHook, NodeExt, SwarmExt};
 use std::{sync::Arc, time::Duration};
 
 #[tokio::test]
@@ -42,3 +42,38 @@ async fn fallback_test() {
             .expect("milestone 1 taking too long");
     }
 }
+
+#[tokio::test]
+async fn fault_injection_hook_test() {
+    let swarm = SwarmBuilder::new_local(4)
+        .with_init_config(Arc::new(|_, config, _| {
+            config.api.failpoints_enabled = true;
+        }))
+        .with_aptos()
+        .build()
+        .await;
+    let client = swarm.validators().next().unwrap().rest_client();
+
+    // Delay the consensus messages of a single validator
+    let validator = swarm.validators().nth(1).unwrap();
+    let hook = FaultInjectionHook::DelayConsensusMessages {
+        delay_ms: 100,
+        count: None,
+    };
+    validator.enable_fault_injection(&hook).await.unwrap();
+
+    // Verify the chain tolerates the delayed messages
+    let version_milestone = get_current_version(&client).await + 5;
+    swarm
+        .wait_for_all_nodes_to_catchup_to_version(version_milestone, Duration::from_secs(60))
+        .await
+        .expect("The chain should progress with delayed consensus messages");
+
+    // Disable the hook and verify the chain continues to progress
+    validator.disable_fault_injection(&hook).await.unwrap();
+    let version_milestone = get_current_version(&client).await + 5;
+    swarm
+        .wait_for_all_nodes_to_catchup_to_version(version_milestone, Duration::from_secs(60))
+        .await
+        .expect("The chain should progress after disabling the hook");
+}
@@ -0,0 +1,60 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::account_address::AccountAddress;
+use anyhow::Result;
+use move_core_types::{
+    ident_str, identifier::IdentStr, language_storage::TypeTag, move_resource::MoveStructType,
+};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+
+/// Struct that represents a KeyRotation (module) event, emitted
+/// when the authentication key of an account is rotated.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct KeyRotation {
+    account: AccountAddress,
+    old_authentication_key: Vec<u8>,
+    new_authentication_key: Vec<u8>,
+}
+
+impl KeyRotation {
+    pub fn new(
+        account: AccountAddress,
+        old_authentication_key: Vec<u8>,
+        new_authentication_key: Vec<u8>,
+    ) -> Self {
+        Self {
+            account,
+            old_authentication_key,
+            new_authentication_key,
+        }
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self> {
+        bcs::from_bytes(bytes).map_err(Into::into)
+    }
+
+    /// Get the (originating) address of the account
+    pub fn account(&self) -> &AccountAddress {
+        &self.account
+    }
+
+    /// Get the authentication key before the rotation
+    pub fn old_authentication_key(&self) -> &[u8] {
+        &self.old_authentication_key
+    }
+
+    /// Get the authentication key after the rotation
+    pub fn new_authentication_key(&self) -> &[u8] {
+        &self.new_authentication_key
+    }
+}
+
+impl MoveStructType for KeyRotation {
+    const MODULE_NAME: &'static IdentStr = ident_str!("account");
+    const STRUCT_NAME: &'static IdentStr = ident_str!("KeyRotation");
+}
+
+pub static KEY_ROTATION_MOVE_TYPE_TAG: Lazy<TypeTag> =
+    Lazy::new(|| TypeTag::Struct(Box::new(KeyRotation::struct_tag())));
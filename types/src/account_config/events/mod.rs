@@ -3,11 +3,13 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub mod deposit;
+pub mod key_rotation;
 pub mod new_block;
 pub mod new_epoch;
 pub mod withdraw;
 
 pub use deposit::*;
+pub use key_rotation::*;
 pub use new_block::*;
 pub use new_epoch::*;
 pub use withdraw::*;
@@ -2,14 +2,19 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::on_chain_config::OnChainConfig;
+use anyhow::anyhow;
 use move_core_types::{
     effects::{ChangeSet, Op},
     language_storage::CORE_CODE_ADDRESS,
 };
 use serde::{Deserialize, Serialize};
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 use strum_macros::FromRepr;
 /// The feature flags define in the Move source. This must stay aligned with the constants there.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, FromRepr)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, FromRepr, Serialize)]
 #[allow(non_camel_case_types)]
 pub enum FeatureFlag {
     CODE_DEPENDENCY_CHECK = 1,
@@ -120,6 +125,188 @@ impl FeatureFlag {
     }
 }
 
+/// A snapshot of the feature flags enabled on mainnet (i.e., the `0x1::features::Features`
+/// resource, as returned by the REST API). To refresh the snapshot, run:
+/// `curl https://fullnode.mainnet.aptoslabs.com/v1/accounts/0x1/resource/0x1::features::Features`
+const MAINNET_FEATURES_SNAPSHOT: &str = include_str!("feature_flag_presets/mainnet.json");
+
+/// The names of the built-in feature flag presets
+pub const DEFAULT_FEATURE_FLAG_PRESET: &str = "default";
+pub const MAINNET_PARITY_FEATURE_FLAG_PRESET: &str = "mainnet-parity";
+pub const EXPERIMENTAL_FEATURE_FLAG_PRESET: &str = "experimental";
+
+/// The definition of a feature flag preset (e.g., in a preset file). The flags of the preset
+/// are the flags of the base preset (if any), plus the enabled flags, minus the disabled flags.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct FeatureFlagPresetDefinition {
+    pub name: String,
+    /// The name of the built-in preset to start from (if any)
+    #[serde(default)]
+    pub base: Option<String>,
+    #[serde(default)]
+    pub enabled: Vec<FeatureFlag>,
+    #[serde(default)]
+    pub disabled: Vec<FeatureFlag>,
+}
+
+/// A feature flag preset in a config (e.g., a genesis layout or a release config)
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeatureFlagPresetConfig {
+    /// The name of a built-in preset (e.g., "mainnet-parity")
+    Builtin(String),
+    /// A YAML file containing the preset definition
+    File(PathBuf),
+    /// An inline preset definition
+    Inline(FeatureFlagPresetDefinition),
+}
+
+impl FeatureFlagPresetConfig {
+    /// Loads and resolves the preset
+    pub fn load(&self) -> anyhow::Result<FeatureFlagPreset> {
+        match self {
+            FeatureFlagPresetConfig::Builtin(name) => FeatureFlagPreset::builtin(name)
+                .ok_or_else(|| anyhow!("Unknown built-in feature flag preset: {}", name)),
+            FeatureFlagPresetConfig::File(path) => FeatureFlagPreset::load_from_file(path),
+            FeatureFlagPresetConfig::Inline(definition) => {
+                FeatureFlagPreset::from_definition(definition.clone())
+            },
+        }
+    }
+}
+
+/// A named set of feature flags that can be applied atomically (e.g., when spinning up
+/// appchains or test networks). Applying a preset enables all of the preset's flags and
+/// disables every other flag.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FeatureFlagPreset {
+    name: String,
+    enabled_features: Vec<FeatureFlag>,
+}
+
+impl FeatureFlagPreset {
+    pub fn new(name: String, enabled_features: Vec<FeatureFlag>) -> Self {
+        Self {
+            name,
+            enabled_features,
+        }
+    }
+
+    /// Returns the built-in preset with the given name (if any)
+    pub fn builtin(name: &str) -> Option<Self> {
+        let enabled_features = match name {
+            DEFAULT_FEATURE_FLAG_PRESET => FeatureFlag::default_features(),
+            MAINNET_PARITY_FEATURE_FLAG_PRESET => mainnet_features_snapshot(),
+            EXPERIMENTAL_FEATURE_FLAG_PRESET => {
+                let mut features = FeatureFlag::default_features();
+                features.extend([
+                    FeatureFlag::PERIODICAL_REWARD_RATE_DECREASE,
+                    FeatureFlag::PARTIAL_GOVERNANCE_VOTING,
+                    FeatureFlag::DELEGATION_POOL_PARTIAL_GOVERNANCE_VOTING,
+                    FeatureFlag::VM_BINARY_FORMAT_V7,
+                    FeatureFlag::RECONFIGURE_WITH_DKG,
+                    FeatureFlag::REMOVE_DETAILED_ERROR_FROM_HASH,
+                    FeatureFlag::CONCURRENT_FUNGIBLE_ASSETS,
+                ]);
+                features
+            },
+            _ => return None,
+        };
+        Some(Self::new(name.into(), enabled_features))
+    }
+
+    /// Resolves the given preset definition (on top of its base preset, if any)
+    pub fn from_definition(definition: FeatureFlagPresetDefinition) -> anyhow::Result<Self> {
+        let FeatureFlagPresetDefinition {
+            name,
+            base,
+            enabled,
+            disabled,
+        } = definition;
+
+        let mut enabled_features = match base {
+            Some(base) => {
+                Self::builtin(&base)
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Unknown base preset for feature flag preset {}: {}",
+                            name,
+                            base
+                        )
+                    })?
+                    .enabled_features
+            },
+            None => vec![],
+        };
+        for flag in enabled {
+            if !enabled_features.contains(&flag) {
+                enabled_features.push(flag);
+            }
+        }
+        enabled_features.retain(|flag| !disabled.contains(flag));
+
+        Ok(Self::new(name, enabled_features))
+    }
+
+    /// Loads the preset definition from the given YAML file (and resolves it)
+    pub fn load_from_file(path: &Path) -> anyhow::Result<Self> {
+        let contents = fs::read_to_string(path).map_err(|error| {
+            anyhow!(
+                "Failed to read the feature flag preset at {:?}: {}",
+                path,
+                error
+            )
+        })?;
+        let definition = serde_yaml::from_str(&contents).map_err(|error| {
+            anyhow!(
+                "Failed to parse the feature flag preset at {:?}: {}",
+                path,
+                error
+            )
+        })?;
+        Self::from_definition(definition)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the feature flags enabled by the preset
+    pub fn enabled_features(&self) -> &[FeatureFlag] {
+        &self.enabled_features
+    }
+
+    /// Returns the on-chain feature bitset for the preset
+    pub fn to_features(&self) -> Features {
+        let mut features = Features { features: vec![] };
+        for flag in &self.enabled_features {
+            features.enable(*flag);
+        }
+        features
+    }
+}
+
+/// Returns the feature flags enabled in the mainnet snapshot. Flags that are unknown to this
+/// build are ignored.
+fn mainnet_features_snapshot() -> Vec<FeatureFlag> {
+    let snapshot: serde_json::Value = serde_json::from_str(MAINNET_FEATURES_SNAPSHOT)
+        .expect("The mainnet features snapshot must be valid JSON!");
+    let features = snapshot["data"]["features"]
+        .as_str()
+        .expect("The mainnet features snapshot must contain the feature bitset!");
+    let features = hex::decode(features.trim_start_matches("0x"))
+        .expect("The mainnet feature bitset must be hex encoded!");
+
+    features
+        .into_iter()
+        .flat_map(|byte| (0..8).map(move |bit_idx| byte & (1 << bit_idx) != 0))
+        .enumerate()
+        .filter(|(_feature_idx, enabled)| *enabled)
+        .filter_map(|(feature_idx, _)| FeatureFlag::from_repr(feature_idx))
+        .collect()
+}
+
 /// Representation of features on chain as a bitset.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub struct Features {
@@ -285,3 +472,79 @@ fn test_features_into_flag_vec() {
         flag_vec
     );
 }
+
+#[test]
+fn test_feature_flag_presets() {
+    // Verify the default preset matches the default features
+    let default_preset = FeatureFlagPreset::builtin(DEFAULT_FEATURE_FLAG_PRESET).unwrap();
+    assert_eq!(
+        default_preset.to_features().into_flag_vec(),
+        Features::default().into_flag_vec()
+    );
+
+    // Verify the mainnet parity preset is derived from the mainnet snapshot
+    let mainnet_features = FeatureFlagPreset::builtin(MAINNET_PARITY_FEATURE_FLAG_PRESET)
+        .unwrap()
+        .to_features();
+    assert!(mainnet_features.is_keyless_enabled());
+    assert!(!mainnet_features.is_keyless_zkless_enabled());
+    assert!(!mainnet_features.is_enabled(FeatureFlag::COLLECT_AND_DISTRIBUTE_GAS_FEES));
+
+    // Verify the experimental preset is a superset of the default preset
+    let experimental_features = FeatureFlagPreset::builtin(EXPERIMENTAL_FEATURE_FLAG_PRESET)
+        .unwrap()
+        .to_features();
+    for flag in FeatureFlag::default_features() {
+        assert!(experimental_features.is_enabled(flag));
+    }
+    assert!(experimental_features.is_reconfigure_with_dkg_enabled());
+    assert!(FeatureFlagPreset::builtin("unknown").is_none());
+}
+
+#[test]
+fn test_custom_feature_flag_presets() {
+    // Verify a custom preset is resolved on top of its base preset
+    let preset_config: FeatureFlagPresetConfig = serde_yaml::from_str(
+        r#"
+        inline:
+          name: appchain
+          base: default
+          enabled: [RECONFIGURE_WITH_DKG]
+          disabled: [KEYLESS_BUT_ZKLESS_ACCOUNTS, MULTISIG_ACCOUNTS]
+        "#,
+    )
+    .unwrap();
+    let preset = preset_config.load().unwrap();
+    assert_eq!(preset.name(), "appchain");
+    let features = preset.to_features();
+    assert!(features.is_reconfigure_with_dkg_enabled());
+    assert!(!features.is_keyless_zkless_enabled());
+    assert!(!features.is_enabled(FeatureFlag::MULTISIG_ACCOUNTS));
+    assert!(features.is_enabled(FeatureFlag::CODE_DEPENDENCY_CHECK));
+
+    // Verify a custom preset without a base only enables the given flags
+    let preset = FeatureFlagPreset::from_definition(FeatureFlagPresetDefinition {
+        name: "minimal".into(),
+        base: None,
+        enabled: vec![FeatureFlag::CODE_DEPENDENCY_CHECK],
+        disabled: vec![],
+    })
+    .unwrap();
+    assert_eq!(preset.to_features().into_flag_vec(), vec![
+        FeatureFlag::CODE_DEPENDENCY_CHECK
+    ]);
+
+    // Verify unknown presets are rejected
+    let preset_config: FeatureFlagPresetConfig =
+        serde_yaml::from_str("builtin: mainnet-parity").unwrap();
+    assert!(preset_config.load().is_ok());
+    let preset_config = FeatureFlagPresetConfig::Builtin("unknown".into());
+    assert!(preset_config.load().is_err());
+    let preset_config = FeatureFlagPresetConfig::Inline(FeatureFlagPresetDefinition {
+        name: "appchain".into(),
+        base: Some("unknown".into()),
+        enabled: vec![],
+        disabled: vec![],
+    });
+    assert!(preset_config.load().is_err());
+}
@@ -0,0 +1,216 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Stable, versioned JSON serialization for on-chain configs. Each config is
+//! rendered as an `OnChainConfigJson` envelope (holding the format version and
+//! the fully qualified Move type of the config) with object keys sorted, so that
+//! the output is deterministic and can be diffed by external tooling. The JSON
+//! can be converted back into the bytes stored on-chain (and vice versa) via the
+//! `ON_CHAIN_CONFIG_REGISTRY`, without knowing the Rust type of the config.
+
+use crate::{
+    chain_id::ChainId,
+    dkg::DKGState,
+    jwks::{ObservedJWKs, PatchedJWKs, SupportedOIDCProviders},
+    on_chain_config::{
+        ApprovedExecutionHashes, CommitHistoryResource, ConfigID, ConfigurationResource,
+        CurrentTimeMicroseconds, Features, GasSchedule, GasScheduleV2, OnChainConfig,
+        OnChainConsensusConfig, OnChainExecutionConfig, StorageGasSchedule, TransactionFeeBurnCap,
+        ValidatorSet, Version,
+    },
+    randomness::PerBlockRandomness,
+};
+use anyhow::{ensure, format_err, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The current version of the canonical JSON format. This must be bumped
+/// whenever the JSON representation of an existing config changes.
+pub const CANONICAL_JSON_FORMAT_VERSION: u64 = 1;
+
+/// The canonical JSON envelope of an on-chain config. Note: the fields are
+/// declared in alphabetical order, so that the envelope itself is canonical.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct OnChainConfigJson {
+    pub config_type: String, // The fully qualified Move type, e.g., "0x1::version::Version"
+    pub format_version: u64,
+    pub value: Value,
+}
+
+impl OnChainConfigJson {
+    /// Parses the envelope from the given JSON string
+    pub fn from_json_string(json: &str) -> Result<Self> {
+        let config_json: Self = serde_json::from_str(json)?;
+        Ok(Self {
+            value: canonicalize_json_value(config_json.value),
+            ..config_json
+        })
+    }
+
+    /// Returns the canonical (i.e., compact and key-sorted) JSON string
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    /// Returns the human readable (i.e., pretty printed and key-sorted) JSON string
+    pub fn to_pretty_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+/// Canonical JSON (and on-chain byte) serialization for an on-chain config
+pub trait CanonicalJsonConfig: OnChainConfig + Serialize {
+    /// Serializes the config into the bytes stored on-chain. This must be the
+    /// inverse of `OnChainConfig::deserialize_into_config`.
+    fn serialize_into_config_bytes(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self)
+            .map_err(|e| format_err!("[on-chain config] Failed to serialize config: {}", e))
+    }
+
+    /// Converts the config into its canonical JSON envelope
+    fn to_canonical_json(&self) -> Result<OnChainConfigJson> {
+        Ok(OnChainConfigJson {
+            config_type: config_type_name(Self::CONFIG_ID),
+            format_version: CANONICAL_JSON_FORMAT_VERSION,
+            value: canonicalize_json_value(serde_json::to_value(self)?),
+        })
+    }
+
+    /// Converts the given canonical JSON envelope into the config
+    fn from_canonical_json(config_json: &OnChainConfigJson) -> Result<Self> {
+        ensure!(
+            config_json.format_version == CANONICAL_JSON_FORMAT_VERSION,
+            "Unsupported canonical JSON format version! Expected: {}, found: {}",
+            CANONICAL_JSON_FORMAT_VERSION,
+            config_json.format_version
+        );
+        let config_type = config_type_name(Self::CONFIG_ID);
+        ensure!(
+            config_json.config_type == config_type,
+            "Mismatched on-chain config type! Expected: {}, found: {}",
+            config_type,
+            config_json.config_type
+        );
+        Ok(serde_json::from_value(config_json.value.clone())?)
+    }
+}
+
+impl CanonicalJsonConfig for ApprovedExecutionHashes {}
+impl CanonicalJsonConfig for ChainId {}
+impl CanonicalJsonConfig for CommitHistoryResource {}
+impl CanonicalJsonConfig for ConfigurationResource {}
+impl CanonicalJsonConfig for CurrentTimeMicroseconds {}
+impl CanonicalJsonConfig for DKGState {}
+impl CanonicalJsonConfig for Features {}
+impl CanonicalJsonConfig for GasSchedule {}
+impl CanonicalJsonConfig for GasScheduleV2 {}
+impl CanonicalJsonConfig for ObservedJWKs {}
+impl CanonicalJsonConfig for PatchedJWKs {}
+impl CanonicalJsonConfig for PerBlockRandomness {}
+impl CanonicalJsonConfig for StorageGasSchedule {}
+impl CanonicalJsonConfig for SupportedOIDCProviders {}
+impl CanonicalJsonConfig for TransactionFeeBurnCap {}
+impl CanonicalJsonConfig for ValidatorSet {}
+impl CanonicalJsonConfig for Version {}
+
+impl CanonicalJsonConfig for OnChainConsensusConfig {
+    /// The config is stored on-chain as an opaque `vector<u8>`
+    fn serialize_into_config_bytes(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&bcs::to_bytes(self)?)?)
+    }
+}
+
+impl CanonicalJsonConfig for OnChainExecutionConfig {
+    /// The config is stored on-chain as an opaque `vector<u8>`
+    fn serialize_into_config_bytes(&self) -> Result<Vec<u8>> {
+        Ok(bcs::to_bytes(&bcs::to_bytes(self)?)?)
+    }
+}
+
+/// A registry entry that converts between the on-chain bytes and the
+/// canonical JSON of a single on-chain config type.
+#[derive(Clone, Copy)]
+pub struct OnChainConfigRegistryEntry {
+    pub config_id: ConfigID,
+    pub bytes_to_json: fn(&[u8]) -> Result<OnChainConfigJson>,
+    pub json_to_bytes: fn(&OnChainConfigJson) -> Result<Vec<u8>>,
+}
+
+impl OnChainConfigRegistryEntry {
+    const fn new<T: CanonicalJsonConfig>() -> Self {
+        Self {
+            config_id: T::CONFIG_ID,
+            bytes_to_json: config_bytes_to_json::<T>,
+            json_to_bytes: config_json_to_bytes::<T>,
+        }
+    }
+
+    /// Returns the fully qualified Move type of the config
+    pub fn config_type(&self) -> String {
+        config_type_name(self.config_id)
+    }
+}
+
+/// All on-chain configs (with a Rust representation) known to this crate
+pub const ON_CHAIN_CONFIG_REGISTRY: &[OnChainConfigRegistryEntry] = &[
+    OnChainConfigRegistryEntry::new::<ApprovedExecutionHashes>(),
+    OnChainConfigRegistryEntry::new::<ChainId>(),
+    OnChainConfigRegistryEntry::new::<CommitHistoryResource>(),
+    OnChainConfigRegistryEntry::new::<ConfigurationResource>(),
+    OnChainConfigRegistryEntry::new::<CurrentTimeMicroseconds>(),
+    OnChainConfigRegistryEntry::new::<DKGState>(),
+    OnChainConfigRegistryEntry::new::<Features>(),
+    OnChainConfigRegistryEntry::new::<GasSchedule>(),
+    OnChainConfigRegistryEntry::new::<GasScheduleV2>(),
+    OnChainConfigRegistryEntry::new::<ObservedJWKs>(),
+    OnChainConfigRegistryEntry::new::<OnChainConsensusConfig>(),
+    OnChainConfigRegistryEntry::new::<OnChainExecutionConfig>(),
+    OnChainConfigRegistryEntry::new::<PatchedJWKs>(),
+    OnChainConfigRegistryEntry::new::<PerBlockRandomness>(),
+    OnChainConfigRegistryEntry::new::<StorageGasSchedule>(),
+    OnChainConfigRegistryEntry::new::<SupportedOIDCProviders>(),
+    OnChainConfigRegistryEntry::new::<TransactionFeeBurnCap>(),
+    OnChainConfigRegistryEntry::new::<ValidatorSet>(),
+    OnChainConfigRegistryEntry::new::<Version>(),
+];
+
+/// Returns the registry entry for the given fully qualified Move type (if any)
+pub fn get_registry_entry(config_type: &str) -> Option<&'static OnChainConfigRegistryEntry> {
+    ON_CHAIN_CONFIG_REGISTRY
+        .iter()
+        .find(|entry| entry.config_type() == config_type)
+}
+
+/// Returns the fully qualified Move type of the given config
+pub fn config_type_name(config_id: ConfigID) -> String {
+    format!("{}::{}::{}", config_id.0, config_id.1, config_id.2)
+}
+
+fn config_bytes_to_json<T: CanonicalJsonConfig>(bytes: &[u8]) -> Result<OnChainConfigJson> {
+    T::deserialize_into_config(bytes)?.to_canonical_json()
+}
+
+fn config_json_to_bytes<T: CanonicalJsonConfig>(
+    config_json: &OnChainConfigJson,
+) -> Result<Vec<u8>> {
+    T::from_canonical_json(config_json)?.serialize_into_config_bytes()
+}
+
+/// Recursively sorts the keys of all JSON objects in the given value. This is
+/// required because the JSON maps may preserve insertion (i.e., field) order.
+fn canonicalize_json_value(value: Value) -> Value {
+    match value {
+        Value::Array(values) => {
+            Value::Array(values.into_iter().map(canonicalize_json_value).collect())
+        },
+        Value::Object(map) => {
+            let mut entries: Vec<_> = map
+                .into_iter()
+                .map(|(key, value)| (key, canonicalize_json_value(value)))
+                .collect();
+            entries.sort_by(|(key_1, _), (key_2, _)| key_1.cmp(key_2));
+            Value::Object(entries.into_iter().collect())
+        },
+        value => value,
+    }
+}
@@ -18,6 +18,11 @@ pub enum ConsensusAlgorithmConfig {
         quorum_store_enabled: bool,
     },
     DAG(DagConsensusConfigV1),
+    JolteonV2 {
+        main: ConsensusConfigV1,
+        quorum_store_enabled: bool,
+        proposer_fallback: ProposerFallbackConfig,
+    },
 }
 
 impl ConsensusAlgorithmConfig {
@@ -40,6 +45,10 @@ impl ConsensusAlgorithmConfig {
             ConsensusAlgorithmConfig::Jolteon {
                 quorum_store_enabled,
                 ..
+            }
+            | ConsensusAlgorithmConfig::JolteonV2 {
+                quorum_store_enabled,
+                ..
             } => *quorum_store_enabled,
             ConsensusAlgorithmConfig::DAG(_) => true,
         }
@@ -47,32 +56,45 @@ impl ConsensusAlgorithmConfig {
 
     pub fn is_dag_enabled(&self) -> bool {
         match self {
-            ConsensusAlgorithmConfig::Jolteon { .. } => false,
+            ConsensusAlgorithmConfig::Jolteon { .. }
+            | ConsensusAlgorithmConfig::JolteonV2 { .. } => false,
             ConsensusAlgorithmConfig::DAG(_) => true,
         }
     }
 
     pub fn leader_reputation_exclude_round(&self) -> u64 {
         match self {
-            ConsensusAlgorithmConfig::Jolteon { main, .. } => main.exclude_round,
+            ConsensusAlgorithmConfig::Jolteon { main, .. }
+            | ConsensusAlgorithmConfig::JolteonV2 { main, .. } => main.exclude_round,
             _ => unimplemented!("method not supported"),
         }
     }
 
     pub fn max_failed_authors_to_store(&self) -> usize {
         match self {
-            ConsensusAlgorithmConfig::Jolteon { main, .. } => main.max_failed_authors_to_store,
+            ConsensusAlgorithmConfig::Jolteon { main, .. }
+            | ConsensusAlgorithmConfig::JolteonV2 { main, .. } => main.max_failed_authors_to_store,
             _ => unimplemented!("method not supported"),
         }
     }
 
     pub fn proposer_election_type(&self) -> &ProposerElectionType {
         match self {
-            ConsensusAlgorithmConfig::Jolteon { main, .. } => &main.proposer_election_type,
+            ConsensusAlgorithmConfig::Jolteon { main, .. }
+            | ConsensusAlgorithmConfig::JolteonV2 { main, .. } => &main.proposer_election_type,
             _ => unimplemented!("method not supported"),
         }
     }
 
+    pub fn proposer_fallback_config(&self) -> ProposerFallbackConfig {
+        match self {
+            ConsensusAlgorithmConfig::JolteonV2 {
+                proposer_fallback, ..
+            } => *proposer_fallback,
+            _ => ProposerFallbackConfig::default_disabled(),
+        }
+    }
+
     pub fn unwrap_dag_config_v1(&self) -> &DagConsensusConfigV1 {
         match self {
             ConsensusAlgorithmConfig::DAG(dag) => dag,
@@ -82,7 +104,8 @@ impl ConsensusAlgorithmConfig {
 
     pub fn unwrap_jolteon_config_v1(&self) -> &ConsensusConfigV1 {
         match self {
-            ConsensusAlgorithmConfig::Jolteon { main, .. } => main,
+            ConsensusAlgorithmConfig::Jolteon { main, .. }
+            | ConsensusAlgorithmConfig::JolteonV2 { main, .. } => main,
             _ => unreachable!("not a jolteon config"),
         }
     }
@@ -209,6 +232,31 @@ impl OnChainConsensusConfig {
         }
     }
 
+    // Configuration of the fallback proposers for each round. Invalid configs
+    // (see `ProposerFallbackConfig::validate`) disable fallback proposers.
+    pub fn proposer_fallback_config(&self) -> ProposerFallbackConfig {
+        let proposer_fallback_config = self.raw_proposer_fallback_config();
+        if proposer_fallback_config.validate().is_ok() {
+            proposer_fallback_config
+        } else {
+            ProposerFallbackConfig::default_disabled()
+        }
+    }
+
+    /// Verifies the config is valid (invalid parts of the config are disabled)
+    pub fn validate(&self) -> Result<()> {
+        self.raw_proposer_fallback_config().validate()
+    }
+
+    fn raw_proposer_fallback_config(&self) -> ProposerFallbackConfig {
+        match &self {
+            OnChainConsensusConfig::V1(_) | OnChainConsensusConfig::V2(_) => {
+                ProposerFallbackConfig::default_disabled()
+            },
+            OnChainConsensusConfig::V3 { alg, .. } => alg.proposer_fallback_config(),
+        }
+    }
+
     pub fn quorum_store_enabled(&self) -> bool {
         match &self {
             OnChainConsensusConfig::V1(_config) => false,
@@ -354,6 +402,46 @@ impl Default for ConsensusConfigV1 {
     }
 }
 
+/// Configuration for the ordered fallback proposers of each round. The fallback
+/// proposers are derived from leader reputation, and (if enabled) each fallback
+/// proposer may propose, in order, if no proposal has been seen within its
+/// fallback timeout. This avoids waiting for the full round timeout when the
+/// round's proposer is down.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct ProposerFallbackConfig {
+    // Number of fallback proposers for each round (0 disables fallback proposers)
+    pub num_fallback_proposers: u64,
+    // Time to wait before each fallback proposer may propose, multiplied by the
+    // position of the fallback proposer, i.e., the first waits 1x, the second 2x, etc.
+    pub fallback_timeout_ms: u64,
+}
+
+impl ProposerFallbackConfig {
+    pub fn default_disabled() -> Self {
+        Self {
+            num_fallback_proposers: 0,
+            fallback_timeout_ms: 0,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.num_fallback_proposers > 0
+    }
+
+    /// Verifies the config is valid, i.e., fallback proposers have a non-zero
+    /// fallback timeout (otherwise, all fallback proposers would propose
+    /// immediately, racing the round's proposer).
+    pub fn validate(&self) -> Result<()> {
+        if self.enabled() && self.fallback_timeout_ms == 0 {
+            return Err(format_err!(
+                "The fallback timeout must be non-zero if fallback proposers are enabled! Config: {:?}",
+                self
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")] // cannot use tag = "type" as nested enums cannot work, and bcs doesn't support it
 pub enum ProposerElectionType {
@@ -521,4 +609,54 @@ mod test {
             ProposerElectionType::RoundProposer(_value)
         ));
     }
+
+    #[test]
+    fn test_config_proposer_fallback() {
+        // Verify that fallback proposers are disabled by default
+        let config = OnChainConsensusConfig::default();
+        assert!(!config.proposer_fallback_config().enabled());
+
+        // Verify that fallback proposers are only configured by JolteonV2
+        let proposer_fallback = ProposerFallbackConfig {
+            num_fallback_proposers: 2,
+            fallback_timeout_ms: 500,
+        };
+        let config = OnChainConsensusConfig::V3 {
+            alg: ConsensusAlgorithmConfig::JolteonV2 {
+                main: ConsensusConfigV1::default(),
+                quorum_store_enabled: true,
+                proposer_fallback,
+            },
+            vtxn: ValidatorTxnConfig::default_enabled(),
+        };
+        let s = bcs::to_bytes(&config).unwrap();
+        let result = bcs::from_bytes::<OnChainConsensusConfig>(&s).unwrap();
+        assert_eq!(result.proposer_fallback_config(), proposer_fallback);
+        assert!(result.quorum_store_enabled());
+        assert!(!result.is_dag_enabled());
+        assert!(matches!(
+            result.proposer_election_type(),
+            ProposerElectionType::LeaderReputation(_)
+        ));
+
+        // Verify that fallback proposers without a fallback timeout are rejected (and disabled)
+        let invalid_proposer_fallback = ProposerFallbackConfig {
+            num_fallback_proposers: 2,
+            fallback_timeout_ms: 0,
+        };
+        assert!(invalid_proposer_fallback.validate().is_err());
+        assert!(ProposerFallbackConfig::default_disabled()
+            .validate()
+            .is_ok());
+        let config = OnChainConsensusConfig::V3 {
+            alg: ConsensusAlgorithmConfig::JolteonV2 {
+                main: ConsensusConfigV1::default(),
+                quorum_store_enabled: true,
+                proposer_fallback: invalid_proposer_fallback,
+            },
+            vtxn: ValidatorTxnConfig::default_enabled(),
+        };
+        assert!(!config.proposer_fallback_config().enabled());
+        assert!(config.validate().is_err());
+    }
 }
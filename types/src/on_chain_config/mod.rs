@@ -22,6 +22,7 @@ use std::{collections::HashMap, fmt, fmt::Debug, sync::Arc};
 mod approved_execution_hashes;
 mod aptos_features;
 mod aptos_version;
+mod canonical_json;
 mod chain_id;
 mod commit_history;
 mod consensus_config;
@@ -38,11 +39,15 @@ pub use self::{
     aptos_version::{
         Version, APTOS_MAX_KNOWN_VERSION, APTOS_VERSION_2, APTOS_VERSION_3, APTOS_VERSION_4,
     },
+    canonical_json::{
+        config_type_name, get_registry_entry, CanonicalJsonConfig, OnChainConfigJson,
+        OnChainConfigRegistryEntry, CANONICAL_JSON_FORMAT_VERSION, ON_CHAIN_CONFIG_REGISTRY,
+    },
     commit_history::CommitHistoryResource,
     consensus_config::{
         AnchorElectionMode, ConsensusAlgorithmConfig, ConsensusConfigV1, DagConsensusConfigV1,
         LeaderReputationType, OnChainConsensusConfig, ProposerAndVoterConfig, ProposerElectionType,
-        ValidatorTxnConfig,
+        ProposerFallbackConfig, ValidatorTxnConfig,
     },
     execution_config::{
         BlockGasLimitType, ExecutionConfigV1, ExecutionConfigV2, ExecutionConfigV4,
@@ -57,7 +62,7 @@ pub use self::{
 
 /// To register an on-chain config in Rust:
 /// 1. Implement the `OnChainConfig` trait for the Rust representation of the config
-/// 2. Add the config's `ConfigID` to `ON_CHAIN_CONFIG_REGISTRY`
+/// 2. Implement the `CanonicalJsonConfig` trait and add the config to `ON_CHAIN_CONFIG_REGISTRY`
 
 #[derive(Copy, Clone, Debug, Eq, Hash, PartialEq)]
 pub struct ConfigID(&'static str, &'static str, &'static str);
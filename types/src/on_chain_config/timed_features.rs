@@ -10,7 +10,7 @@ use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
 const NOT_YET_SPECIFIED: u64 = END_OF_TIME; /* Thursday, December 31, 2099 11:59:59 PM */
 
 pub const END_OF_TIME: u64 = 4102444799000; /* Thursday, December 31, 2099 11:59:59 PM */
-#[derive(Debug, EnumCountMacro, EnumIter, Clone, Copy)]
+#[derive(Debug, EnumCountMacro, EnumIter, Clone, Copy, Eq, PartialEq)]
 pub enum TimedFeatureFlag {
     DisableInvariantViolationCheckInSwapLoc,
     LimitTypeTagSize,
@@ -68,6 +68,7 @@ impl TimedFeatureFlag {
 pub struct TimedFeaturesBuilder {
     inner: TimedFeaturesImpl,
     override_: Option<TimedFeatureOverride>,
+    flag_overrides: Vec<(TimedFeatureFlag, bool)>,
 }
 
 impl TimedFeaturesBuilder {
@@ -82,6 +83,7 @@ impl TimedFeaturesBuilder {
         Self {
             inner,
             override_: None,
+            flag_overrides: vec![],
         }
     }
 
@@ -89,20 +91,36 @@ impl TimedFeaturesBuilder {
         Self {
             inner: TimedFeaturesImpl::EnableAll,
             override_: None,
+            flag_overrides: vec![],
         }
     }
 
     pub fn with_override_profile(self, profile: TimedFeatureOverride) -> Self {
         Self {
-            inner: self.inner,
             override_: Some(profile),
+            ..self
         }
     }
 
+    /// Overrides a single flag. This takes precedence over the override profile.
+    pub fn with_flag_override(mut self, flag: TimedFeatureFlag, enabled: bool) -> Self {
+        self.flag_overrides.push((flag, enabled));
+        self
+    }
+
     /// Determine whether the given feature should be enabled or not.
     fn is_enabled(&self, flag: TimedFeatureFlag) -> bool {
         use TimedFeaturesImpl::*;
 
+        if let Some((_, enabled)) = self
+            .flag_overrides
+            .iter()
+            .rev()
+            .find(|(overridden_flag, _)| *overridden_flag == flag)
+        {
+            return *enabled;
+        }
+
         if let Some(override_) = &self.override_ {
             if let Some(enabled) = override_.get_override(flag) {
                 return enabled;
@@ -26,7 +26,7 @@ use aptos_crypto::{
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest_derive::Arbitrary;
 use serde::{Deserialize, Serialize};
-use std::{any::type_name, marker::PhantomData};
+use std::{any::type_name, marker::PhantomData, ops::Range};
 
 /// A proof that can be used authenticate an element in an accumulator given trusted root hash. For
 /// example, both `LedgerInfoToTransactionInfoProof` and `TransactionInfoToEventProof` can be
@@ -656,6 +656,112 @@ where
 
         Ok(())
     }
+
+    /// Derives a proof for a (non-empty) sub-range of the leaves authenticated by
+    /// this proof. The caller must provide the index of the first leaf and all of
+    /// the leaves authenticated by this proof, as well as the sub-range of leaves
+    /// (relative to the first leaf) that the new proof should authenticate. The
+    /// new proof authenticates the sub-range against the same root hash.
+    pub fn get_sub_range_proof(
+        &self,
+        first_leaf_index: u64,
+        leaf_hashes: &[HashValue],
+        sub_range: Range<usize>,
+    ) -> Result<Self> {
+        ensure!(
+            !sub_range.is_empty() && sub_range.end <= leaf_hashes.len(),
+            "Invalid sub-range: {:?}. Number of leaves: {}.",
+            sub_range,
+            leaf_hashes.len(),
+        );
+
+        // Identify the first and last positions (on the leaf level) of both ranges
+        let last_leaf_index = first_leaf_index
+            .checked_add(leaf_hashes.len() as u64 - 1)
+            .ok_or_else(|| format_err!("The last leaf index has overflown!"))?;
+        let mut first_index = first_leaf_index;
+        let mut last_index = last_leaf_index;
+        let mut sub_range_first_index = first_leaf_index + sub_range.start as u64;
+        let mut sub_range_last_index = first_leaf_index + sub_range.end as u64 - 1;
+
+        let mut left_sibling_iter = self.left_siblings.iter();
+        let mut right_sibling_iter = self.right_siblings.iter();
+        let mut sub_range_left_siblings = vec![];
+        let mut sub_range_right_siblings = vec![];
+        let mut current_hashes = leaf_hashes.to_vec();
+
+        // Walk up the tree (in the same way as verification), and collect the
+        // siblings required by the sub-range on each level.
+        while current_hashes.len() > 1
+            || !left_sibling_iter.as_slice().is_empty()
+            || !right_sibling_iter.as_slice().is_empty()
+        {
+            // Identify the siblings of the full range on the current level
+            let left_sibling = if first_index % 2 == 1 {
+                Some(*left_sibling_iter.next().ok_or_else(|| {
+                    format_err!("First child is a right child, but missing sibling on the left.")
+                })?)
+            } else {
+                None
+            };
+            let right_sibling = if last_index % 2 == 0 {
+                Some(*right_sibling_iter.next().ok_or_else(|| {
+                    format_err!("Last child is a left child, but missing sibling on the right.")
+                })?)
+            } else {
+                None
+            };
+
+            // Identify the siblings of the sub-range on the current level. These
+            // are either nodes in the full range, or siblings of the full range.
+            if sub_range_first_index % 2 == 1 {
+                let sibling = if sub_range_first_index > first_index {
+                    current_hashes[(sub_range_first_index - 1 - first_index) as usize]
+                } else {
+                    left_sibling.expect("The left sibling must exist for a right child!")
+                };
+                sub_range_left_siblings.push(sibling);
+            }
+            if sub_range_last_index % 2 == 0 {
+                let sibling = if sub_range_last_index < last_index {
+                    current_hashes[(sub_range_last_index + 1 - first_index) as usize]
+                } else {
+                    right_sibling.expect("The right sibling must exist for a left child!")
+                };
+                sub_range_right_siblings.push(sibling);
+            }
+
+            // Compute the parent hashes of the full range
+            let mut parent_hashes = vec![];
+            let mut children = current_hashes.as_slice();
+            if let Some(left_hash) = left_sibling {
+                parent_hashes.push(MerkleTreeInternalNode::<H>::new(left_hash, children[0]).hash());
+                children = &children[1..];
+            }
+            let mut children_iter = children.chunks_exact(2);
+            for chunk in children_iter.by_ref() {
+                parent_hashes.push(MerkleTreeInternalNode::<H>::new(chunk[0], chunk[1]).hash());
+            }
+            if let Some(right_hash) = right_sibling {
+                let remainder = children_iter.remainder();
+                ensure!(
+                    remainder.len() == 1,
+                    "Last child is a left child, but it was not found."
+                );
+                parent_hashes
+                    .push(MerkleTreeInternalNode::<H>::new(remainder[0], right_hash).hash());
+            }
+
+            // Move up to the parent level
+            first_index /= 2;
+            last_index /= 2;
+            sub_range_first_index /= 2;
+            sub_range_last_index /= 2;
+            current_hashes = parent_hashes;
+        }
+
+        Ok(Self::new(sub_range_left_siblings, sub_range_right_siblings))
+    }
 }
 
 impl<H> std::fmt::Debug for AccumulatorRangeProof<H> {
@@ -23,7 +23,7 @@ use crate::{
 use anyhow::{ensure, format_err, Context, Error, Result};
 use aptos_crypto::{
     ed25519::*,
-    hash::CryptoHash,
+    hash::{CryptoHash, CryptoHasher},
     multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
     secp256k1_ecdsa,
     traits::{signing_message, SigningKey},
@@ -751,9 +751,15 @@ impl SignedTransaction {
         )
     }
 
-    /// Returns the hash when the transaction is commited onchain.
-    pub fn committed_hash(self) -> HashValue {
-        Transaction::UserTransaction(self).hash()
+    /// Returns the hash when the transaction is commited onchain. This is the hash of
+    /// `Transaction::UserTransaction`, computed without cloning the transaction.
+    pub fn committed_hash(&self) -> HashValue {
+        let mut state = TransactionHasher::default();
+        // The BCS encoding of the `UserTransaction` variant index
+        state.update(&[0]);
+        bcs::serialize_into(&mut state, self)
+            .expect("BCS serialization of a signed transaction should not fail");
+        state.finish()
     }
 }
 
@@ -1208,6 +1214,20 @@ impl TransactionOutput {
         self.gas_used
     }
 
+    /// Returns the number of bytes written by the transaction, i.e., the sizes of
+    /// all state keys and values in the write set. Deletions only count the key.
+    pub fn write_set_size(&self) -> usize {
+        self.write_set
+            .iter()
+            .map(|(key, op)| key.size() + op.size())
+            .sum()
+    }
+
+    /// Returns the number of bytes in all events emitted by the transaction
+    pub fn events_size(&self) -> usize {
+        self.events.iter().map(|event| event.size()).sum()
+    }
+
     pub fn status(&self) -> &TransactionStatus {
         &self.status
     }
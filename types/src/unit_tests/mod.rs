@@ -7,6 +7,7 @@ mod block_metadata_test;
 mod code_debug_fmt_test;
 mod contract_event_test;
 mod keyless_serialization_test;
+mod on_chain_config_json_test;
 mod transaction_test;
 mod trusted_state_test;
 mod validator_set_test;
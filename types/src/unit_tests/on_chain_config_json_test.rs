@@ -0,0 +1,152 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    chain_id::ChainId,
+    dkg::DKGState,
+    jwks::ObservedJWKs,
+    on_chain_config::{
+        get_registry_entry, ApprovedExecutionHashes, CanonicalJsonConfig, CommitHistoryResource,
+        CurrentTimeMicroseconds, Features, GasScheduleV2, OnChainConfigJson,
+        OnChainConsensusConfig, OnChainExecutionConfig, StorageGasSchedule, TransactionFeeBurnCap,
+        ValidatorSet, Version, CANONICAL_JSON_FORMAT_VERSION, ON_CHAIN_CONFIG_REGISTRY,
+    },
+    randomness::PerBlockRandomness,
+};
+use serde::de::DeserializeOwned;
+use std::{collections::HashSet, fmt::Debug};
+
+#[test]
+fn test_canonical_json_golden_files() {
+    // Verify the canonical JSON of simple configs
+    assert_golden_json(
+        Version { major: 4 },
+        r#"{"config_type":"0x1::version::Version","format_version":1,"value":{"major":4}}"#,
+    );
+    assert_golden_json(
+        CurrentTimeMicroseconds {
+            microseconds: 1_700_000_000_000_000,
+        },
+        r#"{"config_type":"0x1::timestamp::CurrentTimeMicroseconds","format_version":1,"value":{"microseconds":1700000000000000}}"#,
+    );
+    assert_golden_json(
+        ChainId::test(),
+        r#"{"config_type":"0x1::chain_id::ChainId","format_version":1,"value":4}"#,
+    );
+    assert_golden_json(
+        TransactionFeeBurnCap,
+        r#"{"config_type":"0x1::transaction_fee::AptosCoinCapabilities","format_version":1,"value":null}"#,
+    );
+    assert_golden_json(
+        ApprovedExecutionHashes {
+            entries: vec![(1, vec![0, 255]), (7, vec![])],
+        },
+        r#"{"config_type":"0x1::aptos_governance::ApprovedExecutionHashes","format_version":1,"value":{"entries":[[1,[0,255]],[7,[]]]}}"#,
+    );
+
+    // Verify the object keys are sorted (and not in field order)
+    assert_golden_json(
+        PerBlockRandomness {
+            epoch: 10,
+            round: 2,
+            seed: Some(vec![1, 2, 3]),
+        },
+        r#"{"config_type":"0x1::randomness::PerBlockRandomness","format_version":1,"value":{"epoch":10,"round":2,"seed":[1,2,3]}}"#,
+    );
+    assert_golden_json(
+        StorageGasSchedule::zeros(),
+        r#"{"config_type":"0x1::storage_gas::StorageGas","format_version":1,"value":{"per_byte_create":0,"per_byte_read":0,"per_byte_write":0,"per_item_create":0,"per_item_read":0,"per_item_write":0}}"#,
+    );
+
+    // Verify configs with private fields can be created from the golden JSON
+    let commit_history_json = r#"{"config_type":"0x1::block::CommitHistory","format_version":1,"value":{"max_capacity":2000,"next_idx":5,"table":{"handle":"0000000000000000000000000000000000000000000000000000000000000abc","length":2000}}}"#;
+    let config_json = OnChainConfigJson::from_json_string(commit_history_json).unwrap();
+    let commit_history = CommitHistoryResource::from_canonical_json(&config_json).unwrap();
+    assert_eq!(commit_history.max_capacity(), 2000);
+    assert_eq!(commit_history.next_idx(), 5);
+    assert_golden_json(commit_history, commit_history_json);
+}
+
+#[test]
+fn test_canonical_json_round_trips() {
+    assert_round_trip(ChainId::test());
+    assert_round_trip(DKGState::default());
+    assert_round_trip(Features::default());
+    assert_round_trip(GasScheduleV2 {
+        feature_version: 12,
+        entries: vec![("txn.min_price_per_gas_unit".into(), 100)],
+    });
+    assert_round_trip(ObservedJWKs::default());
+    assert_round_trip(OnChainConsensusConfig::default());
+    assert_round_trip(OnChainExecutionConfig::default_for_genesis());
+    assert_round_trip(PerBlockRandomness::default());
+    assert_round_trip(ValidatorSet::empty());
+    assert_round_trip(Version { major: 1 });
+}
+
+#[test]
+fn test_canonical_json_mismatches() {
+    // Verify a mismatched config type is rejected
+    let config_json = Version { major: 4 }.to_canonical_json().unwrap();
+    assert!(CurrentTimeMicroseconds::from_canonical_json(&config_json).is_err());
+
+    // Verify an unsupported format version is rejected
+    let config_json = OnChainConfigJson {
+        format_version: CANONICAL_JSON_FORMAT_VERSION + 1,
+        ..config_json
+    };
+    assert!(Version::from_canonical_json(&config_json).is_err());
+}
+
+#[test]
+fn test_on_chain_config_registry() {
+    // Verify the registry entries are unique
+    let config_types: HashSet<_> = ON_CHAIN_CONFIG_REGISTRY
+        .iter()
+        .map(|entry| entry.config_type())
+        .collect();
+    assert_eq!(config_types.len(), ON_CHAIN_CONFIG_REGISTRY.len());
+
+    // Verify the registry can be used to render on-chain bytes as JSON
+    let consensus_config = OnChainConsensusConfig::default();
+    let config_bytes = consensus_config.serialize_into_config_bytes().unwrap();
+    let entry = get_registry_entry("0x1::consensus_config::ConsensusConfig").unwrap();
+    let config_json = (entry.bytes_to_json)(&config_bytes).unwrap();
+    assert_eq!(config_json, consensus_config.to_canonical_json().unwrap());
+    assert_eq!((entry.json_to_bytes)(&config_json).unwrap(), config_bytes);
+
+    // Verify unknown config types are not found
+    assert!(get_registry_entry("0x1::unknown::Config").is_none());
+}
+
+/// Verifies the canonical JSON of the config matches the golden JSON, and
+/// that the golden JSON round-trips to the config.
+fn assert_golden_json<T: CanonicalJsonConfig + DeserializeOwned + Debug + PartialEq>(
+    config: T,
+    golden_json: &str,
+) {
+    let config_json = config.to_canonical_json().unwrap();
+    assert_eq!(config_json.to_json_string().unwrap(), golden_json);
+    assert_round_trip(config);
+}
+
+/// Verifies the config round-trips through the canonical JSON, the on-chain
+/// bytes and the registry.
+fn assert_round_trip<T: CanonicalJsonConfig + DeserializeOwned + Debug + PartialEq>(config: T) {
+    // Verify the JSON round-trip
+    let config_json = config.to_canonical_json().unwrap();
+    let json_string = config_json.to_json_string().unwrap();
+    let parsed_json = OnChainConfigJson::from_json_string(&json_string).unwrap();
+    assert_eq!(parsed_json, config_json);
+    assert_eq!(T::from_canonical_json(&parsed_json).unwrap(), config);
+
+    // Verify the on-chain bytes round-trip
+    let config_bytes = config.serialize_into_config_bytes().unwrap();
+    assert_eq!(T::deserialize_into_config(&config_bytes).unwrap(), config);
+
+    // Verify the registry round-trip
+    let entry = get_registry_entry(&config_json.config_type).unwrap();
+    assert_eq!(entry.config_id, T::CONFIG_ID);
+    assert_eq!((entry.bytes_to_json)(&config_bytes).unwrap(), config_json);
+    assert_eq!((entry.json_to_bytes)(&config_json).unwrap(), config_bytes);
+}
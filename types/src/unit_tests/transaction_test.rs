@@ -5,16 +5,22 @@
 use crate::{
     account_address::AccountAddress,
     chain_id::ChainId,
+    contract_event::ContractEvent,
+    state_store::state_key::StateKey,
     transaction::{
-        AccountTransactionsWithProof, RawTransaction, Script, SignedTransaction, Transaction,
-        TransactionInfo, TransactionListWithProof, TransactionPayload, TransactionWithProof,
+        AccountTransactionsWithProof, ExecutionStatus, RawTransaction, Script, SignedTransaction,
+        Transaction, TransactionAuxiliaryData, TransactionInfo, TransactionListWithProof,
+        TransactionOutput, TransactionPayload, TransactionStatus, TransactionWithProof,
     },
+    write_set::{WriteOp, WriteSetMut},
 };
 use aptos_crypto::{
     ed25519::{self, Ed25519PrivateKey, Ed25519Signature},
+    hash::CryptoHash,
     PrivateKey, Uniform,
 };
 use bcs::test_helpers::assert_canonical_encode_decode;
+use move_core_types::language_storage::TypeTag;
 use proptest::prelude::*;
 use std::convert::TryFrom;
 
@@ -39,6 +45,36 @@ fn test_invalid_signature() {
     )
 }
 
+#[test]
+fn test_transaction_output_sizes() {
+    let write_set = WriteSetMut::new(vec![
+        (
+            StateKey::raw(vec![1; 10]),
+            WriteOp::legacy_creation(vec![0; 100].into()),
+        ),
+        (StateKey::raw(vec![2; 20]), WriteOp::legacy_deletion()),
+    ])
+    .freeze()
+    .unwrap();
+    let events = vec![
+        ContractEvent::new_v2(TypeTag::U64, vec![0; 50]),
+        ContractEvent::new_v2(TypeTag::U64, vec![0; 50]),
+    ];
+    let output = TransactionOutput::new(
+        write_set,
+        events,
+        0,
+        TransactionStatus::Keep(ExecutionStatus::Success),
+        TransactionAuxiliaryData::default(),
+    );
+
+    // Creations count the key and value, deletions only count the key
+    assert_eq!(output.write_set_size(), 10 + 100 + 20);
+
+    // Each event counts the serialized type tag (1 byte) and the event data
+    assert_eq!(output.events_size(), 2 * (1 + 50));
+}
+
 proptest! {
     #[test]
     fn test_sign_raw_transaction(raw_txn in any::<RawTransaction>(), keypair in ed25519::keypair_strategy()) {
@@ -57,6 +93,12 @@ proptest! {
         assert_canonical_encode_decode(raw_txn);
     }
 
+    #[test]
+    fn signed_transaction_committed_hash(signed_txn in any::<SignedTransaction>()) {
+        let committed_hash = signed_txn.committed_hash();
+        prop_assert_eq!(committed_hash, Transaction::UserTransaction(signed_txn).hash());
+    }
+
     #[test]
     fn signed_transaction_bcs_roundtrip(signed_txn in any::<SignedTransaction>()) {
         assert_canonical_encode_decode(signed_txn);